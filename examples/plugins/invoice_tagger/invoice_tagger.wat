@@ -0,0 +1,70 @@
+;; Invoice Tagger: adds the "Invoice" label to incoming messages whose
+;; subject or body contains "invoice" (ASCII, case-insensitive).
+(module
+  (import "comunicado" "field_len" (func $field_len (param i32 i32) (result i32)))
+  (import "comunicado" "read_field" (func $read_field (param i32 i32 i32 i32) (result i32)))
+  (import "comunicado" "add_tag" (func $add_tag (param i32 i32)))
+
+  (memory (export "memory") 1)
+
+  (data (i32.const 0) "subject")
+  (data (i32.const 16) "body")
+  (data (i32.const 32) "invoice")
+  (data (i32.const 48) "Invoice")
+
+  ;; Field values are read into memory from here on
+  (global $buffer i32 (i32.const 1024))
+
+  ;; Read a field into the buffer, growing memory to fit; returns its length
+  (func $read (param $name i32) (param $name_len i32) (result i32)
+    (local $len i32)
+    (local $pages i32)
+    (local.set $len (call $field_len (local.get $name) (local.get $name_len)))
+    (if (i32.le_s (local.get $len) (i32.const 0))
+      (then (return (i32.const 0))))
+    (local.set $pages
+      (i32.sub
+        (i32.shr_u
+          (i32.add (i32.add (local.get $len) (global.get $buffer)) (i32.const 65535))
+          (i32.const 16))
+        (memory.size)))
+    (if (i32.gt_s (local.get $pages) (i32.const 0))
+      (then (drop (memory.grow (local.get $pages)))))
+    ;; If memory could not grow, the host truncates the value to what fits
+    (call $read_field
+      (local.get $name) (local.get $name_len)
+      (global.get $buffer)
+      (i32.sub (i32.shl (memory.size) (i32.const 16)) (global.get $buffer))))
+
+  ;; Whether the buffer's first $len bytes contain "invoice"
+  (func $contains_invoice (param $len i32) (result i32)
+    (local $i i32)
+    (local $j i32)
+    (block $done
+      (loop $outer
+        (br_if $done (i32.gt_s (i32.add (local.get $i) (i32.const 7)) (local.get $len)))
+        (local.set $j (i32.const 0))
+        (block $mismatch
+          (loop $inner
+            ;; OR-ing 0x20 lowercases ASCII letters
+            (br_if $mismatch
+              (i32.ne
+                (i32.or
+                  (i32.load8_u
+                    (i32.add (global.get $buffer) (i32.add (local.get $i) (local.get $j))))
+                  (i32.const 0x20))
+                (i32.load8_u (i32.add (i32.const 32) (local.get $j)))))
+            (local.set $j (i32.add (local.get $j) (i32.const 1)))
+            (if (i32.eq (local.get $j) (i32.const 7))
+              (then (return (i32.const 1))))
+            (br $inner)))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $outer)))
+    (i32.const 0))
+
+  (func (export "process_incoming") (result i32)
+    (if (i32.or
+          (call $contains_invoice (call $read (i32.const 0) (i32.const 7)))
+          (call $contains_invoice (call $read (i32.const 16) (i32.const 4))))
+      (then (call $add_tag (i32.const 48) (i32.const 7))))
+    (i32.const 0)))
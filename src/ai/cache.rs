@@ -242,6 +242,8 @@ pub struct CacheStatistics {
     pub avg_entry_age_seconds: f64,
     /// Cache warming status
     pub warming_active: bool,
+    /// TTL given to new entries
+    pub default_ttl: Duration,
 }
 
 /// AI response cache for performance optimization
@@ -540,6 +542,7 @@ impl AIResponseCache {
             entries_by_strategy,
             avg_entry_age_seconds,
             warming_active,
+            default_ttl: config.default_ttl,
         }
     }
 
@@ -713,6 +716,19 @@ impl AIResponseCache {
         }
     }
 
+    /// Tag an existing entry so `invalidate_by_tags` finds it. Returns
+    /// false if there is no such entry.
+    pub async fn tag_entry(&self, key: &str, tag: &str) -> bool {
+        let mut cache = self.cache.write().await;
+        match cache.get_mut(key) {
+            Some(entry) => {
+                entry.add_tag(tag.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Invalidate cache entries by tags
     pub async fn invalidate_by_tags(&self, tags: &[String]) -> AIResult<usize> {
         let mut cache = self.cache.write().await;
@@ -873,6 +889,23 @@ mod tests {
         assert_eq!(cached.priority, CachePriority::Normal);
     }
 
+    #[tokio::test]
+    async fn test_tag_entry() {
+        let cache = AIResponseCache::new(100, Duration::from_secs(60));
+        let first = cache.generate_prompt_hash("first", None);
+        let second = cache.generate_prompt_hash("second", None);
+        cache.cache_response(&first, "one", "test_provider", None).await.unwrap();
+        cache.cache_response(&second, "two", "test_provider", None).await.unwrap();
+
+        assert!(cache.tag_entry(&first, "message:a").await);
+        assert!(!cache.tag_entry("missing", "message:a").await);
+
+        let removed = cache.invalidate_by_tags(&["message:a".to_string()]).await.unwrap();
+        assert_eq!(removed, 1);
+        assert!(cache.get_cached_response(&first).await.is_none());
+        assert!(cache.get_cached_response(&second).await.is_some());
+    }
+
     #[tokio::test]
     async fn test_cache_expiration() {
         let cache = AIResponseCache::new(100, Duration::from_secs(60));
@@ -72,7 +72,9 @@ pub struct AIConfig {
     /// Whether to cache AI responses for performance
     pub cache_responses: bool,
     
-    /// Cache TTL for AI responses
+    /// How long a cached AI response is reused, written as seconds
+    /// (`cache_ttl = 86400`)
+    #[serde(with = "ttl_secs")]
     pub cache_ttl: Duration,
     
     /// Maximum context length for AI requests
@@ -315,6 +317,37 @@ impl AIConfig {
     }
 }
 
+/// `cache_ttl` as a number of seconds. Files written before it was one
+/// hold a `{ secs, nanos }` table, which is still read.
+mod ttl_secs {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Ttl {
+        Secs(u64),
+        Duration(Duration),
+    }
+
+    pub fn serialize<S>(ttl: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(ttl.as_secs())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match Ttl::deserialize(deserializer)? {
+            Ttl::Secs(secs) => Duration::from_secs(secs),
+            Ttl::Duration(duration) => duration,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -389,4 +422,19 @@ mod tests {
         assert_eq!(loaded_config.enabled, true);
         assert_eq!(loaded_config.provider, AIProviderType::Ollama);
     }
+
+    #[test]
+    fn test_cache_ttl_in_seconds() {
+        let mut config = AIConfig::default();
+        config.cache_ttl = Duration::from_secs(86_400);
+        let toml = toml::to_string_pretty(&config).unwrap();
+        assert!(toml.contains("cache_ttl = 86400"));
+
+        let old = toml.replace(
+            "cache_ttl = 86400",
+            "cache_ttl = { secs = 600, nanos = 0 }",
+        );
+        let loaded: AIConfig = toml::from_str(&old).unwrap();
+        assert_eq!(loaded.cache_ttl, Duration::from_secs(600));
+    }
 }
\ No newline at end of file
@@ -92,6 +92,12 @@ impl AIConfigManager {
             } else {
                 self.shutdown_ai_service().await;
             }
+        } else {
+            let cache_ttl = config.cache_ttl;
+            drop(config);
+            if let Some(service) = self.get_ai_service().await {
+                service.set_cache_ttl(cache_ttl).await;
+            }
         }
         
         // Save the updated configuration
@@ -202,6 +208,11 @@ impl AIConfigManager {
         self.ai_service.read().await.clone()
     }
 
+    /// Shared configuration, for components that follow later changes
+    pub fn config_handle(&self) -> Arc<RwLock<AIConfig>> {
+        self.config.clone()
+    }
+
     /// Check if consent is required for an operation
     pub async fn is_consent_required(&self, operation: &str) -> bool {
         let config = self.config.read().await;
@@ -1,6 +1,6 @@
 //! AI provider factory for creating and managing AI providers
 
-use crate::ai::{AIResult, AIConfig, AIProviderType, AIProviderManager, AIResponseCache, AIService};
+use crate::ai::{AIResult, AIConfig, AIProviderType, AIProviderManager, AIResponseCache, AIService, CacheConfig};
 use crate::ai::error::AIError;
 use crate::ai::providers::{AnthropicProvider, GoogleProvider, OllamaProvider, OpenAIProvider};
 use std::sync::Arc;
@@ -19,7 +19,11 @@ impl AIFactory {
         Self::register_providers(&mut provider_manager, &config).await?;
         
         let provider_manager = Arc::new(RwLock::new(provider_manager));
-        let cache = Arc::new(AIResponseCache::default());
+        let cache_config = CacheConfig {
+            default_ttl: config.read().await.cache_ttl,
+            ..CacheConfig::default()
+        };
+        let cache = Arc::new(AIResponseCache::with_config(cache_config));
         
         Ok(AIService::new(provider_manager, cache, config))
     }
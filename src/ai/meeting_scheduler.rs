@@ -5,7 +5,10 @@
 
 use crate::ai::{AIResult, EnhancedAIService, EnhancedAIRequest, AIOperationType};
 use crate::calendar::manager::CalendarManager;
+use crate::calendar::{timezone, BusyPeriod};
 use crate::calendar::event::{Event, EventAttendee, EventStatus, EventPriority, EventRecurrence, RecurrenceFrequency as CalendarRecurrenceFrequency, RecurrenceDay, AttendeeStatus as CalendarAttendeeStatus, AttendeeRole as CalendarAttendeeRole};
+use chrono::{Datelike, Weekday};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -16,6 +19,7 @@ use uuid::Uuid;
 
 /// Configuration for AI meeting scheduling
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MeetingSchedulerConfig {
     /// Enable AI meeting scheduling
     pub enabled: bool,
@@ -35,6 +39,12 @@ pub struct MeetingSchedulerConfig {
     pub enable_attendee_extraction: bool,
     /// Meeting confirmation timeout (seconds)
     pub confirmation_timeout_seconds: u64,
+    /// First hour of the working day, in the meeting's time zone
+    pub working_hours_start: u32,
+    /// Hour the working day ends
+    pub working_hours_end: u32,
+    /// Calendars attendees share free/busy times of, by email address
+    pub free_busy_calendars: HashMap<String, String>,
 }
 
 impl Default for MeetingSchedulerConfig {
@@ -49,6 +59,9 @@ impl Default for MeetingSchedulerConfig {
             enable_location_detection: true,
             enable_attendee_extraction: true,
             confirmation_timeout_seconds: 300, // 5 minutes
+            working_hours_start: 9,
+            working_hours_end: 17,
+            free_busy_calendars: HashMap::new(),
         }
     }
 }
@@ -293,6 +306,12 @@ pub struct MeetingSchedulerStats {
     pub conflict_resolution_rate: f32,
 }
 
+/// Most alternative times offered for one meeting
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Step between the candidate start times of a meeting
+const SLOT_STEP_MINUTES: i64 = 30;
+
 /// AI-powered meeting scheduler service
 pub struct MeetingSchedulerService {
     /// Enhanced AI service for parsing
@@ -457,6 +476,14 @@ impl MeetingSchedulerService {
             // Auto-create meeting for trusted senders with high confidence
             Ok(Some(self.create_meeting(&meeting_request, true).await?))
         } else {
+            // Let the user weigh any clashes before confirming
+            let conflicts = self.check_conflicts(&meeting_request).await?;
+            let alternative_suggestions = if conflicts.is_empty() && meeting_request.proposed_datetime.is_some() {
+                vec![]
+            } else {
+                self.suggest_alternative_times(&meeting_request).await?
+            };
+
             // Store for user confirmation
             let request_id = meeting_request.id;
             let mut pending = self.pending_confirmations.write().await;
@@ -467,8 +494,8 @@ impl MeetingSchedulerService {
                 success: false,
                 event_id: None,
                 error: Some("User confirmation required".to_string()),
-                conflicts: vec![],
-                alternative_suggestions: vec![],
+                conflicts,
+                alternative_suggestions,
             }))
         }
     }
@@ -733,21 +760,147 @@ If no meeting request is detected, set has_meeting_request to false and confiden
         }))
     }
 
-    /// Check for scheduling conflicts
-    async fn check_conflicts(&self, _meeting_request: &MeetingRequest) -> AIResult<Vec<ConflictInfo>> {
-        // This would check against existing calendar events
-        // For now, return empty conflicts
-        Ok(vec![])
+    /// Check the proposed time against the user's calendar and the
+    /// free/busy times attendees share
+    async fn check_conflicts(&self, meeting_request: &MeetingRequest) -> AIResult<Vec<ConflictInfo>> {
+        let Some(start) = meeting_request.proposed_datetime else {
+            return Ok(vec![]);
+        };
+        let config = self.config.read().await.clone();
+        let end = start + self.meeting_duration(meeting_request, &config);
+        let buffer = chrono::Duration::minutes(config.buffer_time_minutes as i64);
+
+        let events = self
+            .calendar_manager
+            .get_all_events(Some(start - buffer), Some(end + buffer))
+            .await
+            .map_err(|e| crate::ai::AIError::internal_error(format!("Failed to read calendar: {}", e)))?;
+
+        let mut conflicts: Vec<ConflictInfo> = events
+            .iter()
+            .filter(|event| event.status != EventStatus::Cancelled)
+            .filter(|event| event.start_time < end + buffer && start - buffer < event.end_time)
+            .map(|event| ConflictInfo {
+                event_id: event.id.clone(),
+                title: event.title.clone(),
+                start_time: event.start_time,
+                end_time: event.end_time,
+                severity: conflict_severity(event, start, end),
+            })
+            .collect();
+
+        for (email, busy) in self.attendee_busy_periods(meeting_request, &config, start, end).await {
+            conflicts.extend(busy.iter().filter(|period| period.overlaps(start, end)).map(|period| {
+                ConflictInfo {
+                    event_id: String::new(),
+                    title: format!("{} is busy", email),
+                    start_time: period.start,
+                    end_time: period.end,
+                    severity: ConflictSeverity::Moderate,
+                }
+            }));
+        }
+
+        Ok(conflicts)
     }
 
-    /// Suggest alternative meeting times
-    async fn suggest_alternative_times(
+    /// Suggest meeting times, best first
+    ///
+    /// Times the sender offered come first, then working hours from the
+    /// proposed day on. Times that clash with the user's calendar, buffer
+    /// included, are never suggested, and times every attendee sharing
+    /// free/busy is free come before the rest.
+    pub async fn suggest_alternative_times(
         &self,
-        _meeting_request: &MeetingRequest,
+        meeting_request: &MeetingRequest,
     ) -> AIResult<Vec<chrono::DateTime<chrono::Utc>>> {
-        // This would analyze calendar availability and suggest alternatives
-        // For now, return empty suggestions
-        Ok(vec![])
+        let config = self.config.read().await.clone();
+        let duration = self.meeting_duration(meeting_request, &config);
+        let buffer = chrono::Duration::minutes(config.buffer_time_minutes as i64);
+        let tz = meeting_request
+            .timezone
+            .as_deref()
+            .and_then(timezone::parse_tzid)
+            .or_else(timezone::system_timezone)
+            .unwrap_or(Tz::UTC);
+
+        let now = chrono::Utc::now();
+        let from = meeting_request
+            .proposed_datetime
+            .and_then(|proposed| {
+                let day = proposed.with_timezone(&tz).date_naive().and_hms_opt(0, 0, 0)?;
+                Some(timezone::local_to_utc(day, tz))
+            })
+            .unwrap_or(now)
+            .max(now);
+        let to = from + chrono::Duration::days(config.max_lookahead_days as i64);
+
+        let own_busy = self
+            .calendar_manager
+            .busy_periods(from - buffer, to + duration + buffer)
+            .await
+            .map_err(|e| crate::ai::AIError::internal_error(format!("Failed to read calendar: {}", e)))?;
+        let attendee_busy: Vec<BusyPeriod> = self
+            .attendee_busy_periods(meeting_request, &config, from, to + duration)
+            .await
+            .into_iter()
+            .flat_map(|(_, busy)| busy)
+            .collect();
+
+        let candidates = meeting_request
+            .alternative_times
+            .iter()
+            .copied()
+            .filter(|time| *time >= now)
+            .chain(working_slots(
+                from,
+                to,
+                duration,
+                config.working_hours_start,
+                config.working_hours_end,
+                tz,
+            ))
+            .filter(|time| Some(*time) != meeting_request.proposed_datetime);
+
+        Ok(rank_slots(candidates, duration, buffer, &own_busy, &attendee_busy))
+    }
+
+    /// How long a meeting lasts, the default if the email didn't say
+    fn meeting_duration(&self, meeting_request: &MeetingRequest, config: &MeetingSchedulerConfig) -> chrono::Duration {
+        chrono::Duration::minutes(
+            meeting_request
+                .duration_minutes
+                .unwrap_or(config.default_duration_minutes) as i64,
+        )
+    }
+
+    /// Busy times of the attendees who share free/busy, by email address
+    ///
+    /// Attendees whose calendars can't be read are left out.
+    async fn attendee_busy_periods(
+        &self,
+        meeting_request: &MeetingRequest,
+        config: &MeetingSchedulerConfig,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Vec<(String, Vec<BusyPeriod>)> {
+        let mut busy = Vec::new();
+        for attendee in &meeting_request.attendees {
+            let Some(calendar_url) = config
+                .free_busy_calendars
+                .iter()
+                .find(|(email, _)| email.eq_ignore_ascii_case(&attendee.email))
+                .map(|(_, url)| url)
+            else {
+                continue;
+            };
+
+            match self.calendar_manager.shared_busy_periods(calendar_url, start, end).await {
+                Ok(periods) => busy.push((attendee.email.clone(), periods)),
+                Err(e) => warn!("Failed to look up free/busy times of {}: {}", attendee.email, e),
+            }
+        }
+        busy
     }
 
     /// Convert meeting request to calendar event
@@ -755,10 +908,18 @@ If no meeting request is detected, set has_meeting_request to false and confiden
         &self,
         meeting_request: &MeetingRequest,
     ) -> AIResult<Event> {
+        // Without a proposed time, take the best free one
+        let start_time = match meeting_request.proposed_datetime {
+            Some(proposed) => proposed,
+            None => self
+                .suggest_alternative_times(meeting_request)
+                .await?
+                .first()
+                .copied()
+                .unwrap_or_else(|| chrono::Utc::now() + chrono::Duration::hours(1)),
+        };
+
         let config = self.config.read().await;
-        
-        let start_time = meeting_request.proposed_datetime
-            .unwrap_or_else(|| chrono::Utc::now() + chrono::Duration::hours(1));
 
         let duration_minutes = meeting_request.duration_minutes
             .unwrap_or(config.default_duration_minutes);
@@ -798,6 +959,10 @@ If no meeting request is detected, set has_meeting_request to false and confiden
                 by_week_no: vec![],
                 by_year_day: vec![],
                 week_start: RecurrenceDay::Monday,
+                by_day_nth: vec![],
+                by_set_pos: vec![],
+                exception_dates: vec![],
+                recurrence_dates: vec![],
             }
         });
 
@@ -903,6 +1068,96 @@ If no meeting request is detected, set has_meeting_request to false and confiden
     }
 }
 
+/// How badly an existing event clashes with a meeting at `start..end`
+fn conflict_severity(
+    event: &Event,
+    start: chrono::DateTime<chrono::Utc>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> ConflictSeverity {
+    let overlap = event.end_time.min(end) - event.start_time.max(start);
+    if overlap <= chrono::Duration::zero() {
+        // Only the buffer around the meeting is taken
+        ConflictSeverity::Minor
+    } else if event.priority == EventPriority::High {
+        ConflictSeverity::Critical
+    } else if overlap * 2 >= end - start {
+        ConflictSeverity::Major
+    } else {
+        ConflictSeverity::Moderate
+    }
+}
+
+/// Meeting start times within working hours on weekdays between `from` and
+/// `to`, every half hour, for meetings that end by the end of the day
+fn working_slots(
+    from: chrono::DateTime<chrono::Utc>,
+    to: chrono::DateTime<chrono::Utc>,
+    duration: chrono::Duration,
+    start_hour: u32,
+    end_hour: u32,
+    tz: Tz,
+) -> Vec<chrono::DateTime<chrono::Utc>> {
+    let mut slots = Vec::new();
+    let mut date = from.with_timezone(&tz).date_naive();
+    while let Some(midnight) = date.and_hms_opt(0, 0, 0) {
+        if timezone::local_to_utc(midnight, tz) > to {
+            break;
+        }
+        if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            let day_end = midnight + chrono::Duration::hours(end_hour as i64);
+            let mut local = midnight + chrono::Duration::hours(start_hour as i64);
+            while local + duration <= day_end {
+                let start = timezone::local_to_utc(local, tz);
+                if start >= from && start <= to {
+                    slots.push(start);
+                }
+                local += chrono::Duration::minutes(SLOT_STEP_MINUTES);
+            }
+        }
+        let Some(next) = date.succ_opt() else {
+            break;
+        };
+        date = next;
+    }
+    slots
+}
+
+/// The best of `candidates` for a meeting, in order of preference
+///
+/// Candidates that clash with `own_busy`, counting the buffer either side,
+/// are dropped. Of the rest, those clear of `attendee_busy` come first; each
+/// group keeps the order the candidates came in.
+fn rank_slots(
+    candidates: impl IntoIterator<Item = chrono::DateTime<chrono::Utc>>,
+    duration: chrono::Duration,
+    buffer: chrono::Duration,
+    own_busy: &[BusyPeriod],
+    attendee_busy: &[BusyPeriod],
+) -> Vec<chrono::DateTime<chrono::Utc>> {
+    let mut free = Vec::new();
+    let mut attendees_busy = Vec::new();
+    for start in candidates {
+        let end = start + duration;
+        if free.contains(&start) || attendees_busy.contains(&start) {
+            continue;
+        }
+        if own_busy.iter().any(|period| period.overlaps(start - buffer, end + buffer)) {
+            continue;
+        }
+        if attendee_busy.iter().any(|period| period.overlaps(start, end)) {
+            attendees_busy.push(start);
+        } else {
+            free.push(start);
+        }
+        if free.len() >= MAX_SUGGESTIONS {
+            break;
+        }
+    }
+    free.extend(attendees_busy);
+    free.truncate(MAX_SUGGESTIONS);
+    free
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -918,6 +1173,9 @@ mod tests {
             enable_location_detection: true,
             enable_attendee_extraction: true,
             confirmation_timeout_seconds: 600,
+            working_hours_start: 9,
+            working_hours_end: 17,
+            free_busy_calendars: HashMap::new(),
         }
     }
 
@@ -1053,4 +1311,83 @@ mod tests {
         assert_eq!(minor_conflict.severity, ConflictSeverity::Minor);
         assert_eq!(critical_conflict.severity, ConflictSeverity::Critical);
     }
+
+    #[test]
+    fn test_conflict_severity_from_overlap() {
+        use chrono::TimeZone;
+
+        let start = chrono::Utc.with_ymd_and_hms(2025, 3, 10, 10, 0, 0).unwrap();
+        let end = start + chrono::Duration::minutes(60);
+        let event = |from: i64, to: i64| {
+            Event::new(
+                "cal".to_string(),
+                "Existing".to_string(),
+                start + chrono::Duration::minutes(from),
+                start + chrono::Duration::minutes(to),
+            )
+        };
+
+        assert_eq!(conflict_severity(&event(60, 90), start, end), ConflictSeverity::Minor);
+        assert_eq!(conflict_severity(&event(45, 90), start, end), ConflictSeverity::Moderate);
+        assert_eq!(conflict_severity(&event(-30, 30), start, end), ConflictSeverity::Major);
+
+        let mut important = event(45, 90);
+        important.priority = EventPriority::High;
+        assert_eq!(conflict_severity(&important, start, end), ConflictSeverity::Critical);
+    }
+
+    #[test]
+    fn test_working_slots_skip_weekends_and_fit_the_day() {
+        use chrono::TimeZone;
+
+        // Friday 16:00 in New York, looking ahead to Monday
+        let new_york = chrono_tz::America::New_York;
+        let from = new_york.with_ymd_and_hms(2025, 3, 7, 16, 0, 0).unwrap().with_timezone(&chrono::Utc);
+        let to = from + chrono::Duration::days(3);
+        let slots = working_slots(from, to, chrono::Duration::minutes(30), 9, 17, new_york);
+        let local: Vec<String> = slots
+            .iter()
+            .map(|slot| slot.with_timezone(&new_york).format("%a %H:%M").to_string())
+            .collect();
+
+        assert_eq!(&local[..2], ["Fri 16:00", "Fri 16:30"]);
+        // Monday, after the clocks went forward on Sunday
+        assert_eq!(local[2], "Mon 09:00");
+        assert_eq!(local.last().unwrap(), "Mon 16:30");
+        assert_eq!(
+            slots[2],
+            chrono::Utc.with_ymd_and_hms(2025, 3, 10, 13, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rank_slots_avoids_bookings_and_prefers_free_attendees() {
+        use chrono::TimeZone;
+
+        let at = |hour: u32, minute: u32| chrono::Utc.with_ymd_and_hms(2025, 3, 10, hour, minute, 0).unwrap();
+        let candidates: Vec<_> = (0..8).map(|half_hours| at(9, 0) + chrono::Duration::minutes(30 * half_hours)).collect();
+        // Booked 10:00-11:00; Bob is busy 9:00-9:30
+        let own_busy = [BusyPeriod { start: at(10, 0), end: at(11, 0) }];
+        let bob_busy = [BusyPeriod { start: at(9, 0), end: at(9, 30) }];
+
+        let ranked = rank_slots(
+            candidates.clone(),
+            chrono::Duration::minutes(30),
+            chrono::Duration::minutes(15),
+            &own_busy,
+            &bob_busy,
+        );
+        // 9:30 ends within the buffer, 10:00 and 10:30 are booked, and 11:00
+        // starts within the buffer
+        assert_eq!(ranked, vec![at(11, 30), at(12, 0), at(12, 30), at(9, 0)]);
+
+        let ranked = rank_slots(
+            candidates,
+            chrono::Duration::minutes(30),
+            chrono::Duration::zero(),
+            &own_busy,
+            &[],
+        );
+        assert_eq!(ranked, vec![at(9, 0), at(9, 30), at(11, 0), at(11, 30), at(12, 0)]);
+    }
 }
\ No newline at end of file
@@ -13,10 +13,12 @@ pub mod factory;
 pub mod meeting_scheduler;
 pub mod provider;
 pub mod providers;
+pub mod reply_draft;
 pub mod retry;
 pub mod service;
 pub mod smart_compose;
 pub mod streaming;
+pub mod thread_summary;
 
 #[cfg(test)]
 mod triage_tests;
@@ -51,6 +53,8 @@ pub use smart_compose::{
     SmartComposeService, SmartComposeConfig, SmartComposeResponse, ComposeSuggestion,
     CompositionContext, EmailType, SuggestionType, SmartComposeStats
 };
+pub use reply_draft::ReplyTone;
+pub use thread_summary::ThreadSummary;
 pub use streaming::{AIStreamingManager, StreamChunk, StreamingConfig, StreamingSession, StreamingStats};
 
 use serde::{Deserialize, Serialize};
@@ -8,7 +8,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
 
 /// Capabilities that an AI provider supports
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +50,20 @@ pub trait AIProvider: Send + Sync {
     /// Complete text based on prompt and context
     async fn complete_text(&self, prompt: &str, context: Option<&AIContext>) -> AIResult<String>;
 
+    /// Complete text like `complete_text`, sending each piece of the answer
+    /// to `partial` as it is generated. Returns the whole answer
+    ///
+    /// Providers that cannot stream send the answer in one piece.
+    async fn stream_text(
+        &self,
+        prompt: &str,
+        partial: mpsc::UnboundedSender<String>,
+    ) -> AIResult<String> {
+        let text = self.complete_text(prompt, None).await?;
+        let _ = partial.send(text.clone());
+        Ok(text)
+    }
+
     /// Summarize content into a concise overview
     async fn summarize_content(&self, content: &str, max_length: Option<usize>) -> AIResult<String>;
 
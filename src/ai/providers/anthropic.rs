@@ -5,12 +5,17 @@ use crate::ai::config::AIConfig;
 use crate::ai::error::AIError;
 use crate::ai::provider::{AIProvider, ProviderCapabilities};
 use crate::ai::service::{EmailCategory, SchedulingIntent};
+use crate::ai::streaming::{sse_data, LineBuffer};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tokio::time::timeout;
 
+/// System prompt for free-form completions
+const SYSTEM_PROMPT: &str = "You are Claude, a helpful AI assistant for email and calendar management. Provide clear, concise, and professional responses.";
+
 /// Anthropic API request structure
 #[derive(Debug, Clone, Serialize)]
 struct AnthropicRequest {
@@ -25,6 +30,8 @@ struct AnthropicRequest {
     top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     top_k: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 /// Anthropic message structure
@@ -166,6 +173,7 @@ impl AnthropicProvider {
             temperature,
             top_p: None,
             top_k: None,
+            stream: false,
         };
 
         let response = timeout(self.request_timeout,
@@ -181,29 +189,7 @@ impl AnthropicProvider {
         .map_err(|_| AIError::timeout(self.request_timeout))?
         .map_err(AIError::from)?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            
-            return match status.as_u16() {
-                401 => Err(AIError::auth_failure("Anthropic")),
-                429 => Err(AIError::rate_limit("Anthropic", Some(Duration::from_secs(60)))),
-                400 => {
-                    if let Ok(error_response) = serde_json::from_str::<AnthropicErrorResponse>(&error_text) {
-                        if error_response.error.detail_type == "invalid_request_error" {
-                            Err(AIError::invalid_response(error_response.error.message))
-                        } else {
-                            Err(AIError::provider_unavailable(error_response.error.message))
-                        }
-                    } else {
-                        Err(AIError::invalid_response(error_text))
-                    }
-                },
-                413 => Err(AIError::request_too_large(0)),
-                500..=599 => Err(AIError::provider_unavailable("Anthropic server error")),
-                _ => Err(AIError::provider_unavailable(format!("Anthropic API error: {}", status))),
-            };
-        }
+        let response = Self::check_status(response).await?;
 
         let anthropic_response: AnthropicResponse = response
             .json()
@@ -217,6 +203,104 @@ impl AnthropicProvider {
         Ok(anthropic_response.content[0].text.clone())
     }
 
+    /// Make a streaming request to the Anthropic API, sending each piece of
+    /// the answer to `partial`
+    async fn make_streaming_request(
+        &self,
+        messages: Vec<AnthropicMessage>,
+        system_prompt: Option<String>,
+        temperature: Option<f32>,
+        partial: mpsc::UnboundedSender<String>,
+    ) -> AIResult<String> {
+        let request = AnthropicRequest {
+            model: self.model.clone(),
+            max_tokens: 1000,
+            messages,
+            system: system_prompt,
+            temperature,
+            top_p: None,
+            top_k: None,
+            stream: true,
+        };
+
+        let response = timeout(self.request_timeout,
+            self.client
+                .post("https://api.anthropic.com/v1/messages")
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", "2023-06-01")
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .send()
+        )
+        .await
+        .map_err(|_| AIError::timeout(self.request_timeout))?
+        .map_err(AIError::from)?;
+
+        let mut response = Self::check_status(response).await?;
+
+        // Server-sent events; text arrives in content_block_delta events
+        let mut lines = LineBuffer::default();
+        let mut answer = String::new();
+        while let Some(bytes) = response.chunk().await.map_err(AIError::from)? {
+            for line in lines.push(&bytes) {
+                let Some(data) = sse_data(&line) else {
+                    continue;
+                };
+                let event: serde_json::Value = serde_json::from_str(data).map_err(|e| {
+                    AIError::invalid_response(format!("Failed to parse Anthropic stream: {}", e))
+                })?;
+                match event["type"].as_str() {
+                    Some("content_block_delta") => {
+                        if let Some(piece) = event["delta"]["text"].as_str() {
+                            answer.push_str(piece);
+                            let _ = partial.send(piece.to_string());
+                        }
+                    }
+                    Some("message_stop") => return Ok(answer),
+                    Some("error") => {
+                        let message = event["error"]["message"].as_str().unwrap_or("stream error");
+                        return Err(AIError::provider_unavailable(message.to_string()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if answer.is_empty() {
+            return Err(AIError::invalid_response("No content in Anthropic stream"));
+        }
+        Ok(answer)
+    }
+
+    /// The response when it succeeded, otherwise the error it reports
+    async fn check_status(response: reqwest::Response) -> AIResult<reqwest::Response> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+
+        match status.as_u16() {
+            401 => Err(AIError::auth_failure("Anthropic")),
+            429 => Err(AIError::rate_limit("Anthropic", Some(Duration::from_secs(60)))),
+            400 => {
+                if let Ok(error_response) = serde_json::from_str::<AnthropicErrorResponse>(&error_text) {
+                    if error_response.error.detail_type == "invalid_request_error" {
+                        Err(AIError::invalid_response(error_response.error.message))
+                    } else {
+                        Err(AIError::provider_unavailable(error_response.error.message))
+                    }
+                } else {
+                    Err(AIError::invalid_response(error_text))
+                }
+            },
+            413 => Err(AIError::request_too_large(0)),
+            500..=599 => Err(AIError::provider_unavailable("Anthropic server error")),
+            _ => Err(AIError::provider_unavailable(format!("Anthropic API error: {}", status))),
+        }
+    }
+
     /// Create user message for Anthropic
     fn create_user_message(&self, content: &str) -> Vec<AnthropicMessage> {
         vec![AnthropicMessage {
@@ -326,6 +410,7 @@ impl AIProvider for AnthropicProvider {
                     temperature: Some(0.1),
                     top_p: None,
                     top_k: None,
+                    stream: false,
                 })
                 .send()
         ).await {
@@ -339,8 +424,6 @@ impl AIProvider for AnthropicProvider {
             .and_then(|c| c.creativity)
             .unwrap_or(0.7);
 
-        let system_prompt = Some("You are Claude, a helpful AI assistant for email and calendar management. Provide clear, concise, and professional responses.".to_string());
-        
         let enhanced_prompt = if let Some(ctx) = context {
             let mut full_prompt = prompt.to_string();
             
@@ -362,7 +445,17 @@ impl AIProvider for AnthropicProvider {
         };
 
         let messages = self.create_user_message(&enhanced_prompt);
-        self.make_request(messages, system_prompt, Some(temperature)).await
+        self.make_request(messages, Some(SYSTEM_PROMPT.to_string()), Some(temperature)).await
+    }
+
+    async fn stream_text(
+        &self,
+        prompt: &str,
+        partial: mpsc::UnboundedSender<String>,
+    ) -> AIResult<String> {
+        let messages = self.create_user_message(prompt);
+        self.make_streaming_request(messages, Some(SYSTEM_PROMPT.to_string()), Some(0.7), partial)
+            .await
     }
 
     async fn summarize_content(&self, content: &str, max_length: Option<usize>) -> AIResult<String> {
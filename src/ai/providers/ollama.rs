@@ -5,10 +5,12 @@ use crate::ai::config::AIConfig;
 use crate::ai::error::AIError;
 use crate::ai::provider::{AIProvider, ProviderCapabilities};
 use crate::ai::service::{EmailCategory, SchedulingIntent};
+use crate::ai::streaming::LineBuffer;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tokio::time::timeout;
 
 /// Ollama API request structure
@@ -182,6 +184,67 @@ impl OllamaProvider {
         Ok(ollama_response.response)
     }
 
+    /// Make a streaming request to the Ollama API, sending each piece of the
+    /// answer to `partial`
+    async fn make_streaming_request(
+        &self,
+        prompt: &str,
+        temperature: Option<f32>,
+        partial: mpsc::UnboundedSender<String>,
+    ) -> AIResult<String> {
+        let request = OllamaRequest {
+            model: self.model.clone(),
+            prompt: prompt.to_string(),
+            stream: true,
+            context: None,
+            temperature,
+            max_tokens: Some(1000),
+        };
+
+        let url = format!("{}/api/generate", self.endpoint);
+
+        let mut response = timeout(self.request_timeout,
+            self.client
+                .post(&url)
+                .json(&request)
+                .send()
+        )
+        .await
+        .map_err(|_| AIError::timeout(self.request_timeout))?
+        .map_err(AIError::from)?;
+
+        if !response.status().is_success() {
+            return Err(AIError::provider_unavailable(
+                format!("Ollama API returned status: {}", response.status())
+            ));
+        }
+
+        // One JSON object per line, the last one marked done
+        let mut lines = LineBuffer::default();
+        let mut answer = String::new();
+        loop {
+            let (new_lines, finished) = match response.chunk().await.map_err(AIError::from)? {
+                Some(bytes) => (lines.push(&bytes), false),
+                None => (std::mem::take(&mut lines).finish().into_iter().collect(), true),
+            };
+            for line in new_lines {
+                let piece: OllamaResponse = serde_json::from_str(&line).map_err(|e| {
+                    AIError::invalid_response(format!("Failed to parse Ollama stream: {}: {}", e, line))
+                })?;
+                if !piece.response.is_empty() {
+                    answer.push_str(&piece.response);
+                    let _ = partial.send(piece.response);
+                }
+                if piece.done {
+                    return Ok(answer);
+                }
+            }
+            if finished {
+                return Err(AIError::invalid_response("Incomplete response from Ollama"));
+            }
+        }
+    }
+
     /// Get available models from Ollama
     #[allow(dead_code)]
     async fn get_available_models(&self) -> AIResult<Vec<String>> {
@@ -331,6 +394,14 @@ impl AIProvider for OllamaProvider {
         self.make_request(&enhanced_prompt, Some(temperature)).await
     }
 
+    async fn stream_text(
+        &self,
+        prompt: &str,
+        partial: mpsc::UnboundedSender<String>,
+    ) -> AIResult<String> {
+        self.make_streaming_request(prompt, Some(0.7), partial).await
+    }
+
     async fn summarize_content(&self, content: &str, max_length: Option<usize>) -> AIResult<String> {
         let max_len = max_length.unwrap_or(200);
         let prompt = format!(
@@ -5,12 +5,17 @@ use crate::ai::config::AIConfig;
 use crate::ai::error::AIError;
 use crate::ai::provider::{AIProvider, ProviderCapabilities};
 use crate::ai::service::{EmailCategory, SchedulingIntent};
+use crate::ai::streaming::{sse_data, LineBuffer};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tokio::time::timeout;
 
+/// System prompt for free-form completions
+const SYSTEM_PROMPT: &str = "You are a helpful AI assistant for email and calendar management. Provide clear, concise, and professional responses.";
+
 /// OpenAI API request structure
 #[derive(Debug, Clone, Serialize)]
 struct OpenAIRequest {
@@ -26,6 +31,8 @@ struct OpenAIRequest {
     frequency_penalty: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 /// OpenAI message structure
@@ -158,6 +165,7 @@ impl OpenAIProvider {
             top_p: None,
             frequency_penalty: None,
             presence_penalty: None,
+            stream: false,
         };
 
         let response = timeout(self.request_timeout,
@@ -172,29 +180,7 @@ impl OpenAIProvider {
         .map_err(|_| AIError::timeout(self.request_timeout))?
         .map_err(AIError::from)?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            
-            return match status.as_u16() {
-                401 => Err(AIError::auth_failure("OpenAI")),
-                429 => Err(AIError::rate_limit("OpenAI", Some(Duration::from_secs(60)))),
-                400 => {
-                    if let Ok(error_response) = serde_json::from_str::<OpenAIErrorResponse>(&error_text) {
-                        if error_response.error.code.as_deref() == Some("content_policy_violation") {
-                            Err(AIError::content_filtered(error_response.error.message))
-                        } else {
-                            Err(AIError::invalid_response(error_response.error.message))
-                        }
-                    } else {
-                        Err(AIError::invalid_response(error_text))
-                    }
-                },
-                413 => Err(AIError::request_too_large(0)), // Size unknown from status
-                500..=599 => Err(AIError::provider_unavailable("OpenAI server error")),
-                _ => Err(AIError::provider_unavailable(format!("OpenAI API error: {}", status))),
-            };
-        }
+        let response = Self::check_status(response).await?;
 
         let openai_response: OpenAIResponse = response
             .json()
@@ -208,6 +194,97 @@ impl OpenAIProvider {
         Ok(openai_response.choices[0].message.content.clone())
     }
 
+    /// Make a streaming request to the OpenAI API, sending each piece of the
+    /// answer to `partial`
+    async fn make_streaming_request(
+        &self,
+        messages: Vec<OpenAIMessage>,
+        temperature: Option<f32>,
+        partial: mpsc::UnboundedSender<String>,
+    ) -> AIResult<String> {
+        let request = OpenAIRequest {
+            model: self.model.clone(),
+            messages,
+            temperature,
+            max_tokens: Some(1000),
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: true,
+        };
+
+        let response = timeout(self.request_timeout,
+            self.client
+                .post("https://api.openai.com/v1/chat/completions")
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                .json(&request)
+                .send()
+        )
+        .await
+        .map_err(|_| AIError::timeout(self.request_timeout))?
+        .map_err(AIError::from)?;
+
+        let mut response = Self::check_status(response).await?;
+
+        // Server-sent events with the next piece in choices[0].delta.content
+        let mut lines = LineBuffer::default();
+        let mut answer = String::new();
+        while let Some(bytes) = response.chunk().await.map_err(AIError::from)? {
+            for line in lines.push(&bytes) {
+                let Some(data) = sse_data(&line) else {
+                    continue;
+                };
+                if data == "[DONE]" {
+                    return Ok(answer);
+                }
+                let event: serde_json::Value = serde_json::from_str(data).map_err(|e| {
+                    AIError::invalid_response(format!("Failed to parse OpenAI stream: {}", e))
+                })?;
+                if let Some(piece) = event["choices"][0]["delta"]["content"].as_str() {
+                    if !piece.is_empty() {
+                        answer.push_str(piece);
+                        let _ = partial.send(piece.to_string());
+                    }
+                }
+            }
+        }
+
+        if answer.is_empty() {
+            return Err(AIError::invalid_response("No content in OpenAI stream"));
+        }
+        Ok(answer)
+    }
+
+    /// The response when it succeeded, otherwise the error it reports
+    async fn check_status(response: reqwest::Response) -> AIResult<reqwest::Response> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+
+        match status.as_u16() {
+            401 => Err(AIError::auth_failure("OpenAI")),
+            429 => Err(AIError::rate_limit("OpenAI", Some(Duration::from_secs(60)))),
+            400 => {
+                if let Ok(error_response) = serde_json::from_str::<OpenAIErrorResponse>(&error_text) {
+                    if error_response.error.code.as_deref() == Some("content_policy_violation") {
+                        Err(AIError::content_filtered(error_response.error.message))
+                    } else {
+                        Err(AIError::invalid_response(error_response.error.message))
+                    }
+                } else {
+                    Err(AIError::invalid_response(error_text))
+                }
+            },
+            413 => Err(AIError::request_too_large(0)), // Size unknown from status
+            500..=599 => Err(AIError::provider_unavailable("OpenAI server error")),
+            _ => Err(AIError::provider_unavailable(format!("OpenAI API error: {}", status))),
+        }
+    }
+
     /// Create system and user messages for OpenAI
     fn create_messages(&self, system_prompt: &str, user_prompt: &str) -> Vec<OpenAIMessage> {
         vec![
@@ -324,6 +401,7 @@ impl AIProvider for OpenAIProvider {
                     top_p: None,
                     frequency_penalty: None,
                     presence_penalty: None,
+                    stream: false,
                 })
                 .send()
         ).await {
@@ -337,8 +415,6 @@ impl AIProvider for OpenAIProvider {
             .and_then(|c| c.creativity)
             .unwrap_or(0.7);
 
-        let system_prompt = "You are a helpful AI assistant for email and calendar management. Provide clear, concise, and professional responses.";
-        
         let enhanced_prompt = if let Some(ctx) = context {
             let mut full_prompt = prompt.to_string();
             
@@ -359,10 +435,19 @@ impl AIProvider for OpenAIProvider {
             prompt.to_string()
         };
 
-        let messages = self.create_messages(system_prompt, &enhanced_prompt);
+        let messages = self.create_messages(SYSTEM_PROMPT, &enhanced_prompt);
         self.make_request(messages, Some(temperature)).await
     }
 
+    async fn stream_text(
+        &self,
+        prompt: &str,
+        partial: mpsc::UnboundedSender<String>,
+    ) -> AIResult<String> {
+        let messages = self.create_messages(SYSTEM_PROMPT, prompt);
+        self.make_streaming_request(messages, Some(0.7), partial).await
+    }
+
     async fn summarize_content(&self, content: &str, max_length: Option<usize>) -> AIResult<String> {
         let max_len = max_length.unwrap_or(200);
         let system_prompt = "You are an expert at creating concise, informative summaries. Focus on key points and main messages.";
@@ -0,0 +1,125 @@
+//! Whole reply drafts written by the AI provider
+//!
+//! A draft answers one message in a chosen tone. It is the body only: the
+//! compose window keeps the subject, recipients and quoted original, and the
+//! user's signature is added on sending as usual.
+
+use serde::{Deserialize, Serialize};
+
+/// Longest original text sent along with a draft request
+const MAX_ORIGINAL_CHARS: usize = 6_000;
+
+/// Tone of a drafted reply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ReplyTone {
+    /// Polite and businesslike
+    Formal,
+    /// Warm and conversational
+    #[default]
+    Friendly,
+    /// A few sentences at most
+    Brief,
+}
+
+impl ReplyTone {
+    /// Every tone, in the order they are cycled through
+    pub const ALL: [ReplyTone; 3] = [ReplyTone::Formal, ReplyTone::Friendly, ReplyTone::Brief];
+
+    /// Lowercase name, as shown in compose
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReplyTone::Formal => "formal",
+            ReplyTone::Friendly => "friendly",
+            ReplyTone::Brief => "brief",
+        }
+    }
+
+    /// Tone after this one, wrapping around
+    pub fn next(&self) -> ReplyTone {
+        let index = Self::ALL.iter().position(|tone| tone == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// How the provider is asked to write in this tone
+    fn instructions(&self) -> &'static str {
+        match self {
+            ReplyTone::Formal => {
+                "Write in a formal, polite and professional tone, with a proper greeting and closing."
+            }
+            ReplyTone::Friendly => {
+                "Write in a warm, friendly and conversational tone, as a colleague would."
+            }
+            ReplyTone::Brief => {
+                "Be brief: two or three short sentences that answer what was asked, with a short greeting."
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ReplyTone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Prompt asking for a reply to `original` in `tone`
+///
+/// `original` is the message with its headers, cut to a size any provider
+/// takes in one request.
+pub fn reply_prompt(original: &str, tone: ReplyTone) -> String {
+    let original: String = original.chars().take(MAX_ORIGINAL_CHARS).collect();
+    format!(
+        "Draft a complete reply to the email below. {}\n\
+         Answer its questions and respond to any requests. Do not invent facts, \
+         dates or commitments; leave a placeholder like [time] where the sender \
+         must fill something in.\n\
+         Reply with the body of the email only: no subject line, no signature \
+         and no quoted original.\n\n\
+         Email:\n{}",
+        tone.instructions(),
+        original
+    )
+}
+
+/// Reply body from a provider response
+///
+/// Drops a subject line, surrounding code fences and a lead-in such as
+/// "Here is a reply:" that models sometimes add.
+pub fn clean_draft(response: &str) -> String {
+    let mut lines: Vec<&str> = response.trim().lines().collect();
+
+    if lines.first().is_some_and(|line| line.trim_start().starts_with("```")) {
+        lines.remove(0);
+    }
+    if lines.last().is_some_and(|line| line.trim() == "```") {
+        lines.pop();
+    }
+    if lines.first().is_some_and(|line| {
+        let line = line.trim().to_lowercase();
+        line.starts_with("subject:") || (line.starts_with("here") && line.ends_with(':'))
+    }) {
+        lines.remove(0);
+    }
+
+    lines.join("\n").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_draft() {
+        assert_eq!(
+            clean_draft("Here is a friendly reply:\n\nHi Ann,\n\nSounds good!\n"),
+            "Hi Ann,\n\nSounds good!"
+        );
+        assert_eq!(
+            clean_draft("```\nSubject: Re: Lunch\n```"),
+            ""
+        );
+        assert_eq!(clean_draft("Subject: Re: Lunch\nHi,\nYes."), "Hi,\nYes.");
+        assert_eq!(ReplyTone::Brief.next(), ReplyTone::Formal);
+        assert!(reply_prompt("Hello", ReplyTone::Formal).contains("formal"));
+    }
+}
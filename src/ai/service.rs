@@ -1,17 +1,22 @@
 //! Main AI service providing high-level AI functionality
 
 use crate::ai::{AIContext, AIResult};
-use crate::ai::cache::AIResponseCache;
+use crate::ai::cache::{AIResponseCache, CacheStatistics};
 use crate::ai::config::{AIConfig, AIProviderType};
 use crate::ai::error::AIError;
 use crate::ai::provider::AIProviderManager;
+use crate::ai::reply_draft::{self, ReplyTone};
 use crate::ai::retry::{RetryManager, RetryConfig};
+use crate::ai::streaming::{AIStreamingManager, StreamingConfig};
+use crate::ai::thread_summary::{self, ThreadSummary};
+use crate::email::InboxTab;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
 use tracing::{debug, info};
+use uuid::Uuid;
 
 /// Email categorization types
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -209,6 +214,7 @@ pub struct AIService {
     cache: Arc<AIResponseCache>,
     config: Arc<RwLock<AIConfig>>,
     retry_manager: RetryManager,
+    streaming: AIStreamingManager,
 }
 
 impl AIService {
@@ -223,6 +229,7 @@ impl AIService {
             cache,
             config,
             retry_manager: RetryManager::default(),
+            streaming: AIStreamingManager::new(StreamingConfig::default()),
         }
     }
 
@@ -238,6 +245,7 @@ impl AIService {
             cache,
             config,
             retry_manager: RetryManager::new(retry_config),
+            streaming: AIStreamingManager::new(StreamingConfig::default()),
         }
     }
 
@@ -329,6 +337,99 @@ impl AIService {
         result
     }
 
+    /// Streams of partial answers, by operation
+    pub fn streaming(&self) -> &AIStreamingManager {
+        &self.streaming
+    }
+
+    /// Complete text, streaming the answer under `operation_id` as it is
+    /// generated. Returns the whole answer
+    ///
+    /// Unlike `complete_text` this is not retried, since part of the answer
+    /// may already be on screen.
+    pub async fn complete_text_streaming(&self, prompt: &str, operation_id: Uuid) -> AIResult<String> {
+        let (partial_tx, mut partial_rx) = mpsc::unbounded_channel();
+
+        let request = async {
+            let provider_manager = self.provider_manager.read().await;
+            let provider = provider_manager.get_active_provider().await?;
+            provider.stream_text(prompt, partial_tx).await
+        };
+        let forward = async {
+            let mut sequence = 0;
+            while let Some(content) = partial_rx.recv().await {
+                let chunk = AIStreamingManager::create_chunk(operation_id, sequence, content, false);
+                if let Err(e) = self.streaming.send_chunk(chunk).await {
+                    debug!("Dropped streamed chunk for {}: {}", operation_id, e);
+                }
+                sequence += 1;
+            }
+        };
+
+        let (result, ()) = tokio::join!(request, forward);
+        result
+    }
+
+    /// Complete text, streamed when there is an operation to stream it under
+    async fn complete_text_for(&self, prompt: &str, operation_id: Option<Uuid>) -> AIResult<String> {
+        match operation_id {
+            Some(operation_id) => self.complete_text_streaming(prompt, operation_id).await,
+            None => self.complete_text(prompt, None).await,
+        }
+    }
+
+    /// Draft a whole reply to `original` in `tone`
+    ///
+    /// `original` is the message being answered with its sender and subject.
+    /// Returns the reply body only; it is streamed under `operation_id` when
+    /// given.
+    pub async fn draft_reply(
+        &self,
+        original: &str,
+        tone: ReplyTone,
+        operation_id: Option<Uuid>,
+    ) -> AIResult<String> {
+        if !self.is_enabled().await {
+            return Err(AIError::config_error("AI functionality is disabled"));
+        }
+
+        let config = self.config.read().await;
+        if !config.is_feature_enabled("email_suggestions") {
+            return Err(AIError::feature_not_supported(
+                config.provider.to_string(),
+                "email_suggestions".to_string(),
+            ));
+        }
+        drop(config);
+
+        let cache_key = self
+            .cache
+            .generate_prompt_hash(&format!("draft_reply:{}:{}", tone, original), None);
+        if let Some(cached) = self.cache.get_cached_response(&cache_key).await {
+            debug!("Using cached {} reply draft", tone);
+            return Ok(cached.content);
+        }
+
+        let response = self
+            .complete_text_for(&reply_draft::reply_prompt(original, tone), operation_id)
+            .await?;
+        let draft = reply_draft::clean_draft(&response);
+        if draft.is_empty() {
+            return Err(AIError::invalid_response("Reply draft was empty"));
+        }
+
+        let provider_name = {
+            let provider_manager = self.provider_manager.read().await;
+            let provider = provider_manager.get_active_provider().await?;
+            provider.name().to_string()
+        };
+        self.cache
+            .cache_response(&cache_key, &draft, &provider_name, None)
+            .await?;
+
+        Ok(draft)
+    }
+
     /// Summarize email content
     pub async fn summarize_email(&self, content: &str, max_length: Option<usize>) -> AIResult<String> {
         if !self.is_enabled().await {
@@ -344,10 +445,7 @@ impl AIService {
         }
 
         // Generate cache key
-        let cache_key = self.cache.generate_prompt_hash(
-            &format!("summarize:{}", content),
-            max_length.as_ref().map(|l| l.to_string()).as_deref(),
-        );
+        let cache_key = self.summary_cache_key(content, max_length);
 
         // Check cache first
         if let Some(cached) = self.cache.get_cached_response(&cache_key).await {
@@ -400,6 +498,188 @@ impl AIService {
         result
     }
 
+    /// Summarize email content like `summarize_email`, streaming the summary
+    /// under `operation_id` as it is written
+    pub async fn summarize_email_streaming(
+        &self,
+        content: &str,
+        max_length: Option<usize>,
+        operation_id: Uuid,
+    ) -> AIResult<String> {
+        if !self.is_enabled().await {
+            return Err(AIError::config_error("AI functionality is disabled"));
+        }
+
+        let config = self.config.read().await;
+        if !config.is_feature_enabled("email_summarization") {
+            return Err(AIError::feature_not_supported(
+                config.provider.to_string(),
+                "email_summarization".to_string(),
+            ));
+        }
+        drop(config);
+
+        // Shares its cache entries with summarize_email
+        let cache_key = self.summary_cache_key(content, max_length);
+        if let Some(cached) = self.cache.get_cached_response(&cache_key).await {
+            debug!("Using cached email summary");
+            return Ok(cached.content);
+        }
+
+        let prompt = format!(
+            "Please summarize the following content in approximately {} characters or less. \
+             Focus on the key points and main message:\n\n{}",
+            max_length.unwrap_or(200),
+            content
+        );
+        let summary = self.complete_text_streaming(&prompt, operation_id).await?;
+
+        let provider_name = {
+            let provider_manager = self.provider_manager.read().await;
+            let provider = provider_manager.get_active_provider().await?;
+            provider.name().to_string()
+        };
+        self.cache
+            .cache_response(&cache_key, &summary, &provider_name, None)
+            .await?;
+
+        Ok(summary)
+    }
+
+    /// Summarize a stored message, tagging the cached summary with its
+    /// Message-ID so `invalidate_message` can drop it when the message changes.
+    /// With an `operation_id` the summary is streamed under it.
+    pub async fn summarize_message(
+        &self,
+        message_id: &str,
+        content: &str,
+        max_length: Option<usize>,
+        operation_id: Option<Uuid>,
+    ) -> AIResult<String> {
+        let summary = match operation_id {
+            Some(operation_id) => {
+                self.summarize_email_streaming(content, max_length, operation_id)
+                    .await?
+            }
+            None => self.summarize_email(content, max_length).await?,
+        };
+        self.cache
+            .tag_entry(&self.summary_cache_key(content, max_length), &message_cache_tag(message_id))
+            .await;
+        Ok(summary)
+    }
+
+    fn summary_cache_key(&self, content: &str, max_length: Option<usize>) -> String {
+        self.cache.generate_prompt_hash(
+            &format!("summarize:{}", content),
+            max_length.as_ref().map(|l| l.to_string()).as_deref(),
+        )
+    }
+
+    /// Summarize a whole email thread as bullet points
+    ///
+    /// `messages` are the thread's messages in order, already reduced to the
+    /// text worth summarizing. Threads longer than the provider's context are
+    /// summarized in chunks whose partial summaries are then combined. The
+    /// final request is streamed under `operation_id` when given.
+    pub async fn summarize_thread(
+        &self,
+        messages: &[String],
+        operation_id: Option<Uuid>,
+    ) -> AIResult<ThreadSummary> {
+        if !self.is_enabled().await {
+            return Err(AIError::config_error("AI functionality is disabled"));
+        }
+
+        let config = self.config.read().await;
+        if !config.is_feature_enabled("email_summarization") {
+            return Err(AIError::feature_not_supported(
+                config.provider.to_string(),
+                "email_summarization".to_string(),
+            ));
+        }
+        drop(config);
+
+        if messages.is_empty() {
+            return Err(AIError::internal_error("Thread has no messages to summarize"));
+        }
+
+        let cache_key = self
+            .cache
+            .generate_prompt_hash(&format!("thread:{}", messages.join("\n\n")), None);
+        if let Some(cached) = self.cache.get_cached_response(&cache_key).await {
+            if let Ok(summary) = serde_json::from_str::<ThreadSummary>(&cached.content) {
+                debug!("Using cached thread summary");
+                return Ok(summary);
+            }
+        }
+
+        let (budget, provider_name) = {
+            let provider_manager = self.provider_manager.read().await;
+            let provider = provider_manager.get_active_provider().await?;
+            (
+                thread_summary::chunk_budget(provider.capabilities().max_context_length),
+                provider.name().to_string(),
+            )
+        };
+
+        let mut chunks = thread_summary::chunk_texts(messages, budget);
+        let chunk_count = chunks.len();
+        let mut combining = false;
+        let mut levels = 0;
+
+        // Summarize chunks until what is left fits in a single request
+        while chunks.len() > 1 {
+            levels += 1;
+            if levels > thread_summary::MAX_SUMMARY_LEVELS {
+                return Err(AIError::request_too_large(messages.iter().map(String::len).sum()));
+            }
+            info!("Summarizing thread level {} in {} chunks", levels, chunks.len());
+
+            let parts = chunks.len();
+            let partials = futures::future::try_join_all(chunks.iter().enumerate().map(
+                |(index, chunk)| {
+                    let prompt = thread_summary::chunk_prompt(index + 1, parts, chunk);
+                    async move { self.complete_text(&prompt, None).await }
+                },
+            ))
+            .await?;
+
+            let partials: Vec<String> = partials
+                .iter()
+                .map(|partial| partial.trim().to_string())
+                .collect();
+            chunks = thread_summary::chunk_texts(&partials, budget);
+            combining = true;
+        }
+
+        let transcript = chunks.pop().unwrap_or_default();
+        let prompt = if combining {
+            thread_summary::combine_prompt(&transcript)
+        } else {
+            thread_summary::thread_prompt(&transcript)
+        };
+        let response = self.complete_text_for(&prompt, operation_id).await?;
+
+        let bullets = thread_summary::parse_bullets(&response);
+        if bullets.is_empty() {
+            return Err(AIError::invalid_response("Thread summary contained no points"));
+        }
+
+        let summary = ThreadSummary {
+            bullets,
+            message_count: messages.len(),
+            chunk_count,
+        };
+        if let Ok(serialized) = serde_json::to_string(&summary) {
+            self.cache
+                .cache_response(&cache_key, &serialized, &provider_name, None)
+                .await?;
+        }
+
+        Ok(summary)
+    }
+
     /// Categorize email content
     pub async fn categorize_email(&self, content: &str) -> AIResult<EmailCategory> {
         if !self.is_enabled().await {
@@ -415,10 +695,7 @@ impl AIService {
         }
 
         // Generate cache key
-        let cache_key = self.cache.generate_prompt_hash(
-            &format!("categorize:{}", content),
-            None,
-        );
+        let cache_key = self.category_cache_key(content);
 
         // Check cache first
         if let Some(cached) = self.cache.get_cached_response(&cache_key).await {
@@ -445,6 +722,103 @@ impl AIService {
         Ok(category)
     }
 
+    /// Categorize a stored message, tagging the cached category with its
+    /// Message-ID like `summarize_message`
+    pub async fn categorize_message(&self, message_id: &str, content: &str) -> AIResult<EmailCategory> {
+        let category = self.categorize_email(content).await?;
+        self.cache
+            .tag_entry(&self.category_cache_key(content), &message_cache_tag(message_id))
+            .await;
+        Ok(category)
+    }
+
+    fn category_cache_key(&self, content: &str) -> String {
+        self.cache.generate_prompt_hash(&format!("categorize:{}", content), None)
+    }
+
+    /// Drop the cached summary and category of a message that was edited or
+    /// moved, so the next summary is asked for afresh
+    pub async fn invalidate_message(&self, message_id: &str) -> AIResult<usize> {
+        let removed = self
+            .cache
+            .invalidate_by_tags(&[message_cache_tag(message_id)])
+            .await?;
+        if removed > 0 {
+            debug!("Dropped {} cached AI responses for {}", removed, message_id);
+        }
+        Ok(removed)
+    }
+
+    /// Pick the INBOX tab a message belongs under
+    pub async fn classify_inbox_tab(
+        &self,
+        message: &crate::email::StoredMessage,
+    ) -> AIResult<InboxTab> {
+        if !self.is_enabled().await {
+            return Err(AIError::config_error("AI functionality is disabled"));
+        }
+
+        let config = self.config.read().await;
+        if !config.is_feature_enabled("email_categorization") {
+            return Err(AIError::feature_not_supported(
+                config.provider.to_string(),
+                "email_categorization".to_string(),
+            ));
+        }
+        drop(config);
+
+        // The start of the body is enough to tell a newsletter from a person
+        let body: String = message
+            .body_text
+            .as_deref()
+            .unwrap_or("")
+            .chars()
+            .take(1_500)
+            .collect();
+        let email_content = format!(
+            "Subject: {}\nFrom: {} <{}>\n\n{}",
+            message.subject,
+            message.from_name.as_deref().unwrap_or(""),
+            message.from_addr,
+            body
+        );
+
+        let cache_key = self
+            .cache
+            .generate_prompt_hash(&format!("inbox_tab:{}", email_content), None);
+        if let Some(cached) = self.cache.get_cached_response(&cache_key).await {
+            if let Ok(tab) = cached.content.parse::<InboxTab>() {
+                return Ok(tab);
+            }
+        }
+
+        let prompt = format!(
+            "Sort this email into exactly one inbox tab:\n\
+             Primary: personal mail from people and anything important that fits nowhere else\n\
+             Promotions: deals, offers, marketing and other sales mail\n\
+             Social: notifications from social networks, dating and media sharing sites\n\
+             Updates: receipts, bills, statements, confirmations and automated notices\n\
+             Forums: mailing lists, online groups and discussion boards\n\n\
+             Answer with the tab name only.\n\n{}",
+            email_content
+        );
+        let response = self.complete_text(&prompt, None).await?;
+        let tab = InboxTab::from_response(&response).ok_or_else(|| {
+            AIError::invalid_response(format!("No inbox tab in response: {}", response.trim()))
+        })?;
+
+        let provider_name = {
+            let provider_manager = self.provider_manager.read().await;
+            let provider = provider_manager.get_active_provider().await?;
+            provider.name().to_string()
+        };
+        self.cache
+            .cache_response(&cache_key, tab.as_str(), &provider_name, None)
+            .await?;
+
+        Ok(tab)
+    }
+
     /// Parse scheduling intent from natural language
     pub async fn parse_scheduling_intent(&self, text: &str) -> AIResult<SchedulingIntent> {
         if !self.is_enabled().await {
@@ -682,6 +1056,21 @@ impl AIService {
         self.cache.invalidate_cache("*").await
     }
 
+    /// Usage statistics of the response cache
+    pub async fn cache_stats(&self) -> CacheStatistics {
+        self.cache.get_cache_stats().await
+    }
+
+    /// Change how long new responses stay cached. Responses already cached
+    /// keep the TTL they were stored with.
+    pub async fn set_cache_ttl(&self, ttl: Duration) {
+        let mut cache_config = self.cache.get_config().await;
+        if cache_config.default_ttl != ttl {
+            cache_config.default_ttl = ttl;
+            self.cache.update_config(cache_config).await;
+        }
+    }
+
     /// Execute an AI operation with custom retry configuration
     pub async fn execute_with_custom_retry<F, Fut, T>(
         &self,
@@ -1085,6 +1474,11 @@ Respond only with valid JSON."#,
     }
 }
 
+/// Cache tag of the responses about one message
+fn message_cache_tag(message_id: &str) -> String {
+    format!("message:{}", message_id)
+}
+
 /// AI service health information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AIServiceHealth {
@@ -11,6 +11,9 @@ use tokio::sync::RwLock;
 use tracing::{debug, info};
 use uuid::Uuid;
 
+/// Characters before the cursor sent for inline completion
+const INLINE_CONTEXT_CHARS: usize = 1_500;
+
 /// Smart compose configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SmartComposeConfig {
@@ -157,6 +160,19 @@ pub struct StylePreferences {
     pub include_action_items: bool,
 }
 
+impl Default for StylePreferences {
+    fn default() -> Self {
+        Self {
+            formality: FormalityLevel::SemiFormal,
+            tone: ToneType::Professional,
+            length: LengthPreference::Moderate,
+            include_personal_touches: false,
+            prefer_active_voice: true,
+            include_action_items: false,
+        }
+    }
+}
+
 /// Formality levels
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Hash)]
 pub enum FormalityLevel {
@@ -491,6 +507,70 @@ impl SmartComposeService {
         Ok(response)
     }
 
+    /// Predict how the text being typed continues, for ghost text in the editor
+    ///
+    /// Makes a single request, unlike [`generate_suggestions`](Self::generate_suggestions),
+    /// so it stays quick enough to run whenever typing pauses. Returns `None`
+    /// when auto-completion is off, the text is too short or the model had
+    /// nothing to add.
+    pub async fn complete_inline(
+        &self,
+        current_text: &str,
+        context: &CompositionContext,
+    ) -> AIResult<Option<ComposeSuggestion>> {
+        let config = self.config.read().await;
+        if !config.enabled
+            || !config.enable_auto_completion
+            || current_text.trim().chars().count() < config.min_trigger_length
+        {
+            return Ok(None);
+        }
+        drop(config);
+
+        let prompt = self.build_inline_prompt(current_text, context);
+        let ai_request = EnhancedAIRequest::high_priority(AIOperationType::Custom {
+            operation_name: "inline_completion".to_string(),
+            prompt,
+            context: None,
+        });
+        let response = self.ai_service.process_request(ai_request).await?;
+
+        let Some(content) = inline_continuation(&response.content, current_text) else {
+            return Ok(None);
+        };
+        let suggestion = ComposeSuggestion {
+            id: Uuid::new_v4(),
+            suggestion_type: SuggestionType::BodyCompletion,
+            content,
+            confidence: 0.8,
+            reasoning: "Continuation of the text before the cursor".to_string(),
+            context_position: ContextPosition {
+                line: current_text.lines().count().saturating_sub(1),
+                character: current_text.len(),
+                word_position: WordPosition::LineEnd,
+                surrounding_text: current_text
+                    .chars()
+                    .rev()
+                    .take(50)
+                    .collect::<String>()
+                    .chars()
+                    .rev()
+                    .collect(),
+            },
+            alternatives: vec![],
+            metadata: HashMap::new(),
+        };
+
+        let mut stats = self.stats.write().await;
+        stats.total_suggestions += 1;
+        *stats
+            .suggestions_by_type
+            .entry(format!("{:?}", suggestion.suggestion_type))
+            .or_insert(0) += 1;
+
+        Ok(Some(suggestion))
+    }
+
     /// Generate subject line suggestions
     async fn generate_subject_suggestions(&self, context: &CompositionContext) -> AIResult<Vec<ComposeSuggestion>> {
         let prompt = self.build_subject_prompt(context);
@@ -838,6 +918,31 @@ impl SmartComposeService {
         )
     }
 
+    /// Build the prompt continuing the text at the cursor
+    fn build_inline_prompt(&self, current_text: &str, context: &CompositionContext) -> String {
+        // The end of the text says most about what comes next
+        let skip = current_text.chars().count().saturating_sub(INLINE_CONTEXT_CHARS);
+        let recent: String = current_text.chars().skip(skip).collect();
+
+        let mut prompt = format!(
+            "You are completing an email as it is typed. Email type: {:?}. Tone: {:?}.\n",
+            context.email_type, context.style_preferences.tone
+        );
+        if let Some(original) = &context.referenced_email {
+            prompt.push_str(&format!(
+                "It answers this email from {} with subject \"{}\":\n{}\n",
+                original.sender, original.subject, original.content_summary
+            ));
+        }
+        prompt.push_str(&format!(
+            "\nContinue the text below from exactly where it stops, with at most one short \
+             sentence. Reply with the continuation only: do not repeat the text, add quotes \
+             or explain.\n\n{}",
+            recent
+        ));
+        prompt
+    }
+
     /// Build closing generation prompt
     fn build_closing_prompt(&self, context: &CompositionContext) -> String {
         let relationship = context.recipients.first()
@@ -942,6 +1047,35 @@ impl SmartComposeService {
     }
 }
 
+/// Ghost text from a model's continuation of `current_text`
+///
+/// Only the first line is kept, quotes and a repeated tail of the text are
+/// dropped, and a space is added where the continuation would otherwise run
+/// into the last word.
+pub fn inline_continuation(response: &str, current_text: &str) -> Option<String> {
+    let line = response.lines().map(str::trim_end).find(|line| !line.trim().is_empty())?;
+    let mut continuation = line.trim_matches(|c| c == '"' || c == '“' || c == '”').to_string();
+
+    // Models sometimes echo the end of the text before continuing it
+    let last_line = current_text.lines().last().unwrap_or("").trim();
+    if !last_line.is_empty() {
+        if let Some(rest) = continuation.trim_start().strip_prefix(last_line) {
+            continuation = rest.to_string();
+        }
+    }
+
+    if continuation.trim().is_empty() {
+        return None;
+    }
+    let joins_word = current_text.ends_with(|c: char| c.is_alphanumeric() || ",.;:!?".contains(c));
+    if joins_word && continuation.starts_with(char::is_alphanumeric) {
+        continuation.insert(0, ' ');
+    } else if current_text.is_empty() || current_text.ends_with(char::is_whitespace) {
+        continuation = continuation.trim_start().to_string();
+    }
+    Some(continuation)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1010,6 +1144,25 @@ mod tests {
         assert_eq!(suggestion.content, "Test subject line");
     }
 
+    #[test]
+    fn test_inline_continuation() {
+        assert_eq!(
+            inline_continuation("let me know if that works.\nBest,", "Thanks, Ana.").as_deref(),
+            Some(" let me know if that works.")
+        );
+        assert_eq!(
+            inline_continuation("\"I will send the report by Friday\"", "Hi Ben,\n\n").as_deref(),
+            Some("I will send the report by Friday")
+        );
+        // An echoed tail is not suggested again
+        assert_eq!(
+            inline_continuation("Could you review the draft before noon?", "Could you review").as_deref(),
+            Some(" the draft before noon?")
+        );
+        assert_eq!(inline_continuation("  \n", "Hello"), None);
+        assert_eq!(inline_continuation("Could you review", "Could you review"), None);
+    }
+
     #[test]
     fn test_cache_key_generation() {
         // This would need access to SmartComposeService instance
@@ -362,6 +362,37 @@ impl AIStreamingManager {
         });
     }
 
+    /// Run `operation`, handing the content of every chunk streamed under
+    /// `operation_id` meanwhile to `on_chunk`
+    ///
+    /// The stream is started before the operation and ended after it, so it
+    /// is cleaned up whether the operation streamed or answered from cache.
+    pub async fn relay<T>(
+        &self,
+        operation_id: Uuid,
+        mut on_chunk: impl FnMut(String) + Send + 'static,
+        operation: impl std::future::Future<Output = T>,
+    ) -> T {
+        let relay = match self.start_stream(operation_id).await {
+            Ok(mut chunks) => Some(tokio::spawn(async move {
+                while let Some(chunk) = chunks.recv().await {
+                    on_chunk(chunk.content);
+                }
+            })),
+            Err(e) => {
+                debug!("Not streaming operation {}: {}", operation_id, e);
+                None
+            }
+        };
+
+        let result = operation.await;
+        let _ = self.end_stream(operation_id).await;
+        if let Some(relay) = relay {
+            let _ = relay.await;
+        }
+        result
+    }
+
     /// Create a streaming chunk
     pub fn create_chunk(
         operation_id: Uuid,
@@ -398,6 +429,43 @@ impl AIStreamingManager {
     }
 }
 
+/// Splits streamed response bytes into complete lines
+///
+/// Providers stream newline-delimited JSON or server-sent events, and a
+/// network read can end anywhere, even inside a UTF-8 character.
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Add bytes read from the response and take the lines they complete
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if !line.is_empty() {
+                lines.push(line.to_string());
+            }
+        }
+        lines
+    }
+
+    /// Last line, when the response did not end with a newline
+    pub fn finish(self) -> Option<String> {
+        let line = String::from_utf8_lossy(&self.pending).trim().to_string();
+        (!line.is_empty()).then_some(line)
+    }
+}
+
+/// Payload of a server-sent event `data:` line
+pub fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:").map(str::trim_start)
+}
+
 impl Clone for AIStreamingManager {
     fn clone(&self) -> Self {
         Self {
@@ -498,6 +566,47 @@ mod tests {
         assert!(manager.get_session(operation_id).await.is_none());
     }
 
+    #[tokio::test]
+    async fn test_relay() {
+        let manager = AIStreamingManager::new(StreamingConfig::default());
+        let operation_id = Uuid::new_v4();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let answer = manager
+            .relay(operation_id, move |content| tx.send(content).unwrap(), async {
+                for (sequence, content) in ["Hel", "lo"].into_iter().enumerate() {
+                    let chunk = AIStreamingManager::create_chunk(
+                        operation_id,
+                        sequence,
+                        content.to_string(),
+                        false,
+                    );
+                    manager.send_chunk(chunk).await.unwrap();
+                }
+                "Hello"
+            })
+            .await;
+
+        assert_eq!(answer, "Hello");
+        assert_eq!(rx.recv().await.as_deref(), Some("Hel"));
+        assert_eq!(rx.recv().await.as_deref(), Some("lo"));
+        assert_eq!(rx.recv().await, None);
+        assert!(manager.get_session(operation_id).await.is_none());
+    }
+
+    #[test]
+    fn test_line_buffer() {
+        let mut buffer = LineBuffer::default();
+        assert!(buffer.push(b"data: {\"a\"").is_empty());
+        assert_eq!(buffer.push(b":1}\r\n\ndata: [DONE]\n"), vec!["data: {\"a\":1}", "data: [DONE]"]);
+        let euro = "€".as_bytes();
+        assert!(buffer.push(&euro[..1]).is_empty());
+        buffer.push(&euro[1..]);
+        assert_eq!(buffer.finish().as_deref(), Some("€"));
+        assert_eq!(sse_data("data: [DONE]"), Some("[DONE]"));
+        assert_eq!(sse_data("event: ping"), None);
+    }
+
     #[tokio::test]
     async fn test_streaming_statistics() {
         let config = StreamingConfig::default();
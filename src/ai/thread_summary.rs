@@ -0,0 +1,297 @@
+//! Summaries of whole email threads
+//!
+//! A thread is summarized in one request when it fits the provider's context.
+//! Longer threads are split into chunks that each get a partial summary, and
+//! the partial summaries are combined, chunking again if they still do not
+//! fit, until a single bullet list is left.
+
+use serde::{Deserialize, Serialize};
+
+/// Rough characters per token, to size chunks without a tokenizer
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Smallest chunk worth a request, whatever the provider reports
+const MIN_CHUNK_CHARS: usize = 2_000;
+
+/// Separator between messages within a chunk
+const MESSAGE_SEPARATOR: &str = "\n\n---\n\n";
+
+/// Levels of summaries of summaries before giving up
+pub const MAX_SUMMARY_LEVELS: usize = 4;
+
+/// Bullet summary of an email thread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadSummary {
+    /// Concise summary points, oldest developments first
+    pub bullets: Vec<String>,
+    /// Messages the summary covers
+    pub message_count: usize,
+    /// Chunks the thread was split into; 1 when it fit in one request
+    pub chunk_count: usize,
+}
+
+/// Characters of thread text per request for a provider context length in tokens
+///
+/// Half the context is left for the instructions and the answer.
+pub fn chunk_budget(max_context_length: usize) -> usize {
+    (max_context_length.saturating_mul(CHARS_PER_TOKEN) / 2).max(MIN_CHUNK_CHARS)
+}
+
+/// Pack texts into chunks of at most `budget` characters, in order
+///
+/// A text longer than the budget is split at line breaks where possible.
+pub fn chunk_texts(texts: &[String], budget: usize) -> Vec<String> {
+    let budget = budget.max(1);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for piece in texts.iter().flat_map(|text| split_text(text, budget)) {
+        let separator = if current.is_empty() { "" } else { MESSAGE_SEPARATOR };
+        if !current.is_empty()
+            && current.chars().count() + separator.len() + piece.chars().count() > budget
+        {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str(MESSAGE_SEPARATOR);
+        }
+        current.push_str(&piece);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Split a text into pieces of at most `budget` characters
+fn split_text(text: &str, budget: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while rest.chars().count() > budget {
+        let limit = rest
+            .char_indices()
+            .nth(budget)
+            .map_or(rest.len(), |(index, _)| index);
+        let cut = match rest[..limit].rfind('\n') {
+            Some(newline) if newline > limit / 2 => newline + 1,
+            _ => limit,
+        };
+        pieces.push(rest[..cut].to_string());
+        rest = &rest[cut..];
+    }
+    if !rest.trim().is_empty() || pieces.is_empty() {
+        pieces.push(rest.to_string());
+    }
+    pieces
+}
+
+/// Prompt summarizing a thread that fits in one request
+pub fn thread_prompt(transcript: &str) -> String {
+    format!(
+        "Summarize this email thread as 3 to 7 short bullet points. Cover the decisions \
+         made, open questions and who still needs to do what. Start each point with \"- \" \
+         and write nothing else.\n\n{}",
+        transcript
+    )
+}
+
+/// Prompt for a partial summary of one chunk of a long thread
+pub fn chunk_prompt(part: usize, parts: usize, transcript: &str) -> String {
+    format!(
+        "This is part {} of {} of a long email thread. Summarize it as short bullet points \
+         with the facts, decisions, questions and requests in it, naming who said what. \
+         Start each point with \"- \" and write nothing else.\n\n{}",
+        part, parts, transcript
+    )
+}
+
+/// Prompt combining partial summaries into the final summary
+pub fn combine_prompt(partial_summaries: &str) -> String {
+    format!(
+        "These are summaries of consecutive parts of one email thread. Merge them into 3 to \
+         7 short bullet points covering the decisions made, open questions and who still \
+         needs to do what. Start each point with \"- \" and write nothing else.\n\n{}",
+        partial_summaries
+    )
+}
+
+/// Bullet points of a model response, without their markers
+pub fn parse_bullets(response: &str) -> Vec<String> {
+    response
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let line = line
+                .trim_start_matches(['-', '*', '•'])
+                .trim_start();
+            let numbered = line
+                .split_once(['.', ')'])
+                .filter(|(number, _)| {
+                    !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+                });
+            match numbered {
+                Some((_, rest)) => rest.trim_start(),
+                None => line,
+            }
+        })
+        // Headings such as "Summary:" are not points
+        .filter(|line| !line.is_empty() && !line.ends_with(':'))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::cache::AIResponseCache;
+    use crate::ai::config::{AIConfig, AIProviderType};
+    use crate::ai::provider::{AIProvider, AIProviderManager, ProviderCapabilities};
+    use crate::ai::service::{AIService, EmailCategory, SchedulingIntent};
+    use crate::ai::{AIContext, AIResult};
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::sync::RwLock;
+
+    /// Answers every prompt with one bullet and records the prompts
+    struct RecordingProvider {
+        capabilities: ProviderCapabilities,
+        prompts: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl AIProvider for RecordingProvider {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn capabilities(&self) -> &ProviderCapabilities {
+            &self.capabilities
+        }
+
+        async fn health_check(&self) -> AIResult<bool> {
+            Ok(true)
+        }
+
+        async fn complete_text(&self, prompt: &str, _context: Option<&AIContext>) -> AIResult<String> {
+            let mut prompts = self.prompts.lock().unwrap();
+            prompts.push(prompt.to_string());
+            Ok(format!("Summary:\n- point {}", prompts.len()))
+        }
+
+        async fn summarize_content(&self, _content: &str, _max_length: Option<usize>) -> AIResult<String> {
+            unimplemented!()
+        }
+
+        async fn suggest_reply(&self, _email_content: &str, _context: &str) -> AIResult<Vec<String>> {
+            unimplemented!()
+        }
+
+        async fn parse_schedule_request(&self, _text: &str) -> AIResult<SchedulingIntent> {
+            unimplemented!()
+        }
+
+        async fn categorize_email(&self, _content: &str) -> AIResult<EmailCategory> {
+            unimplemented!()
+        }
+
+        async fn compose_email(&self, _prompt: &str, _context: Option<&str>) -> AIResult<String> {
+            unimplemented!()
+        }
+
+        async fn extract_key_info(&self, _content: &str) -> AIResult<Vec<String>> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_chunking_and_bullets() {
+        let texts = vec!["a".repeat(40), "b".repeat(40), "c".repeat(150)];
+        let chunks = chunk_texts(&texts, 100);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].contains("---"));
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 100));
+        assert_eq!(chunks.concat().matches('c').count(), 150);
+
+        assert_eq!(
+            parse_bullets("Summary:\n- Ana approved the budget\n* Ben owes slides\n2. Launch moved\n\n• Ask legal"),
+            vec!["Ana approved the budget", "Ben owes slides", "Launch moved", "Ask legal"]
+        );
+        assert_eq!(chunk_budget(200_000), 400_000);
+        assert_eq!(chunk_budget(100), MIN_CHUNK_CHARS);
+    }
+
+    #[tokio::test]
+    async fn test_long_threads_are_summarized_hierarchically() {
+        let config = Arc::new(RwLock::new(AIConfig {
+            enabled: true,
+            provider: AIProviderType::Ollama,
+            ..Default::default()
+        }));
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+        let mut provider_manager = AIProviderManager::new(config.clone());
+        provider_manager.register_provider(
+            AIProviderType::Ollama,
+            Box::new(RecordingProvider {
+                capabilities: ProviderCapabilities {
+                    name: "recording".to_string(),
+                    text_completion: true,
+                    summarization: true,
+                    email_replies: false,
+                    scheduling: false,
+                    categorization: false,
+                    // 2,000 characters per chunk
+                    max_context_length: 1_000,
+                    streaming: false,
+                    local_processing: true,
+                    available_models: Vec::new(),
+                },
+                prompts: recorded.clone(),
+            }),
+        );
+        let service = AIService::new(
+            Arc::new(RwLock::new(provider_manager)),
+            Arc::new(AIResponseCache::new(100, Duration::from_secs(60))),
+            config,
+        );
+
+        // Fifty messages of about 300 characters need several chunks
+        let messages: Vec<String> = (0..50)
+            .map(|i| format!("From: person{}@example.com\n\n{}", i, "word ".repeat(60)))
+            .collect();
+        let summary = service.summarize_thread(&messages, None).await.unwrap();
+        assert_eq!(summary.message_count, 50);
+        assert!(summary.chunk_count > 1);
+
+        let prompts = recorded.lock().unwrap().clone();
+        assert_eq!(prompts.len(), summary.chunk_count + 1);
+        assert!(prompts[0].starts_with("This is part 1 of"));
+        assert!(prompts.last().unwrap().starts_with("These are summaries"));
+        assert_eq!(summary.bullets, vec![format!("point {}", prompts.len())]);
+
+        // A short thread takes one request, and a repeat comes from the cache
+        let short = vec!["From: ana@example.com\n\nShip it.".to_string()];
+        let summary = service.summarize_thread(&short, None).await.unwrap();
+        assert_eq!(summary.chunk_count, 1);
+        let cached = service.summarize_thread(&short, None).await.unwrap();
+        assert_eq!(cached.bullets, summary.bullets);
+        assert_eq!(recorded.lock().unwrap().len(), prompts.len() + 1);
+
+        // Streaming passes the answer on as it arrives
+        let other = vec!["From: bo@example.com\n\nHold it.".to_string()];
+        let operation_id = uuid::Uuid::new_v4();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let streamed = service
+            .streaming()
+            .relay(
+                operation_id,
+                move |content| tx.send(content).unwrap(),
+                service.summarize_thread(&other, Some(operation_id)),
+            )
+            .await
+            .unwrap();
+        let partial = rx.recv().await.unwrap();
+        assert_eq!(parse_bullets(&partial), streamed.bullets);
+    }
+}
@@ -37,7 +37,12 @@ pub struct App {
     imap_manager: Option<Arc<ImapAccountManager>>,
     token_manager: Option<TokenManager>,
     token_refresh_scheduler: Option<crate::oauth2::token::TokenRefreshScheduler>,
+    token_refresh_events: Option<mpsc::UnboundedReceiver<crate::oauth2::TokenRefreshEvent>>,
+    /// Accounts whose access token is being refreshed in the background
+    refreshing_credentials: std::collections::HashSet<String>,
     smtp_service: Option<SmtpService>,
+    /// Plugins whose compose-time hooks rewrite outgoing mail
+    plugin_manager: Option<crate::plugins::PluginManager>,
     contacts_manager: Option<Arc<ContactsManager>>,
     calendar_manager: Option<Arc<CalendarManager>>,
     unified_notification_manager: Option<Arc<UnifiedNotificationManager>>,
@@ -52,6 +57,23 @@ pub struct App {
     background_processor: Option<Arc<BackgroundProcessor>>,
     sync_progress_rx: Option<mpsc::UnboundedReceiver<SyncProgress>>,
     task_completion_rx: Option<mpsc::UnboundedReceiver<TaskResult>>,
+    // Background prefetch of likely-next folders and the tasks still queued for it
+    prefetch_config: crate::performance::PrefetchConfig,
+    prefetch_tasks: Vec<Uuid>,
+    // Last look for snoozed messages that are due, including ones due while closed
+    last_snooze_check: Option<Instant>,
+    // Undo-send window and the message waiting in it
+    undo_send_config: crate::smtp::outbox::UndoSendConfig,
+    pending_send: Option<crate::smtp::outbox::PendingSend>,
+    // Last look for scheduled sends that are due, and the drafts being sent now
+    last_scheduled_send_check: Option<Instant>,
+    scheduled_sends_in_flight: std::collections::HashSet<String>,
+    // Last look for event reminders that are due, and the last one that went off
+    last_reminder_check: Option<Instant>,
+    last_reminder: Option<crate::calendar::DueReminder>,
+    // SMS through KDE Connect when enabled in mobile.toml, and the last SMS notified about
+    sms: Option<crate::mobile::SmsSession>,
+    last_sms: Option<crate::mobile::SmsMessage>,
     // Sync engine for email operations
     sync_engine: Option<Arc<crate::email::sync_engine::SyncEngine>>,
     // Email operations service
@@ -60,6 +82,62 @@ pub struct App {
     ai_config_manager: Option<Arc<crate::ai::config_manager::AIConfigManager>>,
     // Startup progress manager
     startup_progress_manager: StartupProgressManager,
+    // Session restore
+    session_config: crate::ui::SessionConfig,
+    session_restore_pending: bool,
+    // Account and folder to open instead of the saved session
+    startup_location: Option<(Option<String>, Option<String>)>,
+    // Sender blocklist and allowlist
+    sender_lists: crate::email::SenderLists,
+    // Filter rules run on incoming mail
+    filter_rules: crate::email::FilterRules,
+    // Startup clock skew check
+    clock_check_config: crate::startup::ClockCheckConfig,
+    clock_check: Option<tokio::task::JoinHandle<Result<crate::startup::ClockSkew>>>,
+    clock_skew: Option<crate::startup::ClockSkew>,
+    // Optional today panel
+    today_panel_config: crate::ui::today_panel::TodayPanelConfig,
+    // Per-account IMAP capabilities to ignore
+    capability_overrides: crate::imap::CapabilityOverrides,
+    // Per-account Sent/Drafts/Trash/Junk/Archive folders overriding detection
+    special_folders: crate::email::SpecialFolderConfig,
+    // Server copies of saved drafts by draft ID: account, folder and UID
+    filed_drafts: std::collections::HashMap<String, (String, String, u32)>,
+    // Folder retention rules and the background pass applying them
+    retention_config: crate::email::RetentionConfig,
+    retention_run: Option<
+        tokio::task::JoinHandle<crate::email::EmailOperationResult<crate::email::RetentionOutcome>>,
+    >,
+    last_retention_run: Option<Instant>,
+    retention_preview: Option<crate::email::RetentionPlan>,
+    services: crate::services::ServiceManager,
+    weather_fetch: Option<
+        tokio::task::JoinHandle<crate::services::weather::WeatherResult<crate::services::WeatherReport>>,
+    >,
+    last_weather_fetch: Option<Instant>,
+    // Idle auto-lock and the passphrase check running for the lock screen
+    lock_config: crate::ui::lock_screen::LockConfig,
+    last_activity: Instant,
+    unlock_check: Option<tokio::task::JoinHandle<bool>>,
+    // Mark-all-read: the run in progress and what the last one changed
+    mark_read_config: crate::email::MarkReadConfig,
+    mark_read_run: Option<tokio::task::JoinHandle<crate::email::MarkReadOutcome>>,
+    mark_read_progress: Option<tokio::sync::mpsc::UnboundedReceiver<crate::email::MarkReadProgress>>,
+    mark_read_cancel: Arc<std::sync::atomic::AtomicBool>,
+    mark_read_undo: Option<crate::email::MarkReadUndo>,
+    // Attachment download streaming in the background
+    attachment_download_run: Option<tokio::task::JoinHandle<std::io::Result<std::path::PathBuf>>>,
+    attachment_download_progress:
+        Option<tokio::sync::mpsc::UnboundedReceiver<crate::email::DownloadProgress>>,
+    attachment_download_cancel: Arc<std::sync::atomic::AtomicBool>,
+    attachment_download_name: String,
+    // Mbox import or export started from the wizard
+    mbox_run:
+        Option<tokio::task::JoinHandle<crate::email::mbox::MboxResult<crate::email::MboxOutcome>>>,
+    mbox_progress: Option<tokio::sync::mpsc::UnboundedReceiver<crate::email::MboxProgress>>,
+    mbox_cancel: Arc<std::sync::atomic::AtomicBool>,
+    // Account to reload folders for once an import finishes
+    mbox_import_account: Option<String>,
     // Toast integration service (using simple direct approach now)
     // toast_integration_service: Option<crate::ui::toast_integration::ToastIntegrationService>,
 }
@@ -78,7 +156,10 @@ impl App {
             imap_manager: None,
             token_manager: None,
             token_refresh_scheduler: None,
+            token_refresh_events: None,
+            refreshing_credentials: std::collections::HashSet::new(),
             smtp_service: None,
+            plugin_manager: None,
             contacts_manager: None,
             calendar_manager: None,
             unified_notification_manager: None,
@@ -93,6 +174,17 @@ impl App {
             background_processor: None,
             sync_progress_rx: None,
             task_completion_rx: None,
+            prefetch_config: crate::performance::PrefetchConfig::default(),
+            prefetch_tasks: Vec::new(),
+            last_snooze_check: None,
+            undo_send_config: crate::smtp::outbox::UndoSendConfig::default(),
+            pending_send: None,
+            last_scheduled_send_check: None,
+            scheduled_sends_in_flight: std::collections::HashSet::new(),
+            last_reminder_check: None,
+            last_reminder: None,
+            sms: None,
+            last_sms: None,
             // Sync engine
             sync_engine: None,
             // Email operations service
@@ -101,6 +193,46 @@ impl App {
             ai_config_manager: None,
             // Startup progress manager
             startup_progress_manager: StartupProgressManager::new(),
+            // Session restore
+            session_config: crate::ui::SessionConfig::default(),
+            session_restore_pending: true,
+            startup_location: None,
+            // Sender blocklist and allowlist
+            sender_lists: crate::email::SenderLists::default(),
+            // Filter rules run on incoming mail
+            filter_rules: crate::email::FilterRules::default(),
+            // Startup clock skew check
+            clock_check_config: crate::startup::ClockCheckConfig::default(),
+            clock_check: None,
+            clock_skew: None,
+            // Optional today panel
+            today_panel_config: crate::ui::today_panel::TodayPanelConfig::default(),
+            capability_overrides: crate::imap::CapabilityOverrides::default(),
+            special_folders: crate::email::SpecialFolderConfig::default(),
+            filed_drafts: std::collections::HashMap::new(),
+            retention_config: crate::email::RetentionConfig::default(),
+            retention_run: None,
+            last_retention_run: None,
+            retention_preview: None,
+            services: crate::services::ServiceManager::default(),
+            weather_fetch: None,
+            last_weather_fetch: None,
+            lock_config: crate::ui::lock_screen::LockConfig::default(),
+            last_activity: Instant::now(),
+            unlock_check: None,
+            mark_read_config: crate::email::MarkReadConfig::default(),
+            mark_read_run: None,
+            mark_read_progress: None,
+            mark_read_cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            mark_read_undo: None,
+            attachment_download_run: None,
+            attachment_download_progress: None,
+            attachment_download_cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            attachment_download_name: String::new(),
+            mbox_run: None,
+            mbox_progress: None,
+            mbox_cancel: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            mbox_import_account: None,
             // Toast integration service
             // toast_integration_service: None,
         })
@@ -167,11 +299,51 @@ impl App {
 
         // Create sync engine for background operations
         let (sync_progress_tx, _sync_progress_rx) = mpsc::unbounded_channel::<SyncProgress>();
-        let sync_engine = Arc::new(crate::email::sync_engine::SyncEngine::new(
+        let mut sync_engine = crate::email::sync_engine::SyncEngine::new(
             database_arc.clone(),
             sync_progress_tx
-        ));
-        self.sync_engine = Some(sync_engine);
+        );
+        match crate::imap::FetchConfig::load().await {
+            Ok(fetch_config) => sync_engine.set_fetch_config(fetch_config),
+            Err(e) => tracing::warn!("Failed to load IMAP fetch config, using provider defaults: {}", e),
+        }
+        match crate::email::FolderSyncDefaults::load().await {
+            Ok(defaults) => sync_engine.set_folder_sync_defaults(defaults),
+            Err(e) => tracing::warn!("Failed to load folder sync defaults: {}", e),
+        }
+        match crate::email::PlainTextAccounts::load().await {
+            Ok(accounts) => sync_engine.set_plain_text_accounts(accounts),
+            Err(e) => tracing::warn!("Failed to load plain text accounts: {}", e),
+        }
+        match crate::email::PgpConfig::load().await {
+            Ok(config) => sync_engine.set_pgp_keyring(config.keyring_dir),
+            Err(e) => tracing::warn!("Failed to load PGP settings: {}", e),
+        }
+        match crate::email::SenderLists::load().await {
+            Ok(sender_lists) => self.sender_lists = sender_lists,
+            Err(e) => tracing::warn!("Failed to load sender lists: {}", e),
+        }
+        sync_engine.set_sender_lists(self.sender_lists.clone()).await;
+        match crate::email::FilterRules::load().await {
+            Ok(rules) => self.filter_rules = rules,
+            Err(e) => tracing::warn!("Failed to load filter rules: {}", e),
+        }
+        sync_engine
+            .set_filter_rules(self.filter_rules.clone())
+            .await;
+        match crate::email::SpecialFolderConfig::load().await {
+            Ok(special_folders) => self.special_folders = special_folders,
+            Err(e) => tracing::warn!("Failed to load special folder mapping: {}", e),
+        }
+        sync_engine
+            .set_special_folders(self.special_folders.clone())
+            .await;
+        if let Some(plugin_manager) = &self.plugin_manager {
+            sync_engine
+                .set_incoming_email_hooks(plugin_manager.incoming_email_hooks())
+                .await;
+        }
+        self.sync_engine = Some(Arc::new(sync_engine));
         
         // Initialize calendar database and manager
         tracing::info!("📅 Initializing calendar database...");
@@ -230,6 +402,7 @@ impl App {
             ContactsManager::new(contacts_database, token_manager_for_contacts)
                 .await
                 .map_err(|e| anyhow::anyhow!("Failed to create contacts manager: {}", e))?
+                .with_carddav_accounts(crate::contacts::CardDavConfig::load_accounts().await)
         );
         
         self.contacts_manager = Some(contacts_manager.clone());
@@ -240,9 +413,18 @@ impl App {
 
         // Start the notification processing
         notification_manager.start().await;
+        if let Some(ref sync_engine) = self.sync_engine {
+            sync_engine
+                .set_notification_manager(notification_manager.clone())
+                .await;
+        }
 
         // Initialize unified notification manager with desktop notifications
-        let notification_config = NotificationConfig::default();
+        let mut notification_config = NotificationConfig::default();
+        // Allowlisted senders always notify
+        notification_config
+            .vip_senders
+            .extend(self.sender_lists.allowed.iter().cloned());
         let unified_notification_manager = Arc::new(
             UnifiedNotificationManager::new().with_desktop_notifications(notification_config),
         );
@@ -266,6 +448,8 @@ impl App {
         self.notification_manager = Some(notification_manager);
         self.unified_notification_manager = Some(unified_notification_manager);
 
+        self.start_sms().await;
+
         // Complete database phase in progress manager
         if let Err(e) = self.startup_progress_manager.complete_phase("Database") {
             tracing::warn!("Failed to complete Database phase in progress manager: {}", e);
@@ -281,6 +465,8 @@ impl App {
             // Don't fail the entire initialization for AI config issues
             self.ui.show_toast_warning("AI features may not be available - check configuration");
         }
+        self.connect_ai_service().await;
+        self.enable_inbox_triage().await;
         
         tracing::info!("✅ Database initialization completed successfully");
         Ok(())
@@ -384,6 +570,69 @@ impl App {
         }
     }
 
+    /// Hand the configured AI service to the UI's AI actions
+    async fn connect_ai_service(&mut self) {
+        let Some(ai_config_manager) = &self.ai_config_manager else {
+            return;
+        };
+        let Some(ai_service) = ai_config_manager.get_ai_service().await else {
+            return;
+        };
+        self.ui
+            .set_ai_service(ai_service.clone(), ai_config_manager.config_handle());
+
+        if ai_config_manager
+            .get_config()
+            .await
+            .is_feature_enabled("email_suggestions")
+        {
+            let enhanced = match crate::ai::EnhancedAIService::new(
+                ai_service,
+                crate::ai::EnhancedAIConfig::default(),
+            )
+            .await
+            {
+                Ok(enhanced) => enhanced,
+                Err(e) => {
+                    tracing::warn!("Inline completion unavailable: {}", e);
+                    return;
+                }
+            };
+            let config = crate::ai::SmartComposeConfig::default();
+            let delay = std::time::Duration::from_millis(config.trigger_delay_ms);
+            let smart_compose = crate::ai::SmartComposeService::new(Arc::new(enhanced), config);
+            self.ui.set_smart_compose(Arc::new(smart_compose), delay);
+        }
+    }
+
+    /// Split the INBOX into tabs sorted by the AI provider during sync,
+    /// when email categorization is enabled
+    async fn enable_inbox_triage(&mut self) {
+        let Some(ai_config_manager) = &self.ai_config_manager else {
+            return;
+        };
+        if !ai_config_manager
+            .get_config()
+            .await
+            .is_feature_enabled("email_categorization")
+        {
+            return;
+        }
+        let (Some(ai_service), Some(database), Some(sync_engine)) = (
+            ai_config_manager.get_ai_service().await,
+            self.database.clone(),
+            self.sync_engine.clone(),
+        ) else {
+            return;
+        };
+
+        sync_engine
+            .set_inbox_triage(Arc::new(crate::email::InboxTriage::new(ai_service, database)))
+            .await;
+        self.ui.message_list_mut().set_inbox_tabs_enabled(true);
+        tracing::info!("INBOX tabs enabled with AI triage");
+    }
+
     /// Get database reference for maintenance operations
     pub fn get_database(&self) -> Option<&Arc<EmailDatabase>> {
         self.database.as_ref()
@@ -403,8 +652,84 @@ impl App {
 
     /// Set the initial UI mode based on CLI startup arguments
     pub fn set_initial_mode(&mut self, mode: crate::cli::StartupMode) {
+        // An explicit startup mode takes precedence over the saved session
+        if !matches!(mode, crate::cli::StartupMode::Default | crate::cli::StartupMode::Resume) {
+            self.session_restore_pending = false;
+        }
         self.ui.set_initial_mode(mode);
     }
+
+    /// Open the email interface at an account and folder once accounts are loaded
+    ///
+    /// Without an account the current one is used; without a folder the
+    /// folder list gets focus.
+    pub fn set_startup_location(&mut self, account: Option<String>, folder: Option<String>) {
+        if account.is_some() || folder.is_some() {
+            self.startup_location = Some((account, folder));
+        }
+    }
+
+    /// Go to the account and folder chosen at startup
+    async fn open_startup_location(&mut self, account: Option<String>, folder: Option<String>) {
+        let Some(account_id) = account.or_else(|| self.ui.get_current_account_id().cloned()) else {
+            tracing::warn!("No account to open at startup");
+            return;
+        };
+        let state = crate::ui::SessionState {
+            account_id: Some(account_id.clone()),
+            focused_pane: if folder.is_some() {
+                crate::ui::SessionPane::MessageList
+            } else {
+                crate::ui::SessionPane::FolderTree
+            },
+            folder_path: folder,
+            ..Default::default()
+        };
+        if !self.ui.restore_session_state(&state).await {
+            self.ui
+                .show_toast_warning(format!("Startup account {} not found", account_id));
+        }
+    }
+
+    /// Restore the navigation state saved by the previous session, if enabled
+    async fn restore_session(&mut self) {
+        if let Some((account, folder)) = self.startup_location.take() {
+            self.session_restore_pending = false;
+            self.open_startup_location(account, folder).await;
+            return;
+        }
+        if !self.session_restore_pending {
+            return;
+        }
+        self.session_restore_pending = false;
+
+        if !self.session_config.restore_session {
+            tracing::debug!("Session restore disabled");
+            return;
+        }
+
+        match crate::ui::SessionState::load().await {
+            Ok(Some(state)) => {
+                if self.ui.restore_session_state(&state).await {
+                    tracing::info!("Restored previous session");
+                }
+            }
+            Ok(None) => tracing::debug!("No saved session to restore"),
+            Err(e) => tracing::warn!("Failed to load saved session: {}", e),
+        }
+    }
+
+    /// Persist the current navigation state for the next launch
+    async fn save_session(&self) {
+        if !self.session_config.restore_session {
+            return;
+        }
+
+        let state = self.ui.capture_session_state();
+        if let Err(e) = state.save().await {
+            tracing::warn!("Failed to save session state: {}", e);
+        }
+    }
     
     /// Refresh calendar data from database and update UI
     pub async fn refresh_calendar_data(&mut self) -> Result<()> {
@@ -436,6 +761,13 @@ impl App {
             let events_count = events.len();
             self.ui.set_calendars(calendars);
             self.ui.set_calendar_events(events);
+
+            // Account addresses tell the calendar which events the user organizes
+            if let Ok(configs) = self.storage.load_all_accounts() {
+                self.ui.calendar_ui_mut().set_user_emails(
+                    configs.into_iter().map(|config| config.email_address).collect(),
+                );
+            }
             
             tracing::info!("✅ Loaded {} calendars and {} events into UI", calendars_count, events_count);
         } else {
@@ -582,7 +914,17 @@ impl App {
                 },
                 Ok(Err(e)) => {
                     tracing::warn!("⚠️ Background IMAP initialization failed: {}", e);
-                    self.ui.show_toast_warning("Email connection failed - check account settings");
+                    // A wrong clock is a common hidden cause of OAuth2 and TLS failures
+                    self.poll_clock_check().await;
+                    match self.significant_clock_skew() {
+                        Some(skew) => self.ui.show_toast_warning(format!(
+                            "Email connection failed - your clock is off by {}, fix it and retry",
+                            skew.magnitude()
+                        )),
+                        None => self
+                            .ui
+                            .show_toast_warning("Email connection failed - check account settings"),
+                    }
                 },
                 Err(_) => {
                     tracing::warn!("⏱️ Background IMAP initialization timed out");
@@ -603,6 +945,16 @@ impl App {
             }
         }
 
+        // Without a keyring the token files are only base64-encoded
+        use crate::oauth2::CredentialBackend;
+        if self.storage.backend_status().backend == CredentialBackend::File
+            && !CredentialBackend::System.is_available()
+        {
+            self.ui.show_toast_warning(
+                "Tokens are stored unencrypted - run 'comunicado credentials encrypt'",
+            );
+        }
+
         Ok(())
     }
 
@@ -760,6 +1112,29 @@ impl App {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to load IMAP accounts: {}", e))?;
 
+        // Apply capabilities the user switched off for misbehaving servers
+        match crate::imap::CapabilityOverrides::load().await {
+            Ok(overrides) => self.capability_overrides = overrides,
+            Err(e) => tracing::warn!("Failed to load IMAP capability overrides: {}", e),
+        }
+        for (account_id, disabled) in &self.capability_overrides.accounts {
+            if let Err(e) = imap_manager
+                .set_disabled_capabilities(account_id, disabled.clone())
+                .await
+            {
+                tracing::warn!("Ignoring capability overrides for {}: {}", account_id, e);
+            }
+        }
+
+        match crate::jmap::JmapSettings::load().await {
+            Ok(settings) => imap_manager.set_jmap_settings(settings).await,
+            Err(e) => tracing::warn!("Failed to load JMAP settings: {}", e),
+        }
+        match crate::email::PlainTextAccounts::load().await {
+            Ok(accounts) => imap_manager.set_plain_text_accounts(accounts).await,
+            Err(e) => tracing::warn!("Failed to load plain text accounts: {}", e),
+        }
+
         // Load OAuth2 tokens for all existing accounts into the TokenManager
         tracing::debug!("About to load tokens into manager");
         let load_result = self.load_tokens_into_manager(&token_manager).await?;
@@ -802,15 +1177,21 @@ impl App {
             }
         };
 
-        // Skip token refresh scheduler during startup to prevent blocking
-        // Token refresh will be handled on-demand when needed
-        tracing::info!("Skipping token refresh scheduler during startup for faster initialization");
-        self.token_refresh_scheduler = None;
-        tracing::debug!("Token refresh scheduler setup complete");
-
         // Store token manager for later token refresh operations
         let token_manager_arc = Arc::new(token_manager);
 
+        // Refresh tokens shortly before they expire rather than when a sync
+        // needs one; the first check waits a minute, so startup isn't slowed
+        let (refresh_tx, refresh_rx) = mpsc::unbounded_channel();
+        let scheduler = crate::oauth2::TokenRefreshScheduler::new(token_manager_arc.clone())
+            .with_events(refresh_tx);
+        if let Err(e) = scheduler.start().await {
+            tracing::warn!("Failed to start token refresh scheduler: {}", e);
+        }
+        self.token_refresh_scheduler = Some(scheduler);
+        self.token_refresh_events = Some(refresh_rx);
+        tracing::debug!("Token refresh scheduler setup complete");
+
         // Set IMAP manager in UI for attachment downloading functionality
         let imap_manager_arc = Arc::new(imap_manager);
         self.ui
@@ -874,6 +1255,9 @@ impl App {
         self.sync_progress_rx = Some(progress_rx);
         self.task_completion_rx = Some(completion_rx);
 
+        // The preview looks up prefetched messages before the database
+        self.ui.content_preview_mut().set_message_cache(processor.cache_manager());
+
         // Set background processor on enhanced progress overlay for task cancellation
         self.ui.enhanced_progress_overlay_mut().set_background_processor(processor);
 
@@ -919,11 +1303,80 @@ impl App {
         }
 
         // Process task completion updates
+        let mut snoozes_woke = false;
+        let mut scheduled_sent = Vec::new();
+        let mut reminders = Vec::new();
         if let Some(ref mut completion_rx) = self.task_completion_rx {
             while let Ok(result) = completion_rx.try_recv() {
                 // Handle task completion
                 tracing::debug!("Background task completed: {:?}", result.status);
+
+                // Prefetching is invisible: no progress items or toasts
+                if let crate::performance::background_processor::BackgroundTaskType::CachePreload { ref folder_name, .. } = result.task_type {
+                    self.prefetch_tasks.retain(|id| *id != result.task_id);
+                    match result.result_data {
+                        Some(crate::performance::background_processor::TaskResultData::CacheStats(count)) => {
+                            if let Some(ref processor) = self.background_processor {
+                                let stats = processor.cache_manager().message_cache().get_stats().await;
+                                tracing::debug!(
+                                    "Prefetched {} messages from {}; message cache hit rate {:.0}%",
+                                    count,
+                                    folder_name,
+                                    stats.hit_rate() * 100.0
+                                );
+                            }
+                        }
+                        _ => tracing::debug!("Prefetch of {} ended: {:?}", folder_name, result.status),
+                    }
+                    continue;
+                }
+
+                // Waking snoozed messages is quiet too, unless some came back
+                if let crate::performance::background_processor::BackgroundTaskType::SnoozeWake = result.task_type {
+                    match result.result_data {
+                        Some(crate::performance::background_processor::TaskResultData::MessageCount(count)) if count > 0 => {
+                            self.ui.show_toast_info(if count == 1 {
+                                "1 snoozed message is back in INBOX".to_string()
+                            } else {
+                                format!("{} snoozed messages are back in INBOX", count)
+                            });
+                            snoozes_woke = true;
+                        }
+                        Some(_) => {}
+                        None => tracing::warn!("Waking snoozed messages of {} failed: {:?}", result.account_id, result.error),
+                    }
+                    continue;
+                }
+
+                // A scheduled send only shows whether it went out
+                if let crate::performance::background_processor::BackgroundTaskType::ScheduledSend { ref draft_id, .. } = result.task_type {
+                    self.scheduled_sends_in_flight.remove(draft_id);
+                    match result.result_data {
+                        Some(crate::performance::background_processor::TaskResultData::ScheduledSend(Some(sent))) => {
+                            self.ui.show_toast_success("Scheduled message sent".to_string());
+                            scheduled_sent.push((result.account_id.clone(), sent));
+                        }
+                        Some(_) => {}
+                        None => self.ui.show_toast_warning(format!(
+                            "Scheduled send failed: {}",
+                            result.error.unwrap_or_default()
+                        )),
+                    }
+                    continue;
+                }
                 
+                // Reminders notify on the desktop as well as in a toast
+                if let crate::performance::background_processor::BackgroundTaskType::CalendarReminders = result.task_type {
+                    match result.result_data {
+                        Some(crate::performance::background_processor::TaskResultData::CalendarReminders(due)) => {
+                            reminders.extend(due);
+                        }
+                        Some(_) => {}
+                        None => tracing::warn!("Checking event reminders failed: {:?}", result.error),
+                    }
+                    continue;
+                }
+
                 // Update UI account status for successful account sync tasks
                 if let crate::performance::background_processor::BackgroundTaskType::AccountSync { .. } = result.task_type {
                     match result.status {
@@ -961,6 +1414,15 @@ impl App {
                 );
             }
         }
+        if snoozes_woke {
+            self.refresh_after_snooze_change().await;
+        }
+        for (account_id, sent) in scheduled_sent {
+            self.file_sent_copy(&account_id, &sent).await;
+        }
+        for reminder in reminders {
+            self.notify_reminder(reminder);
+        }
         
         // Process AI operation results
         self.ui.process_ai_results();
@@ -1256,6 +1718,10 @@ impl App {
             account_id,
             folder_name
         );
+        // JMAP accounts sync every mailbox from one change log
+        if self.sync_account_from_jmap(account_id).await? {
+            return Ok(());
+        }
         let imap_manager = self
             .imap_manager
             .as_ref()
@@ -1266,6 +1732,12 @@ impl App {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
 
+        // Gmail label views show messages stored in All Mail
+        let layout = database.get_label_layout(account_id).await?;
+        let folder_name = layout
+            .as_ref()
+            .map_or(folder_name, |layout| layout.storage_folder(folder_name));
+
         tracing::info!(
             "Starting message fetch for account: {}, folder: {}",
             account_id,
@@ -1306,7 +1778,10 @@ impl App {
 
             if message_count > 0 {
                 let sequence_set = format!("1:{message_count}");
-                let fetch_items = vec!["UID", "FLAGS", "ENVELOPE", "BODY.PEEK[]", "BODYSTRUCTURE"];
+                let mut fetch_items = vec!["UID", "FLAGS", "ENVELOPE", "BODY.PEEK[]", "BODYSTRUCTURE"];
+                if client.supports_gmail_labels() {
+                    fetch_items.push("X-GM-LABELS");
+                }
 
                 let messages = client
                     .fetch_messages(&sequence_set, &fetch_items)
@@ -1364,6 +1839,10 @@ impl App {
     /// Sync account data from IMAP (folders and messages)
     async fn sync_account_from_imap(&mut self, account_id: &str) -> Result<()> {
         tracing::debug!("sync_account_from_imap called for: {}", account_id);
+
+        if self.sync_account_from_jmap(account_id).await? {
+            return Ok(());
+        }
         tracing::info!("Starting IMAP sync for account: {}", account_id);
 
         // First sync folders
@@ -1383,6 +1862,21 @@ impl App {
             .map(|row| row.get::<String, _>("name"))
             .collect();
 
+        // Gmail label views are read from All Mail rather than fetched
+        let folders: Vec<String> = match database.get_label_layout(account_id).await? {
+            Some(layout) => {
+                let mut folders: Vec<String> = folders
+                    .into_iter()
+                    .filter(|folder| layout.label_for(folder).is_none())
+                    .collect();
+                if !folders.contains(&layout.all_mail) {
+                    folders.push(layout.all_mail.clone());
+                }
+                folders
+            }
+            None => folders,
+        };
+
         // Define important folders that should always be synced (including Gmail-specific folders)
         let important_folders = [
             "INBOX", "Sent", "Drafts", "Trash", "Spam", "Junk", "Sent Items", "Sent Mail",
@@ -1445,6 +1939,35 @@ impl App {
         Ok(())
     }
 
+    /// Sync an account over JMAP when its provider offers it
+    ///
+    /// Returns false, without syncing, for accounts that use IMAP.
+    async fn sync_account_from_jmap(&mut self, account_id: &str) -> Result<bool> {
+        let (Some(imap_manager), Some(sync_engine)) = (&self.imap_manager, &self.sync_engine) else {
+            return Ok(false);
+        };
+        let Some(jmap) = imap_manager.get_jmap_client(account_id).await else {
+            return Ok(false);
+        };
+        tracing::info!("Starting JMAP sync for account: {}", account_id);
+
+        let outcome = {
+            let mut client = jmap.lock().await;
+            sync_engine
+                .sync_account_jmap(account_id.to_string(), &mut client)
+                .await
+                .map_err(|e| anyhow::anyhow!("JMAP sync failed: {}", e))?
+        };
+        self.store_folders(account_id, &outcome.folders).await?;
+
+        tracing::info!(
+            "Completed JMAP sync for account: {} ({} folders)",
+            account_id,
+            outcome.folders.len()
+        );
+        Ok(true)
+    }
+
     /// Sync folders from IMAP and store in database
     async fn sync_folders_from_imap(&mut self, account_id: &str) -> Result<()> {
         tracing::debug!("sync_folders_from_imap called for: {}", account_id);
@@ -1453,13 +1976,20 @@ impl App {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("IMAP manager not initialized"))?;
 
-        let database = self
-            .database
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
-
         tracing::info!("Syncing folders for account: {}", account_id);
 
+        if let Some(jmap) = imap_manager.get_jmap_client(account_id).await {
+            let folders = jmap
+                .lock()
+                .await
+                .list_folders()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to list mailboxes: {}", e))?;
+            self.store_folders(account_id, &folders).await?;
+            tracing::info!("Successfully synced {} JMAP mailboxes for account {}", folders.len(), account_id);
+            return Ok(());
+        }
+
         // Get IMAP client with timeout to prevent hanging on expired tokens
         tracing::debug!(
             "About to call imap_manager.get_client() for: {}",
@@ -1503,32 +2033,15 @@ impl App {
 
             let folder_count = folders.len();
             tracing::info!("Found {} folders from IMAP", folder_count);
+            let layout = client
+                .supports_gmail_labels()
+                .then(|| crate::email::LabelLayout::detect(&folders))
+                .flatten();
+            drop(client);
 
-            // Delete existing folders for this account to refresh the list
-            sqlx::query("DELETE FROM folders WHERE account_id = ?")
-                .bind(account_id)
-                .execute(&database.pool)
-                .await?;
-
-            // Store each folder in database
-            tracing::debug!("About to store {} folders in database", folders.len());
-            for folder in folders {
-                tracing::debug!("Storing folder: {} ({})", folder.name, folder.full_name);
-                tracing::debug!("Storing folder: {} ({})", folder.name, folder.full_name);
-
-                let attributes_json =
-                    serde_json::to_string(&folder.attributes).unwrap_or_else(|_| "[]".to_string());
-
-                sqlx::query("INSERT INTO folders (account_id, name, full_name, delimiter, attributes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)")
-                    .bind(account_id)
-                    .bind(&folder.name)
-                    .bind(&folder.full_name)
-                    .bind(folder.delimiter.as_deref().unwrap_or("."))
-                    .bind(&attributes_json)
-                    .bind(chrono::Utc::now().to_rfc3339())
-                    .bind(chrono::Utc::now().to_rfc3339())
-                    .execute(&database.pool)
-                    .await?;
+            self.store_folders(account_id, &folders).await?;
+            if let Some(database) = &self.database {
+                database.set_label_layout(account_id, layout.as_ref()).await?;
             }
 
             tracing::info!(
@@ -1541,6 +2054,41 @@ impl App {
         Ok(())
     }
 
+    /// Replace the stored folder list of an account
+    async fn store_folders(&self, account_id: &str, folders: &[crate::imap::ImapFolder]) -> Result<()> {
+        let database = self
+            .database
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
+
+        // Delete existing folders for this account to refresh the list
+        sqlx::query("DELETE FROM folders WHERE account_id = ?")
+            .bind(account_id)
+            .execute(&database.pool)
+            .await?;
+
+        // Store each folder in database
+        tracing::debug!("About to store {} folders in database", folders.len());
+        for folder in folders {
+            tracing::debug!("Storing folder: {} ({})", folder.name, folder.full_name);
+
+            let attributes_json =
+                serde_json::to_string(&folder.attributes).unwrap_or_else(|_| "[]".to_string());
+
+            sqlx::query("INSERT INTO folders (account_id, name, full_name, delimiter, attributes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)")
+                .bind(account_id)
+                .bind(&folder.name)
+                .bind(&folder.full_name)
+                .bind(folder.delimiter.as_deref().unwrap_or("."))
+                .bind(&attributes_json)
+                .bind(chrono::Utc::now().to_rfc3339())
+                .bind(chrono::Utc::now().to_rfc3339())
+                .execute(&database.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         tracing::info!("🚀 Starting Comunicado...");
         
@@ -1571,9 +2119,186 @@ impl App {
         let mut terminal = Terminal::new(backend)
             .map_err(|e| anyhow::anyhow!("Failed to create terminal: {}", e))?;
 
+        // Load session restore settings
+        match crate::ui::SessionConfig::load().await {
+            Ok(config) => self.session_config = config,
+            Err(e) => tracing::warn!("Failed to load session config, using defaults: {}", e),
+        }
+
+        // Conversation behaviour of the threaded message list
+        match crate::ui::thread_behavior::ThreadBehaviorConfig::load().await {
+            Ok(config) => self.ui.message_list_mut().set_thread_behavior(config),
+            Err(e) => tracing::warn!("Failed to load thread settings, using defaults: {}", e),
+        }
+
+        // Signature and disclaimer stripping for reply quotes and AI input
+        match crate::email::SignatureStripConfig::load().await {
+            Ok(config) => {
+                self.ui
+                    .set_signature_stripper(crate::email::SignatureStripper::new(&config))
+                    .await
+            }
+            Err(e) => tracing::warn!("Failed to load signature settings, using defaults: {}", e),
+        }
+
+        // Quote style for replies and forwards
+        match crate::email::QuoteConfig::load().await {
+            Ok(config) => self.ui.set_quote_config(config),
+            Err(e) => tracing::warn!("Failed to load quoting settings, using defaults: {}", e),
+        }
+
+        // Line wrapping while composing
+        match crate::ui::compose_wrap::ComposeWrapConfig::load().await {
+            Ok(config) => self.ui.set_compose_wrap_config(config),
+            Err(e) => tracing::warn!("Failed to load compose wrap settings, using defaults: {}", e),
+        }
+
+        // Duplicate message collapsing
+        match crate::email::DedupConfig::load().await {
+            Ok(config) => self.ui.message_list_mut().set_dedup_config(config),
+            Err(e) => tracing::warn!("Failed to load dedup settings, using defaults: {}", e),
+        }
+
+        // Reply targets for mailing-list mail
+        match crate::email::ReplyConfig::load().await {
+            Ok(config) => self.ui.set_reply_config(config),
+            Err(e) => tracing::warn!("Failed to load reply settings, using defaults: {}", e),
+        }
+
+        // Size limit for rendering message bodies in the preview
+        match crate::ui::preview_limits::PreviewLimitsConfig::load().await {
+            Ok(config) => self.ui.content_preview_mut().set_preview_limits(config),
+            Err(e) => tracing::warn!("Failed to load preview limits, using defaults: {}", e),
+        }
+
+        // Background prefetch of the folders likely to be opened next
+        match crate::performance::PrefetchConfig::load().await {
+            Ok(config) => self.prefetch_config = config,
+            Err(e) => tracing::warn!("Failed to load prefetch settings, using defaults: {}", e),
+        }
+
+        // Remote content policy for opening messages in the browser
+        match crate::ui::browser_view::BrowserViewConfig::load().await {
+            Ok(config) => self.ui.content_preview_mut().set_browser_view_config(config),
+            Err(e) => tracing::warn!("Failed to load browser view settings, using defaults: {}", e),
+        }
+
+        // Handlers for links and attachments
+        match crate::ui::opener::OpenerConfig::load().await {
+            Ok(config) => {
+                for problem in config.validate() {
+                    tracing::warn!("opener.toml: {}", problem);
+                    self.ui.show_toast_warning(format!("opener.toml: {}", problem));
+                }
+                self.ui.content_preview_mut().set_opener_config(config);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load link handlers, using system defaults: {}", e);
+                self.ui.show_toast_warning(format!("opener.toml ignored: {}", e));
+            }
+        }
+
+        // Load attachment save-to settings
+        match crate::email::AttachmentSaveConfig::load().await {
+            Ok(config) => self.ui.content_preview_mut().set_attachment_save_config(config),
+            Err(e) => tracing::warn!("Failed to load attachment config, using defaults: {}", e),
+        }
+
+        // Check the system clock in the background while accounts initialize
+        match crate::startup::ClockCheckConfig::load().await {
+            Ok(config) => self.clock_check_config = config,
+            Err(e) => tracing::warn!("Failed to load clock check config, using defaults: {}", e),
+        }
+        if self.clock_check_config.enabled {
+            let config = self.clock_check_config.clone();
+            self.clock_check = Some(tokio::spawn(async move {
+                crate::startup::clock_check::measure_clock_skew(&config).await
+            }));
+        }
+
+        // Retention rules run later, once accounts and the operations service are up
+        match crate::email::RetentionConfig::load().await {
+            Ok(config) => self.retention_config = config,
+            Err(e) => tracing::warn!("Failed to load retention config, rules disabled: {}", e),
+        }
+
+        // Sender avatars in the email viewer and contacts popup
+        match crate::contacts::AvatarConfig::load().await {
+            Ok(config) => self.ui.set_avatar_config(config),
+            Err(e) => {
+                tracing::warn!("Failed to load avatar config, using defaults: {}", e);
+                self.ui.set_avatar_config(crate::contacts::AvatarConfig::default());
+            }
+        }
+
+        // Animated GIF playback in the viewer and preview pane
+        match crate::ui::AnimationConfig::load().await {
+            Ok(config) => self.ui.set_animation_config(config),
+            Err(e) => tracing::warn!("Failed to load animation config, using defaults: {}", e),
+        }
+
+        // Plain text reflow in the reading pane
+        match crate::ui::reflow::ReflowConfig::load().await {
+            Ok(config) => self.ui.set_reflow_config(config),
+            Err(e) => tracing::warn!("Failed to load reflow config, using defaults: {}", e),
+        }
+
+        // Folding of long quotes in the email viewer
+        match crate::ui::quote_fold::QuoteFoldConfig::load().await {
+            Ok(config) => self.ui.set_quote_fold_config(config),
+            Err(e) => tracing::warn!("Failed to load quote folding config, using defaults: {}", e),
+        }
+
+        // Grace period for taking back a sent message
+        match crate::smtp::outbox::UndoSendConfig::load().await {
+            Ok(config) => self.undo_send_config = config,
+            Err(e) => tracing::warn!("Failed to load undo-send settings, using defaults: {}", e),
+        }
+
+        // Home and display time zones for the calendar
+        match crate::calendar::TimezoneConfig::load().await {
+            Ok(config) => self.ui.calendar_ui_mut().set_timezone(config.display()),
+            Err(e) => tracing::warn!("Failed to load calendar time zone settings, using defaults: {}", e),
+        }
+
+        // Idle auto-lock
+        match crate::ui::lock_screen::LockConfig::load().await {
+            Ok(config) => self.lock_config = config,
+            Err(e) => tracing::warn!("Failed to load lock settings, using defaults: {}", e),
+        }
+
+        // Mark-all-read confirmation, batching and undo window
+        match crate::email::MarkReadConfig::load().await {
+            Ok(config) => self.mark_read_config = config,
+            Err(e) => tracing::warn!("Failed to load mark-all-read settings, using defaults: {}", e),
+        }
+
+        // Load the optional today panel and the services behind it
+        match crate::ui::today_panel::TodayPanelConfig::load().await {
+            Ok(config) => self.today_panel_config = config,
+            Err(e) => tracing::warn!("Failed to load today panel config, using defaults: {}", e),
+        }
+        self.services = match self.today_panel_config.weather_api_key.clone() {
+            Some(api_key) => crate::services::ServiceManager::with_weather_api_key(api_key),
+            None => crate::services::ServiceManager::new(),
+        }
+        .unwrap_or_default();
+        self.services.weather = self
+            .services
+            .weather
+            .clone()
+            .with_location(self.today_panel_config.weather_location.clone())
+            .with_units(self.today_panel_config.weather_units);
+        if self.today_panel_config.show_on_startup {
+            self.ui.today_panel_mut().set_visible(true);
+        }
+
         // Run the main loop
         let result = self.run_loop(&mut terminal).await;
 
+        // Remember where the user was for the next launch
+        self.save_session().await;
+
         // Restore terminal
         disable_raw_mode()?;
         execute!(
@@ -1603,19 +2328,74 @@ impl App {
                 }
                 // Mark as complete to prevent continuous retries
                 self.initialization_complete = true;
+
+                // Accounts and folders are loaded now, so the saved session can be applied
+                self.restore_session().await;
+                previous_selection = self.ui.message_list().get_selection_state();
+
+                if self.ui.is_today_panel_visible() {
+                    self.refresh_today_panel().await;
+                }
             }
 
-            // Process background task updates to prevent UI blocking
-            self.process_background_updates().await;
-            
-            // Check for auto-sync (every 3 minutes) - now uses background processing
-            if self.last_auto_sync.elapsed() >= self.auto_sync_interval {
-                self.queue_auto_sync_background().await;
+            // Report the clock check once it finishes
+            self.poll_clock_check().await;
+
+            // Show the weather once it arrives
+            self.poll_weather_fetch().await;
+
+            // Background token refreshes and the accounts that need a new sign-in
+            self.poll_token_refresh_events();
+
+            // Lock when idle, and unlock once the passphrase checks out
+            self.maybe_auto_lock();
+            self.poll_unlock_check().await;
+
+            // Progress and completion of mark-all-read
+            self.poll_mark_read_run().await;
+
+            // Progress and completion of an attachment download
+            self.poll_attachment_download().await;
+
+            // Progress and completion of an mbox import or export
+            self.poll_mbox_run().await;
+
+            // Folder retention maintenance
+            self.poll_retention_run().await;
+            self.maybe_start_retention_run().await;
+
+            // Snoozed messages whose time has come
+            self.maybe_queue_snooze_wakes().await;
+
+            // Scheduled sends whose time has come, and failed ones due a retry
+            self.maybe_queue_scheduled_sends().await;
+
+            // Event reminders, whichever view is open
+            self.maybe_queue_calendar_reminders().await;
+
+            // SMS that arrived on the phone
+            self.poll_incoming_sms();
+
+            // A sent message whose undo window is over
+            if self.pending_send.as_ref().is_some_and(|pending| pending.is_due()) {
+                self.send_pending().await;
+            }
+
+            // Process background task updates to prevent UI blocking
+            self.process_background_updates().await;
+            
+            // Check for auto-sync (every 3 minutes) - now uses background processing
+            if self.last_auto_sync.elapsed() >= self.auto_sync_interval {
+                self.queue_auto_sync_background().await;
                 
                 // Also refresh calendar and contacts data
                 if let Err(e) = self.check_and_refresh().await {
                     tracing::warn!("Failed to refresh calendar/contacts data: {}", e);
                 }
+
+                if self.ui.is_today_panel_visible() {
+                    self.refresh_today_panel().await;
+                }
                 
                 self.last_auto_sync = Instant::now();
             }
@@ -1642,6 +2422,8 @@ impl App {
                     tracing::warn!("Auto-save failed: {}", e);
                 }
             }
+            self.ui.update_compose_spell_check().await;
+            self.ui.update_compose_ai();
 
             // Draw UI with panic protection
             let draw_result = terminal.draw(|f| {
@@ -1686,7 +2468,13 @@ impl App {
 
             if event::poll(timeout)? {
                 if let Event::Key(key) = event::read()? {
-                    let event_result = self.event_handler.handle_key_event_with_config(key, &mut self.ui).await;
+                    self.last_activity = Instant::now();
+                    let event_result = if self.ui.is_locked() {
+                        self.handle_lock_screen_key(key);
+                        EventResult::Continue
+                    } else {
+                        self.event_handler.handle_key_event_with_config(key, &mut self.ui).await
+                    };
 
                     // Handle the event result
                     match event_result {
@@ -1740,6 +2528,27 @@ impl App {
                         EventResult::ReplyAllToMessage(message_id) => {
                             self.handle_reply_all_to_message(message_id).await?;
                         }
+                        EventResult::ReplyToListMessage(message_id) => {
+                            self.handle_reply_to_list_message(message_id).await?;
+                        }
+                        EventResult::StartQuickReply => {
+                            self.start_quick_reply().await;
+                        }
+                        EventResult::ViewInvitation => {
+                            self.handle_view_invitation().await;
+                        }
+                        EventResult::InvitationAction(action) => {
+                            self.handle_invitation_action(action).await?;
+                        }
+                        EventResult::SendQuickReply => {
+                            self.send_quick_reply().await;
+                        }
+                        EventResult::ExpandQuickReply => {
+                            self.expand_quick_reply().await;
+                        }
+                        EventResult::DecryptMessage(passphrase) => {
+                            self.decrypt_viewed_message(passphrase).await;
+                        }
                         EventResult::ForwardMessage(message_id) => {
                             self.handle_forward_message(message_id).await?;
                         }
@@ -1758,15 +2567,71 @@ impl App {
                         EventResult::DeleteEmail(account_id, message_id, folder) => {
                             self.handle_delete_email(&account_id, message_id, &folder).await?;
                         }
+                        EventResult::DeleteMessages(account_id, message_ids) => {
+                            self.request_delete_messages(account_id, message_ids);
+                        }
                         EventResult::ArchiveEmail(account_id, message_id, folder) => {
                             self.handle_archive_email(&account_id, message_id, &folder).await?;
                         }
+                        EventResult::BlockSender(message_id) => {
+                            self.handle_block_sender(message_id).await?;
+                        }
+                        EventResult::SnoozeMessage(account_id, message_id, until) => {
+                            self.handle_snooze_message(&account_id, message_id, until).await?;
+                        }
+                        EventResult::WakeSnoozed(message_id) => {
+                            self.handle_wake_snoozed(message_id).await?;
+                        }
+                        EventResult::SwitchInboxTab(tab) => {
+                            self.handle_switch_inbox_tab(tab).await;
+                        }
+                        EventResult::MoveToInboxTab(message_id, tab) => {
+                            self.handle_move_to_inbox_tab(message_id, tab).await?;
+                        }
+                        EventResult::UndoSend => {
+                            self.handle_undo_send();
+                        }
+                        EventResult::SaveFolderSort(account_id, folder, sort) => {
+                            self.save_folder_sort(&account_id, &folder, &sort).await;
+                        }
+                        EventResult::ServerSearch(account_id, folder, query) => {
+                            self.handle_server_search(&account_id, &folder, &query).await;
+                        }
+                        EventResult::ReportSpam(account_id, message_ids, folder) => {
+                            self.handle_report_spam(&account_id, &message_ids, &folder).await?;
+                        }
+                        EventResult::ReportNotSpam(account_id, message_ids, folder) => {
+                            self.handle_report_not_spam(&account_id, &message_ids, &folder).await?;
+                        }
                         EventResult::MarkEmailRead(account_id, message_id, folder) => {
                             self.handle_mark_email_read(&account_id, message_id, &folder).await?;
                         }
                         EventResult::MarkEmailUnread(account_id, message_id, folder) => {
                             self.handle_mark_email_unread(&account_id, message_id, &folder).await?;
                         }
+                        EventResult::SetThreadRead(account_id, folder, thread_id, read) => {
+                            self.handle_set_thread_read(&account_id, &folder, &thread_id, read, true)
+                                .await;
+                        }
+                        EventResult::MarkAllRead(account_id, folder) => {
+                            self.request_mark_all_read(account_id, folder).await;
+                        }
+                        EventResult::UndoMarkAllRead => {
+                            self.handle_undo_mark_all_read();
+                        }
+                        EventResult::SaveAttachment => {
+                            self.handle_save_attachment().await;
+                        }
+                        EventResult::Confirmed(action) => match action {
+                            crate::ui::confirm_dialog::ConfirmAction::MarkAllRead {
+                                account_id,
+                                folder,
+                            } => self.start_mark_all_read(account_id, folder).await,
+                            crate::ui::confirm_dialog::ConfirmAction::DeleteMessages {
+                                account_id,
+                                message_ids,
+                            } => self.handle_delete_messages(&account_id, &message_ids).await,
+                        },
                         EventResult::ToggleEmailFlag(account_id, message_id, folder) => {
                             self.handle_toggle_email_flag(&account_id, message_id, &folder).await?;
                         }
@@ -1789,9 +2654,76 @@ impl App {
                         EventResult::DeleteEvent(calendar_id, event_id) => {
                             self.handle_delete_event(&calendar_id, &event_id).await?;
                         }
+                        EventResult::EventFormAction(action) => {
+                            self.handle_event_form_action(action).await?;
+                        }
                         EventResult::ViewEventDetails(calendar_id, event_id) => {
                             self.handle_view_event_details(&calendar_id, &event_id).await?;
                         }
+                        EventResult::NudgeAttendees(event_id) => {
+                            self.handle_nudge_attendees(&event_id).await?;
+                        }
+                        EventResult::SnoozeReminder => {
+                            self.handle_snooze_reminder().await;
+                        }
+                        EventResult::ShowSms => {
+                            self.handle_show_sms().await;
+                        }
+                        EventResult::ReplyToSms => {
+                            self.handle_reply_to_sms().await;
+                        }
+                        EventResult::SmsKey(key) => {
+                            self.handle_sms_key(key).await;
+                        }
+                        EventResult::RefreshTodayPanel => {
+                            self.refresh_today_panel().await;
+                        }
+                        EventResult::ToggleUnifiedView => {
+                            self.handle_toggle_unified_view().await;
+                        }
+                        EventResult::ToggleLargestMessages(folder) => {
+                            self.handle_toggle_largest_messages(folder).await;
+                        }
+                        EventResult::LockScreen => {
+                            self.handle_lock_request();
+                        }
+                        EventResult::ShowAccountDetails(account_id) => {
+                            self.handle_show_account_details(&account_id).await;
+                        }
+                        EventResult::SetCapabilityDisabled(account_id, capability, disabled) => {
+                            self.handle_set_capability_disabled(&account_id, &capability, disabled)
+                                .await;
+                        }
+                        EventResult::ShowSpecialFolders(account_id) => {
+                            self.handle_show_special_folders(&account_id).await;
+                        }
+                        EventResult::SetSpecialFolders(account_id, mapping) => {
+                            self.handle_set_special_folders(&account_id, mapping).await;
+                        }
+                        EventResult::ShowRetention => {
+                            self.handle_show_retention().await;
+                        }
+                        EventResult::AcceptRetentionPreview => {
+                            self.handle_accept_retention_preview().await;
+                        }
+                        EventResult::RestoreRetained(message_id) => {
+                            self.handle_restore_retained(message_id).await;
+                        }
+                        EventResult::ShowFilterRules => {
+                            self.handle_show_filter_rules();
+                        }
+                        EventResult::TestFilterRule(filter_id) => {
+                            self.handle_test_filter_rule(filter_id).await;
+                        }
+                        EventResult::ToggleFilterRule(filter_id) => {
+                            self.handle_toggle_filter_rule(filter_id).await;
+                        }
+                        EventResult::ShowMboxWizard => {
+                            self.handle_show_mbox_wizard().await;
+                        }
+                        EventResult::MboxWizardAction(action) => {
+                            self.handle_mbox_wizard_action(action).await;
+                        }
                         EventResult::CreateTodo(calendar_id) => {
                             self.handle_create_todo(&calendar_id).await?;
                         }
@@ -1801,6 +2733,18 @@ impl App {
                         EventResult::AISummarizeEmail(message_id) => {
                             self.handle_ai_summarize_email(message_id).await?;
                         }
+                        EventResult::AISummarizeThread(message_id) => {
+                            self.handle_ai_summarize_thread(message_id);
+                        }
+                        EventResult::AIDraftReply(message_id) => {
+                            self.handle_ai_draft_reply(message_id).await?;
+                        }
+                        EventResult::ToggleSyncPause(account_id) => {
+                            self.handle_toggle_sync_pause(&account_id).await;
+                        }
+                        EventResult::CancelSync(account_id, folder) => {
+                            self.handle_cancel_sync(&account_id, &folder).await;
+                        }
                     }
 
                     // Check for quit command
@@ -1830,6 +2774,13 @@ impl App {
             }
 
             if self.should_quit {
+                // Quitting doesn't take back a message waiting to be sent
+                if self.pending_send.is_some() {
+                    self.send_pending().await;
+                }
+                if let Some(sms) = &self.sms {
+                    sms.stop().await;
+                }
                 break;
             }
         }
@@ -1869,7 +2820,28 @@ impl App {
             .await;
 
             match smtp_init_result {
-                Ok(Ok(smtp_service)) => {
+                Ok(Ok(mut smtp_service)) => {
+                    match crate::email::FlowedConfig::load().await {
+                        Ok(config) => smtp_service.set_flowed_line_width(config.outgoing_line_width()),
+                        Err(e) => tracing::warn!("Failed to load format=flowed settings: {}", e),
+                    }
+                    match crate::email::PgpConfig::load().await {
+                        Ok(config) => smtp_service.set_pgp_keyring(config.keyring_dir),
+                        Err(e) => tracing::warn!("Failed to load PGP settings: {}", e),
+                    }
+                    match Self::load_plugins().await {
+                        Ok(plugin_manager) => {
+                            smtp_service
+                                .set_before_send_hooks(Some(plugin_manager.before_send_hooks()));
+                            if let Some(sync_engine) = &self.sync_engine {
+                                sync_engine
+                                    .set_incoming_email_hooks(plugin_manager.incoming_email_hooks())
+                                    .await;
+                            }
+                            self.plugin_manager = Some(plugin_manager);
+                        }
+                        Err(e) => tracing::warn!("Failed to load plugins: {}", e),
+                    }
                     self.smtp_service = Some(smtp_service);
                     tracing::debug!("SMTP service initialized successfully");
                 }
@@ -1917,7 +2889,11 @@ impl App {
                             match ContactsManager::new(contacts_database, token_manager.clone())
                                 .await
                             {
-                                Ok(contacts_manager) => Ok(Some(Arc::new(contacts_manager))),
+                                Ok(contacts_manager) => Ok(Some(Arc::new(
+                                    contacts_manager.with_carddav_accounts(
+                                        crate::contacts::CardDavConfig::load_accounts().await,
+                                    ),
+                                ))),
                                 Err(e) => {
                                     tracing::warn!("Failed to initialize contacts manager: {}", e);
                                     Ok(None)
@@ -1936,6 +2912,21 @@ impl App {
             match contacts_init_result {
                 Ok(Ok(Some(contacts_manager))) => {
                     self.contacts_manager = Some(contacts_manager.clone());
+
+                    if let Some(ref mut smtp_service) = self.smtp_service {
+                        match crate::contacts::AutoAddConfig::load().await {
+                            Ok(config) if config.enabled => smtp_service.set_contact_collector(
+                                Some(Arc::new(crate::contacts::SentRecipientCollector::new(
+                                    contacts_manager.clone(),
+                                    config,
+                                ))),
+                            ),
+                            Ok(_) => {}
+                            Err(e) => {
+                                tracing::warn!("Failed to load auto-add contact settings: {}", e)
+                            }
+                        }
+                    }
                     
                     // Set up sender recognition in UI
                     self.ui.set_contacts_manager(contacts_manager);
@@ -1963,6 +2954,9 @@ impl App {
                 imap_manager.clone(),
                 database.clone(),
             ));
+            email_operations_service
+                .set_special_folders(self.special_folders.clone())
+                .await;
             
             self.email_operations_service = Some(email_operations_service);
             tracing::info!("Email operations service initialized successfully");
@@ -1982,6 +2976,9 @@ impl App {
             ComposeAction::Send => {
                 self.send_email().await?;
             }
+            ComposeAction::ScheduleSend(at) => {
+                self.schedule_send(at).await?;
+            }
             ComposeAction::SaveDraft => {
                 self.save_draft().await?;
             }
@@ -2003,26 +3000,16 @@ impl App {
                 self.start_compose_mode();
             }
             ComposeAction::StartReplyFromMessage(message) => {
-                // Start reply compose mode with the message
-                if let Some(ref contacts_manager) = self.contacts_manager {
-                    self.ui
-                        .start_reply_from_message(message, contacts_manager.clone());
-                    self.ui.exit_email_viewer(); // Exit email viewer after starting compose
-                    tracing::info!("Started reply compose mode from email viewer");
-                } else {
-                    tracing::warn!("Cannot start reply: contacts manager not initialized");
-                }
+                self.start_reply_compose(message, crate::email::ReplyMode::Sender)
+                    .await;
             }
             ComposeAction::StartReplyAllFromMessage(message) => {
-                // Start reply all compose mode with the message
-                if let Some(ref contacts_manager) = self.contacts_manager {
-                    self.ui
-                        .start_reply_all_from_message(message, contacts_manager.clone());
-                    self.ui.exit_email_viewer(); // Exit email viewer after starting compose
-                    tracing::info!("Started reply all compose mode from email viewer");
-                } else {
-                    tracing::warn!("Cannot start reply all: contacts manager not initialized");
-                }
+                self.start_reply_compose(message, crate::email::ReplyMode::All)
+                    .await;
+            }
+            ComposeAction::StartReplyToListFromMessage(message) => {
+                self.start_reply_compose(message, crate::email::ReplyMode::List)
+                    .await;
             }
             ComposeAction::StartForwardFromMessage(message) => {
                 // Start forward compose mode with the message
@@ -2036,6 +3023,7 @@ impl App {
                 }
             }
             ComposeAction::StartEditFromMessage(message) => {
+                self.invalidate_ai_summary(&message).await;
                 // Start edit compose mode with the message
                 if let Some(ref contacts_manager) = self.contacts_manager {
                     self.ui
@@ -2062,14 +3050,19 @@ impl App {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("SMTP service not initialized"))?;
 
-        // For now, use the first available account
-        // In a real implementation, this should come from the active account
+        // Replies and forwards go out from the account holding the original,
+        // anything else from the first account
         let configs = self
             .storage
             .load_all_accounts()
             .map_err(|e| anyhow::anyhow!("Failed to load account configs: {}", e))?;
+        let compose_account = self.ui.get_compose_account_id();
+        let config = configs
+            .iter()
+            .find(|config| Some(&config.account_id) == compose_account)
+            .or(configs.first());
 
-        if let Some(config) = configs.first() {
+        if let Some(config) = config {
             let account_id = &config.account_id;
             let from_address = &config.email_address;
 
@@ -2078,28 +3071,50 @@ impl App {
                 self.initialize_smtp_for_account(account_id, config).await?;
             }
 
-            // Send the email
-            match smtp_service
-                .send_email(account_id, from_address, &compose_data)
-                .await
-            {
-                Ok(result) => {
-                    tracing::info!("Email sent successfully: {}", result.message_id);
-                    self.ui.exit_compose();
-                    self.ui.clear_compose_modified();
-
-                    // TODO: Add a success notification to the UI
-                    tracing::info!(
-                        "Email sent to {} recipients",
-                        result.accepted_recipients.len()
-                    );
+            if compose_data.encrypt {
+                let missing = smtp_service
+                    .missing_pgp_keys(&compose_data)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to look up PGP keys: {}", e))?;
+                if !missing.is_empty() {
+                    self.ui.show_toast_warning(format!(
+                        "No trusted PGP key for {}",
+                        missing.join(", ")
+                    ));
+                    self.ui.ask_compose_send_unencrypted(missing);
+                    return Ok(());
                 }
-                Err(e) => {
-                    tracing::error!("Failed to send email: {}", e);
-                    // TODO: Show error message in UI
-                    return Err(anyhow::anyhow!("Failed to send email: {}", e));
+            }
+
+            let draft_id = self.ui.get_compose_draft_id().cloned();
+
+            // Hold the message back for the undo window; one waiting from
+            // before goes out now
+            if let Some(grace) = self.undo_send_config.grace_period() {
+                if self.pending_send.is_some() {
+                    self.send_pending().await;
                 }
+                self.ui.hold_compose();
+                let toast_id = self.ui.show_custom_toast(
+                    "Sending… press u to undo",
+                    crate::tea::message::ToastLevel::Info,
+                    grace,
+                );
+                self.pending_send = Some(crate::smtp::outbox::PendingSend::new(
+                    account_id.clone(),
+                    from_address.clone(),
+                    compose_data,
+                    draft_id,
+                    toast_id,
+                    grace,
+                ));
+                return Ok(());
             }
+
+            self.transmit_email(account_id, from_address, &compose_data, draft_id.as_deref())
+                .await?;
+            self.ui.exit_compose();
+            self.ui.clear_compose_modified();
         } else {
             return Err(anyhow::anyhow!("No email accounts configured"));
         }
@@ -2107,6 +3122,249 @@ impl App {
         Ok(())
     }
 
+    /// Hand a message to SMTP, then file the sent copy and drop its draft copy
+    async fn transmit_email(
+        &mut self,
+        account_id: &str,
+        from_address: &str,
+        compose_data: &crate::ui::EmailComposeData,
+        draft_id: Option<&str>,
+    ) -> Result<()> {
+        let smtp_service = self
+            .smtp_service
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("SMTP service not initialized"))?;
+
+        match smtp_service
+            .send_email(account_id, from_address, compose_data)
+            .await
+        {
+            Ok(result) => {
+                tracing::info!("Email sent successfully: {}", result.message_id);
+                if let Some(draft_id) = draft_id {
+                    self.discard_filed_draft(draft_id).await;
+                }
+                self.file_sent_copy(account_id, &result).await;
+                tracing::info!(
+                    "Email sent to {} recipients",
+                    result.accepted_recipients.len()
+                );
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Failed to send email: {}", e);
+                Err(anyhow::anyhow!("Failed to send email: {}", e))
+            }
+        }
+    }
+
+    /// Send the message whose undo window is over, or that can't wait any longer
+    ///
+    /// If sending fails the message goes back into compose, or to the drafts
+    /// when another message is being written, so it is never lost.
+    async fn send_pending(&mut self) {
+        let Some(pending) = self.pending_send.take() else {
+            return;
+        };
+        self.ui.dismiss_toast(&pending.toast_id);
+
+        match self
+            .transmit_email(
+                &pending.account_id,
+                &pending.from_address,
+                &pending.compose_data,
+                pending.draft_id.as_deref(),
+            )
+            .await
+        {
+            Ok(()) => {
+                self.ui.drop_held_compose();
+                self.ui.show_toast_success("Message sent");
+            }
+            Err(e) if self.ui.is_composing() => {
+                self.ui.drop_held_compose();
+                let draft = crate::email::database::StoredDraft::from_compose_data(
+                    pending.account_id.clone(),
+                    &pending.compose_data,
+                    false,
+                );
+                let saved = match self.database {
+                    Some(ref database) => database.save_draft(&draft).await.is_ok(),
+                    None => false,
+                };
+                if saved {
+                    self.ui.show_toast_error(format!("{}; saved to drafts", e));
+                } else {
+                    self.ui.show_toast_error(format!("{}; the message could not be saved", e));
+                }
+            }
+            Err(e) => {
+                self.ui.restore_held_compose();
+                self.ui.show_toast_error(format!("{}; back in compose", e));
+            }
+        }
+    }
+
+    /// Remember the sort chosen for a folder so it comes back on return
+    async fn save_folder_sort(
+        &self,
+        account_id: &str,
+        folder: &str,
+        sort: &crate::email::SortCriteria,
+    ) {
+        if let Some(database) = &self.database {
+            if let Err(e) = database.set_folder_sort(account_id, folder, sort).await {
+                tracing::warn!("Failed to save sort order of {}: {}", folder, e);
+            }
+        }
+    }
+
+    /// Search a folder on the IMAP server and list the matches with the local ones
+    async fn handle_server_search(&mut self, account_id: &str, folder: &str, query: &str) {
+        let (Some(imap_manager), Some(database)) = (self.imap_manager.clone(), self.database.clone())
+        else {
+            self.ui.show_toast_error("No mail server connection");
+            return;
+        };
+        if imap_manager.get_jmap_client(account_id).await.is_some() {
+            self.ui
+                .show_toast_warning("Server search is only available for IMAP accounts");
+            return;
+        }
+
+        self.ui.show_toast_info(format!("Searching {} on the server…", folder));
+        let engine = crate::ui::SearchEngine::new(database);
+        let results = match imap_manager.get_client(account_id).await {
+            Ok(client) => {
+                let mut client = client.lock().await;
+                engine
+                    .search_server(
+                        &mut client,
+                        account_id,
+                        folder,
+                        query,
+                        crate::ui::search::SERVER_SEARCH_LIMIT,
+                    )
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        };
+
+        match results {
+            Ok(results) => {
+                let messages: Vec<_> = results.into_iter().map(|result| result.message).collect();
+                self.ui
+                    .show_toast_success(format!("{} matches for \"{}\"", messages.len(), query));
+                self.ui.message_list_mut().show_server_results(&messages);
+            }
+            Err(e) => self.ui.show_toast_error(format!("Server search failed: {}", e)),
+        }
+    }
+
+    /// `u` during the undo window: take the message back into compose
+    fn handle_undo_send(&mut self) {
+        let Some(pending) = self.pending_send.take() else {
+            return;
+        };
+        self.ui.dismiss_toast(&pending.toast_id);
+        if self.ui.restore_held_compose() {
+            self.ui.show_toast_info("Send undone");
+        }
+    }
+
+    /// Keep the current compose as a draft that is sent at `at`
+    ///
+    /// The message is checked now, so a scheduled send doesn't fail later on
+    /// something that could have been fixed while composing.
+    async fn schedule_send(&mut self, at: chrono::DateTime<chrono::Utc>) -> Result<()> {
+        let compose_data = self
+            .ui
+            .get_compose_data()
+            .ok_or_else(|| anyhow::anyhow!("No compose data available"))?;
+        if let Err(e) = compose_data.validate() {
+            self.ui.show_toast_warning(format!("Can't schedule: {}", e));
+            return Ok(());
+        }
+        // Scheduled messages wait as drafts, which don't keep attachments
+        if !compose_data.attachments.is_empty() {
+            self.ui
+                .show_toast_warning("Can't schedule a message with attachments; send it now instead");
+            return Ok(());
+        }
+
+        let database = self
+            .database
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
+        let smtp_service = self
+            .smtp_service
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("SMTP service not initialized"))?;
+
+        // Sent from the same account an immediate send would use
+        let configs = self
+            .storage
+            .load_all_accounts()
+            .map_err(|e| anyhow::anyhow!("Failed to load account configs: {}", e))?;
+        let compose_account = self.ui.get_compose_account_id();
+        let account_id = configs
+            .iter()
+            .find(|config| Some(&config.account_id) == compose_account)
+            .or(configs.first())
+            .map(|config| config.account_id.clone())
+            .ok_or_else(|| anyhow::anyhow!("No email accounts configured"))?;
+
+        if compose_data.encrypt {
+            let missing = smtp_service
+                .missing_pgp_keys(&compose_data)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to look up PGP keys: {}", e))?;
+            if !missing.is_empty() {
+                self.ui.show_toast_warning(format!(
+                    "No trusted PGP key for {}; turn off encryption to schedule",
+                    missing.join(", ")
+                ));
+                return Ok(());
+            }
+        }
+
+        // An auto-saved or loaded draft becomes the scheduled send
+        let previous_draft_id = self.ui.get_compose_draft_id().cloned();
+        let existing = match previous_draft_id {
+            Some(ref draft_id) => database.load_draft(draft_id).await?,
+            None => None,
+        };
+        let mut draft = match existing {
+            Some(mut draft) if draft.account_id == account_id => {
+                draft.update_from_compose_data(&compose_data, false);
+                draft
+            }
+            _ => crate::email::database::StoredDraft::from_compose_data(account_id, &compose_data, false),
+        };
+        draft.scheduled_at = Some(at);
+        draft.send_attempts = 0;
+        draft.next_attempt_at = None;
+        draft.last_send_error = None;
+        database.save_draft(&draft).await?;
+
+        if let Some(previous) = previous_draft_id {
+            if previous != draft.id {
+                let _ = database.delete_draft(&previous).await;
+            }
+            self.discard_filed_draft(&previous).await;
+        }
+        self.ui.exit_compose();
+        self.ui.clear_compose_modified();
+        self.ui.show_toast_success(format!(
+            "Scheduled for {}",
+            at.with_timezone(&chrono::Local).format("%a %-d %b, %H:%M")
+        ));
+        // Already due: send on the next check rather than in a minute
+        self.last_scheduled_send_check = None;
+        Ok(())
+    }
+
     /// Save the current compose as a draft
     async fn save_draft(&mut self) -> Result<()> {
         let compose_data = self
@@ -2127,12 +3385,22 @@ impl App {
 
         if let Some(config) = configs.first() {
             let account_id = &config.account_id;
+            let previous_draft_id = self.ui.get_compose_draft_id().cloned();
 
             match smtp_service.save_draft(account_id, &compose_data).await {
                 Ok(draft_id) => {
                     tracing::info!("Draft saved with ID: {}", draft_id);
+                    if let Some(previous) = previous_draft_id {
+                        self.discard_filed_draft(&previous).await;
+                    }
+                    self.file_draft_copy(config, &compose_data, &draft_id).await;
                     self.ui.clear_compose_modified();
                     self.ui.set_compose_draft_id(Some(draft_id));
+                    if !compose_data.attachments.is_empty() {
+                        self.ui.show_toast_warning(
+                            "Draft saved without its attachments; attach them again before sending",
+                        );
+                    }
                     // TODO: Add a success notification to the UI
                 }
                 Err(e) => {
@@ -2209,6 +3477,9 @@ impl App {
             DraftAction::DeleteDraft(draft_id) => {
                 self.delete_draft(&draft_id).await?;
             }
+            DraftAction::CancelSchedule(draft_id) => {
+                self.cancel_scheduled_send(&draft_id).await?;
+            }
             DraftAction::Close => {
                 self.ui.hide_draft_list();
             }
@@ -2251,8 +3522,17 @@ impl App {
             let account_id = &config.account_id;
 
             match smtp_service.list_drafts(account_id).await {
-                Ok(drafts) => {
+                Ok(mut drafts) => {
                     tracing::info!("Loaded {} drafts for account {}", drafts.len(), account_id);
+                    // Pending scheduled sends of the other accounts are listed too
+                    if let Some(ref database) = self.database {
+                        let scheduled = database.get_scheduled_drafts().await?;
+                        drafts.extend(
+                            scheduled
+                                .into_iter()
+                                .filter(|draft| &draft.account_id != account_id),
+                        );
+                    }
                     Ok(drafts)
                 }
                 Err(e) => {
@@ -2267,20 +3547,39 @@ impl App {
 
     /// Load a draft for editing
     async fn load_draft_for_editing(&mut self, draft_id: &str) -> Result<()> {
-        let smtp_service = self
-            .smtp_service
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("SMTP service not initialized"))?;
-
-        // For now, use the first available account
+        // Drafts are loaded from their own account, or else the first one
         let configs = self
             .storage
             .load_all_accounts()
             .map_err(|e| anyhow::anyhow!("Failed to load account configs: {}", e))?;
 
-        if let Some(config) = configs.first() {
-            let account_id = &config.account_id;
+        // A scheduled send goes back to being a draft while it's edited, so
+        // it can't go out half-changed; Ctrl+L schedules it again
+        let stored = match self.database {
+            Some(ref database) => database.load_draft(draft_id).await?,
+            None => None,
+        };
+        if let Some(ref draft) = stored {
+            if draft.scheduled_at.is_some() {
+                if self.scheduled_sends_in_flight.contains(draft_id) {
+                    self.ui.show_toast_warning("Already sending; can't edit now".to_string());
+                    return Ok(());
+                }
+                if let Some(ref database) = self.database {
+                    database.set_draft_schedule(draft_id, None).await?;
+                }
+                self.ui.show_toast_info("Schedule cancelled while editing".to_string());
+            }
+        }
 
+        let smtp_service = self
+            .smtp_service
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("SMTP service not initialized"))?;
+        let account_id = stored
+            .map(|draft| draft.account_id)
+            .or_else(|| configs.first().map(|config| config.account_id.clone()));
+        if let Some(ref account_id) = account_id {
             match smtp_service.load_draft(account_id, draft_id).await {
                 Ok(compose_data) => {
                     // Get contacts manager for compose UI
@@ -2307,6 +3606,22 @@ impl App {
         Ok(())
     }
 
+    /// Keep a scheduled send as an ordinary draft
+    async fn cancel_scheduled_send(&mut self, draft_id: &str) -> Result<()> {
+        let database = self
+            .database
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Database not initialized"))?;
+        if self.scheduled_sends_in_flight.contains(draft_id) {
+            self.ui.show_toast_warning("Already sending; too late to cancel".to_string());
+            return Ok(());
+        }
+
+        database.set_draft_schedule(draft_id, None).await?;
+        self.ui.show_toast_info("Scheduled send cancelled; kept as a draft".to_string());
+        self.show_draft_list().await
+    }
+
     /// Delete a draft
     async fn delete_draft(&mut self, draft_id: &str) -> Result<()> {
         let smtp_service = self
@@ -2341,8 +3656,30 @@ impl App {
         Ok(())
     }
 
-    /// Initialize SMTP for a specific account
-    async fn initialize_smtp_for_account(
+    /// Load the plugins installed in the config directory's `plugins` folder
+    async fn load_plugins() -> Result<crate::plugins::PluginManager> {
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("Failed to get config directory"))?;
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| anyhow::anyhow!("Failed to get data directory"))?;
+
+        let mut plugin_manager = crate::plugins::PluginManager::new(
+            vec![config_dir.join("comunicado").join("plugins")],
+            env!("CARGO_PKG_VERSION").to_string(),
+            data_dir.join("comunicado").join("plugins"),
+        )?;
+        plugin_manager.initialize().await?;
+        Ok(plugin_manager)
+    }
+
+    /// Plugin manager, for registering built-in email plugins such as
+    /// snippet expanders whose `on_before_send` hooks rewrite outgoing mail
+    pub fn plugin_manager_mut(&mut self) -> Option<&mut crate::plugins::PluginManager> {
+        self.plugin_manager.as_mut()
+    }
+
+    /// Initialize SMTP for a specific account
+    async fn initialize_smtp_for_account(
         &self,
         account_id: &str,
         config: &AccountConfig,
@@ -2702,7 +4039,7 @@ impl App {
         let attachments = self.parse_attachments_from_body_structure(imap_message);
 
         // Create StoredMessage
-        let stored_message = crate::email::StoredMessage {
+        let mut stored_message = crate::email::StoredMessage {
             id: Uuid::new_v4(),
             account_id: account_id.to_string(),
             folder_name: folder_name.to_string(),
@@ -2729,7 +4066,7 @@ impl App {
 
             // Metadata
             flags,
-            labels: Vec::new(),
+            labels: imap_message.labels.clone(),
             size: imap_message.size,
             priority: None,
 
@@ -2740,7 +4077,16 @@ impl App {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         };
+        if let Some(calendar) = imap_message
+            .body
+            .as_deref()
+            .and_then(|body| crate::mime::parse_message(body).calendar)
+        {
+            stored_message.attach_calendar(calendar);
+        }
 
         Ok(stored_message)
     }
@@ -3427,6 +4773,20 @@ impl App {
             }
         };
 
+        // The merged inbox is read from the cache; each INBOX is kept fresh by
+        // its account's own sync
+        if folder_path == crate::ui::folder_tree::ALL_INBOXES {
+            if let Err(e) = self
+                .ui
+                .load_messages(current_account_id, folder_path.to_string())
+                .await
+            {
+                tracing::warn!("Failed to load All Inboxes: {}", e);
+                self.ui.show_toast_error(format!("Failed to load All Inboxes: {}", e));
+            }
+            return Ok(());
+        }
+
         tracing::info!(
             "Loading folder: '{}' for account: '{}' (instant load from cache)",
             folder_path,
@@ -3506,9 +4866,50 @@ impl App {
             }
         }
 
+        // STEP 3: Prefetch this folder's newest messages and the folders likely opened next
+        self.queue_prefetch(&current_account_id, folder_path).await;
+
         Ok(())
     }
 
+    /// Queue low-priority prefetching for an opened folder
+    ///
+    /// Prefetch tasks still waiting from the previously opened folder are
+    /// cancelled first.
+    async fn queue_prefetch(&mut self, account_id: &str, folder_path: &str) {
+        let (Some(processor), Some(database)) = (&self.background_processor, &self.database) else {
+            return;
+        };
+
+        for task_id in self.prefetch_tasks.drain(..) {
+            if processor.get_task_status(task_id).await == Some(crate::performance::TaskStatus::Queued) {
+                processor.cancel_task(task_id).await;
+            }
+        }
+
+        if !self.prefetch_config.enabled {
+            return;
+        }
+
+        let activity = match database.get_folder_activity(account_id).await {
+            Ok(activity) => activity,
+            Err(e) => {
+                tracing::debug!("Skipping prefetch, folder activity unavailable: {}", e);
+                return;
+            }
+        };
+
+        for task in self.prefetch_config.plan(account_id, folder_path, &activity) {
+            match processor.queue_task(task).await {
+                Ok(task_id) => self.prefetch_tasks.push(task_id),
+                Err(e) => {
+                    tracing::debug!("Prefetch not queued: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+
     /// Force refresh a folder with full IMAP sync (for F5/Ctrl+R)
     /// This is the blocking version that users can trigger manually
     async fn handle_folder_force_refresh(&mut self, folder_path: &str) -> Result<()> {
@@ -3617,7 +5018,13 @@ impl App {
                 self.handle_folder_refresh(&current_account_id).await?;
             }
             FolderOperation::MarkAllRead => {
-                self.handle_mark_all_read(&current_account_id).await?;
+                if let Some(folder) = selected_folder {
+                    self.request_mark_all_read(current_account_id, Some(folder.path))
+                        .await;
+                }
+            }
+            FolderOperation::MarkAccountRead => {
+                self.request_mark_all_read(current_account_id, None).await;
             }
             FolderOperation::Properties => {
                 self.handle_folder_properties(&current_account_id).await?;
@@ -3661,6 +5068,12 @@ impl App {
                         .await?;
                 }
             }
+            FolderOperation::SyncMode => {
+                if let Some(folder) = selected_folder {
+                    self.handle_cycle_folder_sync_mode(&current_account_id, &folder.path)
+                        .await?;
+                }
+            }
             FolderOperation::Move => {
                 // TODO: Implement move folder functionality
                 tracing::info!("Move folder operation not yet implemented");
@@ -3700,43 +5113,282 @@ impl App {
         Ok(())
     }
 
-    /// Handle mark all messages as read in current folder
-    async fn handle_mark_all_read(&mut self, account_id: &str) -> Result<()> {
-        if let Some(selected_folder) = self.ui.folder_tree().selected_folder() {
-            let folder_path = selected_folder.path.clone();
-            tracing::info!(
-                "Marking all messages as read in folder: {} for account: {}",
-                folder_path,
-                account_id
+    fn bulk_read_service(&self) -> Option<crate::email::BulkReadService> {
+        match (&self.database, &self.email_operations_service) {
+            (Some(database), Some(operations)) => Some(crate::email::BulkReadService::new(
+                database.clone(),
+                operations.clone(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Mark a folder, or the whole account when `folder` is `None`, as read,
+    /// asking first unless confirmation is turned off
+    async fn request_mark_all_read(&mut self, account_id: String, folder: Option<String>) {
+        if self.mark_read_run.is_some() {
+            self.ui
+                .show_toast_warning("Already marking messages; press Ctrl+Z to cancel");
+            return;
+        }
+        let Some(service) = self.bulk_read_service() else {
+            self.ui.show_toast_error("Email operations are not available yet");
+            return;
+        };
+        let unread = match service.unread(&account_id, folder.as_deref()).await {
+            Ok(unread) => unread.len(),
+            Err(e) => {
+                self.ui.show_toast_error(format!("Could not count unread messages: {}", e));
+                return;
+            }
+        };
+        let scope = match folder {
+            Some(ref folder) => folder.clone(),
+            None => format!("all folders of {}", account_id),
+        };
+        if unread == 0 {
+            self.ui.show_toast_info(format!("Nothing unread in {}", scope));
+            return;
+        }
+
+        if self.mark_read_config.confirm {
+            self.ui.show_confirm(crate::ui::confirm_dialog::ConfirmDialog::new(
+                "Mark all read".to_string(),
+                format!("Mark {} unread messages in {} as read?", unread, scope),
+                crate::ui::confirm_dialog::ConfirmAction::MarkAllRead { account_id, folder },
+            ));
+        } else {
+            self.start_mark_all_read(account_id, folder).await;
+        }
+    }
+
+    /// Record the unread messages and mark them read in the background
+    async fn start_mark_all_read(&mut self, account_id: String, folder: Option<String>) {
+        let Some(service) = self.bulk_read_service() else {
+            return;
+        };
+        // Recorded now so the undo restores exactly these messages
+        let unread = match service.unread(&account_id, folder.as_deref()).await {
+            Ok(unread) => unread,
+            Err(e) => {
+                self.ui.show_toast_error(format!("Could not read message flags: {}", e));
+                return;
+            }
+        };
+
+        let (progress_tx, progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        self.mark_read_cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let cancel = self.mark_read_cancel.clone();
+        let batch_size = self.mark_read_config.batch_size;
+        self.mark_read_undo = None;
+        self.mark_read_progress = Some(progress_rx);
+        self.mark_read_run = Some(tokio::spawn(async move {
+            service
+                .mark_read(&account_id, unread, batch_size, &progress_tx, &cancel)
+                .await
+        }));
+    }
+
+    /// Ctrl+Z: cancel a running mark-all-read, or undo the last one within the grace window
+    fn handle_undo_mark_all_read(&mut self) {
+        if self.mark_read_run.is_some() {
+            self.mark_read_cancel
+                .store(true, std::sync::atomic::Ordering::Relaxed);
+            self.ui.show_toast_info("Cancelling after the current batch...");
+            return;
+        }
+
+        let window = self.mark_read_config.undo_window();
+        let Some(undo) = self
+            .mark_read_undo
+            .take()
+            .filter(|undo| !undo.is_expired(window))
+        else {
+            self.ui.show_toast_info("Nothing to undo");
+            return;
+        };
+        let Some(service) = self.bulk_read_service() else {
+            return;
+        };
+
+        let (progress_tx, progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        self.mark_read_cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let cancel = self.mark_read_cancel.clone();
+        let batch_size = self.mark_read_config.batch_size;
+        self.mark_read_progress = Some(progress_rx);
+        self.mark_read_run = Some(tokio::spawn(async move {
+            service.restore(undo, batch_size, &progress_tx, &cancel).await
+        }));
+    }
+
+    /// Show progress of a running mark-all-read and report it once finished
+    async fn poll_mark_read_run(&mut self) {
+        let mut latest = None;
+        if let Some(ref mut progress) = self.mark_read_progress {
+            while let Ok(update) = progress.try_recv() {
+                latest = Some(update);
+            }
+        }
+        // Small folders finish in one batch; only larger ones get a progress line
+        if let Some(progress) = latest.filter(|p| p.total > self.mark_read_config.batch_size) {
+            self.ui.show_notification(
+                format!(
+                    "Marking messages: {}/{} (Ctrl+Z to cancel)",
+                    progress.done, progress.total
+                ),
+                Duration::from_secs(5),
             );
+        }
 
-            // TODO: Implement IMAP STORE command to mark messages as read
-            // For now, just update the database
-            if let Some(ref database) = self.database {
-                let result = sqlx::query("UPDATE messages SET is_read = 1, flags = flags || ',\\Seen' WHERE account_id = ? AND folder_name = ? AND is_read = 0")
-                    .bind(account_id)
-                    .bind(&folder_path)
-                    .execute(&database.pool)
-                    .await?;
+        if !self
+            .mark_read_run
+            .as_ref()
+            .map(|handle| handle.is_finished())
+            .unwrap_or(false)
+        {
+            return;
+        }
+        let Some(handle) = self.mark_read_run.take() else {
+            return;
+        };
+        self.mark_read_progress = None;
 
-                tracing::info!(
-                    "Marked {} messages as read in folder: {}",
-                    result.rows_affected(),
-                    folder_path
-                );
+        let outcome = match handle.await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                tracing::warn!("Mark-all-read task failed: {}", e);
+                return;
+            }
+        };
 
-                // Reload messages to update UI
-                if let Err(e) = self
-                    .ui
-                    .load_messages(account_id.to_string(), folder_path)
-                    .await
-                {
-                    tracing::error!("Failed to reload messages after marking as read: {}", e);
-                }
+        let changed = outcome.changed.len();
+        let failed = outcome.error.is_some();
+        let mut message = if outcome.read {
+            format!("Marked {} messages read", changed)
+        } else {
+            format!("Restored {} messages to unread", changed)
+        };
+        if outcome.cancelled {
+            message.push_str(" before cancelling");
+        }
+        if let Some(ref error) = outcome.error {
+            message.push_str(&format!(", then stopped: {}", error));
+        }
+        let undoable = outcome.read && changed > 0 && self.mark_read_config.undo_window_secs > 0;
+        if undoable {
+            message.push_str(&format!(
+                ". Ctrl+Z to undo within {}s",
+                self.mark_read_config.undo_window_secs
+            ));
+            self.mark_read_undo = Some(outcome.into_undo());
+        }
+        if failed {
+            self.ui.show_toast_warning(message);
+        } else {
+            self.ui.show_toast_info(message);
+        }
+
+        if changed > 0 {
+            if let Err(e) = self.ui.message_list_mut().refresh_messages().await {
+                tracing::warn!("Failed to refresh folder after marking read: {}", e);
             }
         }
+    }
 
-        Ok(())
+    /// Save the selected attachment, downloading it in the background when
+    /// it isn't stored locally; pressed again during a download, cancel it
+    async fn handle_save_attachment(&mut self) {
+        if self.attachment_download_run.is_some() {
+            self.attachment_download_cancel
+                .store(true, std::sync::atomic::Ordering::Relaxed);
+            self.ui.show_toast_info("Cancelling download...");
+            return;
+        }
+
+        let download = match self.ui.content_preview().begin_save_selected_attachment().await {
+            Ok(crate::ui::content_preview::AttachmentSave::Saved(path)) => {
+                tracing::info!("Attachment saved to: {:?}", path);
+                self.ui
+                    .show_toast_success(format!("Saved to {}", path.display()));
+                return;
+            }
+            Ok(crate::ui::content_preview::AttachmentSave::Download(download)) => download,
+            Err(e) => {
+                tracing::error!("Failed to save attachment: {}", e);
+                self.ui
+                    .show_toast_error(format!("Failed to save attachment: {}", e));
+                return;
+            }
+        };
+
+        self.ui.show_toast_info(format!(
+            "Downloading {} (S to cancel)",
+            download.filename
+        ));
+        let (progress_tx, progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        self.attachment_download_cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let cancel = self.attachment_download_cancel.clone();
+        self.attachment_download_name = download.filename.clone();
+        self.attachment_download_progress = Some(progress_rx);
+        self.attachment_download_run =
+            Some(tokio::spawn(async move { download.run(progress_tx, cancel).await }));
+    }
+
+    /// Show progress of a running attachment download and report it once finished
+    async fn poll_attachment_download(&mut self) {
+        let mut latest = None;
+        if let Some(ref mut progress) = self.attachment_download_progress {
+            while let Ok(update) = progress.try_recv() {
+                latest = Some(update);
+            }
+        }
+        if let Some(progress) = latest {
+            let received = format!("{:.1} MB", progress.received as f64 / (1024.0 * 1024.0));
+            let done = match progress.percentage() {
+                Some(percentage) => format!("{}% ({})", percentage, received),
+                None => received,
+            };
+            self.ui.show_notification(
+                format!(
+                    "Downloading {}: {} (S to cancel)",
+                    self.attachment_download_name, done
+                ),
+                Duration::from_secs(5),
+            );
+        }
+
+        if !self
+            .attachment_download_run
+            .as_ref()
+            .map(|handle| handle.is_finished())
+            .unwrap_or(false)
+        {
+            return;
+        }
+        let Some(handle) = self.attachment_download_run.take() else {
+            return;
+        };
+        self.attachment_download_progress = None;
+
+        match handle.await {
+            Ok(Ok(path)) => {
+                tracing::info!("Attachment saved to: {:?}", path);
+                self.ui
+                    .show_toast_success(format!("Saved to {}", path.display()));
+            }
+            Ok(Err(e)) if e.kind() == std::io::ErrorKind::Interrupted => {
+                self.ui.show_toast_info(format!(
+                    "Download of {} cancelled",
+                    self.attachment_download_name
+                ));
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Failed to download attachment: {}", e);
+                self.ui
+                    .show_toast_error(format!("Failed to save attachment: {}", e));
+            }
+            Err(e) => tracing::warn!("Attachment download task failed: {}", e),
+        }
     }
 
     /// Handle folder properties display
@@ -3773,6 +5425,45 @@ impl App {
         Ok(())
     }
 
+    /// Step the folder's sync mode override to the next one
+    async fn handle_cycle_folder_sync_mode(
+        &mut self,
+        account_id: &str,
+        folder_path: &str,
+    ) -> Result<()> {
+        let Some(database) = self.database.clone() else {
+            return Ok(());
+        };
+
+        let current = database
+            .get_folder_sync_state(account_id, folder_path)
+            .await?
+            .and_then(|state| state.sync_mode);
+        let next = crate::email::FolderSyncMode::next_override(current);
+        database
+            .set_folder_sync_mode(account_id, folder_path, next)
+            .await?;
+
+        let label = match next {
+            Some(mode) => mode.to_string(),
+            None => {
+                let default_mode = self
+                    .sync_engine
+                    .as_ref()
+                    .map(|engine| engine.folder_sync_defaults().mode_for(account_id))
+                    .unwrap_or_default();
+                format!("Account default ({})", default_mode)
+            }
+        };
+        tracing::info!("Sync mode for {}/{}: {}", account_id, folder_path, label);
+        self.ui.show_notification(
+            format!("⇅ Sync mode for {}: {}", folder_path, label),
+            std::time::Duration::from_secs(3),
+        );
+
+        Ok(())
+    }
+
     /// Handle create new folder
     async fn handle_create_folder(
         &mut self,
@@ -4419,6 +6110,68 @@ impl App {
         } else {
             tracing::debug!("Contacts manager not available for sender lookup");
         }
+
+        if let (Some(ref database), Some(message_id)) =
+            (&self.database, self.ui.email_viewer().get_message_id())
+        {
+            match database.get_message_security(message_id).await {
+                Ok(security) => {
+                    self.ui.email_viewer_mut().set_message_security(security);
+                    // Encrypted mail has nothing to show until it is decrypted
+                    self.ui.email_viewer_mut().open_passphrase_prompt();
+                }
+                Err(e) => tracing::warn!("Failed to load security status for {}: {}", message_id, e),
+            }
+            match database.get_message_defects(message_id).await {
+                Ok(defects) => self.ui.email_viewer_mut().set_mime_defects(defects),
+                Err(e) => tracing::warn!("Failed to load MIME defects for {}: {}", message_id, e),
+            }
+        }
+
+        // Recognition details: matching contact, mail history and lookalike warnings
+        if let Some(recognition_service) = self.ui.sender_recognition() {
+            let message_date = self
+                .ui
+                .email_viewer()
+                .current_message
+                .as_ref()
+                .map(|message| message.date)
+                .unwrap_or_else(chrono::Utc::now);
+            let history = match self.database {
+                Some(ref database) => database
+                    .sender_history(sender_email, message_date)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to load mail history for {}: {}", sender_email, e);
+                        crate::email::SenderHistory::default()
+                    }),
+                None => crate::email::SenderHistory::default(),
+            };
+            match recognition_service.recognize(sender_email, &history).await {
+                Ok(recognition) => self
+                    .ui
+                    .email_viewer_mut()
+                    .set_sender_recognition(Some(recognition)),
+                Err(e) => tracing::warn!("Failed to recognize sender {}: {}", sender_email, e),
+            }
+        }
+
+        // Reading the newest message of a conversation can mark all of it read
+        if let Some(message_id) = self.ui.email_viewer().get_message_id() {
+            let message_list = self.ui.message_list();
+            if message_list.thread_behavior().mark_thread_read_on_latest
+                && message_list.is_latest_in_thread(message_id)
+            {
+                if let (Some(thread_id), Some(account_id), Some(folder)) = (
+                    message_list.thread_of(message_id).map(str::to_string),
+                    message_list.current_account().cloned(),
+                    message_list.current_folder().cloned(),
+                ) {
+                    self.handle_set_thread_read(&account_id, &folder, &thread_id, true, false)
+                        .await;
+                }
+            }
+        }
         
         Ok(())
     }
@@ -4463,15 +6216,14 @@ impl App {
         Ok(())
     }
 
-    /// Handle forward message action
-    async fn handle_forward_message(&mut self, message_id: uuid::Uuid) -> Result<()> {
-        tracing::info!("Forward message triggered for ID: {}", message_id);
-        
-        // Load the full message from database
+    /// Handle reply to list action
+    async fn handle_reply_to_list_message(&mut self, message_id: uuid::Uuid) -> Result<()> {
+        tracing::info!("Reply to list triggered for ID: {}", message_id);
+
         if let Some(database) = &self.database {
             if let Some(stored_message) = database.get_message_by_id(message_id).await? {
-            // Start compose with forward
-            let compose_action = crate::ui::ComposeAction::StartForwardFromMessage(stored_message);
+                let compose_action =
+                    crate::ui::ComposeAction::StartReplyToListFromMessage(stored_message);
                 self.handle_compose_action(compose_action).await?;
             } else {
                 tracing::error!("Message not found for ID: {}", message_id);
@@ -4479,96 +6231,309 @@ impl App {
         } else {
             tracing::error!("Database not available");
         }
-        
+
         Ok(())
     }
 
-    /// Get the email operations service
-    pub fn email_operations_service(&self) -> Option<Arc<crate::email::EmailOperationsService>> {
-        self.email_operations_service.clone()
+    /// Start composing a reply, with recipients chosen from the message's
+    /// list and reply headers
+    async fn start_reply_compose(
+        &mut self,
+        message: crate::email::StoredMessage,
+        mode: crate::email::ReplyMode,
+    ) {
+        let Some(contacts_manager) = self.contacts_manager.clone() else {
+            tracing::warn!("Cannot start reply: contacts manager not initialized");
+            return;
+        };
+
+        let headers = self.reply_headers(&message).await;
+        self.ui
+            .start_reply_from_message(message, mode, &headers, contacts_manager);
+        self.ui.exit_email_viewer(); // Exit email viewer after starting compose
+        tracing::info!("Started {:?} reply compose mode", mode);
     }
 
-    /// Handle delete email operation
-    async fn handle_delete_email(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
-        if let Some(ref service) = self.email_operations_service {
-            match service.delete_email_by_id(account_id, message_id, folder).await {
-                Ok(()) => {
-                    self.ui.show_toast_info("Email deleted successfully");
-                    // Refresh the message list to reflect the change
-                    if let Err(e) = self.handle_folder_force_refresh(folder).await {
-                        tracing::warn!("Failed to refresh folder after delete: {}", e);
-                    }
-                }
-                Err(e) => {
-                    let error_msg = format!("Failed to delete email: {}", e);
-                    tracing::error!("{}", error_msg);
-                    self.ui.show_toast_error(&error_msg);
-                }
-            }
-        } else {
-            self.ui.show_toast_error("Email operations service not available");
+    /// List and reply headers of a message, empty if they can't be loaded
+    async fn reply_headers(
+        &self,
+        message: &crate::email::StoredMessage,
+    ) -> crate::email::ReplyHeaders {
+        match &self.database {
+            Some(database) => database
+                .get_reply_headers(message.id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load reply headers for {}: {}", message.id, e);
+                    crate::email::ReplyHeaders::default()
+                }),
+            None => crate::email::ReplyHeaders::default(),
         }
-        Ok(())
     }
 
-    /// Handle archive email operation
-    async fn handle_archive_email(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
-        if let Some(ref service) = self.email_operations_service {
-            match service.archive_email_by_id(account_id, message_id, folder).await {
-                Ok(()) => {
-                    self.ui.show_toast_info("Email archived successfully");
-                    // Refresh the message list to reflect the change
-                    if let Err(e) = self.handle_folder_force_refresh(folder).await {
-                        tracing::warn!("Failed to refresh folder after archive: {}", e);
-                    }
-                }
-                Err(e) => {
-                    let error_msg = format!("Failed to archive email: {}", e);
-                    tracing::error!("{}", error_msg);
-                    self.ui.show_toast_error(&error_msg);
-                }
+    /// Open the quick reply box under the message in the email viewer
+    async fn start_quick_reply(&mut self) {
+        let Some(message) = self.ui.email_viewer().current_message.clone() else {
+            return;
+        };
+        let headers = self.reply_headers(&message).await;
+        self.ui.start_quick_reply(message, &headers);
+    }
+
+    /// Send the quick reply from the account the message belongs to
+    ///
+    /// The box stays open with the text if sending fails.
+    async fn send_quick_reply(&mut self) {
+        let Some(quick_reply) = self.ui.email_viewer().quick_reply() else {
+            return;
+        };
+        let account_id = quick_reply.account_id().to_string();
+        let compose_data = quick_reply.compose_data();
+
+        match self.send_from_account(&account_id, &compose_data).await {
+            Ok(result) => {
+                self.file_sent_copy(&account_id, &result).await;
+                self.ui.email_viewer_mut().close_quick_reply();
+                self.ui.show_toast_success(format!(
+                    "Reply sent to {} recipient(s)",
+                    result.accepted_recipients.len()
+                ));
+            }
+            Err(e) => {
+                tracing::error!("Failed to send quick reply: {}", e);
+                self.ui
+                    .show_toast_error(format!("Failed to send reply: {}", e));
             }
-        } else {
-            self.ui.show_toast_error("Email operations service not available");
         }
-        Ok(())
     }
 
-    /// Handle mark email as read operation
-    async fn handle_mark_email_read(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
-        if let Some(ref service) = self.email_operations_service {
-            match service.mark_email_read_by_id(account_id, message_id, folder).await {
-                Ok(()) => {
-                    self.ui.show_toast_info("Email marked as read");
-                    // Update the UI to reflect the change
-                    self.ui.message_list_mut().mark_selected_as_read();
-                }
-                Err(e) => {
-                    let error_msg = format!("Failed to mark email as read: {}", e);
-                    tracing::error!("{}", error_msg);
-                    self.ui.show_toast_error(&error_msg);
+    /// Decrypt the PGP message in the viewer with the typed passphrase
+    ///
+    /// The encrypted source is fetched again from the server, since only its
+    /// displayable parts are stored. A wrong passphrase leaves the box open
+    /// for another try.
+    async fn decrypt_viewed_message(&mut self, passphrase: String) {
+        let Some(message) = self.ui.email_viewer().current_message.clone() else {
+            return;
+        };
+
+        let keyring = match crate::email::PgpConfig::load().await {
+            Ok(config) => config.keyring_dir,
+            Err(e) => {
+                tracing::warn!("Failed to load PGP settings: {}", e);
+                None
+            }
+        };
+        let result = match self.fetch_raw_message(&message).await {
+            Ok(raw) => crate::email::crypto::decrypt(&raw, &passphrase, keyring.as_deref())
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        match result {
+            Ok(entity) => {
+                let parsed = crate::mime::parse_message(&entity);
+                self.ui.email_viewer_mut().show_decrypted(&parsed);
+                self.ui.show_toast_success("Message decrypted");
+            }
+            Err(e) => {
+                tracing::warn!("Failed to decrypt message {}: {}", message.id, e);
+                if let Some(prompt) = self.ui.email_viewer_mut().passphrase_prompt_mut() {
+                    prompt.set_error(e);
                 }
             }
+        }
+    }
+
+    /// Full RFC 5322 source of a stored message, fetched from its server
+    async fn fetch_raw_message(&self, message: &crate::email::StoredMessage) -> Result<String> {
+        let imap_manager = self
+            .imap_manager
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No mail server connection"))?;
+
+        // JMAP accounts store synthetic UIDs, so fetch by email id instead
+        if let Some(jmap) = imap_manager.get_jmap_client(&message.account_id).await {
+            let database = self
+                .database
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("Database not available"))?;
+            let email_id = database
+                .jmap_email_ids(&message.account_id, &[message.imap_uid])
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Message is no longer on the server"))?;
+            return Ok(jmap.lock().await.download_email(&email_id).await?);
+        }
+
+        let client = imap_manager.get_client(&message.account_id).await?;
+        let mut client = client.lock().await;
+        client.select_folder(&message.folder_name).await?;
+        client
+            .uid_fetch_messages(&message.imap_uid.to_string(), &["BODY.PEEK[]"])
+            .await?
+            .into_iter()
+            .find_map(|fetched| fetched.body)
+            .ok_or_else(|| anyhow::anyhow!("Message is no longer on the server"))
+    }
+
+    /// Move the quick reply into a full reply compose, keeping the typed text
+    async fn expand_quick_reply(&mut self) {
+        let Some(quick_reply) = self.ui.email_viewer_mut().close_quick_reply() else {
+            return;
+        };
+        let text = quick_reply.body();
+        self.start_reply_compose(quick_reply.message().clone(), crate::email::ReplyMode::Sender)
+            .await;
+        self.ui.insert_compose_text(&text);
+    }
+
+    /// File a copy of sent mail in the account's mapped Sent folder
+    async fn file_sent_copy(&mut self, account_id: &str, result: &crate::smtp::SendResult) {
+        let Some(service) = self.email_operations_service.clone() else {
+            return;
+        };
+        if let Err(e) = service
+            .file_message(
+                account_id,
+                crate::email::SpecialFolder::Sent,
+                &result.raw_message,
+                &[crate::imap::MessageFlag::Seen],
+            )
+            .await
+        {
+            tracing::warn!("Failed to file sent copy for {}: {}", account_id, e);
+            self.ui
+                .show_toast_warning(format!("Sent, but no copy was saved: {}", e));
+        }
+    }
+
+    /// File a saved draft in the account's mapped Drafts folder
+    async fn file_draft_copy(
+        &mut self,
+        config: &AccountConfig,
+        compose_data: &crate::ui::EmailComposeData,
+        draft_id: &str,
+    ) {
+        let (Some(service), Some(smtp_service)) =
+            (self.email_operations_service.clone(), self.smtp_service.as_ref())
+        else {
+            return;
+        };
+        // Drafts without recipients can't be formatted yet; they stay local
+        let raw_message = match smtp_service.format_message(&config.email_address, compose_data) {
+            Ok(raw_message) => raw_message,
+            Err(e) => {
+                tracing::debug!("Not filing draft {} on the server: {}", draft_id, e);
+                return;
+            }
+        };
+
+        match service
+            .file_message(
+                &config.account_id,
+                crate::email::SpecialFolder::Drafts,
+                &raw_message,
+                &[crate::imap::MessageFlag::Draft, crate::imap::MessageFlag::Seen],
+            )
+            .await
+        {
+            Ok(Some((folder, Some(uid)))) => {
+                self.filed_drafts
+                    .insert(draft_id.to_string(), (config.account_id.clone(), folder, uid));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!("Failed to file draft in {}: {}", config.account_id, e);
+                self.ui
+                    .show_toast_warning(format!("Draft saved locally only: {}", e));
+            }
+        }
+    }
+
+    /// Remove the server copy of a draft that was saved again or sent
+    async fn discard_filed_draft(&mut self, draft_id: &str) {
+        let Some((account_id, folder, uid)) = self.filed_drafts.remove(draft_id) else {
+            return;
+        };
+        let Some(ref service) = self.email_operations_service else {
+            return;
+        };
+        if let Err(e) = service.remove_filed_message(&account_id, &folder, uid).await {
+            tracing::warn!("Failed to remove old draft copy from {}: {}", folder, e);
+        }
+    }
+
+    /// Send compose data from a specific account
+    async fn send_from_account(
+        &self,
+        account_id: &str,
+        compose_data: &crate::ui::EmailComposeData,
+    ) -> Result<crate::smtp::SendResult> {
+        let smtp_service = self
+            .smtp_service
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("SMTP service not initialized"))?;
+
+        let configs = self
+            .storage
+            .load_all_accounts()
+            .map_err(|e| anyhow::anyhow!("Failed to load account configs: {}", e))?;
+        let config = configs
+            .iter()
+            .find(|config| config.account_id == account_id)
+            .ok_or_else(|| anyhow::anyhow!("Account {} not found", account_id))?;
+
+        if !smtp_service.is_account_configured(account_id).await {
+            self.initialize_smtp_for_account(account_id, config).await?;
+        }
+
+        smtp_service
+            .send_email(account_id, &config.email_address, compose_data)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    }
+
+    /// Handle forward message action
+    async fn handle_forward_message(&mut self, message_id: uuid::Uuid) -> Result<()> {
+        tracing::info!("Forward message triggered for ID: {}", message_id);
+        
+        // Load the full message from database
+        if let Some(database) = &self.database {
+            if let Some(stored_message) = database.get_message_by_id(message_id).await? {
+            // Start compose with forward
+            let compose_action = crate::ui::ComposeAction::StartForwardFromMessage(stored_message);
+                self.handle_compose_action(compose_action).await?;
+            } else {
+                tracing::error!("Message not found for ID: {}", message_id);
+            }
         } else {
-            self.ui.show_toast_error("Email operations service not available");
+            tracing::error!("Database not available");
         }
+        
         Ok(())
     }
 
-    /// Handle mark email as unread operation
-    async fn handle_mark_email_unread(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
+    /// Get the email operations service
+    pub fn email_operations_service(&self) -> Option<Arc<crate::email::EmailOperationsService>> {
+        self.email_operations_service.clone()
+    }
+
+    /// Handle delete email operation
+    async fn handle_delete_email(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
         if let Some(ref service) = self.email_operations_service {
-            match service.mark_email_unread_by_id(account_id, message_id, folder).await {
+            match service.delete_email_by_id(account_id, message_id, folder).await {
                 Ok(()) => {
-                    self.ui.show_toast_info("Email marked as unread");
-                    // Update the UI to reflect the change - need to add a method for this
-                    // For now, just refresh the folder
+                    self.ui.show_toast_info("Email deleted successfully");
+                    // Refresh the message list to reflect the change
                     if let Err(e) = self.handle_folder_force_refresh(folder).await {
-                        tracing::warn!("Failed to refresh folder after mark unread: {}", e);
+                        tracing::warn!("Failed to refresh folder after delete: {}", e);
                     }
                 }
                 Err(e) => {
-                    let error_msg = format!("Failed to mark email as unread: {}", e);
+                    let error_msg = format!("Failed to delete email: {}", e);
                     tracing::error!("{}", error_msg);
                     self.ui.show_toast_error(&error_msg);
                 }
@@ -4579,18 +6544,94 @@ impl App {
         Ok(())
     }
 
-    /// Handle toggle email flag operation
-    async fn handle_toggle_email_flag(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
+    /// Ask before permanently deleting a batch of messages
+    fn request_delete_messages(&mut self, account_id: String, message_ids: Vec<uuid::Uuid>) {
+        let total_size: usize = self
+            .ui
+            .message_list()
+            .messages()
+            .iter()
+            .filter(|message| message.message_id.is_some_and(|id| message_ids.contains(&id)))
+            .filter_map(|message| message.size)
+            .map(|size| size as usize)
+            .sum();
+        let mut count = if message_ids.len() == 1 {
+            "1 message".to_string()
+        } else {
+            format!("{} messages", message_ids.len())
+        };
+        if total_size > 0 {
+            count = format!(
+                "{} ({})",
+                count,
+                crate::ui::preview_limits::format_size(total_size)
+            );
+        }
+        let message = match self
+            .special_folders
+            .folder(&account_id, crate::email::SpecialFolder::Trash)
+        {
+            Some(trash) => format!("Move {} to {}?", count, trash),
+            None => format!("Permanently delete {}?", count),
+        };
+
+        self.ui.show_confirm(crate::ui::confirm_dialog::ConfirmDialog::new(
+            "Delete messages".to_string(),
+            message,
+            crate::ui::confirm_dialog::ConfirmAction::DeleteMessages {
+                account_id,
+                message_ids,
+            },
+        ));
+    }
+
+    /// Delete the confirmed messages from whichever folders they are in
+    async fn handle_delete_messages(&mut self, account_id: &str, message_ids: &[uuid::Uuid]) {
+        let Some(service) = self.email_operations_service.clone() else {
+            self.ui.show_toast_error("Email operations service not available");
+            return;
+        };
+
+        match service.delete_emails_by_ids(account_id, message_ids).await {
+            Ok(count) => {
+                self.ui.message_list_mut().clear_marks();
+                self.ui.show_toast_success(if count == 1 {
+                    "Deleted 1 message".to_string()
+                } else {
+                    format!("Deleted {} messages", count)
+                });
+            }
+            Err(e) => {
+                tracing::error!("Failed to delete messages: {}", e);
+                self.ui.show_toast_error(format!("Failed to delete messages: {}", e));
+            }
+        }
+        // Some may have gone even if a later folder failed
+        if let Err(e) = self.ui.message_list_mut().refresh_messages().await {
+            tracing::warn!("Failed to refresh messages after delete: {}", e);
+        }
+    }
+
+    /// Handle archive email operation
+    async fn handle_archive_email(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
         if let Some(ref service) = self.email_operations_service {
-            match service.toggle_email_flag_by_id(account_id, message_id, folder).await {
-                Ok(is_flagged) => {
-                    let status = if is_flagged { "flagged" } else { "unflagged" };
-                    self.ui.show_toast_info(&format!("Email {}", status));
-                    // Update the UI to reflect the change
-                    self.ui.message_list_mut().toggle_selected_important();
+            let message = match &self.database {
+                Some(database) => database.get_message_by_id(message_id).await.ok().flatten(),
+                None => None,
+            };
+            match service.archive_email_by_id(account_id, message_id, folder).await {
+                Ok(()) => {
+                    if let Some(message) = &message {
+                        self.invalidate_ai_summary(message).await;
+                    }
+                    self.ui.show_toast_info("Email archived successfully");
+                    // Refresh the message list to reflect the change
+                    if let Err(e) = self.handle_folder_force_refresh(folder).await {
+                        tracing::warn!("Failed to refresh folder after archive: {}", e);
+                    }
                 }
                 Err(e) => {
-                    let error_msg = format!("Failed to toggle email flag: {}", e);
+                    let error_msg = format!("Failed to archive email: {}", e);
                     tracing::error!("{}", error_msg);
                     self.ui.show_toast_error(&error_msg);
                 }
@@ -4598,7 +6639,1414 @@ impl App {
         } else {
             self.ui.show_toast_error("Email operations service not available");
         }
-        Ok(())
+        Ok(())
+    }
+
+    /// Add the sender of a message to the blocklist
+    async fn handle_block_sender(&mut self, message_id: uuid::Uuid) -> Result<()> {
+        let Some(database) = &self.database else {
+            self.ui.show_toast_error("Database not available");
+            return Ok(());
+        };
+        let Some(message) = database.get_message_by_id(message_id).await? else {
+            tracing::error!("Message not found for ID: {}", message_id);
+            return Ok(());
+        };
+
+        let sender = crate::email::sender_lists::extract_address(&message.from_addr).to_lowercase();
+        if sender.is_empty() {
+            self.ui.show_toast_warning("Message has no sender address");
+            return Ok(());
+        }
+        if !self.sender_lists.block(&sender) {
+            self.ui.show_toast_info(format!("{} is already blocked", sender));
+            return Ok(());
+        }
+
+        if let Err(e) = self.sender_lists.save().await {
+            tracing::error!("Failed to save sender lists: {}", e);
+            self.ui.show_toast_error(format!("Failed to save blocklist: {}", e));
+            return Ok(());
+        }
+        if let Some(sync_engine) = &self.sync_engine {
+            sync_engine.set_sender_lists(self.sender_lists.clone()).await;
+        }
+
+        self.ui.show_toast_success(format!(
+            "Blocked {}; future messages will be filtered on arrival",
+            sender
+        ));
+        Ok(())
+    }
+
+    /// Hide a message until `until`; it comes back unread in the INBOX
+    async fn handle_snooze_message(
+        &mut self,
+        account_id: &str,
+        message_id: uuid::Uuid,
+        until: chrono::DateTime<chrono::Utc>,
+    ) -> Result<()> {
+        let Some(database) = &self.database else {
+            self.ui.show_toast_error("Database not available");
+            return Ok(());
+        };
+
+        if let Err(e) = database.set_snoozed_until(message_id, Some(until)).await {
+            tracing::error!("Failed to snooze message {}: {}", message_id, e);
+            self.ui.show_toast_error(format!("Failed to snooze message: {}", e));
+            return Ok(());
+        }
+        tracing::info!("Snoozed message {} of {} until {}", message_id, account_id, until);
+
+        self.refresh_after_snooze_change().await;
+        self.ui.show_toast_success(format!(
+            "Snoozed until {}",
+            until.with_timezone(&chrono::Local).format("%a %-d %b %H:%M")
+        ));
+        Ok(())
+    }
+
+    /// Bring a snoozed message back to the INBOX now
+    async fn handle_wake_snoozed(&mut self, message_id: uuid::Uuid) -> Result<()> {
+        let (Some(database), Some(service)) =
+            (self.database.clone(), self.email_operations_service.clone())
+        else {
+            self.ui.show_toast_error("Email operations service not available");
+            return Ok(());
+        };
+        let Some(message) = database.get_message_by_id(message_id).await? else {
+            tracing::error!("Message not found for ID: {}", message_id);
+            return Ok(());
+        };
+
+        match crate::email::snooze::wake_message(&database, &service, &message).await {
+            Ok(()) => self.ui.show_toast_success("Message is back in INBOX"),
+            Err(e) => {
+                tracing::error!("Failed to wake snoozed message {}: {}", message_id, e);
+                self.ui.show_toast_error(format!("Failed to unsnooze message: {}", e));
+            }
+        }
+        self.refresh_after_snooze_change().await;
+        Ok(())
+    }
+
+    /// Show another tab of the INBOX
+    async fn handle_switch_inbox_tab(&mut self, tab: crate::email::InboxTab) {
+        self.ui.message_list_mut().set_inbox_tab(tab);
+        if let Err(e) = self.ui.message_list_mut().refresh_messages().await {
+            tracing::warn!("Failed to load {} tab: {}", tab, e);
+        }
+    }
+
+    /// Move a message's sender to another INBOX tab and have triage keep
+    /// their later mail there
+    async fn handle_move_to_inbox_tab(
+        &mut self,
+        message_id: uuid::Uuid,
+        tab: crate::email::InboxTab,
+    ) -> Result<()> {
+        let Some(database) = self.database.clone() else {
+            self.ui.show_toast_error("Database not available");
+            return Ok(());
+        };
+        let Some(message) = database.get_message_by_id(message_id).await? else {
+            tracing::error!("Message not found for ID: {}", message_id);
+            return Ok(());
+        };
+
+        if let Err(e) = crate::email::inbox_tabs::correct_inbox_tab(&database, &message, tab).await {
+            tracing::error!("Failed to move {} to the {} tab: {}", message.from_addr, tab, e);
+            self.ui.show_toast_error(format!("Failed to move message: {}", e));
+            return Ok(());
+        }
+        self.invalidate_ai_summary(&message).await;
+
+        if let Err(e) = self.ui.message_list_mut().refresh_messages().await {
+            tracing::warn!("Failed to refresh messages: {}", e);
+        }
+        self.ui.show_toast_success(format!(
+            "Mail from {} now goes to {}",
+            crate::email::inbox_tabs::override_sender(&message.from_addr),
+            tab
+        ));
+        Ok(())
+    }
+
+    /// Update the message list and folder counts after messages were snoozed or woken
+    async fn refresh_after_snooze_change(&mut self) {
+        if let Err(e) = self.ui.refresh_messages().await {
+            tracing::warn!("Failed to refresh messages: {}", e);
+        }
+        self.ui.refresh_snoozed_folder().await;
+        self.ui.refresh_all_inboxes_unread().await;
+    }
+
+    /// Queue a wake-up task for each account with snoozed messages that are due
+    ///
+    /// Checked once a minute, and right after startup so messages whose time
+    /// came while the app was closed return straight away.
+    async fn maybe_queue_snooze_wakes(&mut self) {
+        if self
+            .last_snooze_check
+            .is_some_and(|last| last.elapsed() < Duration::from_secs(60))
+        {
+            return;
+        }
+        let (Some(database), Some(_)) = (self.database.clone(), self.background_processor.as_ref())
+        else {
+            return;
+        };
+        self.last_snooze_check = Some(Instant::now());
+
+        let account_ids = match database.get_accounts_with_due_snoozes(chrono::Utc::now()).await {
+            Ok(account_ids) => account_ids,
+            Err(e) => {
+                tracing::warn!("Failed to look for due snoozed messages: {}", e);
+                return;
+            }
+        };
+        use crate::performance::background_processor::{BackgroundTaskType, TaskPriority};
+        for account_id in account_ids {
+            let task = BackgroundTask {
+                id: Uuid::new_v4(),
+                name: format!("Wake snoozed messages of {}", account_id),
+                priority: TaskPriority::Normal,
+                account_id,
+                folder_name: None,
+                task_type: BackgroundTaskType::SnoozeWake,
+                created_at: std::time::Instant::now(),
+                estimated_duration: Some(std::time::Duration::from_secs(5)),
+            };
+            if let Err(e) = self.queue_background_task(task).await {
+                tracing::warn!("Failed to queue snooze wake-up: {}", e);
+            }
+        }
+    }
+
+    /// Queue a send task for each scheduled draft whose time has come
+    ///
+    /// Checked once a minute and right after startup, so sends that fell due
+    /// while the app was closed go out on launch. Failed sends come back here
+    /// once their retry time has passed.
+    async fn maybe_queue_scheduled_sends(&mut self) {
+        if self
+            .last_scheduled_send_check
+            .is_some_and(|last| last.elapsed() < Duration::from_secs(60))
+        {
+            return;
+        }
+        let (Some(database), Some(processor), Some(smtp_service)) = (
+            self.database.clone(),
+            self.background_processor.clone(),
+            self.smtp_service.clone(),
+        ) else {
+            return;
+        };
+        self.last_scheduled_send_check = Some(Instant::now());
+
+        let now = chrono::Utc::now();
+        let due = match database.get_due_scheduled_drafts(now).await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::warn!("Failed to look for due scheduled sends: {}", e);
+                return;
+            }
+        };
+        if due.is_empty() {
+            return;
+        }
+        let configs = match self.storage.load_all_accounts() {
+            Ok(configs) => configs,
+            Err(e) => {
+                tracing::warn!("Failed to load account configs for scheduled sends: {}", e);
+                return;
+            }
+        };
+        processor.set_smtp_service(smtp_service.clone()).await;
+
+        use crate::performance::background_processor::{BackgroundTaskType, TaskPriority};
+        for draft in due {
+            if self.scheduled_sends_in_flight.contains(&draft.id) {
+                continue;
+            }
+            let Some(config) = configs.iter().find(|config| config.account_id == draft.account_id) else {
+                tracing::warn!("Scheduled send {} is for unknown account {}", draft.id, draft.account_id);
+                continue;
+            };
+            if !smtp_service.is_account_configured(&config.account_id).await {
+                if let Err(e) = self.initialize_smtp_for_account(&config.account_id, config).await {
+                    // Counts as a failed attempt, so retries back off
+                    let next_attempt = now + crate::smtp::scheduled::retry_delay(draft.send_attempts + 1);
+                    if let Err(e) = database
+                        .record_scheduled_send_failure(&draft.id, &e.to_string(), next_attempt)
+                        .await
+                    {
+                        tracing::warn!("Failed to record scheduled send failure: {}", e);
+                    }
+                    continue;
+                }
+            }
+
+            let task = BackgroundTask {
+                id: Uuid::new_v4(),
+                name: format!("Send scheduled message: {}", draft.subject),
+                priority: TaskPriority::High,
+                account_id: draft.account_id.clone(),
+                folder_name: None,
+                task_type: BackgroundTaskType::ScheduledSend {
+                    draft_id: draft.id.clone(),
+                    from_address: config.email_address.clone(),
+                },
+                created_at: std::time::Instant::now(),
+                estimated_duration: Some(std::time::Duration::from_secs(10)),
+            };
+            match self.queue_background_task(task).await {
+                Ok(_) => {
+                    self.scheduled_sends_in_flight.insert(draft.id);
+                }
+                Err(e) => tracing::warn!("Failed to queue scheduled send: {}", e),
+            }
+        }
+    }
+
+    /// Queue a check for event reminders that are due
+    ///
+    /// Checked every half minute and right after startup, so reminders that
+    /// fell due while the app was closed go off on launch.
+    async fn maybe_queue_calendar_reminders(&mut self) {
+        if self
+            .last_reminder_check
+            .is_some_and(|last| last.elapsed() < Duration::from_secs(30))
+        {
+            return;
+        }
+        let (Some(calendar_manager), Some(processor)) =
+            (self.calendar_manager.clone(), self.background_processor.clone())
+        else {
+            return;
+        };
+        self.last_reminder_check = Some(Instant::now());
+        processor.set_calendar_manager(calendar_manager).await;
+
+        use crate::performance::background_processor::{BackgroundTaskType, TaskPriority};
+        let task = BackgroundTask {
+            id: Uuid::new_v4(),
+            name: "Check event reminders".to_string(),
+            priority: TaskPriority::Normal,
+            account_id: "calendar".to_string(),
+            folder_name: None,
+            task_type: BackgroundTaskType::CalendarReminders,
+            created_at: std::time::Instant::now(),
+            estimated_duration: Some(std::time::Duration::from_secs(1)),
+        };
+        if let Err(e) = self.queue_background_task(task).await {
+            tracing::warn!("Failed to queue event reminder check: {}", e);
+        }
+    }
+
+    /// Show a reminder that went off, on the desktop and in a toast
+    fn notify_reminder(&mut self, reminder: crate::calendar::DueReminder) {
+        use crate::notifications::types::CalendarEventType;
+        use crate::notifications::{NotificationEvent, NotificationPriority};
+
+        let minutes_until = reminder.minutes_until(chrono::Utc::now());
+        if let Some(unified_manager) = &self.unified_notification_manager {
+            let notification = NotificationEvent::Calendar {
+                event_type: CalendarEventType::EventReminder { minutes_until },
+                calendar_id: reminder.event.calendar_id.clone(),
+                event: Some(reminder.event.clone()),
+                event_id: Some(reminder.event.id.clone()),
+                priority: if minutes_until <= 5 {
+                    NotificationPriority::High
+                } else {
+                    NotificationPriority::Normal
+                },
+            };
+            if let Err(e) = unified_manager.get_sender().send(notification) {
+                tracing::warn!("Failed to send event reminder notification: {}", e);
+            }
+        }
+
+        self.ui.show_toast_info(if minutes_until > 0 {
+            format!(
+                "{} starts in {} min (Ctrl+B to snooze)",
+                reminder.event.title, minutes_until
+            )
+        } else {
+            format!("{} is starting (Ctrl+B to snooze)", reminder.event.title)
+        });
+        self.last_reminder = Some(reminder);
+    }
+
+    /// Move the selected or marked messages to the junk folder
+    async fn handle_report_spam(&mut self, account_id: &str, message_ids: &[uuid::Uuid], folder: &str) -> Result<()> {
+        let Some(service) = self.email_operations_service.clone() else {
+            self.ui.show_toast_error("Email operations service not available");
+            return Ok(());
+        };
+
+        match service.report_spam_by_ids(account_id, message_ids, folder).await {
+            Ok(count) => {
+                self.ui.message_list_mut().clear_marks();
+                self.ui.show_toast_success(if count == 1 {
+                    "Reported 1 message as spam".to_string()
+                } else {
+                    format!("Reported {} messages as spam", count)
+                });
+                if let Err(e) = self.handle_folder_force_refresh(folder).await {
+                    tracing::warn!("Failed to refresh folder after spam report: {}", e);
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to report spam: {}", e);
+                self.ui.show_toast_error(format!("Failed to report spam: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the selected or marked messages back to the inbox and allowlist their senders
+    async fn handle_report_not_spam(&mut self, account_id: &str, message_ids: &[uuid::Uuid], folder: &str) -> Result<()> {
+        let Some(service) = self.email_operations_service.clone() else {
+            self.ui.show_toast_error("Email operations service not available");
+            return Ok(());
+        };
+
+        let senders = match service.report_not_spam_by_ids(account_id, message_ids, folder).await {
+            Ok(senders) => senders,
+            Err(e) => {
+                tracing::error!("Failed to report not spam: {}", e);
+                self.ui.show_toast_error(format!("Failed to report not spam: {}", e));
+                return Ok(());
+            }
+        };
+        self.ui.message_list_mut().clear_marks();
+
+        for sender in &senders {
+            self.sender_lists.allow(sender);
+        }
+        if let Err(e) = self.sender_lists.save().await {
+            tracing::error!("Failed to save sender lists: {}", e);
+        }
+        if let Some(sync_engine) = &self.sync_engine {
+            sync_engine.set_sender_lists(self.sender_lists.clone()).await;
+        }
+
+        self.ui.show_toast_success(format!(
+            "Moved {} to Inbox and allowlisted {} sender{}",
+            if message_ids.len() == 1 { "1 message".to_string() } else { format!("{} messages", message_ids.len()) },
+            senders.len(),
+            if senders.len() == 1 { "" } else { "s" }
+        ));
+        if let Err(e) = self.handle_folder_force_refresh(folder).await {
+            tracing::warn!("Failed to refresh folder after not-spam report: {}", e);
+        }
+        Ok(())
+    }
+
+    /// Handle mark email as read operation
+    async fn handle_mark_email_read(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
+        if let Some(ref service) = self.email_operations_service {
+            match service.mark_email_read_by_id(account_id, message_id, folder).await {
+                Ok(()) => {
+                    self.ui.show_toast_info("Email marked as read");
+                    // Update the UI to reflect the change
+                    self.ui.message_list_mut().mark_selected_as_read();
+                    self.ui.refresh_all_inboxes_unread().await;
+                }
+                Err(e) => {
+                    let error_msg = format!("Failed to mark email as read: {}", e);
+                    tracing::error!("{}", error_msg);
+                    self.ui.show_toast_error(&error_msg);
+                }
+            }
+        } else {
+            self.ui.show_toast_error("Email operations service not available");
+        }
+        Ok(())
+    }
+
+    /// Mark every message of a thread read or unread in one IMAP round trip
+    ///
+    /// `announce` shows a toast; automatic marking on open stays quiet.
+    async fn handle_set_thread_read(
+        &mut self,
+        account_id: &str,
+        folder: &str,
+        thread_id: &str,
+        read: bool,
+        announce: bool,
+    ) {
+        let Some(service) = self.email_operations_service.clone() else {
+            self.ui.show_toast_error("Email operations service not available");
+            return;
+        };
+
+        let message_ids = self.ui.message_list().thread_message_ids(thread_id);
+        match service
+            .set_read_status_by_ids(account_id, &message_ids, folder, read)
+            .await
+        {
+            Ok(changed) => {
+                self.ui.message_list_mut().set_thread_read(thread_id, read);
+                self.ui.refresh_all_inboxes_unread().await;
+                if announce {
+                    self.ui.show_toast_info(format!(
+                        "Marked {} messages in thread as {}",
+                        changed,
+                        if read { "read" } else { "unread" }
+                    ));
+                }
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to update thread: {}", e);
+                tracing::error!("{}", error_msg);
+                self.ui.show_toast_error(&error_msg);
+            }
+        }
+    }
+
+    /// Handle mark email as unread operation
+    async fn handle_mark_email_unread(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
+        if let Some(ref service) = self.email_operations_service {
+            match service.mark_email_unread_by_id(account_id, message_id, folder).await {
+                Ok(()) => {
+                    self.ui.show_toast_info("Email marked as unread");
+                    self.ui.refresh_all_inboxes_unread().await;
+                    // Update the UI to reflect the change - need to add a method for this
+                    // For now, just refresh the folder
+                    if let Err(e) = self.handle_folder_force_refresh(folder).await {
+                        tracing::warn!("Failed to refresh folder after mark unread: {}", e);
+                    }
+                }
+                Err(e) => {
+                    let error_msg = format!("Failed to mark email as unread: {}", e);
+                    tracing::error!("{}", error_msg);
+                    self.ui.show_toast_error(&error_msg);
+                }
+            }
+        } else {
+            self.ui.show_toast_error("Email operations service not available");
+        }
+        Ok(())
+    }
+
+    /// Handle toggle email flag operation
+    async fn handle_toggle_email_flag(&mut self, account_id: &str, message_id: uuid::Uuid, folder: &str) -> Result<()> {
+        if let Some(ref service) = self.email_operations_service {
+            match service.toggle_email_flag_by_id(account_id, message_id, folder).await {
+                Ok(is_flagged) => {
+                    let status = if is_flagged { "flagged" } else { "unflagged" };
+                    self.ui.show_toast_info(&format!("Email {}", status));
+                    // Update the UI to reflect the change
+                    self.ui.message_list_mut().toggle_selected_important();
+                }
+                Err(e) => {
+                    let error_msg = format!("Failed to toggle email flag: {}", e);
+                    tracing::error!("{}", error_msg);
+                    self.ui.show_toast_error(&error_msg);
+                }
+            }
+        } else {
+            self.ui.show_toast_error("Email operations service not available");
+        }
+        Ok(())
+    }
+
+    /// Open the capabilities and TLS popup for an account
+    async fn handle_show_account_details(&mut self, account_id: &str) {
+        let Some(ref imap_manager) = self.imap_manager else {
+            self.ui.show_toast_warning("IMAP is not initialized yet");
+            return;
+        };
+        let Some(account) = imap_manager.get_account(account_id).await else {
+            self.ui.show_toast_warning(format!("Account {} not found", account_id));
+            return;
+        };
+
+        // Uses the pooled connection only; never connects from the UI thread
+        let details = imap_manager.connection_details(account_id).await;
+        let view = crate::ui::account_details::AccountDetailsView::new(
+            account_id.to_string(),
+            account.display_name.clone(),
+            format!("{}:{}", account.config.hostname, account.config.port),
+            details,
+            &self.capability_overrides.disabled_for(account_id),
+        );
+        self.ui.show_account_details(view);
+    }
+
+    /// Persist a capability override and apply it to the account's connection
+    async fn handle_set_capability_disabled(
+        &mut self,
+        account_id: &str,
+        capability: &str,
+        disabled: bool,
+    ) {
+        self.capability_overrides
+            .set_disabled(account_id, capability, disabled);
+        if let Err(e) = self.capability_overrides.save().await {
+            tracing::error!("Failed to save IMAP capability overrides: {}", e);
+            self.ui.show_toast_error("Failed to save capability override");
+        }
+
+        if let Some(ref imap_manager) = self.imap_manager {
+            if let Err(e) = imap_manager
+                .set_disabled_capabilities(
+                    account_id,
+                    self.capability_overrides.disabled_for(account_id),
+                )
+                .await
+            {
+                tracing::warn!("Failed to apply capability override: {}", e);
+            }
+        }
+
+        if disabled {
+            self.ui
+                .show_toast_info(format!("{} disabled for this account", capability));
+        } else {
+            self.ui
+                .show_toast_info(format!("{} enabled for this account", capability));
+        }
+    }
+
+    /// Open the special folder setup, suggesting the folders detected from
+    /// the folder list stored at the last sync
+    async fn handle_show_special_folders(&mut self, account_id: &str) {
+        let Some(ref database) = self.database else {
+            self.ui.show_toast_warning("Database is not initialized yet");
+            return;
+        };
+        let stored = match database.get_folders(account_id).await {
+            Ok(stored) => stored,
+            Err(e) => {
+                tracing::error!("Failed to load folders for {}: {}", account_id, e);
+                self.ui.show_toast_error("Failed to load folders");
+                return;
+            }
+        };
+        if stored.is_empty() {
+            self.ui
+                .show_toast_warning("No folders synced for this account yet");
+            return;
+        }
+
+        let folders: Vec<crate::imap::ImapFolder> = stored
+            .iter()
+            .map(|folder| {
+                let mut imap_folder = crate::imap::ImapFolder::new(
+                    folder.name.clone(),
+                    folder.full_name.clone(),
+                );
+                imap_folder.attributes = folder
+                    .attributes
+                    .iter()
+                    // Stored as variant names ("Sent"); raw IMAP flags also work
+                    .map(|attribute| {
+                        crate::imap::FolderAttribute::from_str(&format!(
+                            "\\{}",
+                            attribute.trim_start_matches('\\')
+                        ))
+                    })
+                    .collect();
+                imap_folder
+            })
+            .collect();
+        let detected = crate::email::SpecialFolderMapping::detect(&folders);
+        let mut names: Vec<String> = folders.into_iter().map(|folder| folder.full_name).collect();
+        names.sort();
+
+        let account_name = match self.imap_manager {
+            Some(ref imap_manager) => imap_manager
+                .get_account(account_id)
+                .await
+                .map(|account| account.display_name.clone()),
+            None => None,
+        }
+        .unwrap_or_else(|| account_id.to_string());
+
+        let view = crate::ui::special_folders_view::SpecialFoldersView::new(
+            account_id.to_string(),
+            account_name,
+            names,
+            &detected,
+            &self.special_folders.mapping_for(account_id),
+        );
+        self.ui.show_special_folders(view);
+    }
+
+    /// Persist an account's special folder mapping and apply it
+    async fn handle_set_special_folders(
+        &mut self,
+        account_id: &str,
+        mapping: crate::email::SpecialFolderMapping,
+    ) {
+        self.special_folders.set_mapping(account_id, mapping);
+        if let Err(e) = self.special_folders.save().await {
+            tracing::error!("Failed to save special folder mapping: {}", e);
+            self.ui.show_toast_error("Failed to save special folder mapping");
+        }
+
+        if let Some(ref service) = self.email_operations_service {
+            service.set_special_folders(self.special_folders.clone()).await;
+        }
+        if let Some(ref sync_engine) = self.sync_engine {
+            sync_engine
+                .set_special_folders(self.special_folders.clone())
+                .await;
+        }
+    }
+
+    fn retention_service(&self) -> Option<crate::email::RetentionService> {
+        match (&self.database, &self.email_operations_service) {
+            (Some(database), Some(operations)) => Some(crate::email::RetentionService::new(
+                database.clone(),
+                operations.clone(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Start a retention pass in the background when one is due
+    async fn maybe_start_retention_run(&mut self) {
+        if !self.retention_config.is_active() || self.retention_run.is_some() {
+            return;
+        }
+        let interval = Duration::from_secs(self.retention_config.interval_minutes.max(1) * 60);
+        if self
+            .last_retention_run
+            .is_some_and(|last| last.elapsed() < interval)
+        {
+            return;
+        }
+        let (Some(service), Some(imap_manager)) = (self.retention_service(), self.imap_manager.as_ref())
+        else {
+            return;
+        };
+
+        let account_ids: Vec<String> = imap_manager
+            .get_all_accounts()
+            .await
+            .into_iter()
+            .map(|account| account.account_id)
+            .collect();
+        let config = self.retention_config.clone();
+        self.retention_run = Some(tokio::spawn(async move {
+            service.run(&config, &account_ids, chrono::Utc::now()).await
+        }));
+        self.last_retention_run = Some(Instant::now());
+    }
+
+    /// Report a finished retention pass
+    async fn poll_retention_run(&mut self) {
+        if !self
+            .retention_run
+            .as_ref()
+            .map(|handle| handle.is_finished())
+            .unwrap_or(false)
+        {
+            return;
+        }
+
+        let Some(handle) = self.retention_run.take() else {
+            return;
+        };
+
+        match handle.await {
+            Ok(Ok(crate::email::RetentionOutcome::Preview(plan))) => {
+                // Ask once per session; the preview stays available under Alt+D
+                if !plan.is_empty() && self.retention_preview.is_none() {
+                    self.ui.show_toast_info(format!(
+                        "Retention rules would remove {} messages. Press Alt+D to review",
+                        plan.len()
+                    ));
+                }
+                self.retention_preview = Some(plan);
+            }
+            Ok(Ok(crate::email::RetentionOutcome::Applied {
+                soft_deleted,
+                expunged,
+            })) => {
+                if soft_deleted > 0 {
+                    self.ui.show_toast_info(format!(
+                        "Retention: {} messages pending removal (Alt+D to restore)",
+                        soft_deleted
+                    ));
+                }
+                if soft_deleted > 0 || expunged > 0 {
+                    if let Err(e) = self.ui.message_list_mut().refresh_messages().await {
+                        tracing::warn!("Failed to refresh folder after retention: {}", e);
+                    }
+                }
+            }
+            Ok(Err(e)) => tracing::warn!("Retention pass failed: {}", e),
+            Err(e) => tracing::warn!("Retention task failed: {}", e),
+        }
+    }
+
+    /// Show the dry-run preview, or the messages still within their grace window
+    async fn handle_show_retention(&mut self) {
+        if self.retention_config.rules.is_empty() {
+            self.ui
+                .show_toast_info("No retention rules configured (see retention.toml)");
+            return;
+        }
+
+        let pending = match self.database {
+            Some(ref database) => match database.get_retention_pending().await {
+                Ok(pending) => pending,
+                Err(e) => {
+                    tracing::error!("Failed to load pending retention: {}", e);
+                    Vec::new()
+                }
+            },
+            None => Vec::new(),
+        };
+        let preview = if self.retention_config.preview_accepted {
+            None
+        } else {
+            Some(self.retention_preview.clone().unwrap_or_default())
+        };
+        self.ui.show_retention(crate::ui::retention_view::RetentionView::new(
+            preview,
+            pending,
+            self.retention_config.grace_days,
+        ));
+    }
+
+    /// Leave dry-run mode and apply the rules on the next pass
+    async fn handle_accept_retention_preview(&mut self) {
+        self.retention_config.preview_accepted = true;
+        if let Err(e) = self.retention_config.save().await {
+            tracing::error!("Failed to save retention config: {}", e);
+            self.ui.show_toast_error("Failed to save retention settings");
+        }
+        self.retention_preview = None;
+        // Run right away rather than waiting out the interval
+        self.last_retention_run = None;
+        self.ui.hide_retention();
+        self.ui.show_toast_info(format!(
+            "Retention rules enabled; removed messages can be restored for {} days",
+            self.retention_config.grace_days
+        ));
+    }
+
+    /// Undo a retention soft delete
+    async fn handle_restore_retained(&mut self, message_id: uuid::Uuid) {
+        let Some(service) = self.retention_service() else {
+            self.ui.show_toast_error("Email operations service not available");
+            return;
+        };
+
+        match service.restore(message_id).await {
+            Ok(()) => {
+                if let Some(view) = self.ui.retention_view_mut() {
+                    view.remove_pending(message_id);
+                }
+                if let Err(e) = self.ui.message_list_mut().refresh_messages().await {
+                    tracing::warn!("Failed to refresh folder after restore: {}", e);
+                }
+                self.ui.show_toast_info("Message restored");
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to restore message: {}", e);
+                tracing::error!("{}", error_msg);
+                self.ui.show_toast_error(&error_msg);
+            }
+        }
+    }
+
+    /// Show the filter rules from filters.toml
+    /// Open the mbox wizard on the current account
+    async fn handle_show_mbox_wizard(&mut self) {
+        if self.mbox_run.is_some() {
+            self.ui
+                .show_toast_info("An mbox import or export is still running");
+            return;
+        }
+        let accounts = self
+            .ui
+            .account_switcher()
+            .accounts()
+            .iter()
+            .map(|account| (account.account_id.clone(), account.display_name.clone()))
+            .collect();
+        let current = self.ui.get_current_account_id().cloned();
+        self.ui
+            .show_mbox_wizard(crate::ui::mbox_wizard::MboxWizard::new(
+                accounts,
+                current.as_deref(),
+            ));
+    }
+
+    async fn handle_mbox_wizard_action(
+        &mut self,
+        action: crate::ui::mbox_wizard::MboxWizardAction,
+    ) {
+        use crate::ui::mbox_wizard::MboxWizardAction;
+
+        let Some(database) = self.database.clone() else {
+            self.ui.show_toast_error("Database not available");
+            self.ui.hide_mbox_wizard();
+            return;
+        };
+
+        match action {
+            MboxWizardAction::Scan(path) => {
+                let result = crate::email::mbox::scan(&path).await;
+                let Some(wizard) = self.ui.mbox_wizard_mut() else {
+                    return;
+                };
+                match result {
+                    Ok(mailboxes) if mailboxes.is_empty() => {
+                        wizard.set_error(format!("No mbox files found in {}", path.display()))
+                    }
+                    Ok(mailboxes) => wizard.set_mailboxes(mailboxes),
+                    Err(e) => wizard.set_error(e.to_string()),
+                }
+            }
+            MboxWizardAction::LoadFolders(account_id) => {
+                let result = database.get_folder_message_counts(&account_id).await;
+                let Some(wizard) = self.ui.mbox_wizard_mut() else {
+                    return;
+                };
+                match result {
+                    Ok(folders) => wizard.set_folders(folders),
+                    Err(e) => wizard.set_error(format!("Failed to load folders: {}", e)),
+                }
+            }
+            MboxWizardAction::Import {
+                account_id,
+                targets,
+            } => {
+                let (progress_tx, progress_rx) = tokio::sync::mpsc::unbounded_channel();
+                self.mbox_cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+                let cancel = self.mbox_cancel.clone();
+                self.mbox_progress = Some(progress_rx);
+                self.mbox_import_account = Some(account_id.clone());
+                self.mbox_run = Some(tokio::spawn(async move {
+                    crate::email::MboxImporter::new(database)
+                        .import(&account_id, &targets, true, &progress_tx, &cancel)
+                        .await
+                }));
+            }
+            MboxWizardAction::Export {
+                account_id,
+                folders,
+                directory,
+            } => {
+                let (progress_tx, progress_rx) = tokio::sync::mpsc::unbounded_channel();
+                self.mbox_cancel = Arc::new(std::sync::atomic::AtomicBool::new(false));
+                let cancel = self.mbox_cancel.clone();
+                self.mbox_progress = Some(progress_rx);
+                self.mbox_import_account = None;
+                self.mbox_run = Some(tokio::spawn(async move {
+                    crate::email::MboxExporter::new(database)
+                        .export(&account_id, &folders, &directory, &progress_tx, &cancel)
+                        .await
+                }));
+            }
+            MboxWizardAction::Cancel => {
+                self.mbox_cancel
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+            MboxWizardAction::Continue | MboxWizardAction::Close => {}
+        }
+    }
+
+    /// Show progress of a running mbox import or export in the wizard
+    async fn poll_mbox_run(&mut self) {
+        let mut latest = None;
+        if let Some(ref mut progress) = self.mbox_progress {
+            while let Ok(update) = progress.try_recv() {
+                latest = Some(update);
+            }
+        }
+        if let (Some(progress), Some(wizard)) = (latest, self.ui.mbox_wizard_mut()) {
+            wizard.set_progress(progress);
+        }
+
+        if !self
+            .mbox_run
+            .as_ref()
+            .map(|handle| handle.is_finished())
+            .unwrap_or(false)
+        {
+            return;
+        }
+        let Some(handle) = self.mbox_run.take() else {
+            return;
+        };
+        self.mbox_progress = None;
+
+        let outcome = match handle.await {
+            Ok(Ok(outcome)) => Ok(outcome),
+            Ok(Err(e)) => {
+                tracing::error!("Mbox import or export failed: {}", e);
+                Err(e.to_string())
+            }
+            Err(e) => {
+                tracing::warn!("Mbox task failed: {}", e);
+                Err(format!("Mbox task failed: {}", e))
+            }
+        };
+
+        if let Some(account_id) = self.mbox_import_account.take() {
+            if let Err(e) = self.ui.load_folders(&account_id).await {
+                tracing::error!("Failed to reload folders: {}", e);
+            }
+        }
+
+        match self.ui.mbox_wizard_mut() {
+            Some(wizard) => wizard.finish(outcome),
+            None => match outcome {
+                Ok(outcome) => self
+                    .ui
+                    .show_toast_success(format!("Copied {} messages", outcome.messages)),
+                Err(e) => self.ui.show_toast_error(e),
+            },
+        }
+    }
+
+    fn handle_show_filter_rules(&mut self) {
+        if self.filter_rules.rules.is_empty() {
+            self.ui
+                .show_toast_info("No filter rules configured (see filters.toml)");
+            return;
+        }
+        self.ui
+            .show_filter_rules(crate::ui::filter_rules_view::FilterRulesView::new(
+                self.filter_rules.rules.clone(),
+            ));
+    }
+
+    /// Show which recent messages of the current folder a rule matches
+    async fn handle_test_filter_rule(&mut self, filter_id: uuid::Uuid) {
+        const TEST_MESSAGES: u32 = 500;
+
+        let Some(rule) = self
+            .filter_rules
+            .rules
+            .iter()
+            .find(|rule| rule.id == filter_id)
+            .cloned()
+        else {
+            return;
+        };
+        let (Some(account_id), Some(folder)) = (
+            self.ui.message_list().current_account().cloned(),
+            self.ui.message_list().current_folder().cloned(),
+        ) else {
+            self.ui.show_toast_info("Open a folder to test the rule on");
+            return;
+        };
+        let Some(database) = self.database.clone() else {
+            self.ui.show_toast_error("Database not available");
+            return;
+        };
+
+        let messages = match database
+            .get_messages(&account_id, &folder, Some(TEST_MESSAGES), None)
+            .await
+        {
+            Ok(messages) => messages,
+            Err(e) => {
+                tracing::error!("Failed to load messages to test filter rule: {}", e);
+                self.ui
+                    .show_toast_error("Failed to load the folder's messages");
+                return;
+            }
+        };
+        let engine = crate::email::FilterEngine::new();
+        let mut matches = Vec::new();
+        for message in &messages {
+            let headers = database
+                .get_raw_headers(message.id)
+                .await
+                .unwrap_or_default();
+            if engine.test_filter(&rule, message, headers.as_deref()) {
+                matches.push(message.clone());
+            }
+        }
+
+        if let Some(view) = self.ui.filter_rules_view_mut() {
+            view.set_test(crate::ui::filter_rules_view::RuleTest {
+                filter_id,
+                folder,
+                checked: messages.len(),
+                matches,
+            });
+        }
+    }
+
+    /// Switch a rule on or off for incoming mail and save filters.toml
+    async fn handle_toggle_filter_rule(&mut self, filter_id: uuid::Uuid) {
+        let Some(enabled) = self.filter_rules.toggle(filter_id) else {
+            return;
+        };
+        if let Err(e) = self.filter_rules.save().await {
+            tracing::error!("Failed to save filter rules: {}", e);
+            self.ui.show_toast_error("Failed to save filter rules");
+        }
+        if let Some(ref sync_engine) = self.sync_engine {
+            sync_engine
+                .set_filter_rules(self.filter_rules.clone())
+                .await;
+        }
+        if let Some(view) = self.ui.filter_rules_view_mut() {
+            view.set_enabled(filter_id, enabled);
+        }
+        self.ui.show_toast_info(if enabled {
+            "Rule enabled for incoming mail"
+        } else {
+            "Rule disabled"
+        });
+    }
+
+    /// Refresh the today panel's agenda, unread counts and system stats
+    ///
+    /// Weather is fetched in the background and shown by `poll_weather_fetch`.
+    /// Switch the message list between the unified view and a single folder
+    async fn handle_toggle_unified_view(&mut self) {
+        match self.ui.toggle_unified_view().await {
+            Ok(()) => {
+                let message = if self.ui.message_list().is_unified() {
+                    "Showing all folders"
+                } else {
+                    "Showing current folder"
+                };
+                self.ui.show_toast_info(message);
+            }
+            Err(e) => {
+                tracing::error!("Failed to toggle unified view: {}", e);
+                self.ui.show_toast_error(format!("Failed to switch view: {}", e));
+            }
+        }
+    }
+
+    /// Switch the message list between the largest messages of a folder or
+    /// account and the folder shown before
+    async fn handle_toggle_largest_messages(&mut self, folder: Option<String>) {
+        match self.ui.toggle_largest_messages(folder).await {
+            Ok(()) => match self.ui.message_list().largest_scope() {
+                Some(_) if self.ui.message_list().messages().is_empty() => {
+                    self.ui.show_toast_info("No message sizes known yet; sync to record them");
+                }
+                Some(_) => self.ui.show_toast_info("Showing largest messages"),
+                None => self.ui.show_toast_info("Showing current folder"),
+            },
+            Err(e) => {
+                tracing::error!("Failed to toggle largest messages: {}", e);
+                self.ui.show_toast_error(format!("Failed to switch view: {}", e));
+            }
+        }
+    }
+
+    async fn refresh_today_panel(&mut self) {
+        let today = chrono::Local::now().date_naive();
+        let agenda =
+            crate::ui::today_panel::todays_agenda(self.ui.calendar_ui().get_events(), today);
+        self.ui.today_panel_mut().set_agenda(agenda);
+
+        if let Some(ref database) = self.database {
+            match database.get_inbox_unread_counts().await {
+                Ok(counts) => self.ui.today_panel_mut().set_unread_counts(counts),
+                Err(e) => tracing::warn!("Failed to count unread messages: {}", e),
+            }
+        }
+
+        let stats = self
+            .today_panel_config
+            .show_system_stats
+            .then(|| self.services.system_stats.snapshot());
+        self.ui.today_panel_mut().set_system_stats(stats);
+
+        if !self.today_panel_config.weather_enabled() {
+            self.ui
+                .today_panel_mut()
+                .set_weather(crate::ui::today_panel::WeatherState::Disabled);
+            return;
+        }
+
+        let refresh_interval =
+            Duration::from_secs(self.today_panel_config.weather_refresh_minutes.max(1) * 60);
+        let is_stale = self
+            .last_weather_fetch
+            .map(|fetched| fetched.elapsed() >= refresh_interval)
+            .unwrap_or(true);
+        if self.weather_fetch.is_none() && is_stale {
+            if self.last_weather_fetch.is_none() {
+                self.ui
+                    .today_panel_mut()
+                    .set_weather(crate::ui::today_panel::WeatherState::Loading);
+            }
+            let weather = self.services.weather.clone();
+            self.weather_fetch = Some(tokio::spawn(async move { weather.fetch_current().await }));
+            self.last_weather_fetch = Some(Instant::now());
+        }
+    }
+
+    /// Show the result of a finished weather fetch
+    async fn poll_weather_fetch(&mut self) {
+        if !self
+            .weather_fetch
+            .as_ref()
+            .map(|handle| handle.is_finished())
+            .unwrap_or(false)
+        {
+            return;
+        }
+
+        let Some(handle) = self.weather_fetch.take() else {
+            return;
+        };
+
+        let state = match handle.await {
+            Ok(Ok(report)) => crate::ui::today_panel::WeatherState::Ready(report),
+            Ok(Err(e)) => {
+                tracing::warn!("Weather fetch failed: {}", e);
+                crate::ui::today_panel::WeatherState::Failed(e.to_string())
+            }
+            Err(e) => {
+                tracing::warn!("Weather task failed: {}", e);
+                crate::ui::today_panel::WeatherState::Failed("Weather unavailable".to_string())
+            }
+        };
+        self.ui.today_panel_mut().set_weather(state);
+    }
+
+    /// Collect the result of the startup clock check and warn about significant skew
+    /// Lock once no key has been pressed for the idle timeout
+    ///
+    /// Without a master passphrase there is nothing to unlock with, so the
+    /// timer just starts over.
+    fn maybe_auto_lock(&mut self) {
+        let Some(timeout) = self.lock_config.idle_timeout() else {
+            return;
+        };
+        if self.ui.is_locked() || self.last_activity.elapsed() < timeout {
+            return;
+        }
+
+        if self.storage.has_master_passphrase() {
+            tracing::info!("Locking after {} minutes idle", self.lock_config.idle_timeout_mins);
+            self.ui.lock();
+        } else {
+            self.last_activity = Instant::now();
+        }
+    }
+
+    /// Lock now, at the user's request
+    fn handle_lock_request(&mut self) {
+        if self.storage.has_master_passphrase() {
+            self.ui.lock();
+        } else {
+            self.ui.show_toast_warning(
+                "Set a master passphrase with 'comunicado credentials passphrase' to lock",
+            );
+        }
+    }
+
+    fn handle_lock_screen_key(&mut self, key: crossterm::event::KeyEvent) {
+        use crate::ui::lock_screen::LockAction;
+
+        let Some(lock_screen) = self.ui.lock_screen_mut() else {
+            return;
+        };
+        match lock_screen.handle_key(key) {
+            LockAction::None => {}
+            LockAction::Submit(passphrase) => {
+                // Argon2 is slow on purpose; keep it off the UI thread
+                let storage = self.storage.clone();
+                self.unlock_check = Some(tokio::task::spawn_blocking(move || {
+                    storage.verify_master_passphrase(&passphrase)
+                }));
+            }
+            LockAction::Quit => self.should_quit = true,
+        }
+    }
+
+    async fn poll_unlock_check(&mut self) {
+        if !self
+            .unlock_check
+            .as_ref()
+            .map(|handle| handle.is_finished())
+            .unwrap_or(false)
+        {
+            return;
+        }
+
+        let Some(handle) = self.unlock_check.take() else {
+            return;
+        };
+
+        if handle.await.unwrap_or(false) {
+            tracing::info!("Unlocked");
+            self.ui.unlock();
+            self.last_activity = Instant::now();
+        } else if let Some(lock_screen) = self.ui.lock_screen_mut() {
+            tracing::warn!("Wrong passphrase entered on the lock screen");
+            lock_screen.reject();
+        }
+    }
+
+    /// Report what the token refresh scheduler did since the last loop
+    fn poll_token_refresh_events(&mut self) {
+        use crate::oauth2::TokenRefreshEvent;
+
+        let Some(events) = self.token_refresh_events.as_mut() else {
+            return;
+        };
+        let mut pending = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            pending.push(event);
+        }
+        if pending.is_empty() {
+            return;
+        }
+
+        for event in pending {
+            match event {
+                TokenRefreshEvent::Refreshing { account_id } => {
+                    self.refreshing_credentials.insert(account_id);
+                }
+                TokenRefreshEvent::Refreshed { account_id } => {
+                    self.refreshing_credentials.remove(&account_id);
+                }
+                TokenRefreshEvent::Failed { account_id, error } => {
+                    self.refreshing_credentials.remove(&account_id);
+                    self.ui.show_toast_warning(format!(
+                        "Couldn't refresh credentials for {}: {}",
+                        self.account_label(&account_id),
+                        error
+                    ));
+                }
+                TokenRefreshEvent::Revoked { account_id, reason } => {
+                    self.refreshing_credentials.remove(&account_id);
+                    tracing::warn!("Account {} needs to sign in again: {}", account_id, reason);
+                    self.ui.update_account_status(
+                        &account_id,
+                        crate::ui::AccountSyncStatus::Error,
+                        None,
+                    );
+                    // Stays up long enough to copy the command
+                    self.ui.show_custom_toast(
+                        format!(
+                            "Re-authenticate account {} ({}) - run 'comunicado oauth2 reauth {}'",
+                            self.account_label(&account_id),
+                            reason,
+                            account_id
+                        ),
+                        crate::tea::message::ToastLevel::Error,
+                        Duration::from_secs(30),
+                    );
+                }
+            }
+        }
+        self.ui.update_credentials_status(self.refreshing_credentials.len());
+    }
+
+    /// Email address of an account for messages, or its ID if it isn't loaded
+    fn account_label(&self, account_id: &str) -> String {
+        self.ui
+            .account_switcher()
+            .accounts()
+            .iter()
+            .find(|account| account.account_id == account_id)
+            .map(|account| account.email_address.clone())
+            .unwrap_or_else(|| account_id.to_string())
+    }
+
+    async fn poll_clock_check(&mut self) {
+        if !self
+            .clock_check
+            .as_ref()
+            .map(|handle| handle.is_finished())
+            .unwrap_or(false)
+        {
+            return;
+        }
+
+        let Some(handle) = self.clock_check.take() else {
+            return;
+        };
+
+        match handle.await {
+            Ok(Ok(skew)) => {
+                tracing::info!(
+                    "Clock offset against {}: {}s",
+                    skew.source,
+                    skew.offset.num_seconds()
+                );
+                if skew.exceeds(self.clock_check_config.warn_threshold_seconds) {
+                    tracing::warn!("{}", skew.warning_message());
+                    self.ui.show_toast_warning(skew.warning_message());
+                }
+                self.clock_skew = Some(skew);
+            }
+            Ok(Err(e)) => tracing::debug!("Clock check skipped: {}", e),
+            Err(e) => tracing::warn!("Clock check task failed: {}", e),
+        }
+    }
+
+    /// Measured clock skew, if it is large enough to break authentication
+    fn significant_clock_skew(&self) -> Option<&crate::startup::ClockSkew> {
+        self.clock_skew
+            .as_ref()
+            .filter(|skew| skew.exceeds(self.clock_check_config.warn_threshold_seconds))
+    }
+
+    /// Pause or resume the running sync for an account
+    async fn handle_toggle_sync_pause(&mut self, account_id: &str) {
+        let Some(sync_engine) = self.sync_engine.clone() else {
+            self.ui.show_toast_error("Sync engine not available");
+            return;
+        };
+
+        if sync_engine.is_sync_paused(account_id).await {
+            if sync_engine.resume_sync(account_id).await {
+                self.ui.show_toast_info(format!("Resumed sync for {}", account_id));
+            }
+        } else if sync_engine.pause_sync(account_id).await {
+            self.ui
+                .show_toast_info(format!("Pausing sync for {} after the current batch", account_id));
+        } else {
+            self.ui.show_toast_warning(format!("No running sync for {}", account_id));
+        }
+    }
+
+    /// Cancel the running sync for an account, keeping messages already synced
+    async fn handle_cancel_sync(&mut self, account_id: &str, folder: &str) {
+        let Some(sync_engine) = self.sync_engine.clone() else {
+            self.ui.show_toast_error("Sync engine not available");
+            return;
+        };
+
+        match sync_engine.cancel_sync(account_id, folder).await {
+            Ok(()) => {
+                self.ui.show_toast_info(format!(
+                    "Cancelled sync for {}; it will resume from here next time",
+                    account_id
+                ));
+            }
+            Err(e) => {
+                self.ui.show_toast_error(format!("Failed to cancel sync: {}", e));
+            }
+        }
+    }
+
+    /// Forget the cached AI summary of a message that was edited or moved
+    async fn invalidate_ai_summary(&self, message: &crate::email::StoredMessage) {
+        let Some(ai_config_manager) = &self.ai_config_manager else {
+            return;
+        };
+        let Some(ai_service) = ai_config_manager.get_ai_service().await else {
+            return;
+        };
+        if let Err(e) = ai_service.invalidate_message(&ai_message_id(message)).await {
+            tracing::warn!("Failed to drop cached AI summary of {}: {}", message.id, e);
+        }
     }
 
     /// Handle AI email summarization with real email content from database
@@ -4607,8 +8055,7 @@ impl App {
             match database.get_message_by_id(message_id).await {
                 Ok(Some(stored_message)) => {
                     // Convert StoredMessage to EmailMessage for AI processing
-                    let message_id_str = stored_message.message_id.unwrap_or_else(|| format!("msg_{}", message_id));
-                    let message_id_obj = crate::email::MessageId::new(message_id_str);
+                    let message_id_obj = crate::email::MessageId::new(ai_message_id(&stored_message));
                     
                     // Combine all recipients
                     let mut recipients = stored_message.to_addrs.clone();
@@ -4664,24 +8111,65 @@ impl App {
         Ok(())
     }
 
+    /// Summarize the thread the selected message belongs to
+    fn handle_ai_summarize_thread(&mut self, message_id: uuid::Uuid) {
+        match self.ui.message_list().thread_containing(message_id) {
+            Some(thread) => {
+                let count = thread.message_count();
+                self.ui.start_ai_thread_summarization(thread);
+                self.ui.show_toast_info(format!("Summarizing {} messages in thread...", count));
+            }
+            None => {
+                self.ui.show_toast_error("Thread not found for the selected message");
+            }
+        }
+    }
+
+    /// Open a reply to the message and have the AI draft its body
+    async fn handle_ai_draft_reply(&mut self, message_id: uuid::Uuid) -> Result<()> {
+        let (Some(database), Some(contacts_manager)) =
+            (self.database.clone(), self.contacts_manager.clone())
+        else {
+            tracing::error!("Cannot draft a reply: database or contacts not initialized");
+            return Ok(());
+        };
+        let Some(message) = database.get_message_by_id(message_id).await? else {
+            tracing::error!("Message not found for ID: {}", message_id);
+            return Ok(());
+        };
+
+        let headers = self.reply_headers(&message).await;
+        self.ui
+            .start_ai_reply_draft(message, &headers, contacts_manager);
+        self.ui.exit_email_viewer();
+        self.ui.show_toast_info("Drafting reply...");
+        Ok(())
+    }
+
     /// Handle creating a new calendar event
     async fn handle_create_event(&mut self, calendar_id: &str) -> Result<()> {
         if let Some(ref manager) = self.calendar_manager {
-            // Create a basic event template - in a real implementation this would open an event creation dialog
-            let event = crate::calendar::Event::new(
-                calendar_id.to_string(),
-                "New Event".to_string(),
-                chrono::Utc::now(),
-                chrono::Utc::now() + chrono::Duration::hours(1),
-            );
+            let calendars = manager.get_calendars().await;
+            self.ui
+                .start_event_create(calendars, Some(calendar_id.to_string()));
+        } else {
+            self.ui.show_toast_error("Calendar manager not available");
+        }
+        Ok(())
+    }
 
-            match manager.create_event(event).await {
-                Ok(_created_event) => {
-                    self.ui.show_toast_info("Event created successfully");
-                    // TODO: Refresh calendar view to show new event
+    /// Handle editing an existing calendar event
+    async fn handle_edit_event(&mut self, _calendar_id: &str, event_id: &str) -> Result<()> {
+        if let Some(ref manager) = self.calendar_manager {
+            // Occurrences of a series share its ID, so this edits the series
+            match manager.get_event(event_id).await {
+                Ok(Some(event)) => {
+                    let calendars = manager.get_calendars().await;
+                    self.ui.start_event_edit(event, calendars);
                 }
+                Ok(None) => self.ui.show_toast_error("Event not found"),
                 Err(e) => {
-                    let error_msg = format!("Failed to create event: {}", e);
+                    let error_msg = format!("Failed to load event: {}", e);
                     tracing::error!("{}", error_msg);
                     self.ui.show_toast_error(&error_msg);
                 }
@@ -4692,48 +8180,544 @@ impl App {
         Ok(())
     }
 
-    /// Handle editing an existing calendar event
-    async fn handle_edit_event(&mut self, _calendar_id: &str, event_id: &str) -> Result<()> {
-        if let Some(_manager) = &self.calendar_manager {
-            // TODO: Implement event editing dialog
-            // For now, just show a message that the feature is available
-            self.ui.show_toast_info(&format!("Edit event feature triggered for event: {}", event_id));
-            tracing::info!("Edit event feature would open dialog for event: {}", event_id);
+    /// Handle deleting a calendar event
+    async fn handle_delete_event(&mut self, _calendar_id: &str, event_id: &str) -> Result<()> {
+        if let Some(manager) = self.calendar_manager.clone() {
+            let result = manager.delete_event(event_id).await.map(|_| None);
+            self.finish_event_change(result, "Event deleted").await;
         } else {
             self.ui.show_toast_error("Calendar manager not available");
         }
         Ok(())
     }
 
-    /// Handle deleting a calendar event
-    async fn handle_delete_event(&mut self, _calendar_id: &str, event_id: &str) -> Result<()> {
-        if let Some(ref manager) = self.calendar_manager {
-            match manager.delete_event(event_id).await {
-                Ok(_was_deleted) => {
-                    self.ui.show_toast_info("Event deleted successfully");
-                    // TODO: Refresh calendar view to reflect deletion
-                }
-                Err(e) => {
-                    let error_msg = format!("Failed to delete event: {}", e);
-                    tracing::error!("{}", error_msg);
-                    self.ui.show_toast_error(&error_msg);
+    /// Handle save, delete and conflict choices from the event form
+    async fn handle_event_form_action(
+        &mut self,
+        action: crate::calendar::EventFormAction,
+    ) -> Result<()> {
+        use crate::calendar::{ConflictResolution, EventFormAction, EventFormMode};
+
+        let Some(manager) = self.calendar_manager.clone() else {
+            self.ui.show_toast_error("Calendar manager not available");
+            return Ok(());
+        };
+        let Some(form) = self.ui.event_form_ui_mut() else {
+            return Ok(());
+        };
+
+        match action {
+            EventFormAction::Cancel => self.ui.exit_event_form(),
+            EventFormAction::Save => {
+                if !form.validate() {
+                    self.ui
+                        .show_toast_warning("Fix the highlighted fields before saving");
+                    return Ok(());
                 }
+                let event = form.build_event();
+                let result = if matches!(form.mode, EventFormMode::Create) {
+                    manager.create_event(event).await
+                } else {
+                    manager.update_event(event).await
+                };
+                self.finish_event_change(result.map(Some), "Event saved").await;
             }
-        } else {
-            self.ui.show_toast_error("Calendar manager not available");
+            EventFormAction::Delete => {
+                let event_id = form.event.id.clone();
+                let result = manager.delete_event(&event_id).await.map(|_| None);
+                self.finish_event_change(result, "Event deleted").await;
+            }
+            EventFormAction::ResolveConflict(resolution) => {
+                let Some(conflict) = form.sync_conflict.take() else {
+                    return Ok(());
+                };
+                let done = match resolution {
+                    ConflictResolution::Merge => "Changes merged with the server version",
+                    ConflictResolution::Overwrite if conflict.deleted => "Event deleted",
+                    ConflictResolution::Overwrite => "Server version overwritten",
+                    ConflictResolution::KeepServer => "Kept the server version",
+                };
+                let result = manager.resolve_conflict(conflict, resolution).await;
+                self.finish_event_change(result, done).await;
+            }
+            // Attendee and recurrence edits stay within the form
+            _ => {}
         }
         Ok(())
     }
 
+    /// Report a saved or deleted event, or ask how to settle a sync conflict
+    async fn finish_event_change(
+        &mut self,
+        result: crate::calendar::CalendarResult<Option<crate::calendar::Event>>,
+        done: &str,
+    ) {
+        match result {
+            Ok(_) => {
+                self.ui.exit_event_form();
+                self.ui.show_toast_success(done);
+                if let Err(e) = self.refresh_calendar_data().await {
+                    tracing::warn!("Failed to refresh calendar after change: {}", e);
+                }
+            }
+            Err(crate::calendar::CalendarError::EditConflict(conflict)) => {
+                // A delete from the calendar view opens the event to ask
+                if self.ui.event_form_ui().is_none() {
+                    if let Some(ref manager) = self.calendar_manager {
+                        let calendars = manager.get_calendars().await;
+                        self.ui.start_event_edit(conflict.base.clone(), calendars);
+                    }
+                }
+                if let Some(form) = self.ui.event_form_ui_mut() {
+                    form.sync_conflict = Some(*conflict);
+                }
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to update calendar: {}", e);
+                tracing::error!("{}", error_msg);
+                self.ui.show_toast_error(&error_msg);
+            }
+        }
+    }
+
     /// Handle viewing event details
     async fn handle_view_event_details(&mut self, _calendar_id: &str, event_id: &str) -> Result<()> {
-        if let Some(_manager) = &self.calendar_manager {
-            // TODO: Implement event details popup
-            // For now, just show a message that the feature is available
-            self.ui.show_toast_info(&format!("View event details for: {}", event_id));
-            tracing::info!("Event details view would show details for event: {}", event_id);
+        if !self.ui.calendar_ui_mut().show_event_details_by_id(event_id) {
+            self.ui.show_toast_error("Event not found");
+        }
+        Ok(())
+    }
+
+    /// Start syncing SMS when it is enabled in the mobile settings
+    async fn start_sms(&mut self) {
+        let config = match crate::mobile::MobileConfig::load().await {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!("Failed to load mobile settings: {}", e);
+                return;
+            }
+        };
+        if !config.enabled || !config.sms.enabled {
+            return;
+        }
+
+        match crate::mobile::SmsSession::start(config).await {
+            Ok(sms) => {
+                tracing::info!("SMS sync through KDE Connect started");
+                self.sms = Some(sms);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to start SMS sync: {}", e);
+                self.ui.show_toast_warning(format!("SMS unavailable: {}", e.user_message()));
+            }
+        }
+    }
+
+    /// Notify about SMS that arrived, on the desktop and in a toast
+    ///
+    /// The toast offers a reply to the last one, like event reminders offer
+    /// a snooze.
+    fn poll_incoming_sms(&mut self) {
+        use crate::notifications::types::SystemEventType;
+        use crate::notifications::{NotificationEvent, NotificationPriority};
+
+        let Some(sms) = self.sms.as_mut() else {
+            return;
+        };
+        while let Some(message) = sms.next_incoming() {
+            if !sms.notify {
+                continue;
+            }
+            let sender = message
+                .sender()
+                .cloned()
+                .unwrap_or_else(|| "unknown number".to_string());
+            let text = if sms.show_preview {
+                format!("{}: {}", sender, message.preview_text(80))
+            } else {
+                format!("From {}", sender)
+            };
+
+            if let Some(unified_manager) = &self.unified_notification_manager {
+                let notification = NotificationEvent::System {
+                    event_type: SystemEventType::SmsReceived,
+                    message: text.clone(),
+                    priority: NotificationPriority::Normal,
+                };
+                if let Err(e) = unified_manager.get_sender().send(notification) {
+                    tracing::warn!("Failed to send SMS notification: {}", e);
+                }
+            }
+            self.ui
+                .show_toast_info(format!("SMS {} (Alt+Shift+P to reply)", text));
+            self.last_sms = Some(message);
+        }
+    }
+
+    /// Show the SMS conversations
+    async fn handle_show_sms(&mut self) {
+        let Some(sms) = &self.sms else {
+            self.ui
+                .show_toast_info("SMS is off; enable it in mobile.toml");
+            return;
+        };
+
+        if let Err(e) = sms.screen.load_conversations(&sms.store).await {
+            tracing::error!("Failed to load SMS conversations: {}", e);
+            self.ui.show_toast_error(e.user_message());
+            return;
+        }
+        sms.screen
+            .set_view_mode(crate::mobile::SmsViewMode::ConversationList)
+            .await;
+        self.ui.show_sms_screen(sms.screen.clone());
+    }
+
+    /// Open a reply to the conversation of the last SMS notification
+    async fn handle_reply_to_sms(&mut self) {
+        let Some(sms) = &self.sms else {
+            self.ui
+                .show_toast_info("SMS is off; enable it in mobile.toml");
+            return;
+        };
+        let Some(message) = self.last_sms.take() else {
+            self.ui.show_toast_info("No SMS to reply to");
+            return;
+        };
+
+        // The thread is shown once the reply is sent
+        let loaded = match sms.screen.load_conversations(&sms.store).await {
+            Ok(()) => sms
+                .screen
+                .load_conversation_messages(&sms.store, message.thread_id)
+                .await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = loaded {
+            tracing::warn!("Failed to load SMS conversation {}: {}", message.thread_id, e);
+        }
+        sms.screen
+            .start_reply(message.thread_id, message.addresses.clone())
+            .await;
+        self.ui.show_sms_screen(sms.screen.clone());
+    }
+
+    /// Hand a key to the SMS screen; Ctrl+S sends through KDE Connect
+    async fn handle_sms_key(&mut self, key: char) {
+        use crate::mobile::SmsViewMode;
+
+        let Some(sms) = &self.sms else {
+            self.ui.hide_sms_screen();
+            return;
+        };
+
+        let mode = sms.screen.get_view_mode().await;
+        let client = sms.client.lock().await.clone();
+        if let Err(e) = sms
+            .screen
+            .handle_key(key, &client, self.ui.toast_manager())
+            .await
+        {
+            // Failed sends are already shown in a toast
+            tracing::warn!("SMS screen: {}", e);
+        }
+
+        // Messages of a thread are loaded when it is opened
+        if mode == SmsViewMode::ConversationList
+            && sms.screen.get_view_mode().await == SmsViewMode::MessageThread
+        {
+            if let Some(thread_id) = sms.screen.get_selected_thread_id().await {
+                if let Err(e) = sms.screen.load_conversation_messages(&sms.store, thread_id).await {
+                    tracing::error!("Failed to load SMS conversation {}: {}", thread_id, e);
+                    self.ui.show_toast_error(e.user_message());
+                }
+            }
+        }
+    }
+
+    /// Have the last event reminder that went off go off again in a few minutes
+    async fn handle_snooze_reminder(&mut self) {
+        let Some(reminder) = self.last_reminder.take() else {
+            self.ui.show_toast_info("No event reminder to snooze");
+            return;
+        };
+        let Some(calendar_manager) = self.calendar_manager.clone() else {
+            self.ui.show_toast_error("Calendar not available");
+            return;
+        };
+
+        let until = chrono::Utc::now()
+            + chrono::Duration::minutes(crate::calendar::reminders::SNOOZE_MINUTES);
+        match calendar_manager.snooze_reminder(&reminder.key, until).await {
+            Ok(true) => self.ui.show_toast_success(format!(
+                "Reminder for {} snoozed until {}",
+                reminder.event.title,
+                until.with_timezone(&chrono::Local).format("%H:%M")
+            )),
+            Ok(false) => self.ui.show_toast_warning("Reminder no longer exists"),
+            Err(e) => {
+                tracing::error!("Failed to snooze reminder {}: {}", reminder.key, e);
+                self.ui.show_toast_error(format!("Failed to snooze reminder: {}", e));
+                self.last_reminder = Some(reminder);
+            }
+        }
+    }
+
+    /// Email a reminder to attendees who haven't responded to an event the user organizes
+    async fn handle_nudge_attendees(&mut self, event_id: &str) -> Result<()> {
+        let Some(event) = self
+            .ui
+            .calendar_ui()
+            .get_events()
+            .iter()
+            .find(|event| event.id == event_id)
+            .cloned()
+        else {
+            self.ui.show_toast_error("Event not found");
+            return Ok(());
+        };
+
+        if !self.ui.calendar_ui().is_organizer_of(&event) {
+            self.ui
+                .show_toast_warning("Reminders can only be sent for events you organize");
+            return Ok(());
+        }
+
+        let recipients: Vec<String> = event
+            .non_responders()
+            .iter()
+            .map(|attendee| attendee.email.clone())
+            .collect();
+        if recipients.is_empty() {
+            self.ui.show_toast_info("Everyone has responded");
+            return Ok(());
+        }
+
+        let Some(smtp_service) = self.smtp_service.as_ref() else {
+            self.ui.show_toast_error("SMTP service not initialized");
+            return Ok(());
+        };
+
+        let configs = self
+            .storage
+            .load_all_accounts()
+            .map_err(|e| anyhow::anyhow!("Failed to load account configs: {}", e))?;
+        // Send from the account that organizes the event
+        let Some(config) = configs.iter().find(|config| {
+            event.is_organized_by(std::slice::from_ref(&config.email_address))
+        }) else {
+            self.ui.show_toast_error("No account found for the event organizer");
+            return Ok(());
+        };
+
+        if !smtp_service.is_account_configured(&config.account_id).await {
+            self.initialize_smtp_for_account(&config.account_id, config)
+                .await?;
+        }
+
+        let mut body = format!(
+            "Hi,\n\nThis is a reminder to respond to the invitation for \"{}\" on {}.",
+            event.title,
+            event
+                .start_time
+                .with_timezone(&chrono::Local)
+                .format("%A %Y-%m-%d %H:%M")
+        );
+        if let Some(ref location) = event.location {
+            body.push_str(&format!("\nLocation: {}", location));
+        }
+        body.push_str("\n\nPlease accept or decline so I can plan accordingly.\n\nThanks!");
+
+        let compose_data = crate::ui::EmailComposeData {
+            to: recipients.join(", "),
+            cc: String::new(),
+            bcc: String::new(),
+            subject: format!("Reminder: please respond to \"{}\"", event.title),
+            body,
+            in_reply_to: None,
+            references: None,
+            priority: crate::email::MailPriority::Normal,
+            encrypt: false,
+            sign: false,
+            attachments: Vec::new(),
+        };
+
+        match smtp_service
+            .send_email(&config.account_id, &config.email_address, &compose_data)
+            .await
+        {
+            Ok(result) => {
+                self.file_sent_copy(&config.account_id, &result).await;
+                self.ui.show_toast_success(format!(
+                    "Reminder sent to {} attendee(s)",
+                    recipients.len()
+                ));
+            }
+            Err(e) => {
+                tracing::error!("Failed to send attendee reminder: {}", e);
+                self.ui
+                    .show_toast_error(format!("Failed to send reminder: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Open the meeting invitation carried by the viewed message
+    async fn handle_view_invitation(&mut self) {
+        let Some(message) = self.ui.email_viewer().current_message.clone() else {
+            return;
+        };
+
+        let user_emails = self
+            .storage
+            .load_all_accounts()
+            .map(|configs| {
+                configs
+                    .into_iter()
+                    .map(|config| config.email_address)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let processor = crate::calendar::InvitationProcessor::new(user_emails);
+
+        match processor.extract_invitation(&message).await {
+            Ok(Some(invitation)) => {
+                let user_status = processor.get_user_rsvp_status(&invitation);
+                let user_invited = processor.is_user_invited(&invitation);
+                self.ui
+                    .start_invitation_viewer(invitation, user_status, user_invited);
+            }
+            Ok(None) => self
+                .ui
+                .show_toast_info("No meeting invitation in this message"),
+            Err(e) => {
+                tracing::error!("Failed to read meeting invitation: {}", e);
+                self.ui
+                    .show_toast_error(format!("Failed to read invitation: {}", e));
+            }
+        }
+    }
+
+    /// Apply the chosen invitation action to the calendar and answer the organizer
+    async fn handle_invitation_action(
+        &mut self,
+        action: crate::ui::InvitationAction,
+    ) -> Result<()> {
+        use crate::calendar::AttendeeStatus;
+        use crate::ui::InvitationAction;
+
+        let Some(invitation) = self.ui.invitation_viewer().invitation().cloned() else {
+            return Ok(());
+        };
+        let Some(manager) = self.calendar_manager.clone() else {
+            self.ui.show_toast_error("Calendar not initialized");
+            return Ok(());
+        };
+
+        let (status, response) = match action {
+            InvitationAction::Accept => (AttendeeStatus::Accepted, Some("ACCEPTED")),
+            InvitationAction::Tentative => (AttendeeStatus::Tentative, Some("TENTATIVE")),
+            InvitationAction::Decline => (AttendeeStatus::Declined, Some("DECLINED")),
+            InvitationAction::AddToCalendar => (AttendeeStatus::NeedsAction, None),
+            InvitationAction::RemoveFromCalendar => {
+                match manager.remove_invitation(&invitation.uid).await {
+                    Ok(true) => self
+                        .ui
+                        .show_toast_success("Cancelled meeting removed from the calendar"),
+                    Ok(false) => self.ui.show_toast_info("Meeting was not in the calendar"),
+                    Err(e) => {
+                        tracing::error!("Failed to remove cancelled meeting: {}", e);
+                        self.ui
+                            .show_toast_error(format!("Failed to remove meeting: {}", e));
+                        return Ok(());
+                    }
+                }
+                self.refresh_calendar_data().await?;
+                self.ui.exit_invitation_viewer();
+                return Ok(());
+            }
+            InvitationAction::Close | InvitationAction::ViewDetails => return Ok(()),
+        };
+
+        let configs = self
+            .storage
+            .load_all_accounts()
+            .map_err(|e| anyhow::anyhow!("Failed to load account configs: {}", e))?;
+        // Answer from the account the invitation was sent to
+        let account = configs.into_iter().find(|config| {
+            invitation
+                .attendees
+                .iter()
+                .any(|attendee| attendee.email.eq_ignore_ascii_case(&config.email_address))
+        });
+        let attendee_email = account.as_ref().map(|config| config.email_address.as_str());
+
+        // A declined meeting leaves the calendar; the others are added or updated
+        let calendar_result = if status == AttendeeStatus::Declined {
+            manager.remove_invitation(&invitation.uid).await.map(|_| ())
         } else {
-            self.ui.show_toast_error("Calendar manager not available");
+            manager
+                .import_invitation(&invitation, attendee_email, status)
+                .await
+                .map(|_| ())
+        };
+        if let Err(e) = calendar_result {
+            tracing::error!("Failed to update calendar from invitation: {}", e);
+            self.ui
+                .show_toast_error(format!("Failed to update calendar: {}", e));
+            return Ok(());
+        }
+        self.refresh_calendar_data().await?;
+
+        let Some(response) = response else {
+            self.ui.show_toast_success("Meeting added to the calendar");
+            self.ui.exit_invitation_viewer();
+            return Ok(());
+        };
+        let (Some(config), Some(organizer)) = (account, invitation.organizer.as_ref()) else {
+            self.ui
+                .show_toast_warning("Calendar updated, but there is no organizer to reply to");
+            self.ui.exit_invitation_viewer();
+            return Ok(());
+        };
+
+        let Some(smtp_service) = self.smtp_service.as_ref() else {
+            self.ui.show_toast_error("SMTP service not initialized");
+            return Ok(());
+        };
+        if !smtp_service.is_account_configured(&config.account_id).await {
+            self.initialize_smtp_for_account(&config.account_id, &config)
+                .await?;
+        }
+
+        match smtp_service
+            .send_rsvp_response(
+                &config.account_id,
+                &config.email_address,
+                &organizer.email,
+                &invitation.title,
+                &invitation.uid,
+                response,
+                None,
+                &invitation.icalendar_data,
+            )
+            .await
+        {
+            Ok(result) => {
+                self.file_sent_copy(&config.account_id, &result).await;
+                self.ui.show_toast_success(format!(
+                    "Replied {} to {}",
+                    response.to_lowercase(),
+                    organizer.email
+                ));
+                self.ui.exit_invitation_viewer();
+            }
+            Err(e) => {
+                tracing::error!("Failed to send invitation reply: {}", e);
+                self.ui
+                    .invitation_viewer_mut()
+                    .set_error(format!("Calendar updated, but the reply failed: {}", e));
+            }
         }
         Ok(())
     }
@@ -4788,6 +8772,15 @@ impl Default for App {
     }
 }
 
+/// Id the AI summary of a message is cached under: its Message-ID header, or
+/// the local id for messages without one
+fn ai_message_id(message: &crate::email::StoredMessage) -> String {
+    message
+        .message_id
+        .clone()
+        .unwrap_or_else(|| format!("msg_{}", message.id))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,8 @@
-use crate::calendar::event::{Event, EventPriority, EventStatus};
+use crate::calendar::event::{
+    parse_icalendar_period, parse_icalendar_times, unfold_icalendar_lines, Event, EventAttendee,
+    EventPriority, EventRecurrence, EventReminder, EventStatus,
+};
+use crate::calendar::timezone;
 use chrono::{DateTime, Utc};
 use reqwest::{Client, Method, Response};
 use serde::{Deserialize, Serialize};
@@ -32,6 +36,11 @@ pub enum CalDAVError {
 
     #[error("iCalendar format error: {0}")]
     ICalendarError(String),
+
+    /// The resource's ETag no longer matches: it was changed or created on
+    /// the server since it was last fetched
+    #[error("{0} was changed on the server")]
+    PreconditionFailed(String),
 }
 
 pub type CalDAVResult<T> = Result<T, CalDAVError>;
@@ -74,6 +83,20 @@ pub struct CalDAVQuery {
     pub expand_recurrence: bool,
 }
 
+/// A time a calendar is busy, from a free/busy report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusyPeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl BusyPeriod {
+    /// Whether the period overlaps `start..end`
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.start < end && start < self.end
+    }
+}
+
 impl Default for CalDAVQuery {
     fn default() -> Self {
         Self {
@@ -102,6 +125,11 @@ impl CalDAVClient {
         })
     }
 
+    /// Whether `url` is on this client's server, so its credentials apply
+    pub fn serves(&self, url: &str) -> bool {
+        Url::parse(url).is_ok_and(|url| url.origin() == self.base_url.origin())
+    }
+
     /// Discover available calendars
     pub async fn discover_calendars(&self) -> CalDAVResult<Vec<CalDAVCalendar>> {
         // Perform calendar discovery using PROPFIND
@@ -162,7 +190,64 @@ impl CalDAVClient {
         self.parse_events_response(response).await
     }
 
+    /// Busy times of a calendar between `start` and `end`
+    ///
+    /// Sends a `free-busy-query` REPORT (RFC 4791 section 7.10), which works
+    /// on calendars shared with at least free/busy access. Tentative and
+    /// unavailable times count as busy.
+    pub async fn free_busy_query(
+        &self,
+        calendar_url: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> CalDAVResult<Vec<BusyPeriod>> {
+        let report_body = format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+<C:free-busy-query xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <C:time-range start="{}" end="{}" />
+</C:free-busy-query>"#,
+            start.format("%Y%m%dT%H%M%SZ"),
+            end.format("%Y%m%dT%H%M%SZ")
+        );
+
+        let response = self
+            .send_request(
+                Method::from_bytes(b"REPORT").map_err(|e| CalDAVError::ServerError {
+                    status: 400,
+                    message: format!("Invalid HTTP method: {}", e),
+                })?,
+                calendar_url,
+                Some(&report_body),
+                vec![
+                    ("Depth", "1"),
+                    ("Content-Type", "application/xml; charset=utf-8"),
+                ],
+            )
+            .await?;
+
+        Ok(Self::parse_free_busy(&response.text().await?))
+    }
+
+    /// Busy periods of the VFREEBUSY in a free/busy report
+    fn parse_free_busy(icalendar_data: &str) -> Vec<BusyPeriod> {
+        let mut busy: Vec<BusyPeriod> = unfold_icalendar_lines(icalendar_data)
+            .iter()
+            .filter(|line| line.starts_with("FREEBUSY"))
+            .filter_map(|line| line.split_once(':'))
+            .filter(|(params, _)| !params.to_uppercase().contains("FBTYPE=FREE"))
+            .flat_map(|(_, periods)| periods.split(','))
+            .filter_map(parse_icalendar_period)
+            .map(|(start, end)| BusyPeriod { start, end })
+            .collect();
+        busy.sort_by_key(|period| period.start);
+        busy
+    }
+
     /// Create or update an event
+    ///
+    /// Updates only apply if the server still has `etag`, and creates only
+    /// if nothing exists at `event_url` yet; otherwise the server answers 412
+    /// and this returns `PreconditionFailed`.
     pub async fn put_event(
         &self,
         event_url: &str,
@@ -171,9 +256,9 @@ impl CalDAVClient {
     ) -> CalDAVResult<String> {
         let mut headers = vec![("Content-Type", "text/calendar; charset=utf-8")];
 
-        // Add If-Match header for updates
-        if let Some(etag) = etag {
-            headers.push(("If-Match", etag));
+        match etag {
+            Some(etag) => headers.push(("If-Match", etag)),
+            None => headers.push(("If-None-Match", "*")),
         }
 
         let response = self
@@ -188,10 +273,18 @@ impl CalDAVClient {
             .unwrap_or("")
             .to_string();
 
+        // Servers that rewrite the data on store leave the ETag out
+        if etag.is_empty() {
+            return Ok(self.get_event(event_url).await?.etag);
+        }
+
         Ok(etag)
     }
 
     /// Delete an event
+    ///
+    /// With an `etag` the delete only applies if the event wasn't changed on
+    /// the server since. An event that is already gone counts as deleted.
     pub async fn delete_event(&self, event_url: &str, etag: Option<&str>) -> CalDAVResult<()> {
         let mut headers = vec![];
 
@@ -200,10 +293,13 @@ impl CalDAVClient {
             headers.push(("If-Match", etag));
         }
 
-        self.send_request(Method::DELETE, event_url, None, headers)
-            .await?;
-
-        Ok(())
+        match self
+            .send_request(Method::DELETE, event_url, None, headers)
+            .await
+        {
+            Ok(_) | Err(CalDAVError::ServerError { status: 404, .. }) => Ok(()),
+            Err(e) => Err(e),
+        }
     }
 
     /// Test server connectivity and authentication
@@ -317,41 +413,114 @@ impl CalDAVClient {
     }
 
     /// Parse simple iCalendar data into Event structure
+    ///
+    /// Returns the recurring master when the data also holds overrides of
+    /// single occurrences.
     pub fn parse_icalendar_to_event(
         &self,
         icalendar_data: &str,
         calendar_id: String,
     ) -> CalDAVResult<Event> {
+        let mut events = self.parse_icalendar_to_events(icalendar_data, calendar_id)?;
+        if events.is_empty() {
+            return Err(CalDAVError::ICalendarError("No VEVENT found".to_string()));
+        }
+
+        let index = events
+            .iter()
+            .position(|event| event.recurrence_id.is_none())
+            .unwrap_or(0);
+        Ok(events.swap_remove(index))
+    }
+
+    /// Parse every VEVENT of a calendar object, including RECURRENCE-ID
+    /// overrides of a recurring event
+    pub fn parse_icalendar_to_events(
+        &self,
+        icalendar_data: &str,
+        calendar_id: String,
+    ) -> CalDAVResult<Vec<Event>> {
+        let lines = unfold_icalendar_lines(icalendar_data);
+        let mut events = Vec::new();
+        let mut vevent: Option<Vec<&str>> = None;
+
+        for line in &lines {
+            let line = line.trim();
+
+            if line == "BEGIN:VEVENT" {
+                vevent = Some(Vec::new());
+            } else if line == "END:VEVENT" {
+                if let Some(vevent_lines) = vevent.take() {
+                    events.push(Self::parse_vevent(&vevent_lines, calendar_id.clone())?);
+                }
+            } else if let Some(vevent_lines) = vevent.as_mut() {
+                vevent_lines.push(line);
+            }
+        }
+
+        // Overrides share the UID of their series
+        if let Some(uid) = events.first().map(|event| event.uid.clone()) {
+            for event in &mut events {
+                if event.uid.is_empty() {
+                    event.uid = uid.clone();
+                }
+            }
+        }
+        for event in &mut events {
+            if event.uid.is_empty() {
+                event.uid = uuid::Uuid::new_v4().to_string();
+            }
+            if let Some(recurrence_id) = event.recurrence_id {
+                event.id = format!("{}:{}", event.uid, recurrence_id.format("%Y%m%dT%H%M%SZ"));
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Parse the properties of a single VEVENT
+    fn parse_vevent(lines: &[&str], calendar_id: String) -> CalDAVResult<Event> {
         // Simple iCalendar parser - would use a proper library in production
         let mut uid = String::new();
         let mut title = "Untitled Event".to_string();
         let mut description = None;
         let mut location = None;
         let mut start_time = Utc::now();
-        let mut end_time = Utc::now() + chrono::Duration::hours(1);
+        let mut end_time = None;
+        let mut all_day = false;
         let mut status = EventStatus::Confirmed;
-
-        let lines: Vec<&str> = icalendar_data.lines().collect();
-        let mut in_vevent = false;
-
-        for line in lines {
-            let line = line.trim();
-
-            if line == "BEGIN:VEVENT" {
-                in_vevent = true;
+        let mut organizer = None;
+        let mut attendees = Vec::new();
+        let mut recurrence: Option<EventRecurrence> = None;
+        let mut exception_dates = Vec::new();
+        let mut recurrence_dates = Vec::new();
+        let mut recurrence_id = None;
+        let mut timezone = None;
+        let mut alarms: Vec<Vec<&str>> = Vec::new();
+        let mut alarm: Option<Vec<&str>> = None;
+
+        for &line in lines {
+            // Alarm properties, such as the ATTENDEE lines of email alarms,
+            // are not the event's
+            if line == "BEGIN:VALARM" {
+                alarm = Some(Vec::new());
                 continue;
             }
-
-            if line == "END:VEVENT" {
-                break;
+            if line == "END:VALARM" {
+                alarms.extend(alarm.take());
+                continue;
             }
-
-            if !in_vevent {
+            if let Some(alarm_lines) = alarm.as_mut() {
+                alarm_lines.push(line);
                 continue;
             }
 
             if line.starts_with("UID:") {
                 uid = line.strip_prefix("UID:").unwrap_or("").to_string();
+            } else if line.starts_with("ORGANIZER") {
+                organizer = EventAttendee::from_icalendar_property(line);
+            } else if line.starts_with("ATTENDEE") {
+                attendees.extend(EventAttendee::from_icalendar_property(line));
             } else if line.starts_with("SUMMARY:") {
                 title = line
                     .strip_prefix("SUMMARY:")
@@ -361,18 +530,25 @@ impl CalDAVClient {
                 description = Some(line.strip_prefix("DESCRIPTION:").unwrap_or("").to_string());
             } else if line.starts_with("LOCATION:") {
                 location = Some(line.strip_prefix("LOCATION:").unwrap_or("").to_string());
-            } else if line.starts_with("DTSTART:") {
-                if let Some(datetime_str) = line.strip_prefix("DTSTART:") {
-                    if let Ok(dt) = DateTime::parse_from_str(datetime_str, "%Y%m%dT%H%M%SZ") {
-                        start_time = dt.with_timezone(&Utc);
-                    }
-                }
-            } else if line.starts_with("DTEND:") {
-                if let Some(datetime_str) = line.strip_prefix("DTEND:") {
-                    if let Ok(dt) = DateTime::parse_from_str(datetime_str, "%Y%m%dT%H%M%SZ") {
-                        end_time = dt.with_timezone(&Utc);
-                    }
+            } else if line.starts_with("DTSTART") {
+                if let Some(time) = Self::first_time(line) {
+                    start_time = time;
+                    all_day = line.contains("VALUE=DATE") && !line.contains("VALUE=DATE-TIME");
+                    timezone = timezone::line_timezone(line).filter(|_| !all_day);
                 }
+            } else if line.starts_with("DTEND") {
+                end_time = Self::first_time(line).or(end_time);
+            } else if line.starts_with("RRULE:") {
+                let rule = line.strip_prefix("RRULE:").unwrap_or("");
+                recurrence = Some(
+                    EventRecurrence::from_icalendar(rule).map_err(CalDAVError::ICalendarError)?,
+                );
+            } else if line.starts_with("EXDATE") {
+                exception_dates.extend(parse_icalendar_times(line).unwrap_or_default());
+            } else if line.starts_with("RDATE") {
+                recurrence_dates.extend(parse_icalendar_times(line).unwrap_or_default());
+            } else if line.starts_with("RECURRENCE-ID") {
+                recurrence_id = Self::first_time(line);
             } else if line.starts_with("STATUS:") {
                 if let Some(status_str) = line.strip_prefix("STATUS:") {
                     status = match status_str.to_uppercase().as_str() {
@@ -385,20 +561,45 @@ impl CalDAVClient {
             }
         }
 
-        if uid.is_empty() {
-            uid = uuid::Uuid::new_v4().to_string();
+        let end_time = end_time.unwrap_or_else(|| {
+            if all_day {
+                start_time + chrono::Duration::days(1)
+            } else {
+                start_time + chrono::Duration::hours(1)
+            }
+        });
+
+        if let Some(ref mut recurrence) = recurrence {
+            recurrence.exception_dates = exception_dates;
+            recurrence.recurrence_dates = recurrence_dates;
         }
 
         let mut event = Event::new(calendar_id, title, start_time, end_time);
         event.uid = uid;
         event.description = description;
         event.location = location;
+        event.all_day = all_day;
         event.status = status;
         event.priority = EventPriority::Normal;
+        event.organizer = organizer;
+        event.attendees = attendees;
+        event.recurrence = recurrence;
+        event.recurrence_id = recurrence_id;
+        event.timezone = timezone.map(|tz| tz.name().to_string());
+        // Absolute triggers are relative to the start, so parse them last
+        event.reminders = alarms
+            .iter()
+            .filter_map(|alarm_lines| EventReminder::from_valarm(alarm_lines, start_time))
+            .collect();
 
         Ok(event)
     }
 
+    /// First time of a DTSTART, DTEND or RECURRENCE-ID line
+    fn first_time(line: &str) -> Option<DateTime<Utc>> {
+        parse_icalendar_times(line)?.into_iter().next()
+    }
+
     /// Get a specific event by URL
     pub async fn get_event(&self, event_url: &str) -> CalDAVResult<CalDAVEvent> {
         let response = self
@@ -452,6 +653,11 @@ impl CalDAVClient {
             return Err(CalDAVError::AuthenticationFailed);
         }
 
+        // Conditional request lost against a change on the server
+        if response.status() == 412 {
+            return Err(CalDAVError::PreconditionFailed(url.to_string()));
+        }
+
         // Check for other HTTP errors
         if !response.status().is_success() {
             let status = response.status().as_u16();
@@ -608,6 +814,8 @@ impl CalDAVConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::calendar::event::ReminderAction;
+    use chrono::TimeZone;
 
     #[tokio::test]
     async fn test_caldav_client_creation() {
@@ -648,4 +856,174 @@ mod tests {
         assert_eq!(config.sync_interval_minutes, 15);
         assert!(config.enabled);
     }
+
+    #[test]
+    fn test_parse_recurring_event_with_override() {
+        let client = CalDAVClient::new(
+            "https://calendar.example.com/dav/",
+            "testuser".to_string(),
+            "testpass".to_string(),
+        )
+        .unwrap();
+
+        let ical = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup@example.com\r\n\
+SUMMARY:Standup\r\n\
+DTSTART;TZID=Europe/Oslo:20250106T093000\r\n\
+DTEND;TZID=Europe/Oslo:20250106T094500\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+EXDATE;TZID=Europe/Oslo:20250120T093000,20250203T093000\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup@example.com\r\n\
+RECURRENCE-ID;TZID=Europe/Oslo:20250113T093000\r\n\
+SUMMARY:Standup (moved)\r\n\
+DTSTART;TZID=Europe/Oslo:20250114T110000\r\n\
+DTEND;TZID=Europe/Oslo:20250114T111500\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let events = client
+            .parse_icalendar_to_events(ical, "cal".to_string())
+            .unwrap();
+        assert_eq!(events.len(), 2);
+
+        let master = &events[0];
+        let recurrence = master.recurrence.as_ref().unwrap();
+        assert_eq!(
+            master.end_time - master.start_time,
+            chrono::Duration::minutes(15)
+        );
+        assert_eq!(recurrence.exception_dates.len(), 2);
+        assert!(master.recurrence_id.is_none());
+
+        let moved = &events[1];
+        assert_eq!(moved.uid, master.uid);
+        // Europe/Oslo is an hour ahead of UTC in January
+        assert_eq!(moved.id, "standup@example.com:20250113T083000Z");
+        assert_eq!(
+            moved
+                .recurrence_id
+                .map(|dt| dt.format("%Y%m%dT%H%M%S").to_string()),
+            Some("20250113T083000".to_string())
+        );
+        assert_eq!(moved.timezone.as_deref(), Some("Europe/Oslo"));
+        assert_eq!(
+            master.start_time.format("%Y%m%dT%H%M%SZ").to_string(),
+            "20250106T083000Z"
+        );
+
+        let event = client
+            .parse_icalendar_to_event(ical, "cal".to_string())
+            .unwrap();
+        assert_eq!(event.title, "Standup");
+    }
+
+    #[test]
+    fn test_parse_event_alarms() {
+        let client = CalDAVClient::new(
+            "https://calendar.example.com/dav/",
+            "testuser".to_string(),
+            "testpass".to_string(),
+        )
+        .unwrap();
+
+        let ical = "BEGIN:VCALENDAR\r\n\
+BEGIN:VEVENT\r\n\
+UID:review@example.com\r\n\
+SUMMARY:Review\r\n\
+BEGIN:VALARM\r\n\
+ACTION:DISPLAY\r\n\
+TRIGGER:-PT15M\r\n\
+DESCRIPTION:Review starts soon\r\n\
+END:VALARM\r\n\
+BEGIN:VALARM\r\n\
+ACTION:EMAIL\r\n\
+TRIGGER;RELATED=END:-P1D\r\n\
+ATTENDEE:mailto:me@example.com\r\n\
+END:VALARM\r\n\
+BEGIN:VALARM\r\n\
+ACTION:AUDIO\r\n\
+TRIGGER;VALUE=DATE-TIME:20250106T080000Z\r\n\
+END:VALARM\r\n\
+DTSTART:20250106T093000Z\r\n\
+DTEND:20250106T103000Z\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let event = client
+            .parse_icalendar_to_event(ical, "cal".to_string())
+            .unwrap();
+        assert!(event.attendees.is_empty());
+        assert_eq!(event.reminders.len(), 3);
+
+        let fire_times: Vec<String> = event
+            .reminders
+            .iter()
+            .map(|reminder| {
+                reminder
+                    .trigger
+                    .fire_time(event.start_time, event.end_time)
+                    .format("%Y%m%dT%H%M%SZ")
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(
+            fire_times,
+            vec!["20250106T091500Z", "20250105T103000Z", "20250106T080000Z"]
+        );
+        assert_eq!(
+            event.reminders[0].description.as_deref(),
+            Some("Review starts soon")
+        );
+        assert_eq!(event.reminders[1].action, ReminderAction::Email);
+        assert_eq!(event.reminders[1].attendees, vec!["me@example.com"]);
+        assert_eq!(event.reminders[2].action, ReminderAction::Audio);
+    }
+
+    #[test]
+    fn test_parse_free_busy() {
+        let ical = "BEGIN:VCALENDAR\r\n\
+BEGIN:VFREEBUSY\r\n\
+DTSTART:20250310T000000Z\r\n\
+DTEND:20250317T000000Z\r\n\
+FREEBUSY;FBTYPE=BUSY:20250311T150000Z/PT1H,20250310T140000Z/20250310T143\r\n \
+000Z\r\n\
+FREEBUSY;FBTYPE=FREE:20250312T090000Z/PT8H\r\n\
+FREEBUSY;FBTYPE=BUSY-TENTATIVE:20250313T100000Z/PT30M\r\n\
+END:VFREEBUSY\r\n\
+END:VCALENDAR\r\n";
+
+        let busy = CalDAVClient::parse_free_busy(ical);
+        let at = |day, hour, minute| Utc.with_ymd_and_hms(2025, 3, day, hour, minute, 0).unwrap();
+        assert_eq!(
+            busy,
+            vec![
+                BusyPeriod {
+                    start: at(10, 14, 0),
+                    end: at(10, 14, 30)
+                },
+                BusyPeriod {
+                    start: at(11, 15, 0),
+                    end: at(11, 16, 0)
+                },
+                BusyPeriod {
+                    start: at(13, 10, 0),
+                    end: at(13, 10, 30)
+                },
+            ]
+        );
+        assert!(busy[0].overlaps(at(10, 14, 15), at(10, 15, 0)));
+        assert!(!busy[0].overlaps(at(10, 14, 30), at(10, 15, 0)));
+
+        let client = CalDAVClient::new(
+            "https://calendar.example.com/dav/",
+            "testuser".to_string(),
+            "testpass".to_string(),
+        )
+        .unwrap();
+        assert!(client.serves("https://calendar.example.com/dav/calendars/bob/work/"));
+        assert!(!client.serves("https://other.example.com/dav/calendars/bob/work/"));
+    }
 }
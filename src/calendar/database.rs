@@ -80,11 +80,21 @@ pub struct CalendarEventAttendee {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalendarEventReminder {
     pub trigger_minutes: i32, // Minutes before event (negative for before, positive for after)
-    pub action: String,       // display, email, audio
+    /// Whether `trigger_minutes` counts from the end of the event
+    #[serde(default)]
+    pub related_end: bool,
+    pub action: String, // display, email, audio
     pub description: Option<String>,
     pub attendees: Vec<String>, // Email addresses
 }
 
+/// When an event reminder last went off, and until when it is snoozed
+#[derive(Debug, Clone)]
+pub struct ReminderState {
+    pub fired_at: DateTime<Utc>,
+    pub snoozed_until: Option<DateTime<Utc>>,
+}
+
 /// Stored event recurrence pattern
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalendarEventRecurrence {
@@ -202,6 +212,8 @@ impl CalendarDatabase {
                 organizer_name TEXT,
                 attendees TEXT NOT NULL DEFAULT '[]', -- JSON array
                 recurrence_rule TEXT, -- JSON or RRULE string
+                recurrence_id TEXT, -- start of the overridden occurrence
+                timezone TEXT, -- IANA zone the times were given in
                 reminders TEXT NOT NULL DEFAULT '[]', -- JSON array
                 categories TEXT NOT NULL DEFAULT '[]', -- JSON array
                 url TEXT,
@@ -215,6 +227,10 @@ impl CalendarDatabase {
         )
         .execute(&self.pool)
         .await?;
+        self.add_column_if_missing("calendar_events", "recurrence_id", "TEXT")
+            .await?;
+        self.add_column_if_missing("calendar_events", "timezone", "TEXT")
+            .await?;
 
         // Create calendar_sync_state table
         sqlx::query(
@@ -233,6 +249,20 @@ impl CalendarDatabase {
         .execute(&self.pool)
         .await?;
 
+        // Reminders that went off, so they fire once per occurrence
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS calendar_reminders (
+                reminder_key TEXT PRIMARY KEY, -- event UID, occurrence start and trigger
+                event_id TEXT NOT NULL,
+                fired_at TEXT NOT NULL,
+                snoozed_until TEXT
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
         // Create indexes for performance
         sqlx::query(
             "CREATE INDEX IF NOT EXISTS idx_events_calendar ON calendar_events(calendar_id)",
@@ -302,6 +332,33 @@ impl CalendarDatabase {
         Ok(())
     }
 
+    /// Add a column to an existing table that was created without it
+    async fn add_column_if_missing(
+        &self,
+        table: &str,
+        column: &str,
+        definition: &str,
+    ) -> CalendarDatabaseResult<()> {
+        let columns: Vec<String> = sqlx::query(&format!("PRAGMA table_info({})", table))
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(|row| row.get("name"))
+            .collect();
+
+        if columns.iter().any(|name| name == column) {
+            return Ok(());
+        }
+
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Store a calendar
     pub async fn store_calendar(&self, calendar: &Calendar) -> CalendarDatabaseResult<()> {
         let source_data = serde_json::to_string(&calendar.source)?;
@@ -409,6 +466,7 @@ impl CalendarDatabase {
                         crate::calendar::event::ReminderTrigger::AtStart => 0,
                         crate::calendar::event::ReminderTrigger::AtEnd => 0,
                     },
+                    related_end: !r.trigger.related_param().is_empty(),
                     action: r.action.to_icalendar().to_string(),
                     description: r.description.clone(),
                     attendees: r.attendees.clone(),
@@ -430,11 +488,11 @@ impl CalendarDatabase {
                 start_time, end_time, all_day, status, priority,
                 organizer_email, organizer_name, attendees, recurrence_rule,
                 reminders, categories, url, created_at, updated_at,
-                sequence, etag, sync_status
+                sequence, etag, sync_status, recurrence_id, timezone
             ) VALUES (
                 ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11,
                 ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20,
-                ?21, ?22, ?23
+                ?21, ?22, ?23, ?24, ?25
             )
         "#,
         )
@@ -461,6 +519,8 @@ impl CalendarDatabase {
         .bind(event.sequence as i64)
         .bind(&event.etag)
         .bind("local") // Default sync status
+        .bind(event.recurrence_id.map(|dt| dt.to_rfc3339()))
+        .bind(&event.timezone)
         .execute(&self.pool)
         .await?;
 
@@ -480,7 +540,7 @@ impl CalendarDatabase {
                    start_time, end_time, all_day, status, priority,
                    organizer_email, organizer_name, attendees, recurrence_rule,
                    reminders, categories, url, created_at, updated_at,
-                   sequence, etag
+                   sequence, etag, recurrence_id, timezone
             FROM calendar_events
             WHERE calendar_id = ?1
         "#,
@@ -488,14 +548,22 @@ impl CalendarDatabase {
 
         let mut bind_count = 1;
 
+        // Recurring events and their overrides are returned whatever their
+        // first occurrence, for the caller to expand
         if start_time.is_some() {
             bind_count += 1;
-            query.push_str(&format!(" AND end_time >= ?{}", bind_count));
+            query.push_str(&format!(
+                " AND (end_time >= ?{} OR recurrence_rule IS NOT NULL OR recurrence_id IS NOT NULL)",
+                bind_count
+            ));
         }
 
         if end_time.is_some() {
             bind_count += 1;
-            query.push_str(&format!(" AND start_time <= ?{}", bind_count));
+            query.push_str(&format!(
+                " AND (start_time <= ?{} OR recurrence_id IS NOT NULL)",
+                bind_count
+            ));
         }
 
         query.push_str(" ORDER BY start_time ASC");
@@ -528,7 +596,7 @@ impl CalendarDatabase {
                    start_time, end_time, all_day, status, priority,
                    organizer_email, organizer_name, attendees, recurrence_rule,
                    reminders, categories, url, created_at, updated_at,
-                   sequence, etag
+                   sequence, etag, recurrence_id, timezone
             FROM calendar_events
             WHERE id = ?1
         "#,
@@ -555,7 +623,7 @@ impl CalendarDatabase {
                    start_time, end_time, all_day, status, priority,
                    organizer_email, organizer_name, attendees, recurrence_rule,
                    reminders, categories, url, created_at, updated_at,
-                   sequence, etag
+                   sequence, etag, recurrence_id, timezone
             FROM calendar_events
             WHERE 1=1
         "#,
@@ -563,14 +631,22 @@ impl CalendarDatabase {
 
         let mut bind_count = 0;
 
+        // Recurring events and their overrides are returned whatever their
+        // first occurrence, for the caller to expand
         if start_time.is_some() {
             bind_count += 1;
-            query.push_str(&format!(" AND end_time >= ?{}", bind_count));
+            query.push_str(&format!(
+                " AND (end_time >= ?{} OR recurrence_rule IS NOT NULL OR recurrence_id IS NOT NULL)",
+                bind_count
+            ));
         }
 
         if end_time.is_some() {
             bind_count += 1;
-            query.push_str(&format!(" AND start_time <= ?{}", bind_count));
+            query.push_str(&format!(
+                " AND (start_time <= ?{} OR recurrence_id IS NOT NULL)",
+                bind_count
+            ));
         }
 
         query.push_str(" ORDER BY start_time ASC");
@@ -609,7 +685,7 @@ impl CalendarDatabase {
                    e.start_time, e.end_time, e.all_day, e.status, e.priority,
                    e.organizer_email, e.organizer_name, e.attendees, e.recurrence_rule,
                    e.reminders, e.categories, e.url, e.created_at, e.updated_at,
-                   e.sequence, e.etag
+                   e.sequence, e.etag, e.recurrence_id, e.timezone
             FROM calendar_events e
             JOIN calendar_events_fts fts ON e.rowid = fts.rowid
             WHERE calendar_events_fts MATCH ?1
@@ -640,6 +716,127 @@ impl CalendarDatabase {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Get an event along with the overrides of its single occurrences
+    pub async fn get_events_by_uid(&self, uid: &str) -> CalendarDatabaseResult<Vec<Event>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, uid, calendar_id, title, description, location,
+                   start_time, end_time, all_day, status, priority,
+                   organizer_email, organizer_name, attendees, recurrence_rule,
+                   reminders, categories, url, created_at, updated_at,
+                   sequence, etag, recurrence_id, timezone
+            FROM calendar_events
+            WHERE uid = ?1
+        "#,
+        )
+        .bind(uid)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut events = Vec::new();
+        for row in rows {
+            events.push(self.row_to_event(row)?);
+        }
+
+        Ok(events)
+    }
+
+    /// Delete the overrides of single occurrences of a recurring event
+    pub async fn delete_event_overrides(&self, uid: &str) -> CalendarDatabaseResult<u64> {
+        let result =
+            sqlx::query("DELETE FROM calendar_events WHERE uid = ? AND recurrence_id IS NOT NULL")
+                .bind(uid)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// When a reminder last went off, if it has
+    pub async fn get_reminder_state(
+        &self,
+        reminder_key: &str,
+    ) -> CalendarDatabaseResult<Option<ReminderState>> {
+        let row = sqlx::query(
+            "SELECT fired_at, snoozed_until FROM calendar_reminders WHERE reminder_key = ?",
+        )
+        .bind(reminder_key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let fired_at: DateTime<Utc> = DateTime::parse_from_rfc3339(row.get("fired_at"))?.into();
+        let snoozed_until = row
+            .get::<Option<String>, _>("snoozed_until")
+            .map(|s| DateTime::parse_from_rfc3339(&s))
+            .transpose()?
+            .map(Into::into);
+
+        Ok(Some(ReminderState {
+            fired_at,
+            snoozed_until,
+        }))
+    }
+
+    /// Record that a reminder went off, ending any snooze
+    pub async fn record_reminder_fired(
+        &self,
+        reminder_key: &str,
+        event_id: &str,
+        fired_at: DateTime<Utc>,
+    ) -> CalendarDatabaseResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO calendar_reminders (reminder_key, event_id, fired_at, snoozed_until)
+            VALUES (?1, ?2, ?3, NULL)
+            ON CONFLICT(reminder_key) DO UPDATE SET
+                event_id = excluded.event_id,
+                fired_at = excluded.fired_at,
+                snoozed_until = NULL
+        "#,
+        )
+        .bind(reminder_key)
+        .bind(event_id)
+        .bind(fired_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Have a reminder that went off go off again at `until`
+    pub async fn snooze_reminder(
+        &self,
+        reminder_key: &str,
+        until: DateTime<Utc>,
+    ) -> CalendarDatabaseResult<bool> {
+        let result =
+            sqlx::query("UPDATE calendar_reminders SET snoozed_until = ? WHERE reminder_key = ?")
+                .bind(until.to_rfc3339())
+                .bind(reminder_key)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Forget reminders that went off before `before` and are not snoozed
+    pub async fn prune_reminder_states(
+        &self,
+        before: DateTime<Utc>,
+    ) -> CalendarDatabaseResult<u64> {
+        let result = sqlx::query(
+            "DELETE FROM calendar_reminders WHERE fired_at < ? AND snoozed_until IS NULL",
+        )
+        .bind(before.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Get upcoming events (starting within the next N hours)
     pub async fn get_upcoming_events(
         &self,
@@ -656,7 +853,7 @@ impl CalendarDatabase {
                    start_time, end_time, all_day, status, priority,
                    organizer_email, organizer_name, attendees, recurrence_rule,
                    reminders, categories, url, created_at, updated_at,
-                   sequence, etag
+                   sequence, etag, recurrence_id, timezone
             FROM calendar_events
             WHERE start_time >= ?1 AND start_time <= ?2
             ORDER BY start_time ASC
@@ -711,16 +908,16 @@ impl CalendarDatabase {
         let reminders = stored_reminders
             .into_iter()
             .map(|r| EventReminder {
-                trigger: if r.trigger_minutes < 0 {
-                    crate::calendar::event::ReminderTrigger::BeforeStart(chrono::Duration::minutes(
-                        -r.trigger_minutes as i64,
-                    ))
-                } else if r.trigger_minutes > 0 {
-                    crate::calendar::event::ReminderTrigger::BeforeStart(chrono::Duration::minutes(
-                        r.trigger_minutes as i64,
-                    ))
-                } else {
-                    crate::calendar::event::ReminderTrigger::AtStart
+                trigger: {
+                    let before = chrono::Duration::minutes(-r.trigger_minutes as i64);
+                    match (r.related_end, r.trigger_minutes == 0) {
+                        (true, true) => crate::calendar::event::ReminderTrigger::AtEnd,
+                        (true, false) => crate::calendar::event::ReminderTrigger::BeforeEnd(before),
+                        (false, true) => crate::calendar::event::ReminderTrigger::AtStart,
+                        (false, false) => {
+                            crate::calendar::event::ReminderTrigger::BeforeStart(before)
+                        }
+                    }
                 },
                 action: match r.action.to_uppercase().as_str() {
                     "DISPLAY" => crate::calendar::event::ReminderAction::Display,
@@ -766,6 +963,10 @@ impl CalendarDatabase {
         let end_time: DateTime<Utc> = DateTime::parse_from_rfc3339(row.get("end_time"))?.into();
         let created_at: DateTime<Utc> = DateTime::parse_from_rfc3339(row.get("created_at"))?.into();
         let updated_at: DateTime<Utc> = DateTime::parse_from_rfc3339(row.get("updated_at"))?.into();
+        let recurrence_id = row
+            .get::<Option<String>, _>("recurrence_id")
+            .map(|value| DateTime::parse_from_rfc3339(&value).map(DateTime::<Utc>::from))
+            .transpose()?;
 
         Ok(Event {
             id: row.get("id"),
@@ -782,6 +983,7 @@ impl CalendarDatabase {
             organizer,
             attendees,
             recurrence,
+            recurrence_id,
             reminders,
             categories,
             url: row.get("url"),
@@ -789,6 +991,7 @@ impl CalendarDatabase {
             updated_at,
             sequence: row.get::<i64, _>("sequence") as u32,
             etag: row.get("etag"),
+            timezone: row.get("timezone"),
         })
     }
 }
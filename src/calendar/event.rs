@@ -2,6 +2,8 @@ use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::calendar::timezone;
+
 /// Calendar event representation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Event {
@@ -19,6 +21,10 @@ pub struct Event {
     pub organizer: Option<EventAttendee>,
     pub attendees: Vec<EventAttendee>,
     pub recurrence: Option<EventRecurrence>,
+    /// Original start of the occurrence this event replaces in the series
+    /// with the same UID (iCalendar RECURRENCE-ID)
+    #[serde(default)]
+    pub recurrence_id: Option<DateTime<Utc>>,
     pub reminders: Vec<EventReminder>,
     pub categories: Vec<String>,
     pub url: Option<String>,
@@ -26,6 +32,10 @@ pub struct Event {
     pub updated_at: DateTime<Utc>,
     pub sequence: u32,        // iCalendar SEQUENCE for updates
     pub etag: Option<String>, // CalDAV ETag for sync
+    /// IANA name of the time zone the event's times were given in
+    /// (iCalendar TZID); `None` for UTC and all-day events
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 impl Event {
@@ -54,6 +64,7 @@ impl Event {
             organizer: None,
             attendees: Vec::new(),
             recurrence: None,
+            recurrence_id: None,
             reminders: Vec::new(),
             categories: Vec::new(),
             url: None,
@@ -61,6 +72,7 @@ impl Event {
             updated_at: now,
             sequence: 0,
             etag: None,
+            timezone: None,
         }
     }
 
@@ -134,6 +146,45 @@ impl Event {
         }
     }
 
+    /// Check if any of the given addresses is the event organizer
+    pub fn is_organized_by(&self, emails: &[String]) -> bool {
+        self.organizer.as_ref().is_some_and(|organizer| {
+            emails
+                .iter()
+                .any(|email| email.eq_ignore_ascii_case(&organizer.email))
+        })
+    }
+
+    /// Count attendee responses, leaving out the organizer
+    pub fn response_tally(&self) -> AttendeeTally {
+        let mut tally = AttendeeTally::default();
+        for attendee in self.invitees() {
+            match attendee.status {
+                AttendeeStatus::Accepted => tally.accepted += 1,
+                AttendeeStatus::Declined => tally.declined += 1,
+                AttendeeStatus::Tentative => tally.tentative += 1,
+                AttendeeStatus::NeedsAction => tally.needs_action += 1,
+                AttendeeStatus::Delegated => tally.delegated += 1,
+            }
+        }
+        tally
+    }
+
+    /// Attendees who haven't responded yet
+    pub fn non_responders(&self) -> Vec<&EventAttendee> {
+        self.invitees()
+            .filter(|attendee| attendee.status == AttendeeStatus::NeedsAction)
+            .filter(|attendee| attendee.role != AttendeeRole::NonParticipant)
+            .collect()
+    }
+
+    fn invitees(&self) -> impl Iterator<Item = &EventAttendee> {
+        let organizer = self.organizer.as_ref().map(|o| o.email.as_str());
+        self.attendees
+            .iter()
+            .filter(move |a| organizer.is_none_or(|email| !a.email.eq_ignore_ascii_case(email)))
+    }
+
     /// Add a reminder to the event
     pub fn add_reminder(&mut self, reminder: EventReminder) {
         self.reminders.push(reminder);
@@ -236,11 +287,28 @@ impl Event {
 
         if let Some(ref recurrence) = self.recurrence {
             ical.push_str(&format!("RRULE:{}\r\n", recurrence.to_icalendar()));
+            for date in &recurrence.exception_dates {
+                ical.push_str(&format!("EXDATE:{}\r\n", date.format("%Y%m%dT%H%M%SZ")));
+            }
+            for date in &recurrence.recurrence_dates {
+                ical.push_str(&format!("RDATE:{}\r\n", date.format("%Y%m%dT%H%M%SZ")));
+            }
+        }
+
+        if let Some(recurrence_id) = self.recurrence_id {
+            ical.push_str(&format!(
+                "RECURRENCE-ID:{}\r\n",
+                recurrence_id.format("%Y%m%dT%H%M%SZ")
+            ));
         }
 
         for reminder in &self.reminders {
             ical.push_str("BEGIN:VALARM\r\n");
-            ical.push_str(&format!("TRIGGER:{}\r\n", reminder.to_icalendar()));
+            ical.push_str(&format!(
+                "TRIGGER{}:{}\r\n",
+                reminder.trigger.related_param(),
+                reminder.to_icalendar()
+            ));
             ical.push_str(&format!("ACTION:{}\r\n", reminder.action.to_icalendar()));
             if let Some(ref description) = reminder.description {
                 ical.push_str(&format!("DESCRIPTION:{}\r\n", description));
@@ -368,6 +436,75 @@ impl EventAttendee {
         attendee.rsvp = false;
         attendee
     }
+
+    /// Parse an unfolded `ATTENDEE` or `ORGANIZER` content line
+    ///
+    /// Reads the `CN`, `PARTSTAT`, `ROLE` and `RSVP` parameters; returns `None`
+    /// when the line has no `mailto:` address.
+    pub fn from_icalendar_property(line: &str) -> Option<Self> {
+        // The value starts at the first colon outside a quoted parameter
+        let mut in_quotes = false;
+        let split = line.char_indices().find_map(|(index, c)| match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                None
+            }
+            ':' if !in_quotes => Some(index),
+            _ => None,
+        })?;
+        let (params, value) = (&line[..split], &line[split + 1..]);
+
+        let value = value.trim();
+        let email = if value.len() > 7 && value[..7].eq_ignore_ascii_case("mailto:") {
+            value[7..].trim()
+        } else {
+            return None;
+        };
+
+        let mut attendee = Self::new(email.to_string(), None);
+        for param in params.split(';').skip(1) {
+            let Some((key, val)) = param.split_once('=') else {
+                continue;
+            };
+            let val = val.trim_matches('"');
+            match key.to_uppercase().as_str() {
+                "CN" if !val.is_empty() => attendee.name = Some(val.to_string()),
+                "PARTSTAT" => attendee.status = AttendeeStatus::from_icalendar(val),
+                "ROLE" => attendee.role = AttendeeRole::from_icalendar(val),
+                "RSVP" => attendee.rsvp = val.eq_ignore_ascii_case("TRUE"),
+                _ => {}
+            }
+        }
+
+        Some(attendee)
+    }
+
+    /// Name if known, otherwise the email address
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.email)
+    }
+}
+
+/// Count of attendee responses to an event
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AttendeeTally {
+    pub accepted: usize,
+    pub declined: usize,
+    pub tentative: usize,
+    pub needs_action: usize,
+    pub delegated: usize,
+}
+
+impl AttendeeTally {
+    /// Total number of attendees counted
+    pub fn total(&self) -> usize {
+        self.accepted + self.declined + self.tentative + self.needs_action + self.delegated
+    }
+
+    /// Number of attendees who have responded
+    pub fn responded(&self) -> usize {
+        self.total() - self.needs_action
+    }
 }
 
 /// Attendee status
@@ -412,6 +549,57 @@ pub enum AttendeeRole {
     NonParticipant,
 }
 
+impl AttendeeRole {
+    pub fn from_icalendar(value: &str) -> Self {
+        match value.to_uppercase().as_str() {
+            "CHAIR" => AttendeeRole::Chair,
+            "REQ-PARTICIPANT" => AttendeeRole::RequiredParticipant,
+            "OPT-PARTICIPANT" => AttendeeRole::OptionalParticipant,
+            "NON-PARTICIPANT" => AttendeeRole::NonParticipant,
+            _ => AttendeeRole::RequiredParticipant,
+        }
+    }
+}
+
+/// Times of a DTSTART, DTEND, EXDATE, RDATE or RECURRENCE-ID content line
+///
+/// The value may list several, comma-separated. Local times are converted
+/// from the zone of the line's TZID; floating ones, without a TZID, are read
+/// as UTC like all other event times, and dates as midnight. Returns `None`
+/// if any of them doesn't parse.
+pub fn parse_icalendar_times(line: &str) -> Option<Vec<DateTime<Utc>>> {
+    let (_, value) = line.split_once(':')?;
+    let zone = timezone::line_timezone(line);
+    value
+        .split(',')
+        .map(|time| {
+            let time = time.trim();
+            let parsed = EventRecurrence::parse_icalendar_datetime(time).ok()?;
+            Some(match zone {
+                Some(tz) if time.len() == 15 => timezone::local_to_utc(parsed.naive_utc(), tz),
+                _ => parsed,
+            })
+        })
+        .collect()
+}
+
+/// Join folded iCalendar content lines (RFC 5545 section 3.1)
+pub fn unfold_icalendar_lines(data: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in data.lines() {
+        let raw = raw.trim_end_matches('\r');
+        match raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')) {
+            Some(continuation) if !lines.is_empty() => {
+                if let Some(last) = lines.last_mut() {
+                    last.push_str(continuation);
+                }
+            }
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
 /// Event recurrence pattern
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EventRecurrence {
@@ -425,6 +613,18 @@ pub struct EventRecurrence {
     pub by_week_no: Vec<i8>,
     pub by_year_day: Vec<i16>,
     pub week_start: RecurrenceDay,
+    /// BYDAY entries with an ordinal, such as `3TH` for the third Thursday;
+    /// their days are in `by_day` as well
+    #[serde(default)]
+    pub by_day_nth: Vec<(i8, RecurrenceDay)>,
+    #[serde(default)]
+    pub by_set_pos: Vec<i16>,
+    /// Occurrences removed from the series (EXDATE)
+    #[serde(default)]
+    pub exception_dates: Vec<DateTime<Utc>>,
+    /// Occurrences added to the series (RDATE)
+    #[serde(default)]
+    pub recurrence_dates: Vec<DateTime<Utc>>,
 }
 
 impl EventRecurrence {
@@ -440,6 +640,10 @@ impl EventRecurrence {
             by_week_no: Vec::new(),
             by_year_day: Vec::new(),
             week_start: RecurrenceDay::Monday,
+            by_day_nth: Vec::new(),
+            by_set_pos: Vec::new(),
+            exception_dates: Vec::new(),
+            recurrence_dates: Vec::new(),
         }
     }
 
@@ -455,6 +659,10 @@ impl EventRecurrence {
             by_week_no: Vec::new(),
             by_year_day: Vec::new(),
             week_start: RecurrenceDay::Monday,
+            by_day_nth: Vec::new(),
+            by_set_pos: Vec::new(),
+            exception_dates: Vec::new(),
+            recurrence_dates: Vec::new(),
         }
     }
 
@@ -470,6 +678,10 @@ impl EventRecurrence {
             by_week_no: Vec::new(),
             by_year_day: Vec::new(),
             week_start: RecurrenceDay::Monday,
+            by_day_nth: Vec::new(),
+            by_set_pos: Vec::new(),
+            exception_dates: Vec::new(),
+            recurrence_dates: Vec::new(),
         }
     }
 
@@ -489,11 +701,16 @@ impl EventRecurrence {
         }
 
         if !self.by_day.is_empty() {
-            let days: Vec<String> = self
-                .by_day
+            let mut days: Vec<String> = self
+                .plain_by_day()
                 .iter()
                 .map(|d| d.to_icalendar().to_string())
                 .collect();
+            days.extend(
+                self.by_day_nth
+                    .iter()
+                    .map(|(nth, d)| format!("{}{}", nth, d.to_icalendar())),
+            );
             rrule.push_str(&format!(";BYDAY={}", days.join(",")));
         }
 
@@ -502,6 +719,16 @@ impl EventRecurrence {
             rrule.push_str(&format!(";BYMONTHDAY={}", days.join(",")));
         }
 
+        if !self.by_month.is_empty() {
+            let months: Vec<String> = self.by_month.iter().map(|m| m.to_string()).collect();
+            rrule.push_str(&format!(";BYMONTH={}", months.join(",")));
+        }
+
+        if !self.by_set_pos.is_empty() {
+            let positions: Vec<String> = self.by_set_pos.iter().map(|p| p.to_string()).collect();
+            rrule.push_str(&format!(";BYSETPOS={}", positions.join(",")));
+        }
+
         rrule
     }
 
@@ -521,6 +748,10 @@ impl EventRecurrence {
             by_week_no: Vec::new(),
             by_year_day: Vec::new(),
             week_start: RecurrenceDay::Monday,
+            by_day_nth: Vec::new(),
+            by_set_pos: Vec::new(),
+            exception_dates: Vec::new(),
+            recurrence_dates: Vec::new(),
         };
 
         // Parse key-value pairs separated by semicolons
@@ -552,6 +783,10 @@ impl EventRecurrence {
                     }
                     "BYDAY" => {
                         recurrence.by_day = Self::parse_by_day(value)?;
+                        recurrence.by_day_nth = Self::parse_by_day_nth(value)?;
+                    }
+                    "BYSETPOS" => {
+                        recurrence.by_set_pos = Self::parse_by_set_pos(value)?;
                     }
                     "BYMONTHDAY" => {
                         recurrence.by_month_day = Self::parse_by_month_day(value)?;
@@ -591,6 +826,11 @@ impl EventRecurrence {
             NaiveDateTime::parse_from_str(datetime_str, "%Y%m%dT%H%M%S")
                 .map(|dt| dt.and_utc())
                 .map_err(|_| format!("Invalid datetime format: {}", value))
+        } else if value.len() == 15 {
+            // Local time, read as UTC until its TZID is applied: YYYYMMDDTHHMMSS
+            NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+                .map(|dt| dt.and_utc())
+                .map_err(|_| format!("Invalid datetime format: {}", value))
         } else if value.len() == 8 {
             // Date-only format: YYYYMMDD
             NaiveDate::parse_from_str(value, "%Y%m%d")
@@ -622,6 +862,54 @@ impl EventRecurrence {
         Ok(days)
     }
 
+    /// Parse the ordinals of BYDAY values (e.g., "3TH" or "-1FR")
+    fn parse_by_day_nth(value: &str) -> Result<Vec<(i8, RecurrenceDay)>, String> {
+        let mut days = Vec::new();
+
+        for day_spec in value.split(',') {
+            let day_spec = day_spec.trim();
+            if day_spec.len() <= 2 {
+                continue;
+            }
+
+            let (nth, day_code) = day_spec.split_at(day_spec.len() - 2);
+            let nth = nth
+                .trim_start_matches('+')
+                .parse::<i8>()
+                .ok()
+                .filter(|nth| *nth != 0 && nth.abs() <= 53)
+                .ok_or_else(|| format!("Invalid BYDAY value: {}", day_spec))?;
+            days.push((nth, RecurrenceDay::from_icalendar(day_code)?));
+        }
+
+        Ok(days)
+    }
+
+    /// Parse BYSETPOS values (e.g., "3" or "-1")
+    fn parse_by_set_pos(value: &str) -> Result<Vec<i16>, String> {
+        value
+            .split(',')
+            .map(|s| {
+                s.trim()
+                    .parse::<i16>()
+                    .ok()
+                    .filter(|pos| *pos != 0)
+                    .ok_or_else(|| format!("Invalid BYSETPOS value: {}", s))
+            })
+            .collect()
+    }
+
+    /// BYDAY weekdays without an ordinal
+    pub fn plain_by_day(&self) -> Vec<RecurrenceDay> {
+        let mut days = self.by_day.clone();
+        for (_, day) in &self.by_day_nth {
+            if let Some(index) = days.iter().position(|d| d == day) {
+                days.remove(index);
+            }
+        }
+        days
+    }
+
     /// Parse BYMONTHDAY values (e.g., "1,15,-1")
     fn parse_by_month_day(value: &str) -> Result<Vec<i8>, String> {
         value
@@ -741,6 +1029,18 @@ impl RecurrenceDay {
         }
     }
 
+    pub fn to_weekday(&self) -> chrono::Weekday {
+        match self {
+            RecurrenceDay::Sunday => chrono::Weekday::Sun,
+            RecurrenceDay::Monday => chrono::Weekday::Mon,
+            RecurrenceDay::Tuesday => chrono::Weekday::Tue,
+            RecurrenceDay::Wednesday => chrono::Weekday::Wed,
+            RecurrenceDay::Thursday => chrono::Weekday::Thu,
+            RecurrenceDay::Friday => chrono::Weekday::Fri,
+            RecurrenceDay::Saturday => chrono::Weekday::Sat,
+        }
+    }
+
     pub fn from_icalendar(value: &str) -> Result<Self, String> {
         match value.to_uppercase().as_str() {
             "SU" => Ok(RecurrenceDay::Sunday),
@@ -774,12 +1074,54 @@ impl EventReminder {
         }
     }
 
+    /// Parse the properties of a VALARM component
+    ///
+    /// `start_time` turns an absolute `TRIGGER;VALUE=DATE-TIME` into an
+    /// offset from the start of the event. Returns `None` if the alarm has no
+    /// trigger this parser understands.
+    pub fn from_valarm(lines: &[&str], start_time: DateTime<Utc>) -> Option<Self> {
+        let mut trigger = None;
+        let mut action = ReminderAction::Display;
+        let mut description = None;
+        let mut attendees = Vec::new();
+
+        for &line in lines {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let property = name.split(';').next().unwrap_or(name).to_uppercase();
+            match property.as_str() {
+                "TRIGGER" => trigger = ReminderTrigger::from_icalendar(line, start_time),
+                "ACTION" => action = ReminderAction::from_icalendar(value),
+                "DESCRIPTION" => description = Some(value.to_string()),
+                "ATTENDEE" => attendees.push(
+                    value
+                        .strip_prefix("mailto:")
+                        .or_else(|| value.strip_prefix("MAILTO:"))
+                        .unwrap_or(value)
+                        .to_string(),
+                ),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            trigger: trigger?,
+            action,
+            description,
+            attendees,
+        })
+    }
+
     pub fn to_icalendar(&self) -> String {
         self.trigger.to_icalendar()
     }
 }
 
 /// Reminder trigger timing
+///
+/// Durations count back from the start or end of the event; a negative
+/// duration fires after it.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ReminderTrigger {
     BeforeStart(Duration),
@@ -789,18 +1131,134 @@ pub enum ReminderTrigger {
 }
 
 impl ReminderTrigger {
+    /// Parse a whole `TRIGGER` line, such as `TRIGGER:-PT15M` or
+    /// `TRIGGER;RELATED=END:-P1D`
+    pub fn from_icalendar(line: &str, start_time: DateTime<Utc>) -> Option<Self> {
+        let (params, value) = line.split_once(':')?;
+        let params = params.to_uppercase();
+
+        if params.contains("VALUE=DATE-TIME") {
+            let time = EventRecurrence::parse_icalendar_datetime(value.trim()).ok()?;
+            return Some(ReminderTrigger::BeforeStart(start_time - time));
+        }
+
+        let offset = parse_icalendar_duration(value.trim())?;
+        let trigger = match (params.contains("RELATED=END"), offset.is_zero()) {
+            (true, true) => ReminderTrigger::AtEnd,
+            (true, false) => ReminderTrigger::BeforeEnd(-offset),
+            (false, true) => ReminderTrigger::AtStart,
+            (false, false) => ReminderTrigger::BeforeStart(-offset),
+        };
+        Some(trigger)
+    }
+
     pub fn to_icalendar(&self) -> String {
         match self {
-            ReminderTrigger::BeforeStart(duration) => {
-                format!("-PT{}M", duration.num_minutes())
-            }
-            ReminderTrigger::BeforeEnd(duration) => {
-                format!("-PT{}M", duration.num_minutes())
+            ReminderTrigger::BeforeStart(duration) | ReminderTrigger::BeforeEnd(duration) => {
+                format_icalendar_duration(-*duration)
             }
             ReminderTrigger::AtStart => "PT0M".to_string(),
             ReminderTrigger::AtEnd => "PT0M".to_string(),
         }
     }
+
+    /// `;RELATED=END` for triggers relative to the end of the event
+    pub fn related_param(&self) -> &'static str {
+        match self {
+            ReminderTrigger::BeforeEnd(_) | ReminderTrigger::AtEnd => ";RELATED=END",
+            ReminderTrigger::BeforeStart(_) | ReminderTrigger::AtStart => "",
+        }
+    }
+
+    /// When the reminder goes off for an event running from `start_time` to
+    /// `end_time`
+    pub fn fire_time(&self, start_time: DateTime<Utc>, end_time: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            ReminderTrigger::BeforeStart(duration) => start_time - *duration,
+            ReminderTrigger::BeforeEnd(duration) => end_time - *duration,
+            ReminderTrigger::AtStart => start_time,
+            ReminderTrigger::AtEnd => end_time,
+        }
+    }
+}
+
+/// Parse an iCalendar duration such as `-PT15M`, `P1D` or `-P1DT2H`
+/// (RFC 5545 section 3.3.6)
+pub fn parse_icalendar_duration(value: &str) -> Option<Duration> {
+    let (negative, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let rest = rest.strip_prefix('P')?;
+
+    let mut seconds = 0i64;
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            _ => {
+                let amount: i64 = number.parse().ok()?;
+                number.clear();
+                seconds += amount
+                    * match (c, in_time) {
+                        ('W', false) => 7 * 86_400,
+                        ('D', false) => 86_400,
+                        ('H', true) => 3_600,
+                        ('M', true) => 60,
+                        ('S', true) => 1,
+                        _ => return None,
+                    };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+
+    Some(Duration::seconds(if negative { -seconds } else { seconds }))
+}
+
+/// Parse an iCalendar period such as `20250310T140000Z/20250310T150000Z` or
+/// `20250310T140000Z/PT1H` (RFC 5545 section 3.3.9)
+pub fn parse_icalendar_period(value: &str) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let (start, end) = value.trim().split_once('/')?;
+    let start = EventRecurrence::parse_icalendar_datetime(start).ok()?;
+    let end = match parse_icalendar_duration(end) {
+        Some(duration) => start + duration,
+        None => EventRecurrence::parse_icalendar_datetime(end).ok()?,
+    };
+    Some((start, end))
+}
+
+/// Format a duration the way `parse_icalendar_duration` reads it
+pub fn format_icalendar_duration(duration: Duration) -> String {
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    let seconds = duration.num_seconds().abs();
+    if seconds == 0 {
+        return "PT0M".to_string();
+    }
+
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let mut value = format!("{}P", sign);
+    if days > 0 {
+        value.push_str(&format!("{}D", days));
+    }
+    if rest > 0 {
+        value.push('T');
+        let (hours, minutes, secs) = (rest / 3_600, rest % 3_600 / 60, rest % 60);
+        if hours > 0 {
+            value.push_str(&format!("{}H", hours));
+        }
+        if minutes > 0 {
+            value.push_str(&format!("{}M", minutes));
+        }
+        if secs > 0 {
+            value.push_str(&format!("{}S", secs));
+        }
+    }
+    value
 }
 
 /// Reminder action type
@@ -819,6 +1277,14 @@ impl ReminderAction {
             ReminderAction::Audio => "AUDIO",
         }
     }
+
+    pub fn from_icalendar(value: &str) -> Self {
+        match value.trim().to_uppercase().as_str() {
+            "EMAIL" => ReminderAction::Email,
+            "AUDIO" => ReminderAction::Audio,
+            _ => ReminderAction::Display,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1120,4 +1586,114 @@ mod tests {
         assert!(EventRecurrence::parse_icalendar_datetime("invalid").is_err());
         assert!(EventRecurrence::parse_icalendar_datetime("2025-01-28").is_err());
     }
+
+    #[test]
+    fn test_attendee_property_parsing() {
+        let lines = unfold_icalendar_lines(
+            "ATTENDEE;CN=\"Doe, Jane\";ROLE=OPT-PARTICIPANT;PARTSTAT=TENTATIVE;RSVP=TRUE:mai\r\n lto:jane@example.com\r\nORGANIZER;CN=Bob:mailto:bob@example.com\r\n",
+        );
+        assert_eq!(lines.len(), 2);
+
+        let attendee = EventAttendee::from_icalendar_property(&lines[0]).unwrap();
+        assert_eq!(attendee.email, "jane@example.com");
+        assert_eq!(attendee.name.as_deref(), Some("Doe, Jane"));
+        assert_eq!(attendee.status, AttendeeStatus::Tentative);
+        assert_eq!(attendee.role, AttendeeRole::OptionalParticipant);
+        assert!(attendee.rsvp);
+
+        let organizer = EventAttendee::from_icalendar_property(&lines[1]).unwrap();
+        assert_eq!(organizer.email, "bob@example.com");
+        assert_eq!(organizer.status, AttendeeStatus::NeedsAction);
+
+        assert!(EventAttendee::from_icalendar_property("ATTENDEE:urn:uuid:1234").is_none());
+    }
+
+    #[test]
+    fn test_response_tally() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 28, 10, 0, 0).unwrap();
+        let mut event = Event::new(
+            "calendar1".to_string(),
+            "Planning".to_string(),
+            start,
+            start + Duration::hours(1),
+        );
+        event.organizer = Some(EventAttendee::organizer("me@example.com".to_string(), None));
+        event.attendees = vec![
+            EventAttendee::organizer("ME@example.com".to_string(), None),
+            EventAttendee::new("a@example.com".to_string(), None),
+            EventAttendee::new("b@example.com".to_string(), None),
+            EventAttendee::new("c@example.com".to_string(), None),
+        ];
+        event.update_attendee_status("a@example.com", AttendeeStatus::Accepted);
+        event.update_attendee_status("b@example.com", AttendeeStatus::Declined);
+
+        let tally = event.response_tally();
+        assert_eq!(tally.accepted, 1);
+        assert_eq!(tally.declined, 1);
+        assert_eq!(tally.needs_action, 1);
+        assert_eq!((tally.responded(), tally.total()), (2, 3));
+
+        let pending: Vec<&str> = event
+            .non_responders()
+            .iter()
+            .map(|a| a.email.as_str())
+            .collect();
+        assert_eq!(pending, vec!["c@example.com"]);
+
+        assert!(event.is_organized_by(&["me@EXAMPLE.com".to_string()]));
+        assert!(!event.is_organized_by(&["a@example.com".to_string()]));
+    }
+
+    #[test]
+    fn test_icalendar_duration_round_trip() {
+        assert_eq!(
+            parse_icalendar_duration("-PT15M"),
+            Some(Duration::minutes(-15))
+        );
+        assert_eq!(parse_icalendar_duration("P1W"), Some(Duration::weeks(1)));
+        assert_eq!(
+            parse_icalendar_duration("-P1DT2H30M"),
+            Some(-(Duration::days(1) + Duration::minutes(150)))
+        );
+        assert_eq!(parse_icalendar_duration("+PT0S"), Some(Duration::zero()));
+        assert_eq!(parse_icalendar_duration("PT15"), None);
+        assert_eq!(parse_icalendar_duration("15M"), None);
+
+        for value in ["-PT15M", "P1D", "-P1DT2H30M", "PT45S"] {
+            let duration = parse_icalendar_duration(value).unwrap();
+            assert_eq!(format_icalendar_duration(duration), value);
+        }
+    }
+
+    #[test]
+    fn test_reminder_trigger_round_trip() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 6, 9, 30, 0).unwrap();
+        let mut event = Event::new("cal".to_string(), "Review".to_string(), start, start);
+        event.reminders = vec![
+            EventReminder::new(
+                ReminderTrigger::BeforeStart(Duration::minutes(15)),
+                ReminderAction::Display,
+            ),
+            EventReminder::new(
+                ReminderTrigger::BeforeEnd(Duration::hours(1)),
+                ReminderAction::Audio,
+            ),
+        ];
+
+        let ical = event.to_icalendar();
+        assert!(ical.contains("TRIGGER:-PT15M\r\n"));
+        assert!(ical.contains("TRIGGER;RELATED=END:-PT1H\r\n"));
+
+        let trigger = ReminderTrigger::from_icalendar("TRIGGER;RELATED=END:-PT1H", start).unwrap();
+        assert!(matches!(trigger, ReminderTrigger::BeforeEnd(d) if d == Duration::hours(1)));
+        let trigger = ReminderTrigger::from_icalendar("TRIGGER:PT5M", start).unwrap();
+        assert_eq!(
+            trigger.fire_time(start, start),
+            start + Duration::minutes(5)
+        );
+        assert!(matches!(
+            ReminderTrigger::from_icalendar("TRIGGER:PT0S", start),
+            Some(ReminderTrigger::AtStart)
+        ));
+    }
 }
@@ -1,10 +1,13 @@
-use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+use chrono_tz::Tz;
 use crossterm::event::KeyModifiers;
 use ratatui::{layout::Rect, widgets::ListState, Frame};
 
 use crate::calendar::database::CalendarDatabase;
 use crate::calendar::event::{AttendeeStatus, Event, EventAttendee, EventRecurrence, EventStatus};
-use crate::calendar::{Calendar, CalendarError, CalendarResult};
+use crate::calendar::{
+    timezone, Calendar, CalendarError, CalendarResult, ConflictResolution, SyncConflict,
+};
 use crate::ui::date_picker::DatePicker;
 use crate::ui::time_picker::TimePicker;
 
@@ -57,6 +60,8 @@ pub enum EventFormAction {
     RemoveAttendee(usize),
     ToggleRecurrence,
     UpdateRecurrence(EventRecurrence),
+    /// Settle the conflict shown after the server refused a save or delete
+    ResolveConflict(ConflictResolution),
 }
 
 /// Event form field types
@@ -151,12 +156,20 @@ pub struct EventFormUI {
     pub show_recurrence_editor: bool,
     pub attendee_input: String,
     pub is_modified: bool,
+    /// Save or delete the server refused, waiting for the user's decision
+    pub sync_conflict: Option<SyncConflict>,
+    /// Zone the form's dates and times are in
+    pub timezone: Tz,
 }
 
 impl EventFormUI {
     /// Create a new event form for creating an event
-    pub fn new_create(calendars: Vec<Calendar>, default_calendar_id: Option<String>) -> Self {
-        let now = Local::now();
+    pub fn new_create(
+        calendars: Vec<Calendar>,
+        default_calendar_id: Option<String>,
+        timezone: Tz,
+    ) -> Self {
+        let now = Utc::now().with_timezone(&timezone);
         let start_time = now.time();
         let end_time = (now + Duration::hours(1)).time();
 
@@ -164,12 +177,13 @@ impl EventFormUI {
             .or_else(|| calendars.first().map(|c| c.id.clone()))
             .unwrap_or_default();
 
-        let event = Event::new(
+        let mut event = Event::new(
             default_calendar.clone(),
             String::new(),
             now.with_timezone(&Utc),
             (now + Duration::hours(1)).with_timezone(&Utc),
         );
+        event.timezone = Some(timezone.name().to_string());
 
         Self {
             mode: EventFormMode::Create,
@@ -204,13 +218,17 @@ impl EventFormUI {
             show_recurrence_editor: false,
             attendee_input: String::new(),
             is_modified: false,
+            sync_conflict: None,
+            timezone,
         }
     }
 
     /// Create a new event form for editing an existing event
-    pub fn new_edit(event: Event, calendars: Vec<Calendar>) -> Self {
-        let start_local = event.start_time.with_timezone(&Local);
-        let end_local = event.end_time.with_timezone(&Local);
+    pub fn new_edit(event: Event, calendars: Vec<Calendar>, timezone: Tz) -> Self {
+        // All-day events are kept as UTC dates
+        let zone = if event.all_day { Tz::UTC } else { timezone };
+        let start_local = event.start_time.with_timezone(&zone);
+        let end_local = event.end_time.with_timezone(&zone);
 
         let mut form = Self {
             mode: EventFormMode::Edit(event.id.clone()),
@@ -245,6 +263,8 @@ impl EventFormUI {
             show_recurrence_editor: false,
             attendee_input: String::new(),
             is_modified: false,
+            sync_conflict: None,
+            timezone,
         };
 
         // Set initial calendar selection
@@ -264,8 +284,8 @@ impl EventFormUI {
     }
 
     /// Create a new event form for viewing an existing event (read-only)
-    pub fn new_view(event: Event, calendars: Vec<Calendar>) -> Self {
-        let mut form = Self::new_edit(event, calendars);
+    pub fn new_view(event: Event, calendars: Vec<Calendar>, timezone: Tz) -> Self {
+        let mut form = Self::new_edit(event, calendars, timezone);
         form.mode = EventFormMode::View(form.event.id.clone());
         form
     }
@@ -774,16 +794,14 @@ impl EventFormUI {
             self.end_time
         });
 
-        let start_utc = Local
-            .from_local_datetime(&start_datetime)
-            .single()
-            .unwrap()
-            .with_timezone(&Utc);
-        let end_utc = Local
-            .from_local_datetime(&end_datetime)
-            .single()
-            .unwrap()
-            .with_timezone(&Utc);
+        let (start_utc, end_utc) = if self.is_all_day {
+            (start_datetime.and_utc(), end_datetime.and_utc())
+        } else {
+            (
+                timezone::local_to_utc(start_datetime, self.timezone),
+                timezone::local_to_utc(end_datetime, self.timezone),
+            )
+        };
 
         let mut event = Event::new(
             self.selected_calendar_id.clone(),
@@ -808,6 +826,15 @@ impl EventFormUI {
         event.all_day = self.is_all_day;
         event.recurrence = self.recurrence_rule.clone();
         event.attendees = self.attendees.clone();
+        event.timezone = if self.is_all_day {
+            None
+        } else {
+            // Edited series keep repeating in the zone they were set up in
+            self.event
+                .timezone
+                .clone()
+                .or_else(|| Some(self.timezone.name().to_string()))
+        };
 
         // Note: Event struct doesn't have notes field, we could extend description instead
         if !self.notes_input.is_empty() {
@@ -825,6 +852,12 @@ impl EventFormUI {
             event.created_at = self.event.created_at;
             event.sequence = self.event.sequence + 1;
             event.etag = self.event.etag.clone();
+            event.url = self.event.url.clone();
+            event.recurrence_id = self.event.recurrence_id;
+            event.organizer = self.event.organizer.clone();
+            event.priority = self.event.priority.clone();
+            event.reminders = self.event.reminders.clone();
+            event.categories = self.event.categories.clone();
         }
 
         event
@@ -853,6 +886,17 @@ impl EventFormUI {
     pub async fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> Option<EventFormAction> {
         use crossterm::event::KeyCode;
 
+        // A refused save waits for a decision before anything else
+        if let Some(conflict) = &self.sync_conflict {
+            let resolution = match key.code {
+                KeyCode::Char('m') if !conflict.deleted => ConflictResolution::Merge,
+                KeyCode::Char('o') => ConflictResolution::Overwrite,
+                KeyCode::Char('k') | KeyCode::Esc => ConflictResolution::KeepServer,
+                _ => return None,
+            };
+            return Some(EventFormAction::ResolveConflict(resolution));
+        }
+
         // Handle popup dialogs first
         if self.show_date_picker {
             match key.code {
@@ -954,7 +998,7 @@ impl EventFormUI {
                     return Some(EventFormAction::Save);
                 }
             }
-            KeyCode::Char('d') => {
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 if matches!(self.mode, EventFormMode::Edit(_)) {
                     return Some(EventFormAction::Delete);
                 }
@@ -1022,7 +1066,7 @@ impl EventFormUI {
         let instructions = if self.is_read_only() {
             "Press 'e' to edit, 'd' to delete, or Esc to close"
         } else {
-            "Ctrl+S: Save | d: Delete | Tab/Shift+Tab: Navigate | Esc: Cancel"
+            "Ctrl+S: Save | Ctrl+D: Delete | Tab/Shift+Tab: Navigate | Esc: Cancel"
         };
 
         let instruction_paragraph = Paragraph::new(instructions)
@@ -1039,6 +1083,75 @@ impl EventFormUI {
         if self.show_time_picker {
             self.render_time_picker_popup(frame, area, theme);
         }
+
+        if let Some(conflict) = &self.sync_conflict {
+            self.render_conflict_popup(frame, area, theme, conflict);
+        }
+    }
+
+    /// Render the choice offered when the server refused a change
+    fn render_conflict_popup(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &crate::theme::Theme,
+        conflict: &SyncConflict,
+    ) {
+        use ratatui::{
+            layout::{Alignment, Constraint, Direction, Layout},
+            style::Style,
+            widgets::{Block, Borders, Clear, Paragraph, Wrap},
+        };
+
+        let popup_area = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(30),
+                Constraint::Length(9),
+                Constraint::Percentage(30),
+            ])
+            .split(area)[1];
+
+        let popup_area = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(20),
+                Constraint::Percentage(60),
+                Constraint::Percentage(20),
+            ])
+            .split(popup_area)[1];
+
+        frame.render_widget(Clear, popup_area);
+
+        let remote = &conflict.remote;
+        let choices = if conflict.deleted {
+            "o: Delete anyway | k/Esc: Keep server version"
+        } else {
+            "m: Merge my changes | o: Overwrite | k/Esc: Keep server version"
+        };
+        let content = format!(
+            "\"{}\" was changed on the server since it was loaded.\n\nServer version: {} – {}\n\n{}",
+            remote.title,
+            remote
+                .start_time
+                .with_timezone(&self.timezone)
+                .format("%Y-%m-%d %H:%M"),
+            remote.end_time.with_timezone(&self.timezone).format("%H:%M"),
+            choices
+        );
+
+        let block = Block::default()
+            .title("Sync Conflict")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.colors.palette.warning));
+
+        let paragraph = Paragraph::new(content)
+            .block(block)
+            .style(Style::default().fg(theme.colors.content_preview.body))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(paragraph, popup_area);
     }
 
     /// Render the main form content
@@ -1388,3 +1501,59 @@ pub mod time_picker {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    fn conflict(event: &Event, deleted: bool) -> SyncConflict {
+        SyncConflict {
+            base: event.clone(),
+            local: event.clone(),
+            remote: event.clone(),
+            deleted,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conflict_keys() {
+        let start = Utc::now();
+        let event = Event::new(
+            "cal".to_string(),
+            "Review".to_string(),
+            start,
+            start + Duration::hours(1),
+        );
+        let mut form = EventFormUI::new_edit(event.clone(), Vec::new(), Tz::UTC);
+        let key = |code| KeyEvent::new(code, KeyModifiers::empty());
+
+        form.sync_conflict = Some(conflict(&event, false));
+        assert_eq!(form.handle_key(key(KeyCode::Char('x'))).await, None);
+        assert_eq!(
+            form.handle_key(key(KeyCode::Char('m'))).await,
+            Some(EventFormAction::ResolveConflict(ConflictResolution::Merge))
+        );
+        assert_eq!(
+            form.handle_key(key(KeyCode::Esc)).await,
+            Some(EventFormAction::ResolveConflict(ConflictResolution::KeepServer))
+        );
+
+        // Nothing to merge into a delete
+        form.sync_conflict = Some(conflict(&event, true));
+        assert_eq!(form.handle_key(key(KeyCode::Char('m'))).await, None);
+        assert_eq!(
+            form.handle_key(key(KeyCode::Char('o'))).await,
+            Some(EventFormAction::ResolveConflict(ConflictResolution::Overwrite))
+        );
+
+        // Typing a 'd' no longer deletes the event
+        form.sync_conflict = None;
+        assert_eq!(form.handle_key(key(KeyCode::Char('d'))).await, None);
+        assert_eq!(
+            form.handle_key(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL))
+                .await,
+            Some(EventFormAction::Delete)
+        );
+    }
+}
@@ -405,6 +405,12 @@ impl From<GoogleEvent> for Event {
             }
         });
 
+        let timezone = google_event
+            .start
+            .time_zone
+            .clone()
+            .filter(|_| google_event.start.date.is_none());
+
         let status = match google_event.status.as_deref() {
             Some("confirmed") => EventStatus::Confirmed,
             Some("tentative") => EventStatus::Tentative,
@@ -454,6 +460,7 @@ impl From<GoogleEvent> for Event {
                 })
                 .collect(),
             recurrence: None,       // TODO: Parse Google Calendar recurrence rules
+            recurrence_id: None,
             reminders: Vec::new(),  // TODO: Parse Google Calendar reminders
             categories: Vec::new(), // Google Calendar doesn't have categories
             url: google_event.html_link,
@@ -461,6 +468,7 @@ impl From<GoogleEvent> for Event {
             updated_at: google_event.updated.unwrap_or_else(|| Utc::now()),
             sequence: 0, // Google Calendar doesn't expose sequence
             etag: None,  // Google Calendar uses different sync mechanism
+            timezone,
         }
     }
 }
@@ -478,7 +486,7 @@ impl From<&Event> for GoogleEvent {
             GoogleEventDateTime {
                 date_time: Some(event.start_time),
                 date: None,
-                time_zone: Some("UTC".to_string()),
+                time_zone: Some(event.timezone.clone().unwrap_or_else(|| "UTC".to_string())),
             }
         };
 
@@ -492,7 +500,7 @@ impl From<&Event> for GoogleEvent {
             GoogleEventDateTime {
                 date_time: Some(event.end_time),
                 date: None,
-                time_zone: Some("UTC".to_string()),
+                time_zone: Some(event.timezone.clone().unwrap_or_else(|| "UTC".to_string())),
             }
         };
 
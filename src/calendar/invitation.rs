@@ -3,8 +3,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 
-use crate::calendar::event::{AttendeeRole, AttendeeStatus, Event, EventAttendee, EventStatus};
-use crate::calendar::CalendarError;
+use crate::calendar::event::{
+    parse_icalendar_duration, unfold_icalendar_lines, AttendeeRole, AttendeeStatus, Event,
+    EventAttendee, EventRecurrence, EventStatus,
+};
+use crate::calendar::{timezone, CalendarError};
 use crate::email::{StoredAttachment, StoredMessage};
 
 /// Meeting invitation processing errors
@@ -52,12 +55,20 @@ pub struct MeetingInvitation {
     /// All-day event flag
     pub all_day: bool,
 
+    /// IANA name of the time zone the times were given in (TZID)
+    #[serde(default)]
+    pub timezone: Option<String>,
+
     /// Event organizer
     pub organizer: Option<EventAttendee>,
 
     /// List of attendees
     pub attendees: Vec<EventAttendee>,
 
+    /// How the meeting repeats (RRULE)
+    #[serde(default)]
+    pub recurrence: Option<EventRecurrence>,
+
     /// Invitation method (REQUEST, REPLY, CANCEL, etc.)
     pub method: InvitationMethod,
 
@@ -77,6 +88,40 @@ pub struct MeetingInvitation {
     pub processed_at: DateTime<Utc>,
 }
 
+impl MeetingInvitation {
+    /// The meeting as an event in `calendar_id`, with the invitation's UID as
+    /// its ID
+    pub fn to_event(&self, calendar_id: String) -> Event {
+        let now = Utc::now();
+
+        Event {
+            id: self.uid.clone(),
+            uid: self.uid.clone(),
+            calendar_id,
+            title: self.title.clone(),
+            description: self.description.clone(),
+            location: self.location.clone(),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            all_day: self.all_day,
+            status: self.status,
+            priority: crate::calendar::event::EventPriority::Normal,
+            organizer: self.organizer.clone(),
+            attendees: self.attendees.clone(),
+            recurrence: self.recurrence.clone(),
+            recurrence_id: None,
+            reminders: Vec::new(),
+            categories: Vec::new(),
+            url: None,
+            created_at: now,
+            updated_at: now,
+            sequence: self.sequence,
+            etag: None,
+            timezone: self.timezone.clone(),
+        }
+    }
+}
+
 /// iCalendar invitation methods
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum InvitationMethod {
@@ -228,20 +273,23 @@ impl InvitationProcessor {
         ical_data: &str,
         email_message_id: Option<String>,
     ) -> InvitationResult<MeetingInvitation> {
-        let lines = ical_data
-            .lines()
-            .map(|line| line.trim())
-            .collect::<Vec<_>>();
+        let lines = unfold_icalendar_lines(ical_data);
         let mut properties = HashMap::new();
         let mut attendees = Vec::new();
         let mut organizer = None;
+        let mut time_zones = HashMap::new();
 
         // Basic iCalendar parsing - find VEVENT section
         let mut in_event = false;
         let mut in_calendar = false;
         let mut method = InvitationMethod::Request;
+        // Components inside the event, such as VALARM, have their own
+        // DESCRIPTION and others
+        let mut nested = 0;
+
+        for line in &lines {
+            let line = line.trim();
 
-        for line in lines {
             if line == "BEGIN:VCALENDAR" {
                 in_calendar = true;
                 continue;
@@ -262,10 +310,26 @@ impl InvitationProcessor {
             }
 
             if line == "END:VEVENT" {
+                // Later VEVENTs change single occurrences of the first
+                if properties.contains_key("UID") {
+                    break;
+                }
                 in_event = false;
                 continue;
             }
 
+            if in_event && line.starts_with("BEGIN:") {
+                nested += 1;
+                continue;
+            }
+
+            if in_event && nested > 0 {
+                if line.starts_with("END:") {
+                    nested -= 1;
+                }
+                continue;
+            }
+
             // Parse calendar-level properties
             if !in_event && line.starts_with("METHOD:") {
                 let method_str = line.strip_prefix("METHOD:").unwrap_or("REQUEST");
@@ -293,6 +357,9 @@ impl InvitationProcessor {
                     // Store other properties
                     let clean_key = key.split(';').next().unwrap_or(key);
                     properties.insert(clean_key.to_string(), value.to_string());
+                    if let Some(tz) = timezone::line_timezone(line) {
+                        time_zones.insert(clean_key.to_string(), tz);
+                    }
                 }
             }
         }
@@ -308,17 +375,23 @@ impl InvitationProcessor {
             .ok_or_else(|| InvitationError::MissingField("SUMMARY".to_string()))?
             .clone();
 
-        let start_time = self.parse_datetime(
+        let start_time = self.parse_zoned_datetime(
             properties
                 .get("DTSTART")
                 .ok_or_else(|| InvitationError::MissingField("DTSTART".to_string()))?,
+            time_zones.get("DTSTART"),
         )?;
 
-        let end_time = self.parse_datetime(
-            properties
-                .get("DTEND")
-                .ok_or_else(|| InvitationError::MissingField("DTEND".to_string()))?,
-        )?;
+        let end_time = match (properties.get("DTEND"), properties.get("DURATION")) {
+            (Some(end), _) => self.parse_zoned_datetime(end, time_zones.get("DTEND"))?,
+            (None, Some(duration)) => {
+                start_time
+                    + parse_icalendar_duration(duration).ok_or_else(|| {
+                        InvitationError::InvalidFormat(format!("Invalid DURATION: {}", duration))
+                    })?
+            }
+            (None, None) => return Err(InvitationError::MissingField("DTEND".to_string())),
+        };
 
         // Parse optional fields
         let description = properties.get("DESCRIPTION").cloned();
@@ -347,8 +420,15 @@ impl InvitationProcessor {
             start_time,
             end_time,
             all_day,
+            timezone: time_zones
+                .get("DTSTART")
+                .filter(|_| !all_day)
+                .map(|tz| tz.name().to_string()),
             organizer,
             attendees,
+            recurrence: properties
+                .get("RRULE")
+                .and_then(|rule| EventRecurrence::from_icalendar(rule).ok()),
             method,
             status,
             sequence,
@@ -358,13 +438,28 @@ impl InvitationProcessor {
         })
     }
 
+    /// Parse a datetime, converting local times from the TZID's zone
+    fn parse_zoned_datetime(
+        &self,
+        dt_str: &str,
+        tz: Option<&chrono_tz::Tz>,
+    ) -> InvitationResult<DateTime<Utc>> {
+        let time = self.parse_datetime(dt_str)?;
+        Ok(match tz {
+            Some(tz) if !dt_str.ends_with('Z') && dt_str.contains('T') => {
+                timezone::local_to_utc(time.naive_utc(), *tz)
+            }
+            _ => time,
+        })
+    }
+
     /// Parse datetime from iCalendar format
     fn parse_datetime(&self, dt_str: &str) -> InvitationResult<DateTime<Utc>> {
         // Handle different datetime formats
         if dt_str.ends_with('Z') {
             // UTC format: 20250128T100000Z
-            DateTime::parse_from_str(dt_str, "%Y%m%dT%H%M%SZ")
-                .map(|dt| dt.with_timezone(&Utc))
+            chrono::NaiveDateTime::parse_from_str(dt_str, "%Y%m%dT%H%M%SZ")
+                .map(|dt| dt.and_utc())
                 .map_err(InvitationError::DateParse)
         } else if dt_str.contains('T') {
             // Local format: 20250128T100000
@@ -379,57 +474,7 @@ impl InvitationProcessor {
 
     /// Parse attendee from iCalendar ATTENDEE line
     fn parse_attendee(&self, line: &str) -> Option<EventAttendee> {
-        // Example: ATTENDEE;CN=John Doe;PARTSTAT=NEEDS-ACTION:mailto:john@example.com
-        let mut email = String::new();
-        let mut name = None;
-        let mut status = AttendeeStatus::NeedsAction;
-        let mut role = AttendeeRole::RequiredParticipant;
-        let mut rsvp = false;
-
-        // Extract email from mailto: URI
-        if let Some(mailto_pos) = line.find("mailto:") {
-            email = line[mailto_pos + 7..].to_string();
-        }
-
-        // Parse parameters
-        let parts: Vec<&str> = line.split(';').collect();
-        for part in parts {
-            if let Some(eq_pos) = part.find('=') {
-                let key = &part[..eq_pos];
-                let value = &part[eq_pos + 1..];
-
-                match key {
-                    "CN" => name = Some(value.to_string()),
-                    "PARTSTAT" => status = AttendeeStatus::from_icalendar(value),
-                    "ROLE" => {
-                        role = match value {
-                            "CHAIR" => AttendeeRole::Chair,
-                            "REQ-PARTICIPANT" => AttendeeRole::RequiredParticipant,
-                            "OPT-PARTICIPANT" => AttendeeRole::OptionalParticipant,
-                            "NON-PARTICIPANT" => AttendeeRole::NonParticipant,
-                            _ => AttendeeRole::RequiredParticipant,
-                        };
-                    }
-                    "RSVP" => rsvp = value.to_uppercase() == "TRUE",
-                    _ => {}
-                }
-            }
-        }
-
-        if !email.is_empty() {
-            let now = Utc::now();
-            Some(EventAttendee {
-                email,
-                name,
-                status,
-                role,
-                rsvp,
-                created_at: now,
-                updated_at: now,
-            })
-        } else {
-            None
-        }
+        EventAttendee::from_icalendar_property(line)
     }
 
     /// Parse organizer from iCalendar ORGANIZER line  
@@ -474,31 +519,7 @@ impl InvitationProcessor {
         invitation: &MeetingInvitation,
         calendar_id: String,
     ) -> Event {
-        let now = Utc::now();
-
-        Event {
-            id: invitation.uid.clone(),
-            uid: invitation.uid.clone(),
-            calendar_id,
-            title: invitation.title.clone(),
-            description: invitation.description.clone(),
-            location: invitation.location.clone(),
-            start_time: invitation.start_time,
-            end_time: invitation.end_time,
-            all_day: invitation.all_day,
-            status: invitation.status,
-            priority: crate::calendar::event::EventPriority::Normal,
-            organizer: invitation.organizer.clone(),
-            attendees: invitation.attendees.clone(),
-            recurrence: None,      // TODO: Parse recurrence rules
-            reminders: Vec::new(), // TODO: Parse alarms
-            categories: Vec::new(),
-            url: None,
-            created_at: now,
-            updated_at: now,
-            sequence: invitation.sequence,
-            etag: None,
-        }
+        invitation.to_event(calendar_id)
     }
 }
 
@@ -568,4 +589,75 @@ mod tests {
         let date_only = processor.parse_datetime("20250128").unwrap();
         assert_eq!(date_only.format("%Y%m%d").to_string(), "20250128");
     }
+
+    #[test]
+    fn test_zoned_datetime_parsing() {
+        let processor = InvitationProcessor::new(vec!["test@example.com".to_string()]);
+        let new_york = chrono_tz::America::New_York;
+
+        let local = processor
+            .parse_zoned_datetime("20250310T140000", Some(&new_york))
+            .unwrap();
+        assert_eq!(
+            local.format("%Y%m%dT%H%M%SZ").to_string(),
+            "20250310T180000Z"
+        );
+
+        // UTC times and dates ignore the zone
+        let utc = processor
+            .parse_zoned_datetime("20250310T140000Z", Some(&new_york))
+            .unwrap();
+        assert_eq!(utc.format("%Y%m%dT%H%M%SZ").to_string(), "20250310T140000Z");
+        let date = processor
+            .parse_zoned_datetime("20250310", Some(&new_york))
+            .unwrap();
+        assert_eq!(date.format("%Y%m%d").to_string(), "20250310");
+    }
+
+    #[test]
+    fn test_parse_folded_invitation() {
+        let processor = InvitationProcessor::new(vec!["me@example.com".to_string()]);
+        let ical = "BEGIN:VCALENDAR\r\n\
+METHOD:REQUEST\r\n\
+BEGIN:VEVENT\r\n\
+UID:weekly-sync@example.com\r\n\
+SUMMARY:Weekly sync\r\n\
+DESCRIPTION:Agenda in the doc\r\n\
+DTSTART:20250310T140000Z\r\n\
+DURATION:PT30M\r\n\
+RRULE:FREQ=WEEKLY;COUNT=4\r\n\
+SEQUENCE:2\r\n\
+ORGANIZER;CN=Bob:mailto:bob@example.com\r\n\
+ATTENDEE;CUTYPE=INDIVIDUAL;ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=\r\n \
+TRUE;CN=Me:MAILTO:me@example.com\r\n\
+BEGIN:VALARM\r\n\
+ACTION:DISPLAY\r\n\
+DESCRIPTION:Reminder\r\n\
+TRIGGER:-PT10M\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let invitation = processor.parse_icalendar(ical, None).unwrap();
+        assert_eq!(invitation.method, InvitationMethod::Request);
+        assert_eq!(invitation.description.as_deref(), Some("Agenda in the doc"));
+        assert_eq!(
+            invitation.end_time - invitation.start_time,
+            chrono::Duration::minutes(30)
+        );
+        assert_eq!(invitation.sequence, 2);
+        assert_eq!(
+            invitation.recurrence.as_ref().and_then(|rule| rule.count),
+            Some(4)
+        );
+        assert!(processor.is_user_invited(&invitation));
+        assert_eq!(
+            processor.get_user_rsvp_status(&invitation),
+            Some(AttendeeStatus::NeedsAction)
+        );
+
+        let event = invitation.to_event("cal".to_string());
+        assert_eq!(event.id, "weekly-sync@example.com");
+        assert!(event.recurrence.is_some());
+    }
 }
@@ -5,12 +5,89 @@ use tokio::sync::RwLock;
 
 use crate::calendar::database::CalendarDatabase;
 use crate::calendar::event::{AttendeeStatus, Event, EventAttendee, EventStatus};
+use crate::calendar::invitation::MeetingInvitation;
+use crate::calendar::recurrence;
+use crate::calendar::reminders::{self, DueReminder};
 use crate::calendar::{
-    CalDAVClient, CalDAVConfig, Calendar, CalendarError, CalendarResult, CalendarSource,
-    CalendarStats, GoogleCalendarClient,
+    BusyPeriod, CalDAVClient, CalDAVConfig, CalDAVError, Calendar, CalendarError, CalendarResult,
+    CalendarSource, CalendarStats, GoogleCalendarClient,
 };
 use crate::oauth2::token::TokenManager;
 
+/// Change the CalDAV server refused because the event was changed there
+/// since it was last synced
+#[derive(Debug, Clone)]
+pub struct SyncConflict {
+    /// The event as last synced, before the local change
+    pub base: Event,
+    /// The local version that was being saved
+    pub local: Event,
+    /// The event as it is on the server now, with its current ETag
+    pub remote: Event,
+    /// The local change was deleting the event
+    pub deleted: bool,
+}
+
+impl SyncConflict {
+    /// The server version with the fields changed locally applied on top
+    pub fn merged(&self) -> Event {
+        let (base, local) = (&self.base, &self.local);
+        let mut merged = self.remote.clone();
+
+        if local.title != base.title {
+            merged.title = local.title.clone();
+        }
+        if local.description != base.description {
+            merged.description = local.description.clone();
+        }
+        if local.location != base.location {
+            merged.location = local.location.clone();
+        }
+        if local.start_time != base.start_time || local.end_time != base.end_time {
+            merged.start_time = local.start_time;
+            merged.end_time = local.end_time;
+        }
+        if local.all_day != base.all_day {
+            merged.all_day = local.all_day;
+        }
+        if local.status != base.status {
+            merged.status = local.status;
+        }
+        if local.priority != base.priority {
+            merged.priority = local.priority.clone();
+        }
+        if local.recurrence != base.recurrence {
+            merged.recurrence = local.recurrence.clone();
+        }
+        if local.categories != base.categories {
+            merged.categories = local.categories.clone();
+        }
+        let emails = |event: &Event| {
+            event
+                .attendees
+                .iter()
+                .map(|a| a.email.to_lowercase())
+                .collect::<Vec<_>>()
+        };
+        if emails(local) != emails(base) {
+            merged.attendees = local.attendees.clone();
+        }
+
+        merged
+    }
+}
+
+/// How to settle a [`SyncConflict`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Apply the local changes on top of the server version
+    Merge,
+    /// Replace (or delete) the server version with the local one
+    Overwrite,
+    /// Drop the local change and keep the server version
+    KeepServer,
+}
+
 /// Calendar manager for coordinating all calendar operations
 pub struct CalendarManager {
     database: Arc<CalendarDatabase>,
@@ -258,6 +335,24 @@ impl CalendarManager {
             );
         }
 
+        // If this is a CalDAV calendar, upload the event before storing it
+        if let CalendarSource::CalDAV {
+            account_id,
+            calendar_url,
+        } = &calendar.source
+        {
+            let client = self.caldav_client(account_id).await?;
+            let event_url = Self::caldav_event_url(calendar_url, &event.uid);
+            event.etag = Some(
+                client
+                    .put_event(&event_url, &event.to_icalendar(), None)
+                    .await?,
+            );
+            event.url = Some(event_url);
+
+            tracing::debug!("Created event on CalDAV server: {} ({})", event.title, event.id);
+        }
+
         // Store in local database
         self.database
             .store_event(&event)
@@ -303,6 +398,37 @@ impl CalendarManager {
             );
         }
 
+        // If this is a CalDAV calendar, upload the change only if the server
+        // still has the version it was based on
+        if let CalendarSource::CalDAV {
+            account_id,
+            calendar_url,
+        } = &calendar.source
+        {
+            let client = self.caldav_client(account_id).await?;
+            let event_url = event
+                .url
+                .clone()
+                .unwrap_or_else(|| Self::caldav_event_url(calendar_url, &event.uid));
+            let data = self.caldav_resource_data(&event).await?;
+
+            match client
+                .put_event(&event_url, &data, event.etag.as_deref())
+                .await
+            {
+                Ok(etag) => {
+                    event.etag = Some(etag);
+                    event.url = Some(event_url);
+                }
+                Err(CalDAVError::PreconditionFailed(_)) => {
+                    return Err(self.sync_conflict(&client, &event_url, event, false).await);
+                }
+                Err(e) => return Err(e.into()),
+            }
+
+            tracing::debug!("Updated event on CalDAV server: {} ({})", event.title, event.id);
+        }
+
         // Store in local database
         self.database
             .store_event(&event)
@@ -316,7 +442,40 @@ impl CalendarManager {
     pub async fn delete_event(&self, event_id: &str) -> CalendarResult<bool> {
         // Get the event first to determine if it's from Google Calendar
         if let Ok(Some(event)) = self.database.get_event(event_id).await {
-            if let Some(calendar) = self.calendars.read().await.get(&event.calendar_id) {
+            let calendar = self.calendars.read().await.get(&event.calendar_id).cloned();
+            if let Some(calendar) = calendar {
+                // On a CalDAV server the override of a single occurrence
+                // lives in the series' resource, so it's cancelled instead
+                if let CalendarSource::CalDAV { account_id, .. } = &calendar.source {
+                    if event.recurrence_id.is_some() {
+                        self.cancel_event(event_id).await?;
+                        return Ok(true);
+                    }
+
+                    if let Some(event_url) = &event.url {
+                        let client = self.caldav_client(account_id).await?;
+                        match client.delete_event(event_url, event.etag.as_deref()).await {
+                            Ok(()) => {}
+                            Err(CalDAVError::PreconditionFailed(_)) => {
+                                return Err(self
+                                    .sync_conflict(&client, event_url, event.clone(), true)
+                                    .await);
+                            }
+                            Err(e) => return Err(e.into()),
+                        }
+                        self.database
+                            .delete_event_overrides(&event.uid)
+                            .await
+                            .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
+
+                        tracing::debug!(
+                            "Deleted event from CalDAV server: {} ({})",
+                            event.title,
+                            event_id
+                        );
+                    }
+                }
+
                 // If this is a Google calendar, delete from Google Calendar first
                 if let CalendarSource::Google {
                     account_id,
@@ -364,19 +523,139 @@ impl CalendarManager {
             .map_err(|e| CalendarError::DatabaseError(e.to_string()))?
             .ok_or_else(|| CalendarError::InvalidData(format!("Event {} not found", event_id)))?;
 
-        // Update event status to Cancelled
+        // Update event status to Cancelled; storing it bumps the sequence
         let mut cancelled_event = event;
         cancelled_event.status = EventStatus::Cancelled;
-        cancelled_event.updated_at = Utc::now();
-        cancelled_event.sequence += 1;
 
-        // Store the updated event
+        self.update_event(cancelled_event).await
+    }
+
+    /// Settle a conflict returned by `update_event` or `delete_event`
+    pub async fn resolve_conflict(
+        &self,
+        conflict: SyncConflict,
+        resolution: ConflictResolution,
+    ) -> CalendarResult<Option<Event>> {
+        // Later changes are based on the server's current version
         self.database
-            .store_event(&cancelled_event)
+            .store_event(&conflict.remote)
             .await
             .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
 
-        Ok(cancelled_event)
+        match resolution {
+            ConflictResolution::KeepServer => Ok(Some(conflict.remote)),
+            ConflictResolution::Overwrite if conflict.deleted => {
+                self.delete_event(&conflict.remote.id).await?;
+                Ok(None)
+            }
+            ConflictResolution::Overwrite | ConflictResolution::Merge => {
+                let mut event = if resolution == ConflictResolution::Merge {
+                    conflict.merged()
+                } else {
+                    conflict.local.clone()
+                };
+                event.etag = conflict.remote.etag.clone();
+                event.url = conflict.remote.url.clone();
+                event.sequence = event.sequence.max(conflict.remote.sequence);
+
+                self.update_event(event).await.map(Some)
+            }
+        }
+    }
+
+    /// CalDAV client of a connected account
+    async fn caldav_client(&self, account_id: &str) -> CalendarResult<Arc<CalDAVClient>> {
+        self.caldav_clients
+            .read()
+            .await
+            .get(account_id)
+            .cloned()
+            .ok_or_else(|| {
+                CalendarError::SyncError(format!("CalDAV account {} is not connected", account_id))
+            })
+    }
+
+    /// URL of a new event's resource in a CalDAV calendar
+    fn caldav_event_url(calendar_url: &str, uid: &str) -> String {
+        format!("{}/{}.ics", calendar_url.trim_end_matches('/'), uid)
+    }
+
+    /// iCalendar data of the CalDAV resource holding `event`: the event along
+    /// with the rest of its series (the master or overrides of occurrences)
+    async fn caldav_resource_data(&self, event: &Event) -> CalendarResult<String> {
+        let mut data = event.to_icalendar();
+        let series = self
+            .database
+            .get_events_by_uid(&event.uid)
+            .await
+            .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
+
+        for other in series
+            .iter()
+            .filter(|other| other.recurrence_id != event.recurrence_id)
+        {
+            let other_data = other.to_icalendar();
+            let (Some(begin), Some(end)) = (
+                other_data.find("BEGIN:VEVENT"),
+                other_data.rfind("END:VCALENDAR"),
+            ) else {
+                continue;
+            };
+            if let Some(at) = data.rfind("END:VCALENDAR") {
+                data.insert_str(at, &other_data[begin..end]);
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Conflict for a change the server refused, with its current version
+    async fn sync_conflict(
+        &self,
+        client: &CalDAVClient,
+        event_url: &str,
+        local: Event,
+        deleted: bool,
+    ) -> CalendarError {
+        let caldav_event = match client.get_event(event_url).await {
+            Ok(caldav_event) => caldav_event,
+            Err(e) => return e.into(),
+        };
+        let remote = match client
+            .parse_icalendar_to_events(&caldav_event.icalendar_data, local.calendar_id.clone())
+        {
+            Ok(events) => events
+                .into_iter()
+                .find(|e| e.recurrence_id == local.recurrence_id),
+            Err(e) => return e.into(),
+        };
+        let Some(mut remote) = remote else {
+            return CalendarError::SyncError(format!("{} is no longer on the server", local.title));
+        };
+
+        remote.id = local.id.clone();
+        remote.url = Some(event_url.to_string());
+        remote.etag = Some(caldav_event.etag);
+
+        let base = match self.database.get_event(&local.id).await {
+            Ok(Some(base)) => base,
+            _ => local.clone(),
+        };
+
+        CalendarError::EditConflict(Box::new(SyncConflict {
+            base,
+            local,
+            remote,
+            deleted,
+        }))
+    }
+
+    /// Get a stored event by ID
+    pub async fn get_event(&self, event_id: &str) -> CalendarResult<Option<Event>> {
+        self.database
+            .get_event(event_id)
+            .await
+            .map_err(|e| CalendarError::DatabaseError(e.to_string()))
     }
 
     /// Get events from a calendar within a date range
@@ -386,10 +665,26 @@ impl CalendarManager {
         start_time: Option<DateTime<Utc>>,
         end_time: Option<DateTime<Utc>>,
     ) -> CalendarResult<Vec<Event>> {
-        self.database
+        let events = self
+            .database
             .get_events(calendar_id, start_time, end_time)
             .await
-            .map_err(|e| CalendarError::DatabaseError(e.to_string()))
+            .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
+
+        Ok(Self::expand_recurring(events, start_time, end_time))
+    }
+
+    /// Expand recurring events into their occurrences within a window; an
+    /// open-ended query returns the stored series unexpanded
+    fn expand_recurring(
+        events: Vec<Event>,
+        start_time: Option<DateTime<Utc>>,
+        end_time: Option<DateTime<Utc>>,
+    ) -> Vec<Event> {
+        match (start_time, end_time) {
+            (Some(start), Some(end)) => recurrence::expand_events(events, start, end),
+            _ => events,
+        }
     }
 
     /// Get all events across all calendars within a date range
@@ -406,7 +701,105 @@ impl CalendarManager {
             .await
             .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
 
-        Ok(events)
+        Ok(Self::expand_recurring(events, start_time, end_time))
+    }
+
+    /// Times the user's calendars are busy between `start` and `end`, in
+    /// order
+    ///
+    /// Every event that isn't cancelled counts, all-day ones included.
+    pub async fn busy_periods(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> CalendarResult<Vec<BusyPeriod>> {
+        let events = self.get_all_events(Some(start), Some(end)).await?;
+        let mut busy: Vec<BusyPeriod> = events
+            .iter()
+            .filter(|event| event.status != EventStatus::Cancelled)
+            .map(|event| BusyPeriod {
+                start: event.start_time,
+                end: event.end_time,
+            })
+            .filter(|period| period.overlaps(start, end))
+            .collect();
+        busy.sort_by_key(|period| period.start);
+        Ok(busy)
+    }
+
+    /// Busy times of a calendar someone shares with the user, from a
+    /// free/busy report by the connected CalDAV account on its server
+    pub async fn shared_busy_periods(
+        &self,
+        calendar_url: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> CalendarResult<Vec<BusyPeriod>> {
+        let client = self
+            .caldav_clients
+            .read()
+            .await
+            .values()
+            .find(|client| client.serves(calendar_url))
+            .cloned()
+            .ok_or_else(|| {
+                CalendarError::SyncError(format!(
+                    "No connected CalDAV account can read {}",
+                    calendar_url
+                ))
+            })?;
+        Ok(client.free_busy_query(calendar_url, start, end).await?)
+    }
+
+    /// Reminders due at `now` that haven't gone off yet, or whose snooze is
+    /// over
+    ///
+    /// Each returned reminder is recorded as gone off, so it is returned once.
+    pub async fn take_due_reminders(&self, now: DateTime<Utc>) -> CalendarResult<Vec<DueReminder>> {
+        let events = self
+            .get_all_events(
+                Some(now),
+                Some(now + Duration::days(reminders::LOOKAHEAD_DAYS)),
+            )
+            .await?;
+
+        let mut due = Vec::new();
+        for reminder in reminders::due_reminders(&events, now) {
+            let state = self
+                .database
+                .get_reminder_state(&reminder.key)
+                .await
+                .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
+            let fires = match state {
+                None => true,
+                Some(state) => state.snoozed_until.is_some_and(|until| until <= now),
+            };
+            if !fires {
+                continue;
+            }
+
+            self.database
+                .record_reminder_fired(&reminder.key, &reminder.event.id, now)
+                .await
+                .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
+            due.push(reminder);
+        }
+
+        // Occurrences that went off a month ago are long over
+        self.database
+            .prune_reminder_states(now - Duration::days(30))
+            .await
+            .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
+
+        Ok(due)
+    }
+
+    /// Have a reminder that went off go off again at `until`
+    pub async fn snooze_reminder(&self, key: &str, until: DateTime<Utc>) -> CalendarResult<bool> {
+        self.database
+            .snooze_reminder(key, until)
+            .await
+            .map_err(|e| CalendarError::DatabaseError(e.to_string()))
     }
 
     /// Get upcoming events (starting within the next N hours)
@@ -523,6 +916,97 @@ impl CalendarManager {
         self.update_event(event).await
     }
 
+    /// Put the meeting of an invitation in the calendar, with
+    /// `attendee_email` marked as `status`
+    ///
+    /// Replaces the details of the event already there for the invitation's
+    /// UID, keeping the calendar it's in and its reminders. New meetings go
+    /// in the oldest writable calendar, preferring ones not on Google, which
+    /// adds invitations by itself.
+    pub async fn import_invitation(
+        &self,
+        invitation: &MeetingInvitation,
+        attendee_email: Option<&str>,
+        status: AttendeeStatus,
+    ) -> CalendarResult<Event> {
+        let existing = self.invitation_event(&invitation.uid).await?;
+        let calendar_id = match &existing {
+            Some(existing) => existing.calendar_id.clone(),
+            None => self.invitation_calendar().await?,
+        };
+
+        let mut event = invitation.to_event(calendar_id);
+        if let Some(email) = attendee_email {
+            if let Some(attendee) = event
+                .attendees
+                .iter_mut()
+                .find(|attendee| attendee.email.eq_ignore_ascii_case(email))
+            {
+                attendee.status = status;
+                attendee.updated_at = Utc::now();
+            }
+        }
+
+        match existing {
+            Some(existing) => {
+                event.id = existing.id;
+                event.url = existing.url;
+                event.etag = existing.etag;
+                event.created_at = existing.created_at;
+                event.sequence = existing.sequence;
+                event.priority = existing.priority;
+                event.reminders = existing.reminders;
+                event.categories = existing.categories;
+                self.update_event(event).await
+            }
+            None => self.create_event(event).await,
+        }
+    }
+
+    /// Take the meeting of a cancelled or declined invitation out of the
+    /// calendar, with any changed occurrences
+    ///
+    /// Returns whether there was an event to remove.
+    pub async fn remove_invitation(&self, uid: &str) -> CalendarResult<bool> {
+        let Some(event) = self.invitation_event(uid).await? else {
+            return Ok(false);
+        };
+        self.delete_event(&event.id).await?;
+        self.database
+            .delete_event_overrides(uid)
+            .await
+            .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
+        Ok(true)
+    }
+
+    /// The event (the series, for recurring meetings) with this UID
+    async fn invitation_event(&self, uid: &str) -> CalendarResult<Option<Event>> {
+        let events = self
+            .database
+            .get_events_by_uid(uid)
+            .await
+            .map_err(|e| CalendarError::DatabaseError(e.to_string()))?;
+        Ok(events
+            .into_iter()
+            .find(|event| event.recurrence_id.is_none()))
+    }
+
+    /// Calendar new meetings from invitations go in
+    async fn invitation_calendar(&self) -> CalendarResult<String> {
+        let calendars = self.calendars.read().await;
+        calendars
+            .values()
+            .filter(|calendar| !calendar.read_only)
+            .min_by_key(|calendar| {
+                (
+                    matches!(calendar.source, CalendarSource::Google { .. }),
+                    calendar.created_at,
+                )
+            })
+            .map(|calendar| calendar.id.clone())
+            .ok_or_else(|| CalendarError::InvalidData("No writable calendar available".to_string()))
+    }
+
     /// Get calendar statistics
     pub async fn get_stats(&self) -> CalendarResult<CalendarStats> {
         let calendars = self.calendars.read().await;
@@ -662,45 +1146,68 @@ impl CalendarManager {
                 if needs_update {
                     match client.get_event(event_url).await {
                         Ok(caldav_event) => {
-                            // Parse iCalendar data into Event structure
-                            match client.parse_icalendar_to_event(
+                            // Parse iCalendar data into the event and any
+                            // overrides of its single occurrences
+                            match client.parse_icalendar_to_events(
                                 &caldav_event.icalendar_data,
                                 local_calendar_id.to_string(),
                             ) {
-                                Ok(mut event) => {
-                                    // Set CalDAV-specific metadata
-                                    event.url = Some(event_url.clone());
-                                    event.etag = Some(caldav_event.etag);
-
-                                    // Check if this is an update or new event
-                                    let is_update = local_events.iter().any(|e| e.uid == event.uid);
-
-                                    // Store in database
-                                    match self.database.store_event(&event).await {
-                                        Ok(_) => {
-                                            if is_update {
-                                                events_updated += 1;
-                                                tracing::debug!(
-                                                    "Updated CalDAV event: {}",
-                                                    event.title
-                                                );
-                                            } else {
-                                                events_created += 1;
-                                                tracing::debug!(
-                                                    "Created CalDAV event: {}",
-                                                    event.title
-                                                );
-                                            }
-                                            events_synced += 1;
-                                        }
-                                        Err(e) => {
+                                Ok(events) => {
+                                    // Overrides dropped on the server go with the old copy
+                                    if let Some(uid) = events.first().map(|e| e.uid.clone()) {
+                                        if let Err(e) =
+                                            self.database.delete_event_overrides(&uid).await
+                                        {
                                             tracing::error!(
-                                                "Failed to store CalDAV event {}: {}",
-                                                event.title,
+                                                "Failed to clear overrides of {}: {}",
+                                                uid,
                                                 e
                                             );
                                         }
                                     }
+
+                                    for mut event in events {
+                                        // Set CalDAV-specific metadata
+                                        event.url = Some(event_url.clone());
+                                        event.etag = Some(caldav_event.etag.clone());
+
+                                        // Check if this is an update or new event
+                                        let local_event = local_events.iter().find(|e| {
+                                            e.uid == event.uid
+                                                && e.recurrence_id == event.recurrence_id
+                                        });
+                                        let is_update = local_event.is_some();
+                                        if let Some(local_event) = local_event {
+                                            event.id = local_event.id.clone();
+                                        }
+
+                                        // Store in database
+                                        match self.database.store_event(&event).await {
+                                            Ok(_) => {
+                                                if is_update {
+                                                    events_updated += 1;
+                                                    tracing::debug!(
+                                                        "Updated CalDAV event: {}",
+                                                        event.title
+                                                    );
+                                                } else {
+                                                    events_created += 1;
+                                                    tracing::debug!(
+                                                        "Created CalDAV event: {}",
+                                                        event.title
+                                                    );
+                                                }
+                                                events_synced += 1;
+                                            }
+                                            Err(e) => {
+                                                tracing::error!(
+                                                    "Failed to store CalDAV event {}: {}",
+                                                    event.title,
+                                                    e
+                                                );
+                                            }
+                                        }
+                                    }
                                 }
                                 Err(e) => {
                                     tracing::error!(
@@ -729,11 +1236,7 @@ impl CalendarManager {
                 // Check if this is a local-only event that needs to be uploaded
                 if local_event.url.is_none() && !local_event.uid.is_empty() {
                     // This is a local event that should be uploaded to the server
-                    let event_url = format!(
-                        "{}/{}.ics",
-                        calendar_url.trim_end_matches('/'),
-                        local_event.uid
-                    );
+                    let event_url = Self::caldav_event_url(calendar_url, &local_event.uid);
                     let icalendar_data = local_event.to_icalendar();
 
                     match client.put_event(&event_url, &icalendar_data, None).await {
@@ -971,6 +1474,7 @@ mod tests {
     use super::*;
     use crate::calendar::database::CalendarDatabase;
     use crate::oauth2::token::TokenManager;
+    use chrono::TimeZone;
 
     #[tokio::test]
     async fn test_calendar_manager_creation() {
@@ -1075,4 +1579,293 @@ END:VCALENDAR"#;
         assert!(event.organizer.is_some());
         assert_eq!(event.organizer.unwrap().email, "organizer@example.com");
     }
+    #[tokio::test]
+    async fn test_recurring_events_expanded_in_range() {
+        let db = Arc::new(CalendarDatabase::new_in_memory().await.unwrap());
+        let token_manager = Arc::new(TokenManager::new());
+        let manager = CalendarManager::new(db.clone(), token_manager)
+            .await
+            .unwrap();
+        let calendar = manager
+            .create_local_calendar("Work".to_string(), None)
+            .await
+            .unwrap();
+
+        let ical = r#"BEGIN:VCALENDAR
+BEGIN:VEVENT
+UID:standup@example.com
+SUMMARY:Standup
+DTSTART:20250106T093000Z
+DTEND:20250106T094500Z
+RRULE:FREQ=WEEKLY;BYDAY=MO
+EXDATE:20250120T093000Z
+EXDATE:20250203T093000Z
+END:VEVENT
+BEGIN:VEVENT
+UID:standup@example.com
+RECURRENCE-ID:20250217T093000Z
+SUMMARY:Standup
+DTSTART:20250217T093000Z
+DTEND:20250217T094500Z
+STATUS:CANCELLED
+END:VEVENT
+END:VCALENDAR"#;
+        let client = CalDAVClient::new(
+            "https://calendar.example.com/dav/",
+            String::new(),
+            String::new(),
+        )
+        .unwrap();
+        for event in client
+            .parse_icalendar_to_events(ical, calendar.id.clone())
+            .unwrap()
+        {
+            db.store_event(&event).await.unwrap();
+        }
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 13, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+        let events = manager
+            .get_all_events(Some(start), Some(end))
+            .await
+            .unwrap();
+        let days: Vec<u32> = events.iter().map(|e| e.start_time.ordinal()).collect();
+
+        // Jan 13, Jan 27, Feb 10 and Feb 24; Jan 20, Feb 3 and Feb 17 are cancelled
+        assert_eq!(days, vec![13, 27, 41, 55]);
+        assert!(events.iter().all(|e| e.title == "Standup"));
+
+        let stored = manager.get_all_events(None, None).await.unwrap();
+        assert_eq!(stored.len(), 2);
+    }
+
+    #[test]
+    fn test_sync_conflict_merge() {
+        let start = Utc.with_ymd_and_hms(2025, 3, 4, 14, 0, 0).unwrap();
+        let base = Event::new(
+            "cal".to_string(),
+            "Planning".to_string(),
+            start,
+            start + Duration::hours(1),
+        );
+
+        // Title changed here, room and time changed on the server
+        let mut local = base.clone();
+        local.title = "Quarterly planning".to_string();
+        let mut remote = base.clone();
+        remote.location = Some("Room 4".to_string());
+        remote.start_time = start + Duration::hours(1);
+        remote.end_time = start + Duration::hours(2);
+        remote.etag = Some("\"2\"".to_string());
+
+        let conflict = SyncConflict {
+            base,
+            local,
+            remote,
+            deleted: false,
+        };
+        let merged = conflict.merged();
+
+        assert_eq!(merged.title, "Quarterly planning");
+        assert_eq!(merged.location.as_deref(), Some("Room 4"));
+        assert_eq!(merged.start_time, start + Duration::hours(1));
+        assert_eq!(merged.etag.as_deref(), Some("\"2\""));
+    }
+
+    #[tokio::test]
+    async fn test_caldav_resource_keeps_overrides() {
+        let db = Arc::new(CalendarDatabase::new_in_memory().await.unwrap());
+        let manager = CalendarManager::new(db.clone(), Arc::new(TokenManager::new()))
+            .await
+            .unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 6, 9, 30, 0).unwrap();
+        let mut master = Event::new(
+            "cal".to_string(),
+            "Standup".to_string(),
+            start,
+            start + Duration::minutes(15),
+        );
+        master.recurrence = crate::calendar::EventRecurrence::from_icalendar("FREQ=WEEKLY").ok();
+        let mut moved = master.clone();
+        moved.id = format!("{}:20250113T093000Z", master.uid);
+        moved.recurrence = None;
+        moved.recurrence_id = Some(start + Duration::weeks(1));
+        db.store_event(&master).await.unwrap();
+        db.store_event(&moved).await.unwrap();
+
+        master.title = "Daily standup".to_string();
+        let data = manager.caldav_resource_data(&master).await.unwrap();
+
+        assert_eq!(data.matches("BEGIN:VEVENT").count(), 2);
+        assert_eq!(data.matches("BEGIN:VCALENDAR").count(), 1);
+        assert!(data.contains("SUMMARY:Daily standup"));
+        assert!(data.contains("RECURRENCE-ID:20250113T093000Z"));
+        assert!(data.trim_end().ends_with("END:VCALENDAR"));
+    }
+
+    #[tokio::test]
+    async fn test_reminders_fire_once_until_snoozed() {
+        let db = Arc::new(CalendarDatabase::new_in_memory().await.unwrap());
+        let manager = CalendarManager::new(db.clone(), Arc::new(TokenManager::new()))
+            .await
+            .unwrap();
+
+        let start = Utc::now() + Duration::minutes(10);
+        let mut event = Event::new(
+            "cal".to_string(),
+            "Review".to_string(),
+            start,
+            start + Duration::hours(1),
+        );
+        event.reminders.push(crate::calendar::EventReminder::new(
+            crate::calendar::event::ReminderTrigger::BeforeStart(Duration::minutes(15)),
+            crate::calendar::event::ReminderAction::Display,
+        ));
+        db.store_event(&event).await.unwrap();
+
+        let now = Utc::now();
+        let due = manager.take_due_reminders(now).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].event.title, "Review");
+        assert!(manager.take_due_reminders(now).await.unwrap().is_empty());
+
+        let until = now + Duration::minutes(5);
+        assert!(manager.snooze_reminder(&due[0].key, until).await.unwrap());
+        assert!(manager
+            .take_due_reminders(now + Duration::minutes(4))
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(manager.take_due_reminders(until).await.unwrap().len(), 1);
+        assert!(manager.take_due_reminders(until).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_busy_periods() {
+        let db = Arc::new(CalendarDatabase::new_in_memory().await.unwrap());
+        let manager = CalendarManager::new(db.clone(), Arc::new(TokenManager::new()))
+            .await
+            .unwrap();
+
+        let monday = Utc.with_ymd_and_hms(2025, 3, 10, 9, 0, 0).unwrap();
+        let mut standup = Event::new(
+            "cal".to_string(),
+            "Standup".to_string(),
+            monday,
+            monday + Duration::minutes(15),
+        );
+        standup.recurrence =
+            Some(crate::calendar::EventRecurrence::from_icalendar("FREQ=DAILY;COUNT=5").unwrap());
+        let mut cancelled = Event::new(
+            "cal".to_string(),
+            "Lunch".to_string(),
+            monday + Duration::hours(3),
+            monday + Duration::hours(4),
+        );
+        cancelled.status = EventStatus::Cancelled;
+        db.store_event(&standup).await.unwrap();
+        db.store_event(&cancelled).await.unwrap();
+
+        let busy = manager
+            .busy_periods(
+                monday + Duration::hours(1),
+                monday + Duration::days(2) + Duration::hours(1),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            busy,
+            vec![
+                BusyPeriod {
+                    start: monday + Duration::days(1),
+                    end: monday + Duration::days(1) + Duration::minutes(15),
+                },
+                BusyPeriod {
+                    start: monday + Duration::days(2),
+                    end: monday + Duration::days(2) + Duration::minutes(15),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_and_remove_invitation() {
+        let db = Arc::new(CalendarDatabase::new_in_memory().await.unwrap());
+        let manager = CalendarManager::new(db.clone(), Arc::new(TokenManager::new()))
+            .await
+            .unwrap();
+        let calendar = manager
+            .create_local_calendar("Work".to_string(), None)
+            .await
+            .unwrap();
+
+        let start = Utc.with_ymd_and_hms(2025, 3, 10, 14, 0, 0).unwrap();
+        let mut invitation = MeetingInvitation {
+            uid: "review@example.com".to_string(),
+            title: "Design review".to_string(),
+            description: None,
+            location: None,
+            start_time: start,
+            end_time: start + Duration::hours(1),
+            all_day: false,
+            timezone: None,
+            organizer: Some(EventAttendee::organizer(
+                "bob@example.com".to_string(),
+                None,
+            )),
+            attendees: vec![EventAttendee::new("Me@Example.com".to_string(), None)],
+            recurrence: None,
+            method: crate::calendar::InvitationMethod::Request,
+            status: EventStatus::Confirmed,
+            sequence: 0,
+            email_message_id: None,
+            icalendar_data: String::new(),
+            processed_at: Utc::now(),
+        };
+
+        let event = manager
+            .import_invitation(
+                &invitation,
+                Some("me@example.com"),
+                AttendeeStatus::Accepted,
+            )
+            .await
+            .unwrap();
+        assert_eq!(event.uid, "review@example.com");
+        assert_eq!(event.attendees[0].status, AttendeeStatus::Accepted);
+
+        // A rescheduled invitation updates the same event
+        invitation.start_time += Duration::hours(2);
+        invitation.end_time += Duration::hours(2);
+        invitation.sequence = 1;
+        let updated = manager
+            .import_invitation(
+                &invitation,
+                Some("me@example.com"),
+                AttendeeStatus::Tentative,
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.id, event.id);
+        assert_eq!(updated.calendar_id, calendar.id);
+        let events = db.get_events_by_uid("review@example.com").await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start_time, start + Duration::hours(2));
+        assert_eq!(events[0].attendees[0].status, AttendeeStatus::Tentative);
+
+        assert!(manager
+            .remove_invitation("review@example.com")
+            .await
+            .unwrap());
+        assert!(db
+            .get_events_by_uid("review@example.com")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(!manager
+            .remove_invitation("review@example.com")
+            .await
+            .unwrap());
+    }
 }
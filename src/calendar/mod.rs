@@ -8,25 +8,35 @@ pub mod invitation;
 pub mod invitation_manager;
 pub mod manager;
 pub mod notifications;
+pub mod recurrence;
+pub mod reminders;
 pub mod sync;
+pub mod time_grid;
+pub mod timezone;
 pub mod ui;
 
 pub use ai_assistant::{
     AICalendarAssistant, AvailabilityConflict, CalendarInsights, EventModificationSuggestions,
     MeetingScheduleAnalysis, NaturalLanguageEventRequest, ParsedEventInfo,
 };
-pub use caldav::{CalDAVClient, CalDAVConfig, CalDAVError, CalDAVResult};
+pub use caldav::{BusyPeriod, CalDAVClient, CalDAVConfig, CalDAVError, CalDAVResult};
 pub use database::{
     CalendarDatabase, CalendarEvent, CalendarEventAttendee, CalendarEventRecurrence,
+    ReminderState,
+};
+pub use event::{
+    AttendeeStatus, AttendeeTally, Event, EventAttendee, EventPriority, EventRecurrence,
+    EventReminder, EventStatus,
 };
-pub use event::{Event, EventPriority, EventRecurrence, EventReminder, EventStatus};
 pub use event_form::{EventFormAction, EventFormField, EventFormMode, EventFormUI};
 pub use google::{GoogleCalendar, GoogleCalendarClient, GoogleEvent, GoogleEventList};
 pub use invitation::{InvitationMethod, InvitationProcessor, MeetingInvitation, RSVPResponse};
 pub use invitation_manager::{InvitationManager, InvitationStatistics};
-pub use manager::CalendarManager;
+pub use manager::{CalendarManager, ConflictResolution, SyncConflict};
 pub use notifications::{CalendarNotification, CalendarNotificationManager};
+pub use reminders::DueReminder;
 pub use sync::{CalendarSyncEngine, CalendarSyncProgress};
+pub use timezone::TimezoneConfig;
 pub use ui::{CalendarAction, CalendarUI, CalendarViewMode};
 
 use chrono::{DateTime, Utc};
@@ -59,6 +69,9 @@ pub enum CalendarError {
 
     #[error("DateTime parsing error: {0}")]
     DateTimeError(#[from] chrono::ParseError),
+
+    #[error("{} was changed on the server", .0.remote.title)]
+    EditConflict(Box<SyncConflict>),
 }
 
 pub type CalendarResult<T> = Result<T, CalendarError>;
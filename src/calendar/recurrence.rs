@@ -0,0 +1,689 @@
+//! Expansion of recurring events into their occurrences
+//!
+//! Covers the parts of RFC 5545 recurrence that calendar servers send for
+//! day-or-longer series: RRULE with DAILY, WEEKLY, MONTHLY and YEARLY
+//! frequencies, INTERVAL, COUNT, UNTIL, BYDAY (including ordinals such as
+//! `3TH`), BYMONTHDAY, BYMONTH, BYYEARDAY and BYSETPOS, plus EXDATE, RDATE and
+//! RECURRENCE-ID overrides. Occurrences repeat at the time of day of the
+//! first one in the event's time zone, so they keep their local time across
+//! daylight saving changes, or in UTC for events without one. BYWEEKNO is
+//! ignored, and sub-daily rules only yield their first occurrence.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+
+use crate::calendar::event::{Event, EventRecurrence, EventStatus, RecurrenceFrequency};
+use crate::calendar::timezone;
+
+/// Upper bound on the periods walked for one series, so a rule that never
+/// matches can't loop forever
+const MAX_PERIODS: usize = 50_000;
+
+/// Start times of the occurrences of a series that overlap `from..=to`
+pub fn occurrences(
+    rule: &EventRecurrence,
+    dtstart: DateTime<Utc>,
+    duration: Duration,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    let time = dtstart.time();
+    let earliest = from - duration;
+    let mut starts = Vec::new();
+    let mut counted = 0;
+
+    'periods: for period in 0..MAX_PERIODS {
+        let Some((period_start, dates)) = period_dates(rule, dtstart.date_naive(), period) else {
+            break;
+        };
+        if period_start > to.date_naive() {
+            break;
+        }
+
+        for date in dates {
+            let start = date.and_time(time).and_utc();
+            if start < dtstart {
+                continue;
+            }
+            if start > to || rule.until.is_some_and(|until| start > until) {
+                break 'periods;
+            }
+            if rule.count.is_some_and(|count| counted >= count) {
+                break 'periods;
+            }
+
+            counted += 1;
+            if start >= earliest {
+                starts.push(start);
+            }
+        }
+    }
+
+    starts.extend(
+        rule.recurrence_dates
+            .iter()
+            .filter(|start| **start >= earliest && **start <= to),
+    );
+    starts.retain(|start| !rule.exception_dates.contains(start));
+    starts.sort();
+    starts.dedup();
+    starts
+}
+
+/// Like [`occurrences`], for a series repeating at a wall-clock time in `tz`
+fn zoned_occurrences(
+    rule: &EventRecurrence,
+    dtstart: DateTime<Utc>,
+    duration: Duration,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    tz: Tz,
+) -> Vec<DateTime<Utc>> {
+    // Expand in wall-clock times dressed up as UTC, then convert back
+    let wall = |time: DateTime<Utc>| time.with_timezone(&tz).naive_local().and_utc();
+    let mut wall_rule = rule.clone();
+    wall_rule.until = rule.until.map(wall);
+    wall_rule.exception_dates = rule.exception_dates.iter().copied().map(wall).collect();
+    wall_rule.recurrence_dates = rule.recurrence_dates.iter().copied().map(wall).collect();
+
+    // No zone is more than a day away from UTC
+    let margin = Duration::days(1);
+    occurrences(
+        &wall_rule,
+        wall(dtstart),
+        duration,
+        from - margin,
+        to + margin,
+    )
+    .into_iter()
+    .map(|start| timezone::local_to_utc(start.naive_utc(), tz))
+    .filter(|start| *start >= from - duration && *start <= to)
+    .collect()
+}
+
+/// Replace recurring events with their occurrences within `from..=to`
+///
+/// Occurrences are copies of the series with shifted times and keep its id.
+/// Overrides (events with a `recurrence_id`) take the place of the
+/// occurrence they replace, and cancelled ones remove it.
+pub fn expand_events(events: Vec<Event>, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Event> {
+    let (overrides, events): (Vec<Event>, Vec<Event>) = events
+        .into_iter()
+        .partition(|event| event.recurrence_id.is_some());
+    let overridden: HashMap<(String, DateTime<Utc>), &Event> = overrides
+        .iter()
+        .filter_map(|event| Some(((event.uid.clone(), event.recurrence_id?), event)))
+        .collect();
+
+    let overlaps = |event: &Event| event.end_time >= from && event.start_time <= to;
+    let mut expanded = Vec::new();
+
+    for event in &events {
+        let Some(rule) = &event.recurrence else {
+            if overlaps(event) {
+                expanded.push(event.clone());
+            }
+            continue;
+        };
+
+        let duration = event.end_time - event.start_time;
+        let starts = match event.timezone.as_deref().and_then(timezone::parse_tzid) {
+            Some(tz) => zoned_occurrences(rule, event.start_time, duration, from, to, tz),
+            None => occurrences(rule, event.start_time, duration, from, to),
+        };
+        for start in starts {
+            if overridden.contains_key(&(event.uid.clone(), start)) {
+                continue;
+            }
+
+            let mut occurrence = event.clone();
+            occurrence.start_time = start;
+            occurrence.end_time = start + duration;
+            expanded.push(occurrence);
+        }
+    }
+
+    expanded.extend(
+        overrides
+            .iter()
+            .filter(|event| event.status != EventStatus::Cancelled && overlaps(event))
+            .cloned(),
+    );
+    expanded.sort_by_key(|event| event.start_time);
+    expanded
+}
+
+/// First day and candidate dates of the `period`-th period of a series
+fn period_dates(
+    rule: &EventRecurrence,
+    dtstart: NaiveDate,
+    period: usize,
+) -> Option<(NaiveDate, Vec<NaiveDate>)> {
+    let step = rule.interval.max(1) as i64 * period as i64;
+    let plain_days: Vec<Weekday> = rule.plain_by_day().iter().map(|d| d.to_weekday()).collect();
+    let nth_days: Vec<(i8, Weekday)> = rule
+        .by_day_nth
+        .iter()
+        .map(|(nth, d)| (*nth, d.to_weekday()))
+        .collect();
+
+    let (period_start, mut dates) = match rule.frequency {
+        RecurrenceFrequency::Daily => {
+            let date = dtstart.checked_add_signed(Duration::days(step))?;
+            let matches = (rule.by_month.is_empty()
+                || rule.by_month.contains(&(date.month() as u8)))
+                && (rule.by_month_day.is_empty()
+                    || month_days(date.year(), date.month(), &rule.by_month_day).contains(&date))
+                && (plain_days.is_empty() || plain_days.contains(&date.weekday()));
+            (date, if matches { vec![date] } else { Vec::new() })
+        }
+        RecurrenceFrequency::Weekly => {
+            let week_start = rule.week_start.to_weekday();
+            let offset = (7 + dtstart.weekday().num_days_from_monday() as i64
+                - week_start.num_days_from_monday() as i64)
+                % 7;
+            let first = dtstart.checked_add_signed(Duration::days(7 * step - offset))?;
+            let days = if plain_days.is_empty() {
+                vec![dtstart.weekday()]
+            } else {
+                plain_days
+            };
+            let dates = (0..7)
+                .map(|i| first + Duration::days(i))
+                .filter(|date| days.contains(&date.weekday()))
+                .filter(|date| {
+                    rule.by_month.is_empty() || rule.by_month.contains(&(date.month() as u8))
+                })
+                .collect();
+            (first, dates)
+        }
+        RecurrenceFrequency::Monthly => {
+            let months = dtstart.year() as i64 * 12 + dtstart.month0() as i64 + step;
+            let year = i32::try_from(months.div_euclid(12)).ok()?;
+            let month = months.rem_euclid(12) as u32 + 1;
+            let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+
+            let dates = if !rule.by_month.is_empty() && !rule.by_month.contains(&(month as u8)) {
+                Vec::new()
+            } else {
+                month_dates(rule, dtstart, year, month, &plain_days, &nth_days)
+            };
+            (first, dates)
+        }
+        RecurrenceFrequency::Yearly => {
+            let year = i32::try_from(dtstart.year() as i64 + step).ok()?;
+            let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+            (
+                first,
+                year_dates(rule, dtstart, year, &plain_days, &nth_days),
+            )
+        }
+        RecurrenceFrequency::Hourly
+        | RecurrenceFrequency::Minutely
+        | RecurrenceFrequency::Secondly => {
+            if period > 0 {
+                return None;
+            }
+            (dtstart, vec![dtstart])
+        }
+    };
+
+    dates.sort();
+    dates.dedup();
+    Some((period_start, select_positions(dates, &rule.by_set_pos)))
+}
+
+/// Candidate dates within one month of a monthly (or yearly BYMONTH) rule
+fn month_dates(
+    rule: &EventRecurrence,
+    dtstart: NaiveDate,
+    year: i32,
+    month: u32,
+    plain_days: &[Weekday],
+    nth_days: &[(i8, Weekday)],
+) -> Vec<NaiveDate> {
+    let first = match NaiveDate::from_ymd_opt(year, month, 1) {
+        Some(first) => first,
+        None => return Vec::new(),
+    };
+    let last = first + Duration::days(days_in_month(year, month) as i64 - 1);
+    let has_day_rule = !plain_days.is_empty() || !nth_days.is_empty();
+
+    match (rule.by_month_day.is_empty(), has_day_rule) {
+        (true, false) => NaiveDate::from_ymd_opt(year, month, dtstart.day())
+            .into_iter()
+            .collect(),
+        (false, false) => month_days(year, month, &rule.by_month_day),
+        (true, true) => weekday_dates(first, last, plain_days, nth_days),
+        (false, true) => {
+            let days = month_days(year, month, &rule.by_month_day);
+            weekday_dates(first, last, plain_days, nth_days)
+                .into_iter()
+                .filter(|date| days.contains(date))
+                .collect()
+        }
+    }
+}
+
+/// Candidate dates within one year of a yearly rule
+fn year_dates(
+    rule: &EventRecurrence,
+    dtstart: NaiveDate,
+    year: i32,
+    plain_days: &[Weekday],
+    nth_days: &[(i8, Weekday)],
+) -> Vec<NaiveDate> {
+    let (Some(first), Some(last)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Vec::new();
+    };
+    let in_months = |date: &NaiveDate| {
+        rule.by_month.is_empty() || rule.by_month.contains(&(date.month() as u8))
+    };
+
+    if !rule.by_year_day.is_empty() {
+        let length = last.ordinal() as i64;
+        return rule
+            .by_year_day
+            .iter()
+            .filter_map(|day| {
+                let day = *day as i64;
+                let ordinal = if day < 0 { length + day + 1 } else { day };
+                (1..=length)
+                    .contains(&ordinal)
+                    .then(|| first + Duration::days(ordinal - 1))
+            })
+            .filter(in_months)
+            .collect();
+    }
+
+    if !rule.by_month.is_empty() {
+        // BYDAY ordinals count within each month when BYMONTH is given
+        let mut dates = Vec::new();
+        for month in &rule.by_month {
+            dates.extend(month_dates(
+                rule,
+                dtstart,
+                year,
+                *month as u32,
+                plain_days,
+                nth_days,
+            ));
+        }
+        return dates;
+    }
+
+    if !plain_days.is_empty() || !nth_days.is_empty() {
+        let dates = weekday_dates(first, last, plain_days, nth_days);
+        if rule.by_month_day.is_empty() {
+            return dates;
+        }
+        return dates
+            .into_iter()
+            .filter(|date| month_days(year, date.month(), &rule.by_month_day).contains(date))
+            .collect();
+    }
+
+    if !rule.by_month_day.is_empty() {
+        return (1..=12)
+            .flat_map(|month| month_days(year, month, &rule.by_month_day))
+            .collect();
+    }
+
+    NaiveDate::from_ymd_opt(year, dtstart.month(), dtstart.day())
+        .into_iter()
+        .collect()
+}
+
+/// Dates of BYMONTHDAY values in a month; negative values count from its end
+fn month_days(year: i32, month: u32, by_month_day: &[i8]) -> Vec<NaiveDate> {
+    let length = days_in_month(year, month) as i32;
+    by_month_day
+        .iter()
+        .filter_map(|day| {
+            let day = *day as i32;
+            let day = if day < 0 { length + day + 1 } else { day };
+            if (1..=length).contains(&day) {
+                NaiveDate::from_ymd_opt(year, month, day as u32)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Dates between `first` and `last` falling on the plain weekdays, plus the
+/// nth (or nth-from-last when negative) of each ordinal weekday
+fn weekday_dates(
+    first: NaiveDate,
+    last: NaiveDate,
+    plain_days: &[Weekday],
+    nth_days: &[(i8, Weekday)],
+) -> Vec<NaiveDate> {
+    let days: Vec<NaiveDate> = first.iter_days().take_while(|date| *date <= last).collect();
+    let mut dates: Vec<NaiveDate> = days
+        .iter()
+        .filter(|date| plain_days.contains(&date.weekday()))
+        .copied()
+        .collect();
+
+    for (nth, weekday) in nth_days {
+        let matching: Vec<&NaiveDate> = days.iter().filter(|d| d.weekday() == *weekday).collect();
+        let index = if *nth > 0 {
+            *nth as usize - 1
+        } else {
+            match matching.len().checked_sub(nth.unsigned_abs() as usize) {
+                Some(index) => index,
+                None => continue,
+            }
+        };
+        dates.extend(matching.get(index).copied());
+    }
+
+    dates
+}
+
+/// Apply BYSETPOS to the sorted dates of one period
+fn select_positions(dates: Vec<NaiveDate>, by_set_pos: &[i16]) -> Vec<NaiveDate> {
+    if by_set_pos.is_empty() {
+        return dates;
+    }
+
+    let mut selected: Vec<NaiveDate> = by_set_pos
+        .iter()
+        .filter_map(|pos| {
+            let index = if *pos > 0 {
+                *pos as usize - 1
+            } else {
+                dates.len().checked_sub(pos.unsigned_abs() as usize)?
+            };
+            dates.get(index).copied()
+        })
+        .collect();
+    selected.sort();
+    selected.dedup();
+    selected
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|next| next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn series(rrule: &str, start: DateTime<Utc>) -> Event {
+        let mut event = Event::new(
+            "cal".to_string(),
+            "Series".to_string(),
+            start,
+            start + Duration::minutes(30),
+        );
+        event.recurrence = Some(EventRecurrence::from_icalendar(rrule).unwrap());
+        event
+    }
+
+    fn starts(events: &[Event]) -> Vec<DateTime<Utc>> {
+        events.iter().map(|event| event.start_time).collect()
+    }
+
+    #[test]
+    fn test_weekly_standup_with_exdates() {
+        let mut standup = series("FREQ=WEEKLY;BYDAY=MO;WKST=MO", utc(2025, 1, 6, 9, 30));
+        let recurrence = standup.recurrence.as_mut().unwrap();
+        recurrence.exception_dates = vec![utc(2025, 1, 20, 9, 30), utc(2025, 2, 3, 9, 30)];
+
+        let events = expand_events(vec![standup], utc(2025, 1, 1, 0, 0), utc(2025, 2, 28, 0, 0));
+
+        assert_eq!(
+            starts(&events),
+            vec![
+                utc(2025, 1, 6, 9, 30),
+                utc(2025, 1, 13, 9, 30),
+                utc(2025, 1, 27, 9, 30),
+                utc(2025, 2, 10, 9, 30),
+                utc(2025, 2, 17, 9, 30),
+                utc(2025, 2, 24, 9, 30),
+            ]
+        );
+        assert!(events
+            .iter()
+            .all(|e| e.end_time - e.start_time == Duration::minutes(30)));
+    }
+
+    #[test]
+    fn test_third_thursday_of_month() {
+        let meetup = series("FREQ=MONTHLY;BYDAY=3TH", utc(2025, 1, 16, 18, 0));
+        let events = expand_events(vec![meetup], utc(2025, 1, 1, 0, 0), utc(2025, 4, 30, 0, 0));
+
+        assert_eq!(
+            starts(&events),
+            vec![
+                utc(2025, 1, 16, 18, 0),
+                utc(2025, 2, 20, 18, 0),
+                utc(2025, 3, 20, 18, 0),
+                utc(2025, 4, 17, 18, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_by_set_pos_and_last_friday() {
+        let rule = EventRecurrence::from_icalendar("FREQ=MONTHLY;BYDAY=TH;BYSETPOS=3").unwrap();
+        let third = occurrences(
+            &rule,
+            utc(2025, 1, 16, 18, 0),
+            Duration::hours(1),
+            utc(2025, 1, 1, 0, 0),
+            utc(2025, 3, 31, 0, 0),
+        );
+        assert_eq!(
+            third,
+            vec![
+                utc(2025, 1, 16, 18, 0),
+                utc(2025, 2, 20, 18, 0),
+                utc(2025, 3, 20, 18, 0)
+            ]
+        );
+
+        let rule = EventRecurrence::from_icalendar("FREQ=MONTHLY;BYDAY=-1FR;COUNT=3").unwrap();
+        let last_friday = occurrences(
+            &rule,
+            utc(2025, 1, 31, 16, 0),
+            Duration::hours(1),
+            utc(2025, 1, 1, 0, 0),
+            utc(2025, 12, 31, 0, 0),
+        );
+        assert_eq!(
+            last_friday,
+            vec![
+                utc(2025, 1, 31, 16, 0),
+                utc(2025, 2, 28, 16, 0),
+                utc(2025, 3, 28, 16, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_until_and_window() {
+        // Every other weekday, twelve times
+        let rule =
+            EventRecurrence::from_icalendar("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR;COUNT=12").unwrap();
+        let all = occurrences(
+            &rule,
+            utc(2025, 3, 3, 8, 0),
+            Duration::hours(1),
+            utc(2025, 3, 1, 0, 0),
+            utc(2025, 6, 1, 0, 0),
+        );
+        assert_eq!(all.len(), 12);
+        assert_eq!(all.last(), Some(&utc(2025, 3, 18, 8, 0)));
+
+        // COUNT is counted from the first occurrence, not from the window
+        let later = occurrences(
+            &rule,
+            utc(2025, 3, 3, 8, 0),
+            Duration::hours(1),
+            utc(2025, 3, 17, 0, 0),
+            utc(2025, 6, 1, 0, 0),
+        );
+        assert_eq!(later, vec![utc(2025, 3, 17, 8, 0), utc(2025, 3, 18, 8, 0)]);
+
+        let rule = EventRecurrence::from_icalendar(
+            "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH;UNTIL=20271231T000000Z",
+        )
+        .unwrap();
+        let thanksgiving = occurrences(
+            &rule,
+            utc(2025, 11, 27, 12, 0),
+            Duration::hours(4),
+            utc(2025, 1, 1, 0, 0),
+            utc(2030, 1, 1, 0, 0),
+        );
+        assert_eq!(
+            thanksgiving,
+            vec![
+                utc(2025, 11, 27, 12, 0),
+                utc(2026, 11, 26, 12, 0),
+                utc(2027, 11, 25, 12, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_last_day_and_interval() {
+        let rule =
+            EventRecurrence::from_icalendar("FREQ=MONTHLY;INTERVAL=2;BYMONTHDAY=-1").unwrap();
+        let month_end = occurrences(
+            &rule,
+            utc(2025, 1, 31, 17, 0),
+            Duration::hours(1),
+            utc(2025, 1, 1, 0, 0),
+            utc(2025, 7, 31, 23, 0),
+        );
+        assert_eq!(
+            month_end,
+            vec![
+                utc(2025, 1, 31, 17, 0),
+                utc(2025, 3, 31, 17, 0),
+                utc(2025, 5, 31, 17, 0),
+                utc(2025, 7, 31, 17, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rdate_adds_occurrence() {
+        let mut review = series("FREQ=WEEKLY;BYDAY=FR;COUNT=2", utc(2025, 5, 2, 15, 0));
+        review.recurrence.as_mut().unwrap().recurrence_dates = vec![utc(2025, 5, 14, 15, 0)];
+
+        let events = expand_events(vec![review], utc(2025, 5, 1, 0, 0), utc(2025, 5, 31, 0, 0));
+        assert_eq!(
+            starts(&events),
+            vec![
+                utc(2025, 5, 2, 15, 0),
+                utc(2025, 5, 9, 15, 0),
+                utc(2025, 5, 14, 15, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recurrence_id_overrides() {
+        let standup = series("FREQ=WEEKLY;BYDAY=MO", utc(2025, 1, 6, 9, 30));
+
+        // Moved to Tuesday in the second week
+        let mut moved = standup.clone();
+        moved.id = "moved".to_string();
+        moved.recurrence = None;
+        moved.recurrence_id = Some(utc(2025, 1, 13, 9, 30));
+        moved.start_time = utc(2025, 1, 14, 11, 0);
+        moved.end_time = utc(2025, 1, 14, 11, 30);
+
+        // Cancelled in the third week
+        let mut cancelled = standup.clone();
+        cancelled.id = "cancelled".to_string();
+        cancelled.recurrence = None;
+        cancelled.recurrence_id = Some(utc(2025, 1, 20, 9, 30));
+        cancelled.status = EventStatus::Cancelled;
+
+        let events = expand_events(
+            vec![cancelled, standup.clone(), moved],
+            utc(2025, 1, 1, 0, 0),
+            utc(2025, 1, 31, 0, 0),
+        );
+
+        assert_eq!(
+            starts(&events),
+            vec![
+                utc(2025, 1, 6, 9, 30),
+                utc(2025, 1, 14, 11, 0),
+                utc(2025, 1, 27, 9, 30),
+            ]
+        );
+        assert_eq!(events[0].id, standup.id);
+        assert_eq!(events[1].id, "moved");
+    }
+
+    #[test]
+    fn test_zoned_series_keeps_local_time_across_dst() {
+        let new_york = chrono_tz::America::New_York;
+        let local = |day: u32| {
+            timezone::local_to_utc(
+                NaiveDate::from_ymd_opt(2025, 3, day)
+                    .unwrap()
+                    .and_hms_opt(14, 0, 0)
+                    .unwrap(),
+                new_york,
+            )
+        };
+        let mut weekly = series("FREQ=WEEKLY;COUNT=4", local(3));
+        weekly.timezone = Some("America/New_York".to_string());
+        if let Some(rule) = weekly.recurrence.as_mut() {
+            rule.exception_dates.push(local(17));
+        }
+
+        let events = expand_events(vec![weekly], utc(2025, 3, 1, 0, 0), utc(2025, 4, 1, 0, 0));
+
+        // 14:00 EST is 19:00 UTC, and 14:00 EDT from 9 March is 18:00 UTC
+        assert_eq!(
+            starts(&events),
+            vec![
+                utc(2025, 3, 3, 19, 0),
+                utc(2025, 3, 10, 18, 0),
+                utc(2025, 3, 24, 18, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rule_that_never_matches_terminates() {
+        let rule = EventRecurrence::from_icalendar("FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30").unwrap();
+        let none = occurrences(
+            &rule,
+            utc(2025, 1, 1, 0, 0),
+            Duration::hours(1),
+            utc(2025, 1, 1, 0, 0),
+            utc(2026, 1, 1, 0, 0),
+        );
+        assert!(none.is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+//! Working out which event reminders are due
+//!
+//! Reminders come from the VALARM components of events. A reminder is due
+//! once its trigger time has passed, for as long as its occurrence has not
+//! ended, so reminders that fell due while the app was closed still go off
+//! on launch. Only display and audio alarms notify; email alarms are the
+//! calendar server's to send.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+use crate::calendar::event::{Event, EventStatus, ReminderAction};
+
+/// How long a snoozed reminder waits before it goes off again
+pub const SNOOZE_MINUTES: i64 = 5;
+
+/// How far ahead of an occurrence its reminders are looked for; triggers
+/// further ahead than this go off late
+pub const LOOKAHEAD_DAYS: i64 = 8;
+
+/// A reminder of one occurrence of an event
+#[derive(Debug, Clone)]
+pub struct DueReminder {
+    /// Tells this reminder of this occurrence apart across checks
+    pub key: String,
+    /// The occurrence the reminder is for
+    pub event: Event,
+    /// When the reminder's trigger fires
+    pub fire_at: DateTime<Utc>,
+}
+
+impl DueReminder {
+    /// Whole minutes from `now` until the occurrence starts, zero once it has
+    pub fn minutes_until(&self, now: DateTime<Utc>) -> i64 {
+        (self.event.start_time - now).num_minutes().max(0)
+    }
+}
+
+/// Reminders of `events` whose trigger time is at or before `now` and whose
+/// occurrence hasn't ended, oldest first
+///
+/// Recurring events must already be expanded into their occurrences.
+pub fn due_reminders(events: &[Event], now: DateTime<Utc>) -> Vec<DueReminder> {
+    let mut due: Vec<DueReminder> = events
+        .iter()
+        .filter(|event| event.status != EventStatus::Cancelled && event.end_time > now)
+        .flat_map(|event| {
+            event
+                .reminders
+                .iter()
+                .filter(|reminder| reminder.action != ReminderAction::Email)
+                .map(move |reminder| DueReminder {
+                    key: reminder_key(
+                        event,
+                        &format!(
+                            "{}{}",
+                            reminder.trigger.related_param(),
+                            reminder.to_icalendar()
+                        ),
+                    ),
+                    event: event.clone(),
+                    fire_at: reminder.trigger.fire_time(event.start_time, event.end_time),
+                })
+        })
+        .filter(|reminder| reminder.fire_at <= now)
+        .collect();
+
+    // Identical alarms on one event go off once
+    let mut seen = HashSet::new();
+    due.retain(|reminder| seen.insert(reminder.key.clone()));
+    due.sort_by_key(|reminder| reminder.fire_at);
+    due
+}
+
+fn reminder_key(event: &Event, trigger: &str) -> String {
+    format!(
+        "{}/{}/{}",
+        event.uid,
+        event.start_time.format("%Y%m%dT%H%M%SZ"),
+        trigger
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::event::{EventRecurrence, EventReminder, ReminderTrigger};
+    use chrono::{Duration, TimeZone};
+
+    fn event_with_alarm(start: DateTime<Utc>, trigger: ReminderTrigger) -> Event {
+        let mut event = Event::new(
+            "cal".to_string(),
+            "Standup".to_string(),
+            start,
+            start + Duration::minutes(30),
+        );
+        event
+            .reminders
+            .push(EventReminder::new(trigger, ReminderAction::Display));
+        event
+    }
+
+    #[test]
+    fn test_reminder_due_at_lead_time() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let events = vec![event_with_alarm(
+            start,
+            ReminderTrigger::BeforeStart(Duration::minutes(15)),
+        )];
+
+        assert!(due_reminders(&events, start - Duration::minutes(16)).is_empty());
+
+        let due = due_reminders(&events, start - Duration::minutes(15));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].fire_at, start - Duration::minutes(15));
+        assert_eq!(due[0].minutes_until(start - Duration::minutes(15)), 15);
+
+        // Still due after the trigger time, until the event is over
+        assert_eq!(
+            due_reminders(&events, start + Duration::minutes(10)).len(),
+            1
+        );
+        assert!(due_reminders(&events, start + Duration::minutes(30)).is_empty());
+    }
+
+    #[test]
+    fn test_email_alarms_and_cancelled_events_skipped() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let mut email = event_with_alarm(start, ReminderTrigger::AtStart);
+        email.reminders[0].action = ReminderAction::Email;
+        let mut cancelled = event_with_alarm(start, ReminderTrigger::AtStart);
+        cancelled.status = EventStatus::Cancelled;
+
+        assert!(due_reminders(&[email, cancelled], start).is_empty());
+    }
+
+    #[test]
+    fn test_each_occurrence_has_its_own_key() {
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+        let mut event = event_with_alarm(start, ReminderTrigger::BeforeStart(Duration::minutes(5)));
+        event.recurrence = Some(EventRecurrence::from_icalendar("FREQ=DAILY").unwrap());
+        let occurrences = crate::calendar::recurrence::expand_events(
+            vec![event],
+            start,
+            start + Duration::days(1),
+        );
+        let now = start + Duration::days(1) - Duration::minutes(5);
+
+        let due = due_reminders(&occurrences, now);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].event.start_time, start + Duration::days(1));
+        assert_ne!(
+            due[0].key,
+            due_reminders(&occurrences, start - Duration::minutes(5))[0].key
+        );
+    }
+}
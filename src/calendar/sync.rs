@@ -6,7 +6,7 @@ use tracing::{error, info, warn};
 
 use crate::calendar::{
     caldav::{CalDAVClient, CalDAVConfig, CalDAVQuery},
-    event::Event,
+    event::{unfold_icalendar_lines, Event, EventAttendee},
     CalendarError, CalendarManager, CalendarResult,
 };
 
@@ -389,9 +389,16 @@ impl CalendarSyncEngine {
         let mut location = None;
         let mut start_time = Utc::now();
         let mut end_time = Utc::now() + Duration::hours(1);
-
-        for line in ical_data.lines() {
-            if line.starts_with("SUMMARY:") {
+        let mut organizer = None;
+        let mut attendees = Vec::new();
+
+        for line in unfold_icalendar_lines(ical_data) {
+            let line = line.as_str();
+            if line.starts_with("ORGANIZER") {
+                organizer = EventAttendee::from_icalendar_property(line);
+            } else if line.starts_with("ATTENDEE") {
+                attendees.extend(EventAttendee::from_icalendar_property(line));
+            } else if line.starts_with("SUMMARY:") {
                 title = line.strip_prefix("SUMMARY:").unwrap_or(&title).to_string();
             } else if line.starts_with("DESCRIPTION:") {
                 description = Some(line.strip_prefix("DESCRIPTION:").unwrap_or("").to_string());
@@ -416,6 +423,8 @@ impl CalendarSyncEngine {
 
         event.description = description;
         event.location = location;
+        event.organizer = organizer;
+        event.attendees = attendees;
         event.uid = caldav_event.url.clone(); // Use URL as UID for now
         event.etag = Some(caldav_event.etag.clone());
 
@@ -0,0 +1,213 @@
+//! Time-grid layout for the day and week calendar views
+//!
+//! Splits a day into fixed-length slots and places timed events as blocks
+//! spanning the slots they cover. Events that overlap are laid out side by
+//! side: each cluster of transitively overlapping events is split into as many
+//! columns as it needs, and every event takes the first free column.
+
+use crate::calendar::Event;
+use chrono::{NaiveDate, TimeZone};
+
+/// Length of one grid row in minutes
+pub const SLOT_MINUTES: u32 = 30;
+
+/// Number of slots in a day
+pub const SLOTS_PER_DAY: u32 = 24 * 60 / SLOT_MINUTES;
+
+/// Hour the grid scrolls to by default
+pub const BUSINESS_START_HOUR: u32 = 8;
+
+/// A timed event placed on the grid of one day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridBlock {
+    pub event_id: String,
+    /// Minutes after midnight the block starts, clipped to the day
+    pub start_minute: u32,
+    /// Minutes after midnight the block ends, clipped to the day
+    pub end_minute: u32,
+    /// Column within its overlap cluster
+    pub column: usize,
+    /// Number of columns in its overlap cluster
+    pub columns: usize,
+}
+
+impl GridBlock {
+    /// First slot the block covers
+    pub fn start_slot(&self) -> u32 {
+        self.start_minute / SLOT_MINUTES
+    }
+
+    /// Slot after the last one the block covers; always past `start_slot`
+    pub fn end_slot(&self) -> u32 {
+        self.end_minute
+            .div_ceil(SLOT_MINUTES)
+            .max(self.start_slot() + 1)
+    }
+
+    /// Whether the block covers the given slot
+    pub fn covers_slot(&self, slot: u32) -> bool {
+        slot >= self.start_slot() && slot < self.end_slot()
+    }
+}
+
+/// Slot containing a minute of the day
+pub fn slot_for_minute(minute: u32) -> u32 {
+    (minute / SLOT_MINUTES).min(SLOTS_PER_DAY - 1)
+}
+
+/// Lay out the timed events of `date` in the given timezone
+///
+/// All-day events and events that don't touch the day are skipped.
+pub fn layout_day<Tz: TimeZone>(events: &[&Event], date: NaiveDate, tz: &Tz) -> Vec<GridBlock> {
+    let Some(day_start) = date
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| tz.from_local_datetime(&midnight).earliest())
+    else {
+        return Vec::new();
+    };
+
+    let mut blocks: Vec<GridBlock> = events
+        .iter()
+        .filter(|event| !event.all_day)
+        .filter_map(|event| {
+            let start = event
+                .start_time
+                .signed_duration_since(&day_start)
+                .num_minutes();
+            let end = event
+                .end_time
+                .signed_duration_since(&day_start)
+                .num_minutes();
+            let day_minutes: i64 = 24 * 60;
+            if end <= 0 || start >= day_minutes {
+                return None;
+            }
+
+            let start_minute = start.max(0) as u32;
+            // Zero-length events still get one slot
+            let end_minute = (end.min(day_minutes) as u32).max(start_minute + 1);
+            Some(GridBlock {
+                event_id: event.id.clone(),
+                start_minute,
+                end_minute,
+                column: 0,
+                columns: 1,
+            })
+        })
+        .collect();
+
+    // Earlier first; longer first among equal starts so they get the leftmost column
+    blocks.sort_by(|a, b| {
+        a.start_minute
+            .cmp(&b.start_minute)
+            .then(b.end_minute.cmp(&a.end_minute))
+    });
+
+    let mut cluster_start = 0;
+    let mut cluster_end = 0;
+    // End slot of the last block in each column of the current cluster
+    let mut column_ends: Vec<u32> = Vec::new();
+
+    for index in 0..blocks.len() {
+        let (start, end) = (blocks[index].start_slot(), blocks[index].end_slot());
+
+        if index > cluster_start && start >= cluster_end {
+            finish_cluster(&mut blocks[cluster_start..index], column_ends.len());
+            cluster_start = index;
+            column_ends.clear();
+        }
+
+        let column = match column_ends.iter().position(|&column_end| column_end <= start) {
+            Some(column) => {
+                column_ends[column] = end;
+                column
+            }
+            None => {
+                column_ends.push(end);
+                column_ends.len() - 1
+            }
+        };
+        blocks[index].column = column;
+        cluster_end = if index == cluster_start {
+            end
+        } else {
+            cluster_end.max(end)
+        };
+    }
+    let len = blocks.len();
+    finish_cluster(&mut blocks[cluster_start..len], column_ends.len());
+
+    blocks
+}
+
+fn finish_cluster(blocks: &mut [GridBlock], columns: usize) {
+    for block in blocks {
+        block.columns = columns.max(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    fn event(id: &str, start: &str, end: &str) -> Event {
+        let mut event = Event::new(
+            "local".to_string(),
+            id.to_string(),
+            start.parse::<DateTime<Utc>>().unwrap(),
+            end.parse::<DateTime<Utc>>().unwrap(),
+        );
+        event.id = id.to_string();
+        event
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 4).unwrap()
+    }
+
+    #[test]
+    fn test_blocks_span_their_duration() {
+        let standup = event("standup", "2024-03-04T09:00:00Z", "2024-03-04T09:15:00Z");
+        let workshop = event("workshop", "2024-03-04T13:00:00Z", "2024-03-04T15:30:00Z");
+        let blocks = layout_day(&[&standup, &workshop], date(), &Utc);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!((blocks[0].start_slot(), blocks[0].end_slot()), (18, 19));
+        assert_eq!((blocks[1].start_slot(), blocks[1].end_slot()), (26, 31));
+        assert!(blocks.iter().all(|block| block.columns == 1));
+    }
+
+    #[test]
+    fn test_overlapping_events_side_by_side() {
+        let a = event("a", "2024-03-04T10:00:00Z", "2024-03-04T12:00:00Z");
+        let b = event("b", "2024-03-04T10:30:00Z", "2024-03-04T11:00:00Z");
+        // Starts when b ends, so it reuses b's column
+        let c = event("c", "2024-03-04T11:00:00Z", "2024-03-04T11:30:00Z");
+        // Separate cluster after a ends
+        let d = event("d", "2024-03-04T12:00:00Z", "2024-03-04T13:00:00Z");
+        let blocks = layout_day(&[&d, &c, &b, &a], date(), &Utc);
+
+        let placement: Vec<(&str, usize, usize)> = blocks
+            .iter()
+            .map(|block| (block.event_id.as_str(), block.column, block.columns))
+            .collect();
+        assert_eq!(
+            placement,
+            vec![("a", 0, 2), ("b", 1, 2), ("c", 1, 2), ("d", 0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_events_clipped_to_day() {
+        let overnight = event("overnight", "2024-03-03T22:00:00Z", "2024-03-04T02:00:00Z");
+        let tomorrow = event("tomorrow", "2024-03-05T09:00:00Z", "2024-03-05T10:00:00Z");
+        let mut all_day = event("holiday", "2024-03-04T00:00:00Z", "2024-03-05T00:00:00Z");
+        all_day.all_day = true;
+        let blocks = layout_day(&[&overnight, &tomorrow, &all_day], date(), &Utc);
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].start_minute, 0);
+        assert_eq!(blocks[0].end_slot(), 4);
+    }
+}
@@ -0,0 +1,202 @@
+//! Time zones for calendar events
+//!
+//! Event times are kept in UTC. Times a calendar server sends with a TZID are
+//! converted from that zone when they are parsed, and the zone's name is kept
+//! on the event so recurring series follow its daylight saving changes and
+//! the agenda can show where a meeting was set up. The calendar view shows
+//! times in the display time zone from `calendar_timezone.toml`.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::{OffsetName, Tz};
+use serde::{Deserialize, Serialize};
+
+/// Which time zone the calendar shows times in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimezoneConfig {
+    /// IANA name of the zone you live in, such as `Europe/London`; taken
+    /// from the system when the file is first written
+    pub home_timezone: String,
+    /// `home` to show times in the home zone, `system` to follow the
+    /// computer's clock while travelling, or an IANA name
+    pub display_timezone: String,
+}
+
+impl Default for TimezoneConfig {
+    fn default() -> Self {
+        Self {
+            home_timezone: system_timezone().unwrap_or(Tz::UTC).name().to_string(),
+            display_timezone: "home".to_string(),
+        }
+    }
+}
+
+impl TimezoneConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "calendar_timezone.toml";
+
+    /// The home zone, UTC if the name isn't a known zone
+    pub fn home(&self) -> Tz {
+        parse_tzid(&self.home_timezone).unwrap_or(Tz::UTC)
+    }
+
+    /// The zone to show times in
+    pub fn display(&self) -> Tz {
+        match self.display_timezone.trim() {
+            "" | "home" => self.home(),
+            "system" => system_timezone().unwrap_or_else(|| self.home()),
+            name => parse_tzid(name).unwrap_or_else(|| self.home()),
+        }
+    }
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// The zone the computer's clock is set to, if it is a known IANA zone
+pub fn system_timezone() -> Option<Tz> {
+    parse_tzid(&iana_time_zone::get_timezone().ok()?)
+}
+
+/// Look up a TZID value
+///
+/// Accepts IANA names, optionally quoted, and the path-style names some
+/// clients write, such as `/mozilla.org/20050126_1/Europe/London`.
+pub fn parse_tzid(value: &str) -> Option<Tz> {
+    let name = value.trim().trim_matches('"');
+    name.parse().ok().or_else(|| {
+        name.match_indices('/')
+            .find_map(|(index, _)| name[index + 1..].parse().ok())
+    })
+}
+
+/// Zone named by the TZID parameter of a content line, such as
+/// `DTSTART;TZID=America/New_York:20250310T140000`
+pub fn line_timezone(line: &str) -> Option<Tz> {
+    let (params, _) = line.split_once(':')?;
+    params
+        .split(';')
+        .find_map(|param| param.strip_prefix("TZID="))
+        .and_then(parse_tzid)
+}
+
+/// The UTC time of a wall-clock time in `tz`
+///
+/// Times repeated when clocks go back are read as the first of the two, and
+/// times skipped when they go forward as the hour after, as RFC 5545 asks.
+pub fn local_to_utc(local: NaiveDateTime, tz: Tz) -> DateTime<Utc> {
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(local + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| local.and_utc())
+}
+
+/// Abbreviation of `tz` at `time`, such as `EST` or `BST`, or its UTC offset
+/// where the zone has no abbreviation
+pub fn abbreviation(time: DateTime<Utc>, tz: Tz) -> String {
+    let local = time.with_timezone(&tz);
+    match local.offset().abbreviation() {
+        Some(abbreviation) if !abbreviation.starts_with(['+', '-']) => abbreviation.to_string(),
+        _ => local.format("UTC%:z").to_string(),
+    }
+}
+
+/// Label for the zone an event was set up in, for times shown in `display`
+///
+/// Just the abbreviation when the two zones agree on the wall-clock time,
+/// otherwise the abbreviation and the time in the event's zone.
+pub fn original_zone_label(time: DateTime<Utc>, original: Tz, display: Tz) -> String {
+    let abbreviation = abbreviation(time, original);
+    let original_time = time.with_timezone(&original).naive_local();
+    if original_time == time.with_timezone(&display).naive_local() {
+        abbreviation
+    } else {
+        format!("{} {}", abbreviation, original_time.format("%H:%M"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn wall(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new_york_meeting_in_london() {
+        let new_york = chrono_tz::America::New_York;
+        let london = chrono_tz::Europe::London;
+        let show = |time: DateTime<Utc>| time.with_timezone(&london).format("%H:%M").to_string();
+
+        // Winter: five hours apart
+        let january = local_to_utc(wall(2025, 1, 15, 14, 0), new_york);
+        assert_eq!(show(january), "19:00");
+        assert_eq!(original_zone_label(january, new_york, london), "EST 14:00");
+
+        // The US moves its clocks three weeks before the UK
+        let march = local_to_utc(wall(2025, 3, 12, 14, 0), new_york);
+        assert_eq!(show(march), "18:00");
+        assert_eq!(abbreviation(march, new_york), "EDT");
+
+        // Summer: five hours apart again, now in BST
+        let july = local_to_utc(wall(2025, 7, 9, 14, 0), new_york);
+        assert_eq!(show(july), "19:00");
+        assert_eq!(abbreviation(july, london), "BST");
+        assert_eq!(original_zone_label(july, london, london), "BST");
+    }
+
+    #[test]
+    fn test_clock_change_edges() {
+        let london = chrono_tz::Europe::London;
+
+        // 01:30 doesn't exist on 30 March 2025; it is read as 02:30 BST
+        assert_eq!(
+            local_to_utc(wall(2025, 3, 30, 1, 30), london),
+            Utc.with_ymd_and_hms(2025, 3, 30, 1, 30, 0).unwrap()
+        );
+        // 01:30 happens twice on 26 October 2025; the first is BST
+        assert_eq!(
+            local_to_utc(wall(2025, 10, 26, 1, 30), london),
+            Utc.with_ymd_and_hms(2025, 10, 26, 0, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tzid_parsing() {
+        assert_eq!(
+            line_timezone("DTSTART;TZID=America/New_York:20250310T140000"),
+            Some(chrono_tz::America::New_York)
+        );
+        assert_eq!(
+            line_timezone("DTSTART;VALUE=DATE-TIME;TZID=\"Europe/Oslo\":20250310T140000"),
+            Some(chrono_tz::Europe::Oslo)
+        );
+        assert_eq!(
+            parse_tzid("/mozilla.org/20050126_1/Europe/London"),
+            Some(chrono_tz::Europe::London)
+        );
+        assert_eq!(line_timezone("DTSTART:20250310T140000Z"), None);
+        assert_eq!(parse_tzid("Not/AZone"), None);
+
+        // Zones without an abbreviation show their offset
+        let time = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        assert_eq!(abbreviation(time, chrono_tz::Asia::Dubai), "UTC+04:00");
+    }
+}
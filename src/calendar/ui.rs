@@ -1,8 +1,14 @@
 use crate::{
-    calendar::{Event, EventPriority, EventStatus},
+    calendar::time_grid::{
+        layout_day, slot_for_minute, BUSINESS_START_HOUR, SLOTS_PER_DAY, SLOT_MINUTES,
+    },
+    calendar::event::AttendeeRole,
+    calendar::timezone,
+    calendar::{AttendeeStatus, Event, EventPriority, EventStatus},
     theme::Theme,
 };
-use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Timelike, Utc};
+use chrono_tz::Tz;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
@@ -65,8 +71,10 @@ pub enum CalendarAction {
 pub struct CalendarUI {
     // View state
     current_view: CalendarViewMode,
-    current_date: DateTime<Local>,
+    current_date: DateTime<Tz>,
     selected_date: NaiveDate,
+    /// Zone times are shown in
+    timezone: Tz,
     #[allow(dead_code)] // Used through get_selected_event_id() method
     selected_event_id: Option<String>,
 
@@ -74,12 +82,19 @@ pub struct CalendarUI {
     events: Vec<Event>,
     calendars: Vec<crate::calendar::Calendar>,
     enabled_calendars: std::collections::HashSet<String>,
+    /// Addresses of the user's accounts, used to spot events they organize
+    user_emails: Vec<String>,
 
     // Navigation
     view_tab_index: usize,
     event_list_state: ListState,
     calendar_list_state: ListState,
 
+    // Day/week time grid
+    grid_selected_slot: u32,
+    grid_scroll_slot: u32,
+    grid_visible_slots: u32,
+
     // UI state
     show_event_details: bool,
     show_calendar_list: bool,
@@ -112,17 +127,26 @@ impl CalendarUI {
         // By default, enable local calendar
         enabled_calendars.insert("local".to_string());
 
+        let timezone = timezone::system_timezone().unwrap_or(Tz::UTC);
+        let now = Utc::now().with_timezone(&timezone);
+
         Self {
             current_view: CalendarViewMode::Month,
-            current_date: Local::now(),
-            selected_date: Local::now().date_naive(),
+            current_date: now,
+            selected_date: now.date_naive(),
+            timezone,
             selected_event_id: None,
             events: Vec::new(),
             calendars: Vec::new(),
             enabled_calendars,
+            user_emails: Vec::new(),
             view_tab_index: 0,
             event_list_state: ListState::default(),
             calendar_list_state: ListState::default(),
+            // Start at business hours with the first hour selected
+            grid_selected_slot: BUSINESS_START_HOUR * 60 / SLOT_MINUTES,
+            grid_scroll_slot: BUSINESS_START_HOUR * 60 / SLOT_MINUTES,
+            grid_visible_slots: 0,
             show_event_details: false,
             show_calendar_list: false,
             is_focused: true,
@@ -223,6 +247,7 @@ impl CalendarUI {
 
         // Render view controls
         let controls_text = match self.focused_pane {
+            CalendarPane::Calendar if self.is_time_grid_view() => "h/l: Day, j/k: Time, Enter: Details",
             CalendarPane::Calendar => "h/l: Navigate, Space: Today, c: Create",
             CalendarPane::EventList => "j/k: Navigate, Enter: Details",
             CalendarPane::CalendarList => "j/k: Navigate, Space: Toggle",
@@ -256,19 +281,11 @@ impl CalendarUI {
 
     /// Render week view
     fn render_week_view(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3), // Week header
-                Constraint::Min(10),   // Week grid
-            ])
-            .split(area);
-
-        // Render week header with days
-        self.render_week_header(frame, chunks[0], theme);
+        let week_start = self.get_week_start().date_naive();
+        let days: Vec<NaiveDate> = (0..7).map(|offset| week_start + Duration::days(offset)).collect();
+        let title = format!("Week of {}", week_start.format("%B %d"));
 
-        // Render week grid with events
-        self.render_week_grid(frame, chunks[1], theme);
+        self.render_time_grid(frame, area, theme, &days, title);
     }
 
     /// Render day view
@@ -282,7 +299,8 @@ impl CalendarUI {
             .split(area);
 
         // Render day schedule
-        self.render_day_schedule(frame, chunks[0], theme);
+        let title = format!("Schedule - {}", self.selected_date.format("%A, %B %d"));
+        self.render_time_grid(frame, chunks[0], theme, &[self.selected_date], title);
 
         // Render day summary
         self.render_day_summary(frame, chunks[1], theme);
@@ -346,7 +364,7 @@ impl CalendarUI {
 
         // Get events grouped by date for easy lookup
         let events_by_date = self.group_events_by_date();
-        let today = Local::now().date_naive();
+        let today = self.today();
 
         // Render calendar cells
         for week in 0..6 {
@@ -462,7 +480,11 @@ impl CalendarUI {
                     event.title.clone()
                 }
             } else {
-                let time_str = event.start_time.format("%H:%M").to_string();
+                let time_str = event
+                    .start_time
+                    .with_timezone(&self.timezone)
+                    .format("%H:%M")
+                    .to_string();
                 let title_space = area.width.saturating_sub(6) as usize; // 6 chars for time + space
                 let title = if event.title.len() > title_space {
                     format!("{}…", &event.title[..title_space.saturating_sub(1)])
@@ -512,8 +534,10 @@ impl CalendarUI {
 
         for event in &self.events {
             if self.enabled_calendars.contains(&event.calendar_id) {
-                let event_date = event.start_time.date_naive();
-                events_by_date.entry(event_date).or_default().push(event);
+                events_by_date
+                    .entry(self.start_date(event))
+                    .or_default()
+                    .push(event);
             }
         }
 
@@ -530,14 +554,14 @@ impl CalendarUI {
             .iter()
             .filter(|event| {
                 self.enabled_calendars.contains(&event.calendar_id)
-                    && event.start_time.date_naive() == self.selected_date
+                    && self.start_date(event) == self.selected_date
             })
             .collect();
 
         // Create list items
         let list_items: Vec<ListItem> = selected_events
             .iter()
-            .map(|event| Self::create_event_list_item_static(event, theme))
+            .map(|event| Self::create_event_list_item_static(event, self.timezone, theme))
             .collect();
 
         let title = format!("Events ({})", selected_events.len());
@@ -555,11 +579,15 @@ impl CalendarUI {
     }
 
     /// Create list item for an event (static version to avoid borrowing issues)
-    fn create_event_list_item_static<'a>(event: &'a Event, _theme: &'a Theme) -> ListItem<'a> {
+    fn create_event_list_item_static<'a>(
+        event: &'a Event,
+        timezone: Tz,
+        _theme: &'a Theme,
+    ) -> ListItem<'a> {
         let time_str = if event.all_day {
             "All Day".to_string()
         } else {
-            event.start_time.format("%H:%M").to_string()
+            event.start_time.with_timezone(&timezone).format("%H:%M").to_string()
         };
 
         let status_symbol = match event.status {
@@ -586,236 +614,259 @@ impl CalendarUI {
         ListItem::new(Line::from(spans))
     }
 
-    /// Render week header with day names
-    fn render_week_header(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let week_start = self.get_week_start();
-        let days = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+    /// Render a time grid with one column per day
+    ///
+    /// Rows are `SLOT_MINUTES` long; timed events are drawn as blocks spanning their
+    /// duration, overlapping events side by side, with a line marking the current time.
+    fn render_time_grid(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        days: &[NaiveDate],
+        title: String,
+    ) {
+        let is_focused = self.focused_pane == CalendarPane::Calendar;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(theme.get_component_style("border", is_focused));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        const GUTTER: u16 = 6;
+        if days.is_empty() || inner.height < 3 || inner.width < GUTTER + days.len() as u16 {
+            return;
+        }
 
-        let day_width = area.width / 7;
+        // Day names, all-day events, then the slots
+        let header_y = inner.y;
+        let all_day_y = inner.y + 1;
+        let grid = Rect::new(inner.x, inner.y + 2, inner.width, inner.height - 2);
 
-        for (i, day_name) in days.iter().enumerate() {
-            let current_date = week_start + Duration::days(i as i64);
-            let day_text = format!("{}\n{}", day_name, current_date.day());
+        self.grid_visible_slots = grid.height as u32;
+        self.scroll_to_selected_slot();
 
-            let x = area.x + (i as u16) * day_width;
-            let day_area = Rect::new(x, area.y, day_width, area.height);
+        let day_width = (grid.width - GUTTER) / days.len() as u16;
+        let day_x = |index: usize| grid.x + GUTTER + index as u16 * day_width;
+        let now = Utc::now().with_timezone(&self.timezone);
+        let today = now.date_naive();
+        let now_slot = slot_for_minute(now.hour() * 60 + now.minute());
+        let grid_style = theme.get_component_style("calendar_grid", false);
 
-            let is_today = current_date.date_naive() == Local::now().date_naive();
-            let style = if is_today {
+        for (index, day) in days.iter().enumerate() {
+            let style = if *day == today {
                 theme.get_component_style("calendar_today", true)
             } else {
-                theme.get_component_style("calendar_day_header", false)
+                theme.get_component_style("calendar_day_header", *day == self.selected_date)
             };
-
-            let day_para = Paragraph::new(day_text)
-                .block(Block::default().borders(Borders::ALL))
+            let header = Paragraph::new(day.format("%a %d").to_string())
+                .alignment(Alignment::Center)
                 .style(style);
+            frame.render_widget(header, Rect::new(day_x(index), header_y, day_width, 1));
 
-            frame.render_widget(day_para, day_area);
+            let all_day_titles: Vec<&str> = self
+                .events_on(*day)
+                .into_iter()
+                .filter(|event| event.all_day)
+                .map(|event| event.title.as_str())
+                .collect();
+            if !all_day_titles.is_empty() {
+                let all_day = Paragraph::new(all_day_titles.join(", "))
+                    .style(Style::default().add_modifier(Modifier::ITALIC));
+                frame.render_widget(all_day, Rect::new(day_x(index), all_day_y, day_width, 1));
+            }
         }
-    }
-
-    /// Render week grid with time slots and events
-    fn render_week_grid(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        // Create time slots (6 AM to 10 PM in 2-hour increments)
-        let time_slots = (6..=22).step_by(2).collect::<Vec<_>>();
-        let slot_height = area.height / time_slots.len() as u16;
-
-        let week_start = self.get_week_start();
-        let day_width = area.width / 7;
-
-        // Render time grid
-        for (slot_idx, hour) in time_slots.iter().enumerate() {
-            let y = area.y + (slot_idx as u16) * slot_height;
 
-            // Render time label
-            let time_area = Rect::new(area.x, y, 5, slot_height);
-            let time_text = format!("{:02}:00", hour);
-            let time_para = Paragraph::new(time_text)
-                .style(theme.get_component_style("calendar_time_label", false));
-            frame.render_widget(time_para, time_area);
-
-            // Render day columns
-            for day in 0..7 {
-                let x = area.x + 5 + (day as u16) * day_width;
-                let day_area = Rect::new(x, y, day_width, slot_height);
+        // Hour labels, hour lines, the current-time line and the selected slot
+        for row in 0..grid.height {
+            let slot = self.grid_scroll_slot + row as u32;
+            if slot >= SLOTS_PER_DAY {
+                break;
+            }
+            let y = grid.y + row;
+            let minute = slot * SLOT_MINUTES;
+            let is_now_row = slot == now_slot && days.contains(&today);
+
+            let (label, label_style) = if is_now_row {
+                (now.format("%H:%M").to_string(), Style::default().fg(Color::Red))
+            } else if minute.is_multiple_of(60) {
+                (
+                    format!("{:02}:00", minute / 60),
+                    theme.get_component_style("calendar_time_label", false),
+                )
+            } else {
+                (String::new(), Style::default())
+            };
+            frame.render_widget(
+                Paragraph::new(label).style(label_style),
+                Rect::new(grid.x, y, GUTTER, 1),
+            );
 
-                // Draw grid cell
-                let cell_block = Block::default()
-                    .borders(Borders::ALL)
-                    .border_style(theme.get_component_style("calendar_grid", false));
-                frame.render_widget(cell_block, day_area);
-
-                // Render events in this time slot
-                let current_date = week_start + Duration::days(day as i64);
-                self.render_events_in_time_slot(
-                    frame,
-                    day_area.inner(&Margin::new(1, 0)),
-                    current_date,
-                    *hour,
-                    theme,
+            for (index, day) in days.iter().enumerate() {
+                let (text, mut style) = if is_now_row && *day == today {
+                    ("─".repeat(day_width as usize), Style::default().fg(Color::Red))
+                } else if minute.is_multiple_of(60) {
+                    ("┈".repeat(day_width as usize), grid_style)
+                } else {
+                    (String::new(), Style::default())
+                };
+                if is_focused && *day == self.selected_date && slot == self.grid_selected_slot {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                frame.render_widget(
+                    Paragraph::new(text).style(style),
+                    Rect::new(day_x(index), y, day_width, 1),
                 );
             }
         }
-    }
 
-    /// Render events within a specific time slot
-    fn render_events_in_time_slot(
-        &self,
-        frame: &mut Frame,
-        area: Rect,
-        date: DateTime<Local>,
-        hour: i32,
-        _theme: &Theme,
-    ) {
-        let slot_start = date.date_naive().and_hms_opt(hour as u32, 0, 0).unwrap();
-        let slot_end = slot_start + Duration::hours(2);
-
-        let events_in_slot: Vec<&Event> = self
-            .events
-            .iter()
-            .filter(|event| {
-                if !self.enabled_calendars.contains(&event.calendar_id) {
-                    return false;
+        // Event blocks on top of the grid
+        let first_visible = self.grid_scroll_slot;
+        let last_visible = (first_visible + grid.height as u32).min(SLOTS_PER_DAY);
+        for (index, day) in days.iter().enumerate() {
+            let day_events = self.events_on(*day);
+            for block in layout_day(&day_events, *day, &self.timezone) {
+                let first = block.start_slot().max(first_visible);
+                let last = block.end_slot().min(last_visible);
+                let Some(event) = day_events.iter().find(|event| event.id == block.event_id) else {
+                    continue;
+                };
+                if first >= last {
+                    continue;
                 }
 
-                let event_start = event.start_time.naive_local();
-                let event_end = event.end_time.naive_local();
-
-                // Check if event overlaps with this time slot
-                event_start < slot_end && event_end > slot_start
-            })
-            .collect();
-
-        // Render each event as a small block
-        for (i, event) in events_in_slot.iter().enumerate() {
-            if i >= area.height as usize {
-                break; // Don't overflow the area
-            }
-
-            let event_area = Rect::new(area.x, area.y + i as u16, area.width, 1);
-            let event_text = if event.title.len() > area.width as usize {
-                format!(
-                    "{}...",
-                    &event.title[..area.width.saturating_sub(3) as usize]
-                )
-            } else {
-                event.title.clone()
-            };
+                let column_width = (day_width / block.columns as u16).max(1);
+                let x = day_x(index) + block.column as u16 * column_width;
+                let width = if block.column + 1 == block.columns {
+                    day_width.saturating_sub(block.column as u16 * column_width)
+                } else {
+                    column_width
+                };
+                // Leave a gap between neighbouring blocks when there is room
+                let width = if width > 2 { width - 1 } else { width.max(1) };
+                let rect = Rect::new(
+                    x,
+                    grid.y + (first - first_visible) as u16,
+                    width,
+                    (last - first) as u16,
+                );
 
-            let priority_color = match event.priority {
-                EventPriority::High => Color::Red,
-                EventPriority::Normal => Color::Blue,
-                EventPriority::Low => Color::Gray,
-            };
+                let color = self
+                    .calendars
+                    .iter()
+                    .find(|calendar| calendar.id == event.calendar_id)
+                    .and_then(|calendar| calendar.color.as_deref())
+                    .map(|color| self.parse_color(color))
+                    .unwrap_or(match event.priority {
+                        EventPriority::High => Color::Red,
+                        EventPriority::Normal => Color::Blue,
+                        EventPriority::Low => Color::Gray,
+                    });
+                let mut style = Style::default().bg(color).fg(Color::White);
+                if is_focused && *day == self.selected_date && block.covers_slot(self.grid_selected_slot) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
 
-            let event_para = Paragraph::new(event_text).style(
-                Style::default()
-                    .fg(priority_color)
-                    .add_modifier(Modifier::BOLD),
-            );
+                let mut lines = vec![Line::from(Span::styled(
+                    event.title.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ))];
+                lines.push(Line::from(format!(
+                    "{}-{}",
+                    event.start_time.with_timezone(&self.timezone).format("%H:%M"),
+                    event.end_time.with_timezone(&self.timezone).format("%H:%M")
+                )));
+                if let Some(location) = &event.location {
+                    lines.push(Line::from(location.clone()));
+                }
 
-            frame.render_widget(event_para, event_area);
+                frame.render_widget(Clear, rect);
+                frame.render_widget(Paragraph::new(lines).style(style), rect);
+            }
         }
     }
 
-    /// Render day schedule view
-    fn render_day_schedule(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let is_focused = self.focused_pane == CalendarPane::Calendar;
-
-        // Create hourly schedule from 6 AM to 10 PM
-        let hours = (6..=22).collect::<Vec<_>>();
-        let hour_height = area.height / hours.len() as u16;
-
-        let day_events: Vec<&Event> = self
-            .events
+    /// Events from enabled calendars that touch the given local date
+    fn events_on(&self, date: NaiveDate) -> Vec<&Event> {
+        self.events
             .iter()
             .filter(|event| {
                 self.enabled_calendars.contains(&event.calendar_id)
-                    && event.start_time.date_naive() == self.selected_date
+                    && self.start_date(event) <= date
+                    && self.end_date(event) >= date
             })
-            .collect();
-
-        let schedule_block = Block::default()
-            .borders(Borders::ALL)
-            .title(format!(
-                "Schedule - {}",
-                self.selected_date.format("%A, %B %d")
-            ))
-            .border_style(theme.get_component_style("border", is_focused));
-
-        frame.render_widget(schedule_block, area);
-
-        let inner_area = area.inner(&Margin::new(1, 1));
-
-        // Render hourly grid
-        for (i, hour) in hours.iter().enumerate() {
-            let y = inner_area.y + (i as u16) * hour_height;
-            let _hour_area = Rect::new(inner_area.x, y, inner_area.width, hour_height);
-
-            // Time label
-            let time_label_area = Rect::new(inner_area.x, y, 6, 1);
-            let time_text = format!("{:02}:00", hour);
-            let time_para = Paragraph::new(time_text)
-                .style(theme.get_component_style("calendar_time_label", false));
-            frame.render_widget(time_para, time_label_area);
-
-            // Event area
-            let event_area = Rect::new(inner_area.x + 7, y, inner_area.width - 7, hour_height);
-
-            // Find events for this hour
-            let hour_events: Vec<&Event> = day_events
-                .iter()
-                .filter(|event| {
-                    let event_hour = event.start_time.hour() as i32;
-                    event_hour == *hour
-                })
-                .cloned()
-                .collect();
+            .collect()
+    }
 
-            // Render events in this hour
-            for (j, event) in hour_events.iter().enumerate() {
-                if j >= hour_height as usize {
-                    break;
-                }
+    /// Today's date in the display time zone
+    fn today(&self) -> NaiveDate {
+        Utc::now().with_timezone(&self.timezone).date_naive()
+    }
 
-                let event_y = y + j as u16;
-                let event_rect = Rect::new(event_area.x, event_y, event_area.width, 1);
+    /// Date an event starts on in the display time zone
+    ///
+    /// All-day events are kept as UTC dates, so they stay on their day
+    /// whatever the zone.
+    fn start_date(&self, event: &Event) -> NaiveDate {
+        if event.all_day {
+            event.start_time.date_naive()
+        } else {
+            event.start_time.with_timezone(&self.timezone).date_naive()
+        }
+    }
 
-                let time_str = if event.all_day {
-                    "All Day".to_string()
-                } else {
-                    format!(
-                        "{}-{}",
-                        event.start_time.format("%H:%M"),
-                        event.end_time.format("%H:%M")
-                    )
-                };
+    /// Date an event ends on in the display time zone
+    fn end_date(&self, event: &Event) -> NaiveDate {
+        if event.all_day {
+            // The end of an all-day event is the start of the next day
+            (event.end_time - Duration::seconds(1))
+                .max(event.start_time)
+                .date_naive()
+        } else {
+            event.end_time.with_timezone(&self.timezone).date_naive()
+        }
+    }
 
-                let event_text = format!("{} {}", time_str, event.title);
-                let priority_color = match event.priority {
-                    EventPriority::High => Color::Red,
-                    EventPriority::Normal => Color::Blue,
-                    EventPriority::Low => Color::Gray,
-                };
+    /// Whether the current view is drawn as a time grid
+    fn is_time_grid_view(&self) -> bool {
+        matches!(self.current_view, CalendarViewMode::Day | CalendarViewMode::Week)
+    }
 
-                let event_para =
-                    Paragraph::new(event_text).style(Style::default().fg(priority_color));
+    /// Keep the selected slot inside the visible part of the grid
+    fn scroll_to_selected_slot(&mut self) {
+        let visible = self.grid_visible_slots.max(1);
+        if self.grid_selected_slot < self.grid_scroll_slot {
+            self.grid_scroll_slot = self.grid_selected_slot;
+        } else if self.grid_selected_slot >= self.grid_scroll_slot + visible {
+            self.grid_scroll_slot = self.grid_selected_slot + 1 - visible;
+        }
+        self.grid_scroll_slot = self
+            .grid_scroll_slot
+            .min(SLOTS_PER_DAY.saturating_sub(visible));
+    }
 
-                frame.render_widget(event_para, event_rect);
-            }
+    /// Move the selected time slot up or down
+    fn move_selected_slot(&mut self, delta: i32) {
+        let slot = self.grid_selected_slot as i32 + delta;
+        self.grid_selected_slot = slot.clamp(0, SLOTS_PER_DAY as i32 - 1) as u32;
+        self.scroll_to_selected_slot();
+    }
 
-            // Draw hour separator
-            if i < hours.len() - 1 {
-                let separator_area =
-                    Rect::new(inner_area.x, y + hour_height - 1, inner_area.width, 1);
-                let separator = Paragraph::new("─".repeat(inner_area.width as usize))
-                    .style(theme.get_component_style("calendar_grid", false));
-                frame.render_widget(separator, separator_area);
-            }
-        }
+    /// Move the selected day, keeping the displayed period in sync
+    fn move_selected_day(&mut self, delta: i64) {
+        self.current_date += Duration::days(delta);
+        self.selected_date = self.current_date.date_naive();
     }
 
+    /// Timed event covering the selected slot on the selected date
+    fn event_at_selected_slot(&self) -> Option<&Event> {
+        let day_events = self.events_on(self.selected_date);
+        let block = layout_day(&day_events, self.selected_date, &self.timezone)
+            .into_iter()
+            .find(|block| block.covers_slot(self.grid_selected_slot))?;
+        day_events.into_iter().find(|event| event.id == block.event_id)
+    }
     /// Render day summary sidebar
     fn render_day_summary(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let day_events: Vec<&Event> = self
@@ -823,7 +874,7 @@ impl CalendarUI {
             .iter()
             .filter(|event| {
                 self.enabled_calendars.contains(&event.calendar_id)
-                    && event.start_time.date_naive() == self.selected_date
+                    && self.start_date(event) == self.selected_date
             })
             .collect();
 
@@ -875,18 +926,36 @@ impl CalendarUI {
         let list_items: Vec<ListItem> = upcoming_events
             .iter()
             .map(|event| {
-                let date_str = event.start_time.format("%m/%d");
-                let time_str = if event.all_day {
-                    "All Day".to_string()
+                let start = event.start_time.with_timezone(&self.timezone);
+                let (date_str, time_str) = if event.all_day {
+                    (event.start_time.format("%m/%d").to_string(), "All Day".to_string())
                 } else {
-                    event.start_time.format("%H:%M").to_string()
+                    (start.format("%m/%d").to_string(), start.format("%H:%M").to_string())
                 };
 
-                let spans = vec![
+                let mut spans = vec![
                     Span::styled(format!("{} ", date_str), Style::default().fg(Color::Cyan)),
                     Span::styled(format!("{} ", time_str), Style::default().fg(Color::Yellow)),
-                    Span::styled(event.title.clone(), Style::default().fg(Color::White)),
                 ];
+                // The zone the event was set up in, with its time there when it differs
+                if !event.all_day {
+                    let original = event
+                        .timezone
+                        .as_deref()
+                        .and_then(timezone::parse_tzid)
+                        .unwrap_or(Tz::UTC);
+                    spans.push(Span::styled(
+                        format!(
+                            "[{}] ",
+                            timezone::original_zone_label(event.start_time, original, self.timezone)
+                        ),
+                        Style::default().fg(Color::Gray),
+                    ));
+                }
+                spans.push(Span::styled(
+                    event.title.clone(),
+                    Style::default().fg(Color::White),
+                ));
 
                 ListItem::new(Line::from(spans))
             })
@@ -965,9 +1034,10 @@ impl CalendarUI {
     /// Render status line with calendar information
     fn render_status_line(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let status_text = format!(
-            "View: {} | Date: {} | Events: {} | Calendars: {} enabled",
+            "View: {} | Date: {} | Zone: {} | Events: {} | Calendars: {} enabled",
             self.current_view.name(),
             self.selected_date.format("%Y-%m-%d"),
+            timezone::abbreviation(Utc::now(), self.timezone),
             self.events.len(),
             self.enabled_calendars.len(),
         );
@@ -1034,8 +1104,8 @@ impl CalendarUI {
         } else {
             format!(
                 "{} - {}",
-                event.start_time.format("%Y-%m-%d %H:%M"),
-                event.end_time.format("%Y-%m-%d %H:%M")
+                event.start_time.with_timezone(&self.timezone).format("%Y-%m-%d %H:%M %Z"),
+                event.end_time.with_timezone(&self.timezone).format("%Y-%m-%d %H:%M %Z")
             )
         };
 
@@ -1054,13 +1124,70 @@ impl CalendarUI {
             Span::raw(format!("{:?}", event.priority)),
         ]));
 
-        if !event.attendees.is_empty() {
+        if let Some(ref organizer) = event.organizer {
             lines.push(Line::from(vec![
-                Span::styled("Attendees: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(format!("{}", event.attendees.len())),
+                Span::styled("Organizer: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{} <{}>", organizer.display_name(), organizer.email)),
             ]));
         }
 
+        if !event.attendees.is_empty() {
+            let is_organizer = self.is_organizer_of(event);
+
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Attendees:",
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+
+            for attendee in &event.attendees {
+                let (icon, label, color) = attendee_status_display(&attendee.status);
+                let mut spans = vec![
+                    Span::styled(format!("  {} ", icon), Style::default().fg(color)),
+                    Span::raw(attendee.display_name().to_string()),
+                ];
+                if attendee.name.is_some() {
+                    spans.push(Span::styled(
+                        format!(" <{}>", attendee.email),
+                        Style::default().fg(Color::Gray),
+                    ));
+                }
+                spans.push(Span::styled(
+                    format!("  {}", label),
+                    Style::default().fg(color),
+                ));
+                if attendee.role == AttendeeRole::OptionalParticipant {
+                    spans.push(Span::styled(" (optional)", Style::default().fg(Color::Gray)));
+                }
+                lines.push(Line::from(spans));
+            }
+
+            if is_organizer {
+                let tally = event.response_tally();
+                lines.push(Line::from(""));
+                lines.push(Line::from(vec![
+                    Span::styled("Responses: ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(format!(
+                        "{} of {} responded \u{2014} {} accepted, {} tentative, {} declined, {} pending",
+                        tally.responded(),
+                        tally.total(),
+                        tally.accepted,
+                        tally.tentative,
+                        tally.declined,
+                        tally.needs_action
+                    )),
+                ]));
+
+                let pending = event.non_responders().len();
+                if pending > 0 {
+                    lines.push(Line::from(Span::styled(
+                        format!("Alt+M: email a reminder to {} non-responder(s)", pending),
+                        Style::default().fg(Color::Gray),
+                    )));
+                }
+            }
+        }
+
         Text::from(lines)
     }
 
@@ -1174,13 +1301,24 @@ impl CalendarUI {
     }
 
     /// Get the start of the current week (Monday)
-    fn get_week_start(&self) -> DateTime<Local> {
+    fn get_week_start(&self) -> DateTime<Tz> {
         let days_since_monday = self.current_date.weekday().num_days_from_monday();
         self.current_date - Duration::days(days_since_monday as i64)
     }
 
     // Public methods for external control
 
+    /// Show times in another time zone
+    pub fn set_timezone(&mut self, timezone: Tz) {
+        self.timezone = timezone;
+        self.current_date = self.current_date.with_timezone(&timezone);
+    }
+
+    /// Zone times are shown in
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+    }
+
     /// Set events to display
     pub fn set_events(&mut self, events: Vec<Event>) {
         self.events = events;
@@ -1200,6 +1338,16 @@ impl CalendarUI {
         self.calendars = calendars;
     }
 
+    /// Set the addresses of the user's accounts
+    pub fn set_user_emails(&mut self, emails: Vec<String>) {
+        self.user_emails = emails;
+    }
+
+    /// Whether the user organizes the event
+    pub fn is_organizer_of(&self, event: &Event) -> bool {
+        event.is_organized_by(&self.user_emails)
+    }
+
     /// Handle keyboard input
     pub async fn handle_key(&mut self, key: crossterm::event::KeyCode) -> Option<CalendarAction> {
         use crossterm::event::KeyCode;
@@ -1245,6 +1393,10 @@ impl CalendarUI {
 
             // Navigation
             KeyCode::Left | KeyCode::Char('h') => match self.focused_pane {
+                CalendarPane::Calendar if self.current_view == CalendarViewMode::Week => {
+                    self.move_selected_day(-1);
+                    Some(CalendarAction::PreviousPeriod)
+                }
                 CalendarPane::Calendar => {
                     self.navigate_period(-1);
                     Some(CalendarAction::PreviousPeriod)
@@ -1252,6 +1404,10 @@ impl CalendarUI {
                 _ => None,
             },
             KeyCode::Right | KeyCode::Char('l') => match self.focused_pane {
+                CalendarPane::Calendar if self.current_view == CalendarViewMode::Week => {
+                    self.move_selected_day(1);
+                    Some(CalendarAction::NextPeriod)
+                }
                 CalendarPane::Calendar => {
                     self.navigate_period(1);
                     Some(CalendarAction::NextPeriod)
@@ -1259,6 +1415,10 @@ impl CalendarUI {
                 _ => None,
             },
             KeyCode::Up | KeyCode::Char('k') => match self.focused_pane {
+                CalendarPane::Calendar if self.is_time_grid_view() => {
+                    self.move_selected_slot(-1);
+                    None
+                }
                 CalendarPane::EventList => {
                     self.event_list_previous();
                     None
@@ -1270,6 +1430,10 @@ impl CalendarUI {
                 _ => None,
             },
             KeyCode::Down | KeyCode::Char('j') => match self.focused_pane {
+                CalendarPane::Calendar if self.is_time_grid_view() => {
+                    self.move_selected_slot(1);
+                    None
+                }
                 CalendarPane::EventList => {
                     self.event_list_next();
                     None
@@ -1291,6 +1455,12 @@ impl CalendarUI {
                 _ => None,
             },
             KeyCode::Enter => match self.focused_pane {
+                CalendarPane::Calendar if self.is_time_grid_view() => {
+                    let event = self.event_at_selected_slot().cloned()?;
+                    let event_id = event.id.clone();
+                    self.show_event_details(event);
+                    Some(CalendarAction::ShowEventDetails(event_id))
+                }
                 CalendarPane::EventList => {
                     if let Some(selected_event_id) = self.get_selected_event_id() {
                         if let Some(selected_event) = self.get_event_by_id(&selected_event_id) {
@@ -1382,8 +1552,8 @@ impl CalendarUI {
 
     /// Navigate to today
     pub fn navigate_to_today(&mut self) {
-        self.current_date = Local::now();
-        self.selected_date = Local::now().date_naive();
+        self.current_date = Utc::now().with_timezone(&self.timezone);
+        self.selected_date = self.current_date.date_naive();
     }
 
     /// Handle key input for delete confirmation dialog
@@ -1469,7 +1639,7 @@ impl CalendarUI {
                 .iter()
                 .filter(|event| {
                     self.enabled_calendars.contains(&event.calendar_id)
-                        && event.start_time.date_naive() == self.selected_date
+                        && self.start_date(event) == self.selected_date
                 })
                 .count(),
             CalendarViewMode::Agenda => {
@@ -1533,6 +1703,18 @@ impl CalendarUI {
 
     /// Get currently selected event ID
     pub fn get_selected_event_id(&self) -> Option<String> {
+        // The event shown in the details overlay wins over the list selection
+        if self.show_event_details {
+            if let Some(ref event) = self.selected_event {
+                return Some(event.id.clone());
+            }
+        }
+
+        // In the time grid the event under the selected slot is the selection
+        if self.focused_pane == CalendarPane::Calendar && self.is_time_grid_view() {
+            return self.event_at_selected_slot().map(|event| event.id.clone());
+        }
+
         if let Some(selected) = self.event_list_state.selected() {
             match self.current_view {
                 CalendarViewMode::Month | CalendarViewMode::Week | CalendarViewMode::Day => {
@@ -1541,7 +1723,7 @@ impl CalendarUI {
                         .iter()
                         .filter(|event| {
                             self.enabled_calendars.contains(&event.calendar_id)
-                                && event.start_time.date_naive() == self.selected_date
+                                && self.start_date(event) == self.selected_date
                         })
                         .collect();
                     selected_events.get(selected).map(|e| e.id.clone())
@@ -1579,8 +1761,26 @@ impl CalendarUI {
         self.focused_pane = CalendarPane::EventDetails;
     }
 
+    /// Show the details overlay for an event by ID
+    ///
+    /// Returns `false` if the event isn't loaded.
+    pub fn show_event_details_by_id(&mut self, event_id: &str) -> bool {
+        match self.get_event_by_id(event_id).cloned() {
+            Some(event) => {
+                self.show_event_details(event);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if the event details overlay is visible
+    pub fn is_showing_event_details(&self) -> bool {
+        self.show_event_details
+    }
+
     /// Hide event details overlay
-    fn hide_event_details(&mut self) {
+    pub fn hide_event_details(&mut self) {
         self.show_event_details = false;
         self.selected_event = None;
         self.focused_pane = CalendarPane::Calendar;
@@ -1622,11 +1822,9 @@ impl CalendarUI {
     /// Set selected date
     pub fn set_selected_date(&mut self, date: NaiveDate) {
         self.selected_date = date;
-        self.current_date = date
-            .and_hms_opt(12, 0, 0)
-            .unwrap()
-            .and_local_timezone(Local)
-            .unwrap();
+        self.current_date =
+            timezone::local_to_utc(date.and_hms_opt(12, 0, 0).unwrap(), self.timezone)
+                .with_timezone(&self.timezone);
     }
 
     /// Get enabled calendars
@@ -1659,3 +1857,14 @@ impl Default for CalendarUI {
         Self::new()
     }
 }
+
+/// Icon, label and colour for an attendee's response
+fn attendee_status_display(status: &AttendeeStatus) -> (&'static str, &'static str, Color) {
+    match status {
+        AttendeeStatus::Accepted => ("✓", "accepted", Color::Green),
+        AttendeeStatus::Declined => ("✗", "declined", Color::Red),
+        AttendeeStatus::Tentative => ("?", "tentative", Color::Yellow),
+        AttendeeStatus::Delegated => ("→", "delegated", Color::Cyan),
+        AttendeeStatus::NeedsAction => ("…", "no response", Color::Gray),
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{anyhow, Result};
 use base64::Engine;
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::{Deserialize, Serialize};
 use std::io::Write;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -8,9 +9,11 @@ use tokio::time::Duration;
 
 use crate::email::{DatabaseStats, EmailDatabase};
 use crate::imap::ImapAccountManager;
-use crate::keyboard::{KeyboardAction, KeyboardConfig, KeyboardManager, KeyboardShortcut};
+use crate::keyboard::{
+    KeyboardAction, KeyboardConfig, KeyboardManager, KeyboardPreset, KeyboardShortcut,
+};
 use crate::maildir::{Maildir, MaildirUtils};
-use crate::oauth2::{AccountConfig, SecureStorage, TokenManager};
+use crate::oauth2::{AccountConfig, CredentialBackend, SecureStorage, TokenManager};
 
 /// Comunicado - Modern terminal email and calendar client
 #[derive(Parser)]
@@ -45,6 +48,18 @@ pub struct Cli {
     #[arg(long = "con")]
     pub start_contacts: bool,
 
+    /// Where to start, overriding `startup` in session.toml
+    #[arg(long, value_enum)]
+    pub start: Option<StartupMode>,
+
+    /// Account to open at startup (implies `--start email`)
+    #[arg(long)]
+    pub account: Option<String>,
+
+    /// Folder to open at startup (implies `--start email`)
+    #[arg(long)]
+    pub folder: Option<String>,
+
     /// Reprocess all email content in the database for clean display
     #[arg(long, global = true)]
     pub clean_content: bool,
@@ -53,30 +68,70 @@ pub struct Cli {
 impl Cli {
     /// Determine the startup mode based on CLI arguments
     pub fn get_startup_mode(&self) -> StartupMode {
-        // Only one mode can be selected - priority: contacts > calendar > mail > default
+        // Only one mode can be selected - priority: contacts > calendar > mail > --start > default
         if self.start_contacts {
             StartupMode::Contacts
         } else if self.start_calendar {
             StartupMode::Calendar
         } else if self.start_mail {
             StartupMode::Email
+        } else if let Some(mode) = self.start {
+            mode
+        } else if self.account.is_some() || self.folder.is_some() {
+            StartupMode::Email
         } else {
             StartupMode::Default
         }
     }
+
+    /// Startup mode and location from the command line, falling back to
+    /// the `startup` settings in session.toml
+    pub fn startup(&self, config: &crate::ui::SessionConfig) -> Startup {
+        let mode = match self.get_startup_mode() {
+            StartupMode::Default => config.startup,
+            mode => mode,
+        };
+        let (account, folder) = if self.account.is_some() || self.folder.is_some() {
+            (self.account.clone(), self.folder.clone())
+        } else {
+            (config.startup_account.clone(), config.startup_folder.clone())
+        };
+        Startup {
+            mode,
+            account,
+            folder,
+        }
+    }
 }
 
 /// Available startup modes from CLI
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
 pub enum StartupMode {
-    /// Default mode (email interface)
+    /// Nothing chosen on the command line; use the configured startup
+    #[value(skip)]
+    #[serde(skip)]
     Default,
+    /// Back where the last session left off, when session restore is on
+    Resume,
     /// Start in email interface
     Email,
     /// Start in calendar mode
     Calendar,
     /// Start in contacts mode
     Contacts,
+    /// Start the background daemon instead of the interface
+    Daemon,
+}
+
+/// Where the interface opens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Startup {
+    pub mode: StartupMode,
+    /// Account the email interface opens, for [`StartupMode::Email`]
+    pub account: Option<String>,
+    /// Folder the email interface opens, for [`StartupMode::Email`]
+    pub folder: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -153,8 +208,17 @@ pub enum Commands {
     /// IMAP folder operations and diagnostics
     Folders(FoldersArgs),
 
+    /// Import and export filter rules as Sieve scripts
+    Filters(FiltersArgs),
+
     /// OAuth2 token management and refresh operations
     OAuth2(OAuth2Args),
+
+    /// Move stored credentials between keyrings and machines
+    Credentials(CredentialsArgs),
+
+    /// Sync mail and show new-mail notifications in the background, without the TUI
+    Daemon(DaemonArgs),
 }
 
 #[derive(Args)]
@@ -488,6 +552,12 @@ pub enum KeyboardCommands {
         #[arg(short, long)]
         config: Option<PathBuf>,
     },
+
+    /// Show or choose a keybinding preset (default, gmail)
+    Preset {
+        /// Preset to use; shows the current one when omitted
+        name: Option<String>,
+    },
 }
 
 #[derive(Args)]
@@ -856,6 +926,32 @@ pub enum FoldersCommands {
     },
 }
 
+#[derive(Args)]
+pub struct FiltersArgs {
+    #[command(subcommand)]
+    pub command: FiltersCommands,
+}
+
+#[derive(Subcommand)]
+pub enum FiltersCommands {
+    /// Write the enabled filter rules as a Sieve script
+    Export {
+        /// File to write (prints to stdout if not specified)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Add the rules of a Sieve script to the filter rules
+    Import {
+        /// Sieve script to read
+        file: PathBuf,
+
+        /// Replace the existing rules instead of adding to them
+        #[arg(long)]
+        replace: bool,
+    },
+}
+
 #[derive(Args)]
 pub struct OAuth2Args {
     #[command(subcommand)]
@@ -900,8 +996,56 @@ pub enum OAuth2Commands {
     },
 }
 
+#[derive(Args)]
+pub struct CredentialsArgs {
+    #[command(subcommand)]
+    pub command: CredentialsCommands,
+}
+
+#[derive(Subcommand)]
+pub enum CredentialsCommands {
+    /// Show the credential backend in use and which backends are available
+    Status,
+
+    /// Write all accounts and tokens to a passphrase-encrypted file
+    Export {
+        /// Export file path
+        output: PathBuf,
+    },
+
+    /// Load accounts and tokens from an export file into the current backend
+    Import {
+        /// Export file path
+        input: PathBuf,
+    },
+
+    /// Copy all credentials to another backend and use it from now on
+    Migrate {
+        /// Target backend: system, keyutils or file
+        to: String,
+    },
+
+    /// Set, change or remove the master passphrase that unlocks the TUI
+    Passphrase,
+
+    /// Encrypt stored tokens with a passphrase (only without a system keyring)
+    Encrypt,
+}
+
+#[derive(Args)]
+pub struct DaemonArgs {
+    /// Stay attached to the terminal instead of starting a background process
+    #[arg(long)]
+    pub foreground: bool,
+
+    /// Seconds between sync passes
+    #[arg(long, default_value_t = crate::daemon::DEFAULT_INTERVAL_SECS)]
+    pub interval: u64,
+}
+
 /// Command-line interface handler
 pub struct CliHandler {
+    config_dir: PathBuf,
     database: Arc<EmailDatabase>,
     storage: SecureStorage,
     token_manager: Option<TokenManager>,
@@ -912,23 +1056,24 @@ impl CliHandler {
     pub async fn new(config_dir: Option<PathBuf>) -> Result<Self> {
         let storage = SecureStorage::new("comunicado".to_string())?;
 
-        // Initialize database
-        let db_path = if let Some(ref dir) = config_dir {
-            dir.join("databases").join("email.db")
-        } else {
-            dirs::config_dir()
+        let config_dir = match config_dir {
+            Some(dir) => dir,
+            None => dirs::config_dir()
                 .ok_or_else(|| anyhow!("Cannot find config directory"))?
-                .join("comunicado")
-                .join("databases")
-                .join("email.db")
+                .join("comunicado"),
         };
 
+        // Initialize database
+        let db_path = config_dir.join("databases").join("email.db");
+        tokio::fs::create_dir_all(config_dir.join("databases")).await?;
+
         let database = Arc::new(EmailDatabase::new(db_path.to_str().unwrap()).await?);
 
         // Initialize token manager with storage for access to saved tokens
         let token_manager = Some(TokenManager::new_with_storage(Arc::new(storage.clone())));
 
         Ok(Self {
+            config_dir,
             database,
             storage,
             token_manager,
@@ -937,6 +1082,19 @@ impl CliHandler {
 
     /// Handle CLI commands
     pub async fn handle_command(&self, command: Commands, dry_run: bool) -> Result<()> {
+        let needs_secrets = !matches!(
+            &command,
+            Commands::Credentials(CredentialsArgs {
+                command: CredentialsCommands::Status | CredentialsCommands::Encrypt
+            }) | Commands::Daemon(DaemonArgs {
+                foreground: false,
+                ..
+            })
+        );
+        if needs_secrets && !self.unlock_credential_vault()? {
+            return Err(anyhow!("The credential vault is locked"));
+        }
+
         match command {
             Commands::Test(args) => self.handle_test(args, dry_run).await,
             Commands::Import(args) => self.handle_import(args, dry_run).await,
@@ -957,7 +1115,10 @@ impl CliHandler {
             Commands::CalendarSync(args) => self.handle_calendar_sync(args, dry_run).await,
             Commands::ContactsSync(args) => self.handle_contacts_sync(args, dry_run).await,
             Commands::Folders(args) => self.handle_folders(args, dry_run).await,
+            Commands::Filters(args) => self.handle_filters(args, dry_run).await,
             Commands::OAuth2(args) => self.handle_oauth2(args, dry_run).await,
+            Commands::Credentials(args) => self.handle_credentials(args, dry_run).await,
+            Commands::Daemon(args) => self.handle_daemon(args).await,
         }
     }
 
@@ -2037,7 +2198,37 @@ impl CliHandler {
                 self.handle_keyboard_import(input, merge, dry_run).await
             }
             KeyboardCommands::Validate { config } => self.handle_keyboard_validate(config).await,
+            KeyboardCommands::Preset { name } => self.handle_keyboard_preset(name, dry_run).await,
+        }
+    }
+
+    /// Show or choose the keybinding preset
+    async fn handle_keyboard_preset(&self, name: Option<String>, dry_run: bool) -> Result<()> {
+        println!("⌨️  Keyboard Preset");
+        println!("===================\n");
+
+        let current = KeyboardPreset::load()?;
+        let Some(name) = name else {
+            println!("Current preset: {}", current.name());
+            println!("\nAvailable presets:");
+            for preset in KeyboardPreset::ALL {
+                println!("  - {}", preset.name());
+            }
+            return Ok(());
+        };
+
+        let preset = KeyboardPreset::from_name(&name)
+            .ok_or_else(|| anyhow!("Unknown preset '{}' (available: default, gmail)", name))?;
+
+        if dry_run {
+            println!("🧪 Would switch from '{}' to '{}'", current.name(), preset.name());
+            return Ok(());
         }
+
+        preset.save()?;
+        println!("✅ Keyboard preset set to '{}'", preset.name());
+        println!("   Restart Comunicado to apply it");
+        Ok(())
     }
 
     /// Show current keyboard shortcuts
@@ -2363,6 +2554,8 @@ impl CliHandler {
             "vim_move_down" | "vimdown" => Ok(KeyboardAction::VimMoveDown),
             "move_up" | "moveup" => Ok(KeyboardAction::MoveUp),
             "move_down" | "movedown" => Ok(KeyboardAction::MoveDown),
+            "go_to_inbox" | "gotoinbox" => Ok(KeyboardAction::GoToInbox),
+            "go_to_starred" | "gotostarred" => Ok(KeyboardAction::GoToStarred),
             "select" => Ok(KeyboardAction::Select),
             "escape" => Ok(KeyboardAction::Escape),
             "toggle_expanded" | "toggle" => Ok(KeyboardAction::ToggleExpanded),
@@ -2371,20 +2564,50 @@ impl CliHandler {
             "add_account" | "addaccount" => Ok(KeyboardAction::AddAccount),
             "remove_account" | "removeaccount" => Ok(KeyboardAction::RemoveAccount),
             "refresh_account" | "refreshaccount" => Ok(KeyboardAction::RefreshAccount),
+            "show_account_details" | "accountdetails" => Ok(KeyboardAction::ShowAccountDetails),
+            "show_special_folders" | "specialfolders" => Ok(KeyboardAction::ShowSpecialFolders),
+            "show_retention" | "retention" => Ok(KeyboardAction::ShowRetention),
+            "show_filter_rules" | "filter_rules" => Ok(KeyboardAction::ShowFilterRules),
+            "show_mbox_wizard" | "mbox_wizard" => Ok(KeyboardAction::ShowMboxWizard),
+            "mark_thread_read" | "threadread" => Ok(KeyboardAction::MarkThreadRead),
+            "mark_thread_unread" | "threadunread" => Ok(KeyboardAction::MarkThreadUnread),
+            "mark_all_read" | "markallread" => Ok(KeyboardAction::MarkAllRead),
+            "undo_mark_all_read" | "undomarkallread" => Ok(KeyboardAction::UndoMarkAllRead),
             "start_search" | "search" => Ok(KeyboardAction::StartSearch),
             "start_folder_search" | "foldersearch" => Ok(KeyboardAction::StartFolderSearch),
             "toggle_threaded_view" | "threadview" => Ok(KeyboardAction::ToggleThreadedView),
             "expand_thread" | "expand" => Ok(KeyboardAction::ExpandThread),
             "collapse_thread" | "collapse" => Ok(KeyboardAction::CollapseThread),
             "toggle_view_mode" | "viewmode" => Ok(KeyboardAction::ToggleViewMode),
+            "toggle_reflow" | "reflow" => Ok(KeyboardAction::ToggleReflow),
             "toggle_headers" | "headers" => Ok(KeyboardAction::ToggleHeaders),
+            "render_full_message" | "fullmessage" => Ok(KeyboardAction::RenderFullMessage),
+            "open_in_browser" | "browser" => Ok(KeyboardAction::OpenInBrowser),
+            "show_headers_panel" | "headers_panel" => Ok(KeyboardAction::ShowHeadersPanel),
+            "show_links" | "links" => Ok(KeyboardAction::ShowLinks),
+            "toggle_today_panel" | "today" => Ok(KeyboardAction::ToggleTodayPanel),
             "sort_by_date" | "sortdate" => Ok(KeyboardAction::SortByDate),
             "sort_by_sender" | "sortsender" => Ok(KeyboardAction::SortBySender),
             "sort_by_subject" | "sortsubject" => Ok(KeyboardAction::SortBySubject),
+            "sort_by_size" | "sortsize" => Ok(KeyboardAction::SortBySize),
+            "sort_by_priority" | "sortpriority" => Ok(KeyboardAction::SortByPriority),
+            "cycle_sort_field" | "sortfield" => Ok(KeyboardAction::CycleSortField),
+            "toggle_sort_order" | "sortorder" => Ok(KeyboardAction::ToggleSortOrder),
             "scroll_to_top" | "scrolltop" => Ok(KeyboardAction::ScrollToTop),
             "scroll_to_bottom" | "scrollbottom" => Ok(KeyboardAction::ScrollToBottom),
             "select_first_attachment" | "firstattachment" => Ok(KeyboardAction::SelectFirstAttachment),
             "view_attachment" | "viewattachment" => Ok(KeyboardAction::ViewAttachment),
+            "save_attachment" | "saveattachment" => Ok(KeyboardAction::SaveAttachment),
+            "save_all_attachments" | "saveallattachments" => Ok(KeyboardAction::SaveAllAttachments),
+            "block_sender" | "blocksender" => Ok(KeyboardAction::BlockSender),
+            "toggle_message_mark" | "togglemessagemark" => Ok(KeyboardAction::ToggleMessageMark),
+            "report_spam" | "reportspam" => Ok(KeyboardAction::ReportSpam),
+            "report_not_spam" | "reportnotspam" => Ok(KeyboardAction::ReportNotSpam),
+            "view_event_details" | "eventdetails" => Ok(KeyboardAction::ViewEventDetails),
+            "nudge_attendees" | "nudgeattendees" => Ok(KeyboardAction::NudgeAttendees),
+            "snooze_reminder" | "snoozereminder" => Ok(KeyboardAction::SnoozeReminder),
+            "show_sms" | "sms" => Ok(KeyboardAction::ShowSms),
+            "reply_to_sms" | "smsreply" => Ok(KeyboardAction::ReplyToSms),
             "open_attachment_with_system" | "openattachment" => Ok(KeyboardAction::OpenAttachmentWithSystem),
             "create_folder" | "createfolder" => Ok(KeyboardAction::CreateFolder),
             "delete_folder" | "deletefolder" => Ok(KeyboardAction::DeleteFolder),
@@ -2394,6 +2617,7 @@ impl CliHandler {
             "folder_refresh" | "f5" => Ok(KeyboardAction::FolderRefresh),
             "folder_rename" | "f2" => Ok(KeyboardAction::FolderRename),
             "folder_delete" | "delete" => Ok(KeyboardAction::FolderDelete),
+            "folder_menu" | "foldermenu" => Ok(KeyboardAction::FolderMenu),
             "next_attachment" | "nextattachment" => Ok(KeyboardAction::NextAttachment),
             "previous_attachment" | "prevattachment" => Ok(KeyboardAction::PreviousAttachment),
             _ => Err(anyhow!("Unknown keyboard action: {}. See 'comunicado keyboard show --detailed' for available actions", action_str)),
@@ -3699,6 +3923,8 @@ impl CliHandler {
                 sync_version: 1,
                 is_draft: message.is_draft(),
                 is_deleted: message.is_deleted(),
+                snoozed_until: None,
+                inbox_tab: None,
             };
             
             // Store with improved error handling (no timeout to prevent premature failures)
@@ -4200,7 +4426,356 @@ impl CliHandler {
         Ok(())
     }
 
+    /// Hand off to the background daemon instead of opening the interface
+    pub fn start_daemon(&self) -> Result<()> {
+        self.ensure_daemon_can_unlock()?;
+        match crate::daemon::running_pid(&self.config_dir) {
+            Some(pid) => println!("✅ Daemon already running (pid {})", pid),
+            None => {
+                let pid = crate::daemon::spawn_detached(
+                    &self.config_dir,
+                    crate::daemon::DEFAULT_INTERVAL_SECS,
+                )?;
+                println!("✅ Daemon started in the background (pid {})", pid);
+            }
+        }
+        println!("   Run `comunicado --start email` to open the interface");
+        Ok(())
+    }
+
+    /// A detached daemon has no terminal to ask for the vault passphrase
+    fn ensure_daemon_can_unlock(&self) -> Result<()> {
+        if self.storage.backend_status().backend == CredentialBackend::Encrypted {
+            return Err(anyhow!(
+                "Tokens are encrypted with a passphrase, which a background daemon can't ask for; run 'comunicado daemon --foreground' in a terminal multiplexer instead"
+            ));
+        }
+        Ok(())
+    }
+
     /// Handle OAuth2 token management commands
+    /// Start the background daemon, or run it in this process with `--foreground`
+    async fn handle_daemon(&self, args: DaemonArgs) -> Result<()> {
+        if !args.foreground {
+            self.ensure_daemon_can_unlock()?;
+            let pid = crate::daemon::spawn_detached(&self.config_dir, args.interval)?;
+            println!("✅ Daemon started in the background (pid {})", pid);
+            println!("   Log: {}", self.config_dir.join("daemon.log").display());
+            return Ok(());
+        }
+
+        let _ = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::INFO)
+            .with_ansi(std::io::IsTerminal::is_terminal(&std::io::stderr()))
+            .with_writer(std::io::stderr)
+            .try_init();
+
+        println!("🔄 Daemon running, syncing every {}s. Press Ctrl+C to stop.", args.interval);
+        crate::daemon::run(
+            self.config_dir.clone(),
+            Duration::from_secs(args.interval.max(30)),
+        )
+        .await
+    }
+
+    /// Ask what to do when the credential backend holding the tokens can't be
+    /// reached, before the TUI starts
+    ///
+    /// Returns `false` when the user chose to quit.
+    pub fn resolve_missing_credential_backend(&mut self) -> Result<bool> {
+        let Some(missing) = self.storage.backend_status().missing else {
+            return Ok(true);
+        };
+
+        println!("⚠️  Your accounts' tokens are stored in the {}, which is not available.", missing.description());
+        println!("   Without them, accounts can't sign in.");
+        println!();
+        println!("   1) Use file storage from now on (sign in again or import an export)");
+        println!("   2) Continue this time without the {}", missing.name());
+        println!("   3) Quit, then start the keyring service or run 'comunicado credentials import <file>'");
+        print!("Choose [1-3]: ");
+        std::io::stdout().flush()?;
+
+        let mut choice = String::new();
+        std::io::stdin().read_line(&mut choice)?;
+        match choice.trim() {
+            "1" => {
+                self.storage.migrate_backend(CredentialBackend::File)?;
+                println!("✅ Using file storage");
+                Ok(true)
+            }
+            "2" => Ok(true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Handle filters commands
+    async fn handle_filters(&self, args: FiltersArgs, dry_run: bool) -> Result<()> {
+        match args.command {
+            FiltersCommands::Export { output } => self.handle_filters_export(output, dry_run).await,
+            FiltersCommands::Import { file, replace } => {
+                self.handle_filters_import(file, replace, dry_run).await
+            }
+        }
+    }
+
+    async fn handle_filters_export(&self, output: Option<PathBuf>, dry_run: bool) -> Result<()> {
+        let rules = crate::email::FilterRules::load().await?;
+        let export = crate::email::to_sieve(&rules.rules);
+        for warning in &export.warnings {
+            eprintln!("⚠️  {}", warning);
+        }
+
+        match output {
+            Some(output) if dry_run => {
+                println!("🔍 DRY RUN - would write {} bytes to {}", export.script.len(), output.display());
+            }
+            Some(output) => {
+                std::fs::write(&output, &export.script)
+                    .map_err(|e| anyhow!("Failed to write {}: {}", output.display(), e))?;
+                println!("✅ Filter rules written to {}", output.display());
+                println!("💡 Upload the script to your server with a ManageSieve client to run it there");
+            }
+            None => print!("{}", export.script),
+        }
+        Ok(())
+    }
+
+    async fn handle_filters_import(&self, file: PathBuf, replace: bool, dry_run: bool) -> Result<()> {
+        let script = std::fs::read_to_string(&file)
+            .map_err(|e| anyhow!("Failed to read {}: {}", file.display(), e))?;
+        let import = crate::email::from_sieve(&script)?;
+
+        println!("📥 Importing filter rules from {}", file.display());
+        for rule in &import.rules {
+            println!("   {}", rule.name);
+        }
+        for warning in &import.warnings {
+            println!("⚠️  {}", warning);
+        }
+
+        let count = import.rules.len();
+        // Imported rules stay off until they have been tried on real mail
+        let imported = import.rules.into_iter().map(|mut rule| {
+            rule.enabled = false;
+            rule
+        });
+        let mut rules = crate::email::FilterRules::load().await?;
+        if replace {
+            rules.rules = imported.collect();
+        } else {
+            // Imported rules run after the existing ones
+            let last = rules.rules.iter().map(|rule| rule.priority).max().unwrap_or(0);
+            rules.rules.extend(imported.map(|mut rule| {
+                rule.priority += last;
+                rule
+            }));
+        }
+
+        if dry_run {
+            println!("🔍 DRY RUN - would import {} rules", count);
+            return Ok(());
+        }
+        rules.save().await?;
+        println!("✅ Imported {} rules", count);
+        println!("💡 They are disabled; test and enable them with Alt+e in the email view");
+        Ok(())
+    }
+
+    async fn handle_credentials(&self, args: CredentialsArgs, dry_run: bool) -> Result<()> {
+        match args.command {
+            CredentialsCommands::Status => self.handle_credentials_status(),
+            CredentialsCommands::Export { output } => self.handle_credentials_export(output, dry_run),
+            CredentialsCommands::Import { input } => self.handle_credentials_import(input, dry_run),
+            CredentialsCommands::Migrate { to } => self.handle_credentials_migrate(to, dry_run),
+            CredentialsCommands::Passphrase => self.handle_credentials_passphrase(dry_run),
+            CredentialsCommands::Encrypt => self.handle_credentials_encrypt(dry_run),
+        }
+    }
+
+    /// Ask for the vault passphrase when tokens are stored encrypted
+    ///
+    /// Returns false when it wasn't given.
+    pub fn unlock_credential_vault(&self) -> Result<bool> {
+        if !self.storage.needs_unlock() {
+            return Ok(true);
+        }
+
+        for _ in 0..3 {
+            let passphrase = read_passphrase("🔐 Credential passphrase: ")?;
+            match self.storage.unlock_vault(&passphrase) {
+                Ok(()) => return Ok(true),
+                Err(e) => println!("❌ {}", e),
+            }
+        }
+        Ok(false)
+    }
+
+    fn handle_credentials_status(&self) -> Result<()> {
+        println!("🔑 Credential Storage");
+        println!("=====================");
+
+        let status = self.storage.backend_status();
+        println!("In use: {} ({})", status.backend, status.backend.description());
+        if let Some(missing) = status.missing {
+            println!("⚠️  Recorded backend {} is not available", missing);
+        }
+
+        let has_keyring = CredentialBackend::System.is_available();
+        println!("\nBackends:");
+        for backend in CredentialBackend::ALL {
+            // The passphrase fallback is only offered without a keyring
+            if backend == CredentialBackend::Encrypted && has_keyring && status.backend != backend {
+                continue;
+            }
+            let mark = if backend.is_available() { "✅" } else { "❌" };
+            println!("   {} {:<9} {}", mark, backend.name(), backend.description());
+        }
+
+        if !has_keyring && status.backend != CredentialBackend::Encrypted {
+            println!("\n💡 Use 'comunicado credentials encrypt' to protect tokens with a passphrase");
+        }
+        println!("\n💡 Use 'comunicado credentials migrate <backend>' to switch backends");
+        println!("💡 Use 'comunicado credentials export <file>' to move credentials to another machine");
+        Ok(())
+    }
+
+    fn handle_credentials_export(&self, output: PathBuf, dry_run: bool) -> Result<()> {
+        println!("📤 Exporting credentials to {}", output.display());
+        if dry_run {
+            println!("🔍 DRY RUN - no file written");
+            return Ok(());
+        }
+
+        let passphrase = read_passphrase("Passphrase: ")?;
+        if passphrase.is_empty() {
+            return Err(anyhow!("A passphrase is required"));
+        }
+        if read_passphrase("Repeat passphrase: ")? != passphrase {
+            return Err(anyhow!("Passphrases do not match"));
+        }
+
+        let export = self.storage.export_credentials(&passphrase)?;
+        std::fs::write(&output, export)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&output, std::fs::Permissions::from_mode(0o600))?;
+        }
+
+        println!("✅ Credentials exported");
+        println!("💡 Import on the other system with 'comunicado credentials import {}'", output.display());
+        Ok(())
+    }
+
+    fn handle_credentials_import(&self, input: PathBuf, dry_run: bool) -> Result<()> {
+        let export = std::fs::read_to_string(&input)
+            .map_err(|e| anyhow!("Failed to read {}: {}", input.display(), e))?;
+        let status = self.storage.backend_status();
+        println!("📥 Importing credentials into the {}", status.backend.description());
+
+        let passphrase = read_passphrase("Passphrase: ")?;
+        if dry_run {
+            let accounts = crate::oauth2::credential_backend::decrypt_credentials(&export, &passphrase)?;
+            println!("🔍 DRY RUN - would import {} accounts", accounts.len());
+            return Ok(());
+        }
+
+        let count = self.storage.import_credentials(&export, &passphrase)?;
+        println!("✅ Imported credentials for {} accounts", count);
+        Ok(())
+    }
+
+    fn handle_credentials_migrate(&self, to: String, dry_run: bool) -> Result<()> {
+        let target = CredentialBackend::from_name(&to)
+            .ok_or_else(|| anyhow!("Unknown backend '{}' (expected system, keyutils or file)", to))?;
+        if target == CredentialBackend::Encrypted {
+            return Err(anyhow!("Use 'comunicado credentials encrypt' to encrypt tokens with a passphrase"));
+        }
+        let current = self.storage.backend_status().backend;
+        if target == current {
+            println!("✅ Already using the {}", target.description());
+            return Ok(());
+        }
+
+        println!("🔄 Migrating credentials from {} to {}", current, target);
+        if dry_run {
+            println!("🔍 DRY RUN - nothing copied");
+            return Ok(());
+        }
+
+        let mut storage = self.storage.clone();
+        let count = storage.migrate_backend(target)?;
+        println!("✅ Copied credentials for {} accounts; the {} is used from now on", count, target.description());
+        println!("   The copies in {} were left in place", current);
+        Ok(())
+    }
+
+    fn handle_credentials_passphrase(&self, dry_run: bool) -> Result<()> {
+        println!("🔒 Master Passphrase");
+        if self.storage.has_master_passphrase() {
+            let current = read_passphrase("Current passphrase: ")?;
+            if !self.storage.verify_master_passphrase(&current) {
+                return Err(anyhow!("Wrong passphrase"));
+            }
+        }
+
+        let passphrase = read_passphrase("New passphrase (empty to remove): ")?;
+        if !passphrase.is_empty() && read_passphrase("Repeat passphrase: ")? != passphrase {
+            return Err(anyhow!("Passphrases do not match"));
+        }
+        if dry_run {
+            println!("🔍 DRY RUN - passphrase not changed");
+            return Ok(());
+        }
+
+        self.storage.set_master_passphrase(&passphrase)?;
+        if passphrase.is_empty() {
+            println!("✅ Master passphrase removed; the TUI will no longer lock");
+        } else {
+            println!("✅ Master passphrase set");
+            println!("💡 Press Alt+L to lock the TUI; it also locks after the idle timeout in lock.toml");
+        }
+        Ok(())
+    }
+
+    fn handle_credentials_encrypt(&self, dry_run: bool) -> Result<()> {
+        println!("🔐 Encrypt Stored Tokens");
+        let current = self.storage.backend_status().backend;
+        if current == CredentialBackend::Encrypted {
+            println!("✅ Tokens are already encrypted");
+            return Ok(());
+        }
+        if CredentialBackend::System.is_available() {
+            return Err(anyhow!(
+                "The {} is available; keep tokens there with 'comunicado credentials migrate system'",
+                CredentialBackend::System.description()
+            ));
+        }
+
+        println!("   Tokens will be encrypted with a passphrase you enter each time Comunicado starts.");
+        let passphrase = read_passphrase("Passphrase: ")?;
+        if passphrase.is_empty() {
+            return Err(anyhow!("A passphrase is required"));
+        }
+        if read_passphrase("Repeat passphrase: ")? != passphrase {
+            return Err(anyhow!("Passphrases do not match"));
+        }
+        if dry_run {
+            println!("🔍 DRY RUN - nothing encrypted");
+            return Ok(());
+        }
+
+        let mut storage = self.storage.clone();
+        let count = storage.encrypt_with_passphrase(&passphrase)?;
+        println!("✅ Encrypted credentials for {} accounts", count);
+        if current != CredentialBackend::File {
+            println!("   The copies in {} were left in place", current);
+        }
+        println!("⚠️  A forgotten passphrase can't be recovered; keep an export with 'comunicado credentials export <file>'");
+        Ok(())
+    }
+
     async fn handle_oauth2(&self, args: OAuth2Args, _dry_run: bool) -> Result<()> {
         match args.command {
             OAuth2Commands::Status { verbose } => {
@@ -4333,17 +4908,34 @@ impl CliHandler {
 
     /// Re-authenticate an OAuth2 account
     async fn handle_oauth2_reauth(&self, account: String, _no_browser: bool, _verbose: bool) -> Result<()> {
-        println!("🔐 OAuth2 Re-authentication");
-        println!("============================");
-        println!("\n🔄 Re-authenticating account: {}", account);
-        
-        // This would trigger the full OAuth2 flow
-        println!("❌ Re-authentication flow not yet implemented in CLI");
-        println!("   Please use the TUI interface to re-authenticate:");
-        println!("   1. Run: comunicado");
-        println!("   2. Navigate to Account Management");
-        println!("   3. Select 'Re-authenticate' for the account");
-        
+        let accounts = self.storage.load_all_accounts()
+            .map_err(|e| anyhow!("Failed to load accounts: {}", e))?;
+        let Some(existing) = accounts.iter().find(|candidate| {
+            candidate.account_id == account
+                || candidate.email_address.eq_ignore_ascii_case(&account)
+                || candidate.display_name == account
+        }) else {
+            println!("❌ No account named '{}'", account);
+            println!("   Run 'comunicado oauth2 status' to list accounts");
+            return Ok(());
+        };
+
+        let mut wizard = crate::oauth2::SetupWizard::for_account(existing)
+            .map_err(|e| anyhow!("Failed to start setup wizard: {}", e))?;
+        match wizard.run().await {
+            Ok(Some(updated)) => {
+                println!("✅ Signed in again as {}", updated.email_address);
+                if updated.account_id != existing.account_id {
+                    println!(
+                        "   ⚠️  Stored as {} - remove {} if it is no longer needed",
+                        updated.account_id, existing.account_id
+                    );
+                }
+            }
+            Ok(None) => println!("Re-authentication cancelled"),
+            Err(e) => return Err(anyhow!("Re-authentication failed: {}", e)),
+        }
+
         Ok(())
     }
 
@@ -5044,12 +5636,53 @@ impl CliHandler {
         
         // Create contacts manager
         let contacts_manager = Arc::new(crate::contacts::ContactsManager::new(database, token_manager).await
-            .map_err(|e| anyhow!("Failed to create contacts manager: {}", e))?);
+            .map_err(|e| anyhow!("Failed to create contacts manager: {}", e))?
+            .with_carddav_accounts(crate::contacts::CardDavConfig::load_accounts().await));
         
         Ok(contacts_manager)
     }
 }
 
+/// Read a passphrase from the terminal without echoing it
+fn read_passphrase(prompt: &str) -> Result<String> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use crossterm::terminal;
+
+    print!("{}", prompt);
+    std::io::stdout().flush()?;
+
+    if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        return Ok(line.trim_end_matches(['\r', '\n']).to_string());
+    }
+
+    terminal::enable_raw_mode()?;
+    let mut passphrase = String::new();
+    let result = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => match key.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(anyhow!("Cancelled"))
+                }
+                KeyCode::Char(c) => passphrase.push(c),
+                KeyCode::Backspace => {
+                    passphrase.pop();
+                }
+                KeyCode::Esc => break Err(anyhow!("Cancelled")),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e.into()),
+        }
+    };
+    terminal::disable_raw_mode()?;
+    println!();
+
+    result.map(|_| passphrase)
+}
+
 /// Format a duration for human-readable display
 fn format_duration(duration: chrono::Duration) -> String {
     let total_seconds = duration.num_seconds().abs();
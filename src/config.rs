@@ -0,0 +1,65 @@
+//! Settings files in Comunicado's config directory
+//!
+//! Features keep their settings in separate TOML files under
+//! `~/.config/comunicado/`. A missing file is written out with the defaults
+//! the first time it is loaded, so every option is there to edit.
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+use tokio::fs;
+
+/// Path of a settings file in the config directory, or in the current
+/// directory when the platform has no config directory
+pub fn config_file_path(file_name: &str) -> PathBuf {
+    dirs::config_dir()
+        .map(|config_dir| config_dir.join("comunicado"))
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(file_name)
+}
+
+/// Load a settings file, creating it with the defaults when it doesn't exist
+pub async fn load_or_default<T>(file_name: &str) -> Result<T>
+where
+    T: Default + Serialize + DeserializeOwned,
+{
+    let config_path = config_file_path(file_name);
+
+    if config_path.exists() {
+        let content = fs::read_to_string(&config_path).await?;
+        Ok(toml::from_str(&content)?)
+    } else {
+        let config = T::default();
+        save(&config, file_name).await?;
+        Ok(config)
+    }
+}
+
+/// Write settings to their file, creating the config directory if needed
+pub async fn save<T: Serialize>(config: &T, file_name: &str) -> Result<()> {
+    let config_path = config_file_path(file_name);
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let content = toml::to_string_pretty(config)?;
+    fs::write(&config_path, content).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file_path() {
+        let path = config_file_path("spell_check.toml");
+        assert!(path.ends_with("spell_check.toml"));
+        if dirs::config_dir().is_some() {
+            assert!(path.parent().unwrap().ends_with("comunicado"));
+        }
+    }
+}
@@ -414,6 +414,11 @@ impl AdvancedContactSearch {
                         params.push(account_id.clone());
                         param_count += 1;
                     }
+                    ContactSource::CardDAV { account_id, .. } => {
+                        source_conditions.push(format!("(c.source_type = 'carddav' AND c.source_account_id = ?{})", param_count));
+                        params.push(account_id.clone());
+                        param_count += 1;
+                    }
                     ContactSource::Local => {
                         source_conditions.push("c.source_type = 'local'".to_string());
                     }
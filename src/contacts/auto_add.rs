@@ -0,0 +1,269 @@
+//! Adding sent-mail recipients to the address book
+//!
+//! When enabled in `auto_contacts.toml`, every address mail is sent to that
+//! isn't already a contact becomes a local contact, named from the
+//! recipient header. Addresses at domains on the never-add list are left
+//! out, so the address book fills up with the people actually written to
+//! and compose autocomplete can offer them.
+
+use crate::contacts::{Contact, ContactEmail, ContactSource, ContactsManager, ContactsResult};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Settings for adding sent-mail recipients as contacts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoAddConfig {
+    /// Add recipients of sent mail as contacts. Off by default
+    pub enabled: bool,
+    /// Domains whose addresses are never added; subdomains are included
+    pub never_add_domains: Vec<String>,
+}
+
+impl AutoAddConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "auto_contacts.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Whether `address` may be added, i.e. its domain isn't on the never-add list
+    pub fn allows(&self, address: &str) -> bool {
+        let Some((_, domain)) = address.rsplit_once('@') else {
+            return false;
+        };
+        let domain = domain.to_lowercase();
+        !self.never_add_domains.iter().any(|blocked| {
+            let blocked = blocked.trim().trim_start_matches('@').to_lowercase();
+            domain == blocked || domain.ends_with(&format!(".{}", blocked))
+        })
+    }
+}
+
+/// Adds the recipients of sent mail as local contacts
+pub struct SentRecipientCollector {
+    manager: Arc<ContactsManager>,
+    config: AutoAddConfig,
+}
+
+impl SentRecipientCollector {
+    pub fn new(manager: Arc<ContactsManager>, config: AutoAddConfig) -> Self {
+        Self { manager, config }
+    }
+
+    /// Add each recipient (`Name <address>` or a bare address) that isn't a
+    /// contact yet, returning how many were added
+    pub async fn collect(
+        &self,
+        from_address: &str,
+        recipients: &[String],
+    ) -> ContactsResult<usize> {
+        if !self.config.enabled {
+            return Ok(0);
+        }
+
+        let own_address = parse_recipient(from_address)
+            .map(|(_, address)| address)
+            .unwrap_or_else(|| from_address.to_string());
+        let mut added = 0;
+        for recipient in recipients {
+            let Some((name, address)) = parse_recipient(recipient) else {
+                continue;
+            };
+            if address.eq_ignore_ascii_case(&own_address) || !self.config.allows(&address) {
+                continue;
+            }
+            if self
+                .manager
+                .find_contact_by_email(&address)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+
+            let mut contact = Contact::new(
+                uuid::Uuid::new_v4().to_string(),
+                ContactSource::Local,
+                name.unwrap_or_else(|| address.clone()),
+            );
+            contact
+                .emails
+                .push(ContactEmail::primary(address, "other".to_string()));
+            self.manager.create_contact(contact).await?;
+            added += 1;
+        }
+
+        Ok(added)
+    }
+}
+
+/// Split a recipient header field on the commas between addresses, leaving
+/// commas in quoted names such as `"Doe, Jane" <jane@example.com>` alone
+pub fn split_recipients(field: &str) -> Vec<String> {
+    let mut recipients = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut in_angle = false;
+    let mut escaped = false;
+
+    for c in field.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_angle = true,
+            '>' if !in_quotes => in_angle = false,
+            ',' if !in_quotes && !in_angle => {
+                recipients.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    recipients.push(current);
+
+    recipients
+        .into_iter()
+        .map(|recipient| recipient.trim().to_string())
+        .filter(|recipient| !recipient.is_empty())
+        .collect()
+}
+
+/// Display name and address of `Name <address>`, or just the address
+fn parse_recipient(recipient: &str) -> Option<(Option<String>, String)> {
+    let recipient = recipient.trim();
+    let (name, address) = match (recipient.find('<'), recipient.rfind('>')) {
+        (Some(start), Some(end)) if start < end => {
+            let name = recipient[..start].trim();
+            let name = name
+                .strip_prefix('"')
+                .and_then(|name| name.strip_suffix('"'))
+                .map(|name| name.replace("\\\"", "\"").replace("\\\\", "\\"))
+                .unwrap_or_else(|| name.to_string());
+            (name, recipient[start + 1..end].trim())
+        }
+        _ => (String::new(), recipient),
+    };
+
+    if !address.contains('@') || address.contains(char::is_whitespace) {
+        return None;
+    }
+    let name = name.trim();
+    let name = (!name.is_empty() && name != address).then(|| name.to_string());
+    Some((name, address.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contacts::ContactsDatabase;
+    use crate::oauth2::TokenManager;
+
+    #[test]
+    fn test_split_and_parse_recipients() {
+        let recipients = split_recipients(
+            r#""Doe, Jane" <jane@example.com>, bob@example.com, , Ann Lee <ann@example.org>"#,
+        );
+        assert_eq!(recipients.len(), 3);
+
+        assert_eq!(
+            parse_recipient(&recipients[0]),
+            Some((
+                Some("Doe, Jane".to_string()),
+                "jane@example.com".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_recipient(&recipients[1]),
+            Some((None, "bob@example.com".to_string()))
+        );
+        assert_eq!(
+            parse_recipient(&recipients[2]),
+            Some((Some("Ann Lee".to_string()), "ann@example.org".to_string()))
+        );
+        assert_eq!(parse_recipient("Undisclosed recipients"), None);
+    }
+
+    #[test]
+    fn test_never_add_domains() {
+        let config = AutoAddConfig {
+            enabled: true,
+            never_add_domains: vec!["example.com".to_string(), "@noreply.io".to_string()],
+        };
+
+        assert!(!config.allows("jane@example.com"));
+        assert!(!config.allows("jane@Mail.Example.com"));
+        assert!(!config.allows("bot@noreply.io"));
+        assert!(config.allows("jane@notexample.com"));
+        assert!(config.allows("jane@example.org"));
+    }
+
+    #[tokio::test]
+    async fn test_collect_adds_new_recipients_once() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let database_url = format!(
+            "sqlite:{}?mode=rwc",
+            temp_dir.path().join("contacts.db").display()
+        );
+        let database = ContactsDatabase::new(&database_url).await.unwrap();
+        let manager = Arc::new(
+            ContactsManager::new(database, TokenManager::new())
+                .await
+                .unwrap(),
+        );
+        let collector = SentRecipientCollector::new(
+            manager.clone(),
+            AutoAddConfig {
+                enabled: true,
+                never_add_domains: vec!["lists.example.com".to_string()],
+            },
+        );
+
+        let recipients = vec![
+            "Jane Doe <jane@example.com>".to_string(),
+            "dev@lists.example.com".to_string(),
+            "me@example.com".to_string(),
+        ];
+        assert_eq!(
+            collector
+                .collect("Me <me@example.com>", &recipients)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            collector
+                .collect("me@example.com", &recipients)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let contact = manager
+            .find_contact_by_email("jane@example.com")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(contact.display_name, "Jane Doe");
+        assert_eq!(contact.source, ContactSource::Local);
+        assert!(manager
+            .find_contact_by_email("dev@lists.example.com")
+            .await
+            .unwrap()
+            .is_none());
+
+        let disabled = SentRecipientCollector::new(manager, AutoAddConfig::default());
+        let other = vec!["new@example.net".to_string()];
+        assert_eq!(disabled.collect("me@example.com", &other).await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,387 @@
+//! Contact avatars
+//!
+//! Decodes contact photos (vCard `PHOTO` values, provider photo URLs and,
+//! when the user opts in, Gravatar) into small true-colour blocks for the
+//! email viewer and contacts popup. Contacts without a photo get a coloured
+//! initials badge instead. Decoded avatars are cached per email address and
+//! loaded on background tasks so rendering never waits on the network.
+
+use anyhow::{anyhow, Result};
+use image::imageops::FilterType;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Avatar width in terminal cells
+pub const AVATAR_WIDTH: u16 = 8;
+/// Avatar height in terminal cells; each cell holds two pixel rows
+pub const AVATAR_HEIGHT: u16 = 4;
+
+/// Background colours for initials badges, picked by email hash
+const INITIALS_PALETTE: [Color; 8] = [
+    Color::Rgb(0xc0, 0x39, 0x2b),
+    Color::Rgb(0xd3, 0x54, 0x00),
+    Color::Rgb(0xb7, 0x95, 0x0b),
+    Color::Rgb(0x27, 0xae, 0x60),
+    Color::Rgb(0x16, 0xa0, 0x85),
+    Color::Rgb(0x29, 0x80, 0xb9),
+    Color::Rgb(0x8e, 0x44, 0xad),
+    Color::Rgb(0x7f, 0x8c, 0x8d),
+];
+
+/// Avatar configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AvatarConfig {
+    /// Draw contact photos when the terminal supports graphics
+    pub show_photos: bool,
+    /// Look up Gravatar for senders without a local photo. Off by default
+    /// because it sends a hash of each sender's address to gravatar.com.
+    pub use_gravatar: bool,
+}
+
+impl Default for AvatarConfig {
+    fn default() -> Self {
+        Self {
+            show_photos: true,
+            use_gravatar: false,
+        }
+    }
+}
+
+impl AvatarConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "avatars.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// A decoded photo scaled to avatar size
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvatarImage {
+    /// `(top, bottom)` pixel colours per cell, row by row
+    cells: Vec<Vec<(Color, Color)>>,
+}
+
+impl AvatarImage {
+    /// Decode image bytes (PNG, JPEG, GIF or WebP) and scale to avatar size
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let image = image::load_from_memory(bytes)?;
+        let width = AVATAR_WIDTH as u32;
+        let height = AVATAR_HEIGHT as u32 * 2;
+        let pixels = image.resize_to_fill(width, height, FilterType::Triangle).to_rgb8();
+
+        let pixel = |x: u32, y: u32| {
+            let [r, g, b] = pixels.get_pixel(x, y).0;
+            Color::Rgb(r, g, b)
+        };
+        let cells = (0..AVATAR_HEIGHT as u32)
+            .map(|row| {
+                (0..width)
+                    .map(|x| (pixel(x, row * 2), pixel(x, row * 2 + 1)))
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self { cells })
+    }
+
+    /// One line per cell row, drawn with upper half blocks
+    pub fn lines(&self) -> Vec<Line<'static>> {
+        self.cells
+            .iter()
+            .map(|row| {
+                Line::from(
+                    row.iter()
+                        .map(|(top, bottom)| Span::styled("▀", Style::default().fg(*top).bg(*bottom)))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Up to two initials from a display name, falling back to the email address
+pub fn initials(name: &str, email: &str) -> String {
+    let from_name: String = name
+        .split_whitespace()
+        .filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect();
+
+    if !from_name.is_empty() {
+        return from_name;
+    }
+
+    email
+        .chars()
+        .find(|c| c.is_alphanumeric())
+        .map(|c| c.to_uppercase().collect())
+        .unwrap_or_else(|| "?".to_string())
+}
+
+/// Badge colour for an email address; the same address always gets the same colour
+pub fn initials_color(email: &str) -> Color {
+    // FNV-1a, stable across runs unlike the std hasher
+    let hash = email
+        .trim()
+        .to_lowercase()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+    INITIALS_PALETTE[(hash % INITIALS_PALETTE.len() as u64) as usize]
+}
+
+/// Initials badge as a single span, for list rows
+pub fn initials_span(name: &str, email: &str) -> Span<'static> {
+    Span::styled(
+        format!(" {:<2} ", initials(name, email)),
+        Style::default()
+            .fg(Color::White)
+            .bg(initials_color(email))
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Initials badge filling the full avatar area
+pub fn initials_lines(name: &str, email: &str) -> Vec<Line<'static>> {
+    let style = Style::default()
+        .fg(Color::White)
+        .bg(initials_color(email))
+        .add_modifier(Modifier::BOLD);
+    let width = AVATAR_WIDTH as usize;
+    let blank = Line::from(Span::styled(" ".repeat(width), style));
+
+    (0..AVATAR_HEIGHT)
+        .map(|row| {
+            if row == (AVATAR_HEIGHT - 1) / 2 {
+                Line::from(Span::styled(
+                    format!("{:^width$}", initials(name, email), width = width),
+                    style,
+                ))
+            } else {
+                blank.clone()
+            }
+        })
+        .collect()
+}
+
+/// Gravatar URL for an address, returning 404 when no avatar is registered
+pub fn gravatar_url(email: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let hash = Sha256::digest(email.trim().to_lowercase().as_bytes());
+    format!("https://gravatar.com/avatar/{:x}?s=64&d=404", hash)
+}
+
+#[derive(Debug, Clone)]
+enum AvatarEntry {
+    Loading,
+    Photo(AvatarImage),
+    Missing,
+}
+
+/// Shared cache of decoded avatars keyed by lowercased email address
+#[derive(Clone)]
+pub struct AvatarCache {
+    config: AvatarConfig,
+    graphics: bool,
+    client: reqwest::Client,
+    entries: Arc<RwLock<HashMap<String, AvatarEntry>>>,
+}
+
+impl AvatarCache {
+    /// Create a cache; `graphics` is whether the terminal can draw images
+    pub fn new(config: AvatarConfig, graphics: bool) -> Self {
+        Self {
+            config,
+            graphics,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            entries: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Whether photos are drawn at all
+    pub fn photos_enabled(&self) -> bool {
+        self.config.show_photos && self.graphics
+    }
+
+    /// Decoded photo for an address, if one has finished loading
+    pub fn get(&self, email: &str) -> Option<AvatarImage> {
+        let entries = self.entries.read().ok()?;
+        match entries.get(&email.trim().to_lowercase()) {
+            Some(AvatarEntry::Photo(image)) => Some(image.clone()),
+            _ => None,
+        }
+    }
+
+    /// Avatar lines for a person: their photo when loaded, otherwise initials
+    pub fn lines(&self, name: &str, email: &str) -> Vec<Line<'static>> {
+        match self.get(email) {
+            Some(image) => image.lines(),
+            None => initials_lines(name, email),
+        }
+    }
+
+    /// Start loading the avatar for an address in the background
+    ///
+    /// `photo` is the contact's stored photo: a URL, a `data:` URI or bare
+    /// base64 from a vCard. Each address is only looked up once per session.
+    pub fn request(&self, email: &str, photo: Option<&str>) {
+        if !self.photos_enabled() || email.trim().is_empty() {
+            return;
+        }
+        let has_source = photo.is_some_and(|p| !p.trim().is_empty()) || self.config.use_gravatar;
+        if !has_source {
+            return;
+        }
+        // Loading needs a runtime; without one (e.g. in tests) keep initials
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        let key = email.trim().to_lowercase();
+        {
+            let Ok(mut entries) = self.entries.write() else {
+                return;
+            };
+            if entries.contains_key(&key) {
+                return;
+            }
+            entries.insert(key.clone(), AvatarEntry::Loading);
+        }
+
+        let cache = self.clone();
+        let photo = photo.map(str::to_string);
+        runtime.spawn(async move {
+            let entry = match cache.load(&key, photo.as_deref()).await {
+                Ok(image) => AvatarEntry::Photo(image),
+                Err(e) => {
+                    tracing::debug!("No avatar for {}: {}", key, e);
+                    AvatarEntry::Missing
+                }
+            };
+            if let Ok(mut entries) = cache.entries.write() {
+                entries.insert(key, entry);
+            }
+        });
+    }
+
+    /// Load from the contact photo, then Gravatar if enabled
+    async fn load(&self, email: &str, photo: Option<&str>) -> Result<AvatarImage> {
+        if let Some(photo) = photo.map(str::trim).filter(|p| !p.is_empty()) {
+            match self.photo_bytes(photo).await.and_then(|bytes| AvatarImage::decode(&bytes)) {
+                Ok(image) => return Ok(image),
+                Err(e) => tracing::debug!("Failed to load contact photo for {}: {}", email, e),
+            }
+        }
+
+        if self.config.use_gravatar {
+            let bytes = self.fetch(&gravatar_url(email)).await?;
+            return AvatarImage::decode(&bytes);
+        }
+
+        Err(anyhow!("no photo available"))
+    }
+
+    async fn photo_bytes(&self, photo: &str) -> Result<Vec<u8>> {
+        if photo.starts_with("http://") || photo.starts_with("https://") {
+            self.fetch(photo).await
+        } else {
+            decode_inline_photo(photo)
+        }
+    }
+
+    async fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+/// Decode a `data:` URI or bare base64 photo value
+fn decode_inline_photo(photo: &str) -> Result<Vec<u8>> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let data = match photo.strip_prefix("data:") {
+        Some(rest) => {
+            let (header, data) = rest
+                .split_once(',')
+                .ok_or_else(|| anyhow!("malformed data URI"))?;
+            if !header.ends_with(";base64") {
+                return Err(anyhow!("only base64 data URIs are supported"));
+            }
+            data
+        }
+        None => photo,
+    };
+
+    // vCard values may be folded across lines
+    let data: String = data.chars().filter(|c| !c.is_whitespace()).collect();
+    Ok(general_purpose::STANDARD.decode(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initials_and_color() {
+        assert_eq!(initials("Jane van Doe", "jane@example.com"), "JV");
+        assert_eq!(initials("  ", "_bob@example.com"), "B");
+        assert_eq!(initials("", ""), "?");
+        assert_eq!(
+            initials_color("Jane@Example.com "),
+            initials_color("jane@example.com")
+        );
+
+        let lines = initials_lines("Jane Doe", "jane@example.com");
+        assert_eq!(lines.len(), AVATAR_HEIGHT as usize);
+        assert!(lines.iter().all(|line| line.width() == AVATAR_WIDTH as usize));
+    }
+
+    #[test]
+    fn test_decode_inline_photo() {
+        use base64::{engine::general_purpose, Engine as _};
+
+        let mut png = Vec::new();
+        image::DynamicImage::new_rgb8(3, 3)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        let encoded = general_purpose::STANDARD.encode(&png);
+
+        let from_uri = decode_inline_photo(&format!("data:image/png;base64,{}", encoded)).unwrap();
+        let folded = format!("{}\n {}", &encoded[..10], &encoded[10..]);
+        assert_eq!(from_uri, png);
+        assert_eq!(decode_inline_photo(&folded).unwrap(), png);
+        assert!(decode_inline_photo("data:image/png,raw").is_err());
+
+        let avatar = AvatarImage::decode(&png).unwrap();
+        let lines = avatar.lines();
+        assert_eq!(lines.len(), AVATAR_HEIGHT as usize);
+        assert_eq!(lines[0].width(), AVATAR_WIDTH as usize);
+    }
+
+    #[test]
+    fn test_gravatar_url() {
+        assert_eq!(gravatar_url(" Jane@Example.com"), gravatar_url("jane@example.com"));
+        assert!(gravatar_url("jane@example.com").ends_with("?s=64&d=404"));
+    }
+}
@@ -0,0 +1,574 @@
+//! CardDAV address books
+//!
+//! Pulls contacts from CardDAV (RFC 6352) address books such as Nextcloud's
+//! and pushes local edits back. Each card is stored with its URL as the
+//! external ID along with its ETag, so an edit only applies if the card is
+//! unchanged on the server since the last sync. Otherwise it fails with
+//! `ContactsError::Conflict` and the next sync brings in the server's
+//! version. Edits rewrite only the properties Comunicado knows, keeping
+//! addresses, birthdays, photos and anything else in the card.
+
+use crate::calendar::event::unfold_icalendar_lines;
+use crate::contacts::providers::{ContactsPage, ContactsProvider};
+use crate::contacts::{Contact, ContactSource, ContactsError, ContactsResult};
+use crate::rfc_standards::RfcStandardsParser;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::{Client as HttpClient, Method, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::fs;
+use url::Url;
+
+/// Card properties Comunicado edits; updates replace these and keep the rest
+const EDITED_PROPERTIES: [&str; 7] = ["FN", "N", "EMAIL", "TEL", "ORG", "TITLE", "REV"];
+
+/// A CardDAV address book to sync
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardDavAccount {
+    /// Name the address book's contacts are filed under
+    pub account_id: String,
+    /// URL of the address book collection
+    pub url: String,
+    pub username: String,
+    pub password: String,
+    /// Only pull contacts; edits to them stay local
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl CardDavAccount {
+    /// Source of the contacts synced from this address book
+    pub fn source(&self) -> ContactSource {
+        ContactSource::CardDAV {
+            account_id: self.account_id.clone(),
+            url: self.url.clone(),
+        }
+    }
+
+    /// The collection URL, ending in `/` so card names join onto it
+    fn collection_url(&self) -> ContactsResult<Url> {
+        let url = if self.url.ends_with('/') {
+            self.url.clone()
+        } else {
+            format!("{}/", self.url)
+        };
+        Url::parse(&url).map_err(|e| {
+            ContactsError::InvalidData(format!("Invalid CardDAV URL {}: {}", self.url, e))
+        })
+    }
+}
+
+/// CardDAV address books to sync, from `carddav.toml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CardDavConfig {
+    pub accounts: Vec<CardDavAccount>,
+}
+
+impl CardDavConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "carddav.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Address books to sync, or none if the configuration can't be read
+    pub async fn load_accounts() -> Vec<CardDavAccount> {
+        match Self::load().await {
+            Ok(config) => config.accounts,
+            Err(e) => {
+                tracing::warn!("Failed to load CardDAV configuration: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Save configuration to file, readable only by the user as it holds passwords
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let config_path = crate::config::config_file_path(Self::CONFIG_FILE);
+            fs::set_permissions(&config_path, std::fs::Permissions::from_mode(0o600)).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A card from an address book listing
+#[derive(Debug, Clone, PartialEq)]
+struct CardDavCard {
+    url: String,
+    etag: Option<String>,
+    vcard: String,
+}
+
+/// CardDAV contacts provider
+pub struct CardDavContactsProvider {
+    http_client: HttpClient,
+    accounts: HashMap<String, CardDavAccount>,
+}
+
+impl CardDavContactsProvider {
+    pub fn new(accounts: Vec<CardDavAccount>) -> Self {
+        Self {
+            http_client: HttpClient::new(),
+            accounts: accounts
+                .into_iter()
+                .map(|account| (account.account_id.clone(), account))
+                .collect(),
+        }
+    }
+
+    fn account(&self, account_id: &str) -> ContactsResult<&CardDavAccount> {
+        self.accounts.get(account_id).ok_or_else(|| {
+            ContactsError::InvalidData(format!("No CardDAV address book named {}", account_id))
+        })
+    }
+
+    /// Send an authenticated request, turning error statuses into errors
+    async fn send(
+        &self,
+        account: &CardDavAccount,
+        method: Method,
+        url: &str,
+        body: Option<String>,
+        headers: &[(&str, &str)],
+    ) -> ContactsResult<Response> {
+        let mut request = self
+            .http_client
+            .request(method, url)
+            .basic_auth(&account.username, Some(&account.password));
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if status == 401 {
+            return Err(ContactsError::AuthError(format!(
+                "CardDAV server rejected the credentials for {}",
+                account.account_id
+            )));
+        }
+        if status == 412 {
+            return Err(ContactsError::Conflict(url.to_string()));
+        }
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(ContactsError::ApiError(format!(
+                "CardDAV server error {}: {}",
+                status.as_u16(),
+                error_text
+            )));
+        }
+
+        Ok(response)
+    }
+
+    fn response_etag(response: &Response) -> Option<String> {
+        response
+            .headers()
+            .get("etag")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    }
+
+    fn card_to_contact(account: &CardDavAccount, card: CardDavCard) -> ContactsResult<Contact> {
+        let mut contact = RfcStandardsParser::parse_vcard_to_contact(&card.vcard, account.source())
+            .map_err(|e| ContactsError::InvalidData(format!("{}: {}", card.url, e)))?
+            .into_iter()
+            .next()
+            .ok_or_else(|| ContactsError::InvalidData(format!("{}: no vCard", card.url)))?;
+        contact.external_id = card.url;
+        contact.etag = card.etag;
+        Ok(contact)
+    }
+}
+
+#[async_trait]
+impl ContactsProvider for CardDavContactsProvider {
+    async fn fetch_contacts(&self, account_id: &str) -> ContactsResult<Vec<Contact>> {
+        // One REPORT returns the whole address book
+        Ok(self.fetch_contacts_page(account_id, None).await?.contacts)
+    }
+
+    async fn fetch_contacts_page(
+        &self,
+        account_id: &str,
+        _page_token: Option<String>,
+    ) -> ContactsResult<ContactsPage> {
+        let account = self.account(account_id)?;
+        let collection_url = account.collection_url()?;
+
+        let report_body = r#"<?xml version="1.0" encoding="utf-8" ?>
+<C:addressbook-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:carddav">
+  <D:prop>
+    <D:getetag />
+    <C:address-data />
+  </D:prop>
+  <C:filter />
+</C:addressbook-query>"#;
+
+        let method = Method::from_bytes(b"REPORT")
+            .map_err(|e| ContactsError::ApiError(format!("Invalid HTTP method: {}", e)))?;
+        let response = self
+            .send(
+                account,
+                method,
+                collection_url.as_str(),
+                Some(report_body.to_string()),
+                &[
+                    ("Depth", "1"),
+                    ("Content-Type", "application/xml; charset=utf-8"),
+                ],
+            )
+            .await?;
+        let cards = parse_multistatus(&response.text().await?, &collection_url);
+
+        let mut contacts = Vec::new();
+        for card in cards {
+            match Self::card_to_contact(account, card) {
+                Ok(contact) => contacts.push(contact),
+                Err(e) => tracing::warn!("Skipping CardDAV contact: {}", e),
+            }
+        }
+
+        let total_items = Some(contacts.len());
+        Ok(ContactsPage {
+            contacts,
+            next_page_token: None,
+            total_items,
+        })
+    }
+
+    async fn create_contact(&self, account_id: &str, contact: &Contact) -> ContactsResult<Contact> {
+        let account = self.account(account_id)?;
+        let uid = uuid::Uuid::new_v4().to_string();
+        let card_url = account
+            .collection_url()?
+            .join(&format!("{}.vcf", uid))
+            .map_err(|e| ContactsError::InvalidData(e.to_string()))?;
+
+        let mut card = contact.clone();
+        card.external_id = uid;
+        let vcard = RfcStandardsParser::contact_to_vcard(&card)
+            .map_err(|e| ContactsError::InvalidData(e.to_string()))?;
+
+        let response = self
+            .send(
+                account,
+                Method::PUT,
+                card_url.as_str(),
+                Some(vcard),
+                &[
+                    ("Content-Type", "text/vcard; charset=utf-8"),
+                    ("If-None-Match", "*"),
+                ],
+            )
+            .await?;
+
+        let mut created = contact.clone();
+        created.external_id = card_url.to_string();
+        created.source = account.source();
+        created.etag = Self::response_etag(&response);
+        Ok(created)
+    }
+
+    async fn update_contact(&self, account_id: &str, contact: &Contact) -> ContactsResult<Contact> {
+        let account = self.account(account_id)?;
+
+        // Start from the server's card so properties Comunicado doesn't show survive
+        let existing = self
+            .send(account, Method::GET, &contact.external_id, None, &[])
+            .await?;
+        let etag = contact
+            .etag
+            .clone()
+            .or_else(|| Self::response_etag(&existing));
+        let vcard = merge_vcard(
+            &existing.text().await?,
+            &RfcStandardsParser::contact_to_vcard(contact)
+                .map_err(|e| ContactsError::InvalidData(e.to_string()))?,
+        );
+
+        let mut headers = vec![("Content-Type", "text/vcard; charset=utf-8")];
+        if let Some(ref etag) = etag {
+            headers.push(("If-Match", etag));
+        }
+        let response = self
+            .send(
+                account,
+                Method::PUT,
+                &contact.external_id,
+                Some(vcard),
+                &headers,
+            )
+            .await?;
+
+        let mut updated = contact.clone();
+        updated.etag = Self::response_etag(&response);
+        Ok(updated)
+    }
+
+    async fn delete_contact(&self, account_id: &str, contact_id: &str) -> ContactsResult<()> {
+        let account = self.account(account_id)?;
+        match self
+            .send(account, Method::DELETE, contact_id, None, &[])
+            .await
+        {
+            // Already gone counts as deleted
+            Err(ContactsError::ApiError(message))
+                if message.starts_with("CardDAV server error 404") =>
+            {
+                Ok(())
+            }
+            result => result.map(|_| ()),
+        }
+    }
+
+    fn provider_name(&self) -> &str {
+        "CardDAV"
+    }
+}
+
+/// `existing` card with the properties Comunicado edits taken from `updated`
+fn merge_vcard(existing: &str, updated: &str) -> String {
+    // Property name without its group (`item1.EMAIL`) or parameters
+    let property = |line: &str| {
+        let name = line.split([';', ':']).next().unwrap_or_default();
+        name.rsplit('.').next().unwrap_or(name).to_ascii_uppercase()
+    };
+    let is_edited = |line: &str| EDITED_PROPERTIES.contains(&property(line).as_str());
+
+    let replacements: Vec<String> = unfold_icalendar_lines(updated)
+        .into_iter()
+        .filter(|line| is_edited(line))
+        .collect();
+
+    let mut lines = Vec::new();
+    let mut replaced = false;
+    for line in unfold_icalendar_lines(existing) {
+        if line.eq_ignore_ascii_case("END:VCARD") && !replaced {
+            lines.extend(replacements.iter().cloned());
+            replaced = true;
+        }
+        if !is_edited(&line) && !line.is_empty() {
+            lines.push(line);
+        }
+    }
+
+    let mut vcard = lines.join("\r\n");
+    vcard.push_str("\r\n");
+    vcard
+}
+
+/// Cards in an `addressbook-query` multistatus response
+fn parse_multistatus(xml: &str, base_url: &Url) -> Vec<CardDavCard> {
+    xml_elements(xml, "response")
+        .into_iter()
+        .filter_map(|response| {
+            let href = xml_text(xml_elements(response, "href").first()?);
+            let url = base_url.join(href.trim()).ok()?;
+            let vcard = xml_text(xml_elements(response, "address-data").first()?);
+            if vcard.trim().is_empty() {
+                return None;
+            }
+            let etag = xml_elements(response, "getetag")
+                .first()
+                .map(|etag| xml_text(etag).trim().to_string())
+                .filter(|etag| !etag.is_empty());
+            Some(CardDavCard {
+                url: url.to_string(),
+                etag,
+                vcard,
+            })
+        })
+        .collect()
+}
+
+/// Contents of the elements called `name` in `xml`, whatever their namespace prefix
+fn xml_elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut elements = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        let after = &rest[start + 1..];
+        // Skip character data, which may hold anything
+        if let Some(cdata) = after.strip_prefix("![CDATA[") {
+            match cdata.find("]]>") {
+                Some(end) => {
+                    rest = &cdata[end + 3..];
+                    continue;
+                }
+                None => break,
+            }
+        }
+        let Some(tag_end) = after.find('>') else {
+            break;
+        };
+        let tag = &after[..tag_end];
+        let body = &after[tag_end + 1..];
+        rest = body;
+
+        let qualified = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        let local_name = qualified.rsplit(':').next().unwrap_or(qualified);
+        if qualified.is_empty() || local_name != name {
+            continue;
+        }
+        if tag.ends_with('/') {
+            elements.push("");
+            continue;
+        }
+        let close = format!("</{}>", qualified);
+        if let Some(end) = body.find(&close) {
+            elements.push(&body[..end]);
+            rest = &body[end + close.len()..];
+        }
+    }
+    elements
+}
+
+/// Text content of an element, from CDATA or with entities decoded
+fn xml_text(content: &str) -> String {
+    if let Some(cdata) = content.trim().strip_prefix("<![CDATA[") {
+        return cdata.strip_suffix("]]>").unwrap_or(cdata).to_string();
+    }
+    content
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&#xD;", "\r")
+        .replace("&#10;", "\n")
+        .replace("&#xA;", "\n")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multistatus() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:" xmlns:card="urn:ietf:params:xml:ns:carddav">
+  <d:response>
+    <d:href>/remote.php/dav/addressbooks/users/me/contacts/alice.vcf</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:getetag>&quot;a1&quot;</d:getetag>
+        <card:address-data>BEGIN:VCARD&#13;
+VERSION:3.0&#13;
+UID:alice&#13;
+FN:Alice &amp; Co&#13;
+N:Smith;Alice;;;&#13;
+EMAIL;TYPE=work:alice@example.com&#13;
+END:VCARD&#13;
+</card:address-data>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/remote.php/dav/addressbooks/users/me/contacts/</d:href>
+    <d:propstat>
+      <d:prop><d:getetag/></d:prop>
+      <d:status>HTTP/1.1 404 Not Found</d:status>
+    </d:propstat>
+  </d:response>
+  <response xmlns="DAV:">
+    <href>bob.vcf</href>
+    <propstat>
+      <prop>
+        <getetag>"b2"</getetag>
+        <address-data xmlns="urn:ietf:params:xml:ns:carddav"><![CDATA[BEGIN:VCARD
+VERSION:4.0
+FN:Bob <B>
+END:VCARD
+]]></address-data>
+      </prop>
+    </propstat>
+  </response>
+</d:multistatus>"#;
+
+        let base =
+            Url::parse("https://cloud.example.com/remote.php/dav/addressbooks/users/me/contacts/")
+                .unwrap();
+        let cards = parse_multistatus(xml, &base);
+        assert_eq!(cards.len(), 2);
+        assert_eq!(
+            cards[0].url,
+            "https://cloud.example.com/remote.php/dav/addressbooks/users/me/contacts/alice.vcf"
+        );
+        assert_eq!(cards[0].etag.as_deref(), Some("\"a1\""));
+        assert!(cards[0].vcard.contains("FN:Alice & Co\r\n"));
+        assert_eq!(
+            cards[1].url,
+            "https://cloud.example.com/remote.php/dav/addressbooks/users/me/contacts/bob.vcf"
+        );
+        assert!(cards[1].vcard.contains("FN:Bob <B>"));
+
+        let account = CardDavAccount {
+            account_id: "nextcloud".to_string(),
+            url: base.to_string(),
+            username: "me".to_string(),
+            password: "secret".to_string(),
+            read_only: false,
+        };
+        let contact = CardDavContactsProvider::card_to_contact(&account, cards[0].clone()).unwrap();
+        assert_eq!(contact.external_id, cards[0].url);
+        assert_eq!(contact.etag.as_deref(), Some("\"a1\""));
+        assert_eq!(contact.source, account.source());
+        assert_eq!(contact.first_name.as_deref(), Some("Alice"));
+        assert_eq!(contact.emails[0].address, "alice@example.com");
+    }
+
+    #[test]
+    fn test_merge_vcard_keeps_unknown_properties() {
+        let existing = "BEGIN:VCARD\r\n\
+VERSION:3.0\r\n\
+UID:alice\r\n\
+FN:Alice\r\n\
+item1.EMAIL;TYPE=INTERNET:old@example.com\r\n\
+item1.X-ABLabel:Old\r\n\
+ADR;TYPE=home:;;1 Main St;Oslo;;0150;Norway\r\n\
+BDAY:1990-01-02\r\n\
+PHOTO;ENCODING=b;TYPE=JPEG:AAAA\r\n \
+BBBB\r\n\
+END:VCARD\r\n";
+        let updated = "BEGIN:VCARD\r\n\
+VERSION:4.0\r\n\
+UID:https://cloud.example.com/alice.vcf\r\n\
+FN:Alice Smith\r\n\
+EMAIL;TYPE=work:alice@example.com\r\n\
+REV:2025-03-01T10:00:00+00:00\r\n\
+END:VCARD";
+
+        let merged = merge_vcard(existing, updated);
+        assert!(merged.contains("VERSION:3.0\r\nUID:alice\r\n"));
+        assert!(merged.contains("ADR;TYPE=home:;;1 Main St;Oslo;;0150;Norway\r\n"));
+        assert!(merged.contains("BDAY:1990-01-02\r\n"));
+        assert!(merged.contains("PHOTO;ENCODING=b;TYPE=JPEG:AAAABBBB\r\n"));
+        assert!(merged.contains("FN:Alice Smith\r\n"));
+        assert!(merged.contains("EMAIL;TYPE=work:alice@example.com\r\n"));
+        assert!(!merged.contains("old@example.com"));
+        assert!(!merged.contains("VERSION:4.0"));
+        assert!(merged.ends_with("REV:2025-03-01T10:00:00+00:00\r\nEND:VCARD\r\n"));
+    }
+}
@@ -219,6 +219,8 @@ impl ContactsDatabase {
         .execute(&self.pool)
         .await
         .map_err(|e| ContactsError::DatabaseError(e.to_string()))?;
+        self.add_column_if_missing("contacts", "source_url", "TEXT")
+            .await?;
 
         // Contact emails table
         sqlx::query(
@@ -314,12 +316,44 @@ impl ContactsDatabase {
         Ok(())
     }
 
+    /// Add a column to an existing table that was created without it
+    async fn add_column_if_missing(
+        &self,
+        table: &str,
+        column: &str,
+        definition: &str,
+    ) -> ContactsResult<()> {
+        let columns: Vec<String> = sqlx::query(&format!("PRAGMA table_info({})", table))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ContactsError::DatabaseError(e.to_string()))?
+            .iter()
+            .map(|row| row.get("name"))
+            .collect();
+
+        if columns.iter().any(|name| name == column) {
+            return Ok(());
+        }
+
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ContactsError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
     /// Store or update a contact
     pub async fn store_contact(&self, contact: &mut Contact) -> ContactsResult<()> {
-        let (source_type, source_account_id) = match &contact.source {
-            ContactSource::Google { account_id } => ("google", Some(account_id.as_str())),
-            ContactSource::Outlook { account_id } => ("outlook", Some(account_id.as_str())),
-            ContactSource::Local => ("local", None),
+        let (source_type, source_account_id, source_url) = match &contact.source {
+            ContactSource::Google { account_id } => ("google", Some(account_id.as_str()), None),
+            ContactSource::Outlook { account_id } => ("outlook", Some(account_id.as_str()), None),
+            ContactSource::CardDAV { account_id, url } => {
+                ("carddav", Some(account_id.as_str()), Some(url.as_str()))
+            }
+            ContactSource::Local => ("local", None, None),
         };
 
         // Insert or update contact
@@ -328,8 +362,8 @@ impl ContactsDatabase {
             INSERT INTO contacts (
                 external_id, source_type, source_account_id, display_name,
                 first_name, last_name, company, job_title, notes, photo_url,
-                created_at, updated_at, synced_at, etag
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                created_at, updated_at, synced_at, etag, source_url
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(external_id, source_type, source_account_id) DO UPDATE SET
                 display_name = excluded.display_name,
                 first_name = excluded.first_name,
@@ -340,7 +374,8 @@ impl ContactsDatabase {
                 photo_url = excluded.photo_url,
                 updated_at = excluded.updated_at,
                 synced_at = excluded.synced_at,
-                etag = excluded.etag
+                etag = excluded.etag,
+                source_url = excluded.source_url
             "#,
         )
         .bind(&contact.external_id)
@@ -357,6 +392,7 @@ impl ContactsDatabase {
         .bind(&contact.updated_at.to_rfc3339())
         .bind(contact.synced_at.map(|dt| dt.to_rfc3339()))
         .bind(&contact.etag)
+        .bind(source_url)
         .execute(&self.pool)
         .await
         .map_err(|e| ContactsError::DatabaseError(e.to_string()))?;
@@ -457,6 +493,10 @@ impl ContactsDatabase {
                     query.push_str(" AND c.source_type = 'outlook' AND c.source_account_id = ?");
                     params.push(account_id.clone());
                 }
+                ContactSource::CardDAV { account_id, .. } => {
+                    query.push_str(" AND c.source_type = 'carddav' AND c.source_account_id = ?");
+                    params.push(account_id.clone());
+                }
                 ContactSource::Local => {
                     query.push_str(" AND c.source_type = 'local'");
                 }
@@ -531,7 +571,7 @@ impl ContactsDatabase {
                     FROM contacts c
                     LEFT JOIN contact_emails e ON c.id = e.contact_id
                     LEFT JOIN contact_phones p ON c.id = p.contact_id
-                    WHERE c.id IN (SELECT contact_id FROM contact_emails WHERE address = ?)
+                    WHERE c.id IN (SELECT contact_id FROM contact_emails WHERE address = ? COLLATE NOCASE)
                     GROUP BY c.id
                     LIMIT 1";
 
@@ -577,6 +617,34 @@ impl ContactsDatabase {
         Ok(contacts)
     }
 
+    /// Find contacts with an address at `domain`
+    pub async fn find_contacts_by_email_domain(&self, domain: &str, limit: usize) -> ContactsResult<Vec<Contact>> {
+        let query = "SELECT c.*, 
+                           GROUP_CONCAT(DISTINCT e.address || '|' || e.label || '|' || e.is_primary) as emails,
+                           GROUP_CONCAT(DISTINCT p.number || '|' || p.label || '|' || p.is_primary) as phones
+                    FROM contacts c
+                    LEFT JOIN contact_emails e ON c.id = e.contact_id
+                    LEFT JOIN contact_phones p ON c.id = p.contact_id
+                    WHERE c.id IN (SELECT contact_id FROM contact_emails WHERE address LIKE ?)
+                    GROUP BY c.id
+                    ORDER BY c.display_name
+                    LIMIT ?";
+
+        let rows = sqlx::query(query)
+            .bind(format!("%@{}", domain))
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ContactsError::DatabaseError(e.to_string()))?;
+
+        let mut contacts = Vec::new();
+        for row in rows {
+            contacts.push(self.contact_from_row(&row)?);
+        }
+
+        Ok(contacts)
+    }
+
     /// Get address book statistics
     pub async fn get_stats(&self) -> ContactsResult<AddressBookStats> {
         let total_contacts: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM contacts")
@@ -596,6 +664,12 @@ impl ContactsDatabase {
                 .await
                 .map_err(|e| ContactsError::DatabaseError(e.to_string()))?;
 
+        let carddav_contacts: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM contacts WHERE source_type = 'carddav'")
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| ContactsError::DatabaseError(e.to_string()))?;
+
         let local_contacts: i64 =
             sqlx::query_scalar("SELECT COUNT(*) FROM contacts WHERE source_type = 'local'")
                 .fetch_one(&self.pool)
@@ -636,6 +710,7 @@ impl ContactsDatabase {
             total_contacts: total_contacts as usize,
             google_contacts: google_contacts as usize,
             outlook_contacts: outlook_contacts as usize,
+            carddav_contacts: carddav_contacts as usize,
             local_contacts: local_contacts as usize,
             contacts_with_email: contacts_with_email as usize,
             contacts_with_phone: contacts_with_phone as usize,
@@ -666,6 +741,13 @@ impl ContactsDatabase {
             "outlook" => ContactSource::Outlook {
                 account_id: source_account_id.unwrap_or_default(),
             },
+            "carddav" => ContactSource::CardDAV {
+                account_id: source_account_id.unwrap_or_default(),
+                url: row
+                    .try_get::<Option<String>, _>("source_url")
+                    .map_err(|e| ContactsError::DatabaseError(e.to_string()))?
+                    .unwrap_or_default(),
+            },
             "local" => ContactSource::Local,
             _ => ContactSource::Local,
         };
@@ -1,9 +1,9 @@
 #[cfg(test)]
 use crate::contacts::ContactEmail;
 use crate::contacts::{
-    AddressBookStats, Contact, ContactSearchCriteria, ContactSource, ContactsDatabase,
-    ContactsError, ContactsProvider, ContactsResult, GoogleContactsProvider,
-    OutlookContactsProvider,
+    AddressBookStats, CardDavAccount, CardDavContactsProvider, Contact, ContactSearchCriteria,
+    ContactSource, ContactsDatabase, ContactsError, ContactsProvider, ContactsResult,
+    GoogleContactsProvider, OutlookContactsProvider,
 };
 use crate::oauth2::TokenManager;
 use std::collections::HashMap;
@@ -15,6 +15,7 @@ pub struct ContactsManager {
     database: ContactsDatabase,
     providers: HashMap<String, Box<dyn ContactsProvider>>,
     token_manager: TokenManager,
+    carddav_accounts: Vec<CardDavAccount>,
     sync_enabled: Arc<RwLock<bool>>,
 }
 
@@ -38,10 +39,21 @@ impl ContactsManager {
             database,
             providers,
             token_manager,
+            carddav_accounts: Vec::new(),
             sync_enabled: Arc::new(RwLock::new(true)),
         })
     }
 
+    /// Sync these CardDAV address books as well as the OAuth accounts
+    pub fn with_carddav_accounts(mut self, accounts: Vec<CardDavAccount>) -> Self {
+        self.providers.insert(
+            "carddav".to_string(),
+            Box::new(CardDavContactsProvider::new(accounts.clone())),
+        );
+        self.carddav_accounts = accounts;
+        self
+    }
+
     /// Search contacts across all sources
     pub async fn search_contacts(
         &self,
@@ -65,6 +77,11 @@ impl ContactsManager {
         self.database.find_contacts_by_email_prefix(email_prefix, limit).await
     }
 
+    /// Find contacts with an address at `domain`
+    pub async fn find_contacts_by_email_domain(&self, domain: &str, limit: usize) -> ContactsResult<Vec<Contact>> {
+        self.database.find_contacts_by_email_domain(domain, limit).await
+    }
+
     /// Create a new contact
     pub async fn create_contact(&self, mut contact: Contact) -> ContactsResult<Contact> {
         // Store locally first
@@ -181,6 +198,27 @@ impl ContactsManager {
             }
         }
 
+        for account in &self.carddav_accounts {
+            match self
+                .sync_account_contacts(&account.account_id, "carddav")
+                .await
+            {
+                Ok(account_summary) => {
+                    summary.merge(account_summary);
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to sync CardDAV contacts for {}: {}",
+                        account.account_id,
+                        e
+                    );
+                    summary
+                        .errors
+                        .push(format!("Address book {}: {}", account.account_id, e));
+                }
+            }
+        }
+
         summary.total_duration = summary.start_time.elapsed();
         Ok(summary)
     }
@@ -200,6 +238,10 @@ impl ContactsManager {
         // Fetch contacts from remote provider
         let remote_contacts = provider.fetch_contacts(account_id).await?;
         summary.fetched_count = remote_contacts.len();
+        let remote_ids: Vec<String> = remote_contacts
+            .iter()
+            .map(|contact| contact.external_id.clone())
+            .collect();
 
         // Process each contact
         for remote_contact in remote_contacts {
@@ -219,6 +261,26 @@ impl ContactsManager {
             }
         }
 
+        // A CardDAV listing is the whole address book, so synced cards missing
+        // from it were deleted on the server
+        if provider_type == "carddav" {
+            let criteria = ContactSearchCriteria::new()
+                .with_source(self.account_source(account_id, provider_type)?);
+            for contact in self.search_contacts(&criteria).await? {
+                if contact.synced_at.is_none() || remote_ids.contains(&contact.external_id) {
+                    continue;
+                }
+                if let Some(id) = contact.id {
+                    match self.database.delete_contact(id).await {
+                        Ok(()) => summary.deleted_count += 1,
+                        Err(e) => summary
+                            .errors
+                            .push(format!("Contact {}: {}", contact.external_id, e)),
+                    }
+                }
+            }
+        }
+
         summary.total_duration = summary.start_time.elapsed();
         Ok(summary)
     }
@@ -259,20 +321,7 @@ impl ContactsManager {
         account_id: &str,
         provider_type: &str,
     ) -> ContactsResult<Vec<Contact>> {
-        let source = match provider_type {
-            "google" => ContactSource::Google {
-                account_id: account_id.to_string(),
-            },
-            "outlook" => ContactSource::Outlook {
-                account_id: account_id.to_string(),
-            },
-            _ => {
-                return Err(ContactsError::InvalidData(format!(
-                    "Unknown provider: {}",
-                    provider_type
-                )))
-            }
-        };
+        let source = self.account_source(account_id, provider_type)?;
 
         let criteria = ContactSearchCriteria::new().with_source(source);
         self.search_contacts(&criteria).await
@@ -294,20 +343,7 @@ impl ContactsManager {
         provider_type: &str,
     ) -> ContactsResult<SyncResult> {
         // Check if contact already exists locally
-        let source = match provider_type {
-            "google" => ContactSource::Google {
-                account_id: account_id.to_string(),
-            },
-            "outlook" => ContactSource::Outlook {
-                account_id: account_id.to_string(),
-            },
-            _ => {
-                return Err(ContactsError::InvalidData(format!(
-                    "Unknown provider: {}",
-                    provider_type
-                )))
-            }
-        };
+        let source = self.account_source(account_id, provider_type)?;
 
         // Search for existing contact by external_id and source
         let criteria = ContactSearchCriteria::new().with_source(source);
@@ -362,9 +398,51 @@ impl ContactsManager {
         match source {
             ContactSource::Google { .. } => self.providers.get("google"),
             ContactSource::Outlook { .. } => self.providers.get("outlook"),
+            ContactSource::CardDAV { account_id, .. } => {
+                let read_only = self
+                    .carddav_accounts
+                    .iter()
+                    .any(|account| &account.account_id == account_id && account.read_only);
+                if read_only {
+                    None
+                } else {
+                    self.providers.get("carddav")
+                }
+            }
             ContactSource::Local => None,
         }
     }
+
+    /// Source of the contacts synced from an account
+    fn account_source(
+        &self,
+        account_id: &str,
+        provider_type: &str,
+    ) -> ContactsResult<ContactSource> {
+        match provider_type {
+            "google" => Ok(ContactSource::Google {
+                account_id: account_id.to_string(),
+            }),
+            "outlook" => Ok(ContactSource::Outlook {
+                account_id: account_id.to_string(),
+            }),
+            "carddav" => self
+                .carddav_accounts
+                .iter()
+                .find(|account| account.account_id == account_id)
+                .map(CardDavAccount::source)
+                .ok_or_else(|| {
+                    ContactsError::InvalidData(format!(
+                        "No CardDAV address book named {}",
+                        account_id
+                    ))
+                }),
+            _ => Err(ContactsError::InvalidData(format!(
+                "Unknown provider: {}",
+                provider_type
+            ))),
+        }
+    }
 }
 
 /// Result of syncing a single contact
@@ -385,6 +463,8 @@ pub struct SyncSummary {
     pub created_count: usize,
     pub updated_count: usize,
     pub skipped_count: usize,
+    /// Contacts removed because they were deleted on the server
+    pub deleted_count: usize,
     pub errors: Vec<String>,
 }
 
@@ -398,6 +478,7 @@ impl SyncSummary {
             created_count: 0,
             updated_count: 0,
             skipped_count: 0,
+            deleted_count: 0,
             errors: Vec::new(),
         }
     }
@@ -407,6 +488,7 @@ impl SyncSummary {
         self.created_count += other.created_count;
         self.updated_count += other.updated_count;
         self.skipped_count += other.skipped_count;
+        self.deleted_count += other.deleted_count;
         self.errors.extend(other.errors);
         self.total_duration += other.total_duration;
     }
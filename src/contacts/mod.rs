@@ -1,6 +1,9 @@
 pub mod advanced_search;
 pub mod advanced_search_ui;
+pub mod auto_add;
 pub mod autocomplete;
+pub mod avatar;
+pub mod carddav;
 pub mod database;
 pub mod manager;
 pub mod popup;
@@ -14,12 +17,15 @@ pub use advanced_search::{
     SearchResult, SortDirection, SortField,
 };
 pub use advanced_search_ui::{AdvancedSearchUI, SearchAction, SearchField, SearchTab};
+pub use auto_add::{split_recipients, AutoAddConfig, SentRecipientCollector};
 pub use autocomplete::{ContactAutocomplete, ContactSuggestion};
+pub use avatar::{AvatarCache, AvatarConfig};
+pub use carddav::{CardDavAccount, CardDavConfig, CardDavContactsProvider};
 pub use database::{Contact, ContactEmail, ContactGroup, ContactPhone, ContactsDatabase};
 pub use manager::ContactsManager;
 pub use popup::{ContactPopup, ContactPopupAction, ContactPopupMode};
 pub use providers::{ContactsProvider, GoogleContactsProvider, OutlookContactsProvider};
-pub use sender_recognition::{SenderInfo, SenderRecognitionService};
+pub use sender_recognition::{SenderInfo, SenderRecognition, SenderRecognitionService};
 pub use sync::{ContactsSyncEngine, SyncProgress as ContactsSyncProgress};
 
 use serde::{Deserialize, Serialize};
@@ -43,6 +49,11 @@ pub enum ContactsError {
     #[error("Invalid contact data: {0}")]
     InvalidData(String),
 
+    /// The contact's ETag no longer matches: it was changed on the server
+    /// since it was last synced
+    #[error("{0} was changed on the server")]
+    Conflict(String),
+
     #[error("Network error: {0}")]
     NetworkError(#[from] reqwest::Error),
 
@@ -57,6 +68,8 @@ pub type ContactsResult<T> = Result<T, ContactsError>;
 pub enum ContactSource {
     Google { account_id: String },
     Outlook { account_id: String },
+    /// A CardDAV address book, by its collection URL
+    CardDAV { account_id: String, url: String },
     Local,
 }
 
@@ -65,6 +78,7 @@ impl ContactSource {
         match self {
             ContactSource::Google { account_id } => Some(account_id),
             ContactSource::Outlook { account_id } => Some(account_id),
+            ContactSource::CardDAV { account_id, .. } => Some(account_id),
             ContactSource::Local => None,
         }
     }
@@ -73,6 +87,7 @@ impl ContactSource {
         match self {
             ContactSource::Google { .. } => "Google",
             ContactSource::Outlook { .. } => "Outlook",
+            ContactSource::CardDAV { .. } => "CardDAV",
             ContactSource::Local => "Local",
         }
     }
@@ -136,6 +151,8 @@ pub struct AddressBookStats {
     pub total_contacts: usize,
     pub google_contacts: usize,
     pub outlook_contacts: usize,
+    #[serde(default)]
+    pub carddav_contacts: usize,
     pub local_contacts: usize,
     pub contacts_with_email: usize,
     pub contacts_with_phone: usize,
@@ -149,6 +166,7 @@ impl Default for AddressBookStats {
             total_contacts: 0,
             google_contacts: 0,
             outlook_contacts: 0,
+            carddav_contacts: 0,
             local_contacts: 0,
             contacts_with_email: 0,
             contacts_with_phone: 0,
@@ -1,5 +1,6 @@
 //! Contact popup modal for quick access from anywhere in the app
 
+use crate::contacts::avatar::{self, AvatarCache, AVATAR_HEIGHT, AVATAR_WIDTH};
 use crate::contacts::{Contact, ContactSearchCriteria, ContactsManager};
 use crate::theme::Theme;
 use ratatui::{
@@ -58,6 +59,7 @@ pub struct ContactPopup {
     // Display settings
     show_details: bool,
     max_results: usize,
+    avatars: Option<AvatarCache>,
 }
 
 impl ContactPopup {
@@ -73,9 +75,15 @@ impl ContactPopup {
             filtered_contacts: Vec::new(),
             show_details: false,
             max_results: 1000, // Increased to support viewing all contacts
+            avatars: None,
         }
     }
 
+    /// Show the selected contact's avatar from this cache
+    pub fn set_avatar_cache(&mut self, avatars: AvatarCache) {
+        self.avatars = Some(avatars);
+    }
+
     /// Initialize popup with recent contacts
     pub async fn initialize(&mut self) {
         if let Err(e) = self.load_recent_contacts().await {
@@ -155,8 +163,17 @@ impl ContactPopup {
         // Render header with search bar
         self.render_search_header(f, chunks[0], theme);
         
-        // Render contact list
-        self.render_contact_list(f, chunks[1], theme);
+        // Render contact list, with the selected contact's avatar beside it
+        if self.avatars.is_some() {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(AVATAR_WIDTH + 4), Constraint::Min(0)])
+                .split(chunks[1]);
+            self.render_avatar(f, columns[0], theme);
+            self.render_contact_list(f, columns[1], theme);
+        } else {
+            self.render_contact_list(f, chunks[1], theme);
+        }
         
         // Render footer with help
         self.render_footer(f, chunks[2], theme);
@@ -214,6 +231,35 @@ impl ContactPopup {
         f.render_widget(search_paragraph, area);
     }
 
+    /// Render the selected contact's photo or initials
+    fn render_avatar(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.colors.palette.border));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let (Some(avatars), Some(contact)) = (&self.avatars, self.get_selected_contact()) else {
+            return;
+        };
+        let email = contact
+            .primary_email()
+            .map(|e| e.address.clone())
+            .unwrap_or_default();
+        avatars.request(&email, contact.photo_url.as_deref());
+
+        let avatar_area = Rect {
+            x: inner.x + inner.width.saturating_sub(AVATAR_WIDTH) / 2,
+            y: inner.y,
+            width: AVATAR_WIDTH.min(inner.width),
+            height: AVATAR_HEIGHT.min(inner.height),
+        };
+        f.render_widget(
+            Paragraph::new(avatars.lines(&contact.display_name, &email)),
+            avatar_area,
+        );
+    }
+
     /// Render contact list
     fn render_contact_list(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
         let contacts = self.get_display_contacts();
@@ -260,31 +306,25 @@ impl ContactPopup {
                     info_parts.push(format!("📞 {}", phone.number));
                 }
                 
-                // Add profile picture indicator or initial
-                let avatar = if contact.photo_url.is_some() {
-                    "📷".to_string() // Camera icon for contacts with photos
-                } else {
-                    // Generate initial from display name
-                    contact.display_name.chars().next()
-                        .map(|c| format!("({})", c.to_uppercase()))
-                        .unwrap_or_else(|| "(??)".to_string())
-                };
+                // Coloured initials badge, matching the avatar fallback
+                let badge = avatar::initials_span(&contact.display_name, &email);
                 
                 // Add source indicator
                 let source_icon = match contact.source {
                     crate::contacts::ContactSource::Google { .. } => "🌐G",
                     crate::contacts::ContactSource::Outlook { .. } => "📧O", 
+                    crate::contacts::ContactSource::CardDAV { .. } => "📇C",
                     crate::contacts::ContactSource::Local => "💾L",
                 };
                 
                 // Format: Avatar Name <email> | Additional Info | Source
-                let mut text = format!("{} {} <{}>", avatar, display_name, email);
+                let mut text = format!(" {} <{}>", display_name, email);
                 if !info_parts.is_empty() {
                     text.push_str(&format!(" │ {}", info_parts.join(" │ ")));
                 }
                 text.push_str(&format!(" │ {}", source_icon));
                 
-                ListItem::new(text)
+                ListItem::new(Line::from(vec![badge, Span::raw(text)]))
                     .style(Style::default().fg(theme.colors.palette.text_primary))
             })
             .collect();
@@ -321,6 +361,7 @@ impl ContactPopup {
         let source_icon = match contact.source {
             crate::contacts::ContactSource::Google { .. } => "🌐",
             crate::contacts::ContactSource::Outlook { .. } => "📧", 
+            crate::contacts::ContactSource::CardDAV { .. } => "📇",
             crate::contacts::ContactSource::Local => "💾",
         };
 
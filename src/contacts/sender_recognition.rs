@@ -1,4 +1,6 @@
 use crate::contacts::{Contact, ContactsManager, ContactsResult};
+use crate::email::SenderHistory;
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -82,6 +84,87 @@ impl SenderInfo {
     }
 }
 
+/// Webmail domains shared by unrelated people; knowing someone there says
+/// nothing about another address at the same domain
+const PUBLIC_MAIL_DOMAINS: &[&str] = &[
+    "aol.com",
+    "gmail.com",
+    "gmx.com",
+    "gmx.de",
+    "googlemail.com",
+    "hotmail.com",
+    "icloud.com",
+    "live.com",
+    "mail.com",
+    "me.com",
+    "outlook.com",
+    "proton.me",
+    "protonmail.com",
+    "yahoo.com",
+    "yandex.com",
+];
+
+/// Most same-domain contacts named in a recognition note
+const MAX_DOMAIN_CONTACTS: usize = 3;
+
+/// What is known about the sender of the message being read
+#[derive(Debug, Clone, PartialEq)]
+pub struct SenderRecognition {
+    pub address: String,
+    pub contact_name: Option<String>,
+    pub organization: Option<String>,
+    pub contact_id: Option<i64>,
+    /// When mail was last sent to the address
+    pub last_emailed: Option<DateTime<Utc>>,
+    /// No earlier message from the address is stored
+    pub first_time_sender: bool,
+    /// Contacts at the sender's domain, when the address itself is unknown
+    pub domain_contacts: Vec<String>,
+}
+
+impl SenderRecognition {
+    /// Whether the address belongs to a contact
+    pub fn is_known_contact(&self) -> bool {
+        self.contact_id.is_some()
+    }
+
+    /// Contact name and organization, or a note that the sender is unknown
+    pub fn headline(&self) -> String {
+        match (&self.contact_name, &self.organization) {
+            (Some(name), Some(organization)) => format!("{} · {}", name, organization),
+            (Some(name), None) => name.clone(),
+            (None, _) => "Not in your contacts".to_string(),
+        }
+    }
+
+    /// "last emailed 3 days ago", or `None` if you never wrote to the sender
+    pub fn last_emailed_text(&self, now: DateTime<Utc>) -> Option<String> {
+        let days = (now - self.last_emailed?).num_days();
+        Some(match days {
+            ..=0 => "last emailed today".to_string(),
+            1 => "last emailed yesterday".to_string(),
+            days => format!("last emailed {} days ago", days),
+        })
+    }
+
+    /// Cues that the message deserves a second look
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.first_time_sender {
+            warnings.push("First message from this address".to_string());
+        }
+        if !self.is_known_contact() && !self.domain_contacts.is_empty() {
+            let domain = self.address.rsplit('@').next().unwrap_or_default();
+            warnings.push(format!(
+                "Unknown address at {}, where your contacts are {}",
+                domain,
+                self.domain_contacts.join(", ")
+            ));
+        }
+        warnings
+    }
+}
+
 /// Service for recognizing email senders and looking up contact information
 pub struct SenderRecognitionService {
     contacts_manager: Arc<ContactsManager>,
@@ -128,6 +211,38 @@ impl SenderRecognitionService {
         Ok(sender_info)
     }
 
+    /// Recognition details for the viewer: the matched contact, when you
+    /// last wrote to them, and whether the sender is new or only shares a
+    /// domain with your contacts
+    pub async fn recognize(&self, email: &str, history: &SenderHistory) -> ContactsResult<SenderRecognition> {
+        let address = self.extract_email_address(&email.trim().to_lowercase());
+        let sender_info = self.lookup_sender(&address).await?;
+
+        let domain = address.rsplit_once('@').map(|(_, domain)| domain);
+        let domain_contacts = match domain {
+            Some(domain) if !sender_info.is_known_contact && !PUBLIC_MAIL_DOMAINS.contains(&domain) => self
+                .contacts_manager
+                .find_contacts_by_email_domain(domain, MAX_DOMAIN_CONTACTS)
+                .await?
+                .iter()
+                .map(|contact| SenderInfo::from_contact(contact, &address).best_display_name().to_string())
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(SenderRecognition {
+            contact_name: sender_info
+                .is_known_contact
+                .then(|| sender_info.best_display_name().to_string()),
+            organization: sender_info.company.clone(),
+            contact_id: sender_info.contact_id,
+            last_emailed: history.last_emailed,
+            first_time_sender: history.earlier_messages == 0,
+            domain_contacts,
+            address,
+        })
+    }
+
     /// Look up multiple senders at once (batch operation)
     pub async fn lookup_senders(&self, emails: &[String]) -> ContactsResult<HashMap<String, SenderInfo>> {
         let mut results = HashMap::new();
@@ -191,21 +306,21 @@ mod tests {
     use crate::oauth2::TokenManager;
     use tempfile;
 
-    async fn create_test_service() -> SenderRecognitionService {
+    async fn create_test_service() -> (SenderRecognitionService, tempfile::TempDir) {
         let temp_dir = tempfile::tempdir().unwrap();
         let db_path = temp_dir.path().join("test_contacts.db");
-        let database_url = format!("sqlite:{}", db_path.display());
+        let database_url = format!("sqlite:{}?mode=rwc", db_path.display());
         
         let database = ContactsDatabase::new(&database_url).await.unwrap();
         let token_manager = TokenManager::new();
         let contacts_manager = ContactsManager::new(database, token_manager).await.unwrap();
         
-        SenderRecognitionService::new(Arc::new(contacts_manager))
+        (SenderRecognitionService::new(Arc::new(contacts_manager)), temp_dir)
     }
 
     #[tokio::test]
     async fn test_extract_email_address() {
-        let service = create_test_service().await;
+        let (service, _temp_dir) = create_test_service().await;
         
         assert_eq!(
             service.extract_email_address("john@example.com"),
@@ -235,6 +350,54 @@ mod tests {
         assert!(!sender_info.is_known_contact);
     }
 
+    #[tokio::test]
+    async fn test_sender_recognition_details() {
+        let (service, _temp_dir) = create_test_service().await;
+        let mut contact = Contact::new("1".to_string(), ContactSource::Local, "Jane Roe".to_string());
+        contact.company = Some("Acme Corp".to_string());
+        contact.emails.push(ContactEmail::new("jane@acme.example".to_string(), "work".to_string()));
+        service.contacts_manager.create_contact(contact).await.unwrap();
+
+        let now = Utc::now();
+        let known = service
+            .recognize(
+                "Jane Roe <Jane@acme.example>",
+                &SenderHistory {
+                    earlier_messages: 4,
+                    last_emailed: Some(now - chrono::Duration::days(3)),
+                },
+            )
+            .await
+            .unwrap();
+        assert!(known.is_known_contact());
+        assert_eq!(known.headline(), "Jane Roe · Acme Corp");
+        assert_eq!(known.last_emailed_text(now).as_deref(), Some("last emailed 3 days ago"));
+        assert!(known.warnings().is_empty());
+
+        // A new address at a domain you know is worth pointing out
+        let lookalike = service
+            .recognize("billing@acme.example", &SenderHistory::default())
+            .await
+            .unwrap();
+        assert!(!lookalike.is_known_contact());
+        assert_eq!(lookalike.headline(), "Not in your contacts");
+        assert_eq!(lookalike.last_emailed_text(now), None);
+        assert_eq!(
+            lookalike.warnings(),
+            vec![
+                "First message from this address".to_string(),
+                "Unknown address at acme.example, where your contacts are Jane Roe".to_string(),
+            ]
+        );
+
+        // Shared webmail domains don't count as known
+        let stranger = service
+            .recognize("someone@gmail.com", &SenderHistory { earlier_messages: 1, last_emailed: None })
+            .await
+            .unwrap();
+        assert!(stranger.warnings().is_empty());
+    }
+
     #[tokio::test]
     async fn test_sender_info_from_contact() {
         let mut contact = Contact::new(
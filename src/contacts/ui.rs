@@ -941,7 +941,7 @@ impl AddressBookUI {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(8), // Overview stats
+                    Constraint::Length(11), // Overview stats
                     Constraint::Length(4), // Progress bars
                     Constraint::Min(0),    // Additional info
                 ])
@@ -961,6 +961,10 @@ impl AddressBookUI {
                     Cell::from("Outlook Contacts"),
                     Cell::from(stats.outlook_contacts.to_string()),
                 ]),
+                Row::new(vec![
+                    Cell::from("CardDAV Contacts"),
+                    Cell::from(stats.carddav_contacts.to_string()),
+                ]),
                 Row::new(vec![
                     Cell::from("Local Contacts"),
                     Cell::from(stats.local_contacts.to_string()),
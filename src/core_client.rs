@@ -0,0 +1,397 @@
+//! Embeddable mail core
+//!
+//! [`CoreClient`] wires up the pieces the TUI uses for mail — the message
+//! database, stored accounts, IMAP connections, the sync engine, SMTP and
+//! the notification manager — so other programs can reuse them without the
+//! terminal UI. A new-mail notifier is a few lines:
+//!
+//! ```no_run
+//! use comunicado::core_client::CoreClient;
+//! use comunicado::email::EmailNotification;
+//!
+//! # async fn run() -> anyhow::Result<()> {
+//! let client = CoreClient::open_default().await?;
+//!
+//! let mut notifications = client.notifications();
+//! tokio::spawn(async move {
+//!     while let Ok(notification) = notifications.recv().await {
+//!         if let EmailNotification::NewMessage { message, .. } = notification {
+//!             println!("{}: {}", message.from_addr, message.subject);
+//!         }
+//!     }
+//! });
+//!
+//! loop {
+//!     for account in client.accounts()? {
+//!         client.sync_account(&account.account_id).await?;
+//!     }
+//!     tokio::time::sleep(std::time::Duration::from_secs(300)).await;
+//! }
+//! # }
+//! ```
+//!
+//! Accounts are the ones added through the TUI or `comunicado setup-*`
+//! commands. Feature settings such as `imap_fetch.toml`, `sender_lists.toml`,
+//! `filters.toml`, `folder_sync.toml` and `imap_capabilities.toml` are read
+//! from the standard config location. Every new message is published as a
+//! notification, so the `Notify` filter action has no extra effect here.
+
+use crate::email::{
+    EmailDatabase, EmailNotification, EmailNotificationManager, FilterRules, FolderSyncDefaults,
+    PlainTextAccounts, SenderLists, SyncEngine, SyncStrategy,
+};
+use crate::imap::{CapabilityOverrides, FetchConfig, ImapAccountManager};
+use crate::jmap::{JmapClient, JmapSettings};
+use crate::oauth2::{AccountConfig, SecureStorage, TokenManager};
+use crate::smtp::{EmailMessage, SendResult, SmtpService};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
+
+/// Most new-message notifications published for one folder per sync
+const MAX_NOTIFICATIONS_PER_FOLDER: u32 = 200;
+
+/// Outcome of [`CoreClient::sync_account`]
+#[derive(Debug, Clone, Default)]
+pub struct AccountSyncSummary {
+    /// Folders that synced successfully
+    pub folders_synced: usize,
+    /// Folders that failed, with the error
+    pub folders_failed: Vec<(String, String)>,
+    /// Messages that arrived since the previous sync
+    pub new_messages: usize,
+}
+
+/// Mail sync and sending without the terminal UI
+pub struct CoreClient {
+    config_dir: PathBuf,
+    storage: SecureStorage,
+    token_manager: TokenManager,
+    database: Arc<EmailDatabase>,
+    imap_manager: Arc<ImapAccountManager>,
+    sync_engine: Arc<SyncEngine>,
+    smtp_service: SmtpService,
+    notification_manager: Arc<EmailNotificationManager>,
+}
+
+impl CoreClient {
+    /// Open the mail core using the standard config directory
+    /// (`~/.config/comunicado` on Linux)
+    pub async fn open_default() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not determine config directory"))?
+            .join("comunicado");
+        Self::open(config_dir).await
+    }
+
+    /// Open the mail core for a config directory
+    ///
+    /// Accounts are read from `config_dir` and messages are stored in
+    /// `config_dir/databases/email.db`, the same layout the TUI uses, so both
+    /// can share one directory.
+    pub async fn open(config_dir: impl AsRef<Path>) -> Result<Self> {
+        let config_dir = config_dir.as_ref().to_path_buf();
+
+        let database_dir = config_dir.join("databases");
+        tokio::fs::create_dir_all(&database_dir).await?;
+        let db_path = database_dir.join("email.db");
+        let db_path = db_path
+            .to_str()
+            .ok_or_else(|| anyhow::anyhow!("Invalid database path"))?;
+        let database = Arc::new(
+            EmailDatabase::new(db_path)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to open database: {}", e))?,
+        );
+
+        let storage = SecureStorage::with_config_dir("comunicado".to_string(), config_dir.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to open account storage: {}", e))?;
+        let token_manager = TokenManager::new_with_storage(Arc::new(storage.clone()));
+
+        let mut imap_manager = ImapAccountManager::new_with_storage(storage.clone(), token_manager.clone());
+        imap_manager
+            .load_accounts()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to load accounts: {}", e))?;
+        match CapabilityOverrides::load().await {
+            Ok(overrides) => {
+                for (account_id, disabled) in overrides.accounts {
+                    if let Err(e) = imap_manager
+                        .set_disabled_capabilities(&account_id, disabled)
+                        .await
+                    {
+                        tracing::warn!("Ignoring capability overrides for {}: {}", account_id, e);
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Failed to load IMAP capability overrides: {}", e),
+        }
+        match JmapSettings::load().await {
+            Ok(settings) => imap_manager.set_jmap_settings(settings).await,
+            Err(e) => tracing::warn!("Failed to load JMAP settings: {}", e),
+        }
+        let plain_text_accounts = PlainTextAccounts::load().await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to load plain text accounts: {}", e);
+            PlainTextAccounts::default()
+        });
+        imap_manager
+            .set_plain_text_accounts(plain_text_accounts.clone())
+            .await;
+
+        // Progress is reported through notifications instead
+        let (sync_progress_tx, _sync_progress_rx) = mpsc::unbounded_channel();
+        let mut sync_engine = SyncEngine::new(database.clone(), sync_progress_tx);
+        match FetchConfig::load().await {
+            Ok(fetch_config) => sync_engine.set_fetch_config(fetch_config),
+            Err(e) => tracing::warn!("Failed to load IMAP fetch config, using provider defaults: {}", e),
+        }
+        match FolderSyncDefaults::load().await {
+            Ok(defaults) => sync_engine.set_folder_sync_defaults(defaults),
+            Err(e) => tracing::warn!("Failed to load folder sync defaults: {}", e),
+        }
+        sync_engine.set_plain_text_accounts(plain_text_accounts);
+        match SenderLists::load().await {
+            Ok(sender_lists) => sync_engine.set_sender_lists(sender_lists).await,
+            Err(e) => tracing::warn!("Failed to load sender lists: {}", e),
+        }
+        match FilterRules::load().await {
+            Ok(rules) => sync_engine.set_filter_rules(rules).await,
+            Err(e) => tracing::warn!("Failed to load filter rules: {}", e),
+        }
+
+        let smtp_service = SmtpService::new(Arc::new(token_manager.clone()), database.clone());
+
+        let notification_manager = Arc::new(EmailNotificationManager::new(database.clone()));
+        notification_manager.start().await;
+
+        Ok(Self {
+            config_dir,
+            storage,
+            token_manager,
+            database,
+            imap_manager: Arc::new(imap_manager),
+            sync_engine: Arc::new(sync_engine),
+            smtp_service,
+            notification_manager,
+        })
+    }
+
+    /// Directory the client was opened with
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
+    /// Configured accounts
+    pub fn accounts(&self) -> Result<Vec<AccountConfig>> {
+        self.storage
+            .load_all_accounts()
+            .map_err(|e| anyhow::anyhow!("Failed to load accounts: {}", e))
+    }
+
+    /// Subscribe to new-message and sync notifications
+    ///
+    /// New-message notifications are published for messages that arrive in a
+    /// folder after it has been synced once, so the first sync of a fresh
+    /// database doesn't announce the whole mailbox.
+    pub fn notifications(&self) -> broadcast::Receiver<EmailNotification> {
+        self.notification_manager.subscribe()
+    }
+
+    /// Fetch new mail and flag changes for every folder of an account
+    ///
+    /// A folder that fails is reported in the summary and doesn't stop the
+    /// others.
+    pub async fn sync_account(&self, account_id: &str) -> Result<AccountSyncSummary> {
+        if let Some(jmap) = self.imap_manager.get_jmap_client(account_id).await {
+            return self.sync_jmap_account(account_id, &jmap).await;
+        }
+
+        let client = self
+            .imap_manager
+            .get_client(account_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to connect account {}: {}", account_id, e))?;
+        let mut client = client.lock().await;
+
+        let folders = client
+            .list_folders("", "*")
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to list folders: {}", e))?;
+        let folders = self
+            .sync_engine
+            .apply_label_layout(account_id, &client, folders)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to record Gmail labels: {}", e))?;
+
+        let mut summary = AccountSyncSummary::default();
+        for folder in folders.iter().filter(|folder| folder.is_selectable()) {
+            // Anything at or above the previous UIDNEXT arrived since the last sync
+            let previous_uid_next = match self
+                .database
+                .get_folder_sync_state(account_id, &folder.name)
+                .await
+            {
+                Ok(state) => state.map(|state| state.uid_next),
+                Err(e) => {
+                    tracing::warn!("No sync state for {}/{}: {}", account_id, folder.name, e);
+                    None
+                }
+            };
+
+            self.notification_manager
+                .notify_sync_started(account_id.to_string(), folder.name.clone())
+                .await;
+
+            if let Err(e) = self
+                .sync_engine
+                .sync_folder(
+                    account_id.to_string(),
+                    &mut client,
+                    folder,
+                    SyncStrategy::Incremental,
+                )
+                .await
+            {
+                tracing::warn!("Failed to sync {}/{}: {}", account_id, folder.name, e);
+                self.notification_manager
+                    .notify_sync_failed(account_id.to_string(), folder.name.clone(), e.to_string())
+                    .await;
+                summary.folders_failed.push((folder.name.clone(), e.to_string()));
+                continue;
+            }
+
+            let new_messages = match previous_uid_next {
+                Some(uid_next) => self
+                    .database
+                    .get_messages_since_uid(
+                        account_id,
+                        &folder.name,
+                        uid_next,
+                        MAX_NOTIFICATIONS_PER_FOLDER,
+                    )
+                    .await
+                    .unwrap_or_default(),
+                None => Vec::new(),
+            };
+            let new_count = new_messages.len() as u32;
+            for message in new_messages {
+                self.notification_manager
+                    .notify_new_message(account_id.to_string(), folder.name.clone(), message)
+                    .await;
+            }
+            self.notification_manager
+                .notify_sync_completed(account_id.to_string(), folder.name.clone(), new_count, 0)
+                .await;
+
+            summary.folders_synced += 1;
+            summary.new_messages += new_count as usize;
+        }
+
+        Ok(summary)
+    }
+
+    /// Apply the changes of a JMAP account since its last sync
+    ///
+    /// JMAP syncs the whole account in one round, so a failure fails every
+    /// folder.
+    async fn sync_jmap_account(
+        &self,
+        account_id: &str,
+        jmap: &tokio::sync::Mutex<JmapClient>,
+    ) -> Result<AccountSyncSummary> {
+        self.notification_manager
+            .notify_sync_started(account_id.to_string(), "INBOX".to_string())
+            .await;
+
+        let mut client = jmap.lock().await;
+        let outcome = match self
+            .sync_engine
+            .sync_account_jmap(account_id.to_string(), &mut client)
+            .await
+        {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                self.notification_manager
+                    .notify_sync_failed(account_id.to_string(), "INBOX".to_string(), e.to_string())
+                    .await;
+                return Err(anyhow::anyhow!("Failed to sync account {}: {}", account_id, e));
+            }
+        };
+
+        let summary = AccountSyncSummary {
+            folders_synced: outcome.folders.len(),
+            new_messages: outcome.new_messages.len(),
+            ..Default::default()
+        };
+        let mut per_folder: HashMap<String, u32> = HashMap::new();
+        for message in outcome.new_messages {
+            let count = per_folder.entry(message.folder_name.clone()).or_default();
+            *count += 1;
+            if *count > MAX_NOTIFICATIONS_PER_FOLDER {
+                continue;
+            }
+            self.notification_manager
+                .notify_new_message(account_id.to_string(), message.folder_name.clone(), message)
+                .await;
+        }
+        for folder in &outcome.folders {
+            let new_count = per_folder.get(&folder.name).copied().unwrap_or(0);
+            self.notification_manager
+                .notify_sync_completed(account_id.to_string(), folder.name.clone(), new_count, 0)
+                .await;
+        }
+        Ok(summary)
+    }
+
+    /// Send a message through the account's SMTP server
+    pub async fn send(&self, account_id: &str, message: &EmailMessage) -> Result<SendResult> {
+        if !self.smtp_service.is_account_configured(account_id).await {
+            let account = self
+                .storage
+                .load_account(account_id)
+                .map_err(|e| anyhow::anyhow!("Failed to load account {}: {}", account_id, e))?
+                .ok_or_else(|| anyhow::anyhow!("Account {} not found", account_id))?;
+
+            let token = self
+                .token_manager
+                .get_valid_access_token(account_id)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to get access token: {}", e))?
+                .ok_or_else(|| anyhow::anyhow!("No access token for account {}", account_id))?;
+
+            self.smtp_service
+                .initialize_account(
+                    account_id,
+                    &account.provider,
+                    &account.email_address,
+                    &token.token,
+                )
+                .await
+                .map_err(|e| {
+                    anyhow::anyhow!("Failed to set up SMTP for account {}: {}", account_id, e)
+                })?;
+        }
+
+        self.smtp_service
+            .send_message(account_id, message)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to send message: {}", e))
+    }
+
+    /// Message database shared with the sync engine
+    pub fn database(&self) -> &Arc<EmailDatabase> {
+        &self.database
+    }
+
+    /// Sync engine, for finer-grained control such as single-folder syncs
+    pub fn sync_engine(&self) -> &Arc<SyncEngine> {
+        &self.sync_engine
+    }
+
+    /// IMAP connections for the configured accounts
+    pub fn imap_manager(&self) -> &Arc<ImapAccountManager> {
+        &self.imap_manager
+    }
+}
@@ -0,0 +1,237 @@
+//! Headless background mode
+//!
+//! `comunicado daemon` keeps every account synced and shows desktop
+//! notifications for new mail without the terminal UI, so it can be started
+//! at login. It writes to the same database as the TUI, which then opens
+//! with mail already synced.
+//!
+//! Only one daemon runs per config directory. It records its PID in
+//! `daemon.pid`, and a second daemon refuses to start while that process is
+//! alive. Without `--foreground` the command starts a detached copy of itself
+//! that logs to `daemon.log` and returns straight away.
+
+use crate::core_client::CoreClient;
+use crate::notifications::{NotificationConfig, UnifiedNotificationManager};
+use anyhow::{anyhow, Result};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Seconds between sync passes unless `--interval` says otherwise
+pub const DEFAULT_INTERVAL_SECS: u64 = 300;
+
+const PID_FILE: &str = "daemon.pid";
+const LOG_FILE: &str = "daemon.log";
+
+/// Ownership of the daemon PID file, released on drop
+#[derive(Debug)]
+pub struct DaemonLock {
+    path: PathBuf,
+}
+
+impl DaemonLock {
+    /// Claim the PID file in `config_dir`
+    ///
+    /// A file left behind by a daemon that is no longer running is replaced.
+    pub fn acquire(config_dir: &Path) -> Result<Self> {
+        fs::create_dir_all(config_dir)?;
+        let path = config_dir.join(PID_FILE);
+
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    write!(file, "{}", std::process::id())?;
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if let Some(pid) = running_pid(config_dir) {
+                        return Err(anyhow!("The daemon is already running (pid {})", pid));
+                    }
+                    tracing::info!("Removing stale daemon PID file {}", path.display());
+                    fs::remove_file(&path)?;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Err(anyhow!("Could not create {}", path.display()))
+    }
+}
+
+impl Drop for DaemonLock {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            tracing::warn!("Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// PID of the daemon running for `config_dir`, if any
+pub fn running_pid(config_dir: &Path) -> Option<u32> {
+    let pid: u32 = fs::read_to_string(config_dir.join(PID_FILE))
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    if pid == std::process::id() {
+        return Some(pid);
+    }
+
+    let mut system = sysinfo::System::new();
+    system
+        .refresh_process(sysinfo::Pid::from_u32(pid))
+        .then_some(pid)
+}
+
+/// Start a detached daemon and return its PID
+///
+/// The child runs `daemon --foreground` with the same options and logs to
+/// `daemon.log` in the config directory.
+pub fn spawn_detached(config_dir: &Path, interval_secs: u64) -> Result<u32> {
+    if let Some(pid) = running_pid(config_dir) {
+        return Err(anyhow!("The daemon is already running (pid {})", pid));
+    }
+
+    fs::create_dir_all(config_dir)?;
+    let log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(config_dir.join(LOG_FILE))?;
+
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .arg("--config-dir")
+        .arg(config_dir)
+        .arg("daemon")
+        .arg("--foreground")
+        .arg("--interval")
+        .arg(interval_secs.to_string())
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    #[cfg(unix)]
+    {
+        // Keep Ctrl+C and job control in the launching shell away from the daemon
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let child = command.spawn()?;
+    Ok(child.id())
+}
+
+/// Sync and notify until SIGINT or SIGTERM
+pub async fn run(config_dir: PathBuf, interval: Duration) -> Result<()> {
+    let _lock = DaemonLock::acquire(&config_dir)?;
+    tracing::info!(
+        "Daemon started (pid {}, syncing every {}s)",
+        std::process::id(),
+        interval.as_secs()
+    );
+
+    let client = CoreClient::open(&config_dir).await?;
+
+    let mut notification_config = NotificationConfig::default();
+    // Allowlisted senders always notify, as in the TUI
+    match crate::email::SenderLists::load().await {
+        Ok(sender_lists) => notification_config.vip_senders.extend(sender_lists.allowed),
+        Err(e) => tracing::warn!("Failed to load sender lists: {}", e),
+    }
+    let notifications =
+        UnifiedNotificationManager::new().with_desktop_notifications(notification_config);
+    notifications.connect_email_notifications(client.notifications());
+
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            _ = sync_all(&client) => {}
+            _ = &mut shutdown => break,
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = &mut shutdown => break,
+        }
+    }
+
+    tracing::info!("Daemon shutting down");
+    Ok(())
+}
+
+/// One sync pass over every account
+async fn sync_all(client: &CoreClient) {
+    let accounts = match client.accounts() {
+        Ok(accounts) => accounts,
+        Err(e) => {
+            tracing::error!("Failed to load accounts: {}", e);
+            return;
+        }
+    };
+
+    for account in accounts {
+        match client.sync_account(&account.account_id).await {
+            Ok(summary) => tracing::info!(
+                "Synced {}: {} folders, {} new messages, {} folders failed",
+                account.account_id,
+                summary.folders_synced,
+                summary.new_messages,
+                summary.folders_failed.len()
+            ),
+            Err(e) => tracing::error!("Failed to sync {}: {}", account.account_id, e),
+        }
+    }
+}
+
+/// Resolves on SIGINT or SIGTERM; SIGHUP is ignored so closing the terminal
+/// that started a foreground daemon doesn't stop it
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let (Ok(mut terminate), Ok(mut hangup)) =
+            (signal(SignalKind::terminate()), signal(SignalKind::hangup()))
+        else {
+            tracing::warn!("Failed to install signal handlers, only Ctrl+C stops the daemon");
+            let _ = tokio::signal::ctrl_c().await;
+            return;
+        };
+
+        loop {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => return,
+                _ = terminate.recv() => return,
+                _ = hangup.recv() => tracing::info!("Ignoring SIGHUP"),
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daemon_lock() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let lock = DaemonLock::acquire(dir.path()).unwrap();
+        assert_eq!(running_pid(dir.path()), Some(std::process::id()));
+        assert!(DaemonLock::acquire(dir.path()).is_err());
+        drop(lock);
+        assert!(!dir.path().join(PID_FILE).exists());
+
+        // A PID file from a process that has exited is taken over
+        fs::write(dir.path().join(PID_FILE), u32::MAX.to_string()).unwrap();
+        assert_eq!(running_pid(dir.path()), None);
+        let _lock = DaemonLock::acquire(dir.path()).unwrap();
+    }
+}
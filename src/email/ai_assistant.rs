@@ -1,12 +1,13 @@
 //! AI-powered email assistant for intelligent email management
 
-use crate::ai::{AIFactory, AIService, AIConfig, EmailCategory};
-use crate::email::EmailMessage;
+use crate::ai::{AIFactory, AIService, AIConfig, AIStreamingManager, EmailCategory, ReplyTone, ThreadSummary};
+use crate::email::{EmailMessage, EmailThread, SignatureStripper, StoredMessage};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use uuid::Uuid;
 
 /// Email composition assistance data
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,12 +83,23 @@ pub struct AIEmailAssistant {
     ai_service: Arc<AIService>,
     #[allow(dead_code)]
     config: Arc<RwLock<AIConfig>>,
+    /// Removes signatures and disclaimers before content reaches the provider
+    signature_stripper: RwLock<SignatureStripper>,
 }
 
 impl AIEmailAssistant {
     /// Create a new AI email assistant
     pub fn new(ai_service: Arc<AIService>, config: Arc<RwLock<AIConfig>>) -> Self {
-        Self { ai_service, config }
+        Self {
+            ai_service,
+            config,
+            signature_stripper: RwLock::new(SignatureStripper::default()),
+        }
+    }
+
+    /// Replace the signature and disclaimer patterns applied to AI input
+    pub async fn set_signature_stripper(&self, stripper: SignatureStripper) {
+        *self.signature_stripper.write().await = stripper;
     }
 
     /// Create AI email assistant from configuration
@@ -98,11 +110,21 @@ impl AIEmailAssistant {
         Ok(Self::new(ai_service, config))
     }
 
+    /// AI service the assistant runs on
+    pub fn ai_service(&self) -> &Arc<AIService> {
+        &self.ai_service
+    }
+
     /// Check if AI assistance is available
     pub async fn is_available(&self) -> bool {
         self.ai_service.is_enabled().await
     }
 
+    /// Streams of partial answers, by operation
+    pub fn streaming(&self) -> &AIStreamingManager {
+        self.ai_service.streaming()
+    }
+
     /// Generate email composition assistance
     pub async fn get_composition_assistance(
         &self,
@@ -137,12 +159,7 @@ impl AIEmailAssistant {
         }
 
         // Extract email content for analysis
-        let email_content = format!(
-            "Subject: {}\nFrom: {}\nContent: {}",
-            original_email.subject(),
-            original_email.sender(),
-            original_email.content()
-        );
+        let email_content = self.prompt_content(original_email).await;
 
         let user_context = context.unwrap_or("Professional email reply");
 
@@ -164,8 +181,14 @@ impl AIEmailAssistant {
             .unwrap_or_else(|_| "Professional".to_string());
 
         // Generate context summary
+        let body = self
+            .signature_stripper
+            .read()
+            .await
+            .for_ai(original_email.content())
+            .to_string();
         let summary = self.ai_service
-            .summarize_email(original_email.content(), Some(100))
+            .summarize_email(&body, Some(100))
             .await
             .unwrap_or_else(|_| "Unable to generate summary".to_string());
 
@@ -177,22 +200,67 @@ impl AIEmailAssistant {
         })
     }
 
+    /// Draft a whole reply to a message in `tone`, streamed under
+    /// `operation_id` when given
+    ///
+    /// The provider sees the message without its signature or the older mail
+    /// it quotes.
+    pub async fn draft_reply(
+        &self,
+        message: &StoredMessage,
+        tone: ReplyTone,
+        operation_id: Option<Uuid>,
+    ) -> Result<String> {
+        if !self.is_available().await {
+            return Err(anyhow::anyhow!("AI assistance is not available"));
+        }
+
+        let stripper = self.signature_stripper.read().await;
+        let body: Vec<&str> = stripper
+            .for_ai(message.body_text.as_deref().unwrap_or(""))
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('>'))
+            .collect();
+        let original = format!(
+            "From: {}\nSubject: {}\n\n{}",
+            message.from_name.as_deref().unwrap_or(&message.from_addr),
+            message.subject,
+            body.join("\n").trim()
+        );
+        drop(stripper);
+
+        self.ai_service
+            .draft_reply(&original, tone, operation_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Reply drafting failed: {}", e))
+    }
+
     /// Summarize a single email
     pub async fn summarize_email(&self, email: &EmailMessage) -> Result<EmailSummary> {
+        self.summarize(email, None).await
+    }
+
+    /// Summarize a single email, streaming the summary text under
+    /// `operation_id` before key points and action items are extracted
+    pub async fn summarize_email_streaming(
+        &self,
+        email: &EmailMessage,
+        operation_id: Uuid,
+    ) -> Result<EmailSummary> {
+        self.summarize(email, Some(operation_id)).await
+    }
+
+    async fn summarize(&self, email: &EmailMessage, operation_id: Option<Uuid>) -> Result<EmailSummary> {
         if !self.is_available().await {
             return Err(anyhow::anyhow!("AI assistance is not available"));
         }
 
-        let email_content = format!(
-            "Subject: {}\nFrom: {}\nContent: {}",
-            email.subject(),
-            email.sender(),
-            email.content()
-        );
+        let email_content = self.prompt_content(email).await;
+        let message_id = email.message_id().as_str();
 
         // Generate summary
         let summary = self.ai_service
-            .summarize_email(&email_content, Some(200))
+            .summarize_message(message_id, &email_content, Some(200), operation_id)
             .await
             .map_err(|e| anyhow::anyhow!("Email summarization failed: {}", e))?;
 
@@ -204,7 +272,7 @@ impl AIEmailAssistant {
 
         // Categorize email
         let category = self.ai_service
-            .categorize_email(&email_content)
+            .categorize_message(message_id, &email_content)
             .await
             .unwrap_or(EmailCategory::Uncategorized);
 
@@ -220,6 +288,48 @@ impl AIEmailAssistant {
         })
     }
 
+    /// Summarize a whole thread as bullet points, streamed under
+    /// `operation_id` when given
+    ///
+    /// Messages are fed to the provider oldest first, without signatures or
+    /// quoted text so each reply only contributes what it adds.
+    pub async fn summarize_thread(
+        &self,
+        thread: &EmailThread,
+        operation_id: Option<Uuid>,
+    ) -> Result<ThreadSummary> {
+        if !self.is_available().await {
+            return Err(anyhow::anyhow!("AI assistance is not available"));
+        }
+
+        let mut messages = thread.get_all_messages();
+        messages.sort_by_key(|message| *message.timestamp());
+
+        let stripper = self.signature_stripper.read().await;
+        let transcript: Vec<String> = messages
+            .iter()
+            .map(|message| {
+                let body: Vec<&str> = stripper
+                    .for_ai(message.content())
+                    .lines()
+                    .filter(|line| !line.trim_start().starts_with('>'))
+                    .collect();
+                format!(
+                    "From: {}\nDate: {}\n\n{}",
+                    message.sender(),
+                    message.timestamp().format("%Y-%m-%d %H:%M"),
+                    body.join("\n").trim()
+                )
+            })
+            .collect();
+        drop(stripper);
+
+        self.ai_service
+            .summarize_thread(&transcript, operation_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Thread summarization failed: {}", e))
+    }
+
     /// Perform bulk analysis on multiple emails
     pub async fn analyze_emails_bulk(
         &self,
@@ -292,12 +402,7 @@ impl AIEmailAssistant {
             return Ok((EmailCategory::Uncategorized, 0.0));
         }
 
-        let email_content = format!(
-            "Subject: {}\nFrom: {}\nContent: {}",
-            email.subject(),
-            email.sender(),
-            email.content()
-        );
+        let email_content = self.prompt_content(email).await;
 
         let category = self.ai_service
             .categorize_email(&email_content)
@@ -310,6 +415,18 @@ impl AIEmailAssistant {
         Ok((category, confidence))
     }
 
+    /// Subject, sender and body as sent to the provider, without the
+    /// sender's signature or disclaimer
+    async fn prompt_content(&self, email: &EmailMessage) -> String {
+        let stripper = self.signature_stripper.read().await;
+        format!(
+            "Subject: {}\nFrom: {}\nContent: {}",
+            email.subject(),
+            email.sender(),
+            stripper.for_ai(email.content())
+        )
+    }
+
     /// Extract action items from email content
     async fn extract_action_items(&self, content: &str) -> Vec<String> {
         let action_prompt = format!(
@@ -136,7 +136,7 @@ impl AsyncSyncService {
 
     /// Execute a folder sync task (called by background processor)
     pub async fn execute_folder_sync(
-        _sync_engine: Arc<SyncEngine>,
+        sync_engine: Arc<SyncEngine>,
         account_manager: Arc<ImapAccountManager>,
         progress_sender: mpsc::UnboundedSender<SyncProgress>,
         account_id: String,
@@ -155,9 +155,14 @@ impl AsyncSyncService {
             bytes_downloaded: 0,
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         };
         let _ = progress_sender.send(initial_progress);
 
+        if let Some(result) = Self::execute_jmap_sync(&sync_engine, &account_manager, &account_id).await {
+            return result;
+        }
+
         // Get IMAP client for the account
         let client = account_manager
             .get_client(&account_id)
@@ -191,6 +196,7 @@ impl AsyncSyncService {
             bytes_downloaded: 0,
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         });
 
         // Select folder and get message count
@@ -214,6 +220,7 @@ impl AsyncSyncService {
             bytes_downloaded: 0,
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         });
 
         // For now, we'll do a basic folder sync operation
@@ -230,6 +237,7 @@ impl AsyncSyncService {
             bytes_downloaded: total_messages as u64 * 1024, // Estimate bytes
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         };
         let _ = progress_sender.send(completion_progress);
 
@@ -239,7 +247,7 @@ impl AsyncSyncService {
 
     /// Execute an account sync task (called by background processor)
     pub async fn execute_account_sync(
-        _sync_engine: Arc<SyncEngine>,
+        sync_engine: Arc<SyncEngine>,
         account_manager: Arc<ImapAccountManager>,
         progress_sender: mpsc::UnboundedSender<SyncProgress>,
         account_id: String,
@@ -257,9 +265,14 @@ impl AsyncSyncService {
             bytes_downloaded: 0,
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         };
         let _ = progress_sender.send(initial_progress);
 
+        if let Some(result) = Self::execute_jmap_sync(&sync_engine, &account_manager, &account_id).await {
+            return result;
+        }
+
         // Perform real IMAP account sync by getting folder list and syncing important folders
         info!("Starting real account sync for {}", account_id);
         
@@ -279,6 +292,7 @@ impl AsyncSyncService {
             bytes_downloaded: 0,
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         });
 
         // Get folder list
@@ -305,6 +319,7 @@ impl AsyncSyncService {
             bytes_downloaded: 0,
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         });
 
         // Sync important folders by getting their message counts
@@ -340,6 +355,7 @@ impl AsyncSyncService {
             bytes_downloaded: total_messages as u64 * 1024, // Estimate bytes
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         };
         let _ = progress_sender.send(completion_progress);
 
@@ -347,6 +363,25 @@ impl AsyncSyncService {
         Ok(TaskResultData::MessageCount(total_messages as usize))
     }
 
+    /// Sync an account served over JMAP; `None` for IMAP accounts
+    ///
+    /// One `Email/changes` round covers every mailbox, so folder and account
+    /// syncs both run the account sync.
+    async fn execute_jmap_sync(
+        sync_engine: &SyncEngine,
+        account_manager: &ImapAccountManager,
+        account_id: &str,
+    ) -> Option<Result<TaskResultData, String>> {
+        let jmap = account_manager.get_jmap_client(account_id).await?;
+        let mut client = jmap.lock().await;
+        let result = sync_engine
+            .sync_account_jmap(account_id.to_string(), &mut client)
+            .await
+            .map(|outcome| TaskResultData::MessageCount(outcome.new_messages.len()))
+            .map_err(|e| format!("JMAP sync failed: {}", e));
+        Some(result)
+    }
+
     /// Execute a folder refresh task (called by background processor)
     pub async fn execute_folder_refresh(
         account_manager: Arc<ImapAccountManager>,
@@ -366,6 +401,7 @@ impl AsyncSyncService {
             bytes_downloaded: 0,
             started_at: chrono::Utc::now(),
             estimated_completion: Some(chrono::Utc::now() + chrono::Duration::seconds(5)),
+            account_progress: None,
         };
         let _ = progress_sender.send(progress);
 
@@ -397,6 +433,7 @@ impl AsyncSyncService {
                 bytes_downloaded: 0,
                 started_at: chrono::Utc::now(),
                 estimated_completion: Some(chrono::Utc::now() + chrono::Duration::seconds(10)),
+                account_progress: None,
             };
             let _ = progress_sender.send(progress_update);
             
@@ -452,6 +489,7 @@ impl AsyncSyncService {
                 bytes_downloaded: 0,
                 started_at: chrono::Utc::now(),
                 estimated_completion: Some(chrono::Utc::now() + chrono::Duration::seconds(5)),
+                account_progress: None,
             };
             let _ = progress_sender.send(headers_progress);
             
@@ -469,6 +507,7 @@ impl AsyncSyncService {
             bytes_downloaded: 0,
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         };
         let _ = progress_sender.send(completion_progress);
 
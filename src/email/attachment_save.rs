@@ -0,0 +1,259 @@
+//! Attachment save-to configuration and filename handling
+//!
+//! Resolves where attachments are written when saved from the content preview:
+//! a configurable download directory, optional per-sender or per-date subfolders,
+//! sanitization of filenames taken from MIME headers and `name(1).ext` style
+//! de-duplication so existing files are never overwritten.
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// How saved attachments are grouped below the download directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentOrganization {
+    /// Save directly into the download directory
+    #[default]
+    Flat,
+    /// One subfolder per sender address
+    BySender,
+    /// One subfolder per message date (YYYY-MM-DD)
+    ByDate,
+}
+
+/// Attachment save configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttachmentSaveConfig {
+    /// Directory attachments are saved to; defaults to the system download directory
+    pub download_dir: Option<PathBuf>,
+    /// Subfolder layout below the download directory
+    pub organize_by: AttachmentOrganization,
+    /// Append `(1)`, `(2)`, ... instead of overwriting existing files
+    pub deduplicate: bool,
+}
+
+impl Default for AttachmentSaveConfig {
+    fn default() -> Self {
+        Self {
+            download_dir: None,
+            organize_by: AttachmentOrganization::Flat,
+            deduplicate: true,
+        }
+    }
+}
+
+impl AttachmentSaveConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "attachments.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Base download directory, falling back to the system download directory
+    pub fn base_directory(&self) -> PathBuf {
+        if let Some(ref dir) = self.download_dir {
+            return expand_home(dir);
+        }
+
+        if let Some(downloads) = dirs::download_dir() {
+            return downloads;
+        }
+
+        if let Some(home_dir) = dirs::home_dir() {
+            return home_dir.join("comunicado-downloads");
+        }
+
+        PathBuf::from("./downloads")
+    }
+
+    /// Directory an attachment from the given sender and date should be saved to
+    pub fn target_directory(&self, sender: &str, date: Option<DateTime<Local>>) -> PathBuf {
+        let base = self.base_directory();
+
+        match self.organize_by {
+            AttachmentOrganization::Flat => base,
+            AttachmentOrganization::BySender => {
+                let sender_dir = sanitize_filename(&extract_address(sender));
+                base.join(sender_dir)
+            }
+            AttachmentOrganization::ByDate => {
+                let date = date.unwrap_or_else(Local::now);
+                base.join(date.format("%Y-%m-%d").to_string())
+            }
+        }
+    }
+
+    /// Full path for saving a file into a directory, honoring de-duplication
+    pub fn target_path(&self, directory: &Path, filename: &str) -> PathBuf {
+        let filename = sanitize_filename(filename);
+        if self.deduplicate {
+            unique_path(directory, &filename)
+        } else {
+            directory.join(filename)
+        }
+    }
+}
+
+/// Make a filename from a message header safe to write to disk
+///
+/// Strips directory components, control characters and characters that are
+/// reserved on common filesystems, and never returns an empty or dot-only name.
+pub fn sanitize_filename(filename: &str) -> String {
+    // Only keep the final path component so "../../etc/passwd" can't escape the target dir
+    let last_component = filename
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(filename);
+
+    let sanitized: String = last_component
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    let trimmed = sanitized.trim().trim_matches('.').trim();
+    if trimmed.is_empty() {
+        "attachment".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Return a path in `directory` that doesn't collide with an existing file
+///
+/// `report.pdf` becomes `report(1).pdf`, `report(2).pdf`, ... as needed.
+pub fn unique_path(directory: &Path, filename: &str) -> PathBuf {
+    let candidate = directory.join(filename);
+    if !candidate.exists() {
+        return candidate;
+    }
+
+    let path = Path::new(filename);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(filename);
+    let extension = path.extension().and_then(|e| e.to_str());
+
+    let mut counter = 1;
+    loop {
+        let name = match extension {
+            Some(ext) => format!("{}({}).{}", stem, counter, ext),
+            None => format!("{}({})", stem, counter),
+        };
+        let candidate = directory.join(name);
+        if !candidate.exists() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+/// Extract the bare address from a "Name <address>" sender string
+fn extract_address(sender: &str) -> String {
+    match (sender.find('<'), sender.rfind('>')) {
+        (Some(start), Some(end)) if start < end => sender[start + 1..end].trim().to_string(),
+        _ => sender.trim().to_string(),
+    }
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~") {
+        if let Some(home_dir) = dirs::home_dir() {
+            return home_dir.join(rest);
+        }
+    }
+    path.to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("report.pdf"), "report.pdf");
+        assert_eq!(sanitize_filename("../../etc/passwd"), "passwd");
+        assert_eq!(sanitize_filename("C:\\Users\\evil.exe"), "evil.exe");
+        assert_eq!(sanitize_filename("what?<is>:this*.txt"), "what__is__this_.txt");
+        assert_eq!(sanitize_filename("line\nbreak.txt"), "line_break.txt");
+        assert_eq!(sanitize_filename(".."), "attachment");
+        assert_eq!(sanitize_filename("   "), "attachment");
+    }
+
+    #[test]
+    fn test_unique_path_deduplicates() {
+        let dir = tempdir().unwrap();
+
+        let first = unique_path(dir.path(), "report.pdf");
+        assert_eq!(first, dir.path().join("report.pdf"));
+        std::fs::write(&first, b"one").unwrap();
+
+        let second = unique_path(dir.path(), "report.pdf");
+        assert_eq!(second, dir.path().join("report(1).pdf"));
+        std::fs::write(&second, b"two").unwrap();
+
+        let third = unique_path(dir.path(), "report.pdf");
+        assert_eq!(third, dir.path().join("report(2).pdf"));
+
+        std::fs::write(dir.path().join("README"), b"x").unwrap();
+        assert_eq!(unique_path(dir.path(), "README"), dir.path().join("README(1)"));
+    }
+
+    #[test]
+    fn test_target_directory_organization() {
+        let mut config = AttachmentSaveConfig {
+            download_dir: Some(PathBuf::from("/tmp/saved")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.target_directory("Jane <jane@example.com>", None),
+            PathBuf::from("/tmp/saved")
+        );
+
+        config.organize_by = AttachmentOrganization::BySender;
+        assert_eq!(
+            config.target_directory("Jane <jane@example.com>", None),
+            PathBuf::from("/tmp/saved/jane@example.com")
+        );
+
+        config.organize_by = AttachmentOrganization::ByDate;
+        let date = DateTime::parse_from_rfc3339("2024-03-05T10:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Local);
+        let expected = format!("/tmp/saved/{}", date.format("%Y-%m-%d"));
+        assert_eq!(
+            config.target_directory("jane@example.com", Some(date)),
+            PathBuf::from(expected)
+        );
+    }
+
+    #[test]
+    fn test_config_defaults_from_empty_file() {
+        let config: AttachmentSaveConfig = toml::from_str("").unwrap();
+        assert!(config.download_dir.is_none());
+        assert!(config.deduplicate);
+        assert_eq!(config.organize_by, AttachmentOrganization::Flat);
+
+        let config: AttachmentSaveConfig =
+            toml::from_str("organize_by = \"by_sender\"\ndownload_dir = \"~/Mail\"").unwrap();
+        assert_eq!(config.organize_by, AttachmentOrganization::BySender);
+        assert_eq!(config.download_dir, Some(PathBuf::from("~/Mail")));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::email::document_preview::{self, PreviewError};
 use crate::email::{AttachmentInfo, AttachmentType};
 use crate::images::ImageManager;
 use crate::theme::Theme;
@@ -46,6 +47,8 @@ pub struct AttachmentViewer {
     viewer_mode: ViewerMode,
     scroll: usize,
     max_text_size: usize, // Maximum size for text preview (bytes)
+    current_data: Option<Vec<u8>>,
+    external_files: Vec<std::path::PathBuf>, // Temp copies opened externally
 }
 
 impl AttachmentViewer {
@@ -62,6 +65,8 @@ impl AttachmentViewer {
             viewer_mode: ViewerMode::Preview,
             scroll: 0,
             max_text_size: 1024 * 1024, // 1MB max for text preview
+            current_data: None,
+            external_files: Vec::new(),
         })
     }
 
@@ -72,6 +77,7 @@ impl AttachmentViewer {
         data: &[u8],
     ) -> ViewResult {
         self.current_attachment = Some(attachment.clone());
+        self.current_data = Some(data.to_vec());
 
         // Determine the best viewer mode for this attachment
         self.viewer_mode = self.determine_viewer_mode(&attachment.attachment_type);
@@ -79,7 +85,7 @@ impl AttachmentViewer {
         match self.viewer_mode {
             ViewerMode::Image => self.view_image_attachment(attachment, data).await,
             ViewerMode::Text => self.view_text_attachment(attachment, data),
-            ViewerMode::Preview => self.view_preview_attachment(attachment, data),
+            ViewerMode::Preview => self.view_preview_attachment(attachment, data).await,
             ViewerMode::Info => self.view_info_attachment(attachment),
         }
     }
@@ -233,7 +239,42 @@ impl AttachmentViewer {
     }
 
     /// View a preview of a document or other file type
-    fn view_preview_attachment(&mut self, attachment: &AttachmentInfo, data: &[u8]) -> ViewResult {
+    ///
+    /// PDFs show their first page as an image, or their text when the
+    /// terminal has no graphics; office documents show their text. Anything
+    /// that can't be extracted falls back to the file summary below.
+    async fn view_preview_attachment(
+        &mut self,
+        attachment: &AttachmentInfo,
+        data: &[u8],
+    ) -> ViewResult {
+        let preview = match attachment.attachment_type {
+            AttachmentType::Pdf => self.preview_pdf(attachment, data).await,
+            AttachmentType::Word | AttachmentType::Excel | AttachmentType::PowerPoint => {
+                document_preview::office_text(&attachment.attachment_type, data, &self.temp_dir)
+                    .await
+                    .map(|text| self.document_text_lines("📝 Document Preview", attachment, &text))
+            }
+            _ => Err(PreviewError::Unsupported(
+                "no preview for this file type".to_string(),
+            )),
+        };
+
+        let preview_error = match preview {
+            Ok(lines) => {
+                self.current_content = Some(lines.clone());
+                return ViewResult::Content(lines);
+            }
+            Err(e) => {
+                tracing::debug!(
+                    "No preview for attachment '{}': {}",
+                    attachment.display_name,
+                    e
+                );
+                e
+            }
+        };
+
         let lines = vec![
             Line::styled(
                 "📋 File Preview",
@@ -294,6 +335,15 @@ impl AttachmentViewer {
             }
         }
 
+        if attachment.attachment_type.is_document() {
+            content_lines.push(Line::raw(""));
+            content_lines.push(Line::styled(
+                format!("  ⚠️  Preview unavailable: {}", preview_error),
+                Style::default().fg(Color::Yellow),
+            ));
+            content_lines.push(Line::raw("  Use 's' to save the file and open it externally."));
+        }
+
         content_lines.push(Line::raw(""));
         content_lines.push(Line::styled(
             "Actions",
@@ -302,6 +352,7 @@ impl AttachmentViewer {
                 .add_modifier(Modifier::BOLD),
         ));
         content_lines.push(Line::raw("  s - Save attachment to disk"));
+        content_lines.push(Line::raw("  o - Open with external application"));
         if attachment.attachment_type.is_previewable() {
             content_lines.push(Line::raw("  t - Switch to text view"));
         }
@@ -344,7 +395,7 @@ impl AttachmentViewer {
                 "This file type cannot be viewed in the terminal.",
                 Style::default().fg(Color::Yellow),
             ),
-            Line::raw("Use 's' to save the file and open it with an external application."),
+            Line::raw("Press 'o' to open it with an external application, or 's' to save it."),
             Line::raw(""),
             Line::styled(
                 "Actions",
@@ -353,6 +404,7 @@ impl AttachmentViewer {
                     .add_modifier(Modifier::BOLD),
             ),
             Line::raw("  s - Save attachment to disk"),
+            Line::raw("  o - Open with external application"),
             Line::raw("  q - Close viewer"),
         ];
 
@@ -432,6 +484,91 @@ impl AttachmentViewer {
         }
     }
 
+    /// First page of a PDF as an image, or its text without graphics support
+    async fn preview_pdf(
+        &mut self,
+        attachment: &AttachmentInfo,
+        data: &[u8],
+    ) -> Result<Vec<Line<'static>>, PreviewError> {
+        if self.image_manager.supports_images() {
+            match document_preview::render_pdf_page(data, &self.temp_dir).await {
+                Ok(png) => match self
+                    .image_manager
+                    .load_image_from_bytes(&png, Some("image/png"))
+                    .await
+                {
+                    Ok(rendered) => {
+                        let mut lines = self.document_header("📄 PDF Preview", attachment);
+                        lines.push(Line::styled(
+                            "Page 1",
+                            Style::default().fg(Color::Green),
+                        ));
+                        lines.push(Line::raw(""));
+                        lines.extend(rendered.lines().map(|line| Line::raw(line.to_string())));
+                        return Ok(lines);
+                    }
+                    Err(e) => {
+                        tracing::debug!("Failed to display rendered PDF page: {}", e);
+                    }
+                },
+                Err(e) => {
+                    tracing::debug!("Failed to render PDF page: {}", e);
+                }
+            }
+        }
+
+        let text = document_preview::pdf_text(data, &self.temp_dir).await?;
+        Ok(self.document_text_lines("📄 PDF Preview", attachment, &text))
+    }
+
+    /// Title and file line shown above a document preview
+    fn document_header(&self, title: &str, attachment: &AttachmentInfo) -> Vec<Line<'static>> {
+        vec![
+            Line::styled(
+                title.to_string(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Line::raw(format!(
+                "File: {} ({})",
+                attachment.display_name,
+                attachment.format_size()
+            )),
+            Line::raw(""),
+        ]
+    }
+
+    /// Extracted document text as scrollable lines
+    fn document_text_lines(
+        &self,
+        title: &str,
+        attachment: &AttachmentInfo,
+        text: &str,
+    ) -> Vec<Line<'static>> {
+        let mut lines = self.document_header(title, attachment);
+        lines.extend(
+            text.lines()
+                .take(1000)
+                .map(|line| Line::raw(line.trim_end().to_string())),
+        );
+
+        if text.lines().count() > 1000 {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled(
+                "... (content truncated)",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            "o - Open externally  •  s - Save  •  q - Close viewer",
+            Style::default().fg(Color::Green),
+        ));
+        lines
+    }
+
     /// Generate preview information for PDF files
     fn preview_pdf_info(&self, data: &[u8]) -> Vec<Line<'static>> {
         let mut lines = vec![];
@@ -641,10 +778,47 @@ impl AttachmentViewer {
     }
 
     /// Clear current content
+    ///
+    /// Temporary copies handed to external applications are removed too.
     pub fn clear(&mut self) {
         self.current_content = None;
         self.current_attachment = None;
+        self.current_data = None;
         self.scroll = 0;
+        self.remove_external_files();
+    }
+
+    /// Write the current attachment to a temporary file for an external
+    /// application
+    ///
+    /// Each copy gets its own directory so the file keeps its name, and is
+    /// removed when the viewer is cleared. Returns the path and MIME type.
+    pub async fn export_for_external(
+        &mut self,
+    ) -> Result<Option<(std::path::PathBuf, String)>, Box<dyn std::error::Error>> {
+        let (Some(attachment), Some(data)) = (&self.current_attachment, &self.current_data) else {
+            return Ok(None);
+        };
+
+        let file_name = Path::new(&attachment.display_name)
+            .file_name()
+            .map(|name| name.to_os_string())
+            .unwrap_or_else(|| "attachment".into());
+        let dir = self.temp_dir.join(format!("open-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir_all(&dir).await?;
+        let path = dir.join(file_name);
+        tokio::fs::write(&path, data).await?;
+
+        self.external_files.push(dir);
+        Ok(Some((path, attachment.stored.content_type.clone())))
+    }
+
+    fn remove_external_files(&mut self) {
+        for dir in self.external_files.drain(..) {
+            if let Err(e) = std::fs::remove_dir_all(&dir) {
+                tracing::debug!("Failed to remove {}: {}", dir.display(), e);
+            }
+        }
     }
 
     /// Create a temporary file for the attachment (for external viewing)
@@ -670,6 +844,12 @@ impl AttachmentViewer {
     }
 }
 
+impl Drop for AttachmentViewer {
+    fn drop(&mut self) {
+        self.remove_external_files();
+    }
+}
+
 impl Default for AttachmentViewer {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| {
@@ -682,6 +862,8 @@ impl Default for AttachmentViewer {
                 viewer_mode: ViewerMode::Info,
                 scroll: 0,
                 max_text_size: 1024 * 1024,
+                current_data: None,
+                external_files: Vec::new(),
             }
         })
     }
@@ -1,8 +1,17 @@
 use crate::email::StoredAttachment;
+use crate::imap::{ImapClient, ImapResult};
 use crate::mime::decode_mime_header;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use futures::{Stream, StreamExt};
 use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{mpsc, Mutex};
 use uuid::Uuid;
 
 /// Supported attachment types and their handling
@@ -360,6 +369,263 @@ impl AttachmentManager {
     pub fn attachments_dir(&self) -> &Path {
         &self.attachments_dir
     }
+
+    /// Write a streamed attachment to `target`, decoding its transfer encoding on the way
+    ///
+    /// Data goes to a `.part` file next to `target` that is renamed into
+    /// place only once the stream has ended, so a failed or cancelled
+    /// download never leaves a truncated file under the real name.
+    /// `cancel` is checked between chunks. Returns the decoded size.
+    pub async fn download_streaming<S>(
+        stream: S,
+        encoding: Option<&str>,
+        target: &Path,
+        total: Option<u64>,
+        cancel: &AtomicBool,
+        mut on_progress: impl FnMut(DownloadProgress),
+    ) -> std::io::Result<u64>
+    where
+        S: Stream<Item = ImapResult<Vec<u8>>>,
+    {
+        let partial_path = Self::partial_path(target);
+        let result =
+            Self::write_stream(stream, encoding, &partial_path, total, cancel, &mut on_progress)
+                .await;
+
+        let result = match result {
+            Ok(written) => tokio::fs::rename(&partial_path, target).await.map(|_| written),
+            Err(e) => Err(e),
+        };
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(&partial_path).await;
+        }
+        result
+    }
+
+    async fn write_stream<S>(
+        stream: S,
+        encoding: Option<&str>,
+        path: &Path,
+        total: Option<u64>,
+        cancel: &AtomicBool,
+        on_progress: &mut impl FnMut(DownloadProgress),
+    ) -> std::io::Result<u64>
+    where
+        S: Stream<Item = ImapResult<Vec<u8>>>,
+    {
+        tokio::pin!(stream);
+        let mut file = tokio::fs::File::create(path).await?;
+        let mut decoder = TransferDecoder::new(encoding);
+        let mut received = 0u64;
+        let mut written = 0u64;
+
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Interrupted,
+                    "Download cancelled",
+                ));
+            }
+            let Some(chunk) = stream.next().await else {
+                break;
+            };
+            let chunk = chunk.map_err(std::io::Error::other)?;
+
+            received += chunk.len() as u64;
+            let decoded = decoder.feed(&chunk)?;
+            file.write_all(&decoded).await?;
+            written += decoded.len() as u64;
+            on_progress(DownloadProgress { received, total });
+        }
+
+        let decoded = decoder.finish()?;
+        file.write_all(&decoded).await?;
+        written += decoded.len() as u64;
+        file.sync_all().await?;
+
+        Ok(written)
+    }
+
+    /// Where a download is written until it completes
+    fn partial_path(target: &Path) -> PathBuf {
+        let mut name = target.file_name().unwrap_or_default().to_os_string();
+        name.push(".part");
+        target.with_file_name(name)
+    }
+}
+
+/// Progress of a streamed attachment download
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Encoded bytes received so far
+    pub received: u64,
+    /// Encoded size of the part, when the server reported one
+    pub total: Option<u64>,
+}
+
+impl DownloadProgress {
+    /// Percentage received, when the size is known
+    pub fn percentage(&self) -> Option<u64> {
+        self.total
+            .filter(|total| *total > 0)
+            .map(|total| (self.received * 100 / total).min(100))
+    }
+}
+
+/// An attachment to stream from the server to disk
+pub struct AttachmentDownload {
+    pub client: Arc<Mutex<ImapClient>>,
+    pub folder: String,
+    pub uid: u32,
+    /// Name of the attachment, used to find its part in the message
+    pub filename: String,
+    pub target: PathBuf,
+}
+
+impl AttachmentDownload {
+    /// Find the attachment's part and stream it to the target file
+    ///
+    /// The account's connection stays locked for the whole download, as the
+    /// partial fetches depend on the folder staying selected.
+    pub async fn run(
+        self,
+        progress: mpsc::UnboundedSender<DownloadProgress>,
+        cancel: Arc<AtomicBool>,
+    ) -> std::io::Result<PathBuf> {
+        let mut client = self.client.lock().await;
+        client
+            .select_folder(&self.folder)
+            .await
+            .map_err(std::io::Error::other)?;
+        let structure = client
+            .uid_fetch_bodystructure(self.uid)
+            .await
+            .map_err(std::io::Error::other)?
+            .ok_or_else(|| std::io::Error::other("Server sent no message structure"))?;
+
+        let (section, part) = structure
+            .sections()
+            .into_iter()
+            .find(|(_, part)| {
+                part.filename()
+                    .is_some_and(|name| decode_mime_header(name) == self.filename)
+            })
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("'{}' not found in the message", self.filename),
+                )
+            })?;
+        let encoding = part.content_transfer_encoding.clone();
+        let total = part.size.map(u64::from);
+
+        let range = 0..total.map_or(usize::MAX, |size| size as usize);
+        let stream = client.fetch_body_section(self.uid, &section, range);
+        AttachmentManager::download_streaming(
+            stream,
+            encoding.as_deref(),
+            &self.target,
+            total,
+            &cancel,
+            |update| {
+                let _ = progress.send(update);
+            },
+        )
+        .await?;
+
+        Ok(self.target)
+    }
+}
+
+/// Content-Transfer-Encoding decoder fed one chunk at a time
+enum TransferDecoder {
+    /// Holds characters short of a whole 4-character group
+    Base64(Vec<u8>),
+    /// Holds the incomplete last line, so escapes are never split
+    QuotedPrintable(Vec<u8>),
+    Identity,
+}
+
+impl TransferDecoder {
+    fn new(encoding: Option<&str>) -> Self {
+        match encoding.map(|encoding| encoding.trim().to_ascii_lowercase()).as_deref() {
+            Some("base64") => TransferDecoder::Base64(Vec::new()),
+            Some("quoted-printable") => TransferDecoder::QuotedPrintable(Vec::new()),
+            _ => TransferDecoder::Identity,
+        }
+    }
+
+    fn feed(&mut self, chunk: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            TransferDecoder::Base64(pending) => {
+                pending.extend(chunk.iter().filter(|byte| !byte.is_ascii_whitespace()));
+                let complete = pending.len() / 4 * 4;
+                let decoded = Self::decode_base64(&pending[..complete])?;
+                pending.drain(..complete);
+                Ok(decoded)
+            }
+            TransferDecoder::QuotedPrintable(pending) => {
+                pending.extend_from_slice(chunk);
+                let Some(last_newline) = pending.iter().rposition(|byte| *byte == b'\n') else {
+                    return Ok(Vec::new());
+                };
+                let lines: Vec<u8> = pending.drain(..=last_newline).collect();
+                Ok(Self::decode_quoted_printable(&lines))
+            }
+            TransferDecoder::Identity => Ok(chunk.to_vec()),
+        }
+    }
+
+    fn finish(self) -> std::io::Result<Vec<u8>> {
+        match self {
+            TransferDecoder::Base64(pending) => Self::decode_base64(&pending),
+            TransferDecoder::QuotedPrintable(pending) => Ok(Self::decode_quoted_printable(&pending)),
+            TransferDecoder::Identity => Ok(Vec::new()),
+        }
+    }
+
+    fn decode_base64(data: &[u8]) -> std::io::Result<Vec<u8>> {
+        // Some senders leave off the final padding
+        const ENGINE: GeneralPurpose = GeneralPurpose::new(
+            &base64::alphabet::STANDARD,
+            GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+        );
+        ENGINE
+            .decode(data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    fn decode_quoted_printable(data: &[u8]) -> Vec<u8> {
+        let mut decoded = Vec::with_capacity(data.len());
+        let mut i = 0;
+        while i < data.len() {
+            if data[i] == b'=' {
+                match (data.get(i + 1), data.get(i + 2)) {
+                    // Soft line breaks
+                    (Some(b'\r'), Some(b'\n')) => {
+                        i += 3;
+                        continue;
+                    }
+                    (Some(b'\n'), _) => {
+                        i += 2;
+                        continue;
+                    }
+                    (Some(high), Some(low)) => {
+                        let hex = (char::from(*high).to_digit(16), char::from(*low).to_digit(16));
+                        if let (Some(high), Some(low)) = hex {
+                            decoded.push((high * 16 + low) as u8);
+                            i += 3;
+                            continue;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            decoded.push(data[i]);
+            i += 1;
+        }
+        decoded
+    }
 }
 
 #[cfg(test)]
@@ -468,4 +734,58 @@ mod tests {
         let info2 = AttachmentInfo::from_stored(stored2);
         assert_eq!(info2.display_name, "café_menu.pdf");
     }
+
+    #[tokio::test]
+    async fn test_download_streaming() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("report.txt");
+
+        // "Hello, streamed world!" in base64, split mid-group and across lines
+        let chunks = vec!["SGVsbG8sIHN0c", "mVhbWVk\r\nIHdvcm", "xkIQ=="];
+        let stream = futures::stream::iter(
+            chunks.into_iter().map(|chunk| Ok(chunk.as_bytes().to_vec())),
+        );
+        let mut updates = Vec::new();
+        let written = AttachmentManager::download_streaming(
+            stream,
+            Some("BASE64"),
+            &target,
+            Some(34),
+            &AtomicBool::new(false),
+            |update| updates.push(update),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(written, 22);
+        assert_eq!(std::fs::read(&target).unwrap(), b"Hello, streamed world!");
+        assert_eq!(updates.last().unwrap().percentage(), Some(100));
+        assert!(!dir.path().join("report.txt.part").exists());
+
+        // A cancelled download leaves neither the target nor the partial file
+        let cancelled = dir.path().join("cancelled.txt");
+        let stream = futures::stream::iter(vec![Ok(b"data".to_vec())]);
+        let error = AttachmentManager::download_streaming(
+            stream,
+            None,
+            &cancelled,
+            None,
+            &AtomicBool::new(true),
+            |_| {},
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::Interrupted);
+        assert!(!cancelled.exists());
+        assert!(!dir.path().join("cancelled.txt.part").exists());
+    }
+
+    #[test]
+    fn test_quoted_printable_decoder() {
+        let mut decoder = TransferDecoder::new(Some("quoted-printable"));
+        let mut decoded = decoder.feed(b"caf=C3=A9 soft=\r\nbreak\r\nsplit =").unwrap();
+        decoded.extend(decoder.feed(b"3D end").unwrap());
+        decoded.extend(decoder.finish().unwrap());
+        assert_eq!(String::from_utf8(decoded).unwrap(), "café softbreak\r\nsplit = end");
+    }
 }
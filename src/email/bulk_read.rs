@@ -0,0 +1,276 @@
+//! Marking a whole folder or account as read
+//!
+//! The change is sent as batched UID STOREs so a large folder doesn't turn
+//! into one command per message, and progress is reported after each batch.
+//! Before anything is sent the flags of every unread message are recorded,
+//! so the operation can be cancelled part-way and undone within a grace
+//! window by clearing `\Seen` again on exactly those messages.
+
+use crate::email::{EmailDatabase, EmailOperationResult, EmailOperationsService};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// "Mark all read" configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkReadConfig {
+    /// Ask before marking a folder or account read
+    pub confirm: bool,
+    /// Seconds during which the last run can be undone; 0 turns undo off
+    pub undo_window_secs: u64,
+    /// Messages per UID STORE
+    pub batch_size: usize,
+}
+
+impl Default for MarkReadConfig {
+    fn default() -> Self {
+        Self {
+            confirm: true,
+            undo_window_secs: 60,
+            batch_size: 500,
+        }
+    }
+}
+
+impl MarkReadConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "mark_read.toml";
+
+    pub fn undo_window(&self) -> Duration {
+        Duration::from_secs(self.undo_window_secs)
+    }
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// An unread message and its flags before it was marked read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeenState {
+    pub message_id: Uuid,
+    pub folder_name: String,
+    pub imap_uid: u32,
+    pub flags: Vec<String>,
+}
+
+/// Messages handled so far out of the total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkReadProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+/// How far a run got
+#[derive(Debug)]
+pub struct MarkReadOutcome {
+    pub account_id: String,
+    /// Whether the run marked messages read, or restored them by an undo
+    pub read: bool,
+    /// Messages whose `\Seen` state was changed, with their flags from before the run
+    pub changed: Vec<SeenState>,
+    pub cancelled: bool,
+    /// Why the run stopped early, if a batch failed
+    pub error: Option<String>,
+}
+
+impl MarkReadOutcome {
+    /// Undo record for what this run changed
+    pub fn into_undo(self) -> MarkReadUndo {
+        MarkReadUndo {
+            account_id: self.account_id,
+            messages: self.changed,
+            marked_at: Instant::now(),
+        }
+    }
+}
+
+/// What the last "mark all read" changed, kept for the undo window
+#[derive(Debug, Clone)]
+pub struct MarkReadUndo {
+    pub account_id: String,
+    pub messages: Vec<SeenState>,
+    pub marked_at: Instant,
+}
+
+impl MarkReadUndo {
+    pub fn is_expired(&self, window: Duration) -> bool {
+        self.marked_at.elapsed() > window
+    }
+}
+
+/// Marks messages read, or back unread, through the email operations service
+pub struct BulkReadService {
+    database: Arc<EmailDatabase>,
+    operations: Arc<EmailOperationsService>,
+}
+
+impl BulkReadService {
+    pub fn new(database: Arc<EmailDatabase>, operations: Arc<EmailOperationsService>) -> Self {
+        Self {
+            database,
+            operations,
+        }
+    }
+
+    /// Unread messages of one folder, or of the whole account when `folder_name` is `None`
+    pub async fn unread(
+        &self,
+        account_id: &str,
+        folder_name: Option<&str>,
+    ) -> EmailOperationResult<Vec<SeenState>> {
+        Ok(self.database.get_unread_flags(account_id, folder_name).await?)
+    }
+
+    /// Add `\Seen` to `messages`, one batch at a time until done or cancelled
+    pub async fn mark_read(
+        &self,
+        account_id: &str,
+        messages: Vec<SeenState>,
+        batch_size: usize,
+        progress: &mpsc::UnboundedSender<MarkReadProgress>,
+        cancel: &AtomicBool,
+    ) -> MarkReadOutcome {
+        self.apply(account_id, messages, true, batch_size, progress, cancel)
+            .await
+    }
+
+    /// Undo [`mark_read`](Self::mark_read): clear `\Seen` and put the recorded flags back
+    pub async fn restore(
+        &self,
+        undo: MarkReadUndo,
+        batch_size: usize,
+        progress: &mpsc::UnboundedSender<MarkReadProgress>,
+        cancel: &AtomicBool,
+    ) -> MarkReadOutcome {
+        self.apply(
+            &undo.account_id,
+            undo.messages,
+            false,
+            batch_size,
+            progress,
+            cancel,
+        )
+        .await
+    }
+
+    async fn apply(
+        &self,
+        account_id: &str,
+        messages: Vec<SeenState>,
+        seen: bool,
+        batch_size: usize,
+        progress: &mpsc::UnboundedSender<MarkReadProgress>,
+        cancel: &AtomicBool,
+    ) -> MarkReadOutcome {
+        let total = messages.len();
+        let mut outcome = MarkReadOutcome {
+            account_id: account_id.to_string(),
+            read: seen,
+            changed: Vec::new(),
+            cancelled: false,
+            error: None,
+        };
+
+        for batch in batches(&messages, batch_size) {
+            if cancel.load(Ordering::Relaxed) {
+                outcome.cancelled = true;
+                break;
+            }
+
+            let folder_name = &batch[0].folder_name;
+            let uids: Vec<u32> = batch.iter().map(|message| message.imap_uid).collect();
+            if let Err(e) = self
+                .operations
+                .set_seen_uids(account_id, folder_name, &uids, seen)
+                .await
+            {
+                warn!("Marking {}/{} stopped: {}", account_id, folder_name, e);
+                outcome.error = Some(e.to_string());
+                break;
+            }
+
+            // The server has the change; keep the local copy in step
+            for message in batch {
+                let mut flags = message.flags.clone();
+                if seen {
+                    flags.push("\\Seen".to_string());
+                }
+                if let Err(e) = self.database.update_message_flags(message.message_id, &flags).await {
+                    warn!("Failed to update flags of {}: {}", message.message_id, e);
+                }
+            }
+            outcome.changed.extend_from_slice(batch);
+            let _ = progress.send(MarkReadProgress {
+                done: outcome.changed.len(),
+                total,
+            });
+        }
+
+        info!(
+            "Marked {} of {} messages in {} as {}",
+            outcome.changed.len(),
+            total,
+            account_id,
+            if seen { "read" } else { "unread" }
+        );
+        outcome
+    }
+}
+
+/// Split messages into runs of one folder, each at most `batch_size` long
+fn batches(messages: &[SeenState], batch_size: usize) -> Vec<&[SeenState]> {
+    messages
+        .chunk_by(|a, b| a.folder_name == b.folder_name)
+        .flat_map(|folder| folder.chunks(batch_size.max(1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unread(folder: &str, uid: u32) -> SeenState {
+        SeenState {
+            message_id: Uuid::new_v4(),
+            folder_name: folder.to_string(),
+            imap_uid: uid,
+            flags: vec!["\\Flagged".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_batches_split_by_folder_and_size() {
+        let messages = vec![
+            unread("Archive", 1),
+            unread("INBOX", 1),
+            unread("INBOX", 2),
+            unread("INBOX", 3),
+        ];
+
+        let sizes: Vec<(&str, usize)> = batches(&messages, 2)
+            .iter()
+            .map(|batch| (batch[0].folder_name.as_str(), batch.len()))
+            .collect();
+        assert_eq!(sizes, vec![("Archive", 1), ("INBOX", 2), ("INBOX", 1)]);
+
+        // A zero batch size still makes progress
+        assert_eq!(batches(&messages, 0).len(), 4);
+
+        let config: MarkReadConfig = toml::from_str("undo_window_secs = 0").unwrap();
+        assert!(config.confirm);
+        assert_eq!(config.undo_window(), Duration::ZERO);
+    }
+}
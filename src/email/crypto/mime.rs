@@ -0,0 +1,168 @@
+//! Locating signed content, signatures and encrypted payloads in a message
+
+use crate::email::security::{header_values, split_headers};
+use base64::Engine;
+
+/// Content and detached signature of a `multipart/signed` message
+pub(super) struct SignedParts {
+    /// The signed entity exactly as it was signed, with CRLF line endings
+    pub content: String,
+    pub signature: Vec<u8>,
+}
+
+pub(super) fn signed_parts(raw: &str, content_type: &str) -> Option<SignedParts> {
+    let (_, body) = split_headers(raw);
+    let parts = multipart_parts(body, &boundary(raw, content_type)?);
+    let [content, signature, ..] = parts.as_slice() else {
+        return None;
+    };
+
+    let (signature_headers, signature_body) = split_headers(signature);
+    let is_base64 = header_values(&signature_headers, "content-transfer-encoding")
+        .first()
+        .is_some_and(|encoding| encoding.eq_ignore_ascii_case("base64"));
+    let signature = if is_base64 {
+        let encoded: String = signature_body.split_whitespace().collect();
+        base64::engine::general_purpose::STANDARD.decode(encoded).ok()?
+    } else {
+        signature_body.as_bytes().to_vec()
+    };
+
+    Some(SignedParts {
+        content: to_crlf(content),
+        signature,
+    })
+}
+
+/// Encrypted data of a PGP/MIME message, or the armored block of an inline one
+pub(super) fn encrypted_payload<'a>(raw: &'a str, content_type: &str) -> Option<&'a str> {
+    if content_type.starts_with("multipart/encrypted") {
+        let (_, body) = split_headers(raw);
+        // The first part only holds the version; the second the encrypted data
+        return multipart_parts(body, &boundary(raw, content_type)?)
+            .into_iter()
+            .map(|part| split_headers(part).1)
+            .find(|part_body| part_body.contains("-----BEGIN PGP MESSAGE-----"));
+    }
+    armored_block(raw, "-----BEGIN PGP MESSAGE-----", "-----END PGP MESSAGE-----")
+}
+
+/// Inline PGP clear-signed text, from its header to the end of the signature
+pub(super) fn cleartext_signed(raw: &str) -> Option<&str> {
+    armored_block(raw, "-----BEGIN PGP SIGNED MESSAGE-----", "-----END PGP SIGNATURE-----")
+}
+
+fn armored_block<'a>(raw: &'a str, begin: &str, end: &str) -> Option<&'a str> {
+    let start = raw.find(begin)?;
+    let length = raw[start..].find(end)? + end.len();
+    Some(&raw[start..start + length])
+}
+
+/// Boundary parameter of the message's Content-Type, in its original case
+fn boundary(raw: &str, content_type: &str) -> Option<String> {
+    let position = content_type.find("boundary=")? + "boundary=".len();
+    let (headers, _) = split_headers(raw);
+    // The lowercased value only tells where the parameter is
+    let original = header_values(&headers, "content-type").into_iter().next()?;
+    let value = original.get(position..)?;
+    let value = match value.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next()?,
+        None => value.split([';', ' ']).next()?,
+    };
+    Some(value.to_string()).filter(|boundary| !boundary.is_empty())
+}
+
+/// Bodies of a multipart's parts, without the line break before each delimiter
+fn multipart_parts<'a>(body: &'a str, boundary: &str) -> Vec<&'a str> {
+    let delimiter = format!("--{}", boundary);
+    let close = format!("--{}--", boundary);
+    let mut parts = Vec::new();
+    let mut part_start = None;
+    let mut offset = 0;
+
+    for line in body.split_inclusive('\n') {
+        let content = line.trim_end();
+        if content == delimiter || content == close {
+            if let Some(start) = part_start {
+                // The line break before a delimiter belongs to the delimiter
+                let part: &str = &body[start..offset];
+                let part = part
+                    .strip_suffix("\r\n")
+                    .or_else(|| part.strip_suffix('\n'))
+                    .unwrap_or(part);
+                parts.push(part);
+            }
+            if content == close {
+                break;
+            }
+            part_start = Some(offset + line.len());
+        }
+        offset += line.len();
+    }
+
+    parts
+}
+
+/// Signatures are made over CRLF line endings
+fn to_crlf(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\n', "\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::top_content_type;
+    use super::*;
+
+    #[test]
+    fn test_signed_parts() {
+        let raw = "From: alice@example.com\r\n\
+                   Content-Type: multipart/signed; micalg=pgp-sha256;\r\n \
+                   protocol=\"application/pgp-signature\"; Boundary=\"Sig-B\"\r\n\
+                   \r\n\
+                   --Sig-B\r\n\
+                   Content-Type: text/plain\n\
+                   \n\
+                   Hello\n\
+                   \r\n\
+                   --Sig-B\r\n\
+                   Content-Type: application/pgp-signature\r\n\
+                   Content-Transfer-Encoding: base64\r\n\
+                   \r\n\
+                   c2ln\r\n\
+                   bmF0dXJl\r\n\
+                   --Sig-B--\r\n";
+
+        let parts = signed_parts(raw, &top_content_type(raw)).unwrap();
+        assert_eq!(parts.content, "Content-Type: text/plain\r\n\r\nHello\r\n");
+        assert_eq!(parts.signature, b"signature");
+    }
+
+    #[test]
+    fn test_encrypted_payload() {
+        let pgp_mime = "Content-Type: multipart/encrypted; boundary=enc;\r\n \
+                        protocol=\"application/pgp-encrypted\"\r\n\
+                        \r\n\
+                        --enc\r\n\
+                        Content-Type: application/pgp-encrypted\r\n\
+                        \r\n\
+                        Version: 1\r\n\
+                        --enc\r\n\
+                        Content-Type: application/octet-stream\r\n\
+                        \r\n\
+                        -----BEGIN PGP MESSAGE-----\r\n\
+                        hQEMA\r\n\
+                        -----END PGP MESSAGE-----\r\n\
+                        --enc--\r\n";
+        assert_eq!(
+            encrypted_payload(pgp_mime, &top_content_type(pgp_mime)),
+            Some("-----BEGIN PGP MESSAGE-----\r\nhQEMA\r\n-----END PGP MESSAGE-----")
+        );
+
+        let inline = "Subject: secret\n\nHi,\n-----BEGIN PGP MESSAGE-----\nhQEMA\n-----END PGP MESSAGE-----\nBye";
+        assert_eq!(
+            encrypted_payload(inline, "text/plain"),
+            Some("-----BEGIN PGP MESSAGE-----\nhQEMA\n-----END PGP MESSAGE-----")
+        );
+        assert_eq!(encrypted_payload("Subject: plain\n\nHello", "text/plain"), None);
+    }
+}
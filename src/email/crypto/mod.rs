@@ -0,0 +1,260 @@
+//! PGP and S/MIME signature verification, PGP decryption, and PGP signing
+//! and encryption of outgoing mail
+//!
+//! PGP signatures are checked with Sequoia against the public keys in the
+//! user's GnuPG keyring, and S/MIME signatures with OpenSSL against the
+//! system trust store, so no external tool runs to read signed mail.
+//! Verification runs once during sync and the result is stored with the
+//! message's security status. Decrypting needs the key's passphrase, so it
+//! happens on request in the viewer, which shows the decrypted MIME entity
+//! in place of the encrypted one. The secret key stays in gpg-agent, which
+//! unwraps the session key.
+//!
+//! Outgoing mail is encrypted to the recipients' public keys, looked up in
+//! the keyring directory set in `pgp.toml` or else the user's GnuPG home.
+//! Signing and encrypting go through `gpg`, and signing through gpg-agent,
+//! which asks for the passphrase itself.
+
+mod mime;
+mod openpgp;
+mod pgp;
+mod smime;
+
+use crate::email::security::{header_values, split_headers};
+use crate::email::CryptoScheme;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{Output, Stdio};
+use tokio::io::AsyncWriteExt;
+
+/// Result type for crypto operations
+pub type CryptoResult<T> = Result<T, CryptoError>;
+
+/// Errors from verifying or decrypting a message
+#[derive(Debug, thiserror::Error)]
+pub enum CryptoError {
+    #[error("{0} is not installed")]
+    ToolMissing(String),
+
+    #[error("Wrong passphrase")]
+    BadPassphrase,
+
+    #[error("Message is not PGP encrypted")]
+    NotEncrypted,
+
+    #[error("No secret key in gpg-agent can decrypt this message")]
+    NoSecretKey,
+
+    #[error("Unsupported OpenPGP data: {0}")]
+    Unsupported(String),
+
+    #[error("{tool} failed: {message}")]
+    Failed { tool: String, message: String },
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Keyring used for outgoing PGP mail
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PgpConfig {
+    /// GnuPG home directory holding recipients' public keys and the signing
+    /// key; `None` uses gpg's default (`$GNUPGHOME` or `~/.gnupg`)
+    pub keyring_dir: Option<PathBuf>,
+}
+
+impl PgpConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "pgp.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// Detached PGP signature of an outgoing MIME entity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetachedSignature {
+    /// ASCII-armored signature
+    pub armored: String,
+    /// Hash algorithm for the `micalg` parameter, e.g. `pgp-sha256`
+    pub micalg: String,
+}
+
+/// Outcome of checking a signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// The content is unchanged and the signer's key or certificate is trusted
+    Valid,
+    /// The content was changed after signing, or the signing key was revoked
+    Invalid,
+    /// The signer's key is missing or not trusted, so the signature proves nothing
+    UnknownSigner,
+}
+
+/// Verified signature of a message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureCheck {
+    pub scheme: CryptoScheme,
+    pub status: SignatureStatus,
+    /// User ID, certificate email or key ID of the signer, when known
+    pub signer: Option<String>,
+}
+
+/// Verify the signature of a raw RFC 5322 message
+///
+/// Handles PGP/MIME and S/MIME `multipart/signed`, S/MIME signed-data
+/// `application/pkcs7-mime` and inline PGP clear-signed text. A PGP signature
+/// is only valid when made by a key in `keyring` (the GnuPG home from
+/// `pgp.toml`, or gpg's default) with a user ID for `sender`, and an S/MIME
+/// signature when the trusted certificate carries `sender`'s address. `None`
+/// when the message isn't signed or the signature couldn't be checked, e.g.
+/// because it uses an algorithm the policy rejects.
+pub async fn verify(raw: &str, sender: &str, keyring: Option<&Path>) -> Option<SignatureCheck> {
+    let content_type = top_content_type(raw);
+
+    let result = if content_type.starts_with("multipart/signed") {
+        if content_type.contains("pkcs7-signature") {
+            verify_smime(raw, sender)
+        } else {
+            let parts = mime::signed_parts(raw, &content_type)?;
+            let certificates = openpgp::load_keyring(keyring).await;
+            openpgp::verify_detached(parts.content.as_bytes(), &parts.signature, &certificates, sender)
+        }
+    } else if content_type.contains("pkcs7-mime") && content_type.contains("signed-data") {
+        verify_smime(raw, sender)
+    } else {
+        let block = mime::cleartext_signed(raw)?;
+        let certificates = openpgp::load_keyring(keyring).await;
+        openpgp::verify_cleartext(block, &certificates, sender)
+    };
+
+    match result {
+        Ok(check) => Some(check),
+        Err(e) => {
+            tracing::warn!("Could not verify message signature: {}", e);
+            None
+        }
+    }
+}
+
+/// Check an S/MIME signature and that its certificate belongs to `sender`
+///
+/// A certificate from a trusted authority only vouches for the addresses
+/// in it, so a valid signature from anyone else's is an unknown signer.
+fn verify_smime(raw: &str, sender: &str) -> CryptoResult<SignatureCheck> {
+    let (mut check, addresses) = smime::verify(raw)?;
+    let sender = sender.trim();
+    if check.status == SignatureStatus::Valid
+        && !addresses.iter().any(|address| address.eq_ignore_ascii_case(sender))
+    {
+        check.status = SignatureStatus::UnknownSigner;
+    }
+    Ok(check)
+}
+
+/// Decrypt a PGP/MIME or inline PGP message
+///
+/// Returns a MIME entity ready for the MIME parser: the decrypted entity of a
+/// PGP/MIME message, or the decrypted text of an inline one. The secret key
+/// must be in the gpg-agent of `keyring`, or of gpg's default home.
+pub async fn decrypt(raw: &str, passphrase: &str, keyring: Option<&Path>) -> CryptoResult<String> {
+    let content_type = top_content_type(raw);
+    let payload = mime::encrypted_payload(raw, &content_type).ok_or(CryptoError::NotEncrypted)?;
+    let plaintext = openpgp::decrypt(payload, passphrase, keyring).await?;
+
+    if content_type.starts_with("multipart/encrypted") {
+        Ok(plaintext)
+    } else {
+        Ok(format!("Content-Type: text/plain; charset=utf-8\r\n\r\n{}", plaintext))
+    }
+}
+
+/// Recipients that have no valid, trusted encryption key in `keyring`
+pub async fn missing_keys(recipients: &[String], keyring: Option<&Path>) -> CryptoResult<Vec<String>> {
+    pgp::missing_keys(recipients, keyring).await
+}
+
+/// Encrypt a MIME entity to `recipients` and, without naming them in the
+/// message, `hidden_recipients`; signed by `signer` when given
+///
+/// Returns the armored PGP message for the second part of a
+/// `multipart/encrypted` message.
+pub async fn encrypt(
+    entity: &[u8],
+    recipients: &[String],
+    hidden_recipients: &[String],
+    signer: Option<&str>,
+    keyring: Option<&Path>,
+) -> CryptoResult<String> {
+    pgp::encrypt(entity, recipients, hidden_recipients, signer, keyring).await
+}
+
+/// Sign a MIME entity for a `multipart/signed` message
+///
+/// `entity` must already be in its final transfer encoding with CRLF line
+/// endings, since the signature covers it byte for byte.
+pub async fn sign(entity: &[u8], signer: &str, keyring: Option<&Path>) -> CryptoResult<DetachedSignature> {
+    pgp::sign_detached(entity, signer, keyring).await
+}
+
+/// Lowercased Content-Type of the message itself
+fn top_content_type(raw: &str) -> String {
+    let (headers, _) = split_headers(raw);
+    header_values(&headers, "content-type")
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// Run a tool with `input` on its standard input and collect its output
+async fn run(program: &str, args: &[&str], input: &[u8]) -> CryptoResult<Output> {
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => CryptoError::ToolMissing(program.to_string()),
+            _ => CryptoError::Io(e),
+        })?;
+
+    // Write on a separate task so a tool that answers before reading all of
+    // its input can't block us on a full pipe
+    if let Some(mut stdin) = child.stdin.take() {
+        let input = input.to_vec();
+        tokio::spawn(async move {
+            let _ = stdin.write_all(&input).await;
+        });
+    }
+
+    Ok(child.wait_with_output().await?)
+}
+
+/// Error for a tool that exited unsuccessfully, with the last line it printed
+fn failed(tool: &str, output: &Output) -> CryptoError {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = stderr
+        .lines()
+        .filter(|line| !line.starts_with("[GNUPG:]"))
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .unwrap_or("no error message")
+        .to_string();
+    CryptoError::Failed {
+        tool: tool.to_string(),
+        message,
+    }
+}
@@ -0,0 +1,558 @@
+//! OpenPGP through Sequoia and gpg-agent
+//!
+//! Public keys are read straight from a GnuPG home (`pubring.kbx`, or the
+//! older `pubring.gpg`) and signatures are checked under Sequoia's standard
+//! policy, which rejects weak algorithms and keys that are expired, revoked
+//! or not bound to their certificate. Secret keys never leave gpg-agent:
+//! decrypting asks the agent to unwrap the session key with the passphrase
+//! the user typed.
+
+use super::{CryptoError, CryptoResult, SignatureCheck, SignatureStatus};
+use crate::email::CryptoScheme;
+use openpgp::cert::amalgamation::ValidAmalgamation;
+use openpgp::cert::{Cert, CertParser};
+use openpgp::crypto::{self, mpi, Password, SessionKey};
+use openpgp::packet::key::{PublicParts, UnspecifiedRole};
+use openpgp::packet::{Key, PKESK, SKESK};
+use openpgp::parse::stream::{
+    DecryptionHelper, DecryptorBuilder, DetachedVerifierBuilder, MessageLayer, MessageStructure,
+    VerificationError, VerificationHelper, VerificationResult, VerifierBuilder,
+};
+use openpgp::parse::Parse;
+use openpgp::policy::StandardPolicy;
+use openpgp::types::{RevocationStatus, SymmetricAlgorithm};
+use openpgp::{Fingerprint, KeyHandle, KeyID};
+use sequoia_gpg_agent::{Agent, KeyPair};
+use sequoia_ipc::keybox::{Keybox, KeyboxRecord};
+use sequoia_openpgp as openpgp;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+static POLICY: StandardPolicy = StandardPolicy::new();
+
+/// Public keys of a GnuPG home
+///
+/// `keyring` is the directory set in `pgp.toml`; without one, gpg's own
+/// default of `$GNUPGHOME` or `~/.gnupg` is used.
+pub(super) async fn load_keyring(keyring: Option<&Path>) -> Vec<Cert> {
+    let Some(home) = gnupg_home(keyring) else {
+        return Vec::new();
+    };
+
+    let mut certificates = Vec::new();
+    if let Ok(keybox) = tokio::fs::read(home.join("pubring.kbx")).await {
+        if let Ok(records) = Keybox::from_bytes(&keybox) {
+            certificates.extend(records.filter_map(|record| match record {
+                Ok(KeyboxRecord::OpenPGP(record)) => record.cert().ok(),
+                _ => None,
+            }));
+        }
+    }
+    if let Ok(pubring) = tokio::fs::read(home.join("pubring.gpg")).await {
+        if let Ok(parser) = CertParser::from_bytes(&pubring) {
+            certificates.extend(parser.filter_map(Result::ok));
+        }
+    }
+    certificates
+}
+
+fn gnupg_home(keyring: Option<&Path>) -> Option<PathBuf> {
+    keyring
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::var_os("GNUPGHOME").map(PathBuf::from))
+        .or_else(|| dirs::home_dir().map(|home| home.join(".gnupg")))
+}
+
+/// Check a detached signature, armored or binary, over `content`
+///
+/// The signature is valid when it checks out with a live, unrevoked key
+/// from `keyring` whose certificate has a user ID with `sender`'s address.
+pub(super) fn verify_detached(
+    content: &[u8],
+    signature: &[u8],
+    keyring: &[Cert],
+    sender: &str,
+) -> CryptoResult<SignatureCheck> {
+    let helper = Verification::new(keyring, sender);
+    let mut verifier = DetachedVerifierBuilder::from_bytes(signature)
+        .and_then(|builder| builder.with_policy(&POLICY, None, helper))
+        .map_err(unsupported)?;
+    verifier.verify_bytes(content).map_err(unsupported)?;
+    verifier.into_helper().result()
+}
+
+/// Check a clear-signed text block
+pub(super) fn verify_cleartext(
+    block: &str,
+    keyring: &[Cert],
+    sender: &str,
+) -> CryptoResult<SignatureCheck> {
+    let helper = Verification::new(keyring, sender);
+    let mut verifier = VerifierBuilder::from_bytes(block.as_bytes())
+        .and_then(|builder| builder.with_policy(&POLICY, None, helper))
+        .map_err(unsupported)?;
+    // The signatures are checked once the text has been read to the end
+    std::io::copy(&mut verifier, &mut std::io::sink())?;
+    verifier.into_helper().result()
+}
+
+/// Decrypt an armored PGP message with a secret key held by gpg-agent
+///
+/// Only keys whose public half is in `keyring` are tried, and the agent
+/// gets the passphrase directly instead of asking for it with its own
+/// pinentry.
+pub(super) async fn decrypt(
+    armored: &str,
+    passphrase: &str,
+    keyring: Option<&Path>,
+) -> CryptoResult<String> {
+    let certificates = load_keyring(keyring).await;
+    let agent = match keyring {
+        Some(home) => Agent::connect_to(home).await,
+        None => Agent::connect_to_default().await,
+    }
+    .map_err(|e| CryptoError::Failed {
+        tool: "gpg-agent".to_string(),
+        message: e.to_string(),
+    })?;
+
+    let armored = armored.to_string();
+    let passphrase = Password::from(passphrase);
+    // Sequoia's streaming parser blocks while the agent answers
+    tokio::task::spawn_blocking(move || {
+        let helper = Decryption {
+            certificates: &certificates,
+            agent,
+            passphrase,
+        };
+        let mut decryptor = DecryptorBuilder::from_bytes(armored.as_bytes())
+            .and_then(|builder| builder.with_policy(&POLICY, None, helper))
+            .map_err(|e| match e.downcast::<CryptoError>() {
+                Ok(error) => error,
+                Err(e) => unsupported(e),
+            })?;
+        let mut plaintext = Vec::new();
+        decryptor.read_to_end(&mut plaintext)?;
+        Ok(String::from_utf8_lossy(&plaintext).into_owned())
+    })
+    .await
+    .map_err(|e| CryptoError::Io(std::io::Error::other(e)))?
+}
+
+/// Collects the outcome of each signature in a message
+struct Verification<'a> {
+    keyring: &'a [Cert],
+    sender: String,
+    check: Option<SignatureCheck>,
+}
+
+impl<'a> Verification<'a> {
+    fn new(keyring: &'a [Cert], sender: &str) -> Self {
+        Self {
+            keyring,
+            sender: sender.trim().to_lowercase(),
+            check: None,
+        }
+    }
+
+    fn result(self) -> CryptoResult<SignatureCheck> {
+        self.check
+            .ok_or_else(|| CryptoError::Unsupported("no signature could be checked".to_string()))
+    }
+
+    /// Status of one signature, or `None` when it can't be checked at all,
+    /// e.g. because it uses an algorithm the policy rejects
+    fn check(&self, result: VerificationResult) -> Option<SignatureCheck> {
+        let check = |status, signer| SignatureCheck {
+            scheme: CryptoScheme::Pgp,
+            status,
+            signer: Some(signer),
+        };
+
+        match result {
+            Ok(good) => {
+                let (matching, signer) = self.signer(good.ka.valid_cert());
+                let status = if matching {
+                    SignatureStatus::Valid
+                } else {
+                    // A good signature, but from a key that isn't the sender's
+                    SignatureStatus::UnknownSigner
+                };
+                Some(check(status, signer.unwrap_or_else(|| good.ka.key().keyid().to_hex())))
+            }
+            // The content was changed, or the key has expired or been revoked
+            Err(VerificationError::BadSignature { ka, .. } | VerificationError::BadKey { ka, .. }) => {
+                let (_, signer) = self.signer(ka.valid_cert());
+                Some(check(
+                    SignatureStatus::Invalid,
+                    signer.unwrap_or_else(|| ka.key().keyid().to_hex()),
+                ))
+            }
+            Err(VerificationError::MissingKey { sig }) => {
+                let issuer = sig.get_issuers().into_iter().next()?;
+                Some(check(SignatureStatus::UnknownSigner, KeyID::from(issuer).to_hex()))
+            }
+            // A key without a valid binding claims no user ID at all
+            Err(VerificationError::UnboundKey { cert, .. }) => {
+                Some(check(SignatureStatus::UnknownSigner, cert.keyid().to_hex()))
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Whether the certificate has a live user ID with the sender's
+    /// address, and the user ID to show for the signer
+    fn signer(&self, cert: &openpgp::cert::ValidCert) -> (bool, Option<String>) {
+        let user_ids: Vec<_> = cert
+            .userids()
+            .filter(|user_id| !matches!(user_id.revocation_status(), RevocationStatus::Revoked(_)))
+            .collect();
+        let matching = user_ids.iter().find(|user_id| {
+            user_id
+                .userid()
+                .email_normalized()
+                .ok()
+                .flatten()
+                .is_some_and(|address| address == self.sender)
+        });
+        let signer = matching
+            .or(user_ids.first())
+            .map(|user_id| String::from_utf8_lossy(user_id.userid().value()).into_owned());
+        (matching.is_some(), signer)
+    }
+}
+
+impl VerificationHelper for Verification<'_> {
+    fn get_certs(&mut self, ids: &[KeyHandle]) -> openpgp::Result<Vec<Cert>> {
+        Ok(certificates_for(self.keyring, ids))
+    }
+
+    fn check(&mut self, structure: MessageStructure) -> openpgp::Result<()> {
+        for layer in structure {
+            let MessageLayer::SignatureGroup { results } = layer else {
+                continue;
+            };
+            for result in results {
+                let Some(check) = Verification::check(self, result) else {
+                    continue;
+                };
+                // A bad signature outweighs anything else in the message
+                let replace = match &self.check {
+                    None => true,
+                    Some(current) => {
+                        current.status != SignatureStatus::Invalid
+                            && (check.status == SignatureStatus::Invalid
+                                || (current.status == SignatureStatus::UnknownSigner
+                                    && check.status == SignatureStatus::Valid))
+                    }
+                };
+                if replace {
+                    self.check = Some(check);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Unwraps a message's session key with the recipient's key in gpg-agent
+struct Decryption<'a> {
+    certificates: &'a [Cert],
+    agent: Agent,
+    passphrase: Password,
+}
+
+impl Decryption<'_> {
+    /// Encryption keys in the keyring for a recipient; a hidden recipient
+    /// could be any of them
+    fn recipient_keys(&self, recipient: Option<&KeyHandle>) -> Vec<Key<PublicParts, UnspecifiedRole>> {
+        self.certificates
+            .iter()
+            .filter_map(|cert| cert.with_policy(&POLICY, None).ok())
+            .flat_map(|cert| {
+                cert.keys()
+                    .for_transport_encryption()
+                    .for_storage_encryption()
+                    .map(|ka| ka.key().clone().role_into_unspecified())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|key| recipient.is_none_or(|recipient| key.key_handle().aliases(recipient)))
+            .collect()
+    }
+}
+
+impl VerificationHelper for Decryption<'_> {
+    fn get_certs(&mut self, ids: &[KeyHandle]) -> openpgp::Result<Vec<Cert>> {
+        Ok(certificates_for(self.certificates, ids))
+    }
+
+    fn check(&mut self, _structure: MessageStructure) -> openpgp::Result<()> {
+        // Signatures inside encrypted mail aren't reported
+        Ok(())
+    }
+}
+
+impl DecryptionHelper for Decryption<'_> {
+    fn decrypt(
+        &mut self,
+        pkesks: &[PKESK],
+        _skesks: &[SKESK],
+        sym_algo: Option<SymmetricAlgorithm>,
+        decrypt: &mut dyn FnMut(Option<SymmetricAlgorithm>, &SessionKey) -> bool,
+    ) -> openpgp::Result<Option<Cert>> {
+        let mut bad_passphrase = false;
+        for pkesk in pkesks {
+            for key in self.recipient_keys(pkesk.recipient().as_ref()) {
+                let fingerprint = key.fingerprint();
+                let mut agent_key = AgentKey {
+                    pair: self.agent.keypair(&key)?.with_password(self.passphrase.clone()),
+                    error: None,
+                };
+                if let Some((algo, session_key)) = pkesk.decrypt(&mut agent_key, sym_algo) {
+                    if decrypt(algo, &session_key) {
+                        return Ok(self.certificate_of(&fingerprint));
+                    }
+                }
+                bad_passphrase |= agent_key
+                    .error
+                    .is_some_and(|error| error.to_lowercase().contains("bad passphrase"));
+            }
+        }
+
+        if bad_passphrase {
+            Err(CryptoError::BadPassphrase.into())
+        } else {
+            Err(CryptoError::NoSecretKey.into())
+        }
+    }
+}
+
+impl Decryption<'_> {
+    fn certificate_of(&self, fingerprint: &Fingerprint) -> Option<Cert> {
+        self.certificates
+            .iter()
+            .find(|cert| cert.keys().any(|ka| ka.key().fingerprint() == *fingerprint))
+            .cloned()
+    }
+}
+
+/// A gpg-agent key that remembers why it failed, since Sequoia only
+/// reports that a session key couldn't be unwrapped
+struct AgentKey {
+    pair: KeyPair,
+    error: Option<String>,
+}
+
+impl crypto::Decryptor for AgentKey {
+    fn public(&self) -> &Key<PublicParts, UnspecifiedRole> {
+        self.pair.public()
+    }
+
+    fn decrypt(&mut self, ciphertext: &mpi::Ciphertext, plaintext_len: Option<usize>) -> openpgp::Result<SessionKey> {
+        self.pair.decrypt(ciphertext, plaintext_len).inspect_err(|error| {
+            self.error = Some(format!("{:#}", error));
+        })
+    }
+}
+
+/// Certificates holding any of the keys `ids`
+fn certificates_for(keyring: &[Cert], ids: &[KeyHandle]) -> Vec<Cert> {
+    keyring
+        .iter()
+        .filter(|cert| cert.keys().key_handles(ids.iter()).next().is_some())
+        .cloned()
+        .collect()
+}
+
+fn unsupported(error: anyhow::Error) -> CryptoError {
+    CryptoError::Unsupported(format!("{:#}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE_KEY: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----\n\
+                         \n\
+                         mDMEatP3jhYJKwYBBAHaRw8BAQdAAL2qzVvRTM41spyiRl5/dz4R3YQMnZXI8yR2\n\
+                         ANb36t60GUFsaWNlIDxhbGljZUBleGFtcGxlLmNvbT6IkAQTFggAOBYhBJttvN1d\n\
+                         zaLiCDnMBuOxBQh0pSIMBQJq0/eOAhsDBQsJCAcCBhUKCQgLAgQWAgMBAh4BAheA\n\
+                         AAoJEOOxBQh0pSIMLFEA/jMtKMwgg+2bmFXkP9rRy1n1ELauCyvlGuQykE/8+maK\n\
+                         AQD4GPJ28deBVZKFO442ixtR70nF6+wm8yY5ShJuT285AA==\n\
+                         =0lJf\n\
+                         -----END PGP PUBLIC KEY BLOCK-----\n";
+
+    const BOB_KEY: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----\n\
+                         \n\
+                         mQENBGrT944BCADPbfX0IeaWiBkw//8xH+jKSiWSKhgLWT9QOgCzjzMP1sfQ2t9G\n\
+                         KqscX/R4w2u9g0uMn8dXRt+3kmc7nr1pmMP7wi7JugfA6t778diQehLelw7UWkkU\n\
+                         UbfkG/PrBiSzF+3MFUJDN/nSEMpaRTBm9lSVl0chndKMOS3Lpd2zE8WWE6GFVjYr\n\
+                         ZJjbenQV0GybXnL7nXjcJoeiyTnD7p8YAdd7dTNYZaz4xudmxtYfwKCJk/oGPvEg\n\
+                         VRz+U3BD7LqXRIDOnA6U1kuJw2bMKAp5Nv4HnWncvbtYNpvoOcOxlP+ypeniehBN\n\
+                         wcgMh7MRlOHyGPyD8SxAtI3N2smhGe0tz7KvABEBAAG0FUJvYiA8Ym9iQGV4YW1w\n\
+                         bGUuY29tPokBTgQTAQoAOBYhBJheBbnXK+1alhayt8hx0luxKrcfBQJq0/eOAhsD\n\
+                         BQsJCAcCBhUKCQgLAgQWAgMBAh4BAheAAAoJEMhx0luxKrcfiUgH/1WmSrtdOemj\n\
+                         HQRdChBjfgWS0iKP7aDJ5BKVjLUaiiV+HlkHd7ML9bPDAlHAhozovjSuy2vMdaxK\n\
+                         QMxd9KUJx2UmWCT3rrZkmRT7Efpn/HFx2jzq65kcSEKHrr8C56UCd8kGxqjqT2zZ\n\
+                         BmUAEagatIWTJBQlp2KBTnw99GHQ6zQ2PsaqIOnpcAqwEIL7baxxUNCVYKDUQmZJ\n\
+                         LGBSGGWS6XkKixoEDGmLBZ7JgJph9FQYklvRLdLpT0BaOJu10rfXCDIfyMYP/vMk\n\
+                         7kHzLOHxyx0iFzY5n8foh6jdL6DPkMUF6sgIeyBJV2YMWEwT1MPAbwAP0v3Zy3bB\n\
+                         kie893mK6lE=\n\
+                         =03GF\n\
+                         -----END PGP PUBLIC KEY BLOCK-----\n";
+
+    /// Alice's detached SHA-256 signature over `CONTENT`
+    const ALICE_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----\n\
+                         \n\
+                         iIgEABYIADAWIQSbbbzdXc2i4gg5zAbjsQUIdKUiDAUCatP3jxIcYWxpY2VAZXhh\n\
+                         bXBsZS5jb20ACgkQ47EFCHSlIgyx/gD/TMPPZdynTiIwRA60RSfixvch3FvNTUmX\n\
+                         UTU6ees3T74BALf9OTTip1Xxg16+A68HmG8fyNFJ2dwJ5/4KZu1uQEsM\n\
+                         =dFwC\n\
+                         -----END PGP SIGNATURE-----\n";
+
+    /// Bob's detached SHA-512 signature over `CONTENT`
+    const BOB_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----\n\
+                         \n\
+                         iQFEBAABCgAuFiEEmF4Fudcr7VqWFrK3yHHSW7Eqtx8FAmrT948QHGJvYkBleGFt\n\
+                         cGxlLmNvbQAKCRDIcdJbsSq3H1ldCACuhdLeajNXMZX6tfzM7EXgxMxHeflR/2VC\n\
+                         zLvKkWTYl8jtbzWRuOK3+GcYgsV87C+aHgmYMPKoMJPTcW3r3sVAzb/mDdDpb4Fg\n\
+                         fib+EPWMM4Kok1Ni4ZTYIEgBeVV3g8tkr0Sbmg3NnH5Q7Wm6jDcPQKyzdRFUo6Wk\n\
+                         R6UF87AjQSOj+IcW/cdEfzC7Urwl0QRCl2xLe2EJOFCIKoLQNTDDg/MNpT/Kf4kA\n\
+                         ybQRoXjyP6L11Wl033fFL85jHHqGrFQqF2GHKJsAqqS/joXlXpCks78KhsmSCl/b\n\
+                         B5XQtF5uHsQgOwuU9lmGkpc4d/z6NfCqXO/0rvB/j3+wTwN5KRsd\n\
+                         =lKen\n\
+                         -----END PGP SIGNATURE-----\n";
+
+    const CLEARSIGNED: &str = "-----BEGIN PGP SIGNED MESSAGE-----\n\
+                         Hash: SHA256\n\
+                         \n\
+                         Meet me at noon.\n\
+                         - - Alice\n\
+                         -----BEGIN PGP SIGNATURE-----\n\
+                         \n\
+                         iIgEARYIADAWIQSbbbzdXc2i4gg5zAbjsQUIdKUiDAUCatP3jxIcYWxpY2VAZXhh\n\
+                         bXBsZS5jb20ACgkQ47EFCHSlIgwzUwEA2WwZWttrTBBdQGVMK8tFOR330E3X180C\n\
+                         ItgbpjW+4KUBANueQNjg7yuabr5xVfNzlWCQo8GYNH70jxxkuP5lyUoO\n\
+                         =ThFZ\n\
+                         -----END PGP SIGNATURE-----\n";
+
+    const CONTENT: &[u8] = b"Content-Type: text/plain\r\n\r\nHello Bob,\r\nsee you at 5.\r\n";
+
+    fn certificates(key: &str) -> Vec<Cert> {
+        CertParser::from_bytes(key.as_bytes())
+            .unwrap()
+            .collect::<openpgp::Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    fn keyring() -> Vec<Cert> {
+        [ALICE_KEY, BOB_KEY].iter().flat_map(|key| certificates(key)).collect()
+    }
+
+    /// Binary form of an armored block
+    fn dearmor(armored: &str) -> Vec<u8> {
+        let mut reader = openpgp::armor::Reader::from_bytes(
+            armored.as_bytes(),
+            openpgp::armor::ReaderMode::Tolerant(None),
+        );
+        let mut binary = Vec::new();
+        reader.read_to_end(&mut binary).unwrap();
+        binary
+    }
+
+    #[test]
+    fn test_verify_detached() {
+        let keyring = keyring();
+
+        let check = verify_detached(CONTENT, ALICE_SIGNATURE.as_bytes(), &keyring, "Alice@Example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::Valid);
+        assert_eq!(check.signer.as_deref(), Some("Alice <alice@example.com>"));
+
+        let check = verify_detached(CONTENT, BOB_SIGNATURE.as_bytes(), &keyring, "bob@example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::Valid);
+        assert_eq!(check.signer.as_deref(), Some("Bob <bob@example.com>"));
+
+        // A good signature, but not from the sender's key
+        let check = verify_detached(CONTENT, ALICE_SIGNATURE.as_bytes(), &keyring, "bob@example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::UnknownSigner);
+
+        let tampered = [CONTENT, b"P.S. bring cash\r\n"].concat();
+        let check = verify_detached(&tampered, BOB_SIGNATURE.as_bytes(), &keyring, "bob@example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::Invalid);
+
+        // The binary form of the signature works too
+        let binary = dearmor(ALICE_SIGNATURE);
+        let check = verify_detached(CONTENT, &binary, &keyring, "alice@example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::Valid);
+    }
+
+    #[test]
+    fn test_verify_without_key() {
+        let check = verify_detached(CONTENT, ALICE_SIGNATURE.as_bytes(), &[], "alice@example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::UnknownSigner);
+        assert_eq!(check.signer.as_deref(), Some("E3B1050874A5220C"));
+
+        assert!(verify_detached(CONTENT, b"not a signature", &[], "alice@example.com").is_err());
+    }
+
+    #[test]
+    fn test_verify_cleartext() {
+        let keyring = keyring();
+        let check = verify_cleartext(CLEARSIGNED, &keyring, "alice@example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::Valid);
+
+        let tampered = CLEARSIGNED.replace("noon", "midnight");
+        let check = verify_cleartext(&tampered, &keyring, "alice@example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::Invalid);
+    }
+
+    #[test]
+    fn test_forged_user_id_ignored() {
+        // Swapping the user ID breaks its self-signature, so the key claims
+        // no address at all
+        let mut key = dearmor(ALICE_KEY);
+        let at = key.windows(5).position(|window| window == b"alice").unwrap();
+        key[at..at + 5].copy_from_slice(b"carol");
+        let keyring = vec![Cert::from_bytes(&key).unwrap()];
+        let user_ids = keyring[0]
+            .with_policy(&POLICY, None)
+            .map(|cert| cert.userids().count())
+            .unwrap_or(0);
+        assert_eq!(user_ids, 0);
+
+        let check = verify_detached(CONTENT, ALICE_SIGNATURE.as_bytes(), &keyring, "carol@example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::UnknownSigner);
+    }
+
+    #[tokio::test]
+    async fn test_load_keyring_from_keybox() {
+        // A keybox header blob followed by one OpenPGP blob per key, each
+        // ending in a SHA-1 checksum of the rest of the blob
+        let mut keybox = Vec::new();
+        let mut header = vec![0, 0, 0, 32, 1, 1, 0, 0];
+        header.extend_from_slice(b"KBXf");
+        header.resize(32, 0);
+        keybox.extend_from_slice(&header);
+        for key in [ALICE_KEY, BOB_KEY] {
+            let keyblock = dearmor(key);
+            let length = 20 + keyblock.len() as u32 + 20;
+            let mut blob = length.to_be_bytes().to_vec();
+            blob.extend_from_slice(&[2, 1, 0, 0]);
+            blob.extend_from_slice(&20u32.to_be_bytes());
+            blob.extend_from_slice(&(keyblock.len() as u32).to_be_bytes());
+            blob.extend_from_slice(&[0; 4]);
+            blob.extend_from_slice(&keyblock);
+            let checksum = ring::digest::digest(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY, &blob);
+            blob.extend_from_slice(checksum.as_ref());
+            keybox.extend_from_slice(&blob);
+        }
+
+        let home = tempfile::tempdir().unwrap();
+        std::fs::write(home.path().join("pubring.kbx"), keybox).unwrap();
+        let keyring = load_keyring(Some(home.path())).await;
+        assert_eq!(keyring.len(), 2);
+
+        let check = verify_detached(CONTENT, BOB_SIGNATURE.as_bytes(), &keyring, "bob@example.com").unwrap();
+        assert_eq!(check.status, SignatureStatus::Valid);
+    }
+}
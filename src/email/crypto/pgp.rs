@@ -0,0 +1,186 @@
+//! Outgoing PGP mail through the system's `gpg`
+
+use super::{failed, run, CryptoResult, DetachedSignature};
+use std::path::Path;
+
+const GPG: &str = "gpg";
+
+/// Recipients without a valid, trusted encryption key in the keyring
+pub(super) async fn missing_keys(
+    recipients: &[String],
+    keyring: Option<&Path>,
+) -> CryptoResult<Vec<String>> {
+    let mut missing = Vec::new();
+    for recipient in recipients {
+        let pattern = format!("<{}>", recipient);
+        let mut args = homedir_args(keyring);
+        args.extend(["--batch", "--no-tty", "--with-colons", "--list-keys", &pattern]);
+        let output = run(GPG, &args, b"").await?;
+        if !has_encryption_key(&String::from_utf8_lossy(&output.stdout), recipient) {
+            missing.push(recipient.clone());
+        }
+    }
+    Ok(missing)
+}
+
+/// Encrypt `entity` to every recipient as an armored PGP message, signing it
+/// first with `signer`'s key when given
+///
+/// Hidden recipients' key IDs are left out of the message, so the other
+/// recipients can't see who else can read it.
+pub(super) async fn encrypt(
+    entity: &[u8],
+    recipients: &[String],
+    hidden_recipients: &[String],
+    signer: Option<&str>,
+    keyring: Option<&Path>,
+) -> CryptoResult<String> {
+    // The angle brackets make gpg match the whole email address
+    let recipients: Vec<(&str, String)> = recipients
+        .iter()
+        .map(|recipient| ("--recipient", format!("<{}>", recipient)))
+        .chain(
+            hidden_recipients
+                .iter()
+                .map(|recipient| ("--hidden-recipient", format!("<{}>", recipient))),
+        )
+        .collect();
+
+    let mut args = homedir_args(keyring);
+    args.extend(["--batch", "--no-tty", "--armor"]);
+    if let Some(signer) = signer {
+        args.extend(["--sign", "--local-user", signer]);
+    }
+    args.push("--encrypt");
+    for (option, pattern) in &recipients {
+        args.extend([*option, pattern.as_str()]);
+    }
+
+    let output = run(GPG, &args, entity).await?;
+    if !output.status.success() {
+        return Err(failed(GPG, &output));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Armored detached signature over `entity`
+pub(super) async fn sign_detached(
+    entity: &[u8],
+    signer: &str,
+    keyring: Option<&Path>,
+) -> CryptoResult<DetachedSignature> {
+    let mut args = homedir_args(keyring);
+    args.extend([
+        "--batch",
+        "--no-tty",
+        "--status-fd",
+        "2",
+        "--armor",
+        "--detach-sign",
+        "--local-user",
+        signer,
+    ]);
+
+    let output = run(GPG, &args, entity).await?;
+    if !output.status.success() {
+        return Err(failed(GPG, &output));
+    }
+    let micalg = micalg_from_status(&String::from_utf8_lossy(&output.stderr))
+        .ok_or_else(|| failed(GPG, &output))?;
+    Ok(DetachedSignature {
+        armored: String::from_utf8_lossy(&output.stdout).into_owned(),
+        micalg,
+    })
+}
+
+fn homedir_args(keyring: Option<&Path>) -> Vec<&str> {
+    match keyring.and_then(Path::to_str) {
+        Some(directory) => vec!["--homedir", directory],
+        None => Vec::new(),
+    }
+}
+
+/// Whether `--with-colons` key listing holds a key that can still encrypt
+/// to `address` and whose user ID for it is trusted
+fn has_encryption_key(listing: &str, address: &str) -> bool {
+    let address = format!("<{}>", address.to_lowercase());
+    let mut usable_key = false;
+    listing.lines().any(|line| {
+        let fields: Vec<&str> = line.split(':').collect();
+        // Field 2 is the validity, field 10 the user ID and field 12 the
+        // key's capabilities; the primary key's upper-case letters cover its
+        // subkeys
+        match fields.first() {
+            Some(&"pub") => {
+                usable_key = !matches!(fields.get(1), Some(&("r" | "e" | "d" | "i")))
+                    && fields.get(11).is_some_and(|caps| caps.contains('E'));
+                false
+            }
+            // Only full or ultimate validity means the key was checked to
+            // belong to the address, as gpg's own trust model sees it
+            Some(&"uid") => {
+                usable_key
+                    && matches!(fields.get(1), Some(&("f" | "u")))
+                    && fields
+                        .get(9)
+                        .is_some_and(|user_id| user_id.to_lowercase().contains(&address))
+            }
+            _ => false,
+        }
+    })
+}
+
+/// RFC 3156 `micalg` from gpg's `SIG_CREATED` status line
+fn micalg_from_status(status: &str) -> Option<String> {
+    let line = status
+        .lines()
+        .find_map(|line| line.strip_prefix("[GNUPG:] SIG_CREATED "))?;
+    // Type, public key algorithm, hash algorithm, ...
+    let hash = match line.split_whitespace().nth(2)? {
+        "1" => "md5",
+        "2" => "sha1",
+        "3" => "ripemd160",
+        "8" => "sha256",
+        "9" => "sha384",
+        "10" => "sha512",
+        "11" => "sha224",
+        _ => return None,
+    };
+    Some(format!("pgp-{}", hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_encryption_key() {
+        let listing = "tru::1:1704067200:0:3:1:5\n\
+                       pub:u:255:22:0123456789ABCDEF:1704067200:::u:::scESC::::::ed25519:::0:\n\
+                       uid:u::::1704067200::HASH::Alice <alice@example.com>::::::::::0:\n";
+        assert!(has_encryption_key(listing, "Alice@example.com"));
+
+        let revoked = listing.replace("pub:u:", "pub:r:");
+        assert!(!has_encryption_key(&revoked, "alice@example.com"));
+
+        let sign_only = listing.replace("scESC", "scSC");
+        assert!(!has_encryption_key(&sign_only, "alice@example.com"));
+        assert!(!has_encryption_key("", "alice@example.com"));
+
+        // Imported but never certified, or certified for another address
+        let untrusted = listing.replace("uid:u:", "uid:-:");
+        assert!(!has_encryption_key(&untrusted, "alice@example.com"));
+        let marginal = listing.replace("uid:u:", "uid:m:");
+        assert!(!has_encryption_key(&marginal, "alice@example.com"));
+        assert!(!has_encryption_key(listing, "mallory@example.com"));
+    }
+
+    #[test]
+    fn test_micalg_from_status() {
+        assert_eq!(
+            micalg_from_status("[GNUPG:] KEY_CONSIDERED ABCDEF 2\n[GNUPG:] SIG_CREATED D 22 8 00 1704067200 ABCDEF\n"),
+            Some("pgp-sha256".to_string())
+        );
+        assert_eq!(micalg_from_status("[GNUPG:] BEGIN_SIGNING H10\n"), None);
+    }
+}
@@ -0,0 +1,168 @@
+//! S/MIME through the `openssl` crate
+
+use super::{CryptoError, CryptoResult, SignatureCheck, SignatureStatus};
+use crate::email::CryptoScheme;
+use openssl::error::ErrorStack;
+use openssl::nid::Nid;
+use openssl::pkcs7::{Pkcs7, Pkcs7Flags};
+use openssl::stack::Stack;
+use openssl::x509::store::{X509Store, X509StoreBuilder};
+use openssl::x509::{X509Ref, X509};
+
+/// Check the signature of an S/MIME message against the system trust store
+///
+/// Returns the check with the email addresses of the signing certificate,
+/// which the caller compares with the sender.
+pub(super) fn verify(raw: &str) -> CryptoResult<(SignatureCheck, Vec<String>)> {
+    let mut store = X509StoreBuilder::new().map_err(failed)?;
+    store.set_default_paths().map_err(failed)?;
+    verify_with_store(raw, &store.build())
+}
+
+fn verify_with_store(raw: &str, store: &X509Store) -> CryptoResult<(SignatureCheck, Vec<String>)> {
+    let (pkcs7, content) = Pkcs7::from_smime(raw.as_bytes()).map_err(failed)?;
+    let certificates = Stack::<X509>::new().map_err(failed)?;
+    let signers = pkcs7
+        .signers(&certificates, Pkcs7Flags::empty())
+        .map_err(failed)?;
+
+    let status = if pkcs7
+        .verify(&certificates, store, content.as_deref(), None, Pkcs7Flags::empty())
+        .is_ok()
+    {
+        SignatureStatus::Valid
+    } else {
+        // Without the certificate chain check, a failure means the content
+        // itself doesn't match the signature
+        match pkcs7.verify(&certificates, store, content.as_deref(), None, Pkcs7Flags::NOVERIFY) {
+            Ok(()) => SignatureStatus::UnknownSigner,
+            Err(e) => {
+                let message = e.to_string();
+                if message.contains("digest failure") || message.contains("signature failure") {
+                    SignatureStatus::Invalid
+                } else {
+                    return Err(failed(e));
+                }
+            }
+        }
+    };
+
+    let addresses = signers.iter().next().map(addresses).unwrap_or_default();
+    let signer = addresses
+        .first()
+        .cloned()
+        .or_else(|| signers.iter().next().map(subject));
+    Ok((
+        SignatureCheck {
+            scheme: CryptoScheme::Smime,
+            status,
+            signer,
+        },
+        addresses,
+    ))
+}
+
+/// Email addresses of a certificate, from its subject alternative names
+/// and the legacy `emailAddress` attribute of its subject
+fn addresses(certificate: &X509Ref) -> Vec<String> {
+    let mut addresses: Vec<String> = certificate
+        .subject_alt_names()
+        .into_iter()
+        .flatten()
+        .filter_map(|name| name.email().map(str::to_string))
+        .collect();
+    addresses.extend(
+        certificate
+            .subject_name()
+            .entries_by_nid(Nid::PKCS9_EMAILADDRESS)
+            .filter_map(|entry| entry.data().to_string().ok()),
+    );
+    addresses
+}
+
+/// Subject of a certificate without an email address, e.g. `CN=Alice`
+fn subject(certificate: &X509Ref) -> String {
+    certificate
+        .subject_name()
+        .entries()
+        .filter_map(|entry| {
+            let value = entry.data().to_string().ok()?;
+            Some(format!("{}={}", entry.object().nid().short_name().ok()?, value))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn failed(error: ErrorStack) -> CryptoError {
+    CryptoError::Failed {
+        tool: "openssl".to_string(),
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::asn1::Asn1Time;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::{PKey, Private};
+    use openssl::rsa::Rsa;
+    use openssl::x509::extension::SubjectAlternativeName;
+    use openssl::x509::X509NameBuilder;
+
+    /// Self-signed certificate for `alice@example.com`
+    fn certificate() -> (X509, PKey<Private>) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "Alice").unwrap();
+        let name = name.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder.set_not_before(&Asn1Time::days_from_now(0).unwrap()).unwrap();
+        builder.set_not_after(&Asn1Time::days_from_now(1).unwrap()).unwrap();
+        let alt_names = SubjectAlternativeName::new()
+            .email("alice@example.com")
+            .build(&builder.x509v3_context(None, None))
+            .unwrap();
+        builder.append_extension(alt_names).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        (builder.build(), key)
+    }
+
+    fn signed_message(certificate: &X509, key: &PKey<Private>, content: &[u8]) -> String {
+        let certificates = Stack::new().unwrap();
+        let flags = Pkcs7Flags::DETACHED | Pkcs7Flags::BINARY;
+        let pkcs7 = Pkcs7::sign(certificate, key, &certificates, content, flags).unwrap();
+        let smime = pkcs7.to_smime(content, flags).unwrap();
+        format!("From: alice@example.com\r\n{}", String::from_utf8(smime).unwrap())
+    }
+
+    #[test]
+    fn test_verify() {
+        let (certificate, key) = certificate();
+        let mut trusted = X509StoreBuilder::new().unwrap();
+        trusted.add_cert(certificate.clone()).unwrap();
+        let trusted = trusted.build();
+        let untrusted = X509StoreBuilder::new().unwrap().build();
+
+        let content = b"Content-Type: text/plain\r\n\r\nHello Bob\r\n";
+        let raw = signed_message(&certificate, &key, content);
+
+        let (check, addresses) = verify_with_store(&raw, &trusted).unwrap();
+        assert_eq!(check.scheme, CryptoScheme::Smime);
+        assert_eq!(check.status, SignatureStatus::Valid);
+        assert_eq!(check.signer.as_deref(), Some("alice@example.com"));
+        assert_eq!(addresses, vec!["alice@example.com".to_string()]);
+
+        let (check, _) = verify_with_store(&raw, &untrusted).unwrap();
+        assert_eq!(check.status, SignatureStatus::UnknownSigner);
+
+        let tampered = raw.replace("Hello Bob", "Hello Eve");
+        let (check, _) = verify_with_store(&tampered, &trusted).unwrap();
+        assert_eq!(check.status, SignatureStatus::Invalid);
+    }
+}
@@ -1,9 +1,14 @@
+use crate::email::folder_sync::FolderSyncMode;
+use crate::email::gmail_labels::LabelLayout;
+use crate::email::inbox_tabs::InboxTab;
+use crate::email::sorting::SortCriteria;
 use crate::imap::{ImapMessage, MessageFlag};
 use crate::ui::EmailComposeData;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::migrate::MigrateDatabase;
 use sqlx::{sqlite::SqlitePoolOptions, Row, SqlitePool};
+use std::collections::HashMap;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -71,6 +76,10 @@ pub struct StoredMessage {
     pub sync_version: i64,
     pub is_draft: bool,
     pub is_deleted: bool,
+    /// Hidden from its folder until this time, then back in INBOX as unread
+    pub snoozed_until: Option<DateTime<Utc>>,
+    /// INBOX tab chosen by AI triage or the user; None shows under Primary
+    pub inbox_tab: Option<InboxTab>,
 }
 
 /// Stored email attachment
@@ -110,6 +119,27 @@ pub struct FolderSyncState {
     pub message_count: u32,
     pub unread_count: u32,
     pub sync_status: SyncStatus,
+    /// Folder override of the account's default sync mode. Only written when
+    /// the row is first created; change it with `set_folder_sync_mode`
+    pub sync_mode: Option<FolderSyncMode>,
+}
+
+/// Unread mail and latest message of a folder, used to guess which folder
+/// is opened next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderActivity {
+    pub folder_name: String,
+    pub unread_count: u32,
+    pub latest_message: Option<DateTime<Utc>>,
+}
+
+/// Past mail with an address, used to recognize senders
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SenderHistory {
+    /// Messages received from the address before the one being read
+    pub earlier_messages: u32,
+    /// When mail was last sent to the address
+    pub last_emailed: Option<DateTime<Utc>>,
 }
 
 /// Synchronization status
@@ -119,8 +149,16 @@ pub enum SyncStatus {
     Syncing,
     Error(String),
     Complete,
+    /// Full sync was cancelled; messages with UIDs below `resume_uid` are already stored
+    Cancelled { resume_uid: u32 },
 }
 
+/// Columns read back into a `StoredDraft`
+const DRAFT_COLUMNS: &str = "id, account_id, subject, to_addrs, cc_addrs, bcc_addrs, reply_to,
+    body_text, body_html, attachments, in_reply_to, draft_references,
+    created_at, updated_at, auto_saved, encrypt, sign,
+    scheduled_at, send_attempts, next_attempt_at, last_send_error";
+
 /// Stored draft email in the database
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredDraft {
@@ -139,6 +177,15 @@ pub struct StoredDraft {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub auto_saved: bool,
+    /// PGP choices to send the draft with
+    pub encrypt: bool,
+    pub sign: bool,
+    /// Send at this time instead of keeping the draft; a scheduled send
+    pub scheduled_at: Option<DateTime<Utc>>,
+    /// Failed attempts at a scheduled send, and when to try again
+    pub send_attempts: u32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub last_send_error: Option<String>,
 }
 
 /// Email database manager
@@ -289,7 +336,9 @@ impl EmailDatabase {
                 last_synced TEXT NOT NULL,
                 sync_version INTEGER NOT NULL DEFAULT 1,
                 is_draft BOOLEAN NOT NULL DEFAULT FALSE,
-                is_deleted BOOLEAN NOT NULL DEFAULT FALSE
+                is_deleted BOOLEAN NOT NULL DEFAULT FALSE,
+                snoozed_until TEXT, -- hidden from its folder until then
+                inbox_tab TEXT -- InboxTab name, None for uncategorized
             )
         ",
         )
@@ -339,12 +388,35 @@ impl EmailDatabase {
                 message_count INTEGER NOT NULL DEFAULT 0,
                 unread_count INTEGER NOT NULL DEFAULT 0,
                 sync_status TEXT NOT NULL,
+                sync_mode TEXT,
                 PRIMARY KEY (account_id, folder_name)
             )
         ",
         )
         .execute(&self.pool)
         .await?;
+        self.add_column_if_missing("folder_sync_state", "sync_mode", "TEXT")
+            .await?;
+        // Databases created before PGP compose options
+        self.add_column_if_missing("drafts", "encrypt", "BOOLEAN NOT NULL DEFAULT FALSE")
+            .await?;
+        self.add_column_if_missing("drafts", "sign", "BOOLEAN NOT NULL DEFAULT FALSE")
+            .await?;
+        // Databases created before snoozing
+        self.add_column_if_missing("messages", "snoozed_until", "TEXT")
+            .await?;
+        // Databases created before inbox tabs
+        self.add_column_if_missing("messages", "inbox_tab", "TEXT")
+            .await?;
+        // Databases created before scheduled sends
+        self.add_column_if_missing("drafts", "scheduled_at", "TEXT")
+            .await?;
+        self.add_column_if_missing("drafts", "send_attempts", "INTEGER NOT NULL DEFAULT 0")
+            .await?;
+        self.add_column_if_missing("drafts", "next_attempt_at", "TEXT")
+            .await?;
+        self.add_column_if_missing("drafts", "last_send_error", "TEXT")
+            .await?;
 
         sqlx::query(
             r"
@@ -382,7 +454,147 @@ impl EmailDatabase {
                 draft_references TEXT NOT NULL DEFAULT '', -- JSON array of Message IDs
                 created_at TEXT NOT NULL,
                 updated_at TEXT NOT NULL,
-                auto_saved BOOLEAN NOT NULL DEFAULT FALSE
+                auto_saved BOOLEAN NOT NULL DEFAULT FALSE,
+                encrypt BOOLEAN NOT NULL DEFAULT FALSE,
+                sign BOOLEAN NOT NULL DEFAULT FALSE,
+                scheduled_at TEXT, -- sent by the scheduler at this time
+                send_attempts INTEGER NOT NULL DEFAULT 0,
+                next_attempt_at TEXT, -- retry time after a failed scheduled send
+                last_send_error TEXT
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_security (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                security TEXT NOT NULL -- JSON MessageSecurity
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_mime_defects (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                defects TEXT NOT NULL -- JSON array of MimeDefect
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_flowed (
+                message_id TEXT PRIMARY KEY -- messages.id of a body stored unflowed from format=flowed
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_reply_headers (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                headers TEXT NOT NULL -- JSON ReplyHeaders
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_raw_headers (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                headers TEXT NOT NULL -- header block as received
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS retention_pending (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                account_id TEXT NOT NULL,
+                folder_name TEXT NOT NULL,
+                imap_uid INTEGER NOT NULL,
+                action TEXT NOT NULL, -- RetentionAction
+                subject TEXT NOT NULL,
+                from_addr TEXT NOT NULL,
+                marked_at TEXT NOT NULL
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS jmap_email_ids (
+                account_id TEXT NOT NULL,
+                uid INTEGER NOT NULL, -- stands in for the IMAP UID in every folder
+                email_id TEXT NOT NULL, -- JMAP Email id
+                PRIMARY KEY (account_id, uid),
+                UNIQUE (account_id, email_id)
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS jmap_sync_state (
+                account_id TEXT PRIMARY KEY,
+                email_state TEXT NOT NULL -- Email state string after the last sync
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS label_layouts (
+                account_id TEXT PRIMARY KEY,
+                all_mail TEXT NOT NULL, -- folder storing the labelled messages
+                views TEXT NOT NULL -- JSON object, view folder name -> Gmail label
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS folder_sort_prefs (
+                account_id TEXT NOT NULL,
+                folder_name TEXT NOT NULL,
+                sort TEXT NOT NULL, -- JSON SortCriteria chosen in the message list
+                PRIMARY KEY (account_id, folder_name)
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS inbox_tab_overrides (
+                account_id TEXT NOT NULL,
+                sender TEXT NOT NULL, -- lowercase address
+                tab TEXT NOT NULL, -- InboxTab the user moved the sender's mail to
+                PRIMARY KEY (account_id, sender)
             )
         ",
         )
@@ -546,7 +758,143 @@ impl EmailDatabase {
                 last_synced TEXT NOT NULL,
                 sync_version INTEGER NOT NULL DEFAULT 1,
                 is_draft BOOLEAN NOT NULL DEFAULT FALSE,
-                is_deleted BOOLEAN NOT NULL DEFAULT FALSE
+                is_deleted BOOLEAN NOT NULL DEFAULT FALSE,
+                snoozed_until TEXT, -- hidden from its folder until then
+                inbox_tab TEXT -- InboxTab name, None for uncategorized
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_security (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                security TEXT NOT NULL -- JSON MessageSecurity
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_mime_defects (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                defects TEXT NOT NULL -- JSON array of MimeDefect
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_flowed (
+                message_id TEXT PRIMARY KEY -- messages.id of a body stored unflowed from format=flowed
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_reply_headers (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                headers TEXT NOT NULL -- JSON ReplyHeaders
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS message_raw_headers (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                headers TEXT NOT NULL -- header block as received
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS retention_pending (
+                message_id TEXT PRIMARY KEY, -- messages.id
+                account_id TEXT NOT NULL,
+                folder_name TEXT NOT NULL,
+                imap_uid INTEGER NOT NULL,
+                action TEXT NOT NULL, -- RetentionAction
+                subject TEXT NOT NULL,
+                from_addr TEXT NOT NULL,
+                marked_at TEXT NOT NULL
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS jmap_email_ids (
+                account_id TEXT NOT NULL,
+                uid INTEGER NOT NULL, -- stands in for the IMAP UID in every folder
+                email_id TEXT NOT NULL, -- JMAP Email id
+                PRIMARY KEY (account_id, uid),
+                UNIQUE (account_id, email_id)
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS jmap_sync_state (
+                account_id TEXT PRIMARY KEY,
+                email_state TEXT NOT NULL -- Email state string after the last sync
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS label_layouts (
+                account_id TEXT PRIMARY KEY,
+                all_mail TEXT NOT NULL, -- folder storing the labelled messages
+                views TEXT NOT NULL -- JSON object, view folder name -> Gmail label
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS folder_sort_prefs (
+                account_id TEXT NOT NULL,
+                folder_name TEXT NOT NULL,
+                sort TEXT NOT NULL, -- JSON SortCriteria chosen in the message list
+                PRIMARY KEY (account_id, folder_name)
+            )
+        ",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS inbox_tab_overrides (
+                account_id TEXT NOT NULL,
+                sender TEXT NOT NULL, -- lowercase address
+                tab TEXT NOT NULL, -- InboxTab the user moved the sender's mail to
+                PRIMARY KEY (account_id, sender)
             )
         ",
         )
@@ -569,6 +917,58 @@ impl EmailDatabase {
             .execute(&self.pool)
             .await?;
 
+        // Databases created before per-folder sync modes
+        self.add_column_if_missing("folder_sync_state", "sync_mode", "TEXT")
+            .await?;
+        // Databases created before PGP compose options
+        self.add_column_if_missing("drafts", "encrypt", "BOOLEAN NOT NULL DEFAULT FALSE")
+            .await?;
+        self.add_column_if_missing("drafts", "sign", "BOOLEAN NOT NULL DEFAULT FALSE")
+            .await?;
+        // Databases created before snoozing
+        self.add_column_if_missing("messages", "snoozed_until", "TEXT")
+            .await?;
+        // Databases created before inbox tabs
+        self.add_column_if_missing("messages", "inbox_tab", "TEXT")
+            .await?;
+        // Databases created before scheduled sends
+        self.add_column_if_missing("drafts", "scheduled_at", "TEXT")
+            .await?;
+        self.add_column_if_missing("drafts", "send_attempts", "INTEGER NOT NULL DEFAULT 0")
+            .await?;
+        self.add_column_if_missing("drafts", "next_attempt_at", "TEXT")
+            .await?;
+        self.add_column_if_missing("drafts", "last_send_error", "TEXT")
+            .await?;
+
+        Ok(())
+    }
+
+    /// Add a column to an existing table that was created without it
+    async fn add_column_if_missing(
+        &self,
+        table: &str,
+        column: &str,
+        definition: &str,
+    ) -> DatabaseResult<()> {
+        let columns: Vec<String> = sqlx::query(&format!("PRAGMA table_info({})", table))
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(|row| row.get("name"))
+            .collect();
+
+        // A missing table is created with the column later
+        if columns.is_empty() || columns.iter().any(|name| name == column) {
+            return Ok(());
+        }
+
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(&self.pool)
+        .await?;
         Ok(())
     }
 
@@ -715,6 +1115,8 @@ impl EmailDatabase {
     }
 
     /// Get messages from a folder
+    ///
+    /// A Gmail label view lists the All Mail messages carrying its label.
     pub async fn get_messages(
         &self,
         account_id: &str,
@@ -722,6 +1124,14 @@ impl EmailDatabase {
         limit: Option<u32>,
         offset: Option<u32>,
     ) -> DatabaseResult<Vec<StoredMessage>> {
+        if let Some(layout) = self.get_label_layout(account_id).await? {
+            if let Some(label) = layout.label_for(folder_name) {
+                return self
+                    .get_labelled_messages(account_id, &layout.all_mail, label, limit, offset)
+                    .await;
+            }
+        }
+
         let limit = limit.unwrap_or(100) as i64;
         let offset = offset.unwrap_or(0) as i64;
 
@@ -730,7 +1140,7 @@ impl EmailDatabase {
                    subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
                    body_text, body_html, attachments,
                    flags, labels, size, priority,
-                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
             FROM messages
             WHERE account_id = ?1 AND folder_name = ?2 AND is_deleted = FALSE
             ORDER BY date DESC
@@ -751,30 +1161,280 @@ impl EmailDatabase {
         Ok(messages)
     }
 
-    /// Get a message by UID
-    pub async fn get_message_by_uid(
+    /// Messages of `folder_name` carrying a Gmail label
+    async fn get_labelled_messages(
         &self,
         account_id: &str,
         folder_name: &str,
-        uid: u32,
-    ) -> DatabaseResult<Option<StoredMessage>> {
-        let row = sqlx::query(r"
+        label: &str,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> DatabaseResult<Vec<StoredMessage>> {
+        let limit = limit.unwrap_or(100) as i64;
+        let offset = offset.unwrap_or(0) as i64;
+
+        let rows = sqlx::query(r"
             SELECT id, account_id, folder_name, imap_uid, message_id, thread_id, in_reply_to, message_references,
                    subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
                    body_text, body_html, attachments,
                    flags, labels, size, priority,
-                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
             FROM messages
-            WHERE account_id = ?1 AND folder_name = ?2 AND imap_uid = ?3
+            WHERE account_id = ?1 AND folder_name = ?2 AND is_deleted = FALSE
+                  AND EXISTS (SELECT 1 FROM json_each(messages.labels) WHERE json_each.value = ?3)
+            ORDER BY date DESC
+            LIMIT ?4 OFFSET ?5
         ")
         .bind(account_id)
         .bind(folder_name)
-        .bind(uid as i64)
-        .fetch_optional(&self.pool)
+        .bind(label)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
         .await?;
 
-        match row {
-            Some(row) => Ok(Some(self.row_to_stored_message(row)?)),
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(self.row_to_stored_message(row)?);
+        }
+
+        Ok(messages)
+    }
+
+    /// Newest messages of an account across all of its folders
+    pub async fn get_account_messages(
+        &self,
+        account_id: &str,
+        limit: Option<u32>,
+    ) -> DatabaseResult<Vec<StoredMessage>> {
+        let limit = limit.unwrap_or(100) as i64;
+
+        let rows = sqlx::query(r"
+            SELECT id, account_id, folder_name, imap_uid, message_id, thread_id, in_reply_to, message_references,
+                   subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
+                   body_text, body_html, attachments,
+                   flags, labels, size, priority,
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
+            FROM messages
+            WHERE account_id = ?1 AND is_deleted = FALSE AND snoozed_until IS NULL
+            ORDER BY date DESC
+            LIMIT ?2
+        ")
+        .bind(account_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(self.row_to_stored_message(row)?);
+        }
+
+        Ok(messages)
+    }
+
+    /// The newest INBOX messages of every account, for the unified inbox
+    pub async fn get_all_inbox_messages(&self, limit: Option<u32>) -> DatabaseResult<Vec<StoredMessage>> {
+        let limit = limit.unwrap_or(100) as i64;
+
+        let rows = sqlx::query(r"
+            SELECT id, account_id, folder_name, imap_uid, message_id, thread_id, in_reply_to, message_references,
+                   subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
+                   body_text, body_html, attachments,
+                   flags, labels, size, priority,
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
+            FROM messages
+            WHERE UPPER(folder_name) = 'INBOX' AND is_deleted = FALSE AND snoozed_until IS NULL
+            ORDER BY date DESC
+            LIMIT ?1
+        ")
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(self.row_to_stored_message(row)?);
+        }
+
+        Ok(messages)
+    }
+
+    /// Hide a message until `until`, or (None) show it again
+    pub async fn set_snoozed_until(
+        &self,
+        message_id: Uuid,
+        until: Option<DateTime<Utc>>,
+    ) -> DatabaseResult<()> {
+        sqlx::query("UPDATE messages SET snoozed_until = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(until.map(|until| until.to_rfc3339()))
+            .bind(Utc::now().to_rfc3339())
+            .bind(message_id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Put a message under an INBOX tab, or (None) back to uncategorized
+    pub async fn set_inbox_tab(&self, message_id: Uuid, tab: Option<InboxTab>) -> DatabaseResult<()> {
+        sqlx::query("UPDATE messages SET inbox_tab = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(tab.map(|tab| tab.as_str()))
+            .bind(Utc::now().to_rfc3339())
+            .bind(message_id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// INBOX tab the user chose for a sender's mail, if any
+    pub async fn get_inbox_tab_override(
+        &self,
+        account_id: &str,
+        sender: &str,
+    ) -> DatabaseResult<Option<InboxTab>> {
+        let tab: Option<String> = sqlx::query_scalar("SELECT tab FROM inbox_tab_overrides WHERE account_id = ?1 AND sender = ?2")
+            .bind(account_id)
+            .bind(sender)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(tab.and_then(|tab| tab.parse().ok()))
+    }
+
+    /// Keep a sender's mail under `tab`, moving what is already stored
+    ///
+    /// `sender` is the lowercase address, as triage looks it up.
+    pub async fn set_inbox_tab_override(
+        &self,
+        account_id: &str,
+        sender: &str,
+        tab: InboxTab,
+    ) -> DatabaseResult<()> {
+        sqlx::query("INSERT OR REPLACE INTO inbox_tab_overrides (account_id, sender, tab) VALUES (?1, ?2, ?3)")
+            .bind(account_id)
+            .bind(sender)
+            .bind(tab.as_str())
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("UPDATE messages SET inbox_tab = ?1, updated_at = ?2 WHERE account_id = ?3 AND (LOWER(from_addr) = ?4 OR LOWER(from_addr) LIKE '%<' || ?4 || '>')")
+            .bind(tab.as_str())
+            .bind(Utc::now().to_rfc3339())
+            .bind(account_id)
+            .bind(sender)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Snoozed messages of an account, the soonest to wake up first
+    pub async fn get_snoozed_messages(&self, account_id: &str) -> DatabaseResult<Vec<StoredMessage>> {
+        let rows = sqlx::query(r"
+            SELECT id, account_id, folder_name, imap_uid, message_id, thread_id, in_reply_to, message_references,
+                   subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
+                   body_text, body_html, attachments,
+                   flags, labels, size, priority,
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
+            FROM messages
+            WHERE account_id = ?1 AND is_deleted = FALSE AND snoozed_until IS NOT NULL
+            ORDER BY snoozed_until
+        ")
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(self.row_to_stored_message(row)?);
+        }
+
+        Ok(messages)
+    }
+
+    /// Number of snoozed messages of an account
+    pub async fn count_snoozed_messages(&self, account_id: &str) -> DatabaseResult<u32> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM messages WHERE account_id = ? AND is_deleted = FALSE AND snoozed_until IS NOT NULL")
+            .bind(account_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(count as u32)
+    }
+
+    /// Accounts with at least one snoozed message due by `now`
+    ///
+    /// Snooze times are all stored as UTC RFC 3339, so they compare as text.
+    pub async fn get_accounts_with_due_snoozes(&self, now: DateTime<Utc>) -> DatabaseResult<Vec<String>> {
+        let accounts = sqlx::query_scalar("SELECT DISTINCT account_id FROM messages WHERE is_deleted = FALSE AND snoozed_until IS NOT NULL AND snoozed_until <= ?")
+            .bind(now.to_rfc3339())
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(accounts)
+    }
+
+    /// The biggest messages of one folder, or of the whole account when
+    /// `folder_name` is `None`, largest first
+    ///
+    /// Messages synced before sizes were recorded have no size and are left out.
+    pub async fn get_largest_messages(
+        &self,
+        account_id: &str,
+        folder_name: Option<&str>,
+        limit: u32,
+    ) -> DatabaseResult<Vec<StoredMessage>> {
+        let rows = sqlx::query(r"
+            SELECT id, account_id, folder_name, imap_uid, message_id, thread_id, in_reply_to, message_references,
+                   subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
+                   body_text, body_html, attachments,
+                   flags, labels, size, priority,
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
+            FROM messages
+            WHERE account_id = ?1 AND (?2 IS NULL OR folder_name = ?2)
+                  AND is_deleted = FALSE AND size IS NOT NULL
+            ORDER BY size DESC
+            LIMIT ?3
+        ")
+        .bind(account_id)
+        .bind(folder_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(self.row_to_stored_message(row)?);
+        }
+
+        Ok(messages)
+    }
+
+    /// Get a message by UID
+    pub async fn get_message_by_uid(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        uid: u32,
+    ) -> DatabaseResult<Option<StoredMessage>> {
+        let row = sqlx::query(r"
+            SELECT id, account_id, folder_name, imap_uid, message_id, thread_id, in_reply_to, message_references,
+                   subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
+                   body_text, body_html, attachments,
+                   flags, labels, size, priority,
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
+            FROM messages
+            WHERE account_id = ?1 AND folder_name = ?2 AND imap_uid = ?3
+        ")
+        .bind(account_id)
+        .bind(folder_name)
+        .bind(uid as i64)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_stored_message(row)?)),
             None => Ok(None),
         }
     }
@@ -786,7 +1446,7 @@ impl EmailDatabase {
                    subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
                    body_text, body_html, attachments,
                    flags, labels, size, priority,
-                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
             FROM messages
             WHERE id = ?1
         ")
@@ -814,7 +1474,7 @@ impl EmailDatabase {
                    m.subject, m.from_addr, m.from_name, m.to_addrs, m.cc_addrs, m.bcc_addrs, m.reply_to, m.date,
                    m.body_text, m.body_html, m.attachments,
                    m.flags, m.labels, m.size, m.priority,
-                   m.created_at, m.updated_at, m.last_synced, m.sync_version, m.is_draft, m.is_deleted
+                   m.created_at, m.updated_at, m.last_synced, m.sync_version, m.is_draft, m.is_deleted, m.snoozed_until, m.inbox_tab
             FROM messages m
             JOIN messages_fts fts ON m.rowid = fts.rowid
             WHERE m.account_id = ?1 AND m.is_deleted = FALSE AND messages_fts MATCH ?2
@@ -836,13 +1496,23 @@ impl EmailDatabase {
     }
 
     /// Update folder sync state
+    ///
+    /// An existing row keeps its sync mode override.
     pub async fn update_folder_sync_state(&self, state: &FolderSyncState) -> DatabaseResult<()> {
         sqlx::query(
             r"
-            INSERT OR REPLACE INTO folder_sync_state (
+            INSERT INTO folder_sync_state (
                 account_id, folder_name, uid_validity, uid_next, highest_modseq,
-                last_sync, message_count, unread_count, sync_status
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                last_sync, message_count, unread_count, sync_status, sync_mode
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            ON CONFLICT (account_id, folder_name) DO UPDATE SET
+                uid_validity = excluded.uid_validity,
+                uid_next = excluded.uid_next,
+                highest_modseq = excluded.highest_modseq,
+                last_sync = excluded.last_sync,
+                message_count = excluded.message_count,
+                unread_count = excluded.unread_count,
+                sync_status = excluded.sync_status
         ",
         )
         .bind(&state.account_id)
@@ -854,6 +1524,38 @@ impl EmailDatabase {
         .bind(state.message_count as i64)
         .bind(state.unread_count as i64)
         .bind(serde_json::to_string(&state.sync_status)?)
+        .bind(state.sync_mode.map(|mode| serde_json::to_string(&mode)).transpose()?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set or clear a folder's sync mode override
+    ///
+    /// A folder that has never been synced gets a placeholder state, so its
+    /// first sync starts from scratch.
+    pub async fn set_folder_sync_mode(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        mode: Option<FolderSyncMode>,
+    ) -> DatabaseResult<()> {
+        sqlx::query(
+            r"
+            INSERT INTO folder_sync_state (
+                account_id, folder_name, uid_validity, uid_next, highest_modseq,
+                last_sync, message_count, unread_count, sync_status, sync_mode
+            ) VALUES (?1, ?2, 0, 1, NULL, ?3, 0, 0, ?4, ?5)
+            ON CONFLICT (account_id, folder_name) DO UPDATE SET
+                sync_mode = excluded.sync_mode
+        ",
+        )
+        .bind(account_id)
+        .bind(folder_name)
+        .bind(DateTime::<Utc>::UNIX_EPOCH.to_rfc3339())
+        .bind(serde_json::to_string(&SyncStatus::Idle)?)
+        .bind(mode.map(|mode| serde_json::to_string(&mode)).transpose()?)
         .execute(&self.pool)
         .await?;
 
@@ -930,7 +1632,7 @@ impl EmailDatabase {
         let row = sqlx::query(
             r"
             SELECT account_id, folder_name, uid_validity, uid_next, highest_modseq,
-                   last_sync, message_count, unread_count, sync_status
+                   last_sync, message_count, unread_count, sync_status, sync_mode
             FROM folder_sync_state
             WHERE account_id = ?1 AND folder_name = ?2
         ",
@@ -945,6 +1647,10 @@ impl EmailDatabase {
                 let sync_status: SyncStatus = serde_json::from_str(row.get("sync_status"))?;
                 let last_sync: DateTime<Utc> =
                     DateTime::parse_from_rfc3339(row.get("last_sync"))?.into();
+                let sync_mode = row
+                    .get::<Option<String>, _>("sync_mode")
+                    .map(|mode| serde_json::from_str(&mode))
+                    .transpose()?;
 
                 Ok(Some(FolderSyncState {
                     account_id: row.get("account_id"),
@@ -958,6 +1664,7 @@ impl EmailDatabase {
                     message_count: row.get::<i64, _>("message_count") as u32,
                     unread_count: row.get::<i64, _>("unread_count") as u32,
                     sync_status,
+                    sync_mode,
                 }))
             }
             None => Ok(None),
@@ -984,6 +1691,479 @@ impl EmailDatabase {
         Ok(())
     }
 
+    /// Replace the IMAP flags stored for a message
+    pub async fn update_message_flags(&self, id: Uuid, flags: &[String]) -> DatabaseResult<()> {
+        sqlx::query("UPDATE messages SET flags = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(serde_json::to_string(flags)?)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the Gmail labels stored for a message
+    pub async fn update_message_labels(&self, id: Uuid, labels: &[String]) -> DatabaseResult<()> {
+        sqlx::query("UPDATE messages SET labels = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(serde_json::to_string(labels)?)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Gmail label layout of an account, if its folders are label views
+    pub async fn get_label_layout(&self, account_id: &str) -> DatabaseResult<Option<LabelLayout>> {
+        let row = sqlx::query("SELECT all_mail, views FROM label_layouts WHERE account_id = ?1")
+            .bind(account_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match row {
+            Some(row) => Ok(Some(LabelLayout {
+                all_mail: row.get("all_mail"),
+                views: serde_json::from_str(&row.get::<String, _>("views"))?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Record the Gmail label layout of an account; `None` clears it
+    pub async fn set_label_layout(
+        &self,
+        account_id: &str,
+        layout: Option<&LabelLayout>,
+    ) -> DatabaseResult<()> {
+        match layout {
+            Some(layout) => {
+                sqlx::query("INSERT OR REPLACE INTO label_layouts (account_id, all_mail, views) VALUES (?1, ?2, ?3)")
+                    .bind(account_id)
+                    .bind(&layout.all_mail)
+                    .bind(serde_json::to_string(&layout.views)?)
+                    .execute(&self.pool)
+                    .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM label_layouts WHERE account_id = ?1")
+                    .bind(account_id)
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sort order last chosen for a folder, if any
+    pub async fn get_folder_sort(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+    ) -> DatabaseResult<Option<SortCriteria>> {
+        let row = sqlx::query("SELECT sort FROM folder_sort_prefs WHERE account_id = ?1 AND folder_name = ?2")
+            .bind(account_id)
+            .bind(folder_name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        match row {
+            Some(row) => Ok(Some(serde_json::from_str(&row.get::<String, _>("sort"))?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Remember the sort order chosen for a folder
+    pub async fn set_folder_sort(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        sort: &SortCriteria,
+    ) -> DatabaseResult<()> {
+        sqlx::query("INSERT OR REPLACE INTO folder_sort_prefs (account_id, folder_name, sort) VALUES (?1, ?2, ?3)")
+            .bind(account_id)
+            .bind(folder_name)
+            .bind(serde_json::to_string(sort)?)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Undo [`delete_messages_by_uids`](Self::delete_messages_by_uids)
+    pub async fn restore_messages_by_uids(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        uids: &[u32],
+    ) -> DatabaseResult<()> {
+        for uid in uids {
+            sqlx::query("UPDATE messages SET is_deleted = FALSE, updated_at = ?1 WHERE account_id = ?2 AND folder_name = ?3 AND imap_uid = ?4")
+                .bind(Utc::now().to_rfc3339())
+                .bind(account_id)
+                .bind(folder_name)
+                .bind(*uid as i64)
+                .execute(&self.pool)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// UID standing in for a JMAP email, allocating one on first sight
+    ///
+    /// A JMAP email keeps its UID in every folder it appears in.
+    pub async fn jmap_uid(&self, account_id: &str, email_id: &str) -> DatabaseResult<u32> {
+        sqlx::query(
+            r"
+            INSERT OR IGNORE INTO jmap_email_ids (account_id, uid, email_id)
+            VALUES (?1, (SELECT COALESCE(MAX(uid), 0) + 1 FROM jmap_email_ids WHERE account_id = ?1), ?2)
+        ",
+        )
+        .bind(account_id)
+        .bind(email_id)
+        .execute(&self.pool)
+        .await?;
+
+        let uid: i64 = sqlx::query_scalar(
+            "SELECT uid FROM jmap_email_ids WHERE account_id = ?1 AND email_id = ?2",
+        )
+        .bind(account_id)
+        .bind(email_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(uid as u32)
+    }
+
+    /// JMAP email ids of UIDs; UIDs without one are skipped
+    pub async fn jmap_email_ids(
+        &self,
+        account_id: &str,
+        uids: &[u32],
+    ) -> DatabaseResult<Vec<String>> {
+        let mut email_ids = Vec::with_capacity(uids.len());
+        for uid in uids {
+            let email_id: Option<String> = sqlx::query_scalar(
+                "SELECT email_id FROM jmap_email_ids WHERE account_id = ?1 AND uid = ?2",
+            )
+            .bind(account_id)
+            .bind(*uid as i64)
+            .fetch_optional(&self.pool)
+            .await?;
+            email_ids.extend(email_id);
+        }
+        Ok(email_ids)
+    }
+
+    /// Mark the messages of destroyed JMAP emails deleted in every folder
+    ///
+    /// The UIDs stay allocated so they are never handed to another email.
+    /// Returns the UIDs that were mapped.
+    pub async fn remove_jmap_emails(
+        &self,
+        account_id: &str,
+        email_ids: &[String],
+    ) -> DatabaseResult<Vec<u32>> {
+        let mut uids = Vec::new();
+        for email_id in email_ids {
+            let uid: Option<i64> = sqlx::query_scalar(
+                "SELECT uid FROM jmap_email_ids WHERE account_id = ?1 AND email_id = ?2",
+            )
+            .bind(account_id)
+            .bind(email_id)
+            .fetch_optional(&self.pool)
+            .await?;
+            let Some(uid) = uid else { continue };
+
+            sqlx::query("UPDATE messages SET is_deleted = TRUE, updated_at = ?1 WHERE account_id = ?2 AND imap_uid = ?3")
+                .bind(Utc::now().to_rfc3339())
+                .bind(account_id)
+                .bind(uid)
+                .execute(&self.pool)
+                .await?;
+            uids.push(uid as u32);
+        }
+        Ok(uids)
+    }
+
+    /// UIDs of the live messages stored for a folder
+    pub async fn get_folder_uids(&self, account_id: &str, folder_name: &str) -> DatabaseResult<Vec<u32>> {
+        let uids: Vec<i64> = sqlx::query_scalar(
+            "SELECT imap_uid FROM messages WHERE account_id = ?1 AND folder_name = ?2 AND is_deleted = FALSE",
+        )
+        .bind(account_id)
+        .bind(folder_name)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(uids.into_iter().map(|uid| uid as u32).collect())
+    }
+
+    /// Message-ID headers of the live messages stored for a folder
+    pub async fn get_folder_message_ids(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+    ) -> DatabaseResult<Vec<String>> {
+        let ids = sqlx::query_scalar(
+            "SELECT message_id FROM messages WHERE account_id = ?1 AND folder_name = ?2 AND is_deleted = FALSE AND message_id IS NOT NULL",
+        )
+        .bind(account_id)
+        .bind(folder_name)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(ids)
+    }
+
+    /// Live message count of each folder of an account that has messages
+    pub async fn get_folder_message_counts(&self, account_id: &str) -> DatabaseResult<Vec<(String, u32)>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT folder_name, COUNT(*) FROM messages WHERE account_id = ?1 AND is_deleted = FALSE GROUP BY folder_name ORDER BY folder_name",
+        )
+        .bind(account_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(folder, count)| (folder, count as u32))
+            .collect())
+    }
+
+    /// Folders holding a live copy of a UID
+    pub async fn folders_with_uid(&self, account_id: &str, uid: u32) -> DatabaseResult<Vec<String>> {
+        let folders = sqlx::query_scalar(
+            "SELECT folder_name FROM messages WHERE account_id = ?1 AND imap_uid = ?2 AND is_deleted = FALSE",
+        )
+        .bind(account_id)
+        .bind(uid as i64)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(folders)
+    }
+
+    /// JMAP Email state the account was last synced to
+    pub async fn get_jmap_email_state(&self, account_id: &str) -> DatabaseResult<Option<String>> {
+        let state = sqlx::query_scalar(
+            "SELECT email_state FROM jmap_sync_state WHERE account_id = ?1",
+        )
+        .bind(account_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(state)
+    }
+
+    /// Record the JMAP Email state the account is synced to
+    pub async fn set_jmap_email_state(&self, account_id: &str, state: &str) -> DatabaseResult<()> {
+        sqlx::query("INSERT OR REPLACE INTO jmap_sync_state (account_id, email_state) VALUES (?1, ?2)")
+            .bind(account_id)
+            .bind(state)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Earlier mail from and to an address, across all accounts
+    ///
+    /// `before` is the date of the message being read, so it doesn't count
+    /// itself. Mail sent to the address is looked up in folders whose name
+    /// contains "sent".
+    pub async fn sender_history(
+        &self,
+        address: &str,
+        before: DateTime<Utc>,
+    ) -> DatabaseResult<SenderHistory> {
+        let earlier_messages: i64 = sqlx::query_scalar(
+            r"
+            SELECT COUNT(*) FROM messages
+            WHERE LOWER(from_addr) = LOWER(?1) AND date < ?2 AND is_deleted = FALSE
+        ",
+        )
+        .bind(address)
+        .bind(before.to_rfc3339())
+        .fetch_one(&self.pool)
+        .await?;
+
+        let last_emailed: Option<String> = sqlx::query_scalar(
+            r"
+            SELECT MAX(date) FROM messages
+            WHERE LOWER(folder_name) LIKE '%sent%' AND is_deleted = FALSE
+              AND (to_addrs LIKE ?1 OR cc_addrs LIKE ?1)
+        ",
+        )
+        .bind(format!("%{}%", address))
+        .fetch_one(&self.pool)
+        .await?;
+        let last_emailed = match last_emailed {
+            Some(date) => Some(DateTime::parse_from_rfc3339(&date)?.with_timezone(&Utc)),
+            None => None,
+        };
+
+        Ok(SenderHistory {
+            earlier_messages: earlier_messages as u32,
+            last_emailed,
+        })
+    }
+
+    /// Messages in a folder with a UID of at least `min_uid`, in UID order
+    pub async fn get_messages_since_uid(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        min_uid: u32,
+        limit: u32,
+    ) -> DatabaseResult<Vec<StoredMessage>> {
+        let rows = sqlx::query(r"
+            SELECT id, account_id, folder_name, imap_uid, message_id, thread_id, in_reply_to, message_references,
+                   subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
+                   body_text, body_html, attachments,
+                   flags, labels, size, priority,
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
+            FROM messages
+            WHERE account_id = ?1 AND folder_name = ?2 AND is_deleted = FALSE AND imap_uid >= ?3
+            ORDER BY imap_uid ASC
+            LIMIT ?4
+        ")
+        .bind(account_id)
+        .bind(folder_name)
+        .bind(min_uid as i64)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(self.row_to_stored_message(row)?);
+        }
+
+        Ok(messages)
+    }
+
+    /// Messages in a folder dated before `cutoff`, oldest first
+    pub async fn get_messages_older_than(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        cutoff: DateTime<Utc>,
+        limit: u32,
+    ) -> DatabaseResult<Vec<StoredMessage>> {
+        let rows = sqlx::query(r"
+            SELECT id, account_id, folder_name, imap_uid, message_id, thread_id, in_reply_to, message_references,
+                   subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
+                   body_text, body_html, attachments,
+                   flags, labels, size, priority,
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
+            FROM messages
+            WHERE account_id = ?1 AND folder_name = ?2 AND is_deleted = FALSE AND date < ?3
+            ORDER BY date ASC
+            LIMIT ?4
+        ")
+        .bind(account_id)
+        .bind(folder_name)
+        .bind(cutoff.to_rfc3339())
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut messages = Vec::new();
+        for row in rows {
+            messages.push(self.row_to_stored_message(row)?);
+        }
+
+        Ok(messages)
+    }
+
+    /// Unread messages of an account, or of one of its folders, with their current flags
+    pub async fn get_unread_flags(
+        &self,
+        account_id: &str,
+        folder_name: Option<&str>,
+    ) -> DatabaseResult<Vec<crate::email::SeenState>> {
+        let rows = sqlx::query(
+            r"
+            SELECT id, folder_name, imap_uid, flags
+            FROM messages
+            WHERE account_id = ?1 AND (?2 IS NULL OR folder_name = ?2) AND is_deleted = FALSE
+            ORDER BY folder_name, imap_uid
+        ",
+        )
+        .bind(account_id)
+        .bind(folder_name)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut unread = Vec::new();
+        for row in rows {
+            let flags: Vec<String> =
+                serde_json::from_str(row.get("flags")).unwrap_or_else(|_| Vec::new());
+            if flags.iter().any(|flag| flag == "\\Seen") {
+                continue;
+            }
+            unread.push(crate::email::SeenState {
+                message_id: Uuid::parse_str(row.get("id"))?,
+                folder_name: row.get("folder_name"),
+                imap_uid: row.get::<i64, _>("imap_uid") as u32,
+                flags,
+            });
+        }
+        Ok(unread)
+    }
+
+    /// Record a message soft-deleted by a retention rule
+    pub async fn store_retention_pending(
+        &self,
+        pending: &crate::email::PendingRetention,
+    ) -> DatabaseResult<()> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO retention_pending (message_id, account_id, folder_name, imap_uid, action, subject, from_addr, marked_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(pending.message_id.to_string())
+        .bind(&pending.account_id)
+        .bind(&pending.folder_name)
+        .bind(pending.imap_uid as i64)
+        .bind(pending.action.as_str())
+        .bind(&pending.subject)
+        .bind(&pending.from_addr)
+        .bind(pending.marked_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Messages soft-deleted by retention rules and not yet expunged, oldest first
+    pub async fn get_retention_pending(
+        &self,
+    ) -> DatabaseResult<Vec<crate::email::PendingRetention>> {
+        let rows = sqlx::query(
+            "SELECT message_id, account_id, folder_name, imap_uid, action, subject, from_addr, marked_at FROM retention_pending ORDER BY marked_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut pending = Vec::with_capacity(rows.len());
+        for row in rows {
+            let action: String = row.get("action");
+            pending.push(crate::email::PendingRetention {
+                message_id: Uuid::parse_str(row.get("message_id"))?,
+                account_id: row.get("account_id"),
+                folder_name: row.get("folder_name"),
+                imap_uid: row.get::<i64, _>("imap_uid") as u32,
+                action: crate::email::RetentionAction::parse(&action).ok_or_else(|| {
+                    DatabaseError::Query(format!("Unknown retention action: {}", action))
+                })?,
+                subject: row.get("subject"),
+                from_addr: row.get("from_addr"),
+                marked_at: DateTime::parse_from_rfc3339(row.get("marked_at"))?.with_timezone(&Utc),
+            });
+        }
+        Ok(pending)
+    }
+
+    /// Forget a pending retention entry after it was expunged or restored
+    pub async fn delete_retention_pending(&self, message_id: Uuid) -> DatabaseResult<()> {
+        sqlx::query("DELETE FROM retention_pending WHERE message_id = ?")
+            .bind(message_id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     /// Get database statistics
     pub async fn get_stats(&self) -> DatabaseResult<DatabaseStats> {
         let message_count: i64 =
@@ -994,25 +2174,216 @@ impl EmailDatabase {
         let unread_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM messages WHERE is_deleted = FALSE AND flags NOT LIKE '%\"\\\\Seen\"%'")
             .fetch_one(&self.pool).await?;
 
-        let account_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM accounts")
-            .fetch_one(&self.pool)
+        let account_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM accounts")
+            .fetch_one(&self.pool)
+            .await?;
+
+        let folder_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM folders")
+            .fetch_one(&self.pool)
+            .await?;
+
+        let db_size = std::fs::metadata(&self.db_path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+
+        Ok(DatabaseStats {
+            message_count: message_count as u32,
+            unread_count: unread_count as u32,
+            account_count: account_count as u32,
+            folder_count: folder_count as u32,
+            db_size_bytes: db_size,
+        })
+    }
+
+    /// Count unread inbox messages per account
+    pub async fn get_inbox_unread_counts(&self) -> DatabaseResult<Vec<(String, u32)>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as("SELECT account_id, COUNT(*) FROM messages WHERE is_deleted = FALSE AND snoozed_until IS NULL AND UPPER(folder_name) = 'INBOX' AND flags NOT LIKE '%\"\\\\Seen\"%' GROUP BY account_id ORDER BY account_id")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(account_id, count)| (account_id, count as u32))
+            .collect())
+    }
+
+    /// Accounts that have an INBOX folder
+    pub async fn get_inbox_accounts(&self) -> DatabaseResult<Vec<String>> {
+        let rows: Vec<String> = sqlx::query_scalar("SELECT DISTINCT account_id FROM folders WHERE UPPER(name) = 'INBOX' ORDER BY account_id")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    /// Unread count and newest message date of every folder of an account
+    /// that holds messages
+    pub async fn get_folder_activity(&self, account_id: &str) -> DatabaseResult<Vec<FolderActivity>> {
+        let rows: Vec<(String, i64, Option<String>)> = sqlx::query_as("SELECT folder_name, SUM(CASE WHEN flags NOT LIKE '%\"\\\\Seen\"%' THEN 1 ELSE 0 END), MAX(date) FROM messages WHERE account_id = ? AND is_deleted = FALSE GROUP BY folder_name")
+            .bind(account_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(folder_name, unread_count, latest)| FolderActivity {
+                folder_name,
+                unread_count: unread_count as u32,
+                latest_message: latest
+                    .and_then(|date| DateTime::parse_from_rfc3339(&date).ok())
+                    .map(Into::into),
+            })
+            .collect())
+    }
+
+    /// Store the security status computed for a message during sync
+    pub async fn store_message_security(
+        &self,
+        message_id: Uuid,
+        security: &crate::email::MessageSecurity,
+    ) -> DatabaseResult<()> {
+        sqlx::query("INSERT OR REPLACE INTO message_security (message_id, security) VALUES (?, ?)")
+            .bind(message_id.to_string())
+            .bind(serde_json::to_string(security)?)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Security status of one message, if it was computed
+    pub async fn get_message_security(
+        &self,
+        message_id: Uuid,
+    ) -> DatabaseResult<Option<crate::email::MessageSecurity>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT security FROM message_security WHERE message_id = ?")
+                .bind(message_id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(row.map(|(json,)| serde_json::from_str(&json)).transpose()?)
+    }
+
+    /// Store the MIME defects found while parsing a message during sync
+    pub async fn store_message_defects(
+        &self,
+        message_id: Uuid,
+        defects: &[crate::mime::MimeDefect],
+    ) -> DatabaseResult<()> {
+        sqlx::query("INSERT OR REPLACE INTO message_mime_defects (message_id, defects) VALUES (?, ?)")
+            .bind(message_id.to_string())
+            .bind(serde_json::to_string(defects)?)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// MIME defects recorded for a message; empty when it parsed cleanly
+    pub async fn get_message_defects(
+        &self,
+        message_id: Uuid,
+    ) -> DatabaseResult<Vec<crate::mime::MimeDefect>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT defects FROM message_mime_defects WHERE message_id = ?")
+                .bind(message_id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(row
+            .map(|(json,)| serde_json::from_str(&json))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// Store the list and reply headers of a message during sync
+    pub async fn store_reply_headers(
+        &self,
+        message_id: Uuid,
+        headers: &crate::email::ReplyHeaders,
+    ) -> DatabaseResult<()> {
+        sqlx::query("INSERT OR REPLACE INTO message_reply_headers (message_id, headers) VALUES (?, ?)")
+            .bind(message_id.to_string())
+            .bind(serde_json::to_string(headers)?)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// List and reply headers of a message; empty when it had none
+    pub async fn get_reply_headers(
+        &self,
+        message_id: Uuid,
+    ) -> DatabaseResult<crate::email::ReplyHeaders> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT headers FROM message_reply_headers WHERE message_id = ?")
+                .bind(message_id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(row
+            .map(|(json,)| serde_json::from_str(&json))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    /// Record that a message's body arrived as `format=flowed`
+    pub async fn mark_flowed(&self, message_id: Uuid) -> DatabaseResult<()> {
+        sqlx::query("INSERT OR IGNORE INTO message_flowed (message_id) VALUES (?)")
+            .bind(message_id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Whether a message's body arrived as `format=flowed`
+    pub async fn is_flowed(&self, message_id: Uuid) -> DatabaseResult<bool> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT message_id FROM message_flowed WHERE message_id = ?")
+                .bind(message_id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.is_some())
+    }
+
+    /// Keep a message's header block for the headers panel
+    pub async fn store_raw_headers(&self, message_id: Uuid, headers: &str) -> DatabaseResult<()> {
+        sqlx::query("INSERT OR REPLACE INTO message_raw_headers (message_id, headers) VALUES (?, ?)")
+            .bind(message_id.to_string())
+            .bind(headers)
+            .execute(&self.pool)
             .await?;
+        Ok(())
+    }
 
-        let folder_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM folders")
-            .fetch_one(&self.pool)
-            .await?;
+    /// Header block of a message; `None` for messages synced before headers were kept
+    pub async fn get_raw_headers(&self, message_id: Uuid) -> DatabaseResult<Option<String>> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT headers FROM message_raw_headers WHERE message_id = ?")
+                .bind(message_id.to_string())
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.map(|(headers,)| headers))
+    }
 
-        let db_size = std::fs::metadata(&self.db_path)
-            .map(|metadata| metadata.len())
-            .unwrap_or(0);
+    /// Security status of every message in a folder that has one
+    pub async fn get_folder_message_security(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+    ) -> DatabaseResult<HashMap<Uuid, crate::email::MessageSecurity>> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT s.message_id, s.security FROM message_security s JOIN messages m ON m.id = s.message_id WHERE m.account_id = ? AND m.folder_name = ?",
+        )
+        .bind(account_id)
+        .bind(folder_name)
+        .fetch_all(&self.pool)
+        .await?;
 
-        Ok(DatabaseStats {
-            message_count: message_count as u32,
-            unread_count: unread_count as u32,
-            account_count: account_count as u32,
-            folder_count: folder_count as u32,
-            db_size_bytes: db_size,
-        })
+        let mut security = HashMap::new();
+        for (id, json) in rows {
+            security.insert(Uuid::parse_str(&id)?, serde_json::from_str(&json)?);
+        }
+        Ok(security)
     }
 
     /// Helper to convert database row to StoredMessage
@@ -1065,6 +2436,13 @@ impl EmailDatabase {
             sync_version: row.get("sync_version"),
             is_draft: row.get("is_draft"),
             is_deleted: row.get("is_deleted"),
+            snoozed_until: row
+                .get::<Option<String>, _>("snoozed_until")
+                .and_then(|until| DateTime::parse_from_rfc3339(&until).ok())
+                .map(Into::into),
+            inbox_tab: row
+                .get::<Option<String>, _>("inbox_tab")
+                .and_then(|tab| tab.parse().ok()),
         })
     }
 
@@ -1228,8 +2606,9 @@ impl EmailDatabase {
             INSERT OR REPLACE INTO drafts (
                 id, account_id, subject, to_addrs, cc_addrs, bcc_addrs, reply_to,
                 body_text, body_html, attachments, in_reply_to, draft_references,
-                created_at, updated_at, auto_saved
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                created_at, updated_at, auto_saved, encrypt, sign,
+                scheduled_at, send_attempts, next_attempt_at, last_send_error
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         ",
         )
         .bind(&draft.id)
@@ -1247,6 +2626,12 @@ impl EmailDatabase {
         .bind(draft.created_at.to_rfc3339())
         .bind(draft.updated_at.to_rfc3339())
         .bind(draft.auto_saved)
+        .bind(draft.encrypt)
+        .bind(draft.sign)
+        .bind(draft.scheduled_at.map(|at| at.to_rfc3339()))
+        .bind(draft.send_attempts as i64)
+        .bind(draft.next_attempt_at.map(|at| at.to_rfc3339()))
+        .bind(&draft.last_send_error)
         .execute(&self.pool)
         .await?;
 
@@ -1255,50 +2640,12 @@ impl EmailDatabase {
 
     /// Load a draft by ID
     pub async fn load_draft(&self, draft_id: &str) -> DatabaseResult<Option<StoredDraft>> {
-        let row = sqlx::query(
-            r"
-            SELECT id, account_id, subject, to_addrs, cc_addrs, bcc_addrs, reply_to,
-                   body_text, body_html, attachments, in_reply_to, draft_references,
-                   created_at, updated_at, auto_saved
-            FROM drafts WHERE id = ?
-        ",
-        )
-        .bind(draft_id)
-        .fetch_optional(&self.pool)
-        .await?;
+        let row = sqlx::query(&format!("SELECT {} FROM drafts WHERE id = ?", DRAFT_COLUMNS))
+            .bind(draft_id)
+            .fetch_optional(&self.pool)
+            .await?;
 
-        if let Some(row) = row {
-            let to_addrs: Vec<String> = serde_json::from_str(&row.get::<String, _>("to_addrs"))?;
-            let cc_addrs: Vec<String> = serde_json::from_str(&row.get::<String, _>("cc_addrs"))?;
-            let bcc_addrs: Vec<String> = serde_json::from_str(&row.get::<String, _>("bcc_addrs"))?;
-            let attachments: Vec<StoredAttachment> =
-                serde_json::from_str(&row.get::<String, _>("attachments"))?;
-            let references: Vec<String> =
-                serde_json::from_str(&row.get::<String, _>("draft_references"))?;
-
-            let created_at_str: String = row.get("created_at");
-            let updated_at_str: String = row.get("updated_at");
-
-            Ok(Some(StoredDraft {
-                id: row.get("id"),
-                account_id: row.get("account_id"),
-                subject: row.get("subject"),
-                to_addrs,
-                cc_addrs,
-                bcc_addrs,
-                reply_to: row.get("reply_to"),
-                body_text: row.get("body_text"),
-                body_html: row.get("body_html"),
-                attachments,
-                in_reply_to: row.get("in_reply_to"),
-                references,
-                created_at: DateTime::parse_from_rfc3339(&created_at_str)?.with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&updated_at_str)?.with_timezone(&Utc),
-                auto_saved: row.get("auto_saved"),
-            }))
-        } else {
-            Ok(None)
-        }
+        row.map(Self::row_to_stored_draft).transpose()
     }
 
     /// Load all drafts for an account
@@ -1306,52 +2653,130 @@ impl EmailDatabase {
         &self,
         account_id: &str,
     ) -> DatabaseResult<Vec<StoredDraft>> {
-        let rows = sqlx::query(
-            r"
-            SELECT id, account_id, subject, to_addrs, cc_addrs, bcc_addrs, reply_to,
-                   body_text, body_html, attachments, in_reply_to, draft_references,
-                   created_at, updated_at, auto_saved
-            FROM drafts WHERE account_id = ?
-            ORDER BY updated_at DESC
-        ",
-        )
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM drafts WHERE account_id = ? ORDER BY updated_at DESC",
+            DRAFT_COLUMNS
+        ))
         .bind(account_id)
         .fetch_all(&self.pool)
         .await?;
 
-        let mut drafts = Vec::new();
-        for row in rows {
-            let to_addrs: Vec<String> = serde_json::from_str(&row.get::<String, _>("to_addrs"))?;
-            let cc_addrs: Vec<String> = serde_json::from_str(&row.get::<String, _>("cc_addrs"))?;
-            let bcc_addrs: Vec<String> = serde_json::from_str(&row.get::<String, _>("bcc_addrs"))?;
-            let attachments: Vec<StoredAttachment> =
-                serde_json::from_str(&row.get::<String, _>("attachments"))?;
-            let references: Vec<String> =
-                serde_json::from_str(&row.get::<String, _>("draft_references"))?;
-
-            let created_at_str: String = row.get("created_at");
-            let updated_at_str: String = row.get("updated_at");
-
-            drafts.push(StoredDraft {
-                id: row.get("id"),
-                account_id: row.get("account_id"),
-                subject: row.get("subject"),
-                to_addrs,
-                cc_addrs,
-                bcc_addrs,
-                reply_to: row.get("reply_to"),
-                body_text: row.get("body_text"),
-                body_html: row.get("body_html"),
-                attachments,
-                in_reply_to: row.get("in_reply_to"),
-                references,
-                created_at: DateTime::parse_from_rfc3339(&created_at_str)?.with_timezone(&Utc),
-                updated_at: DateTime::parse_from_rfc3339(&updated_at_str)?.with_timezone(&Utc),
-                auto_saved: row.get("auto_saved"),
-            });
-        }
+        rows.into_iter().map(Self::row_to_stored_draft).collect()
+    }
+
+    /// Scheduled sends of every account that are due by `now`, including
+    /// failed ones whose retry time has come
+    pub async fn get_due_scheduled_drafts(&self, now: DateTime<Utc>) -> DatabaseResult<Vec<StoredDraft>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM drafts
+             WHERE scheduled_at IS NOT NULL AND scheduled_at <= ?1
+               AND (next_attempt_at IS NULL OR next_attempt_at <= ?1)
+             ORDER BY scheduled_at",
+            DRAFT_COLUMNS
+        ))
+        .bind(now.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_stored_draft).collect()
+    }
 
-        Ok(drafts)
+    /// Pending scheduled sends of every account, soonest first
+    pub async fn get_scheduled_drafts(&self) -> DatabaseResult<Vec<StoredDraft>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM drafts WHERE scheduled_at IS NOT NULL ORDER BY scheduled_at",
+            DRAFT_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter().map(Self::row_to_stored_draft).collect()
+    }
+
+    /// Schedule a draft to be sent at `at`, or (None) make it a plain draft again
+    ///
+    /// Any earlier failed attempts are forgotten.
+    pub async fn set_draft_schedule(
+        &self,
+        draft_id: &str,
+        at: Option<DateTime<Utc>>,
+    ) -> DatabaseResult<bool> {
+        let result = sqlx::query(
+            "UPDATE drafts SET scheduled_at = ?1, send_attempts = 0, next_attempt_at = NULL,
+                    last_send_error = NULL, auto_saved = FALSE, updated_at = ?2
+             WHERE id = ?3",
+        )
+        .bind(at.map(|at| at.to_rfc3339()))
+        .bind(Utc::now().to_rfc3339())
+        .bind(draft_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Note a failed scheduled send and when to try it again
+    pub async fn record_scheduled_send_failure(
+        &self,
+        draft_id: &str,
+        error: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> DatabaseResult<()> {
+        sqlx::query(
+            "UPDATE drafts SET send_attempts = send_attempts + 1, next_attempt_at = ?1,
+                    last_send_error = ?2
+             WHERE id = ?3",
+        )
+        .bind(next_attempt_at.to_rfc3339())
+        .bind(error)
+        .bind(draft_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    fn row_to_stored_draft(row: sqlx::sqlite::SqliteRow) -> DatabaseResult<StoredDraft> {
+        let to_addrs: Vec<String> = serde_json::from_str(&row.get::<String, _>("to_addrs"))?;
+        let cc_addrs: Vec<String> = serde_json::from_str(&row.get::<String, _>("cc_addrs"))?;
+        let bcc_addrs: Vec<String> = serde_json::from_str(&row.get::<String, _>("bcc_addrs"))?;
+        let attachments: Vec<StoredAttachment> =
+            serde_json::from_str(&row.get::<String, _>("attachments"))?;
+        let references: Vec<String> =
+            serde_json::from_str(&row.get::<String, _>("draft_references"))?;
+
+        let created_at_str: String = row.get("created_at");
+        let updated_at_str: String = row.get("updated_at");
+        let parse_optional = |value: Option<String>| -> DatabaseResult<Option<DateTime<Utc>>> {
+            Ok(value
+                .map(|value| DateTime::parse_from_rfc3339(&value))
+                .transpose()?
+                .map(|at| at.with_timezone(&Utc)))
+        };
+
+        Ok(StoredDraft {
+            id: row.get("id"),
+            account_id: row.get("account_id"),
+            subject: row.get("subject"),
+            to_addrs,
+            cc_addrs,
+            bcc_addrs,
+            reply_to: row.get("reply_to"),
+            body_text: row.get("body_text"),
+            body_html: row.get("body_html"),
+            attachments,
+            in_reply_to: row.get("in_reply_to"),
+            references,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)?.with_timezone(&Utc),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at_str)?.with_timezone(&Utc),
+            auto_saved: row.get("auto_saved"),
+            encrypt: row.get("encrypt"),
+            sign: row.get("sign"),
+            scheduled_at: parse_optional(row.get("scheduled_at"))?,
+            send_attempts: row.get::<i64, _>("send_attempts") as u32,
+            next_attempt_at: parse_optional(row.get("next_attempt_at"))?,
+            last_send_error: row.get("last_send_error"),
+        })
     }
 
     /// Delete a draft by ID
@@ -1542,11 +2967,17 @@ impl StoredDraft {
             body_text: compose_data.body.clone(),
             body_html: String::new(), // TODO: Support HTML composition
             attachments: Vec::new(),  // TODO: Support draft attachments
-            in_reply_to: None,        // TODO: Extract from compose data if this is a reply
-            references: Vec::new(),   // TODO: Extract references for replies
+            in_reply_to: compose_data.in_reply_to.clone(),
+            references: Self::split_references(compose_data),
             created_at: now,
             updated_at: now,
             auto_saved,
+            encrypt: compose_data.encrypt,
+            sign: compose_data.sign,
+            scheduled_at: None,
+            send_attempts: 0,
+            next_attempt_at: None,
+            last_send_error: None,
         }
     }
 
@@ -1557,6 +2988,10 @@ impl StoredDraft {
         self.cc_addrs = EmailComposeData::parse_addresses(&compose_data.cc);
         self.bcc_addrs = EmailComposeData::parse_addresses(&compose_data.bcc);
         self.body_text = compose_data.body.clone();
+        self.in_reply_to = compose_data.in_reply_to.clone();
+        self.references = Self::split_references(compose_data);
+        self.encrypt = compose_data.encrypt;
+        self.sign = compose_data.sign;
         self.updated_at = Utc::now();
         self.auto_saved = auto_saved;
     }
@@ -1569,8 +3004,23 @@ impl StoredDraft {
             bcc: self.bcc_addrs.join(", "),
             subject: self.subject.clone(),
             body: self.body_text.clone(),
+            in_reply_to: self.in_reply_to.clone(),
+            references: (!self.references.is_empty()).then(|| self.references.join(" ")),
+            priority: crate::email::MailPriority::Normal,
+            encrypt: self.encrypt,
+            sign: self.sign,
+            attachments: Vec::new(),
         }
     }
+
+    /// Keep the thread headers of a reply, so a draft sent later still threads
+    fn split_references(compose_data: &EmailComposeData) -> Vec<String> {
+        compose_data
+            .references
+            .as_deref()
+            .map(|refs| refs.split_whitespace().map(String::from).collect())
+            .unwrap_or_default()
+    }
 }
 
 /// Convert IMAP message to stored message
@@ -1579,8 +3029,26 @@ impl StoredMessage {
         imap_message: &ImapMessage,
         account_id: String,
         folder_name: String,
+    ) -> Self {
+        let parsed = imap_message
+            .body
+            .as_deref()
+            .map(crate::mime::parse_message);
+        Self::from_parsed_imap_message(imap_message, parsed.as_ref(), account_id, folder_name)
+    }
+
+    /// Convert an IMAP message whose body has already been MIME-parsed
+    ///
+    /// Bodies the parser found no text in fall back to the line-based cleanup.
+    pub fn from_parsed_imap_message(
+        imap_message: &ImapMessage,
+        parsed: Option<&crate::mime::ParsedBody>,
+        account_id: String,
+        folder_name: String,
     ) -> Self {
         let now = Utc::now();
+        let calendar = parsed.and_then(|parsed| parsed.calendar.clone());
+        let parsed = parsed.filter(|parsed| parsed.has_content());
 
         // Extract envelope information if available
         let envelope = imap_message.envelope.as_ref();
@@ -1594,7 +3062,7 @@ impl StoredMessage {
             Uuid::new_v5(&namespace, id_string.as_bytes())
         };
 
-        Self {
+        let mut message = Self {
             id: deterministic_id,
             account_id,
             folder_name,
@@ -1641,8 +3109,26 @@ impl StoredMessage {
                 .and_then(|env| env.reply_to.first())
                 .and_then(|addr| addr.email_address()),
             date: imap_message.internal_date.unwrap_or(now),
-            body_text: Self::parse_and_clean_body_text(&imap_message.body),
-            body_html: Self::parse_and_clean_body_html(&imap_message.body),
+            body_text: match parsed {
+                Some(parsed) => parsed.text.clone().or_else(|| {
+                    parsed
+                        .html
+                        .as_deref()
+                        .map(|html| crate::html::HtmlRenderer::new(80).html_to_plain_text(html))
+                }),
+                None => Self::parse_and_clean_body_text(&imap_message.body),
+            }
+            .map(|text| {
+                // Join format=flowed paragraphs so the viewer can wrap them to its width
+                match imap_message.body.as_deref().and_then(crate::email::flowed::detect) {
+                    Some(format) => crate::email::flowed::unflow(&text, format.delsp),
+                    None => text,
+                }
+            }),
+            body_html: match parsed {
+                Some(parsed) => parsed.html.clone(),
+                None => Self::parse_and_clean_body_html(&imap_message.body),
+            },
             attachments: Vec::new(), // Would need body structure parsing
             flags: imap_message
                 .flags
@@ -1657,15 +3143,53 @@ impl StoredMessage {
                     MessageFlag::Custom(s) => s.clone(),
                 })
                 .collect(),
-            labels: Vec::new(), // Gmail-specific labels handled separately
+            labels: imap_message.labels.clone(),
             size: imap_message.size,
-            priority: None, // Extract from headers if needed
+            priority: imap_message
+                .body
+                .as_deref()
+                .and_then(crate::email::MailPriority::from_raw_message)
+                .map(|priority| priority.as_str().to_string()),
             created_at: now,
             updated_at: now,
             last_synced: now,
             sync_version: 1,
             is_draft: imap_message.flags.contains(&MessageFlag::Draft),
             is_deleted: imap_message.flags.contains(&MessageFlag::Deleted),
+            snoozed_until: None,
+            inbox_tab: None,
+        };
+        if let Some(calendar) = calendar {
+            message.attach_calendar(calendar);
+        }
+        message
+    }
+
+    /// Keep the iCalendar object of a meeting invitation with the message
+    ///
+    /// Fills in the calendar attachment found in the body structure, or adds
+    /// one when the calendar was an inline part.
+    pub fn attach_calendar(&mut self, data: String) {
+        let is_calendar = |attachment: &StoredAttachment| {
+            let content_type = attachment.content_type.to_ascii_lowercase();
+            content_type.starts_with("text/calendar") || content_type.starts_with("application/ics")
+        };
+        match self
+            .attachments
+            .iter_mut()
+            .find(|attachment| is_calendar(attachment) && attachment.data.is_none())
+        {
+            Some(attachment) => attachment.data = Some(data.into_bytes()),
+            None => self.attachments.push(StoredAttachment {
+                id: format!("calendar_{}", self.id),
+                filename: "invite.ics".to_string(),
+                content_type: "text/calendar".to_string(),
+                size: data.len() as u32,
+                content_id: None,
+                is_inline: true,
+                data: Some(data.into_bytes()),
+                file_path: None,
+            }),
         }
     }
 
@@ -2168,6 +3692,8 @@ mod tests {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         };
 
         // Store message
@@ -2253,6 +3779,8 @@ mod tests {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         };
 
         db.store_message(&message).await.unwrap();
@@ -2279,6 +3807,363 @@ mod tests {
             .unwrap();
         assert_eq!(results.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_folder_sort_prefs() {
+        use crate::email::SortOrder;
+
+        let db = EmailDatabase::new_in_memory().await.unwrap();
+        assert_eq!(db.get_folder_sort("acct", "INBOX").await.unwrap(), None);
+
+        db.set_folder_sort("acct", "INBOX", &SortCriteria::Sender(SortOrder::Ascending))
+            .await
+            .unwrap();
+        db.set_folder_sort("acct", "INBOX", &SortCriteria::Size(SortOrder::Descending))
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_folder_sort("acct", "INBOX").await.unwrap(),
+            Some(SortCriteria::Size(SortOrder::Descending))
+        );
+
+        // Each folder and account keeps its own
+        assert_eq!(db.get_folder_sort("acct", "Archive").await.unwrap(), None);
+        assert_eq!(db.get_folder_sort("other", "INBOX").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_folder_sync_mode_override() {
+        let db = EmailDatabase::new_in_memory().await.unwrap();
+
+        // Setting a mode on a folder that was never synced creates its state
+        db.set_folder_sync_mode("acct", "Archive", Some(FolderSyncMode::HeadersOnly))
+            .await
+            .unwrap();
+        let mut state = db
+            .get_folder_sync_state("acct", "Archive")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(state.sync_mode, Some(FolderSyncMode::HeadersOnly));
+
+        // A sync writing back a stale copy keeps the override
+        state.sync_mode = None;
+        state.uid_next = 42;
+        db.update_folder_sync_state(&state).await.unwrap();
+        let state = db
+            .get_folder_sync_state("acct", "Archive")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(state.uid_next, 42);
+        assert_eq!(state.sync_mode, Some(FolderSyncMode::HeadersOnly));
+
+        db.set_folder_sync_mode("acct", "Archive", None).await.unwrap();
+        let state = db
+            .get_folder_sync_state("acct", "Archive")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(state.sync_mode, None);
+        assert_eq!(state.uid_next, 42);
+    }
+
+    #[tokio::test]
+    async fn test_jmap_uid_mapping() {
+        let db = EmailDatabase::new_in_memory().await.unwrap();
+
+        let first = db.jmap_uid("acct", "M1").await.unwrap();
+        let second = db.jmap_uid("acct", "M2").await.unwrap();
+        assert_eq!((first, second), (1, 2));
+        assert_eq!(db.jmap_uid("acct", "M1").await.unwrap(), first);
+        assert_eq!(db.jmap_uid("other", "M1").await.unwrap(), 1);
+
+        assert_eq!(
+            db.jmap_email_ids("acct", &[second, 99, first]).await.unwrap(),
+            vec!["M2".to_string(), "M1".to_string()]
+        );
+
+        // Destroyed emails keep their UID so it's never reused
+        assert_eq!(db.remove_jmap_emails("acct", &["M2".to_string()]).await.unwrap(), vec![second]);
+        assert_eq!(db.jmap_uid("acct", "M3").await.unwrap(), 3);
+
+        assert_eq!(db.get_jmap_email_state("acct").await.unwrap(), None);
+        db.set_jmap_email_state("acct", "s1").await.unwrap();
+        db.set_jmap_email_state("acct", "s2").await.unwrap();
+        assert_eq!(db.get_jmap_email_state("acct").await.unwrap().as_deref(), Some("s2"));
+    }
+
+    #[tokio::test]
+    async fn test_gmail_label_views() {
+        let db = EmailDatabase::new_in_memory().await.unwrap();
+
+        let labelled = |uid: u32, labels: &[&str]| StoredMessage {
+            id: Uuid::new_v4(),
+            account_id: "gmail".to_string(),
+            folder_name: "[Gmail]/All Mail".to_string(),
+            imap_uid: uid,
+            message_id: Some(format!("{}@example.com", uid)),
+            thread_id: None,
+            in_reply_to: None,
+            references: vec![],
+            subject: format!("Message {}", uid),
+            from_addr: "sender@example.com".to_string(),
+            from_name: None,
+            to_addrs: vec!["me@gmail.com".to_string()],
+            cc_addrs: vec![],
+            bcc_addrs: vec![],
+            reply_to: None,
+            date: Utc::now(),
+            body_text: None,
+            body_html: None,
+            attachments: vec![],
+            flags: vec![],
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            size: None,
+            priority: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_synced: Utc::now(),
+            sync_version: 1,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        };
+        let important = labelled(1, &["\\Important", "\\Inbox"]);
+        let work = labelled(2, &["Work"]);
+        db.store_message(&important).await.unwrap();
+        db.store_message(&work).await.unwrap();
+
+        let layout = LabelLayout {
+            all_mail: "[Gmail]/All Mail".to_string(),
+            views: [
+                ("INBOX", "\\Inbox"),
+                ("[Gmail]/Important", "\\Important"),
+                ("Work", "Work"),
+            ]
+            .into_iter()
+            .map(|(folder, label)| (folder.to_string(), label.to_string()))
+            .collect(),
+        };
+        db.set_label_layout("gmail", Some(&layout)).await.unwrap();
+        assert_eq!(db.get_label_layout("gmail").await.unwrap(), Some(layout));
+
+        // One stored message, listed by both of its labels
+        let ids = |messages: Vec<StoredMessage>| -> Vec<Uuid> {
+            messages.into_iter().map(|message| message.id).collect()
+        };
+        let inbox = db.get_messages("gmail", "INBOX", None, None).await.unwrap();
+        let important_view = db.get_messages("gmail", "[Gmail]/Important", None, None).await.unwrap();
+        assert_eq!(ids(inbox), vec![important.id]);
+        assert_eq!(ids(important_view), vec![important.id]);
+        assert_eq!(db.get_messages("gmail", "[Gmail]/All Mail", None, None).await.unwrap().len(), 2);
+
+        db.update_message_labels(important.id, &["\\Important".to_string(), "Work".to_string()])
+            .await
+            .unwrap();
+        assert!(db.get_messages("gmail", "INBOX", None, None).await.unwrap().is_empty());
+        assert_eq!(db.get_messages("gmail", "Work", None, None).await.unwrap().len(), 2);
+
+        db.set_label_layout("gmail", None).await.unwrap();
+        assert_eq!(db.get_label_layout("gmail").await.unwrap(), None);
+        assert!(db.get_messages("gmail", "Work", None, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_inbox_tab_overrides() {
+        let db = EmailDatabase::new_in_memory().await.unwrap();
+
+        let message = |uid: u32, from_addr: &str| StoredMessage {
+            id: Uuid::new_v4(),
+            account_id: "work".to_string(),
+            folder_name: "INBOX".to_string(),
+            imap_uid: uid,
+            message_id: Some(format!("{}@example.com", uid)),
+            thread_id: None,
+            in_reply_to: None,
+            references: vec![],
+            subject: format!("Message {}", uid),
+            from_addr: from_addr.to_string(),
+            from_name: None,
+            to_addrs: vec!["me@example.com".to_string()],
+            cc_addrs: vec![],
+            bcc_addrs: vec![],
+            reply_to: None,
+            date: Utc::now(),
+            body_text: None,
+            body_html: None,
+            attachments: vec![],
+            flags: vec![],
+            labels: vec![],
+            size: None,
+            priority: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_synced: Utc::now(),
+            sync_version: 1,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        };
+        let shop = message(1, "Shop <Deals@Shop.example>");
+        let bare = message(2, "deals@shop.example");
+        let friend = message(3, "ana@example.com");
+        for m in [&shop, &bare, &friend] {
+            db.store_message(m).await.unwrap();
+        }
+
+        db.set_inbox_tab(friend.id, Some(InboxTab::Social)).await.unwrap();
+        let stored = db.get_message_by_id(friend.id).await.unwrap().unwrap();
+        assert_eq!(stored.inbox_tab, Some(InboxTab::Social));
+
+        // Correcting a tab moves the sender's stored mail and is remembered
+        assert_eq!(db.get_inbox_tab_override("work", "deals@shop.example").await.unwrap(), None);
+        db.set_inbox_tab_override("work", "deals@shop.example", InboxTab::Promotions)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_inbox_tab_override("work", "deals@shop.example").await.unwrap(),
+            Some(InboxTab::Promotions)
+        );
+        for id in [shop.id, bare.id] {
+            let stored = db.get_message_by_id(id).await.unwrap().unwrap();
+            assert_eq!(stored.inbox_tab, Some(InboxTab::Promotions));
+        }
+        let stored = db.get_message_by_id(friend.id).await.unwrap().unwrap();
+        assert_eq!(stored.inbox_tab, Some(InboxTab::Social));
+        assert_eq!(db.get_inbox_tab_override("home", "deals@shop.example").await.unwrap(), None);
+
+        // A later sync keeps the tab
+        db.store_message(&shop).await.unwrap();
+        let stored = db.get_message_by_id(shop.id).await.unwrap().unwrap();
+        assert_eq!(stored.inbox_tab, Some(InboxTab::Promotions));
+    }
+
+    #[tokio::test]
+    async fn test_snoozed_messages() {
+        let db = EmailDatabase::new_in_memory().await.unwrap();
+
+        let message = |uid: u32| StoredMessage {
+            id: Uuid::new_v4(),
+            account_id: "work".to_string(),
+            folder_name: "INBOX".to_string(),
+            imap_uid: uid,
+            message_id: Some(format!("{}@example.com", uid)),
+            thread_id: None,
+            in_reply_to: None,
+            references: vec![],
+            subject: format!("Message {}", uid),
+            from_addr: "sender@example.com".to_string(),
+            from_name: None,
+            to_addrs: vec!["me@example.com".to_string()],
+            cc_addrs: vec![],
+            bcc_addrs: vec![],
+            reply_to: None,
+            date: Utc::now(),
+            body_text: None,
+            body_html: None,
+            attachments: vec![],
+            flags: vec![],
+            labels: vec![],
+            size: None,
+            priority: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_synced: Utc::now(),
+            sync_version: 1,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        };
+        let later = message(1);
+        let due = message(2);
+        let awake = message(3);
+        for m in [&later, &due, &awake] {
+            db.store_message(m).await.unwrap();
+        }
+
+        let now = Utc::now();
+        db.set_snoozed_until(later.id, Some(now + chrono::Duration::hours(12))).await.unwrap();
+        db.set_snoozed_until(due.id, Some(now - chrono::Duration::minutes(5))).await.unwrap();
+
+        // Snoozed messages leave the folder counts but stay in the folder
+        assert_eq!(db.get_account_messages("work", None).await.unwrap().len(), 1);
+        assert_eq!(db.count_snoozed_messages("work").await.unwrap(), 2);
+        let snoozed: Vec<Uuid> = db
+            .get_snoozed_messages("work")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(snoozed, vec![due.id, later.id]);
+        assert_eq!(db.get_accounts_with_due_snoozes(now).await.unwrap(), vec!["work".to_string()]);
+
+        // A sync of the message keeps its snooze
+        db.store_message(&due).await.unwrap();
+        assert!(db.get_message_by_id(due.id).await.unwrap().unwrap().snoozed_until.is_some());
+
+        db.set_snoozed_until(due.id, None).await.unwrap();
+        assert!(db.get_accounts_with_due_snoozes(now).await.unwrap().is_empty());
+        assert_eq!(db.count_snoozed_messages("work").await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_drafts() {
+        let db = EmailDatabase::new_in_memory().await.unwrap();
+
+        let compose = EmailComposeData {
+            to: "friend@example.com".to_string(),
+            subject: "Re: Plans".to_string(),
+            body: "See you then".to_string(),
+            in_reply_to: Some("<plans@example.com>".to_string()),
+            references: Some("<start@example.com> <plans@example.com>".to_string()),
+            ..Default::default()
+        };
+        let now = Utc::now();
+        let mut due = StoredDraft::from_compose_data("work".to_string(), &compose, false);
+        due.scheduled_at = Some(now - chrono::Duration::minutes(1));
+        let mut later = StoredDraft::from_compose_data("work".to_string(), &compose, false);
+        later.scheduled_at = Some(now + chrono::Duration::hours(3));
+        let plain = StoredDraft::from_compose_data("work".to_string(), &compose, false);
+        for draft in [&due, &later, &plain] {
+            db.save_draft(draft).await.unwrap();
+        }
+
+        let found = db.get_due_scheduled_drafts(now).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, due.id);
+        assert_eq!(found[0].references.len(), 2);
+        assert_eq!(
+            found[0].to_compose_data().in_reply_to.as_deref(),
+            Some("<plans@example.com>")
+        );
+
+        // A failed send waits for its retry time
+        let retry = now + chrono::Duration::minutes(2);
+        db.record_scheduled_send_failure(&due.id, "connection refused", retry)
+            .await
+            .unwrap();
+        assert!(db.get_due_scheduled_drafts(now).await.unwrap().is_empty());
+        let failed = db.get_due_scheduled_drafts(retry).await.unwrap();
+        assert_eq!(failed[0].send_attempts, 1);
+        assert_eq!(failed[0].last_send_error.as_deref(), Some("connection refused"));
+
+        // Cancelling keeps the message as a plain draft
+        assert!(db.set_draft_schedule(&due.id, None).await.unwrap());
+        let cancelled = db.load_draft(&due.id).await.unwrap().unwrap();
+        assert!(cancelled.scheduled_at.is_none());
+        assert_eq!(cancelled.send_attempts, 0);
+        assert!(db
+            .get_due_scheduled_drafts(now + chrono::Duration::days(1))
+            .await
+            .unwrap()
+            .iter()
+            .all(|draft| draft.id == later.id));
+    }
 }
 
 // CLI Support Methods
@@ -217,7 +217,7 @@ impl OptimizedDatabase {
                    subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
                    body_text, body_html, attachments,
                    flags, labels, size, priority,
-                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted
+                   created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
             FROM messages
             WHERE account_id = ?1 AND folder_name = ?2 AND is_deleted = FALSE
             {}
@@ -416,7 +416,7 @@ impl OptimizedDatabase {
                        m.subject, m.from_addr, m.from_name, m.to_addrs, m.cc_addrs, m.bcc_addrs, m.reply_to, m.date,
                        m.body_text, m.body_html, m.attachments,
                        m.flags, m.labels, m.size, m.priority,
-                       m.created_at, m.updated_at, m.last_synced, m.sync_version, m.is_draft, m.is_deleted
+                       m.created_at, m.updated_at, m.last_synced, m.sync_version, m.is_draft, m.is_deleted, m.snoozed_until, m.inbox_tab
                 FROM messages m
                 WHERE {}
                 ORDER BY m.date DESC
@@ -437,7 +437,7 @@ impl OptimizedDatabase {
                        m.subject, m.from_addr, m.from_name, m.to_addrs, m.cc_addrs, m.bcc_addrs, m.reply_to, m.date,
                        m.body_text, m.body_html, m.attachments,
                        m.flags, m.labels, m.size, m.priority,
-                       m.created_at, m.updated_at, m.last_synced, m.sync_version, m.is_draft, m.is_deleted
+                       m.created_at, m.updated_at, m.last_synced, m.sync_version, m.is_draft, m.is_deleted, m.snoozed_until, m.inbox_tab
                 FROM messages m
                 JOIN messages_fts fts ON m.rowid = fts.rowid
                 WHERE {}
@@ -663,6 +663,13 @@ impl OptimizedDatabase {
             sync_version: row.get("sync_version"),
             is_draft: row.get("is_draft"),
             is_deleted: row.get("is_deleted"),
+            snoozed_until: row
+                .get::<Option<String>, _>("snoozed_until")
+                .and_then(|until| chrono::DateTime::parse_from_rfc3339(&until).ok())
+                .map(Into::into),
+            inbox_tab: row
+                .get::<Option<String>, _>("inbox_tab")
+                .and_then(|tab| tab.parse().ok()),
         })
     }
 }
@@ -0,0 +1,231 @@
+//! Collapsing copies of the same message
+//!
+//! Gmail keeps every message in "All Mail" as well as under its labels, and
+//! mail you send to yourself or to a list you're on comes back to the inbox
+//! next to the copy in Sent. Copies are matched by Message-ID, or by a hash
+//! of sender, subject, date and body when a message has none. One copy is
+//! shown, preferring the folder being viewed, then the inbox, then other
+//! folders, with Sent and All Mail last, and the item notes every folder and
+//! label the message is in.
+//!
+//! Within a single folder duplicates are rare and usually deliberate, so by
+//! default only the unified view is deduplicated.
+
+use crate::email::StoredMessage;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Where duplicates are collapsed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DedupConfig {
+    /// Collapse duplicates within a folder
+    pub folder_view: bool,
+    /// Collapse duplicates in the unified view of all folders
+    pub unified_view: bool,
+}
+
+impl Default for DedupConfig {
+    fn default() -> Self {
+        Self {
+            folder_view: false,
+            unified_view: true,
+        }
+    }
+}
+
+impl DedupConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "dedup.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// A message with every folder and label its copies are in
+#[derive(Debug, Clone)]
+pub struct DedupedMessage {
+    pub message: StoredMessage,
+    pub locations: Vec<String>,
+}
+
+/// Key shared by copies of the same message
+pub fn dedup_key(message: &StoredMessage) -> String {
+    if let Some(id) = message
+        .message_id
+        .as_deref()
+        .map(|id| id.trim().trim_start_matches('<').trim_end_matches('>'))
+        .filter(|id| !id.is_empty())
+    {
+        return format!("id:{}", id.to_lowercase());
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(message.from_addr.trim().to_lowercase());
+    hasher.update([0]);
+    hasher.update(message.subject.trim());
+    hasher.update([0]);
+    hasher.update(message.date.timestamp().to_be_bytes());
+    hasher.update([0]);
+    hasher.update(message.body_text.as_deref().unwrap_or("").trim());
+    format!("hash:{:x}", hasher.finalize())
+}
+
+/// Folders that are left out of the unified view
+pub fn excluded_from_unified(folder_name: &str) -> bool {
+    let name = folder_name.to_lowercase();
+    ["trash", "deleted", "spam", "junk", "draft"]
+        .iter()
+        .any(|excluded| name.contains(excluded))
+}
+
+/// Lower is preferred as the copy to show
+fn folder_rank(folder_name: &str, preferred: Option<&str>) -> u8 {
+    let name = folder_name.to_lowercase();
+    let leaf = name.rsplit(['/', '.']).next().unwrap_or(&name);
+    if preferred == Some(folder_name) {
+        0
+    } else if name == "inbox" {
+        1
+    } else if leaf.starts_with("sent") {
+        3
+    } else if leaf == "all mail" || leaf == "archive" {
+        4
+    } else {
+        2
+    }
+}
+
+/// Collapse copies of the same message, keeping the order of first sight
+///
+/// `preferred_folder` is the folder being viewed, if any; its copy is shown
+/// when it has one.
+pub fn dedupe(messages: Vec<StoredMessage>, preferred_folder: Option<&str>) -> Vec<DedupedMessage> {
+    let mut deduped: Vec<DedupedMessage> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for message in messages {
+        let key = dedup_key(&message);
+        let Some(&i) = index.get(&key) else {
+            index.insert(key, deduped.len());
+            let mut locations = vec![message.folder_name.clone()];
+            add_labels(&mut locations, &message.labels);
+            deduped.push(DedupedMessage { message, locations });
+            continue;
+        };
+
+        let entry = &mut deduped[i];
+        if !entry.locations.contains(&message.folder_name) {
+            entry.locations.push(message.folder_name.clone());
+        }
+        add_labels(&mut entry.locations, &message.labels);
+        if folder_rank(&message.folder_name, preferred_folder)
+            < folder_rank(&entry.message.folder_name, preferred_folder)
+        {
+            entry.message = message;
+        }
+    }
+
+    deduped
+}
+
+fn add_labels(locations: &mut Vec<String>, labels: &[String]) {
+    for label in labels {
+        // Gmail's system labels mirror flags or folders already listed
+        let label = label.trim_matches('"');
+        if label.starts_with('\\') || locations.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+            continue;
+        }
+        locations.push(label.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    fn stored(folder: &str, message_id: Option<&str>, subject: &str) -> StoredMessage {
+        let date = Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap();
+        StoredMessage {
+            id: Uuid::new_v4(),
+            account_id: "gmail".to_string(),
+            folder_name: folder.to_string(),
+            imap_uid: 1,
+            message_id: message_id.map(str::to_string),
+            thread_id: None,
+            in_reply_to: None,
+            references: Vec::new(),
+            subject: subject.to_string(),
+            from_addr: "me@example.com".to_string(),
+            from_name: None,
+            to_addrs: Vec::new(),
+            cc_addrs: Vec::new(),
+            bcc_addrs: Vec::new(),
+            reply_to: None,
+            date,
+            body_text: Some("Notes attached.".to_string()),
+            body_html: None,
+            attachments: Vec::new(),
+            flags: Vec::new(),
+            labels: Vec::new(),
+            size: None,
+            priority: None,
+            created_at: date,
+            updated_at: date,
+            last_synced: date,
+            sync_version: 1,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        }
+    }
+
+    #[test]
+    fn test_dedupe_prefers_inbox_and_notes_locations() {
+        let mut all_mail = stored("[Gmail]/All Mail", Some("<a1@example.com>"), "Minutes");
+        all_mail.labels = vec!["\\Inbox".to_string(), "Work".to_string()];
+        let messages = vec![
+            all_mail,
+            stored("[Gmail]/Sent Mail", Some("<A1@example.com>"), "Minutes"),
+            stored("INBOX", Some("a1@example.com"), "Minutes"),
+            // No Message-ID: matched on content
+            stored("[Gmail]/Sent Mail", None, "Self note"),
+            stored("INBOX", None, "Self note"),
+            stored("INBOX", None, "Other note"),
+        ];
+
+        let deduped = dedupe(messages, None);
+        assert_eq!(deduped.len(), 3);
+        assert_eq!(deduped[0].message.folder_name, "INBOX");
+        assert_eq!(
+            deduped[0].locations,
+            vec!["[Gmail]/All Mail", "Work", "[Gmail]/Sent Mail", "INBOX"]
+        );
+        assert_eq!(deduped[1].message.folder_name, "INBOX");
+        assert_eq!(deduped[2].locations, vec!["INBOX"]);
+
+        let preferred = dedupe(
+            vec![
+                stored("INBOX", Some("<b@example.com>"), "x"),
+                stored("Projects", Some("<b@example.com>"), "x"),
+            ],
+            Some("Projects"),
+        );
+        assert_eq!(preferred[0].message.folder_name, "Projects");
+
+        assert!(excluded_from_unified("[Gmail]/Trash"));
+        assert!(!excluded_from_unified("INBOX"));
+    }
+}
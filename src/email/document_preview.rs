@@ -0,0 +1,396 @@
+//! Previews of PDF and office attachments
+//!
+//! The first page of a PDF is rendered to a PNG with `pdftoppm` (poppler) or
+//! `mutool` (MuPDF), so it can go through the image pipeline like any other
+//! picture; `pdftotext` gives a text preview where the terminal can't show
+//! graphics. Word, Excel and PowerPoint files in the zip-based formats are
+//! unpacked with `unzip` and their text pulled out of the XML parts.
+//!
+//! None of these tools is required: when one is missing or fails the viewer
+//! falls back to file information and offers to save the attachment.
+
+use crate::email::AttachmentType;
+use std::path::{Path, PathBuf};
+use std::process::{Output, Stdio};
+use thiserror::Error;
+
+/// Width in pixels of the rendered first page
+const PAGE_WIDTH: u32 = 1024;
+
+/// Pages of a PDF included in a text preview
+const TEXT_PAGES: u32 = 3;
+
+/// Errors from building a document preview
+#[derive(Error, Debug)]
+pub enum PreviewError {
+    #[error("{0} is not installed")]
+    ToolMissing(String),
+    #[error("{tool} failed: {message}")]
+    Failed { tool: String, message: String },
+    #[error("{0}")]
+    Unsupported(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type PreviewResult<T> = Result<T, PreviewError>;
+
+/// PNG of the first page of a PDF
+///
+/// Tries `pdftoppm` first and `mutool` when poppler isn't installed.
+/// Scratch files go in `work_dir` and are removed afterwards.
+pub async fn render_pdf_page(data: &[u8], work_dir: &Path) -> PreviewResult<Vec<u8>> {
+    let input = ScratchFile::write(work_dir, "pdf", data).await?;
+    let output = ScratchFile::reserve(work_dir, "png");
+    let (input_path, output_path) = (input.path_str(), output.path_str());
+    let width = PAGE_WIDTH.to_string();
+
+    // pdftoppm appends the extension itself
+    let prefix = output
+        .path
+        .with_extension("")
+        .to_string_lossy()
+        .into_owned();
+    let poppler = run(
+        "pdftoppm",
+        &[
+            "-f",
+            "1",
+            "-l",
+            "1",
+            "-singlefile",
+            "-png",
+            "-scale-to-x",
+            &width,
+            "-scale-to-y",
+            "-1",
+            &input_path,
+            &prefix,
+        ],
+    )
+    .await;
+
+    match poppler {
+        Err(PreviewError::ToolMissing(_)) => {
+            run(
+                "mutool",
+                &[
+                    "draw",
+                    "-q",
+                    "-F",
+                    "png",
+                    "-w",
+                    &width,
+                    "-o",
+                    &output_path,
+                    &input_path,
+                    "1",
+                ],
+            )
+            .await
+            .map_err(|e| match e {
+                PreviewError::ToolMissing(_) => {
+                    PreviewError::ToolMissing("pdftoppm or mutool".to_string())
+                }
+                e => e,
+            })?;
+        }
+        result => {
+            result?;
+        }
+    }
+
+    Ok(tokio::fs::read(&output.path).await?)
+}
+
+/// Text of the first few pages of a PDF, laid out as on the page
+pub async fn pdf_text(data: &[u8], work_dir: &Path) -> PreviewResult<String> {
+    let input = ScratchFile::write(work_dir, "pdf", data).await?;
+    let input_path = input.path_str();
+    let last_page = TEXT_PAGES.to_string();
+
+    let output = run(
+        "pdftotext",
+        &[
+            "-l",
+            &last_page,
+            "-layout",
+            "-enc",
+            "UTF-8",
+            &input_path,
+            "-",
+        ],
+    )
+    .await?;
+
+    non_empty(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Text of a Word document, spreadsheet or presentation
+///
+/// Only the zip-based formats (.docx, .xlsx, .pptx) are read; the older
+/// binary formats are reported as unsupported.
+pub async fn office_text(
+    attachment_type: &AttachmentType,
+    data: &[u8],
+    work_dir: &Path,
+) -> PreviewResult<String> {
+    if !data.starts_with(b"PK\x03\x04") {
+        return Err(PreviewError::Unsupported(
+            "only .docx, .xlsx and .pptx files can be previewed".to_string(),
+        ));
+    }
+
+    let input = ScratchFile::write(work_dir, "zip", data).await?;
+    let input_path = input.path_str();
+    let listing = run("unzip", &["-Z1", &input_path]).await?;
+    let entries: Vec<String> = String::from_utf8_lossy(&listing.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+
+    let parts = office_parts(attachment_type, &entries);
+    if parts.is_empty() {
+        return Err(PreviewError::Unsupported(
+            "no document text found in the file".to_string(),
+        ));
+    }
+
+    let mut sections = Vec::new();
+    for part in parts {
+        let output = run("unzip", &["-p", &input_path, &part]).await?;
+        let text = xml_text(&String::from_utf8_lossy(&output.stdout));
+        if text.trim().is_empty() {
+            continue;
+        }
+        if *attachment_type == AttachmentType::PowerPoint {
+            sections.push(format!("── Slide {} ──\n{}", sections.len() + 1, text));
+        } else {
+            sections.push(text);
+        }
+    }
+
+    non_empty(sections.join("\n\n"))
+}
+
+/// Parts of an office file that hold its text, in reading order
+fn office_parts(attachment_type: &AttachmentType, entries: &[String]) -> Vec<String> {
+    let numbered = |prefix: &str| {
+        let mut parts: Vec<(u32, String)> = entries
+            .iter()
+            .filter_map(|entry| {
+                let number = entry.strip_prefix(prefix)?.strip_suffix(".xml")?;
+                Some((number.parse().ok()?, entry.clone()))
+            })
+            .collect();
+        parts.sort();
+        parts
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect::<Vec<_>>()
+    };
+
+    let wanted: Vec<String> = match attachment_type {
+        AttachmentType::Word => vec!["word/document.xml".to_string()],
+        AttachmentType::Excel => vec!["xl/sharedStrings.xml".to_string()],
+        AttachmentType::PowerPoint => numbered("ppt/slides/slide"),
+        _ => Vec::new(),
+    };
+
+    wanted
+        .into_iter()
+        .filter(|part| entries.contains(part))
+        .collect()
+}
+
+/// Text runs of an Office Open XML part, one paragraph per line
+///
+/// Keeps the content of `<w:t>`, `<a:t>` and `<t>` elements and ends a line
+/// at each paragraph, shared string or line break.
+pub fn xml_text(xml: &str) -> String {
+    let mut text = String::new();
+    let mut in_text = false;
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        if in_text {
+            text.push_str(&decode_entities(&rest[..start]));
+        }
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let closing = tag.starts_with('/');
+        let self_closing = tag.ends_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .trim_end_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or("");
+        let local = name.rsplit(':').next().unwrap_or(name);
+
+        match local {
+            "t" if !self_closing => in_text = !closing,
+            "p" | "si" if closing => text.push('\n'),
+            "br" | "cr" => text.push('\n'),
+            "tab" => text.push('\t'),
+            _ => {}
+        }
+    }
+
+    text.trim_end().to_string()
+}
+
+/// Replace the predefined XML entities and character references
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let character = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match character {
+            Some(c) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn non_empty(text: String) -> PreviewResult<String> {
+    if text.trim().is_empty() {
+        Err(PreviewError::Unsupported(
+            "the file contains no extractable text".to_string(),
+        ))
+    } else {
+        Ok(text)
+    }
+}
+
+/// Run a tool to completion, failing on a non-zero exit
+async fn run(program: &str, args: &[&str]) -> PreviewResult<Output> {
+    let output = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => PreviewError::ToolMissing(program.to_string()),
+            _ => PreviewError::Io(e),
+        })?;
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(PreviewError::Failed {
+            tool: program.to_string(),
+            message: stderr
+                .lines()
+                .map(str::trim)
+                .rfind(|line| !line.is_empty())
+                .unwrap_or("no error message")
+                .to_string(),
+        })
+    }
+}
+
+/// Scratch file that is removed when dropped
+struct ScratchFile {
+    path: PathBuf,
+}
+
+impl ScratchFile {
+    fn reserve(dir: &Path, extension: &str) -> Self {
+        Self {
+            path: dir.join(format!("preview-{}.{}", uuid::Uuid::new_v4(), extension)),
+        }
+    }
+
+    async fn write(dir: &Path, extension: &str, data: &[u8]) -> PreviewResult<Self> {
+        tokio::fs::create_dir_all(dir).await?;
+        let file = Self::reserve(dir, extension);
+        tokio::fs::write(&file.path, data).await?;
+        Ok(file)
+    }
+
+    fn path_str(&self) -> String {
+        self.path.to_string_lossy().into_owned()
+    }
+}
+
+impl Drop for ScratchFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xml_text() {
+        let docx = r#"<?xml version="1.0"?><w:document><w:body><w:p><w:r><w:t>Dear Ann,</w:t></w:r></w:p><w:p><w:r><w:t xml:space="preserve">Fish &amp; chips </w:t></w:r><w:r><w:tab/><w:t>at 5&#x2009;pm</w:t><w:br/><w:t/></w:r></w:p></w:body></w:document>"#;
+        assert_eq!(xml_text(docx), "Dear Ann,\nFish & chips \tat 5\u{2009}pm");
+
+        let shared =
+            "<sst><si><t>Name</t></si><si><r><t>Q</t></r><r><t>1 &lt; 2</t></r></si></sst>";
+        assert_eq!(xml_text(shared), "Name\nQ1 < 2");
+        assert_eq!(decode_entities("a &bogus; b"), "a &bogus; b");
+    }
+
+    #[test]
+    fn test_office_parts_in_slide_order() {
+        let entries: Vec<String> = [
+            "[Content_Types].xml",
+            "ppt/slides/slide10.xml",
+            "ppt/slides/slide2.xml",
+            "ppt/slides/_rels/slide1.xml.rels",
+            "ppt/slides/slide1.xml",
+        ]
+        .iter()
+        .map(|entry| entry.to_string())
+        .collect();
+
+        assert_eq!(
+            office_parts(&AttachmentType::PowerPoint, &entries),
+            vec![
+                "ppt/slides/slide1.xml",
+                "ppt/slides/slide2.xml",
+                "ppt/slides/slide10.xml"
+            ]
+        );
+        assert!(office_parts(&AttachmentType::Word, &entries).is_empty());
+    }
+}
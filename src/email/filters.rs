@@ -1,27 +1,48 @@
+use crate::email::security::{header_values, split_headers};
+use crate::email::sender_lists::{BlockAction, SenderLists, SenderVerdict};
 use crate::email::StoredMessage;
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Email filter rule for organizing messages
+///
+/// Only `name`, `conditions` and `actions` are required when rules are
+/// written by hand in `filters.toml`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmailFilter {
+    #[serde(default = "Uuid::new_v4")]
     pub id: Uuid,
     pub name: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default = "default_enabled")]
     pub enabled: bool,
+    #[serde(default = "default_priority")]
     pub priority: i32, // Lower numbers = higher priority
     pub conditions: Vec<FilterCondition>,
     pub actions: Vec<FilterAction>,
+    #[serde(default = "chrono::Utc::now")]
     pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default = "chrono::Utc::now")]
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_priority() -> i32 {
+    100
+}
+
 /// Condition to match against email messages
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilterCondition {
     pub field: FilterField,
     pub operator: FilterOperator,
     pub value: String,
+    #[serde(default)]
     pub case_sensitive: bool,
 }
 
@@ -45,6 +66,7 @@ pub enum FilterField {
     Date,
     Priority,
     Folder,
+    Header(String), // Any header by name, every value joined
 }
 
 /// Operators for filter conditions
@@ -79,6 +101,7 @@ pub enum FilterAction {
     AutoReply(String),
     SetFlag(String),
     RemoveFlag(String),
+    Notify,         // Show a new message notification
     StopProcessing, // Stop applying further filters
 }
 
@@ -93,6 +116,7 @@ pub struct FilterResult {
 /// Filter engine for processing email messages
 pub struct FilterEngine {
     filters: Vec<EmailFilter>,
+    sender_lists: SenderLists,
 }
 
 impl FilterEngine {
@@ -100,9 +124,20 @@ impl FilterEngine {
     pub fn new() -> Self {
         Self {
             filters: Vec::new(),
+            sender_lists: SenderLists::default(),
         }
     }
 
+    /// Replace the sender blocklist and allowlist
+    pub fn set_sender_lists(&mut self, sender_lists: SenderLists) {
+        self.sender_lists = sender_lists;
+    }
+
+    /// Current sender blocklist and allowlist
+    pub fn sender_lists(&self) -> &SenderLists {
+        &self.sender_lists
+    }
+
     /// Load filters from database/storage
     pub fn load_filters(&mut self, filters: Vec<EmailFilter>) {
         self.filters = filters;
@@ -131,18 +166,44 @@ impl FilterEngine {
 
     /// Process a message through all filters
     pub fn process_message(&self, message: &StoredMessage) -> FilterResult {
+        self.process_message_with_headers(message, None)
+    }
+
+    /// Process a message through all filters, matching header conditions
+    /// against its raw header block
+    pub fn process_message_with_headers(
+        &self,
+        message: &StoredMessage,
+        headers: Option<&str>,
+    ) -> FilterResult {
         let mut result = FilterResult {
             matched_filters: Vec::new(),
             actions_applied: Vec::new(),
             stop_processing: false,
         };
 
+        // Sender lists are checked before any user filter
+        let verdict = self.sender_lists.classify(&message.from_addr);
+        if verdict == SenderVerdict::Blocked {
+            match self.sender_lists.block_action {
+                BlockAction::Delete => result.actions_applied.push(FilterAction::Delete),
+                BlockAction::MoveToSpam => {
+                    result.actions_applied.push(FilterAction::MarkAsSpam);
+                    result
+                        .actions_applied
+                        .push(FilterAction::MoveToFolder(self.sender_lists.spam_folder.clone()));
+                }
+            }
+            result.stop_processing = true;
+            return result;
+        }
+
         for filter in &self.filters {
             if !filter.enabled {
                 continue;
             }
 
-            if self.evaluate_filter(filter, message) {
+            if self.evaluate_filter(filter, message, headers) {
                 result.matched_filters.push(filter.id);
 
                 for action in &filter.actions {
@@ -159,11 +220,38 @@ impl FilterEngine {
             }
         }
 
+        if verdict == SenderVerdict::Allowed {
+            // Allowed senders are never treated as spam
+            let spam_folder = &self.sender_lists.spam_folder;
+            result.actions_applied.retain(|action| match action {
+                FilterAction::MarkAsSpam | FilterAction::Delete => false,
+                FilterAction::MoveToFolder(folder) => !folder.eq_ignore_ascii_case(spam_folder),
+                _ => true,
+            });
+        }
+
         result
     }
 
+    /// Whether a filter matches a message, enabled or not
+    ///
+    /// Used to try a rule out before it runs on incoming mail.
+    pub fn test_filter(
+        &self,
+        filter: &EmailFilter,
+        message: &StoredMessage,
+        headers: Option<&str>,
+    ) -> bool {
+        self.evaluate_filter(filter, message, headers)
+    }
+
     /// Evaluate if a filter matches a message
-    fn evaluate_filter(&self, filter: &EmailFilter, message: &StoredMessage) -> bool {
+    fn evaluate_filter(
+        &self,
+        filter: &EmailFilter,
+        message: &StoredMessage,
+        headers: Option<&str>,
+    ) -> bool {
         if filter.conditions.is_empty() {
             return false;
         }
@@ -172,12 +260,17 @@ impl FilterEngine {
         filter
             .conditions
             .iter()
-            .all(|condition| self.evaluate_condition(condition, message))
+            .all(|condition| self.evaluate_condition(condition, message, headers))
     }
 
     /// Evaluate a single condition against a message
-    fn evaluate_condition(&self, condition: &FilterCondition, message: &StoredMessage) -> bool {
-        let field_value = self.extract_field_value(&condition.field, message);
+    fn evaluate_condition(
+        &self,
+        condition: &FilterCondition,
+        message: &StoredMessage,
+        headers: Option<&str>,
+    ) -> bool {
+        let field_value = self.extract_field_value(&condition.field, message, headers);
 
         let value_to_check = if condition.case_sensitive {
             field_value.clone()
@@ -229,7 +322,12 @@ impl FilterEngine {
     }
 
     /// Extract field value from message
-    fn extract_field_value(&self, field: &FilterField, message: &StoredMessage) -> String {
+    fn extract_field_value(
+        &self,
+        field: &FilterField,
+        message: &StoredMessage,
+        headers: Option<&str>,
+    ) -> String {
         match field {
             FilterField::From => message.from_addr.clone(),
             FilterField::To => message.to_addrs.join(", "),
@@ -267,6 +365,10 @@ impl FilterEngine {
                 .collect::<Vec<_>>()
                 .join(", "),
             FilterField::Priority => message.priority.clone().unwrap_or_default(),
+            FilterField::Header(name) => match headers {
+                Some(headers) => header_values(&split_headers(headers).0, name).join(", "),
+                None => String::new(),
+            },
         }
     }
 
@@ -345,6 +447,37 @@ impl FilterCondition {
     }
 }
 
+/// User-defined filter rules applied to incoming mail
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterRules {
+    /// Rules, run in priority order
+    pub rules: Vec<EmailFilter>,
+}
+
+impl FilterRules {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "filters.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Enable or disable a rule, returning its new state
+    pub fn toggle(&mut self, filter_id: Uuid) -> Option<bool> {
+        let rule = self.rules.iter_mut().find(|rule| rule.id == filter_id)?;
+        rule.enabled = !rule.enabled;
+        rule.updated_at = chrono::Utc::now();
+        Some(rule.enabled)
+    }
+}
+
 /// Pre-defined filter templates for common use cases
 pub struct FilterTemplates;
 
@@ -437,6 +570,81 @@ mod tests {
         assert_eq!(condition.field, FilterField::Subject);
     }
 
+    #[test]
+    fn test_sender_lists_applied_before_filters() {
+        let mut engine = FilterEngine::new();
+        engine.add_filter(FilterTemplates::spam_filter());
+
+        let mut lists = SenderLists::default();
+        lists.block("*@spammer.com");
+        lists.allow("test@example.com");
+        engine.set_sender_lists(lists);
+
+        // Allowed sender keeps non-spam actions only
+        let mut message = create_test_message();
+        message.subject = "[SPAM] Quarterly report".to_string();
+        let result = engine.process_message(&message);
+        assert_eq!(result.matched_filters.len(), 1);
+        assert!(matches!(result.actions_applied.as_slice(), [FilterAction::MarkAsRead]));
+
+        // Blocked sender is moved to spam without running other filters
+        message.from_addr = "offers@spammer.com".to_string();
+        let result = engine.process_message(&message);
+        assert!(result.matched_filters.is_empty());
+        assert!(result.stop_processing);
+        assert!(matches!(
+            result.actions_applied.as_slice(),
+            [FilterAction::MarkAsSpam, FilterAction::MoveToFolder(folder)] if folder == "Spam"
+        ));
+    }
+
+    #[test]
+    fn test_filter_rules_from_toml() {
+        let config: FilterRules = toml::from_str(
+            r#"
+            [[rules]]
+            name = "Lists"
+            priority = 10
+            conditions = [{ field = { Header = "List-Id" }, operator = "Contains", value = "dev.example.com" }]
+            actions = [{ MoveToFolder = "Lists" }, "MarkAsRead", "StopProcessing"]
+
+            [[rules]]
+            name = "Boss"
+            enabled = false
+            conditions = [{ field = "From", operator = "Equals", value = "test@example.com" }]
+            actions = ["Notify"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.rules.len(), 2);
+        assert!(config.rules[0].enabled);
+
+        let mut engine = FilterEngine::new();
+        engine.load_filters(config.rules.clone());
+        let message = create_test_message();
+        let headers = "From: test@example.com\r\nList-Id: Developers\r\n <dev.example.com>\r\n";
+
+        // Header conditions need the raw headers
+        assert!(engine.process_message(&message).matched_filters.is_empty());
+        let result = engine.process_message_with_headers(&message, Some(headers));
+        assert!(result.stop_processing);
+        assert!(matches!(
+            result.actions_applied.as_slice(),
+            [FilterAction::MoveToFolder(folder), FilterAction::MarkAsRead] if folder == "Lists"
+        ));
+
+        // Disabled rules can still be tried out
+        let mut config = config;
+        let boss = config.rules[1].clone();
+        assert!(engine.test_filter(&boss, &message, None));
+        assert_eq!(config.toggle(boss.id), Some(true));
+
+        // Saved rules load back
+        let saved: FilterRules = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(saved.rules[1].id, config.rules[1].id);
+        assert!(saved.rules[1].enabled);
+    }
+
     fn create_test_message() -> StoredMessage {
         StoredMessage {
             id: Uuid::new_v4(),
@@ -468,6 +676,8 @@ mod tests {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         }
     }
 }
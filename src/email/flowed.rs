@@ -0,0 +1,281 @@
+//! RFC 3676 `format=flowed` plain text
+//!
+//! Flowed text breaks paragraphs into short lines that end in a space (soft
+//! breaks), so a client can join them again and wrap to its own width.
+//! Incoming flowed bodies are decoded during sync: each paragraph becomes one
+//! line, quoted paragraphs keep their `>` prefix, and the viewer wraps them
+//! to the pane. Outgoing mail can optionally be encoded as flowed so
+//! recipients get reflowable text.
+//!
+//! Quote depth is the number of leading `>` characters. A single space after
+//! the quote markers, or at the start of an unquoted line that begins with a
+//! space or `From `, is space-stuffing and is not part of the text.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Outgoing format=flowed settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlowedConfig {
+    /// Send composed plain text as `format=flowed`
+    pub send_flowed: bool,
+    /// Width soft-broken lines are wrapped at; RFC 3676 recommends 66 to 78
+    pub line_width: usize,
+}
+
+impl Default for FlowedConfig {
+    fn default() -> Self {
+        Self {
+            send_flowed: false,
+            line_width: 72,
+        }
+    }
+}
+
+impl FlowedConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "flowed.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Line width for outgoing mail, if it is sent flowed
+    pub fn outgoing_line_width(&self) -> Option<usize> {
+        self.send_flowed.then_some(self.line_width)
+    }
+}
+
+/// Parameters of a flowed `text/plain` part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlowedFormat {
+    /// `DelSp=yes`: the space before a soft break was added by the sender
+    /// and is removed when joining
+    pub delsp: bool,
+}
+
+/// One paragraph of decoded flowed text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowedParagraph {
+    pub depth: usize,
+    pub text: String,
+}
+
+/// Find the `format=flowed` parameter on the plain text part of a raw message
+///
+/// Returns `None` when the first `text/plain` part isn't flowed.
+pub fn detect(raw: &str) -> Option<FlowedFormat> {
+    let mut lines = raw.lines().peekable();
+    while let Some(line) = lines.next() {
+        if !line
+            .get(..13)
+            .is_some_and(|name| name.eq_ignore_ascii_case("content-type:"))
+        {
+            continue;
+        }
+
+        // Unfold continuation lines into one value
+        let mut value = line[13..].to_string();
+        while let Some(next) = lines.peek() {
+            if !next.starts_with([' ', '\t']) {
+                break;
+            }
+            value.push_str(next);
+            lines.next();
+        }
+
+        let value = value.to_lowercase().replace(['"', ' ', '\t'], "");
+        if !value.starts_with("text/plain") {
+            continue;
+        }
+        let params: Vec<&str> = value.split(';').skip(1).collect();
+        if !params.contains(&"format=flowed") {
+            return None;
+        }
+        return Some(FlowedFormat {
+            delsp: params.contains(&"delsp=yes"),
+        });
+    }
+    None
+}
+
+/// Join soft-broken lines into paragraphs
+pub fn decode(text: &str, delsp: bool) -> Vec<FlowedParagraph> {
+    let mut paragraphs: Vec<FlowedParagraph> = Vec::new();
+    let mut open = false;
+
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let depth = line.chars().take_while(|&c| c == '>').count();
+        let content = &line[depth..];
+        let content = content.strip_prefix(' ').unwrap_or(content);
+
+        let soft = content.ends_with(' ') && content != "-- ";
+        let content = if soft && delsp {
+            &content[..content.len() - 1]
+        } else {
+            content
+        };
+
+        match paragraphs.last_mut() {
+            // A soft break followed by a change of depth ends the paragraph
+            Some(last) if open && last.depth == depth => last.text.push_str(content),
+            _ => paragraphs.push(FlowedParagraph {
+                depth,
+                text: content.to_string(),
+            }),
+        }
+        open = soft;
+    }
+
+    paragraphs
+}
+
+/// Decode flowed text into one line per paragraph, keeping quote prefixes
+pub fn unflow(text: &str, delsp: bool) -> String {
+    decode(text, delsp)
+        .into_iter()
+        .map(|paragraph| {
+            let text = match paragraph.text.as_str() {
+                "-- " => "-- ",
+                text => text.trim_end_matches(' '),
+            };
+            match paragraph.depth {
+                0 => text.to_string(),
+                depth if text.is_empty() => ">".repeat(depth),
+                depth => format!("{} {}", ">".repeat(depth), text),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Encode plain text as `format=flowed` (without DelSp)
+///
+/// Each line is a paragraph. Lines longer than `width` are soft-broken at
+/// spaces; words longer than the width stay whole. Trailing spaces typed by
+/// the user are dropped so they can't turn into soft breaks.
+pub fn encode(text: &str, width: usize) -> String {
+    let mut output = Vec::new();
+
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line == "-- " {
+            output.push(line.to_string());
+            continue;
+        }
+
+        let depth = line.chars().take_while(|&c| c == '>').count();
+        let content = &line[depth..];
+        let content = if depth > 0 {
+            content.strip_prefix(' ').unwrap_or(content)
+        } else {
+            content
+        };
+        let content = content.trim_end_matches(' ');
+
+        let prefix = ">".repeat(depth);
+        // Room for the prefix, its stuffing space and the soft-break space
+        let room = width.saturating_sub(prefix.len() + 2).max(1);
+        for segment in soft_break(content, room) {
+            let stuffed = depth > 0 || segment.starts_with(' ') || segment.starts_with("From ");
+            if stuffed && !segment.is_empty() {
+                output.push(format!("{} {}", prefix, segment));
+            } else {
+                output.push(format!("{}{}", prefix, segment));
+            }
+        }
+    }
+
+    output.join("\n")
+}
+
+/// Split a line at spaces into segments of at most `width` characters.
+/// Every segment but the last keeps the space it was broken at
+fn soft_break(text: &str, width: usize) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+
+    for (index, word) in text.split(' ').enumerate() {
+        if index > 0 {
+            if current.chars().count() + 1 + word.chars().count() > width && !current.is_empty() {
+                current.push(' ');
+                segments.push(std::mem::take(&mut current));
+            } else {
+                current.push(' ');
+            }
+        }
+        current.push_str(word);
+    }
+    segments.push(current);
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flowed_round_trip() {
+        let raw = "From: jane@example.com\r\n\
+            Content-Type: text/plain; charset=utf-8;\r\n format=flowed; delsp=no\r\n\r\nbody";
+        assert_eq!(detect(raw), Some(FlowedFormat { delsp: false }));
+        assert_eq!(detect("Content-Type: text/plain; charset=utf-8\n\nbody"), None);
+        assert_eq!(
+            detect("Content-Type: multipart/alternative; boundary=x\n\n--x\nContent-Type: text/plain; format=\"flowed\"; DelSp=\"yes\"\n\nbody"),
+            Some(FlowedFormat { delsp: true })
+        );
+
+        // A reply with a reflowed quoted paragraph, a stuffed line and a signature
+        let flowed = "Sounds good, see you \n\
+            there.\n\
+            \n\
+            > On Monday we could meet at \n\
+            > the usual place and then \n\
+            > walk over.\n\
+            >> Older quote that was \n\
+            >> also flowed.\n\
+            \x20From the team\n\
+            -- \n\
+            Jane";
+        assert_eq!(
+            unflow(flowed, false),
+            "Sounds good, see you there.\n\
+            \n\
+            > On Monday we could meet at the usual place and then walk over.\n\
+            >> Older quote that was also flowed.\n\
+            From the team\n\
+            -- \n\
+            Jane"
+        );
+
+        // DelSp removes the space before each soft break
+        assert_eq!(unflow("Sehr \nlange Zei \nle", true), "Sehrlange Zeile");
+
+        // Encoding wraps long paragraphs with soft breaks and stuffs where needed
+        let text = "> On Monday we could meet at the usual place and then walk over.\n\
+            From the team\n\
+            Trailing space   \n\
+            -- \n\
+            Jane";
+        let encoded = encode(text, 30);
+        assert_eq!(
+            encoded,
+            "> On Monday we could meet at \n\
+            > the usual place and then \n\
+            > walk over.\n\
+            \x20From the team\n\
+            Trailing space\n\
+            -- \n\
+            Jane"
+        );
+        assert_eq!(unflow(&encoded, false), text.replace("space   ", "space"));
+    }
+}
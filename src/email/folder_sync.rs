@@ -0,0 +1,134 @@
+//! Per-folder sync modes
+//!
+//! Each account has a default [`FolderSyncMode`], set in `folder_sync.toml`,
+//! and individual folders can override it from the folder tree context menu.
+//! Overrides live in the folder's `FolderSyncState` row, so they survive
+//! restarts and are shared with the daemon. `SyncEngine` looks the mode up
+//! before each folder sync: `full` syncs as requested, `headers_only` never
+//! downloads bodies beyond the prefetch depth, and `skip` leaves the folder
+//! alone.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// How much of a folder the sync engine downloads
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderSyncMode {
+    /// Headers and bodies
+    #[default]
+    Full,
+    /// Headers only; bodies are fetched when a message is opened
+    HeadersOnly,
+    /// Not synced at all
+    Skip,
+}
+
+impl FolderSyncMode {
+    /// The folder override after `current` when cycling from the context menu:
+    /// account default, full, headers only, skip, then back to the default
+    pub fn next_override(current: Option<Self>) -> Option<Self> {
+        match current {
+            None => Some(Self::Full),
+            Some(Self::Full) => Some(Self::HeadersOnly),
+            Some(Self::HeadersOnly) => Some(Self::Skip),
+            Some(Self::Skip) => None,
+        }
+    }
+}
+
+impl fmt::Display for FolderSyncMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full => write!(f, "Full"),
+            Self::HeadersOnly => write!(f, "Headers only"),
+            Self::Skip => write!(f, "Skip"),
+        }
+    }
+}
+
+/// Default sync mode for folders without an override
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FolderSyncDefaults {
+    /// Mode for accounts not listed in `accounts`
+    pub default_mode: FolderSyncMode,
+    /// Per-account default modes, keyed by account ID
+    pub accounts: HashMap<String, FolderSyncMode>,
+}
+
+impl FolderSyncDefaults {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "folder_sync.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Default mode for an account's folders
+    pub fn mode_for(&self, account_id: &str) -> FolderSyncMode {
+        self.accounts
+            .get(account_id)
+            .copied()
+            .unwrap_or(self.default_mode)
+    }
+
+    /// Mode a folder syncs with: its override, or the account default
+    pub fn resolve(&self, account_id: &str, folder_override: Option<FolderSyncMode>) -> FolderSyncMode {
+        folder_override.unwrap_or_else(|| self.mode_for(account_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folder_sync_mode_resolution() {
+        let defaults: FolderSyncDefaults = toml::from_str(
+            r#"
+            default_mode = "full"
+
+            [accounts]
+            archive = "headers_only"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(defaults.resolve("work", None), FolderSyncMode::Full);
+        assert_eq!(defaults.resolve("archive", None), FolderSyncMode::HeadersOnly);
+        assert_eq!(
+            defaults.resolve("archive", Some(FolderSyncMode::Full)),
+            FolderSyncMode::Full
+        );
+        assert_eq!(
+            defaults.resolve("work", Some(FolderSyncMode::Skip)),
+            FolderSyncMode::Skip
+        );
+
+        // Cycling visits every override and returns to the account default
+        let mut current = None;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            current = FolderSyncMode::next_override(current);
+            seen.push(current);
+        }
+        assert_eq!(
+            seen,
+            vec![
+                Some(FolderSyncMode::Full),
+                Some(FolderSyncMode::HeadersOnly),
+                Some(FolderSyncMode::Skip),
+                None
+            ]
+        );
+    }
+}
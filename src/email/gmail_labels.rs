@@ -0,0 +1,172 @@
+//! Gmail labels as virtual folders
+//!
+//! Gmail lists every label as an IMAP folder, so a message labelled both
+//! Inbox and Important shows up in two folders. Syncing each of them would
+//! store the message twice. For accounts with Gmail's X-GM-EXT-1 extension
+//! only All Mail is synced, along with Trash and Spam whose messages All Mail
+//! leaves out, and every message keeps its X-GM-LABELS. The other folders
+//! become label views listing the All Mail messages that carry their label.
+
+use crate::imap::{FolderAttribute, ImapFolder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How an account's folders map onto Gmail labels
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelLayout {
+    /// Full name of the All Mail folder, which stores the labelled messages
+    pub all_mail: String,
+    /// Label each view folder shows, keyed by the folder's full name
+    pub views: HashMap<String, String>,
+}
+
+impl LabelLayout {
+    /// Layout of a Gmail account's folders
+    ///
+    /// `None` when no folder has the `\All` attribute, e.g. when All Mail is
+    /// hidden from IMAP in the Gmail settings; every folder is then synced on
+    /// its own.
+    pub fn detect(folders: &[ImapFolder]) -> Option<Self> {
+        let all_mail = folders
+            .iter()
+            .find(|folder| folder.attributes.contains(&FolderAttribute::All))?
+            .full_name
+            .clone();
+
+        let views = folders
+            .iter()
+            .filter_map(|folder| Some((folder.full_name.clone(), Self::folder_label(folder)?)))
+            .collect();
+
+        Some(Self { all_mail, views })
+    }
+
+    /// Label a folder stands for; `None` for folders holding their own messages
+    fn folder_label(folder: &ImapFolder) -> Option<String> {
+        let has = |attribute: FolderAttribute| folder.attributes.contains(&attribute);
+        if has(FolderAttribute::All)
+            || has(FolderAttribute::Trash)
+            || has(FolderAttribute::Junk)
+            || has(FolderAttribute::Noselect)
+        {
+            return None;
+        }
+
+        let label = if folder.full_name.eq_ignore_ascii_case("INBOX") {
+            "\\Inbox"
+        } else if has(FolderAttribute::Sent) {
+            "\\Sent"
+        } else if has(FolderAttribute::Drafts) {
+            "\\Draft"
+        } else if has(FolderAttribute::Flagged) {
+            "\\Starred"
+        } else if folder.attributes.iter().any(|attribute| {
+            matches!(attribute, FolderAttribute::Custom(name) if name.eq_ignore_ascii_case("\\Important"))
+        }) {
+            "\\Important"
+        } else {
+            // User labels are named like their folder
+            return Some(folder.full_name.clone());
+        };
+        Some(label.to_string())
+    }
+
+    /// Label shown by a folder, if it is a label view
+    pub fn label_for(&self, folder_name: &str) -> Option<&str> {
+        self.views.get(folder_name).map(String::as_str)
+    }
+
+    /// Folder storing the messages shown in `folder_name`
+    pub fn storage_folder<'a>(&'a self, folder_name: &'a str) -> &'a str {
+        if self.views.contains_key(folder_name) {
+            &self.all_mail
+        } else {
+            folder_name
+        }
+    }
+
+    /// Labels of a message after removing label `from` and adding `to`
+    pub fn relabel(labels: &[String], from: Option<&str>, to: Option<&str>) -> Vec<String> {
+        let mut relabelled: Vec<String> = labels
+            .iter()
+            .filter(|label| Some(label.as_str()) != from)
+            .cloned()
+            .collect();
+        if let Some(to) = to.filter(|to| !relabelled.iter().any(|label| label == to)) {
+            relabelled.push(to.to_string());
+        }
+        relabelled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(full_name: &str, attributes: &[&str]) -> ImapFolder {
+        let mut folder = ImapFolder::new(
+            full_name.rsplit('/').next().unwrap().to_string(),
+            full_name.to_string(),
+        );
+        folder.attributes = attributes.iter().map(|a| FolderAttribute::from_str(a)).collect();
+        folder
+    }
+
+    fn gmail_folders() -> Vec<ImapFolder> {
+        vec![
+            folder("INBOX", &["\\HasNoChildren"]),
+            folder("Work", &["\\HasNoChildren"]),
+            folder("[Gmail]", &["\\HasChildren", "\\Noselect"]),
+            folder("[Gmail]/All Mail", &["\\All", "\\HasNoChildren"]),
+            folder("[Gmail]/Bin", &["\\HasNoChildren", "\\Trash"]),
+            folder("[Gmail]/Drafts", &["\\Drafts", "\\HasNoChildren"]),
+            folder("[Gmail]/Important", &["\\HasNoChildren", "\\Important"]),
+            folder("[Gmail]/Sent Mail", &["\\HasNoChildren", "\\Sent"]),
+            folder("[Gmail]/Spam", &["\\HasNoChildren", "\\Junk"]),
+            folder("[Gmail]/Starred", &["\\Flagged", "\\HasNoChildren"]),
+        ]
+    }
+
+    #[test]
+    fn test_detect_gmail_layout() {
+        let layout = LabelLayout::detect(&gmail_folders()).unwrap();
+
+        assert_eq!(layout.all_mail, "[Gmail]/All Mail");
+        assert_eq!(layout.label_for("INBOX"), Some("\\Inbox"));
+        assert_eq!(layout.label_for("Work"), Some("Work"));
+        assert_eq!(layout.label_for("[Gmail]/Drafts"), Some("\\Draft"));
+        assert_eq!(layout.label_for("[Gmail]/Important"), Some("\\Important"));
+        assert_eq!(layout.label_for("[Gmail]/Sent Mail"), Some("\\Sent"));
+        assert_eq!(layout.label_for("[Gmail]/Starred"), Some("\\Starred"));
+        for stored in ["[Gmail]", "[Gmail]/All Mail", "[Gmail]/Bin", "[Gmail]/Spam"] {
+            assert_eq!(layout.label_for(stored), None, "{}", stored);
+        }
+
+        assert_eq!(layout.storage_folder("INBOX"), "[Gmail]/All Mail");
+        assert_eq!(layout.storage_folder("[Gmail]/Bin"), "[Gmail]/Bin");
+
+        // Without All Mail there is nothing to read label views from
+        let without_all_mail: Vec<ImapFolder> = gmail_folders()
+            .into_iter()
+            .filter(|folder| folder.full_name != "[Gmail]/All Mail")
+            .collect();
+        assert_eq!(LabelLayout::detect(&without_all_mail), None);
+    }
+
+    #[test]
+    fn test_relabel() {
+        let labels = vec!["\\Important".to_string(), "\\Inbox".to_string()];
+        assert_eq!(
+            LabelLayout::relabel(&labels, Some("\\Inbox"), Some("Work")),
+            vec!["\\Important", "Work"]
+        );
+        assert_eq!(
+            LabelLayout::relabel(&labels, Some("\\Inbox"), Some("\\Important")),
+            vec!["\\Important"]
+        );
+        assert_eq!(
+            LabelLayout::relabel(&labels, None, Some("Work")),
+            vec!["\\Important", "\\Inbox", "Work"]
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Structured view of a message's headers
+//!
+//! The header block is kept during sync so deliverability and phishing
+//! questions can be answered later: which servers the message passed
+//! through and how long each hop took, and what the receiving server made of
+//! its DKIM, SPF and DMARC checks.
+
+use crate::email::security::{
+    parse_authentication_results, split_headers, AuthMethodResult, AuthenticationResults,
+};
+use chrono::{DateTime, Duration, FixedOffset};
+
+/// One header as it appeared in the message, unfolded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderField {
+    pub name: String,
+    pub value: String,
+}
+
+/// One `Received` header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedHop {
+    /// Host that handed the message over
+    pub from: Option<String>,
+    /// Host that took it
+    pub by: Option<String>,
+    /// Protocol, e.g. `ESMTPS`
+    pub with: Option<String>,
+    pub date: Option<DateTime<FixedOffset>>,
+    /// Time since the previous hop, when both are dated
+    pub delay: Option<Duration>,
+    pub raw: String,
+}
+
+impl ReceivedHop {
+    fn parse(value: &str) -> Self {
+        let (route, date) = match value.rsplit_once(';') {
+            Some((route, date)) => (route, parse_date(date)),
+            None => (value, None),
+        };
+
+        let tokens: Vec<&str> = route.split_whitespace().collect();
+        let after = |keyword: &str| {
+            tokens
+                .iter()
+                .position(|token| token.eq_ignore_ascii_case(keyword))
+                .and_then(|i| tokens.get(i + 1))
+                .map(|token| token.trim_end_matches([';', ',']).to_string())
+        };
+
+        Self {
+            from: after("from"),
+            by: after("by"),
+            with: after("with"),
+            date,
+            delay: None,
+            raw: value.to_string(),
+        }
+    }
+}
+
+/// Parse an RFC 5322 date, ignoring a trailing comment like `(UTC)`
+fn parse_date(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+    let value = match value.find('(') {
+        Some(comment) => value[..comment].trim(),
+        None => value,
+    };
+    DateTime::parse_from_rfc2822(value).ok()
+}
+
+/// Everything the headers panel shows about a message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HeaderReport {
+    /// All headers in message order
+    pub fields: Vec<HeaderField>,
+    /// The Received chain, from the sender's first server to ours
+    pub received: Vec<ReceivedHop>,
+    /// Every Authentication-Results header, topmost (our server's) first
+    pub authentication: Vec<AuthenticationResults>,
+}
+
+impl HeaderReport {
+    /// Build a report from a raw header block, or a whole raw message
+    pub fn from_raw_headers(raw: &str) -> Self {
+        let (lines, _) = split_headers(raw);
+        let fields: Vec<HeaderField> = lines
+            .iter()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| HeaderField {
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+            })
+            .collect();
+
+        // Servers prepend Received headers, so the oldest is last
+        let mut received: Vec<ReceivedHop> = fields
+            .iter()
+            .filter(|field| field.name.eq_ignore_ascii_case("received"))
+            .map(|field| ReceivedHop::parse(&field.value))
+            .rev()
+            .collect();
+        for i in 1..received.len() {
+            if let (Some(previous), Some(current)) = (received[i - 1].date, received[i].date) {
+                received[i].delay = Some(current.signed_duration_since(previous));
+            }
+        }
+
+        let authentication = fields
+            .iter()
+            .filter(|field| field.name.eq_ignore_ascii_case("authentication-results"))
+            .map(|field| parse_authentication_results(&field.value))
+            .collect();
+
+        Self {
+            fields,
+            received,
+            authentication,
+        }
+    }
+
+    /// Total time from the first dated hop to the last
+    pub fn transit_time(&self) -> Option<Duration> {
+        let mut dates = self.received.iter().filter_map(|hop| hop.date);
+        let first = dates.next()?;
+        let last = dates.next_back()?;
+        Some(last.signed_duration_since(first))
+    }
+}
+
+/// One line of an Authentication-Results header, for display and copying
+pub fn describe_auth_result(result: &AuthMethodResult) -> String {
+    let mut text = format!("{}={}", result.method, result.verdict.as_str());
+    if let Some(ref policy) = result.policy {
+        text.push_str(&format!(" (p={})", policy));
+    }
+    for (key, value) in &result.properties {
+        text.push_str(&format!(" {}={}", key, value));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::email::AuthVerdict;
+
+    #[test]
+    fn test_header_report() {
+        let raw = "Received: from mail-out.example.com (mail-out.example.com [192.0.2.1])\r\n\
+                   \tby mx.example.net with ESMTPS id abc123\r\n\
+                   \tfor <me@example.net>; Fri, 16 Oct 2026 09:30:42 +0000 (UTC)\r\n\
+                   Authentication-Results: mx.example.net; dkim=pass header.d=example.com;\r\n\
+                   \tspf=fail smtp.mailfrom=example.com\r\n\
+                   Received: from [10.0.0.5] by mail-out.example.com with ESMTPSA;\r\n\
+                   \tFri, 16 Oct 2026 11:30:00 +0200\r\n\
+                   Received: by localhost\r\n\
+                   Subject: Report\r\n\r\nbody";
+
+        let report = HeaderReport::from_raw_headers(raw);
+        assert_eq!(report.fields.len(), 5);
+        assert_eq!(report.fields[4].value, "Report");
+
+        assert_eq!(report.received.len(), 3);
+        assert_eq!(report.received[0].by.as_deref(), Some("localhost"));
+        assert_eq!(report.received[0].date, None);
+        assert_eq!(report.received[1].from.as_deref(), Some("[10.0.0.5]"));
+        assert_eq!(report.received[1].delay, None);
+        assert_eq!(report.received[2].from.as_deref(), Some("mail-out.example.com"));
+        assert_eq!(report.received[2].with.as_deref(), Some("ESMTPS"));
+        assert_eq!(report.received[2].delay, Some(Duration::seconds(42)));
+        assert_eq!(report.transit_time(), Some(Duration::seconds(42)));
+
+        assert_eq!(report.authentication.len(), 1);
+        let spf = &report.authentication[0].methods[1];
+        assert_eq!(spf.verdict, AuthVerdict::Fail);
+        assert_eq!(describe_auth_result(spf), "spf=fail smtp.mailfrom=example.com");
+    }
+}
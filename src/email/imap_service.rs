@@ -1,5 +1,5 @@
 use crate::email::database::EmailDatabase;
-use crate::email::sync_engine::{SyncEngine, SyncProgress, SyncStrategy};
+use crate::email::sync_engine::{SyncEngine, SyncProgress, SyncResult, SyncStrategy};
 use crate::imap::{
     IdleNotification, ImapAccountManager, ImapClient, ImapResult
 };
@@ -18,12 +18,18 @@ pub struct ImapService {
     
     // IDLE management
     idle_connections: Arc<RwLock<HashMap<String, Arc<Mutex<ImapClient>>>>>,
-    active_folders: Arc<RwLock<HashMap<String, String>>>, // account_id -> folder_name
+    active_folders: Arc<RwLock<HashMap<String, Vec<String>>>>, // account_id -> watched folders
     
     // Notification channels
     idle_notification_sender: mpsc::UnboundedSender<IdleUpdate>,
     idle_notification_receiver: Arc<Mutex<mpsc::UnboundedReceiver<IdleUpdate>>>,
     
+    // Folders with new mail, waiting for an incremental sync
+    sync_request_sender: mpsc::UnboundedSender<FolderSyncRequest>,
+    sync_request_receiver: Arc<Mutex<mpsc::UnboundedReceiver<FolderSyncRequest>>>,
+    // Last MESSAGES/UIDNEXT reported by NOTIFY per (account_id, folder)
+    folder_status: Arc<Mutex<HashMap<(String, String), FolderStatus>>>,
+    
     // Sync coordination
     #[allow(dead_code)]
     sync_progress_sender: mpsc::UnboundedSender<SyncProgress>,
@@ -41,6 +47,20 @@ pub struct IdleUpdate {
     pub timestamp: Instant,
 }
 
+/// Request to bring a folder up to date with an incremental sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderSyncRequest {
+    pub account_id: String,
+    pub folder_name: String,
+}
+
+/// Counters from the last NOTIFY status of a folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FolderStatus {
+    messages: Option<u32>,
+    uid_next: Option<u32>,
+}
+
 impl ImapService {
     /// Create a new IMAP service
     pub async fn new(
@@ -51,6 +71,7 @@ impl ImapService {
         let sync_engine = Arc::new(SyncEngine::new(database.clone(), sync_progress_sender.clone()));
         
         let (idle_notification_sender, idle_notification_receiver) = mpsc::unbounded_channel();
+        let (sync_request_sender, sync_request_receiver) = mpsc::unbounded_channel();
         
         let service = Self {
             account_manager: Arc::new(account_manager),
@@ -60,6 +81,9 @@ impl ImapService {
             active_folders: Arc::new(RwLock::new(HashMap::new())),
             idle_notification_sender,
             idle_notification_receiver: Arc::new(Mutex::new(idle_notification_receiver)),
+            sync_request_sender,
+            sync_request_receiver: Arc::new(Mutex::new(sync_request_receiver)),
+            folder_status: Arc::new(Mutex::new(HashMap::new())),
             sync_progress_sender,
             background_tasks: Arc::new(RwLock::new(Vec::new())),
         };
@@ -67,6 +91,9 @@ impl ImapService {
         // Start background IDLE notification processor
         service.start_idle_processor().await;
         
+        // Start background sync of folders with new mail
+        service.start_sync_worker().await;
+        
         // Start periodic connection health check
         service.start_connection_health_check().await;
         
@@ -75,7 +102,18 @@ impl ImapService {
     
     /// Start monitoring a folder with IDLE
     pub async fn start_folder_monitoring(&self, account_id: String, folder_name: String) -> ImapResult<()> {
-        info!("Starting IDLE monitoring for {}/{}", account_id, folder_name);
+        self.start_folders_monitoring(account_id, vec![folder_name]).await
+    }
+
+    /// Start monitoring several folders on one connection
+    ///
+    /// Uses NOTIFY when the server supports it; otherwise only the first
+    /// folder is watched, with IDLE.
+    pub async fn start_folders_monitoring(&self, account_id: String, folders: Vec<String>) -> ImapResult<()> {
+        let Some(folder_name) = folders.first().cloned() else {
+            return Ok(());
+        };
+        info!("Starting IDLE monitoring for {}/{:?}", account_id, folders);
         
         // Get or create a dedicated IDLE connection
         let client = self.get_idle_connection(&account_id).await?;
@@ -86,7 +124,7 @@ impl ImapService {
             client_guard.select_folder(&folder_name).await?;
             
             // Start IDLE monitoring
-            client_guard.start_folder_monitoring(folder_name.clone()).await?;
+            client_guard.start_folders_monitoring(folders.clone()).await?;
             
             // Add IDLE callback to handle notifications
             let notification_sender = self.idle_notification_sender.clone();
@@ -94,9 +132,14 @@ impl ImapService {
             let folder_name_clone = folder_name.clone();
             
             client_guard.add_idle_callback(move |notification| {
+                // NOTIFY status updates name their own folder
+                let folder_name = match &notification {
+                    IdleNotification::MailboxStatus { folder, .. } => folder.clone(),
+                    _ => folder_name_clone.clone(),
+                };
                 let update = IdleUpdate {
                     account_id: account_id_clone.clone(),
-                    folder_name: folder_name_clone.clone(),
+                    folder_name,
                     notification,
                     timestamp: Instant::now(),
                 };
@@ -110,10 +153,10 @@ impl ImapService {
         // Track active monitoring
         {
             let mut active_folders = self.active_folders.write().await;
-            active_folders.insert(account_id.clone(), folder_name.clone());
+            active_folders.insert(account_id.clone(), folders.clone());
         }
         
-        info!("IDLE monitoring started for {}/{}", account_id, folder_name);
+        info!("IDLE monitoring started for {}/{:?}", account_id, folders);
         Ok(())
     }
     
@@ -126,6 +169,10 @@ impl ImapService {
             let mut active_folders = self.active_folders.write().await;
             active_folders.remove(account_id);
         }
+        self.folder_status
+            .lock()
+            .await
+            .retain(|(account, _), _| account != account_id);
         
         // Stop IDLE on the connection
         if let Some(client) = self.get_existing_idle_connection(account_id).await {
@@ -164,6 +211,7 @@ impl ImapService {
                 unseen: None,
                 uid_validity: None,
                 uid_next: None,
+                highest_modseq: None,
             }
         };
         
@@ -205,8 +253,8 @@ impl ImapService {
     /// Start background IDLE notification processor
     async fn start_idle_processor(&self) {
         let idle_receiver = Arc::clone(&self.idle_notification_receiver);
-        let sync_engine = Arc::clone(&self.sync_engine);
-        let account_manager = Arc::clone(&self.account_manager);
+        let folder_status = Arc::clone(&self.folder_status);
+        let sync_requests = self.sync_request_sender.clone();
         
         let task = tokio::spawn(async move {
             let mut receiver = idle_receiver.lock().await;
@@ -214,8 +262,8 @@ impl ImapService {
             while let Some(update) = receiver.recv().await {
                 if let Err(e) = Self::process_idle_notification(
                     &update,
-                    &sync_engine,
-                    &account_manager,
+                    &folder_status,
+                    &sync_requests,
                 ).await {
                     error!("Failed to process IDLE notification: {}", e);
                 }
@@ -226,11 +274,47 @@ impl ImapService {
         background_tasks.push(task);
     }
     
+    /// Start background incremental syncs of folders reported as changed
+    async fn start_sync_worker(&self) {
+        let sync_receiver = Arc::clone(&self.sync_request_receiver);
+        let sync_engine = Arc::clone(&self.sync_engine);
+        let account_manager = Arc::clone(&self.account_manager);
+        
+        let task = tokio::spawn(async move {
+            let mut receiver = sync_receiver.lock().await;
+            
+            while let Some(request) = receiver.recv().await {
+                if let Err(e) = Self::sync_changed_folder(&request, &sync_engine, &account_manager).await {
+                    error!("Failed to sync {}/{}: {}", request.account_id, request.folder_name, e);
+                }
+            }
+        });
+        
+        let mut background_tasks = self.background_tasks.write().await;
+        background_tasks.push(task);
+    }
+    
+    /// Run an incremental sync of a folder on a regular (non-IDLE) connection
+    async fn sync_changed_folder(
+        request: &FolderSyncRequest,
+        sync_engine: &Arc<SyncEngine>,
+        account_manager: &Arc<ImapAccountManager>,
+    ) -> SyncResult<()> {
+        info!("Incremental sync of {}/{} after a status change", request.account_id, request.folder_name);
+        
+        let client = account_manager.get_client(&request.account_id).await?;
+        let mut client_guard = client.lock().await;
+        let folder = client_guard.select_folder(&request.folder_name).await?;
+        sync_engine
+            .sync_folder(request.account_id.clone(), &mut client_guard, &folder, SyncStrategy::Incremental)
+            .await
+    }
+    
     /// Process an IDLE notification
     async fn process_idle_notification(
         update: &IdleUpdate,
-        _sync_engine: &Arc<SyncEngine>,
-        _account_manager: &Arc<ImapAccountManager>,
+        folder_status: &Mutex<HashMap<(String, String), FolderStatus>>,
+        sync_requests: &mpsc::UnboundedSender<FolderSyncRequest>,
     ) -> ImapResult<()> {
         debug!("Processing IDLE notification: {:?}", update);
         
@@ -254,6 +338,25 @@ impl ImapService {
                 
                 // TODO: Trigger flag sync
             }
+            IdleNotification::MailboxStatus { messages, uid_next, unseen, .. } => {
+                info!("Status changed in {}/{}: {:?} messages, {:?} unseen, UIDNEXT {:?}",
+                      update.account_id, update.folder_name, messages, unseen, uid_next);
+                
+                // A folder seen for the first time is synced too, since mail
+                // may have arrived before it was watched
+                let status = FolderStatus { messages: *messages, uid_next: *uid_next };
+                let key = (update.account_id.clone(), update.folder_name.clone());
+                let previous = folder_status.lock().await.insert(key, status);
+                if previous != Some(status) {
+                    let request = FolderSyncRequest {
+                        account_id: update.account_id.clone(),
+                        folder_name: update.folder_name.clone(),
+                    };
+                    if sync_requests.send(request).is_err() {
+                        warn!("Sync worker stopped; {}/{} not synced", update.account_id, update.folder_name);
+                    }
+                }
+            }
             IdleNotification::ConnectionLost => {
                 warn!("IDLE connection lost for {}/{}", 
                       update.account_id, update.folder_name);
@@ -305,12 +408,12 @@ impl ImapService {
                                 warn!("IDLE connection inactive for account: {}", account_id);
                                 
                                 // Try to restart IDLE if we have an active folder
-                                if let Some(folder_name) = {
+                                if let Some(folders) = {
                                     let folders = active_folders.read().await;
                                     folders.get(&account_id).cloned()
                                 } {
                                     let mut client_guard = client.lock().await;
-                                    if let Err(e) = client_guard.start_folder_monitoring(folder_name).await {
+                                    if let Err(e) = client_guard.start_folders_monitoring(folders).await {
                                         error!("Failed to restart IDLE for {}: {}", account_id, e);
                                     }
                                 }
@@ -405,4 +508,40 @@ mod tests {
         
         // Would verify that the notification triggers appropriate sync actions
     }
+    
+    #[tokio::test]
+    async fn test_notify_status_requests_sync() {
+        let folder_status = Mutex::new(HashMap::new());
+        let (sync_requests, mut sync_receiver) = mpsc::unbounded_channel();
+        let status_update = |line: &str| {
+            let mut notifications = crate::imap::idle::IdleResponseParser::parse_idle_response(line);
+            assert_eq!(notifications.len(), 1);
+            let notification = notifications.remove(0);
+            let IdleNotification::MailboxStatus { folder, .. } = &notification else {
+                panic!("expected a status notification, got {:?}", notification);
+            };
+            IdleUpdate {
+                account_id: "work_account".to_string(),
+                folder_name: folder.clone(),
+                notification,
+                timestamp: Instant::now(),
+            }
+        };
+        let work = FolderSyncRequest {
+            account_id: "work_account".to_string(),
+            folder_name: "Work".to_string(),
+        };
+        
+        let update = status_update("* STATUS \"Work\" (MESSAGES 5 UIDNEXT 42)");
+        ImapService::process_idle_notification(&update, &folder_status, &sync_requests).await.unwrap();
+        assert_eq!(sync_receiver.try_recv(), Ok(work.clone()));
+        
+        // The same counters again don't sync
+        ImapService::process_idle_notification(&update, &folder_status, &sync_requests).await.unwrap();
+        assert!(sync_receiver.try_recv().is_err());
+        
+        let update = status_update("* STATUS \"Work\" (MESSAGES 6 UIDNEXT 43)");
+        ImapService::process_idle_notification(&update, &folder_status, &sync_requests).await.unwrap();
+        assert_eq!(sync_receiver.try_recv(), Ok(work));
+    }
 }
\ No newline at end of file
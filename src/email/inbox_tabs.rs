@@ -0,0 +1,184 @@
+//! Gmail-style INBOX tabs filled by AI triage
+//!
+//! New INBOX mail is sorted into Primary, Promotions, Social, Updates or
+//! Forums by the configured AI provider while it syncs; with Ollama nothing
+//! leaves the machine. The tab is kept on the stored message and messages
+//! nobody categorized yet show under Primary. Moving a message to another tab
+//! by hand records that tab for its sender, and later mail from the sender
+//! goes straight there without asking the provider.
+
+use crate::ai::AIService;
+use crate::email::sender_lists::extract_address;
+use crate::email::{DatabaseResult, EmailDatabase, StoredMessage};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Tab of the INBOX a message is listed under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum InboxTab {
+    /// Mail from people and anything that fits nowhere else
+    #[default]
+    Primary,
+    /// Deals, offers and marketing
+    Promotions,
+    /// Social networks and media sites
+    Social,
+    /// Receipts, bills, statements and automated notices
+    Updates,
+    /// Mailing lists, groups and discussion boards
+    Forums,
+}
+
+impl InboxTab {
+    /// Every tab, in the order they are shown
+    pub const ALL: [InboxTab; 5] = [
+        InboxTab::Primary,
+        InboxTab::Promotions,
+        InboxTab::Social,
+        InboxTab::Updates,
+        InboxTab::Forums,
+    ];
+
+    /// Name shown on the tab and stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InboxTab::Primary => "Primary",
+            InboxTab::Promotions => "Promotions",
+            InboxTab::Social => "Social",
+            InboxTab::Updates => "Updates",
+            InboxTab::Forums => "Forums",
+        }
+    }
+
+    /// Tab after this one, wrapping around
+    pub fn next(&self) -> InboxTab {
+        let index = Self::ALL.iter().position(|tab| tab == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Tab before this one, wrapping around
+    pub fn previous(&self) -> InboxTab {
+        let index = Self::ALL.iter().position(|tab| tab == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Tab named first in a model response
+    pub fn from_response(response: &str) -> Option<InboxTab> {
+        let response = response.to_lowercase();
+        Self::ALL
+            .iter()
+            .filter_map(|tab| {
+                response
+                    .find(&tab.as_str().to_lowercase())
+                    .map(|position| (position, *tab))
+            })
+            .min_by_key(|(position, _)| *position)
+            .map(|(_, tab)| tab)
+    }
+}
+
+impl std::fmt::Display for InboxTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for InboxTab {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|tab| tab.as_str().eq_ignore_ascii_case(s.trim()))
+            .copied()
+            .ok_or_else(|| format!("Unknown inbox tab: {}", s))
+    }
+}
+
+/// Sender key overrides are stored under
+pub fn override_sender(from_addr: &str) -> String {
+    extract_address(from_addr).to_lowercase()
+}
+
+/// Sorts new INBOX mail into tabs
+pub struct InboxTriage {
+    ai_service: Arc<AIService>,
+    database: Arc<EmailDatabase>,
+}
+
+impl InboxTriage {
+    /// Create a triage using `ai_service` for senders without an override
+    pub fn new(ai_service: Arc<AIService>, database: Arc<EmailDatabase>) -> Self {
+        Self {
+            ai_service,
+            database,
+        }
+    }
+
+    /// Tab for a message: the sender's override when there is one, the
+    /// provider's choice otherwise
+    pub async fn categorize(&self, message: &StoredMessage) -> anyhow::Result<InboxTab> {
+        let sender = override_sender(&message.from_addr);
+        if let Some(tab) = self
+            .database
+            .get_inbox_tab_override(&message.account_id, &sender)
+            .await?
+        {
+            debug!("Using {} tab chosen for {}", tab, sender);
+            return Ok(tab);
+        }
+        Ok(self.ai_service.classify_inbox_tab(message).await?)
+    }
+
+    /// Categorize messages and store their tabs
+    ///
+    /// Messages the provider could not categorize are left for Primary.
+    /// Returns the number of messages that got a tab.
+    pub async fn triage(&self, messages: &[StoredMessage]) -> usize {
+        let mut categorized = 0;
+        for message in messages {
+            match self.categorize(message).await {
+                Ok(tab) => match self.database.set_inbox_tab(message.id, Some(tab)).await {
+                    Ok(()) => categorized += 1,
+                    Err(e) => warn!("Failed to store inbox tab of {}: {}", message.id, e),
+                },
+                Err(e) => warn!("Failed to categorize {}: {}", message.id, e),
+            }
+        }
+        categorized
+    }
+}
+
+/// Move a message to `tab` and keep later mail from its sender there
+///
+/// Messages from the sender already in the account's INBOX move along.
+pub async fn correct_inbox_tab(
+    database: &EmailDatabase,
+    message: &StoredMessage,
+    tab: InboxTab,
+) -> DatabaseResult<()> {
+    database
+        .set_inbox_tab_override(&message.account_id, &override_sender(&message.from_addr), tab)
+        .await?;
+    database.set_inbox_tab(message.id, Some(tab)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_names_and_order() {
+        assert_eq!(InboxTab::from_response("Category: promotions (a sale)"), Some(InboxTab::Promotions));
+        assert_eq!(
+            InboxTab::from_response("Updates. Not Primary, since it is a receipt"),
+            Some(InboxTab::Updates)
+        );
+        assert_eq!(InboxTab::from_response("no idea"), None);
+        assert_eq!("forums".parse::<InboxTab>(), Ok(InboxTab::Forums));
+        assert_eq!(InboxTab::Forums.next(), InboxTab::Primary);
+        assert_eq!(InboxTab::Primary.previous(), InboxTab::Forums);
+        assert_eq!(override_sender("Shop <Deals@Shop.example>"), "deals@shop.example");
+    }
+}
@@ -312,6 +312,8 @@ impl MaildirHandler {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         };
 
         // Parse headers and body
@@ -752,6 +754,8 @@ This is the body of the email."#;
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         };
 
         let email_content = handler.format_message_as_email(&message).unwrap();
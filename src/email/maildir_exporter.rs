@@ -661,6 +661,8 @@ mod tests {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         }
     }
 
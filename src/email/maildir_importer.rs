@@ -527,6 +527,8 @@ impl MaildirImporter {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         };
 
         // Parse headers and body
@@ -953,6 +955,8 @@ This is a work-related message."#;
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         };
         
         assert!(importer.validate_message_format(&valid_message).is_ok());
@@ -107,6 +107,8 @@ impl MaildirTestEnvironment {
             sync_version: 1,
             is_draft,
             is_deleted,
+            snoozed_until: None,
+            inbox_tab: None,
         }
     }
     
@@ -495,6 +495,8 @@ mod tests {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,739 @@
+//! Mbox import and export
+//!
+//! Reads the mbox files mutt and most Unix mail clients write: each message
+//! starts at a `From ` line at the top of the file or after a blank line, and
+//! body lines escaped as `>From ` (mboxrd) lose one `>`. Read, answered and
+//! flagged state comes from the `Status` and `X-Status` headers mutt keeps,
+//! and the date from the `Date` header, falling back to the `From ` line.
+//!
+//! Export writes one mboxrd file per folder with those headers set, so the
+//! messages show the same state when opened in mutt.
+
+use crate::email::database::StoredFolder;
+use crate::email::reply_target::parse_address_list;
+use crate::email::security::{header_values, split_headers};
+use crate::email::sender_lists::extract_address;
+use crate::email::{EmailDatabase, StoredMessage};
+use crate::maildir::MaildirMessage;
+use crate::mime::decode_mime_header;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Messages between progress reports
+const PROGRESS_EVERY: usize = 50;
+
+/// Errors that can occur during mbox import and export
+#[derive(Error, Debug)]
+pub enum MboxError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Database error: {0}")]
+    Database(String),
+
+    #[error("{0} is not an mbox file or a directory holding them")]
+    NotMbox(PathBuf),
+}
+
+pub type MboxResult<T> = Result<T, MboxError>;
+
+impl From<crate::email::database::DatabaseError> for MboxError {
+    fn from(error: crate::email::database::DatabaseError) -> Self {
+        MboxError::Database(error.to_string())
+    }
+}
+
+/// An mbox file found under the import path
+#[derive(Debug, Clone, PartialEq)]
+pub struct MboxMailbox {
+    /// Path below the import path, or the file name for a single file
+    pub name: String,
+    pub path: PathBuf,
+    pub message_count: usize,
+    pub unread_count: usize,
+}
+
+/// A mailbox and the folder its messages go into
+#[derive(Debug, Clone)]
+pub struct MboxTarget {
+    pub mailbox: MboxMailbox,
+    pub folder: String,
+}
+
+/// How far an import or export has got
+#[derive(Debug, Clone, PartialEq)]
+pub struct MboxProgress {
+    /// Mailbox or folder being copied
+    pub current: String,
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Result of an import or export
+#[derive(Debug, Clone, Default)]
+pub struct MboxOutcome {
+    /// Messages imported or exported
+    pub messages: usize,
+    /// Messages already in the target folder
+    pub duplicates_skipped: usize,
+    /// Files written by an export
+    pub files: Vec<PathBuf>,
+    /// Messages or folders that could not be copied
+    pub errors: Vec<String>,
+    pub cancelled: bool,
+}
+
+/// One message of an mbox file
+#[derive(Debug, Clone, PartialEq)]
+pub struct MboxEntry {
+    /// The `From ` line the message starts with
+    pub envelope: String,
+    /// The message, unescaped, with `\n` line endings
+    pub raw: String,
+}
+
+impl MboxEntry {
+    /// Date from the `From ` line, such as `From jo@example.com Mon Jan  1 12:00:00 2024`
+    pub fn envelope_date(&self) -> Option<DateTime<Utc>> {
+        let words: Vec<&str> = self.envelope.split_whitespace().skip(2).take(5).collect();
+        NaiveDateTime::parse_from_str(&words.join(" "), "%a %b %d %H:%M:%S %Y")
+            .ok()
+            .map(|date| date.and_utc())
+    }
+}
+
+/// Messages of an mbox file, read one at a time
+pub struct MboxReader<R> {
+    lines: std::io::Split<R>,
+    envelope: Option<String>,
+    current: String,
+    previous_blank: bool,
+}
+
+impl<R: BufRead> MboxReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.split(b'\n'),
+            envelope: None,
+            current: String::new(),
+            // The first line can start a message without a blank line before it
+            previous_blank: true,
+        }
+    }
+
+    fn finish(&mut self, envelope: String) -> MboxEntry {
+        let mut raw = std::mem::take(&mut self.current);
+        // The blank line before the next `From ` separates messages
+        if raw.ends_with("\n\n") {
+            raw.pop();
+        }
+        MboxEntry { envelope, raw }
+    }
+}
+
+impl MboxReader<BufReader<File>> {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: BufRead> Iterator for MboxReader<R> {
+    type Item = std::io::Result<MboxEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for line in self.lines.by_ref() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            let line = String::from_utf8_lossy(&line);
+            let line = line.strip_suffix('\r').unwrap_or(&line);
+
+            if line.starts_with("From ") && self.previous_blank {
+                self.previous_blank = false;
+                if let Some(envelope) = self.envelope.replace(line.to_string()) {
+                    return Some(Ok(self.finish(envelope)));
+                }
+                continue;
+            }
+            self.previous_blank = line.is_empty();
+            if self.envelope.is_none() {
+                continue;
+            }
+
+            // mboxrd: `>From ` and `>>From ` lose one `>`
+            let unescaped = match line.strip_prefix('>') {
+                Some(rest) if rest.trim_start_matches('>').starts_with("From ") => rest,
+                _ => line,
+            };
+            self.current.push_str(unescaped);
+            self.current.push('\n');
+        }
+
+        let envelope = self.envelope.take()?;
+        Some(Ok(self.finish(envelope)))
+    }
+}
+
+/// Whether a file starts like an mbox file
+pub fn is_mbox_file(path: &Path) -> bool {
+    let mut start = [0u8; 5];
+    File::open(path)
+        .and_then(|mut file| std::io::Read::read_exact(&mut file, &mut start))
+        .is_ok()
+        && &start == b"From "
+}
+
+/// Find the mbox files at a path: the file itself, or every mbox file in a
+/// directory such as mutt's `~/Mail`
+pub async fn scan(path: &Path) -> MboxResult<Vec<MboxMailbox>> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || scan_blocking(&path))
+        .await
+        .map_err(|e| MboxError::Io(std::io::Error::other(e)))?
+}
+
+fn scan_blocking(path: &Path) -> MboxResult<Vec<MboxMailbox>> {
+    if path.is_file() {
+        if !is_mbox_file(path) {
+            return Err(MboxError::NotMbox(path.to_path_buf()));
+        }
+        let name = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "mbox".to_string());
+        return Ok(vec![count_mailbox(path, name)?]);
+    }
+    if !path.is_dir() {
+        return Err(MboxError::NotMbox(path.to_path_buf()));
+    }
+
+    let mut mailboxes = Vec::new();
+    let entries = walkdir::WalkDir::new(path)
+        .min_depth(1)
+        .max_depth(5)
+        .into_iter()
+        // Dot files and Maildir folders are not mbox files
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            !name.starts_with('.') && !matches!(name.as_ref(), "cur" | "new" | "tmp")
+        });
+    for entry in entries.filter_map(Result::ok) {
+        if !entry.file_type().is_file() || !is_mbox_file(entry.path()) {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(path).unwrap_or(entry.path());
+        let name = relative
+            .with_extension("")
+            .to_string_lossy()
+            .replace(std::path::MAIN_SEPARATOR, "/");
+        mailboxes.push(count_mailbox(entry.path(), name)?);
+    }
+    if mailboxes.is_empty() {
+        return Err(MboxError::NotMbox(path.to_path_buf()));
+    }
+
+    mailboxes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(mailboxes)
+}
+
+fn count_mailbox(path: &Path, name: String) -> MboxResult<MboxMailbox> {
+    let mut mailbox = MboxMailbox {
+        name,
+        path: path.to_path_buf(),
+        message_count: 0,
+        unread_count: 0,
+    };
+    for entry in MboxReader::open(path)? {
+        let entry = entry?;
+        mailbox.message_count += 1;
+        let (headers, _) = split_headers(&entry.raw);
+        if !mbox_flags(&headers).iter().any(|flag| flag == "\\Seen") {
+            mailbox.unread_count += 1;
+        }
+    }
+    Ok(mailbox)
+}
+
+/// IMAP flags from mutt's `Status` and `X-Status` headers
+fn mbox_flags(headers: &[String]) -> Vec<String> {
+    let status: String = header_values(headers, "status")
+        .into_iter()
+        .chain(header_values(headers, "x-status"))
+        .collect();
+    [
+        ('R', "\\Seen"),
+        ('A', "\\Answered"),
+        ('F', "\\Flagged"),
+        ('T', "\\Draft"),
+    ]
+    .into_iter()
+    .filter(|(letter, _)| status.contains(*letter))
+    .map(|(_, flag)| flag.to_string())
+    .collect()
+}
+
+/// Build a stored message from an mbox entry
+pub fn parse_entry(entry: &MboxEntry, account_id: &str, folder_name: &str) -> StoredMessage {
+    let (headers, _) = split_headers(&entry.raw);
+    let first = |name: &str| header_values(&headers, name).into_iter().next();
+    let addresses = |name: &str| -> Vec<String> {
+        header_values(&headers, name)
+            .iter()
+            .flat_map(|value| parse_address_list(value))
+            .collect()
+    };
+
+    let from = first("from")
+        .map(|from| decode_mime_header(&from))
+        .unwrap_or_default();
+    let from_name = from
+        .find('<')
+        .map(|start| from[..start].trim().trim_matches('"').to_string())
+        .filter(|name| !name.is_empty());
+    let date = first("date")
+        .and_then(|date| DateTime::parse_from_rfc2822(date.trim()).ok())
+        .map(|date| date.with_timezone(&Utc))
+        .or_else(|| entry.envelope_date())
+        .unwrap_or_else(Utc::now);
+    let flags = mbox_flags(&headers);
+    let body = crate::mime::parse_message(&entry.raw);
+    let now = Utc::now();
+
+    StoredMessage {
+        id: Uuid::new_v4(),
+        account_id: account_id.to_string(),
+        folder_name: folder_name.to_string(),
+        imap_uid: 0,
+        message_id: first("message-id"),
+        thread_id: None,
+        in_reply_to: first("in-reply-to"),
+        references: first("references")
+            .map(|references| references.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default(),
+        subject: first("subject")
+            .map(|subject| decode_mime_header(&subject))
+            .unwrap_or_default(),
+        from_addr: extract_address(&from).to_string(),
+        from_name,
+        to_addrs: addresses("to"),
+        cc_addrs: addresses("cc"),
+        bcc_addrs: addresses("bcc"),
+        reply_to: addresses("reply-to").into_iter().next(),
+        date,
+        body_text: body.text,
+        body_html: body.html,
+        attachments: Vec::new(),
+        is_draft: flags.iter().any(|flag| flag == "\\Draft"),
+        flags,
+        labels: Vec::new(),
+        size: Some(entry.raw.len() as u32),
+        priority: None,
+        created_at: now,
+        updated_at: now,
+        last_synced: now,
+        sync_version: 1,
+        is_deleted: false,
+        snoozed_until: None,
+        inbox_tab: None,
+    }
+}
+
+/// A stored message as an mbox entry, ending in the blank separator line
+pub fn format_entry(message: &StoredMessage) -> String {
+    let rfc822 = MaildirMessage::from_stored_message(message).to_rfc5322();
+    let (header_block, body) = rfc822.split_once("\n\n").unwrap_or((&rfc822, ""));
+    let sender = if message.from_addr.is_empty() {
+        "MAILER-DAEMON"
+    } else {
+        message.from_addr.as_str()
+    };
+    let has = |flag: &str| message.flags.iter().any(|f| f == flag);
+
+    let mut entry = format!(
+        "From {} {}\n{}\n",
+        sender,
+        message.date.format("%a %b %e %H:%M:%S %Y"),
+        header_block
+    );
+    entry.push_str(if has("\\Seen") {
+        "Status: RO\n"
+    } else {
+        "Status: O\n"
+    });
+    let x_status: String = [('A', "\\Answered"), ('F', "\\Flagged"), ('T', "\\Draft")]
+        .into_iter()
+        .filter(|(_, flag)| has(flag))
+        .map(|(letter, _)| letter)
+        .collect();
+    if !x_status.is_empty() {
+        entry.push_str(&format!("X-Status: {}\n", x_status));
+    }
+    entry.push('\n');
+
+    for line in body.lines() {
+        // mboxrd: quote `From ` lines, and lines that already look quoted
+        if line.trim_start_matches('>').starts_with("From ") {
+            entry.push('>');
+        }
+        entry.push_str(line);
+        entry.push('\n');
+    }
+    entry.push('\n');
+    entry
+}
+
+/// File name an exported folder is written to, such as `Lists.dev.mbox`
+pub fn export_file_name(folder: &str) -> String {
+    let name: String = folder
+        .chars()
+        .map(|c| {
+            if matches!(c, '/' | '\\' | ':') {
+                '.'
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("{}.mbox", name.trim_start_matches('.'))
+}
+
+/// Imports mbox files into the local message store
+pub struct MboxImporter {
+    database: Arc<EmailDatabase>,
+}
+
+impl MboxImporter {
+    pub fn new(database: Arc<EmailDatabase>) -> Self {
+        Self { database }
+    }
+
+    /// Copy the messages of each mailbox into its folder of the account
+    ///
+    /// Messages whose Message-ID is already in the folder are skipped when
+    /// `skip_duplicates` is set, so an import can be run again after adding
+    /// to the file.
+    pub async fn import(
+        &self,
+        account_id: &str,
+        targets: &[MboxTarget],
+        skip_duplicates: bool,
+        progress: &mpsc::UnboundedSender<MboxProgress>,
+        cancel: &AtomicBool,
+    ) -> MboxResult<MboxOutcome> {
+        let mut outcome = MboxOutcome::default();
+        let total = targets
+            .iter()
+            .map(|target| target.mailbox.message_count)
+            .sum();
+        let mut done = 0;
+
+        for target in targets {
+            self.ensure_folder(account_id, &target.folder).await?;
+            let mut known: HashSet<String> = self
+                .database
+                .get_folder_message_ids(account_id, &target.folder)
+                .await?
+                .into_iter()
+                .collect();
+            // Imported messages get UIDs above those already in the folder
+            let mut next_uid = self
+                .database
+                .get_folder_uids(account_id, &target.folder)
+                .await?
+                .into_iter()
+                .max()
+                .unwrap_or(0)
+                + 1;
+
+            for entry in MboxReader::open(&target.mailbox.path)? {
+                if cancel.load(Ordering::Relaxed) {
+                    outcome.cancelled = true;
+                    return Ok(outcome);
+                }
+                done += 1;
+                if done % PROGRESS_EVERY == 0 {
+                    let _ = progress.send(MboxProgress {
+                        current: target.mailbox.name.clone(),
+                        done,
+                        total,
+                    });
+                }
+
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        outcome
+                            .errors
+                            .push(format!("{}: {}", target.mailbox.name, e));
+                        break;
+                    }
+                };
+                let mut message = parse_entry(&entry, account_id, &target.folder);
+                if let Some(ref message_id) = message.message_id {
+                    if !known.insert(message_id.clone()) && skip_duplicates {
+                        outcome.duplicates_skipped += 1;
+                        continue;
+                    }
+                }
+
+                message.imap_uid = next_uid;
+                next_uid += 1;
+                if let Err(e) = self.database.store_message(&message).await {
+                    outcome
+                        .errors
+                        .push(format!("{}: message {}: {}", target.mailbox.name, done, e));
+                    continue;
+                }
+                // Kept for header filter rules and the headers panel
+                let (header_block, _) = entry.raw.split_once("\n\n").unwrap_or((&entry.raw, ""));
+                if let Err(e) = self
+                    .database
+                    .store_raw_headers(message.id, header_block)
+                    .await
+                {
+                    tracing::warn!("Failed to store headers of imported message: {}", e);
+                }
+                outcome.messages += 1;
+            }
+        }
+
+        let _ = progress.send(MboxProgress {
+            current: String::new(),
+            done: total,
+            total,
+        });
+        Ok(outcome)
+    }
+
+    /// Add the folder to the account's folder list if it isn't there yet
+    async fn ensure_folder(&self, account_id: &str, folder: &str) -> MboxResult<()> {
+        let folders = self.database.get_folders(account_id).await?;
+        if folders
+            .iter()
+            .any(|existing| existing.name == folder || existing.full_name == folder)
+        {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        self.database
+            .store_folder(&StoredFolder {
+                account_id: account_id.to_string(),
+                name: folder.to_string(),
+                full_name: folder.to_string(),
+                delimiter: Some("/".to_string()),
+                attributes: Vec::new(),
+                created_at: now,
+                updated_at: now,
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+/// Exports folders of the local message store as mbox files
+pub struct MboxExporter {
+    database: Arc<EmailDatabase>,
+}
+
+impl MboxExporter {
+    pub fn new(database: Arc<EmailDatabase>) -> Self {
+        Self { database }
+    }
+
+    /// Write each folder to its own file in `directory`, oldest message first
+    ///
+    /// Existing files are left alone and reported as errors.
+    pub async fn export(
+        &self,
+        account_id: &str,
+        folders: &[String],
+        directory: &Path,
+        progress: &mpsc::UnboundedSender<MboxProgress>,
+        cancel: &AtomicBool,
+    ) -> MboxResult<MboxOutcome> {
+        tokio::fs::create_dir_all(directory).await?;
+        let mut outcome = MboxOutcome::default();
+        let mut done = 0;
+
+        let mut batches = Vec::new();
+        for folder in folders {
+            let mut messages = self
+                .database
+                .get_messages(account_id, folder, Some(u32::MAX), None)
+                .await?;
+            messages.sort_by_key(|message| message.date);
+            batches.push((folder, messages));
+        }
+        let total = batches.iter().map(|(_, messages)| messages.len()).sum();
+
+        for (folder, messages) in batches {
+            let path = directory.join(export_file_name(folder));
+            let file = match File::options().write(true).create_new(true).open(&path) {
+                Ok(file) => file,
+                Err(e) => {
+                    outcome
+                        .errors
+                        .push(format!("{}: {}: {}", folder, path.display(), e));
+                    done += messages.len();
+                    continue;
+                }
+            };
+            let mut writer = BufWriter::new(file);
+            for message in &messages {
+                if cancel.load(Ordering::Relaxed) {
+                    writer.flush()?;
+                    outcome.files.push(path);
+                    outcome.cancelled = true;
+                    return Ok(outcome);
+                }
+                writer.write_all(format_entry(message).as_bytes())?;
+                outcome.messages += 1;
+                done += 1;
+                if done % PROGRESS_EVERY == 0 {
+                    let _ = progress.send(MboxProgress {
+                        current: folder.clone(),
+                        done,
+                        total,
+                    });
+                }
+            }
+            writer.flush()?;
+            outcome.files.push(path);
+        }
+
+        let _ = progress.send(MboxProgress {
+            current: String::new(),
+            done: total,
+            total,
+        });
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MBOX: &str = "From jo@example.com Mon Jan  1 12:00:00 2024\n\
+        From: Jo Doe <jo@example.com>\n\
+        To: sam@example.com, \"Lee, Kim\" <kim@example.com>\n\
+        Subject: =?UTF-8?Q?Caf=C3=A9?=\n\
+        Message-ID: <1@example.com>\n\
+        Status: RO\n\
+        X-Status: AF\n\
+        \n\
+        Hello\n\
+        >From the start\n\
+        \n\
+        From sam@example.com Tue Feb 13 08:30:05 2024\n\
+        From: sam@example.com\n\
+        Subject: No date header\n\
+        \n\
+        Second\n";
+
+    fn entries(mbox: &str) -> Vec<MboxEntry> {
+        MboxReader::new(mbox.as_bytes())
+            .collect::<std::io::Result<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_read_mbox() {
+        let entries = entries(MBOX);
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].raw.ends_with("Hello\nFrom the start\n"));
+
+        let first = parse_entry(&entries[0], "account", "Archive");
+        assert_eq!(first.subject, "Café");
+        assert_eq!(first.from_addr, "jo@example.com");
+        assert_eq!(first.from_name.as_deref(), Some("Jo Doe"));
+        assert_eq!(first.to_addrs, ["sam@example.com", "kim@example.com"]);
+        assert_eq!(first.flags, ["\\Seen", "\\Answered", "\\Flagged"]);
+        assert_eq!(
+            first.body_text.as_deref().map(str::trim),
+            Some("Hello\nFrom the start")
+        );
+
+        let second = parse_entry(&entries[1], "account", "Archive");
+        assert!(second.flags.is_empty());
+        assert_eq!(second.date.to_rfc3339(), "2024-02-13T08:30:05+00:00");
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let mut message = parse_entry(&entries(MBOX)[0], "account", "INBOX");
+        message.flags = vec!["\\Seen".to_string(), "\\Flagged".to_string()];
+
+        let mbox = format_entry(&message) + &format_entry(&message);
+        assert!(mbox.starts_with("From jo@example.com Mon Jan  1 12:00:00 2024\n"));
+        assert!(mbox.contains("\n>From the start\n"));
+
+        let entries = entries(&mbox);
+        assert_eq!(entries.len(), 2);
+        let copy = parse_entry(&entries[1], "account", "INBOX");
+        assert_eq!(copy.subject, message.subject);
+        assert_eq!(copy.date, message.date);
+        assert_eq!(copy.flags, message.flags);
+        assert_eq!(
+            copy.body_text.as_deref().map(str::trim),
+            Some("Hello\nFrom the start")
+        );
+
+        assert_eq!(export_file_name("Lists/dev"), "Lists.dev.mbox");
+    }
+
+    #[tokio::test]
+    async fn test_import_into_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("archive");
+        std::fs::write(&path, MBOX).unwrap();
+        let mailboxes = scan(dir.path()).await.unwrap();
+        assert_eq!(mailboxes.len(), 1);
+        assert_eq!(
+            (mailboxes[0].message_count, mailboxes[0].unread_count),
+            (2, 1)
+        );
+
+        let database = Arc::new(EmailDatabase::new_in_memory().await.unwrap());
+        let importer = MboxImporter::new(database.clone());
+        let targets = vec![MboxTarget {
+            mailbox: mailboxes[0].clone(),
+            folder: "Archive".to_string(),
+        }];
+        let (progress, _receiver) = mpsc::unbounded_channel();
+        let cancel = AtomicBool::new(false);
+        let outcome = importer
+            .import("account", &targets, true, &progress, &cancel)
+            .await
+            .unwrap();
+        assert_eq!(outcome.messages, 2);
+
+        // Running it again only adds the message without a Message-ID
+        let outcome = importer
+            .import("account", &targets, true, &progress, &cancel)
+            .await
+            .unwrap();
+        assert_eq!((outcome.messages, outcome.duplicates_skipped), (1, 1));
+        let stored = database
+            .get_messages("account", "Archive", None, None)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 3);
+        assert!(database
+            .get_folders("account")
+            .await
+            .unwrap()
+            .iter()
+            .any(|folder| folder.name == "Archive"));
+    }
+}
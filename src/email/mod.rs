@@ -1,13 +1,23 @@
 pub mod advanced_filters;
 pub mod advanced_filters_ui;
 pub mod ai_assistant;
+pub mod attachment_save;
 pub mod attachment_viewer;
 pub mod attachments;
+pub mod bulk_read;
+pub mod crypto;
 pub mod database;
 pub mod database_optimizations;
+pub mod dedup;
+pub mod document_preview;
 pub mod desktop_notifications;
 pub mod filters;
+pub mod flowed;
 pub mod folder_hierarchy;
+pub mod folder_sync;
+pub mod gmail_labels;
+pub mod header_report;
+pub mod inbox_tabs;
 pub mod maildir;
 pub mod maildir_exporter;
 pub mod maildir_export_wizard;
@@ -18,10 +28,22 @@ pub mod maildir_integration_tests;
 pub mod maildir_error_handling;
 pub mod maildir_mapper;
 pub mod maildir_ui;
+pub mod mbox;
 pub mod message;
 pub mod notifications;
 pub mod operations_service;
+pub mod plain_text;
+pub mod priority;
+pub mod quoting;
+pub mod reply_target;
+pub mod retention;
+pub mod security;
+pub mod sender_lists;
+pub mod sieve;
+pub mod signature_strip;
+pub mod snooze;
 pub mod sorting;
+pub mod special_folders;
 pub mod sync_engine;
 pub mod imap_service;
 pub mod async_sync_service;
@@ -42,24 +64,40 @@ pub use ai_assistant::{
     AIEmailAssistant, EmailCompositionAssistance, EmailReplyAssistance, EmailSummary,
     BulkEmailAnalysis, BulkAnalysisStats,
 };
+pub use attachment_save::{AttachmentOrganization, AttachmentSaveConfig};
 pub use attachment_viewer::{AttachmentViewer, ViewResult, ViewerMode};
-pub use attachments::{AttachmentInfo, AttachmentManager, AttachmentType};
+pub use attachments::{
+    AttachmentDownload, AttachmentInfo, AttachmentManager, AttachmentType, DownloadProgress,
+};
+pub use bulk_read::{
+    BulkReadService, MarkReadConfig, MarkReadOutcome, MarkReadProgress, MarkReadUndo, SeenState,
+};
 pub use database::{
     BackupResult, CleanupResult, DatabaseError, DatabaseResult, DatabaseStats, EmailDatabase,
-    FolderSyncState, RestoreResult, StoredAttachment, StoredMessage, SyncStatus,
+    FolderActivity, FolderSyncState, RestoreResult, SenderHistory, StoredAttachment, StoredMessage,
+    SyncStatus,
 };
 pub use database_optimizations::{
     OptimizedDatabase, DatabaseOptimizationConfig, PaginationConfig, SearchFilters,
     SortDirection, QueryStats, BatchOperationResult, FolderMessageCount,
 };
+pub use crypto::{
+    CryptoError, CryptoResult, DetachedSignature, PgpConfig, SignatureCheck, SignatureStatus,
+};
+pub use dedup::{DedupConfig, DedupedMessage};
 pub use desktop_notifications::DesktopNotificationService;
 pub use filters::{
     EmailFilter, FilterAction, FilterCondition, FilterEngine, FilterField, FilterOperator,
-    FilterResult, FilterTemplates,
+    FilterResult, FilterRules, FilterTemplates,
 };
+pub use flowed::{FlowedConfig, FlowedFormat};
 pub use folder_hierarchy::{
     FolderHierarchy, FolderHierarchyError, FolderHierarchyMapper, FolderHierarchyResult,
 };
+pub use folder_sync::{FolderSyncDefaults, FolderSyncMode};
+pub use gmail_labels::LabelLayout;
+pub use header_report::{HeaderField, HeaderReport, ReceivedHop};
+pub use inbox_tabs::{InboxTab, InboxTriage};
 pub use maildir::{MaildirError, MaildirFolderStats, MaildirHandler, MaildirResult, MaildirStats};
 pub use maildir_exporter::{
     ExportConfig, ExportStats, MaildirExportError, MaildirExporter, MaildirExportResult,
@@ -87,14 +125,35 @@ pub use maildir_mapper::{
     MaildirMessageMetadata,
 };
 pub use maildir_ui::{MaildirExportPreview, MaildirImportFolder, MaildirImportPreview, MaildirUI};
+pub use mbox::{
+    MboxError, MboxExporter, MboxImporter, MboxMailbox, MboxOutcome, MboxProgress, MboxTarget,
+};
 pub use message::{EmailMessage, MessageId};
-pub use operations_service::{EmailOperationsService, EmailOperationError, EmailOperationResult};
+pub use operations_service::{
+    EmailOperationsService, EmailOperationError, EmailOperationResult, SpamReport,
+};
+pub use plain_text::PlainTextAccounts;
+pub use priority::MailPriority;
+pub use quoting::{QuoteConfig, QuotePosition, QuotePrefix, QuoteStyle};
+pub use reply_target::{
+    reply_threading, ListReplyDefault, ReplyConfig, ReplyHeaders, ReplyMode, ReplyRecipients,
+};
+pub use retention::{
+    PendingRetention, RetentionAction, RetentionCandidate, RetentionConfig, RetentionOutcome,
+    RetentionPlan, RetentionRule, RetentionService,
+};
+pub use security::{AuthVerdict, CryptoScheme, MessageSecurity, SecurityBadge};
+pub use sender_lists::{BlockAction, SenderLists, SenderVerdict};
+pub use sieve::{from_sieve, to_sieve, SieveError, SieveExport, SieveImport};
+pub use signature_strip::{SignatureStripConfig, SignatureStripper};
 pub use notifications::{
     EmailNotification, EmailNotificationHandler, EmailNotificationManager, UIEmailUpdater,
 };
 pub use sorting::{MultiCriteriaSorter, SortCriteria, SortOrder};
+pub use special_folders::{SpecialFolder, SpecialFolderConfig, SpecialFolderMapping};
 pub use sync_engine::{
-    ConflictResolution, SyncEngine, SyncError, SyncPhase, SyncProgress, SyncResult, SyncStrategy,
+    AccountSyncProgress, ConflictResolution, SyncControl, SyncEngine, SyncError, SyncPhase,
+    SyncProgress, SyncResult, SyncStrategy,
 };
 pub use imap_service::{ImapService, IdleUpdate};
 pub use async_sync_service::AsyncSyncService;
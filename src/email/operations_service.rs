@@ -2,11 +2,18 @@
 //!
 //! This service provides high-level email operations like delete, archive, mark read/unread
 //! and handles the coordination between IMAP client, local database, and UI updates.
+//! Accounts served over JMAP get the same operations as `Email/set` keyword and
+//! mailbox changes. On Gmail accounts with label views, operations on a view
+//! act on the message in All Mail, and moving between views changes labels.
 
-use crate::email::EmailDatabase;
+use crate::email::{
+    EmailDatabase, LabelLayout, SpecialFolder, SpecialFolderConfig, SpecialFolderMapping,
+};
 use crate::imap::{ImapAccountManager, MessageFlag};
+use crate::jmap::JmapClient;
+use std::collections::BTreeMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, error, info, warn};
 
 /// Result type for email operations
@@ -18,6 +25,9 @@ pub enum EmailOperationError {
     #[error("IMAP error: {0}")]
     Imap(#[from] crate::imap::ImapError),
     
+    #[error("JMAP error: {0}")]
+    Jmap(#[from] crate::jmap::JmapError),
+    
     #[error("Database error: {0}")]
     Database(#[from] crate::email::database::DatabaseError),
     
@@ -37,12 +47,41 @@ pub enum EmailOperationError {
     InvalidState { reason: String },
 }
 
+/// Direction of a spam report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpamReport {
+    /// Message is spam: move it to the junk folder
+    Spam,
+    /// Message was wrongly classified: move it back to the inbox
+    NotSpam,
+}
+
+impl SpamReport {
+    /// IMAP keyword (RFC 5788) that trains server-side filters such as Dovecot, Fastmail and iCloud
+    pub fn keyword(self) -> &'static str {
+        match self {
+            SpamReport::Spam => "$Junk",
+            SpamReport::NotSpam => "$NotJunk",
+        }
+    }
+
+    /// The keyword this report replaces
+    pub fn opposite_keyword(self) -> &'static str {
+        match self {
+            SpamReport::Spam => "$NotJunk",
+            SpamReport::NotSpam => "$Junk",
+        }
+    }
+}
+
 /// Email operations service
 pub struct EmailOperationsService {
     imap_manager: Arc<ImapAccountManager>,
     database: Arc<EmailDatabase>,
     /// Cache of folder names by type for each account
     folder_cache: Arc<RwLock<std::collections::HashMap<String, FolderCache>>>,
+    /// Folders mapped in configuration, which win over detection
+    special_folders: RwLock<SpecialFolderConfig>,
 }
 
 /// Cached folder information for an account
@@ -56,6 +95,19 @@ pub struct FolderCache {
     pub spam: Option<String>,
 }
 
+impl FolderCache {
+    /// The detected special folders as a mapping
+    pub fn detected(&self) -> SpecialFolderMapping {
+        SpecialFolderMapping {
+            sent: self.sent.clone(),
+            drafts: self.drafts.clone(),
+            trash: self.trash.clone(),
+            junk: self.spam.clone(),
+            archive: self.archive.clone(),
+        }
+    }
+}
+
 impl EmailOperationsService {
     /// Create a new email operations service
     pub fn new(
@@ -66,9 +118,15 @@ impl EmailOperationsService {
             imap_manager,
             database,
             folder_cache: Arc::new(RwLock::new(std::collections::HashMap::new())),
+            special_folders: RwLock::new(SpecialFolderConfig::default()),
         }
     }
 
+    /// Use these mapped special folders from now on
+    pub async fn set_special_folders(&self, config: SpecialFolderConfig) {
+        *self.special_folders.write().await = config;
+    }
+
     /// Delete an email by message ID
     pub async fn delete_email_by_id(
         &self,
@@ -85,6 +143,9 @@ impl EmailOperationsService {
     }
 
     /// Delete an email by marking it as deleted and expunging
+    ///
+    /// With a Trash folder mapped for the account, the message is copied
+    /// there first, unless it is already in it.
     pub async fn delete_email(
         &self,
         account_id: &str,
@@ -92,6 +153,15 @@ impl EmailOperationsService {
         folder_name: &str,
     ) -> EmailOperationResult<()> {
         info!("Deleting email UID {} from folder {} in account {}", message_uid, folder_name, account_id);
+        let trash = self.mapped_folder(account_id, SpecialFolder::Trash).await;
+
+        if let Some(jmap) = self.get_jmap_client(account_id).await {
+            self.jmap_delete(&jmap, account_id, folder_name, &[message_uid], trash.as_deref())
+                .await?;
+            info!("Successfully deleted email UID {} from {}/{}", message_uid, account_id, folder_name);
+            return Ok(());
+        }
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
 
         // Get IMAP client for the account
         let client_arc = self.get_imap_client(account_id).await?;
@@ -102,6 +172,9 @@ impl EmailOperationsService {
 
         // Mark message as deleted
         let uid_set = message_uid.to_string();
+        if let Some(trash) = trash.as_deref().filter(|trash| !trash.eq_ignore_ascii_case(folder_name)) {
+            client.uid_copy_messages(&uid_set, trash).await?;
+        }
         client.uid_store_flags(&uid_set, &[MessageFlag::Deleted], false).await?;
 
         // Expunge to permanently delete
@@ -114,6 +187,58 @@ impl EmailOperationsService {
         Ok(())
     }
 
+    /// Delete several messages, possibly from different folders, with one STORE
+    /// and EXPUNGE per folder
+    ///
+    /// Like [`Self::delete_email`], messages go to a mapped Trash folder first.
+    /// Returns the number of messages deleted.
+    pub async fn delete_emails_by_ids(
+        &self,
+        account_id: &str,
+        message_ids: &[uuid::Uuid],
+    ) -> EmailOperationResult<usize> {
+        let mut by_folder: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for message_id in message_ids {
+            match self.database.get_message_by_id(*message_id).await? {
+                Some(message) if message.account_id == account_id => {
+                    by_folder.entry(message.folder_name).or_default().push(message.imap_uid);
+                }
+                _ => warn!("Message {} to delete not found in {}", message_id, account_id),
+            }
+        }
+        if by_folder.is_empty() {
+            return Err(EmailOperationError::MessageNotFound { uid: 0, folder: String::new() });
+        }
+
+        let trash = self.mapped_folder(account_id, SpecialFolder::Trash).await;
+        if let Some(jmap) = self.get_jmap_client(account_id).await {
+            let mut deleted = 0;
+            for (folder_name, uids) in &by_folder {
+                info!("Deleting {} messages from {}/{}", uids.len(), account_id, folder_name);
+                self.jmap_delete(&jmap, account_id, folder_name, uids, trash.as_deref())
+                    .await?;
+                deleted += uids.len();
+            }
+            return Ok(deleted);
+        }
+
+        let client_arc = self.get_imap_client(account_id).await?;
+        let mut client = client_arc.lock().await;
+        let mut deleted = 0;
+        for (folder_name, uids) in &by_folder {
+            info!("Deleting {} messages from {}/{}", uids.len(), account_id, folder_name);
+            client.select_folder(folder_name).await?;
+            if let Some(trash) = trash.as_deref().filter(|trash| !trash.eq_ignore_ascii_case(folder_name)) {
+                client.uid_copy_messages(&uid_set(uids), trash).await?;
+            }
+            client.uid_store_flags(&uid_set(uids), &[MessageFlag::Deleted], false).await?;
+            client.expunge().await?;
+            self.database.delete_messages_by_uids(account_id, folder_name, uids).await?;
+            deleted += uids.len();
+        }
+        Ok(deleted)
+    }
+
     /// Archive an email by message ID
     pub async fn archive_email_by_id(
         &self,
@@ -141,6 +266,18 @@ impl EmailOperationsService {
         // Get archive folder for this account
         let archive_folder = self.get_archive_folder(account_id).await?;
 
+        if let Some(jmap) = self.get_jmap_client(account_id).await {
+            self.jmap_move(&jmap, account_id, &[message_uid], source_folder, &archive_folder)
+                .await?;
+            info!("Successfully archived email UID {} from {}/{} to {}", message_uid, account_id, source_folder, archive_folder);
+            return Ok(());
+        }
+
+        // Archiving out of a Gmail label view drops the label
+        if self.database.get_label_layout(account_id).await?.is_some() {
+            return self.move_email(account_id, message_uid, source_folder, &archive_folder).await;
+        }
+
         // Get IMAP client
         let client_arc = self.get_imap_client(account_id).await?;
         let mut client = client_arc.lock().await;
@@ -191,6 +328,11 @@ impl EmailOperationsService {
     ) -> EmailOperationResult<()> {
         info!("Marking email UID {} as read in folder {} of account {}", message_uid, folder_name, account_id);
 
+        if self.jmap_set_keyword(account_id, &[message_uid], "$seen", true).await? {
+            return Ok(());
+        }
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
+
         // Get IMAP client
         let client_arc = self.get_imap_client(account_id).await?;
         let mut client = client_arc.lock().await;
@@ -233,6 +375,11 @@ impl EmailOperationsService {
     ) -> EmailOperationResult<()> {
         info!("Marking email UID {} as unread in folder {} of account {}", message_uid, folder_name, account_id);
 
+        if self.jmap_set_keyword(account_id, &[message_uid], "$seen", false).await? {
+            return Ok(());
+        }
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
+
         // Get IMAP client
         let client_arc = self.get_imap_client(account_id).await?;
         let mut client = client_arc.lock().await;
@@ -251,6 +398,85 @@ impl EmailOperationsService {
         Ok(())
     }
 
+    /// Mark several messages of one folder read or unread with a single UID STORE
+    ///
+    /// Used for whole threads. Messages already in the requested state are skipped.
+    /// Returns the number of messages changed.
+    pub async fn set_read_status_by_ids(
+        &self,
+        account_id: &str,
+        message_ids: &[uuid::Uuid],
+        folder_name: &str,
+        read: bool,
+    ) -> EmailOperationResult<usize> {
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
+        let mut messages = Vec::with_capacity(message_ids.len());
+        for message_id in message_ids {
+            match self.database.get_message_by_id(*message_id).await? {
+                Some(message) if message.folder_name == *folder_name => messages.push(message),
+                Some(message) => warn!("Skipping message {} from folder {}", message.id, message.folder_name),
+                None => warn!("Message {} not found in database", message_id),
+            }
+        }
+        messages.retain(|message| message.flags.iter().any(|flag| flag == "\\Seen") != read);
+        if messages.is_empty() {
+            return Ok(0);
+        }
+
+        let uids: Vec<u32> = messages.iter().map(|message| message.imap_uid).collect();
+        info!("Marking {} messages as {} in {}/{}", uids.len(), if read { "read" } else { "unread" }, account_id, folder_name);
+
+        if !self.jmap_set_keyword(account_id, &uids, "$seen", read).await? {
+            let client_arc = self.get_imap_client(account_id).await?;
+            let mut client = client_arc.lock().await;
+            client.select_folder(folder_name).await?;
+            if read {
+                client.uid_store_flags(&uid_set(&uids), &[MessageFlag::Seen], false).await?;
+            } else {
+                client.uid_remove_flags(&uid_set(&uids), &[MessageFlag::Seen]).await?;
+            }
+        }
+
+        // Keep the local copy in step so the list doesn't flip back before the next sync
+        for message in &mut messages {
+            message.flags.retain(|flag| flag != "\\Seen");
+            if read {
+                message.flags.push("\\Seen".to_string());
+            }
+            self.database.update_message_flags(message.id, &message.flags).await?;
+        }
+
+        Ok(messages.len())
+    }
+
+    /// Set or clear `\Seen` on a set of UIDs with one UID STORE, leaving the local copy alone
+    pub async fn set_seen_uids(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        uids: &[u32],
+        seen: bool,
+    ) -> EmailOperationResult<()> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        debug!("Setting \\Seen={} on {} messages in {}/{}", seen, uids.len(), account_id, folder_name);
+        if self.jmap_set_keyword(account_id, uids, "$seen", seen).await? {
+            return Ok(());
+        }
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
+
+        let client_arc = self.get_imap_client(account_id).await?;
+        let mut client = client_arc.lock().await;
+        client.select_folder(folder_name).await?;
+        if seen {
+            client.uid_store_flags(&uid_set(uids), &[MessageFlag::Seen], false).await?;
+        } else {
+            client.uid_remove_flags(&uid_set(uids), &[MessageFlag::Seen]).await?;
+        }
+        Ok(())
+    }
+
     /// Toggle read/unread status of an email
     pub async fn toggle_email_read_status(
         &self,
@@ -258,6 +484,8 @@ impl EmailOperationsService {
         message_uid: u32,
         folder_name: &str,
     ) -> EmailOperationResult<bool> {
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
+
         // Check current read status from database
         match self.database.get_message_by_uid(account_id, folder_name, message_uid).await {
             Ok(Some(message)) => {
@@ -303,27 +531,30 @@ impl EmailOperationsService {
         folder_name: &str,
     ) -> EmailOperationResult<bool> {
         info!("Toggling flag for email UID {} in folder {} of account {}", message_uid, folder_name, account_id);
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
 
         // Check current flag status from database
         let message = self.database.get_message_by_uid(account_id, folder_name, message_uid).await?
             .ok_or_else(|| EmailOperationError::MessageNotFound { uid: message_uid, folder: folder_name.to_string() })?;
         let is_flagged = message.flags.contains(&"\\Flagged".to_string());
 
-        // Get IMAP client
-        let client_arc = self.get_imap_client(account_id).await?;
-        let mut client = client_arc.lock().await;
+        if !self.jmap_set_keyword(account_id, &[message_uid], "$flagged", !is_flagged).await? {
+            // Get IMAP client
+            let client_arc = self.get_imap_client(account_id).await?;
+            let mut client = client_arc.lock().await;
 
-        // Select folder
-        client.select_folder(folder_name).await?;
+            // Select folder
+            client.select_folder(folder_name).await?;
 
-        let uid_set = message_uid.to_string();
-        
-        if is_flagged {
-            // Remove flag
-            client.uid_remove_flags(&uid_set, &[MessageFlag::Flagged]).await?;
-        } else {
-            // Add flag
-            client.uid_store_flags(&uid_set, &[MessageFlag::Flagged], false).await?;
+            let uid_set = message_uid.to_string();
+
+            if is_flagged {
+                // Remove flag
+                client.uid_remove_flags(&uid_set, &[MessageFlag::Flagged]).await?;
+            } else {
+                // Add flag
+                client.uid_store_flags(&uid_set, &[MessageFlag::Flagged], false).await?;
+            }
         }
 
         // Note: Database will be updated on next sync
@@ -348,6 +579,25 @@ impl EmailOperationsService {
         info!("Moving email UID {} from {} to {} in account {}", 
               message_uid, source_folder, destination_folder, account_id);
 
+        if let Some(jmap) = self.get_jmap_client(account_id).await {
+            self.jmap_move(&jmap, account_id, &[message_uid], source_folder, destination_folder)
+                .await?;
+            info!("Successfully moved email UID {} from {} to {} in account {}", 
+                  message_uid, source_folder, destination_folder, account_id);
+            return Ok(());
+        }
+
+        // Within Gmail's All Mail a move is a change of labels
+        if let Some(layout) = self.database.get_label_layout(account_id).await? {
+            let from = layout.label_for(source_folder);
+            let to = layout.label_for(destination_folder);
+            let in_all_mail = from.is_some() || source_folder == layout.all_mail;
+            if in_all_mail && (to.is_some() || destination_folder == layout.all_mail) {
+                return self.relabel(account_id, &layout, &[message_uid], from, to).await;
+            }
+        }
+        let source_folder = &self.storage_folder(account_id, source_folder).await?;
+
         // Get IMAP client
         let client_arc = self.get_imap_client(account_id).await?;
         let mut client = client_arc.lock().await;
@@ -373,6 +623,290 @@ impl EmailOperationsService {
         Ok(())
     }
 
+    /// Copy an email to another folder, leaving the original in place
+    ///
+    /// The copy shows up locally with the next sync of the destination.
+    pub async fn copy_email(
+        &self,
+        account_id: &str,
+        message_uid: u32,
+        source_folder: &str,
+        destination_folder: &str,
+    ) -> EmailOperationResult<()> {
+        info!("Copying email UID {} from {} to {} in account {}",
+              message_uid, source_folder, destination_folder, account_id);
+
+        if let Some(jmap) = self.get_jmap_client(account_id).await {
+            let ids = self.database.jmap_email_ids(account_id, &[message_uid]).await?;
+            let mut client = jmap.lock().await;
+            let destination = client.mailbox_id(destination_folder).await?;
+            client.copy_emails(&ids, &destination).await?;
+            return Ok(());
+        }
+
+        // Copying an All Mail message into a Gmail label view adds the label
+        if let Some(layout) = self.database.get_label_layout(account_id).await? {
+            let in_all_mail =
+                layout.label_for(source_folder).is_some() || source_folder == layout.all_mail;
+            if let Some(to) = layout.label_for(destination_folder).filter(|_| in_all_mail) {
+                return self.relabel(account_id, &layout, &[message_uid], None, Some(to)).await;
+            }
+        }
+        let source_folder = &self.storage_folder(account_id, source_folder).await?;
+
+        let client_arc = self.get_imap_client(account_id).await?;
+        let mut client = client_arc.lock().await;
+        client.select_folder(source_folder).await?;
+        client.uid_copy_messages(&message_uid.to_string(), destination_folder).await?;
+        Ok(())
+    }
+
+    /// Soft-delete messages: flag them `\Deleted` and hide them locally without expunging
+    ///
+    /// JMAP has no `\Deleted` flag, so for JMAP accounts only the local copy is hidden.
+    /// The messages stay on the server until [`expunge_soft_deleted`](Self::expunge_soft_deleted)
+    /// runs and can be brought back with [`restore_soft_deleted`](Self::restore_soft_deleted).
+    pub async fn soft_delete_uids(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        uids: &[u32],
+    ) -> EmailOperationResult<()> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        info!("Soft-deleting {} messages in {}/{}", uids.len(), account_id, folder_name);
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
+
+        if self.get_jmap_client(account_id).await.is_some() {
+            self.database.delete_messages_by_uids(account_id, folder_name, uids).await?;
+            return Ok(());
+        }
+
+        let client_arc = self.get_imap_client(account_id).await?;
+        let mut client = client_arc.lock().await;
+        client.select_folder(folder_name).await?;
+        client.uid_store_flags(&uid_set(uids), &[MessageFlag::Deleted], false).await?;
+
+        self.database.delete_messages_by_uids(account_id, folder_name, uids).await?;
+        Ok(())
+    }
+
+    /// Undo a soft delete by clearing the `\Deleted` flag
+    pub async fn restore_soft_deleted(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        uids: &[u32],
+    ) -> EmailOperationResult<()> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        info!("Restoring {} soft-deleted messages in {}/{}", uids.len(), account_id, folder_name);
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
+
+        if self.get_jmap_client(account_id).await.is_some() {
+            self.database.restore_messages_by_uids(account_id, folder_name, uids).await?;
+            return Ok(());
+        }
+
+        let client_arc = self.get_imap_client(account_id).await?;
+        let mut client = client_arc.lock().await;
+        client.select_folder(folder_name).await?;
+        client.uid_remove_flags(&uid_set(uids), &[MessageFlag::Deleted]).await?;
+
+        self.database.restore_messages_by_uids(account_id, folder_name, uids).await?;
+        Ok(())
+    }
+
+    /// Permanently remove soft-deleted messages, copying them to the archive folder first
+    /// when `archive` is set
+    ///
+    /// `keep` lists messages in the same folder that are soft-deleted but must survive.
+    /// With UIDPLUS only `uids` are expunged; without it a plain EXPUNGE would take every
+    /// `\Deleted` message along, so `keep` is unflagged around it.
+    pub async fn expunge_soft_deleted(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        uids: &[u32],
+        archive: bool,
+        keep: &[u32],
+    ) -> EmailOperationResult<()> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        let folder_name = &self.storage_folder(account_id, folder_name).await?;
+        let archive_folder = if archive {
+            Some(self.get_archive_folder(account_id).await?)
+        } else {
+            None
+        };
+
+        if let Some(jmap) = self.get_jmap_client(account_id).await {
+            match archive_folder {
+                Some(ref archive_folder) => {
+                    self.jmap_move(&jmap, account_id, uids, folder_name, archive_folder)
+                        .await?
+                }
+                None => {
+                    let ids = self.database.jmap_email_ids(account_id, uids).await?;
+                    jmap.lock().await.destroy_emails(&ids).await?;
+                }
+            }
+            info!("Expunged {} messages from {}/{}", uids.len(), account_id, folder_name);
+            return Ok(());
+        }
+
+        let client_arc = self.get_imap_client(account_id).await?;
+        let mut client = client_arc.lock().await;
+        client.select_folder(folder_name).await?;
+
+        let targets = uid_set(uids);
+        if let Some(ref archive_folder) = archive_folder {
+            client.uid_copy_messages(&targets, archive_folder).await?;
+        }
+        // Re-flag in case another client cleared the flag during the grace window
+        client.uid_store_flags(&targets, &[MessageFlag::Deleted], false).await?;
+
+        if client.supports_uid_expunge() {
+            client.uid_expunge(&targets).await?;
+        } else if keep.is_empty() {
+            client.expunge().await?;
+        } else {
+            let keep_set = uid_set(keep);
+            client.uid_remove_flags(&keep_set, &[MessageFlag::Deleted]).await?;
+            let result = client.expunge().await;
+            client.uid_store_flags(&keep_set, &[MessageFlag::Deleted], false).await?;
+            result?;
+        }
+
+        info!(
+            "Expunged {} messages from {}/{}{}",
+            uids.len(),
+            account_id,
+            folder_name,
+            archive_folder.map(|folder| format!(" after copying to {}", folder)).unwrap_or_default()
+        );
+        Ok(())
+    }
+
+    /// Report messages as spam by moving them to the account's junk folder
+    ///
+    /// Servers that advertise a SPECIAL-USE `\Junk` folder (including Gmail, where moving to
+    /// Spam trains the filter) get the message moved there; the `$Junk` keyword is set as well
+    /// for servers that train on keywords. Returns the number of messages moved.
+    pub async fn report_spam_by_ids(
+        &self,
+        account_id: &str,
+        message_ids: &[uuid::Uuid],
+        source_folder: &str,
+    ) -> EmailOperationResult<usize> {
+        let spam_folder = self.get_spam_folder(account_id).await?;
+        let reported = self
+            .move_reported_messages(account_id, message_ids, source_folder, &spam_folder, SpamReport::Spam)
+            .await?;
+        Ok(reported.len())
+    }
+
+    /// Report messages as not spam by moving them back to the inbox
+    ///
+    /// Returns the sender addresses of the moved messages so they can be allowlisted.
+    pub async fn report_not_spam_by_ids(
+        &self,
+        account_id: &str,
+        message_ids: &[uuid::Uuid],
+        source_folder: &str,
+    ) -> EmailOperationResult<Vec<String>> {
+        let inbox = self
+            .get_folder_cache(account_id)
+            .await
+            .map(|folders| folders.inbox)
+            .unwrap_or_else(|| "INBOX".to_string());
+        let reported = self
+            .move_reported_messages(account_id, message_ids, source_folder, &inbox, SpamReport::NotSpam)
+            .await?;
+
+        let mut senders: Vec<String> = reported.into_iter().map(|message| message.from_addr).collect();
+        senders.sort();
+        senders.dedup();
+        Ok(senders)
+    }
+
+    /// Tag and move a batch of reported messages in a single IMAP round
+    async fn move_reported_messages(
+        &self,
+        account_id: &str,
+        message_ids: &[uuid::Uuid],
+        source_folder: &str,
+        destination_folder: &str,
+        report: SpamReport,
+    ) -> EmailOperationResult<Vec<crate::email::StoredMessage>> {
+        let mut messages = Vec::with_capacity(message_ids.len());
+        for message_id in message_ids {
+            match self.database.get_message_by_id(*message_id).await? {
+                Some(message) => messages.push(message),
+                None => warn!("Reported message {} not found in database", message_id),
+            }
+        }
+        if messages.is_empty() {
+            return Err(EmailOperationError::MessageNotFound { uid: 0, folder: source_folder.to_string() });
+        }
+        if source_folder.eq_ignore_ascii_case(destination_folder) {
+            return Err(EmailOperationError::InvalidState {
+                reason: format!("Messages are already in {}", destination_folder),
+            });
+        }
+
+        let uids: Vec<u32> = messages.iter().map(|message| message.imap_uid).collect();
+        let uid_set = uid_set(&uids);
+        info!("Reporting {} messages as {:?} in {}/{}", uids.len(), report, account_id, source_folder);
+
+        if let Some(jmap) = self.get_jmap_client(account_id).await {
+            let ids = self.database.jmap_email_ids(account_id, &uids).await?;
+            {
+                let client = jmap.lock().await;
+                client.set_keyword(&ids, report.opposite_keyword(), false).await?;
+                client.set_keyword(&ids, report.keyword(), true).await?;
+            }
+            self.jmap_move(&jmap, account_id, &uids, source_folder, destination_folder)
+                .await?;
+            info!("Moved {} reported messages from {} to {}", uids.len(), source_folder, destination_folder);
+            return Ok(messages);
+        }
+        let source_folder = &self.storage_folder(account_id, source_folder).await?;
+
+        let client_arc = self.get_imap_client(account_id).await?;
+        let mut client = client_arc.lock().await;
+        client.select_folder(source_folder).await?;
+
+        // Keywords are best effort: not every server allows custom flags
+        let keyword = MessageFlag::Custom(report.keyword().to_string());
+        let opposite = MessageFlag::Custom(report.opposite_keyword().to_string());
+        if let Err(e) = client.uid_remove_flags(&uid_set, &[opposite]).await {
+            debug!("Could not clear {} keyword: {}", report.opposite_keyword(), e);
+        }
+        if let Err(e) = client.uid_store_flags(&uid_set, &[keyword], false).await {
+            debug!("Could not set {} keyword: {}", report.keyword(), e);
+        }
+
+        client.uid_copy_messages(&uid_set, destination_folder).await?;
+        client.uid_store_flags(&uid_set, &[MessageFlag::Deleted], false).await?;
+        client.expunge().await?;
+
+        self.database.delete_messages_by_uids(account_id, source_folder, &uids).await?;
+
+        info!("Moved {} reported messages from {} to {}", uids.len(), source_folder, destination_folder);
+        Ok(messages)
+    }
+
+    /// Get the spam/junk folder name for an account
+    async fn get_spam_folder(&self, account_id: &str) -> EmailOperationResult<String> {
+        self.special_folder(account_id, SpecialFolder::Junk)
+            .await?
+            .ok_or_else(|| EmailOperationError::FolderNotFound { folder: "Junk".to_string() })
+    }
+
     /// Get IMAP client for an account
     async fn get_imap_client(&self, account_id: &str) -> EmailOperationResult<std::sync::Arc<tokio::sync::Mutex<crate::imap::ImapClient>>> {
         self.imap_manager
@@ -381,79 +915,230 @@ impl EmailOperationsService {
             .map_err(|e| EmailOperationError::Imap(e))
     }
 
-    /// Get the archive folder name for an account
-    async fn get_archive_folder(&self, account_id: &str) -> EmailOperationResult<String> {
-        // Try to get from cache first
+    /// Get the JMAP client when the account is served over JMAP
+    async fn get_jmap_client(&self, account_id: &str) -> Option<Arc<Mutex<JmapClient>>> {
+        self.imap_manager.get_jmap_client(account_id).await
+    }
+
+    /// Set or clear a keyword on messages of a JMAP account
+    ///
+    /// Returns false without doing anything for IMAP accounts.
+    async fn jmap_set_keyword(
+        &self,
+        account_id: &str,
+        uids: &[u32],
+        keyword: &str,
+        set: bool,
+    ) -> EmailOperationResult<bool> {
+        let Some(jmap) = self.get_jmap_client(account_id).await else {
+            return Ok(false);
+        };
+        let ids = self.database.jmap_email_ids(account_id, uids).await?;
+        jmap.lock().await.set_keyword(&ids, keyword, set).await?;
+        Ok(true)
+    }
+
+    /// Move messages of a JMAP account between mailboxes and drop the local
+    /// copies in the source folder
+    async fn jmap_move(
+        &self,
+        jmap: &Mutex<JmapClient>,
+        account_id: &str,
+        uids: &[u32],
+        source_folder: &str,
+        destination_folder: &str,
+    ) -> EmailOperationResult<()> {
+        let ids = self.database.jmap_email_ids(account_id, uids).await?;
+        let mut client = jmap.lock().await;
+        let source = client.mailbox_id(source_folder).await?;
+        let destination = client.mailbox_id(destination_folder).await?;
+        client.move_emails(&ids, &source, &destination).await?;
+        drop(client);
+
+        self.database.delete_messages_by_uids(account_id, source_folder, uids).await?;
+        Ok(())
+    }
+
+    /// Delete messages of a JMAP account: move them to `trash`, or destroy
+    /// them when there is none or they are already in it
+    async fn jmap_delete(
+        &self,
+        jmap: &Mutex<JmapClient>,
+        account_id: &str,
+        folder_name: &str,
+        uids: &[u32],
+        trash: Option<&str>,
+    ) -> EmailOperationResult<()> {
+        if let Some(trash) = trash.filter(|trash| !trash.eq_ignore_ascii_case(folder_name)) {
+            return self.jmap_move(jmap, account_id, uids, folder_name, trash).await;
+        }
+
+        let ids = self.database.jmap_email_ids(account_id, uids).await?;
+        jmap.lock().await.destroy_emails(&ids).await?;
+        self.database.delete_messages_by_uids(account_id, folder_name, uids).await?;
+        Ok(())
+    }
+
+    /// Folder storing the messages shown in `folder_name`: All Mail for a
+    /// Gmail label view, otherwise the folder itself
+    async fn storage_folder(&self, account_id: &str, folder_name: &str) -> EmailOperationResult<String> {
+        Ok(match self.database.get_label_layout(account_id).await? {
+            Some(layout) => layout.storage_folder(folder_name).to_string(),
+            None => folder_name.to_string(),
+        })
+    }
+
+    /// Add label `to` and remove label `from` on Gmail messages in All Mail
+    /// with UID STORE X-GM-LABELS, and record the new labels locally
+    async fn relabel(
+        &self,
+        account_id: &str,
+        layout: &LabelLayout,
+        uids: &[u32],
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> EmailOperationResult<()> {
+        let from = from.filter(|from| Some(*from) != to);
+        if from.is_none() && to.is_none() {
+            return Ok(());
+        }
+        info!("Relabelling {} messages in {} from {:?} to {:?}", uids.len(), account_id, from, to);
+
         {
-            let cache = self.folder_cache.read().await;
-            if let Some(folders) = cache.get(account_id) {
-                if let Some(archive) = &folders.archive {
-                    return Ok(archive.clone());
-                }
+            let client_arc = self.get_imap_client(account_id).await?;
+            let mut client = client_arc.lock().await;
+            client.select_folder(&layout.all_mail).await?;
+            if let Some(to) = to {
+                client.uid_store_labels(&uid_set(uids), &[to.to_string()], true).await?;
+            }
+            if let Some(from) = from {
+                client.uid_store_labels(&uid_set(uids), &[from.to_string()], false).await?;
             }
         }
 
-        // If not cached, fetch folder list and find archive folder
-        self.refresh_folder_cache(account_id).await?;
-        
-        let cache = self.folder_cache.read().await;
-        if let Some(folders) = cache.get(account_id) {
-            if let Some(archive) = &folders.archive {
-                return Ok(archive.clone());
+        for uid in uids {
+            if let Some(message) = self.database.get_message_by_uid(account_id, &layout.all_mail, *uid).await? {
+                let labels = LabelLayout::relabel(&message.labels, from, to);
+                self.database.update_message_labels(message.id, &labels).await?;
             }
         }
+        Ok(())
+    }
+
+    /// Get the archive folder name for an account
+    async fn get_archive_folder(&self, account_id: &str) -> EmailOperationResult<String> {
+        if let Some(archive) = self.special_folder(account_id, SpecialFolder::Archive).await? {
+            return Ok(archive);
+        }
 
         // Fallback to common archive folder names
         warn!("No archive folder found for account {}, using default 'Archive'", account_id);
         Ok("Archive".to_string())
     }
 
-    /// Refresh folder cache for an account
-    async fn refresh_folder_cache(&self, account_id: &str) -> EmailOperationResult<()> {
-        debug!("Refreshing folder cache for account {}", account_id);
+    /// The folder mapped in configuration for a role, ignoring detection
+    async fn mapped_folder(&self, account_id: &str, kind: SpecialFolder) -> Option<String> {
+        self.special_folders
+            .read()
+            .await
+            .folder(account_id, kind)
+            .map(str::to_string)
+    }
+
+    /// The folder for a role: the mapped one, else the detected one
+    pub async fn special_folder(
+        &self,
+        account_id: &str,
+        kind: SpecialFolder,
+    ) -> EmailOperationResult<Option<String>> {
+        if let Some(folder) = self.mapped_folder(account_id, kind).await {
+            return Ok(Some(folder));
+        }
+        Ok(self.detected_special_folders(account_id).await?.get(kind).map(str::to_string))
+    }
+
+    /// Special folders detected from the server's folder list, without the
+    /// mapped overrides
+    pub async fn detected_special_folders(
+        &self,
+        account_id: &str,
+    ) -> EmailOperationResult<SpecialFolderMapping> {
+        if self.get_folder_cache(account_id).await.is_none() {
+            self.refresh_folder_cache(account_id).await?;
+        }
+        Ok(self
+            .get_folder_cache(account_id)
+            .await
+            .map(|folders| folders.detected())
+            .unwrap_or_default())
+    }
+
+    /// Append a message to the folder mapped for `kind`, e.g. a copy of sent
+    /// mail
+    ///
+    /// Does nothing when no folder is mapped. Returns the folder and, when the
+    /// server reports it, the new message's UID.
+    pub async fn file_message(
+        &self,
+        account_id: &str,
+        kind: SpecialFolder,
+        raw_message: &[u8],
+        flags: &[MessageFlag],
+    ) -> EmailOperationResult<Option<(String, Option<u32>)>> {
+        let Some(folder) = self.mapped_folder(account_id, kind).await else {
+            return Ok(None);
+        };
 
         let client_arc = self.get_imap_client(account_id).await?;
         let mut client = client_arc.lock().await;
-        let folders = client.list_folders("", "*").await?;
+        let uid = client
+            .append(&folder, flags, None, raw_message, raw_message.len() as u64)
+            .await?;
 
-        let mut folder_cache = FolderCache {
-            inbox: "INBOX".to_string(),
-            sent: None,
-            drafts: None,
-            trash: None,
-            archive: None,
-            spam: None,
-        };
+        info!("Filed a {} byte copy in {}/{}", raw_message.len(), account_id, folder);
+        Ok(Some((folder, uid)))
+    }
 
-        // Categorize folders by common names and attributes
-        for folder in folders {
-            let name_upper = folder.name.to_uppercase();
-            
-            // Check folder attributes first
-            for attr in &folder.attributes {
-                match attr {
-                    crate::imap::FolderAttribute::Sent => folder_cache.sent = Some(folder.name.clone()),
-                    crate::imap::FolderAttribute::Drafts => folder_cache.drafts = Some(folder.name.clone()),
-                    crate::imap::FolderAttribute::Trash => folder_cache.trash = Some(folder.name.clone()),
-                    crate::imap::FolderAttribute::Archive => folder_cache.archive = Some(folder.name.clone()),
-                    crate::imap::FolderAttribute::Junk => folder_cache.spam = Some(folder.name.clone()),
-                    _ => {}
-                }
-            }
+    /// Remove a message filed earlier, such as the previous copy of a draft,
+    /// without moving it to Trash
+    pub async fn remove_filed_message(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        message_uid: u32,
+    ) -> EmailOperationResult<()> {
+        let client_arc = self.get_imap_client(account_id).await?;
+        let mut client = client_arc.lock().await;
+        client.select_folder(folder_name).await?;
+        client
+            .uid_store_flags(&message_uid.to_string(), &[MessageFlag::Deleted], false)
+            .await?;
+        client.expunge().await?;
+        Ok(())
+    }
 
-            // Fallback to name-based detection
-            if name_upper.contains("SENT") && folder_cache.sent.is_none() {
-                folder_cache.sent = Some(folder.name.clone());
-            } else if name_upper.contains("DRAFT") && folder_cache.drafts.is_none() {
-                folder_cache.drafts = Some(folder.name.clone());
-            } else if (name_upper.contains("TRASH") || name_upper.contains("DELETED")) && folder_cache.trash.is_none() {
-                folder_cache.trash = Some(folder.name.clone());
-            } else if (name_upper.contains("ARCHIVE") || name_upper.contains("ALL MAIL")) && folder_cache.archive.is_none() {
-                folder_cache.archive = Some(folder.name.clone());
-            } else if (name_upper.contains("SPAM") || name_upper.contains("JUNK")) && folder_cache.spam.is_none() {
-                folder_cache.spam = Some(folder.name.clone());
+    /// Refresh folder cache for an account
+    async fn refresh_folder_cache(&self, account_id: &str) -> EmailOperationResult<()> {
+        debug!("Refreshing folder cache for account {}", account_id);
+
+        let folders = match self.get_jmap_client(account_id).await {
+            Some(jmap) => jmap.lock().await.list_folders().await?,
+            None => {
+                let client_arc = self.get_imap_client(account_id).await?;
+                let mut client = client_arc.lock().await;
+                client.list_folders("", "*").await?
             }
-        }
+        };
+
+        let detected = SpecialFolderMapping::detect(&folders);
+        let folder_cache = FolderCache {
+            inbox: "INBOX".to_string(),
+            sent: detected.sent,
+            drafts: detected.drafts,
+            trash: detected.trash,
+            archive: detected.archive,
+            spam: detected.junk,
+        };
 
         // Update cache
         let mut cache = self.folder_cache.write().await;
@@ -470,6 +1155,11 @@ impl EmailOperationsService {
     }
 }
 
+/// Format UIDs as an IMAP sequence set
+fn uid_set(uids: &[u32]) -> String {
+    uids.iter().map(|uid| uid.to_string()).collect::<Vec<_>>().join(",")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -483,6 +1173,13 @@ mod tests {
         // In a real implementation, we'd use dependency injection with mocks
     }
 
+    #[test]
+    fn test_spam_report_keywords() {
+        assert_eq!(SpamReport::Spam.keyword(), "$Junk");
+        assert_eq!(SpamReport::Spam.opposite_keyword(), SpamReport::NotSpam.keyword());
+        assert_eq!(SpamReport::NotSpam.opposite_keyword(), SpamReport::Spam.keyword());
+    }
+
     #[test]
     fn test_email_operation_error_display() {
         let error = EmailOperationError::AccountNotFound {
@@ -0,0 +1,82 @@
+//! Plain-text-only accounts
+//!
+//! Accounts listed in `plain_text.toml` never download or show HTML. Sync
+//! fetches a message's header and its parts one by one, leaving out the
+//! HTML alternative whenever there is plain text to show instead. A message
+//! that only has HTML is converted to text locally, so none of its remote
+//! images or other resources are ever requested.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Accounts whose mail is only ever handled as plain text
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlainTextAccounts {
+    /// Account IDs
+    pub accounts: Vec<String>,
+}
+
+impl PlainTextAccounts {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "plain_text.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Whether an account is plain text only
+    pub fn contains(&self, account_id: &str) -> bool {
+        self.accounts.iter().any(|account| account == account_id)
+    }
+}
+
+/// Body to keep for a plain-text-only account
+///
+/// The text part when it has content, otherwise the HTML part converted to
+/// text. Nothing the HTML refers to is fetched.
+pub fn text_only_body(text: Option<&str>, html: Option<&str>) -> Option<String> {
+    let present = |body: &&str| !body.trim().is_empty();
+    let body = text.filter(present).or(html.filter(present))?;
+    if crate::html::is_html_content(body) {
+        Some(crate::html::HtmlRenderer::new(80).html_to_plain_text(body))
+    } else {
+        Some(body.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_accounts() {
+        let config: PlainTextAccounts = toml::from_str(r#"accounts = ["throwaway"]"#).unwrap();
+        assert!(config.contains("throwaway"));
+        assert!(!config.contains("work"));
+    }
+
+    #[test]
+    fn test_text_only_body() {
+        assert_eq!(
+            text_only_body(Some("Hello"), Some("<p>Hi</p>")).as_deref(),
+            Some("Hello")
+        );
+
+        let converted = text_only_body(
+            Some("  "),
+            Some("<html><body><p>Hello</p><img src=\"https://track.example/p.gif\"></body></html>"),
+        )
+        .unwrap();
+        assert!(converted.contains("Hello"));
+        assert!(!converted.contains('<'));
+
+        assert_eq!(text_only_body(None, None), None);
+    }
+}
@@ -0,0 +1,176 @@
+//! Message priority from the competing priority headers
+//!
+//! Mail clients mark urgent mail in several ways: `X-Priority` (1 to 5,
+//! often with a comment such as `1 (Highest)`), `Importance`
+//! (high/normal/low), Outlook's `X-MSMail-Priority` and RFC 2156's
+//! `Priority` (urgent/normal/non-urgent). Sync maps whichever one a message
+//! carries onto the three levels of [`MailPriority`], stored in
+//! `StoredMessage::priority`. Outgoing mail sets the first three, which is
+//! what most clients read.
+
+use crate::email::security::{header_values, split_headers};
+use crate::email::StoredMessage;
+use serde::{Deserialize, Serialize};
+
+/// Priority of a message, whatever header it came from
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum MailPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl MailPriority {
+    /// Priority from a raw message's headers, `None` when it has none of them
+    pub fn from_raw_message(raw: &str) -> Option<Self> {
+        let (headers, _) = split_headers(raw);
+        Self::from_headers(&headers)
+    }
+
+    /// Priority from unfolded header lines
+    ///
+    /// `X-Priority` is read first as the most widespread, then `Importance`,
+    /// `X-MSMail-Priority` and `Priority`; values that can't be read are
+    /// skipped.
+    pub fn from_headers(headers: &[String]) -> Option<Self> {
+        let first = |name: &str, parse: fn(&str) -> Option<Self>| {
+            header_values(headers, name)
+                .iter()
+                .find_map(|value| parse(value))
+        };
+
+        first("x-priority", Self::from_x_priority)
+            .or_else(|| first("importance", Self::from_word))
+            .or_else(|| first("x-msmail-priority", Self::from_word))
+            .or_else(|| first("priority", Self::from_word))
+    }
+
+    /// `X-Priority: 1 (Highest)` style values
+    fn from_x_priority(value: &str) -> Option<Self> {
+        let digit = value.trim().chars().next()?.to_digit(10)?;
+        match digit {
+            1 | 2 => Some(Self::High),
+            3 => Some(Self::Normal),
+            4 | 5 => Some(Self::Low),
+            _ => None,
+        }
+    }
+
+    /// `high`/`normal`/`low` and `urgent`/`non-urgent` style values
+    fn from_word(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "high" | "urgent" => Some(Self::High),
+            "normal" | "medium" => Some(Self::Normal),
+            "low" | "non-urgent" => Some(Self::Low),
+            _ => None,
+        }
+    }
+
+    /// Value stored in the `priority` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+
+    /// Read a stored value; anything unknown is normal priority
+    pub fn parse(value: &str) -> Self {
+        Self::from_word(value).unwrap_or_default()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Low => "Low",
+            Self::Normal => "Normal",
+            Self::High => "High",
+        }
+    }
+
+    /// Next level when cycling in compose: normal, high, low
+    pub fn next(self) -> Self {
+        match self {
+            Self::Normal => Self::High,
+            Self::High => Self::Low,
+            Self::Low => Self::Normal,
+        }
+    }
+
+    /// Headers sent with an outgoing message; none for normal priority
+    pub fn headers(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            Self::Normal => Vec::new(),
+            Self::High => vec![
+                ("X-Priority", "1 (Highest)"),
+                ("Importance", "High"),
+                ("X-MSMail-Priority", "High"),
+            ],
+            Self::Low => vec![
+                ("X-Priority", "5 (Lowest)"),
+                ("Importance", "Low"),
+                ("X-MSMail-Priority", "Low"),
+            ],
+        }
+    }
+}
+
+impl StoredMessage {
+    /// Priority read from the message's headers during sync
+    pub fn message_priority(&self) -> MailPriority {
+        self.priority
+            .as_deref()
+            .map(MailPriority::parse)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_headers() {
+        let raw = |headers: &str| format!("From: a@example.com\r\n{}\r\n\r\nbody", headers);
+
+        assert_eq!(
+            MailPriority::from_raw_message(&raw("X-Priority: 1 (Highest)")),
+            Some(MailPriority::High)
+        );
+        assert_eq!(
+            MailPriority::from_raw_message(&raw("X-Priority: 5")),
+            Some(MailPriority::Low)
+        );
+        assert_eq!(
+            MailPriority::from_raw_message(&raw("Importance: High")),
+            Some(MailPriority::High)
+        );
+        assert_eq!(
+            MailPriority::from_raw_message(&raw("X-MSMail-Priority: Low")),
+            Some(MailPriority::Low)
+        );
+        assert_eq!(
+            MailPriority::from_raw_message(&raw("Priority: urgent")),
+            Some(MailPriority::High)
+        );
+        // X-Priority wins over the others; unreadable values are skipped
+        assert_eq!(
+            MailPriority::from_raw_message(&raw("Importance: low\r\nX-Priority: 2")),
+            Some(MailPriority::High)
+        );
+        assert_eq!(
+            MailPriority::from_raw_message(&raw("X-Priority: soon\r\nImportance: low")),
+            Some(MailPriority::Low)
+        );
+        assert_eq!(MailPriority::from_raw_message(&raw("Subject: hi")), None);
+
+        for priority in [MailPriority::Low, MailPriority::Normal, MailPriority::High] {
+            assert_eq!(MailPriority::parse(priority.as_str()), priority);
+        }
+        assert!(MailPriority::Normal.headers().is_empty());
+    }
+}
@@ -0,0 +1,386 @@
+//! Quoting of the original message in replies and forwards
+//!
+//! Controls where the original goes relative to the new text (top- or
+//! bottom-posting), the attribution line above it and how its lines are
+//! marked. Each account can override the global style, for people whose
+//! work and personal mail follow different conventions.
+//!
+//! Bodies sent as `format=flowed` (RFC 3676) break paragraphs into lines
+//! ending in a space, and sync stores them with each paragraph on one line.
+//! With `reflow_flowed` such paragraphs are wrapped again to fit behind the
+//! quote prefix, instead of leaving every original line with an awkward
+//! short tail or quoting one very long line.
+
+use crate::email::StoredMessage;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Where the original goes relative to the new text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotePosition {
+    /// New text above the original
+    #[default]
+    Top,
+    /// New text below the original
+    Bottom,
+}
+
+/// How quoted lines are marked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotePrefix {
+    /// `> ` before every line; existing quotes nest as `>>`
+    #[default]
+    Angle,
+    /// Indent every line by `indent_width` spaces
+    Indent,
+}
+
+/// Quoting style for one account or the default
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuoteStyle {
+    pub position: QuotePosition,
+    /// Line above a quoted reply. `{date}`, `{name}`, `{email}` and
+    /// `{subject}` are replaced with details of the original
+    pub attribution: String,
+    /// chrono format string for `{date}` and the forward header
+    pub date_format: String,
+    pub prefix: QuotePrefix,
+    /// Spaces per level with the `indent` prefix
+    pub indent_width: usize,
+    /// Mark forwarded text with the quote prefix too
+    pub quote_forwards: bool,
+    /// Rejoin `format=flowed` paragraphs and wrap long lines when quoting
+    pub reflow_flowed: bool,
+    /// Column reflowed paragraphs are wrapped at, prefix included;
+    /// 0 keeps each paragraph on one line
+    pub wrap_width: usize,
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        Self {
+            position: QuotePosition::Top,
+            attribution: "On {date}, {name} wrote:".to_string(),
+            date_format: "%Y-%m-%d %H:%M".to_string(),
+            prefix: QuotePrefix::Angle,
+            indent_width: 4,
+            quote_forwards: false,
+            reflow_flowed: true,
+            wrap_width: 72,
+        }
+    }
+}
+
+/// Quoting configuration
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuoteConfig {
+    /// Style for accounts without an override
+    pub style: QuoteStyle,
+    /// Per-account styles keyed by account ID. An override replaces the
+    /// whole style; fields it leaves out take the built-in defaults
+    pub accounts: HashMap<String, QuoteStyle>,
+}
+
+impl QuoteConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "quoting.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Style used for messages of an account
+    pub fn style_for(&self, account_id: &str) -> &QuoteStyle {
+        self.accounts.get(account_id).unwrap_or(&self.style)
+    }
+}
+
+/// A run of text at one quote depth, quoted as a unit
+struct Paragraph {
+    depth: usize,
+    text: String,
+    /// Joined from soft-broken lines
+    flowed: bool,
+}
+
+impl QuoteStyle {
+    /// Compose body for a reply quoting `body`, the original's text
+    ///
+    /// Empty when there is nothing to quote.
+    pub fn reply_body(&self, message: &StoredMessage, body: &str) -> String {
+        if body.trim().is_empty() {
+            return String::new();
+        }
+        let quoted = format!("{}\n{}", self.attribution_line(message), self.quote(body));
+        self.place(quoted)
+    }
+
+    /// Compose body for forwarding a message
+    pub fn forward_body(&self, message: &StoredMessage) -> String {
+        let text = match message.body_text.as_deref() {
+            Some(text) if self.quote_forwards => self.quote(text),
+            Some(text) => text.to_string(),
+            None => "(No message content)".to_string(),
+        };
+        let forwarded = format!(
+            "---------- Forwarded message ----------\n\
+            From: {}\n\
+            Date: {}\n\
+            Subject: {}\n\
+            To: {}\n\n{}",
+            message.from_addr,
+            self.format_date(message),
+            message.subject,
+            message.to_addrs.join(", "),
+            text
+        );
+        self.place(forwarded)
+    }
+
+    /// The attribution line for a message
+    pub fn attribution_line(&self, message: &StoredMessage) -> String {
+        self.attribution
+            .replace("{date}", &self.format_date(message))
+            .replace(
+                "{name}",
+                message.from_name.as_deref().unwrap_or(&message.from_addr),
+            )
+            .replace("{email}", &message.from_addr)
+            .replace("{subject}", &message.subject)
+    }
+
+    /// The original's date in `date_format`, or the default format if that
+    /// isn't a valid format string
+    fn format_date(&self, message: &StoredMessage) -> String {
+        let mut date = String::new();
+        if write!(date, "{}", message.date.format(&self.date_format)).is_err() {
+            tracing::warn!("Invalid quote date format '{}'", self.date_format);
+            date = message.date.format("%Y-%m-%d %H:%M").to_string();
+        }
+        date
+    }
+
+    /// Mark every line of `text` as quoted
+    pub fn quote(&self, text: &str) -> String {
+        let mut lines = Vec::new();
+        for paragraph in self.paragraphs(text) {
+            let prefix = self.line_prefix(paragraph.depth, paragraph.text.is_empty());
+            let width = self.wrap_width.saturating_sub(prefix.chars().count());
+            let too_long = paragraph.text.chars().count() > width;
+            if self.reflow_flowed && self.wrap_width > 0 && (paragraph.flowed || too_long) {
+                for line in wrap(&paragraph.text, width.max(20)) {
+                    lines.push(format!("{}{}", prefix, line));
+                }
+            } else {
+                lines.push(format!("{}{}", prefix, paragraph.text));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Put the new text's place above or below the quoted block
+    fn place(&self, quoted: String) -> String {
+        match self.position {
+            QuotePosition::Top => format!("\n\n{}", quoted),
+            QuotePosition::Bottom => format!("{}\n\n", quoted),
+        }
+    }
+
+    /// Prefix for a line that was at quote depth `depth` in the original
+    fn line_prefix(&self, depth: usize, empty: bool) -> String {
+        match self.prefix {
+            QuotePrefix::Angle if empty => ">".repeat(depth + 1),
+            QuotePrefix::Angle => format!("{} ", ">".repeat(depth + 1)),
+            QuotePrefix::Indent => {
+                let indent = " ".repeat(self.indent_width);
+                if depth == 0 {
+                    indent
+                } else {
+                    format!("{}{} ", indent, ">".repeat(depth))
+                }
+            }
+        }
+    }
+
+    /// Split `text` into lines, or into flowed paragraphs when reflowing
+    fn paragraphs(&self, text: &str) -> Vec<Paragraph> {
+        let mut paragraphs: Vec<Paragraph> = Vec::new();
+        let mut open = false;
+
+        for raw in text.lines() {
+            let (depth, content) = split_quote_depth(raw);
+            // The space after existing quote markers
+            let content = if depth > 0 {
+                content.strip_prefix(' ').unwrap_or(content)
+            } else {
+                content
+            };
+            // A trailing space marks a soft break, except on the signature delimiter
+            let soft = self.reflow_flowed && content.ends_with(' ') && content != "-- ";
+
+            match paragraphs.last_mut() {
+                Some(last) if open && last.depth == depth => {
+                    last.text.push_str(content);
+                    last.flowed = true;
+                }
+                _ => paragraphs.push(Paragraph {
+                    depth,
+                    text: content.to_string(),
+                    flowed: false,
+                }),
+            }
+            open = soft;
+        }
+
+        for paragraph in &mut paragraphs {
+            if paragraph.flowed {
+                paragraph.text.truncate(paragraph.text.trim_end().len());
+            }
+        }
+        paragraphs
+    }
+}
+
+/// Quote depth of a line and the text after its markers. `>>` and `> >`
+/// both count as depth two
+fn split_quote_depth(line: &str) -> (usize, &str) {
+    let mut depth = 0;
+    let mut rest = line;
+    loop {
+        let trimmed = if depth > 0 { rest.trim_start_matches(' ') } else { rest };
+        match trimmed.strip_prefix('>') {
+            Some(after) => {
+                depth += 1;
+                rest = after;
+            }
+            None => return (depth, rest),
+        }
+    }
+}
+
+/// Greedy word wrap at `width` characters; long words get a line of their own
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn message() -> StoredMessage {
+        let date = chrono::Utc.with_ymd_and_hms(2024, 3, 1, 12, 30, 0).unwrap();
+        StoredMessage {
+            id: uuid::Uuid::new_v4(),
+            account_id: "work".to_string(),
+            folder_name: "INBOX".to_string(),
+            imap_uid: 1,
+            message_id: None,
+            thread_id: None,
+            in_reply_to: None,
+            references: Vec::new(),
+            subject: "Lunch".to_string(),
+            from_addr: "jane@example.com".to_string(),
+            from_name: Some("Jane Doe".to_string()),
+            to_addrs: Vec::new(),
+            cc_addrs: Vec::new(),
+            bcc_addrs: Vec::new(),
+            reply_to: None,
+            date,
+            body_text: None,
+            body_html: None,
+            attachments: Vec::new(),
+            flags: Vec::new(),
+            labels: Vec::new(),
+            size: None,
+            priority: None,
+            created_at: date,
+            updated_at: date,
+            last_synced: date,
+            sync_version: 1,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        }
+    }
+
+    #[test]
+    fn test_quote_styles() {
+        let message = message();
+        let style = QuoteStyle::default();
+
+        assert_eq!(
+            style.reply_body(&message, "Noon?\n\n> Lunch tomorrow?"),
+            "\n\nOn 2024-03-01 12:30, Jane Doe wrote:\n> Noon?\n>\n>> Lunch tomorrow?"
+        );
+
+        // Flowed paragraphs are joined and wrapped to fit behind the prefix
+        let flowed = "The quick brown fox jumps \nover the lazy dog.\nNext line";
+        let narrow = QuoteStyle {
+            wrap_width: 24,
+            ..QuoteStyle::default()
+        };
+        assert_eq!(
+            narrow.quote(flowed),
+            "> The quick brown fox\n> jumps over the lazy\n> dog.\n> Next line"
+        );
+        // So are long lines, such as paragraphs unflowed during sync
+        assert_eq!(
+            narrow.quote("The quick brown fox jumps over the lazy dog."),
+            "> The quick brown fox\n> jumps over the lazy\n> dog."
+        );
+        let verbatim = QuoteStyle {
+            reflow_flowed: false,
+            ..narrow
+        };
+        assert_eq!(
+            verbatim.quote(flowed),
+            "> The quick brown fox jumps \n> over the lazy dog.\n> Next line"
+        );
+
+        let bottom = QuoteStyle {
+            position: QuotePosition::Bottom,
+            prefix: QuotePrefix::Indent,
+            indent_width: 2,
+            attribution: "{email} ({date}):".to_string(),
+            date_format: "%d/%m/%Y".to_string(),
+            ..QuoteStyle::default()
+        };
+        assert_eq!(
+            bottom.reply_body(&message, "Sure\n> earlier"),
+            "jane@example.com (01/03/2024):\n  Sure\n  > earlier\n\n"
+        );
+
+        let mut config = QuoteConfig::default();
+        config.accounts.insert("work".to_string(), bottom.clone());
+        assert_eq!(config.style_for("work"), &bottom);
+        assert_eq!(config.style_for("personal"), &QuoteStyle::default());
+    }
+}
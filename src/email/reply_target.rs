@@ -0,0 +1,362 @@
+//! Reply recipients and mailing-list detection
+//!
+//! Where a reply goes depends on headers the envelope doesn't carry:
+//! `List-Post` marks list mail, `Reply-To` may point anywhere (lists often
+//! set it to themselves), and `Mail-Followup-To` says where the author wants
+//! follow-ups. Sync reads them into [`ReplyHeaders`], stored per message.
+//!
+//! A plain reply to list mail goes to the author or to the list, as set in
+//! `reply.toml`; the separate reply-to-list action always goes to the list.
+//! Every choice comes with a short reason, shown in the compose header so
+//! the recipients are never a surprise.
+
+use crate::email::security::{header_values, split_headers};
+use crate::email::sender_lists::extract_address;
+use crate::email::StoredMessage;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Target of a plain reply to mailing-list mail
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListReplyDefault {
+    /// Only the author, even when the list sets `Reply-To` to itself
+    #[default]
+    Author,
+    /// The list's posting address
+    List,
+}
+
+/// Reply configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplyConfig {
+    /// Where a plain reply to list mail goes
+    pub list_reply_default: ListReplyDefault,
+    /// Send reply-all to the `Mail-Followup-To` addresses when present
+    pub honor_mail_followup_to: bool,
+}
+
+impl Default for ReplyConfig {
+    fn default() -> Self {
+        Self {
+            list_reply_default: ListReplyDefault::Author,
+            honor_mail_followup_to: true,
+        }
+    }
+}
+
+impl ReplyConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "reply.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// Which reply the user asked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplyMode {
+    Sender,
+    All,
+    List,
+}
+
+/// Headers that decide where replies go
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplyHeaders {
+    /// Posting address from `List-Post`
+    pub list_post: Option<String>,
+    pub reply_to: Vec<String>,
+    pub mail_followup_to: Vec<String>,
+}
+
+impl ReplyHeaders {
+    pub fn from_raw_message(raw: &str) -> Self {
+        let (headers, _) = split_headers(raw);
+        let addresses = |name: &str| -> Vec<String> {
+            header_values(&headers, name)
+                .iter()
+                .flat_map(|value| parse_address_list(value))
+                .collect()
+        };
+
+        Self {
+            list_post: header_values(&headers, "list-post")
+                .iter()
+                .find_map(|value| parse_list_post(value)),
+            reply_to: addresses("reply-to"),
+            mail_followup_to: addresses("mail-followup-to"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list_post.is_none() && self.reply_to.is_empty() && self.mail_followup_to.is_empty()
+    }
+}
+
+/// Chosen recipients and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplyRecipients {
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub reason: String,
+}
+
+/// In-Reply-To and References headers that keep a reply in the thread
+pub fn reply_threading(message: &StoredMessage) -> (Option<String>, Option<String>) {
+    let references = message
+        .references
+        .iter()
+        .chain(message.message_id.iter())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (
+        message.message_id.clone(),
+        (!references.is_empty()).then_some(references),
+    )
+}
+
+/// Work out the recipients of a reply
+///
+/// `own_address` is left out of reply-all. Messages synced before the reply
+/// headers were stored fall back to the envelope's `Reply-To`.
+pub fn reply_recipients(
+    message: &StoredMessage,
+    headers: &ReplyHeaders,
+    mode: ReplyMode,
+    config: &ReplyConfig,
+    own_address: Option<&str>,
+) -> ReplyRecipients {
+    let reply_to = if headers.reply_to.is_empty() {
+        message
+            .reply_to
+            .iter()
+            .map(|address| extract_address(address).to_string())
+            .collect()
+    } else {
+        headers.reply_to.clone()
+    };
+    let list = headers.list_post.as_deref();
+    // Lists that set Reply-To to themselves would turn a private reply public
+    let munged = list.is_some_and(|list| reply_to.iter().all(|address| same(address, list)));
+
+    let (sender, sender_reason) = if !reply_to.is_empty() && !munged {
+        (reply_to, "the Reply-To address")
+    } else {
+        (
+            vec![extract_address(&message.from_addr).to_string()],
+            "the sender",
+        )
+    };
+
+    match (mode, list) {
+        (ReplyMode::List, Some(list)) => recipients(vec![list.to_string()], "the list (List-Post)"),
+        (ReplyMode::List, None) => ReplyRecipients {
+            to: sender,
+            cc: Vec::new(),
+            reason: format!("{}; this is not list mail", sender_reason),
+        },
+        (ReplyMode::Sender, Some(list)) => match config.list_reply_default {
+            ListReplyDefault::List => {
+                recipients(vec![list.to_string()], "the list, the default for list mail")
+            }
+            ListReplyDefault::Author => {
+                recipients(sender, "the author only, the default for list mail")
+            }
+        },
+        (ReplyMode::Sender, None) => recipients(sender, sender_reason),
+        (ReplyMode::All, _) => {
+            let is_own = |address: &str| own_address.is_some_and(|own| same(address, own));
+
+            if config.honor_mail_followup_to && !headers.mail_followup_to.is_empty() {
+                let to = headers
+                    .mail_followup_to
+                    .iter()
+                    .filter(|address| !is_own(address))
+                    .cloned()
+                    .collect();
+                return recipients(to, "the Mail-Followup-To addresses");
+            }
+
+            let mut cc: Vec<String> = Vec::new();
+            for address in message.to_addrs.iter().chain(&message.cc_addrs) {
+                let address = extract_address(address);
+                let seen = sender.iter().chain(&cc).any(|other| same(other, address));
+                if !seen && !is_own(address) && !address.is_empty() {
+                    cc.push(address.to_string());
+                }
+            }
+            let reason = match cc.len() {
+                0 => format!("{} (no other recipients)", sender_reason),
+                count => format!("{} and {} other recipient(s)", sender_reason, count),
+            };
+            ReplyRecipients {
+                to: sender,
+                cc,
+                reason,
+            }
+        }
+    }
+}
+
+fn recipients(to: Vec<String>, reason: &str) -> ReplyRecipients {
+    ReplyRecipients {
+        to,
+        cc: Vec::new(),
+        reason: reason.to_string(),
+    }
+}
+
+fn same(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+/// Posting address of a `List-Post` value such as `<mailto:dev@example.org>`
+///
+/// `NO` (posting not allowed) and non-mailto URLs give `None`.
+fn parse_list_post(value: &str) -> Option<String> {
+    value
+        .split(',')
+        .filter_map(|entry| entry.trim().strip_prefix('<')?.split('>').next())
+        .find_map(|url| {
+            let address = url.get(..7)?.eq_ignore_ascii_case("mailto:").then(|| &url[7..])?;
+            let address = address.split('?').next().unwrap_or_default().trim();
+            address.contains('@').then(|| address.to_string())
+        })
+}
+
+/// Addresses of a header such as `"Doe, Jane" <jane@example.com>, bob@example.com`
+pub(crate) fn parse_address_list(value: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut bracketed = false;
+
+    for c in value.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            '<' if !quoted => bracketed = true,
+            '>' if !quoted => bracketed = false,
+            ',' if !quoted && !bracketed => {
+                entries.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    entries.push(current);
+
+    entries
+        .iter()
+        .map(|entry| extract_address(entry))
+        .filter(|address| address.contains('@'))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> StoredMessage {
+        let date = chrono::Utc::now();
+        StoredMessage {
+            id: uuid::Uuid::new_v4(),
+            account_id: "work".to_string(),
+            folder_name: "INBOX".to_string(),
+            imap_uid: 1,
+            message_id: None,
+            thread_id: None,
+            in_reply_to: None,
+            references: Vec::new(),
+            subject: "Release plan".to_string(),
+            from_addr: "jane@example.com".to_string(),
+            from_name: Some("Jane Doe".to_string()),
+            to_addrs: vec!["dev@lists.example.org".to_string(), "me@example.com".to_string()],
+            cc_addrs: vec!["Bob <bob@example.com>".to_string()],
+            bcc_addrs: Vec::new(),
+            reply_to: Some("dev@lists.example.org".to_string()),
+            date,
+            body_text: None,
+            body_html: None,
+            attachments: Vec::new(),
+            flags: Vec::new(),
+            labels: Vec::new(),
+            size: None,
+            priority: None,
+            created_at: date,
+            updated_at: date,
+            last_synced: date,
+            sync_version: 1,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        }
+    }
+
+    #[test]
+    fn test_reply_recipients() {
+        let raw = "From: Jane Doe <jane@example.com>\r\n\
+            List-Post: <mailto:dev@lists.example.org?subject=help>\r\n\
+            Reply-To: dev@lists.example.org\r\n\
+            Mail-Followup-To: \"Doe, Jane\" <jane@example.com>,\r\n dev@lists.example.org\r\n\r\nbody";
+        let headers = ReplyHeaders::from_raw_message(raw);
+        assert_eq!(headers.list_post.as_deref(), Some("dev@lists.example.org"));
+        assert_eq!(headers.reply_to, vec!["dev@lists.example.org"]);
+        assert_eq!(headers.mail_followup_to, vec!["jane@example.com", "dev@lists.example.org"]);
+        assert!(ReplyHeaders::from_raw_message("List-Post: NO\n\nbody").is_empty());
+
+        let message = message();
+        let config = ReplyConfig::default();
+        let own = Some("me@example.com");
+
+        // A plain reply ignores the list's own Reply-To and goes to the author
+        let reply = reply_recipients(&message, &headers, ReplyMode::Sender, &config, own);
+        assert_eq!(reply.to, vec!["jane@example.com"]);
+        assert_eq!(reply.reason, "the author only, the default for list mail");
+
+        let list_default = ReplyConfig {
+            list_reply_default: ListReplyDefault::List,
+            ..ReplyConfig::default()
+        };
+        let reply = reply_recipients(&message, &headers, ReplyMode::Sender, &list_default, own);
+        assert_eq!(reply.to, vec!["dev@lists.example.org"]);
+
+        let reply = reply_recipients(&message, &headers, ReplyMode::List, &config, own);
+        assert_eq!(reply.to, vec!["dev@lists.example.org"]);
+        assert_eq!(reply.reason, "the list (List-Post)");
+
+        // Reply-all follows Mail-Followup-To, or else everyone but yourself
+        let reply = reply_recipients(&message, &headers, ReplyMode::All, &config, own);
+        assert_eq!(reply.to, vec!["jane@example.com", "dev@lists.example.org"]);
+        let no_followup = ReplyHeaders {
+            mail_followup_to: Vec::new(),
+            ..headers.clone()
+        };
+        let reply = reply_recipients(&message, &no_followup, ReplyMode::All, &config, own);
+        assert_eq!(reply.to, vec!["jane@example.com"]);
+        assert_eq!(reply.cc, vec!["dev@lists.example.org", "bob@example.com"]);
+        assert_eq!(reply.reason, "the sender and 2 other recipient(s)");
+
+        // Without list headers, Reply-To is honoured and reply-to-list falls back
+        let mut direct = message.clone();
+        direct.reply_to = Some("Jane <jane.private@example.net>".to_string());
+        let none = ReplyHeaders::default();
+        let reply = reply_recipients(&direct, &none, ReplyMode::Sender, &config, own);
+        assert_eq!(reply.to, vec!["jane.private@example.net"]);
+        assert_eq!(reply.reason, "the Reply-To address");
+        let reply = reply_recipients(&direct, &none, ReplyMode::List, &config, own);
+        assert_eq!(reply.reason, "the Reply-To address; this is not list mail");
+    }
+}
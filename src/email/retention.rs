@@ -0,0 +1,465 @@
+//! Folder retention policies
+//!
+//! Rules such as "empty Trash after 30 days" or "archive INBOX messages older
+//! than a year" are applied by a background maintenance pass. Nothing is
+//! removed straight away: matching messages are soft-deleted (flagged
+//! `\Deleted` on the server and hidden locally) and only expunged once a
+//! grace window has passed, so a misconfigured rule can be undone. The very
+//! first pass is a dry run whose preview has to be accepted before any rule
+//! takes effect.
+
+use crate::email::{EmailDatabase, EmailOperationResult, EmailOperationsService};
+use anyhow::Result;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// What happens to a message once a rule matches it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionAction {
+    /// Copy to the account's archive folder, then remove from the source folder
+    Archive,
+    /// Remove permanently
+    Delete,
+}
+
+impl RetentionAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RetentionAction::Archive => "archive",
+            RetentionAction::Delete => "delete",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "archive" => Some(RetentionAction::Archive),
+            "delete" => Some(RetentionAction::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// One retention rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionRule {
+    /// Account the rule applies to; all accounts when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    /// Folder name as shown by the server, e.g. `Trash` or `[Gmail]/Trash`
+    pub folder: String,
+    pub action: RetentionAction,
+    /// Messages dated more than this many days ago match
+    pub older_than_days: u32,
+}
+
+impl RetentionRule {
+    pub fn applies_to(&self, account_id: &str) -> bool {
+        self.account_id
+            .as_deref()
+            .is_none_or(|rule_account| rule_account == account_id)
+    }
+
+    /// Folder name as stored locally; INBOX is case-insensitive in IMAP
+    fn folder_name(&self) -> String {
+        if self.folder.eq_ignore_ascii_case("INBOX") {
+            "INBOX".to_string()
+        } else {
+            self.folder.clone()
+        }
+    }
+}
+
+/// Retention configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Run the maintenance pass at all
+    pub enabled: bool,
+    /// Minutes between maintenance passes
+    pub interval_minutes: u64,
+    /// Days a soft-deleted message can still be restored before it is expunged
+    pub grace_days: u32,
+    /// Upper bound on messages soft-deleted per rule and account in one pass
+    pub max_messages_per_run: u32,
+    /// Set once the first dry-run preview was accepted; until then passes only preview
+    pub preview_accepted: bool,
+    pub rules: Vec<RetentionRule>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 60,
+            grace_days: 7,
+            max_messages_per_run: 500,
+            preview_accepted: false,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "retention.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Whether a maintenance pass has anything to do
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.rules.is_empty()
+    }
+}
+
+/// A message a rule would act on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionCandidate {
+    pub message_id: Uuid,
+    pub account_id: String,
+    pub folder_name: String,
+    pub imap_uid: u32,
+    pub action: RetentionAction,
+    pub subject: String,
+    pub from_addr: String,
+    pub date: DateTime<Utc>,
+}
+
+/// Messages the rules currently match
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionPlan {
+    pub candidates: Vec<RetentionCandidate>,
+}
+
+impl RetentionPlan {
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Candidate counts per account, folder and action, sorted for display
+    pub fn summary(&self) -> Vec<(String, String, RetentionAction, usize)> {
+        let mut counts: HashMap<(String, String, RetentionAction), usize> = HashMap::new();
+        for candidate in &self.candidates {
+            *counts
+                .entry((
+                    candidate.account_id.clone(),
+                    candidate.folder_name.clone(),
+                    candidate.action,
+                ))
+                .or_default() += 1;
+        }
+
+        let mut summary: Vec<_> = counts
+            .into_iter()
+            .map(|((account_id, folder, action), count)| (account_id, folder, action, count))
+            .collect();
+        summary.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        summary
+    }
+}
+
+/// A soft-deleted message waiting for its grace window to pass
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingRetention {
+    pub message_id: Uuid,
+    pub account_id: String,
+    pub folder_name: String,
+    pub imap_uid: u32,
+    pub action: RetentionAction,
+    pub subject: String,
+    pub from_addr: String,
+    pub marked_at: DateTime<Utc>,
+}
+
+impl PendingRetention {
+    /// When the message will be expunged
+    pub fn expires_at(&self, grace_days: u32) -> DateTime<Utc> {
+        self.marked_at + ChronoDuration::days(grace_days as i64)
+    }
+
+    pub fn is_expired(&self, grace_days: u32, now: DateTime<Utc>) -> bool {
+        self.expires_at(grace_days) <= now
+    }
+}
+
+/// Result of one maintenance pass
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetentionOutcome {
+    /// Dry run: nothing was changed because the preview has not been accepted yet
+    Preview(RetentionPlan),
+    Applied {
+        soft_deleted: usize,
+        expunged: usize,
+    },
+}
+
+/// Applies retention rules through the email operations service
+pub struct RetentionService {
+    database: Arc<EmailDatabase>,
+    operations: Arc<EmailOperationsService>,
+}
+
+impl RetentionService {
+    pub fn new(database: Arc<EmailDatabase>, operations: Arc<EmailOperationsService>) -> Self {
+        Self {
+            database,
+            operations,
+        }
+    }
+
+    /// Find the messages the rules match for the given accounts
+    pub async fn plan(
+        &self,
+        config: &RetentionConfig,
+        account_ids: &[String],
+        now: DateTime<Utc>,
+    ) -> EmailOperationResult<RetentionPlan> {
+        let mut plan = RetentionPlan::default();
+
+        for account_id in account_ids {
+            for rule in config.rules.iter().filter(|rule| rule.applies_to(account_id)) {
+                let cutoff = now - ChronoDuration::days(rule.older_than_days as i64);
+                let messages = self
+                    .database
+                    .get_messages_older_than(
+                        account_id,
+                        &rule.folder_name(),
+                        cutoff,
+                        config.max_messages_per_run,
+                    )
+                    .await?;
+
+                for message in messages {
+                    // A message matched by two rules gets the first one
+                    if plan.candidates.iter().any(|c| c.message_id == message.id) {
+                        continue;
+                    }
+                    plan.candidates.push(RetentionCandidate {
+                        message_id: message.id,
+                        account_id: message.account_id,
+                        folder_name: message.folder_name,
+                        imap_uid: message.imap_uid,
+                        action: rule.action,
+                        subject: message.subject,
+                        from_addr: message.from_addr,
+                        date: message.date,
+                    });
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Run one maintenance pass
+    ///
+    /// Until the preview is accepted this only returns the plan. Afterwards it
+    /// expunges soft-deleted messages whose grace window has passed, then
+    /// soft-deletes whatever the rules match now.
+    pub async fn run(
+        &self,
+        config: &RetentionConfig,
+        account_ids: &[String],
+        now: DateTime<Utc>,
+    ) -> EmailOperationResult<RetentionOutcome> {
+        let plan = self.plan(config, account_ids, now).await?;
+        if !config.preview_accepted {
+            return Ok(RetentionOutcome::Preview(plan));
+        }
+
+        let expunged = self.expunge_expired(config.grace_days, now).await?;
+
+        let mut soft_deleted = 0;
+        for ((account_id, folder_name), candidates) in group_by_folder(plan.candidates) {
+            let uids: Vec<u32> = candidates.iter().map(|c| c.imap_uid).collect();
+            if let Err(e) = self
+                .operations
+                .soft_delete_uids(&account_id, &folder_name, &uids)
+                .await
+            {
+                warn!("Retention skipped {}/{}: {}", account_id, folder_name, e);
+                continue;
+            }
+
+            for candidate in candidates {
+                self.database
+                    .store_retention_pending(&PendingRetention {
+                        message_id: candidate.message_id,
+                        account_id: candidate.account_id,
+                        folder_name: candidate.folder_name,
+                        imap_uid: candidate.imap_uid,
+                        action: candidate.action,
+                        subject: candidate.subject,
+                        from_addr: candidate.from_addr,
+                        marked_at: now,
+                    })
+                    .await?;
+                soft_deleted += 1;
+            }
+        }
+
+        if soft_deleted > 0 || expunged > 0 {
+            info!(
+                "Retention pass soft-deleted {} and expunged {} messages",
+                soft_deleted, expunged
+            );
+        }
+        Ok(RetentionOutcome::Applied {
+            soft_deleted,
+            expunged,
+        })
+    }
+
+    /// Soft-deleted messages that can still be restored
+    pub async fn pending(&self) -> EmailOperationResult<Vec<PendingRetention>> {
+        Ok(self.database.get_retention_pending().await?)
+    }
+
+    /// Bring back a soft-deleted message before it is expunged
+    pub async fn restore(&self, message_id: Uuid) -> EmailOperationResult<()> {
+        let pending = self.database.get_retention_pending().await?;
+        let Some(entry) = pending.into_iter().find(|p| p.message_id == message_id) else {
+            return Err(crate::email::EmailOperationError::InvalidState {
+                reason: "Message is no longer pending removal".to_string(),
+            });
+        };
+
+        self.operations
+            .restore_soft_deleted(&entry.account_id, &entry.folder_name, &[entry.imap_uid])
+            .await?;
+        self.database.delete_retention_pending(message_id).await?;
+        Ok(())
+    }
+
+    /// Expunge pending messages whose grace window has passed
+    async fn expunge_expired(
+        &self,
+        grace_days: u32,
+        now: DateTime<Utc>,
+    ) -> EmailOperationResult<usize> {
+        let pending = self.database.get_retention_pending().await?;
+        let (expired, waiting): (Vec<_>, Vec<_>) = pending
+            .into_iter()
+            .partition(|p| p.is_expired(grace_days, now));
+
+        let mut expunged = 0;
+        let mut groups: HashMap<(String, String, RetentionAction), Vec<PendingRetention>> =
+            HashMap::new();
+        for entry in expired {
+            groups
+                .entry((entry.account_id.clone(), entry.folder_name.clone(), entry.action))
+                .or_default()
+                .push(entry);
+        }
+
+        for ((account_id, folder_name, action), entries) in groups {
+            let uids: Vec<u32> = entries.iter().map(|p| p.imap_uid).collect();
+            let keep: Vec<u32> = waiting
+                .iter()
+                .filter(|p| p.account_id == account_id && p.folder_name == folder_name)
+                .map(|p| p.imap_uid)
+                .collect();
+
+            if let Err(e) = self
+                .operations
+                .expunge_soft_deleted(
+                    &account_id,
+                    &folder_name,
+                    &uids,
+                    action == RetentionAction::Archive,
+                    &keep,
+                )
+                .await
+            {
+                warn!("Retention could not expunge {}/{}: {}", account_id, folder_name, e);
+                continue;
+            }
+
+            for entry in entries {
+                self.database.delete_retention_pending(entry.message_id).await?;
+                expunged += 1;
+            }
+        }
+
+        Ok(expunged)
+    }
+}
+
+/// Group candidates by account and folder so each folder is selected once
+fn group_by_folder(
+    candidates: Vec<RetentionCandidate>,
+) -> Vec<((String, String), Vec<RetentionCandidate>)> {
+    let mut groups: Vec<((String, String), Vec<RetentionCandidate>)> = Vec::new();
+    for candidate in candidates {
+        let key = (candidate.account_id.clone(), candidate.folder_name.clone());
+        match groups.iter_mut().find(|(group, _)| *group == key) {
+            Some((_, group)) => group.push(candidate),
+            None => groups.push((key, vec![candidate])),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention_config_and_grace_window() {
+        let config: RetentionConfig = toml::from_str(
+            r#"
+            grace_days = 3
+
+            [[rules]]
+            folder = "inbox"
+            action = "archive"
+            older_than_days = 365
+
+            [[rules]]
+            account_id = "work"
+            folder = "Trash"
+            action = "delete"
+            older_than_days = 30
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.is_active());
+        assert!(!config.preview_accepted);
+        assert_eq!(config.rules[0].folder_name(), "INBOX");
+        assert!(config.rules[0].applies_to("personal"));
+        assert!(config.rules[1].applies_to("work"));
+        assert!(!config.rules[1].applies_to("personal"));
+
+        let marked_at = Utc::now();
+        let pending = PendingRetention {
+            message_id: Uuid::new_v4(),
+            account_id: "work".to_string(),
+            folder_name: "Trash".to_string(),
+            imap_uid: 7,
+            action: RetentionAction::Delete,
+            subject: "Old".to_string(),
+            from_addr: "alice@example.com".to_string(),
+            marked_at,
+        };
+        assert!(!pending.is_expired(config.grace_days, marked_at + ChronoDuration::days(2)));
+        assert!(pending.is_expired(config.grace_days, marked_at + ChronoDuration::days(3)));
+        assert_eq!(RetentionAction::parse(RetentionAction::Archive.as_str()), Some(RetentionAction::Archive));
+    }
+}
@@ -0,0 +1,492 @@
+//! Per-message security status
+//!
+//! Reads the receiving server's `Authentication-Results` header (DKIM, SPF
+//! and DMARC verdicts) and looks for PGP or S/MIME signatures and encryption
+//! in the raw message. The result is computed once during sync and shown as
+//! a badge in the message list and a panel in the viewer.
+
+use crate::email::crypto::{SignatureCheck, SignatureStatus};
+use serde::{Deserialize, Serialize};
+
+/// Verdict for one authentication method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthVerdict {
+    Pass,
+    Fail,
+    SoftFail,
+    Neutral,
+    None,
+    TempError,
+    PermError,
+    Policy,
+}
+
+impl AuthVerdict {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "pass" => Some(AuthVerdict::Pass),
+            "fail" | "hardfail" => Some(AuthVerdict::Fail),
+            "softfail" => Some(AuthVerdict::SoftFail),
+            "neutral" => Some(AuthVerdict::Neutral),
+            "none" => Some(AuthVerdict::None),
+            "temperror" => Some(AuthVerdict::TempError),
+            "permerror" => Some(AuthVerdict::PermError),
+            "policy" => Some(AuthVerdict::Policy),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthVerdict::Pass => "pass",
+            AuthVerdict::Fail => "fail",
+            AuthVerdict::SoftFail => "softfail",
+            AuthVerdict::Neutral => "neutral",
+            AuthVerdict::None => "none",
+            AuthVerdict::TempError => "temperror",
+            AuthVerdict::PermError => "permerror",
+            AuthVerdict::Policy => "policy",
+        }
+    }
+}
+
+/// Cryptographic scheme used for a signature or encryption
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CryptoScheme {
+    Pgp,
+    Smime,
+}
+
+impl CryptoScheme {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CryptoScheme::Pgp => "PGP",
+            CryptoScheme::Smime => "S/MIME",
+        }
+    }
+}
+
+/// Summary badge shown next to a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityBadge {
+    Encrypted,
+    Signed,
+    /// DKIM or DMARC passed
+    Verified,
+    /// Authentication failed, or the sender's domain can be spoofed and
+    /// nothing vouched for this message
+    Suspicious,
+}
+
+impl SecurityBadge {
+    pub fn icon(&self) -> char {
+        match self {
+            SecurityBadge::Encrypted => '🔒',
+            SecurityBadge::Signed => '🔏',
+            SecurityBadge::Verified => '🛡',
+            SecurityBadge::Suspicious => '⚠',
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SecurityBadge::Encrypted => "Encrypted",
+            SecurityBadge::Signed => "Signed",
+            SecurityBadge::Verified => "Verified sender",
+            SecurityBadge::Suspicious => "Unauthenticated sender",
+        }
+    }
+}
+
+/// Security status of one message
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageSecurity {
+    /// Server that produced the authentication results
+    pub authserv_id: Option<String>,
+    pub dkim: Option<AuthVerdict>,
+    pub spf: Option<AuthVerdict>,
+    pub dmarc: Option<AuthVerdict>,
+    /// DMARC policy of the From domain (`none`, `quarantine` or `reject`)
+    pub dmarc_policy: Option<String>,
+    /// Domain the DMARC check was made for, falling back to the From domain
+    pub from_domain: Option<String>,
+    pub signed: Option<CryptoScheme>,
+    pub encrypted: Option<CryptoScheme>,
+    /// Result of checking the signature, when it could be checked
+    #[serde(default)]
+    pub signature: Option<SignatureCheck>,
+}
+
+impl MessageSecurity {
+    /// Inspect a raw RFC 5322 message (headers and body)
+    pub fn from_raw_message(raw: &str, from_addr: &str) -> Self {
+        let (headers, body) = split_headers(raw);
+        let mut security = Self {
+            from_domain: from_addr
+                .rsplit_once('@')
+                .map(|(_, domain)| domain.trim_end_matches('>').to_lowercase())
+                .filter(|domain| !domain.is_empty()),
+            ..Self::default()
+        };
+
+        // The topmost Authentication-Results header was added by our own
+        // server; ones further down could have been forged by the sender
+        if let Some(value) = header_values(&headers, "authentication-results").into_iter().next() {
+            security.apply_authentication_results(&value);
+        }
+
+        let content_type = header_values(&headers, "content-type")
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        security.detect_crypto(&content_type, body);
+
+        security
+    }
+
+    fn apply_authentication_results(&mut self, value: &str) {
+        let results = parse_authentication_results(value);
+        self.authserv_id = results.authserv_id;
+
+        for result in results.methods {
+            // Keep the best verdict when a method appears more than once,
+            // e.g. several DKIM signatures
+            let slot = match result.method.as_str() {
+                "dkim" => &mut self.dkim,
+                "spf" => &mut self.spf,
+                "dmarc" => {
+                    if let Some(policy) = result.policy.clone() {
+                        self.dmarc_policy = Some(policy);
+                    }
+                    if let Some(domain) = result.property("header.from") {
+                        self.from_domain = Some(domain.to_lowercase());
+                    }
+                    &mut self.dmarc
+                }
+                _ => continue,
+            };
+            if *slot != Some(AuthVerdict::Pass) {
+                *slot = Some(result.verdict);
+            }
+        }
+    }
+
+    fn detect_crypto(&mut self, content_type: &str, body: &str) {
+        if content_type.starts_with("multipart/encrypted") {
+            self.encrypted = Some(CryptoScheme::Pgp);
+        } else if content_type.contains("pkcs7-mime") {
+            if content_type.contains("signed-data") {
+                self.signed = Some(CryptoScheme::Smime);
+            } else {
+                self.encrypted = Some(CryptoScheme::Smime);
+            }
+        } else if content_type.starts_with("multipart/signed") {
+            self.signed = if content_type.contains("pkcs7-signature") {
+                Some(CryptoScheme::Smime)
+            } else {
+                Some(CryptoScheme::Pgp)
+            };
+        }
+
+        if self.encrypted.is_none() && body.contains("-----BEGIN PGP MESSAGE-----") {
+            self.encrypted = Some(CryptoScheme::Pgp);
+        }
+        if self.signed.is_none() && body.contains("-----BEGIN PGP SIGNED MESSAGE-----") {
+            self.signed = Some(CryptoScheme::Pgp);
+        }
+    }
+
+    /// Whether any authentication results were found
+    pub fn has_authentication_results(&self) -> bool {
+        self.dkim.is_some() || self.spf.is_some() || self.dmarc.is_some()
+    }
+
+    /// Whether the sender is vouched for by DKIM, SPF or DMARC
+    pub fn is_authenticated(&self) -> bool {
+        self.dmarc == Some(AuthVerdict::Pass)
+            || self.dkim == Some(AuthVerdict::Pass)
+            || self.spf == Some(AuthVerdict::Pass)
+    }
+
+    /// Whether others can send mail as the From domain without being rejected
+    ///
+    /// True when the domain publishes no DMARC policy or only `p=none`.
+    pub fn is_spoofable_domain(&self) -> bool {
+        match self.dmarc {
+            None | Some(AuthVerdict::None) => true,
+            _ => self
+                .dmarc_policy
+                .as_deref()
+                .is_some_and(|policy| policy.eq_ignore_ascii_case("none")),
+        }
+    }
+
+    /// Badge to show, if any
+    ///
+    /// Encryption and signatures take precedence. Messages without
+    /// authentication results (local drafts, sent mail) get no badge.
+    pub fn badge(&self) -> Option<SecurityBadge> {
+        if self.encrypted.is_some() {
+            return Some(SecurityBadge::Encrypted);
+        }
+        if self.is_suspicious() {
+            return Some(SecurityBadge::Suspicious);
+        }
+        if self.signed.is_some() {
+            return Some(SecurityBadge::Signed);
+        }
+        if self.dmarc == Some(AuthVerdict::Pass) || self.dkim == Some(AuthVerdict::Pass) {
+            return Some(SecurityBadge::Verified);
+        }
+        None
+    }
+
+    /// Whether the message carries a signature that doesn't match its content
+    pub fn has_invalid_signature(&self) -> bool {
+        self.signature
+            .as_ref()
+            .is_some_and(|check| check.status == SignatureStatus::Invalid)
+    }
+
+    /// Failed DMARC, unauthenticated mail from a spoofable domain, or a
+    /// signature that doesn't match
+    pub fn is_suspicious(&self) -> bool {
+        if self.has_invalid_signature() {
+            return true;
+        }
+        if !self.has_authentication_results() {
+            return false;
+        }
+        self.dmarc == Some(AuthVerdict::Fail)
+            || (!self.is_authenticated() && self.is_spoofable_domain())
+    }
+}
+
+/// One `method=result` entry of an Authentication-Results header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthMethodResult {
+    pub method: String,
+    pub verdict: AuthVerdict,
+    /// `p=` policy from a DMARC comment, e.g. `(p=REJECT sp=NONE)`
+    pub policy: Option<String>,
+    /// `ptype.property=value` pairs such as `header.d=example.com`
+    pub properties: Vec<(String, String)>,
+}
+
+impl AuthMethodResult {
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parsed Authentication-Results header (RFC 8601)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuthenticationResults {
+    pub authserv_id: Option<String>,
+    pub methods: Vec<AuthMethodResult>,
+}
+
+/// Parse an unfolded Authentication-Results header value
+///
+/// Tolerates comments (including nested ones), quoted strings, method
+/// versions (`dkim/1=pass`), reason clauses and unknown methods.
+pub fn parse_authentication_results(value: &str) -> AuthenticationResults {
+    let mut results = AuthenticationResults::default();
+
+    for (index, (text, comments)) in split_resinfo(value).into_iter().enumerate() {
+        let text = text.trim();
+        if index == 0 {
+            // authserv-id, optionally followed by a version number
+            results.authserv_id = text
+                .split_whitespace()
+                .next()
+                .map(str::to_string)
+                .filter(|id| !id.is_empty());
+            continue;
+        }
+
+        let mut tokens = text.split_whitespace();
+        let Some((method, verdict)) = tokens.next().and_then(|token| token.split_once('=')) else {
+            continue;
+        };
+        let method = method.split('/').next().unwrap_or(method).to_lowercase();
+        let Some(verdict) = AuthVerdict::parse(verdict) else {
+            continue;
+        };
+
+        let properties = tokens
+            .filter_map(|token| token.split_once('='))
+            .filter(|(key, _)| key.contains('.'))
+            .map(|(key, value)| (key.to_lowercase(), value.trim_matches('"').to_string()))
+            .collect();
+
+        let policy = comments.iter().find_map(|comment| {
+            comment
+                .split_whitespace()
+                .find_map(|part| part.strip_prefix("p=").or_else(|| part.strip_prefix("P=")))
+                .map(|policy| policy.trim_end_matches([',', ';']).to_lowercase())
+        });
+
+        results.methods.push(AuthMethodResult {
+            method,
+            verdict,
+            policy,
+            properties,
+        });
+    }
+
+    results
+}
+
+/// Split a header value on `;` outside comments and quotes
+///
+/// Returns each part's text with comments removed, plus the comments.
+fn split_resinfo(value: &str) -> Vec<(String, Vec<String>)> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut comments = Vec::new();
+    let mut comment = String::new();
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_quotes || depth > 0 => {
+                if let Some(escaped) = chars.next() {
+                    if depth > 0 {
+                        comment.push(escaped);
+                    } else {
+                        text.push(escaped);
+                    }
+                }
+            }
+            '"' if depth == 0 => {
+                in_quotes = !in_quotes;
+                text.push(c);
+            }
+            '(' if !in_quotes => {
+                if depth > 0 {
+                    comment.push(c);
+                }
+                depth += 1;
+            }
+            ')' if !in_quotes && depth > 0 => {
+                depth -= 1;
+                if depth == 0 {
+                    comments.push(std::mem::take(&mut comment));
+                    // Keep tokens on either side of the comment apart
+                    text.push(' ');
+                } else {
+                    comment.push(c);
+                }
+            }
+            ';' if !in_quotes && depth == 0 => {
+                parts.push((std::mem::take(&mut text), std::mem::take(&mut comments)));
+            }
+            _ if depth > 0 => comment.push(c),
+            _ => text.push(c),
+        }
+    }
+    parts.push((text, comments));
+
+    parts
+}
+
+/// Split a raw message into its unfolded header lines and body
+pub(crate) fn split_headers(raw: &str) -> (Vec<String>, &str) {
+    let (header_block, body) = raw
+        .split_once("\r\n\r\n")
+        .or_else(|| raw.split_once("\n\n"))
+        .unwrap_or((raw, ""));
+
+    let mut headers: Vec<String> = Vec::new();
+    for line in header_block.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some(last) = headers.last_mut() {
+                last.push(' ');
+                last.push_str(line.trim());
+                continue;
+            }
+        }
+        headers.push(line.trim_end().to_string());
+    }
+
+    (headers, body)
+}
+
+/// Values of every header with the given (lowercase) name, in message order
+pub(crate) fn header_values(headers: &[String], name: &str) -> Vec<String> {
+    headers
+        .iter()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_authentication_results() {
+        let value = "mx.google.com; dkim=pass header.i=@example.com header.s=s1 \
+                     header.b=\"abc;def\"; spf=softfail (google.com: domain of \
+                     transitioning bounce@example.com does not designate 1.2.3.4 as \
+                     permitted sender) smtp.mailfrom=bounce@example.com; \
+                     dmarc=pass (p=REJECT sp=NONE dis=NONE) header.from=Example.com; \
+                     x-unknown=maybe; dkim/1=fail reason=\"bad signature\"";
+
+        let results = parse_authentication_results(value);
+        assert_eq!(results.authserv_id.as_deref(), Some("mx.google.com"));
+        assert_eq!(results.methods.len(), 4);
+        assert_eq!(results.methods[0].property("header.b"), Some("abc;def"));
+        assert_eq!(results.methods[1].verdict, AuthVerdict::SoftFail);
+        assert_eq!(
+            results.methods[1].property("smtp.mailfrom"),
+            Some("bounce@example.com")
+        );
+        assert_eq!(results.methods[2].policy.as_deref(), Some("reject"));
+        assert_eq!(results.methods[3].method, "dkim");
+        assert_eq!(results.methods[3].verdict, AuthVerdict::Fail);
+    }
+
+    #[test]
+    fn test_message_security_badges() {
+        let verified = "Authentication-Results: mx.example.net;\r\n\
+                        \tdkim=pass header.d=shop.com;\r\n\
+                        \tdmarc=pass (p=QUARANTINE) header.from=shop.com\r\n\
+                        Authentication-Results: forged.example; dmarc=fail\r\n\
+                        Content-Type: text/plain\r\n\r\nHello";
+        let security = MessageSecurity::from_raw_message(verified, "orders@shop.com");
+        assert_eq!(security.dkim, Some(AuthVerdict::Pass));
+        assert_eq!(security.dmarc_policy.as_deref(), Some("quarantine"));
+        assert_eq!(security.badge(), Some(SecurityBadge::Verified));
+
+        let spoofed = "Authentication-Results: mx.example.net; spf=fail \
+                       smtp.mailfrom=bank.com; dkim=none; dmarc=none\n\
+                       Subject: Urgent\n\nPay now";
+        let security = MessageSecurity::from_raw_message(spoofed, "Bank <alerts@bank.com>");
+        assert_eq!(security.from_domain.as_deref(), Some("bank.com"));
+        assert!(security.is_spoofable_domain());
+        assert_eq!(security.badge(), Some(SecurityBadge::Suspicious));
+
+        let signed = "Content-Type: multipart/signed; protocol=\"application/pkcs7-signature\"\n\n";
+        let security = MessageSecurity::from_raw_message(signed, "me@example.com");
+        assert_eq!(security.signed, Some(CryptoScheme::Smime));
+        assert_eq!(security.badge(), Some(SecurityBadge::Signed));
+
+        let encrypted = "Content-Type: multipart/encrypted;\n protocol=\"application/pgp-encrypted\"\n\n";
+        let security = MessageSecurity::from_raw_message(encrypted, "me@example.com");
+        assert_eq!(security.badge(), Some(SecurityBadge::Encrypted));
+
+        let local = "Subject: Draft\n\nNo auth headers here";
+        assert_eq!(MessageSecurity::from_raw_message(local, "me@example.com").badge(), None);
+    }
+}
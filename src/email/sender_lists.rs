@@ -0,0 +1,220 @@
+//! Sender blocklist and allowlist
+//!
+//! Messages from blocked senders are deleted or moved to the spam folder as
+//! soon as they arrive; allowed senders are never treated as spam and always
+//! notify. Entries are addresses or glob patterns such as `*@spammer.com`,
+//! matched case-insensitively. When an address is on both lists the allowlist
+//! wins.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// What happens to messages from blocked senders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockAction {
+    /// Move to the account's spam/junk folder
+    #[default]
+    MoveToSpam,
+    /// Delete the message
+    Delete,
+}
+
+/// How a sender is classified by the lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SenderVerdict {
+    Allowed,
+    Blocked,
+    Neutral,
+}
+
+/// Sender blocklist and allowlist configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SenderLists {
+    /// Blocked addresses or patterns
+    pub blocked: Vec<String>,
+    /// Allowed addresses or patterns
+    pub allowed: Vec<String>,
+    /// Action applied to messages from blocked senders
+    pub block_action: BlockAction,
+    /// Folder used when the server doesn't advertise a junk folder
+    pub spam_folder: String,
+}
+
+impl Default for SenderLists {
+    fn default() -> Self {
+        Self {
+            blocked: Vec::new(),
+            allowed: Vec::new(),
+            block_action: BlockAction::MoveToSpam,
+            spam_folder: "Spam".to_string(),
+        }
+    }
+}
+
+impl SenderLists {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "sender_lists.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Classify a sender; accepts a bare address or a `Name <address>` string
+    pub fn classify(&self, sender: &str) -> SenderVerdict {
+        if self.is_allowed(sender) {
+            SenderVerdict::Allowed
+        } else if self.is_blocked(sender) {
+            SenderVerdict::Blocked
+        } else {
+            SenderVerdict::Neutral
+        }
+    }
+
+    /// Whether the sender matches an allowlist entry
+    pub fn is_allowed(&self, sender: &str) -> bool {
+        self.allowed.iter().any(|pattern| sender_matches(pattern, sender))
+    }
+
+    /// Whether the sender matches a blocklist entry
+    pub fn is_blocked(&self, sender: &str) -> bool {
+        self.blocked.iter().any(|pattern| sender_matches(pattern, sender))
+    }
+
+    /// Add an entry to the blocklist, removing it from the allowlist
+    ///
+    /// Returns `false` if the entry was already blocked.
+    pub fn block(&mut self, pattern: &str) -> bool {
+        let pattern = normalize_entry(pattern);
+        self.allowed.retain(|entry| !entry.eq_ignore_ascii_case(&pattern));
+        if self.blocked.iter().any(|entry| entry.eq_ignore_ascii_case(&pattern)) {
+            return false;
+        }
+        self.blocked.push(pattern);
+        true
+    }
+
+    /// Add an entry to the allowlist, removing it from the blocklist
+    ///
+    /// Returns `false` if the entry was already allowed.
+    pub fn allow(&mut self, pattern: &str) -> bool {
+        let pattern = normalize_entry(pattern);
+        self.blocked.retain(|entry| !entry.eq_ignore_ascii_case(&pattern));
+        if self.allowed.iter().any(|entry| entry.eq_ignore_ascii_case(&pattern)) {
+            return false;
+        }
+        self.allowed.push(pattern);
+        true
+    }
+
+    /// Remove an entry from both lists
+    pub fn remove(&mut self, pattern: &str) {
+        let pattern = normalize_entry(pattern);
+        self.blocked.retain(|entry| !entry.eq_ignore_ascii_case(&pattern));
+        self.allowed.retain(|entry| !entry.eq_ignore_ascii_case(&pattern));
+    }
+}
+
+/// Extract the bare address from a `Name <address>` string
+pub fn extract_address(sender: &str) -> &str {
+    match (sender.rfind('<'), sender.rfind('>')) {
+        (Some(start), Some(end)) if start < end => sender[start + 1..end].trim(),
+        _ => sender.trim(),
+    }
+}
+
+fn normalize_entry(pattern: &str) -> String {
+    extract_address(pattern).to_lowercase()
+}
+
+/// Match a sender against a list entry
+///
+/// Entries may contain `*` (any run of characters) and `?` (one character).
+/// An entry starting with `@` matches the whole domain, so `@example.com` is
+/// the same as `*@example.com`.
+pub fn sender_matches(pattern: &str, sender: &str) -> bool {
+    let pattern = pattern.trim().to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+    let pattern = if pattern.starts_with('@') {
+        format!("*{}", pattern)
+    } else {
+        pattern
+    };
+    let address = extract_address(sender).to_lowercase();
+
+    glob_match(pattern.as_bytes(), address.as_bytes())
+}
+
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` swallow one more character and retry
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sender_matching() {
+        assert!(sender_matches("*@spammer.com", "Deals <offers@Spammer.com>"));
+        assert!(sender_matches("@spammer.com", "offers@spammer.com"));
+        assert!(sender_matches("news*@example.org", "newsletter@example.org"));
+        assert!(sender_matches("alice@example.org", "ALICE@example.org"));
+        assert!(!sender_matches("*@spammer.com", "offers@notspammer.com.evil"));
+        assert!(!sender_matches("*@spammer.com", "friend@example.com"));
+        assert!(!sender_matches("", "friend@example.com"));
+    }
+
+    #[test]
+    fn test_allowlist_wins() {
+        let mut lists = SenderLists::default();
+        lists.block("*@example.com");
+        lists.allow("boss@example.com");
+
+        assert_eq!(lists.classify("Boss <boss@example.com>"), SenderVerdict::Allowed);
+        assert_eq!(lists.classify("ads@example.com"), SenderVerdict::Blocked);
+        assert_eq!(lists.classify("friend@other.org"), SenderVerdict::Neutral);
+    }
+
+    #[test]
+    fn test_block_moves_entry_between_lists() {
+        let mut lists = SenderLists::default();
+        assert!(lists.allow("Someone <someone@example.com>"));
+        assert!(lists.block("someone@example.com"));
+        assert!(!lists.block("SOMEONE@example.com"));
+
+        assert!(lists.allowed.is_empty());
+        assert_eq!(lists.blocked, vec!["someone@example.com".to_string()]);
+
+        lists.remove("someone@example.com");
+        assert!(lists.blocked.is_empty());
+    }
+}
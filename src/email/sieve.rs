@@ -0,0 +1,1164 @@
+//! Sieve (RFC 5228) import and export of filter rules
+//!
+//! Covers the part of Sieve that maps onto `EmailFilter`: `header`,
+//! `address`, `exists` and `size` tests combined with `allof`, `anyof` and
+//! `not`, and the `fileinto`, `keep`, `discard`, `redirect`, `addflag`,
+//! `removeflag` and `stop` actions. An `anyof` test or a list of headers or
+//! keys becomes one rule per alternative, since rules match when all their
+//! conditions do. Anything else is reported rather than guessed at.
+//!
+//! Exported rules are preceded by a `# rule: <name>` comment, which import
+//! reads back as the rule's name.
+
+use crate::email::filters::{
+    EmailFilter, FilterAction, FilterCondition, FilterField, FilterOperator,
+};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// Most rules a single Sieve `if` is expanded into
+const MAX_ALTERNATIVES: usize = 32;
+
+/// Sieve script errors
+#[derive(Error, Debug)]
+pub enum SieveError {
+    #[error("line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+pub type SieveResult<T> = Result<T, SieveError>;
+
+/// A script made from filter rules
+#[derive(Debug, Clone, Default)]
+pub struct SieveExport {
+    pub script: String,
+    /// Rules or parts of rules with no Sieve equivalent
+    pub warnings: Vec<String>,
+}
+
+/// Filter rules read from a script
+#[derive(Debug, Clone, Default)]
+pub struct SieveImport {
+    pub rules: Vec<EmailFilter>,
+    /// Tests, actions or commands that were not converted
+    pub warnings: Vec<String>,
+}
+
+/// Write the enabled rules as a Sieve script, in priority order
+pub fn to_sieve(rules: &[EmailFilter]) -> SieveExport {
+    let mut rules: Vec<&EmailFilter> = rules.iter().filter(|rule| rule.enabled).collect();
+    rules.sort_by_key(|rule| rule.priority);
+
+    let mut extensions = BTreeSet::new();
+    let mut export = SieveExport::default();
+    let mut body = String::new();
+    for rule in rules {
+        match export_rule(rule, &mut extensions, &mut export.warnings) {
+            Ok(text) => body.push_str(&text),
+            Err(reason) => export
+                .warnings
+                .push(format!("{}: left out, {}", rule.name, reason)),
+        }
+    }
+
+    if !extensions.is_empty() {
+        let names: Vec<String> = extensions.iter().map(|name| quote(name)).collect();
+        export
+            .script
+            .push_str(&format!("require [{}];\n\n", names.join(", ")));
+    }
+    export.script.push_str(&body);
+    export
+}
+
+fn export_rule(
+    rule: &EmailFilter,
+    extensions: &mut BTreeSet<&'static str>,
+    warnings: &mut Vec<String>,
+) -> Result<String, String> {
+    if rule.conditions.is_empty() {
+        return Err("it has no conditions".to_string());
+    }
+    let tests = rule
+        .conditions
+        .iter()
+        .map(|condition| export_condition(condition, extensions))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Flags first so messages are filed with them
+    let mut flags = Vec::new();
+    let mut delivery = Vec::new();
+    let mut keep = false;
+    let mut stop = false;
+    for action in &rule.actions {
+        match action {
+            FilterAction::MarkAsRead => flags.push(format!("addflag {};", quote("\\Seen"))),
+            FilterAction::MarkAsUnread => flags.push(format!("removeflag {};", quote("\\Seen"))),
+            FilterAction::MarkAsImportant => flags.push(format!("addflag {};", quote("\\Flagged"))),
+            FilterAction::SetFlag(flag) | FilterAction::AddLabel(flag) => {
+                flags.push(format!("addflag {};", quote(flag)))
+            }
+            FilterAction::RemoveFlag(flag) | FilterAction::RemoveLabel(flag) => {
+                flags.push(format!("removeflag {};", quote(flag)))
+            }
+            FilterAction::MoveToFolder(folder) => {
+                extensions.insert("fileinto");
+                delivery.push(format!("fileinto {};", quote(folder)));
+            }
+            FilterAction::CopyToFolder(folder) => {
+                extensions.insert("fileinto");
+                delivery.push(format!("fileinto {};", quote(folder)));
+                keep = true;
+            }
+            FilterAction::Forward(address) => {
+                delivery.push(format!("redirect {};", quote(address)))
+            }
+            FilterAction::Delete => delivery.push("discard;".to_string()),
+            FilterAction::StopProcessing => stop = true,
+            action => warnings.push(format!(
+                "{}: {:?} has no Sieve equivalent, left out",
+                rule.name, action
+            )),
+        }
+    }
+    if !flags.is_empty() {
+        extensions.insert("imap4flags");
+    }
+    if keep {
+        delivery.push("keep;".to_string());
+    }
+    if stop {
+        delivery.push("stop;".to_string());
+    }
+    if flags.is_empty() && delivery.is_empty() {
+        return Err("none of its actions can be expressed in Sieve".to_string());
+    }
+
+    let test = match tests.as_slice() {
+        [test] => test.clone(),
+        tests => format!("allof({})", tests.join(", ")),
+    };
+    let mut text = format!("# rule: {}\nif {} {{\n", rule.name, test);
+    for line in flags.iter().chain(delivery.iter()) {
+        text.push_str("    ");
+        text.push_str(line);
+        text.push('\n');
+    }
+    text.push_str("}\n\n");
+    Ok(text)
+}
+
+fn export_condition(
+    condition: &FilterCondition,
+    extensions: &mut BTreeSet<&'static str>,
+) -> Result<String, String> {
+    let (test, header) = match condition.field {
+        FilterField::From => ("address", "from".to_string()),
+        FilterField::To => ("address", "to".to_string()),
+        FilterField::CC => ("address", "cc".to_string()),
+        FilterField::BCC => ("address", "bcc".to_string()),
+        FilterField::ReplyTo => ("address", "reply-to".to_string()),
+        FilterField::Sender => ("address", "sender".to_string()),
+        FilterField::Subject => ("header", "subject".to_string()),
+        FilterField::MessageId => ("header", "message-id".to_string()),
+        FilterField::InReplyTo => ("header", "in-reply-to".to_string()),
+        FilterField::Priority => ("header", "x-priority".to_string()),
+        FilterField::Header(ref name) => ("header", name.clone()),
+        FilterField::Size => return export_size(condition),
+        ref field => return Err(format!("{:?} conditions have no Sieve test", field)),
+    };
+
+    let comparator = if condition.case_sensitive {
+        format!(" :comparator {}", quote("i;octet"))
+    } else {
+        String::new()
+    };
+    let escaped = escape_wildcards(&condition.value);
+    let (negate, match_type, key) = match condition.operator {
+        FilterOperator::Contains => (false, ":contains", condition.value.clone()),
+        FilterOperator::NotContains => (true, ":contains", condition.value.clone()),
+        FilterOperator::Equals => (false, ":is", condition.value.clone()),
+        FilterOperator::NotEquals => (true, ":is", condition.value.clone()),
+        FilterOperator::StartsWith => (false, ":matches", format!("{}*", escaped)),
+        FilterOperator::EndsWith => (false, ":matches", format!("*{}", escaped)),
+        FilterOperator::Regex => {
+            extensions.insert("regex");
+            (false, ":regex", condition.value.clone())
+        }
+        FilterOperator::IsEmpty => return Ok(format!("not exists {}", quote(&header))),
+        FilterOperator::IsNotEmpty => return Ok(format!("exists {}", quote(&header))),
+        ref operator => return Err(format!("{:?} has no Sieve match type", operator)),
+    };
+
+    Ok(format!(
+        "{}{} {}{} {} {}",
+        if negate { "not " } else { "" },
+        test,
+        match_type,
+        comparator,
+        quote(&header),
+        quote(&key)
+    ))
+}
+
+fn export_size(condition: &FilterCondition) -> Result<String, String> {
+    let size: u64 = condition
+        .value
+        .trim()
+        .parse()
+        .map_err(|_| format!("size {:?} is not a number", condition.value))?;
+    match condition.operator {
+        FilterOperator::GreaterThan => Ok(format!("size :over {}", size)),
+        FilterOperator::LessThan => Ok(format!("size :under {}", size)),
+        ref operator => Err(format!("{:?} can't be used on sizes", operator)),
+    }
+}
+
+/// A Sieve quoted string
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Escape `:matches` wildcards so `value` is matched literally
+fn escape_wildcards(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('*', "\\*")
+        .replace('?', "\\?")
+}
+
+/// Read filter rules from a Sieve script
+///
+/// Fails only on syntax errors; tests and actions that can't be converted
+/// are listed in the warnings.
+pub fn from_sieve(script: &str) -> SieveResult<SieveImport> {
+    let tokens = tokenize(script)?;
+    let commands = Parser {
+        tokens: &tokens,
+        position: 0,
+    }
+    .commands(false)?;
+
+    let mut import = SieveImport::default();
+    let mut previous_if = false;
+    for command in commands {
+        match command.name.as_str() {
+            "require" => {}
+            "if" => {
+                let index = import.rules.len();
+                let name = command
+                    .rule_name
+                    .clone()
+                    .unwrap_or_else(|| format!("Sieve rule {}", index + 1));
+                import_if(&command, &name, &mut import);
+                previous_if = true;
+                continue;
+            }
+            "elsif" | "else" if previous_if => import.warnings.push(format!(
+                "line {}: `{}` branches are not supported, left out",
+                command.line, command.name
+            )),
+            name => import.warnings.push(format!(
+                "line {}: `{}` outside of an `if` is not supported, left out",
+                command.line, name
+            )),
+        }
+        previous_if = matches!(command.name.as_str(), "elsif");
+    }
+
+    for (index, rule) in import.rules.iter_mut().enumerate() {
+        rule.priority = (index as i32 + 1) * 10;
+    }
+    Ok(import)
+}
+
+fn import_if(command: &Command, name: &str, import: &mut SieveImport) {
+    let alternatives = match command.tests.as_slice() {
+        [test] => alternatives(test),
+        _ => Err("`if` needs exactly one test".to_string()),
+    };
+    let alternatives = match alternatives {
+        Ok(alternatives) => alternatives,
+        Err(reason) => {
+            import.warnings.push(format!(
+                "line {}: {} left out, {}",
+                command.line, name, reason
+            ));
+            return;
+        }
+    };
+
+    let mut actions = Vec::new();
+    let mut keep = false;
+    for action in command.block.iter().flatten() {
+        match import_action(action, &mut actions) {
+            Ok(kept) => keep |= kept,
+            Err(reason) => import.warnings.push(format!(
+                "line {}: {}: {}, left out",
+                action.line, name, reason
+            )),
+        }
+    }
+    // Filing a message that is also kept leaves a copy in the inbox
+    if keep {
+        for action in actions.iter_mut() {
+            if let FilterAction::MoveToFolder(folder) = action {
+                *action = FilterAction::CopyToFolder(std::mem::take(folder));
+            }
+        }
+    }
+    if actions.is_empty() {
+        import.warnings.push(format!(
+            "line {}: {} left out, it has no supported actions",
+            command.line, name
+        ));
+        return;
+    }
+
+    let count = alternatives.len();
+    for (index, conditions) in alternatives.into_iter().enumerate() {
+        let name = if count > 1 {
+            format!("{} ({}/{})", name, index + 1, count)
+        } else {
+            name.to_string()
+        };
+        let mut rule = EmailFilter::new(name, "Imported from Sieve".to_string());
+        rule.conditions = conditions;
+        rule.actions = actions.clone();
+        import.rules.push(rule);
+    }
+}
+
+/// Add a block command's actions, returning whether it was `keep`
+fn import_action(command: &Command, actions: &mut Vec<FilterAction>) -> Result<bool, String> {
+    match command.name.as_str() {
+        "keep" => return Ok(true),
+        "fileinto" => {
+            let folder = command.single_string()?;
+            actions.push(if command.has_tag("copy") {
+                FilterAction::CopyToFolder(folder)
+            } else {
+                FilterAction::MoveToFolder(folder)
+            });
+        }
+        "redirect" => actions.push(FilterAction::Forward(command.single_string()?)),
+        "discard" => actions.push(FilterAction::Delete),
+        "stop" => actions.push(FilterAction::StopProcessing),
+        "addflag" | "setflag" => {
+            for flag in command.last_strings()? {
+                actions.push(match flag.to_lowercase().as_str() {
+                    "\\seen" => FilterAction::MarkAsRead,
+                    "\\flagged" => FilterAction::MarkAsImportant,
+                    _ => FilterAction::SetFlag(flag),
+                });
+            }
+        }
+        "removeflag" => {
+            for flag in command.last_strings()? {
+                actions.push(if flag.eq_ignore_ascii_case("\\seen") {
+                    FilterAction::MarkAsUnread
+                } else {
+                    FilterAction::RemoveFlag(flag)
+                });
+            }
+        }
+        name => return Err(format!("`{}` is not supported", name)),
+    }
+    Ok(false)
+}
+
+/// A test as alternatives, each a set of conditions that must all match
+fn alternatives(test: &Test) -> Result<Vec<Vec<FilterCondition>>, String> {
+    let result = match test.name.as_str() {
+        "allof" => {
+            let mut product = vec![Vec::new()];
+            for child in &test.tests {
+                let child = alternatives(child)?;
+                product = product
+                    .iter()
+                    .flat_map(|conditions| {
+                        child.iter().map(move |more| {
+                            conditions.iter().chain(more).cloned().collect::<Vec<_>>()
+                        })
+                    })
+                    .collect();
+                if product.len() > MAX_ALTERNATIVES {
+                    break;
+                }
+            }
+            product
+        }
+        "anyof" => {
+            let mut all = Vec::new();
+            for child in &test.tests {
+                all.extend(alternatives(child)?);
+            }
+            all
+        }
+        "not" => match test.tests.as_slice() {
+            [child] => match alternatives(child)?.as_slice() {
+                [conditions] if conditions.len() == 1 => vec![vec![negate(&conditions[0])?]],
+                _ => return Err("`not` is only supported on a single test".to_string()),
+            },
+            _ => return Err("`not` needs one test".to_string()),
+        },
+        "header" | "address" => compare_test(test)?,
+        "exists" => vec![test
+            .strings(0)?
+            .iter()
+            .map(|header| {
+                FilterCondition::new(field_for(header), FilterOperator::IsNotEmpty, String::new())
+            })
+            .collect()],
+        "size" => {
+            let size = test
+                .number()
+                .ok_or_else(|| "`size` needs a number".to_string())?
+                .to_string();
+            let operator = if test.has_tag("over") {
+                FilterOperator::GreaterThan
+            } else if test.has_tag("under") {
+                FilterOperator::LessThan
+            } else {
+                return Err("`size` needs :over or :under".to_string());
+            };
+            vec![vec![FilterCondition::new(
+                FilterField::Size,
+                operator,
+                size,
+            )]]
+        }
+        name => return Err(format!("`{}` tests are not supported", name)),
+    };
+
+    if result.len() > MAX_ALTERNATIVES {
+        return Err(format!(
+            "it expands to more than {} rules",
+            MAX_ALTERNATIVES
+        ));
+    }
+    Ok(result)
+}
+
+/// A `header` or `address` test: one alternative per header and key
+fn compare_test(test: &Test) -> Result<Vec<Vec<FilterCondition>>, String> {
+    let case_sensitive = match test.tag_value("comparator") {
+        Some(comparator) if comparator.eq_ignore_ascii_case("i;octet") => true,
+        Some(comparator) if comparator.eq_ignore_ascii_case("i;ascii-casemap") => false,
+        Some(comparator) => return Err(format!("comparator {:?} is not supported", comparator)),
+        None => false,
+    };
+    let domain = test.has_tag("domain");
+    if test.has_tag("localpart") || test.has_tag("user") || test.has_tag("detail") {
+        return Err("only whole addresses and domains are supported".to_string());
+    }
+
+    let headers = test.strings(0)?;
+    let keys = test.strings(1)?;
+    let mut result = Vec::new();
+    for header in &headers {
+        for key in &keys {
+            let (operator, value) = if test.has_tag("contains") {
+                (FilterOperator::Contains, key.clone())
+            } else if test.has_tag("regex") {
+                (FilterOperator::Regex, key.clone())
+            } else if test.has_tag("matches") {
+                wildcard_operator(key)?
+            } else {
+                (FilterOperator::Equals, key.clone())
+            };
+            // A domain is the part after the address' @
+            let (operator, value) = match operator {
+                _ if !domain => (operator, value),
+                FilterOperator::Equals => (FilterOperator::EndsWith, format!("@{}", value)),
+                FilterOperator::EndsWith => (FilterOperator::EndsWith, value),
+                FilterOperator::Contains => (FilterOperator::Contains, value),
+                _ => return Err("this :domain match is not supported".to_string()),
+            };
+            result.push(vec![FilterCondition::new(
+                field_for(header),
+                operator,
+                value,
+            )
+            .case_sensitive(case_sensitive)]);
+        }
+    }
+    Ok(result)
+}
+
+/// Operator for a `:matches` pattern with a leading or trailing `*`
+fn wildcard_operator(pattern: &str) -> Result<(FilterOperator, String), String> {
+    let mut literal = String::new();
+    let mut leading = false;
+    let mut trailing = false;
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    literal.push(escaped);
+                }
+            }
+            '*' if literal.is_empty() && !leading => leading = true,
+            '*' if chars.peek().is_none() => trailing = true,
+            '*' | '?' => return Err(format!("pattern {:?} is not supported", pattern)),
+            c => literal.push(c),
+        }
+    }
+
+    let operator = match (leading, trailing) {
+        (true, true) => FilterOperator::Contains,
+        (true, false) => FilterOperator::EndsWith,
+        (false, true) => FilterOperator::StartsWith,
+        (false, false) => FilterOperator::Equals,
+    };
+    Ok((operator, literal))
+}
+
+fn negate(condition: &FilterCondition) -> Result<FilterCondition, String> {
+    let operator = match condition.operator {
+        FilterOperator::Contains => FilterOperator::NotContains,
+        FilterOperator::NotContains => FilterOperator::Contains,
+        FilterOperator::Equals => FilterOperator::NotEquals,
+        FilterOperator::NotEquals => FilterOperator::Equals,
+        FilterOperator::IsEmpty => FilterOperator::IsNotEmpty,
+        FilterOperator::IsNotEmpty => FilterOperator::IsEmpty,
+        ref operator => return Err(format!("`not` can't be applied to {:?}", operator)),
+    };
+    Ok(FilterCondition {
+        operator,
+        ..condition.clone()
+    })
+}
+
+fn field_for(header: &str) -> FilterField {
+    match header.to_lowercase().as_str() {
+        "from" => FilterField::From,
+        "to" => FilterField::To,
+        "cc" => FilterField::CC,
+        "bcc" => FilterField::BCC,
+        "reply-to" => FilterField::ReplyTo,
+        "sender" => FilterField::Sender,
+        "subject" => FilterField::Subject,
+        "message-id" => FilterField::MessageId,
+        "in-reply-to" => FilterField::InReplyTo,
+        "x-priority" => FilterField::Priority,
+        _ => FilterField::Header(header.to_string()),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Tag(String),
+    String(String),
+    Number(u64),
+    Comment(String),
+    Symbol(char),
+}
+
+fn tokenize(script: &str) -> SieveResult<Vec<(Token, usize)>> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut chars = script.chars().peekable();
+    let error = |line: usize, message: &str| SieveError::Parse {
+        line,
+        message: message.to_string(),
+    };
+
+    while let Some(&c) = chars.peek() {
+        let start = line;
+        match c {
+            '\n' => {
+                line += 1;
+                chars.next();
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '#' => {
+                let mut comment = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    comment.push(c);
+                    chars.next();
+                }
+                tokens.push((Token::Comment(comment[1..].trim().to_string()), start));
+            }
+            '/' => {
+                chars.next();
+                if chars.next() != Some('*') {
+                    return Err(error(line, "unexpected `/`"));
+                }
+                let mut last = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            last = c;
+                        }
+                        None => return Err(error(start, "unterminated comment")),
+                    }
+                }
+            }
+            '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => value.push(escaped),
+                            None => return Err(error(start, "unterminated string")),
+                        },
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            value.push(c);
+                        }
+                        None => return Err(error(start, "unterminated string")),
+                    }
+                }
+                tokens.push((Token::String(value.replace("\r\n", "\n")), start));
+            }
+            ':' => {
+                chars.next();
+                let mut tag = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    tag.push(c.to_ascii_lowercase());
+                    chars.next();
+                }
+                if tag.is_empty() {
+                    return Err(error(line, "empty tag"));
+                }
+                tokens.push((Token::Tag(tag), start));
+            }
+            c if c.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    digits.push(c);
+                    chars.next();
+                }
+                let multiplier = match chars.peek().map(|c| c.to_ascii_uppercase()) {
+                    Some('K') => 1 << 10,
+                    Some('M') => 1 << 20,
+                    Some('G') => 1 << 30,
+                    _ => 1,
+                };
+                if multiplier > 1 {
+                    chars.next();
+                }
+                let number: u64 = digits
+                    .parse()
+                    .map_err(|_| error(line, "number out of range"))?;
+                tokens.push((Token::Number(number * multiplier), start));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut identifier = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    identifier.push(c.to_ascii_lowercase());
+                    chars.next();
+                }
+                if identifier == "text" && chars.peek() == Some(&':') {
+                    chars.next();
+                    let (text, lines) = multiline(&mut chars)
+                        .ok_or_else(|| error(start, "unterminated text: block"))?;
+                    line += lines;
+                    tokens.push((Token::String(text), start));
+                } else {
+                    tokens.push((Token::Identifier(identifier), start));
+                }
+            }
+            '[' | ']' | '(' | ')' | '{' | '}' | ',' | ';' => {
+                chars.next();
+                tokens.push((Token::Symbol(c), start));
+            }
+            c => return Err(error(line, &format!("unexpected `{}`", c))),
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Body of a `text:` string up to the line holding a single dot, and the
+/// number of lines it spans
+fn multiline(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<(String, usize)> {
+    // The rest of the `text:` line is ignored
+    for c in chars.by_ref() {
+        if c == '\n' {
+            break;
+        }
+    }
+
+    let mut lines = 1;
+    let mut text = String::new();
+    loop {
+        let mut current = String::new();
+        let mut ended = false;
+        for c in chars.by_ref() {
+            if c == '\n' {
+                ended = true;
+                break;
+            }
+            current.push(c);
+        }
+        if !ended && current.is_empty() {
+            return None;
+        }
+        lines += 1;
+        let current = current.trim_end_matches('\r');
+        if current == "." {
+            return Some((text, lines - 1));
+        }
+        // Dot-stuffed lines lose their first dot
+        text.push_str(current.strip_prefix('.').unwrap_or(current));
+        text.push('\n');
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Argument {
+    Tag(String),
+    Strings(Vec<String>),
+    Number(u64),
+}
+
+#[derive(Debug, Clone)]
+struct Test {
+    name: String,
+    arguments: Vec<Argument>,
+    tests: Vec<Test>,
+}
+
+#[derive(Debug, Clone)]
+struct Command {
+    name: String,
+    arguments: Vec<Argument>,
+    tests: Vec<Test>,
+    block: Option<Vec<Command>>,
+    /// Name from a `# rule:` comment just above
+    rule_name: Option<String>,
+    line: usize,
+}
+
+trait Arguments {
+    fn arguments(&self) -> &[Argument];
+
+    fn has_tag(&self, name: &str) -> bool {
+        self.arguments()
+            .iter()
+            .any(|argument| matches!(argument, Argument::Tag(tag) if tag == name))
+    }
+
+    /// String given after a tag, such as `:comparator "i;octet"`
+    fn tag_value(&self, name: &str) -> Option<&str> {
+        let arguments = self.arguments();
+        let position = arguments
+            .iter()
+            .position(|argument| matches!(argument, Argument::Tag(tag) if tag == name))?;
+        match arguments.get(position + 1) {
+            Some(Argument::Strings(strings)) => strings.first().map(String::as_str),
+            _ => None,
+        }
+    }
+
+    /// The `index`th string or string list that isn't a tag's value
+    fn strings(&self, index: usize) -> Result<Vec<String>, String> {
+        let arguments = self.arguments();
+        arguments
+            .iter()
+            .enumerate()
+            .filter(|(position, argument)| {
+                matches!(argument, Argument::Strings(_))
+                    && !matches!(
+                        position.checked_sub(1).map(|previous| &arguments[previous]),
+                        Some(Argument::Tag(tag)) if tag == "comparator"
+                    )
+            })
+            .filter_map(|(_, argument)| match argument {
+                Argument::Strings(strings) => Some(strings.clone()),
+                _ => None,
+            })
+            .nth(index)
+            .ok_or_else(|| "a string argument is missing".to_string())
+    }
+
+    fn number(&self) -> Option<u64> {
+        self.arguments().iter().find_map(|argument| match argument {
+            Argument::Number(number) => Some(*number),
+            _ => None,
+        })
+    }
+}
+
+impl Arguments for Test {
+    fn arguments(&self) -> &[Argument] {
+        &self.arguments
+    }
+}
+
+impl Arguments for Command {
+    fn arguments(&self) -> &[Argument] {
+        &self.arguments
+    }
+}
+
+impl Command {
+    fn single_string(&self) -> Result<String, String> {
+        match self.strings(0)?.as_slice() {
+            [value] => Ok(value.clone()),
+            _ => Err(format!("`{}` needs a single string", self.name)),
+        }
+    }
+
+    /// The flag list, which follows an optional variable name
+    fn last_strings(&self) -> Result<Vec<String>, String> {
+        match self.strings(1) {
+            Ok(strings) => Ok(strings),
+            Err(_) => self.strings(0),
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn error(&self, message: impl Into<String>) -> SieveError {
+        SieveError::Parse {
+            line: self.line(),
+            message: message.into(),
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .map(|(token, _)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, symbol: char) -> SieveResult<()> {
+        match self.next() {
+            Some(Token::Symbol(c)) if c == symbol => Ok(()),
+            _ => {
+                self.position -= 1;
+                Err(self.error(format!("expected `{}`", symbol)))
+            }
+        }
+    }
+
+    /// Commands up to the end of the script, or of the block when `nested`
+    fn commands(&mut self, nested: bool) -> SieveResult<Vec<Command>> {
+        let mut commands = Vec::new();
+        let mut rule_name = None;
+        loop {
+            match self.peek() {
+                None if nested => return Err(self.error("expected `}`")),
+                None => return Ok(commands),
+                Some(Token::Symbol('}')) if nested => {
+                    self.position += 1;
+                    return Ok(commands);
+                }
+                Some(Token::Comment(comment)) => {
+                    rule_name = comment
+                        .strip_prefix("rule:")
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty());
+                    self.position += 1;
+                }
+                Some(Token::Identifier(_)) => {
+                    let mut command = self.command()?;
+                    command.rule_name = rule_name.take();
+                    commands.push(command);
+                }
+                Some(_) => return Err(self.error("expected a command")),
+            }
+        }
+    }
+
+    fn command(&mut self) -> SieveResult<Command> {
+        let line = self.line();
+        let Some(Token::Identifier(name)) = self.next() else {
+            return Err(self.error("expected a command"));
+        };
+        let (arguments, tests) = self.arguments()?;
+
+        let block = match self.peek() {
+            Some(Token::Symbol('{')) => {
+                self.position += 1;
+                Some(self.commands(true)?)
+            }
+            _ => {
+                self.expect(';')?;
+                None
+            }
+        };
+
+        Ok(Command {
+            name,
+            arguments,
+            tests,
+            block,
+            rule_name: None,
+            line,
+        })
+    }
+
+    fn arguments(&mut self) -> SieveResult<(Vec<Argument>, Vec<Test>)> {
+        let mut arguments = Vec::new();
+        loop {
+            match self.peek() {
+                Some(Token::Tag(tag)) => {
+                    arguments.push(Argument::Tag(tag.clone()));
+                    self.position += 1;
+                }
+                Some(Token::String(value)) => {
+                    arguments.push(Argument::Strings(vec![value.clone()]));
+                    self.position += 1;
+                }
+                Some(Token::Number(number)) => {
+                    arguments.push(Argument::Number(*number));
+                    self.position += 1;
+                }
+                Some(Token::Symbol('[')) => {
+                    self.position += 1;
+                    arguments.push(Argument::Strings(self.string_list()?));
+                }
+                Some(Token::Identifier(_)) => return Ok((arguments, vec![self.test()?])),
+                Some(Token::Symbol('(')) => {
+                    self.position += 1;
+                    let mut tests = vec![self.test()?];
+                    while let Some(Token::Symbol(',')) = self.peek() {
+                        self.position += 1;
+                        tests.push(self.test()?);
+                    }
+                    self.expect(')')?;
+                    return Ok((arguments, tests));
+                }
+                _ => return Ok((arguments, Vec::new())),
+            }
+        }
+    }
+
+    fn string_list(&mut self) -> SieveResult<Vec<String>> {
+        let mut strings = Vec::new();
+        loop {
+            match self.next() {
+                Some(Token::String(value)) => strings.push(value),
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error("expected a string"));
+                }
+            }
+            match self.next() {
+                Some(Token::Symbol(',')) => {}
+                Some(Token::Symbol(']')) => return Ok(strings),
+                _ => {
+                    self.position -= 1;
+                    return Err(self.error("expected `,` or `]`"));
+                }
+            }
+        }
+    }
+
+    fn test(&mut self) -> SieveResult<Test> {
+        let Some(Token::Identifier(name)) = self.next() else {
+            self.position -= 1;
+            return Err(self.error("expected a test"));
+        };
+        let (arguments, tests) = self.arguments()?;
+        Ok(Test {
+            name,
+            arguments,
+            tests,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        name: &str,
+        conditions: Vec<FilterCondition>,
+        actions: Vec<FilterAction>,
+    ) -> EmailFilter {
+        let mut rule = EmailFilter::new(name.to_string(), String::new());
+        rule.conditions = conditions;
+        rule.actions = actions;
+        rule
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let rules = vec![
+            rule(
+                "Lists",
+                vec![
+                    FilterCondition::new(
+                        FilterField::Header("List-Id".to_string()),
+                        FilterOperator::Contains,
+                        "dev.example.com".to_string(),
+                    ),
+                    FilterCondition::new(
+                        FilterField::Subject,
+                        FilterOperator::StartsWith,
+                        "[dev*]".to_string(),
+                    )
+                    .case_sensitive(true),
+                ],
+                vec![
+                    FilterAction::MarkAsRead,
+                    FilterAction::MoveToFolder("Lists/dev".to_string()),
+                    FilterAction::StopProcessing,
+                ],
+            ),
+            rule(
+                "Big \"attachments\"",
+                vec![FilterCondition::new(
+                    FilterField::Size,
+                    FilterOperator::GreaterThan,
+                    "1048576".to_string(),
+                )],
+                vec![
+                    FilterAction::CopyToFolder("Large".to_string()),
+                    FilterAction::SetFlag("$big".to_string()),
+                    FilterAction::Notify,
+                ],
+            ),
+            rule(
+                "Spam",
+                vec![
+                    FilterCondition::new(
+                        FilterField::From,
+                        FilterOperator::NotEquals,
+                        "a@b.c".to_string(),
+                    ),
+                    FilterCondition::new(
+                        FilterField::Header("X-Spam-Flag".to_string()),
+                        FilterOperator::IsNotEmpty,
+                        String::new(),
+                    ),
+                ],
+                vec![FilterAction::Delete],
+            ),
+        ];
+
+        let export = to_sieve(&rules);
+        assert!(export
+            .script
+            .starts_with("require [\"fileinto\", \"imap4flags\"];"));
+        assert!(export
+            .script
+            .contains("header :matches :comparator \"i;octet\" \"subject\" \"[dev\\\\*]*\""));
+        assert_eq!(export.warnings.len(), 1, "{:?}", export.warnings);
+
+        let import = from_sieve(&export.script).unwrap();
+        assert!(import.warnings.is_empty(), "{:?}", import.warnings);
+        assert_eq!(import.rules.len(), 3);
+        for (imported, original) in import.rules.iter().zip(&rules) {
+            assert_eq!(imported.name, original.name);
+            assert_eq!(
+                format!("{:?}", imported.conditions),
+                format!("{:?}", original.conditions)
+            );
+        }
+        assert_eq!(
+            format!("{:?}", import.rules[0].actions),
+            format!("{:?}", rules[0].actions)
+        );
+        assert!(matches!(
+            import.rules[1].actions.as_slice(),
+            [FilterAction::SetFlag(flag), FilterAction::CopyToFolder(folder)]
+                if flag == "$big" && folder == "Large"
+        ));
+        assert!(import.rules[0].priority < import.rules[1].priority);
+    }
+
+    #[test]
+    fn test_import_server_script() {
+        let script = r#"require ["fileinto", "imap4flags", "vacation"];
+/* Sorted by the server */
+if anyof (address :domain :is "from" "shop.example",
+          header :contains ["subject", "x-category"] "receipt") {
+    fileinto :copy "Receipts";
+    addflag "\\Seen";
+} elsif header :is "x-spam" "yes" {
+    discard;
+}
+if body :contains "unsubscribe" { fileinto "Bulk"; }
+if size :over 5M { keep; }
+vacation :days 7 text:
+Away until Monday.
+..signed
+.
+;
+"#;
+
+        let import = from_sieve(script).unwrap();
+        assert_eq!(import.rules.len(), 3);
+        assert_eq!(import.rules[0].name, "Sieve rule 1 (1/3)");
+        let conditions: Vec<(FilterField, String)> = import
+            .rules
+            .iter()
+            .map(|rule| {
+                (
+                    rule.conditions[0].field.clone(),
+                    rule.conditions[0].value.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            conditions,
+            [
+                (FilterField::From, "@shop.example".to_string()),
+                (FilterField::Subject, "receipt".to_string()),
+                (
+                    FilterField::Header("x-category".to_string()),
+                    "receipt".to_string()
+                ),
+            ]
+        );
+        assert!(matches!(
+            import.rules[0].actions.as_slice(),
+            [FilterAction::CopyToFolder(folder), FilterAction::MarkAsRead] if folder == "Receipts"
+        ));
+        // elsif, body test, keep-only rule and vacation are reported
+        assert_eq!(import.warnings.len(), 4, "{:?}", import.warnings);
+
+        let error = from_sieve("if header :is \"a\" \"b\" { stop;").unwrap_err();
+        assert!(error.to_string().starts_with("line 1"));
+    }
+}
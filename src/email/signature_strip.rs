@@ -0,0 +1,209 @@
+//! Signature and disclaimer stripping
+//!
+//! Finds where the sender's signature or legal disclaimer starts in a plain
+//! text body so it can be left out of reply quotes and AI input. The stored
+//! message is never changed; the viewer always shows the original.
+//!
+//! A signature starts at the `-- ` delimiter line or at a configured marker
+//! such as "Sent from my iPhone". A disclaimer starts at a line matching one
+//! of the configured patterns. Everything from the earliest of those lines
+//! onwards is dropped, unless it would leave nothing of the body.
+
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Signature stripping configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SignatureStripConfig {
+    /// Leave signatures and disclaimers out of the quoted text in replies
+    pub strip_reply_quotes: bool,
+    /// Leave signatures and disclaimers out of text sent to the AI provider
+    pub strip_ai_input: bool,
+    /// Line prefixes that start a signature, matched case-insensitively
+    pub signature_markers: Vec<String>,
+    /// Regular expressions for the first line of a disclaimer, matched
+    /// case-insensitively against each line
+    pub disclaimer_patterns: Vec<String>,
+}
+
+impl Default for SignatureStripConfig {
+    fn default() -> Self {
+        Self {
+            strip_reply_quotes: true,
+            strip_ai_input: true,
+            signature_markers: vec![
+                "Sent from my ".to_string(),
+                "Get Outlook for ".to_string(),
+                "Sent from Mail for Windows".to_string(),
+            ],
+            disclaimer_patterns: vec![
+                r"^(confidentiality notice|legal disclaimer|disclaimer)\b".to_string(),
+                r"^this (e-?mail|message)\b.*\b(confidential|privileged|intended (solely|only))\b"
+                    .to_string(),
+                r"^if you (have )?received this (e-?mail|message|communication) in error\b"
+                    .to_string(),
+            ],
+        }
+    }
+}
+
+impl SignatureStripConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "signature_strip.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// Compiled form of [`SignatureStripConfig`]
+#[derive(Debug, Clone)]
+pub struct SignatureStripper {
+    strip_reply_quotes: bool,
+    strip_ai_input: bool,
+    markers: Vec<String>,
+    disclaimers: Vec<Regex>,
+}
+
+impl Default for SignatureStripper {
+    fn default() -> Self {
+        Self::new(&SignatureStripConfig::default())
+    }
+}
+
+impl SignatureStripper {
+    /// Compile the configured patterns. Invalid patterns are logged and skipped
+    pub fn new(config: &SignatureStripConfig) -> Self {
+        let disclaimers = config
+            .disclaimer_patterns
+            .iter()
+            .filter_map(|pattern| {
+                match RegexBuilder::new(pattern).case_insensitive(true).build() {
+                    Ok(regex) => Some(regex),
+                    Err(e) => {
+                        tracing::warn!("Ignoring invalid disclaimer pattern '{}': {}", pattern, e);
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self {
+            strip_reply_quotes: config.strip_reply_quotes,
+            strip_ai_input: config.strip_ai_input,
+            markers: config
+                .signature_markers
+                .iter()
+                .map(|marker| marker.to_lowercase())
+                .collect(),
+            disclaimers,
+        }
+    }
+
+    /// Byte offset where the signature or disclaimer starts, if there is one
+    pub fn find_cut(&self, text: &str) -> Option<usize> {
+        let mut offset = 0;
+        let mut seen_content = false;
+
+        for line in text.split_inclusive('\n') {
+            let trimmed = line.trim_end_matches(['\n', '\r']);
+            let content = trimmed.trim();
+
+            // Quoted history isn't the sender's own signature
+            if seen_content && !content.starts_with('>') && self.starts_signature(trimmed) {
+                return Some(offset);
+            }
+            if !content.is_empty() {
+                seen_content = true;
+            }
+            offset += line.len();
+        }
+
+        None
+    }
+
+    /// The body without its signature and disclaimer
+    pub fn strip<'a>(&self, text: &'a str) -> &'a str {
+        match self.find_cut(text) {
+            Some(cut) => text[..cut].trim_end(),
+            None => text,
+        }
+    }
+
+    /// Text to quote in a reply
+    pub fn for_reply<'a>(&self, text: &'a str) -> &'a str {
+        if self.strip_reply_quotes {
+            self.strip(text)
+        } else {
+            text
+        }
+    }
+
+    /// Text to send to the AI provider
+    pub fn for_ai<'a>(&self, text: &'a str) -> &'a str {
+        if self.strip_ai_input {
+            self.strip(text)
+        } else {
+            text
+        }
+    }
+
+    fn starts_signature(&self, line: &str) -> bool {
+        // RFC 3676 delimiter; some clients drop the trailing space
+        if line == "-- " || line == "--" {
+            return true;
+        }
+
+        let content = line.trim();
+        let lower = content.to_lowercase();
+        self.markers.iter().any(|marker| lower.starts_with(marker.as_str()))
+            || self.disclaimers.iter().any(|regex| regex.is_match(content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_signature_and_disclaimer() {
+        let stripper = SignatureStripper::default();
+
+        let body = "Hi,\n\nSee you at 3.\n\n-- \nJane Doe\nACME Corp\n";
+        assert_eq!(stripper.strip(body), "Hi,\n\nSee you at 3.");
+
+        let body = "Works for me.\n\nSent from my iPhone";
+        assert_eq!(stripper.strip(body), "Works for me.");
+
+        let body = "Invoice attached.\n\nCONFIDENTIALITY NOTICE: This e-mail is private.\nMore legalese.";
+        assert_eq!(stripper.strip(body), "Invoice attached.");
+
+        let body = "Thanks!\n\nThis message and any attachments are confidential and intended solely for the addressee.";
+        assert_eq!(stripper.strip(body), "Thanks!");
+
+        // A banner at the very top doesn't swallow the whole body
+        let body = "Disclaimer: external sender\nPlease review the draft.";
+        assert_eq!(stripper.strip(body), body);
+
+        // Horizontal rules and quoted signatures are left alone
+        let body = "Notes\n----\nItem one\n> -- \n> Old sig";
+        assert_eq!(stripper.find_cut(body), None);
+
+        let config = SignatureStripConfig {
+            strip_ai_input: false,
+            ..SignatureStripConfig::default()
+        };
+        let stripper = SignatureStripper::new(&config);
+        let body = "Yes.\n-- \nBob";
+        assert_eq!(stripper.for_reply(body), "Yes.");
+        assert_eq!(stripper.for_ai(body), body);
+    }
+}
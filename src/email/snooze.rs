@@ -0,0 +1,74 @@
+//! Snoozing messages until a chosen time
+//!
+//! Snoozing only touches the local copy: the message gets a `snoozed_until`
+//! time, drops out of its folder and is listed under the account's Snoozed
+//! folder instead. Once that time has passed, a background task clears the
+//! snooze, marks the message unread and moves it back to the INBOX if it was
+//! snoozed from elsewhere. Due snoozes are checked at startup as well, so a
+//! message whose time came while the app was closed returns on next launch.
+
+use crate::email::{EmailDatabase, EmailOperationResult, EmailOperationsService, StoredMessage};
+use chrono::{DateTime, Utc};
+use tracing::{info, warn};
+
+/// Bring back every message of an account whose snooze time has passed
+///
+/// Returns the number of messages that came back.
+pub async fn wake_due_snoozes(
+    database: &EmailDatabase,
+    operations: &EmailOperationsService,
+    account_id: &str,
+    now: DateTime<Utc>,
+) -> EmailOperationResult<usize> {
+    let due: Vec<StoredMessage> = database
+        .get_snoozed_messages(account_id)
+        .await?
+        .into_iter()
+        .filter(|message| message.snoozed_until.is_some_and(|until| until <= now))
+        .collect();
+
+    for message in &due {
+        wake_message(database, operations, message).await?;
+    }
+    if !due.is_empty() {
+        info!("{} snoozed messages back in {}", due.len(), account_id);
+    }
+    Ok(due.len())
+}
+
+/// Return one snoozed message to the INBOX as unread, whatever its time
+///
+/// The snooze is cleared first, so a server that can't be reached leaves
+/// the message visible and unread locally rather than hidden.
+pub async fn wake_message(
+    database: &EmailDatabase,
+    operations: &EmailOperationsService,
+    message: &StoredMessage,
+) -> EmailOperationResult<()> {
+    database.set_snoozed_until(message.id, None).await?;
+
+    if let Err(e) = operations
+        .set_read_status_by_ids(&message.account_id, &[message.id], &message.folder_name, false)
+        .await
+    {
+        warn!("Could not mark snoozed message {} unread on the server: {}", message.id, e);
+        let flags: Vec<String> = message
+            .flags
+            .iter()
+            .filter(|flag| *flag != "\\Seen")
+            .cloned()
+            .collect();
+        database.update_message_flags(message.id, &flags).await?;
+        return Ok(());
+    }
+
+    if !message.folder_name.eq_ignore_ascii_case("INBOX") {
+        if let Err(e) = operations
+            .move_email(&message.account_id, message.imap_uid, &message.folder_name, "INBOX")
+            .await
+        {
+            warn!("Could not move snoozed message {} back to INBOX: {}", message.id, e);
+        }
+    }
+    Ok(())
+}
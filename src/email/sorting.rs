@@ -1,15 +1,16 @@
 use crate::email::{EmailMessage, EmailThread};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
 /// Sort order for email and thread sorting
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SortOrder {
     Ascending,
     Descending,
 }
 
 /// Criteria for sorting emails and threads
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SortCriteria {
     Date(SortOrder),
     Sender(SortOrder),
@@ -132,6 +133,36 @@ impl SortCriteria {
         }
     }
 
+    /// The same field sorted the other way round
+    pub fn reversed(&self) -> SortCriteria {
+        let flip = |order: &SortOrder| match order {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        };
+        match self {
+            SortCriteria::Date(order) => SortCriteria::Date(flip(order)),
+            SortCriteria::Sender(order) => SortCriteria::Sender(flip(order)),
+            SortCriteria::Subject(order) => SortCriteria::Subject(flip(order)),
+            SortCriteria::Size(order) => SortCriteria::Size(flip(order)),
+            SortCriteria::Priority(order) => SortCriteria::Priority(flip(order)),
+            SortCriteria::ThreadDepth(order) => SortCriteria::ThreadDepth(flip(order)),
+            SortCriteria::MessageCount(order) => SortCriteria::MessageCount(flip(order)),
+            SortCriteria::HasUnread(order) => SortCriteria::HasUnread(flip(order)),
+        }
+    }
+
+    /// The next field of the message list, in its usual order: date, sender,
+    /// subject, size, priority and round to date again
+    pub fn next_field(&self) -> SortCriteria {
+        match self {
+            SortCriteria::Date(_) => SortCriteria::Sender(SortOrder::Ascending),
+            SortCriteria::Sender(_) => SortCriteria::Subject(SortOrder::Ascending),
+            SortCriteria::Subject(_) => SortCriteria::Size(SortOrder::Descending),
+            SortCriteria::Size(_) => SortCriteria::Priority(SortOrder::Descending),
+            _ => SortCriteria::Date(SortOrder::Descending),
+        }
+    }
+
     /// Get a human-readable description of this sort criteria
     pub fn description(&self) -> String {
         let (name, order) = match self {
@@ -209,6 +240,11 @@ impl MultiCriteriaSorter {
         self.criteria.clear();
     }
 
+    /// The criteria applied first, if any
+    pub fn primary(&self) -> Option<&SortCriteria> {
+        self.criteria.first()
+    }
+
     /// Compare two email messages using all criteria
     pub fn compare_messages(&self, a: &EmailMessage, b: &EmailMessage) -> Ordering {
         for criteria in &self.criteria {
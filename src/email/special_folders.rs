@@ -0,0 +1,225 @@
+//! Per-account special folder mapping
+//!
+//! Sent, Drafts, Trash, Junk and Archive are normally found from the
+//! SPECIAL-USE attributes a server advertises, falling back to folder names.
+//! Servers that advertise nothing, or the wrong thing, can have their folders
+//! named in `special_folders.toml` instead; a mapped folder always wins over
+//! detection. Mapping Sent or Drafts also turns on filing copies of sent mail
+//! and saved drafts there, and mapping Trash makes delete move messages to it.
+
+use crate::imap::{FolderAttribute, ImapFolder};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A folder with a special role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpecialFolder {
+    Sent,
+    Drafts,
+    Trash,
+    Junk,
+    Archive,
+}
+
+impl SpecialFolder {
+    pub const ALL: [SpecialFolder; 5] = [
+        SpecialFolder::Sent,
+        SpecialFolder::Drafts,
+        SpecialFolder::Trash,
+        SpecialFolder::Junk,
+        SpecialFolder::Archive,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpecialFolder::Sent => "Sent",
+            SpecialFolder::Drafts => "Drafts",
+            SpecialFolder::Trash => "Trash",
+            SpecialFolder::Junk => "Junk",
+            SpecialFolder::Archive => "Archive",
+        }
+    }
+
+    /// SPECIAL-USE attribute marking this folder (RFC 6154)
+    fn attribute(&self) -> FolderAttribute {
+        match self {
+            SpecialFolder::Sent => FolderAttribute::Sent,
+            SpecialFolder::Drafts => FolderAttribute::Drafts,
+            SpecialFolder::Trash => FolderAttribute::Trash,
+            SpecialFolder::Junk => FolderAttribute::Junk,
+            SpecialFolder::Archive => FolderAttribute::Archive,
+        }
+    }
+
+    /// Whether an upper-cased folder name looks like this folder
+    fn matches_name(&self, name: &str) -> bool {
+        match self {
+            SpecialFolder::Sent => name.contains("SENT"),
+            SpecialFolder::Drafts => name.contains("DRAFT"),
+            SpecialFolder::Trash => name.contains("TRASH") || name.contains("DELETED"),
+            SpecialFolder::Junk => name.contains("SPAM") || name.contains("JUNK"),
+            SpecialFolder::Archive => name.contains("ARCHIVE") || name.contains("ALL MAIL"),
+        }
+    }
+}
+
+/// Folder names for each special role; `None` means not mapped (or not found)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpecialFolderMapping {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drafts: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub junk: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive: Option<String>,
+}
+
+impl SpecialFolderMapping {
+    /// Detect special folders from a folder listing
+    ///
+    /// SPECIAL-USE attributes are trusted first; roles no folder is marked
+    /// with fall back to the first folder whose name fits. Full names are
+    /// returned, as IMAP commands need them.
+    pub fn detect(folders: &[ImapFolder]) -> Self {
+        let mut mapping = Self::default();
+        for kind in SpecialFolder::ALL {
+            let attribute = kind.attribute();
+            let found = folders
+                .iter()
+                .find(|folder| folder.attributes.contains(&attribute))
+                .or_else(|| {
+                    folders
+                        .iter()
+                        .find(|folder| kind.matches_name(&folder.name.to_uppercase()))
+                })
+                .map(|folder| folder.full_name.clone());
+            mapping.set(kind, found);
+        }
+        mapping
+    }
+
+    pub fn get(&self, kind: SpecialFolder) -> Option<&str> {
+        match kind {
+            SpecialFolder::Sent => self.sent.as_deref(),
+            SpecialFolder::Drafts => self.drafts.as_deref(),
+            SpecialFolder::Trash => self.trash.as_deref(),
+            SpecialFolder::Junk => self.junk.as_deref(),
+            SpecialFolder::Archive => self.archive.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, kind: SpecialFolder, folder: Option<String>) {
+        let folder = folder.filter(|name| !name.trim().is_empty());
+        match kind {
+            SpecialFolder::Sent => self.sent = folder,
+            SpecialFolder::Drafts => self.drafts = folder,
+            SpecialFolder::Trash => self.trash = folder,
+            SpecialFolder::Junk => self.junk = folder,
+            SpecialFolder::Archive => self.archive = folder,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        SpecialFolder::ALL.iter().all(|kind| self.get(*kind).is_none())
+    }
+}
+
+/// Special folder mappings, per account
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpecialFolderConfig {
+    /// Mapped folders keyed by account ID
+    pub accounts: HashMap<String, SpecialFolderMapping>,
+}
+
+impl SpecialFolderConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "special_folders.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Folders mapped for an account
+    pub fn mapping_for(&self, account_id: &str) -> SpecialFolderMapping {
+        self.accounts.get(account_id).cloned().unwrap_or_default()
+    }
+
+    /// The folder mapped for a role, if any
+    pub fn folder(&self, account_id: &str, kind: SpecialFolder) -> Option<&str> {
+        self.accounts.get(account_id).and_then(|mapping| mapping.get(kind))
+    }
+
+    /// Replace an account's mapping; an empty one goes back to detection
+    pub fn set_mapping(&mut self, account_id: &str, mapping: SpecialFolderMapping) {
+        if mapping.is_empty() {
+            self.accounts.remove(account_id);
+        } else {
+            self.accounts.insert(account_id.to_string(), mapping);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(full_name: &str, attributes: Vec<FolderAttribute>) -> ImapFolder {
+        let name = full_name.rsplit('/').next().unwrap_or(full_name).to_string();
+        let mut folder = ImapFolder::new(name, full_name.to_string());
+        folder.attributes = attributes;
+        folder
+    }
+
+    #[test]
+    fn test_detect_special_folders() {
+        let folders = vec![
+            folder("INBOX", Vec::new()),
+            folder("Sent Items", Vec::new()),
+            folder("[Gmail]/Sent Mail", vec![FolderAttribute::Sent]),
+            folder("Deleted Messages", Vec::new()),
+            folder("Junk E-mail", Vec::new()),
+            folder("[Gmail]/All Mail", Vec::new()),
+        ];
+
+        let detected = SpecialFolderMapping::detect(&folders);
+        // The attribute beats an earlier name match
+        assert_eq!(detected.get(SpecialFolder::Sent), Some("[Gmail]/Sent Mail"));
+        assert_eq!(detected.get(SpecialFolder::Drafts), None);
+        assert_eq!(detected.get(SpecialFolder::Trash), Some("Deleted Messages"));
+        assert_eq!(detected.get(SpecialFolder::Junk), Some("Junk E-mail"));
+        assert_eq!(detected.get(SpecialFolder::Archive), Some("[Gmail]/All Mail"));
+    }
+
+    #[test]
+    fn test_mapping_overrides() {
+        let mut config = SpecialFolderConfig::default();
+        let mut mapping = SpecialFolderMapping::default();
+        mapping.set(SpecialFolder::Sent, Some("INBOX.Sent".to_string()));
+        mapping.set(SpecialFolder::Trash, Some("  ".to_string()));
+        config.set_mapping("work", mapping);
+
+        assert_eq!(config.folder("work", SpecialFolder::Sent), Some("INBOX.Sent"));
+        assert_eq!(config.folder("work", SpecialFolder::Trash), None);
+        assert_eq!(config.folder("home", SpecialFolder::Sent), None);
+
+        let toml = toml::to_string(&config).unwrap();
+        assert!(toml.contains("sent = \"INBOX.Sent\""));
+        assert!(!toml.contains("trash"));
+
+        config.set_mapping("work", SpecialFolderMapping::default());
+        assert!(config.accounts.is_empty());
+    }
+}
@@ -1,11 +1,28 @@
 use crate::email::database::{EmailDatabase, FolderSyncState, StoredMessage, SyncStatus};
-use crate::imap::{ImapCapability, ImapClient, ImapFolder, ImapMessage, SearchCriteria};
+use crate::email::filters::{FilterAction, FilterEngine, FilterResult, FilterRules};
+use crate::email::folder_sync::{FolderSyncDefaults, FolderSyncMode};
+use crate::email::gmail_labels::LabelLayout;
+use crate::email::inbox_tabs::InboxTriage;
+use crate::email::notifications::EmailNotificationManager;
+use crate::email::plain_text::{self, PlainTextAccounts};
+use crate::email::reply_target::ReplyHeaders;
+use crate::email::security::MessageSecurity;
+use crate::email::sender_lists::SenderLists;
+use crate::email::special_folders::{SpecialFolder, SpecialFolderConfig, SpecialFolderMapping};
+use crate::imap::{
+    BatchSizer, FetchConfig, FetchSettings, ImapCapability, ImapClient,
+    ImapFolder, ImapMessage, MessageFlag, SearchCriteria,
+};
+use crate::jmap::{Email, JmapClient, JmapError};
+use crate::plugins::{EmailPluginContext, EmailProcessResult, IncomingEmailHooks};
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
-use tokio::sync::{mpsc, Mutex, RwLock};
-use tokio::time::{sleep, Duration};
+use tokio::sync::{mpsc, Mutex, Notify, RwLock};
+use tokio::time::{sleep, Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 /// Sync engine errors
@@ -17,6 +34,9 @@ pub enum SyncError {
     #[error("IMAP error: {0}")]
     Imap(#[from] crate::imap::ImapError),
 
+    #[error("JMAP error: {0}")]
+    Jmap(#[from] JmapError),
+
     #[error("Sync conflict: {0}")]
     Conflict(String),
 
@@ -28,10 +48,28 @@ pub enum SyncError {
 
     #[error("Sync timeout: {0}")]
     Timeout(String),
+
+    #[error("Sync cancelled: {0}")]
+    Cancelled(String),
 }
 
 pub type SyncResult<T> = Result<T, SyncError>;
 
+/// Emails requested per `Email/get` during a JMAP sync
+const JMAP_BATCH_SIZE: usize = 50;
+
+/// Changes requested per `Email/changes` call
+const JMAP_MAX_CHANGES: usize = 500;
+
+/// What a JMAP account sync found
+#[derive(Debug, Clone, Default)]
+pub struct JmapSyncOutcome {
+    /// The account's mailboxes as folders
+    pub folders: Vec<ImapFolder>,
+    /// Messages that arrived since the previous sync; empty after a full sync
+    pub new_messages: Vec<StoredMessage>,
+}
+
 /// Sync progress information
 #[derive(Debug, Clone)]
 pub struct SyncProgress {
@@ -43,6 +81,278 @@ pub struct SyncProgress {
     pub bytes_downloaded: u64,
     pub started_at: DateTime<Utc>,
     pub estimated_completion: Option<DateTime<Utc>>,
+    /// Aggregate progress of the account-wide sync this folder belongs to
+    pub account_progress: Option<AccountSyncProgress>,
+}
+
+impl SyncProgress {
+    /// Percentage of messages processed in this folder
+    pub fn percent(&self) -> f64 {
+        percent_of(self.messages_processed as u64, self.total_messages as u64)
+    }
+
+    /// Messages processed per second since the folder sync started
+    pub fn messages_per_second(&self, now: DateTime<Utc>) -> f64 {
+        rate_since(self.messages_processed as u64, self.started_at, now)
+    }
+
+    /// Estimated completion time based on the current processing rate
+    pub fn estimate_completion(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let remaining = self.total_messages.saturating_sub(self.messages_processed) as u64;
+        eta_from_rate(remaining, self.messages_per_second(now)).map(|eta| now + eta)
+    }
+}
+
+/// Aggregate progress for an account-wide sync
+///
+/// Folders are weighted by their message counts so a large INBOX moves the
+/// overall percentage more than a handful of small folders.
+#[derive(Debug, Clone)]
+pub struct AccountSyncProgress {
+    pub account_id: String,
+    /// Folder currently being synced
+    pub current_folder: String,
+    /// Zero-based index of the current folder
+    pub folder_index: usize,
+    pub total_folders: usize,
+    /// Messages in folders that have finished syncing
+    pub completed_folder_messages: u64,
+    /// Messages processed so far in the current folder
+    pub current_folder_processed: u64,
+    /// Message count of the current folder
+    pub current_folder_total: u64,
+    /// Sum of message counts across all folders
+    pub total_messages: u64,
+    pub started_at: DateTime<Utc>,
+}
+
+impl AccountSyncProgress {
+    /// Create progress for an account sync over folders with the given message counts
+    pub fn new(account_id: String, folder_counts: &[(String, u64)]) -> Self {
+        Self {
+            account_id,
+            current_folder: folder_counts
+                .first()
+                .map(|(name, _)| name.clone())
+                .unwrap_or_default(),
+            folder_index: 0,
+            total_folders: folder_counts.len(),
+            completed_folder_messages: 0,
+            current_folder_processed: 0,
+            current_folder_total: folder_counts.first().map(|(_, count)| *count).unwrap_or(0),
+            total_messages: folder_counts.iter().map(|(_, count)| count).sum(),
+            started_at: Utc::now(),
+        }
+    }
+
+    /// Messages processed across all folders
+    pub fn messages_completed(&self) -> u64 {
+        self.completed_folder_messages + self.current_folder_processed
+    }
+
+    /// Overall percentage weighted by folder message counts
+    pub fn percent(&self) -> f64 {
+        percent_of(self.messages_completed(), self.total_messages)
+    }
+
+    /// Folders still waiting to be synced, excluding the current one
+    pub fn folders_remaining(&self) -> usize {
+        self.total_folders.saturating_sub(self.folder_index + 1)
+    }
+
+    /// Messages processed per second since the account sync started
+    pub fn messages_per_second(&self, now: DateTime<Utc>) -> f64 {
+        rate_since(self.messages_completed(), self.started_at, now)
+    }
+
+    /// Estimated time until the whole account has been synced
+    pub fn estimated_remaining(&self, now: DateTime<Utc>) -> Option<ChronoDuration> {
+        let remaining = self.total_messages.saturating_sub(self.messages_completed());
+        eta_from_rate(remaining, self.messages_per_second(now))
+    }
+
+    /// Record progress reported for the current folder
+    fn record_folder_progress(&mut self, folder_name: &str, processed: u32, total: u32) {
+        if self.current_folder != folder_name {
+            return;
+        }
+
+        // The folder list may not carry message counts; use the real count once known
+        let total = total as u64;
+        if total > 0 && total != self.current_folder_total {
+            self.total_messages = self.total_messages - self.current_folder_total + total;
+            self.current_folder_total = total;
+        }
+        self.current_folder_processed = processed as u64;
+    }
+
+    /// Move on to the next folder
+    fn advance(&mut self, next_folder: Option<(&str, u64)>) {
+        self.completed_folder_messages += self.current_folder_total.max(self.current_folder_processed);
+        self.current_folder_processed = 0;
+        self.folder_index += 1;
+
+        match next_folder {
+            Some((name, count)) => {
+                self.current_folder = name.to_string();
+                self.current_folder_total = count;
+            }
+            None => {
+                self.current_folder_total = 0;
+            }
+        }
+    }
+}
+
+/// What the filter engine and email plugins decided for newly arrived
+/// messages, by the messages' server-side handle
+struct ArrivalActions<T> {
+    delete: Vec<T>,
+    moves: HashMap<String, Vec<T>>,
+    /// IMAP flags or keywords to set
+    flags: HashMap<String, Vec<T>>,
+    labels: HashMap<String, Vec<T>>,
+    notify: Vec<StoredMessage>,
+}
+
+impl<T: Clone> ArrivalActions<T> {
+    fn new() -> Self {
+        Self {
+            delete: Vec::new(),
+            moves: HashMap::new(),
+            flags: HashMap::new(),
+            labels: HashMap::new(),
+            notify: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, handle: T, message: &StoredMessage, result: FilterResult) {
+        for action in result.actions_applied {
+            let flag = match action {
+                FilterAction::Delete => {
+                    self.delete.push(handle.clone());
+                    continue;
+                }
+                FilterAction::MoveToFolder(folder) => {
+                    self.moves.entry(folder).or_default().push(handle.clone());
+                    continue;
+                }
+                FilterAction::AddLabel(label) => {
+                    self.labels.entry(label).or_default().push(handle.clone());
+                    continue;
+                }
+                FilterAction::Notify => {
+                    self.notify.push(message.clone());
+                    continue;
+                }
+                FilterAction::MarkAsRead => MessageFlag::Seen,
+                FilterAction::MarkAsImportant => MessageFlag::Flagged,
+                FilterAction::SetFlag(flag) => MessageFlag::from_str(&flag),
+                _ => continue,
+            };
+            self.flags
+                .entry(flag.to_string())
+                .or_default()
+                .push(handle.clone());
+        }
+    }
+
+    /// Record what email plugins asked for; copies and custom results have
+    /// no arrival action
+    fn record_plugins(&mut self, handle: T, message: &StoredMessage, results: Vec<EmailProcessResult>) {
+        for result in results {
+            match result {
+                EmailProcessResult::Delete => self.delete.push(handle.clone()),
+                EmailProcessResult::Move(folder) => {
+                    self.moves.entry(folder).or_default().push(handle.clone());
+                }
+                EmailProcessResult::SetFlags(flags) => {
+                    for flag in flags {
+                        self.flags
+                            .entry(MessageFlag::from_str(&flag).to_string())
+                            .or_default()
+                            .push(handle.clone());
+                    }
+                }
+                // Tags become labels and new flags are set
+                EmailProcessResult::Modified(modified) => {
+                    for label in modified.labels {
+                        if !message.labels.contains(&label) {
+                            self.labels.entry(label).or_default().push(handle.clone());
+                        }
+                    }
+                    for flag in modified.flags {
+                        if !message.flags.contains(&flag) {
+                            self.flags
+                                .entry(MessageFlag::from_str(&flag).to_string())
+                                .or_default()
+                                .push(handle.clone());
+                        }
+                    }
+                }
+                EmailProcessResult::NoChange
+                | EmailProcessResult::Copy(_)
+                | EmailProcessResult::Custom(_) => {}
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.delete.is_empty()
+            && self.moves.is_empty()
+            && self.flags.is_empty()
+            && self.labels.is_empty()
+            && self.notify.is_empty()
+    }
+}
+
+/// Add a flag or label set on the server to the local copy of a message
+fn add_local(
+    updated: &mut HashMap<uuid::Uuid, StoredMessage>,
+    message: &StoredMessage,
+    flag: Option<&str>,
+    label: Option<&str>,
+) {
+    let local = updated.entry(message.id).or_insert_with(|| message.clone());
+    if let Some(flag) = flag.filter(|flag| !local.flags.iter().any(|f| f == flag)) {
+        local.flags.push(flag.to_string());
+    }
+    if let Some(label) = label.filter(|label| !local.labels.iter().any(|l| l == label)) {
+        local.labels.push(label.to_string());
+    }
+}
+
+fn uid_set_string(uids: &[u32]) -> String {
+    uids.iter()
+        .map(|uid| uid.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn percent_of(done: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        (done.min(total) as f64 / total as f64) * 100.0
+    }
+}
+
+fn rate_since(processed: u64, started_at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    // Milliseconds so the rate is available within the first second
+    let elapsed_ms = now.signed_duration_since(started_at).num_milliseconds();
+    if elapsed_ms <= 0 {
+        0.0
+    } else {
+        processed as f64 * 1000.0 / elapsed_ms as f64
+    }
+}
+
+fn eta_from_rate(remaining: u64, rate: f64) -> Option<ChronoDuration> {
+    if rate > 0.0 {
+        Some(ChronoDuration::milliseconds((remaining as f64 / rate * 1000.0) as i64))
+    } else {
+        None
+    }
 }
 
 /// Sync phase
@@ -53,10 +363,82 @@ pub enum SyncPhase {
     FetchingHeaders,
     FetchingBodies,
     ProcessingChanges,
+    Paused,
     Complete,
     Error(String),
 }
 
+/// Pause/cancel token shared between a running sync and whoever controls it
+///
+/// The sync checks the token between batches, so pausing or cancelling takes
+/// effect once the batch in flight has been stored and never leaves a
+/// half-written batch behind.
+#[derive(Debug, Clone, Default)]
+pub struct SyncControl {
+    state: Arc<SyncControlState>,
+}
+
+#[derive(Debug, Default)]
+struct SyncControlState {
+    paused: AtomicBool,
+    cancelled: AtomicBool,
+    changed: Notify,
+}
+
+impl SyncControl {
+    /// Create a new token in the running state
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pause the sync at the next checkpoint
+    pub fn pause(&self) {
+        self.state.paused.store(true, Ordering::SeqCst);
+        self.state.changed.notify_waiters();
+    }
+
+    /// Resume a paused sync
+    pub fn resume(&self) {
+        self.state.paused.store(false, Ordering::SeqCst);
+        self.state.changed.notify_waiters();
+    }
+
+    /// Cancel the sync at the next checkpoint, waking it if it is paused
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::SeqCst);
+        self.state.changed.notify_waiters();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the sync is resumed or cancelled
+    ///
+    /// Returns `false` if the sync was cancelled.
+    pub async fn wait_while_paused(&self) -> bool {
+        loop {
+            let changed = self.state.changed.notified();
+            tokio::pin!(changed);
+            // Register before checking so a resume between the check and the await isn't lost
+            changed.as_mut().enable();
+
+            if self.is_cancelled() {
+                return false;
+            }
+            if !self.is_paused() {
+                return true;
+            }
+
+            changed.await;
+        }
+    }
+}
+
 /// Sync strategy
 #[derive(Debug, Clone)]
 pub enum SyncStrategy {
@@ -89,9 +471,20 @@ pub enum ConflictResolution {
 pub struct SyncEngine {
     database: Arc<EmailDatabase>,
     sync_progress: Arc<RwLock<HashMap<String, SyncProgress>>>,
+    account_progress: Arc<RwLock<HashMap<String, AccountSyncProgress>>>,
+    sync_controls: Arc<RwLock<HashMap<String, SyncControl>>>,
     sync_locks: Arc<RwLock<HashMap<String, Arc<Mutex<()>>>>>,
     progress_sender: mpsc::UnboundedSender<SyncProgress>,
     conflict_resolution: ConflictResolution,
+    fetch_config: FetchConfig,
+    folder_sync_defaults: FolderSyncDefaults,
+    plain_text_accounts: PlainTextAccounts,
+    pgp_keyring: Option<PathBuf>,
+    filter_engine: Arc<RwLock<FilterEngine>>,
+    notification_manager: Arc<RwLock<Option<Arc<EmailNotificationManager>>>>,
+    inbox_triage: Arc<RwLock<Option<Arc<InboxTriage>>>>,
+    incoming_email_hooks: Arc<RwLock<Option<IncomingEmailHooks>>>,
+    special_folders: Arc<RwLock<SpecialFolderConfig>>,
     #[allow(dead_code)]
     max_concurrent_syncs: usize,
 }
@@ -105,9 +498,20 @@ impl SyncEngine {
         Self {
             database,
             sync_progress: Arc::new(RwLock::new(HashMap::new())),
+            account_progress: Arc::new(RwLock::new(HashMap::new())),
+            sync_controls: Arc::new(RwLock::new(HashMap::new())),
             sync_locks: Arc::new(RwLock::new(HashMap::new())),
             progress_sender,
             conflict_resolution: ConflictResolution::ServerWins,
+            fetch_config: FetchConfig::default(),
+            folder_sync_defaults: FolderSyncDefaults::default(),
+            plain_text_accounts: PlainTextAccounts::default(),
+            pgp_keyring: None,
+            special_folders: Arc::new(RwLock::new(SpecialFolderConfig::default())),
+            filter_engine: Arc::new(RwLock::new(FilterEngine::new())),
+            notification_manager: Arc::new(RwLock::new(None)),
+            inbox_triage: Arc::new(RwLock::new(None)),
+            incoming_email_hooks: Arc::new(RwLock::new(None)),
             max_concurrent_syncs: 3,
         }
     }
@@ -117,6 +521,75 @@ impl SyncEngine {
         self.conflict_resolution = strategy;
     }
 
+    /// Set per-account FETCH batching overrides
+    pub fn set_fetch_config(&mut self, config: FetchConfig) {
+        self.fetch_config = config;
+    }
+
+    /// Set the per-account default folder sync modes
+    pub fn set_folder_sync_defaults(&mut self, defaults: FolderSyncDefaults) {
+        self.folder_sync_defaults = defaults;
+    }
+
+    /// Per-account default folder sync modes
+    pub fn folder_sync_defaults(&self) -> &FolderSyncDefaults {
+        &self.folder_sync_defaults
+    }
+
+    /// Set the accounts whose HTML is never downloaded
+    pub fn set_plain_text_accounts(&mut self, accounts: PlainTextAccounts) {
+        self.plain_text_accounts = accounts;
+    }
+
+    /// Set the GnuPG home whose public keys check PGP signatures
+    pub fn set_pgp_keyring(&mut self, keyring: Option<PathBuf>) {
+        self.pgp_keyring = keyring;
+    }
+
+    /// Replace the sender blocklist and allowlist applied to newly arrived messages
+    pub async fn set_sender_lists(&self, sender_lists: SenderLists) {
+        self.filter_engine.write().await.set_sender_lists(sender_lists);
+    }
+
+    /// Replace the user's filter rules run on newly arrived messages
+    pub async fn set_filter_rules(&self, rules: FilterRules) {
+        self.filter_engine.write().await.load_filters(rules.rules);
+    }
+
+    /// Publish notifications asked for by filter rules through `manager`
+    pub async fn set_notification_manager(&self, manager: Arc<EmailNotificationManager>) {
+        *self.notification_manager.write().await = Some(manager);
+    }
+
+    /// Sort newly arrived INBOX messages into tabs with `triage`
+    pub async fn set_inbox_triage(&self, triage: Arc<InboxTriage>) {
+        *self.inbox_triage.write().await = Some(triage);
+    }
+
+    /// Run newly arrived INBOX messages through the email plugins of `hooks`
+    pub async fn set_incoming_email_hooks(&self, hooks: IncomingEmailHooks) {
+        *self.incoming_email_hooks.write().await = Some(hooks);
+    }
+
+    /// Replace the mapped special folders; a mapped Junk folder receives
+    /// blocked senders' mail
+    pub async fn set_special_folders(&self, config: SpecialFolderConfig) {
+        *self.special_folders.write().await = config;
+    }
+
+    /// Filter engine applied to newly arrived INBOX messages
+    pub fn filter_engine(&self) -> Arc<RwLock<FilterEngine>> {
+        self.filter_engine.clone()
+    }
+
+    /// FETCH settings for an account: the configured override or the server's provider defaults
+    pub fn fetch_settings(&self, account_id: &str, client: &ImapClient) -> FetchSettings {
+        self.fetch_config
+            .settings_for(account_id)
+            .cloned()
+            .unwrap_or_else(|| client.config().fetch.clone())
+    }
+
     /// Sync all folders for an account
     pub async fn sync_account(
         &self,
@@ -138,6 +611,38 @@ impl SyncEngine {
 
         let _guard = sync_lock.lock().await;
 
+        // Fresh control token for this run; pause/cancel requests target it by account
+        let control = SyncControl::new();
+        self.sync_controls
+            .write()
+            .await
+            .insert(account_id.clone(), control.clone());
+
+        let result = self
+            .sync_account_folders(&account_id, &mut client, strategy, &control)
+            .await;
+
+        self.sync_controls.write().await.remove(&account_id);
+        self.account_progress.write().await.remove(&account_id);
+
+        match result {
+            Ok(()) => info!("Completed account sync: {}", account_id),
+            Err(SyncError::Cancelled(_)) => warn!("Account sync cancelled: {}", account_id),
+            Err(ref e) => error!("Account sync failed for {}: {}", account_id, e),
+        }
+        result
+    }
+
+    /// Sync every folder of a connected account, checking the control token between folders
+    async fn sync_account_folders(
+        &self,
+        account_id: &str,
+        client: &mut ImapClient,
+        strategy: SyncStrategy,
+        control: &SyncControl,
+    ) -> SyncResult<()> {
+        let account_id = account_id.to_string();
+
         // Connect and authenticate
         client.connect().await?;
         client.authenticate().await?;
@@ -145,30 +650,516 @@ impl SyncEngine {
         // Get folder list
         let folders = client.list_folders("", "*").await?;
         info!("🗂️  Found {} folders for account {}", folders.len(), account_id);
+        let folders = self.apply_label_layout(&account_id, client, folders).await?;
         
         // Log all folder names for debugging
         for folder in &folders {
             info!("📁 Folder found: '{}' (messages: {:?})", folder.name, folder.exists);
         }
 
+        let folder_counts: Vec<(String, u64)> = folders
+            .iter()
+            .map(|folder| (folder.name.clone(), folder.exists.unwrap_or(0) as u64))
+            .collect();
+        self.account_progress.write().await.insert(
+            account_id.clone(),
+            AccountSyncProgress::new(account_id.clone(), &folder_counts),
+        );
+
         // Sync each folder
-        for folder in folders {
+        for (index, folder) in folders.iter().enumerate() {
+            self.checkpoint(control, &account_id, &folder.name).await?;
+
             info!("🔄 Starting sync for folder: '{}'", folder.name);
-            if let Err(e) = self
-                .sync_folder(account_id.clone(), &mut client, &folder, strategy.clone())
+            match self
+                .sync_folder_with_control(
+                    account_id.clone(),
+                    client,
+                    folder,
+                    strategy.clone(),
+                    control,
+                )
                 .await
             {
-                error!("❌ Failed to sync folder {}: {}", folder.name, e);
+                Ok(()) => info!("✅ Successfully synced folder: '{}'", folder.name),
+                Err(SyncError::Cancelled(reason)) => return Err(SyncError::Cancelled(reason)),
                 // Continue with other folders
-            } else {
-                info!("✅ Successfully synced folder: '{}'", folder.name);
+                Err(e) => error!("❌ Failed to sync folder {}: {}", folder.name, e),
+            }
+
+            if let Some(account) = self.account_progress.write().await.get_mut(&account_id) {
+                let next = folder_counts
+                    .get(index + 1)
+                    .map(|(name, count)| (name.as_str(), *count));
+                account.advance(next);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sync an account over JMAP
+    ///
+    /// `Email/changes` since the stored state is the incremental source of
+    /// truth. The first sync, or one whose state the server can no longer
+    /// calculate changes from, downloads every mailbox instead.
+    pub async fn sync_account_jmap(
+        &self,
+        account_id: String,
+        client: &mut JmapClient,
+    ) -> SyncResult<JmapSyncOutcome> {
+        info!("Starting JMAP account sync: {}", account_id);
+
+        let sync_lock = {
+            let mut locks = self.sync_locks.write().await;
+            let key = format!("account:{}", account_id);
+            locks
+                .entry(key)
+                .or_insert_with(|| Arc::new(Mutex::new(())))
+                .clone()
+        };
+
+        let _guard = sync_lock.lock().await;
+
+        let control = SyncControl::new();
+        self.sync_controls
+            .write()
+            .await
+            .insert(account_id.clone(), control.clone());
+
+        let result = self.sync_jmap_account(&account_id, client, &control).await;
+
+        self.sync_controls.write().await.remove(&account_id);
+        self.account_progress.write().await.remove(&account_id);
+
+        match result {
+            Ok(ref outcome) => info!(
+                "Completed JMAP account sync: {} ({} new messages)",
+                account_id,
+                outcome.new_messages.len()
+            ),
+            Err(SyncError::Cancelled(_)) => warn!("JMAP account sync cancelled: {}", account_id),
+            Err(ref e) => error!("JMAP account sync failed for {}: {}", account_id, e),
+        }
+        result
+    }
+
+    async fn sync_jmap_account(
+        &self,
+        account_id: &str,
+        client: &mut JmapClient,
+        control: &SyncControl,
+    ) -> SyncResult<JmapSyncOutcome> {
+        let folders = client.list_folders().await?;
+        info!("🗂️  Found {} mailboxes for account {}", folders.len(), account_id);
+
+        let mut modes = HashMap::new();
+        for folder in &folders {
+            let stored_mode = self
+                .database
+                .get_folder_sync_state(account_id, &folder.name)
+                .await?
+                .and_then(|state| state.sync_mode);
+            modes.insert(
+                folder.name.clone(),
+                self.folder_sync_defaults.resolve(account_id, stored_mode),
+            );
+        }
+
+        let new_messages = match self.database.get_jmap_email_state(account_id).await? {
+            Some(state) => {
+                match self
+                    .jmap_incremental_sync(account_id, client, &state, &folders, &modes, control)
+                    .await
+                {
+                    Err(SyncError::Jmap(JmapError::CannotCalculateChanges)) => {
+                        warn!("JMAP state of {} expired, resyncing in full", account_id);
+                        self.jmap_full_sync(account_id, client, &folders, &modes, control)
+                            .await?;
+                        Vec::new()
+                    }
+                    result => result?,
+                }
+            }
+            None => {
+                self.jmap_full_sync(account_id, client, &folders, &modes, control)
+                    .await?;
+                Vec::new()
+            }
+        };
+
+        Ok(JmapSyncOutcome {
+            folders,
+            new_messages,
+        })
+    }
+
+    /// Download every mailbox and drop local messages the server no longer has
+    async fn jmap_full_sync(
+        &self,
+        account_id: &str,
+        client: &mut JmapClient,
+        folders: &[ImapFolder],
+        modes: &HashMap<String, FolderSyncMode>,
+        control: &SyncControl,
+    ) -> SyncResult<()> {
+        // Taken first so changes made during the download show up next time
+        let state = client.email_state().await?;
+
+        let folder_counts: Vec<(String, u64)> = folders
+            .iter()
+            .filter(|folder| modes.get(&folder.name) != Some(&FolderSyncMode::Skip))
+            .map(|folder| (folder.name.clone(), folder.exists.unwrap_or(0) as u64))
+            .collect();
+        self.account_progress.write().await.insert(
+            account_id.to_string(),
+            AccountSyncProgress::new(account_id.to_string(), &folder_counts),
+        );
+
+        // An email in several mailboxes is stored in all of them at once
+        let mut synced: HashSet<String> = HashSet::new();
+        for (index, (folder_name, _)) in folder_counts.iter().enumerate() {
+            self.checkpoint(control, account_id, folder_name).await?;
+
+            let mailbox_id = client.mailbox_id(folder_name).await?;
+            let ids = client.query_emails(&mailbox_id, None).await?;
+            let pending: Vec<String> = ids
+                .iter()
+                .filter(|id| !synced.contains(*id))
+                .cloned()
+                .collect();
+
+            let mut progress = SyncProgress {
+                account_id: account_id.to_string(),
+                folder_name: folder_name.clone(),
+                phase: SyncPhase::FetchingBodies,
+                messages_processed: 0,
+                total_messages: pending.len() as u32,
+                bytes_downloaded: 0,
+                started_at: Utc::now(),
+                estimated_completion: None,
+                account_progress: None,
+            };
+            self.update_progress(progress.clone()).await;
+
+            for batch in pending.chunks(JMAP_BATCH_SIZE) {
+                self.checkpoint(control, account_id, folder_name).await?;
+                for email in client.get_emails(batch).await? {
+                    self.store_jmap_email(account_id, client, &email, modes)
+                        .await?;
+                    progress.bytes_downloaded += email.size as u64;
+                }
+                synced.extend(batch.iter().cloned());
+                progress.messages_processed += batch.len() as u32;
+                self.update_progress(progress.clone()).await;
+            }
+
+            let mut live_uids = HashSet::with_capacity(ids.len());
+            for id in &ids {
+                live_uids.insert(self.database.jmap_uid(account_id, id).await?);
+            }
+            let gone: Vec<u32> = self
+                .database
+                .get_folder_uids(account_id, folder_name)
+                .await?
+                .into_iter()
+                .filter(|uid| !live_uids.contains(uid))
+                .collect();
+            if !gone.is_empty() {
+                self.database
+                    .delete_messages_by_uids(account_id, folder_name, &gone)
+                    .await?;
+            }
+
+            progress.phase = SyncPhase::Complete;
+            self.update_progress(progress).await;
+
+            if let Some(account) = self.account_progress.write().await.get_mut(account_id) {
+                let next = folder_counts
+                    .get(index + 1)
+                    .map(|(name, count)| (name.as_str(), *count));
+                account.advance(next);
             }
         }
 
-        info!("Completed account sync: {}", account_id);
+        self.database.set_jmap_email_state(account_id, &state).await?;
         Ok(())
     }
 
+    /// Apply `Email/changes` since `since_state`, returning the new arrivals
+    async fn jmap_incremental_sync(
+        &self,
+        account_id: &str,
+        client: &mut JmapClient,
+        since_state: &str,
+        folders: &[ImapFolder],
+        modes: &HashMap<String, FolderSyncMode>,
+        control: &SyncControl,
+    ) -> SyncResult<Vec<StoredMessage>> {
+        let mut state = since_state.to_string();
+        let mut new_messages = Vec::new();
+        let mut filtered: Vec<(Email, StoredMessage)> = Vec::new();
+
+        loop {
+            self.checkpoint(control, account_id, "INBOX").await?;
+
+            let changes = client.email_changes(&state, Some(JMAP_MAX_CHANGES)).await?;
+            debug!(
+                "JMAP changes for {}: {} created, {} updated, {} destroyed",
+                account_id,
+                changes.created.len(),
+                changes.updated.len(),
+                changes.destroyed.len()
+            );
+
+            if !changes.destroyed.is_empty() {
+                self.database
+                    .remove_jmap_emails(account_id, &changes.destroyed)
+                    .await?;
+            }
+
+            let created: HashSet<&String> = changes.created.iter().collect();
+            let changed: Vec<String> = changes
+                .created
+                .iter()
+                .chain(changes.updated.iter())
+                .cloned()
+                .collect();
+            for batch in changed.chunks(JMAP_BATCH_SIZE) {
+                for email in client.get_emails(batch).await? {
+                    let stored = self
+                        .store_jmap_email(account_id, client, &email, modes)
+                        .await?;
+                    if !created.contains(&email.id) {
+                        continue;
+                    }
+                    for message in stored {
+                        if message.folder_name.eq_ignore_ascii_case("INBOX") {
+                            filtered.push((email.clone(), message.clone()));
+                        }
+                        new_messages.push(message);
+                    }
+                }
+            }
+
+            // Saved per page so a cancelled sync resumes where it stopped
+            state = changes.new_state;
+            self.database.set_jmap_email_state(account_id, &state).await?;
+            if !changes.has_more_changes {
+                break;
+            }
+        }
+
+        let arrivals: Vec<StoredMessage> =
+            filtered.iter().map(|(_, message)| message.clone()).collect();
+        self.triage_arrivals(&arrivals).await;
+        let removed = self
+            .apply_jmap_arrival_actions(account_id, client, folders, filtered)
+            .await?;
+        new_messages.retain(|message| !removed.contains(&message.id));
+        Ok(new_messages)
+    }
+
+    /// Store an email in every synced folder it's in, and drop it from
+    /// folders it left
+    ///
+    /// The message source is only downloaded for folders that don't have it
+    /// yet; copies already stored just get their flags updated. Returns the
+    /// newly stored copies.
+    async fn store_jmap_email(
+        &self,
+        account_id: &str,
+        client: &JmapClient,
+        email: &Email,
+        modes: &HashMap<String, FolderSyncMode>,
+    ) -> SyncResult<Vec<StoredMessage>> {
+        let uid = self.database.jmap_uid(account_id, &email.id).await?;
+        let folders: Vec<&str> = email
+            .mailboxes()
+            .filter_map(|mailbox_id| client.folder_name(mailbox_id))
+            .filter(|folder| modes.get(*folder) != Some(&FolderSyncMode::Skip))
+            .collect();
+
+        for folder in self.database.folders_with_uid(account_id, uid).await? {
+            if !folders.contains(&folder.as_str()) {
+                self.database
+                    .delete_messages_by_uids(account_id, &folder, &[uid])
+                    .await?;
+            }
+        }
+
+        let flags: Vec<String> = email.flags().iter().map(MessageFlag::to_string).collect();
+        let mut raw: Option<String> = None;
+        let mut stored = Vec::new();
+        for folder in folders {
+            let existing = self
+                .database
+                .get_message_by_uid(account_id, folder, uid)
+                .await?
+                .filter(|message| !message.is_deleted);
+            if let Some(existing) = existing {
+                if existing.flags.iter().collect::<HashSet<_>>() != flags.iter().collect() {
+                    self.database.update_message_flags(existing.id, &flags).await?;
+                }
+                continue;
+            }
+
+            if raw.is_none() && modes.get(folder) != Some(&FolderSyncMode::HeadersOnly) {
+                raw = Some(client.download_message(&email.blob_id).await?);
+            }
+            let message = email.to_imap_message(uid, raw.clone());
+            stored.push(self.store_fetched_message(account_id, folder, &message).await?);
+        }
+
+        Ok(stored)
+    }
+
+    /// Run the filter rules and email plugins on newly arrived INBOX emails
+    /// and carry out their actions, returning the ids of the local messages
+    /// removed
+    async fn apply_jmap_arrival_actions(
+        &self,
+        account_id: &str,
+        client: &mut JmapClient,
+        folders: &[ImapFolder],
+        arrivals: Vec<(Email, StoredMessage)>,
+    ) -> SyncResult<HashSet<uuid::Uuid>> {
+        let mut actions = ArrivalActions::new();
+        for (email, message) in arrivals {
+            let result = self.filter_arrival(&message).await;
+            actions.record((email.clone(), message.clone()), &message, result);
+            let results = self.plugin_arrival(account_id, &message).await;
+            actions.record_plugins((email, message.clone()), &message, results);
+        }
+        if actions.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let mut updated = HashMap::new();
+        for (flag, messages) in &actions.flags {
+            let Some(keyword) = crate::jmap::flag_to_keyword(&MessageFlag::from_str(flag)) else {
+                continue;
+            };
+            let ids: Vec<String> = messages.iter().map(|(email, _)| email.id.clone()).collect();
+            if let Err(e) = client.set_keyword(&ids, &keyword, true).await {
+                warn!("Failed to set {} on filtered messages: {}", keyword, e);
+                continue;
+            }
+            for (_, message) in messages {
+                add_local(&mut updated, message, Some(flag), None);
+            }
+        }
+        // JMAP has no labels; keywords stand in for them
+        for (label, messages) in &actions.labels {
+            let ids: Vec<String> = messages.iter().map(|(email, _)| email.id.clone()).collect();
+            if let Err(e) = client.set_keyword(&ids, label, true).await {
+                warn!("Failed to label filtered messages {}: {}", label, e);
+                continue;
+            }
+            for (_, message) in messages {
+                add_local(&mut updated, message, Some(label), None);
+            }
+        }
+        self.store_local_flags(updated).await?;
+        let ArrivalActions {
+            delete,
+            moves,
+            notify,
+            ..
+        } = actions;
+        let deleted: HashSet<uuid::Uuid> = delete.iter().map(|(_, message)| message.id).collect();
+        self.notify_arrivals(account_id, notify, &deleted).await;
+        if delete.is_empty() && moves.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let junk = self
+            .special_folders
+            .read()
+            .await
+            .folder(account_id, SpecialFolder::Junk)
+            .map(str::to_string)
+            .or_else(|| SpecialFolderMapping::detect(folders).junk);
+        let spam_fallback = self.filter_engine.read().await.sender_lists().spam_folder.clone();
+        let inbox = client.mailbox_id("INBOX").await?;
+
+        let mut removed = Vec::new();
+        for (destination, messages) in moves {
+            // The blocklist's configured spam folder maps to the server's junk folder
+            let destination = match &junk {
+                Some(junk) if destination.eq_ignore_ascii_case(&spam_fallback) => junk.clone(),
+                _ => destination,
+            };
+            let ids: Vec<String> = messages.iter().map(|(email, _)| email.id.clone()).collect();
+            let moved = match client.mailbox_id(&destination).await {
+                Ok(target) => client.move_emails(&ids, &inbox, &target).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = moved {
+                warn!("Failed to move filtered messages to {}: {}", destination, e);
+                continue;
+            }
+            removed.extend(messages);
+        }
+
+        if !delete.is_empty() {
+            let ids: Vec<String> = delete.iter().map(|(email, _)| email.id.clone()).collect();
+            client.destroy_emails(&ids).await?;
+            removed.extend(delete);
+        }
+
+        let uids: Vec<u32> = removed.iter().map(|(_, message)| message.imap_uid).collect();
+        self.database
+            .delete_messages_by_uids(account_id, "INBOX", &uids)
+            .await?;
+        info!(
+            "Removed {} filtered messages from INBOX for account {}",
+            uids.len(),
+            account_id
+        );
+        Ok(removed.into_iter().map(|(_, message)| message.id).collect())
+    }
+
+    /// Record the Gmail label layout of an account and drop its label views
+    /// from `folders`
+    ///
+    /// Label views are read from All Mail, so only the folders that store
+    /// messages are returned, named by their full name, which the views
+    /// refer to. Other servers' folders are returned unchanged.
+    pub async fn apply_label_layout(
+        &self,
+        account_id: &str,
+        client: &ImapClient,
+        folders: Vec<ImapFolder>,
+    ) -> SyncResult<Vec<ImapFolder>> {
+        if !client.supports_gmail_labels() {
+            return Ok(folders);
+        }
+
+        let layout = LabelLayout::detect(&folders);
+        self.database
+            .set_label_layout(account_id, layout.as_ref())
+            .await?;
+        let Some(layout) = layout else {
+            return Ok(folders);
+        };
+
+        info!(
+            "Gmail account {}: syncing {} with {} label views",
+            account_id,
+            layout.all_mail,
+            layout.views.len()
+        );
+        Ok(folders
+            .into_iter()
+            .filter(|folder| layout.label_for(&folder.full_name).is_none())
+            .map(|mut folder| {
+                folder.name = folder.full_name.clone();
+                folder
+            })
+            .collect())
+    }
+
     /// Sync a specific folder
     pub async fn sync_folder(
         &self,
@@ -176,6 +1167,38 @@ impl SyncEngine {
         client: &mut ImapClient,
         folder: &ImapFolder,
         strategy: SyncStrategy,
+    ) -> SyncResult<()> {
+        // Reuse the account's token if an account sync is running, otherwise register one
+        let (control, registered) = {
+            let mut controls = self.sync_controls.write().await;
+            match controls.get(&account_id) {
+                Some(control) => (control.clone(), false),
+                None => {
+                    let control = SyncControl::new();
+                    controls.insert(account_id.clone(), control.clone());
+                    (control, true)
+                }
+            }
+        };
+
+        let result = self
+            .sync_folder_with_control(account_id.clone(), client, folder, strategy, &control)
+            .await;
+
+        if registered {
+            self.sync_controls.write().await.remove(&account_id);
+        }
+        result
+    }
+
+    /// Sync a folder, honoring pause and cancel requests on the given token
+    async fn sync_folder_with_control(
+        &self,
+        account_id: String,
+        client: &mut ImapClient,
+        folder: &ImapFolder,
+        strategy: SyncStrategy,
+        control: &SyncControl,
     ) -> SyncResult<()> {
         let folder_key = format!("{}:{}", account_id, folder.name);
 
@@ -190,6 +1213,16 @@ impl SyncEngine {
 
         let _guard = sync_lock.lock().await;
 
+        if let Some(layout) = self.database.get_label_layout(&account_id).await? {
+            if layout.label_for(&folder.full_name).is_some() {
+                info!(
+                    "Skipping Gmail label view {} - {}, it is read from {}",
+                    account_id, folder.full_name, layout.all_mail
+                );
+                return Ok(());
+            }
+        }
+
         info!("Starting folder sync: {} - {}", account_id, folder.name);
 
         // Initialize progress tracking
@@ -202,29 +1235,49 @@ impl SyncEngine {
             bytes_downloaded: 0,
             started_at: Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         };
 
+        // Get existing sync state
+        let stored_state = self
+            .database
+            .get_folder_sync_state(&account_id, &folder.name)
+            .await?;
+
+        let sync_mode = self.folder_sync_defaults.resolve(
+            &account_id,
+            stored_state.as_ref().and_then(|state| state.sync_mode),
+        );
+        if sync_mode == FolderSyncMode::Skip {
+            info!("Skipping folder sync: {} - {}", account_id, folder.name);
+            return Ok(());
+        }
+
         self.update_progress(progress.clone()).await;
 
         // Select folder
-        let selected_folder = client.select_folder(&folder.name).await?;
+        let (selected_folder, vanished) = self
+            .select_for_sync(client, &folder.name, stored_state.as_ref())
+            .await?;
 
-        // Get existing sync state
-        let mut sync_state = self
-            .database
-            .get_folder_sync_state(&account_id, &folder.name)
-            .await?
-            .unwrap_or(FolderSyncState {
-                account_id: account_id.clone(),
-                folder_name: folder.name.clone(),
-                uid_validity: selected_folder.uid_validity.unwrap_or(0),
-                uid_next: selected_folder.uid_next.unwrap_or(1),
-                highest_modseq: None,
-                last_sync: DateTime::from_timestamp(0, 0).unwrap().into(),
-                message_count: 0,
-                unread_count: 0,
-                sync_status: SyncStatus::Idle,
-            });
+        let mut sync_state = stored_state.unwrap_or(FolderSyncState {
+            account_id: account_id.clone(),
+            folder_name: folder.name.clone(),
+            uid_validity: selected_folder.uid_validity.unwrap_or(0),
+            uid_next: selected_folder.uid_next.unwrap_or(1),
+            highest_modseq: None,
+            last_sync: DateTime::from_timestamp(0, 0).unwrap(),
+            message_count: 0,
+            unread_count: 0,
+            sync_status: SyncStatus::Idle,
+            sync_mode: None,
+        });
+
+        // A previously cancelled full sync picks up where it stopped
+        let mut resume_uid = match sync_state.sync_status {
+            SyncStatus::Cancelled { resume_uid } => Some(resume_uid),
+            _ => None,
+        };
 
         // Check for UID validity change (indicates folder reset)
         let needs_full_sync = if let Some(folder_uid_validity) = selected_folder.uid_validity {
@@ -234,9 +1287,11 @@ impl SyncEngine {
                     folder.name
                 );
                 sync_state.uid_validity = folder_uid_validity;
+                // Stored UIDs no longer mean anything, start over
+                resume_uid = None;
                 true
             } else {
-                matches!(strategy, SyncStrategy::Full)
+                resume_uid.is_some() || matches!(strategy, SyncStrategy::Full)
             }
         } else {
             true // No UID validity, force full sync
@@ -245,11 +1300,44 @@ impl SyncEngine {
         sync_state.sync_status = SyncStatus::Syncing;
         self.database.update_folder_sync_state(&sync_state).await?;
 
+        // Messages expunged since the last sync, as reported by QRESYNC
+        if !vanished.is_empty() {
+            info!(
+                "{} messages vanished from folder {} since the last sync",
+                vanished.len(),
+                folder.name
+            );
+            self.database
+                .delete_messages_by_uids(&account_id, &folder.name, &vanished)
+                .await?;
+        }
+
+        // Headers-only folders never download bodies, even when a full sync is due
+        let headers_only = sync_mode == FolderSyncMode::HeadersOnly
+            && !matches!(strategy, SyncStrategy::FlagsOnly);
+
         // Determine sync approach
-        match (needs_full_sync, strategy) {
+        let result = match (needs_full_sync, strategy) {
+            _ if headers_only => {
+                self.headers_only_sync_folder(
+                    &account_id,
+                    client,
+                    &selected_folder,
+                    &mut sync_state,
+                    control,
+                )
+                .await
+            }
             (true, _) | (false, SyncStrategy::Full) => {
-                self.full_sync_folder(&account_id, client, &selected_folder, &mut sync_state)
-                    .await?;
+                self.full_sync_folder(
+                    &account_id,
+                    client,
+                    &selected_folder,
+                    &mut sync_state,
+                    resume_uid,
+                    control,
+                )
+                .await
             }
             (false, SyncStrategy::Incremental) => {
                 self.incremental_sync_folder(
@@ -258,7 +1346,7 @@ impl SyncEngine {
                     &selected_folder,
                     &mut sync_state,
                 )
-                .await?;
+                .await
             }
             (false, SyncStrategy::HeadersOnly) => {
                 self.headers_only_sync_folder(
@@ -266,8 +1354,9 @@ impl SyncEngine {
                     client,
                     &selected_folder,
                     &mut sync_state,
+                    control,
                 )
-                .await?;
+                .await
             }
             (false, SyncStrategy::Recent(days)) => {
                 self.recent_sync_folder(
@@ -277,7 +1366,7 @@ impl SyncEngine {
                     &mut sync_state,
                     days,
                 )
-                .await?;
+                .await
             }
             (false, SyncStrategy::FlagsOnly) => {
                 self.flags_only_sync_folder(
@@ -286,9 +1375,25 @@ impl SyncEngine {
                     &selected_folder,
                     &mut sync_state,
                 )
-                .await?;
+                .await
+            }
+        };
+
+        if let Err(SyncError::Cancelled(reason)) = result {
+            // Keep what was stored; a cancelled full sync has already recorded its resume point
+            if !matches!(sync_state.sync_status, SyncStatus::Cancelled { .. }) {
+                sync_state.sync_status = SyncStatus::Idle;
             }
+            self.database.update_folder_sync_state(&sync_state).await?;
+
+            let mut cancelled_progress = progress;
+            cancelled_progress.phase = SyncPhase::Error("Cancelled by user".to_string());
+            self.update_progress(cancelled_progress).await;
+
+            info!("Cancelled folder sync: {} - {}", account_id, folder.name);
+            return Err(SyncError::Cancelled(reason));
         }
+        result?;
 
         // Update final sync state
         sync_state.sync_status = SyncStatus::Complete;
@@ -311,6 +1416,8 @@ impl SyncEngine {
         client: &mut ImapClient,
         folder: &ImapFolder,
         sync_state: &mut FolderSyncState,
+        resume_uid: Option<u32>,
+        control: &SyncControl,
     ) -> SyncResult<()> {
         debug!("Starting full sync for folder: {}", folder.name);
 
@@ -323,7 +1430,7 @@ impl SyncEngine {
         }
 
         // Fetch all message UIDs first
-        let search_results = client.search(&SearchCriteria::All).await?;
+        let mut search_results = client.search(&SearchCriteria::All).await?;
         info!(
             "📊 Found {} messages in folder '{}' using SearchCriteria::All",
             search_results.len(),
@@ -335,22 +1442,63 @@ impl SyncEngine {
             return Ok(());
         }
 
+        // Process in UID order so a cancelled sync can resume from a single UID
+        search_results.sort_unstable();
+        let already_synced = match resume_uid {
+            Some(resume_uid) => {
+                let skipped = search_results.partition_point(|uid| *uid < resume_uid);
+                info!(
+                    "Resuming full sync of '{}' from UID {} ({} messages already stored)",
+                    folder.name, resume_uid, skipped
+                );
+                skipped
+            }
+            None => 0,
+        };
+        let remaining_uids = &search_results[already_synced..];
+
         // Batch process messages to avoid overwhelming the server
-        const BATCH_SIZE: usize = 50;
-        let batches: Vec<_> = search_results.chunks(BATCH_SIZE).collect();
+        let mut sizer = BatchSizer::new(&self.fetch_settings(account_id, client));
+        let mut offset = 0;
+
+        while offset < remaining_uids.len() {
+            if let Err(e) = self.checkpoint(control, account_id, &folder.name).await {
+                // Everything below the first UID of this batch is stored
+                sync_state.sync_status = SyncStatus::Cancelled {
+                    resume_uid: remaining_uids[offset],
+                };
+                return Err(e);
+            }
 
-        for (batch_index, batch_uids) in batches.iter().enumerate() {
-            self.process_message_batch(
-                account_id,
-                client,
-                &folder.name,
-                batch_uids,
-                SyncStrategy::Full,
-            )
-            .await?;
+            let end = (offset + sizer.batch_size()).min(remaining_uids.len());
+            let started = Instant::now();
+
+            if let Err(e) = self
+                .process_message_batch(
+                    account_id,
+                    client,
+                    &folder.name,
+                    &remaining_uids[offset..end],
+                    SyncStrategy::Full,
+                )
+                .await
+            {
+                if sizer.record_error() {
+                    warn!(
+                        "Batch fetch failed in '{}', retrying with batch size {}: {}",
+                        folder.name,
+                        sizer.batch_size(),
+                        e
+                    );
+                    continue;
+                }
+                return Err(e);
+            }
+            sizer.record_success(started.elapsed());
+            offset = end;
 
             // Update progress
-            let processed = ((batch_index + 1) * BATCH_SIZE).min(search_results.len()) as u32;
+            let processed = (already_synced + offset) as u32;
             self.update_progress_count(account_id, &folder.name, processed, total_messages)
                 .await;
 
@@ -359,9 +1507,39 @@ impl SyncEngine {
         }
 
         sync_state.message_count = search_results.len() as u32;
+        sync_state.highest_modseq = folder.highest_modseq;
         Ok(())
     }
 
+    /// Select a folder for syncing, with mod-sequences where the server has them
+    ///
+    /// On CONDSTORE servers QRESYNC is enabled when available and the folder's
+    /// last HIGHESTMODSEQ passed along, so the UIDs expunged since come back
+    /// with the folder. Other servers get a plain SELECT.
+    async fn select_for_sync(
+        &self,
+        client: &mut ImapClient,
+        folder_name: &str,
+        stored_state: Option<&FolderSyncState>,
+    ) -> SyncResult<(ImapFolder, Vec<u32>)> {
+        let capabilities = client.capabilities();
+        if !capabilities.contains(&ImapCapability::CondStore)
+            && !capabilities.contains(&ImapCapability::QResync)
+        {
+            return Ok((client.select_folder(folder_name).await?, Vec::new()));
+        }
+
+        if let Err(e) = client.enable_qresync().await {
+            warn!("Failed to enable QRESYNC, continuing with CONDSTORE: {}", e);
+        }
+        let known = stored_state.and_then(|state| {
+            state
+                .highest_modseq
+                .map(|modseq| (state.uid_validity, modseq))
+        });
+        Ok(client.select_folder_condstore(folder_name, known).await?)
+    }
+
     /// Perform incremental folder synchronization
     async fn incremental_sync_folder(
         &self,
@@ -375,8 +1553,8 @@ impl SyncEngine {
         self.update_progress_phase(account_id, &folder.name, SyncPhase::CheckingFolders)
             .await;
 
-        // Use CONDSTORE if available for efficient sync
-        if client.capabilities().contains(&ImapCapability::CondStore) {
+        // Use CONDSTORE when the folder keeps mod-sequences
+        if folder.highest_modseq.is_some() {
             self.condstore_sync_folder(account_id, client, folder, sync_state)
                 .await?;
         } else {
@@ -389,6 +1567,11 @@ impl SyncEngine {
     }
 
     /// CONDSTORE-based incremental sync
+    ///
+    /// Only messages whose mod-sequence moved past the stored HIGHESTMODSEQ
+    /// are fetched: known ones get their flags updated, new ones are
+    /// downloaded. The first run without a stored value reads every
+    /// message's flags once to establish it.
     async fn condstore_sync_folder(
         &self,
         account_id: &str,
@@ -396,33 +1579,86 @@ impl SyncEngine {
         folder: &ImapFolder,
         sync_state: &mut FolderSyncState,
     ) -> SyncResult<()> {
-        let modseq = sync_state.highest_modseq.unwrap_or(0);
+        if sync_state.highest_modseq.is_some() && sync_state.highest_modseq == folder.highest_modseq {
+            debug!("Folder {} unchanged since modseq {:?}", folder.name, folder.highest_modseq);
+            return Ok(());
+        }
+
+        // Gmail label changes bump the mod-sequence like flag changes do
+        let gmail_labels = client.supports_gmail_labels();
+        let mut fetch_items = vec!["UID", "FLAGS"];
+        if gmail_labels {
+            fetch_items.push("X-GM-LABELS");
+        }
+
+        let changed = if folder.exists == Some(0) {
+            Vec::new()
+        } else {
+            match sync_state.highest_modseq {
+                Some(modseq) => {
+                    client
+                        .uid_fetch_changed_since("1:*", &fetch_items, modseq)
+                        .await?
+                }
+                None => client.uid_fetch_messages("1:*", &fetch_items).await?,
+            }
+        };
 
-        // Use UID search since CONDSTORE is available
-        // In a real implementation, this would use SEARCH (MODSEQ xxx)
-        let criteria = SearchCriteria::All; // Simplified for now
-        let changed_uids = client.search(&criteria).await?;
+        let mut new_uids = Vec::new();
+        let mut updated = 0;
+        for message in &changed {
+            let Some(uid) = message.uid else {
+                continue;
+            };
+            match self
+                .database
+                .get_message_by_uid(account_id, &folder.name, uid)
+                .await?
+            {
+                Some(existing) => {
+                    let flags: Vec<String> = message.flags.iter().map(|flag| flag.to_string()).collect();
+                    if existing.flags.iter().collect::<HashSet<_>>() != flags.iter().collect() {
+                        self.database.update_message_flags(existing.id, &flags).await?;
+                        updated += 1;
+                    }
+                    if gmail_labels
+                        && existing.labels.iter().collect::<HashSet<_>>()
+                            != message.labels.iter().collect()
+                    {
+                        self.database
+                            .update_message_labels(existing.id, &message.labels)
+                            .await?;
+                        updated += 1;
+                    }
+                }
+                None => new_uids.push(uid),
+            }
+        }
 
-        if !changed_uids.is_empty() {
+        if updated > 0 || !new_uids.is_empty() {
             info!(
-                "Found {} changed messages in folder {}",
-                changed_uids.len(),
-                folder.name
+                "CONDSTORE sync of folder {}: {} flag or label changes, {} new messages",
+                folder.name,
+                updated,
+                new_uids.len()
             );
+        }
 
+        if !new_uids.is_empty() {
+            new_uids.sort_unstable();
             self.process_message_batch(
                 account_id,
                 client,
                 &folder.name,
-                &changed_uids,
+                &new_uids,
                 SyncStrategy::Incremental,
             )
             .await?;
         }
 
-        // Update highest modseq from folder status
-        if let Some(new_modseq) = folder.uid_next.map(|_| modseq + 1) {
-            sync_state.highest_modseq = Some(new_modseq as u64);
+        sync_state.highest_modseq = folder.highest_modseq;
+        if let Some(folder_uid_next) = folder.uid_next {
+            sync_state.uid_next = folder_uid_next;
         }
 
         Ok(())
@@ -474,6 +1710,7 @@ impl SyncEngine {
         client: &mut ImapClient,
         folder: &ImapFolder,
         sync_state: &mut FolderSyncState,
+        control: &SyncControl,
     ) -> SyncResult<()> {
         debug!("Starting headers-only sync for folder: {}", folder.name);
 
@@ -490,34 +1727,65 @@ impl SyncEngine {
             return Ok(());
         }
 
-        // Fetch only headers for efficiency
-        const BATCH_SIZE: usize = 100; // Larger batches for headers-only
-        let batches: Vec<_> = search_results.chunks(BATCH_SIZE).collect();
+        // Fetch only headers for efficiency; headers are small so start with double batches
+        let mut settings = self.fetch_settings(account_id, client);
+        settings.batch_size *= 2;
+        let mut sizer = BatchSizer::new(&settings);
+        let mut offset = 0;
 
-        for batch_uids in batches {
-            // Fetch headers only
-            let uid_set = batch_uids
-                .iter()
-                .map(|uid| uid.to_string())
-                .collect::<Vec<_>>()
-                .join(",");
-
-            let messages = client
-                .uid_fetch_messages(
-                    &uid_set,
-                    &["UID", "FLAGS", "ENVELOPE", "INTERNALDATE", "RFC822.SIZE"],
+        while offset < search_results.len() {
+            self.checkpoint(control, account_id, &folder.name).await?;
+
+            let end = (offset + sizer.batch_size()).min(search_results.len());
+            let started = Instant::now();
+
+            if let Err(e) = self
+                .process_message_batch(
+                    account_id,
+                    client,
+                    &folder.name,
+                    &search_results[offset..end],
+                    SyncStrategy::HeadersOnly,
                 )
-                .await?;
+                .await
+            {
+                if sizer.record_error() {
+                    warn!(
+                        "Header fetch failed in '{}', retrying with batch size {}: {}",
+                        folder.name,
+                        sizer.batch_size(),
+                        e
+                    );
+                    continue;
+                }
+                return Err(e);
+            }
+            sizer.record_success(started.elapsed());
+            offset = end;
+        }
 
-            // Store messages (without bodies)
-            for message in messages {
-                let stored_message = StoredMessage::from_imap_message(
-                    &message,
-                    account_id.to_string(),
-                    folder.name.clone(),
-                );
+        // Prefetch full bodies of the newest messages so opening them is instant
+        let prefetch_depth = settings.prefetch_depth.min(search_results.len());
+        if prefetch_depth > 0 {
+            let mut newest = search_results.clone();
+            newest.sort_unstable();
+            let newest = &newest[newest.len() - prefetch_depth..];
 
-                self.database.store_message(&stored_message).await?;
+            debug!(
+                "Prefetching {} message bodies in folder '{}'",
+                newest.len(),
+                folder.name
+            );
+            for batch_uids in newest.chunks(sizer.batch_size()) {
+                self.checkpoint(control, account_id, &folder.name).await?;
+                self.process_message_batch(
+                    account_id,
+                    client,
+                    &folder.name,
+                    batch_uids,
+                    SyncStrategy::Full,
+                )
+                .await?;
             }
         }
 
@@ -618,8 +1886,18 @@ impl SyncEngine {
             .collect::<Vec<_>>()
             .join(",");
 
+        // Plain-text-only accounts fetch bodies part by part below, without HTML
+        let text_only = self.plain_text_accounts.contains(account_id)
+            && !matches!(
+                strategy,
+                SyncStrategy::HeadersOnly | SyncStrategy::FlagsOnly
+            );
+
         // Determine what to fetch based on strategy
-        let fetch_items = match strategy {
+        let mut fetch_items = match strategy {
+            _ if text_only => {
+                vec!["UID", "FLAGS", "ENVELOPE", "INTERNALDATE", "RFC822.SIZE"]
+            }
             SyncStrategy::HeadersOnly => {
                 vec!["UID", "FLAGS", "ENVELOPE", "INTERNALDATE", "RFC822.SIZE"]
             }
@@ -635,15 +1913,35 @@ impl SyncEngine {
                 "BODY.PEEK[]",
             ],
         };
+        if client.supports_gmail_labels() {
+            fetch_items.push("X-GM-LABELS");
+        }
 
         self.update_progress_phase(account_id, folder_name, SyncPhase::FetchingBodies)
             .await;
 
-        let messages = client.uid_fetch_messages(&uid_set, &fetch_items).await?;
+        let mut messages = client.uid_fetch_messages(&uid_set, &fetch_items).await?;
+        if text_only {
+            for message in messages.iter_mut() {
+                let Some(uid) = message.uid else {
+                    continue;
+                };
+                match client.uid_fetch_without_html(uid).await {
+                    Ok(raw) => message.body = Some(raw),
+                    Err(e) => warn!("Failed to fetch text parts of UID {}: {}", uid, e),
+                }
+            }
+        }
 
         self.update_progress_phase(account_id, folder_name, SyncPhase::ProcessingChanges)
             .await;
 
+        // Newly arrived INBOX messages go through the filter engine and plugins
+        let apply_filters =
+            matches!(strategy, SyncStrategy::Incremental) && folder_name.eq_ignore_ascii_case("INBOX");
+        let mut actions = ArrivalActions::new();
+        let mut arrivals = Vec::new();
+
         // Process each message
         for message in messages {
             // Check for conflicts if this is an update
@@ -659,19 +1957,336 @@ impl SyncEngine {
                 }
             }
 
-            // Convert and store message
-            let stored_message = StoredMessage::from_imap_message(
-                &message,
-                account_id.to_string(),
-                folder_name.to_string(),
+            let stored_message = self
+                .store_fetched_message(account_id, folder_name, &message)
+                .await?;
+
+            if apply_filters {
+                let result = self.filter_arrival(&stored_message).await;
+                actions.record(stored_message.clone(), &stored_message, result);
+                let results = self.plugin_arrival(account_id, &stored_message).await;
+                actions.record_plugins(stored_message.clone(), &stored_message, results);
+                arrivals.push(stored_message);
+            }
+        }
+
+        self.triage_arrivals(&arrivals).await;
+        if !actions.is_empty() {
+            self.apply_arrival_actions(account_id, client, folder_name, actions)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Store a fetched message along with what its raw source tells about
+    /// security, headers and MIME defects
+    async fn store_fetched_message(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        message: &ImapMessage,
+    ) -> SyncResult<StoredMessage> {
+        let parsed = message.body.as_deref().map(crate::mime::parse_message);
+        let mut stored_message = StoredMessage::from_parsed_imap_message(
+            message,
+            parsed.as_ref(),
+            account_id.to_string(),
+            folder_name.to_string(),
+        );
+        if self.plain_text_accounts.contains(account_id) {
+            stored_message.body_text = plain_text::text_only_body(
+                stored_message.body_text.as_deref(),
+                stored_message.body_html.as_deref(),
             );
+            stored_message.body_html = None;
+        }
+
+        self.database.store_message(&stored_message).await?;
+
+        if let Some(ref raw) = message.body {
+            let mut security = MessageSecurity::from_raw_message(raw, &stored_message.from_addr);
+            if security.signed.is_some() {
+                security.signature = crate::email::crypto::verify(
+                    raw,
+                    &stored_message.from_addr,
+                    self.pgp_keyring.as_deref(),
+                )
+                .await;
+            }
+            if let Err(e) = self
+                .database
+                .store_message_security(stored_message.id, &security)
+                .await
+            {
+                warn!("Failed to store security status for {}: {}", stored_message.id, e);
+            }
+
+            let header_block = raw
+                .split_once("\r\n\r\n")
+                .or_else(|| raw.split_once("\n\n"))
+                .map_or(raw.as_str(), |(headers, _)| headers);
+            if let Err(e) = self
+                .database
+                .store_raw_headers(stored_message.id, header_block)
+                .await
+            {
+                warn!("Failed to store headers for {}: {}", stored_message.id, e);
+            }
+
+            let reply_headers = ReplyHeaders::from_raw_message(raw);
+            if !reply_headers.is_empty() {
+                if let Err(e) = self
+                    .database
+                    .store_reply_headers(stored_message.id, &reply_headers)
+                    .await
+                {
+                    warn!("Failed to store reply headers for {}: {}", stored_message.id, e);
+                }
+            }
+
+            if crate::email::flowed::detect(raw).is_some() {
+                if let Err(e) = self.database.mark_flowed(stored_message.id).await {
+                    warn!("Failed to record flowed body for {}: {}", stored_message.id, e);
+                }
+            }
+        }
+
+        if let Some(parsed) = parsed.filter(|parsed| parsed.is_malformed()) {
+            if let Err(e) = self
+                .database
+                .store_message_defects(stored_message.id, &parsed.defects)
+                .await
+            {
+                warn!("Failed to store MIME defects for {}: {}", stored_message.id, e);
+            }
+        }
+
+        Ok(stored_message)
+    }
+
+    /// Run the filter engine on a newly stored message, header conditions
+    /// included
+    async fn filter_arrival(&self, message: &StoredMessage) -> FilterResult {
+        let headers = match self.database.get_raw_headers(message.id).await {
+            Ok(headers) => headers,
+            Err(e) => {
+                warn!(
+                    "Failed to load headers of {} for filtering: {}",
+                    message.id, e
+                );
+                None
+            }
+        };
+        self.filter_engine
+            .read()
+            .await
+            .process_message_with_headers(message, headers.as_deref())
+    }
+
+    /// Run the email plugins on a newly stored message, leaving out the
+    /// plugins that failed
+    async fn plugin_arrival(&self, account_id: &str, message: &StoredMessage) -> Vec<EmailProcessResult> {
+        let Some(hooks) = self.incoming_email_hooks.read().await.clone() else {
+            return Vec::new();
+        };
+        let context = EmailPluginContext {
+            account_id: account_id.to_string(),
+            folder_name: message.folder_name.clone(),
+            context_data: HashMap::new(),
+        };
+        hooks
+            .run(message, &context)
+            .into_iter()
+            .filter_map(|(plugin_id, result)| match result {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    warn!("Email plugin {} failed on {}: {}", plugin_id, message.id, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Sort newly arrived INBOX messages into tabs, when triage is set up
+    async fn triage_arrivals(&self, messages: &[StoredMessage]) {
+        if messages.is_empty() {
+            return;
+        }
+        let Some(triage) = self.inbox_triage.read().await.clone() else {
+            return;
+        };
+        let categorized = triage.triage(messages).await;
+        debug!("Sorted {} of {} new messages into inbox tabs", categorized, messages.len());
+    }
+
+    /// Save the flags and labels filter rules set on the server locally
+    async fn store_local_flags(
+        &self,
+        updated: HashMap<uuid::Uuid, StoredMessage>,
+    ) -> SyncResult<()> {
+        for message in updated.values() {
+            self.database
+                .update_message_flags(message.id, &message.flags)
+                .await?;
+            self.database
+                .update_message_labels(message.id, &message.labels)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Publish the notifications filter rules asked for, skipping deleted messages
+    async fn notify_arrivals(
+        &self,
+        account_id: &str,
+        messages: Vec<StoredMessage>,
+        deleted: &HashSet<uuid::Uuid>,
+    ) {
+        let Some(manager) = self.notification_manager.read().await.clone() else {
+            return;
+        };
+        for message in messages {
+            if deleted.contains(&message.id) {
+                continue;
+            }
+            manager
+                .notify_new_message(account_id.to_string(), message.folder_name.clone(), message)
+                .await;
+        }
+    }
+
+    /// Carry out the filter engine's and plugins' actions on newly arrived
+    /// messages
+    ///
+    /// Flags and labels are set first so that moved messages keep them.
+    async fn apply_arrival_actions(
+        &self,
+        account_id: &str,
+        client: &mut ImapClient,
+        folder_name: &str,
+        actions: ArrivalActions<StoredMessage>,
+    ) -> SyncResult<()> {
+        let uids = |messages: &[StoredMessage]| -> Vec<u32> {
+            messages.iter().map(|message| message.imap_uid).collect()
+        };
+
+        let mut updated = HashMap::new();
+        for (flag, messages) in &actions.flags {
+            let uid_set = uid_set_string(&uids(messages));
+            let flags = [MessageFlag::from_str(flag)];
+            if let Err(e) = client.uid_store_flags(&uid_set, &flags, false).await {
+                warn!("Failed to set {} on filtered messages: {}", flag, e);
+                continue;
+            }
+            for message in messages {
+                add_local(&mut updated, message, Some(flag), None);
+            }
+        }
+        // Gmail labels where the server has them, keywords elsewhere
+        let gmail = client.supports_gmail_labels();
+        for (label, messages) in &actions.labels {
+            let uid_set = uid_set_string(&uids(messages));
+            let stored = if gmail {
+                client
+                    .uid_store_labels(&uid_set, std::slice::from_ref(label), true)
+                    .await
+            } else {
+                let keyword = [MessageFlag::Custom(label.clone())];
+                client.uid_store_flags(&uid_set, &keyword, false).await
+            };
+            if let Err(e) = stored {
+                warn!("Failed to label filtered messages {}: {}", label, e);
+                continue;
+            }
+            for message in messages {
+                if gmail {
+                    add_local(&mut updated, message, None, Some(label));
+                } else {
+                    add_local(&mut updated, message, Some(label), None);
+                }
+            }
+        }
+        self.store_local_flags(updated).await?;
+
+        let ArrivalActions {
+            delete,
+            moves,
+            notify,
+            ..
+        } = actions;
+        let deleted: HashSet<uuid::Uuid> = delete.iter().map(|message| message.id).collect();
+        self.notify_arrivals(account_id, notify, &deleted).await;
+
+        let delete_uids = uids(&delete);
+        let move_uids: HashMap<String, Vec<u32>> = moves
+            .into_iter()
+            .map(|(folder, messages)| (folder, uids(&messages)))
+            .collect();
+        let mapped_junk = self
+            .special_folders
+            .read()
+            .await
+            .folder(account_id, SpecialFolder::Junk)
+            .map(str::to_string);
+        let spam_folder = if move_uids.is_empty() {
+            None
+        } else if mapped_junk.is_some() {
+            mapped_junk
+        } else {
+            Self::find_spam_folder(client).await
+        };
+        let spam_fallback = self.filter_engine.read().await.sender_lists().spam_folder.clone();
+
+        let mut removed = delete_uids;
+        for (destination, uids) in move_uids {
+            // The blocklist's configured spam folder maps to the server's junk folder
+            let destination = match &spam_folder {
+                Some(junk) if destination.eq_ignore_ascii_case(&spam_fallback) => junk.clone(),
+                _ => destination,
+            };
+            let uid_set = uid_set_string(&uids);
+            if let Err(e) = client.uid_copy_messages(&uid_set, &destination).await {
+                warn!("Failed to move filtered messages to {}: {}", destination, e);
+                continue;
+            }
+            removed.extend(uids);
+        }
 
-            self.database.store_message(&stored_message).await?;
+        if removed.is_empty() {
+            return Ok(());
         }
 
+        client
+            .uid_store_flags(&uid_set_string(&removed), &[MessageFlag::Deleted], false)
+            .await?;
+        client.expunge().await?;
+        self.database
+            .delete_messages_by_uids(account_id, folder_name, &removed)
+            .await?;
+
+        info!(
+            "Removed {} filtered messages from {} for account {}",
+            removed.len(),
+            folder_name,
+            account_id
+        );
         Ok(())
     }
 
+    /// Find the server's junk folder via SPECIAL-USE or its name
+    async fn find_spam_folder(client: &mut ImapClient) -> Option<String> {
+        let folders = match client.list_folders("", "*").await {
+            Ok(folders) => folders,
+            Err(e) => {
+                warn!("Failed to list folders while looking for the spam folder: {}", e);
+                return None;
+            }
+        };
+
+        SpecialFolderMapping::detect(&folders).junk
+    }
+
     /// Resolve message conflicts
     async fn resolve_message_conflict(
         &self,
@@ -720,6 +2335,9 @@ impl SyncEngine {
                 let merged_flags: Vec<String> = server_flags.union(&local_flags).cloned().collect();
                 merged_message.flags = merged_flags;
 
+                // Gmail labels only ever change on the server
+                merged_message.labels = server_message.labels.clone();
+
                 merged_message.updated_at = Utc::now();
                 merged_message.last_synced = Utc::now();
                 merged_message.sync_version += 1;
@@ -746,10 +2364,97 @@ impl SyncEngine {
         Ok(())
     }
 
+    /// Wait out a pause and stop if the sync was cancelled
+    async fn checkpoint(
+        &self,
+        control: &SyncControl,
+        account_id: &str,
+        folder_name: &str,
+    ) -> SyncResult<()> {
+        if control.is_paused() && !control.is_cancelled() {
+            let previous_phase = self
+                .get_folder_sync_progress(account_id, folder_name)
+                .await
+                .map(|progress| progress.phase);
+
+            info!("Sync paused: {} - {}", account_id, folder_name);
+            self.update_progress_phase(account_id, folder_name, SyncPhase::Paused)
+                .await;
+
+            if control.wait_while_paused().await {
+                info!("Sync resumed: {} - {}", account_id, folder_name);
+                if let Some(phase) = previous_phase {
+                    self.update_progress_phase(account_id, folder_name, phase)
+                        .await;
+                }
+            }
+        }
+
+        if control.is_cancelled() {
+            return Err(SyncError::Cancelled(format!(
+                "{} - {}",
+                account_id, folder_name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Pause the running sync for an account
+    ///
+    /// Returns `false` if no sync is running for the account.
+    pub async fn pause_sync(&self, account_id: &str) -> bool {
+        match self.sync_controls.read().await.get(account_id) {
+            Some(control) => {
+                control.pause();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resume a paused sync for an account
+    ///
+    /// Returns `false` if no sync is running for the account.
+    pub async fn resume_sync(&self, account_id: &str) -> bool {
+        match self.sync_controls.read().await.get(account_id) {
+            Some(control) => {
+                control.resume();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check whether the sync for an account is paused
+    pub async fn is_sync_paused(&self, account_id: &str) -> bool {
+        self.sync_controls
+            .read()
+            .await
+            .get(account_id)
+            .map(|control| control.is_paused())
+            .unwrap_or(false)
+    }
+
     /// Update sync progress
-    pub async fn update_progress(&self, progress: SyncProgress) {
+    pub async fn update_progress(&self, mut progress: SyncProgress) {
         let key = format!("{}:{}", progress.account_id, progress.folder_name);
 
+        // Attach the account-wide view when this folder is part of an account sync
+        if let Some(account) = self
+            .account_progress
+            .write()
+            .await
+            .get_mut(&progress.account_id)
+        {
+            account.record_folder_progress(
+                &progress.folder_name,
+                progress.messages_processed,
+                progress.total_messages,
+            );
+            progress.account_progress = Some(account.clone());
+        }
+
         {
             let mut progress_map = self.sync_progress.write().await;
             progress_map.insert(key, progress.clone());
@@ -784,15 +2489,7 @@ impl SyncEngine {
             progress.total_messages = total;
 
             // Estimate completion time
-            if processed > 0 {
-                let elapsed = Utc::now().signed_duration_since(progress.started_at);
-                let rate = processed as f64 / elapsed.num_seconds() as f64;
-                if rate > 0.0 {
-                    let remaining_seconds = (total - processed) as f64 / rate;
-                    progress.estimated_completion =
-                        Some(Utc::now() + ChronoDuration::seconds(remaining_seconds as i64));
-                }
-            }
+            progress.estimated_completion = progress.estimate_completion(Utc::now());
 
             self.update_progress(progress).await;
         }
@@ -813,10 +2510,17 @@ impl SyncEngine {
         self.sync_progress.read().await.get(&key).cloned()
     }
 
-    /// Cancel sync for a folder
+    /// Cancel the running sync for a folder's account
+    ///
+    /// The sync stops at its next checkpoint; messages already stored are kept and
+    /// an interrupted full sync resumes from where it stopped next time.
     pub async fn cancel_sync(&self, account_id: &str, folder_name: &str) -> SyncResult<()> {
         let key = format!("{}:{}", account_id, folder_name);
 
+        if let Some(control) = self.sync_controls.write().await.remove(account_id) {
+            control.cancel();
+        }
+
         // Remove from progress tracking
         {
             let mut progress_map = self.sync_progress.write().await;
@@ -826,8 +2530,6 @@ impl SyncEngine {
             }
         }
 
-        // The actual cancellation would need to be handled by the sync task
-        // This is a simplified implementation
         warn!(
             "Sync cancellation requested for {}:{}",
             account_id, folder_name
@@ -888,6 +2590,7 @@ mod tests {
             bytes_downloaded: 0,
             started_at: Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         };
 
         sync_engine.update_progress(progress.clone()).await;
@@ -905,4 +2608,241 @@ mod tests {
         assert!(stored_progress.is_some());
         assert_eq!(stored_progress.unwrap().total_messages, 100);
     }
+
+    #[test]
+    fn test_progress_rate_and_eta() {
+        let started_at = Utc::now();
+        let progress = SyncProgress {
+            account_id: "test-account".to_string(),
+            folder_name: "INBOX".to_string(),
+            phase: SyncPhase::FetchingBodies,
+            messages_processed: 50,
+            total_messages: 200,
+            bytes_downloaded: 0,
+            started_at,
+            estimated_completion: None,
+            account_progress: None,
+        };
+
+        assert_eq!(progress.percent(), 25.0);
+
+        // No elapsed time yet: no rate, no ETA instead of dividing by zero
+        assert_eq!(progress.messages_per_second(started_at), 0.0);
+        assert!(progress.estimate_completion(started_at).is_none());
+
+        let now = started_at + ChronoDuration::seconds(10);
+        assert_eq!(progress.messages_per_second(now), 5.0);
+        assert_eq!(
+            progress.estimate_completion(now),
+            Some(now + ChronoDuration::seconds(30))
+        );
+    }
+
+    #[test]
+    fn test_account_progress_weighted_by_message_count() {
+        let folders = vec![
+            ("INBOX".to_string(), 900),
+            ("Sent".to_string(), 100),
+            ("Drafts".to_string(), 0),
+        ];
+        let mut account = AccountSyncProgress::new("test-account".to_string(), &folders);
+
+        assert_eq!(account.total_messages, 1000);
+        assert_eq!(account.current_folder, "INBOX");
+        assert_eq!(account.folders_remaining(), 2);
+
+        account.record_folder_progress("INBOX", 450, 900);
+        assert_eq!(account.percent(), 45.0);
+
+        // Progress for another folder doesn't affect the current one
+        account.record_folder_progress("Sent", 100, 100);
+        assert_eq!(account.messages_completed(), 450);
+
+        account.advance(Some(("Sent", 100)));
+        assert_eq!(account.current_folder, "Sent");
+        assert_eq!(account.percent(), 90.0);
+        assert_eq!(account.folders_remaining(), 1);
+
+        // The real folder size replaces the count from the folder list
+        account.record_folder_progress("Sent", 0, 300);
+        assert_eq!(account.total_messages, 1200);
+        assert_eq!(account.percent(), 75.0);
+
+        let now = account.started_at + ChronoDuration::seconds(9);
+        assert_eq!(account.messages_per_second(now), 100.0);
+        assert_eq!(
+            account.estimated_remaining(now),
+            Some(ChronoDuration::seconds(3))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_progress_attaches_account_progress() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("account_progress_test.db");
+        let db = Arc::new(EmailDatabase::new(db_path.to_str().unwrap()).await.unwrap());
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let sync_engine = SyncEngine::new(db, sender);
+
+        let folders = vec![("INBOX".to_string(), 100), ("Archive".to_string(), 300)];
+        sync_engine.account_progress.write().await.insert(
+            "test-account".to_string(),
+            AccountSyncProgress::new("test-account".to_string(), &folders),
+        );
+
+        sync_engine
+            .update_progress(SyncProgress {
+                account_id: "test-account".to_string(),
+                folder_name: "INBOX".to_string(),
+                phase: SyncPhase::FetchingBodies,
+                messages_processed: 40,
+                total_messages: 100,
+                bytes_downloaded: 0,
+                started_at: Utc::now(),
+                estimated_completion: None,
+                account_progress: None,
+            })
+            .await;
+
+        let received = receiver.recv().await.unwrap();
+        let account = received.account_progress.unwrap();
+        assert_eq!(account.current_folder, "INBOX");
+        assert_eq!(account.messages_completed(), 40);
+        assert_eq!(account.percent(), 10.0);
+        assert_eq!(account.folders_remaining(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sync_control_pause_resume_cancel() {
+        let control = SyncControl::new();
+        assert!(!control.is_paused());
+        assert!(control.wait_while_paused().await);
+
+        control.pause();
+        assert!(control.is_paused());
+
+        let waiter = {
+            let control = control.clone();
+            tokio::spawn(async move { control.wait_while_paused().await })
+        };
+        sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        control.resume();
+        assert!(waiter.await.unwrap());
+
+        control.pause();
+        let waiter = {
+            let control = control.clone();
+            tokio::spawn(async move { control.wait_while_paused().await })
+        };
+        control.cancel();
+        assert!(!waiter.await.unwrap());
+        assert!(control.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_at_checkpoint() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("cancel_test.db");
+        let db = Arc::new(EmailDatabase::new(db_path.to_str().unwrap()).await.unwrap());
+
+        let (sender, _receiver) = mpsc::unbounded_channel();
+        let sync_engine = SyncEngine::new(db, sender);
+
+        // Nothing is running yet
+        assert!(!sync_engine.pause_sync("test-account").await);
+
+        let control = SyncControl::new();
+        sync_engine
+            .sync_controls
+            .write()
+            .await
+            .insert("test-account".to_string(), control.clone());
+
+        assert!(sync_engine.pause_sync("test-account").await);
+        assert!(sync_engine.is_sync_paused("test-account").await);
+        assert!(sync_engine.resume_sync("test-account").await);
+        assert!(sync_engine
+            .checkpoint(&control, "test-account", "INBOX")
+            .await
+            .is_ok());
+
+        sync_engine.cancel_sync("test-account", "INBOX").await.unwrap();
+        assert!(matches!(
+            sync_engine.checkpoint(&control, "test-account", "INBOX").await,
+            Err(SyncError::Cancelled(_))
+        ));
+        assert!(!sync_engine.resume_sync("test-account").await);
+    }
+
+    #[test]
+    fn test_plugin_results_become_arrival_actions() {
+        let now = Utc::now();
+        let message = StoredMessage {
+            id: uuid::Uuid::new_v4(),
+            account_id: "work".to_string(),
+            folder_name: "INBOX".to_string(),
+            imap_uid: 7,
+            message_id: None,
+            thread_id: None,
+            in_reply_to: None,
+            references: Vec::new(),
+            subject: "Your invoice".to_string(),
+            from_addr: "billing@example.com".to_string(),
+            from_name: None,
+            to_addrs: vec!["me@example.com".to_string()],
+            cc_addrs: Vec::new(),
+            bcc_addrs: Vec::new(),
+            reply_to: None,
+            date: now,
+            body_text: None,
+            body_html: None,
+            attachments: Vec::new(),
+            flags: vec!["\\Seen".to_string()],
+            labels: Vec::new(),
+            size: None,
+            priority: None,
+            created_at: now,
+            updated_at: now,
+            last_synced: now,
+            sync_version: 0,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        };
+        let mut tagged = message.clone();
+        tagged.labels.push("Invoice".to_string());
+        tagged.flags.push("\\Flagged".to_string());
+
+        let mut actions = ArrivalActions::new();
+        actions.record_plugins(
+            message.imap_uid,
+            &message,
+            vec![
+                EmailProcessResult::NoChange,
+                EmailProcessResult::Modified(tagged),
+                EmailProcessResult::Move("Receipts".to_string()),
+            ],
+        );
+        assert_eq!(actions.labels.get("Invoice"), Some(&vec![7]));
+        assert_eq!(actions.flags.len(), 1);
+        assert_eq!(actions.flags.get(&MessageFlag::Flagged.to_string()), Some(&vec![7]));
+        assert_eq!(actions.moves.get("Receipts"), Some(&vec![7]));
+        assert!(actions.delete.is_empty());
+
+        let mut actions = ArrivalActions::new();
+        actions.record_plugins(7, &message, vec![EmailProcessResult::Delete]);
+        assert_eq!(actions.delete, vec![7]);
+    }
+
+    #[test]
+    fn test_cancelled_status_round_trip() {
+        let status = SyncStatus::Cancelled { resume_uid: 4242 };
+        let json = serde_json::to_string(&status).unwrap();
+        let restored: SyncStatus = serde_json::from_str(&json).unwrap();
+        assert!(matches!(restored, SyncStatus::Cancelled { resume_uid: 4242 }));
+    }
 }
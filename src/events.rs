@@ -1,12 +1,16 @@
 use crate::keyboard::{KeyboardAction, KeyboardManager};
 use crate::tea::message::ViewMode;
+use crate::ui::passphrase_prompt::PassphraseAction;
+use crate::ui::quick_reply::QuickReplyAction;
 use crate::ui::{ComposeAction, DraftAction, FocusedPane, UIMode, UI};
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use chrono::Datelike;
 
 pub struct EventHandler {
     should_quit: bool,
     keyboard_manager: KeyboardManager,
+    /// First key of a two-key sequence waiting for its second key
+    pending_sequence: Option<char>,
 }
 
 /// Result of handling a key event
@@ -33,21 +37,70 @@ pub enum EventResult {
     ContactQuickActions(String), // Show quick actions menu for email address
     ReplyToMessage(uuid::Uuid), // Message ID to reply to
     ReplyAllToMessage(uuid::Uuid), // Message ID to reply all to
+    ReplyToListMessage(uuid::Uuid), // Message ID to reply to the list of
+    StartQuickReply, // Open the quick reply box under the viewed message
+    ViewInvitation, // Open the meeting invitation in the viewed message
+    InvitationAction(crate::ui::InvitationAction), // Act on the invitation being viewed
+    SendQuickReply, // Send the reply typed in the quick reply box
+    ExpandQuickReply, // Move the quick reply into a full reply compose
+    DecryptMessage(String), // Passphrase to decrypt the viewed message with
     ForwardMessage(uuid::Uuid), // Message ID to forward
     DeleteEmail(String, uuid::Uuid, String), // Account ID, Message ID, Folder
+    DeleteMessages(String, Vec<uuid::Uuid>), // Account ID, Message IDs in any folder
     ArchiveEmail(String, uuid::Uuid, String), // Account ID, Message ID, Folder
+    BlockSender(uuid::Uuid), // Message ID whose sender is blocked
+    SnoozeMessage(String, uuid::Uuid, chrono::DateTime<chrono::Utc>), // Account ID, Message ID, until
+    WakeSnoozed(uuid::Uuid), // Message ID to bring back from Snoozed now
+    SwitchInboxTab(crate::email::InboxTab), // INBOX tab to show
+    MoveToInboxTab(uuid::Uuid, crate::email::InboxTab), // Message ID whose sender's mail to move, tab
+    ReportSpam(String, Vec<uuid::Uuid>, String), // Account ID, Message IDs, Folder
+    ReportNotSpam(String, Vec<uuid::Uuid>, String), // Account ID, Message IDs, Folder
     MarkEmailRead(String, uuid::Uuid, String), // Account ID, Message ID, Folder
     MarkEmailUnread(String, uuid::Uuid, String), // Account ID, Message ID, Folder
+    SetThreadRead(String, String, String, bool), // Account ID, Folder, Thread ID, read
+    MarkAllRead(String, Option<String>), // Account ID, Folder (None: whole account)
+    UndoMarkAllRead, // Cancel the running mark-all-read, or undo the last one
+    UndoSend, // Take the message waiting in the undo-send window back into compose
+    SaveFolderSort(String, String, crate::email::SortCriteria), // Account ID, Folder, sort chosen for it
+    ServerSearch(String, String, String), // Account ID, Folder, query to search for on the server
+    SaveAttachment, // Save the selected attachment, or cancel the download in progress
+    Confirmed(crate::ui::confirm_dialog::ConfirmAction), // Action the user confirmed
     ToggleEmailFlag(String, uuid::Uuid, String), // Account ID, Message ID, Folder
     CreateEvent(String), // Calendar ID
     EditEvent(String, String), // Calendar ID, Event ID
     DeleteEvent(String, String), // Calendar ID, Event ID
+    EventFormAction(crate::calendar::EventFormAction), // Save, delete or conflict choice from the event form
     ViewEventDetails(String, String), // Calendar ID, Event ID
+    NudgeAttendees(String), // Event ID
+    SnoozeReminder,
+    ShowSms,
+    ReplyToSms, // Reply to the conversation of the last SMS notification
+    SmsKey(char), // Key for the SMS screen, in the characters it reads
+    RefreshTodayPanel,
+    ToggleUnifiedView,
+    ToggleLargestMessages(Option<String>), // Folder (None: whole account)
+    LockScreen,
+    ShowAccountDetails(String), // Account ID
+    SetCapabilityDisabled(String, String, bool), // Account ID, capability, disabled
+    ShowSpecialFolders(String), // Account ID
+    SetSpecialFolders(String, crate::email::SpecialFolderMapping), // Account ID, mapped folders
+    ShowRetention,
+    AcceptRetentionPreview,
+    RestoreRetained(uuid::Uuid), // Message ID pending removal
+    ShowFilterRules,
+    TestFilterRule(uuid::Uuid), // Filter ID, tried on the current folder
+    ToggleFilterRule(uuid::Uuid), // Filter ID
+    ShowMboxWizard,
+    MboxWizardAction(crate::ui::mbox_wizard::MboxWizardAction), // Scan, import, export or cancel
     CreateTodo(String), // Calendar ID
     ToggleTodoComplete(String, String), // Calendar ID, Event ID
     RetryInitialization, // Retry failed initialization
     CancelBackgroundTask, // Cancel selected background task
     AISummarizeEmail(uuid::Uuid), // Message ID to summarize with AI
+    AISummarizeThread(uuid::Uuid), // Message ID whose thread to summarize with AI
+    AIDraftReply(uuid::Uuid), // Message ID to draft a reply to with AI
+    ToggleSyncPause(String), // Account ID whose sync to pause or resume
+    CancelSync(String, String), // Account ID, Folder
 }
 
 impl EventHandler {
@@ -56,6 +109,7 @@ impl EventHandler {
         Self {
             should_quit: false,
             keyboard_manager: KeyboardManager::default(),
+            pending_sequence: None,
         }
     }
 
@@ -86,6 +140,14 @@ impl EventHandler {
             return self.handle_ai_popup_keys(key, ui);
         }
         
+        // The quick reply box takes typed text, so it comes before the help keys
+        if ui.mode() == &UIMode::EmailViewer && ui.email_viewer().quick_reply().is_some() {
+            return self.handle_quick_reply_keys(key, ui);
+        }
+        if ui.mode() == &UIMode::EmailViewer && ui.email_viewer().passphrase_prompt().is_some() {
+            return self.handle_passphrase_keys(key, ui);
+        }
+
         // Handle global help overlay first (works in all modes)
         if self.handle_help_keys(key, ui) {
             return EventResult::Continue;
@@ -93,7 +155,7 @@ impl EventHandler {
 
         // Handle compose mode separately (these use different input handling)
         if ui.mode() == &UIMode::Compose {
-            if let Some(action) = ui.handle_compose_key(key.code).await {
+            if let Some(action) = ui.handle_compose_key(key).await {
                 return EventResult::ComposeAction(action);
             }
             return EventResult::Continue;
@@ -107,6 +169,14 @@ impl EventHandler {
             return EventResult::Continue;
         }
 
+        // Handle the event create/edit form
+        if ui.is_event_form_visible() {
+            if let Some(action) = ui.handle_event_form_key(key).await {
+                return EventResult::EventFormAction(action);
+            }
+            return EventResult::Continue;
+        }
+
         // Handle attachment viewer mode
         if ui.focused_pane() == FocusedPane::ContentPreview
             && ui.content_preview().is_viewing_attachment()
@@ -114,11 +184,30 @@ impl EventHandler {
             return self.handle_attachment_viewer_keys(key, ui).await;
         }
 
+        // Ctrl+S while searching the message list searches the folder on the server too
+        if key.code == KeyCode::Char('s')
+            && key.modifiers.contains(KeyModifiers::CONTROL)
+            && ui.focused_pane() == FocusedPane::MessageList
+        {
+            if let Some((account_id, folder, query)) = ui.message_list().server_search_target() {
+                return EventResult::ServerSearch(account_id, folder, query);
+            }
+        }
+
         // Handle text input modes (search, folder search)
         if self.handle_text_input_modes(key, ui) {
             return EventResult::Continue;
         }
 
+        // While a sent message waits out its undo window, u takes it back
+        if ui.has_held_compose()
+            && ui.mode() == &UIMode::Normal
+            && key.code == KeyCode::Char('u')
+            && key.modifiers.is_empty()
+        {
+            return EventResult::UndoSend;
+        }
+
         // Handle enhanced progress overlay keys with high priority (before keyboard manager)
         if ui.enhanced_progress_overlay().is_visible() {
             match key.code {
@@ -157,12 +246,77 @@ impl EventHandler {
             }
         }
 
+        // Handle sync progress overlay keys (pause/resume/cancel the selected sync)
+        if ui.is_sync_progress_visible() {
+            match key.code {
+                KeyCode::Esc => {
+                    ui.toggle_sync_progress_overlay();
+                    return EventResult::Continue;
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    ui.sync_progress_previous();
+                    return EventResult::Continue;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    ui.sync_progress_next();
+                    return EventResult::Continue;
+                }
+                KeyCode::Char('p') | KeyCode::Char(' ') => {
+                    if let Some(progress) = ui.selected_sync_progress() {
+                        return EventResult::ToggleSyncPause(progress.account_id.clone());
+                    }
+                    return EventResult::Continue;
+                }
+                KeyCode::Char('x') => {
+                    if let Some(progress) = ui.selected_sync_progress() {
+                        return EventResult::CancelSync(
+                            progress.account_id.clone(),
+                            progress.folder_name.clone(),
+                        );
+                    }
+                    return EventResult::Continue;
+                }
+                _ => {} // Fall through to keyboard manager for other keys
+            }
+        }
+
+        // Two-key sequences such as `gi` apply while reading mail
+        if matches!(ui.mode(), UIMode::Normal | UIMode::EmailViewer) {
+            if let Some(first) = self.pending_sequence.take() {
+                // An unknown second key cancels the sequence
+                if let KeyCode::Char(second) = key.code {
+                    if let Some(action) = self.keyboard_manager.get_sequence_action(first, second) {
+                        return self.execute_keyboard_action(action.clone(), ui).await;
+                    }
+                }
+                return EventResult::Continue;
+            }
+            if let KeyCode::Char(c) = key.code {
+                if key.modifiers == KeyModifiers::NONE && self.keyboard_manager.is_sequence_prefix(c) {
+                    self.pending_sequence = Some(c);
+                    return EventResult::Continue;
+                }
+            }
+        }
+
         // Handle mode-specific keys BEFORE keyboard manager to avoid conflicts
         let mode_result = match ui.mode() {
             UIMode::EmailViewer => self.handle_email_viewer_keys(key, ui).await,
             UIMode::KeyboardShortcuts => self.handle_keyboard_shortcuts_keys(key, ui).await,
             UIMode::Settings => self.handle_settings_keys(key, ui).await,
             UIMode::ContactsPopup => self.handle_contacts_popup_keys(key, ui).await,
+            // Modal: nothing falls through to the global shortcuts
+            UIMode::AccountDetails => return self.handle_account_details_keys(key, ui).await,
+            UIMode::SpecialFolders => return self.handle_special_folders_keys(key, ui),
+            UIMode::Retention => return self.handle_retention_keys(key, ui).await,
+            UIMode::FilterRules => return self.handle_filter_rules_keys(key, ui),
+            UIMode::MboxWizard => return self.handle_mbox_wizard_keys(key, ui),
+            UIMode::Headers => return self.handle_headers_panel_keys(key, ui),
+            UIMode::Confirm => return self.handle_confirm_keys(key, ui),
+            UIMode::Links => return self.handle_links_panel_keys(key, ui).await,
+            UIMode::Snooze => return self.handle_snooze_keys(key, ui),
+            UIMode::Sms => return self.handle_sms_keys(key, ui).await,
+            UIMode::InvitationViewer => return self.handle_invitation_viewer_keys(key, ui),
             _ => EventResult::Continue,
         };
         
@@ -329,6 +483,39 @@ impl EventHandler {
                 self.handle_move_up(ui);
                 EventResult::Continue
             }
+            KeyboardAction::GoToInbox | KeyboardAction::GoToStarred => {
+                let inbox = action == KeyboardAction::GoToInbox;
+                let path = ui
+                    .folder_tree()
+                    .find_folder(|folder| {
+                        if inbox {
+                            folder.folder_type == crate::ui::folder_tree::FolderType::Inbox
+                                && folder.path != crate::ui::folder_tree::ALL_INBOXES
+                        } else {
+                            let name = folder.name.to_lowercase();
+                            name == "starred" || name == "flagged"
+                        }
+                    })
+                    .map(|folder| folder.path.clone());
+
+                match path {
+                    Some(path) => {
+                        if ui.mode() == &UIMode::EmailViewer {
+                            ui.exit_email_viewer();
+                        }
+                        ui.folder_tree_mut().select_folder_by_path(&path);
+                        EventResult::FolderSelect(path)
+                    }
+                    None => {
+                        ui.show_toast_info(if inbox {
+                            "No inbox in the folder list"
+                        } else {
+                            "No Starred folder on this account"
+                        });
+                        EventResult::Continue
+                    }
+                }
+            }
 
             // Selection and interaction
             KeyboardAction::Select => self.handle_select(ui),
@@ -379,6 +566,19 @@ impl EventHandler {
                     EventResult::Continue
                 }
             }
+            KeyboardAction::ReplyToList => {
+                let message_id = if ui.mode() == &UIMode::EmailViewer {
+                    ui.email_viewer().get_message_id()
+                } else if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                    ui.message_list().selected_message().and_then(|message| message.message_id)
+                } else {
+                    None
+                };
+                match message_id {
+                    Some(message_id) => EventResult::ReplyToListMessage(message_id),
+                    None => EventResult::Continue,
+                }
+            }
             KeyboardAction::ReplyAllEmail => {
                 if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
                     if let Some(message) = ui.message_list().selected_message() {
@@ -410,7 +610,20 @@ impl EventHandler {
                 }
             }
             KeyboardAction::DeleteEmail => {
-                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                // Marked messages, and anything in the largest-messages view where
+                // messages come from several folders, are deleted in one batch
+                let message_list = ui.message_list();
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview)
+                    && (message_list.has_marks() || message_list.largest_scope().is_some())
+                {
+                    let message_ids = message_list.action_targets();
+                    match message_list.current_account() {
+                        Some(account_id) if !message_ids.is_empty() => {
+                            EventResult::DeleteMessages(account_id.clone(), message_ids)
+                        }
+                        _ => EventResult::Continue,
+                    }
+                } else if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
                     if let Some(message) = ui.message_list().selected_message() {
                         if let (Some(message_id), Some(account_id), Some(folder)) = (
                             &message.message_id,
@@ -431,6 +644,104 @@ impl EventHandler {
                     EventResult::Continue
                 }
             }
+            KeyboardAction::BlockSender => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                    if let Some(message_id) = ui.message_list().selected_message().and_then(|m| m.message_id) {
+                        EventResult::BlockSender(message_id)
+                    } else {
+                        EventResult::Continue
+                    }
+                } else {
+                    EventResult::Continue
+                }
+            }
+            KeyboardAction::SnoozeMessage => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                    let list = ui.message_list();
+                    let selected = list
+                        .selected_message()
+                        .and_then(|m| Some((m.message_id?, m.subject.clone())));
+                    match (selected, list.current_account().cloned()) {
+                        // In the Snoozed folder the same key brings the message back now
+                        (Some((message_id, _)), Some(_)) if list.is_snoozed_view() => {
+                            EventResult::WakeSnoozed(message_id)
+                        }
+                        (Some((message_id, subject)), Some(account_id)) => {
+                            ui.show_snooze_picker(crate::ui::snooze_picker::SnoozePicker::new(
+                                account_id, message_id, subject,
+                            ));
+                            EventResult::Continue
+                        }
+                        _ => EventResult::Continue,
+                    }
+                } else {
+                    EventResult::Continue
+                }
+            }
+            KeyboardAction::NextInboxTab | KeyboardAction::PreviousInboxTab => {
+                let list = ui.message_list();
+                if !matches!(ui.focused_pane(), FocusedPane::MessageList) || !list.shows_inbox_tabs() {
+                    return EventResult::Continue;
+                }
+                let tab = if action == KeyboardAction::NextInboxTab {
+                    list.inbox_tab().next()
+                } else {
+                    list.inbox_tab().previous()
+                };
+                EventResult::SwitchInboxTab(tab)
+            }
+            KeyboardAction::MoveToPrimaryTab
+            | KeyboardAction::MoveToPromotionsTab
+            | KeyboardAction::MoveToSocialTab
+            | KeyboardAction::MoveToUpdatesTab
+            | KeyboardAction::MoveToForumsTab => {
+                let list = ui.message_list();
+                if !matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview)
+                    || !list.shows_inbox_tabs()
+                {
+                    return EventResult::Continue;
+                }
+                let tab = match action {
+                    KeyboardAction::MoveToPromotionsTab => crate::email::InboxTab::Promotions,
+                    KeyboardAction::MoveToSocialTab => crate::email::InboxTab::Social,
+                    KeyboardAction::MoveToUpdatesTab => crate::email::InboxTab::Updates,
+                    KeyboardAction::MoveToForumsTab => crate::email::InboxTab::Forums,
+                    _ => crate::email::InboxTab::Primary,
+                };
+                match list.selected_message().and_then(|m| m.message_id) {
+                    Some(message_id) => EventResult::MoveToInboxTab(message_id, tab),
+                    None => EventResult::Continue,
+                }
+            }
+            KeyboardAction::ToggleMessageMark => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList) {
+                    let marked = ui.message_list_mut().toggle_mark_selected();
+                    tracing::debug!("{} messages marked", marked);
+                    // Move on so several messages can be marked in a row
+                    ui.message_list_mut().handle_down();
+                }
+                EventResult::Continue
+            }
+            KeyboardAction::ReportSpam | KeyboardAction::ReportNotSpam => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                    let message_ids = ui.message_list().action_targets();
+                    if let (false, Some(account_id), Some(folder)) = (
+                        message_ids.is_empty(),
+                        ui.message_list().current_account(),
+                        ui.message_list().current_folder(),
+                    ) {
+                        if action == KeyboardAction::ReportSpam {
+                            EventResult::ReportSpam(account_id.clone(), message_ids, folder.clone())
+                        } else {
+                            EventResult::ReportNotSpam(account_id.clone(), message_ids, folder.clone())
+                        }
+                    } else {
+                        EventResult::Continue
+                    }
+                } else {
+                    EventResult::Continue
+                }
+            }
             KeyboardAction::ArchiveEmail => {
                 if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
                     if let Some(message) = ui.message_list().selected_message() {
@@ -494,6 +805,53 @@ impl EventHandler {
                     EventResult::Continue
                 }
             }
+            KeyboardAction::MarkAllRead => match (ui.focused_pane(), ui.get_current_account_id()) {
+                (FocusedPane::AccountSwitcher, Some(account_id)) => {
+                    EventResult::MarkAllRead(account_id.clone(), None)
+                }
+                (FocusedPane::FolderTree, Some(account_id)) => {
+                    match ui.folder_tree().selected_folder().filter(|f| !f.is_virtual()) {
+                        Some(folder) => {
+                            EventResult::MarkAllRead(account_id.clone(), Some(folder.path.clone()))
+                        }
+                        None => EventResult::Continue,
+                    }
+                }
+                (FocusedPane::MessageList, _) => {
+                    let message_list = ui.message_list();
+                    match (message_list.current_account(), message_list.current_folder()) {
+                        (Some(account_id), Some(folder)) => {
+                            EventResult::MarkAllRead(account_id.clone(), Some(folder.clone()))
+                        }
+                        _ => EventResult::Continue,
+                    }
+                }
+                _ => EventResult::Continue,
+            },
+            KeyboardAction::UndoMarkAllRead => EventResult::UndoMarkAllRead,
+            KeyboardAction::MarkThreadRead | KeyboardAction::MarkThreadUnread => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                    let message_list = ui.message_list();
+                    match (
+                        message_list.selected_thread_id(),
+                        message_list.current_account(),
+                        message_list.current_folder(),
+                    ) {
+                        (Some(thread_id), Some(account_id), Some(folder)) => EventResult::SetThreadRead(
+                            account_id.clone(),
+                            folder.clone(),
+                            thread_id,
+                            action == KeyboardAction::MarkThreadRead,
+                        ),
+                        _ => {
+                            tracing::debug!("Thread read state needs the threaded view");
+                            EventResult::Continue
+                        }
+                    }
+                } else {
+                    EventResult::Continue
+                }
+            }
             KeyboardAction::NextMessage => {
                 if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
                     ui.message_list_mut().handle_down();
@@ -520,6 +878,23 @@ impl EventHandler {
                     EventResult::Continue
                 }
             }
+            KeyboardAction::ShowAccountDetails => {
+                if let Some(account_id) = ui.account_switcher().get_current_account_id() {
+                    EventResult::ShowAccountDetails(account_id.clone())
+                } else {
+                    EventResult::Continue
+                }
+            }
+            KeyboardAction::ShowSpecialFolders => {
+                if let Some(account_id) = ui.account_switcher().get_current_account_id() {
+                    EventResult::ShowSpecialFolders(account_id.clone())
+                } else {
+                    EventResult::Continue
+                }
+            }
+            KeyboardAction::ShowRetention => EventResult::ShowRetention,
+            KeyboardAction::ShowFilterRules => EventResult::ShowFilterRules,
+            KeyboardAction::ShowMboxWizard => EventResult::ShowMboxWizard,
             KeyboardAction::RefreshAccount => {
                 if matches!(ui.focused_pane(), FocusedPane::AccountSwitcher) {
                     if let Some(account_id) = ui.account_switcher().get_current_account_id() {
@@ -557,6 +932,28 @@ impl EventHandler {
                 }
                 EventResult::Continue
             }
+            KeyboardAction::LockScreen => EventResult::LockScreen,
+            KeyboardAction::ToggleUnifiedView => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::FolderTree) {
+                    EventResult::ToggleUnifiedView
+                } else {
+                    EventResult::Continue
+                }
+            }
+            KeyboardAction::ToggleLargestMessages => match ui.focused_pane() {
+                // The selected folder from the tree, the whole account from the list
+                FocusedPane::FolderTree => match ui.folder_tree().selected_folder() {
+                    Some(folder) => EventResult::ToggleLargestMessages(Some(folder.path.clone())),
+                    None => EventResult::Continue,
+                },
+                FocusedPane::MessageList => EventResult::ToggleLargestMessages(
+                    ui.message_list()
+                        .largest_scope()
+                        .flatten()
+                        .map(str::to_string),
+                ),
+                _ => EventResult::Continue,
+            },
             KeyboardAction::ExpandThread => {
                 if let FocusedPane::MessageList = ui.focused_pane() {
                     ui.message_list_mut().expand_selected_thread();
@@ -575,12 +972,78 @@ impl EventHandler {
                 }
                 EventResult::Continue
             }
+            KeyboardAction::ToggleReflow => {
+                if let FocusedPane::ContentPreview = ui.focused_pane() {
+                    let reflow = ui.content_preview_mut().toggle_reflow();
+                    ui.show_toast_info(if reflow { "Reflow on" } else { "Reflow off" });
+                }
+                EventResult::Continue
+            }
             KeyboardAction::ToggleHeaders => {
                 if let FocusedPane::ContentPreview = ui.focused_pane() {
                     ui.content_preview_mut().toggle_headers();
                 }
                 EventResult::Continue
             }
+            KeyboardAction::RenderFullMessage => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview)
+                    && ui.content_preview().is_truncated()
+                {
+                    if let Err(e) = ui.content_preview_mut().render_full_message().await {
+                        tracing::error!("Failed to render full message: {}", e);
+                        ui.show_toast_error(format!("Failed to render full message: {}", e));
+                    }
+                }
+                EventResult::Continue
+            }
+            KeyboardAction::OpenInBrowser => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview)
+                    && ui.content_preview().current_message_id().is_some()
+                {
+                    match ui.content_preview().open_in_browser(ui.current_theme()).await {
+                        Ok(()) => ui.show_toast_info("Opened message in browser"),
+                        Err(e) => {
+                            tracing::error!("Failed to open message in browser: {}", e);
+                            ui.show_toast_error(format!("Failed to open in browser: {}", e));
+                        }
+                    }
+                }
+                EventResult::Continue
+            }
+            KeyboardAction::ShowLinks => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                    if let Some(email) = ui.content_preview().get_email_content() {
+                        let panel = crate::ui::links_panel::LinksPanel::new(&email.parsed_urls);
+                        if panel.is_empty() {
+                            ui.show_toast_info("No links in this message");
+                        } else {
+                            ui.show_links_panel(panel);
+                        }
+                    }
+                }
+                EventResult::Continue
+            }
+            KeyboardAction::ShowHeadersPanel => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                    if let Some(subject) = ui
+                        .content_preview()
+                        .get_email_content()
+                        .map(|email| email.headers.subject.clone())
+                    {
+                        let report = ui.content_preview().load_header_report().await;
+                        ui.show_headers_panel(crate::ui::headers_panel::HeadersPanel::new(subject, report));
+                    }
+                }
+                EventResult::Continue
+            }
+            KeyboardAction::ToggleTodayPanel => {
+                ui.toggle_today_panel();
+                if ui.is_today_panel_visible() {
+                    EventResult::RefreshTodayPanel
+                } else {
+                    EventResult::Continue
+                }
+            }
             KeyboardAction::OpenEmailViewer => {
                 tracing::debug!("🔍 OpenEmailViewer action triggered! Current pane: {:?}", ui.focused_pane());
                 // Open email popup viewer for reply/forward/edit actions
@@ -623,6 +1086,8 @@ impl EventHandler {
                                     priority: None,
                                     is_draft: false,
                                     is_deleted: false,
+                                    snoozed_until: None,
+                                    inbox_tab: None,
                                     reply_to: email_content.headers.reply_to.clone(),
                                     message_id: Some(email_content.headers.message_id.clone()),
                                     thread_id: None,
@@ -648,29 +1113,27 @@ impl EventHandler {
 
             // Sorting
             KeyboardAction::SortByDate => {
-                if let FocusedPane::MessageList = ui.focused_pane() {
-                    use crate::email::{SortCriteria, SortOrder};
-                    ui.message_list_mut()
-                        .set_sort_criteria(SortCriteria::Date(SortOrder::Descending));
-                }
-                EventResult::Continue
+                use crate::email::{SortCriteria, SortOrder};
+                self.handle_sort(ui, |list| list.set_sort_criteria(SortCriteria::Date(SortOrder::Descending)))
             }
             KeyboardAction::SortBySender => {
-                if let FocusedPane::MessageList = ui.focused_pane() {
-                    use crate::email::{SortCriteria, SortOrder};
-                    ui.message_list_mut()
-                        .set_sort_criteria(SortCriteria::Sender(SortOrder::Ascending));
-                }
-                EventResult::Continue
+                use crate::email::{SortCriteria, SortOrder};
+                self.handle_sort(ui, |list| list.set_sort_criteria(SortCriteria::Sender(SortOrder::Ascending)))
             }
             KeyboardAction::SortBySubject => {
-                if let FocusedPane::MessageList = ui.focused_pane() {
-                    use crate::email::{SortCriteria, SortOrder};
-                    ui.message_list_mut()
-                        .set_sort_criteria(SortCriteria::Subject(SortOrder::Ascending));
-                }
-                EventResult::Continue
+                use crate::email::{SortCriteria, SortOrder};
+                self.handle_sort(ui, |list| list.set_sort_criteria(SortCriteria::Subject(SortOrder::Ascending)))
             }
+            KeyboardAction::SortBySize => {
+                use crate::email::{SortCriteria, SortOrder};
+                self.handle_sort(ui, |list| list.set_sort_criteria(SortCriteria::Size(SortOrder::Descending)))
+            }
+            KeyboardAction::SortByPriority => {
+                use crate::email::{SortCriteria, SortOrder};
+                self.handle_sort(ui, |list| list.set_sort_criteria(SortCriteria::Priority(SortOrder::Descending)))
+            }
+            KeyboardAction::CycleSortField => self.handle_sort(ui, |list| list.cycle_sort_field()),
+            KeyboardAction::ToggleSortOrder => self.handle_sort(ui, |list| list.toggle_sort_order()),
 
             // Content preview
             KeyboardAction::ScrollToTop => {
@@ -718,6 +1181,7 @@ impl EventHandler {
                                     "Failed to open attachment with system application: {}",
                                     e
                                 );
+                                ui.show_toast_error(format!("Failed to open attachment: {}", e));
                             }
                         }
                     }
@@ -790,6 +1254,12 @@ impl EventHandler {
                 }
                 _ => EventResult::Continue,
             },
+            KeyboardAction::FolderMenu => {
+                if let FocusedPane::FolderTree = ui.focused_pane() {
+                    ui.folder_tree_mut().show_context_menu();
+                }
+                EventResult::Continue
+            }
 
             // Copy operations
             KeyboardAction::CopyEmailContent => {
@@ -900,13 +1370,22 @@ impl EventHandler {
                     EventResult::Continue
                 }
             }
-            KeyboardAction::ViewEventDetails => {
+            KeyboardAction::ViewEventDetails => self.handle_view_selected_event(ui),
+            KeyboardAction::NudgeAttendees => {
                 if ui.mode() == &UIMode::Calendar {
-                    // TODO: Implement event details view
-                    tracing::info!("View event details action triggered in calendar mode");
+                    if let Some(event_id) = ui.calendar_ui().get_selected_event_id() {
+                        EventResult::NudgeAttendees(event_id)
+                    } else {
+                        tracing::warn!("No event selected for attendee reminder");
+                        EventResult::Continue
+                    }
+                } else {
+                    EventResult::Continue
                 }
-                EventResult::Continue
             }
+            KeyboardAction::SnoozeReminder => EventResult::SnoozeReminder,
+            KeyboardAction::ShowSms => EventResult::ShowSms,
+            KeyboardAction::ReplyToSms => EventResult::ReplyToSms,
             KeyboardAction::CreateTodo => {
                 if ui.mode() == &UIMode::Calendar {
                     // Use default calendar ID for creating todos
@@ -1032,6 +1511,16 @@ impl EventHandler {
                 }
                 EventResult::Continue
             }
+            KeyboardAction::AISummarizeThread => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                    if let Some(message_item) = ui.message_list().selected_message() {
+                        if let Some(message_id) = message_item.message_id {
+                            return EventResult::AISummarizeThread(message_id);
+                        }
+                    }
+                }
+                EventResult::Continue
+            }
             KeyboardAction::AICalendarAssist => {
                 if ui.mode() == &UIMode::Calendar {
                     ui.show_ai_calendar_assistance();
@@ -1046,6 +1535,10 @@ impl EventHandler {
                 ui.show_ai_configuration();
                 EventResult::Continue
             }
+            KeyboardAction::AIDiagnostics => {
+                ui.show_ai_diagnostics();
+                EventResult::Continue
+            }
             KeyboardAction::AIQuickReply => {
                 if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
                     if let Some(message) = ui.message_list().selected_message() {
@@ -1058,6 +1551,16 @@ impl EventHandler {
                 }
                 EventResult::Continue
             }
+            KeyboardAction::AIDraftReply => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
+                    if let Some(message_item) = ui.message_list().selected_message() {
+                        if let Some(message_id) = message_item.message_id {
+                            return EventResult::AIDraftReply(message_id);
+                        }
+                    }
+                }
+                EventResult::Continue
+            }
             KeyboardAction::AIEmailAnalysis => {
                 if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview) {
                     if let Some(_message) = ui.message_list().selected_message() {
@@ -1286,12 +1789,37 @@ impl EventHandler {
 
             // Attachment actions
             KeyboardAction::SaveAttachment => {
-                if ui.focused_pane() == FocusedPane::ContentPreview {
-                    if ui.content_preview().is_viewing_attachment() {
-                        // TODO: Implement save attachment functionality
-                        tracing::info!("Save attachment action triggered");
-                        // For now, show a toast message
-                        ui.show_toast_info("Save attachment functionality coming soon");
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview)
+                    && ui.content_preview().has_attachments()
+                {
+                    if ui.content_preview().get_selected_attachment().is_none() {
+                        ui.content_preview_mut().select_first_attachment();
+                    }
+                    return EventResult::SaveAttachment;
+                }
+                EventResult::Continue
+            }
+            KeyboardAction::SaveAllAttachments => {
+                if matches!(ui.focused_pane(), FocusedPane::MessageList | FocusedPane::ContentPreview)
+                    && ui.content_preview().has_attachments()
+                {
+                    match ui.content_preview().save_all_attachments().await {
+                        Ok(paths) => {
+                            let location = paths
+                                .first()
+                                .and_then(|path| path.parent())
+                                .map(|dir| dir.display().to_string())
+                                .unwrap_or_default();
+                            ui.show_toast_success(format!(
+                                "Saved {} attachment(s) to {}",
+                                paths.len(),
+                                location
+                            ));
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to save attachments: {}", e);
+                            ui.show_toast_error(format!("Failed to save attachments: {}", e));
+                        }
                     }
                 }
                 EventResult::Continue
@@ -1393,6 +1921,7 @@ impl EventHandler {
                     .await
                 {
                     tracing::error!("Error handling attachment viewer key: {}", e);
+                    ui.show_toast_error(format!("Attachment: {}", e));
                 }
                 EventResult::Continue
             }
@@ -1464,6 +1993,10 @@ impl EventHandler {
             }
         }
 
+        if ui.mode() == &UIMode::Calendar {
+            return self.handle_view_selected_event(ui);
+        }
+
         match ui.focused_pane() {
             FocusedPane::AccountSwitcher => {
                 if let Some(account_id) = ui.account_switcher_mut().select_current() {
@@ -1477,6 +2010,11 @@ impl EventHandler {
                 if ui.folder_tree().is_in_search_mode() {
                     ui.folder_tree_mut().exit_search_mode(true);
                     EventResult::Continue
+                } else if ui.folder_tree().is_context_menu_visible() {
+                    match ui.folder_tree_mut().execute_context_menu_action() {
+                        Some(operation) => EventResult::FolderOperation(operation),
+                        None => EventResult::Continue,
+                    }
                 } else {
                     if let Some(folder_path) = ui.folder_tree_mut().handle_enter() {
                         tracing::debug!("Folder selection: returning FolderSelect({})", folder_path);
@@ -1494,7 +2032,37 @@ impl EventHandler {
         }
     }
 
+    /// Open the details of the selected calendar event
+    fn handle_view_selected_event(&mut self, ui: &mut UI) -> EventResult {
+        if ui.mode() != &UIMode::Calendar {
+            return EventResult::Continue;
+        }
+        match ui.calendar_ui().get_selected_event_id() {
+            Some(event_id) => EventResult::ViewEventDetails("primary".to_string(), event_id),
+            None => EventResult::Continue,
+        }
+    }
+
     /// Handle escape action for different panes and modes
+    /// Re-sort the message list and have the new order saved for its folder
+    fn handle_sort<F>(&mut self, ui: &mut UI, sort: F) -> EventResult
+    where
+        F: FnOnce(&mut crate::ui::message_list::MessageList),
+    {
+        if !matches!(ui.focused_pane(), FocusedPane::MessageList) {
+            return EventResult::Continue;
+        }
+        let list = ui.message_list_mut();
+        sort(list);
+        let criteria = list.sort_criteria();
+        let folder = list.sort_folder();
+        ui.show_toast_info(format!("Sorted by {}", criteria.description().to_lowercase()));
+        match folder {
+            Some((account_id, folder)) => EventResult::SaveFolderSort(account_id, folder, criteria),
+            None => EventResult::Continue,
+        }
+    }
+
     fn handle_escape(&mut self, ui: &mut UI) {
         // First handle enhanced progress overlay
         if ui.enhanced_progress_overlay().is_visible() {
@@ -1510,6 +2078,10 @@ impl EventHandler {
 
         // First check UI mode for mode-specific escape handling
         match ui.mode() {
+            UIMode::Calendar if ui.calendar_ui().is_showing_event_details() => {
+                ui.calendar_ui_mut().hide_event_details();
+                return;
+            }
             UIMode::Calendar => {
                 // Return to email view from calendar
                 ui.show_email();
@@ -1544,7 +2116,44 @@ impl EventHandler {
 
 
     /// Handle email viewer mode key events
+    /// Handle typing in the email viewer's quick reply box
+    fn handle_quick_reply_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        let Some(quick_reply) = ui.email_viewer_mut().quick_reply_mut() else {
+            return EventResult::Continue;
+        };
+        match quick_reply.handle_key(key) {
+            QuickReplyAction::Continue => EventResult::Continue,
+            QuickReplyAction::Send => EventResult::SendQuickReply,
+            QuickReplyAction::Expand => EventResult::ExpandQuickReply,
+            QuickReplyAction::Cancel => {
+                ui.email_viewer_mut().close_quick_reply();
+                EventResult::Continue
+            }
+        }
+    }
+
+    /// Handle typing in the email viewer's passphrase box
+    fn handle_passphrase_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        let Some(prompt) = ui.email_viewer_mut().passphrase_prompt_mut() else {
+            return EventResult::Continue;
+        };
+        match prompt.handle_key(key) {
+            PassphraseAction::Continue => EventResult::Continue,
+            PassphraseAction::Submit => EventResult::DecryptMessage(prompt.take_passphrase()),
+            PassphraseAction::Cancel => {
+                ui.email_viewer_mut().close_passphrase_prompt();
+                EventResult::Continue
+            }
+        }
+    }
+
     async fn handle_email_viewer_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        // Keys the preset takes over from the viewer
+        if key.modifiers == KeyModifiers::NONE {
+            if let Some(action) = self.keyboard_manager.preset().viewer_action(key.code) {
+                return self.execute_keyboard_action(action, ui).await;
+            }
+        }
         if let Some(action) = ui.handle_email_viewer_key(key.code) {
             match action {
                 crate::ui::email_viewer::EmailViewerAction::Reply => {
@@ -1555,6 +2164,17 @@ impl EventHandler {
                     // Start reply all composition
                     self.handle_email_reply_all(ui).await
                 }
+                crate::ui::email_viewer::EmailViewerAction::ReplyToList => {
+                    match ui.email_viewer().current_message.clone() {
+                        Some(message) => EventResult::ComposeAction(
+                            crate::ui::ComposeAction::StartReplyToListFromMessage(message),
+                        ),
+                        None => EventResult::Continue,
+                    }
+                }
+                crate::ui::email_viewer::EmailViewerAction::QuickReply => {
+                    EventResult::StartQuickReply
+                }
                 crate::ui::email_viewer::EmailViewerAction::Forward => {
                     // Start forward composition
                     self.handle_email_forward(ui).await
@@ -1583,6 +2203,26 @@ impl EventHandler {
                     // Add sender to contacts
                     self.handle_email_add_to_contacts(ui).await
                 }
+                crate::ui::email_viewer::EmailViewerAction::ViewSenderContact => {
+                    match ui.email_viewer().get_sender_email() {
+                        Some(email) => EventResult::ViewSenderContact(email),
+                        None => EventResult::Continue,
+                    }
+                }
+                crate::ui::email_viewer::EmailViewerAction::ViewInvitation => {
+                    EventResult::ViewInvitation
+                }
+                crate::ui::email_viewer::EmailViewerAction::LoadRemoteImages => {
+                    // Chosen from the actions panel rather than with P
+                    ui.email_viewer_mut().load_remote_images();
+                    if let Some(message_id) = ui.email_viewer().get_message_id() {
+                        ui.content_preview_mut()
+                            .allow_remote_images(message_id)
+                            .await;
+                    }
+                    ui.show_toast_info("Loading remote images");
+                    EventResult::Continue
+                }
                 crate::ui::email_viewer::EmailViewerAction::Close => {
                     // Exit email viewer
                     ui.exit_email_viewer();
@@ -1809,6 +2449,295 @@ impl EventHandler {
         }
     }
 
+    /// Handle keys in the account details popup
+    async fn handle_account_details_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        let Some(view) = ui.account_details_mut() else {
+            ui.show_email_interface();
+            return EventResult::Continue;
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                ui.hide_account_details();
+                EventResult::Continue
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                view.select_previous();
+                EventResult::Continue
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                view.select_next();
+                EventResult::Continue
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => match view.toggle_selected() {
+                Some((capability, disabled)) => EventResult::SetCapabilityDisabled(
+                    view.account_id().to_string(),
+                    capability,
+                    disabled,
+                ),
+                None => EventResult::Continue,
+            },
+            _ => EventResult::Continue,
+        }
+    }
+
+    /// Handle keys in the special folder setup popup
+    fn handle_special_folders_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        let Some(view) = ui.special_folders_view_mut() else {
+            ui.show_email_interface();
+            return EventResult::Continue;
+        };
+
+        let mapping = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                ui.hide_special_folders();
+                return EventResult::Continue;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                view.select_previous();
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                view.select_next();
+                None
+            }
+            KeyCode::Left | KeyCode::Char('h') => view.cycle_selected(false),
+            KeyCode::Right | KeyCode::Char('l') => view.cycle_selected(true),
+            KeyCode::Enter => view.accept_selected(),
+            KeyCode::Char('a') => Some(view.accept_all()),
+            KeyCode::Char('x') | KeyCode::Backspace | KeyCode::Delete => view.clear_selected(),
+            _ => None,
+        };
+
+        match mapping {
+            Some(mapping) => EventResult::SetSpecialFolders(view.account_id().to_string(), mapping),
+            None => EventResult::Continue,
+        }
+    }
+
+    async fn handle_retention_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        let Some(view) = ui.retention_view_mut() else {
+            ui.show_email_interface();
+            return EventResult::Continue;
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                ui.hide_retention();
+                EventResult::Continue
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                view.select_previous();
+                EventResult::Continue
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                view.select_next();
+                EventResult::Continue
+            }
+            KeyCode::Enter if view.is_preview() => EventResult::AcceptRetentionPreview,
+            KeyCode::Char('u') | KeyCode::Char(' ') => match view.selected_pending() {
+                Some(pending) => EventResult::RestoreRetained(pending.message_id),
+                None => EventResult::Continue,
+            },
+            _ => EventResult::Continue,
+        }
+    }
+
+    fn handle_filter_rules_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        let Some(view) = ui.filter_rules_view_mut() else {
+            ui.show_email_interface();
+            return EventResult::Continue;
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                ui.hide_filter_rules();
+                EventResult::Continue
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                view.select_previous();
+                EventResult::Continue
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                view.select_next();
+                EventResult::Continue
+            }
+            KeyCode::Enter => match view.selected_rule() {
+                Some(rule) => EventResult::TestFilterRule(rule.id),
+                None => EventResult::Continue,
+            },
+            KeyCode::Char(' ') => match view.selected_rule() {
+                Some(rule) => EventResult::ToggleFilterRule(rule.id),
+                None => EventResult::Continue,
+            },
+            _ => EventResult::Continue,
+        }
+    }
+
+    fn handle_mbox_wizard_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        use crate::ui::mbox_wizard::MboxWizardAction;
+
+        let Some(wizard) = ui.mbox_wizard_mut() else {
+            ui.show_email_interface();
+            return EventResult::Continue;
+        };
+
+        match wizard.handle_key(key) {
+            MboxWizardAction::Continue => EventResult::Continue,
+            MboxWizardAction::Close => {
+                ui.hide_mbox_wizard();
+                EventResult::Continue
+            }
+            action => EventResult::MboxWizardAction(action),
+        }
+    }
+
+    fn handle_invitation_viewer_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        use crate::ui::InvitationAction;
+
+        let key = match key.code {
+            KeyCode::Char(c) => c,
+            KeyCode::Enter => '\n',
+            KeyCode::Esc => '\x1b',
+            KeyCode::Up => 'k',
+            KeyCode::Down => 'j',
+            _ => return EventResult::Continue,
+        };
+
+        match ui.handle_invitation_viewer_key(key) {
+            Some(InvitationAction::Close) => {
+                ui.exit_invitation_viewer();
+                EventResult::Continue
+            }
+            Some(action) => EventResult::InvitationAction(action),
+            None => EventResult::Continue,
+        }
+    }
+
+    fn handle_snooze_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        use crate::ui::schedule_picker::SchedulePickerAction;
+
+        let Some(picker) = ui.snooze_picker_mut() else {
+            ui.show_email_interface();
+            return EventResult::Continue;
+        };
+
+        match picker.handle_key(key.code) {
+            SchedulePickerAction::Continue => EventResult::Continue,
+            SchedulePickerAction::Cancel => {
+                ui.hide_snooze_picker();
+                EventResult::Continue
+            }
+            SchedulePickerAction::Pick(until) => {
+                let result = EventResult::SnoozeMessage(
+                    picker.account_id().to_string(),
+                    picker.message_id(),
+                    until,
+                );
+                ui.hide_snooze_picker();
+                result
+            }
+        }
+    }
+
+    fn handle_headers_panel_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        let Some(panel) = ui.headers_panel_mut() else {
+            ui.show_email_interface();
+            return EventResult::Continue;
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ui.hide_headers_panel(),
+            KeyCode::Up | KeyCode::Char('k') => panel.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => panel.select_next(),
+            KeyCode::PageUp => panel.page_up(),
+            KeyCode::PageDown => panel.page_down(),
+            KeyCode::Enter | KeyCode::Char('y') => match panel.copy_selected() {
+                Ok(text) => {
+                    let first_line = text.lines().next().unwrap_or_default();
+                    let preview: String = first_line.chars().take(60).collect();
+                    ui.show_toast_info(format!("Copied: {}", preview));
+                }
+                Err(e) => ui.show_toast_error(format!("Copy failed: {}", e)),
+            },
+            _ => {}
+        }
+        EventResult::Continue
+    }
+
+    /// Translate keys for the SMS screen, which the app hands to it
+    ///
+    /// Esc or `q` on the conversation list closes the screen; the send key
+    /// Ctrl+S becomes `'\x13'`.
+    async fn handle_sms_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        let Some(screen) = ui.sms_screen() else {
+            ui.show_email_interface();
+            return EventResult::Continue;
+        };
+
+        let on_list = screen.get_view_mode().await == crate::mobile::SmsViewMode::ConversationList;
+        let key = match key.code {
+            KeyCode::Esc | KeyCode::Char('q') if on_list => {
+                ui.hide_sms_screen();
+                return EventResult::Continue;
+            }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => '\x13',
+            KeyCode::Char(c) => c,
+            KeyCode::Enter => '\n',
+            KeyCode::Backspace => '\x08',
+            KeyCode::Esc => '\x1b',
+            KeyCode::Up => '↑',
+            KeyCode::Down => '↓',
+            KeyCode::Left => '←',
+            _ => return EventResult::Continue,
+        };
+        EventResult::SmsKey(key)
+    }
+
+    async fn handle_links_panel_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        let Some(panel) = ui.links_panel_mut() else {
+            ui.show_email_interface();
+            return EventResult::Continue;
+        };
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => ui.hide_links_panel(),
+            KeyCode::Up | KeyCode::Char('k') => panel.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => panel.select_next(),
+            KeyCode::Char('y') => match panel.copy_selected() {
+                Ok(link) => ui.show_toast_info(format!("Copied: {}", link)),
+                Err(e) => ui.show_toast_error(format!("Copy failed: {}", e)),
+            },
+            KeyCode::Enter | KeyCode::Char('o') => {
+                if let Some(link) = panel.selected().map(str::to_string) {
+                    match ui.content_preview().opener().open_url(&link).await {
+                        Ok(()) => ui.show_toast_info(format!("Opened {}", link)),
+                        Err(e) => {
+                            tracing::error!("Failed to open {}: {}", link, e);
+                            ui.show_toast_error(format!("Failed to open link: {}", e));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        EventResult::Continue
+    }
+
+    fn handle_confirm_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => match ui.take_confirm() {
+                Some(action) => EventResult::Confirmed(action),
+                None => EventResult::Continue,
+            },
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char('q') | KeyCode::Esc => {
+                ui.take_confirm();
+                EventResult::Continue
+            }
+            _ => EventResult::Continue,
+        }
+    }
+
     /// Handle contacts popup key events
     async fn handle_contacts_popup_keys(&mut self, key: KeyEvent, ui: &mut UI) -> EventResult {
         match key.code {
@@ -1851,13 +2780,13 @@ impl EventHandler {
         // Check for global AI toggle shortcut (Ctrl+Alt+I) even when popup is visible
         if key.modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::ALT) 
             && key.code == KeyCode::Char('i') {
-            ui.ai_popup_mut().hide();
+            ui.close_ai_popup();
             return EventResult::Continue;
         }
         
         match key.code {
             KeyCode::Esc => {
-                ui.ai_popup_mut().hide();
+                ui.close_ai_popup();
                 EventResult::Continue
             }
             KeyCode::Tab => {
@@ -1882,6 +2811,10 @@ impl EventHandler {
                 }
                 EventResult::Continue
             }
+            KeyCode::Char('f') if ui.ai_popup().is_showing_diagnostics() => {
+                ui.clear_ai_cache();
+                EventResult::Continue
+            }
             _ => EventResult::Continue,
         }
     }
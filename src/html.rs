@@ -1,9 +1,16 @@
+use crate::images::ImageReference;
+use crate::theme::Theme;
+use crate::ui::text_width::{display_width, take_width};
 use ammonia::clean;
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
 };
+use regex::Regex;
 use scraper::{ElementRef, Html, Selector};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::LazyLock;
 
 /// HTML to terminal text converter for email content
 pub struct HtmlRenderer {
@@ -36,11 +43,21 @@ impl HtmlRenderer {
         // Step 1: Clean and sanitize HTML to remove malicious/unnecessary content
         let cleaned_html = self.clean_and_sanitize_html(html_content);
 
-        // Step 2: Use html2text as primary renderer (industry standard)
+        // Step 2: Lay out blocks, lists and tables ourselves, fitted to the width
+        let layout = self.render_html_layout(&cleaned_html);
+        if !layout.lines.is_empty() {
+            tracing::debug!(
+                "HTML Renderer: Block layout successful ({} lines)",
+                layout.lines.len()
+            );
+            return layout;
+        }
+
+        // Step 3: Fall back to html2text
         let plain_text = self.html_to_plain_text_optimized(&cleaned_html);
         if !plain_text.trim().is_empty() {
             tracing::debug!(
-                "HTML Renderer: html2text rendering successful ({} chars)",
+                "HTML Renderer: html2text fallback successful ({} chars)",
                 plain_text.len()
             );
             let lines: Vec<Line<'static>> = plain_text
@@ -57,16 +74,6 @@ impl HtmlRenderer {
             return Text::from(lines);
         }
 
-        // Step 3: Try our enhanced w3m-style renderer as fallback
-        let enhanced_result = self.render_html_enhanced(&cleaned_html);
-        if !enhanced_result.lines.is_empty() {
-            tracing::debug!(
-                "HTML Renderer: Enhanced rendering fallback successful ({} lines)",
-                enhanced_result.lines.len()
-            );
-            return enhanced_result;
-        }
-
         // Step 4: Last resort - basic HTML parsing
         tracing::warn!("HTML Renderer: Using basic HTML parsing fallback");
         let document = Html::parse_fragment(&cleaned_html);
@@ -216,255 +223,12 @@ impl HtmlRenderer {
         filtered_lines.join("\n")
     }
 
-    /// Enhanced HTML renderer (w3m/lynx style)
-    fn render_html_enhanced(&mut self, html: &str) -> Text<'static> {
+    /// Lay HTML out as wrapped paragraphs, indented lists and box-drawn
+    /// tables that fit `max_width`
+    fn render_html_layout(&self, html: &str) -> Text<'static> {
         let document = Html::parse_fragment(html);
-        let mut lines = Vec::new();
-        let mut current_line = Vec::new();
-        let mut list_depth = 0;
-        let mut in_pre = false;
-
-        // Walk through the HTML tree and render like a terminal browser
-        self.render_element_enhanced(
-            &document.root_element(),
-            &mut lines,
-            &mut current_line,
-            &mut list_depth,
-            &mut in_pre,
-        );
-
-        // Add any remaining content
-        if !current_line.is_empty() {
-            lines.push(Line::from(current_line));
-        }
-
-        Text::from(lines)
-    }
-
-    /// Render HTML elements in a w3m/lynx style
-    fn render_element_enhanced(
-        &self,
-        element: &ElementRef,
-        lines: &mut Vec<Line<'static>>,
-        current_line: &mut Vec<Span<'static>>,
-        list_depth: &mut usize,
-        in_pre: &mut bool,
-    ) {
-        use scraper::Node;
-
-        for node in element.children() {
-            match node.value() {
-                Node::Element(elem) => {
-                    if let Some(element_ref) = ElementRef::wrap(node) {
-                        let tag_name = elem.name();
-
-                        match tag_name {
-                            // Skip these entirely
-                            "script" | "style" | "meta" | "link" | "head" => continue,
-
-                            // Block elements - create new lines
-                            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
-                                self.flush_current_line(lines, current_line);
-                                let text = element_ref.text().collect::<String>();
-                                if !text.trim().is_empty() {
-                                    let level =
-                                        tag_name.chars().last().unwrap().to_digit(10).unwrap_or(1)
-                                            as usize;
-                                    let style = match level {
-                                        1 => Style::default()
-                                            .fg(Color::Yellow)
-                                            .add_modifier(Modifier::BOLD),
-                                        2 => Style::default()
-                                            .fg(Color::Cyan)
-                                            .add_modifier(Modifier::BOLD),
-                                        _ => Style::default()
-                                            .fg(Color::White)
-                                            .add_modifier(Modifier::BOLD),
-                                    };
-                                    lines.push(Line::from(vec![Span::styled(
-                                        text.trim().to_string(),
-                                        style,
-                                    )]));
-                                    lines.push(Line::from("")); // Add spacing after headers
-                                }
-                            }
-
-                            "p" | "div" => {
-                                self.flush_current_line(lines, current_line);
-                                self.render_element_enhanced(
-                                    &element_ref,
-                                    lines,
-                                    current_line,
-                                    list_depth,
-                                    in_pre,
-                                );
-                                self.flush_current_line(lines, current_line);
-                                lines.push(Line::from("")); // Add spacing
-                            }
-
-                            "br" => {
-                                self.flush_current_line(lines, current_line);
-                            }
-
-                            "ul" | "ol" => {
-                                self.flush_current_line(lines, current_line);
-                                *list_depth += 1;
-                                self.render_element_enhanced(
-                                    &element_ref,
-                                    lines,
-                                    current_line,
-                                    list_depth,
-                                    in_pre,
-                                );
-                                *list_depth -= 1;
-                                self.flush_current_line(lines, current_line);
-                            }
-
-                            "li" => {
-                                self.flush_current_line(lines, current_line);
-                                let indent = "  ".repeat(*list_depth);
-                                current_line.push(Span::raw(format!("{}• ", indent)));
-                                self.render_element_enhanced(
-                                    &element_ref,
-                                    lines,
-                                    current_line,
-                                    list_depth,
-                                    in_pre,
-                                );
-                            }
-
-                            "a" => {
-                                let text = element_ref.text().collect::<String>();
-                                if !text.trim().is_empty() {
-                                    let href = element_ref.value().attr("href").unwrap_or("");
-                                    let display_text = if href.is_empty() {
-                                        text.trim().to_string()
-                                    } else {
-                                        format!("{} ({})", text.trim(), href)
-                                    };
-                                    current_line.push(Span::styled(
-                                        display_text,
-                                        Style::default()
-                                            .fg(Color::Blue)
-                                            .add_modifier(Modifier::UNDERLINED),
-                                    ));
-                                }
-                            }
-
-                            "strong" | "b" => {
-                                let text = element_ref.text().collect::<String>();
-                                if !text.trim().is_empty() {
-                                    current_line.push(Span::styled(
-                                        text.trim().to_string(),
-                                        Style::default().add_modifier(Modifier::BOLD),
-                                    ));
-                                }
-                            }
-
-                            "em" | "i" => {
-                                let text = element_ref.text().collect::<String>();
-                                if !text.trim().is_empty() {
-                                    current_line.push(Span::styled(
-                                        text.trim().to_string(),
-                                        Style::default().add_modifier(Modifier::ITALIC),
-                                    ));
-                                }
-                            }
-
-                            "pre" | "code" => {
-                                let old_pre = *in_pre;
-                                *in_pre = true;
-                                let text = element_ref.text().collect::<String>();
-                                if !text.trim().is_empty() {
-                                    self.flush_current_line(lines, current_line);
-                                    for line in text.lines() {
-                                        lines.push(Line::from(vec![Span::styled(
-                                            line.to_string(),
-                                            Style::default().fg(Color::Green),
-                                        )]));
-                                    }
-                                }
-                                *in_pre = old_pre;
-                            }
-
-                            "table" => {
-                                self.flush_current_line(lines, current_line);
-                                lines.push(Line::from("┌─ Table ─┐"));
-                                self.render_element_enhanced(
-                                    &element_ref,
-                                    lines,
-                                    current_line,
-                                    list_depth,
-                                    in_pre,
-                                );
-                                lines.push(Line::from("└─────────┘"));
-                            }
-
-                            "tr" => {
-                                self.flush_current_line(lines, current_line);
-                                current_line.push(Span::raw("│ "));
-                                self.render_element_enhanced(
-                                    &element_ref,
-                                    lines,
-                                    current_line,
-                                    list_depth,
-                                    in_pre,
-                                );
-                                current_line.push(Span::raw(" │"));
-                                self.flush_current_line(lines, current_line);
-                            }
-
-                            "td" | "th" => {
-                                let text = element_ref.text().collect::<String>();
-                                if !text.trim().is_empty() {
-                                    let style = if tag_name == "th" {
-                                        Style::default().add_modifier(Modifier::BOLD)
-                                    } else {
-                                        Style::default()
-                                    };
-                                    current_line
-                                        .push(Span::styled(format!("{} ", text.trim()), style));
-                                }
-                            }
-
-                            // Recursive rendering for other elements
-                            _ => {
-                                self.render_element_enhanced(
-                                    &element_ref,
-                                    lines,
-                                    current_line,
-                                    list_depth,
-                                    in_pre,
-                                );
-                            }
-                        }
-                    }
-                }
-                Node::Text(text) => {
-                    let text_content = if *in_pre {
-                        text.to_string()
-                    } else {
-                        text.trim().to_string()
-                    };
-
-                    if !text_content.is_empty() {
-                        current_line.push(Span::raw(text_content));
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-
-    /// Helper to flush current line to lines vector
-    fn flush_current_line(
-        &self,
-        lines: &mut Vec<Line<'static>>,
-        current_line: &mut Vec<Span<'static>>,
-    ) {
-        if !current_line.is_empty() {
-            lines.push(Line::from(current_line.drain(..).collect::<Vec<_>>()));
-        }
+        let width = self.max_width.max(MIN_LAYOUT_WIDTH);
+        Text::from(BlockLayout::default().layout_children(&document.root_element(), width))
     }
 
     /// Convert HTML to plain text using html2text (most reliable method) - optimized version
@@ -672,6 +436,1080 @@ impl HtmlRenderer {
     }
 }
 
+/// Narrowest width the block layout wraps to
+const MIN_LAYOUT_WIDTH: usize = 20;
+
+/// Bullets for unordered lists, by nesting depth
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+/// Elements that start a new block rather than continuing the current line
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "center",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Elements whose content is never shown
+const HIDDEN_TAGS: &[&str] = &["head", "link", "meta", "script", "style", "title"];
+
+/// Block elements that mark a table as page layout rather than data
+const LAYOUT_TABLE_CONTENT: &str = "table, p, div, ul, ol, h1, h2, h3, h4, h5, h6, blockquote";
+
+/// Inline content waiting to be wrapped into lines
+enum Inline {
+    Text(String, Style),
+    Break,
+}
+
+/// One table cell and the columns it spans
+struct TableCell<'a> {
+    element: ElementRef<'a>,
+    header: bool,
+    colspan: usize,
+}
+
+/// Lays out parsed HTML as terminal lines within a given width
+///
+/// Paragraphs wrap at word boundaries, lists get bullets or numbers and
+/// indent with their nesting, and data tables are drawn with box
+/// characters, columns sized to their content and the width available.
+/// Tables used for page layout, as most newsletters do, are read as a
+/// sequence of blocks instead, since boxing them helps nobody.
+#[derive(Debug, Clone, Copy, Default)]
+struct BlockLayout {
+    list_depth: usize,
+}
+
+impl BlockLayout {
+    /// Lay out the children of `element`, blocks on their own lines and
+    /// runs of inline content wrapped to `width`
+    fn layout_children(&self, element: &ElementRef, width: usize) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        let mut inline = Vec::new();
+
+        for node in element.children() {
+            match node.value() {
+                scraper::Node::Text(text) => {
+                    inline.push(Inline::Text(text.to_string(), Style::default()))
+                }
+                scraper::Node::Element(elem) => {
+                    let Some(child) = ElementRef::wrap(node) else {
+                        continue;
+                    };
+                    if BLOCK_TAGS.contains(&elem.name()) {
+                        lines.extend(wrap_inline(&inline, width));
+                        inline.clear();
+                        self.layout_block(&child, width, &mut lines);
+                    } else {
+                        collect_inline(&child, Style::default(), &mut inline);
+                    }
+                }
+                _ => {}
+            }
+        }
+        lines.extend(wrap_inline(&inline, width));
+
+        tidy_blank_lines(lines)
+    }
+
+    fn layout_block(&self, element: &ElementRef, width: usize, lines: &mut Vec<Line<'static>>) {
+        let tag = element.value().name();
+        match tag {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let style = match tag {
+                    "h1" => Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                    "h2" => Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                    _ => Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                };
+                let mut inline = Vec::new();
+                collect_children_inline(element, style, &mut inline);
+                push_spaced(lines, wrap_inline(&inline, width));
+            }
+            "p" | "figure" | "address" => {
+                push_spaced(lines, self.layout_children(element, width));
+            }
+            "blockquote" => {
+                let quoted = self.layout_children(element, width.saturating_sub(2).max(1));
+                let quoted = prefix_lines(quoted, "│ ", "│ ", Style::default().fg(Color::Gray));
+                push_spaced(lines, quoted);
+            }
+            "pre" => {
+                let text = element.text().collect::<String>();
+                let pre = text
+                    .trim_matches('\n')
+                    .lines()
+                    .map(|line| {
+                        Line::from(Span::styled(
+                            line.trim_end().to_string(),
+                            Style::default().fg(Color::Green),
+                        ))
+                    })
+                    .collect();
+                push_spaced(lines, pre);
+            }
+            "hr" => lines.push(Line::from(Span::styled(
+                "─".repeat(width),
+                Style::default().fg(Color::DarkGray),
+            ))),
+            "ul" | "ol" => {
+                let list = self.layout_list(element, tag == "ol", width);
+                if self.list_depth == 0 {
+                    push_spaced(lines, list);
+                } else {
+                    lines.extend(list);
+                }
+            }
+            // A list item outside a list still gets its bullet
+            "li" => lines.extend(self.layout_list_item(element, BULLETS[0], width)),
+            "table" => self.layout_table(element, width, lines),
+            "dt" => {
+                let mut inline = Vec::new();
+                collect_children_inline(
+                    element,
+                    Style::default().add_modifier(Modifier::BOLD),
+                    &mut inline,
+                );
+                lines.extend(wrap_inline(&inline, width));
+            }
+            "dd" => {
+                let definition = self.layout_children(element, width.saturating_sub(4).max(1));
+                lines.extend(prefix_lines(definition, "    ", "    ", Style::default()));
+            }
+            _ if HIDDEN_TAGS.contains(&tag) => {}
+            _ => lines.extend(self.layout_children(element, width)),
+        }
+    }
+
+    /// Lay out a `ul` or `ol`, numbering from its `start` attribute
+    fn layout_list(&self, list: &ElementRef, ordered: bool, width: usize) -> Vec<Line<'static>> {
+        let nested = BlockLayout {
+            list_depth: self.list_depth + 1,
+        };
+        let items: Vec<ElementRef> = list.children().filter_map(ElementRef::wrap).collect();
+        let mut number = list
+            .value()
+            .attr("start")
+            .and_then(|start| start.trim().parse::<i64>().ok())
+            .unwrap_or(1);
+        let item_count = items
+            .iter()
+            .filter(|item| item.value().name() == "li")
+            .count() as i64;
+        let number_width = [number, number + item_count.max(1) - 1]
+            .iter()
+            .map(|n| n.to_string().len())
+            .max()
+            .unwrap_or(1);
+        let bullet = BULLETS[self.list_depth % BULLETS.len()];
+
+        let mut lines = Vec::new();
+        for item in &items {
+            if item.value().name() != "li" {
+                // Lists nested directly in a list, which mail often has
+                let nested_lines = nested.layout_children(item, width.saturating_sub(2).max(1));
+                lines.extend(prefix_lines(nested_lines, "  ", "  ", Style::default()));
+                continue;
+            }
+            let marker = if ordered {
+                let marker = format!("{:>width$}.", number, width = number_width);
+                number += 1;
+                marker
+            } else {
+                bullet.to_string()
+            };
+            lines.extend(nested.layout_list_item(item, &marker, width));
+        }
+        lines
+    }
+
+    /// An item's content with `marker` before its first line and later
+    /// lines indented to match
+    fn layout_list_item(
+        &self,
+        item: &ElementRef,
+        marker: &str,
+        width: usize,
+    ) -> Vec<Line<'static>> {
+        let marker = format!("{} ", marker);
+        let indent = " ".repeat(display_width(&marker));
+        let mut content = self.layout_children(item, width.saturating_sub(indent.len()).max(1));
+        if content.is_empty() {
+            content.push(Line::from(""));
+        }
+        prefix_lines(content, &marker, &indent, Style::default().fg(Color::Cyan))
+    }
+
+    fn layout_table(&self, table: &ElementRef, width: usize, lines: &mut Vec<Line<'static>>) {
+        let mut block = Vec::new();
+        for child in table.children().filter_map(ElementRef::wrap) {
+            if child.value().name() == "caption" {
+                let mut inline = Vec::new();
+                collect_children_inline(
+                    &child,
+                    Style::default().add_modifier(Modifier::BOLD),
+                    &mut inline,
+                );
+                block.extend(wrap_inline(&inline, width));
+            }
+        }
+
+        let rows = table_rows(table);
+        let columns = rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.colspan).sum::<usize>())
+            .max()
+            .unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+
+        // Room left for cell content once borders and padding are drawn
+        let available = width.saturating_sub(3 * columns + 1);
+        if is_layout_table(table, columns) || available < columns * 3 {
+            for cell in rows.iter().flatten() {
+                push_spaced(lines, self.layout_children(&cell.element, width));
+            }
+            return;
+        }
+
+        let mut min_widths = vec![1; columns];
+        let mut max_widths = vec![1; columns];
+        for row in &rows {
+            let mut column = 0;
+            for cell in row {
+                if cell.colspan == 1 {
+                    let text = cell.element.text().collect::<String>();
+                    let longest_word = text.split_whitespace().map(display_width).max();
+                    let natural = self
+                        .layout_children(&cell.element, usize::MAX / 2)
+                        .iter()
+                        .map(line_width)
+                        .max();
+                    min_widths[column] = min_widths[column].max(longest_word.unwrap_or(1));
+                    max_widths[column] = max_widths[column].max(natural.unwrap_or(1));
+                }
+                column += cell.colspan;
+            }
+        }
+        let widths = fit_columns(&min_widths, &max_widths, available);
+
+        let rendered: Vec<Vec<(Vec<Line<'static>>, usize)>> = rows
+            .iter()
+            .map(|row| {
+                let mut column = 0;
+                row.iter()
+                    .map(|cell| {
+                        let span_columns = (column..column + cell.colspan).filter(|&c| c < columns);
+                        let cell_width = span_columns.clone().map(|c| widths[c]).sum::<usize>()
+                            + 3 * (cell.colspan - 1);
+                        column += cell.colspan;
+                        let mut content = self.layout_children(&cell.element, cell_width);
+                        if cell.header {
+                            content = content
+                                .into_iter()
+                                .map(|line| line.patch_style(Modifier::BOLD))
+                                .collect();
+                        }
+                        (content, cell_width)
+                    })
+                    .collect()
+            })
+            .collect();
+        let wraps = rendered
+            .iter()
+            .any(|row| row.iter().any(|(content, _)| content.len() > 1));
+
+        let border = Style::default().fg(Color::DarkGray);
+        block.push(border_line(&widths, ['┌', '┬', '┐'], border));
+        for (index, (row, cells)) in rows.iter().zip(&rendered).enumerate() {
+            if index > 0 {
+                let after_header = rows[index - 1].iter().all(|cell| cell.header);
+                if wraps || after_header {
+                    block.push(border_line(&widths, ['├', '┼', '┤'], border));
+                }
+            }
+
+            let used: usize = row.iter().map(|cell| cell.colspan).sum();
+            let height = cells
+                .iter()
+                .map(|(content, _)| content.len())
+                .max()
+                .unwrap_or(0)
+                .max(1);
+            for line_index in 0..height {
+                let mut spans = vec![Span::styled("│", border)];
+                for (content, cell_width) in cells {
+                    spans.push(Span::raw(" "));
+                    let line = content.get(line_index);
+                    let used_width = line.map(line_width).unwrap_or(0);
+                    if let Some(line) = line {
+                        spans.extend(line.spans.iter().cloned());
+                    }
+                    spans.push(Span::raw(
+                        " ".repeat(cell_width.saturating_sub(used_width) + 1),
+                    ));
+                    spans.push(Span::styled("│", border));
+                }
+                // Rows with fewer cells than the table still get every column
+                for &missing in &widths[used.min(columns)..] {
+                    spans.push(Span::raw(" ".repeat(missing + 2)));
+                    spans.push(Span::styled("│", border));
+                }
+                block.push(Line::from(spans));
+            }
+        }
+        block.push(border_line(&widths, ['└', '┴', '┘'], border));
+
+        push_spaced(lines, block);
+    }
+}
+
+/// Gather the inline content of `element` with the styling of its tags
+fn collect_inline(element: &ElementRef, style: Style, inline: &mut Vec<Inline>) {
+    let tag = element.value().name();
+    let style = match tag {
+        _ if HIDDEN_TAGS.contains(&tag) => return,
+        "br" => {
+            inline.push(Inline::Break);
+            return;
+        }
+        "img" => {
+            if let Some(alt) = element.value().attr("alt").map(str::trim) {
+                if !alt.is_empty() {
+                    inline.push(Inline::Text(
+                        format!(" [image: {}] ", alt),
+                        style.fg(Color::DarkGray),
+                    ));
+                }
+            }
+            return;
+        }
+        "a" => style.fg(Color::Blue).add_modifier(Modifier::UNDERLINED),
+        "b" | "strong" => style.add_modifier(Modifier::BOLD),
+        "i" | "em" | "cite" => style.add_modifier(Modifier::ITALIC),
+        "u" | "ins" => style.add_modifier(Modifier::UNDERLINED),
+        "s" | "strike" | "del" => style.add_modifier(Modifier::CROSSED_OUT),
+        "code" | "kbd" | "tt" | "samp" => style.fg(Color::Green),
+        _ => style,
+    };
+
+    // Blocks inside inline elements still start on their own line
+    let block = BLOCK_TAGS.contains(&tag);
+    if block {
+        inline.push(Inline::Break);
+    }
+    collect_children_inline(element, style, inline);
+    if block {
+        inline.push(Inline::Break);
+    }
+}
+
+fn collect_children_inline(element: &ElementRef, style: Style, inline: &mut Vec<Inline>) {
+    for node in element.children() {
+        match node.value() {
+            scraper::Node::Text(text) => inline.push(Inline::Text(text.to_string(), style)),
+            scraper::Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(node) {
+                    collect_inline(&child, style, inline);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Word-wrap inline content to `width`, collapsing whitespace as a browser
+/// does and breaking words longer than a whole line
+fn wrap_inline(inline: &[Inline], width: usize) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current: Vec<Span<'static>> = Vec::new();
+    let mut current_width = 0;
+    let mut pending_space = false;
+
+    for item in inline {
+        let (text, style) = match item {
+            Inline::Break => {
+                lines.push(Line::from(std::mem::take(&mut current)));
+                current_width = 0;
+                pending_space = false;
+                continue;
+            }
+            Inline::Text(text, style) => (text, *style),
+        };
+
+        if text.starts_with(char::is_whitespace) {
+            pending_space = true;
+        }
+        for (index, word) in text.split_whitespace().enumerate() {
+            if index > 0 {
+                pending_space = true;
+            }
+            let mut word = word;
+            loop {
+                let space = usize::from(pending_space && current_width > 0);
+                let word_width = display_width(word);
+                if current_width + space + word_width <= width {
+                    if space == 1 {
+                        current.push(Span::raw(" "));
+                    }
+                    current.push(Span::styled(word.to_string(), style));
+                    current_width += space + word_width;
+                    break;
+                }
+                if current_width > 0 && word_width <= width {
+                    lines.push(Line::from(std::mem::take(&mut current)));
+                    current_width = 0;
+                    continue;
+                }
+
+                // Longer than a line: fill what's left and carry on below
+                let room = width.saturating_sub(current_width + space);
+                let mut head = take_width(word, room);
+                if head.is_empty() && current_width == 0 {
+                    // A double-width character on a one-cell line
+                    head = &word[..word.chars().next().map_or(0, char::len_utf8)];
+                }
+                if head.is_empty() {
+                    lines.push(Line::from(std::mem::take(&mut current)));
+                    current_width = 0;
+                    continue;
+                }
+                if space == 1 {
+                    current.push(Span::raw(" "));
+                }
+                current.push(Span::styled(head.to_string(), style));
+                lines.push(Line::from(std::mem::take(&mut current)));
+                current_width = 0;
+                pending_space = false;
+                word = &word[head.len()..];
+                if word.is_empty() {
+                    break;
+                }
+            }
+            pending_space = false;
+        }
+        if text.ends_with(char::is_whitespace) {
+            pending_space = true;
+        }
+    }
+    if !current.is_empty() {
+        lines.push(Line::from(current));
+    }
+    lines
+}
+
+/// Rows of a table, looking inside `thead`, `tbody` and `tfoot` but not
+/// into nested tables
+fn table_rows<'a>(table: &ElementRef<'a>) -> Vec<Vec<TableCell<'a>>> {
+    let mut rows = Vec::new();
+    for child in table.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "tr" => rows.push(table_cells(&child, false)),
+            section @ ("thead" | "tbody" | "tfoot") => {
+                for row in child.children().filter_map(ElementRef::wrap) {
+                    if row.value().name() == "tr" {
+                        rows.push(table_cells(&row, section == "thead"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    rows.retain(|row| !row.is_empty());
+    rows
+}
+
+fn table_cells<'a>(row: &ElementRef<'a>, in_head: bool) -> Vec<TableCell<'a>> {
+    row.children()
+        .filter_map(ElementRef::wrap)
+        .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+        .map(|cell| TableCell {
+            header: in_head || cell.value().name() == "th",
+            colspan: cell
+                .value()
+                .attr("colspan")
+                .and_then(|span| span.trim().parse::<usize>().ok())
+                .unwrap_or(1)
+                .clamp(1, 64),
+            element: cell,
+        })
+        .collect()
+}
+
+/// Whether a table arranges the page rather than holding data: a single
+/// column, a presentation role, or cells containing blocks or tables
+fn is_layout_table(table: &ElementRef, columns: usize) -> bool {
+    static LAYOUT_CONTENT: LazyLock<Selector> =
+        LazyLock::new(|| Selector::parse(LAYOUT_TABLE_CONTENT).unwrap());
+
+    columns <= 1
+        || table.value().attr("role") == Some("presentation")
+        || table.select(&LAYOUT_CONTENT).next().is_some()
+}
+
+/// Share `available` cells between columns: every column at its widest
+/// if that fits, otherwise long words kept whole and the rest handed out
+/// in proportion to how much each column wants
+fn fit_columns(min: &[usize], max: &[usize], available: usize) -> Vec<usize> {
+    let total_max: usize = max.iter().sum();
+    if total_max <= available {
+        return max.to_vec();
+    }
+
+    let total_min: usize = min.iter().sum();
+    if total_min > available {
+        // Even the longest words don't fit; they will be broken
+        let mut widths: Vec<usize> = min
+            .iter()
+            .map(|&w| (w * available / total_min).max(1))
+            .collect();
+        while widths.iter().sum::<usize>() > available {
+            match widths.iter_mut().filter(|w| **w > 1).max() {
+                Some(widest) => *widest -= 1,
+                None => break,
+            }
+        }
+        return widths;
+    }
+
+    let slack: Vec<usize> = min.iter().zip(max).map(|(min, max)| max - min).collect();
+    let total_slack: usize = slack.iter().sum();
+    let extra = available - total_min;
+    let mut widths: Vec<usize> = min
+        .iter()
+        .zip(&slack)
+        .map(|(&w, &s)| w + extra * s / total_slack.max(1))
+        .collect();
+
+    // Rounding leftovers go to columns still short of their natural width
+    let mut leftover = available - widths.iter().sum::<usize>();
+    while leftover > 0 {
+        let mut given = false;
+        for (width, &max) in widths.iter_mut().zip(max) {
+            if leftover > 0 && *width < max {
+                *width += 1;
+                leftover -= 1;
+                given = true;
+            }
+        }
+        if !given {
+            break;
+        }
+    }
+    widths
+}
+
+fn border_line(widths: &[usize], [left, middle, right]: [char; 3], style: Style) -> Line<'static> {
+    let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+    Line::from(Span::styled(
+        format!("{}{}{}", left, segments.join(&middle.to_string()), right),
+        style,
+    ))
+}
+
+/// Put `first` before the first line and `rest` before the others
+fn prefix_lines(
+    lines: Vec<Line<'static>>,
+    first: &str,
+    rest: &str,
+    style: Style,
+) -> Vec<Line<'static>> {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let prefix = if index == 0 { first } else { rest };
+            let mut spans = vec![Span::styled(prefix.to_string(), style)];
+            spans.extend(line.spans);
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Append a block with a blank line before and after it
+fn push_spaced(lines: &mut Vec<Line<'static>>, block: Vec<Line<'static>>) {
+    if block.is_empty() {
+        return;
+    }
+    lines.push(Line::from(""));
+    lines.extend(block);
+    lines.push(Line::from(""));
+}
+
+fn line_width(line: &Line) -> usize {
+    line.spans
+        .iter()
+        .map(|span| display_width(&span.content))
+        .sum()
+}
+
+fn is_blank(line: &Line) -> bool {
+    line.spans.iter().all(|span| span.content.trim().is_empty())
+}
+
+/// Drop blank lines at either end and collapse runs of them into one
+fn tidy_blank_lines(lines: Vec<Line<'static>>) -> Vec<Line<'static>> {
+    let mut tidy: Vec<Line<'static>> = Vec::with_capacity(lines.len());
+    for line in lines {
+        if is_blank(&line) {
+            if tidy.last().is_some_and(|last| !is_blank(last)) {
+                tidy.push(Line::from(""));
+            }
+        } else {
+            tidy.push(line);
+        }
+    }
+    while tidy.last().is_some_and(is_blank) {
+        tidy.pop();
+    }
+    tidy
+}
+
+/// Removes tracking pixels from email HTML, holds back remote images and
+/// recolors inline colors to suit the theme, much like Dark Reader does
+/// for web pages
+///
+/// Scripts, stylesheets and event handlers are always removed. Remote
+/// images are replaced by their alt text until loading them is allowed,
+/// so opening a message doesn't tell the sender it was read.
+#[derive(Debug, Clone, Default)]
+pub struct EmailSanitizer {
+    load_remote_images: bool,
+    palette: Option<RecolorPalette>,
+}
+
+/// Result of sanitizing a message body
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SanitizedHtml {
+    pub html: String,
+    /// Remote images replaced by their alt text
+    pub blocked_images: usize,
+    /// Tiny or hidden remote images removed as read trackers
+    pub tracking_pixels: usize,
+}
+
+/// Background and text colors email colors are adjusted against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecolorPalette {
+    pub background: (u8, u8, u8),
+    pub foreground: (u8, u8, u8),
+}
+
+impl RecolorPalette {
+    pub fn from_theme(theme: &Theme) -> Self {
+        let palette = &theme.colors.palette;
+        Self {
+            background: color_to_rgb(palette.background).unwrap_or((0, 0, 0)),
+            foreground: color_to_rgb(palette.foreground).unwrap_or((255, 255, 255)),
+        }
+    }
+
+    fn is_dark(&self) -> bool {
+        hsl(self.background).2 < 0.5
+    }
+}
+
+/// Whether a color is used for text or behind it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorRole {
+    Text,
+    Background,
+}
+
+static IMG_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?is)<img\b[^>]*>").unwrap());
+static TAG_ATTRIBUTE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?s)([a-zA-Z-]+)\s*=\s*("[^"]*"|'[^']*'|[^\s"'>]+)"#).unwrap());
+static CSS_COLOR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"#[0-9a-fA-F]{3,8}\b|(?i:rgba?)\([^)]*\)|[a-zA-Z]+").unwrap());
+
+impl EmailSanitizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep remote images instead of replacing them with their alt text
+    pub fn load_remote_images(mut self, load: bool) -> Self {
+        self.load_remote_images = load;
+        self
+    }
+
+    /// Recolor inline colors so they read well on the theme's background
+    pub fn recolor_for(mut self, theme: &Theme) -> Self {
+        self.palette = Some(RecolorPalette::from_theme(theme));
+        self
+    }
+
+    pub fn sanitize(&self, html: &str) -> SanitizedHtml {
+        let mut blocked_images = 0;
+        let mut tracking_pixels = 0;
+
+        let html = IMG_TAG.replace_all(html, |captures: &regex::Captures| {
+            let attributes = tag_attributes(&captures[0]);
+            let src = attributes.get("src").map(String::as_str).unwrap_or("");
+            let remote = is_remote_url(src);
+
+            if remote && is_tracking_pixel(&attributes) {
+                tracking_pixels += 1;
+                String::new()
+            } else if remote && !self.load_remote_images {
+                blocked_images += 1;
+                match attributes.get("alt").map(|alt| alt.trim()) {
+                    Some(alt) if !alt.is_empty() => format!("<span>[image: {}]</span>", alt),
+                    _ => "<span>[image]</span>".to_string(),
+                }
+            } else {
+                captures[0].to_string()
+            }
+        });
+
+        let load_remote_images = self.load_remote_images;
+        let palette = self.palette;
+        let html = ammonia::Builder::default()
+            .add_url_schemes(&["data", "cid"])
+            .add_tags(&["font"])
+            .add_generic_attributes(&["style"])
+            .add_tag_attributes("font", &["color"])
+            .add_tag_attributes("table", &["bgcolor"])
+            .add_tag_attributes("tr", &["bgcolor"])
+            .add_tag_attributes("td", &["bgcolor"])
+            .add_tag_attributes("th", &["bgcolor"])
+            .attribute_filter(move |_element, attribute, value| match attribute {
+                "style" => {
+                    let style = if load_remote_images {
+                        Cow::Borrowed(value)
+                    } else {
+                        Cow::Owned(strip_remote_urls(value))
+                    };
+                    Some(match palette {
+                        Some(palette) => recolor_style(&style, &palette).into(),
+                        None => style.into_owned().into(),
+                    })
+                }
+                "color" | "bgcolor" => {
+                    let role = if attribute == "color" {
+                        ColorRole::Text
+                    } else {
+                        ColorRole::Background
+                    };
+                    Some(match palette {
+                        Some(palette) => recolor_value(value, role, &palette).into(),
+                        None => value.into(),
+                    })
+                }
+                _ => Some(value.into()),
+            })
+            .clean(&html)
+            .to_string();
+
+        SanitizedHtml {
+            html,
+            blocked_images,
+            tracking_pixels,
+        }
+    }
+}
+
+/// Lowercased attribute names and unquoted values of a start tag
+fn tag_attributes(tag: &str) -> HashMap<String, String> {
+    TAG_ATTRIBUTE
+        .captures_iter(tag)
+        .map(|captures| {
+            let value = captures[2].trim_matches(|c| c == '"' || c == '\'');
+            (captures[1].to_lowercase(), value.to_string())
+        })
+        .collect()
+}
+
+fn is_remote_url(url: &str) -> bool {
+    let url = url.trim().to_ascii_lowercase();
+    url.starts_with("http://") || url.starts_with("https://") || url.starts_with("//")
+}
+
+/// Images no bigger than a pixel, or hidden, are only there to report that
+/// the message was opened
+fn is_tracking_pixel(attributes: &HashMap<String, String>) -> bool {
+    let image = ImageReference {
+        src: attributes.get("src").cloned().unwrap_or_default(),
+        alt: None,
+        width: attributes.get("width").and_then(|w| parse_pixels(w)),
+        height: attributes.get("height").and_then(|h| parse_pixels(h)),
+    };
+    if image.is_tracking_pixel() {
+        return true;
+    }
+
+    let style = attributes
+        .get("style")
+        .map(|style| style.to_ascii_lowercase().replace(' ', ""))
+        .unwrap_or_default();
+    let tiny = |property: &str| {
+        style.split(';').any(|declaration| {
+            declaration
+                .strip_prefix(property)
+                .and_then(|value| value.strip_prefix(':'))
+                .and_then(parse_pixels)
+                .is_some_and(|pixels| pixels <= 1)
+        })
+    };
+    style.contains("display:none")
+        || style.contains("visibility:hidden")
+        || (tiny("width") && tiny("height"))
+}
+
+fn parse_pixels(value: &str) -> Option<u32> {
+    value.trim().trim_end_matches("px").trim().parse().ok()
+}
+
+/// Drop CSS declarations that load something from the network
+fn strip_remote_urls(style: &str) -> String {
+    style
+        .split(';')
+        .filter(|declaration| {
+            let declaration = declaration.to_ascii_lowercase();
+            !declaration.contains("url(") || declaration.contains("url(data:")
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Recolor the color, background and background-color declarations
+fn recolor_style(style: &str, palette: &RecolorPalette) -> String {
+    style
+        .split(';')
+        .map(|declaration| {
+            let Some((property, value)) = declaration.split_once(':') else {
+                return declaration.to_string();
+            };
+            let role = match property.trim().to_ascii_lowercase().as_str() {
+                "color" => ColorRole::Text,
+                "background" | "background-color" => ColorRole::Background,
+                _ => return declaration.to_string(),
+            };
+            format!("{}:{}", property, recolor_value(value, role, palette))
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Replace every color in a CSS value; other words are kept
+fn recolor_value(value: &str, role: ColorRole, palette: &RecolorPalette) -> String {
+    CSS_COLOR
+        .replace_all(value, |captures: &regex::Captures| {
+            let token = &captures[0];
+            match parse_css_color(token) {
+                Some(rgb) => {
+                    let (r, g, b) = recolor(rgb, role, palette);
+                    format!("#{:02x}{:02x}{:02x}", r, g, b)
+                }
+                None => token.to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Move a color's lightness to the theme's side: light backgrounds become
+/// dark on a dark theme and dark text becomes light, keeping the hue.
+/// Colors that already contrast with the theme are left alone
+fn recolor(rgb: (u8, u8, u8), role: ColorRole, palette: &RecolorPalette) -> (u8, u8, u8) {
+    let (hue, saturation, lightness) = hsl(rgb);
+    let dark = palette.is_dark();
+
+    match role {
+        ColorRole::Background => {
+            // Near-white on a dark theme (or near-black on a light one) is
+            // just "the page", so use the theme's own background
+            let page = if dark {
+                lightness > 0.9
+            } else {
+                lightness < 0.1
+            };
+            if page && saturation < 0.3 {
+                return palette.background;
+            }
+            if dark && lightness > 0.5 {
+                rgb_from_hsl(hue, saturation, (1.0 - lightness).min(0.3))
+            } else if !dark && lightness < 0.5 {
+                rgb_from_hsl(hue, saturation, (1.0 - lightness).max(0.7))
+            } else {
+                rgb
+            }
+        }
+        ColorRole::Text => {
+            let plain = if dark {
+                lightness < 0.2
+            } else {
+                lightness > 0.8
+            };
+            if plain && saturation < 0.3 {
+                return palette.foreground;
+            }
+            if dark && lightness < 0.6 {
+                rgb_from_hsl(hue, saturation, (1.0 - lightness).max(0.6))
+            } else if !dark && lightness > 0.4 {
+                rgb_from_hsl(hue, saturation, (1.0 - lightness).min(0.4))
+            } else {
+                rgb
+            }
+        }
+    }
+}
+
+/// Parse `#rgb`, `#rrggbb` (alpha ignored), `rgb()`/`rgba()` or a basic
+/// named color
+fn parse_css_color(token: &str) -> Option<(u8, u8, u8)> {
+    if let Some(hex) = token.strip_prefix('#') {
+        let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+        let pair = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return match hex.len() {
+            3 | 4 => Some((digit(0)? * 17, digit(1)? * 17, digit(2)? * 17)),
+            6 | 8 => Some((pair(0)?, pair(2)?, pair(4)?)),
+            _ => None,
+        };
+    }
+
+    let lower = token.to_ascii_lowercase();
+    if let Some(arguments) = lower
+        .strip_prefix("rgba(")
+        .or_else(|| lower.strip_prefix("rgb("))
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let channels: Vec<u8> = arguments
+            .split([',', ' ', '/'])
+            .filter(|part| !part.is_empty())
+            .take(3)
+            .map(|part| match part.strip_suffix('%') {
+                Some(percent) => percent
+                    .parse::<f32>()
+                    .ok()
+                    .map(|p| (p.clamp(0.0, 100.0) * 2.55).round() as u8),
+                None => part.parse::<f32>().ok().map(|v| v.clamp(0.0, 255.0) as u8),
+            })
+            .collect::<Option<_>>()?;
+        return match channels[..] {
+            [r, g, b] => Some((r, g, b)),
+            _ => None,
+        };
+    }
+
+    let rgb = match lower.as_str() {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "red" => (255, 0, 0),
+        "green" => (0, 128, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "orange" => (255, 165, 0),
+        "purple" => (128, 0, 128),
+        "gray" | "grey" => (128, 128, 128),
+        "silver" => (192, 192, 192),
+        "lightgray" | "lightgrey" => (211, 211, 211),
+        "darkgray" | "darkgrey" => (169, 169, 169),
+        "gainsboro" => (220, 220, 220),
+        "whitesmoke" => (245, 245, 245),
+        "maroon" => (128, 0, 0),
+        "navy" => (0, 0, 128),
+        "teal" => (0, 128, 128),
+        "olive" => (128, 128, 0),
+        "lime" => (0, 255, 0),
+        "aqua" | "cyan" => (0, 255, 255),
+        "fuchsia" | "magenta" => (255, 0, 255),
+        _ => return None,
+    };
+    Some(rgb)
+}
+
+/// RGB of a terminal color, for the named ANSI colors and true colors
+fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    Some(match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black => (0, 0, 0),
+        Color::White => (255, 255, 255),
+        Color::Gray => (192, 192, 192),
+        Color::DarkGray => (128, 128, 128),
+        Color::Red | Color::LightRed => (255, 0, 0),
+        Color::Green | Color::LightGreen => (0, 255, 0),
+        Color::Blue | Color::LightBlue => (0, 0, 255),
+        Color::Yellow | Color::LightYellow => (255, 255, 0),
+        Color::Cyan | Color::LightCyan => (0, 255, 255),
+        Color::Magenta | Color::LightMagenta => (255, 0, 255),
+        _ => return None,
+    })
+}
+
+/// Hue in degrees, saturation and lightness in 0..=1
+fn hsl((r, g, b): (u8, u8, u8)) -> (f32, f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return (0.0, 0.0, lightness);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    (hue, saturation, lightness)
+}
+
+fn rgb_from_hsl(hue: f32, saturation: f32, lightness: f32) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match hue as u32 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    let channel = |value: f32| ((value + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
 /// Check if content appears to be HTML
 pub fn is_html_content(content: &str) -> bool {
     let content_lower = content.to_lowercase();
@@ -816,4 +1654,119 @@ mod tests {
         assert!(result.contains("Hello world"));
         assert!(result.contains("Second paragraph"));
     }
+
+    #[test]
+    fn test_sanitizer_blocks_trackers_and_remote_images() {
+        let html = r#"<p>Hi</p>
+            <img src="https://shop.example/logo.png" alt="Shop logo">
+            <img src="https://t.example/open?id=1" width="1" height="1">
+            <img src="https://t.example/o.gif" style="display: none">
+            <img src="data:image/png;base64,iVBORw0KGgo=" alt="inline">
+            <div style="color: red; background: url(https://t.example/bg.png)">x</div>"#;
+
+        let blocked = EmailSanitizer::new().sanitize(html);
+        assert_eq!(blocked.blocked_images, 1);
+        assert_eq!(blocked.tracking_pixels, 2);
+        assert!(blocked.html.contains("[image: Shop logo]"));
+        assert!(blocked.html.contains("data:image/png;base64"));
+        assert!(!blocked.html.contains("t.example"));
+        assert!(!blocked.html.contains("shop.example"));
+        assert!(blocked.html.contains("color: red"));
+
+        let loaded = EmailSanitizer::new()
+            .load_remote_images(true)
+            .sanitize(html);
+        assert_eq!(loaded.blocked_images, 0);
+        assert_eq!(loaded.tracking_pixels, 2);
+        assert!(loaded.html.contains("https://shop.example/logo.png"));
+        assert!(!loaded.html.contains("open?id=1"));
+    }
+
+    #[test]
+    fn test_sanitizer_recolors_for_theme() {
+        let html = r##"<table bgcolor="#ffffff"><tr><td style="background-color: #FFF; color: rgb(0, 0, 0)">
+            <font color="navy">Text</font></td></tr></table>"##;
+
+        let dark = Theme::professional_dark();
+        let palette = RecolorPalette::from_theme(&dark);
+        let hex = |(r, g, b): (u8, u8, u8)| format!("#{:02x}{:02x}{:02x}", r, g, b);
+        let recolored = EmailSanitizer::new().recolor_for(&dark).sanitize(html).html;
+        assert!(recolored.contains(&format!(r#"bgcolor="{}""#, hex(palette.background))));
+        assert!(recolored.contains(&format!("background-color: {}", hex(palette.background))));
+        assert!(recolored.contains(&format!("color: {}", hex(palette.foreground))));
+        // Dark blue text becomes a light blue that reads on a dark background
+        let navy = Regex::new(r#"<font color="(#[0-9a-f]{6})""#).unwrap();
+        let navy = navy.captures(&recolored).unwrap()[1].to_string();
+        let (_, _, lightness) = hsl(parse_css_color(&navy).unwrap());
+        assert!(lightness >= 0.6, "{} is too dark", navy);
+
+        // A light theme leaves a white page alone
+        let light = Theme::professional_light();
+        let recolored = EmailSanitizer::new()
+            .recolor_for(&light)
+            .sanitize(html)
+            .html;
+        assert!(recolored.contains(r##"bgcolor="#ffffff""##));
+    }
+
+    fn plain_lines(text: &Text) -> Vec<String> {
+        text.lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tables_are_boxed_to_fit_width() {
+        let html = "<table><tr><th>Item</th><th>Notes</th></tr>\
+            <tr><td>Coffee</td><td>Freshly ground beans from the roastery down the road</td></tr>\
+            <tr><td>Tea</td><td>Green</td></tr></table>";
+        let mut renderer = HtmlRenderer::new(40);
+        let lines = plain_lines(&renderer.render_html(html));
+
+        assert!(lines[0].starts_with('┌') && lines[0].ends_with('┐'));
+        assert!(lines.last().unwrap().starts_with('└'));
+        assert!(lines.iter().any(|line| line.starts_with('├')));
+        // Every row lines up with the borders and stays within the width
+        let width = display_width(&lines[0]);
+        assert!(width <= 40);
+        assert!(lines.iter().all(|line| display_width(line) == width));
+        assert!(lines.iter().any(|line| line.starts_with("│ Coffee │")));
+        // The long note wraps inside its column
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("│        │ the roastery")));
+    }
+
+    #[test]
+    fn test_layout_tables_read_as_blocks() {
+        let html = r#"<table width="600"><tr><td><p>Weekly news</p></td></tr>
+            <tr><td><table><tr><td><p>Story one</p></td><td><p>Story two</p></td></tr></table></td></tr>
+            </table>"#;
+        let mut renderer = HtmlRenderer::new(60);
+        let lines = plain_lines(&renderer.render_html(html));
+
+        assert!(lines.iter().all(|line| !line.contains('│')));
+        assert_eq!(lines, vec!["Weekly news", "", "Story one", "", "Story two"]);
+    }
+
+    #[test]
+    fn test_lists_number_and_indent() {
+        let html = r#"<ol start="9"><li>Ninth</li><li>Tenth
+            <ul><li>Nested point that is long enough to wrap onto another line</li></ul></li></ol>"#;
+        let mut renderer = HtmlRenderer::new(30);
+        let lines = plain_lines(&renderer.render_html(html));
+
+        assert_eq!(lines[0], " 9. Ninth");
+        assert_eq!(lines[1], "10. Tenth");
+        assert!(lines[2].starts_with("    ◦ Nested point"));
+        // Wrapped lines continue under the item text, not the bullet
+        assert!(lines[3].starts_with("      ") && !lines[3].trim().is_empty());
+        assert!(lines.iter().all(|line| display_width(line) <= 30));
+    }
 }
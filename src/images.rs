@@ -1,3 +1,4 @@
+use crate::ui::graphics::{placeholder_lines, GraphicsProtocol, TerminalCapabilities};
 use anyhow::{anyhow, Result};
 use image::{DynamicImage, ImageFormat, ImageOutputFormat};
 use std::collections::HashMap;
@@ -236,107 +237,46 @@ impl ImageManager {
         Ok(encoded)
     }
 
-    /// Generate a placeholder for unsupported images
+    /// Generate a placeholder for unsupported images: a box with the alt
+    /// text, shortened to fit
     pub fn generate_placeholder(
         &self,
         alt_text: Option<&str>,
         width: Option<u32>,
         height: Option<u32>,
     ) -> String {
-        let text = alt_text.unwrap_or("Image");
-        let w = width.unwrap_or(20).min(self.max_width);
-        let h = height.unwrap_or(5).min(self.max_height);
-
-        // Create a simple ASCII art placeholder
-        let mut placeholder = String::new();
-
-        // Ensure minimum dimensions
-        let w = w.max(3); // Minimum width of 3 for borders
-        let h = h.max(3); // Minimum height of 3 for borders
-
-        // Top border
-        placeholder.push('┌');
-        for _ in 0..(w - 2) {
-            placeholder.push('─');
-        }
-        placeholder.push_str("┐\n");
-
-        // Middle rows with text
-        for row in 0..h.saturating_sub(2) {
-            placeholder.push('│');
-            if row == h / 2 - 1 && text.len() <= (w - 2) as usize {
-                // Center the text
-                let padding = ((w - 2) as usize).saturating_sub(text.len()) / 2;
-                for _ in 0..padding {
-                    placeholder.push(' ');
-                }
-                placeholder.push_str(text);
-                for _ in 0..((w - 2) as usize - padding - text.len()) {
-                    placeholder.push(' ');
-                }
-            } else {
-                for _ in 0..(w - 2) {
-                    placeholder.push(' ');
-                }
-            }
-            placeholder.push_str("│\n");
-        }
-
-        // Bottom border
-        placeholder.push('└');
-        for _ in 0..(w - 2) {
-            placeholder.push('─');
-        }
-        placeholder.push('┘');
-
-        placeholder
+        let w = width.unwrap_or(20).min(self.max_width).min(u16::MAX as u32);
+        let h = height
+            .unwrap_or(5)
+            .min(self.max_height)
+            .min(u16::MAX as u32);
+
+        placeholder_lines(alt_text, w as u16, h as u16)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     /// Detect which terminal graphics protocol is supported
+    ///
+    /// Inside tmux or screen this is `None` unless `COMUNICADO_GRAPHICS`
+    /// forces a protocol, so escape sequences never reach a multiplexer
+    /// that would print them as text.
     fn detect_terminal_protocol() -> TerminalProtocol {
-        // Check environment variables that indicate terminal capabilities
-        if let Ok(term) = std::env::var("TERM") {
-            if term.contains("kitty") {
-                return TerminalProtocol::Kitty;
-            }
-            if term.contains("xterm") {
-                // Check for Sixel support
-                if Self::test_sixel_support() {
-                    return TerminalProtocol::Sixel;
-                }
-            }
-        }
-
-        // Check TERM_PROGRAM for kitty
-        if let Ok(term_program) = std::env::var("TERM_PROGRAM") {
-            if term_program == "kitty" {
-                return TerminalProtocol::Kitty;
-            }
-        }
-
-        // Check for Kitty graphics protocol support by querying terminal
-        if Self::test_kitty_support() {
-            return TerminalProtocol::Kitty;
-        }
-
-        TerminalProtocol::None
-    }
-
-    /// Test if terminal supports Kitty graphics protocol
-    fn test_kitty_support() -> bool {
-        // This is a simplified test - in production you'd query the terminal
-        // For now, we'll just check if we're in a kitty terminal
-        std::env::var("KITTY_WINDOW_ID").is_ok()
-    }
-
-    /// Test if terminal supports Sixel graphics
-    fn test_sixel_support() -> bool {
-        // This is a simplified test - in production you'd query terminal capabilities
-        // Common terminals that support Sixel: xterm (with Sixel enabled), foot, wezterm
-        if let Ok(term) = std::env::var("TERM") {
-            return term.contains("xterm") || term.contains("foot") || term.contains("wezterm");
+        let capabilities = TerminalCapabilities::detect();
+        if capabilities.supports(GraphicsProtocol::Kitty) {
+            TerminalProtocol::Kitty
+        } else if capabilities.supports(GraphicsProtocol::Sixel) {
+            TerminalProtocol::Sixel
+        } else {
+            TerminalProtocol::None
         }
-        false
     }
 
     /// Get cache directory for images
@@ -558,6 +498,12 @@ impl ImageReference {
         Some((data.to_string(), mime_type))
     }
 
+    /// Whether the image is at most one pixel in both directions, as read
+    /// tracking images are
+    pub fn is_tracking_pixel(&self) -> bool {
+        matches!((self.width, self.height), (Some(width), Some(height)) if width <= 1 && height <= 1)
+    }
+
     /// Check if this is a valid HTTP/HTTPS URL
     pub fn is_http_url(&self) -> bool {
         self.src.starts_with("http://") || self.src.starts_with("https://")
@@ -1,8 +1,12 @@
-use crate::imap::{ImapAuthMethod, ImapClient, ImapConfig, ImapError, ImapResult};
+use crate::email::PlainTextAccounts;
+use crate::imap::{
+    ImapAuthMethod, ImapCapability, ImapClient, ImapConfig, ImapError, ImapResult, TlsInfo,
+};
+use crate::jmap::{JmapAuthMethod, JmapClient, JmapConfig, JmapMode, JmapSettings};
 use crate::oauth2::{AccountConfig as OAuth2AccountConfig, SecureStorage, TokenManager};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, OnceCell, RwLock};
 
 /// IMAP account information
 #[derive(Debug, Clone)]
@@ -76,6 +80,15 @@ impl ImapAccount {
     }
 }
 
+/// What a live connection negotiated with the server
+#[derive(Debug, Clone)]
+pub struct ConnectionDetails {
+    /// Capabilities as advertised, before overrides
+    pub server_capabilities: Vec<ImapCapability>,
+    /// `None` for plain-text connections
+    pub tls: Option<TlsInfo>,
+}
+
 /// Connection pool for IMAP clients
 struct ConnectionPool {
     clients: HashMap<String, Arc<Mutex<ImapClient>>>,
@@ -121,6 +134,10 @@ impl ConnectionPool {
         Ok(self.clients.get(&account.account_id).unwrap().clone())
     }
 
+    fn client(&self, account_id: &str) -> Option<Arc<Mutex<ImapClient>>> {
+        self.clients.get(account_id).cloned()
+    }
+
     fn disconnect_account(&mut self, account_id: &str) {
         self.clients.remove(account_id);
     }
@@ -130,6 +147,12 @@ impl ConnectionPool {
     }
 }
 
+/// A JMAP client shared between the sync engine and user actions
+type SharedJmapClient = Arc<Mutex<JmapClient>>;
+
+/// JMAP discovery for one account, run at most once however many callers wait on it
+type JmapDiscovery = Arc<OnceCell<Option<SharedJmapClient>>>;
+
 /// Manager for multiple IMAP accounts
 pub struct ImapAccountManager {
     accounts: Arc<RwLock<HashMap<String, ImapAccount>>>,
@@ -137,6 +160,11 @@ pub struct ImapAccountManager {
     token_manager: Option<TokenManager>,
     storage: SecureStorage,
     default_account: Option<String>,
+    jmap_settings: Arc<RwLock<JmapSettings>>,
+    /// Accounts whose HTML is never downloaded
+    plain_text_accounts: Arc<RwLock<PlainTextAccounts>>,
+    /// Result of JMAP discovery per account; `None` means the account uses IMAP
+    jmap_clients: Arc<RwLock<HashMap<String, JmapDiscovery>>>,
 }
 
 impl ImapAccountManager {
@@ -151,6 +179,9 @@ impl ImapAccountManager {
             token_manager: None,
             storage,
             default_account: None,
+            jmap_settings: Arc::new(RwLock::new(JmapSettings::default())),
+            plain_text_accounts: Arc::new(RwLock::new(PlainTextAccounts::default())),
+            jmap_clients: Arc::new(RwLock::new(HashMap::new())),
         })
     }
 
@@ -161,6 +192,20 @@ impl ImapAccountManager {
         Ok(manager)
     }
 
+    /// Create account manager reading accounts from the given storage
+    pub fn new_with_storage(storage: SecureStorage, token_manager: TokenManager) -> Self {
+        Self {
+            accounts: Arc::new(RwLock::new(HashMap::new())),
+            connection_pool: Arc::new(RwLock::new(ConnectionPool::new(10))), // Max 10 connections
+            token_manager: Some(token_manager),
+            storage,
+            default_account: None,
+            jmap_settings: Arc::new(RwLock::new(JmapSettings::default())),
+            plain_text_accounts: Arc::new(RwLock::new(PlainTextAccounts::default())),
+            jmap_clients: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
     /// Load accounts from storage
     pub async fn load_accounts(&mut self) -> ImapResult<()> {
         // Load OAuth2 accounts
@@ -209,6 +254,7 @@ impl ImapAccountManager {
             // Disconnect the account
             let mut pool = self.connection_pool.write().await;
             pool.disconnect_account(account_id);
+            self.jmap_clients.write().await.remove(account_id);
 
             // Update default account if necessary
             if self.default_account.as_ref() == Some(&account_id.to_string()) {
@@ -265,6 +311,51 @@ impl ImapAccountManager {
         }
     }
 
+    /// Ignore capabilities for an account even when its server advertises them
+    ///
+    /// Applies to later reconnects, and to the pooled connection as soon as
+    /// whatever it's doing finishes.
+    pub async fn set_disabled_capabilities(
+        &self,
+        account_id: &str,
+        disabled: Vec<String>,
+    ) -> ImapResult<()> {
+        {
+            let mut accounts = self.accounts.write().await;
+            let account = accounts
+                .get_mut(account_id)
+                .ok_or_else(|| ImapError::not_found(format!("Account {} not found", account_id)))?;
+            account.config.disabled_capabilities = disabled.clone();
+        }
+
+        let client = self.connection_pool.read().await.client(account_id);
+        if let Some(client) = client {
+            // A sync may hold the client for a while; don't wait for it here
+            tokio::spawn(async move {
+                client.lock().await.set_disabled_capabilities(disabled);
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Capabilities and TLS parameters of the account's live connection
+    ///
+    /// Never connects; returns `None` when there is no connection yet or it is
+    /// busy with another operation.
+    pub async fn connection_details(&self, account_id: &str) -> Option<ConnectionDetails> {
+        let client = self.connection_pool.read().await.client(account_id)?;
+        let client = client.try_lock().ok()?;
+        if !client.is_connected() {
+            return None;
+        }
+
+        Some(ConnectionDetails {
+            server_capabilities: client.server_capabilities().to_vec(),
+            tls: client.tls_info().cloned(),
+        })
+    }
+
     /// Get IMAP client for account
     pub async fn get_client(&self, account_id: &str) -> ImapResult<Arc<Mutex<ImapClient>>> {
         tracing::debug!("Getting IMAP client for account: '{}'", account_id);
@@ -369,6 +460,91 @@ impl ImapAccountManager {
         Ok(client_arc)
     }
 
+    /// Choose per-account JMAP use; discovery reruns on the next request
+    pub async fn set_jmap_settings(&self, settings: JmapSettings) {
+        *self.jmap_settings.write().await = settings;
+        self.jmap_clients.write().await.clear();
+    }
+
+    /// Set the accounts whose HTML is never downloaded
+    pub async fn set_plain_text_accounts(&self, accounts: PlainTextAccounts) {
+        *self.plain_text_accounts.write().await = accounts;
+    }
+
+    /// Whether an account's mail is only fetched and shown as plain text
+    pub async fn is_plain_text_only(&self, account_id: &str) -> bool {
+        self.plain_text_accounts.read().await.contains(account_id)
+    }
+
+    /// JMAP client for an account whose provider offers JMAP
+    ///
+    /// Discovery runs once per account; `None` means the account keeps using
+    /// IMAP, either by configuration or because no JMAP session answered.
+    pub async fn get_jmap_client(&self, account_id: &str) -> Option<Arc<Mutex<JmapClient>>> {
+        // Callers arriving during discovery wait for it instead of starting another
+        let discovery = self
+            .jmap_clients
+            .write()
+            .await
+            .entry(account_id.to_string())
+            .or_default()
+            .clone();
+        discovery
+            .get_or_init(|| self.discover_jmap(account_id))
+            .await
+            .clone()
+    }
+
+    /// Connect to the account's JMAP session, if it should have one
+    async fn discover_jmap(&self, account_id: &str) -> Option<Arc<Mutex<JmapClient>>> {
+        let account = self.get_account(account_id).await?;
+        let settings = self.jmap_settings.read().await.for_account(account_id);
+
+        let session_url = match settings.mode {
+            JmapMode::Never => return None,
+            JmapMode::Auto => settings
+                .session_url
+                .or_else(|| crate::jmap::discover_session_url(&account.config.hostname))?,
+            JmapMode::Always => settings.session_url.unwrap_or_else(|| {
+                let domain = account
+                    .email_address
+                    .rsplit_once('@')
+                    .map_or(account.config.hostname.as_str(), |(_, domain)| domain);
+                crate::jmap::well_known_session_url(domain)
+            }),
+        };
+
+        let mut config = JmapConfig::from_imap_config(&account.config, session_url);
+        if let Some(api_token) = settings.api_token {
+            config.auth_method = JmapAuthMethod::Bearer(api_token);
+        }
+        let mut client = match (&config.auth_method, &self.token_manager) {
+            (JmapAuthMethod::OAuth2 { .. }, Some(token_manager)) => {
+                JmapClient::new_with_oauth2(config, token_manager.clone())
+            }
+            _ => JmapClient::new(config),
+        };
+
+        match tokio::time::timeout(std::time::Duration::from_secs(30), client.connect()).await {
+            Ok(Ok(())) => {
+                tracing::info!("Using JMAP for account {}", account_id);
+                Some(Arc::new(Mutex::new(client)))
+            }
+            Ok(Err(e)) => {
+                if settings.mode == JmapMode::Always {
+                    tracing::warn!("JMAP unavailable for account {}, using IMAP: {}", account_id, e);
+                } else {
+                    tracing::debug!("No JMAP session for account {}: {}", account_id, e);
+                }
+                None
+            }
+            Err(_) => {
+                tracing::warn!("JMAP discovery timed out for account {}, using IMAP", account_id);
+                None
+            }
+        }
+    }
+
     /// Test connection for an account
     pub async fn test_connection(&self, account_id: &str) -> ImapResult<bool> {
         match self.get_client(account_id).await {
@@ -427,6 +603,7 @@ impl ImapAccountManager {
     pub async fn disconnect_all(&mut self) -> ImapResult<()> {
         let mut pool = self.connection_pool.write().await;
         pool.disconnect_all();
+        self.jmap_clients.write().await.clear();
         Ok(())
     }
 
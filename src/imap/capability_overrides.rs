@@ -0,0 +1,55 @@
+//! Per-account capability overrides
+//!
+//! Some servers advertise extensions they don't implement correctly. Listing
+//! a capability here makes the client behave as if the server never
+//! advertised it, without affecting other accounts.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Capabilities to ignore, per account
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CapabilityOverrides {
+    /// Disabled capability names keyed by account ID, e.g. `["IDLE"]`
+    pub accounts: HashMap<String, Vec<String>>,
+}
+
+impl CapabilityOverrides {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "imap_capabilities.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Capabilities disabled for an account
+    pub fn disabled_for(&self, account_id: &str) -> Vec<String> {
+        self.accounts.get(account_id).cloned().unwrap_or_default()
+    }
+
+    pub fn is_disabled(&self, account_id: &str, capability: &str) -> bool {
+        self.accounts
+            .get(account_id)
+            .is_some_and(|disabled| disabled.iter().any(|name| name.eq_ignore_ascii_case(capability)))
+    }
+
+    /// Enable or disable a capability for an account
+    pub fn set_disabled(&mut self, account_id: &str, capability: &str, disabled: bool) {
+        let names = self.accounts.entry(account_id.to_string()).or_default();
+        names.retain(|name| !name.eq_ignore_ascii_case(capability));
+        if disabled {
+            names.push(capability.to_uppercase());
+        }
+        if names.is_empty() {
+            self.accounts.remove(account_id);
+        }
+    }
+}
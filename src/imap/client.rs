@@ -1,22 +1,36 @@
 use crate::imap::connection::ConnectionState;
 use crate::imap::protocol::ImapProtocol;
 use crate::imap::{
-    IdleNotification, IdleNotificationService, ImapAuthMethod, ImapCapability, ImapConfig,
-    ImapConnection, ImapError, ImapFolder, ImapMessage, ImapResult, MessageFlag, SearchCriteria,
+    BodyStructure, IdleNotification, IdleNotificationService, ImapAuthMethod, ImapCapability,
+    ImapConfig, ImapConnection, ImapError, ImapFolder, ImapMessage, ImapResult, MessageFlag,
+    SearchCriteria, TlsInfo,
 };
 use crate::oauth2::TokenManager;
+use chrono::{DateTime, Utc};
+use futures::Stream;
 use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
 use std::sync::Arc;
+use tokio::io::AsyncRead;
 use tokio::sync::Mutex;
 
+/// Bytes asked for by each partial FETCH when streaming a body section
+const BODY_SECTION_CHUNK_SIZE: usize = 256 * 1024;
+
 /// High-level IMAP client
 pub struct ImapClient {
     connection: ImapConnection,
+    /// Capabilities in effect, after per-account overrides
     capabilities: Vec<ImapCapability>,
+    /// Capabilities exactly as the server advertised them
+    server_capabilities: Vec<ImapCapability>,
     selected_folder: Option<String>,
     folders_cache: HashMap<String, ImapFolder>,
     token_manager: Option<TokenManager>,
     idle_service: Option<Arc<IdleNotificationService>>,
+    /// QRESYNC has been enabled on this connection
+    qresync_enabled: bool,
 }
 
 impl ImapClient {
@@ -25,10 +39,12 @@ impl ImapClient {
         Self {
             connection: ImapConnection::new(config),
             capabilities: Vec::new(),
+            server_capabilities: Vec::new(),
             selected_folder: None,
             folders_cache: HashMap::new(),
             token_manager: None,
             idle_service: None,
+            qresync_enabled: false,
         }
     }
 
@@ -37,19 +53,28 @@ impl ImapClient {
         Self {
             connection: ImapConnection::new(config),
             capabilities: Vec::new(),
+            server_capabilities: Vec::new(),
             selected_folder: None,
             folders_cache: HashMap::new(),
             token_manager: Some(token_manager),
             idle_service: None,
+            qresync_enabled: false,
         }
     }
 
+    /// Get the server configuration
+    pub fn config(&self) -> &ImapConfig {
+        self.connection.config()
+    }
+
     /// Connect to the IMAP server
     pub async fn connect(&mut self) -> ImapResult<()> {
         self.connection.connect().await?;
+        self.qresync_enabled = false;
 
         // Fetch server capabilities
-        self.capabilities = self.get_capabilities().await?;
+        self.server_capabilities = self.get_capabilities().await?;
+        self.apply_capability_overrides();
 
         Ok(())
     }
@@ -58,8 +83,10 @@ impl ImapClient {
     pub async fn disconnect(&mut self) -> ImapResult<()> {
         self.connection.disconnect().await?;
         self.capabilities.clear();
+        self.server_capabilities.clear();
         self.selected_folder = None;
         self.folders_cache.clear();
+        self.qresync_enabled = false;
         Ok(())
     }
 
@@ -247,6 +274,60 @@ impl ImapClient {
         Ok(folder)
     }
 
+    /// Select a folder asking for mod-sequences (RFC 7162)
+    ///
+    /// Once QRESYNC is enabled, passing the folder's last known UIDVALIDITY
+    /// and HIGHESTMODSEQ makes the server report the UIDs expunged since
+    /// then; these are returned with the folder. Without it the list is empty.
+    pub async fn select_folder_condstore(
+        &mut self,
+        folder_name: &str,
+        known: Option<(u32, u64)>,
+    ) -> ImapResult<(ImapFolder, Vec<u32>)> {
+        if !self.connection.is_authenticated() {
+            return Err(ImapError::invalid_state("Not authenticated"));
+        }
+
+        let qresync = known.filter(|_| self.qresync_enabled);
+        let command = ImapProtocol::format_select_condstore(folder_name, qresync);
+        let response = self.connection.send_command(&command).await?;
+        let mut folder = ImapProtocol::parse_select_response(&response)?;
+        let vanished = ImapProtocol::parse_vanished(&response);
+
+        folder.name = folder_name.to_string();
+        folder.full_name = folder_name.to_string();
+
+        self.connection
+            .set_state(ConnectionState::Selected(folder_name.to_string()));
+        self.selected_folder = Some(folder_name.to_string());
+        self.folders_cache
+            .insert(folder_name.to_string(), folder.clone());
+
+        Ok((folder, vanished))
+    }
+
+    /// Enable QRESYNC for this connection (RFC 7162)
+    ///
+    /// Returns whether it is on; servers that don't advertise it are left alone.
+    pub async fn enable_qresync(&mut self) -> ImapResult<bool> {
+        if self.qresync_enabled {
+            return Ok(true);
+        }
+        if !self.capabilities.contains(&ImapCapability::QResync) {
+            return Ok(false);
+        }
+        if !self.connection.is_authenticated() {
+            return Err(ImapError::invalid_state("Not authenticated"));
+        }
+
+        let command = ImapProtocol::format_enable(&["QRESYNC"]);
+        let response = self.connection.send_command(&command).await?;
+        self.qresync_enabled = ImapProtocol::parse_enabled(&response)
+            .iter()
+            .any(|capability| capability.eq_ignore_ascii_case("QRESYNC"));
+        Ok(self.qresync_enabled)
+    }
+
     /// Examine a folder (read-only)
     pub async fn examine_folder(&mut self, folder_name: &str) -> ImapResult<ImapFolder> {
         if !self.connection.is_authenticated() {
@@ -299,6 +380,144 @@ impl ImapClient {
         ImapProtocol::parse_fetch_response(&response)
     }
 
+    /// Fetch messages whose mod-sequence is above `modseq` (CONDSTORE CHANGEDSINCE)
+    pub async fn uid_fetch_changed_since(
+        &mut self,
+        uid_set: &str,
+        items: &[&str],
+        modseq: u64,
+    ) -> ImapResult<Vec<ImapMessage>> {
+        if self.selected_folder.is_none() {
+            return Err(ImapError::invalid_state("No folder selected"));
+        }
+
+        let command = ImapProtocol::format_uid_fetch_changed_since(uid_set, items, modseq);
+        let response = self.connection.send_command(&command).await?;
+        ImapProtocol::parse_fetch_response(&response)
+    }
+
+    /// Fetch the MIME structure of a message
+    pub async fn uid_fetch_bodystructure(&mut self, uid: u32) -> ImapResult<Option<BodyStructure>> {
+        if self.selected_folder.is_none() {
+            return Err(ImapError::invalid_state("No folder selected"));
+        }
+
+        let command = ImapProtocol::format_uid_fetch(&uid.to_string(), &["UID", "BODYSTRUCTURE"]);
+        let response = self.connection.send_command(&command).await?;
+        Ok(ImapProtocol::parse_bodystructure(&response))
+    }
+
+    /// Fetch one body section exactly as stored, without setting `\Seen`
+    pub async fn uid_fetch_section(&mut self, uid: u32, section: &str) -> ImapResult<Vec<u8>> {
+        self.fetch_section_bytes(uid, section, None)
+            .await
+            .map(|(data, _)| data)
+    }
+
+    /// Fetch a message without its HTML, as a raw message
+    ///
+    /// The header and the parts from [`BodyStructure::text_only_sections`]
+    /// are fetched one by one and put back together. A message the server
+    /// can't describe, or that isn't multipart, is fetched whole.
+    pub async fn uid_fetch_without_html(&mut self, uid: u32) -> ImapResult<String> {
+        let header =
+            String::from_utf8_lossy(&self.uid_fetch_section(uid, "HEADER").await?).into_owned();
+        let structure = self.uid_fetch_bodystructure(uid).await?;
+        let Some(structure) = structure.filter(|structure| structure.is_multipart()) else {
+            let body = self.uid_fetch_section(uid, "TEXT").await?;
+            return Ok(format!("{}{}", header, String::from_utf8_lossy(&body)));
+        };
+
+        let mut parts = Vec::new();
+        for (section, _) in structure.text_only_sections() {
+            let mime = self
+                .uid_fetch_section(uid, &format!("{}.MIME", section))
+                .await?;
+            let body = self.uid_fetch_section(uid, &section).await?;
+            parts.push((
+                String::from_utf8_lossy(&mime).into_owned(),
+                String::from_utf8_lossy(&body).into_owned(),
+            ));
+        }
+        Ok(ImapProtocol::assemble_parts(&header, &parts))
+    }
+
+    /// Stream a byte range of one body section (`"2"`, `"1.2"`, ...)
+    ///
+    /// Each chunk is a separate partial FETCH, so the section never has to
+    /// fit in one response. A chunk shorter than asked for means the section
+    /// ended, which lets `range` be open-ended. Servers that reject partial
+    /// FETCH, or answer it with the whole section, get one full fetch
+    /// instead, sliced to `range`. Bytes are the section's encoded form; the
+    /// caller decodes its Content-Transfer-Encoding.
+    pub fn fetch_body_section<'a>(
+        &'a mut self,
+        uid: u32,
+        section: &'a str,
+        range: Range<usize>,
+    ) -> impl Stream<Item = ImapResult<Vec<u8>>> + 'a {
+        let end = range.end;
+        futures::stream::try_unfold(
+            (self, range.start, false),
+            move |(client, offset, done)| async move {
+                if done || offset >= end {
+                    return Ok(None);
+                }
+
+                let length = (end - offset).min(BODY_SECTION_CHUNK_SIZE);
+                let data = match client
+                    .fetch_section_bytes(uid, section, Some((offset, length)))
+                    .await
+                {
+                    Ok((data, true)) => data,
+                    // The server sent the whole section rather than the range
+                    Ok((data, false)) => {
+                        let data = data.get(offset..end.min(data.len())).unwrap_or_default();
+                        return Ok(Some((data.to_vec(), (client, end, true))));
+                    }
+                    Err(ImapError::Protocol(_)) | Err(ImapError::Server(_)) => {
+                        tracing::debug!(
+                            "Partial FETCH of section {} refused, fetching it whole",
+                            section
+                        );
+                        let (data, _) = client.fetch_section_bytes(uid, section, None).await?;
+                        let data = data.get(offset..end.min(data.len())).unwrap_or_default();
+                        return Ok(Some((data.to_vec(), (client, end, true))));
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                if data.is_empty() {
+                    return Ok(None);
+                }
+                let next = offset + data.len();
+                let finished = data.len() < length;
+                Ok(Some((data, (client, next, finished))))
+            },
+        )
+    }
+
+    /// Fetch a body section, or part of one, returning its raw bytes and
+    /// whether the server honoured the byte range
+    async fn fetch_section_bytes(
+        &mut self,
+        uid: u32,
+        section: &str,
+        partial: Option<(usize, usize)>,
+    ) -> ImapResult<(Vec<u8>, bool)> {
+        if self.selected_folder.is_none() {
+            return Err(ImapError::invalid_state("No folder selected"));
+        }
+
+        let command = ImapProtocol::format_uid_fetch_section(uid, section, partial);
+        let (response, literals) = self.connection.send_command_with_literals(&command).await?;
+        let partial_honoured =
+            partial.is_some() && ImapProtocol::is_partial_section_response(&response, section);
+        // NIL or an empty string comes back without a literal
+        let data = literals.into_iter().next().unwrap_or_default();
+        Ok((data, partial_honoured))
+    }
+
     /// Search for messages
     pub async fn search(&mut self, criteria: &SearchCriteria) -> ImapResult<Vec<u32>> {
         if self.selected_folder.is_none() {
@@ -355,6 +574,26 @@ impl ImapClient {
         Ok(())
     }
 
+    /// Add (`add`) or remove Gmail labels by UID
+    pub async fn uid_store_labels(
+        &mut self,
+        uid_set: &str,
+        labels: &[String],
+        add: bool,
+    ) -> ImapResult<()> {
+        if self.selected_folder.is_none() {
+            return Err(ImapError::invalid_state("No folder selected"));
+        }
+        if !self.supports_gmail_labels() {
+            return Err(ImapError::not_supported("X-GM-LABELS requires X-GM-EXT-1"));
+        }
+
+        let action = if add { "+X-GM-LABELS" } else { "-X-GM-LABELS" };
+        let command = ImapProtocol::format_uid_store_labels(uid_set, labels, action);
+        let _response = self.connection.send_command(&command).await?;
+        Ok(())
+    }
+
     /// Remove message flags
     pub async fn remove_flags(
         &mut self,
@@ -418,6 +657,33 @@ impl ImapClient {
         Ok(())
     }
 
+    /// Expunge only the given `\Deleted` messages
+    ///
+    /// Requires UIDPLUS; a plain EXPUNGE would also remove every other
+    /// message in the folder that carries the `\Deleted` flag.
+    pub async fn uid_expunge(&mut self, uid_set: &str) -> ImapResult<()> {
+        if self.selected_folder.is_none() {
+            return Err(ImapError::invalid_state("No folder selected"));
+        }
+        if !self.supports_uid_expunge() {
+            return Err(ImapError::not_supported("UID EXPUNGE requires UIDPLUS"));
+        }
+
+        let command = ImapProtocol::format_uid_expunge(uid_set);
+        let _response = self.connection.send_command(&command).await?;
+        Ok(())
+    }
+
+    /// Whether individual messages can be expunged with UID EXPUNGE
+    pub fn supports_uid_expunge(&self) -> bool {
+        self.capabilities.contains(&ImapCapability::UidPlus)
+    }
+
+    /// Whether messages carry Gmail labels (X-GM-LABELS)
+    pub fn supports_gmail_labels(&self) -> bool {
+        self.capabilities.contains(&ImapCapability::GmailExt)
+    }
+
     /// Create a new folder
     pub async fn create_folder(&mut self, folder_name: &str) -> ImapResult<()> {
         if !self.connection.is_authenticated() {
@@ -487,6 +753,61 @@ impl ImapClient {
         Ok(())
     }
 
+    /// Append a message to a folder, streaming it from `message`
+    ///
+    /// `size` is the exact length of the message in bytes. The message is
+    /// copied from the reader to the server without being buffered, using a
+    /// non-synchronizing literal when the server advertises LITERAL+ (or
+    /// LITERAL- for small messages). Returns the new UID when the server
+    /// reports it (UIDPLUS).
+    pub async fn append<R>(
+        &mut self,
+        folder_name: &str,
+        flags: &[MessageFlag],
+        internal_date: Option<DateTime<Utc>>,
+        message: R,
+        size: u64,
+    ) -> ImapResult<Option<u32>>
+    where
+        R: AsyncRead + Unpin,
+    {
+        if !self.connection.is_authenticated() {
+            return Err(ImapError::invalid_state("Not authenticated"));
+        }
+
+        let non_sync = self.supports_non_sync_literal(size);
+        let command = ImapProtocol::format_append(folder_name, flags, internal_date, size, non_sync);
+        let response = self
+            .connection
+            .send_literal_command(&command, message, size, non_sync)
+            .await?;
+
+        // The folder's message count changed
+        self.folders_cache.remove(folder_name);
+
+        Ok(ImapProtocol::parse_append_uid(&response))
+    }
+
+    /// Append a message stored in a file, e.g. a large sent message
+    pub async fn append_file(
+        &mut self,
+        folder_name: &str,
+        flags: &[MessageFlag],
+        path: &Path,
+    ) -> ImapResult<Option<u32>> {
+        let file = tokio::fs::File::open(path).await?;
+        let size = file.metadata().await?.len();
+
+        self.append(folder_name, flags, None, tokio::io::BufReader::new(file), size)
+            .await
+    }
+
+    /// Whether a literal of `size` bytes can be sent without waiting for a continuation
+    fn supports_non_sync_literal(&self, size: u64) -> bool {
+        self.capabilities.contains(&ImapCapability::LiteralPlus)
+            || (size <= 4096 && self.capabilities.contains(&ImapCapability::LiteralMinus))
+    }
+
     /// Get folder status
     pub async fn get_folder_status(
         &mut self,
@@ -545,6 +866,41 @@ impl ImapClient {
         Ok(())
     }
 
+    /// Start monitoring several folders for real-time updates
+    ///
+    /// When the server supports NOTIFY, the first folder is selected and the
+    /// others report status changes on the same connection. Otherwise only
+    /// the first folder is watched, with IDLE.
+    pub async fn start_folders_monitoring(&mut self, folders: Vec<String>) -> ImapResult<()> {
+        let Some((folder_name, others)) = folders.split_first() else {
+            return Err(ImapError::invalid_state("No folders to monitor"));
+        };
+
+        if others.is_empty() || !self.capabilities.contains(&ImapCapability::Notify) {
+            if !others.is_empty() {
+                tracing::debug!(
+                    "NOTIFY not supported, watching only {} with IDLE",
+                    folder_name
+                );
+            }
+            return self.start_folder_monitoring(folder_name.clone()).await;
+        }
+
+        // Initialize IDLE service if not already done
+        if self.idle_service.is_none() {
+            self.init_idle_service()?;
+        }
+
+        let idle_service = self
+            .idle_service
+            .as_ref()
+            .ok_or_else(|| ImapError::invalid_state("IDLE service not initialized"))?;
+
+        idle_service
+            .start_monitoring_folders(folder_name.clone(), others.to_vec())
+            .await
+    }
+
     /// Stop folder monitoring
     pub async fn stop_folder_monitoring(&mut self) -> ImapResult<()> {
         if let Some(idle_service) = &self.idle_service {
@@ -627,6 +983,46 @@ impl ImapClient {
         &self.capabilities
     }
 
+    /// Capabilities as advertised by the server, including disabled ones
+    pub fn server_capabilities(&self) -> &[ImapCapability] {
+        &self.server_capabilities
+    }
+
+    /// Negotiated TLS protocol and cipher
+    pub fn tls_info(&self) -> Option<&TlsInfo> {
+        self.connection.tls_info()
+    }
+
+    /// Ignore the named capabilities even though the server advertises them
+    ///
+    /// Takes effect immediately for the current connection and is kept for
+    /// reconnects.
+    pub fn set_disabled_capabilities(&mut self, disabled: Vec<String>) {
+        self.connection.config_mut().disabled_capabilities = disabled;
+        self.apply_capability_overrides();
+    }
+
+    /// Derive the effective capabilities from what the server advertised
+    fn apply_capability_overrides(&mut self) {
+        let disabled = &self.connection.config().disabled_capabilities;
+        self.capabilities = self
+            .server_capabilities
+            .iter()
+            .filter(|capability| !disabled.iter().any(|name| capability.matches(name)))
+            .cloned()
+            .collect();
+
+        let ignored = self.server_capabilities.len() - self.capabilities.len();
+        if ignored > 0 {
+            tracing::info!(
+                "Ignoring {} server capabilities for {}: {:?}",
+                ignored,
+                self.connection.config().hostname,
+                disabled
+            );
+        }
+    }
+
     /// Set capabilities (for testing)
     pub fn set_capabilities(&mut self, capabilities: Vec<ImapCapability>) {
         self.capabilities = capabilities;
@@ -652,4 +1048,29 @@ mod tests {
         assert!(client.selected_folder().is_none());
         assert_eq!(client.capabilities().len(), 0);
     }
+
+    #[test]
+    fn test_capability_overrides() {
+        let config = ImapConfig::new(
+            "imap.example.com".to_string(),
+            993,
+            "user@example.com".to_string(),
+            "password".to_string(),
+        );
+        let mut client = ImapClient::new(config);
+        client.server_capabilities = ["IMAP4rev1", "IDLE", "COMPRESS=DEFLATE", "MOVE"]
+            .iter()
+            .map(|name| ImapCapability::from_str(name))
+            .collect();
+
+        client.set_disabled_capabilities(vec!["idle".to_string(), "compress=deflate".to_string()]);
+        assert_eq!(
+            client.capabilities(),
+            &[ImapCapability::Imap4Rev1, ImapCapability::Move]
+        );
+        assert_eq!(client.server_capabilities().len(), 4);
+
+        client.set_disabled_capabilities(Vec::new());
+        assert!(client.capabilities().contains(&ImapCapability::Idle));
+    }
 }
@@ -5,8 +5,8 @@ use std::net::ToSocketAddrs;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::io::{
-    AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader as AsyncBufReader,
-    BufWriter as AsyncBufWriter,
+    AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+    BufReader as AsyncBufReader, BufWriter as AsyncBufWriter,
 };
 use tokio::net::TcpStream as AsyncTcpStream;
 use tokio::time::timeout;
@@ -33,6 +33,35 @@ pub enum ConnectionState {
     Selected(String), // Selected folder name
 }
 
+/// Parameters negotiated during the TLS handshake
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsInfo {
+    /// e.g. `TLS 1.3`
+    pub protocol: String,
+    /// e.g. `TLS13_AES_256_GCM_SHA384`
+    pub cipher_suite: String,
+}
+
+impl TlsInfo {
+    fn from_session(session: &rustls::ClientConnection) -> Self {
+        let protocol = match session.protocol_version() {
+            Some(rustls::ProtocolVersion::TLSv1_3) => "TLS 1.3".to_string(),
+            Some(rustls::ProtocolVersion::TLSv1_2) => "TLS 1.2".to_string(),
+            Some(version) => format!("{:?}", version),
+            None => "unknown".to_string(),
+        };
+        let cipher_suite = session
+            .negotiated_cipher_suite()
+            .map(|suite| format!("{:?}", suite.suite()))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        Self {
+            protocol,
+            cipher_suite,
+        }
+    }
+}
+
 /// IMAP connection wrapper
 pub struct ImapConnection {
     config: ImapConfig,
@@ -40,6 +69,7 @@ pub struct ImapConnection {
     stream: Option<SplitStream>,
     tag_counter: u32,
     greeting: Option<String>,
+    tls_info: Option<TlsInfo>,
 }
 
 impl ImapConnection {
@@ -51,6 +81,7 @@ impl ImapConnection {
             stream: None,
             tag_counter: 0,
             greeting: None,
+            tls_info: None,
         }
     }
 
@@ -124,7 +155,14 @@ impl ImapConnection {
                 ImapError::connection(format!("TLS handshake failed: {}", e))
             })?;
 
-            tracing::info!("TLS handshake with {} completed successfully", addr);
+            let tls_info = TlsInfo::from_session(tls_stream.get_ref().1);
+            tracing::info!(
+                "TLS handshake with {} completed successfully ({}, {})",
+                addr,
+                tls_info.protocol,
+                tls_info.cipher_suite
+            );
+            self.tls_info = Some(tls_info);
 
             // Split TLS stream
             let (read_half, write_half) = tokio::io::split(tls_stream);
@@ -178,12 +216,32 @@ impl ImapConnection {
         self.state = ConnectionState::Disconnected;
         self.tag_counter = 0;
         self.greeting = None;
+        self.tls_info = None;
 
         Ok(())
     }
 
     /// Send a command to the server
     pub async fn send_command(&mut self, command: &str) -> ImapResult<String> {
+        let tag = self.write_command(command).await?;
+        self.read_tagged_response(&tag).await
+    }
+
+    /// Send a command and keep the raw bytes of any literals in its response
+    ///
+    /// The returned text is the same as [`send_command`](Self::send_command)
+    /// gives; the literals are also returned untouched, in order, so binary
+    /// body sections survive without a lossy UTF-8 round trip.
+    pub async fn send_command_with_literals(
+        &mut self,
+        command: &str,
+    ) -> ImapResult<(String, Vec<Vec<u8>>)> {
+        let tag = self.write_command(command).await?;
+        self.read_tagged_response_with_literals(&tag).await
+    }
+
+    /// Write a tagged command to the server, returning its tag
+    async fn write_command(&mut self, command: &str) -> ImapResult<String> {
         if self.state == ConnectionState::Disconnected {
             return Err(ImapError::invalid_state("Not connected"));
         }
@@ -216,8 +274,23 @@ impl ImapConnection {
             None => return Err(ImapError::invalid_state("No connection available")),
         }
 
-        // Read response until we get the tagged response
+        Ok(tag)
+    }
+
+    /// Read response lines, including literals, until the tagged completion
+    async fn read_tagged_response(&mut self, tag: &str) -> ImapResult<String> {
+        self.read_tagged_response_with_literals(tag)
+            .await
+            .map(|(response, _)| response)
+    }
+
+    /// Read response lines until the tagged completion, also collecting literal bytes
+    async fn read_tagged_response_with_literals(
+        &mut self,
+        tag: &str,
+    ) -> ImapResult<(String, Vec<Vec<u8>>)> {
         let mut responses = Vec::new();
+        let mut literals = Vec::new();
         loop {
             let line = self.read_response().await?;
 
@@ -239,11 +312,12 @@ impl ImapConnection {
                     "Read literal content, length: {} chars",
                     literal_string.len()
                 );
+                literals.push(literal_data);
             } else {
                 responses.push(line.clone());
             }
 
-            if line.starts_with(&tag) {
+            if line.starts_with(tag) {
                 // This is our tagged response
                 if line.starts_with(&format!("{} OK", tag)) {
                     break;
@@ -255,7 +329,95 @@ impl ImapConnection {
             }
         }
 
-        Ok(responses.join("\n"))
+        Ok((responses.join("\n"), literals))
+    }
+
+    /// Send a command ending in a literal size, then stream the literal from `literal`
+    ///
+    /// `command` must end with `{size}` or, when the server supports
+    /// LITERAL+, `{size+}`. For a synchronizing literal the server's `+`
+    /// continuation is awaited first. The literal is copied straight from the
+    /// reader to the socket, so memory use does not grow with its size.
+    pub async fn send_literal_command<R>(
+        &mut self,
+        command: &str,
+        literal: R,
+        size: u64,
+        non_sync: bool,
+    ) -> ImapResult<String>
+    where
+        R: AsyncRead + Unpin,
+    {
+        if self.state == ConnectionState::Disconnected {
+            return Err(ImapError::invalid_state("Not connected"));
+        }
+
+        self.tag_counter += 1;
+        let tag = format!("A{:04}", self.tag_counter);
+
+        let full_command = format!("{} {}\r\n", tag, command);
+        let writer = self.writer()?;
+        writer
+            .write_all(full_command.as_bytes())
+            .await
+            .map_err(|e| ImapError::connection(format!("Failed to send command: {}", e)))?;
+        writer
+            .flush()
+            .await
+            .map_err(|e| ImapError::connection(format!("Failed to flush command: {}", e)))?;
+
+        if !non_sync {
+            // Wait for the server to accept the literal
+            loop {
+                let line = self.read_response().await?;
+                if line.starts_with('+') {
+                    break;
+                }
+                if line.starts_with(&format!("{} NO", tag)) {
+                    return Err(ImapError::server(format!("Command failed: {}", line)));
+                }
+                if line.starts_with(&format!("{} BAD", tag)) {
+                    return Err(ImapError::protocol(format!("Bad command: {}", line)));
+                }
+            }
+        }
+
+        let writer = self.writer()?;
+        let copied = tokio::io::copy(&mut literal.take(size), writer)
+            .await
+            .map_err(|e| ImapError::connection(format!("Failed to send literal: {}", e)))?;
+        if copied != size {
+            // The server is still waiting for the rest of the literal, so
+            // the connection cannot be used for further commands
+            self.stream = None;
+            self.state = ConnectionState::Disconnected;
+            self.tls_info = None;
+            return Err(ImapError::protocol(format!(
+                "Literal ended after {} of {} bytes",
+                copied, size
+            )));
+        }
+
+        let writer = self.writer()?;
+        writer
+            .write_all(b"\r\n")
+            .await
+            .map_err(|e| ImapError::connection(format!("Failed to send literal: {}", e)))?;
+        writer
+            .flush()
+            .await
+            .map_err(|e| ImapError::connection(format!("Failed to flush literal: {}", e)))?;
+
+        self.read_tagged_response(&tag).await
+    }
+
+    /// Writer half of the connection
+    fn writer(&mut self) -> ImapResult<&mut (dyn AsyncWrite + Unpin + Send)> {
+        match self.stream.as_mut() {
+            Some(SplitStream::Plain { writer, .. }) => Ok(writer),
+            Some(SplitStream::Tls { writer, .. }) => Ok(writer),
+            None => Err(ImapError::invalid_state("No connection available")),
+        }
     }
 
     /// Read a single response line from the server (public for IDLE)
@@ -364,6 +526,16 @@ impl ImapConnection {
         &self.config
     }
 
+    /// Get mutable configuration; takes effect on the next connect
+    pub(crate) fn config_mut(&mut self) -> &mut ImapConfig {
+        &mut self.config
+    }
+
+    /// TLS protocol and cipher, when connected over TLS
+    pub fn tls_info(&self) -> Option<&TlsInfo> {
+        self.tls_info.as_ref()
+    }
+
     /// Send raw data to the server (for continuation responses)
     pub async fn send_raw(&mut self, data: &str) -> ImapResult<()> {
         if self.state == ConnectionState::Disconnected {
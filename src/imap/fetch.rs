@@ -0,0 +1,251 @@
+//! IMAP FETCH batching and prefetch settings
+//!
+//! Servers differ a lot in how much they like to be asked for at once: Gmail
+//! happily streams hundreds of messages per FETCH, while Yahoo and Office 365
+//! throttle or drop the connection on large requests. These settings control
+//! the batch size used during sync, how many recent message bodies are
+//! prefetched during headers-only syncs, and whether the batch size adapts to
+//! observed latency.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// FETCH batching settings for a server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FetchSettings {
+    /// Messages requested per FETCH command
+    pub batch_size: usize,
+    /// Newest messages whose full bodies are downloaded during a headers-only sync
+    pub prefetch_depth: usize,
+    /// Grow the batch while the server responds quickly and shrink it on errors
+    pub auto_tune: bool,
+    /// Lower bound for auto-tuning
+    pub min_batch_size: usize,
+    /// Upper bound for auto-tuning
+    pub max_batch_size: usize,
+    /// Batch round-trip time auto-tuning aims for, in milliseconds
+    pub target_batch_latency_ms: u64,
+}
+
+impl Default for FetchSettings {
+    fn default() -> Self {
+        Self {
+            batch_size: 50,
+            prefetch_depth: 25,
+            auto_tune: true,
+            min_batch_size: 10,
+            max_batch_size: 200,
+            target_batch_latency_ms: 2000,
+        }
+    }
+}
+
+impl FetchSettings {
+    /// Provider-specific defaults for an IMAP hostname
+    pub fn for_host(hostname: &str) -> Self {
+        let host = hostname.to_lowercase();
+        let defaults = Self::default();
+
+        if host.ends_with("gmail.com")
+            || host.ends_with("googlemail.com")
+            || host.ends_with("fastmail.com")
+        {
+            Self {
+                batch_size: 100,
+                prefetch_depth: 50,
+                max_batch_size: 500,
+                ..defaults
+            }
+        } else if host.ends_with("office365.com") || host.ends_with("outlook.com") {
+            // Exchange Online throttles large fetches per mailbox
+            Self {
+                batch_size: 40,
+                max_batch_size: 150,
+                ..defaults
+            }
+        } else if host.ends_with("yahoo.com") || host.ends_with("aol.com") {
+            Self {
+                batch_size: 25,
+                min_batch_size: 5,
+                max_batch_size: 100,
+                ..defaults
+            }
+        } else {
+            defaults
+        }
+    }
+
+    /// Batch size clamped to the configured bounds
+    pub fn effective_batch_size(&self) -> usize {
+        let min = self.min_batch_size.max(1);
+        let max = self.max_batch_size.max(min);
+        self.batch_size.clamp(min, max)
+    }
+}
+
+/// Picks the size of the next FETCH batch, adapting to server latency
+#[derive(Debug, Clone)]
+pub struct BatchSizer {
+    settings: FetchSettings,
+    current: usize,
+}
+
+impl BatchSizer {
+    pub fn new(settings: &FetchSettings) -> Self {
+        Self {
+            current: settings.effective_batch_size(),
+            settings: settings.clone(),
+        }
+    }
+
+    /// Size of the next batch
+    pub fn batch_size(&self) -> usize {
+        self.current
+    }
+
+    /// Record a successful batch and its round-trip time
+    pub fn record_success(&mut self, elapsed: Duration) {
+        if !self.settings.auto_tune {
+            return;
+        }
+
+        let target = Duration::from_millis(self.settings.target_batch_latency_ms);
+        if elapsed < target / 2 {
+            // Grow by half again while the server keeps up
+            self.current = (self.current + self.current.div_ceil(2)).min(self.max());
+        } else if elapsed > target * 2 {
+            self.current = (self.current / 2).max(self.min());
+        }
+    }
+
+    /// Record a failed batch
+    ///
+    /// Returns `true` if the batch size was reduced and the batch is worth retrying.
+    pub fn record_error(&mut self) -> bool {
+        if !self.settings.auto_tune || self.current <= self.min() {
+            return false;
+        }
+
+        self.current = (self.current / 2).max(self.min());
+        true
+    }
+
+    fn min(&self) -> usize {
+        self.settings.min_batch_size.max(1)
+    }
+
+    fn max(&self) -> usize {
+        self.settings.max_batch_size.max(self.min())
+    }
+}
+
+/// Per-account overrides for the provider fetch defaults
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FetchConfig {
+    /// Settings keyed by account ID; accounts without an entry use provider defaults
+    pub accounts: HashMap<String, FetchSettings>,
+}
+
+impl FetchConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "imap_fetch.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Override for an account, if one is configured
+    pub fn settings_for(&self, account_id: &str) -> Option<&FetchSettings> {
+        self.accounts.get(account_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_defaults() {
+        assert_eq!(FetchSettings::for_host("imap.gmail.com").batch_size, 100);
+        assert_eq!(FetchSettings::for_host("outlook.office365.com").batch_size, 40);
+        assert_eq!(FetchSettings::for_host("imap.mail.yahoo.com").batch_size, 25);
+        assert_eq!(
+            FetchSettings::for_host("mail.example.org"),
+            FetchSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_batch_sizer_grows_and_backs_off() {
+        let settings = FetchSettings {
+            batch_size: 40,
+            min_batch_size: 10,
+            max_batch_size: 100,
+            target_batch_latency_ms: 2000,
+            ..Default::default()
+        };
+        let mut sizer = BatchSizer::new(&settings);
+        assert_eq!(sizer.batch_size(), 40);
+
+        // Fast responses grow the batch up to the maximum
+        sizer.record_success(Duration::from_millis(200));
+        assert_eq!(sizer.batch_size(), 60);
+        sizer.record_success(Duration::from_millis(200));
+        sizer.record_success(Duration::from_millis(200));
+        assert_eq!(sizer.batch_size(), 100);
+
+        // Latency within the target band leaves it alone
+        sizer.record_success(Duration::from_millis(2500));
+        assert_eq!(sizer.batch_size(), 100);
+
+        // Slow responses and errors halve it down to the minimum
+        sizer.record_success(Duration::from_secs(5));
+        assert_eq!(sizer.batch_size(), 50);
+        assert!(sizer.record_error());
+        assert_eq!(sizer.batch_size(), 25);
+        assert!(sizer.record_error());
+        assert!(sizer.record_error());
+        assert_eq!(sizer.batch_size(), 10);
+        assert!(!sizer.record_error());
+    }
+
+    #[test]
+    fn test_batch_sizer_fixed_when_auto_tune_disabled() {
+        let settings = FetchSettings {
+            batch_size: 500,
+            auto_tune: false,
+            ..Default::default()
+        };
+        let mut sizer = BatchSizer::new(&settings);
+
+        // Still clamped to the configured bounds
+        assert_eq!(sizer.batch_size(), 200);
+        sizer.record_success(Duration::from_millis(1));
+        assert!(!sizer.record_error());
+        assert_eq!(sizer.batch_size(), 200);
+    }
+
+    #[test]
+    fn test_fetch_config_partial_overrides() {
+        let config: FetchConfig = toml::from_str(
+            "[accounts.work]\nbatch_size = 20\nauto_tune = false\n",
+        )
+        .unwrap();
+
+        let work = config.settings_for("work").unwrap();
+        assert_eq!(work.batch_size, 20);
+        assert!(!work.auto_tune);
+        assert_eq!(work.max_batch_size, 200);
+        assert!(config.settings_for("personal").is_none());
+    }
+}
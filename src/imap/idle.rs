@@ -17,6 +17,13 @@ pub enum IdleNotification {
     Expunge { sequence: u32 },
     /// Fetch notification for updated message
     Fetch { sequence: u32, uid: Option<u32> },
+    /// Status of a watched folder that isn't selected, sent under NOTIFY
+    MailboxStatus {
+        folder: String,
+        messages: Option<u32>,
+        uid_next: Option<u32>,
+        unseen: Option<u32>,
+    },
     /// IDLE connection was lost
     ConnectionLost,
     /// IDLE timeout occurred
@@ -45,6 +52,10 @@ impl IdleResponseParser {
     }
 
     fn parse_untagged_response(line: &str) -> Option<IdleNotification> {
+        if let Some(status) = line.strip_prefix("* STATUS ") {
+            return Self::parse_status_response(status);
+        }
+
         let parts: Vec<&str> = line.split_whitespace().collect();
 
         if parts.len() < 3 {
@@ -89,6 +100,39 @@ impl IdleResponseParser {
         }
     }
 
+    /// Parse the rest of `* STATUS <mailbox> (<item> <value> ...)`
+    fn parse_status_response(status: &str) -> Option<IdleNotification> {
+        let open = status.rfind('(')?;
+        let folder = status[..open].trim();
+        let folder = folder
+            .strip_prefix('"')
+            .and_then(|name| name.strip_suffix('"'))
+            .unwrap_or(folder)
+            .replace("\\\"", "\"");
+        if folder.is_empty() {
+            return None;
+        }
+
+        let items: Vec<&str> = status[open + 1..]
+            .trim_end_matches(')')
+            .split_whitespace()
+            .collect();
+        let item = |name: &str| {
+            items
+                .chunks(2)
+                .find(|pair| pair[0].eq_ignore_ascii_case(name))
+                .and_then(|pair| pair.get(1))
+                .and_then(|value| value.parse().ok())
+        };
+
+        Some(IdleNotification::MailboxStatus {
+            folder,
+            messages: item("MESSAGES"),
+            uid_next: item("UIDNEXT"),
+            unseen: item("UNSEEN"),
+        })
+    }
+
     fn extract_uid_from_fetch_response(line: &str) -> Option<u32> {
         // Look for UID in FETCH response
         // Example: * 1 FETCH (UID 1234 FLAGS (\Seen))
@@ -111,6 +155,8 @@ pub struct IdleManager {
     notification_sender: mpsc::UnboundedSender<IdleNotification>,
     is_idle: Arc<RwLock<bool>>,
     selected_folder: Arc<RwLock<Option<String>>>,
+    /// Unselected folders watched with NOTIFY; empty under plain IDLE
+    notify_mailboxes: Arc<RwLock<Vec<String>>>,
     last_heartbeat: Arc<RwLock<Instant>>,
     idle_timeout: Duration,
     heartbeat_interval: Duration,
@@ -127,6 +173,7 @@ impl IdleManager {
             notification_sender,
             is_idle: Arc::new(RwLock::new(false)),
             selected_folder: Arc::new(RwLock::new(None)),
+            notify_mailboxes: Arc::new(RwLock::new(Vec::new())),
             last_heartbeat: Arc::new(RwLock::new(Instant::now())),
             idle_timeout: Duration::from_secs(29 * 60), // 29 minutes (RFC recommends < 30 min)
             heartbeat_interval: Duration::from_secs(60), // Check every minute
@@ -174,19 +221,52 @@ impl IdleManager {
         Ok(())
     }
 
-    /// Stop IDLE mode
-    pub async fn stop_idle(&self) -> ImapResult<()> {
-        let mut is_idle = self.is_idle.write().await;
-        if !*is_idle {
-            return Ok(()); // Already stopped
+    /// Watch several folders on one connection with NOTIFY (RFC 5465)
+    ///
+    /// `folder_name` is selected and reports changes as under IDLE. The other
+    /// `mailboxes` send [`IdleNotification::MailboxStatus`] when messages
+    /// arrive or are expunged. The connection then idles so the server keeps
+    /// it open. Only use this when the server advertises NOTIFY.
+    pub async fn start_notify(&self, folder_name: String, mailboxes: Vec<String>) -> ImapResult<()> {
+        if self.is_idle().await {
+            return Err(ImapError::invalid_state("Already in IDLE mode"));
         }
 
-        // Send DONE command to exit IDLE
-        let mut connection = self.connection.lock().await;
-        let command = ImapProtocol::format_done();
-        let _response = connection.send_command(&command).await?;
+        // The selected folder is covered by the SELECTED event group
+        let mailboxes: Vec<String> = mailboxes
+            .into_iter()
+            .filter(|mailbox| *mailbox != folder_name)
+            .collect();
 
-        *is_idle = false;
+        let response = {
+            let mut connection = self.connection.lock().await;
+            let command = ImapProtocol::format_notify_set(&mailboxes);
+            connection.send_command(&command).await?
+        };
+
+        // With STATUS the server reports every watched mailbox up front
+        for notification in IdleResponseParser::parse_idle_response(&response) {
+            if self.notification_sender.send(notification).is_err() {
+                warn!("Failed to send NOTIFY status - receiver dropped");
+                break;
+            }
+        }
+
+        info!(
+            "NOTIFY set for folder {} and {} other folders",
+            folder_name,
+            mailboxes.len()
+        );
+        *self.notify_mailboxes.write().await = mailboxes;
+
+        self.start_idle(folder_name).await
+    }
+
+    /// Stop IDLE mode, and NOTIFY if it was set
+    pub async fn stop_idle(&self) -> ImapResult<()> {
+        if !self.end_idle().await? {
+            return Ok(()); // Already stopped
+        }
 
         // Clear selected folder
         {
@@ -194,10 +274,33 @@ impl IdleManager {
             *selected = None;
         }
 
+        let notify_mailboxes = std::mem::take(&mut *self.notify_mailboxes.write().await);
+        if !notify_mailboxes.is_empty() {
+            let mut connection = self.connection.lock().await;
+            let command = ImapProtocol::format_notify_none();
+            let _response = connection.send_command(&command).await?;
+        }
+
         info!("Stopped IDLE mode");
         Ok(())
     }
 
+    /// Send DONE to leave IDLE; returns false if it wasn't idling
+    async fn end_idle(&self) -> ImapResult<bool> {
+        let mut is_idle = self.is_idle.write().await;
+        if !*is_idle {
+            return Ok(false);
+        }
+
+        // Send DONE command to exit IDLE
+        let mut connection = self.connection.lock().await;
+        let command = ImapProtocol::format_done();
+        let _response = connection.send_command(&command).await?;
+
+        *is_idle = false;
+        Ok(true)
+    }
+
     /// Check if currently in IDLE mode
     pub async fn is_idle(&self) -> bool {
         *self.is_idle.read().await
@@ -208,6 +311,11 @@ impl IdleManager {
         self.selected_folder.read().await.clone()
     }
 
+    /// Unselected folders watched with NOTIFY
+    pub async fn get_notify_mailboxes(&self) -> Vec<String> {
+        self.notify_mailboxes.read().await.clone()
+    }
+
     /// Start the IDLE response listener
     async fn start_idle_listener(&self) -> ImapResult<()> {
         let connection = Arc::clone(&self.connection);
@@ -306,8 +414,8 @@ impl IdleManager {
             return Err(ImapError::invalid_state("No folder selected for IDLE"));
         };
 
-        // Stop and restart IDLE
-        self.stop_idle().await?;
+        // Restart IDLE; NOTIFY stays set on the connection
+        self.end_idle().await?;
         tokio::time::sleep(Duration::from_millis(100)).await; // Brief pause
         self.start_idle(folder).await?;
 
@@ -343,6 +451,19 @@ impl IdleNotificationService {
         Ok(())
     }
 
+    /// Start monitoring several folders with NOTIFY
+    ///
+    /// `folder_name` is selected; `mailboxes` report status changes.
+    pub async fn start_monitoring_folders(
+        &self,
+        folder_name: String,
+        mailboxes: Vec<String>,
+    ) -> ImapResult<()> {
+        self.idle_manager.start_notify(folder_name, mailboxes).await?;
+        self.start_notification_dispatcher().await;
+        Ok(())
+    }
+
     /// Stop monitoring
     pub async fn stop_monitoring(&self) -> ImapResult<()> {
         self.idle_manager.stop_idle().await
@@ -412,6 +533,7 @@ impl IdleNotificationService {
         IdleStats {
             is_active: self.idle_manager.is_idle().await,
             monitored_folder: self.idle_manager.get_idle_folder().await,
+            notify_folders: self.idle_manager.get_notify_mailboxes().await,
             callback_count: self.callbacks.read().await.len(),
         }
     }
@@ -422,6 +544,8 @@ impl IdleNotificationService {
 pub struct IdleStats {
     pub is_active: bool,
     pub monitored_folder: Option<String>,
+    /// Other folders watched with NOTIFY
+    pub notify_folders: Vec<String>,
     pub callback_count: usize,
 }
 
@@ -467,6 +591,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_notify_status_parsing() {
+        let response = "* STATUS \"Lists/rust\" (MESSAGES 231 UIDNEXT 44292 UNSEEN 3)\n\
+            * STATUS INBOX (UIDNEXT 12)\n\
+            * 4 EXISTS";
+        let notifications = IdleResponseParser::parse_idle_response(response);
+
+        assert_eq!(
+            notifications,
+            vec![
+                IdleNotification::MailboxStatus {
+                    folder: "Lists/rust".to_string(),
+                    messages: Some(231),
+                    uid_next: Some(44292),
+                    unseen: Some(3),
+                },
+                IdleNotification::MailboxStatus {
+                    folder: "INBOX".to_string(),
+                    messages: None,
+                    uid_next: Some(12),
+                    unseen: None,
+                },
+                IdleNotification::Exists { count: 4 },
+            ]
+        );
+
+        assert_eq!(
+            ImapProtocol::format_notify_set(&["Lists/rust".to_string(), "Archive".to_string()]),
+            "NOTIFY SET STATUS (SELECTED (MessageNew MessageExpunge FlagChange)) \
+             (MAILBOXES (\"Lists/rust\" \"Archive\") (MessageNew MessageExpunge))"
+        );
+    }
+
     #[test]
     fn test_invalid_response_parsing() {
         let response = "* INVALID RESPONSE\n+ OK IDLE";
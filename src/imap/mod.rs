@@ -1,15 +1,19 @@
 pub mod account_manager;
+pub mod capability_overrides;
 pub mod client;
 pub mod connection;
 pub mod error;
+pub mod fetch;
 pub mod idle;
 pub mod protocol;
 pub mod types;
 
-pub use account_manager::{AccountManagerStats, ImapAccount, ImapAccountManager};
+pub use account_manager::{AccountManagerStats, ConnectionDetails, ImapAccount, ImapAccountManager};
+pub use capability_overrides::CapabilityOverrides;
 pub use client::ImapClient;
-pub use connection::ImapConnection;
+pub use connection::{ImapConnection, TlsInfo};
 pub use error::{ImapError, ImapResult};
+pub use fetch::{BatchSizer, FetchConfig, FetchSettings};
 pub use idle::{IdleManager, IdleNotification, IdleNotificationService, IdleStats};
 pub use types::*;
 
@@ -24,6 +28,8 @@ pub enum ImapCapability {
     AuthLogin,
     AuthXOAuth2,
     Idle,
+    /// Mailbox event notifications across folders (RFC 5465)
+    Notify,
     Namespace,
     Unselect,
     Children,
@@ -32,6 +38,12 @@ pub enum ImapCapability {
     QResync,
     Move,
     Special,
+    /// Non-synchronizing literals of any size (RFC 7888)
+    LiteralPlus,
+    /// Non-synchronizing literals up to 4096 bytes (RFC 7888)
+    LiteralMinus,
+    /// Gmail's IMAP extensions, among them X-GM-LABELS
+    GmailExt,
     Custom(String),
 }
 
@@ -47,6 +59,7 @@ impl ImapCapability {
             "AUTH=LOGIN" => ImapCapability::AuthLogin,
             "AUTH=XOAUTH2" => ImapCapability::AuthXOAuth2,
             "IDLE" => ImapCapability::Idle,
+            "NOTIFY" => ImapCapability::Notify,
             "NAMESPACE" => ImapCapability::Namespace,
             "UNSELECT" => ImapCapability::Unselect,
             "CHILDREN" => ImapCapability::Children,
@@ -55,9 +68,44 @@ impl ImapCapability {
             "QRESYNC" => ImapCapability::QResync,
             "MOVE" => ImapCapability::Move,
             "SPECIAL-USE" => ImapCapability::Special,
+            "LITERAL+" => ImapCapability::LiteralPlus,
+            "LITERAL-" => ImapCapability::LiteralMinus,
+            "X-GM-EXT-1" => ImapCapability::GmailExt,
             _ => ImapCapability::Custom(capability.to_string()),
         }
     }
+
+    /// Capability name as the server advertises it
+    pub fn name(&self) -> &str {
+        match self {
+            ImapCapability::Imap4Rev1 => "IMAP4rev1",
+            ImapCapability::StartTls => "STARTTLS",
+            ImapCapability::LoginDisabled => "LOGINDISABLED",
+            ImapCapability::SaslIr => "SASL-IR",
+            ImapCapability::AuthPlain => "AUTH=PLAIN",
+            ImapCapability::AuthLogin => "AUTH=LOGIN",
+            ImapCapability::AuthXOAuth2 => "AUTH=XOAUTH2",
+            ImapCapability::Idle => "IDLE",
+            ImapCapability::Notify => "NOTIFY",
+            ImapCapability::Namespace => "NAMESPACE",
+            ImapCapability::Unselect => "UNSELECT",
+            ImapCapability::Children => "CHILDREN",
+            ImapCapability::UidPlus => "UIDPLUS",
+            ImapCapability::CondStore => "CONDSTORE",
+            ImapCapability::QResync => "QRESYNC",
+            ImapCapability::Move => "MOVE",
+            ImapCapability::Special => "SPECIAL-USE",
+            ImapCapability::LiteralPlus => "LITERAL+",
+            ImapCapability::LiteralMinus => "LITERAL-",
+            ImapCapability::GmailExt => "X-GM-EXT-1",
+            ImapCapability::Custom(name) => name,
+        }
+    }
+
+    /// Whether `name` refers to this capability, ignoring case
+    pub fn matches(&self, name: &str) -> bool {
+        self.name().eq_ignore_ascii_case(name.trim())
+    }
 }
 
 /// IMAP authentication method
@@ -78,13 +126,17 @@ pub struct ImapConfig {
     pub use_starttls: bool,
     pub timeout_seconds: u64,
     pub validate_certificates: bool,
+    /// FETCH batching and prefetch settings
+    pub fetch: FetchSettings,
+    /// Advertised capabilities to ignore, e.g. `IDLE` on a server whose
+    /// implementation misbehaves
+    pub disabled_capabilities: Vec<String>,
 }
 
 impl ImapConfig {
     /// Create a new IMAP configuration with password auth
     pub fn new(hostname: String, port: u16, username: String, password: String) -> Self {
         Self {
-            hostname,
             port,
             username,
             auth_method: ImapAuthMethod::Password(password),
@@ -92,13 +144,15 @@ impl ImapConfig {
             use_starttls: port == 143, // Default to STARTTLS for port 143
             timeout_seconds: 60,       // Increased timeout for better reliability
             validate_certificates: true,
+            fetch: FetchSettings::for_host(&hostname),
+            disabled_capabilities: Vec::new(),
+            hostname,
         }
     }
 
     /// Create a new IMAP configuration with OAuth2 auth
     pub fn new_oauth2(hostname: String, port: u16, username: String, account_id: String) -> Self {
         Self {
-            hostname,
             port,
             username,
             auth_method: ImapAuthMethod::OAuth2 { account_id },
@@ -106,6 +160,9 @@ impl ImapConfig {
             use_starttls: port == 143,
             timeout_seconds: 60, // Increased timeout for better reliability
             validate_certificates: true,
+            fetch: FetchSettings::for_host(&hostname),
+            disabled_capabilities: Vec::new(),
+            hostname,
         }
     }
 
@@ -167,4 +224,16 @@ impl ImapConfig {
         self.validate_certificates = validate;
         self
     }
+
+    /// Set FETCH batching and prefetch settings
+    pub fn with_fetch_settings(mut self, fetch: FetchSettings) -> Self {
+        self.fetch = fetch;
+        self
+    }
+
+    /// Set capabilities to ignore even when the server advertises them
+    pub fn with_disabled_capabilities(mut self, disabled: Vec<String>) -> Self {
+        self.disabled_capabilities = disabled;
+        self
+    }
 }
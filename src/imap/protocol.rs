@@ -1,7 +1,9 @@
+use chrono::{DateTime, Utc};
 use crate::imap::{
-    FolderAttribute, ImapCapability, ImapError, ImapFolder, ImapMessage, ImapResult, MessageFlag,
-    SearchCriteria,
+    BodyStructure, FolderAttribute, ImapCapability, ImapError, ImapFolder, ImapMessage,
+    ImapResult, MessageFlag, SearchCriteria,
 };
+use std::collections::HashMap;
 
 /// IMAP protocol parser and formatter
 pub struct ImapProtocol;
@@ -136,6 +138,13 @@ impl ImapProtocol {
                             folder.uid_next = uid_str.parse().ok();
                         }
                     }
+                } else if line.contains("[HIGHESTMODSEQ ") {
+                    if let Some(start) = line.find("[HIGHESTMODSEQ ") {
+                        if let Some(end) = line[start..].find(']') {
+                            let modseq_str = &line[start + 15..start + end];
+                            folder.highest_modseq = modseq_str.parse().ok();
+                        }
+                    }
                 }
             }
         }
@@ -259,12 +268,23 @@ impl ImapProtocol {
             }
         }
 
+        // Parse X-GM-LABELS (Gmail); labels may be quoted or atoms
+        if let Some(start) = line.find("X-GM-LABELS (") {
+            let mut pos = start + 12;
+            if let Some(ResponseValue::List(labels)) = ResponseValue::parse(line.as_bytes(), &mut pos) {
+                message.labels = labels
+                    .iter()
+                    .filter_map(|label| label.as_str().map(str::to_string))
+                    .collect();
+            }
+        }
+
         // Parse UID
         if line.contains("UID ") {
             if let Some(uid_pos) = line.find("UID ") {
                 let uid_start = uid_pos + 4;
                 let uid_end = line[uid_start..]
-                    .find(' ')
+                    .find([' ', ')'])
                     .map(|i| uid_start + i)
                     .unwrap_or(line.len());
                 if let Ok(uid) = line[uid_start..uid_end].parse::<u32>() {
@@ -359,6 +379,142 @@ impl ImapProtocol {
         Ok(())
     }
 
+    /// UIDs from `* VANISHED` responses (RFC 7162), expanded from their UID sets
+    pub fn parse_vanished(response: &str) -> Vec<u32> {
+        let mut uids = Vec::new();
+
+        for line in response.lines() {
+            if let Some(rest) = line.strip_prefix("* VANISHED ") {
+                let set = rest.strip_prefix("(EARLIER) ").unwrap_or(rest);
+                uids.extend(Self::parse_uid_set(set.trim()));
+            }
+        }
+
+        uids
+    }
+
+    /// Expand a UID set such as `41,43:116,118`
+    fn parse_uid_set(set: &str) -> Vec<u32> {
+        let mut uids = Vec::new();
+
+        for part in set.split(',') {
+            match part.split_once(':') {
+                Some((first, last)) => {
+                    if let (Ok(first), Ok(last)) = (first.parse::<u32>(), last.parse::<u32>()) {
+                        uids.extend(first.min(last)..=first.max(last));
+                    }
+                }
+                None => uids.extend(part.parse::<u32>().ok()),
+            }
+        }
+
+        uids
+    }
+
+    /// Capabilities the server turned on, from `* ENABLED` (RFC 5161)
+    pub fn parse_enabled(response: &str) -> Vec<String> {
+        response
+            .lines()
+            .filter_map(|line| line.strip_prefix("* ENABLED"))
+            .flat_map(|rest| rest.split_whitespace().map(str::to_string))
+            .collect()
+    }
+
+    /// Parse the BODYSTRUCTURE from a FETCH response (RFC 3501 section 7.4.2)
+    pub fn parse_bodystructure(response: &str) -> Option<BodyStructure> {
+        let start = response.find("BODYSTRUCTURE (")? + "BODYSTRUCTURE ".len();
+        let mut pos = start;
+        let value = ResponseValue::parse(response.as_bytes(), &mut pos)?;
+        Self::body_structure_from_value(&value)
+    }
+
+    fn body_structure_from_value(value: &ResponseValue) -> Option<BodyStructure> {
+        let items = value.as_list()?;
+
+        if let Some(ResponseValue::List(_)) = items.first() {
+            // Multipart: the child bodies, then the subtype and extension data
+            let part_count = items
+                .iter()
+                .take_while(|item| matches!(item, ResponseValue::List(_)))
+                .count();
+            let mut body = BodyStructure::new(
+                "multipart".to_string(),
+                items.get(part_count)?.as_str()?.to_lowercase(),
+            );
+            body.parts = items[..part_count]
+                .iter()
+                .filter_map(Self::body_structure_from_value)
+                .collect();
+            if let Some(parameters) = items.get(part_count + 1) {
+                body.parameters = Self::body_parameters(parameters);
+            }
+            Self::apply_disposition(&mut body, items.get(part_count + 2));
+            return Some(body);
+        }
+
+        let mut body = BodyStructure::new(
+            items.first()?.as_str()?.to_lowercase(),
+            items.get(1)?.as_str()?.to_lowercase(),
+        );
+        if let Some(parameters) = items.get(2) {
+            body.parameters = Self::body_parameters(parameters);
+        }
+        let field = |index: usize| items.get(index).and_then(ResponseValue::as_str);
+        body.content_id = field(3).map(str::to_string);
+        body.content_description = field(4).map(str::to_string);
+        body.content_transfer_encoding = field(5).map(str::to_string);
+        body.size = field(6).and_then(|size| size.parse().ok());
+
+        // Text parts add a line count, and encapsulated messages an envelope,
+        // body and line count, before the MD5 and disposition
+        let md5_index = if body.is_text() {
+            8
+        } else if body.media_type == "message" && body.media_subtype == "rfc822" {
+            10
+        } else {
+            7
+        };
+        Self::apply_disposition(&mut body, items.get(md5_index + 1));
+        Some(body)
+    }
+
+    /// Body parameters: a list of alternating names and values, or NIL
+    fn body_parameters(value: &ResponseValue) -> HashMap<String, String> {
+        value
+            .as_list()
+            .unwrap_or_default()
+            .chunks(2)
+            .filter_map(|pair| {
+                Some((
+                    pair.first()?.as_str()?.to_lowercase(),
+                    pair.get(1)?.as_str()?.to_string(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Body disposition: `("attachment" ("filename" "report.pdf"))` or NIL
+    fn apply_disposition(body: &mut BodyStructure, value: Option<&ResponseValue>) {
+        let Some(items) = value.and_then(ResponseValue::as_list) else {
+            return;
+        };
+        body.disposition = items
+            .first()
+            .and_then(ResponseValue::as_str)
+            .map(str::to_lowercase);
+        if let Some(parameters) = items.get(1) {
+            body.disposition_parameters = Self::body_parameters(parameters);
+        }
+    }
+
+    /// Whether a FETCH response returned `section` as a partial (`BODY[section]<origin>`)
+    ///
+    /// Servers that ignore the byte range send the whole section without an
+    /// origin instead.
+    pub fn is_partial_section_response(response: &str, section: &str) -> bool {
+        response.contains(&format!("BODY[{}]<", section))
+    }
+
     /// Parse SEARCH response
     pub fn parse_search_response(response: &str) -> ImapResult<Vec<u32>> {
         let mut message_ids = Vec::new();
@@ -401,6 +557,26 @@ impl ImapProtocol {
         format!("SELECT \"{}\"", folder)
     }
 
+    /// Format SELECT asking for mod-sequences (RFC 7162)
+    ///
+    /// With the folder's last known UIDVALIDITY and HIGHESTMODSEQ the QRESYNC
+    /// parameter is used, so the server reports what was expunged since;
+    /// otherwise CONDSTORE is enabled for the folder.
+    pub fn format_select_condstore(folder: &str, qresync: Option<(u32, u64)>) -> String {
+        match qresync {
+            Some((uid_validity, modseq)) => format!(
+                "SELECT \"{}\" (QRESYNC ({} {}))",
+                folder, uid_validity, modseq
+            ),
+            None => format!("SELECT \"{}\" (CONDSTORE)", folder),
+        }
+    }
+
+    /// Format ENABLE command (RFC 5161)
+    pub fn format_enable(capabilities: &[&str]) -> String {
+        format!("ENABLE {}", capabilities.join(" "))
+    }
+
     /// Format EXAMINE command
     pub fn format_examine(folder: &str) -> String {
         format!("EXAMINE \"{}\"", folder)
@@ -426,6 +602,63 @@ impl ImapProtocol {
         format!("UID FETCH {} ({})", uid_set, items.join(" "))
     }
 
+    /// Format UID FETCH limited to messages changed after a mod-sequence
+    pub fn format_uid_fetch_changed_since(uid_set: &str, items: &[&str], modseq: u64) -> String {
+        format!(
+            "UID FETCH {} ({}) (CHANGEDSINCE {})",
+            uid_set,
+            items.join(" "),
+            modseq
+        )
+    }
+
+    /// Format UID FETCH of one body section, optionally limited to `(offset, length)` bytes
+    pub fn format_uid_fetch_section(uid: u32, section: &str, partial: Option<(usize, usize)>) -> String {
+        match partial {
+            Some((offset, length)) => format!(
+                "UID FETCH {} (BODY.PEEK[{}]<{}.{}>)",
+                uid, section, offset, length
+            ),
+            None => format!("UID FETCH {} (BODY.PEEK[{}])", uid, section),
+        }
+    }
+
+    /// Put a message back together from its header and some of its parts
+    ///
+    /// `header` is the message's `HEADER` section and each part is its
+    /// `.MIME` header followed by its body, as fetched. The parts go into a
+    /// `multipart/mixed` body, replacing the message's own Content-Type.
+    pub fn assemble_parts(header: &str, parts: &[(String, String)]) -> String {
+        const BOUNDARY: &str = "comunicado-parts";
+
+        let mut message = String::new();
+        let mut skipping = false;
+        for line in header.lines() {
+            if line.is_empty() {
+                break;
+            }
+            // Folded continuation lines belong to the field before them
+            if !line.starts_with([' ', '\t']) {
+                let name = line.split(':').next().unwrap_or_default();
+                skipping = name.eq_ignore_ascii_case("Content-Type")
+                    || name.eq_ignore_ascii_case("Content-Transfer-Encoding");
+            }
+            if !skipping {
+                message.push_str(line);
+                message.push_str("\r\n");
+            }
+        }
+        message.push_str(&format!(
+            "Content-Type: multipart/mixed; boundary=\"{}\"\r\n\r\n",
+            BOUNDARY
+        ));
+        for (mime, body) in parts {
+            message.push_str(&format!("--{}\r\n{}{}\r\n", BOUNDARY, mime, body));
+        }
+        message.push_str(&format!("--{}--\r\n", BOUNDARY));
+        message
+    }
+
     /// Format SEARCH command
     pub fn format_search(criteria: &SearchCriteria) -> String {
         format!("SEARCH {}", criteria.to_imap_string())
@@ -453,6 +686,16 @@ impl ImapProtocol {
         format!("UID STORE {} {} ({})", uid_set, action, flags_str.join(" "))
     }
 
+    /// Format UID STORE of Gmail labels, `action` being `+X-GM-LABELS` or
+    /// `-X-GM-LABELS`
+    pub fn format_uid_store_labels(uid_set: &str, labels: &[String], action: &str) -> String {
+        let labels_str: Vec<String> = labels
+            .iter()
+            .map(|label| format!("\"{}\"", label.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect();
+        format!("UID STORE {} {} ({})", uid_set, action, labels_str.join(" "))
+    }
+
     /// Format COPY command
     pub fn format_copy(sequence_set: &str, destination: &str) -> String {
         format!("COPY {} \"{}\"", sequence_set, destination)
@@ -468,6 +711,11 @@ impl ImapProtocol {
         "EXPUNGE".to_string()
     }
 
+    /// Format UID EXPUNGE command (RFC 4315)
+    pub fn format_uid_expunge(uid_set: &str) -> String {
+        format!("UID EXPUNGE {}", uid_set)
+    }
+
     /// Format CREATE command
     pub fn format_create(folder: &str) -> String {
         format!("CREATE \"{}\"", folder)
@@ -498,6 +746,36 @@ impl ImapProtocol {
         format!("STATUS \"{}\" ({})", folder, items.join(" "))
     }
 
+    /// Format APPEND command up to and including the literal size
+    ///
+    /// With `non_sync` the literal is sent as `{size+}` so the client can
+    /// send the message without waiting for a continuation (LITERAL+).
+    pub fn format_append(
+        folder: &str,
+        flags: &[MessageFlag],
+        internal_date: Option<DateTime<Utc>>,
+        size: u64,
+        non_sync: bool,
+    ) -> String {
+        let mut command = format!("APPEND \"{}\"", folder);
+        if !flags.is_empty() {
+            let flags_str: Vec<String> = flags.iter().map(|f| f.to_string()).collect();
+            command.push_str(&format!(" ({})", flags_str.join(" ")));
+        }
+        if let Some(date) = internal_date {
+            command.push_str(&format!(" \"{}\"", date.format("%d-%b-%Y %H:%M:%S %z")));
+        }
+        command.push_str(&format!(" {{{}{}}}", size, if non_sync { "+" } else { "" }));
+        command
+    }
+
+    /// Extract the new message UID from an `[APPENDUID uidvalidity uid]` response code
+    pub fn parse_append_uid(response: &str) -> Option<u32> {
+        let start = response.find("[APPENDUID ")? + "[APPENDUID ".len();
+        let end = response[start..].find(']')? + start;
+        response[start..end].split_whitespace().nth(1)?.parse().ok()
+    }
+
     /// Format IDLE command
     pub fn format_idle() -> String {
         "IDLE".to_string()
@@ -508,6 +786,33 @@ impl ImapProtocol {
         "DONE".to_string()
     }
 
+    /// Format NOTIFY SET command (RFC 5465)
+    ///
+    /// Asks for new messages, expunges and flag changes in the selected
+    /// folder, and for status updates on new messages and expunges in the
+    /// other `mailboxes`. `STATUS` makes the server report the current state
+    /// of each mailbox straight away.
+    pub fn format_notify_set(mailboxes: &[String]) -> String {
+        let mut command = "NOTIFY SET STATUS (SELECTED (MessageNew MessageExpunge FlagChange))"
+            .to_string();
+        if !mailboxes.is_empty() {
+            let names: Vec<String> = mailboxes
+                .iter()
+                .map(|mailbox| format!("\"{}\"", mailbox))
+                .collect();
+            command.push_str(&format!(
+                " (MAILBOXES ({}) (MessageNew MessageExpunge))",
+                names.join(" ")
+            ));
+        }
+        command
+    }
+
+    /// Format NOTIFY NONE command to stop all notifications
+    pub fn format_notify_none() -> String {
+        "NOTIFY NONE".to_string()
+    }
+
     /// Extract literal size from a line containing {size}
     fn extract_literal_size_from_line(line: &str) -> Option<usize> {
         // Look for {size} pattern
@@ -780,6 +1085,104 @@ impl ImapProtocol {
     }
 }
 
+/// A value in a parenthesized FETCH item such as BODYSTRUCTURE
+#[derive(Debug, Clone, PartialEq)]
+enum ResponseValue {
+    Nil,
+    Atom(String),
+    Text(String),
+    List(Vec<ResponseValue>),
+}
+
+impl ResponseValue {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            ResponseValue::Atom(value) | ResponseValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn as_list(&self) -> Option<&[ResponseValue]> {
+        match self {
+            ResponseValue::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Parse one value starting at `pos`, leaving `pos` just past it
+    ///
+    /// Literals are expected in the form the connection joins them: the
+    /// `{size}` line, a newline, then the literal's bytes.
+    fn parse(data: &[u8], pos: &mut usize) -> Option<ResponseValue> {
+        while data.get(*pos).is_some_and(|byte| byte.is_ascii_whitespace()) {
+            *pos += 1;
+        }
+
+        match data.get(*pos)? {
+            b'(' => {
+                *pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    while data.get(*pos).is_some_and(|byte| byte.is_ascii_whitespace()) {
+                        *pos += 1;
+                    }
+                    if *data.get(*pos)? == b')' {
+                        *pos += 1;
+                        return Some(ResponseValue::List(items));
+                    }
+                    items.push(Self::parse(data, pos)?);
+                }
+            }
+            b'"' => {
+                *pos += 1;
+                let mut text = Vec::new();
+                loop {
+                    match *data.get(*pos)? {
+                        b'"' => break,
+                        b'\\' => {
+                            *pos += 1;
+                            text.push(*data.get(*pos)?);
+                        }
+                        byte => text.push(byte),
+                    }
+                    *pos += 1;
+                }
+                *pos += 1;
+                Some(ResponseValue::Text(String::from_utf8_lossy(&text).into_owned()))
+            }
+            b'{' => {
+                let close = *pos + data[*pos..].iter().position(|byte| *byte == b'}')?;
+                let size: usize = std::str::from_utf8(&data[*pos + 1..close]).ok()?.parse().ok()?;
+                *pos = close + 1;
+                if data.get(*pos) == Some(&b'\r') {
+                    *pos += 1;
+                }
+                if data.get(*pos) == Some(&b'\n') {
+                    *pos += 1;
+                }
+                let literal = data.get(*pos..*pos + size)?;
+                *pos += size;
+                Some(ResponseValue::Text(String::from_utf8_lossy(literal).into_owned()))
+            }
+            _ => {
+                let start = *pos;
+                while data
+                    .get(*pos)
+                    .is_some_and(|byte| !byte.is_ascii_whitespace() && *byte != b'(' && *byte != b')')
+                {
+                    *pos += 1;
+                }
+                let atom = String::from_utf8_lossy(&data[start..*pos]).into_owned();
+                if atom.eq_ignore_ascii_case("NIL") {
+                    Some(ResponseValue::Nil)
+                } else {
+                    Some(ResponseValue::Atom(atom))
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -824,6 +1227,174 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_append() {
+        let date = DateTime::parse_from_rfc3339("2024-03-04T09:05:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            ImapProtocol::format_append("Sent", &[MessageFlag::Seen], Some(date), 2048, true),
+            "APPEND \"Sent\" (\\Seen) \"04-Mar-2024 09:05:00 +0000\" {2048+}"
+        );
+        assert_eq!(
+            ImapProtocol::format_append("Drafts", &[], None, 10, false),
+            "APPEND \"Drafts\" {10}"
+        );
+
+        assert_eq!(
+            ImapProtocol::parse_append_uid("A0005 OK [APPENDUID 38505 3955] APPEND completed"),
+            Some(3955)
+        );
+        assert_eq!(ImapProtocol::parse_append_uid("A0005 OK APPEND completed"), None);
+    }
+
+    #[test]
+    fn test_condstore_responses() {
+        let response = "* 172 EXISTS\r\n\
+            * OK [UIDVALIDITY 3857529045] UIDs valid\r\n\
+            * OK [UIDNEXT 4392] Predicted next UID\r\n\
+            * OK [HIGHESTMODSEQ 715194045007] Highest\r\n\
+            * VANISHED (EARLIER) 41,43:45,118\r\n\
+            * 49 FETCH (UID 117 FLAGS (\\Seen \\Answered) MODSEQ (90060115194045001))\r\n\
+            A02 OK [READ-WRITE] Sorry, UIDVALIDITY mismatch\r\n";
+
+        let folder = ImapProtocol::parse_select_response(response).unwrap();
+        assert_eq!(folder.uid_next, Some(4392));
+        assert_eq!(folder.highest_modseq, Some(715194045007));
+        assert_eq!(ImapProtocol::parse_vanished(response), vec![41, 43, 44, 45, 118]);
+
+        let messages = ImapProtocol::parse_fetch_response(response).unwrap();
+        assert_eq!(messages[0].uid, Some(117));
+        assert!(messages[0].flags.contains(&MessageFlag::Answered));
+
+        assert_eq!(
+            ImapProtocol::format_select_condstore("INBOX", Some((67890007, 90060115194045000))),
+            "SELECT \"INBOX\" (QRESYNC (67890007 90060115194045000))"
+        );
+        assert_eq!(
+            ImapProtocol::format_uid_fetch_changed_since("1:*", &["UID", "FLAGS"], 12111230047),
+            "UID FETCH 1:* (UID FLAGS) (CHANGEDSINCE 12111230047)"
+        );
+        assert_eq!(
+            ImapProtocol::parse_enabled("* ENABLED CONDSTORE QRESYNC\r\nA01 OK\r\n"),
+            vec!["CONDSTORE", "QRESYNC"]
+        );
+    }
+
+    #[test]
+    fn test_gmail_labels() {
+        let response = "* 3 FETCH (X-GM-LABELS (\"\\\\Important\" \"\\\\Inbox\" Work \"Project X\") UID 812 FLAGS (\\Seen))\r\n\
+            A07 OK Success\r\n";
+
+        let messages = ImapProtocol::parse_fetch_response(response).unwrap();
+        assert_eq!(messages[0].uid, Some(812));
+        assert_eq!(messages[0].flags, vec![MessageFlag::Seen]);
+        assert_eq!(
+            messages[0].labels,
+            vec!["\\Important", "\\Inbox", "Work", "Project X"]
+        );
+
+        assert_eq!(
+            ImapProtocol::format_uid_store_labels(
+                "812",
+                &["\\Inbox".to_string(), "Say \"hi\"".to_string()],
+                "+X-GM-LABELS"
+            ),
+            "UID STORE 812 +X-GM-LABELS (\"\\\\Inbox\" \"Say \\\"hi\\\"\")"
+        );
+    }
+
+    #[test]
+    fn test_parse_bodystructure() {
+        let response = "* 12 FETCH (UID 4827 BODYSTRUCTURE (((\"TEXT\" \"PLAIN\" (\"CHARSET\" \"utf-8\") NIL NIL \"QUOTED-PRINTABLE\" 812 24 NIL NIL NIL)\
+            (\"TEXT\" \"HTML\" (\"CHARSET\" \"utf-8\") NIL NIL \"7BIT\" 2410 60 NIL NIL NIL) \"ALTERNATIVE\" (\"BOUNDARY\" \"b2\") NIL NIL)\
+            (\"APPLICATION\" \"PDF\" (\"NAME\" \"report.pdf\") NIL NIL \"BASE64\" 41943040 NIL (\"ATTACHMENT\" (\"FILENAME\" {10}\nreport.pdf)) NIL)\
+            \"MIXED\" (\"BOUNDARY\" \"b1\") NIL NIL))\nA0007 OK FETCH completed";
+
+        let structure = ImapProtocol::parse_bodystructure(response).unwrap();
+        assert!(structure.is_multipart());
+        assert_eq!(structure.media_subtype, "mixed");
+
+        let sections = structure.sections();
+        let numbers: Vec<&str> = sections.iter().map(|(section, _)| section.as_str()).collect();
+        assert_eq!(numbers, vec!["1.1", "1.2", "2"]);
+        assert!(sections[0].1.is_plain_text());
+        assert_eq!(sections[0].1.content_transfer_encoding.as_deref(), Some("QUOTED-PRINTABLE"));
+
+        let attachment = sections[2].1;
+        assert!(attachment.is_attachment());
+        assert_eq!(attachment.filename(), Some("report.pdf"));
+        assert_eq!(attachment.size, Some(41943040));
+
+        // A single-part message is section 1
+        let single = ImapProtocol::parse_bodystructure(
+            "* 1 FETCH (BODYSTRUCTURE (\"text\" \"plain\" NIL NIL NIL \"7bit\" 12 1 NIL NIL NIL))",
+        )
+        .unwrap();
+        assert_eq!(single.sections()[0].0, "1");
+        assert_eq!(single.disposition, None);
+    }
+
+    #[test]
+    fn test_text_only_parts() {
+        let response = "* 12 FETCH (UID 4827 BODYSTRUCTURE (((\"TEXT\" \"PLAIN\" (\"CHARSET\" \"utf-8\") NIL NIL \"7BIT\" 812 24 NIL NIL NIL)\
+            (\"TEXT\" \"HTML\" (\"CHARSET\" \"utf-8\") NIL NIL \"7BIT\" 2410 60 NIL NIL NIL) \"ALTERNATIVE\" (\"BOUNDARY\" \"b2\") NIL NIL)\
+            (\"APPLICATION\" \"PDF\" (\"NAME\" \"report.pdf\") NIL NIL \"BASE64\" 4104 NIL (\"ATTACHMENT\" (\"FILENAME\" \"report.pdf\")) NIL)\
+            \"MIXED\" (\"BOUNDARY\" \"b1\") NIL NIL))\nA0007 OK FETCH completed";
+        let structure = ImapProtocol::parse_bodystructure(response).unwrap();
+        let sections: Vec<String> = structure
+            .text_only_sections()
+            .into_iter()
+            .map(|(section, _)| section)
+            .collect();
+        assert_eq!(sections, vec!["1.1", "2"]);
+
+        // HTML is kept when it is the only body
+        let html_only = ImapProtocol::parse_bodystructure(
+            "* 1 FETCH (BODYSTRUCTURE (\"text\" \"html\" NIL NIL NIL \"7bit\" 12 1 NIL NIL NIL))",
+        )
+        .unwrap();
+        assert_eq!(html_only.text_only_sections().len(), 1);
+
+        let message = ImapProtocol::assemble_parts(
+            "Subject: Hi\r\nContent-Type: multipart/alternative;\r\n boundary=\"b2\"\r\nFrom: sam@example.com\r\n\r\n",
+            &[(
+                "Content-Type: text/plain\r\n\r\n".to_string(),
+                "Hello".to_string(),
+            )],
+        );
+        assert_eq!(
+            message,
+            "Subject: Hi\r\nFrom: sam@example.com\r\n\
+            Content-Type: multipart/mixed; boundary=\"comunicado-parts\"\r\n\r\n\
+            --comunicado-parts\r\nContent-Type: text/plain\r\n\r\nHello\r\n\
+            --comunicado-parts--\r\n"
+        );
+        let parsed = crate::mime::parse_message(&message);
+        assert_eq!(parsed.text.as_deref().map(str::trim), Some("Hello"));
+    }
+
+    #[test]
+    fn test_partial_section_fetch() {
+        assert_eq!(
+            ImapProtocol::format_uid_fetch_section(4827, "2", Some((262144, 262144))),
+            "UID FETCH 4827 (BODY.PEEK[2]<262144.262144>)"
+        );
+        assert_eq!(
+            ImapProtocol::format_uid_fetch_section(4827, "1.MIME", None),
+            "UID FETCH 4827 (BODY.PEEK[1.MIME])"
+        );
+        assert!(ImapProtocol::is_partial_section_response(
+            "* 12 FETCH (UID 4827 BODY[2]<0> {262144}",
+            "2"
+        ));
+        assert!(!ImapProtocol::is_partial_section_response(
+            "* 12 FETCH (UID 4827 BODY[2] {41943040}",
+            "2"
+        ));
+    }
+
     #[test]
     fn test_search_criteria() {
         let criteria = SearchCriteria::From("test@example.com".to_string());
@@ -14,6 +14,8 @@ pub struct ImapFolder {
     pub unseen: Option<u32>,
     pub uid_validity: Option<u32>,
     pub uid_next: Option<u32>,
+    /// HIGHESTMODSEQ reported on SELECT by CONDSTORE servers (RFC 7162)
+    pub highest_modseq: Option<u64>,
 }
 
 impl ImapFolder {
@@ -28,6 +30,7 @@ impl ImapFolder {
             unseen: None,
             uid_validity: None,
             uid_next: None,
+            highest_modseq: None,
         }
     }
 
@@ -96,6 +99,8 @@ pub struct ImapMessage {
     pub body_structure: Option<BodyStructure>,
     pub headers: HashMap<String, String>,
     pub body: Option<String>,
+    /// Gmail labels from X-GM-LABELS, e.g. `\Inbox` or `Work`
+    pub labels: Vec<String>,
 }
 
 impl ImapMessage {
@@ -110,6 +115,7 @@ impl ImapMessage {
             body_structure: None,
             headers: HashMap::new(),
             body: None,
+            labels: Vec::new(),
         }
     }
 
@@ -266,6 +272,9 @@ pub struct BodyStructure {
     pub content_description: Option<String>,
     pub content_transfer_encoding: Option<String>,
     pub size: Option<u32>,
+    /// Content-Disposition, e.g. `attachment` or `inline`
+    pub disposition: Option<String>,
+    pub disposition_parameters: HashMap<String, String>,
     pub parts: Vec<BodyStructure>,
 }
 
@@ -279,6 +288,8 @@ impl BodyStructure {
             content_description: None,
             content_transfer_encoding: None,
             size: None,
+            disposition: None,
+            disposition_parameters: HashMap::new(),
             parts: Vec::new(),
         }
     }
@@ -300,7 +311,76 @@ impl BodyStructure {
     }
 
     pub fn is_attachment(&self) -> bool {
-        self.parameters.get("name").is_some() || self.content_id.is_some()
+        self.disposition
+            .as_deref()
+            .is_some_and(|disposition| disposition.eq_ignore_ascii_case("attachment"))
+            || self.parameters.contains_key("name")
+            || self.content_id.is_some()
+    }
+
+    /// File name from the disposition, falling back to the `name` parameter
+    pub fn filename(&self) -> Option<&str> {
+        self.disposition_parameters
+            .get("filename")
+            .or_else(|| self.parameters.get("name"))
+            .map(String::as_str)
+    }
+
+    /// Leaf parts with their FETCH section numbers (`1`, `2.1`, ...)
+    ///
+    /// A single-part message is section `1`. Encapsulated messages are
+    /// listed as one part rather than descended into.
+    pub fn sections(&self) -> Vec<(String, &BodyStructure)> {
+        let mut sections = Vec::new();
+        if self.is_multipart() {
+            self.collect_sections("", &mut sections);
+        } else {
+            sections.push(("1".to_string(), self));
+        }
+        sections
+    }
+
+    /// Leaf parts to fetch when HTML is never downloaded
+    ///
+    /// HTML bodies and the inline images they show are left out, unless the
+    /// message has no plain text body to show instead.
+    pub fn text_only_sections(&self) -> Vec<(String, &BodyStructure)> {
+        let sections = self.sections();
+        let body = |part: &BodyStructure| {
+            !part
+                .disposition
+                .as_deref()
+                .is_some_and(|disposition| disposition.eq_ignore_ascii_case("attachment"))
+        };
+        if !sections
+            .iter()
+            .any(|(_, part)| part.is_plain_text() && body(part))
+        {
+            return sections;
+        }
+        sections
+            .into_iter()
+            .filter(|(_, part)| !(body(part) && (part.is_html() || part.content_id.is_some())))
+            .collect()
+    }
+
+    fn collect_sections<'a>(
+        &'a self,
+        prefix: &str,
+        sections: &mut Vec<(String, &'a BodyStructure)>,
+    ) {
+        for (index, part) in self.parts.iter().enumerate() {
+            let section = if prefix.is_empty() {
+                (index + 1).to_string()
+            } else {
+                format!("{}.{}", prefix, index + 1)
+            };
+            if part.is_multipart() {
+                part.collect_sections(&section, sections);
+            } else {
+                sections.push((section, part));
+            }
+        }
     }
 }
 
@@ -0,0 +1,639 @@
+use crate::imap::ImapFolder;
+use crate::jmap::{
+    mailbox_paths, Email, EmailBodyPart, EmailChanges, JmapAuthMethod, JmapCapability, JmapConfig, JmapError,
+    JmapResult, JmapSession, Mailbox,
+};
+use crate::oauth2::TokenManager;
+use reqwest::header::{AUTHORIZATION, LOCATION};
+use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Ids requested per `Email/query` page
+const QUERY_PAGE_SIZE: usize = 256;
+
+/// Redirects followed when fetching the session or a blob
+const MAX_REDIRECTS: usize = 5;
+
+/// JMAP client for a single account
+pub struct JmapClient {
+    config: JmapConfig,
+    http: reqwest::Client,
+    token_manager: Option<TokenManager>,
+    session: Option<JmapSession>,
+    capabilities: Vec<JmapCapability>,
+    /// Mailbox ids keyed by folder name
+    mailbox_ids: HashMap<String, String>,
+    /// Folder names keyed by mailbox id
+    folder_names: HashMap<String, String>,
+}
+
+impl JmapClient {
+    /// Create a new JMAP client with password or token auth
+    pub fn new(config: JmapConfig) -> Self {
+        // Redirects are followed by hand so credentials survive the
+        // `.well-known/jmap` hop to the real session URL
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        Self {
+            config,
+            http,
+            token_manager: None,
+            session: None,
+            capabilities: Vec::new(),
+            mailbox_ids: HashMap::new(),
+            folder_names: HashMap::new(),
+        }
+    }
+
+    /// Create a new JMAP client with OAuth2 authentication
+    pub fn new_with_oauth2(config: JmapConfig, token_manager: TokenManager) -> Self {
+        let mut client = Self::new(config);
+        client.token_manager = Some(token_manager);
+        client
+    }
+
+    /// Client configuration
+    pub fn config(&self) -> &JmapConfig {
+        &self.config
+    }
+
+    /// Fetch the session resource and check the server offers JMAP mail
+    pub async fn connect(&mut self) -> JmapResult<()> {
+        let url = self.config.session_url.clone();
+        let response = self.get(&url).await?;
+        let session: JmapSession = response.json().await?;
+
+        let capabilities: Vec<JmapCapability> = session
+            .capabilities
+            .keys()
+            .map(|name| JmapCapability::from(name.as_str()))
+            .collect();
+        if !capabilities.contains(&JmapCapability::Mail) || session.mail_account_id().is_none() {
+            return Err(JmapError::not_supported(
+                "Server does not offer JMAP mail for this account",
+            ));
+        }
+
+        tracing::info!(
+            "Connected to JMAP session at {} ({} capabilities)",
+            url,
+            capabilities.len()
+        );
+        self.capabilities = capabilities;
+        self.session = Some(session);
+        Ok(())
+    }
+
+    /// Forget the session; the next call needs [`connect`](Self::connect)
+    pub fn disconnect(&mut self) {
+        self.session = None;
+        self.capabilities.clear();
+    }
+
+    /// Check if a session has been fetched
+    pub fn is_connected(&self) -> bool {
+        self.session.is_some()
+    }
+
+    /// Session resource, once connected
+    pub fn session(&self) -> Option<&JmapSession> {
+        self.session.as_ref()
+    }
+
+    /// Capabilities advertised by the session
+    pub fn capabilities(&self) -> &[JmapCapability] {
+        &self.capabilities
+    }
+
+    /// Check if the session advertises a capability
+    pub fn has_capability(&self, capability: &JmapCapability) -> bool {
+        self.capabilities.contains(capability)
+    }
+
+    /// List mailboxes as folders named by their full path
+    ///
+    /// Also refreshes the folder name ↔ mailbox id mapping used by the other
+    /// calls.
+    pub async fn list_folders(&mut self) -> JmapResult<Vec<ImapFolder>> {
+        let account_id = self.account_id()?;
+        let result = self
+            .call_one("Mailbox/get", json!({ "accountId": account_id, "ids": null }))
+            .await?;
+        let mailboxes: Vec<Mailbox> = field(&result, "list")?;
+
+        let paths = mailbox_paths(&mailboxes);
+        let parents: HashSet<&str> = mailboxes
+            .iter()
+            .filter_map(|mailbox| mailbox.parent_id.as_deref())
+            .collect();
+
+        self.mailbox_ids.clear();
+        self.folder_names.clear();
+        let mut folders = Vec::with_capacity(mailboxes.len());
+        for mailbox in &mailboxes {
+            let folder =
+                mailbox.to_imap_folder(&paths[&mailbox.id], parents.contains(mailbox.id.as_str()));
+            self.mailbox_ids.insert(folder.name.clone(), mailbox.id.clone());
+            self.folder_names.insert(mailbox.id.clone(), folder.name.clone());
+            folders.push(folder);
+        }
+
+        folders.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(folders)
+    }
+
+    /// Mailbox id of a folder, listing mailboxes if it isn't known yet
+    pub async fn mailbox_id(&mut self, folder_name: &str) -> JmapResult<String> {
+        if self.lookup_mailbox_id(folder_name).is_none() {
+            self.list_folders().await?;
+        }
+
+        self.lookup_mailbox_id(folder_name)
+            .map(str::to_string)
+            .ok_or_else(|| JmapError::not_found(format!("Folder {}", folder_name)))
+    }
+
+    fn lookup_mailbox_id(&self, folder_name: &str) -> Option<&str> {
+        self.mailbox_ids
+            .get(folder_name)
+            .or_else(|| {
+                folder_name
+                    .eq_ignore_ascii_case("INBOX")
+                    .then(|| self.mailbox_ids.get("INBOX"))
+                    .flatten()
+            })
+            .map(String::as_str)
+    }
+
+    /// Folder name of a mailbox id from the last listing
+    pub fn folder_name(&self, mailbox_id: &str) -> Option<&str> {
+        self.folder_names.get(mailbox_id).map(String::as_str)
+    }
+
+    /// Ids of the emails in a mailbox, newest first
+    pub async fn query_emails(
+        &self,
+        mailbox_id: &str,
+        limit: Option<usize>,
+    ) -> JmapResult<Vec<String>> {
+        let account_id = self.account_id()?;
+        let mut ids: Vec<String> = Vec::new();
+
+        loop {
+            let page_size = limit.map_or(QUERY_PAGE_SIZE, |limit| {
+                limit.saturating_sub(ids.len()).min(QUERY_PAGE_SIZE)
+            });
+            if page_size == 0 {
+                break;
+            }
+
+            let result = self
+                .call_one(
+                    "Email/query",
+                    json!({
+                        "accountId": account_id,
+                        "filter": { "inMailbox": mailbox_id },
+                        "sort": [{ "property": "receivedAt", "isAscending": false }],
+                        "position": ids.len(),
+                        "limit": page_size,
+                        "calculateTotal": true,
+                    }),
+                )
+                .await?;
+            let page: Vec<String> = field(&result, "ids")?;
+            let total = result.get("total").and_then(Value::as_u64);
+
+            let fetched = page.len();
+            ids.extend(page);
+            if fetched < page_size || total.is_some_and(|total| ids.len() as u64 >= total) {
+                break;
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Metadata of emails; ids the server no longer has are left out
+    pub async fn get_emails(&self, ids: &[String]) -> JmapResult<Vec<Email>> {
+        let account_id = self.account_id()?;
+        let batch_size = self.connected_session()?.max_objects_in_get();
+
+        let mut emails = Vec::with_capacity(ids.len());
+        for batch in ids.chunks(batch_size) {
+            let result = self
+                .call_one(
+                    "Email/get",
+                    json!({
+                        "accountId": account_id,
+                        "ids": batch,
+                        "properties": Email::PROPERTIES,
+                    }),
+                )
+                .await?;
+            emails.extend(field::<Vec<Email>>(&result, "list")?);
+        }
+
+        Ok(emails)
+    }
+
+    /// Current state string of the account's emails
+    pub async fn email_state(&self) -> JmapResult<String> {
+        let account_id = self.account_id()?;
+        let result = self
+            .call_one("Email/get", json!({ "accountId": account_id, "ids": [] }))
+            .await?;
+        field(&result, "state")
+    }
+
+    /// Emails created, updated and destroyed since `since_state`
+    ///
+    /// Fails with [`JmapError::CannotCalculateChanges`] when the server no
+    /// longer has history back to that state.
+    pub async fn email_changes(
+        &self,
+        since_state: &str,
+        max_changes: Option<usize>,
+    ) -> JmapResult<EmailChanges> {
+        let account_id = self.account_id()?;
+        let mut arguments = json!({ "accountId": account_id, "sinceState": since_state });
+        if let Some(max_changes) = max_changes {
+            arguments["maxChanges"] = json!(max_changes);
+        }
+
+        let result = self.call_one("Email/changes", arguments).await?;
+        serde_json::from_value(result)
+            .map_err(|e| JmapError::protocol(format!("Invalid Email/changes response: {}", e)))
+    }
+
+    /// Full RFC 5322 source of an email
+    pub async fn download_message(&self, blob_id: &str) -> JmapResult<String> {
+        let bytes = self
+            .download_blob(blob_id, "message/rfc822", "message.eml")
+            .await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Content of an email's attachment, found by file name
+    pub async fn download_attachment(&self, email_id: &str, filename: &str) -> JmapResult<Vec<u8>> {
+        let account_id = self.account_id()?;
+        let result = self
+            .call_one(
+                "Email/get",
+                json!({
+                    "accountId": account_id,
+                    "ids": [email_id],
+                    "properties": ["attachments"],
+                }),
+            )
+            .await?;
+        let attachments: Vec<EmailBodyPart> = result
+            .pointer("/list/0/attachments")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| JmapError::protocol(format!("Invalid attachments: {}", e)))?
+            .ok_or_else(|| JmapError::not_found(format!("Email {}", email_id)))?;
+
+        let (blob_id, part) = attachments
+            .iter()
+            .find_map(|part| {
+                let blob_id = part.blob_id.as_deref()?;
+                (part.name.as_deref() == Some(filename)).then_some((blob_id, part))
+            })
+            .ok_or_else(|| JmapError::not_found(format!("Attachment '{}'", filename)))?;
+        self.download_blob(blob_id, &part.content_type, filename).await
+    }
+
+    async fn download_blob(
+        &self,
+        blob_id: &str,
+        content_type: &str,
+        name: &str,
+    ) -> JmapResult<Vec<u8>> {
+        let url = self.connected_session()?.blob_download_url(
+            &self.account_id()?,
+            blob_id,
+            content_type,
+            name,
+        );
+        let response = self.get(&url).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Full RFC 5322 source of an email by id
+    pub async fn download_email(&self, email_id: &str) -> JmapResult<String> {
+        let email = self
+            .get_emails(&[email_id.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| JmapError::not_found(format!("Email {}", email_id)))?;
+        self.download_message(&email.blob_id).await
+    }
+
+    /// Set or clear a keyword such as `$seen` on emails
+    pub async fn set_keyword(&self, ids: &[String], keyword: &str, set: bool) -> JmapResult<()> {
+        let path = format!("keywords/{}", pointer_escape(keyword));
+        let value = if set { json!(true) } else { Value::Null };
+        self.update_emails(ids, |patch| {
+            patch.insert(path.clone(), value.clone());
+        })
+        .await
+    }
+
+    /// Move emails from one mailbox to another
+    pub async fn move_emails(&self, ids: &[String], from_mailbox: &str, to_mailbox: &str) -> JmapResult<()> {
+        let from = format!("mailboxIds/{}", pointer_escape(from_mailbox));
+        let to = format!("mailboxIds/{}", pointer_escape(to_mailbox));
+        self.update_emails(ids, |patch| {
+            patch.insert(from.clone(), Value::Null);
+            patch.insert(to.clone(), json!(true));
+        })
+        .await
+    }
+
+    /// Copy emails into another mailbox
+    ///
+    /// Within one account a JMAP email can be in several mailboxes, so this
+    /// adds the mailbox rather than creating a second email.
+    pub async fn copy_emails(&self, ids: &[String], to_mailbox: &str) -> JmapResult<()> {
+        let to = format!("mailboxIds/{}", pointer_escape(to_mailbox));
+        self.update_emails(ids, |patch| {
+            patch.insert(to.clone(), json!(true));
+        })
+        .await
+    }
+
+    /// Permanently delete emails
+    pub async fn destroy_emails(&self, ids: &[String]) -> JmapResult<()> {
+        let account_id = self.account_id()?;
+        let batch_size = self.connected_session()?.max_objects_in_set();
+
+        for batch in ids.chunks(batch_size) {
+            let result = self
+                .call_one("Email/set", json!({ "accountId": account_id, "destroy": batch }))
+                .await?;
+            check_set_errors(&result, "notDestroyed")?;
+        }
+
+        Ok(())
+    }
+
+    /// Apply the same patch to every email, batched per `maxObjectsInSet`
+    async fn update_emails(
+        &self,
+        ids: &[String],
+        build_patch: impl Fn(&mut Map<String, Value>),
+    ) -> JmapResult<()> {
+        let account_id = self.account_id()?;
+        let batch_size = self.connected_session()?.max_objects_in_set();
+
+        let mut patch = Map::new();
+        build_patch(&mut patch);
+        let patch = Value::Object(patch);
+
+        for batch in ids.chunks(batch_size) {
+            let update: Map<String, Value> = batch
+                .iter()
+                .map(|id| (id.clone(), patch.clone()))
+                .collect();
+            let result = self
+                .call_one("Email/set", json!({ "accountId": account_id, "update": update }))
+                .await?;
+            check_set_errors(&result, "notUpdated")?;
+        }
+
+        Ok(())
+    }
+
+    /// Make a single method call and return its response arguments
+    async fn call_one(&self, method: &str, arguments: Value) -> JmapResult<Value> {
+        self.call(vec![(method, arguments)])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| JmapError::protocol(format!("No response to {}", method)))
+    }
+
+    /// Send method calls in one API request and return each response's arguments
+    pub async fn call(&self, method_calls: Vec<(&str, Value)>) -> JmapResult<Vec<Value>> {
+        let api_url = self.connected_session()?.api_url.clone();
+        let method_calls: Vec<Value> = method_calls
+            .into_iter()
+            .enumerate()
+            .map(|(index, (method, arguments))| json!([method, arguments, format!("c{}", index)]))
+            .collect();
+        let request = json!({
+            "using": [JmapCapability::Core.name(), JmapCapability::Mail.name()],
+            "methodCalls": method_calls,
+        });
+
+        let response = self
+            .http
+            .post(&api_url)
+            .header(AUTHORIZATION, self.authorization().await?)
+            .json(&request)
+            .send()
+            .await?;
+        let response: Value = check_status(response)?.json().await?;
+
+        let responses = response
+            .get("methodResponses")
+            .and_then(Value::as_array)
+            .ok_or_else(|| JmapError::protocol("Response has no methodResponses"))?;
+
+        responses
+            .iter()
+            .map(|invocation| match invocation.as_array().map(Vec::as_slice) {
+                Some([name, arguments, _call_id]) if name == "error" => {
+                    Err(JmapError::from_method_error(arguments))
+                }
+                Some([_name, arguments, _call_id]) => Ok(arguments.clone()),
+                _ => Err(JmapError::protocol("Malformed method response")),
+            })
+            .collect()
+    }
+
+    /// GET a URL with credentials, following redirects
+    ///
+    /// Credentials only go to the origin of `url`; once a redirect leads
+    /// elsewhere, the rest of the chain is requested without them.
+    async fn get(&self, url: &str) -> JmapResult<Response> {
+        let mut url = url::Url::parse(url)
+            .map_err(|e| JmapError::invalid_config(format!("Invalid URL {}: {}", url, e)))?;
+        let origin = url.origin();
+        let mut with_credentials = true;
+
+        for _ in 0..=MAX_REDIRECTS {
+            let mut request = self.http.get(url.clone());
+            if with_credentials {
+                request = request.header(AUTHORIZATION, self.authorization().await?);
+            }
+            let response = request.send().await?;
+            if !response.status().is_redirection() {
+                return check_status(response);
+            }
+
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .ok_or_else(|| JmapError::protocol("Redirect without a Location header"))?;
+            url = redirect_target(&url, location)?;
+            with_credentials &= url.origin() == origin;
+        }
+
+        Err(JmapError::protocol("Too many redirects"))
+    }
+
+    /// Authorization header value for the configured auth method
+    async fn authorization(&self) -> JmapResult<String> {
+        match &self.config.auth_method {
+            JmapAuthMethod::Basic(password) => {
+                let credentials = format!("{}:{}", self.config.username, password);
+                Ok(format!(
+                    "Basic {}",
+                    base64::Engine::encode(&base64::engine::general_purpose::STANDARD, credentials)
+                ))
+            }
+            JmapAuthMethod::Bearer(token) => Ok(format!("Bearer {}", token)),
+            JmapAuthMethod::OAuth2 { account_id } => {
+                let token_manager = self.token_manager.as_ref().ok_or_else(|| {
+                    JmapError::authentication("Token manager required for OAuth2")
+                })?;
+                let token = token_manager
+                    .get_valid_access_token(account_id)
+                    .await
+                    .map_err(|e| JmapError::authentication(format!("Failed to get access token: {}", e)))?
+                    .ok_or_else(|| JmapError::authentication("No access token available"))?;
+                Ok(format!("Bearer {}", token.token))
+            }
+        }
+    }
+
+    fn connected_session(&self) -> JmapResult<&JmapSession> {
+        self.session
+            .as_ref()
+            .ok_or_else(|| JmapError::protocol("Not connected to a JMAP session"))
+    }
+
+    fn account_id(&self) -> JmapResult<String> {
+        self.connected_session()?
+            .mail_account_id()
+            .map(str::to_string)
+            .ok_or_else(|| JmapError::not_supported("Session has no mail account"))
+    }
+}
+
+/// Map authentication failures and other HTTP errors
+fn check_status(response: Response) -> JmapResult<Response> {
+    match response.status() {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(JmapError::authentication(
+            format!("Server returned {}", response.status()),
+        )),
+        _ => Ok(response.error_for_status()?),
+    }
+}
+
+/// Deserialize a field of a method response
+fn field<T: DeserializeOwned>(result: &Value, name: &str) -> JmapResult<T> {
+    let value = result
+        .get(name)
+        .ok_or_else(|| JmapError::protocol(format!("Response has no {}", name)))?;
+    serde_json::from_value(value.clone())
+        .map_err(|e| JmapError::protocol(format!("Invalid {} in response: {}", name, e)))
+}
+
+/// Fail with the first per-email error of an `Email/set` response
+fn check_set_errors(result: &Value, key: &str) -> JmapResult<()> {
+    match result
+        .get(key)
+        .and_then(Value::as_object)
+        .and_then(|errors| errors.values().next())
+    {
+        Some(error) => Err(JmapError::from_method_error(error)),
+        None => Ok(()),
+    }
+}
+
+/// Escape a JSON pointer path segment (RFC 6901)
+fn pointer_escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Where a redirect from `from` leads; plain HTTP is refused
+fn redirect_target(from: &url::Url, location: &str) -> JmapResult<url::Url> {
+    let next = from
+        .join(location)
+        .map_err(|e| JmapError::protocol(format!("Invalid redirect {}: {}", location, e)))?;
+    if next.scheme() != "https" {
+        return Err(JmapError::protocol(format!("Refusing redirect to {}", next)));
+    }
+    Ok(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pointer_escape() {
+        assert_eq!(pointer_escape("$seen"), "$seen");
+        assert_eq!(pointer_escape("a/b~c"), "a~1b~0c");
+    }
+
+    #[test]
+    fn test_set_errors() {
+        let ok = json!({ "updated": { "M1": null }, "notUpdated": null });
+        assert!(check_set_errors(&ok, "notUpdated").is_ok());
+
+        let failed = json!({
+            "notUpdated": { "M1": { "type": "notFound", "description": "gone" } }
+        });
+        match check_set_errors(&failed, "notUpdated") {
+            Err(JmapError::Method { kind, description }) => {
+                assert_eq!(kind, "notFound");
+                assert_eq!(description.as_deref(), Some("gone"));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cannot_calculate_changes_error() {
+        let error = JmapError::from_method_error(&json!({ "type": "cannotCalculateChanges" }));
+        assert!(matches!(error, JmapError::CannotCalculateChanges));
+    }
+
+    #[test]
+    fn test_redirect_target() {
+        let from = url::Url::parse("https://example.org/.well-known/jmap").unwrap();
+
+        let same_origin = redirect_target(&from, "/jmap/session").unwrap();
+        assert_eq!(same_origin.as_str(), "https://example.org/jmap/session");
+        assert_eq!(same_origin.origin(), from.origin());
+
+        // Another host is followed, but `get` drops the credentials for it
+        let elsewhere = redirect_target(&from, "https://jmap.example.net/session").unwrap();
+        assert_ne!(elsewhere.origin(), from.origin());
+
+        assert!(redirect_target(&from, "http://example.org/jmap/session").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_calls_require_session() {
+        let client = JmapClient::new(JmapConfig::fastmail(
+            "me@fastmail.com".to_string(),
+            "token".to_string(),
+        ));
+        assert!(!client.is_connected());
+        assert!(client.email_state().await.is_err());
+    }
+}
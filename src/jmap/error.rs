@@ -0,0 +1,98 @@
+use thiserror::Error;
+
+pub type JmapResult<T> = Result<T, JmapError>;
+
+/// JMAP client errors
+#[derive(Error, Debug)]
+pub enum JmapError {
+    /// HTTP transport error
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// Authentication error
+    #[error("Authentication failed: {0}")]
+    Authentication(String),
+
+    /// Protocol error (unexpected or malformed response)
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+
+    /// A method call returned an error response
+    #[error("Method error: {kind}{}", description.as_ref().map(|d| format!(" ({})", d)).unwrap_or_default())]
+    Method {
+        kind: String,
+        description: Option<String>,
+    },
+
+    /// The server can't compute changes from the given state; resync in full
+    #[error("Server cannot calculate changes from the stored state")]
+    CannotCalculateChanges,
+
+    /// Mailbox or email not found
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    /// Feature not supported by the server
+    #[error("Feature not supported: {0}")]
+    NotSupported(String),
+
+    /// Invalid configuration
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl JmapError {
+    /// Create a new authentication error
+    pub fn authentication<S: Into<String>>(msg: S) -> Self {
+        JmapError::Authentication(msg.into())
+    }
+
+    /// Create a new protocol error
+    pub fn protocol<S: Into<String>>(msg: S) -> Self {
+        JmapError::Protocol(msg.into())
+    }
+
+    /// Create a new not found error
+    pub fn not_found<S: Into<String>>(msg: S) -> Self {
+        JmapError::NotFound(msg.into())
+    }
+
+    /// Create a new not supported error
+    pub fn not_supported<S: Into<String>>(msg: S) -> Self {
+        JmapError::NotSupported(msg.into())
+    }
+
+    /// Create a new invalid configuration error
+    pub fn invalid_config<S: Into<String>>(msg: S) -> Self {
+        JmapError::InvalidConfig(msg.into())
+    }
+
+    /// Error for a method error response's arguments
+    pub fn from_method_error(arguments: &serde_json::Value) -> Self {
+        let kind = arguments
+            .get("type")
+            .and_then(|kind| kind.as_str())
+            .unwrap_or("serverFail");
+        if kind == "cannotCalculateChanges" {
+            return JmapError::CannotCalculateChanges;
+        }
+
+        JmapError::Method {
+            kind: kind.to_string(),
+            description: arguments
+                .get("description")
+                .and_then(|description| description.as_str())
+                .map(str::to_string),
+        }
+    }
+
+    /// Check if this is a connection-related error
+    pub fn is_connection_error(&self) -> bool {
+        matches!(self, JmapError::Http(e) if e.is_connect() || e.is_timeout())
+    }
+
+    /// Check if this is an authentication error
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self, JmapError::Authentication(_))
+    }
+}
@@ -0,0 +1,206 @@
+//! JMAP (RFC 8620/8621) mail backend
+//!
+//! An alternative to IMAP for providers that expose JMAP, such as Fastmail.
+//! The public surface mirrors the `imap` module: a [`JmapClient`] configured
+//! by a [`JmapConfig`], with capability detection via [`JmapCapability`].
+//! Mailboxes and emails convert to the IMAP types so the rest of the app
+//! stores and displays them the same way.
+
+pub mod client;
+pub mod error;
+pub mod settings;
+pub mod types;
+
+pub use client::JmapClient;
+pub use error::{JmapError, JmapResult};
+pub use settings::{JmapAccountSettings, JmapMode, JmapSettings};
+pub use types::*;
+
+use crate::imap::{ImapAuthMethod, ImapConfig};
+
+/// Session URL of Fastmail's JMAP API
+pub const FASTMAIL_SESSION_URL: &str = "https://api.fastmail.com/jmap/session";
+
+/// JMAP capability URNs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JmapCapability {
+    Core,
+    Mail,
+    Submission,
+    VacationResponse,
+    Custom(String),
+}
+
+impl From<&str> for JmapCapability {
+    /// Parse a capability URN into a JmapCapability
+    fn from(capability: &str) -> Self {
+        match capability {
+            "urn:ietf:params:jmap:core" => JmapCapability::Core,
+            "urn:ietf:params:jmap:mail" => JmapCapability::Mail,
+            "urn:ietf:params:jmap:submission" => JmapCapability::Submission,
+            "urn:ietf:params:jmap:vacationresponse" => JmapCapability::VacationResponse,
+            _ => JmapCapability::Custom(capability.to_string()),
+        }
+    }
+}
+
+impl JmapCapability {
+    /// Capability URN as the session advertises it
+    pub fn name(&self) -> &str {
+        match self {
+            JmapCapability::Core => "urn:ietf:params:jmap:core",
+            JmapCapability::Mail => "urn:ietf:params:jmap:mail",
+            JmapCapability::Submission => "urn:ietf:params:jmap:submission",
+            JmapCapability::VacationResponse => "urn:ietf:params:jmap:vacationresponse",
+            JmapCapability::Custom(name) => name,
+        }
+    }
+
+    /// Whether `name` refers to this capability
+    pub fn matches(&self, name: &str) -> bool {
+        self.name() == name.trim()
+    }
+}
+
+/// JMAP authentication method
+#[derive(Debug, Clone)]
+pub enum JmapAuthMethod {
+    /// HTTP Basic with the account password or an app password
+    Basic(String),
+    /// A static API token, e.g. a Fastmail API token
+    Bearer(String),
+    /// Access token from the account's OAuth2 credentials
+    OAuth2 { account_id: String },
+}
+
+/// JMAP server configuration
+#[derive(Debug, Clone)]
+pub struct JmapConfig {
+    /// URL of the JMAP session resource
+    pub session_url: String,
+    pub username: String,
+    pub auth_method: JmapAuthMethod,
+    pub timeout_seconds: u64,
+}
+
+impl JmapConfig {
+    /// Create a new JMAP configuration with password auth
+    pub fn new(session_url: String, username: String, password: String) -> Self {
+        Self {
+            session_url,
+            username,
+            auth_method: JmapAuthMethod::Basic(password),
+            timeout_seconds: 60,
+        }
+    }
+
+    /// Create a new JMAP configuration with OAuth2 auth
+    pub fn new_oauth2(session_url: String, username: String, account_id: String) -> Self {
+        Self {
+            session_url,
+            username,
+            auth_method: JmapAuthMethod::OAuth2 { account_id },
+            timeout_seconds: 60,
+        }
+    }
+
+    /// Create configuration for Fastmail with an API token
+    pub fn fastmail(username: String, api_token: String) -> Self {
+        Self {
+            session_url: FASTMAIL_SESSION_URL.to_string(),
+            username,
+            auth_method: JmapAuthMethod::Bearer(api_token),
+            timeout_seconds: 60,
+        }
+    }
+
+    /// JMAP configuration reusing an account's IMAP credentials
+    pub fn from_imap_config(imap: &ImapConfig, session_url: String) -> Self {
+        let auth_method = match &imap.auth_method {
+            ImapAuthMethod::Password(password) => JmapAuthMethod::Basic(password.clone()),
+            ImapAuthMethod::OAuth2 { account_id } => JmapAuthMethod::OAuth2 {
+                account_id: account_id.clone(),
+            },
+        };
+
+        Self {
+            session_url,
+            username: imap.username.clone(),
+            auth_method,
+            timeout_seconds: imap.timeout_seconds,
+        }
+    }
+
+    /// Set request timeout
+    pub fn with_timeout(mut self, timeout_seconds: u64) -> Self {
+        self.timeout_seconds = timeout_seconds;
+        self
+    }
+}
+
+/// Session URL of a known JMAP provider whose IMAP server is `imap_host`
+///
+/// Only providers whose session resource is known get the account's
+/// credentials automatically. A domain's `/.well-known/jmap` is served by its
+/// website rather than its mail server, so it is never probed on a guess.
+pub fn discover_session_url(imap_host: &str) -> Option<String> {
+    let imap_host = imap_host.to_lowercase();
+    (imap_host == "imap.fastmail.com" || imap_host.ends_with(".messagingengine.com"))
+        .then(|| FASTMAIL_SESSION_URL.to_string())
+}
+
+/// `/.well-known/jmap` URL of a domain (RFC 8620 §2.2)
+pub fn well_known_session_url(domain: &str) -> String {
+    format!("https://{}/.well-known/jmap", domain.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capability_round_trip() {
+        for urn in [
+            "urn:ietf:params:jmap:core",
+            "urn:ietf:params:jmap:mail",
+            "urn:ietf:params:jmap:submission",
+            "https://www.fastmail.com/dev/maskedemail",
+        ] {
+            let capability = JmapCapability::from(urn);
+            assert_eq!(capability.name(), urn);
+            assert!(capability.matches(urn));
+        }
+        assert_eq!(
+            JmapCapability::from("urn:ietf:params:jmap:mail"),
+            JmapCapability::Mail
+        );
+    }
+
+    #[test]
+    fn test_session_url_discovery() {
+        assert_eq!(
+            discover_session_url("IMAP.Fastmail.com").as_deref(),
+            Some(FASTMAIL_SESSION_URL)
+        );
+        // Other domains' well-known URLs are their websites, which never get
+        // the credentials without being configured
+        assert_eq!(discover_session_url("mail.example.org"), None);
+        assert_eq!(discover_session_url("imap.gmail.com"), None);
+    }
+
+    #[test]
+    fn test_config_from_imap_reuses_credentials() {
+        let imap = ImapConfig::new(
+            "imap.fastmail.com".to_string(),
+            993,
+            "me@fastmail.com".to_string(),
+            "app-password".to_string(),
+        );
+        let config = JmapConfig::from_imap_config(&imap, FASTMAIL_SESSION_URL.to_string());
+
+        assert_eq!(config.session_url, FASTMAIL_SESSION_URL);
+        assert_eq!(config.username, "me@fastmail.com");
+        assert_eq!(config.timeout_seconds, imap.timeout_seconds);
+        assert!(matches!(config.auth_method, JmapAuthMethod::Basic(ref p) if p == "app-password"));
+    }
+}
@@ -0,0 +1,97 @@
+//! Per-account JMAP backend selection
+//!
+//! By default an account uses JMAP when its provider advertises it and falls
+//! back to IMAP otherwise. Accounts can opt in or out explicitly, or point at
+//! a session URL that discovery wouldn't find.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// When an account uses JMAP instead of IMAP
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JmapMode {
+    /// Use JMAP for known providers, or at the session URL when one is set
+    #[default]
+    Auto,
+    /// Always try JMAP, at the email domain's `/.well-known/jmap` unless a
+    /// session URL is set
+    Always,
+    /// Never try JMAP
+    Never,
+}
+
+/// JMAP settings for one account
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JmapAccountSettings {
+    pub mode: JmapMode,
+    /// Session resource URL; discovered from the account when unset
+    pub session_url: Option<String>,
+    /// API token sent as a Bearer token instead of the IMAP credentials,
+    /// e.g. a Fastmail API token
+    pub api_token: Option<String>,
+}
+
+/// JMAP settings, per account
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JmapSettings {
+    /// Settings keyed by account ID
+    pub accounts: HashMap<String, JmapAccountSettings>,
+}
+
+impl JmapSettings {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "jmap.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Settings for an account, defaulting to automatic detection
+    pub fn for_account(&self, account_id: &str) -> JmapAccountSettings {
+        self.accounts.get(account_id).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_parse() {
+        let settings: JmapSettings = toml::from_str(
+            r#"
+            [accounts.fastmail]
+            mode = "always"
+
+            [accounts.work]
+            mode = "never"
+
+            [accounts.home]
+            session_url = "https://mail.example.org/jmap/session"
+            api_token = "fmu1-token"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.for_account("fastmail").mode, JmapMode::Always);
+        assert_eq!(settings.for_account("work").mode, JmapMode::Never);
+        let home = settings.for_account("home");
+        assert_eq!(home.mode, JmapMode::Auto);
+        assert_eq!(
+            home.session_url.as_deref(),
+            Some("https://mail.example.org/jmap/session")
+        );
+        assert_eq!(home.api_token.as_deref(), Some("fmu1-token"));
+        assert_eq!(settings.for_account("other"), JmapAccountSettings::default());
+    }
+}
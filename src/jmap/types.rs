@@ -0,0 +1,452 @@
+use crate::imap::{Address, FolderAttribute, ImapFolder, ImapMessage, MessageEnvelope, MessageFlag};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Separator used to join nested mailbox names into a folder path
+pub const MAILBOX_DELIMITER: &str = "/";
+
+/// JMAP session resource (RFC 8620 §2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JmapSession {
+    pub capabilities: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub primary_accounts: HashMap<String, String>,
+    #[serde(default)]
+    pub username: String,
+    pub api_url: String,
+    pub download_url: String,
+    #[serde(default)]
+    pub upload_url: String,
+    #[serde(default)]
+    pub state: String,
+}
+
+impl JmapSession {
+    /// Account that holds the user's mail
+    pub fn mail_account_id(&self) -> Option<&str> {
+        self.primary_accounts
+            .get(crate::jmap::JmapCapability::Mail.name())
+            .map(String::as_str)
+    }
+
+    /// Largest number of ids the server accepts in one `/get` call
+    pub fn max_objects_in_get(&self) -> usize {
+        self.core_limit("maxObjectsInGet").unwrap_or(100)
+    }
+
+    /// Largest number of changes the server accepts in one `/set` call
+    pub fn max_objects_in_set(&self) -> usize {
+        self.core_limit("maxObjectsInSet").unwrap_or(100)
+    }
+
+    fn core_limit(&self, name: &str) -> Option<usize> {
+        self.capabilities
+            .get(crate::jmap::JmapCapability::Core.name())?
+            .get(name)?
+            .as_u64()
+            .map(|limit| limit.max(1) as usize)
+    }
+
+    /// Download URL for a blob, expanded from the session's URL template
+    pub fn blob_download_url(
+        &self,
+        account_id: &str,
+        blob_id: &str,
+        content_type: &str,
+        name: &str,
+    ) -> String {
+        self.download_url
+            .replace("{accountId}", &url_encode(account_id))
+            .replace("{blobId}", &url_encode(blob_id))
+            .replace("{type}", &url_encode(content_type))
+            .replace("{name}", &url_encode(name))
+    }
+}
+
+/// Everything but RFC 3986 unreserved characters
+const URL_ENCODE_SET: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+fn url_encode(value: &str) -> String {
+    percent_encoding::utf8_percent_encode(value, URL_ENCODE_SET).to_string()
+}
+
+/// JMAP mailbox (RFC 8621 §2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mailbox {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub role: Option<String>,
+    #[serde(default)]
+    pub sort_order: u32,
+    #[serde(default)]
+    pub total_emails: u32,
+    #[serde(default)]
+    pub unread_emails: u32,
+}
+
+impl Mailbox {
+    /// Folder for this mailbox, named by its full path
+    pub fn to_imap_folder(&self, path: &str, has_children: bool) -> ImapFolder {
+        // The inbox keeps the IMAP name the rest of the app looks for
+        let path = if self.role.as_deref() == Some("inbox") {
+            "INBOX"
+        } else {
+            path
+        };
+
+        let mut folder = ImapFolder::new(path.to_string(), path.to_string());
+        folder.delimiter = Some(MAILBOX_DELIMITER.to_string());
+        folder.exists = Some(self.total_emails);
+        folder.unseen = Some(self.unread_emails);
+        folder.attributes.push(if has_children {
+            FolderAttribute::HasChildren
+        } else {
+            FolderAttribute::HasNoChildren
+        });
+        if let Some(attribute) = self.role.as_deref().and_then(role_attribute) {
+            folder.attributes.push(attribute);
+        }
+        folder
+    }
+}
+
+/// SPECIAL-USE attribute matching a mailbox role
+fn role_attribute(role: &str) -> Option<FolderAttribute> {
+    match role {
+        "all" => Some(FolderAttribute::All),
+        "archive" => Some(FolderAttribute::Archive),
+        "drafts" => Some(FolderAttribute::Drafts),
+        "flagged" => Some(FolderAttribute::Flagged),
+        "junk" => Some(FolderAttribute::Junk),
+        "sent" => Some(FolderAttribute::Sent),
+        "trash" => Some(FolderAttribute::Trash),
+        _ => None,
+    }
+}
+
+/// Full `/`-separated paths of mailboxes, keyed by mailbox id
+pub fn mailbox_paths(mailboxes: &[Mailbox]) -> HashMap<String, String> {
+    let by_id: HashMap<&str, &Mailbox> = mailboxes
+        .iter()
+        .map(|mailbox| (mailbox.id.as_str(), mailbox))
+        .collect();
+
+    mailboxes
+        .iter()
+        .map(|mailbox| {
+            let mut names = vec![mailbox.name.as_str()];
+            let mut parent = mailbox.parent_id.as_deref();
+            // Bounded in case a broken server reports a parent cycle
+            while let Some(parent_mailbox) = parent.and_then(|id| by_id.get(id)) {
+                if names.len() > mailboxes.len() {
+                    break;
+                }
+                names.push(parent_mailbox.name.as_str());
+                parent = parent_mailbox.parent_id.as_deref();
+            }
+            names.reverse();
+            (mailbox.id.clone(), names.join(MAILBOX_DELIMITER))
+        })
+        .collect()
+}
+
+/// Email address as JMAP reports it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmailAddress {
+    pub name: Option<String>,
+    pub email: String,
+}
+
+impl EmailAddress {
+    fn to_address(&self) -> Address {
+        let (mailbox, host) = self
+            .email
+            .rsplit_once('@')
+            .unwrap_or((self.email.as_str(), ""));
+        let address = Address::new(mailbox.to_string(), host.to_string());
+        match self.name.as_deref().filter(|name| !name.is_empty()) {
+            Some(name) => address.with_name(name.to_string()),
+            None => address,
+        }
+    }
+}
+
+/// Attachment of an email (RFC 8621 §4.1.4)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailBodyPart {
+    pub blob_id: Option<String>,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub content_type: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// JMAP email metadata (RFC 8621 §4)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Email {
+    pub id: String,
+    pub blob_id: String,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    #[serde(default)]
+    pub mailbox_ids: HashMap<String, bool>,
+    #[serde(default)]
+    pub keywords: HashMap<String, bool>,
+    #[serde(default)]
+    pub size: u32,
+    pub received_at: Option<DateTime<Utc>>,
+    pub message_id: Option<Vec<String>>,
+    pub in_reply_to: Option<Vec<String>>,
+    pub subject: Option<String>,
+    pub sent_at: Option<String>,
+    pub from: Option<Vec<EmailAddress>>,
+    pub sender: Option<Vec<EmailAddress>>,
+    pub reply_to: Option<Vec<EmailAddress>>,
+    pub to: Option<Vec<EmailAddress>>,
+    pub cc: Option<Vec<EmailAddress>>,
+    pub bcc: Option<Vec<EmailAddress>>,
+}
+
+impl Email {
+    /// Properties requested for every email
+    pub const PROPERTIES: &'static [&'static str] = &[
+        "id",
+        "blobId",
+        "threadId",
+        "mailboxIds",
+        "keywords",
+        "size",
+        "receivedAt",
+        "messageId",
+        "inReplyTo",
+        "subject",
+        "sentAt",
+        "from",
+        "sender",
+        "replyTo",
+        "to",
+        "cc",
+        "bcc",
+    ];
+
+    /// Ids of the mailboxes the email is in
+    pub fn mailboxes(&self) -> impl Iterator<Item = &str> {
+        self.mailbox_ids
+            .iter()
+            .filter(|(_, member)| **member)
+            .map(|(id, _)| id.as_str())
+    }
+
+    /// IMAP flags equivalent to the email's keywords
+    pub fn flags(&self) -> Vec<MessageFlag> {
+        self.keywords
+            .iter()
+            .filter(|(_, set)| **set)
+            .map(|(keyword, _)| keyword_to_flag(keyword))
+            .collect()
+    }
+
+    /// IMAP message for the email, stored under `uid`
+    ///
+    /// `raw` is the full RFC 5322 message when it has been downloaded.
+    pub fn to_imap_message(&self, uid: u32, raw: Option<String>) -> ImapMessage {
+        let addresses = |list: &Option<Vec<EmailAddress>>| -> Vec<Address> {
+            list.iter().flatten().map(EmailAddress::to_address).collect()
+        };
+        let bracketed = |ids: &Option<Vec<String>>| {
+            ids.as_ref()
+                .and_then(|ids| ids.first())
+                .map(|id| format!("<{}>", id))
+        };
+
+        let envelope = MessageEnvelope {
+            date: self.sent_at.clone(),
+            subject: self.subject.clone(),
+            from: addresses(&self.from),
+            sender: addresses(&self.sender),
+            reply_to: addresses(&self.reply_to),
+            to: addresses(&self.to),
+            cc: addresses(&self.cc),
+            bcc: addresses(&self.bcc),
+            in_reply_to: bracketed(&self.in_reply_to),
+            message_id: bracketed(&self.message_id),
+        };
+
+        let mut message = ImapMessage::new(uid);
+        message.uid = Some(uid);
+        message.flags = self.flags();
+        message.internal_date = self.received_at;
+        message.size = Some(self.size);
+        message.envelope = Some(envelope);
+        message.body = raw;
+        message
+    }
+}
+
+/// Result of `Email/changes` (RFC 8620 §5.2)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailChanges {
+    pub old_state: String,
+    pub new_state: String,
+    pub has_more_changes: bool,
+    #[serde(default)]
+    pub created: Vec<String>,
+    #[serde(default)]
+    pub updated: Vec<String>,
+    #[serde(default)]
+    pub destroyed: Vec<String>,
+}
+
+/// IMAP flag for a JMAP keyword
+pub fn keyword_to_flag(keyword: &str) -> MessageFlag {
+    match keyword.to_lowercase().as_str() {
+        "$seen" => MessageFlag::Seen,
+        "$flagged" => MessageFlag::Flagged,
+        "$answered" => MessageFlag::Answered,
+        "$draft" => MessageFlag::Draft,
+        _ => MessageFlag::Custom(keyword.to_string()),
+    }
+}
+
+/// JMAP keyword for an IMAP flag
+///
+/// `\Deleted` and `\Recent` have no keyword: JMAP destroys emails outright
+/// and has no notion of recent.
+pub fn flag_to_keyword(flag: &MessageFlag) -> Option<String> {
+    match flag {
+        MessageFlag::Seen => Some("$seen".to_string()),
+        MessageFlag::Flagged => Some("$flagged".to_string()),
+        MessageFlag::Answered => Some("$answered".to_string()),
+        MessageFlag::Draft => Some("$draft".to_string()),
+        MessageFlag::Deleted | MessageFlag::Recent => None,
+        MessageFlag::Custom(keyword) => Some(keyword.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mailbox(id: &str, name: &str, parent_id: Option<&str>, role: Option<&str>) -> Mailbox {
+        Mailbox {
+            id: id.to_string(),
+            name: name.to_string(),
+            parent_id: parent_id.map(str::to_string),
+            role: role.map(str::to_string),
+            sort_order: 0,
+            total_emails: 3,
+            unread_emails: 1,
+        }
+    }
+
+    #[test]
+    fn test_mailbox_paths_follow_parents() {
+        let mailboxes = vec![
+            mailbox("a", "Projects", None, None),
+            mailbox("b", "2026", Some("a"), None),
+            mailbox("c", "Q4", Some("b"), None),
+        ];
+        let paths = mailbox_paths(&mailboxes);
+
+        assert_eq!(paths["a"], "Projects");
+        assert_eq!(paths["c"], "Projects/2026/Q4");
+    }
+
+    #[test]
+    fn test_mailbox_roles_become_folder_attributes() {
+        let inbox = mailbox("i", "Inbox", None, Some("inbox")).to_imap_folder("Inbox", false);
+        assert_eq!(inbox.name, "INBOX");
+        assert!(inbox.is_inbox());
+        assert_eq!(inbox.exists, Some(3));
+        assert_eq!(inbox.unseen, Some(1));
+
+        let trash = mailbox("t", "Trash", None, Some("trash")).to_imap_folder("Trash", true);
+        assert!(trash.attributes.contains(&FolderAttribute::Trash));
+        assert!(trash.has_children());
+    }
+
+    #[test]
+    fn test_keyword_flag_mapping() {
+        assert_eq!(keyword_to_flag("$seen"), MessageFlag::Seen);
+        assert_eq!(keyword_to_flag("$Flagged"), MessageFlag::Flagged);
+        assert_eq!(
+            keyword_to_flag("$label1"),
+            MessageFlag::Custom("$label1".to_string())
+        );
+        assert_eq!(flag_to_keyword(&MessageFlag::Answered).as_deref(), Some("$answered"));
+        assert_eq!(flag_to_keyword(&MessageFlag::Deleted), None);
+    }
+
+    #[test]
+    fn test_email_converts_to_imap_message() {
+        let email: Email = serde_json::from_value(serde_json::json!({
+            "id": "M1",
+            "blobId": "B1",
+            "threadId": "T1",
+            "mailboxIds": { "inbox": true },
+            "keywords": { "$seen": true, "$flagged": true },
+            "size": 2048,
+            "receivedAt": "2026-10-01T09:30:00Z",
+            "messageId": ["abc@example.com"],
+            "inReplyTo": null,
+            "subject": "Quarterly report",
+            "sentAt": "2026-10-01T11:30:00+02:00",
+            "from": [{ "name": "Ada", "email": "ada@example.com" }],
+            "to": [{ "name": null, "email": "me@example.org" }]
+        }))
+        .unwrap();
+
+        let message = email.to_imap_message(42, None);
+        assert_eq!(message.uid, Some(42));
+        assert!(message.is_seen());
+        assert!(message.is_flagged());
+        assert_eq!(message.size, Some(2048));
+
+        let envelope = message.envelope.unwrap();
+        assert_eq!(envelope.subject.as_deref(), Some("Quarterly report"));
+        assert_eq!(envelope.message_id.as_deref(), Some("<abc@example.com>"));
+        assert_eq!(envelope.from[0].email_address().as_deref(), Some("ada@example.com"));
+        assert_eq!(envelope.from[0].name.as_deref(), Some("Ada"));
+        assert_eq!(envelope.to[0].name, None);
+        assert_eq!(email.mailboxes().collect::<Vec<_>>(), vec!["inbox"]);
+    }
+
+    #[test]
+    fn test_session_download_url_template() {
+        let session: JmapSession = serde_json::from_value(serde_json::json!({
+            "capabilities": {
+                "urn:ietf:params:jmap:core": { "maxObjectsInGet": 500 },
+                "urn:ietf:params:jmap:mail": {}
+            },
+            "accounts": {},
+            "primaryAccounts": { "urn:ietf:params:jmap:mail": "u123" },
+            "username": "me@fastmail.com",
+            "apiUrl": "https://api.fastmail.com/jmap/api/",
+            "downloadUrl": "https://www.fastmailusercontent.com/jmap/download/{accountId}/{blobId}/{name}?type={type}",
+            "uploadUrl": "https://api.fastmail.com/jmap/upload/{accountId}/",
+            "eventSourceUrl": "https://api.fastmail.com/jmap/event/",
+            "state": "s1"
+        }))
+        .unwrap();
+
+        assert_eq!(session.mail_account_id(), Some("u123"));
+        assert_eq!(session.max_objects_in_get(), 500);
+        assert_eq!(session.max_objects_in_set(), 100);
+        assert_eq!(
+            session.blob_download_url("u123", "B 1", "message/rfc822", "message.eml"),
+            "https://www.fastmailusercontent.com/jmap/download/u123/B%201/message.eml?type=message%2Frfc822"
+        );
+    }
+}
@@ -153,6 +153,65 @@ mod keymodifiers_serde {
     }
 }
 
+/// Serde serialization module for the shortcut map
+///
+/// TOML only allows string keys, so shortcuts are stored as a list of
+/// bindings, sorted to keep the file stable between saves.
+mod shortcut_map_serde {
+    use super::{KeyboardAction, KeyboardShortcut};
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    #[derive(Serialize, Deserialize)]
+    struct Binding {
+        #[serde(with = "super::keycode_serde")]
+        key: KeyCode,
+        #[serde(with = "super::keymodifiers_serde")]
+        modifiers: KeyModifiers,
+        action: KeyboardAction,
+    }
+
+    pub fn serialize<S>(
+        shortcuts: &HashMap<KeyboardShortcut, KeyboardAction>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut bindings: Vec<Binding> = shortcuts
+            .iter()
+            .map(|(shortcut, action)| Binding {
+                key: shortcut.key,
+                modifiers: shortcut.modifiers,
+                action: action.clone(),
+            })
+            .collect();
+        bindings.sort_by_cached_key(|binding| {
+            KeyboardShortcut::new(binding.key, binding.modifiers).to_string()
+        });
+        bindings.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<HashMap<KeyboardShortcut, KeyboardAction>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bindings = Vec::<Binding>::deserialize(deserializer)?;
+        Ok(bindings
+            .into_iter()
+            .map(|binding| {
+                (
+                    KeyboardShortcut::new(binding.key, binding.modifiers),
+                    binding.action,
+                )
+            })
+            .collect())
+    }
+}
+
 impl KeyboardShortcut {
     /// Create a new keyboard shortcut with the specified key and modifiers
     pub fn new(key: KeyCode, modifiers: KeyModifiers) -> Self {
@@ -243,6 +302,8 @@ pub enum KeyboardAction {
     MoveDown,
     MoveLeft,
     MoveRight,
+    GoToInbox,
+    GoToStarred,
 
     // Selection and interaction
     Select,
@@ -254,17 +315,39 @@ pub enum KeyboardAction {
     ShowDraftList,
     ReplyEmail,
     ReplyAllEmail,
+    ReplyToList,
     ForwardEmail,
     DeleteEmail,
     ArchiveEmail,
+    BlockSender,
+    SnoozeMessage,
+    NextInboxTab,
+    PreviousInboxTab,
+    MoveToPrimaryTab,
+    MoveToPromotionsTab,
+    MoveToSocialTab,
+    MoveToUpdatesTab,
+    MoveToForumsTab,
+    ToggleMessageMark,
+    ReportSpam,
+    ReportNotSpam,
     MarkAsRead,
     MarkAsUnread,
+    MarkThreadRead,
+    MarkThreadUnread,
+    MarkAllRead,
+    UndoMarkAllRead,
 
     // Account management
     AddAccount,
     RemoveAccount,
     RefreshAccount,
     SwitchAccount,
+    ShowAccountDetails,
+    ShowSpecialFolders,
+    ShowRetention,
+    ShowFilterRules,
+    ShowMboxWizard,
 
     // Search and filter
     StartSearch,
@@ -277,18 +360,32 @@ pub enum KeyboardAction {
     CollapseThread,
     ToggleViewMode,
     ToggleHeaders,
+    RenderFullMessage,
+    OpenInBrowser,
+    ShowHeadersPanel,
+    ShowLinks,
     OpenEmailViewer,
+    ToggleTodayPanel,
+    ToggleUnifiedView,
+    ToggleLargestMessages,
+    ToggleReflow,
+    LockScreen,
 
     // Sorting
     SortByDate,
     SortBySender,
     SortBySubject,
+    SortBySize,
+    SortByPriority,
+    CycleSortField,
+    ToggleSortOrder,
 
     // Content preview
     ScrollToTop,
     ScrollToBottom,
     SelectFirstAttachment,
     SaveAttachment,
+    SaveAllAttachments,
     ViewAttachment,
     OpenAttachmentWithSystem,
 
@@ -305,6 +402,7 @@ pub enum KeyboardAction {
     FolderRefresh,
     FolderRename,
     FolderDelete,
+    FolderMenu,
 
     // Message navigation
     NextMessage,
@@ -329,6 +427,8 @@ pub enum KeyboardAction {
     EditEvent,
     DeleteEvent,
     ViewEventDetails,
+    NudgeAttendees,
+    SnoozeReminder,
     CreateTodo,
     ToggleTodoComplete,
     ViewTodos,
@@ -351,28 +451,148 @@ pub enum KeyboardAction {
     AddSenderToContacts,
     RemoveSenderFromContacts,
     ContactQuickActions,
+    ShowSms,
+    ReplyToSms,
     
     // AI Assistant actions
     AIToggleAssistant,      // Toggle AI assistant panel
     AIEmailSuggestions,     // Show AI email suggestions
     AIComposeSuggestions,   // Show AI compose suggestions
     AISummarizeEmail,       // Summarize current email
+    AISummarizeThread,      // Summarize the current email's whole thread
     AICalendarAssist,       // AI calendar assistance
     AIConfigureSettings,    // Open AI configuration
     AIQuickReply,          // Generate quick reply suggestions
+    AIDraftReply,          // Draft a whole reply and open it in compose
     AIEmailAnalysis,       // Analyze email content
     AIScheduleRequest,     // Parse schedule request with AI
     AIContentGeneration,   // Generate email content
+    AIDiagnostics,         // Show AI cache statistics, where the cache can be flushed
     
     // Settings and configuration
     OpenSettings,          // Open application settings
 }
 
+/// A named set of bindings applied on top of the configured shortcuts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardPreset {
+    /// The configured shortcuts as they are
+    #[default]
+    Default,
+    /// Gmail's keys: `e` archive, `#` delete, `r`/`a`/`f` reply, `gi`/`gs` go to folders
+    Gmail,
+}
+
+/// Preset choice as stored in `keyboard_preset.toml`
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct KeyboardPresetFile {
+    preset: KeyboardPreset,
+}
+
+impl KeyboardPreset {
+    pub const ALL: [KeyboardPreset; 2] = [KeyboardPreset::Default, KeyboardPreset::Gmail];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyboardPreset::Default => "default",
+            KeyboardPreset::Gmail => "gmail",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    fn config_file_path() -> Result<PathBuf> {
+        Ok(KeyboardConfig::get_config_dir()?.join("keyboard_preset.toml"))
+    }
+
+    /// The chosen preset; `Default` when none has been chosen
+    pub fn load() -> Result<Self> {
+        let path = Self::config_file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let file: KeyboardPresetFile = toml::from_str(&fs::read_to_string(path)?)?;
+        Ok(file.preset)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let content = toml::to_string_pretty(&KeyboardPresetFile { preset: *self })?;
+        fs::write(Self::config_file_path()?, content)?;
+        Ok(())
+    }
+
+    /// Keys the preset binds over the configured shortcuts
+    pub fn shortcuts(&self) -> HashMap<KeyboardShortcut, KeyboardAction> {
+        match self {
+            KeyboardPreset::Default => HashMap::new(),
+            KeyboardPreset::Gmail => {
+                let keys = [
+                    ('j', KeyboardAction::VimMoveDown),
+                    ('k', KeyboardAction::VimMoveUp),
+                    ('o', KeyboardAction::Select),
+                    ('e', KeyboardAction::ArchiveEmail),
+                    ('#', KeyboardAction::DeleteEmail),
+                    ('!', KeyboardAction::ReportSpam),
+                    ('r', KeyboardAction::ReplyEmail),
+                    ('a', KeyboardAction::ReplyAllEmail),
+                    ('f', KeyboardAction::ForwardEmail),
+                    ('c', KeyboardAction::ComposeEmail),
+                    ('/', KeyboardAction::StartSearch),
+                    ('x', KeyboardAction::ToggleMessageMark),
+                ];
+                let mut shortcuts: HashMap<_, _> = keys
+                    .into_iter()
+                    .map(|(key, action)| (KeyboardShortcut::simple(KeyCode::Char(key)), action))
+                    .collect();
+                // `e` now archives; new events move to Shift+E
+                shortcuts.insert(
+                    KeyboardShortcut::shift(KeyCode::Char('E')),
+                    KeyboardAction::CreateEvent,
+                );
+                shortcuts
+            }
+        }
+    }
+
+    /// Two-key sequences the preset adds
+    pub fn sequences(&self) -> HashMap<String, KeyboardAction> {
+        match self {
+            KeyboardPreset::Default => HashMap::new(),
+            KeyboardPreset::Gmail => HashMap::from([
+                ("gi".to_string(), KeyboardAction::GoToInbox),
+                ("gs".to_string(), KeyboardAction::GoToStarred),
+            ]),
+        }
+    }
+
+    /// Action the preset gives a key in the email viewer, taking it over
+    /// from the viewer's own single-letter keys
+    pub fn viewer_action(&self, key: KeyCode) -> Option<KeyboardAction> {
+        match (self, key) {
+            (KeyboardPreset::Gmail, KeyCode::Char('e')) => Some(KeyboardAction::EmailViewerArchive),
+            (KeyboardPreset::Gmail, KeyCode::Char('#')) => Some(KeyboardAction::EmailViewerDelete),
+            (KeyboardPreset::Gmail, KeyCode::Char('a')) => Some(KeyboardAction::EmailViewerReplyAll),
+            (KeyboardPreset::Gmail, KeyCode::Char('c')) => Some(KeyboardAction::ComposeEmail),
+            _ => None,
+        }
+    }
+}
+
 /// Configuration for keyboard shortcuts
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyboardConfig {
     /// Mapping of shortcuts to actions
+    #[serde(with = "shortcut_map_serde")]
     shortcuts: HashMap<KeyboardShortcut, KeyboardAction>,
+    /// Two-key sequences such as `gi`, typed without modifiers
+    #[serde(default)]
+    sequences: HashMap<String, KeyboardAction>,
     /// Description of each action for help display
     action_descriptions: HashMap<KeyboardAction, String>,
 }
@@ -381,6 +601,7 @@ impl Default for KeyboardConfig {
     fn default() -> Self {
         let mut config = KeyboardConfig {
             shortcuts: HashMap::new(),
+            sequences: HashMap::new(),
             action_descriptions: HashMap::new(),
         };
 
@@ -493,6 +714,10 @@ impl KeyboardConfig {
             KeyboardShortcut::shift(KeyCode::Char('R')),
             KeyboardAction::ReplyAllEmail,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::shift(KeyCode::Char('L')),
+            KeyboardAction::ReplyToList,
+        );
         self.shortcuts.insert(
             KeyboardShortcut::ctrl(KeyCode::Char('f')),
             KeyboardAction::ForwardEmail,
@@ -505,6 +730,54 @@ impl KeyboardConfig {
             KeyboardShortcut::shift(KeyCode::Char('A')),
             KeyboardAction::ArchiveEmail,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::shift(KeyCode::Char('B')),
+            KeyboardAction::BlockSender,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::simple(KeyCode::Char('b')),
+            KeyboardAction::SnoozeMessage,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::simple(KeyCode::Char(']')),
+            KeyboardAction::NextInboxTab,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::simple(KeyCode::Char('[')),
+            KeyboardAction::PreviousInboxTab,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('1')),
+            KeyboardAction::MoveToPrimaryTab,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('2')),
+            KeyboardAction::MoveToPromotionsTab,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('3')),
+            KeyboardAction::MoveToSocialTab,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('4')),
+            KeyboardAction::MoveToUpdatesTab,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('5')),
+            KeyboardAction::MoveToForumsTab,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::simple(KeyCode::Char('x')),
+            KeyboardAction::ToggleMessageMark,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::simple(KeyCode::Char('!')),
+            KeyboardAction::ReportSpam,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::shift(KeyCode::Char('N')),
+            KeyboardAction::ReportNotSpam,
+        );
         self.shortcuts.insert(
             KeyboardShortcut::shift(KeyCode::Char('U')),
             KeyboardAction::MarkAsUnread,
@@ -513,6 +786,22 @@ impl KeyboardConfig {
             KeyboardShortcut::shift(KeyCode::Char('M')), 
             KeyboardAction::MarkAsRead,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::new(KeyCode::Char('M'), KeyModifiers::ALT | KeyModifiers::SHIFT),
+            KeyboardAction::MarkThreadRead,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::new(KeyCode::Char('U'), KeyModifiers::ALT | KeyModifiers::SHIFT),
+            KeyboardAction::MarkThreadUnread,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::new(KeyCode::Char('R'), KeyModifiers::ALT | KeyModifiers::SHIFT),
+            KeyboardAction::MarkAllRead,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::ctrl(KeyCode::Char('z')),
+            KeyboardAction::UndoMarkAllRead,
+        );
         
         // Message navigation
         self.shortcuts.insert(
@@ -541,6 +830,26 @@ impl KeyboardConfig {
             KeyboardShortcut::ctrl(KeyCode::Char('s')),
             KeyboardAction::SwitchAccount,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('i')),
+            KeyboardAction::ShowAccountDetails,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('s')),
+            KeyboardAction::ShowSpecialFolders,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('d')),
+            KeyboardAction::ShowRetention,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('e')),
+            KeyboardAction::ShowFilterRules,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('x')),
+            KeyboardAction::ShowMboxWizard,
+        );
 
         // Search
         self.shortcuts.insert(
@@ -577,10 +886,46 @@ impl KeyboardConfig {
             KeyboardShortcut::simple(KeyCode::Char('H')),
             KeyboardAction::ToggleHeaders,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::shift(KeyCode::Char('F')),
+            KeyboardAction::RenderFullMessage,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('o')),
+            KeyboardAction::OpenInBrowser,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('h')),
+            KeyboardAction::ShowHeadersPanel,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('u')),
+            KeyboardAction::ShowLinks,
+        );
         self.shortcuts.insert(
             KeyboardShortcut::simple(KeyCode::Char('V')),
             KeyboardAction::OpenEmailViewer,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('t')),
+            KeyboardAction::ToggleTodayPanel,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::shift(KeyCode::Char('I')),
+            KeyboardAction::ToggleUnifiedView,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::shift(KeyCode::Char('Z')),
+            KeyboardAction::ToggleLargestMessages,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('w')),
+            KeyboardAction::ToggleReflow,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('l')),
+            KeyboardAction::LockScreen,
+        );
 
         // Sorting
         self.shortcuts.insert(
@@ -595,6 +940,22 @@ impl KeyboardConfig {
             KeyboardShortcut::simple(KeyCode::Char('u')),
             KeyboardAction::SortBySubject,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::simple(KeyCode::Char('z')),
+            KeyboardAction::SortBySize,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::shift(KeyCode::Char('P')),
+            KeyboardAction::SortByPriority,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::simple(KeyCode::Char('>')),
+            KeyboardAction::CycleSortField,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::simple(KeyCode::Char('<')),
+            KeyboardAction::ToggleSortOrder,
+        );
 
         // Content preview
         self.shortcuts.insert(
@@ -617,6 +978,10 @@ impl KeyboardConfig {
             KeyboardShortcut::simple(KeyCode::Char('S')),
             KeyboardAction::SaveAttachment,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::simple(KeyCode::Char('W')),
+            KeyboardAction::SaveAllAttachments,
+        );
         self.shortcuts.insert(
             KeyboardShortcut::simple(KeyCode::Char('O')),
             KeyboardAction::OpenAttachmentWithSystem,
@@ -659,6 +1024,10 @@ impl KeyboardConfig {
             KeyboardShortcut::simple(KeyCode::Delete),
             KeyboardAction::FolderDelete,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('f')),
+            KeyboardAction::FolderMenu,
+        );
 
         // Email viewer shortcuts - context-sensitive shortcuts for email viewer mode
         self.shortcuts.insert(
@@ -728,6 +1097,14 @@ impl KeyboardConfig {
         );
         // ViewEventDetails should use a different key (Enter conflicts with Select)
         // Use Space or another key for viewing event details in calendar context
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('m')),
+            KeyboardAction::NudgeAttendees,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::ctrl(KeyCode::Char('b')),
+            KeyboardAction::SnoozeReminder,
+        );
         self.shortcuts.insert(
             KeyboardShortcut::simple(KeyCode::Char('T')),
             KeyboardAction::CreateTodo,
@@ -796,6 +1173,14 @@ impl KeyboardConfig {
             KeyboardShortcut::shift(KeyCode::Char('C')),
             KeyboardAction::ContactQuickActions,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::alt(KeyCode::Char('p')),
+            KeyboardAction::ShowSms,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::new(KeyCode::Char('P'), KeyModifiers::ALT | KeyModifiers::SHIFT),
+            KeyboardAction::ReplyToSms,
+        );
 
         // AI Assistant shortcuts
         self.shortcuts.insert(
@@ -814,6 +1199,20 @@ impl KeyboardConfig {
             KeyboardShortcut::new(KeyCode::Char('u'), KeyModifiers::CONTROL | KeyModifiers::ALT),
             KeyboardAction::AISummarizeEmail,
         );
+        // Terminals without the kitty keyboard protocol send Ctrl+Shift+S as
+        // Ctrl+S, so Ctrl+Alt+H is bound as well
+        self.shortcuts.insert(
+            KeyboardShortcut::new(KeyCode::Char('s'), KeyModifiers::CONTROL | KeyModifiers::SHIFT),
+            KeyboardAction::AISummarizeThread,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::new(KeyCode::Char('S'), KeyModifiers::CONTROL | KeyModifiers::SHIFT),
+            KeyboardAction::AISummarizeThread,
+        );
+        self.shortcuts.insert(
+            KeyboardShortcut::new(KeyCode::Char('h'), KeyModifiers::CONTROL | KeyModifiers::ALT),
+            KeyboardAction::AISummarizeThread,
+        );
         self.shortcuts.insert(
             KeyboardShortcut::new(KeyCode::Char('l'), KeyModifiers::CONTROL | KeyModifiers::ALT),
             KeyboardAction::AICalendarAssist,
@@ -826,6 +1225,10 @@ impl KeyboardConfig {
             KeyboardShortcut::new(KeyCode::Char('r'), KeyModifiers::CONTROL | KeyModifiers::ALT),
             KeyboardAction::AIQuickReply,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::new(KeyCode::Char('d'), KeyModifiers::CONTROL | KeyModifiers::ALT),
+            KeyboardAction::AIDraftReply,
+        );
         self.shortcuts.insert(
             KeyboardShortcut::new(KeyCode::Char('a'), KeyModifiers::CONTROL | KeyModifiers::ALT),
             KeyboardAction::AIEmailAnalysis,
@@ -838,6 +1241,10 @@ impl KeyboardConfig {
             KeyboardShortcut::new(KeyCode::Char('e'), KeyModifiers::CONTROL | KeyModifiers::ALT),
             KeyboardAction::AIContentGeneration,
         );
+        self.shortcuts.insert(
+            KeyboardShortcut::new(KeyCode::Char('k'), KeyModifiers::CONTROL | KeyModifiers::ALT),
+            KeyboardAction::AIDiagnostics,
+        );
     }
 
     /// Set up descriptions for each action
@@ -869,6 +1276,12 @@ impl KeyboardConfig {
             KeyboardAction::VimMoveDown,
             "Move down (vim-style)".to_string(),
         );
+        self.action_descriptions
+            .insert(KeyboardAction::GoToInbox, "Go to the inbox".to_string());
+        self.action_descriptions.insert(
+            KeyboardAction::GoToStarred,
+            "Go to the Starred folder".to_string(),
+        );
 
         self.action_descriptions
             .insert(KeyboardAction::Select, "Select current item".to_string());
@@ -895,6 +1308,10 @@ impl KeyboardConfig {
             KeyboardAction::ReplyAllEmail,
             "Reply to all recipients".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::ReplyToList,
+            "Reply to the mailing list".to_string(),
+        );
         self.action_descriptions.insert(
             KeyboardAction::ForwardEmail,
             "Forward selected message".to_string(),
@@ -907,6 +1324,54 @@ impl KeyboardConfig {
             KeyboardAction::ArchiveEmail,
             "Archive selected message".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::BlockSender,
+            "Block sender of selected message".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::SnoozeMessage,
+            "Snooze selected message until a chosen time".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::NextInboxTab,
+            "Show the next INBOX tab".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::PreviousInboxTab,
+            "Show the previous INBOX tab".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::MoveToPrimaryTab,
+            "Move sender's mail to the Primary tab".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::MoveToPromotionsTab,
+            "Move sender's mail to the Promotions tab".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::MoveToSocialTab,
+            "Move sender's mail to the Social tab".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::MoveToUpdatesTab,
+            "Move sender's mail to the Updates tab".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::MoveToForumsTab,
+            "Move sender's mail to the Forums tab".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ToggleMessageMark,
+            "Mark message for batch operations".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ReportSpam,
+            "Report selected or marked messages as spam".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ReportNotSpam,
+            "Report selected or marked messages as not spam".to_string(),
+        );
         self.action_descriptions.insert(
             KeyboardAction::MarkAsRead,
             "Mark message as read".to_string(),
@@ -915,6 +1380,22 @@ impl KeyboardConfig {
             KeyboardAction::MarkAsUnread,
             "Mark message as unread".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::MarkThreadRead,
+            "Mark every message in the thread as read".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::MarkThreadUnread,
+            "Mark every message in the thread as unread".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::MarkAllRead,
+            "Mark every message in the folder, or the account, as read".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::UndoMarkAllRead,
+            "Cancel or undo the last mark-all-read".to_string(),
+        );
         self.action_descriptions.insert(
             KeyboardAction::NextMessage,
             "Navigate to next message".to_string(),
@@ -938,6 +1419,26 @@ impl KeyboardConfig {
             KeyboardAction::SwitchAccount,
             "Switch to next account".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::ShowAccountDetails,
+            "Show server capabilities and TLS details".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ShowSpecialFolders,
+            "Map Sent, Drafts, Trash, Junk and Archive folders".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ShowRetention,
+            "Review retention preview and restore pending removals".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ShowFilterRules,
+            "Test filter rules on the current folder and enable them".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ShowMboxWizard,
+            "Import mbox files or export folders to mbox".to_string(),
+        );
 
         self.action_descriptions.insert(
             KeyboardAction::StartSearch,
@@ -972,10 +1473,46 @@ impl KeyboardConfig {
             KeyboardAction::ToggleHeaders,
             "Toggle extended headers".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::RenderFullMessage,
+            "Render a truncated message in full".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::OpenInBrowser,
+            "Open message HTML in web browser".to_string(),
+        );
         self.action_descriptions.insert(
             KeyboardAction::OpenEmailViewer,
             "Open email in full-screen viewer".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::ToggleTodayPanel,
+            "Toggle today panel (agenda, unread, weather)".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ShowHeadersPanel,
+            "Show all headers, the Received chain and authentication results".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ShowLinks,
+            "List the links in the message to open or copy".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ToggleUnifiedView,
+            "Toggle unified view of all folders".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ToggleLargestMessages,
+            "Show the largest messages of the folder or account".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ToggleReflow,
+            "Switch plain text between as sent and reflowed".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::LockScreen,
+            "Lock the screen until the master passphrase is entered".to_string(),
+        );
 
         self.action_descriptions.insert(
             KeyboardAction::SortByDate,
@@ -989,6 +1526,22 @@ impl KeyboardConfig {
             KeyboardAction::SortBySubject,
             "Sort messages by subject".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::SortBySize,
+            "Sort messages by size, largest first".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::SortByPriority,
+            "Sort messages by priority, high first".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::CycleSortField,
+            "Sort messages by the next field".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ToggleSortOrder,
+            "Reverse the sort order of the message list".to_string(),
+        );
 
         self.action_descriptions
             .insert(KeyboardAction::ScrollToTop, "Scroll to top".to_string());
@@ -1008,6 +1561,10 @@ impl KeyboardConfig {
             KeyboardAction::SaveAttachment,
             "Save selected attachment".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::SaveAllAttachments,
+            "Save all attachments".to_string(),
+        );
         self.action_descriptions.insert(
             KeyboardAction::OpenAttachmentWithSystem,
             "Open attachment with system app".to_string(),
@@ -1041,6 +1598,8 @@ impl KeyboardConfig {
             .insert(KeyboardAction::FolderRename, "Rename folder".to_string());
         self.action_descriptions
             .insert(KeyboardAction::FolderDelete, "Delete (Del)".to_string());
+        self.action_descriptions
+            .insert(KeyboardAction::FolderMenu, "Folder actions menu".to_string());
 
         self.action_descriptions.insert(
             KeyboardAction::NextAttachment,
@@ -1076,6 +1635,14 @@ impl KeyboardConfig {
             KeyboardAction::ViewEventDetails,
             "View event details".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::NudgeAttendees,
+            "Email a reminder to attendees who haven't responded".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::SnoozeReminder,
+            "Snooze the last event reminder".to_string(),
+        );
         self.action_descriptions.insert(
             KeyboardAction::CreateTodo,
             "Create new todo/task".to_string(),
@@ -1142,6 +1709,14 @@ impl KeyboardConfig {
             KeyboardAction::ContactQuickActions,
             "Show contact quick actions menu".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::ShowSms,
+            "Show SMS conversations from the paired phone".to_string(),
+        );
+        self.action_descriptions.insert(
+            KeyboardAction::ReplyToSms,
+            "Reply to the last SMS notification".to_string(),
+        );
 
         // Email Viewer action descriptions
         self.action_descriptions.insert(
@@ -1198,6 +1773,10 @@ impl KeyboardConfig {
             KeyboardAction::AISummarizeEmail,
             "Summarize current email with AI".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::AISummarizeThread,
+            "Summarize the whole thread with AI".to_string(),
+        );
         self.action_descriptions.insert(
             KeyboardAction::AICalendarAssist,
             "AI calendar assistance".to_string(),
@@ -1210,6 +1789,10 @@ impl KeyboardConfig {
             KeyboardAction::AIQuickReply,
             "Generate quick reply suggestions".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::AIDraftReply,
+            "Draft a reply with AI".to_string(),
+        );
         self.action_descriptions.insert(
             KeyboardAction::AIEmailAnalysis,
             "Analyze email content with AI".to_string(),
@@ -1222,6 +1805,10 @@ impl KeyboardConfig {
             KeyboardAction::AIContentGeneration,
             "Generate email content with AI".to_string(),
         );
+        self.action_descriptions.insert(
+            KeyboardAction::AIDiagnostics,
+            "Show AI cache statistics".to_string(),
+        );
     }
 
     /// Get the action for a given keyboard shortcut
@@ -1229,6 +1816,16 @@ impl KeyboardConfig {
         self.shortcuts.get(shortcut)
     }
 
+    /// Get the action for a two-key sequence
+    pub fn get_sequence_action(&self, first: char, second: char) -> Option<&KeyboardAction> {
+        self.sequences.get(&format!("{}{}", first, second))
+    }
+
+    /// Whether a key starts a two-key sequence
+    pub fn is_sequence_prefix(&self, key: char) -> bool {
+        self.sequences.keys().any(|sequence| sequence.starts_with(key))
+    }
+
     /// Get the shortcut(s) for a given action
     pub fn get_shortcuts_for_action(&self, action: &KeyboardAction) -> Vec<&KeyboardShortcut> {
         self.shortcuts
@@ -1293,7 +1890,9 @@ impl KeyboardConfig {
             | KeyboardAction::MoveUp
             | KeyboardAction::MoveDown
             | KeyboardAction::MoveLeft
-            | KeyboardAction::MoveRight => "Navigation".to_string(),
+            | KeyboardAction::MoveRight
+            | KeyboardAction::GoToInbox
+            | KeyboardAction::GoToStarred => "Navigation".to_string(),
             KeyboardAction::Select | KeyboardAction::Escape | KeyboardAction::ToggleExpanded => {
                 "Selection".to_string()
             }
@@ -1301,17 +1900,39 @@ impl KeyboardConfig {
             | KeyboardAction::ShowDraftList
             | KeyboardAction::ReplyEmail
             | KeyboardAction::ReplyAllEmail
+            | KeyboardAction::ReplyToList
             | KeyboardAction::ForwardEmail
             | KeyboardAction::DeleteEmail
             | KeyboardAction::ArchiveEmail
+            | KeyboardAction::BlockSender
+            | KeyboardAction::SnoozeMessage
+            | KeyboardAction::NextInboxTab
+            | KeyboardAction::PreviousInboxTab
+            | KeyboardAction::MoveToPrimaryTab
+            | KeyboardAction::MoveToPromotionsTab
+            | KeyboardAction::MoveToSocialTab
+            | KeyboardAction::MoveToUpdatesTab
+            | KeyboardAction::MoveToForumsTab
+            | KeyboardAction::ToggleMessageMark
+            | KeyboardAction::ReportSpam
+            | KeyboardAction::ReportNotSpam
             | KeyboardAction::MarkAsRead
             | KeyboardAction::MarkAsUnread
+            | KeyboardAction::MarkThreadRead
+            | KeyboardAction::MarkThreadUnread
+            | KeyboardAction::MarkAllRead
+            | KeyboardAction::UndoMarkAllRead
             | KeyboardAction::NextMessage
             | KeyboardAction::PreviousMessage => "Email".to_string(),
             KeyboardAction::AddAccount
             | KeyboardAction::RemoveAccount
             | KeyboardAction::RefreshAccount
-            | KeyboardAction::SwitchAccount => "Account Management".to_string(),
+            | KeyboardAction::SwitchAccount
+            | KeyboardAction::ShowAccountDetails
+            | KeyboardAction::ShowSpecialFolders
+            | KeyboardAction::ShowRetention
+            | KeyboardAction::ShowFilterRules
+            | KeyboardAction::ShowMboxWizard => "Account Management".to_string(),
             KeyboardAction::StartSearch
             | KeyboardAction::StartFolderSearch
             | KeyboardAction::EndSearch => "Search".to_string(),
@@ -1320,14 +1941,28 @@ impl KeyboardConfig {
             | KeyboardAction::CollapseThread
             | KeyboardAction::ToggleViewMode
             | KeyboardAction::ToggleHeaders
-            | KeyboardAction::OpenEmailViewer => "View Controls".to_string(),
+            | KeyboardAction::RenderFullMessage
+            | KeyboardAction::OpenInBrowser
+            | KeyboardAction::ShowHeadersPanel
+            | KeyboardAction::ShowLinks
+            | KeyboardAction::OpenEmailViewer
+            | KeyboardAction::ToggleTodayPanel
+            | KeyboardAction::ToggleUnifiedView
+            | KeyboardAction::ToggleLargestMessages
+            | KeyboardAction::ToggleReflow
+            | KeyboardAction::LockScreen => "View Controls".to_string(),
             KeyboardAction::SortByDate
             | KeyboardAction::SortBySender
-            | KeyboardAction::SortBySubject => "Sorting".to_string(),
+            | KeyboardAction::SortBySubject
+            | KeyboardAction::SortBySize
+            | KeyboardAction::SortByPriority
+            | KeyboardAction::CycleSortField
+            | KeyboardAction::ToggleSortOrder => "Sorting".to_string(),
             KeyboardAction::ScrollToTop
             | KeyboardAction::ScrollToBottom
             | KeyboardAction::SelectFirstAttachment
             | KeyboardAction::SaveAttachment
+            | KeyboardAction::SaveAllAttachments
             | KeyboardAction::ViewAttachment
             | KeyboardAction::OpenAttachmentWithSystem
             | KeyboardAction::NextAttachment
@@ -1337,7 +1972,8 @@ impl KeyboardConfig {
             | KeyboardAction::RefreshFolder
             | KeyboardAction::FolderRefresh
             | KeyboardAction::FolderRename
-            | KeyboardAction::FolderDelete => "Folder Operations".to_string(),
+            | KeyboardAction::FolderDelete
+            | KeyboardAction::FolderMenu => "Folder Operations".to_string(),
             KeyboardAction::CopyEmailContent | KeyboardAction::CopyAttachmentInfo => {
                 "Copy Operations".to_string()
             }
@@ -1356,6 +1992,8 @@ impl KeyboardConfig {
             | KeyboardAction::EditEvent
             | KeyboardAction::DeleteEvent
             | KeyboardAction::ViewEventDetails
+            | KeyboardAction::NudgeAttendees
+            | KeyboardAction::SnoozeReminder
             | KeyboardAction::CreateTodo
             | KeyboardAction::ToggleTodoComplete
             | KeyboardAction::ViewTodos
@@ -1371,18 +2009,23 @@ impl KeyboardConfig {
             | KeyboardAction::EditSenderContact
             | KeyboardAction::AddSenderToContacts
             | KeyboardAction::RemoveSenderFromContacts
-            | KeyboardAction::ContactQuickActions => "Contacts".to_string(),
+            | KeyboardAction::ContactQuickActions
+            | KeyboardAction::ShowSms
+            | KeyboardAction::ReplyToSms => "Contacts".to_string(),
             
             KeyboardAction::AIToggleAssistant
             | KeyboardAction::AIEmailSuggestions
             | KeyboardAction::AIComposeSuggestions
             | KeyboardAction::AISummarizeEmail
+            | KeyboardAction::AISummarizeThread
             | KeyboardAction::AICalendarAssist
             | KeyboardAction::AIConfigureSettings
             | KeyboardAction::AIQuickReply
+            | KeyboardAction::AIDraftReply
             | KeyboardAction::AIEmailAnalysis
             | KeyboardAction::AIScheduleRequest
-            | KeyboardAction::AIContentGeneration => "AI Assistant".to_string(),
+            | KeyboardAction::AIContentGeneration
+            | KeyboardAction::AIDiagnostics => "AI Assistant".to_string(),
         }
     }
 
@@ -1489,20 +2132,54 @@ impl KeyboardConfig {
 }
 
 /// Manager for keyboard shortcuts and configuration
+///
+/// The preset's bindings are kept apart from the configuration, so they
+/// take precedence over it but are never written to `keyboard.toml`.
 pub struct KeyboardManager {
     config: KeyboardConfig,
+    preset: KeyboardPreset,
+    preset_shortcuts: HashMap<KeyboardShortcut, KeyboardAction>,
+    preset_sequences: HashMap<String, KeyboardAction>,
 }
 
 impl KeyboardManager {
     /// Create a new keyboard manager with default configuration
     pub fn new() -> Result<Self> {
         let config = KeyboardConfig::load_or_create_default()?;
-        Ok(Self { config })
+        Ok(Self::with_preset(config, KeyboardPreset::load()?))
     }
 
     /// Create a keyboard manager with a specific configuration
     pub fn with_config(config: KeyboardConfig) -> Self {
-        Self { config }
+        Self::with_preset(config, KeyboardPreset::Default)
+    }
+
+    /// Create a keyboard manager with a preset applied over a configuration
+    pub fn with_preset(config: KeyboardConfig, preset: KeyboardPreset) -> Self {
+        Self {
+            config,
+            preset,
+            preset_shortcuts: preset.shortcuts(),
+            preset_sequences: preset.sequences(),
+        }
+    }
+
+    /// The preset in use
+    pub fn preset(&self) -> KeyboardPreset {
+        self.preset
+    }
+
+    /// Get the action for a two-key sequence
+    pub fn get_sequence_action(&self, first: char, second: char) -> Option<&KeyboardAction> {
+        self.preset_sequences
+            .get(&format!("{}{}", first, second))
+            .or_else(|| self.config.get_sequence_action(first, second))
+    }
+
+    /// Whether a key starts a two-key sequence
+    pub fn is_sequence_prefix(&self, key: char) -> bool {
+        self.preset_sequences.keys().any(|sequence| sequence.starts_with(key))
+            || self.config.is_sequence_prefix(key)
     }
 
     /// Get the action for a key event
@@ -1513,7 +2190,17 @@ impl KeyboardManager {
     ) -> Option<&KeyboardAction> {
         let shortcut = KeyboardShortcut::new(key_code, modifiers);
         
-        self.config.get_action(&shortcut)
+        self.preset_shortcuts
+            .get(&shortcut)
+            .or_else(|| self.config.get_action(&shortcut))
+    }
+
+    /// The configuration with the preset's bindings applied, as in effect
+    fn effective_config(&self) -> KeyboardConfig {
+        let mut config = self.config.clone();
+        config.shortcuts.extend(self.preset_shortcuts.clone());
+        config.sequences.extend(self.preset_sequences.clone());
+        config
     }
 
     /// Get the keyboard configuration
@@ -1526,10 +2213,13 @@ impl KeyboardManager {
         &mut self.config
     }
 
-    /// Save the current configuration to file
+    /// Save the current configuration to file, without the preset's bindings
     pub fn save_config(&self) -> Result<()> {
-        let config_path = KeyboardConfig::get_default_config_path()?;
-        self.config.save_to_file(config_path)
+        self.save_config_to(KeyboardConfig::get_default_config_path()?)
+    }
+
+    fn save_config_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.config.save_to_file(path)
     }
 
     /// Reload configuration from file
@@ -1566,7 +2256,8 @@ impl KeyboardManager {
         help.push_str("Keyboard Shortcuts\n");
         help.push_str("==================\n\n");
 
-        let categories = self.config.get_shortcuts_by_category();
+        let config = self.effective_config();
+        let categories = config.get_shortcuts_by_category();
         let mut sorted_categories: Vec<_> = categories.keys().collect();
         sorted_categories.sort();
 
@@ -1587,22 +2278,35 @@ impl KeyboardManager {
             help.push('\n');
         }
 
+        if !config.sequences.is_empty() {
+            help.push_str("Sequences:\n");
+            help.push_str("----------\n");
+            let mut sequences: Vec<_> = config.sequences.iter().collect();
+            sequences.sort_by(|a, b| a.0.cmp(b.0));
+            for (sequence, action) in sequences {
+                let description = config.get_action_description(action).unwrap_or_default();
+                help.push_str(&format!("  {:15} - {}\n", sequence, description));
+            }
+            help.push('\n');
+        }
+
         help
     }
 
     /// Get all keyboard shortcuts as a vector of (shortcut, action) pairs
     pub fn get_all_shortcuts(&self) -> Vec<(KeyboardShortcut, KeyboardAction)> {
-        self.config
-            .shortcuts
-            .iter()
-            .map(|(shortcut, action)| (shortcut.clone(), action.clone()))
-            .collect()
+        self.effective_config().shortcuts.into_iter().collect()
     }
 }
 
 impl Default for KeyboardManager {
     fn default() -> Self {
-        Self::new().unwrap_or_else(|_| Self::with_config(KeyboardConfig::default()))
+        Self::new().unwrap_or_else(|_| {
+            Self::with_preset(
+                KeyboardConfig::default(),
+                KeyboardPreset::load().unwrap_or_default(),
+            )
+        })
     }
 }
 
@@ -1739,4 +2443,72 @@ mod tests {
             .iter()
             .any(|(_, action, _)| **action == KeyboardAction::Quit));
     }
+
+    #[test]
+    fn test_gmail_preset() {
+        let manager = KeyboardManager::with_preset(KeyboardConfig::default(), KeyboardPreset::Gmail);
+        assert_eq!(manager.preset(), KeyboardPreset::Gmail);
+        assert_eq!(
+            manager.get_action(KeyCode::Char('e'), KeyModifiers::NONE),
+            Some(&KeyboardAction::ArchiveEmail)
+        );
+        assert_eq!(
+            manager.get_action(KeyCode::Char('#'), KeyModifiers::NONE),
+            Some(&KeyboardAction::DeleteEmail)
+        );
+        assert_eq!(
+            manager.get_action(KeyCode::Char('a'), KeyModifiers::NONE),
+            Some(&KeyboardAction::ReplyAllEmail)
+        );
+        assert_eq!(
+            manager.get_action(KeyCode::Char('E'), KeyModifiers::SHIFT),
+            Some(&KeyboardAction::CreateEvent)
+        );
+
+        assert!(manager.is_sequence_prefix('g'));
+        assert_eq!(manager.get_sequence_action('g', 'i'), Some(&KeyboardAction::GoToInbox));
+        assert_eq!(manager.get_sequence_action('g', 's'), Some(&KeyboardAction::GoToStarred));
+        assert_eq!(manager.get_sequence_action('g', 'x'), None);
+
+        // The default preset leaves the configured keys alone
+        let manager = KeyboardManager::with_config(KeyboardConfig::default());
+        assert!(!manager.is_sequence_prefix('g'));
+        assert_eq!(
+            manager.get_action(KeyCode::Char('e'), KeyModifiers::NONE),
+            Some(&KeyboardAction::CreateEvent)
+        );
+
+        assert_eq!(KeyboardPreset::from_name(" Gmail "), Some(KeyboardPreset::Gmail));
+        assert_eq!(KeyboardPreset::from_name("emacs"), None);
+    }
+
+    #[test]
+    fn test_preset_not_saved() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("keyboard.toml");
+
+        let mut manager =
+            KeyboardManager::with_preset(KeyboardConfig::default(), KeyboardPreset::Gmail);
+        let user_shortcut = KeyboardShortcut::ctrl(KeyCode::Char('y'));
+        manager
+            .config_mut()
+            .set_shortcut(user_shortcut.clone(), KeyboardAction::ArchiveEmail);
+        manager.save_config_to(&config_path).unwrap();
+
+        let saved = KeyboardConfig::load_from_file(&config_path).unwrap();
+        assert_eq!(saved.get_action(&user_shortcut), Some(&KeyboardAction::ArchiveEmail));
+        assert_eq!(
+            saved.get_action(&KeyboardShortcut::simple(KeyCode::Char('e'))),
+            Some(&KeyboardAction::CreateEvent)
+        );
+        assert!(saved.sequences.is_empty());
+
+        // The preset still applies over the saved configuration
+        let manager = KeyboardManager::with_preset(saved, KeyboardPreset::Gmail);
+        assert_eq!(
+            manager.get_action(KeyCode::Char('e'), KeyModifiers::NONE),
+            Some(&KeyboardAction::ArchiveEmail)
+        );
+        assert_eq!(manager.get_sequence_action('g', 'i'), Some(&KeyboardAction::GoToInbox));
+    }
 }
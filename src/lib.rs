@@ -4,12 +4,16 @@ pub mod app;
 pub mod calendar;
 pub mod cli;
 pub mod clipboard;
+pub mod config;
 pub mod contacts;
+pub mod core_client;
+pub mod daemon;
 pub mod email;
 pub mod events;
 pub mod html;
 pub mod images;
 pub mod imap;
+pub mod jmap;
 pub mod keyboard;
 pub mod maildir;
 pub mod mime;
@@ -30,3 +34,4 @@ pub mod theme;
 pub mod ui;
 
 pub use app::App;
+pub use core_client::{AccountSyncSummary, CoreClient};
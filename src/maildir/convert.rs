@@ -111,6 +111,8 @@ impl MaildirMessage {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         })
     }
 
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use clap::Parser;
 use comunicado::app::App;
-use comunicado::cli::{Cli, CliHandler};
+use comunicado::cli::{Cli, CliHandler, StartupMode};
 
 
 #[tokio::main]
@@ -9,7 +9,7 @@ async fn main() -> Result<()> {
     println!("🚀 Comunicado starting...");
     let cli = Cli::parse();
     println!("📋 CLI parsed");
-    let cli_handler = CliHandler::new(cli.config_dir.clone()).await?;
+    let mut cli_handler = CliHandler::new(cli.config_dir.clone()).await?;
     println!("🔧 CLI handler created");
 
     // Handle CLI commands that exit immediately
@@ -20,9 +20,31 @@ async fn main() -> Result<()> {
         return cli_handler.handle_command(command, cli.dry_run).await;
     }
 
+    // Tokens in a keyring that isn't running would make every account fail
+    // to sign in, so ask before starting
+    if !cli_handler.resolve_missing_credential_backend()? {
+        return Ok(());
+    }
+
     // Continue with normal TUI application
     let debug_mode = cli.debug;
-    let startup_mode = cli.get_startup_mode();
+    let session_config = comunicado::ui::SessionConfig::load()
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to load session config, using defaults: {}", e);
+            Default::default()
+        });
+    let startup = cli.startup(&session_config);
+
+    // Daemon-first startup leaves the interface closed
+    if startup.mode == StartupMode::Daemon {
+        return cli_handler.start_daemon();
+    }
+
+    // Encrypted tokens need their passphrase before anything signs in
+    if !cli_handler.unlock_credential_vault()? {
+        return Ok(());
+    }
 
     // Initialize tracing for logging - write to file to avoid interfering with TUI
     let log_file = match std::fs::OpenOptions::new()
@@ -59,8 +81,11 @@ async fn main() -> Result<()> {
     let mut app = App::new()?;
     println!("✅ Application created");
     
-    // Set initial UI mode based on CLI arguments
-    app.set_initial_mode(startup_mode);
+    // Set initial UI mode based on CLI arguments and the configured startup
+    app.set_initial_mode(startup.mode);
+    if startup.mode == StartupMode::Email {
+        app.set_startup_location(startup.account, startup.folder);
+    }
     println!("🔧 Initial mode set");
 
     // Run the application
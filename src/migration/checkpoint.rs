@@ -0,0 +1,210 @@
+//! Resumable migration checkpoints
+//!
+//! A checkpoint records which source items a migration task has already
+//! imported, so a migration that stopped partway through (a crash, a cancel,
+//! an unreadable file) skips them when it is run again instead of starting
+//! over. Messages are keyed by target folder and Message-ID, as a source
+//! may store one message in several folders.
+//!
+//! Each task's checkpoint is an append-only journal with one key per line,
+//! so recording an item costs one small write no matter how large the
+//! migration is.
+
+use crate::email::StoredMessage;
+use crate::migration::{MigrationDataType, MigrationResult};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+/// Journal entries between syncs to disk
+const SYNC_INTERVAL: usize = 500;
+
+/// Items a migration task has already imported
+pub struct MigrationCheckpoint {
+    path: PathBuf,
+    completed: HashSet<String>,
+    journal: File,
+    unsynced: usize,
+}
+
+impl MigrationCheckpoint {
+    /// Open the checkpoint for a task's source and data type, creating it if needed
+    ///
+    /// The same source path and data type always map to the same checkpoint,
+    /// so a new plan for the same source resumes where the last one stopped.
+    pub async fn open(
+        checkpoint_dir: &Path,
+        source_path: &Path,
+        data_type: &MigrationDataType,
+    ) -> MigrationResult<Self> {
+        tokio::fs::create_dir_all(checkpoint_dir).await?;
+        let path = Self::path(checkpoint_dir, source_path, data_type);
+
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        // A line cut off by a crash was never confirmed, so it is dropped
+        let confirmed = content.rfind('\n').map_or(0, |end| end + 1);
+        if confirmed < content.len() {
+            let file = OpenOptions::new().write(true).open(&path).await?;
+            file.set_len(confirmed as u64).await?;
+        }
+        let completed = content[..confirmed]
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+
+        let journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+
+        Ok(Self {
+            path,
+            completed,
+            journal,
+            unsynced: 0,
+        })
+    }
+
+    /// Remove the checkpoint for a task's source and data type, so the next run starts over
+    pub async fn discard(
+        checkpoint_dir: &Path,
+        source_path: &Path,
+        data_type: &MigrationDataType,
+    ) -> MigrationResult<()> {
+        match tokio::fs::remove_file(Self::path(checkpoint_dir, source_path, data_type)).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether the item was imported by an earlier run
+    pub fn is_completed(&self, key: &str) -> bool {
+        self.completed.contains(key)
+    }
+
+    /// Number of items imported so far
+    pub fn completed_count(&self) -> usize {
+        self.completed.len()
+    }
+
+    /// Record an imported item
+    ///
+    /// Call this after the item is stored: a crash in between imports that
+    /// one item again on the next run rather than losing it.
+    pub async fn mark_completed(&mut self, key: &str) -> MigrationResult<()> {
+        let key = key.replace(['\r', '\n'], " ");
+        if !self.completed.insert(key.clone()) {
+            return Ok(());
+        }
+
+        self.journal
+            .write_all(format!("{}\n", key).as_bytes())
+            .await?;
+        self.unsynced += 1;
+        if self.unsynced >= SYNC_INTERVAL {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Make sure every recorded item is on disk
+    pub async fn flush(&mut self) -> MigrationResult<()> {
+        self.journal.flush().await?;
+        self.journal.sync_data().await?;
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    /// Path of the journal file
+    pub fn file_path(&self) -> &Path {
+        &self.path
+    }
+
+    fn path(checkpoint_dir: &Path, source_path: &Path, data_type: &MigrationDataType) -> PathBuf {
+        let name = format!("{:?}:{}", data_type, source_path.display());
+        let id = Uuid::new_v5(&Uuid::NAMESPACE_URL, name.as_bytes());
+        checkpoint_dir.join(format!("{}.checkpoint", id))
+    }
+}
+
+/// Checkpoint key of a message: its folder and Message-ID, or sender, date and subject without one
+pub fn message_key(message: &StoredMessage) -> String {
+    match message.message_id.as_deref().map(str::trim) {
+        Some(message_id) if !message_id.is_empty() => {
+            format!("{}|{}", message.folder_name, message_id)
+        }
+        _ => format!(
+            "{}|{}|{}|{}",
+            message.folder_name,
+            message.from_addr,
+            message.date.timestamp(),
+            message.subject
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_checkpoint_resumes() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = Path::new("/mail/archive.pst");
+        let data_type = MigrationDataType::Emails;
+
+        let mut checkpoint = MigrationCheckpoint::open(temp_dir.path(), source, &data_type)
+            .await
+            .unwrap();
+        checkpoint.mark_completed("<a@example.com>").await.unwrap();
+        checkpoint.mark_completed("<b@example.com>").await.unwrap();
+        checkpoint.mark_completed("<a@example.com>").await.unwrap();
+        checkpoint.flush().await.unwrap();
+        let path = checkpoint.file_path().to_path_buf();
+        drop(checkpoint);
+
+        // A crash while writing leaves half a line behind
+        let mut content = std::fs::read_to_string(&path).unwrap();
+        content.push_str("<c@exa");
+        std::fs::write(&path, content).unwrap();
+
+        let mut checkpoint = MigrationCheckpoint::open(temp_dir.path(), source, &data_type)
+            .await
+            .unwrap();
+        assert_eq!(checkpoint.completed_count(), 2);
+        assert!(checkpoint.is_completed("<b@example.com>"));
+        assert!(!checkpoint.is_completed("<c@exa"));
+        checkpoint.mark_completed("<c@example.com>").await.unwrap();
+        checkpoint.flush().await.unwrap();
+        drop(checkpoint);
+
+        let checkpoint = MigrationCheckpoint::open(temp_dir.path(), source, &data_type)
+            .await
+            .unwrap();
+        assert!(checkpoint.is_completed("<c@example.com>"));
+        assert_eq!(checkpoint.completed_count(), 3);
+
+        let contacts =
+            MigrationCheckpoint::open(temp_dir.path(), source, &MigrationDataType::Contacts)
+                .await
+                .unwrap();
+        assert_eq!(contacts.completed_count(), 0);
+
+        MigrationCheckpoint::discard(temp_dir.path(), source, &data_type)
+            .await
+            .unwrap();
+        let checkpoint = MigrationCheckpoint::open(temp_dir.path(), source, &data_type)
+            .await
+            .unwrap();
+        assert_eq!(checkpoint.completed_count(), 0);
+    }
+}
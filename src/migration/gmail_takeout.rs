@@ -0,0 +1,455 @@
+//! Gmail Takeout migration support
+//!
+//! Google Takeout exports mail as mbox files, usually a single
+//! `All mail Including Spam and Trash.mbox`. Gmail has labels rather than
+//! folders, and Takeout keeps each message's labels in an `X-Gmail-Labels`
+//! header. How those labels become folders is chosen with
+//! `TakeoutLabelOptions`, set on the migration config before importing.
+
+use crate::email::mbox::{self, MboxReader};
+use crate::email::security::{header_values, split_headers};
+use crate::email::StoredMessage;
+use crate::migration::{MigrationConfig, MigrationError, MigrationResult};
+use crate::mime::decode_mime_header;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Labels Gmail uses for message state rather than for filing
+const STATE_LABELS: [&str; 7] = [
+    "Unread",
+    "Opened",
+    "Starred",
+    "Important",
+    "Archived",
+    "Chat",
+    "Sent Messages",
+];
+
+/// How Gmail labels become folders
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum TakeoutLabelMode {
+    /// A copy of the message in a folder for each of its labels
+    FolderPerLabel,
+    /// One copy of the message, with its labels kept in the `labels` field
+    Labels,
+}
+
+/// Label mapping chosen for a Gmail Takeout import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TakeoutLabelOptions {
+    pub mode: TakeoutLabelMode,
+    /// Gmail labels that go into Comunicado's special folders, as (label, folder)
+    ///
+    /// When a message has several of them, the earlier entry decides its
+    /// folder in `Labels` mode.
+    pub special_folders: Vec<(String, String)>,
+    /// Folder for messages without a folder label, which Gmail shows only in All Mail
+    pub archive_folder: String,
+    /// Leave out Hangouts chat transcripts, which Takeout exports as messages
+    pub skip_chats: bool,
+}
+
+impl Default for TakeoutLabelOptions {
+    fn default() -> Self {
+        let special = |label: &str, folder: &str| (label.to_string(), folder.to_string());
+        Self {
+            mode: TakeoutLabelMode::Labels,
+            special_folders: vec![
+                special("Trash", "Trash"),
+                special("Spam", "Spam"),
+                special("Drafts", "Drafts"),
+                special("Draft", "Drafts"),
+                special("Inbox", "INBOX"),
+                special("Sent", "Sent"),
+            ],
+            archive_folder: "Archive".to_string(),
+            skip_chats: true,
+        }
+    }
+}
+
+/// Where a message goes and what it carries, from its Gmail labels
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelPlacement {
+    /// Folders to store a copy in, never empty
+    pub folders: Vec<String>,
+    pub labels: Vec<String>,
+    pub flags: Vec<String>,
+}
+
+/// Gmail Takeout mail export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TakeoutArchive {
+    pub path: PathBuf,
+    pub mbox_files: Vec<PathBuf>,
+    pub message_count: usize,
+    /// Messages per label, to choose the mapping before importing
+    pub label_counts: BTreeMap<String, usize>,
+}
+
+/// Main Gmail Takeout migrator
+pub struct GmailTakeoutMigrator {
+    options: TakeoutLabelOptions,
+}
+
+impl GmailTakeoutMigrator {
+    /// Create a migrator that maps labels as `options` says
+    pub fn new(options: TakeoutLabelOptions) -> Self {
+        Self { options }
+    }
+
+    /// Create a migrator with the label mapping of a migration config
+    pub fn from_config(config: &MigrationConfig) -> Self {
+        Self::new(config.takeout_labels.clone())
+    }
+
+    /// Find the mbox files of a Takeout export and count their labels
+    ///
+    /// `path` is an mbox file or a directory such as the unpacked `Takeout`.
+    /// Every message is read, so this takes a while for large archives.
+    pub async fn open_archive(&self, path: &Path) -> MigrationResult<TakeoutArchive> {
+        let path = path.to_path_buf();
+
+        tokio::task::spawn_blocking(move || {
+            let mbox_files = find_mbox_files(&path)?;
+            let mut archive = TakeoutArchive {
+                path,
+                mbox_files,
+                message_count: 0,
+                label_counts: BTreeMap::new(),
+            };
+
+            for file in &archive.mbox_files {
+                for entry in MboxReader::open(file)? {
+                    let entry = entry?;
+                    archive.message_count += 1;
+                    for label in message_labels(&entry.raw) {
+                        *archive.label_counts.entry(label).or_default() += 1;
+                    }
+                }
+            }
+
+            Ok(archive)
+        })
+        .await
+        .map_err(|e| MigrationError::ConversionFailed(e.to_string()))?
+    }
+
+    /// Folders, labels and flags for a message with the given Gmail labels
+    pub fn place(&self, gmail_labels: &[String]) -> LabelPlacement {
+        let has = |name: &str| {
+            gmail_labels
+                .iter()
+                .any(|label| label.eq_ignore_ascii_case(name))
+        };
+
+        let mut flags = Vec::new();
+        if !has("Unread") {
+            flags.push("\\Seen".to_string());
+        }
+        if has("Starred") {
+            flags.push("\\Flagged".to_string());
+        }
+        if has("Draft") || has("Drafts") {
+            flags.push("\\Draft".to_string());
+        }
+
+        let special: Vec<&(String, String)> = self
+            .options
+            .special_folders
+            .iter()
+            .filter(|(label, _)| has(label))
+            .collect();
+        let user_labels: Vec<String> = gmail_labels
+            .iter()
+            .filter(|label| {
+                !STATE_LABELS
+                    .iter()
+                    .any(|state| label.eq_ignore_ascii_case(state))
+                    && !label.starts_with("Category ")
+                    && !self
+                        .options
+                        .special_folders
+                        .iter()
+                        .any(|(special, _)| label.eq_ignore_ascii_case(special))
+            })
+            .cloned()
+            .collect();
+
+        // Gmail hides trashed and spam messages from every other label
+        let binned = special.iter().find(|(label, _)| {
+            label.eq_ignore_ascii_case("Trash") || label.eq_ignore_ascii_case("Spam")
+        });
+
+        let mut folders: Vec<String> = Vec::new();
+        let mut labels = Vec::new();
+        match (&self.options.mode, binned) {
+            (TakeoutLabelMode::Labels, _) => {
+                folders.extend(special.first().map(|(_, folder)| folder.clone()));
+                labels = user_labels;
+            }
+            (TakeoutLabelMode::FolderPerLabel, Some((_, folder))) => folders.push(folder.clone()),
+            (TakeoutLabelMode::FolderPerLabel, None) => {
+                for folder in special.iter().map(|(_, folder)| folder).chain(&user_labels) {
+                    if !folders.contains(folder) {
+                        folders.push(folder.clone());
+                    }
+                }
+            }
+        }
+        if folders.is_empty() {
+            folders.push(self.options.archive_folder.clone());
+        }
+
+        LabelPlacement {
+            folders,
+            labels,
+            flags,
+        }
+    }
+
+    /// Stream the messages of a Takeout archive as stored messages
+    ///
+    /// In `FolderPerLabel` mode a message comes once for each of its folders.
+    /// Messages are read on a blocking thread; an `Err` ends the stream.
+    pub fn stream_emails(
+        &self,
+        archive: &TakeoutArchive,
+        target_account_id: &str,
+    ) -> mpsc::Receiver<MigrationResult<StoredMessage>> {
+        let (sender, receiver) = mpsc::channel(64);
+        let files = archive.mbox_files.clone();
+        let account_id = target_account_id.to_string();
+        let migrator = Self::new(self.options.clone());
+
+        tokio::task::spawn_blocking(move || {
+            for file in files {
+                let entries = match MboxReader::open(&file) {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        let _ = sender.blocking_send(Err(e.into()));
+                        return;
+                    }
+                };
+                for entry in entries {
+                    let entry = match entry {
+                        Ok(entry) => entry,
+                        Err(e) => {
+                            let _ = sender.blocking_send(Err(e.into()));
+                            return;
+                        }
+                    };
+                    for message in migrator.convert_entry(&entry, &account_id) {
+                        if sender.blocking_send(Ok(message)).is_err() {
+                            return; // Receiver gone, the import was cancelled
+                        }
+                    }
+                }
+            }
+        });
+
+        receiver
+    }
+
+    /// Migrate the emails of a Takeout archive
+    pub async fn migrate_emails(
+        &self,
+        archive: &TakeoutArchive,
+        target_account_id: &str,
+    ) -> MigrationResult<Vec<StoredMessage>> {
+        let mut receiver = self.stream_emails(archive, target_account_id);
+        let mut messages = Vec::new();
+
+        while let Some(message) = receiver.recv().await {
+            messages.push(message?);
+        }
+
+        Ok(messages)
+    }
+
+    /// The stored copies of one Takeout message, none for skipped chats
+    fn convert_entry(&self, entry: &mbox::MboxEntry, account_id: &str) -> Vec<StoredMessage> {
+        let gmail_labels = message_labels(&entry.raw);
+        if self.options.skip_chats && gmail_labels.iter().any(|label| label == "Chat") {
+            return Vec::new();
+        }
+
+        let placement = self.place(&gmail_labels);
+        let mut message = mbox::parse_entry(entry, account_id, &placement.folders[0]);
+        message.is_draft = placement.flags.iter().any(|flag| flag == "\\Draft");
+        message.flags = placement.flags;
+        message.labels = placement.labels;
+
+        placement
+            .folders
+            .iter()
+            .map(|folder| StoredMessage {
+                id: Uuid::new_v4(),
+                folder_name: folder.clone(),
+                ..message.clone()
+            })
+            .collect()
+    }
+}
+
+/// Gmail labels of a raw message, from its `X-Gmail-Labels` header
+///
+/// Labels are separated by commas; a label holding a comma is quoted.
+pub fn message_labels(raw: &str) -> Vec<String> {
+    let (headers, _) = split_headers(raw);
+    let mut labels = Vec::new();
+
+    for value in header_values(&headers, "x-gmail-labels") {
+        let value = decode_mime_header(&value);
+        let mut current = String::new();
+        let mut quoted = false;
+        for c in value.chars().chain(std::iter::once(',')) {
+            match c {
+                '"' => quoted = !quoted,
+                ',' if !quoted => {
+                    let label = current.trim();
+                    if !label.is_empty() && !labels.iter().any(|l: &String| l == label) {
+                        labels.push(label.to_string());
+                    }
+                    current.clear();
+                }
+                c => current.push(c),
+            }
+        }
+    }
+
+    labels
+}
+
+/// The mbox files of a Takeout export, the path itself when it is one
+fn find_mbox_files(path: &Path) -> MigrationResult<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(path)
+        .max_depth(4)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && mbox::is_mbox_file(entry.path()))
+        .map(|entry| entry.into_path())
+        .collect();
+    if files.is_empty() {
+        return Err(MigrationError::SourceNotAccessible(format!(
+            "No mbox files found in {}",
+            path.display()
+        )));
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_message_labels() {
+        let raw =
+            "X-Gmail-Labels: Inbox,Opened,\"Clients, 2024\",Work/Projects\nSubject: Hi\n\nBody\n";
+        assert_eq!(
+            message_labels(raw),
+            ["Inbox", "Opened", "Clients, 2024", "Work/Projects"]
+        );
+    }
+
+    #[test]
+    fn test_place_labels() {
+        let migrator = GmailTakeoutMigrator::new(TakeoutLabelOptions::default());
+
+        let placement = migrator.place(&labels(&[
+            "Inbox",
+            "Unread",
+            "Work",
+            "Category Updates",
+            "Starred",
+        ]));
+        assert_eq!(placement.folders, ["INBOX"]);
+        assert_eq!(placement.labels, ["Work"]);
+        assert_eq!(placement.flags, ["\\Flagged"]);
+
+        let placement = migrator.place(&labels(&["Sent", "Inbox", "Opened"]));
+        assert_eq!(placement.folders, ["INBOX"]);
+
+        let placement = migrator.place(&labels(&["Work", "Opened"]));
+        assert_eq!(placement.folders, ["Archive"]);
+        assert_eq!(placement.flags, ["\\Seen"]);
+    }
+
+    #[test]
+    fn test_place_folder_per_label() {
+        let migrator = GmailTakeoutMigrator::new(TakeoutLabelOptions {
+            mode: TakeoutLabelMode::FolderPerLabel,
+            ..TakeoutLabelOptions::default()
+        });
+
+        let placement = migrator.place(&labels(&["Inbox", "Work", "Work/Projects", "Important"]));
+        assert_eq!(placement.folders, ["INBOX", "Work", "Work/Projects"]);
+        assert!(placement.labels.is_empty());
+
+        let placement = migrator.place(&labels(&["Trash", "Work"]));
+        assert_eq!(placement.folders, ["Trash"]);
+    }
+
+    #[tokio::test]
+    async fn test_import_takeout_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let mail_dir = temp_dir.path().join("Takeout/Mail");
+        std::fs::create_dir_all(&mail_dir).unwrap();
+        std::fs::write(
+            mail_dir.join("All mail Including Spam and Trash.mbox"),
+            "From 1234@xxx Mon Mar 04 10:00:00 +0000 2024\n\
+             X-Gmail-Labels: Inbox,Opened,Work,Family\n\
+             Message-ID: <one@example.com>\n\
+             From: Sam <sam@example.com>\n\
+             Subject: One\n\
+             \n\
+             First\n\
+             \n\
+             From 5678@xxx Mon Mar 04 11:00:00 +0000 2024\n\
+             X-Gmail-Labels: Chat\n\
+             From: Kim <kim@example.com>\n\
+             Subject: Chat with Kim\n\
+             \n\
+             Hi\n",
+        )
+        .unwrap();
+
+        let migrator = GmailTakeoutMigrator::new(TakeoutLabelOptions::default());
+        let archive = migrator.open_archive(temp_dir.path()).await.unwrap();
+        assert_eq!(archive.mbox_files.len(), 1);
+        assert_eq!(archive.message_count, 2);
+        assert_eq!(archive.label_counts.get("Work"), Some(&1));
+
+        let messages = migrator.migrate_emails(&archive, "account").await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].folder_name, "INBOX");
+        assert_eq!(messages[0].labels, ["Work", "Family"]);
+        assert_eq!(messages[0].flags, ["\\Seen"]);
+
+        let migrator = GmailTakeoutMigrator::new(TakeoutLabelOptions {
+            mode: TakeoutLabelMode::FolderPerLabel,
+            ..TakeoutLabelOptions::default()
+        });
+        let messages = migrator.migrate_emails(&archive, "account").await.unwrap();
+        let folders: Vec<&str> = messages.iter().map(|m| m.folder_name.as_str()).collect();
+        assert_eq!(folders, ["INBOX", "Work", "Family"]);
+        assert!(messages
+            .iter()
+            .all(|m| m.message_id.as_deref() == Some("<one@example.com>")));
+    }
+}
@@ -7,9 +7,11 @@
 use crate::migration::{
     MigrationConfig, MigrationSource, MigrationTarget, MigrationStatistics,
     MigrationDataType, ConflictInfo, ConflictResolution, ValidationResults,
-    EmailClient, MigrationErrorInfo,
+    EmailClient, MigrationErrorInfo, GmailTakeoutMigrator, OutlookMigrator,
 };
-use crate::email::{EmailDatabase, StoredMessage};
+use crate::migration::checkpoint::{message_key, MigrationCheckpoint};
+use crate::email::mbox::{self, MboxReader};
+use crate::email::{EmailDatabase, StoredFolder, StoredMessage};
 use crate::contacts::{ContactsDatabase, Contact};
 use chrono::{DateTime, Utc};
 use futures::stream::{self, StreamExt};
@@ -83,6 +85,9 @@ pub struct MigrationTask {
     pub completed_at: Option<DateTime<Utc>>,
     pub estimated_items: Option<usize>,
     pub processed_items: usize,
+    /// Items skipped because an earlier, interrupted run already imported them
+    #[serde(default)]
+    pub resumed_items: usize,
     pub failed_items: usize,
     pub error_message: Option<String>,
 }
@@ -267,6 +272,8 @@ impl MigrationEngine {
         progress_callback: Option<ProgressCallback>,
     ) -> MigrationResult<MigrationStatistics> {
         let migration_id = plan.id;
+        let config = plan.config.clone();
+        let account_id = plan.target.account_id.clone();
         let mut stats = MigrationStatistics::default();
         stats.started_at = Some(Utc::now());
 
@@ -321,12 +328,19 @@ impl MigrationEngine {
             main_pb.set_message(format!("Processing: {}", task.name));
 
             // Execute individual task
-            match self.execute_task(&mut task, &mut stats).await {
+            match self.execute_task(migration_id, &config, &account_id, &mut task, &mut stats).await {
                 Ok(_) => {
                     task.status = MigrationStatus::Completed;
                     task.completed_at = Some(Utc::now());
                     progress.tasks_completed += 1;
-                    progress.items_processed += task.processed_items;
+                    progress.items_processed += task.processed_items + task.resumed_items;
+                }
+                Err(MigrationError::Cancelled) => {
+                    // The checkpoint lets the next run pick up from here
+                    progress.status = MigrationStatus::Cancelled;
+                    self.update_progress(migration_id, progress.clone(), &progress_callback).await;
+                    self.cleanup_migration(migration_id).await;
+                    return Err(MigrationError::Cancelled);
                 }
                 Err(e) => {
                     task.status = MigrationStatus::Failed;
@@ -384,10 +398,24 @@ impl MigrationEngine {
         self.active_migrations.read().await.get(&migration_id).cloned()
     }
 
+    /// Forget what earlier runs of a plan imported, so running it again imports everything
+    pub async fn discard_checkpoints(&self, plan: &MigrationPlan) -> MigrationResult<()> {
+        for task in &plan.tasks {
+            MigrationCheckpoint::discard(
+                &Self::checkpoint_dir(&task.target_path),
+                &task.source_path,
+                &task.data_type,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
     /// Analyze source for migration planning
     async fn analyze_source(&self, config: &MigrationConfig) -> MigrationResult<MigrationSource> {
         let profile_path = config.source_path.clone();
-        let is_accessible = profile_path.exists() && profile_path.is_dir();
+        // Outlook data is a single .pst file rather than a profile directory
+        let is_accessible = profile_path.exists();
         let permissions_ok = is_accessible; // TODO: Implement proper permission checking
 
         let mut data_paths = HashMap::new();
@@ -482,6 +510,7 @@ impl MigrationEngine {
                     completed_at: None,
                     estimated_items: self.estimate_items_for_type(source_path, data_type).await.ok(),
                     processed_items: 0,
+                    resumed_items: 0,
                     failed_items: 0,
                     error_message: None,
                 };
@@ -506,15 +535,18 @@ impl MigrationEngine {
     /// Execute a single migration task
     async fn execute_task(
         &self,
+        migration_id: Uuid,
+        config: &MigrationConfig,
+        account_id: &str,
         task: &mut MigrationTask,
         stats: &mut MigrationStatistics,
     ) -> MigrationResult<()> {
         match task.data_type {
             MigrationDataType::Emails => {
-                self.migrate_emails(task, stats).await
+                self.migrate_emails(migration_id, config, account_id, task, stats).await
             }
             MigrationDataType::Contacts => {
-                self.migrate_contacts(task, stats).await
+                self.migrate_contacts(migration_id, &config.source_client, task, stats).await
             }
             _ => {
                 task.error_message = Some("Data type not yet supported".to_string());
@@ -523,38 +555,249 @@ impl MigrationEngine {
         }
     }
 
-    /// Migrate emails
+    /// Migrate emails, skipping those an earlier run already imported
     async fn migrate_emails(
         &self,
+        migration_id: Uuid,
+        config: &MigrationConfig,
+        account_id: &str,
         task: &mut MigrationTask,
         stats: &mut MigrationStatistics,
     ) -> MigrationResult<()> {
-        // TODO: Implement email migration logic
-        // This would involve:
-        // 1. Reading emails from source format
-        // 2. Converting to StoredMessage format
-        // 3. Storing in target database
-        
-        task.processed_items = 100; // Placeholder
-        stats.emails.migrated = 100;
-        stats.total_items_migrated += 100;
-        
-        Ok(())
+        let mut checkpoint = MigrationCheckpoint::open(
+            &Self::checkpoint_dir(&task.target_path),
+            &task.source_path,
+            &task.data_type,
+        )
+        .await?;
+        let mut messages = self.email_source(config, &task.source_path, account_id).await?;
+        let mut next_uids: HashMap<String, u32> = HashMap::new();
+
+        let result = async {
+            while let Some(message) = messages.recv().await {
+                if self.is_cancelled(migration_id).await {
+                    return Err(MigrationError::Cancelled);
+                }
+                let mut message = message?;
+                stats.emails.found += 1;
+                stats.total_items_found += 1;
+
+                let key = message_key(&message);
+                if checkpoint.is_completed(&key) {
+                    task.resumed_items += 1;
+                    stats.emails.resumed += 1;
+                    stats.total_items_resumed += 1;
+                    continue;
+                }
+
+                message.imap_uid = self.next_uid(&mut next_uids, account_id, &message.folder_name).await?;
+                match self.email_db.store_message(&message).await {
+                    Ok(()) => {
+                        checkpoint.mark_completed(&key).await?;
+                        task.processed_items += 1;
+                        stats.emails.migrated += 1;
+                        stats.emails.size_bytes += u64::from(message.size.unwrap_or_default());
+                        stats.total_items_migrated += 1;
+                    }
+                    Err(e) => {
+                        task.failed_items += 1;
+                        stats.emails.failed += 1;
+                        stats.total_items_failed += 1;
+                        stats.errors.push(MigrationErrorInfo {
+                            timestamp: Utc::now(),
+                            error_type: "StoreMessage".to_string(),
+                            description: format!("{}: {}", key, e),
+                            item_path: Some(task.source_path.clone()),
+                            data_type: Some(MigrationDataType::Emails),
+                            recoverable: true,
+                            retry_count: 0,
+                        });
+                    }
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        checkpoint.flush().await?;
+        result
     }
 
-    /// Migrate contacts
+    /// Migrate contacts, skipping those an earlier run already imported
     async fn migrate_contacts(
         &self,
+        migration_id: Uuid,
+        client: &EmailClient,
         task: &mut MigrationTask,
         stats: &mut MigrationStatistics,
     ) -> MigrationResult<()> {
-        // TODO: Implement contact migration logic
-        
-        task.processed_items = 50; // Placeholder
-        stats.contacts.migrated = 50;
-        stats.total_items_migrated += 50;
-        
-        Ok(())
+        let contacts = match client {
+            EmailClient::Outlook => {
+                let mut migrator = OutlookMigrator::new()?;
+                let profile = migrator.add_pst_file(&task.source_path).await?;
+                migrator.migrate_contacts(&profile).await?
+            }
+            _ => return Err(MigrationError::UnsupportedClient(client.clone())),
+        };
+        let mut checkpoint = MigrationCheckpoint::open(
+            &Self::checkpoint_dir(&task.target_path),
+            &task.source_path,
+            &task.data_type,
+        )
+        .await?;
+
+        let result = async {
+            for mut contact in contacts {
+                if self.is_cancelled(migration_id).await {
+                    return Err(MigrationError::Cancelled);
+                }
+                stats.contacts.found += 1;
+                stats.total_items_found += 1;
+
+                if checkpoint.is_completed(&contact.external_id) {
+                    task.resumed_items += 1;
+                    stats.contacts.resumed += 1;
+                    stats.total_items_resumed += 1;
+                    continue;
+                }
+
+                match self.contacts_db.store_contact(&mut contact).await {
+                    Ok(()) => {
+                        checkpoint.mark_completed(&contact.external_id).await?;
+                        task.processed_items += 1;
+                        stats.contacts.migrated += 1;
+                        stats.total_items_migrated += 1;
+                    }
+                    Err(e) => {
+                        task.failed_items += 1;
+                        stats.contacts.failed += 1;
+                        stats.total_items_failed += 1;
+                        stats.errors.push(MigrationErrorInfo {
+                            timestamp: Utc::now(),
+                            error_type: "StoreContact".to_string(),
+                            description: format!("{}: {}", contact.display_name, e),
+                            item_path: Some(task.source_path.clone()),
+                            data_type: Some(MigrationDataType::Contacts),
+                            recoverable: true,
+                            retry_count: 0,
+                        });
+                    }
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        checkpoint.flush().await?;
+        result
+    }
+
+    /// Messages of a migration source, read in the background
+    async fn email_source(
+        &self,
+        config: &MigrationConfig,
+        source_path: &Path,
+        account_id: &str,
+    ) -> MigrationResult<mpsc::Receiver<MigrationResult<StoredMessage>>> {
+        match &config.source_client {
+            EmailClient::Outlook => {
+                let mut migrator = OutlookMigrator::new()?;
+                let profile = migrator.add_pst_file(source_path).await?;
+                Ok(migrator.stream_emails(&profile, account_id))
+            }
+            EmailClient::Gmail => {
+                let migrator = GmailTakeoutMigrator::from_config(config);
+                let archive = migrator.open_archive(source_path).await?;
+                Ok(migrator.stream_emails(&archive, account_id))
+            }
+            EmailClient::Thunderbird
+            | EmailClient::Mutt
+            | EmailClient::Neomutt
+            | EmailClient::Alpine
+            | EmailClient::Pine => {
+                let mailboxes = mbox::scan(source_path)
+                    .await
+                    .map_err(|e| MigrationError::SourceNotAccessible(e.to_string()))?;
+                let (sender, receiver) = mpsc::channel(64);
+                let account_id = account_id.to_string();
+
+                tokio::task::spawn_blocking(move || {
+                    for mailbox in mailboxes {
+                        let entries = match MboxReader::open(&mailbox.path) {
+                            Ok(entries) => entries,
+                            Err(e) => {
+                                let _ = sender.blocking_send(Err(e.into()));
+                                return;
+                            }
+                        };
+                        for entry in entries {
+                            let message = entry
+                                .map(|entry| mbox::parse_entry(&entry, &account_id, &mailbox.name))
+                                .map_err(MigrationError::from);
+                            if sender.blocking_send(message).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                });
+
+                Ok(receiver)
+            }
+            client => Err(MigrationError::UnsupportedClient(client.clone())),
+        }
+    }
+
+    /// Next free UID in a folder, creating the folder the first time it is seen
+    async fn next_uid(
+        &self,
+        next_uids: &mut HashMap<String, u32>,
+        account_id: &str,
+        folder: &str,
+    ) -> MigrationResult<u32> {
+        if let Some(uid) = next_uids.get_mut(folder) {
+            *uid += 1;
+            return Ok(*uid - 1);
+        }
+
+        let database = |e: crate::email::database::DatabaseError| MigrationError::Database(e.to_string());
+        let folders = self.email_db.get_folders(account_id).await.map_err(database)?;
+        if !folders.iter().any(|existing| existing.name == folder || existing.full_name == folder) {
+            let now = Utc::now();
+            self.email_db
+                .store_folder(&StoredFolder {
+                    account_id: account_id.to_string(),
+                    name: folder.to_string(),
+                    full_name: folder.to_string(),
+                    delimiter: Some("/".to_string()),
+                    attributes: Vec::new(),
+                    created_at: now,
+                    updated_at: now,
+                })
+                .await
+                .map_err(database)?;
+        }
+
+        // Imported messages get UIDs above those already in the folder
+        let uid = self
+            .email_db
+            .get_folder_uids(account_id, folder)
+            .await
+            .map_err(database)?
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+            + 1;
+        next_uids.insert(folder.to_string(), uid + 1);
+        Ok(uid)
+    }
+
+    async fn is_cancelled(&self, migration_id: Uuid) -> bool {
+        *self.cancel_tokens.read().await.get(&migration_id).unwrap_or(&false)
+    }
+
+    /// Where the checkpoints of migrations into `target_path` are kept
+    fn checkpoint_dir(target_path: &Path) -> PathBuf {
+        target_path.join("checkpoints")
     }
 
     /// Helper methods
@@ -589,6 +832,17 @@ impl MigrationEngine {
             (EmailClient::Thunderbird, MigrationDataType::Contacts) => {
                 Ok(profile_path.join("abook.mab"))
             }
+            (EmailClient::Outlook, MigrationDataType::Emails | MigrationDataType::Contacts) => {
+                Ok(profile_path.to_path_buf())
+            }
+            (
+                EmailClient::Gmail
+                | EmailClient::Mutt
+                | EmailClient::Neomutt
+                | EmailClient::Alpine
+                | EmailClient::Pine,
+                MigrationDataType::Emails,
+            ) => Ok(profile_path.to_path_buf()),
             _ => Err(MigrationError::UnsupportedClient(client.clone())),
         }
     }
@@ -654,6 +908,7 @@ mod tests {
             completed_at: None,
             estimated_items: Some(100),
             processed_items: 0,
+            resumed_items: 0,
             failed_items: 0,
             error_message: None,
         };
@@ -11,6 +11,7 @@
 //! - KMail
 //! - And more...
 
+pub mod checkpoint;
 pub mod client_detection;
 pub mod thunderbird;
 pub mod outlook;
@@ -25,17 +26,22 @@ pub mod migration_wizard;
 pub mod format_converters;
 pub mod profile_detector;
 
+pub use checkpoint::MigrationCheckpoint;
 pub use client_detection::{ClientDetector, DetectedClient, ClientInfo};
 pub use thunderbird::{ThunderbirdMigrator, ThunderbirdProfile};
-pub use outlook::{OutlookMigrator, OutlookProfile, PstReader};
+pub use outlook::{
+    OutlookMigrator, OutlookProfile, PstContact, PstFolder, PstFormat, PstMessage, PstReader,
+};
 pub use apple_mail::{AppleMailMigrator, AppleMailProfile};
-pub use gmail_takeout::{GmailTakeoutMigrator, TakeoutArchive};
+pub use gmail_takeout::{
+    GmailTakeoutMigrator, LabelPlacement, TakeoutArchive, TakeoutLabelMode, TakeoutLabelOptions,
+};
 pub use mutt::{MuttMigrator, MuttConfig};
 pub use evolution::{EvolutionMigrator, EvolutionProfile};
 pub use kmail::{KMailMigrator, KMailProfile};
 pub use migration_engine::{
     MigrationEngine, MigrationPlan, MigrationProgress, MigrationResult,
-    MigrationError, MigrationTask, MigrationStatus,
+    MigrationError, MigrationTask, MigrationStatus, ProgressCallback,
 };
 pub use migration_ui::{MigrationUI, MigrationAction};
 pub use migration_wizard::{MigrationWizard, WizardStep, WizardState};
@@ -117,6 +123,9 @@ pub struct MigrationConfig {
     pub batch_size: usize,
     pub max_concurrent_tasks: usize,
     pub timeout_seconds: u64,
+    /// How Gmail labels become folders when importing a Gmail Takeout archive
+    #[serde(default)]
+    pub takeout_labels: TakeoutLabelOptions,
     pub created_at: DateTime<Utc>,
 }
 
@@ -141,6 +150,7 @@ impl Default for MigrationConfig {
             batch_size: 100,
             max_concurrent_tasks: 4,
             timeout_seconds: 3600,
+            takeout_labels: TakeoutLabelOptions::default(),
             created_at: Utc::now(),
         }
     }
@@ -221,6 +231,8 @@ pub struct MigrationStatistics {
     // Overall statistics
     pub total_items_found: usize,
     pub total_items_migrated: usize,
+    /// Items an earlier, interrupted run had already migrated
+    pub total_items_resumed: usize,
     pub total_items_skipped: usize,
     pub total_items_failed: usize,
     pub total_size_bytes: u64,
@@ -242,6 +254,7 @@ pub struct MigrationStatistics {
 pub struct DataTypeStats {
     pub found: usize,
     pub migrated: usize,
+    pub resumed: usize,
     pub skipped: usize,
     pub failed: usize,
     pub size_bytes: u64,
@@ -0,0 +1,2116 @@
+//! Microsoft Outlook migration support
+//!
+//! This module reads Outlook personal folder files (`.pst`) directly, without
+//! Outlook or any external tools. Both the 32-bit ANSI format (Outlook 97-2002)
+//! and the 64-bit Unicode format (Outlook 2003 and later) are supported, with
+//! plain or compressibly encoded blocks. Folders, messages with their
+//! recipients and attachments, and contacts are read; the file is never written.
+//!
+//! A PST file has three layers, following [MS-PST]:
+//! - the node database: two B-trees mapping node IDs to blocks, and blocks to
+//!   file offsets
+//! - the lists, tables and properties layer: a heap inside each node holding a
+//!   property context (one object's properties) or a table context (rows)
+//! - the messaging layer: folders, messages, recipients and attachments built
+//!   from those properties and tables
+
+use crate::contacts::{Contact, ContactEmail, ContactPhone, ContactSource};
+use crate::email::{StoredAttachment, StoredMessage};
+use crate::migration::{
+    MigrationError, MigrationProgress, MigrationResult, MigrationStatus, ProgressCallback,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Outlook data file found on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlookProfile {
+    pub name: String,
+    pub path: PathBuf,
+    pub format: PstFormat,
+    pub size_bytes: u64,
+    pub folders: Vec<PstFolder>,
+}
+
+/// On-disk layout of a PST file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PstFormat {
+    /// 32-bit offsets, 8-bit strings (Outlook 97-2002)
+    Ansi,
+    /// 64-bit offsets, UTF-16 strings (Outlook 2003 and later)
+    Unicode,
+}
+
+/// How the data blocks of a PST file are encoded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PstEncryption {
+    None,
+    /// "Compressible encryption", a fixed byte substitution
+    Permute,
+}
+
+/// Folder in a PST file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PstFolder {
+    pub nid: u32,
+    pub name: String,
+    /// Names from the top of the folder tree, joined with `/`
+    pub path: String,
+    /// `IPF.Note` for mail, `IPF.Contact` for contacts and so on
+    pub container_class: Option<String>,
+    pub message_count: usize,
+    pub unread_count: usize,
+}
+
+impl PstFolder {
+    /// Whether the folder holds mail rather than contacts, calendar items or notes
+    pub fn is_mail(&self) -> bool {
+        self.container_class
+            .as_deref()
+            .is_none_or(|class| class.is_empty() || class.starts_with("IPF.Note"))
+    }
+
+    /// Whether the folder is an address book
+    pub fn is_contacts(&self) -> bool {
+        self.container_class
+            .as_deref()
+            .is_some_and(|class| class.starts_with("IPF.Contact"))
+    }
+}
+
+/// Message read from a PST file
+#[derive(Debug, Clone, Default)]
+pub struct PstMessage {
+    pub nid: u32,
+    pub message_class: String,
+    pub subject: String,
+    pub sender_name: Option<String>,
+    pub sender_address: Option<String>,
+    pub recipients: Vec<PstRecipient>,
+    pub date: Option<DateTime<Utc>>,
+    pub body_text: Option<String>,
+    pub body_html: Option<String>,
+    pub message_id: Option<String>,
+    pub in_reply_to: Option<String>,
+    pub references: Vec<String>,
+    pub is_read: bool,
+    pub is_draft: bool,
+    pub is_flagged: bool,
+    pub is_answered: bool,
+    /// 0 low, 1 normal, 2 high
+    pub importance: Option<i32>,
+    pub size: Option<u32>,
+    pub attachments: Vec<PstAttachment>,
+}
+
+impl PstMessage {
+    /// The message as RFC 822 text, used for messages attached to other messages
+    pub fn to_rfc822(&self) -> String {
+        let mailbox = |name: &Option<String>, address: &str| match name {
+            Some(name) if !name.is_empty() && name != address => format!("\"{}\" <{}>", name, address),
+            _ => address.to_string(),
+        };
+        let list = |kind: RecipientKind| {
+            self.recipients
+                .iter()
+                .filter(|recipient| recipient.kind == kind)
+                .map(|recipient| mailbox(&recipient.name, &recipient.address))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mut text = String::new();
+        if let Some(address) = &self.sender_address {
+            text.push_str(&format!("From: {}\r\n", mailbox(&self.sender_name, address)));
+        }
+        for (header, kind) in [("To", RecipientKind::To), ("Cc", RecipientKind::Cc)] {
+            let addresses = list(kind);
+            if !addresses.is_empty() {
+                text.push_str(&format!("{}: {}\r\n", header, addresses));
+            }
+        }
+        text.push_str(&format!("Subject: {}\r\n", self.subject));
+        if let Some(date) = self.date {
+            text.push_str(&format!("Date: {}\r\n", date.to_rfc2822()));
+        }
+        if let Some(message_id) = &self.message_id {
+            text.push_str(&format!("Message-ID: {}\r\n", message_id));
+        }
+        text.push_str("MIME-Version: 1.0\r\n");
+        match (&self.body_text, &self.body_html) {
+            (None, Some(html)) => {
+                text.push_str("Content-Type: text/html; charset=utf-8\r\n\r\n");
+                text.push_str(html);
+            }
+            (body, _) => {
+                text.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
+                text.push_str(body.as_deref().unwrap_or_default());
+            }
+        }
+        text
+    }
+}
+
+/// Recipient of a PST message
+#[derive(Debug, Clone, PartialEq)]
+pub struct PstRecipient {
+    pub kind: RecipientKind,
+    pub name: Option<String>,
+    pub address: String,
+}
+
+/// Recipient field a PST recipient belongs to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecipientKind {
+    To,
+    Cc,
+    Bcc,
+}
+
+/// Attachment of a PST message
+#[derive(Debug, Clone)]
+pub struct PstAttachment {
+    pub filename: String,
+    pub mime_type: Option<String>,
+    pub content_id: Option<String>,
+    pub is_inline: bool,
+    pub data: Vec<u8>,
+}
+
+/// Contact read from a PST file
+#[derive(Debug, Clone, Default)]
+pub struct PstContact {
+    pub nid: u32,
+    pub display_name: String,
+    pub given_name: Option<String>,
+    pub surname: Option<String>,
+    pub company: Option<String>,
+    pub job_title: Option<String>,
+    pub emails: Vec<String>,
+    pub business_phone: Option<String>,
+    pub home_phone: Option<String>,
+    pub mobile_phone: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// Main Outlook migrator
+pub struct OutlookMigrator {
+    detected_profiles: Vec<OutlookProfile>,
+    progress: Option<(Uuid, ProgressCallback)>,
+}
+
+impl OutlookMigrator {
+    /// Create a new Outlook migrator
+    pub fn new() -> MigrationResult<Self> {
+        Ok(Self {
+            detected_profiles: Vec::new(),
+            progress: None,
+        })
+    }
+
+    /// Report email migration progress to `callback` under `migration_id`
+    pub fn with_progress(mut self, migration_id: Uuid, callback: ProgressCallback) -> Self {
+        self.progress = Some((migration_id, callback));
+        self
+    }
+
+    /// Find PST files in the places Outlook keeps them
+    pub async fn detect_profiles(&mut self) -> MigrationResult<Vec<OutlookProfile>> {
+        let home_dir = dirs::home_dir().ok_or_else(|| {
+            MigrationError::SourceNotAccessible("Cannot find home directory".to_string())
+        })?;
+
+        let search_dirs = [
+            home_dir.join("Documents/Outlook Files"),
+            home_dir.join("AppData/Local/Microsoft/Outlook"),
+            home_dir.join("AppData/Roaming/Microsoft/Outlook"),
+            home_dir.join("Documents"),
+        ];
+
+        let mut profiles = Vec::new();
+        for dir in search_dirs {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let is_pst = path
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("pst"));
+                if is_pst && !profiles.iter().any(|p: &OutlookProfile| p.path == path) {
+                    profiles.push(Self::open_profile(path).await?);
+                }
+            }
+        }
+
+        self.detected_profiles = profiles;
+        Ok(self.detected_profiles.clone())
+    }
+
+    /// Open a PST file chosen by the user
+    pub async fn add_pst_file(&mut self, path: &Path) -> MigrationResult<OutlookProfile> {
+        let profile = Self::open_profile(path.to_path_buf()).await?;
+        self.detected_profiles.push(profile.clone());
+        Ok(profile)
+    }
+
+    /// Get a specific profile by name
+    pub fn get_profile(&self, name: &str) -> Option<&OutlookProfile> {
+        self.detected_profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Stream the mail of a PST file as stored messages
+    ///
+    /// Messages are read on a blocking thread and handed over one at a time,
+    /// so even very large files are never held in memory at once. A message
+    /// that cannot be read is logged and skipped; an `Err` means the file
+    /// itself could not be read and ends the stream.
+    pub fn stream_emails(
+        &self,
+        profile: &OutlookProfile,
+        target_account_id: &str,
+    ) -> mpsc::Receiver<MigrationResult<StoredMessage>> {
+        let (sender, receiver) = mpsc::channel(64);
+        let path = profile.path.clone();
+        let account_id = target_account_id.to_string();
+        let progress = self.progress.clone();
+
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = read_emails(&path, &account_id, progress, &sender) {
+                let _ = sender.blocking_send(Err(e));
+            }
+        });
+
+        receiver
+    }
+
+    /// Migrate emails from a PST file
+    pub async fn migrate_emails(
+        &self,
+        profile: &OutlookProfile,
+        target_account_id: &str,
+    ) -> MigrationResult<Vec<StoredMessage>> {
+        let mut receiver = self.stream_emails(profile, target_account_id);
+        let mut messages = Vec::new();
+
+        while let Some(message) = receiver.recv().await {
+            messages.push(message?);
+        }
+
+        Ok(messages)
+    }
+
+    /// Migrate contacts from every contacts folder of a PST file
+    pub async fn migrate_contacts(&self, profile: &OutlookProfile) -> MigrationResult<Vec<Contact>> {
+        let path = profile.path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mut reader = PstReader::open(&path)?;
+            let mut contacts = Vec::new();
+
+            for folder in reader.folders()?.iter().filter(|folder| folder.is_contacts()) {
+                for nid in reader.folder_messages(folder.nid)? {
+                    match reader.read_contact(nid) {
+                        Ok(Some(contact)) => contacts.push(convert_contact(&contact)),
+                        Ok(None) => {}
+                        Err(e) => tracing::warn!("Skipping PST contact {:#x}: {}", nid, e),
+                    }
+                }
+            }
+
+            Ok(contacts)
+        })
+        .await
+        .map_err(|e| MigrationError::ConversionFailed(e.to_string()))?
+    }
+
+    /// Read the header and folder list of a PST file
+    async fn open_profile(path: PathBuf) -> MigrationResult<OutlookProfile> {
+        tokio::task::spawn_blocking(move || {
+            let size_bytes = std::fs::metadata(&path)?.len();
+            let mut reader = PstReader::open(&path)?;
+            let folders = reader.folders()?;
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Outlook".to_string());
+
+            Ok(OutlookProfile {
+                name,
+                format: reader.format(),
+                path,
+                size_bytes,
+                folders,
+            })
+        })
+        .await
+        .map_err(|e| MigrationError::ConversionFailed(e.to_string()))?
+    }
+}
+
+/// Read every mail folder of a PST file into `sender`, reporting progress
+fn read_emails(
+    path: &Path,
+    account_id: &str,
+    progress: Option<(Uuid, ProgressCallback)>,
+    sender: &mpsc::Sender<MigrationResult<StoredMessage>>,
+) -> MigrationResult<()> {
+    let mut reader = PstReader::open(path)?;
+    let folders: Vec<PstFolder> = reader.folders()?.into_iter().filter(PstFolder::is_mail).collect();
+    let items_total: usize = folders.iter().map(|folder| folder.message_count).sum();
+    let started = Instant::now();
+    let mut items_processed = 0;
+    let mut bytes_processed = 0u64;
+
+    let report = |items_processed: usize, bytes_processed: u64, current: Option<&str>, status| {
+        let Some((migration_id, callback)) = &progress else {
+            return;
+        };
+        let elapsed = started.elapsed().as_secs_f64();
+        let speed = if elapsed > 0.0 { items_processed as f64 / elapsed } else { 0.0 };
+        let remaining = items_total.saturating_sub(items_processed);
+        callback(MigrationProgress {
+            migration_id: *migration_id,
+            overall_progress: if items_total == 0 {
+                1.0
+            } else {
+                (items_processed as f64 / items_total as f64).min(1.0)
+            },
+            current_task: current.map(|folder| format!("Importing {}", folder)),
+            tasks_completed: 0,
+            tasks_total: 1,
+            items_processed,
+            items_total,
+            bytes_processed,
+            bytes_total: 0,
+            elapsed_seconds: elapsed as u64,
+            estimated_remaining_seconds: (speed > 0.0).then(|| (remaining as f64 / speed) as u64),
+            current_speed_items_per_sec: speed,
+            current_speed_bytes_per_sec: if elapsed > 0.0 { bytes_processed as f64 / elapsed } else { 0.0 },
+            status,
+            last_updated: Utc::now(),
+        });
+    };
+
+    for folder in &folders {
+        let folder_name = target_folder_name(&folder.path);
+        for nid in reader.folder_messages(folder.nid)? {
+            match reader.read_message(nid) {
+                Ok(message) => {
+                    let message = convert_message(&message, account_id, &folder_name);
+                    bytes_processed += u64::from(message.size.unwrap_or_default());
+                    if sender.blocking_send(Ok(message)).is_err() {
+                        return Ok(()); // Receiver gone, the import was cancelled
+                    }
+                }
+                Err(e) => tracing::warn!("Skipping PST message {:#x} in {}: {}", nid, folder.path, e),
+            }
+            items_processed += 1;
+            if items_processed % 50 == 0 {
+                report(items_processed, bytes_processed, Some(folder.path.as_str()), MigrationStatus::Running);
+            }
+        }
+    }
+
+    report(items_processed, bytes_processed, None, MigrationStatus::Completed);
+    Ok(())
+}
+
+/// Comunicado folder name for a PST folder path
+fn target_folder_name(path: &str) -> String {
+    match path.split_once('/') {
+        Some((top, rest)) if top.eq_ignore_ascii_case("inbox") => format!("INBOX/{}", rest),
+        _ if path.eq_ignore_ascii_case("inbox") => "INBOX".to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// Convert a PST message to a StoredMessage
+pub fn convert_message(message: &PstMessage, account_id: &str, folder_name: &str) -> StoredMessage {
+    let addresses = |kind: RecipientKind| -> Vec<String> {
+        message
+            .recipients
+            .iter()
+            .filter(|recipient| recipient.kind == kind)
+            .map(|recipient| recipient.address.clone())
+            .collect()
+    };
+
+    let mut flags = Vec::new();
+    if message.is_read {
+        flags.push("\\Seen".to_string());
+    }
+    if message.is_answered {
+        flags.push("\\Answered".to_string());
+    }
+    if message.is_flagged {
+        flags.push("\\Flagged".to_string());
+    }
+    if message.is_draft {
+        flags.push("\\Draft".to_string());
+    }
+
+    let attachments = message
+        .attachments
+        .iter()
+        .map(|attachment| StoredAttachment {
+            id: Uuid::new_v4().to_string(),
+            filename: attachment.filename.clone(),
+            content_type: attachment
+                .mime_type
+                .clone()
+                .unwrap_or_else(|| "application/octet-stream".to_string()),
+            size: attachment.data.len() as u32,
+            content_id: attachment.content_id.clone(),
+            is_inline: attachment.is_inline,
+            data: Some(attachment.data.clone()),
+            file_path: None,
+        })
+        .collect();
+
+    let now = Utc::now();
+    StoredMessage {
+        id: Uuid::new_v4(),
+        account_id: account_id.to_string(),
+        folder_name: folder_name.to_string(),
+        imap_uid: 0,
+        message_id: message.message_id.clone(),
+        thread_id: None,
+        in_reply_to: message.in_reply_to.clone(),
+        references: message.references.clone(),
+        subject: message.subject.clone(),
+        from_addr: message.sender_address.clone().unwrap_or_default(),
+        from_name: message.sender_name.clone(),
+        to_addrs: addresses(RecipientKind::To),
+        cc_addrs: addresses(RecipientKind::Cc),
+        bcc_addrs: addresses(RecipientKind::Bcc),
+        reply_to: None,
+        date: message.date.unwrap_or(now),
+        body_text: message.body_text.clone(),
+        body_html: message.body_html.clone(),
+        attachments,
+        is_draft: message.is_draft,
+        flags,
+        labels: Vec::new(),
+        size: message.size,
+        priority: match message.importance {
+            Some(2) => Some("high".to_string()),
+            Some(0) => Some("low".to_string()),
+            _ => None,
+        },
+        created_at: now,
+        updated_at: now,
+        last_synced: now,
+        sync_version: 1,
+        is_deleted: false,
+        snoozed_until: None,
+        inbox_tab: None,
+    }
+}
+
+/// Convert a PST contact to a Contact
+pub fn convert_contact(contact: &PstContact) -> Contact {
+    let mut converted = Contact::new(
+        format!("pst:{:x}", contact.nid),
+        ContactSource::Local,
+        contact.display_name.clone(),
+    );
+    converted.first_name = contact.given_name.clone();
+    converted.last_name = contact.surname.clone();
+    converted.company = contact.company.clone();
+    converted.job_title = contact.job_title.clone();
+    converted.notes = contact.notes.clone();
+
+    for (i, address) in contact.emails.iter().enumerate() {
+        converted.emails.push(if i == 0 {
+            ContactEmail::primary(address.clone(), "other".to_string())
+        } else {
+            ContactEmail::new(address.clone(), "other".to_string())
+        });
+    }
+    let phones = [
+        (&contact.mobile_phone, "mobile"),
+        (&contact.business_phone, "work"),
+        (&contact.home_phone, "home"),
+    ];
+    for (number, label) in phones {
+        if let Some(number) = number {
+            converted.phones.push(if converted.phones.is_empty() {
+                ContactPhone::primary(number.clone(), label.to_string())
+            } else {
+                ContactPhone::new(number.clone(), label.to_string())
+            });
+        }
+    }
+
+    converted
+}
+
+// Special node IDs
+const NID_MESSAGE_STORE: u32 = 0x21;
+const NID_NAME_TO_ID_MAP: u32 = 0x61;
+const NID_ROOT_FOLDER: u32 = 0x122;
+const NID_ATTACHMENT_TABLE: u32 = 0x671;
+const NID_RECIPIENT_TABLE: u32 = 0x692;
+
+// Node ID types, the low five bits of a node ID
+const NID_TYPE_HID: u32 = 0x00;
+const NID_TYPE_NORMAL_FOLDER: u32 = 0x02;
+const NID_TYPE_HIERARCHY_TABLE: u32 = 0x0D;
+const NID_TYPE_CONTENTS_TABLE: u32 = 0x0E;
+
+// Property types
+const PT_SHORT: u16 = 0x0002;
+const PT_LONG: u16 = 0x0003;
+const PT_FLOAT: u16 = 0x0004;
+const PT_DOUBLE: u16 = 0x0005;
+const PT_CURRENCY: u16 = 0x0006;
+const PT_APPTIME: u16 = 0x0007;
+const PT_ERROR: u16 = 0x000A;
+const PT_BOOLEAN: u16 = 0x000B;
+const PT_OBJECT: u16 = 0x000D;
+const PT_LONGLONG: u16 = 0x0014;
+const PT_STRING8: u16 = 0x001E;
+const PT_UNICODE: u16 = 0x001F;
+const PT_SYSTIME: u16 = 0x0040;
+const PT_BINARY: u16 = 0x0102;
+
+// Property IDs
+const PR_IMPORTANCE: u16 = 0x0017;
+const PR_MESSAGE_CLASS: u16 = 0x001A;
+const PR_SUBJECT: u16 = 0x0037;
+const PR_CLIENT_SUBMIT_TIME: u16 = 0x0039;
+const PR_SENT_REPRESENTING_NAME: u16 = 0x0042;
+const PR_SENT_REPRESENTING_EMAIL_ADDRESS: u16 = 0x0065;
+const PR_SENDER_NAME: u16 = 0x0C1A;
+const PR_RECIPIENT_TYPE: u16 = 0x0C15;
+const PR_SENDER_EMAIL_ADDRESS: u16 = 0x0C1F;
+const PR_MESSAGE_DELIVERY_TIME: u16 = 0x0E06;
+const PR_MESSAGE_FLAGS: u16 = 0x0E07;
+const PR_MESSAGE_SIZE: u16 = 0x0E08;
+const PR_BODY: u16 = 0x1000;
+const PR_HTML: u16 = 0x1013;
+const PR_INTERNET_MESSAGE_ID: u16 = 0x1035;
+const PR_INTERNET_REFERENCES: u16 = 0x1039;
+const PR_IN_REPLY_TO_ID: u16 = 0x1042;
+const PR_LAST_VERB_EXECUTED: u16 = 0x1081;
+const PR_FLAG_STATUS: u16 = 0x1090;
+const PR_DISPLAY_NAME: u16 = 0x3001;
+const PR_EMAIL_ADDRESS: u16 = 0x3003;
+const PR_CREATION_TIME: u16 = 0x3007;
+const PR_IPM_SUBTREE_ENTRYID: u16 = 0x35E0;
+const PR_CONTENT_COUNT: u16 = 0x3602;
+const PR_CONTENT_UNREAD: u16 = 0x3603;
+const PR_CONTAINER_CLASS: u16 = 0x3613;
+const PR_ATTACH_DATA: u16 = 0x3701;
+const PR_ATTACH_FILENAME: u16 = 0x3704;
+const PR_ATTACH_METHOD: u16 = 0x3705;
+const PR_ATTACH_LONG_FILENAME: u16 = 0x3707;
+const PR_ATTACH_MIME_TAG: u16 = 0x370E;
+const PR_ATTACH_CONTENT_ID: u16 = 0x3712;
+const PR_ATTACH_FLAGS: u16 = 0x3714;
+const PR_SMTP_ADDRESS: u16 = 0x39FE;
+const PR_GIVEN_NAME: u16 = 0x3A06;
+const PR_BUSINESS_TELEPHONE_NUMBER: u16 = 0x3A08;
+const PR_HOME_TELEPHONE_NUMBER: u16 = 0x3A09;
+const PR_SURNAME: u16 = 0x3A11;
+const PR_COMPANY_NAME: u16 = 0x3A16;
+const PR_TITLE: u16 = 0x3A17;
+const PR_MOBILE_TELEPHONE_NUMBER: u16 = 0x3A1C;
+const PR_INTERNET_CPID: u16 = 0x3FDE;
+const PR_SENDER_SMTP_ADDRESS: u16 = 0x5D01;
+const PR_SENT_REPRESENTING_SMTP_ADDRESS: u16 = 0x5D02;
+const PR_ATTACHMENT_HIDDEN: u16 = 0x7FFE;
+
+// Name-to-ID map streams
+const PR_NAMEID_STREAM_GUID: u16 = 0x0002;
+const PR_NAMEID_STREAM_ENTRY: u16 = 0x0003;
+
+/// PSETID_Address, the property set of contact addresses
+const PSETID_ADDRESS: [u8; 16] = [
+    0x04, 0x20, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+/// PidLidEmail1EmailAddress, PidLidEmail2EmailAddress and PidLidEmail3EmailAddress
+const LID_EMAIL_ADDRESSES: [u32; 3] = [0x8083, 0x8093, 0x80A3];
+
+const ATTACH_EMBEDDED_MSG: i32 = 5;
+const ATTACH_MHTML_REF: i32 = 0x4;
+const MSGFLAG_READ: i32 = 0x1;
+const MSGFLAG_UNSENT: i32 = 0x8;
+const FLAG_STATUS_FLAGGED: i32 = 2;
+const VERB_REPLY_TO_SENDER: i32 = 102;
+const VERB_REPLY_TO_ALL: i32 = 103;
+
+/// B-trees and data trees deeper than this are treated as corrupt
+const MAX_TREE_DEPTH: usize = 16;
+
+/// Byte substitution that decodes compressibly encoded blocks (`mpbbI` in [MS-PST])
+const PERMUTE_DECODE: [u8; 256] = [
+    0x47, 0xf1, 0xb4, 0xe6, 0x0b, 0x6a, 0x72, 0x48, 0x85, 0x4e, 0x9e, 0xeb, 0xe2, 0xf8, 0x94, 0x53,
+    0xe0, 0xbb, 0xa0, 0x02, 0xe8, 0x5a, 0x09, 0xab, 0xdb, 0xe3, 0xba, 0xc6, 0x7c, 0xc3, 0x10, 0xdd,
+    0x39, 0x05, 0x96, 0x30, 0xf5, 0x37, 0x60, 0x82, 0x8c, 0xc9, 0x13, 0x4a, 0x6b, 0x1d, 0xf3, 0xfb,
+    0x8f, 0x26, 0x97, 0xca, 0x91, 0x17, 0x01, 0xc4, 0x32, 0x2d, 0x6e, 0x31, 0x95, 0xff, 0xd9, 0x23,
+    0xd1, 0x00, 0x5e, 0x79, 0xdc, 0x44, 0x3b, 0x1a, 0x28, 0xc5, 0x61, 0x57, 0x20, 0x90, 0x3d, 0x83,
+    0xb9, 0x43, 0xbe, 0x67, 0xd2, 0x46, 0x42, 0x76, 0xc0, 0x6d, 0x5b, 0x7e, 0xb2, 0x0f, 0x16, 0x29,
+    0x3c, 0xa9, 0x03, 0x54, 0x0d, 0xda, 0x5d, 0xdf, 0xf6, 0xb7, 0xc7, 0x62, 0xcd, 0x8d, 0x06, 0xd3,
+    0x69, 0x5c, 0x86, 0xd6, 0x14, 0xf7, 0xa5, 0x66, 0x75, 0xac, 0xb1, 0xe9, 0x45, 0x21, 0x70, 0x0c,
+    0x87, 0x9f, 0x74, 0xa4, 0x22, 0x4c, 0x6f, 0xbf, 0x1f, 0x56, 0xaa, 0x2e, 0xb3, 0x78, 0x33, 0x50,
+    0xb0, 0xa3, 0x92, 0xbc, 0xcf, 0x19, 0x1c, 0xa7, 0x63, 0xcb, 0x1e, 0x4d, 0x3e, 0x4b, 0x1b, 0x9b,
+    0x4f, 0xe7, 0xf0, 0xee, 0xad, 0x3a, 0xb5, 0x59, 0x04, 0xea, 0x40, 0x55, 0x25, 0x51, 0xe5, 0x7a,
+    0x89, 0x38, 0x68, 0x52, 0x7b, 0xfc, 0x27, 0xae, 0xd7, 0xbd, 0xfa, 0x07, 0xf4, 0xcc, 0x8e, 0x5f,
+    0xef, 0x35, 0x9c, 0x84, 0x2b, 0x15, 0xd5, 0x77, 0x34, 0x49, 0xb6, 0x12, 0x0a, 0x7f, 0x71, 0x88,
+    0xfd, 0x9d, 0x18, 0x41, 0x7d, 0x93, 0xd8, 0x58, 0x2c, 0xce, 0xfe, 0x24, 0xaf, 0xde, 0xb8, 0x36,
+    0xc8, 0xa1, 0x80, 0xa6, 0x99, 0x98, 0xa8, 0x2f, 0x0e, 0x81, 0x65, 0x73, 0xe4, 0xc2, 0xa2, 0x8a,
+    0xd4, 0xe1, 0x11, 0xd0, 0x08, 0x8b, 0x2a, 0xf2, 0xed, 0x9a, 0x64, 0x3f, 0xc1, 0x6c, 0xf9, 0xec,
+];
+
+fn corrupt(what: impl std::fmt::Display) -> MigrationError {
+    MigrationError::ConversionFailed(format!("Corrupt PST file: {}", what))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> MigrationResult<u16> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| corrupt(format!("read past end at {}", offset)))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> MigrationResult<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| corrupt(format!("read past end at {}", offset)))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> MigrationResult<u64> {
+    data.get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| corrupt(format!("read past end at {}", offset)))
+}
+
+/// Data and subnode blocks of a node
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    data_bid: u64,
+    sub_bid: u64,
+}
+
+/// Raw property value with its type
+#[derive(Debug, Clone)]
+struct PropValue {
+    prop_type: u16,
+    data: Vec<u8>,
+}
+
+/// Properties of one object, from a property context or a table row
+#[derive(Debug, Clone, Default)]
+struct PropertyBag {
+    values: HashMap<u16, PropValue>,
+}
+
+impl PropertyBag {
+    fn string(&self, id: u16) -> Option<String> {
+        let value = self.values.get(&id)?;
+        let text = match value.prop_type {
+            PT_UNICODE => {
+                let units: Vec<u16> = value
+                    .data
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            PT_STRING8 | PT_BINARY => decode_8bit(&value.data),
+            _ => return None,
+        };
+        let text = text.trim_end_matches('\0').to_string();
+        (!text.is_empty()).then_some(text)
+    }
+
+    fn int(&self, id: u16) -> Option<i32> {
+        let value = self.values.get(&id)?;
+        match value.prop_type {
+            PT_SHORT => read_u16(&value.data, 0).ok().map(|v| v as i16 as i32),
+            PT_LONG | PT_ERROR => read_u32(&value.data, 0).ok().map(|v| v as i32),
+            _ => None,
+        }
+    }
+
+    fn bool(&self, id: u16) -> bool {
+        self.values
+            .get(&id)
+            .is_some_and(|value| value.prop_type == PT_BOOLEAN && value.data.first().is_some_and(|&b| b != 0))
+    }
+
+    fn time(&self, id: u16) -> Option<DateTime<Utc>> {
+        let value = self.values.get(&id)?;
+        if value.prop_type != PT_SYSTIME {
+            return None;
+        }
+        filetime_to_utc(read_u64(&value.data, 0).ok()?)
+    }
+
+    fn binary(&self, id: u16) -> Option<&[u8]> {
+        self.values
+            .get(&id)
+            .filter(|value| value.prop_type == PT_BINARY)
+            .map(|value| value.data.as_slice())
+    }
+}
+
+/// 8-bit PST strings as UTF-8 when valid, otherwise as Latin-1
+fn decode_8bit(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&b| b as char).collect(),
+    }
+}
+
+/// Windows FILETIME (100 ns ticks since 1601) as a UTC time
+fn filetime_to_utc(filetime: u64) -> Option<DateTime<Utc>> {
+    const UNIX_EPOCH_TICKS: u64 = 116_444_736_000_000_000;
+    if filetime < UNIX_EPOCH_TICKS {
+        return None;
+    }
+    let ticks = filetime - UNIX_EPOCH_TICKS;
+    Utc.timestamp_opt((ticks / 10_000_000) as i64, ((ticks % 10_000_000) * 100) as u32)
+        .single()
+}
+
+/// Heap-on-node: the allocations inside a node's data blocks
+struct Heap {
+    blocks: Vec<Vec<u8>>,
+    client_sig: u8,
+    user_root: u32,
+}
+
+impl Heap {
+    const SIGNATURE: u8 = 0xEC;
+    const PROPERTY_CONTEXT: u8 = 0xBC;
+    const TABLE_CONTEXT: u8 = 0x7C;
+    const BTREE: u8 = 0xB5;
+
+    fn new(blocks: Vec<Vec<u8>>) -> MigrationResult<Self> {
+        let first = blocks.first().ok_or_else(|| corrupt("empty heap"))?;
+        if first.get(2) != Some(&Self::SIGNATURE) {
+            return Err(corrupt("bad heap signature"));
+        }
+        Ok(Self {
+            client_sig: first[3],
+            user_root: read_u32(first, 4)?,
+            blocks,
+        })
+    }
+
+    /// The allocation a heap ID points at
+    fn item(&self, hid: u32) -> MigrationResult<&[u8]> {
+        if hid & 0x1F != NID_TYPE_HID {
+            return Err(corrupt(format!("{:#x} is not a heap ID", hid)));
+        }
+        let index = ((hid >> 5) & 0x7FF) as usize;
+        let block = self
+            .blocks
+            .get((hid >> 16) as usize)
+            .ok_or_else(|| corrupt(format!("heap ID {:#x} past last block", hid)))?;
+        let page_map = read_u16(block, 0)? as usize;
+        let allocations = read_u16(block, page_map)? as usize;
+        if index == 0 || index > allocations {
+            return Err(corrupt(format!("heap ID {:#x} past last allocation", hid)));
+        }
+        let start = read_u16(block, page_map + 4 + (index - 1) * 2)? as usize;
+        let end = read_u16(block, page_map + 4 + index * 2)? as usize;
+        block
+            .get(start..end)
+            .ok_or_else(|| corrupt(format!("heap ID {:#x} out of bounds", hid)))
+    }
+
+    /// All leaf records of the B-tree-on-heap rooted at `hid`, as (key, value)
+    fn btree_records(&self, hid: u32) -> MigrationResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        let header = self.item(hid)?;
+        if header.first() != Some(&Self::BTREE) || header.len() < 8 {
+            return Err(corrupt("bad B-tree-on-heap header"));
+        }
+        let key_size = header[1] as usize;
+        let value_size = header[2] as usize;
+        let levels = header[3] as usize;
+        let root = read_u32(header, 4)?;
+
+        let mut records = Vec::new();
+        if root != 0 {
+            self.collect_records(root, levels, key_size, value_size, &mut records)?;
+        }
+        Ok(records)
+    }
+
+    fn collect_records(
+        &self,
+        hid: u32,
+        level: usize,
+        key_size: usize,
+        value_size: usize,
+        records: &mut Vec<(Vec<u8>, Vec<u8>)>,
+    ) -> MigrationResult<()> {
+        if level > MAX_TREE_DEPTH || key_size == 0 {
+            return Err(corrupt("B-tree-on-heap too deep"));
+        }
+        let data = self.item(hid)?;
+        if level == 0 {
+            for record in data.chunks_exact(key_size + value_size) {
+                let (key, value) = record.split_at(key_size);
+                records.push((key.to_vec(), value.to_vec()));
+            }
+        } else {
+            for record in data.chunks_exact(key_size + 4) {
+                let child = read_u32(record, key_size)?;
+                self.collect_records(child, level - 1, key_size, value_size, records)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read-only access to a PST file
+pub struct PstReader<R = BufReader<File>> {
+    source: R,
+    format: PstFormat,
+    encryption: PstEncryption,
+    nbt_root: u64,
+    bbt_root: u64,
+    /// Intermediate B-tree pages, which every lookup walks through
+    page_cache: HashMap<u64, Vec<u8>>,
+    /// Named property IDs by (property set, LID), loaded on first use
+    named_properties: Option<HashMap<([u8; 16], u32), u16>>,
+}
+
+impl PstReader {
+    /// Open a PST file
+    pub fn open(path: &Path) -> MigrationResult<Self> {
+        let file = File::open(path).map_err(|e| {
+            MigrationError::SourceNotAccessible(format!("{}: {}", path.display(), e))
+        })?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read + Seek> PstReader<R> {
+    const PAGE_SIZE: usize = 512;
+    const PAGE_TYPE_BBT: u8 = 0x80;
+    const PAGE_TYPE_NBT: u8 = 0x81;
+
+    /// Read the header of a PST file from `source`
+    pub fn new(mut source: R) -> MigrationResult<Self> {
+        let mut header = [0u8; 564];
+        source.seek(SeekFrom::Start(0))?;
+        source
+            .read_exact(&mut header)
+            .map_err(|_| MigrationError::ConversionFailed("File is too short to be a PST file".to_string()))?;
+
+        if &header[0..4] != b"!BDN" || &header[8..10] != b"SM" {
+            return Err(MigrationError::ConversionFailed("Not a PST file".to_string()));
+        }
+
+        let format = match u16::from_le_bytes([header[10], header[11]]) {
+            14 | 15 => PstFormat::Ansi,
+            36 => {
+                return Err(MigrationError::ConversionFailed(
+                    "PST files with 4K pages (OST caches) are not supported".to_string(),
+                ))
+            }
+            version if version >= 23 => PstFormat::Unicode,
+            version => {
+                return Err(MigrationError::ConversionFailed(format!(
+                    "Unknown PST version {}",
+                    version
+                )))
+            }
+        };
+
+        let (crypt_offset, nbt_root, bbt_root) = match format {
+            PstFormat::Ansi => (461, read_u32(&header, 188)? as u64, read_u32(&header, 196)? as u64),
+            PstFormat::Unicode => (513, read_u64(&header, 224)?, read_u64(&header, 240)?),
+        };
+        let encryption = match header[crypt_offset] {
+            0x00 => PstEncryption::None,
+            0x01 => PstEncryption::Permute,
+            0x02 => {
+                return Err(MigrationError::ConversionFailed(
+                    "PST files with high encryption are not supported".to_string(),
+                ))
+            }
+            other => {
+                return Err(MigrationError::ConversionFailed(format!(
+                    "Unknown PST encryption {:#x}",
+                    other
+                )))
+            }
+        };
+
+        Ok(Self {
+            source,
+            format,
+            encryption,
+            nbt_root,
+            bbt_root,
+            page_cache: HashMap::new(),
+            named_properties: None,
+        })
+    }
+
+    /// Format of the file
+    pub fn format(&self) -> PstFormat {
+        self.format
+    }
+
+    /// Block encoding of the file
+    pub fn encryption(&self) -> PstEncryption {
+        self.encryption
+    }
+
+    /// All folders below the top of the personal folders, parents before children
+    pub fn folders(&mut self) -> MigrationResult<Vec<PstFolder>> {
+        let top = self.ipm_subtree().unwrap_or(NID_ROOT_FOLDER);
+        let mut folders = Vec::new();
+        self.collect_folders(top, "", 0, &mut folders)?;
+        Ok(folders)
+    }
+
+    /// Node IDs of the messages in a folder, in contents table order
+    pub fn folder_messages(&mut self, folder_nid: u32) -> MigrationResult<Vec<u32>> {
+        self.table_row_ids((folder_nid & !0x1F) | NID_TYPE_CONTENTS_TABLE)
+    }
+
+    /// Read a message with its recipients and attachments
+    pub fn read_message(&mut self, nid: u32) -> MigrationResult<PstMessage> {
+        let node = self.node(nid)?;
+        self.read_message_node(nid, node, 0)
+    }
+
+    /// Read a contact, or `None` when the item is not a contact
+    pub fn read_contact(&mut self, nid: u32) -> MigrationResult<Option<PstContact>> {
+        let node = self.node(nid)?;
+        let (props, _) = self.read_properties(node)?;
+        let class = props.string(PR_MESSAGE_CLASS).unwrap_or_default();
+        if !class.starts_with("IPM.Contact") {
+            return Ok(None);
+        }
+
+        let mut emails = Vec::new();
+        for lid in LID_EMAIL_ADDRESSES {
+            let Some(id) = self.named_property(PSETID_ADDRESS, lid)? else {
+                continue;
+            };
+            // Exchange addresses are X.500 paths rather than SMTP addresses
+            if let Some(address) = props.string(id).filter(|a| a.contains('@') && !a.starts_with('/')) {
+                emails.push(address);
+            }
+        }
+
+        let given_name = props.string(PR_GIVEN_NAME);
+        let surname = props.string(PR_SURNAME);
+        let display_name = props.string(PR_DISPLAY_NAME).unwrap_or_else(|| {
+            [given_name.as_deref(), surname.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+
+        Ok(Some(PstContact {
+            nid,
+            display_name,
+            given_name,
+            surname,
+            company: props.string(PR_COMPANY_NAME),
+            job_title: props.string(PR_TITLE),
+            emails,
+            business_phone: props.string(PR_BUSINESS_TELEPHONE_NUMBER),
+            home_phone: props.string(PR_HOME_TELEPHONE_NUMBER),
+            mobile_phone: props.string(PR_MOBILE_TELEPHONE_NUMBER),
+            notes: props.string(PR_BODY),
+        }))
+    }
+
+    /// Node ID of the IPM subtree ("Top of Personal Folders") from the message store
+    fn ipm_subtree(&mut self) -> Option<u32> {
+        let node = self.node(NID_MESSAGE_STORE).ok()?;
+        let (props, _) = self.read_properties(node).ok()?;
+        // Entry IDs are 4 flag bytes, a 16-byte provider UID and the node ID
+        let entry_id = props.binary(PR_IPM_SUBTREE_ENTRYID)?;
+        read_u32(entry_id, 20).ok()
+    }
+
+    fn collect_folders(
+        &mut self,
+        nid: u32,
+        parent_path: &str,
+        depth: usize,
+        folders: &mut Vec<PstFolder>,
+    ) -> MigrationResult<()> {
+        if depth > MAX_TREE_DEPTH * 4 {
+            return Err(corrupt("folder tree too deep"));
+        }
+
+        for child in self.table_row_ids((nid & !0x1F) | NID_TYPE_HIERARCHY_TABLE)? {
+            // Search folders only hold links to messages stored elsewhere
+            if child & 0x1F != NID_TYPE_NORMAL_FOLDER {
+                continue;
+            }
+            let node = self.node(child)?;
+            let (props, _) = self.read_properties(node)?;
+            let name = props
+                .string(PR_DISPLAY_NAME)
+                .unwrap_or_else(|| format!("Folder {:x}", child))
+                .replace('/', "-");
+            let path = if parent_path.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", parent_path, name)
+            };
+
+            folders.push(PstFolder {
+                nid: child,
+                name,
+                path: path.clone(),
+                container_class: props.string(PR_CONTAINER_CLASS),
+                message_count: props.int(PR_CONTENT_COUNT).unwrap_or_default().max(0) as usize,
+                unread_count: props.int(PR_CONTENT_UNREAD).unwrap_or_default().max(0) as usize,
+            });
+            self.collect_folders(child, &path, depth + 1, folders)?;
+        }
+
+        Ok(())
+    }
+
+    /// Row IDs of a top-level table node, empty when the node does not exist
+    fn table_row_ids(&mut self, nid: u32) -> MigrationResult<Vec<u32>> {
+        let Some(node) = self.find_node(nid)? else {
+            return Ok(Vec::new());
+        };
+        let rows = self.read_table(node, &HashMap::new())?;
+        Ok(rows.into_iter().map(|(row_id, _)| row_id).collect())
+    }
+
+    fn read_message_node(&mut self, nid: u32, node: Node, depth: usize) -> MigrationResult<PstMessage> {
+        if depth > 4 {
+            return Err(corrupt("attached messages nested too deep"));
+        }
+        let (props, subnodes) = self.read_properties(node)?;
+
+        let mut message = PstMessage {
+            nid,
+            message_class: props.string(PR_MESSAGE_CLASS).unwrap_or_default(),
+            subject: props.string(PR_SUBJECT).map(strip_subject_prefix).unwrap_or_default(),
+            sender_name: props
+                .string(PR_SENDER_NAME)
+                .or_else(|| props.string(PR_SENT_REPRESENTING_NAME)),
+            sender_address: [
+                PR_SENDER_SMTP_ADDRESS,
+                PR_SENT_REPRESENTING_SMTP_ADDRESS,
+                PR_SENDER_EMAIL_ADDRESS,
+                PR_SENT_REPRESENTING_EMAIL_ADDRESS,
+            ]
+            .into_iter()
+            .filter_map(|id| props.string(id))
+            .find(|address| address.contains('@')),
+            date: props
+                .time(PR_MESSAGE_DELIVERY_TIME)
+                .or_else(|| props.time(PR_CLIENT_SUBMIT_TIME))
+                .or_else(|| props.time(PR_CREATION_TIME)),
+            body_text: props.string(PR_BODY),
+            body_html: props.string(PR_HTML),
+            message_id: props.string(PR_INTERNET_MESSAGE_ID),
+            in_reply_to: props.string(PR_IN_REPLY_TO_ID),
+            references: props
+                .string(PR_INTERNET_REFERENCES)
+                .map(|refs| refs.split_whitespace().map(str::to_string).collect())
+                .unwrap_or_default(),
+            importance: props.int(PR_IMPORTANCE),
+            size: props.int(PR_MESSAGE_SIZE).map(|size| size.max(0) as u32),
+            ..PstMessage::default()
+        };
+
+        if props.values.get(&PR_HTML).is_some_and(|v| v.prop_type == PT_BINARY)
+            && props.int(PR_INTERNET_CPID) == Some(1200)
+        {
+            // UTF-16 HTML is stored as binary with code page 1200
+            message.body_html = props.values.get(&PR_HTML).map(|value| {
+                let units: Vec<u16> = value
+                    .data
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16_lossy(&units)
+            });
+        }
+
+        let flags = props.int(PR_MESSAGE_FLAGS).unwrap_or_default();
+        message.is_read = flags & MSGFLAG_READ != 0;
+        message.is_draft = flags & MSGFLAG_UNSENT != 0;
+        message.is_flagged = props.int(PR_FLAG_STATUS) == Some(FLAG_STATUS_FLAGGED);
+        message.is_answered = matches!(
+            props.int(PR_LAST_VERB_EXECUTED),
+            Some(VERB_REPLY_TO_SENDER) | Some(VERB_REPLY_TO_ALL)
+        );
+
+        if let Some(&table) = subnodes.get(&NID_RECIPIENT_TABLE) {
+            for (_, row) in self.read_table(table, &subnodes)? {
+                let address = [PR_SMTP_ADDRESS, PR_EMAIL_ADDRESS]
+                    .into_iter()
+                    .filter_map(|id| row.string(id))
+                    .find(|address| address.contains('@'));
+                let Some(address) = address else {
+                    continue;
+                };
+                let kind = match row.int(PR_RECIPIENT_TYPE) {
+                    Some(2) => RecipientKind::Cc,
+                    Some(3) => RecipientKind::Bcc,
+                    _ => RecipientKind::To,
+                };
+                message.recipients.push(PstRecipient {
+                    kind,
+                    name: row.string(PR_DISPLAY_NAME),
+                    address,
+                });
+            }
+        }
+
+        if let Some(&table) = subnodes.get(&NID_ATTACHMENT_TABLE) {
+            for (attachment_nid, _) in self.read_table(table, &subnodes)? {
+                let Some(&attachment_node) = subnodes.get(&attachment_nid) else {
+                    continue;
+                };
+                if let Some(attachment) = self.read_attachment(attachment_node, depth)? {
+                    message.attachments.push(attachment);
+                }
+            }
+        }
+
+        Ok(message)
+    }
+
+    fn read_attachment(&mut self, node: Node, depth: usize) -> MigrationResult<Option<PstAttachment>> {
+        let (props, subnodes) = self.read_properties(node)?;
+        let filename = props
+            .string(PR_ATTACH_LONG_FILENAME)
+            .or_else(|| props.string(PR_ATTACH_FILENAME))
+            .or_else(|| props.string(PR_DISPLAY_NAME));
+
+        if props.int(PR_ATTACH_METHOD) == Some(ATTACH_EMBEDDED_MSG) {
+            // The value is the subnode ID of the attached message and its size
+            let Some(value) = props.values.get(&PR_ATTACH_DATA).filter(|v| v.prop_type == PT_OBJECT) else {
+                return Ok(None);
+            };
+            let nid = read_u32(&value.data, 0)?;
+            let Some(&embedded) = subnodes.get(&nid) else {
+                return Ok(None);
+            };
+            let message = self.read_message_node(nid, embedded, depth + 1)?;
+            let filename = filename.unwrap_or_else(|| format!("{}.eml", message.subject));
+            return Ok(Some(PstAttachment {
+                filename,
+                mime_type: Some("message/rfc822".to_string()),
+                content_id: None,
+                is_inline: false,
+                data: message.to_rfc822().into_bytes(),
+            }));
+        }
+
+        let Some(data) = props.binary(PR_ATTACH_DATA) else {
+            return Ok(None);
+        };
+        let content_id = props.string(PR_ATTACH_CONTENT_ID);
+        let is_inline = content_id.is_some()
+            && (props.bool(PR_ATTACHMENT_HIDDEN)
+                || props.int(PR_ATTACH_FLAGS).unwrap_or_default() & ATTACH_MHTML_REF != 0);
+
+        Ok(Some(PstAttachment {
+            filename: filename.unwrap_or_else(|| "attachment".to_string()),
+            mime_type: props.string(PR_ATTACH_MIME_TAG),
+            content_id,
+            is_inline,
+            data: data.to_vec(),
+        }))
+    }
+
+    /// Property ID of a numeric named property, from the name-to-ID map
+    fn named_property(&mut self, guid: [u8; 16], lid: u32) -> MigrationResult<Option<u16>> {
+        if self.named_properties.is_none() {
+            let mut map = HashMap::new();
+            if let Some(node) = self.find_node(NID_NAME_TO_ID_MAP)? {
+                let (props, _) = self.read_properties(node)?;
+                let guids = props.binary(PR_NAMEID_STREAM_GUID).unwrap_or_default();
+                let entries = props.binary(PR_NAMEID_STREAM_ENTRY).unwrap_or_default();
+
+                for entry in entries.chunks_exact(8) {
+                    let id = read_u32(entry, 0)?;
+                    let guid_and_kind = read_u16(entry, 4)?;
+                    let index = read_u16(entry, 6)?;
+                    // String-named properties are not needed
+                    if guid_and_kind & 1 != 0 {
+                        continue;
+                    }
+                    let guid = match guid_and_kind >> 1 {
+                        0 => continue,
+                        // PS_MAPI and PS_PUBLIC_STRINGS are not in the GUID stream
+                        1 => [0x28, 0x03, 0x02, 0, 0, 0, 0, 0, 0xC0, 0, 0, 0, 0, 0, 0, 0x46],
+                        2 => [0x29, 0x03, 0x02, 0, 0, 0, 0, 0, 0xC0, 0, 0, 0, 0, 0, 0, 0x46],
+                        n => {
+                            let start = (n as usize - 3) * 16;
+                            match guids.get(start..start + 16) {
+                                Some(bytes) => bytes.try_into().unwrap(),
+                                None => continue,
+                            }
+                        }
+                    };
+                    map.insert((guid, id), 0x8000 + index);
+                }
+            }
+            self.named_properties = Some(map);
+        }
+
+        Ok(self
+            .named_properties
+            .as_ref()
+            .and_then(|map| map.get(&(guid, lid)).copied()))
+    }
+
+    /// Properties of a property context node, and its subnodes
+    fn read_properties(&mut self, node: Node) -> MigrationResult<(PropertyBag, HashMap<u32, Node>)> {
+        let heap = Heap::new(self.data_blocks(node.data_bid, 0)?)?;
+        if heap.client_sig != Heap::PROPERTY_CONTEXT {
+            return Err(corrupt("expected a property context"));
+        }
+        let subnodes = self.subnodes(node.sub_bid, 0)?;
+
+        let mut bag = PropertyBag::default();
+        for (key, value) in heap.btree_records(heap.user_root)? {
+            if key.len() != 2 || value.len() != 6 {
+                return Err(corrupt("bad property context record"));
+            }
+            let id = read_u16(&key, 0)?;
+            let prop_type = read_u16(&value, 0)?;
+            let data = match prop_type {
+                // Values of four bytes or less are stored in the record
+                PT_SHORT | PT_LONG | PT_FLOAT | PT_ERROR | PT_BOOLEAN => value[2..6].to_vec(),
+                _ => self.resolve_hnid(&heap, &subnodes, read_u32(&value, 2)?)?,
+            };
+            bag.values.insert(id, PropValue { prop_type, data });
+        }
+
+        Ok((bag, subnodes))
+    }
+
+    /// Rows of a table context node as (row ID, properties)
+    ///
+    /// `parent_subnodes` resolves values stored in subnodes of the object the
+    /// table belongs to, as tables kept in a message's subnode tree do.
+    fn read_table(
+        &mut self,
+        node: Node,
+        parent_subnodes: &HashMap<u32, Node>,
+    ) -> MigrationResult<Vec<(u32, PropertyBag)>> {
+        let heap = Heap::new(self.data_blocks(node.data_bid, 0)?)?;
+        if heap.client_sig != Heap::TABLE_CONTEXT {
+            return Err(corrupt("expected a table context"));
+        }
+        let mut subnodes = self.subnodes(node.sub_bid, 0)?;
+        for (nid, subnode) in parent_subnodes {
+            subnodes.entry(*nid).or_insert(*subnode);
+        }
+
+        let info = heap.item(heap.user_root)?.to_vec();
+        if info.first() != Some(&Heap::TABLE_CONTEXT) || info.len() < 22 {
+            return Err(corrupt("bad table header"));
+        }
+        let column_count = info[1] as usize;
+        let bitmap_start = read_u16(&info, 6)? as usize;
+        let row_size = read_u16(&info, 8)? as usize;
+        let row_index = read_u32(&info, 10)?;
+        let rows_hnid = read_u32(&info, 14)?;
+
+        let mut columns = Vec::with_capacity(column_count);
+        for i in 0..column_count {
+            let offset = 22 + i * 8;
+            let tag = read_u32(&info, offset)?;
+            let data_offset = read_u16(&info, offset + 4)? as usize;
+            let data_size = *info.get(offset + 6).ok_or_else(|| corrupt("bad column"))? as usize;
+            let bit = info[offset + 7] as usize;
+            columns.push((tag, data_offset, data_size, bit));
+        }
+
+        let row_count = if row_index == 0 { 0 } else { heap.btree_records(row_index)?.len() };
+        if row_count == 0 || rows_hnid == 0 || row_size < 4 {
+            return Ok(Vec::new());
+        }
+        let row_blocks = if rows_hnid & 0x1F == NID_TYPE_HID {
+            vec![heap.item(rows_hnid)?.to_vec()]
+        } else {
+            let rows_node = subnodes
+                .get(&rows_hnid)
+                .copied()
+                .ok_or_else(|| corrupt(format!("missing row matrix {:#x}", rows_hnid)))?;
+            self.data_blocks(rows_node.data_bid, 0)?
+        };
+
+        let mut rows = Vec::with_capacity(row_count);
+        // Rows never straddle blocks, the end of each block is padding
+        for row in row_blocks.iter().flat_map(|block| block.chunks_exact(row_size)).take(row_count) {
+            let row_id = read_u32(row, 0)?;
+            let bitmap = &row[bitmap_start.min(row_size)..];
+            let mut bag = PropertyBag::default();
+
+            for &(tag, data_offset, data_size, bit) in &columns {
+                let present = bitmap.get(bit / 8).is_some_and(|byte| byte & (0x80 >> (bit % 8)) != 0);
+                let Some(cell) = row.get(data_offset..data_offset + data_size).filter(|_| present) else {
+                    continue;
+                };
+                let prop_type = (tag & 0xFFFF) as u16;
+                let data = match prop_type {
+                    // Fixed-size values of up to eight bytes are stored in the row
+                    PT_SHORT | PT_LONG | PT_FLOAT | PT_DOUBLE | PT_CURRENCY | PT_APPTIME | PT_ERROR
+                    | PT_BOOLEAN | PT_LONGLONG | PT_SYSTIME => cell.to_vec(),
+                    _ => self.resolve_hnid(&heap, &subnodes, read_u32(cell, 0)?)?,
+                };
+                bag.values.insert((tag >> 16) as u16, PropValue { prop_type, data });
+            }
+            rows.push((row_id, bag));
+        }
+
+        Ok(rows)
+    }
+
+    /// Bytes of a value stored in the heap or in a subnode
+    fn resolve_hnid(&mut self, heap: &Heap, subnodes: &HashMap<u32, Node>, hnid: u32) -> MigrationResult<Vec<u8>> {
+        if hnid & 0x1F == NID_TYPE_HID {
+            if hnid == 0 {
+                return Ok(Vec::new());
+            }
+            return Ok(heap.item(hnid)?.to_vec());
+        }
+        let node = subnodes
+            .get(&hnid)
+            .copied()
+            .ok_or_else(|| corrupt(format!("missing subnode {:#x}", hnid)))?;
+        Ok(self.data_blocks(node.data_bid, 0)?.concat())
+    }
+
+    /// Subnodes of a node by node ID
+    fn subnodes(&mut self, bid: u64, depth: usize) -> MigrationResult<HashMap<u32, Node>> {
+        let mut subnodes = HashMap::new();
+        if bid == 0 {
+            return Ok(subnodes);
+        }
+        if depth > MAX_TREE_DEPTH {
+            return Err(corrupt("subnode tree too deep"));
+        }
+
+        let block = self.read_block(bid)?;
+        if block.first() != Some(&0x02) {
+            return Err(corrupt("bad subnode block"));
+        }
+        let level = block[1];
+        let count = read_u16(&block, 2)? as usize;
+        let (width, start) = match self.format {
+            PstFormat::Ansi => (4, 4),
+            PstFormat::Unicode => (8, 8),
+        };
+
+        let entry_size = if level == 0 { width * 3 } else { width * 2 };
+        for i in 0..count {
+            let offset = start + i * entry_size;
+            let nid = self.read_width(&block, offset)? as u32;
+            let child = self.read_width(&block, offset + width)?;
+            if level == 0 {
+                let sub_bid = self.read_width(&block, offset + width * 2)?;
+                subnodes.insert(nid, Node { data_bid: child, sub_bid });
+            } else {
+                subnodes.extend(self.subnodes(child, depth + 1)?);
+            }
+        }
+
+        Ok(subnodes)
+    }
+
+    /// The data blocks of a node, in order
+    fn data_blocks(&mut self, bid: u64, depth: usize) -> MigrationResult<Vec<Vec<u8>>> {
+        let block = self.read_block(bid)?;
+        // Internal blocks list other blocks rather than hold data
+        if bid & 0x2 == 0 {
+            return Ok(vec![block]);
+        }
+        if depth > MAX_TREE_DEPTH || block.first() != Some(&0x01) {
+            return Err(corrupt("bad data tree block"));
+        }
+        let level = block[1];
+        let count = read_u16(&block, 2)? as usize;
+        let width = self.bid_width();
+
+        let mut blocks = Vec::with_capacity(count);
+        for i in 0..count {
+            let child = self.read_width(&block, 8 + i * width)?;
+            if level == 1 {
+                blocks.push(self.read_block(child)?);
+            } else {
+                blocks.extend(self.data_blocks(child, depth + 1)?);
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// A top-level node by node ID
+    fn node(&mut self, nid: u32) -> MigrationResult<Node> {
+        self.find_node(nid)?
+            .ok_or_else(|| corrupt(format!("missing node {:#x}", nid)))
+    }
+
+    fn find_node(&mut self, nid: u32) -> MigrationResult<Option<Node>> {
+        let Some(entry) = self.btree_lookup(self.nbt_root, nid as u64, Self::PAGE_TYPE_NBT)? else {
+            return Ok(None);
+        };
+        let width = self.bid_width();
+        Ok(Some(Node {
+            data_bid: self.read_width(&entry, width)?,
+            sub_bid: self.read_width(&entry, width * 2)?,
+        }))
+    }
+
+    /// Contents of a block, decoded
+    fn read_block(&mut self, bid: u64) -> MigrationResult<Vec<u8>> {
+        let entry = self
+            .btree_lookup(self.bbt_root, bid, Self::PAGE_TYPE_BBT)?
+            .ok_or_else(|| corrupt(format!("missing block {:#x}", bid)))?;
+        let width = self.bid_width();
+        let offset = self.read_width(&entry, width)?;
+        let size = read_u16(&entry, width * 2)? as usize;
+
+        let mut data = vec![0u8; size];
+        self.source.seek(SeekFrom::Start(offset))?;
+        self.source.read_exact(&mut data)?;
+
+        if self.encryption == PstEncryption::Permute && bid & 0x2 == 0 {
+            for byte in &mut data {
+                *byte = PERMUTE_DECODE[*byte as usize];
+            }
+        }
+        Ok(data)
+    }
+
+    /// Leaf entry for `key` in the node or block B-tree rooted at page `root`
+    fn btree_lookup(&mut self, root: u64, key: u64, page_type: u8) -> MigrationResult<Option<Vec<u8>>> {
+        // The lowest bit of block IDs is reserved and not part of the key
+        let mask = if page_type == Self::PAGE_TYPE_BBT { !1 } else { !0 };
+        let key = key & mask;
+        let width = self.bid_width();
+        let mut offset = root;
+
+        for _ in 0..MAX_TREE_DEPTH {
+            let page = self.read_page(offset)?;
+            let (count_offset, type_offset) = match self.format {
+                PstFormat::Ansi => (496, 500),
+                PstFormat::Unicode => (488, 496),
+            };
+            if page[type_offset] != page_type {
+                return Err(corrupt(format!("bad B-tree page at {}", offset)));
+            }
+            let count = page[count_offset] as usize;
+            let entry_size = page[count_offset + 2] as usize;
+            let level = page[count_offset + 3];
+            if entry_size < width * 2 || count * entry_size > count_offset {
+                return Err(corrupt(format!("bad B-tree page at {}", offset)));
+            }
+
+            let entries = page[..count * entry_size].chunks_exact(entry_size);
+            if level == 0 {
+                for entry in entries {
+                    if self.read_width(entry, 0)? & mask == key {
+                        return Ok(Some(entry.to_vec()));
+                    }
+                }
+                return Ok(None);
+            }
+
+            // Descend into the last child whose first key is not past ours
+            let mut child = None;
+            for entry in entries {
+                if self.read_width(entry, 0)? & mask > key {
+                    break;
+                }
+                child = Some(self.read_width(entry, width * 2)?);
+            }
+            match child {
+                Some(child) => offset = child,
+                None => return Ok(None),
+            }
+        }
+
+        Err(corrupt("B-tree too deep"))
+    }
+
+    fn read_page(&mut self, offset: u64) -> MigrationResult<Vec<u8>> {
+        if let Some(page) = self.page_cache.get(&offset) {
+            return Ok(page.clone());
+        }
+
+        let mut page = vec![0u8; Self::PAGE_SIZE];
+        self.source.seek(SeekFrom::Start(offset))?;
+        self.source.read_exact(&mut page)?;
+
+        let level_offset = match self.format {
+            PstFormat::Ansi => 499,
+            PstFormat::Unicode => 491,
+        };
+        if page[level_offset] > 0 {
+            self.page_cache.insert(offset, page.clone());
+        }
+        Ok(page)
+    }
+
+    /// Size of block IDs and file offsets in this format
+    fn bid_width(&self) -> usize {
+        match self.format {
+            PstFormat::Ansi => 4,
+            PstFormat::Unicode => 8,
+        }
+    }
+
+    fn read_width(&self, data: &[u8], offset: usize) -> MigrationResult<u64> {
+        match self.format {
+            PstFormat::Ansi => read_u32(data, offset).map(u64::from),
+            PstFormat::Unicode => read_u64(data, offset),
+        }
+    }
+}
+
+/// Drop the length-prefix marker Outlook puts in front of `RE:` style prefixes
+fn strip_subject_prefix(subject: String) -> String {
+    match subject.strip_prefix('\u{1}') {
+        Some(rest) => rest.chars().skip(1).collect(),
+        None => subject,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::Arc;
+
+    /// Value of a property in a test property context
+    enum TestValue {
+        Inline(u32),
+        Heap(Vec<u8>),
+        Subnode(u32),
+    }
+
+    /// Writes a minimal PST file with the same layout Outlook uses
+    struct PstBuilder {
+        format: PstFormat,
+        encryption: PstEncryption,
+        data: Vec<u8>,
+        blocks: Vec<(u64, u64, u16)>,
+        nodes: Vec<(u32, u64, u64)>,
+        next_bid: u64,
+    }
+
+    impl PstBuilder {
+        fn new(format: PstFormat, encryption: PstEncryption) -> Self {
+            Self {
+                format,
+                encryption,
+                data: vec![0u8; 1024],
+                blocks: Vec::new(),
+                nodes: Vec::new(),
+                next_bid: 4,
+            }
+        }
+
+        fn width(&self) -> usize {
+            match self.format {
+                PstFormat::Ansi => 4,
+                PstFormat::Unicode => 8,
+            }
+        }
+
+        fn put(&self, out: &mut Vec<u8>, value: u64) {
+            out.extend_from_slice(&value.to_le_bytes()[..self.width()]);
+        }
+
+        fn block(&mut self, bytes: &[u8], internal: bool) -> u64 {
+            let bid = self.next_bid | if internal { 2 } else { 0 };
+            self.next_bid += 4;
+            let offset = self.data.len() as u64;
+            let mut bytes = bytes.to_vec();
+            if self.encryption == PstEncryption::Permute && !internal {
+                let mut encode = [0u8; 256];
+                for (plain, &coded) in PERMUTE_DECODE.iter().enumerate() {
+                    encode[coded as usize] = plain as u8;
+                }
+                bytes.iter_mut().for_each(|b| *b = encode[*b as usize]);
+            }
+            self.data.extend_from_slice(&bytes);
+            self.data.resize(self.data.len().div_ceil(64) * 64, 0);
+            self.blocks.push((bid, offset, bytes.len() as u16));
+            bid
+        }
+
+        /// Data stored in blocks of `chunk` bytes under an XBLOCK when needed
+        fn data(&mut self, bytes: &[u8], chunk: usize) -> u64 {
+            if bytes.len() <= chunk {
+                return self.block(bytes, false);
+            }
+            let children: Vec<u64> = bytes.chunks(chunk).map(|part| self.block(part, false)).collect();
+            let mut xblock = vec![0x01, 0x01];
+            xblock.extend_from_slice(&(children.len() as u16).to_le_bytes());
+            xblock.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            for child in children {
+                self.put(&mut xblock, child);
+            }
+            self.block(&xblock, true)
+        }
+
+        fn subnode_block(&mut self, entries: &[(u32, u64, u64)]) -> u64 {
+            let mut block = vec![0x02, 0x00];
+            block.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+            if self.format == PstFormat::Unicode {
+                block.extend_from_slice(&[0; 4]);
+            }
+            for &(nid, data, sub) in entries {
+                self.put(&mut block, nid as u64);
+                self.put(&mut block, data);
+                self.put(&mut block, sub);
+            }
+            self.block(&block, true)
+        }
+
+        fn node(&mut self, nid: u32, data: u64, sub: u64) {
+            self.nodes.push((nid, data, sub));
+        }
+
+        fn string(&self, text: &str) -> (u16, Vec<u8>) {
+            match self.format {
+                PstFormat::Ansi => (PT_STRING8, text.as_bytes().to_vec()),
+                PstFormat::Unicode => (
+                    PT_UNICODE,
+                    text.encode_utf16().flat_map(|unit| unit.to_le_bytes()).collect(),
+                ),
+            }
+        }
+
+        /// Write a B-tree over `entries`, with leaf pages of at most two
+        /// entries under an intermediate page
+        fn btree(&mut self, mut entries: Vec<Vec<u8>>, page_type: u8) -> u64 {
+            entries.sort_by_key(|entry| u64::from_le_bytes({
+                let mut key = [0u8; 8];
+                key[..self.width()].copy_from_slice(&entry[..self.width()]);
+                key
+            }));
+            let mut children = Vec::new();
+            for chunk in entries.chunks(2) {
+                let first = chunk[0][..self.width()].to_vec();
+                children.push((first, self.page(chunk, page_type, 0)));
+            }
+            let intermediate: Vec<Vec<u8>> = children
+                .into_iter()
+                .map(|(key, offset)| {
+                    let mut entry = key;
+                    self.put(&mut entry, 0);
+                    self.put(&mut entry, offset);
+                    entry
+                })
+                .collect();
+            self.page(&intermediate, page_type, 1)
+        }
+
+        fn page(&mut self, entries: &[Vec<u8>], page_type: u8, level: u8) -> u64 {
+            self.data.resize(self.data.len().div_ceil(512) * 512, 0);
+            let offset = self.data.len() as u64;
+            let mut page = vec![0u8; 512];
+            let mut at = 0;
+            for entry in entries {
+                page[at..at + entry.len()].copy_from_slice(entry);
+                at += entry.len();
+            }
+            let (count_offset, type_offset) = match self.format {
+                PstFormat::Ansi => (496, 500),
+                PstFormat::Unicode => (488, 496),
+            };
+            page[count_offset] = entries.len() as u8;
+            page[count_offset + 1] = entries.len() as u8;
+            page[count_offset + 2] = entries[0].len() as u8;
+            page[count_offset + 3] = level;
+            page[type_offset] = page_type;
+            page[type_offset + 1] = page_type;
+            self.data.extend_from_slice(&page);
+            offset
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            let nodes: Vec<Vec<u8>> = self
+                .nodes
+                .clone()
+                .into_iter()
+                .map(|(nid, data, sub)| {
+                    let mut entry = Vec::new();
+                    self.put(&mut entry, nid as u64);
+                    self.put(&mut entry, data);
+                    self.put(&mut entry, sub);
+                    entry.extend_from_slice(&[0; 4]); // Parent
+                    if self.format == PstFormat::Unicode {
+                        entry.extend_from_slice(&[0; 4]);
+                    }
+                    entry
+                })
+                .collect();
+            let blocks: Vec<Vec<u8>> = self
+                .blocks
+                .clone()
+                .into_iter()
+                .map(|(bid, offset, size)| {
+                    let mut entry = Vec::new();
+                    self.put(&mut entry, bid);
+                    self.put(&mut entry, offset);
+                    entry.extend_from_slice(&size.to_le_bytes());
+                    entry.extend_from_slice(&[1, 0]); // Reference count
+                    if self.format == PstFormat::Unicode {
+                        entry.extend_from_slice(&[0; 4]);
+                    }
+                    entry
+                })
+                .collect();
+            let nbt = self.btree(nodes, PstReader::<Cursor<Vec<u8>>>::PAGE_TYPE_NBT);
+            let bbt = self.btree(blocks, PstReader::<Cursor<Vec<u8>>>::PAGE_TYPE_BBT);
+
+            let header = &mut self.data;
+            header[0..4].copy_from_slice(b"!BDN");
+            header[8..10].copy_from_slice(b"SM");
+            match self.format {
+                PstFormat::Ansi => {
+                    header[10..12].copy_from_slice(&14u16.to_le_bytes());
+                    header[188..192].copy_from_slice(&(nbt as u32).to_le_bytes());
+                    header[196..200].copy_from_slice(&(bbt as u32).to_le_bytes());
+                    header[461] = self.encryption as u8;
+                }
+                PstFormat::Unicode => {
+                    header[10..12].copy_from_slice(&23u16.to_le_bytes());
+                    header[224..232].copy_from_slice(&nbt.to_le_bytes());
+                    header[240..248].copy_from_slice(&bbt.to_le_bytes());
+                    header[513] = self.encryption as u8;
+                }
+            }
+            self.data
+        }
+    }
+
+    /// A heap-on-node with `items`, whose user root is the first item
+    fn heap(client_sig: u8, items: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = vec![0u8; 12];
+        let mut offsets = vec![12u16];
+        for item in items {
+            data.extend_from_slice(item);
+            offsets.push(data.len() as u16);
+        }
+        let page_map = data.len() as u16;
+        data.extend_from_slice(&(items.len() as u16).to_le_bytes());
+        data.extend_from_slice(&[0, 0]);
+        for offset in offsets {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data[0..2].copy_from_slice(&page_map.to_le_bytes());
+        data[2] = Heap::SIGNATURE;
+        data[3] = client_sig;
+        data[4..8].copy_from_slice(&0x20u32.to_le_bytes());
+        data
+    }
+
+    fn hid(index: usize) -> u32 {
+        (index as u32) << 5
+    }
+
+    fn property_context(props: Vec<(u16, u16, TestValue)>) -> Vec<u8> {
+        let mut props = props;
+        props.sort_by_key(|(id, _, _)| *id);
+        let mut records = Vec::new();
+        let mut values = Vec::new();
+        for (id, prop_type, value) in props {
+            let hnid = match value {
+                TestValue::Inline(value) => value,
+                TestValue::Heap(bytes) => {
+                    values.push(bytes);
+                    hid(2 + values.len())
+                }
+                TestValue::Subnode(nid) => nid,
+            };
+            records.extend_from_slice(&id.to_le_bytes());
+            records.extend_from_slice(&prop_type.to_le_bytes());
+            records.extend_from_slice(&hnid.to_le_bytes());
+        }
+        let mut header = vec![Heap::BTREE, 2, 6, 0];
+        header.extend_from_slice(&hid(2).to_le_bytes());
+
+        let mut items = vec![header, records];
+        items.extend(values);
+        heap(Heap::PROPERTY_CONTEXT, &items)
+    }
+
+    /// A table of string and 32-bit integer columns
+    fn table_context(columns: &[(u16, u16)], rows: &[(u32, Vec<Vec<u8>>)]) -> Vec<u8> {
+        let row_size_fixed = 4 + columns.len() * 4;
+        let bitmap_size = (columns.len() + 1).div_ceil(8);
+        let row_size = row_size_fixed + bitmap_size;
+
+        let mut values = Vec::new();
+        let mut matrix = Vec::new();
+        for (row_id, cells) in rows {
+            let mut row = row_id.to_le_bytes().to_vec();
+            for ((_, prop_type), cell) in columns.iter().zip(cells) {
+                if *prop_type == PT_LONG {
+                    row.extend_from_slice(cell);
+                } else {
+                    values.push(cell.clone());
+                    row.extend_from_slice(&hid(4 + values.len()).to_le_bytes());
+                }
+            }
+            row.extend(std::iter::repeat_n(0xFF, bitmap_size));
+            matrix.extend_from_slice(&row);
+        }
+
+        let mut info = vec![Heap::TABLE_CONTEXT, columns.len() as u8 + 1];
+        for _ in 0..3 {
+            info.extend_from_slice(&(row_size_fixed as u16).to_le_bytes());
+        }
+        info.extend_from_slice(&(row_size as u16).to_le_bytes());
+        info.extend_from_slice(&hid(2).to_le_bytes());
+        info.extend_from_slice(&hid(4).to_le_bytes());
+        info.extend_from_slice(&0u32.to_le_bytes());
+        let mut descriptors = vec![(0x67F2u32 << 16 | PT_LONG as u32, 0u16, 4u8, 0u8)];
+        for (i, (id, prop_type)) in columns.iter().enumerate() {
+            descriptors.push(((*id as u32) << 16 | *prop_type as u32, 4 + i as u16 * 4, 4, i as u8 + 1));
+        }
+        for (tag, offset, size, bit) in descriptors {
+            info.extend_from_slice(&tag.to_le_bytes());
+            info.extend_from_slice(&offset.to_le_bytes());
+            info.extend_from_slice(&[size, bit]);
+        }
+
+        let mut index_header = vec![Heap::BTREE, 4, 4, 0];
+        index_header.extend_from_slice(&hid(3).to_le_bytes());
+        let index: Vec<u8> = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (row_id, _))| [row_id.to_le_bytes(), (i as u32).to_le_bytes()].concat())
+            .collect();
+
+        let mut items = vec![info, index_header, index, matrix];
+        items.extend(values);
+        heap(Heap::TABLE_CONTEXT, &items)
+    }
+
+    /// A PST file with an inbox holding one message with an attachment, and
+    /// a contacts folder holding one contact
+    fn sample_pst(format: PstFormat, encryption: PstEncryption) -> Vec<u8> {
+        const IPM_SUBTREE: u32 = 0x8022;
+        const INBOX: u32 = 0x8042;
+        const CONTACTS: u32 = 0x8062;
+        const MESSAGE: u32 = 0x20_0024;
+        const CONTACT: u32 = 0x20_0044;
+        const ATTACHMENT: u32 = 0x8025;
+        const ATTACHMENT_DATA: u32 = 0x803F;
+
+        let mut pst = PstBuilder::new(format, encryption);
+        let text = |pst: &PstBuilder, value: &str| {
+            let (prop_type, bytes) = pst.string(value);
+            (prop_type, TestValue::Heap(bytes))
+        };
+        let prop = |id: u16, (prop_type, value): (u16, TestValue)| (id, prop_type, value);
+
+        let mut entry_id = vec![0u8; 20];
+        entry_id.extend_from_slice(&IPM_SUBTREE.to_le_bytes());
+        let store = property_context(vec![(PR_IPM_SUBTREE_ENTRYID, PT_BINARY, TestValue::Heap(entry_id))]);
+        let bid = pst.block(&store, false);
+        pst.node(NID_MESSAGE_STORE, bid, 0);
+
+        let mut name_entry = 0x8083u32.to_le_bytes().to_vec();
+        name_entry.extend_from_slice(&(3u16 << 1).to_le_bytes());
+        name_entry.extend_from_slice(&0u16.to_le_bytes());
+        let name_map = property_context(vec![
+            (PR_NAMEID_STREAM_GUID, PT_BINARY, TestValue::Heap(PSETID_ADDRESS.to_vec())),
+            (PR_NAMEID_STREAM_ENTRY, PT_BINARY, TestValue::Heap(name_entry)),
+        ]);
+        let bid = pst.block(&name_map, false);
+        pst.node(NID_NAME_TO_ID_MAP, bid, 0);
+
+        let folders = [
+            (IPM_SUBTREE, "Top of Personal Folders", None, vec![INBOX, CONTACTS], vec![]),
+            (INBOX, "Inbox", Some("IPF.Note"), vec![], vec![MESSAGE]),
+            (CONTACTS, "Contacts", Some("IPF.Contact"), vec![], vec![CONTACT]),
+        ];
+        for (nid, name, class, children, messages) in folders {
+            let mut props = vec![
+                prop(PR_DISPLAY_NAME, text(&pst, name)),
+                (PR_CONTENT_COUNT, PT_LONG, TestValue::Inline(messages.len() as u32)),
+            ];
+            if let Some(class) = class {
+                props.push(prop(PR_CONTAINER_CLASS, text(&pst, class)));
+            }
+            let bid = pst.block(&property_context(props), false);
+            pst.node(nid, bid, 0);
+
+            for (table_type, rows) in [(NID_TYPE_HIERARCHY_TABLE, children), (NID_TYPE_CONTENTS_TABLE, messages)] {
+                let rows: Vec<(u32, Vec<Vec<u8>>)> = rows.into_iter().map(|nid| (nid, vec![])).collect();
+                let bid = pst.block(&table_context(&[], &rows), false);
+                pst.node((nid & !0x1F) | table_type, bid, 0);
+            }
+        }
+
+        let attachment_bytes: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        let attachment_data = pst.data(&attachment_bytes, 8176);
+        let attachment_sub = pst.subnode_block(&[(ATTACHMENT_DATA, attachment_data, 0)]);
+        let attachment = property_context(vec![
+            prop(PR_ATTACH_LONG_FILENAME, text(&pst, "report.pdf")),
+            prop(PR_ATTACH_MIME_TAG, text(&pst, "application/pdf")),
+            (PR_ATTACH_METHOD, PT_LONG, TestValue::Inline(1)),
+            (PR_ATTACH_DATA, PT_BINARY, TestValue::Subnode(ATTACHMENT_DATA)),
+        ]);
+        let attachment = pst.block(&attachment, false);
+
+        let (string_type, _) = pst.string("");
+        let recipients = table_context(
+            &[(PR_RECIPIENT_TYPE, PT_LONG), (PR_DISPLAY_NAME, string_type), (PR_SMTP_ADDRESS, string_type)],
+            &[
+                (1, vec![1u32.to_le_bytes().to_vec(), pst.string("Kim").1, pst.string("kim@example.com").1]),
+                (2, vec![2u32.to_le_bytes().to_vec(), pst.string("Lee").1, pst.string("lee@example.com").1]),
+            ],
+        );
+        let recipients = pst.block(&recipients, false);
+        let attachments = pst.block(&table_context(&[], &[(ATTACHMENT, vec![])]), false);
+        let message_sub = pst.subnode_block(&[
+            (ATTACHMENT, attachment, attachment_sub),
+            (NID_ATTACHMENT_TABLE, attachments, 0),
+            (NID_RECIPIENT_TABLE, recipients, 0),
+        ]);
+        // 2024-03-01 12:00:00 UTC
+        let delivered = 133_537_680_000_000_000u64;
+        let message = property_context(vec![
+            prop(PR_MESSAGE_CLASS, text(&pst, "IPM.Note")),
+            prop(PR_SUBJECT, text(&pst, "\u{1}\u{4}RE: Quarterly report")),
+            prop(PR_SENDER_NAME, text(&pst, "Sam Doe")),
+            prop(PR_SENDER_EMAIL_ADDRESS, text(&pst, "sam@example.com")),
+            prop(PR_BODY, text(&pst, "See attached.")),
+            prop(PR_INTERNET_MESSAGE_ID, text(&pst, "<report@example.com>")),
+            (PR_MESSAGE_DELIVERY_TIME, PT_SYSTIME, TestValue::Heap(delivered.to_le_bytes().to_vec())),
+            (PR_MESSAGE_FLAGS, PT_LONG, TestValue::Inline(MSGFLAG_READ as u32)),
+            (PR_FLAG_STATUS, PT_LONG, TestValue::Inline(FLAG_STATUS_FLAGGED as u32)),
+            (PR_IMPORTANCE, PT_LONG, TestValue::Inline(2)),
+        ]);
+        let bid = pst.block(&message, false);
+        pst.node(MESSAGE, bid, message_sub);
+
+        let contact = property_context(vec![
+            prop(PR_MESSAGE_CLASS, text(&pst, "IPM.Contact")),
+            prop(PR_DISPLAY_NAME, text(&pst, "Ada Lovelace")),
+            prop(PR_GIVEN_NAME, text(&pst, "Ada")),
+            prop(PR_SURNAME, text(&pst, "Lovelace")),
+            prop(PR_MOBILE_TELEPHONE_NUMBER, text(&pst, "+44 7700 900000")),
+            prop(0x8000, text(&pst, "ada@example.com")),
+        ]);
+        let bid = pst.block(&contact, false);
+        pst.node(CONTACT, bid, 0);
+
+        pst.finish()
+    }
+
+    #[test]
+    fn test_permute_table_is_a_permutation() {
+        let mut seen = [false; 256];
+        for &byte in PERMUTE_DECODE.iter() {
+            assert!(!seen[byte as usize]);
+            seen[byte as usize] = true;
+        }
+    }
+
+    #[test]
+    fn test_rejects_non_pst_files() {
+        let result = PstReader::new(Cursor::new(vec![0u8; 1024]));
+        assert!(matches!(result, Err(MigrationError::ConversionFailed(_))));
+    }
+
+    #[test]
+    fn test_read_pst_formats() {
+        for (format, encryption) in [
+            (PstFormat::Unicode, PstEncryption::Permute),
+            (PstFormat::Unicode, PstEncryption::None),
+            (PstFormat::Ansi, PstEncryption::Permute),
+        ] {
+            let mut reader = PstReader::new(Cursor::new(sample_pst(format, encryption))).unwrap();
+            assert_eq!(reader.format(), format);
+            assert_eq!(reader.encryption(), encryption);
+
+            let folders = reader.folders().unwrap();
+            let paths: Vec<&str> = folders.iter().map(|folder| folder.path.as_str()).collect();
+            assert_eq!(paths, ["Inbox", "Contacts"]);
+            assert!(folders[0].is_mail());
+            assert!(folders[1].is_contacts());
+            assert_eq!(folders[0].message_count, 1);
+
+            let nids = reader.folder_messages(folders[0].nid).unwrap();
+            let message = reader.read_message(nids[0]).unwrap();
+            assert_eq!(message.subject, "RE: Quarterly report");
+            assert_eq!(message.sender_address.as_deref(), Some("sam@example.com"));
+            assert_eq!(message.recipients.len(), 2);
+            assert_eq!(message.recipients[1].kind, RecipientKind::Cc);
+            assert_eq!(message.date.unwrap().to_rfc3339(), "2024-03-01T12:00:00+00:00");
+            assert_eq!(message.attachments.len(), 1);
+            assert_eq!(message.attachments[0].filename, "report.pdf");
+            assert_eq!(message.attachments[0].data.len(), 20_000);
+            assert_eq!(message.attachments[0].data[8176], (8176 % 251) as u8);
+
+            let stored = convert_message(&message, "account", &target_folder_name(&folders[0].path));
+            assert_eq!(stored.folder_name, "INBOX");
+            assert_eq!(stored.to_addrs, ["kim@example.com"]);
+            assert_eq!(stored.cc_addrs, ["lee@example.com"]);
+            assert_eq!(stored.flags, ["\\Seen", "\\Flagged"]);
+            assert_eq!(stored.priority.as_deref(), Some("high"));
+            assert_eq!(stored.attachments[0].content_type, "application/pdf");
+
+            let nids = reader.folder_messages(folders[1].nid).unwrap();
+            let contact = reader.read_contact(nids[0]).unwrap().unwrap();
+            let contact = convert_contact(&contact);
+            assert_eq!(contact.full_name(), "Ada Lovelace");
+            assert_eq!(contact.primary_email().unwrap().address, "ada@example.com");
+            assert_eq!(contact.primary_phone().unwrap().label, "mobile");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_pst_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("archive.pst");
+        std::fs::write(&path, sample_pst(PstFormat::Unicode, PstEncryption::Permute)).unwrap();
+
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = updates.clone();
+        let mut migrator = OutlookMigrator::new()
+            .unwrap()
+            .with_progress(Uuid::new_v4(), Arc::new(move |progress| seen.lock().unwrap().push(progress)));
+        let profile = migrator.add_pst_file(&path).await.unwrap();
+        assert_eq!(profile.name, "archive");
+        assert_eq!(profile.folders.len(), 2);
+
+        let messages = migrator.migrate_emails(&profile, "account").await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].from_addr, "sam@example.com");
+
+        let contacts = migrator.migrate_contacts(&profile).await.unwrap();
+        assert_eq!(contacts.len(), 1);
+
+        let updates = updates.lock().unwrap();
+        let last = updates.last().unwrap();
+        assert_eq!(last.status, MigrationStatus::Completed);
+        assert_eq!((last.items_processed, last.items_total), (1, 1));
+    }
+}
@@ -4,7 +4,10 @@
 //! including emails, contacts, filters, and account settings.
 
 use crate::migration::{MigrationError, MigrationResult, MigrationDataType};
-use crate::email::{StoredMessage, StoredAttachment};
+use crate::email::{
+    EmailFilter, FilterAction, FilterCondition, FilterField, FilterOperator, StoredAttachment,
+    StoredMessage,
+};
 use crate::contacts::{Contact, ContactEmail, ContactPhone, ContactSource};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -119,6 +122,8 @@ pub struct ThunderbirdFilter {
     pub name: String,
     pub enabled: bool,
     pub log_matches: bool,
+    /// Conditions are OR-ed ("match any") rather than AND-ed
+    pub match_any: bool,
     pub conditions: Vec<ThunderbirdFilterCondition>,
     pub actions: Vec<ThunderbirdFilterAction>,
 }
@@ -138,6 +143,64 @@ pub struct ThunderbirdFilterAction {
     pub value: Option<String>,
 }
 
+/// A Thunderbird filter that could not be converted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnconvertedFilter {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Result of converting Thunderbird filters, previewed before it is applied
+#[derive(Debug, Clone, Default)]
+pub struct FilterImportReport {
+    pub converted: Vec<EmailFilter>,
+    pub unconverted: Vec<UnconvertedFilter>,
+    /// Conditions or actions dropped from filters that were otherwise converted
+    pub warnings: Vec<String>,
+}
+
+impl FilterImportReport {
+    /// Merge another account's report into this one
+    pub fn extend(&mut self, other: FilterImportReport) {
+        self.converted.extend(other.converted);
+        self.unconverted.extend(other.unconverted);
+        self.warnings.extend(other.warnings);
+    }
+
+    /// Human-readable summary of what will be imported
+    pub fn preview_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{} filter(s) will be imported, {} cannot be converted",
+            self.converted.len(),
+            self.unconverted.len()
+        )];
+
+        for filter in &self.converted {
+            let state = if filter.enabled { "" } else { " (disabled)" };
+            lines.push(format!(
+                "  ✓ {}{}: {} condition(s) → {}",
+                filter.name,
+                state,
+                filter.conditions.len(),
+                filter
+                    .actions
+                    .iter()
+                    .map(describe_action)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        for warning in &self.warnings {
+            lines.push(format!("  ⚠ {}", warning));
+        }
+        for filter in &self.unconverted {
+            lines.push(format!("  ✗ {}: {}", filter.name, filter.reason));
+        }
+
+        lines
+    }
+}
+
 /// Main Thunderbird migrator
 pub struct ThunderbirdMigrator {
     profiles_ini_path: PathBuf,
@@ -208,19 +271,29 @@ impl ThunderbirdMigrator {
         Ok(contacts)
     }
 
-    /// Migrate filters from a Thunderbird profile
-    pub async fn migrate_filters(
+    /// Convert the filters of every account in a profile without applying them
+    ///
+    /// Show the report's `preview_lines` to the user before importing the
+    /// converted filters.
+    pub async fn preview_filters(
         &self,
         profile: &ThunderbirdProfile,
-    ) -> MigrationResult<Vec<crate::email::EmailFilter>> {
-        let mut filters = Vec::new();
+    ) -> MigrationResult<FilterImportReport> {
+        let mut report = FilterImportReport::default();
 
         for account in &profile.accounts {
-            let account_filters = self.migrate_account_filters(account).await?;
-            filters.extend(account_filters);
+            report.extend(self.migrate_account_filters(account).await?);
         }
 
-        Ok(filters)
+        Ok(report)
+    }
+
+    /// Migrate filters from a Thunderbird profile
+    pub async fn migrate_filters(
+        &self,
+        profile: &ThunderbirdProfile,
+    ) -> MigrationResult<Vec<EmailFilter>> {
+        Ok(self.preview_filters(profile).await?.converted)
     }
 
     /// Find the profiles.ini file
@@ -448,20 +521,20 @@ impl ThunderbirdMigrator {
         Ok(contacts)
     }
 
-    /// Migrate filters from an account
+    /// Migrate filters from an account's `msgFilterRules.dat`
     async fn migrate_account_filters(
         &self,
         account: &ThunderbirdAccount,
-    ) -> MigrationResult<Vec<crate::email::EmailFilter>> {
-        let mut filters = Vec::new();
+    ) -> MigrationResult<FilterImportReport> {
+        let rules_path = account.local_folders_path.join("msgFilterRules.dat");
+        if !rules_path.exists() {
+            return Ok(FilterImportReport::default());
+        }
 
-        // TODO: Implement filter migration
-        // This would:
-        // 1. Read msgFilterRules.dat files
-        // 2. Parse filter definitions
-        // 3. Convert to EmailFilter format
+        let content = tokio::fs::read_to_string(&rules_path).await?;
+        let filters = parse_filter_rules(&content)?;
 
-        Ok(filters)
+        Ok(convert_filters(&filters))
     }
 
     /// Convert Thunderbird message to StoredMessage
@@ -508,6 +581,291 @@ impl ThunderbirdMigrator {
     }
 }
 
+/// Parse the contents of a Thunderbird `msgFilterRules.dat` file
+///
+/// The file is a list of `key="value"` lines; each `name` starts a new
+/// filter, and an `actionValue` belongs to the `action` before it.
+pub fn parse_filter_rules(content: &str) -> MigrationResult<Vec<ThunderbirdFilter>> {
+    let mut filters: Vec<ThunderbirdFilter> = Vec::new();
+
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .map(|(key, value)| (key.trim(), unquote_filter_value(value.trim())))
+            .ok_or_else(|| {
+                MigrationError::ConversionFailed(format!(
+                    "msgFilterRules.dat line {}: expected key=\"value\"",
+                    line_number + 1
+                ))
+            })?;
+
+        if key == "name" {
+            filters.push(ThunderbirdFilter {
+                name: value,
+                enabled: true,
+                log_matches: false,
+                match_any: false,
+                conditions: Vec::new(),
+                actions: Vec::new(),
+            });
+            continue;
+        }
+
+        // version and logging come before the first filter
+        let Some(filter) = filters.last_mut() else {
+            continue;
+        };
+
+        match key {
+            "enabled" => filter.enabled = value == "yes",
+            "action" => filter.actions.push(ThunderbirdFilterAction {
+                action_type: value,
+                value: None,
+            }),
+            "actionValue" => {
+                if let Some(action) = filter.actions.last_mut() {
+                    action.value = Some(value);
+                }
+            }
+            "condition" => {
+                let (match_any, conditions) = parse_filter_condition(&value);
+                filter.match_any = match_any;
+                filter.conditions = conditions;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(filters)
+}
+
+/// Strip the surrounding quotes and `\"` / `\\` escapes from a value
+fn unquote_filter_value(value: &str) -> String {
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                result.push(escaped);
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Parse a condition string such as `AND (from,contains,shop) AND (subject,is,"a, b")`
+///
+/// Returns whether the terms are OR-ed together, and the terms. `ALL`
+/// (match every message) yields no terms.
+fn parse_filter_condition(condition: &str) -> (bool, Vec<ThunderbirdFilterCondition>) {
+    let mut conditions = Vec::new();
+    let mut match_any = false;
+    let mut rest = condition.trim();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("OR") {
+            match_any = true;
+            rest = after.trim_start();
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("AND") {
+            rest = after.trim_start();
+            continue;
+        }
+        let Some(term) = rest.strip_prefix('(') else {
+            break;
+        };
+
+        let mut parts = term.splitn(3, ',');
+        let (Some(field), Some(operator), Some(tail)) = (parts.next(), parts.next(), parts.next())
+        else {
+            break;
+        };
+
+        // Values containing commas or parentheses are quoted
+        let (value, after) = if let Some(quoted) = tail.strip_prefix('"') {
+            let mut value = String::new();
+            let mut chars = quoted.char_indices();
+            let mut end = quoted.len();
+            while let Some((index, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some((_, escaped)) = chars.next() {
+                            value.push(escaped);
+                        }
+                    }
+                    '"' => {
+                        end = index + 1;
+                        break;
+                    }
+                    _ => value.push(c),
+                }
+            }
+            let after = quoted[end..].trim_start();
+            (value, after.strip_prefix(')').unwrap_or(after))
+        } else {
+            match tail.split_once(')') {
+                Some((value, after)) => (value.to_string(), after),
+                None => (tail.to_string(), ""),
+            }
+        };
+
+        conditions.push(ThunderbirdFilterCondition {
+            field: field.trim().to_string(),
+            operator: operator.trim().to_string(),
+            value,
+        });
+        rest = after.trim_start();
+    }
+
+    (match_any, conditions)
+}
+
+/// Folder path from a Thunderbird folder URI
+///
+/// `imap://user%40example.com@imap.example.com/INBOX/Receipts` becomes
+/// `INBOX/Receipts`.
+fn folder_from_uri(uri: &str) -> String {
+    let path = uri
+        .split_once("://")
+        .and_then(|(_, rest)| rest.split_once('/'))
+        .map(|(_, path)| path)
+        .unwrap_or(uri);
+    percent_encoding::percent_decode_str(path)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+fn convert_condition(condition: &ThunderbirdFilterCondition) -> Result<FilterCondition, String> {
+    let field = match condition.field.to_lowercase().as_str() {
+        "from" => FilterField::From,
+        "to" => FilterField::To,
+        "cc" => FilterField::CC,
+        "subject" => FilterField::Subject,
+        "body" => FilterField::Body,
+        "date" => FilterField::Date,
+        "priority" => FilterField::Priority,
+        "size" => FilterField::Size,
+        other => return Err(format!("unsupported field \"{}\"", other)),
+    };
+
+    let operator = match condition.operator.as_str() {
+        "contains" => FilterOperator::Contains,
+        "doesn't contain" => FilterOperator::NotContains,
+        "is" => FilterOperator::Equals,
+        "isn't" => FilterOperator::NotEquals,
+        "begins with" => FilterOperator::StartsWith,
+        "ends with" => FilterOperator::EndsWith,
+        "is greater than" | "is after" | "is higher than" => FilterOperator::GreaterThan,
+        "is less than" | "is before" | "is lower than" => FilterOperator::LessThan,
+        "matches regex" => FilterOperator::Regex,
+        other => return Err(format!("unsupported operator \"{}\"", other)),
+    };
+
+    Ok(FilterCondition::new(field, operator, condition.value.clone()))
+}
+
+fn convert_action(action: &ThunderbirdFilterAction) -> Result<FilterAction, String> {
+    let value = action.value.clone().unwrap_or_default();
+
+    match action.action_type.as_str() {
+        "Move to folder" => Ok(FilterAction::MoveToFolder(folder_from_uri(&value))),
+        "Copy to folder" => Ok(FilterAction::CopyToFolder(folder_from_uri(&value))),
+        "Mark read" => Ok(FilterAction::MarkAsRead),
+        "Mark unread" => Ok(FilterAction::MarkAsUnread),
+        "Mark flagged" => Ok(FilterAction::MarkAsImportant),
+        "Delete" => Ok(FilterAction::Delete),
+        "Forward" => Ok(FilterAction::Forward(value)),
+        "AddTag" => Ok(FilterAction::AddLabel(value)),
+        "Stop execution" => Ok(FilterAction::StopProcessing),
+        "JunkScore" if value == "100" => Ok(FilterAction::MarkAsSpam),
+        other => Err(format!("unsupported action \"{}\"", other)),
+    }
+}
+
+/// Convert a Thunderbird filter, returning warnings for anything dropped
+///
+/// Fails when the filter would match differently (OR-ed or unsupported
+/// conditions, or "match all messages") or has no supported action.
+pub fn convert_filter(
+    filter: &ThunderbirdFilter,
+    priority: i32,
+) -> Result<(EmailFilter, Vec<String>), String> {
+    if filter.conditions.is_empty() {
+        return Err("matches every message".to_string());
+    }
+    if filter.match_any && filter.conditions.len() > 1 {
+        return Err("uses \"match any\" conditions".to_string());
+    }
+
+    let mut converted = EmailFilter::new(
+        filter.name.clone(),
+        "Imported from Thunderbird".to_string(),
+    )
+    .set_priority(priority)
+    .set_enabled(filter.enabled);
+
+    for condition in &filter.conditions {
+        converted = converted.add_condition(convert_condition(condition)?);
+    }
+
+    let mut warnings = Vec::new();
+    for action in &filter.actions {
+        match convert_action(action) {
+            Ok(action) => converted = converted.add_action(action),
+            Err(reason) => warnings.push(format!("{}: dropped {}", filter.name, reason)),
+        }
+    }
+
+    if converted.actions.is_empty() {
+        return Err("no supported actions".to_string());
+    }
+
+    Ok((converted, warnings))
+}
+
+/// Convert parsed filters, keeping their order as priority
+pub fn convert_filters(filters: &[ThunderbirdFilter]) -> FilterImportReport {
+    let mut report = FilterImportReport::default();
+
+    for (index, filter) in filters.iter().enumerate() {
+        match convert_filter(filter, index as i32) {
+            Ok((converted, warnings)) => {
+                report.converted.push(converted);
+                report.warnings.extend(warnings);
+            }
+            Err(reason) => report.unconverted.push(UnconvertedFilter {
+                name: filter.name.clone(),
+                reason,
+            }),
+        }
+    }
+
+    report
+}
+
+fn describe_action(action: &FilterAction) -> String {
+    match action {
+        FilterAction::MoveToFolder(folder) => format!("move to {}", folder),
+        FilterAction::CopyToFolder(folder) => format!("copy to {}", folder),
+        FilterAction::AddLabel(label) => format!("tag {}", label),
+        FilterAction::Forward(address) => format!("forward to {}", address),
+        other => format!("{:?}", other),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -565,4 +923,56 @@ Path=efgh5678.work
         assert_eq!(profiles[1].name, "work");
         assert!(!profiles[1].is_default);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_filter_rules_parsing() {
+        let content = r#"version="9"
+logging="no"
+name="Receipts"
+enabled="yes"
+type="17"
+action="Move to folder"
+actionValue="imap://me%40example.com@imap.example.com/INBOX/Receipts%20%26%20Bills"
+action="Mark read"
+condition="AND (from,contains,shop) AND (subject,contains,\"order, confirmed\")"
+name="Team"
+enabled="no"
+type="17"
+action="AddTag"
+actionValue="$label1"
+action="Change priority"
+actionValue="Highest"
+condition="OR (to,contains,team@example.com)"
+name="Either"
+enabled="yes"
+type="17"
+action="Delete"
+condition="OR (from,is,a@example.com) OR (from,is,b@example.com)"
+name="Address book"
+enabled="yes"
+type="17"
+action="Mark flagged"
+condition="AND (from,is in ab,moz-abmdbdirectory://abook.mab)"
+"#;
+
+        let filters = parse_filter_rules(content).unwrap();
+        assert_eq!(filters.len(), 4);
+        assert_eq!(filters[0].conditions.len(), 2);
+        assert_eq!(filters[0].conditions[1].value, "order, confirmed");
+        assert_eq!(filters[0].actions.len(), 2);
+        assert!(!filters[1].enabled);
+        assert!(filters[2].match_any);
+
+        let report = convert_filters(&filters);
+        assert_eq!(report.converted.len(), 2);
+        assert!(matches!(
+            &report.converted[0].actions[0],
+            FilterAction::MoveToFolder(folder) if folder == "INBOX/Receipts & Bills"
+        ));
+        assert!(!report.converted[1].enabled);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.unconverted.len(), 2);
+        assert!(report.unconverted[1].reason.contains("is in ab"));
+        assert_eq!(report.preview_lines().len(), 6);
+    }
+}
@@ -1,3 +1,5 @@
 pub mod decoder;
+pub mod parser;
 
 pub use decoder::decode_mime_header;
+pub use parser::{parse_message, MimeDefect, ParsedBody};
@@ -0,0 +1,518 @@
+//! Tolerant MIME body parser
+//!
+//! Real-world mail is often broken: multiparts without a boundary parameter
+//! or without a closing delimiter, base64 with stray characters, parts cut
+//! off half way. Instead of giving up, the parser recovers whatever text and
+//! HTML it can and records each problem as a [`MimeDefect`], so the viewer
+//! can warn that the message may be incomplete. Every defect is logged at
+//! debug level. Attachments are skipped; only displayable text is collected,
+//! and the iCalendar part that meeting invitations carry.
+
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Nesting depth of multiparts the parser follows
+const MAX_DEPTH: usize = 8;
+
+/// Decodes base64 without padding and ignoring non-zero trailing bits
+const LENIENT_BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new()
+        .with_decode_allow_trailing_bits(true)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// A problem found while parsing, with the content that was recovered anyway
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MimeDefect {
+    /// Headers run straight into the body without a blank line
+    MissingHeaderSeparator,
+    /// A multipart has no boundary parameter
+    MissingBoundary { content_type: String },
+    /// The declared boundary never appears in the body
+    BoundaryNotFound { boundary: String },
+    /// The closing delimiter is missing, so the last part may be cut short
+    UnterminatedMultipart { boundary: String },
+    /// base64 with invalid characters or a truncated final group
+    InvalidBase64 { content_type: String },
+    /// quoted-printable with malformed escapes
+    InvalidQuotedPrintable { content_type: String },
+    /// Text that isn't valid in its declared charset
+    InvalidCharset { charset: String },
+    /// Multiparts nested deeper than the parser follows
+    TooDeeplyNested,
+}
+
+impl fmt::Display for MimeDefect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeaderSeparator => write!(f, "headers and body are not separated"),
+            Self::MissingBoundary { content_type } => {
+                write!(f, "{} part has no boundary", content_type)
+            }
+            Self::BoundaryNotFound { boundary } => {
+                write!(f, "boundary \"{}\" never appears", boundary)
+            }
+            Self::UnterminatedMultipart { boundary } => {
+                write!(f, "multipart \"{}\" is not closed, the last part may be truncated", boundary)
+            }
+            Self::InvalidBase64 { content_type } => {
+                write!(f, "{} part has invalid base64", content_type)
+            }
+            Self::InvalidQuotedPrintable { content_type } => {
+                write!(f, "{} part has invalid quoted-printable", content_type)
+            }
+            Self::InvalidCharset { charset } => write!(f, "text is not valid {}", charset),
+            Self::TooDeeplyNested => write!(f, "parts are nested too deeply"),
+        }
+    }
+}
+
+/// Displayable content of a message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedBody {
+    /// Inline `text/plain` parts, joined
+    pub text: Option<String>,
+    /// Inline `text/html` parts, joined
+    pub html: Option<String>,
+    /// The first `text/calendar` part, inline or attached
+    pub calendar: Option<String>,
+    pub defects: Vec<MimeDefect>,
+}
+
+impl ParsedBody {
+    /// Whether any text or HTML was found
+    pub fn has_content(&self) -> bool {
+        self.text.is_some() || self.html.is_some()
+    }
+
+    pub fn is_malformed(&self) -> bool {
+        !self.defects.is_empty()
+    }
+
+    fn defect(&mut self, defect: MimeDefect) {
+        tracing::debug!("Malformed MIME: {}", defect);
+        if !self.defects.contains(&defect) {
+            self.defects.push(defect);
+        }
+    }
+
+    fn append(target: &mut Option<String>, content: String) {
+        match target {
+            Some(existing) => {
+                existing.push_str("\n\n");
+                existing.push_str(&content);
+            }
+            None => *target = Some(content),
+        }
+    }
+}
+
+/// Parse a raw RFC 5322 message into its displayable text and HTML
+///
+/// Never fails: a message that can't be parsed at all comes back without
+/// content, and the caller falls back to showing it as it is.
+pub fn parse_message(raw: &str) -> ParsedBody {
+    let mut parsed = ParsedBody::default();
+    let (headers, body) = split_headers(raw, &mut parsed);
+    parse_entity(&headers, body, 0, &mut parsed);
+    parsed
+}
+
+type Headers = Vec<(String, String)>;
+
+/// Split an entity into its headers and body
+fn split_headers<'a>(entity: &'a str, parsed: &mut ParsedBody) -> (Headers, &'a str) {
+    let mut headers: Headers = Vec::new();
+    let mut offset = 0;
+
+    for line in entity.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\r', '\n']);
+        if content.is_empty() {
+            return (headers, &entity[offset + line.len()..]);
+        }
+
+        if content.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(content.trim());
+                offset += line.len();
+                continue;
+            }
+        } else if let Some((name, value)) = content.split_once(':') {
+            if !name.is_empty() && !name.contains(char::is_whitespace) {
+                headers.push((name.to_ascii_lowercase(), value.trim().to_string()));
+                offset += line.len();
+                continue;
+            }
+        }
+
+        // Not a header line: the body starts here
+        if !headers.is_empty() {
+            parsed.defect(MimeDefect::MissingHeaderSeparator);
+        }
+        return (headers, &entity[offset..]);
+    }
+
+    (headers, &entity[entity.len()..])
+}
+
+fn header<'h>(headers: &'h Headers, name: &str) -> Option<&'h str> {
+    headers
+        .iter()
+        .find(|(header, _)| header == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Media type and parameters of a Content-Type value
+fn content_type(headers: &Headers) -> (String, Vec<(String, String)>) {
+    let Some(value) = header(headers, "content-type") else {
+        return ("text/plain".to_string(), Vec::new());
+    };
+
+    let mut fields = value.split(';');
+    let media_type = fields
+        .next()
+        .map(|media_type| media_type.trim().to_ascii_lowercase())
+        .filter(|media_type| !media_type.is_empty())
+        .unwrap_or_else(|| "text/plain".to_string());
+    let params = fields
+        .filter_map(|param| param.split_once('='))
+        .map(|(name, value)| {
+            (
+                name.trim().to_ascii_lowercase(),
+                value.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+
+    (media_type, params)
+}
+
+fn param<'p>(params: &'p [(String, String)], name: &str) -> Option<&'p str> {
+    params
+        .iter()
+        .find(|(param, _)| param == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn parse_entity(headers: &Headers, body: &str, depth: usize, parsed: &mut ParsedBody) {
+    let (media_type, params) = content_type(headers);
+
+    if media_type.starts_with("multipart/") {
+        if depth >= MAX_DEPTH {
+            parsed.defect(MimeDefect::TooDeeplyNested);
+            return;
+        }
+        parse_multipart(&media_type, &params, body, depth, parsed);
+        return;
+    }
+
+    // Invitations often attach the same calendar again as invite.ics
+    if media_type == "text/calendar" || media_type == "application/ics" {
+        if parsed.calendar.is_none() {
+            parsed.calendar = Some(decode_text(headers, &params, body, &media_type, parsed));
+        }
+        return;
+    }
+
+    let is_attachment = header(headers, "content-disposition")
+        .is_some_and(|disposition| disposition.to_ascii_lowercase().starts_with("attachment"));
+    if is_attachment || (media_type != "text/plain" && media_type != "text/html") {
+        return;
+    }
+
+    let text = decode_text(headers, &params, body, &media_type, parsed).replace("\r\n", "\n");
+
+    if media_type == "text/html" {
+        ParsedBody::append(&mut parsed.html, text);
+    } else {
+        ParsedBody::append(&mut parsed.text, text);
+    }
+}
+
+/// Undo the transfer encoding and charset of a part
+fn decode_text(
+    headers: &Headers,
+    params: &[(String, String)],
+    body: &str,
+    media_type: &str,
+    parsed: &mut ParsedBody,
+) -> String {
+    let encoding = header(headers, "content-transfer-encoding")
+        .map(|encoding| encoding.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    let bytes = match encoding.as_str() {
+        "base64" => decode_base64(body, media_type, parsed),
+        "quoted-printable" => decode_quoted_printable(body, media_type, parsed),
+        _ => body.as_bytes().to_vec(),
+    };
+    decode_charset(bytes, param(params, "charset"), parsed)
+}
+
+fn parse_multipart(
+    media_type: &str,
+    params: &[(String, String)],
+    body: &str,
+    depth: usize,
+    parsed: &mut ParsedBody,
+) {
+    let boundary = match param(params, "boundary").filter(|boundary| !boundary.is_empty()) {
+        Some(boundary) => boundary.to_string(),
+        None => {
+            parsed.defect(MimeDefect::MissingBoundary {
+                content_type: media_type.to_string(),
+            });
+            // The first delimiter-looking line is the best guess
+            match body
+                .lines()
+                .map(str::trim_end)
+                .find(|line| line.len() > 2 && line.starts_with("--"))
+            {
+                Some(line) => line[2..].to_string(),
+                None => {
+                    // Nothing to split on; show the body as text
+                    ParsedBody::append(&mut parsed.text, body.replace("\r\n", "\n"));
+                    return;
+                }
+            }
+        }
+    };
+
+    let delimiter = format!("--{}", boundary);
+    let close = format!("--{}--", boundary);
+    let mut parts: Vec<&str> = Vec::new();
+    let mut part_start: Option<usize> = None;
+    let mut closed = false;
+    let mut offset = 0;
+
+    for line in body.split_inclusive('\n') {
+        let content = line.trim_end();
+        if content == close || content == delimiter {
+            if let Some(start) = part_start {
+                parts.push(&body[start..offset]);
+            }
+            if content == close {
+                closed = true;
+                break;
+            }
+            part_start = Some(offset + line.len());
+        }
+        offset += line.len();
+    }
+
+    match part_start {
+        None => {
+            parsed.defect(MimeDefect::BoundaryNotFound { boundary });
+            ParsedBody::append(&mut parsed.text, body.replace("\r\n", "\n"));
+            return;
+        }
+        Some(start) if !closed => {
+            parsed.defect(MimeDefect::UnterminatedMultipart { boundary });
+            parts.push(&body[start..]);
+        }
+        Some(_) => {}
+    }
+
+    for part in parts {
+        let (headers, part_body) = split_headers(part, parsed);
+        parse_entity(&headers, part_body, depth + 1, parsed);
+    }
+}
+
+fn decode_base64(body: &str, media_type: &str, parsed: &mut ParsedBody) -> Vec<u8> {
+    let mut valid = true;
+    let mut padding_seen = false;
+    let mut data = String::with_capacity(body.len());
+
+    for c in body.chars() {
+        match c {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '+' | '/' => {
+                // Data after padding: two encodings glued together
+                valid &= !padding_seen;
+                data.push(c);
+            }
+            '=' => padding_seen = true,
+            c if c.is_whitespace() => {}
+            _ => valid = false,
+        }
+    }
+
+    // A single leftover character can't encode a byte
+    if data.len() % 4 == 1 {
+        data.pop();
+        valid = false;
+    }
+
+    let bytes = LENIENT_BASE64.decode(data.as_bytes()).unwrap_or_else(|_| {
+        valid = false;
+        Vec::new()
+    });
+    if !valid {
+        parsed.defect(MimeDefect::InvalidBase64 {
+            content_type: media_type.to_string(),
+        });
+    }
+    bytes
+}
+
+fn decode_quoted_printable(body: &str, media_type: &str, parsed: &mut ParsedBody) -> Vec<u8> {
+    let bytes = body.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut valid = true;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if byte != b'=' {
+            decoded.push(byte);
+            index += 1;
+            continue;
+        }
+
+        let rest = &bytes[index + 1..];
+        if rest.starts_with(b"\r\n") {
+            index += 3; // Soft line break
+        } else if rest.starts_with(b"\n") {
+            index += 2;
+        } else if rest.is_empty() {
+            index += 1;
+        } else {
+            let hex = rest
+                .get(..2)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            match hex {
+                Some(value) => {
+                    decoded.push(value);
+                    index += 3;
+                }
+                None => {
+                    // Keep the stray '=' as it is
+                    valid = false;
+                    decoded.push(b'=');
+                    index += 1;
+                }
+            }
+        }
+    }
+
+    if !valid {
+        parsed.defect(MimeDefect::InvalidQuotedPrintable {
+            content_type: media_type.to_string(),
+        });
+    }
+    decoded
+}
+
+fn decode_charset(bytes: Vec<u8>, charset: Option<&str>, parsed: &mut ParsedBody) -> String {
+    let charset = charset.unwrap_or("us-ascii").to_ascii_lowercase();
+    let latin1 = |bytes: &[u8]| bytes.iter().map(|&b| b as char).collect::<String>();
+
+    match charset.as_str() {
+        "iso-8859-1" | "iso-8859-15" | "latin1" | "windows-1252" | "cp1252" => latin1(&bytes),
+        // Undeclared 8-bit text is usually UTF-8, otherwise Latin-1
+        "us-ascii" | "ascii" => match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) => {
+                parsed.defect(MimeDefect::InvalidCharset { charset });
+                latin1(e.as_bytes())
+            }
+        },
+        _ => match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(e) => {
+                parsed.defect(MimeDefect::InvalidCharset { charset });
+                String::from_utf8_lossy(e.as_bytes()).into_owned()
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_malformed_messages() {
+        // Well-formed alternative: both parts, no defects
+        let good = "From: a@example.com\r\n\
+            Content-Type: multipart/alternative; boundary=\"b1\"\r\n\r\n\
+            --b1\r\nContent-Type: text/plain; charset=utf-8\r\n\r\nHello\r\n\
+            --b1\r\nContent-Type: text/html\r\nContent-Transfer-Encoding: base64\r\n\r\n\
+            PHA+SGVsbG88L3A+\r\n--b1--\r\n";
+        let parsed = parse_message(good);
+        assert_eq!(parsed.text.as_deref(), Some("Hello\n"));
+        assert_eq!(parsed.html.as_deref(), Some("<p>Hello</p>"));
+        assert!(!parsed.is_malformed());
+
+        // Missing closing delimiter and bad base64: the text is still recovered
+        let truncated = "Content-Type: multipart/mixed; boundary=x\n\n\
+            --x\nContent-Type: text/plain\nContent-Transfer-Encoding: base64\n\n\
+            SGVsbG8g!V29y\n";
+        let parsed = parse_message(truncated);
+        assert_eq!(parsed.text.as_deref(), Some("Hello Wor"));
+        assert_eq!(
+            parsed.defects,
+            vec![
+                MimeDefect::UnterminatedMultipart { boundary: "x".to_string() },
+                MimeDefect::InvalidBase64 { content_type: "text/plain".to_string() },
+            ]
+        );
+
+        // No boundary parameter: guessed from the body
+        let parsed = parse_message(
+            "Content-Type: multipart/alternative\n\n--zz\nContent-Type: text/plain\n\nGuessed\n--zz--\n",
+        );
+        assert_eq!(parsed.text.as_deref(), Some("Guessed\n"));
+        assert!(matches!(parsed.defects[..], [MimeDefect::MissingBoundary { .. }]));
+
+        // Boundary that never appears, broken quoted-printable, bad UTF-8
+        let parsed = parse_message("Content-Type: multipart/mixed; boundary=nope\n\nJust text\n");
+        assert_eq!(parsed.text.as_deref(), Some("Just text\n"));
+        assert!(matches!(parsed.defects[..], [MimeDefect::BoundaryNotFound { .. }]));
+
+        let parsed = parse_message(
+            "Content-Type: text/plain; charset=utf-8\nContent-Transfer-Encoding: quoted-printable\n\n\
+             Caf=C3=A9 =ZZ soft=\nbreak =FF",
+        );
+        assert_eq!(parsed.text.as_deref(), Some("Café =ZZ softbreak \u{FFFD}"));
+        assert_eq!(parsed.defects.len(), 2);
+
+        // Attachments are skipped, and garbage never panics
+        let parsed = parse_message(
+            "Content-Type: multipart/mixed; boundary=m\n\n--m\nContent-Type: text/plain\n\nBody\n\
+             --m\nContent-Type: text/plain\nContent-Disposition: attachment\n\nnotes.txt\n--m--",
+        );
+        assert_eq!(parsed.text.as_deref(), Some("Body\n"));
+        for garbage in ["", "\n", "--", "Content-Type: multipart/mixed; boundary=\n\n--", "a:b\nc"] {
+            let _ = parse_message(garbage);
+        }
+    }
+
+    #[test]
+    fn test_invitation_calendar_part() {
+        // The inline calendar is kept; the attached copy doesn't replace it
+        let parsed = parse_message(
+            "Content-Type: multipart/mixed; boundary=m\n\n\
+             --m\nContent-Type: multipart/alternative; boundary=a\n\n\
+             --a\nContent-Type: text/plain\n\nYou have been invited\n\
+             --a\nContent-Type: text/calendar; charset=utf-8; method=REQUEST\n\
+             Content-Transfer-Encoding: base64\n\n\
+             QkVHSU46VkNBTEVOREFSDQpNRVRIT0Q6UkVRVUVTVA0KRU5EOlZDQUxFTkRBUg0K\n\
+             --a--\n\
+             --m\nContent-Type: application/ics; name=invite.ics\n\
+             Content-Disposition: attachment; filename=invite.ics\n\nBEGIN:VCALENDAR\n--m--\n",
+        );
+        assert_eq!(parsed.text.as_deref(), Some("You have been invited\n"));
+        assert_eq!(
+            parsed.calendar.as_deref(),
+            Some("BEGIN:VCALENDAR\r\nMETHOD:REQUEST\r\nEND:VCALENDAR\r\n")
+        );
+        assert!(!parsed.is_malformed());
+    }
+}
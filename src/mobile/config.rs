@@ -163,6 +163,17 @@ impl Default for PrivacySettings {
 }
 
 impl MobileConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "mobile.toml";
+
+    /// Load the settings from the config directory, writing the defaults
+    /// there when the file doesn't exist
+    pub async fn load() -> anyhow::Result<Self> {
+        let config: Self = crate::config::load_or_default(Self::CONFIG_FILE).await?;
+        config.validate()?;
+        Ok(config)
+    }
+
     pub fn load_from_file(path: &str) -> crate::mobile::Result<Self> {
         let content = std::fs::read_to_string(path)
             .map_err(|e| crate::mobile::MobileError::IoError(e))?;
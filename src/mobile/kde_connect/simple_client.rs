@@ -6,6 +6,7 @@ use super::types::{DeviceInfo, SmsMessage, MobileNotification};
 
 /// Production KDE Connect client that attempts to use real D-Bus connections
 /// when KDE Connect is available, otherwise provides clear error messages
+#[derive(Clone)]
 pub struct KdeConnectClient {
     device_id: Option<String>,
     timeout: Duration,
@@ -382,6 +383,90 @@ impl KdeConnectClient {
         }
     }
 
+    /// Reply to an SMS conversation
+    ///
+    /// Sends `text` with the SMS plugin's D-Bus `sendSms` to `addresses`,
+    /// everyone in the conversation, so the phone files it in the same thread.
+    pub fn send_reply(
+        &self,
+        conversation_id: i64,
+        addresses: &[String],
+        text: &str,
+    ) -> crate::mobile::Result<()> {
+        if !self.kde_connect_available {
+            return Err(crate::mobile::MobileError::KdeConnectNotAvailable(
+                "KDE Connect is not available".to_string()
+            ));
+        }
+
+        let device_id = self.device_id.as_ref()
+            .ok_or_else(|| crate::mobile::MobileError::DeviceNotPaired("No device connected".to_string()))?;
+
+        if text.trim().is_empty() {
+            return Err(crate::mobile::MobileError::MessageSendFailed(
+                "Reply is empty".to_string()
+            ));
+        }
+        if addresses.is_empty() {
+            return Err(crate::mobile::MobileError::MessageSendFailed(
+                format!("Conversation {} has no addresses", conversation_id)
+            ));
+        }
+
+        info!("Replying to conversation {} via device {}", conversation_id, device_id);
+
+        #[cfg(feature = "kde-connect")]
+        {
+            use dbus::arg::{RefArg, Variant};
+            use dbus::blocking::stdintf::org_freedesktop_dbus::Properties;
+
+            let connection = dbus::blocking::Connection::new_session()?;
+            let device_path = format!("/modules/kdeconnect/devices/{}", device_id);
+            let device = connection.with_proxy("org.kde.kdeconnect", &device_path, self.timeout);
+
+            if !device.get::<bool>("org.kde.kdeconnect.device", "isPaired").unwrap_or(false) {
+                return Err(crate::mobile::MobileError::DeviceNotPaired(
+                    format!("Device {} is not paired", device_id)
+                ));
+            }
+            if !device.get::<bool>("org.kde.kdeconnect.device", "isReachable").unwrap_or(false) {
+                return Err(crate::mobile::MobileError::DeviceNotReachable(
+                    format!("Device {} is not reachable", device_id)
+                ));
+            }
+
+            // Each address is a ConversationAddress, a struct holding the number
+            let addresses: Vec<Variant<(String,)>> = addresses
+                .iter()
+                .map(|address| Variant((address.clone(),)))
+                .collect();
+            let attachments: Vec<Variant<Box<dyn RefArg>>> = Vec::new();
+            let sms = connection.with_proxy(
+                "org.kde.kdeconnect",
+                format!("{}/sms", device_path),
+                self.timeout,
+            );
+            sms.method_call::<(), _, _, _>(
+                "org.kde.kdeconnect.device.sms",
+                "sendSms",
+                (addresses, text, attachments),
+            )
+            .map_err(|e| crate::mobile::MobileError::MessageSendFailed(
+                format!("Failed to reply to conversation {}: {}", conversation_id, e)
+            ))?;
+
+            info!("Reply sent to conversation {}", conversation_id);
+            Ok(())
+        }
+
+        #[cfg(not(feature = "kde-connect"))]
+        {
+            Err(crate::mobile::MobileError::KdeConnectNotAvailable(
+                "KDE Connect support not compiled".to_string()
+            ))
+        }
+    }
+
     /// Listen for incoming SMS messages (placeholder - would use D-Bus in real implementation)
     pub async fn listen_for_messages(&mut self) -> crate::mobile::Result<mpsc::UnboundedReceiver<SmsMessage>> {
         if !self.is_connected() {
@@ -537,5 +622,23 @@ mod tests {
         assert!(client.discover_devices().is_err());
         assert!(client.connect_device("test".to_string()).is_err());
         assert!(client.send_sms("test", &["123".to_string()]).is_err());
+        assert!(matches!(
+            client.send_reply(42, &["123".to_string()], "test"),
+            Err(crate::mobile::MobileError::KdeConnectNotAvailable(_))
+        ));
+    }
+
+    #[test]
+    fn test_send_reply_without_device() {
+        let client = KdeConnectClient {
+            device_id: None,
+            timeout: Duration::from_secs(30),
+            kde_connect_available: true,
+        };
+
+        assert!(matches!(
+            client.send_reply(42, &["+1234567890".to_string()], "On my way"),
+            Err(crate::mobile::MobileError::DeviceNotPaired(_))
+        ));
     }
 }
\ No newline at end of file
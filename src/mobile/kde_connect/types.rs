@@ -164,6 +164,19 @@ impl SmsConversation {
             .collect()
     }
 
+    /// Name of the participant with an address, or the address itself for
+    /// someone not in contacts
+    pub fn participant_label(&self, address: &str) -> String {
+        let digits = |number: &str| -> String { number.chars().filter(char::is_ascii_digit).collect() };
+        let wanted = digits(address);
+
+        self.participants
+            .iter()
+            .find(|contact| contact.address == address || (!wanted.is_empty() && digits(&contact.address) == wanted))
+            .and_then(|contact| contact.display_name.clone())
+            .unwrap_or_else(|| address.to_string())
+    }
+
     pub fn update_last_activity(&mut self, timestamp: DateTime<Utc>) {
         self.last_message_date = timestamp;
     }
@@ -484,6 +497,29 @@ mod tests {
         assert_eq!(attachment.size_formatted(), "5 B");
     }
 
+    #[test]
+    fn test_group_participant_labels() {
+        let conversation = SmsConversation {
+            id: 1,
+            thread_id: 7,
+            display_name: "Ana, +44 7700 900123".to_string(),
+            participants: vec![
+                ContactInfo::new("+1 (555) 010-0199".to_string(), Some("Ana".to_string())),
+                ContactInfo::new("+447700900123".to_string(), None),
+            ],
+            message_count: 2,
+            unread_count: 0,
+            last_message_date: chrono::Utc::now(),
+            is_archived: false,
+            messages: vec![],
+        };
+
+        assert!(conversation.is_group_conversation());
+        assert_eq!(conversation.participant_label("+15550100199"), "Ana");
+        assert_eq!(conversation.participant_label("+447700900123"), "+447700900123");
+        assert_eq!(conversation.participant_label("+15550100000"), "+15550100000");
+    }
+
     #[test]
     fn test_notification_action() {
         let reply_action = NotificationAction::new("reply".to_string(), "Reply".to_string());
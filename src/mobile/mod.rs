@@ -7,7 +7,7 @@ pub mod config;
 // Re-export main types for easier access
 pub use kde_connect::{KdeConnectClient, SmsMessage, MobileNotification, DeviceInfo};
 pub use storage::{MessageStore, MessageStoreStats};
-pub use services::{MobileSyncService, MobileSyncStats, ServiceControl};
+pub use services::{MobileSyncService, MobileSyncStats, ServiceControl, SmsSession};
 pub use config::{MobileConfig, SmsSettings, NotificationSettings};
 pub use ui::{SmsUi, SmsViewMode, SmsComposition, SmsRenderConfig, SmsColorScheme};
 
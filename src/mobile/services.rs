@@ -1,14 +1,14 @@
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, RwLock, Mutex};
+use tokio::sync::{broadcast, mpsc, RwLock, Mutex};
 use tokio::time::Interval;
 use chrono::{DateTime, Utc};
 use tracing::{info, debug, warn, error};
 use serde::{Deserialize, Serialize};
 
 use crate::mobile::{
-    KdeConnectClient, MessageStore, MobileConfig, Result,
-    kde_connect::types::MobileNotification
+    KdeConnectClient, MessageStore, MobileConfig, Result, SmsUi,
+    kde_connect::types::{MobileNotification, SmsMessage}
 };
 
 /// Background service that synchronizes SMS/MMS messages and notifications
@@ -21,6 +21,8 @@ pub struct MobileSyncService {
     control_tx: mpsc::UnboundedSender<ServiceControl>,
     control_rx: Arc<Mutex<mpsc::UnboundedReceiver<ServiceControl>>>,
     is_running: Arc<RwLock<bool>>,
+    /// Incoming messages, as they are stored
+    incoming_tx: broadcast::Sender<SmsMessage>,
 }
 
 /// Statistics and status information for the mobile sync service
@@ -70,6 +72,7 @@ impl MobileSyncService {
         message_store: Arc<MessageStore>,
     ) -> Result<Self> {
         let (control_tx, control_rx) = mpsc::unbounded_channel();
+        let (incoming_tx, _) = broadcast::channel(64);
         
         let stats = MobileSyncStats {
             is_running: false,
@@ -95,6 +98,7 @@ impl MobileSyncService {
             control_tx,
             control_rx: Arc::new(Mutex::new(control_rx)),
             is_running: Arc::new(RwLock::new(false)),
+            incoming_tx,
         })
     }
 
@@ -103,6 +107,11 @@ impl MobileSyncService {
         self.control_tx.clone()
     }
 
+    /// Receive incoming messages as they are synced, e.g. to notify about them
+    pub fn subscribe_incoming(&self) -> broadcast::Receiver<SmsMessage> {
+        self.incoming_tx.subscribe()
+    }
+
     /// Get current service statistics
     pub async fn get_stats(&self) -> MobileSyncStats {
         let mut stats = self.stats.read().await.clone();
@@ -142,10 +151,20 @@ impl MobileSyncService {
         let stats = self.stats.clone();
         let control_rx = self.control_rx.clone();
         let is_running = self.is_running.clone();
+        let incoming_tx = self.incoming_tx.clone();
 
         // Spawn the main service loop
         tokio::spawn(async move {
-            Self::service_loop(config, kde_connect, message_store, stats, control_rx, is_running).await;
+            Self::service_loop(
+                config,
+                kde_connect,
+                message_store,
+                stats,
+                control_rx,
+                is_running,
+                incoming_tx,
+            )
+            .await;
         });
 
         info!("Mobile sync service started successfully");
@@ -188,6 +207,7 @@ impl MobileSyncService {
         stats: Arc<RwLock<MobileSyncStats>>,
         control_rx: Arc<Mutex<mpsc::UnboundedReceiver<ServiceControl>>>,
         is_running: Arc<RwLock<bool>>,
+        incoming_tx: broadcast::Sender<SmsMessage>,
     ) {
         let mut sync_interval = Self::create_sync_interval(&config).await;
         let mut paused = false;
@@ -218,7 +238,7 @@ impl MobileSyncService {
                         Some(ServiceControl::ForceSync) => {
                             if !paused {
                                 debug!("Performing forced sync");
-                                Self::perform_sync(&kde_connect, &message_store, &stats).await;
+                                Self::perform_sync(&kde_connect, &message_store, &stats, &incoming_tx).await;
                             }
                         }
                         Some(ServiceControl::Reconnect) => {
@@ -245,7 +265,7 @@ impl MobileSyncService {
                 // Handle periodic sync
                 _ = sync_interval.tick(), if !paused => {
                     debug!("Performing scheduled sync");
-                    Self::perform_sync(&kde_connect, &message_store, &stats).await;
+                    Self::perform_sync(&kde_connect, &message_store, &stats, &incoming_tx).await;
                 }
 
                 // Update uptime stats every minute
@@ -277,6 +297,7 @@ impl MobileSyncService {
         kde_connect: &Arc<Mutex<KdeConnectClient>>,
         message_store: &Arc<MessageStore>,
         stats: &Arc<RwLock<MobileSyncStats>>,
+        incoming_tx: &broadcast::Sender<SmsMessage>,
     ) {
         let sync_start = Utc::now();
         let mut result = SyncResult {
@@ -287,7 +308,7 @@ impl MobileSyncService {
         };
 
         // Sync SMS messages
-        match Self::sync_messages(kde_connect, message_store).await {
+        match Self::sync_messages(kde_connect, message_store, incoming_tx).await {
             Ok(message_count) => {
                 result.messages_processed = message_count;
                 debug!("Synced {} messages", message_count);
@@ -340,6 +361,7 @@ impl MobileSyncService {
     async fn sync_messages(
         kde_connect: &Arc<Mutex<KdeConnectClient>>,
         message_store: &Arc<MessageStore>,
+        incoming_tx: &broadcast::Sender<SmsMessage>,
     ) -> Result<u32> {
         let mut client = kde_connect.lock().await;
         
@@ -365,9 +387,14 @@ impl MobileSyncService {
                         Some(message) => {
                             debug!("Received message: ID={}, Thread={}", message.id, message.thread_id);
                             
+                            let incoming = (!message.is_outgoing()).then(|| message.clone());
                             match message_store.store_message(message).await {
                                 Ok(conversation_id) => {
                                     messages_processed += 1;
+                                    if let Some(message) = incoming {
+                                        // Nobody listening is fine
+                                        let _ = incoming_tx.send(message);
+                                    }
                                     debug!("Stored message in conversation {}", conversation_id);
                                 }
                                 Err(e) => {
@@ -545,6 +572,75 @@ impl MobileSyncService {
     }
 }
 
+/// SMS in the TUI: the sync service with the screen showing its messages
+///
+/// Incoming messages are handed out by [`SmsSession::next_incoming`] so the
+/// app can notify about them and offer a reply.
+pub struct SmsSession {
+    pub screen: Arc<SmsUi>,
+    pub store: Arc<MessageStore>,
+    pub client: Arc<Mutex<KdeConnectClient>>,
+    /// Whether arriving messages are notified about
+    pub notify: bool,
+    /// Whether notifications may show the message text
+    pub show_preview: bool,
+    service: MobileSyncService,
+    incoming: broadcast::Receiver<SmsMessage>,
+}
+
+impl SmsSession {
+    /// Open the message store and start syncing with the configured device,
+    /// or the first reachable one with the SMS plugin
+    pub async fn start(config: MobileConfig) -> Result<Self> {
+        let mut client = KdeConnectClient::new()?;
+        if let Some(device_id) = config.kde_connect_device_id.clone() {
+            client.connect_device(device_id)?;
+        }
+        let connected = client.is_connected();
+        let client = Arc::new(Mutex::new(client));
+
+        let store = Arc::new(MessageStore::new(&config.storage.database_path).await?);
+        let notify = config.sms.notification_on_receive;
+        let show_preview = config.notifications.show_preview && config.privacy.notification_preview;
+        let service = MobileSyncService::new(config, client.clone(), store.clone()).await?;
+        let incoming = service.subscribe_incoming();
+        service.start().await?;
+        if !connected {
+            let _ = service.control_handle().send(ServiceControl::Reconnect);
+        }
+
+        Ok(Self {
+            screen: Arc::new(SmsUi::new()),
+            store,
+            client,
+            notify,
+            show_preview,
+            service,
+            incoming,
+        })
+    }
+
+    /// Next message that arrived since the last call, if any
+    pub fn next_incoming(&mut self) -> Option<SmsMessage> {
+        loop {
+            match self.incoming.try_recv() {
+                Ok(message) => return Some(message),
+                Err(broadcast::error::TryRecvError::Lagged(skipped)) => {
+                    warn!("Skipped notifying about {} SMS messages", skipped);
+                }
+                Err(_) => return None,
+            }
+        }
+    }
+
+    /// Stop syncing
+    pub async fn stop(&self) {
+        if let Err(e) = self.service.stop().await {
+            warn!("Failed to stop mobile sync: {}", e);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
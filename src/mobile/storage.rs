@@ -103,6 +103,7 @@ impl MessageStore {
                 date_sent INTEGER NOT NULL,
                 date_received INTEGER NOT NULL,
                 sub_id INTEGER NOT NULL DEFAULT 1,
+                addresses TEXT, -- JSON list of the message's addresses, sender first
                 created_at INTEGER NOT NULL,
                 updated_at INTEGER NOT NULL,
                 FOREIGN KEY (conversation_id) REFERENCES conversations (id) ON DELETE CASCADE
@@ -134,6 +135,7 @@ impl MessageStore {
             CREATE TABLE IF NOT EXISTS message_attachments (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 message_id INTEGER NOT NULL,
+                part_id INTEGER NOT NULL DEFAULT 0,
                 filename TEXT NOT NULL,
                 mime_type TEXT NOT NULL,
                 file_size INTEGER NOT NULL,
@@ -148,6 +150,10 @@ impl MessageStore {
         .await
         .map_err(crate::mobile::MobileError::DatabaseError)?;
 
+        // Columns added since the first schema
+        self.add_column_if_missing("messages", "addresses", "TEXT").await?;
+        self.add_column_if_missing("message_attachments", "part_id", "INTEGER NOT NULL DEFAULT 0").await?;
+
         // Create indexes for better query performance
         let indexes = [
             "CREATE INDEX IF NOT EXISTS idx_conversations_thread_id ON conversations (thread_id)",
@@ -185,7 +191,29 @@ impl MessageStore {
         Ok(())
     }
 
+    /// Add a column to a table created by an older version of the schema
+    async fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<()> {
+        let exists = sqlx::query(&format!("SELECT 1 FROM pragma_table_info('{}') WHERE name = ?", table))
+            .bind(column)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(crate::mobile::MobileError::DatabaseError)?
+            .is_some();
+
+        if !exists {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+                .execute(&self.pool)
+                .await
+                .map_err(crate::mobile::MobileError::DatabaseError)?;
+        }
+        Ok(())
+    }
+
     /// Store a new SMS message, creating conversation if needed
+    ///
+    /// Messages thread by their KDE Connect conversation (`thread_id`), so a
+    /// group conversation stays one thread whoever sends; participants seen
+    /// for the first time are added to it.
     pub async fn store_message(&self, message: SmsMessage) -> Result<i64> {
         debug!("Storing SMS message: ID={}, Thread={}", message.id, message.thread_id);
 
@@ -204,8 +232,8 @@ impl MessageStore {
 
         sqlx::query(r#"
             INSERT OR REPLACE INTO messages 
-            (id, conversation_id, body, message_type, is_read, date_sent, date_received, sub_id, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            (id, conversation_id, body, message_type, is_read, date_sent, date_received, sub_id, addresses, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#)
         .bind(message.id)
         .bind(conversation_id)
@@ -215,13 +243,21 @@ impl MessageStore {
         .bind(message.date)
         .bind(now * 1000) // Current time as received time
         .bind(message.sub_id)
+        .bind(serde_json::to_string(&message.addresses)?)
         .bind(now)
         .bind(now)
         .execute(&mut *tx)
         .await
         .map_err(crate::mobile::MobileError::DatabaseError)?;
 
-        // Store attachments if any
+        self.store_participants(&mut tx, conversation_id, &message.addresses).await?;
+
+        // A message synced again brings its attachments again
+        sqlx::query("DELETE FROM message_attachments WHERE message_id = ?")
+            .bind(message.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(crate::mobile::MobileError::DatabaseError)?;
         for attachment in &message.attachments {
             self.store_attachment(&mut tx, message.id, attachment).await?;
         }
@@ -272,26 +308,38 @@ impl MessageStore {
 
         let conversation_id = result.last_insert_rowid();
 
-        // Store conversation contacts
-        for (index, address) in message.addresses.iter().enumerate() {
+        debug!("Created new conversation {} for thread {}", conversation_id, message.thread_id);
+        Ok((conversation_id, true))
+    }
+
+    /// Add a message's addresses to its conversation's participants
+    async fn store_participants(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        conversation_id: i64,
+        addresses: &[String],
+    ) -> Result<()> {
+        let now = Utc::now().timestamp();
+
+        for address in addresses {
             let contact_info = ContactInfo::from_address(address);
+            // The first participant of a conversation is its primary contact
             sqlx::query(r#"
                 INSERT OR IGNORE INTO conversation_contacts 
                 (conversation_id, phone_number, display_name, is_primary, created_at)
-                VALUES (?, ?, ?, ?, ?)
+                VALUES (?, ?, ?, NOT EXISTS (SELECT 1 FROM conversation_contacts WHERE conversation_id = ?), ?)
             "#)
             .bind(conversation_id)
             .bind(&contact_info.address)
             .bind(contact_info.display_name.as_deref())
-            .bind(index == 0) // First contact is primary
+            .bind(conversation_id)
             .bind(now)
             .execute(&mut **tx)
             .await
             .map_err(crate::mobile::MobileError::DatabaseError)?;
         }
 
-        debug!("Created new conversation {} for thread {}", conversation_id, message.thread_id);
-        Ok((conversation_id, true))
+        Ok(())
     }
 
     /// Store message attachment
@@ -305,10 +353,11 @@ impl MessageStore {
         
         sqlx::query(r#"
             INSERT INTO message_attachments 
-            (message_id, filename, mime_type, file_size, data, is_downloaded, download_url, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            (message_id, part_id, filename, mime_type, file_size, data, is_downloaded, download_url, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#)
         .bind(message_id)
+        .bind(attachment.part_id)
         .bind(&attachment.filename)
         .bind(&attachment.mime_type)
         .bind(attachment.file_size)
@@ -323,6 +372,27 @@ impl MessageStore {
         Ok(())
     }
 
+    /// Store the content of an MMS attachment downloaded after its message arrived
+    pub async fn store_attachment_data(&self, message_id: i32, part_id: i64, data: &[u8]) -> Result<()> {
+        let result = sqlx::query(r#"
+            UPDATE message_attachments
+            SET data = ?, file_size = ?, is_downloaded = TRUE
+            WHERE message_id = ? AND part_id = ?
+        "#)
+        .bind(data)
+        .bind(data.len() as i64)
+        .bind(message_id)
+        .bind(part_id)
+        .execute(&self.pool)
+        .await
+        .map_err(crate::mobile::MobileError::DatabaseError)?;
+
+        if result.rows_affected() == 0 {
+            return Err(crate::mobile::MobileError::DatabaseError(sqlx::Error::RowNotFound));
+        }
+        Ok(())
+    }
+
     /// Update conversation metadata after adding a message
     async fn update_conversation_metadata(
         &self,
@@ -396,8 +466,7 @@ impl MessageStore {
         let mut sql = String::from(r#"
             SELECT c.id, c.thread_id, c.display_name, c.last_message_date, 
                    c.unread_count, c.is_archived, c.created_at,
-                   GROUP_CONCAT(cc.phone_number, ',') as phone_numbers,
-                   GROUP_CONCAT(cc.display_name, ',') as contact_names
+                   GROUP_CONCAT(cc.phone_number || char(30) || COALESCE(cc.display_name, ''), char(31)) as participants
             FROM conversations c
             LEFT JOIN conversation_contacts cc ON c.id = cc.conversation_id
         "#);
@@ -446,10 +515,8 @@ impl MessageStore {
 
         let mut conversations = Vec::new();
         for row in rows {
-            let phone_numbers: String = row.get("phone_numbers");
-            let contact_names: Option<String> = row.try_get("contact_names").ok();
-
-            let participants = self.parse_conversation_participants(&phone_numbers, contact_names.as_deref());
+            let participants: Option<String> = row.get("participants");
+            let participants = self.parse_conversation_participants(participants.as_deref().unwrap_or_default());
 
             let conversation = SmsConversation {
                 id: row.get::<i64, _>("id"),
@@ -472,15 +539,16 @@ impl MessageStore {
     }
 
     /// Parse conversation participants from database results
-    fn parse_conversation_participants(&self, phone_numbers: &str, contact_names: Option<&str>) -> Vec<ContactInfo> {
-        let phones: Vec<&str> = phone_numbers.split(',').collect();
-        let names: Vec<&str> = contact_names
-            .map(|names| names.split(',').collect())
-            .unwrap_or_else(|| vec![""; phones.len()]);
-
-        phones.into_iter()
-            .zip(names.into_iter())
-            .map(|(phone, name)| {
+    ///
+    /// Each participant is its number and name separated by `\x1e`, and
+    /// participants are separated by `\x1f`. A participant without a name
+    /// (not in contacts) keeps just the number.
+    fn parse_conversation_participants(&self, participants: &str) -> Vec<ContactInfo> {
+        participants
+            .split('\x1f')
+            .filter(|participant| !participant.is_empty())
+            .map(|participant| {
+                let (phone, name) = participant.split_once('\x1e').unwrap_or((participant, ""));
                 ContactInfo::new(
                     phone.to_string(),
                     if name.is_empty() { None } else { Some(name.to_string()) }
@@ -489,13 +557,26 @@ impl MessageStore {
             .collect()
     }
 
+    /// Addresses of a stored message: its own, or its conversation's for
+    /// messages stored before they were kept
+    fn message_addresses(row: &sqlx::sqlite::SqliteRow) -> Result<Vec<String>> {
+        if let Some(addresses) = row.get::<Option<String>, _>("message_addresses") {
+            return Ok(serde_json::from_str(&addresses)?);
+        }
+
+        let participants: Option<String> = row.get("addresses");
+        Ok(participants
+            .map(|addresses| addresses.split(',').map(|s| s.to_string()).collect())
+            .unwrap_or_default())
+    }
+
     /// Get messages for a specific conversation
     pub async fn get_messages(&self, conversation_id: i64, query: &MessageQuery) -> Result<Vec<SmsMessage>> {
         debug!("Retrieving messages for conversation {}", conversation_id);
 
         let mut sql = String::from(r#"
             SELECT m.id, m.body, m.message_type, m.is_read, m.date_sent, m.sub_id,
-                   c.thread_id,
+                   m.addresses as message_addresses, c.thread_id,
                    GROUP_CONCAT(cc.phone_number, ',') as addresses
             FROM messages m
             JOIN conversations c ON m.conversation_id = c.id
@@ -564,8 +645,7 @@ impl MessageStore {
                 _ => MessageType::Sms,
             };
 
-            let addresses: String = row.get("addresses");
-            let address_list: Vec<String> = addresses.split(',').map(|s| s.to_string()).collect();
+            let address_list = Self::message_addresses(&row)?;
 
             // Load attachments for this message
             let attachments = self.get_message_attachments(row.get::<i32, _>("id")).await?;
@@ -592,7 +672,7 @@ impl MessageStore {
     /// Get attachments for a specific message
     async fn get_message_attachments(&self, message_id: i32) -> Result<Vec<Attachment>> {
         let rows = sqlx::query(r#"
-            SELECT filename, mime_type, file_size, data, is_downloaded, download_url
+            SELECT part_id, filename, mime_type, file_size, data, is_downloaded, download_url
             FROM message_attachments 
             WHERE message_id = ?
             ORDER BY id
@@ -604,7 +684,7 @@ impl MessageStore {
 
         let attachments = rows.into_iter().map(|row| {
             Attachment {
-                part_id: 0, // Not stored separately, use default
+                part_id: row.get("part_id"),
                 filename: row.get("filename"),
                 mime_type: row.get("mime_type"),
                 file_size: row.get("file_size"),
@@ -824,7 +904,7 @@ impl MessageStore {
 
         let sql = r#"
             SELECT m.id, m.body, m.message_type, m.is_read, m.date_sent, m.sub_id,
-                   c.thread_id, c.display_name as conversation_name,
+                   m.addresses as message_addresses, c.thread_id, c.display_name as conversation_name,
                    GROUP_CONCAT(cc.phone_number, ',') as addresses
             FROM messages m
             JOIN conversations c ON m.conversation_id = c.id
@@ -852,8 +932,7 @@ impl MessageStore {
                 _ => MessageType::Sms,
             };
 
-            let addresses: String = row.get("addresses");
-            let address_list: Vec<String> = addresses.split(',').map(|s| s.to_string()).collect();
+            let address_list = Self::message_addresses(&row)?;
 
             let attachments = self.get_message_attachments(row.get::<i32, _>("id")).await?;
 
@@ -1021,6 +1100,57 @@ mod tests {
         assert_eq!(retrieved_attachment.file_size, 1024);
     }
 
+    #[tokio::test]
+    async fn test_group_conversation_threads_by_conversation() {
+        let store = create_test_store().await;
+
+        let mut first = create_test_message();
+        first.thread_id = 9;
+        first.addresses = vec!["+1111".to_string(), "+2222".to_string()];
+        let mut second = create_test_message();
+        second.id = 2;
+        second.thread_id = 9;
+        second.addresses = vec!["+3333".to_string(), "+1111".to_string(), "+2222".to_string()];
+        second.message_type = MessageType::Mms;
+        second.attachments = vec![Attachment::with_download_url(
+            5,
+            "image/png".to_string(),
+            "photo.png".to_string(),
+            "content://mms/part/5".to_string(),
+            0,
+        )];
+
+        let conversation_id = store.store_message(first).await.unwrap();
+        assert_eq!(store.store_message(second.clone()).await.unwrap(), conversation_id);
+
+        let conversations = store.get_conversations(&MessageQuery::default()).await.unwrap();
+        assert_eq!(conversations.len(), 1);
+        let participants: Vec<&str> = conversations[0]
+            .participants
+            .iter()
+            .map(|p| p.address.as_str())
+            .collect();
+        assert_eq!(participants, ["+1111", "+2222", "+3333"]);
+        // Not in contacts, so shown by number
+        assert_eq!(conversations[0].participant_label("+3333"), "+3333");
+
+        // The image arrives after the message
+        store.store_attachment_data(2, 5, &[1, 2, 3]).await.unwrap();
+        assert!(store.store_attachment_data(2, 6, &[1]).await.is_err());
+
+        let messages = store.get_messages(conversation_id, &MessageQuery::default()).await.unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1].sender(), Some(&"+3333".to_string()));
+        assert_eq!(messages[1].attachments.len(), 1);
+        assert_eq!(messages[1].attachments[0].part_id, 5);
+        assert_eq!(messages[1].attachments[0].data.as_deref(), Some(&[1, 2, 3][..]));
+
+        // Syncing the message again doesn't duplicate its attachments
+        store.store_message(second).await.unwrap();
+        let messages = store.get_messages(conversation_id, &MessageQuery::default()).await.unwrap();
+        assert_eq!(messages[1].attachments.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_storage_stats() {
         let store = create_test_store().await;
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -13,9 +14,18 @@ use chrono::Local;
 use tokio::sync::{RwLock, Mutex};
 
 use crate::mobile::{
-    MessageStore, MobileSyncStats,
+    MessageStore, MobileSyncStats, KdeConnectClient,
     kde_connect::types::{SmsMessage, SmsConversation}
 };
+use crate::ui::graphics::ImageRenderer;
+use crate::ui::toast::ToastManager;
+
+/// Size of MMS image thumbnails, in terminal cells
+const THUMBNAIL_COLUMNS: u16 = 24;
+const THUMBNAIL_ROWS: u16 = 8;
+
+/// Rendered thumbnails by message ID and part ID
+type ThumbnailCache = HashMap<(i32, i64), Vec<Line<'static>>>;
 
 /// Main SMS/MMS UI component
 pub struct SmsUi {
@@ -35,6 +45,10 @@ pub struct SmsUi {
     /// Scroll states for content
     message_scroll_state: Arc<Mutex<ScrollbarState>>,
     conversation_scroll_state: Arc<Mutex<ScrollbarState>>,
+    /// Renders MMS images as thumbnails
+    image_renderer: ImageRenderer,
+    /// Thumbnails of image attachments, by message ID and part ID
+    thumbnails: Arc<RwLock<ThumbnailCache>>,
 }
 
 /// Different view modes for the SMS interface
@@ -57,6 +71,8 @@ pub enum SmsViewMode {
 pub struct SmsComposition {
     /// Recipients (phone numbers)
     pub recipients: Vec<String>,
+    /// Conversation being replied to, if any
+    pub conversation_id: Option<i64>,
     /// Current message body
     pub message_body: String,
     /// Cursor position in message body
@@ -137,6 +153,7 @@ impl Default for SmsComposition {
     fn default() -> Self {
         Self {
             recipients: Vec::new(),
+            conversation_id: None,
             message_body: String::new(),
             cursor_position: 0,
             editing_recipients: true,
@@ -165,6 +182,8 @@ impl SmsUi {
             service_stats: Arc::new(RwLock::new(None)),
             message_scroll_state: Arc::new(Mutex::new(ScrollbarState::default())),
             conversation_scroll_state: Arc::new(Mutex::new(ScrollbarState::default())),
+            image_renderer: ImageRenderer::auto(),
+            thumbnails: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -208,11 +227,44 @@ impl SmsUi {
             
             // Update scroll state
             let _ = self.message_scroll_state.lock().await.content_length(conversation.messages.len());
+
+            self.load_thumbnails(&conversation.messages).await;
         }
 
         Ok(())
     }
 
+    /// Render thumbnails for the image attachments of messages
+    ///
+    /// Images that can't be decoded, or whose content hasn't been downloaded
+    /// from the phone yet, are shown as a placeholder with their file name.
+    pub async fn load_thumbnails(&self, messages: &[SmsMessage]) {
+        let mut thumbnails = self.thumbnails.write().await;
+
+        for message in messages {
+            for attachment in message.attachments.iter().filter(|a| a.is_image()) {
+                let key = (message.id, attachment.part_id);
+                if thumbnails.contains_key(&key) {
+                    continue;
+                }
+
+                let image = attachment
+                    .data
+                    .as_deref()
+                    .and_then(|data| image::load_from_memory(data).ok());
+                if let Some(image) = image {
+                    let lines = self.image_renderer.render_lines(
+                        Some(&image),
+                        Some(&attachment.filename),
+                        THUMBNAIL_COLUMNS,
+                        THUMBNAIL_ROWS,
+                    );
+                    thumbnails.insert(key, lines);
+                }
+            }
+        }
+    }
+
     /// Update service statistics
     pub async fn update_service_stats(&self, stats: MobileSyncStats) {
         *self.service_stats.write().await = Some(stats);
@@ -228,6 +280,22 @@ impl SmsUi {
         self.view_mode.read().await.clone()
     }
 
+    /// Handle a key on the SMS screen
+    ///
+    /// Ctrl+S (`'\x13'`) sends the composition through `client`, with the
+    /// outcome shown in `toasts`; other keys go to [`Self::handle_navigation`].
+    pub async fn handle_key(
+        &self,
+        key: char,
+        client: &KdeConnectClient,
+        toasts: &mut ToastManager,
+    ) -> Result<(), crate::mobile::MobileError> {
+        if key == '\x13' && self.get_view_mode().await == SmsViewMode::Compose {
+            return self.send_composition(client, toasts).await;
+        }
+        self.handle_navigation(key).await
+    }
+
     /// Handle navigation input (arrow keys, enter, etc.)
     pub async fn handle_navigation(&self, key: char) -> Result<(), crate::mobile::MobileError> {
         let current_mode = self.get_view_mode().await;
@@ -242,6 +310,12 @@ impl SmsUi {
             SmsViewMode::Compose => {
                 self.handle_composition_input(key).await
             }
+            SmsViewMode::MessageDetail => {
+                if matches!(key, 'q' | '←' | '\x1b') {
+                    self.set_view_mode(SmsViewMode::MessageThread).await;
+                }
+                Ok(())
+            }
             _ => Ok(()),
         }
     }
@@ -295,22 +369,45 @@ impl SmsUi {
 
     /// Handle message thread navigation
     async fn handle_message_navigation(&self, key: char) -> Result<(), crate::mobile::MobileError> {
+        let message_count = self.selected_conversation.read().await
+            .as_ref()
+            .map_or(0, |conversation| conversation.messages.len());
+
         match key {
-            'q' | '←' => {
+            'q' | '←' | '\x1b' => {
                 // Go back to conversation list
                 self.set_view_mode(SmsViewMode::ConversationList).await;
             }
+            'j' | '↓' => {
+                let mut state = self.message_list_state.lock().await;
+                let i = state.selected().map_or(0, |i| (i + 1).min(message_count.saturating_sub(1)));
+                state.select(Some(i));
+            }
+            'k' | '↑' => {
+                let mut state = self.message_list_state.lock().await;
+                let i = state.selected().map_or(0, |i| i.saturating_sub(1));
+                state.select(Some(i));
+            }
+            '\n' => {
+                // Show the message with its attachments
+                let selected = self.message_list_state.lock().await.selected();
+                if selected.is_some_and(|i| i < message_count) {
+                    self.set_view_mode(SmsViewMode::MessageDetail).await;
+                }
+            }
             'r' => {
                 // Reply to conversation
-                if let Some(conversation) = &*self.selected_conversation.read().await {
-                    let mut composition = self.composition.write().await;
-                    composition.recipients = conversation.participants
-                        .iter()
-                        .map(|p| p.address.clone())
-                        .collect();
-                    composition.editing_recipients = false;
+                let conversation = self.selected_conversation.read().await.clone();
+                match conversation {
+                    Some(conversation) => {
+                        let recipients = conversation.participants
+                            .iter()
+                            .map(|p| p.address.clone())
+                            .collect();
+                        self.start_reply(conversation.thread_id, recipients).await;
+                    }
+                    None => self.set_view_mode(SmsViewMode::Compose).await,
                 }
-                self.set_view_mode(SmsViewMode::Compose).await;
             }
             _ => {}
         }
@@ -318,8 +415,34 @@ impl SmsUi {
         Ok(())
     }
 
+    /// Start a reply to the conversation `conversation_id` with `recipients`
+    ///
+    /// Used by `r` in a thread and by replying from an SMS notification.
+    pub async fn start_reply(&self, conversation_id: i64, recipients: Vec<String>) {
+        *self.composition.write().await = SmsComposition {
+            recipients,
+            conversation_id: Some(conversation_id),
+            editing_recipients: false,
+            ..SmsComposition::default()
+        };
+        self.set_view_mode(SmsViewMode::Compose).await;
+    }
+
     /// Handle composition input
     async fn handle_composition_input(&self, key: char) -> Result<(), crate::mobile::MobileError> {
+        if key == '\x1b' {
+            // Esc drops the composition
+            let replying = self.composition.read().await.conversation_id.is_some();
+            self.clear_composition().await;
+            let mode = if replying && self.selected_conversation.read().await.is_some() {
+                SmsViewMode::MessageThread
+            } else {
+                SmsViewMode::ConversationList
+            };
+            self.set_view_mode(mode).await;
+            return Ok(());
+        }
+
         let mut composition = self.composition.write().await;
         
         if composition.editing_recipients {
@@ -369,6 +492,56 @@ impl SmsUi {
         Ok(())
     }
 
+    /// Send the current composition (Ctrl+S), as a reply when it answers a conversation
+    ///
+    /// The outcome is shown as a toast; on failure the composition is kept
+    /// with the error so it can be sent again. The D-Bus call blocks, so it
+    /// runs on the blocking thread pool.
+    pub async fn send_composition(
+        &self,
+        client: &KdeConnectClient,
+        toasts: &mut ToastManager,
+    ) -> Result<(), crate::mobile::MobileError> {
+        let composition = self.get_composition().await;
+        let send = {
+            let client = client.clone();
+            let composition = composition.clone();
+            tokio::task::spawn_blocking(move || match composition.conversation_id {
+                Some(conversation_id) => client.send_reply(
+                    conversation_id,
+                    &composition.recipients,
+                    &composition.message_body,
+                ),
+                None => client.send_sms(&composition.message_body, &composition.recipients),
+            })
+        };
+        let result = send.await.unwrap_or_else(|e| {
+            Err(crate::mobile::MobileError::MessageSendFailed(format!(
+                "Send task failed: {}",
+                e
+            )))
+        });
+
+        match result {
+            Ok(()) => {
+                toasts.success(format!("SMS sent to {}", composition.recipients.join(", ")));
+                self.clear_composition().await;
+                let mode = if composition.conversation_id.is_some() {
+                    SmsViewMode::MessageThread
+                } else {
+                    SmsViewMode::ConversationList
+                };
+                self.set_view_mode(mode).await;
+                Ok(())
+            }
+            Err(e) => {
+                toasts.error(e.user_message());
+                self.set_error(e.to_string()).await;
+                Err(e)
+            }
+        }
+    }
+
     /// Render the SMS UI
     pub async fn render(
         &self,
@@ -456,7 +629,7 @@ impl SmsUi {
         if let Some(conversation) = &*self.selected_conversation.read().await {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+                .constraints([Constraint::Length(3), Constraint::Min(0)])
                 .split(area);
 
             // Header with conversation info
@@ -475,7 +648,7 @@ impl SmsUi {
             let messages: Vec<ListItem> = conversation
                 .messages
                 .iter()
-                .map(|msg| self.format_message_item(msg, config))
+                .map(|msg| self.format_message_item(conversation, msg, config))
                 .collect();
 
             let message_list = List::new(messages)
@@ -671,18 +844,62 @@ impl SmsUi {
         Ok(())
     }
 
-    /// Render message detail view (for attachments, etc.)
+    /// Render message detail view, with thumbnails of image attachments
     async fn render_message_detail(
         &self,
         f: &mut Frame<'_>,
         area: Rect,
-        _config: &SmsRenderConfig,
+        config: &SmsRenderConfig,
     ) -> Result<(), crate::mobile::MobileError> {
-        // Message detail view would show attachment thumbnails, full message content,
-        // delivery status, and other metadata when implemented
-        let detail_widget = Paragraph::new("Message detail view\n\nThis view would display:\n• Message attachments and media\n• Full message content and formatting\n• Delivery and read receipts\n• Message metadata and timestamps")
+        let selected_conversation = self.selected_conversation.read().await;
+        let selected = self.message_list_state.lock().await.selected();
+        let Some((conversation, message)) = selected_conversation
+            .as_ref()
+            .and_then(|conversation| Some((conversation, conversation.messages.get(selected?)?)))
+        else {
+            return Ok(());
+        };
+
+        let label = Style::default().fg(config.colors.timestamp);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("From: ", label),
+                Span::raw(Self::sender_label(conversation, message)),
+            ]),
+            Line::from(vec![Span::styled("Date: ", label), Span::raw(message.formatted_date())]),
+        ];
+        if conversation.is_group_conversation() {
+            lines.push(Line::from(vec![
+                Span::styled("Group: ", label),
+                Span::raw(conversation.participant_names().join(", ")),
+            ]));
+        }
+        lines.push(Line::from(""));
+        lines.extend(message.body.lines().map(|line| Line::from(line.to_string())));
+
+        let thumbnails = self.thumbnails.read().await;
+        for attachment in &message.attachments {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("📎 {} ({})", attachment.filename, attachment.size_formatted()),
+                Style::default().fg(config.colors.attachment_indicator),
+            )));
+            if attachment.is_image() {
+                match thumbnails.get(&(message.id, attachment.part_id)) {
+                    Some(thumbnail) => lines.extend(thumbnail.iter().cloned()),
+                    None => lines.extend(self.image_renderer.render_lines(
+                        None,
+                        Some(&attachment.filename),
+                        THUMBNAIL_COLUMNS,
+                        THUMBNAIL_ROWS,
+                    )),
+                }
+            }
+        }
+
+        let detail_widget = Paragraph::new(lines)
             .block(Block::default().borders(Borders::ALL).title(" Message Details "))
-            .wrap(Wrap { trim: true });
+            .wrap(Wrap { trim: false });
 
         f.render_widget(detail_widget, area);
         Ok(())
@@ -726,22 +943,19 @@ impl SmsUi {
     }
 
     /// Format a message item for the list
-    fn format_message_item<'a>(&self, message: &'a SmsMessage, config: &SmsRenderConfig) -> ListItem<'a> {
+    fn format_message_item<'a>(
+        &self,
+        conversation: &SmsConversation,
+        message: &'a SmsMessage,
+        config: &SmsRenderConfig,
+    ) -> ListItem<'a> {
         let style = if message.is_outgoing() {
             Style::default().fg(config.colors.outgoing_message)
         } else {
             Style::default().fg(config.colors.incoming_message)
         };
 
-        let sender_prefix = if message.is_outgoing() {
-            "You: "
-        } else {
-            if let Some(sender) = message.sender() {
-                sender
-            } else {
-                "Unknown: "
-            }
-        };
+        let sender_prefix = format!("{}: ", Self::sender_label(conversation, message));
 
         let attachment_indicator = if message.has_attachments() {
             format!(" 📎{}", message.attachment_count())
@@ -762,6 +976,18 @@ impl SmsUi {
         ListItem::new(line)
     }
 
+    /// Who sent a message: the contact's name, or their number when they
+    /// aren't in contacts
+    fn sender_label(conversation: &SmsConversation, message: &SmsMessage) -> String {
+        if message.is_outgoing() {
+            return "You".to_string();
+        }
+        message
+            .sender()
+            .map(|sender| conversation.participant_label(sender))
+            .unwrap_or_else(|| "Unknown".to_string())
+    }
+
     /// Create a centered rectangle for popups
     fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         let popup_layout = Layout::default()
@@ -989,6 +1215,59 @@ mod tests {
         let composition = ui.get_composition().await;
         assert!(!composition.recipients.is_empty());
         assert_eq!(composition.recipients[0], "+1234567890");
+        assert_eq!(composition.conversation_id, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_failed_reply_keeps_composition() {
+        let ui = create_test_ui().await;
+        *ui.selected_conversation.write().await = Some(create_test_conversation().await);
+        ui.set_view_mode(SmsViewMode::MessageThread).await;
+        ui.handle_navigation('r').await.unwrap();
+        ui.handle_navigation('O').await.unwrap();
+        ui.handle_navigation('k').await.unwrap();
+
+        // Without KDE Connect the reply can't go out
+        let client = KdeConnectClient::new().unwrap();
+        let mut toasts = ToastManager::new();
+        let result = ui.handle_key('\x13', &client, &mut toasts).await;
+
+        assert!(result.is_err());
+        assert!(toasts.has_toasts());
+        let composition = ui.get_composition().await;
+        assert_eq!(composition.message_body, "Ok");
+        assert!(composition.error_message.is_some());
+        assert_eq!(ui.get_view_mode().await, SmsViewMode::Compose);
+    }
+
+    #[tokio::test]
+    async fn test_mms_thumbnails_and_detail() {
+        let ui = create_test_ui().await;
+        let mut conversation = create_test_conversation().await;
+
+        let mut png = Vec::new();
+        image::DynamicImage::new_rgb8(4, 4)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .unwrap();
+        conversation.messages[0].attachments = vec![
+            Attachment::new(3, "image/png".to_string(), "cat.png".to_string(), png),
+            Attachment::with_download_url(4, "image/jpeg".to_string(), "dog.jpg".to_string(), "/tmp/dog.jpg".to_string(), 2048),
+        ];
+        let message_id = conversation.messages[0].id;
+
+        ui.load_thumbnails(&conversation.messages).await;
+        let thumbnails = ui.thumbnails.read().await;
+        assert!(thumbnails.contains_key(&(message_id, 3)));
+        // Not downloaded yet, so drawn as a placeholder
+        assert!(!thumbnails.contains_key(&(message_id, 4)));
+        drop(thumbnails);
+
+        *ui.selected_conversation.write().await = Some(conversation);
+        ui.set_view_mode(SmsViewMode::MessageThread).await;
+        ui.handle_navigation('\n').await.unwrap();
+        assert_eq!(ui.get_view_mode().await, SmsViewMode::MessageDetail);
+        ui.handle_navigation('q').await.unwrap();
+        assert_eq!(ui.get_view_mode().await, SmsViewMode::MessageThread);
     }
 
     #[tokio::test]
@@ -1072,7 +1351,7 @@ mod tests {
         let conversation = create_test_conversation().await;
         let message = &conversation.messages[0];
         
-        let formatted = ui.format_message_item(message, &config);
+        let formatted = ui.format_message_item(&conversation, message, &config);
         
         // The formatted item should contain message content
         assert!(!format!("{:?}", formatted).is_empty());
@@ -1115,6 +1394,26 @@ mod tests {
         assert!(composition.editing_recipients);
     }
 
+    #[tokio::test]
+    async fn test_reply_from_notification() {
+        let ui = create_test_ui().await;
+        *ui.selected_conversation.write().await = Some(create_test_conversation().await);
+
+        ui.start_reply(1, vec!["+1234567890".to_string()]).await;
+        assert_eq!(ui.get_view_mode().await, SmsViewMode::Compose);
+        let composition = ui.get_composition().await;
+        assert_eq!(composition.conversation_id, Some(1));
+        assert_eq!(composition.recipients, vec!["+1234567890".to_string()]);
+        assert!(!composition.editing_recipients);
+
+        // Esc drops the reply and goes back to the thread
+        let client = KdeConnectClient::new().unwrap();
+        let mut toasts = ToastManager::new();
+        ui.handle_key('\x1b', &client, &mut toasts).await.unwrap();
+        assert_eq!(ui.get_view_mode().await, SmsViewMode::MessageThread);
+        assert!(ui.get_composition().await.conversation_id.is_none());
+    }
+
     #[tokio::test]
     async fn test_conversation_selection() {
         let ui = create_test_ui().await;
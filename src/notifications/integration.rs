@@ -252,6 +252,11 @@ impl NotificationIntegrationService {
         if message.flags.iter().any(|flag| flag.as_str() == "\\Flagged") {
             return true;
         }
+
+        // Check the sender's priority headers
+        if message.message_priority() == crate::email::MailPriority::High {
+            return true;
+        }
         
         // Check subject for urgent keywords
         let subject_lower = message.subject.to_lowercase();
@@ -353,6 +358,7 @@ impl NotificationIntegrationService {
                                 status: event_tracking.status.clone(),
                                 calendar_id: "default".to_string(),
                                 recurrence: None,
+                                recurrence_id: None,
                                 created_at: now,
                                 updated_at: now,
                                 categories: Vec::new(),
@@ -361,6 +367,7 @@ impl NotificationIntegrationService {
                                 sequence: 0,
                                 url: None,
                                 etag: None,
+                                timezone: None,
                             };
                             
                             let notification_event = NotificationEvent::Calendar {
@@ -516,12 +523,15 @@ mod tests {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         };
         
         assert!(service.determine_email_importance(&urgent_message));
         
         let normal_message = StoredMessage {
             subject: "Regular email".to_string(),
+            priority: None,
             ..urgent_message
         };
         
@@ -547,6 +557,7 @@ mod tests {
             status: EventStatus::Confirmed,
             calendar_id: "default".to_string(),
             recurrence: None,
+            recurrence_id: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             categories: Vec::new(),
@@ -555,6 +566,7 @@ mod tests {
             sequence: 0,
             url: None,
             etag: None,
+            timezone: None,
         };
         
         assert_eq!(service.determine_calendar_priority(&urgent_event), NotificationPriority::High);
@@ -2,6 +2,7 @@ use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info, warn};
 
 use crate::calendar::CalendarNotification;
+use crate::email::sender_lists::sender_matches;
 use crate::email::EmailNotification;
 use crate::notifications::{
     types::{
@@ -356,7 +357,16 @@ impl UnifiedNotificationManager {
         config: &NotificationConfig,
     ) -> NotificationPriority {
         // Check for VIP senders
-        if config.vip_senders.contains(&message.from_addr) {
+        if config
+            .vip_senders
+            .iter()
+            .any(|vip| sender_matches(vip, &message.from_addr))
+        {
+            return NotificationPriority::High;
+        }
+
+        // Mail the sender marked urgent (X-Priority, Importance)
+        if message.message_priority() == crate::email::MailPriority::High {
             return NotificationPriority::High;
         }
 
@@ -493,6 +503,8 @@ mod tests {
                 sync_version: 1,
                 is_draft: false,
                 is_deleted: false,
+                snoozed_until: None,
+                inbox_tab: None,
             },
         };
 
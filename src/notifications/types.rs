@@ -1,5 +1,6 @@
 // use chrono::{DateTime, Utc};
 use crate::calendar::Event;
+use crate::email::sender_lists::sender_matches;
 use crate::email::StoredMessage;
 use serde::{Deserialize, Serialize};
 
@@ -76,6 +77,8 @@ pub enum SystemEventType {
     ConnectionError,
     AuthenticationRequired,
     UpdateAvailable,
+    /// An SMS arrived on the phone paired through KDE Connect
+    SmsReceived,
 }
 
 /// Configuration for the notification system
@@ -237,6 +240,7 @@ impl NotificationEvent {
                 SystemEventType::ConnectionError => "Connection Error".to_string(),
                 SystemEventType::AuthenticationRequired => "Authentication Required".to_string(),
                 SystemEventType::UpdateAvailable => "Update Available".to_string(),
+                SystemEventType::SmsReceived => "New SMS".to_string(),
                 _ => "System Notification".to_string(),
             },
         }
@@ -317,6 +321,14 @@ impl NotificationEvent {
                         "Calendar event created".to_string()
                     }
                 }
+                CalendarEventType::EventReminder { minutes_until } if *minutes_until <= 0 => {
+                    match event {
+                        Some(evt) if config.show_content_preview => {
+                            format!("Starting now: {}", evt.title)
+                        }
+                        _ => "Event starting now".to_string(),
+                    }
+                }
                 CalendarEventType::EventReminder { minutes_until } => {
                     if let Some(evt) = event {
                         if config.show_content_preview {
@@ -374,6 +386,7 @@ impl NotificationEvent {
                 SystemEventType::ConnectionError => "network-error",
                 SystemEventType::AuthenticationRequired => "dialog-password",
                 SystemEventType::UpdateAvailable => "software-update-available",
+                SystemEventType::SmsReceived => "phone",
                 _ => "dialog-information",
             },
         }
@@ -392,7 +405,10 @@ impl NotificationEvent {
     pub fn is_vip_notification(&self, config: &NotificationConfig) -> bool {
         if let NotificationEvent::Email { message, .. } = self {
             if let Some(msg) = message {
-                return config.vip_senders.contains(&msg.from_addr)
+                return config
+                    .vip_senders
+                    .iter()
+                    .any(|vip| sender_matches(vip, &msg.from_addr))
                     || config.vip_senders.iter().any(|vip| {
                         msg.from_name
                             .as_ref()
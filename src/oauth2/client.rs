@@ -236,7 +236,7 @@ impl OAuth2Client {
                 .text()
                 .await
                 .unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(OAuth2Error::TokenRefreshFailed(error_text));
+            return Err(parse_refresh_error(&error_text));
         }
 
         let token_data: Value = response.json().await.map_err(OAuth2Error::NetworkError)?;
@@ -244,6 +244,86 @@ impl OAuth2Client {
         self.parse_token_response(token_data)
     }
 
+    /// Start a device authorization grant (RFC 8628)
+    ///
+    /// Used where no browser can reach a local redirect, such as over SSH.
+    /// The returned code is entered at the verification URL on any other
+    /// device, while [`Self::poll_device_token`] waits for the result.
+    pub async fn start_device_authorization(&self) -> OAuth2Result<DeviceAuthorization> {
+        let device_url = self.config.device_authorization_url.as_ref().ok_or_else(|| {
+            OAuth2Error::InvalidConfig(format!(
+                "{} does not support signing in with a device code",
+                self.config.provider.display_name()
+            ))
+        })?;
+
+        let scope = self.config.scope_string();
+        let mut params = HashMap::new();
+        params.insert("client_id", self.config.client_id.as_str());
+        params.insert("scope", scope.as_str());
+
+        let response = self
+            .http_client
+            .post(device_url)
+            .form(&params)
+            .send()
+            .await
+            .map_err(OAuth2Error::NetworkError)?;
+
+        if !response.status().is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+
+            let helpful_message = if error_text.contains("invalid_scope") {
+                format!("{}\n\nThe provider does not allow these scopes for device sign-in.\nGoogle only grants a few scopes to 'TVs and Limited Input devices' clients;\nuse the browser flow on a desktop instead.", error_text)
+            } else if error_text.contains("invalid_client") || error_text.contains("unauthorized_client") {
+                format!("{}\n\nThis client is not allowed to use device sign-in:\n• Google: create a 'TVs and Limited Input devices' OAuth client\n• Microsoft: enable 'Allow public client flows' for the app", error_text)
+            } else {
+                error_text
+            };
+
+            return Err(OAuth2Error::AuthorizationFailed(helpful_message));
+        }
+
+        let data: Value = response.json().await.map_err(OAuth2Error::NetworkError)?;
+
+        parse_device_authorization(&data)
+    }
+
+    /// Ask the token endpoint once whether a device authorization was granted
+    pub async fn poll_device_token(
+        &self,
+        device: &DeviceAuthorization,
+    ) -> OAuth2Result<DevicePoll> {
+        let mut params = HashMap::new();
+        params.insert("grant_type", DEVICE_CODE_GRANT_TYPE);
+        params.insert("device_code", &device.device_code);
+        params.insert("client_id", &self.config.client_id);
+
+        if let Some(client_secret) = &self.config.client_secret {
+            params.insert("client_secret", client_secret);
+        }
+
+        let response = self
+            .http_client
+            .post(&self.config.token_url)
+            .form(&params)
+            .send()
+            .await
+            .map_err(OAuth2Error::NetworkError)?;
+
+        let status = response.status();
+        let data: Value = response.json().await.map_err(OAuth2Error::NetworkError)?;
+
+        if status.is_success() {
+            Ok(DevicePoll::Complete(self.parse_token_response(data)?))
+        } else {
+            parse_device_poll_error(&data)
+        }
+    }
+
     /// Get user info using access token (for email address detection)
     pub async fn get_user_info(&self, access_token: &str) -> OAuth2Result<UserInfo> {
         let user_info_url = match self.config.provider {
@@ -253,9 +333,11 @@ impl OAuth2Client {
                 "https://api.login.yahoo.com/openid/v1/userinfo"
             }
             crate::oauth2::OAuth2Provider::Custom(_) => {
-                return Err(OAuth2Error::InvalidConfig(
-                    "User info endpoint not configured for custom provider".to_string(),
-                ));
+                self.config.userinfo_url.as_deref().ok_or_else(|| {
+                    OAuth2Error::InvalidConfig(
+                        "User info endpoint not configured for custom provider; set userinfo_url in oauth2_providers.toml".to_string(),
+                    )
+                })?
             }
         };
 
@@ -408,9 +490,16 @@ impl OAuth2Client {
                 (email, name)
             }
             crate::oauth2::OAuth2Provider::Custom(_) => {
-                return Err(OAuth2Error::InvalidConfig(
-                    "User info parsing not configured for custom provider".to_string(),
-                ));
+                // Standard OpenID Connect claims
+                let email = data["email"]
+                    .as_str()
+                    .or_else(|| data["preferred_username"].as_str().filter(|s| s.contains('@')))
+                    .ok_or_else(|| {
+                        OAuth2Error::InvalidToken("Missing email in user info".to_string())
+                    })?
+                    .to_string();
+                let name = data["name"].as_str().unwrap_or(&email).to_string();
+                (email, name)
             }
         };
 
@@ -432,6 +521,37 @@ pub struct AuthorizationRequest {
     pub callback_port: u16,
 }
 
+/// Grant type for polling a device authorization
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Polling interval when the provider doesn't name one
+const DEFAULT_DEVICE_POLL_SECS: u64 = 5;
+
+/// Device authorization to show the user while polling for tokens
+#[derive(Debug, Clone)]
+pub struct DeviceAuthorization {
+    pub device_code: String,
+    /// Code the user types in at the verification URL
+    pub user_code: String,
+    pub verification_uri: String,
+    /// Verification URL with the code already filled in, if offered
+    pub verification_uri_complete: Option<String>,
+    /// Seconds until the codes expire
+    pub expires_in: u64,
+    /// Seconds to wait between polls
+    pub interval: u64,
+}
+
+/// Outcome of one poll of the token endpoint
+#[derive(Debug)]
+pub enum DevicePoll {
+    /// The user hasn't finished signing in yet
+    Pending,
+    /// Polling too fast; wait longer before the next poll
+    SlowDown,
+    Complete(TokenResponse),
+}
+
 /// User information from OAuth2 provider
 #[derive(Debug, Clone)]
 pub struct UserInfo {
@@ -642,6 +762,78 @@ fn generate_random_string(length: usize) -> String {
         .collect()
 }
 
+/// Parse a device authorization response
+///
+/// Google names the URL `verification_url` where RFC 8628 and Microsoft use
+/// `verification_uri`.
+fn parse_device_authorization(data: &Value) -> OAuth2Result<DeviceAuthorization> {
+    let field = |name: &str| {
+        data[name]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                OAuth2Error::AuthorizationFailed(format!(
+                    "Missing {} in device authorization response",
+                    name
+                ))
+            })
+    };
+
+    let verification_uri = data["verification_uri"]
+        .as_str()
+        .or_else(|| data["verification_url"].as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| {
+            OAuth2Error::AuthorizationFailed(
+                "Missing verification_uri in device authorization response".to_string(),
+            )
+        })?;
+
+    Ok(DeviceAuthorization {
+        device_code: field("device_code")?,
+        user_code: field("user_code")?,
+        verification_uri,
+        verification_uri_complete: data["verification_uri_complete"]
+            .as_str()
+            .map(|s| s.to_string()),
+        expires_in: data["expires_in"].as_u64().unwrap_or(900),
+        interval: data["interval"]
+            .as_u64()
+            .unwrap_or(DEFAULT_DEVICE_POLL_SECS),
+    })
+}
+
+/// Map a token endpoint error while polling a device authorization
+fn parse_device_poll_error(data: &Value) -> OAuth2Result<DevicePoll> {
+    match data["error"].as_str().unwrap_or_default() {
+        "authorization_pending" => Ok(DevicePoll::Pending),
+        "slow_down" => Ok(DevicePoll::SlowDown),
+        "access_denied" | "authorization_declined" => Err(OAuth2Error::UserCancelled),
+        "expired_token" | "code_expired" => Err(OAuth2Error::AuthorizationTimeout),
+        error => {
+            let description = data["error_description"].as_str().unwrap_or("Unknown error");
+            Err(OAuth2Error::TokenExchangeFailed(format!("{}: {}", error, description)))
+        }
+    }
+}
+
+/// Map a token endpoint error while refreshing
+///
+/// `invalid_grant` means the refresh token was revoked or has expired, which
+/// no retry will fix.
+fn parse_refresh_error(body: &str) -> OAuth2Error {
+    let data: Value = serde_json::from_str(body).unwrap_or(Value::Null);
+    match data["error"].as_str() {
+        Some("invalid_grant") => {
+            let description = data["error_description"]
+                .as_str()
+                .unwrap_or("the refresh token is no longer valid");
+            OAuth2Error::RefreshTokenRevoked(description.to_string())
+        }
+        _ => OAuth2Error::TokenRefreshFailed(body.to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -682,4 +874,93 @@ mod tests {
         assert_eq!(s2.len(), 32);
         assert_ne!(s1, s2); // Very unlikely to be the same
     }
+
+    #[test]
+    fn test_device_authorization_parsing() {
+        // Google's response shape
+        let google = serde_json::json!({
+            "device_code": "dev-123",
+            "user_code": "GQVQ-JKEC",
+            "verification_url": "https://www.google.com/device",
+            "expires_in": 1800,
+            "interval": 5
+        });
+        let device = parse_device_authorization(&google).unwrap();
+        assert_eq!(device.user_code, "GQVQ-JKEC");
+        assert_eq!(device.verification_uri, "https://www.google.com/device");
+        assert_eq!(device.expires_in, 1800);
+
+        // RFC 8628 / Microsoft shape, without an interval
+        let microsoft = serde_json::json!({
+            "device_code": "dev-456",
+            "user_code": "ABCD1234",
+            "verification_uri": "https://microsoft.com/devicelogin",
+            "expires_in": 900
+        });
+        let device = parse_device_authorization(&microsoft).unwrap();
+        assert_eq!(device.verification_uri, "https://microsoft.com/devicelogin");
+        assert_eq!(device.interval, DEFAULT_DEVICE_POLL_SECS);
+
+        let missing = serde_json::json!({ "device_code": "dev-789" });
+        assert!(parse_device_authorization(&missing).is_err());
+    }
+
+    #[test]
+    fn test_device_poll_errors() {
+        let poll = |error: &str| parse_device_poll_error(&serde_json::json!({ "error": error }));
+
+        assert!(matches!(poll("authorization_pending"), Ok(DevicePoll::Pending)));
+        assert!(matches!(poll("slow_down"), Ok(DevicePoll::SlowDown)));
+        assert!(matches!(poll("access_denied"), Err(OAuth2Error::UserCancelled)));
+        assert!(matches!(poll("expired_token"), Err(OAuth2Error::AuthorizationTimeout)));
+        assert!(matches!(poll("invalid_grant"), Err(OAuth2Error::TokenExchangeFailed(_))));
+    }
+
+    #[test]
+    fn test_refresh_errors() {
+        let revoked = r#"{"error": "invalid_grant", "error_description": "Token has been expired or revoked."}"#;
+        assert!(matches!(
+            parse_refresh_error(revoked),
+            OAuth2Error::RefreshTokenRevoked(description) if description == "Token has been expired or revoked."
+        ));
+        assert!(matches!(
+            parse_refresh_error(r#"{"error": "temporarily_unavailable"}"#),
+            OAuth2Error::TokenRefreshFailed(_)
+        ));
+        assert!(matches!(
+            parse_refresh_error("Bad Gateway"),
+            OAuth2Error::TokenRefreshFailed(_)
+        ));
+    }
+
+    #[test]
+    fn test_custom_user_info_parsing() {
+        let config = ProviderConfig::custom(
+            "Keycloak".to_string(),
+            "comunicado".to_string(),
+            None,
+            "https://id.example.org/auth".to_string(),
+            "https://id.example.org/token".to_string(),
+            "mail.example.org".to_string(),
+            993,
+            "mail.example.org".to_string(),
+            587,
+        );
+        let client = OAuth2Client::new(config).unwrap();
+
+        let info = client
+            .parse_user_info(serde_json::json!({ "email": "me@example.org", "name": "Me" }))
+            .unwrap();
+        assert_eq!(info.email, "me@example.org");
+        assert_eq!(info.name, "Me");
+
+        let info = client
+            .parse_user_info(serde_json::json!({ "preferred_username": "me@example.org" }))
+            .unwrap();
+        assert_eq!(info.name, "me@example.org");
+
+        assert!(client
+            .parse_user_info(serde_json::json!({ "preferred_username": "me" }))
+            .is_err());
+    }
 }
@@ -0,0 +1,383 @@
+//! Credential backends and encrypted credential export
+//!
+//! Tokens and OAuth2 client credentials live in one of several stores. The
+//! system store is the platform keyring: the Secret Service API on Linux
+//! (GNOME Keyring, KWallet, KeePassXC), the Keychain on macOS and the
+//! Credential Manager on Windows. The chosen backend is recorded in
+//! `credential_backend.toml` so every part of the app, and every later run,
+//! uses the same one. When the recorded backend can't be reached on this
+//! system, [`BackendStatus::missing`] says so and nothing is recorded, so the
+//! user can decide what to do instead of authentication failing silently.
+//!
+//! Without a system keyring the files can be encrypted with a passphrase
+//! instead, see [`crate::oauth2::vault`].
+//!
+//! Moving to another machine or keyring goes through an export file: the
+//! credentials are serialized, encrypted with XChaCha20-Poly1305 under a key
+//! derived from a passphrase with Argon2id, as the vault does, and written as
+//! JSON.
+//!
+//! The master passphrase that unlocks the TUI is never stored; only a salted
+//! Argon2id hash of it is kept, in the same backend as the tokens.
+
+use crate::oauth2::{vault, OAuth2Error, OAuth2Result};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::prelude::*;
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// File in the config directory recording the backend in use
+pub const BACKEND_FILE: &str = "credential_backend.toml";
+
+/// Format of export files; version 1 used AES-256-GCM with PBKDF2
+const EXPORT_VERSION: u32 = 2;
+
+/// Label bound into every export so other sealed values can't be passed off
+/// as one
+const EXPORT_LABEL: &str = "credential-export";
+
+/// Where credentials are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CredentialBackend {
+    /// The platform keyring
+    System,
+    /// The Linux kernel keyring; cleared when the user logs out
+    Keyutils,
+    /// Base64 files in the config directory, readable by the user only
+    File,
+    /// Files in the config directory sealed with a passphrase-derived key
+    Encrypted,
+}
+
+impl CredentialBackend {
+    pub const ALL: [CredentialBackend; 4] =
+        [Self::System, Self::Keyutils, Self::File, Self::Encrypted];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::Keyutils => "keyutils",
+            Self::File => "file",
+            Self::Encrypted => "encrypted",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::System => "system keyring (Secret Service, Keychain or Credential Manager)",
+            Self::Keyutils => "Linux kernel keyring",
+            Self::File => "files in the config directory",
+            Self::Encrypted => "passphrase-encrypted files in the config directory",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|backend| backend.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Keyring entry for a secret; `None` for the file backends
+    pub fn entry(&self, service: &str, user: &str) -> Option<Entry> {
+        match self {
+            Self::System => Entry::new(service, user).ok(),
+            #[cfg(target_os = "linux")]
+            Self::Keyutils => keyring::keyutils::KeyutilsCredential::new_with_target(None, service, user)
+                .ok()
+                .map(|credential| Entry::new_with_credential(Box::new(credential))),
+            #[cfg(not(target_os = "linux"))]
+            Self::Keyutils => None,
+            Self::File | Self::Encrypted => None,
+        }
+    }
+
+    /// Whether the backend can be used on this system
+    ///
+    /// Keyrings are probed once per process with a lookup of an entry that
+    /// never exists: "no entry" means the store answered.
+    pub fn is_available(&self) -> bool {
+        static SYSTEM: OnceLock<bool> = OnceLock::new();
+        static KEYUTILS: OnceLock<bool> = OnceLock::new();
+
+        let probe = || match self.entry("comunicado-backend-probe", "probe") {
+            Some(entry) => match entry.get_password() {
+                Ok(_) | Err(keyring::Error::NoEntry) => true,
+                Err(e) => {
+                    tracing::debug!("Credential backend {} unavailable: {}", self.name(), e);
+                    false
+                }
+            },
+            None => false,
+        };
+
+        match self {
+            Self::System => *SYSTEM.get_or_init(probe),
+            Self::Keyutils => *KEYUTILS.get_or_init(probe),
+            Self::File | Self::Encrypted => true,
+        }
+    }
+}
+
+impl std::fmt::Display for CredentialBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackendRecord {
+    backend: CredentialBackend,
+}
+
+/// Backend chosen at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackendStatus {
+    /// Backend credentials are read from and written to
+    pub backend: CredentialBackend,
+    /// Backend that holds the credentials but can't be reached here
+    pub missing: Option<CredentialBackend>,
+}
+
+impl BackendStatus {
+    /// Pick the backend for `config_dir`
+    ///
+    /// The recorded backend wins when it is available. Without a record the
+    /// system keyring is preferred and the choice is recorded. When the
+    /// credentials are in a backend that can't be reached, files are used for
+    /// now and `missing` is set. `has_keyring_only_tokens` tells whether
+    /// accounts exist whose tokens have no file copy, which is how an
+    /// unrecorded system keyring is noticed missing.
+    pub fn detect(config_dir: &Path, has_keyring_only_tokens: impl FnOnce() -> bool) -> Self {
+        match read_recorded_backend(config_dir) {
+            Some(backend) if backend.is_available() => Self {
+                backend,
+                missing: None,
+            },
+            Some(backend) => {
+                tracing::warn!(
+                    "Credential backend {} is not available, falling back to files",
+                    backend
+                );
+                Self {
+                    backend: CredentialBackend::File,
+                    missing: Some(backend),
+                }
+            }
+            None if CredentialBackend::System.is_available() => {
+                record_backend(config_dir, CredentialBackend::System);
+                Self {
+                    backend: CredentialBackend::System,
+                    missing: None,
+                }
+            }
+            None if has_keyring_only_tokens() => Self {
+                backend: CredentialBackend::File,
+                missing: Some(CredentialBackend::System),
+            },
+            None => {
+                record_backend(config_dir, CredentialBackend::File);
+                Self {
+                    backend: CredentialBackend::File,
+                    missing: None,
+                }
+            }
+        }
+    }
+}
+
+/// Backend recorded for `config_dir`, if any
+pub fn read_recorded_backend(config_dir: &Path) -> Option<CredentialBackend> {
+    let content = fs::read_to_string(config_dir.join(BACKEND_FILE)).ok()?;
+    match toml::from_str::<BackendRecord>(&content) {
+        Ok(record) => Some(record.backend),
+        Err(e) => {
+            tracing::warn!("Ignoring unreadable {}: {}", BACKEND_FILE, e);
+            None
+        }
+    }
+}
+
+/// Record the backend for `config_dir`
+pub fn write_recorded_backend(config_dir: &Path, backend: CredentialBackend) -> OAuth2Result<()> {
+    let content = toml::to_string(&BackendRecord { backend })
+        .map_err(|e| OAuth2Error::StorageError(format!("Failed to serialize backend: {}", e)))?;
+    fs::write(config_dir.join(BACKEND_FILE), content)
+        .map_err(|e| OAuth2Error::StorageError(format!("Failed to record credential backend: {}", e)))
+}
+
+fn record_backend(config_dir: &Path, backend: CredentialBackend) {
+    if let Err(e) = write_recorded_backend(config_dir, backend) {
+        tracing::warn!("{}", e);
+    }
+}
+
+/// Secrets of one account, with its account file so an export is enough to
+/// set the account up on another machine
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AccountCredentials {
+    pub account_id: String,
+    pub config: Option<serde_json::Value>,
+    pub access_token: Option<String>,
+    pub refresh_token: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+/// Encrypted export file
+#[derive(Debug, Serialize, Deserialize)]
+struct CredentialExport {
+    version: u32,
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    /// Serialized credentials sealed under the passphrase key
+    ciphertext: String,
+}
+
+/// Encrypt credentials into an export file
+pub fn encrypt_credentials(
+    credentials: &[AccountCredentials],
+    passphrase: &str,
+) -> OAuth2Result<String> {
+    encrypt_with_cost(credentials, passphrase, vault::MEMORY_KIB, vault::ITERATIONS)
+}
+
+fn encrypt_with_cost(
+    credentials: &[AccountCredentials],
+    passphrase: &str,
+    memory_kib: u32,
+    iterations: u32,
+) -> OAuth2Result<String> {
+    let mut salt = [0u8; 16];
+    vault::random_fill(&mut salt)?;
+    let key = vault::derive_key(passphrase, &salt, memory_kib, iterations, vault::PARALLELISM)?;
+
+    let data = serde_json::to_string(credentials)
+        .map_err(|e| OAuth2Error::StorageError(format!("Failed to serialize credentials: {}", e)))?;
+    let export = CredentialExport {
+        version: EXPORT_VERSION,
+        salt: BASE64_STANDARD.encode(salt),
+        memory_kib,
+        iterations,
+        parallelism: vault::PARALLELISM,
+        ciphertext: vault::seal_with(&key, EXPORT_LABEL, &data)?,
+    };
+    serde_json::to_string_pretty(&export)
+        .map_err(|e| OAuth2Error::StorageError(format!("Failed to serialize export: {}", e)))
+}
+
+/// Decrypt an export file
+///
+/// A wrong passphrase and a damaged file give the same error; the AEAD
+/// can't tell them apart.
+pub fn decrypt_credentials(export: &str, passphrase: &str) -> OAuth2Result<Vec<AccountCredentials>> {
+    let invalid = |what: &str| OAuth2Error::StorageError(format!("Invalid credential export: {}", what));
+
+    let export: CredentialExport =
+        serde_json::from_str(export).map_err(|e| invalid(&e.to_string()))?;
+    if export.version != EXPORT_VERSION {
+        return Err(invalid(&format!("unsupported version {}", export.version)));
+    }
+    let salt = BASE64_STANDARD.decode(&export.salt).map_err(|_| invalid("bad salt"))?;
+
+    let key = vault::derive_key(
+        passphrase,
+        &salt,
+        export.memory_kib,
+        export.iterations,
+        export.parallelism,
+    )?;
+    let data = vault::open_with(&key, EXPORT_LABEL, &export.ciphertext).map_err(|_| {
+        OAuth2Error::StorageError("Wrong passphrase or damaged export file".to_string())
+    })?;
+
+    serde_json::from_str(&data).map_err(|e| invalid(&e.to_string()))
+}
+
+/// Salted Argon2id hash of a master passphrase, for storing in place of it
+pub fn hash_passphrase(passphrase: &str) -> OAuth2Result<String> {
+    hash_with_cost(passphrase, vault::MEMORY_KIB, vault::ITERATIONS)
+}
+
+fn hash_with_cost(passphrase: &str, memory_kib: u32, iterations: u32) -> OAuth2Result<String> {
+    let mut salt = [0u8; 16];
+    vault::random_fill(&mut salt)?;
+    let salt = SaltString::encode_b64(&salt)
+        .map_err(|e| OAuth2Error::StorageError(format!("Invalid salt: {}", e)))?;
+    let params = Params::new(memory_kib, iterations, vault::PARALLELISM, None)
+        .map_err(|e| OAuth2Error::StorageError(format!("Invalid Argon2 parameters: {}", e)))?;
+
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password(passphrase.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| OAuth2Error::StorageError(format!("Failed to hash passphrase: {}", e)))
+}
+
+/// Check a passphrase against a hash from [`hash_passphrase`]
+///
+/// The Argon2 parameters are read from the hash itself.
+pub fn verify_passphrase(passphrase: &str, stored: &str) -> bool {
+    PasswordHash::new(stored).is_ok_and(|hash| {
+        Argon2::default()
+            .verify_password(passphrase.as_bytes(), &hash)
+            .is_ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_export_round_trip() {
+        let credentials = vec![AccountCredentials {
+            account_id: "gmail_jane".to_string(),
+            config: Some(serde_json::json!({ "account_id": "gmail_jane" })),
+            access_token: Some("ya29.access".to_string()),
+            refresh_token: Some("1//refresh".to_string()),
+            client_id: Some("client.apps.googleusercontent.com".to_string()),
+            client_secret: None,
+        }];
+
+        // Cheap Argon2 parameters keep the tests fast
+        let export = encrypt_with_cost(&credentials, "correct horse", 64, 1).unwrap();
+        assert!(!export.contains("ya29.access"));
+        assert_eq!(decrypt_credentials(&export, "correct horse").unwrap(), credentials);
+        assert!(decrypt_credentials(&export, "wrong horse").is_err());
+        assert!(decrypt_credentials("{}", "correct horse").is_err());
+    }
+
+    #[test]
+    fn test_passphrase_hash() {
+        let stored = hash_with_cost("correct horse", 64, 1).unwrap();
+        assert!(stored.starts_with("$argon2id$"));
+        assert!(!stored.contains("correct horse"));
+        assert!(verify_passphrase("correct horse", &stored));
+        assert!(!verify_passphrase("wrong horse", &stored));
+        assert!(!verify_passphrase("correct horse", "plain"));
+        assert_ne!(stored, hash_with_cost("correct horse", 64, 1).unwrap());
+    }
+
+    #[test]
+    fn test_backend_record() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(read_recorded_backend(dir.path()), None);
+
+        write_recorded_backend(dir.path(), CredentialBackend::File).unwrap();
+        assert_eq!(read_recorded_backend(dir.path()), Some(CredentialBackend::File));
+
+        let status = BackendStatus::detect(dir.path(), || unreachable!());
+        assert_eq!(status.backend, CredentialBackend::File);
+        assert_eq!(status.missing, None);
+
+        assert_eq!(CredentialBackend::from_name("Keyutils"), Some(CredentialBackend::Keyutils));
+        assert_eq!(CredentialBackend::from_name("kwallet"), None);
+    }
+}
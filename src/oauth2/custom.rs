@@ -0,0 +1,208 @@
+//! OAuth2 providers defined by the user
+//!
+//! Self-hosted identity providers (Keycloak, Authentik, Dex and the like)
+//! are described in `oauth2_providers.toml`, one `[[provider]]` table each.
+//! A custom provider is selected like a built-in one: by its email domains
+//! in the setup wizard, or from the provider list. Accounts remember it by
+//! name, so token refresh finds the same token endpoint again.
+
+use crate::oauth2::{OAuth2Error, OAuth2Provider, OAuth2Result, OAuth2Scope, ProviderConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// One provider from `oauth2_providers.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomProvider {
+    /// Name the provider is listed and stored under
+    pub name: String,
+    /// Email domains that select this provider in the setup wizard
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// Client credentials; can be left out and entered in the wizard
+    #[serde(default)]
+    pub client_id: String,
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    pub authorization_url: String,
+    pub token_url: String,
+    #[serde(default)]
+    pub device_authorization_url: Option<String>,
+    /// OpenID Connect userinfo endpoint, used to look up the account's address
+    #[serde(default)]
+    pub userinfo_url: Option<String>,
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+    /// Extra query parameters for the authorization URL
+    #[serde(default)]
+    pub additional_params: HashMap<String, String>,
+    pub imap_server: String,
+    #[serde(default = "default_imap_port")]
+    pub imap_port: u16,
+    pub smtp_server: String,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default = "default_true")]
+    pub uses_pkce: bool,
+}
+
+fn default_scopes() -> Vec<String> {
+    ["openid", "email", "profile", "offline_access"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_imap_port() -> u16 {
+    993
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl CustomProvider {
+    /// Provider configuration to authenticate and refresh tokens with
+    pub fn to_config(&self) -> ProviderConfig {
+        let mut config = ProviderConfig::custom(
+            self.name.clone(),
+            self.client_id.clone(),
+            self.client_secret.clone(),
+            self.authorization_url.clone(),
+            self.token_url.clone(),
+            self.imap_server.clone(),
+            self.imap_port,
+            self.smtp_server.clone(),
+            self.smtp_port,
+        )
+        .with_scopes(self.scopes.iter().cloned().map(OAuth2Scope::Custom).collect())
+        .with_additional_params(self.additional_params.clone());
+
+        config.device_authorization_url = self.device_authorization_url.clone();
+        config.userinfo_url = self.userinfo_url.clone();
+        config.uses_pkce = self.uses_pkce;
+        config
+    }
+
+    fn matches_domain(&self, domain: &str) -> bool {
+        self.domains
+            .iter()
+            .any(|candidate| candidate.trim_start_matches('@').eq_ignore_ascii_case(domain))
+    }
+}
+
+/// Contents of `oauth2_providers.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomProviders {
+    #[serde(rename = "provider")]
+    pub providers: Vec<CustomProvider>,
+}
+
+impl CustomProviders {
+    /// Load the custom providers; none when the file doesn't exist
+    pub fn load() -> OAuth2Result<Self> {
+        let path = Self::config_file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path).map_err(|e| {
+            OAuth2Error::InvalidConfig(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        Self::parse(&content)
+            .map_err(|e| OAuth2Error::InvalidConfig(format!("{}: {}", path.display(), e)))
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        let providers: Self = toml::from_str(content).map_err(|e| e.to_string())?;
+
+        // Built-in names would resolve to the built-in provider, not this one
+        if let Some(clash) = providers
+            .providers
+            .iter()
+            .find(|provider| OAuth2Provider::from_str(&provider.name).is_ok())
+        {
+            return Err(format!(
+                "custom provider '{}' has the name of a built-in provider",
+                clash.name
+            ));
+        }
+        Ok(providers)
+    }
+
+    /// Provider with the given name
+    pub fn find(&self, name: &str) -> Option<&CustomProvider> {
+        self.providers
+            .iter()
+            .find(|provider| provider.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Provider that claims the domain of an email address
+    pub fn detect_from_email(&self, email: &str) -> Option<&CustomProvider> {
+        let domain = email.split('@').nth(1)?;
+        self.providers
+            .iter()
+            .find(|provider| provider.matches_domain(domain))
+    }
+
+    fn config_file_path() -> OAuth2Result<PathBuf> {
+        dirs::config_dir()
+            .map(|dir| dir.join("comunicado").join("oauth2_providers.toml"))
+            .ok_or_else(|| {
+                OAuth2Error::InvalidConfig("Could not determine config directory".to_string())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+[[provider]]
+name = "Keycloak"
+domains = ["example.org", "@mail.example.org"]
+client_id = "comunicado"
+authorization_url = "https://id.example.org/realms/mail/protocol/openid-connect/auth"
+token_url = "https://id.example.org/realms/mail/protocol/openid-connect/token"
+userinfo_url = "https://id.example.org/realms/mail/protocol/openid-connect/userinfo"
+imap_server = "mail.example.org"
+smtp_server = "mail.example.org"
+"#;
+
+    #[test]
+    fn test_parse_and_convert() {
+        let providers = CustomProviders::parse(EXAMPLE).unwrap();
+        let provider = providers.find("keycloak").unwrap();
+        assert_eq!(provider.imap_port, 993);
+        assert!(provider.uses_pkce);
+
+        let config = provider.to_config();
+        assert_eq!(config.provider, OAuth2Provider::Custom("Keycloak".to_string()));
+        assert_eq!(config.token_url, provider.token_url);
+        assert_eq!(config.scope_string(), "openid email profile offline_access");
+        assert!(config.userinfo_url.is_some());
+        assert!(!config.supports_device_flow());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_detect_from_email() {
+        let providers = CustomProviders::parse(EXAMPLE).unwrap();
+        assert!(providers.detect_from_email("me@example.org").is_some());
+        assert!(providers.detect_from_email("me@MAIL.example.org").is_some());
+        assert!(providers.detect_from_email("me@example.com").is_none());
+    }
+
+    #[test]
+    fn test_builtin_names_rejected() {
+        let clash = EXAMPLE.replace("name = \"Keycloak\"", "name = \"gmail\"");
+        assert!(CustomProviders::parse(&clash).is_err());
+    }
+}
@@ -1,15 +1,20 @@
 pub mod client;
+pub mod credential_backend;
+pub mod custom;
 pub mod providers;
 pub mod storage;
 pub mod token;
+pub mod vault;
 pub mod wizard;
 
 pub use client::OAuth2Client;
+pub use credential_backend::{BackendStatus, CredentialBackend};
+pub use custom::{CustomProvider, CustomProviders};
 pub use providers::{OAuth2Provider, ProviderConfig, ProviderDetector};
 pub use storage::SecureStorage;
 pub use token::{
-    AccessToken, RefreshStats, RefreshToken, TokenDiagnosis, TokenManager, TokenRefreshScheduler,
-    TokenStats,
+    AccessToken, RefreshStats, RefreshToken, TokenDiagnosis, TokenManager, TokenRefreshEvent,
+    TokenRefreshScheduler, TokenStats,
 };
 pub use wizard::SetupWizard;
 
@@ -31,6 +36,11 @@ pub enum OAuth2Error {
     #[error("Token refresh failed: {0}")]
     TokenRefreshFailed(String),
 
+    /// The provider no longer accepts the refresh token; the account has to
+    /// sign in again
+    #[error("Refresh token revoked: {0}")]
+    RefreshTokenRevoked(String),
+
     #[error("Invalid token: {0}")]
     InvalidToken(String),
 
@@ -55,6 +65,9 @@ pub enum OAuth2Error {
 
 pub type OAuth2Result<T> = Result<T, OAuth2Error>;
 
+/// Access tokens expiring within this many minutes are refreshed ahead of use
+pub const REFRESH_WINDOW_MINUTES: i64 = 5;
+
 /// OAuth2 authorization code with PKCE
 #[derive(Debug, Clone)]
 pub struct AuthorizationCode {
@@ -263,10 +276,10 @@ impl AccountConfig {
     }
 
     pub fn needs_refresh(&self) -> bool {
-        // Consider token expired if it expires within 5 minutes
+        // Consider token expired if it expires within the refresh window
         if let Some(expires_at) = self.token_expires_at {
             let now = chrono::Utc::now();
-            let buffer = chrono::Duration::minutes(5);
+            let buffer = chrono::Duration::minutes(REFRESH_WINDOW_MINUTES);
             now + buffer > expires_at
         } else {
             false
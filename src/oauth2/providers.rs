@@ -1,4 +1,4 @@
-use crate::oauth2::{OAuth2Error, OAuth2Result, OAuth2Scope};
+use crate::oauth2::{CustomProviders, OAuth2Error, OAuth2Result, OAuth2Scope};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -38,6 +38,11 @@ impl OAuth2Provider {
             _ => Err(OAuth2Error::InvalidProvider(s.to_string())),
         }
     }
+
+    /// Provider for a stored provider name; names that aren't built in are custom
+    pub fn from_name(name: &str) -> Self {
+        Self::from_str(name).unwrap_or_else(|_| OAuth2Provider::Custom(name.to_string()))
+    }
 }
 
 /// OAuth2 provider configuration
@@ -48,6 +53,12 @@ pub struct ProviderConfig {
     pub client_secret: Option<String>, // Some providers don't require client secret for PKCE
     pub authorization_url: String,
     pub token_url: String,
+    /// Device authorization endpoint (RFC 8628), for signing in without a local browser
+    #[serde(default)]
+    pub device_authorization_url: Option<String>,
+    /// User info endpoint for custom providers; built-ins use their own
+    #[serde(default)]
+    pub userinfo_url: Option<String>,
     pub redirect_uri: String,
     pub scopes: Vec<OAuth2Scope>,
     pub additional_params: HashMap<String, String>,
@@ -69,6 +80,8 @@ impl ProviderConfig {
             client_secret: Some("your-gmail-client-secret".to_string()),
             authorization_url: "https://accounts.google.com/o/oauth2/v2/auth".to_string(),
             token_url: "https://oauth2.googleapis.com/token".to_string(),
+            device_authorization_url: Some("https://oauth2.googleapis.com/device/code".to_string()),
+            userinfo_url: None,
             redirect_uri: "http://localhost:8080/oauth/callback".to_string(), // Standard desktop app redirect
             scopes: vec![
                 OAuth2Scope::OpenId,         // Required for user info access
@@ -102,6 +115,10 @@ impl ProviderConfig {
             authorization_url: "https://login.microsoftonline.com/common/oauth2/v2.0/authorize"
                 .to_string(),
             token_url: "https://login.microsoftonline.com/common/oauth2/v2.0/token".to_string(),
+            device_authorization_url: Some(
+                "https://login.microsoftonline.com/common/oauth2/v2.0/devicecode".to_string(),
+            ),
+            userinfo_url: None,
             redirect_uri: "http://localhost:8080/oauth/callback".to_string(),
             scopes: vec![
                 OAuth2Scope::OutlookMailReadWrite,
@@ -127,6 +144,8 @@ impl ProviderConfig {
             client_secret: Some("your-yahoo-client-secret".to_string()),
             authorization_url: "https://api.login.yahoo.com/oauth2/request_auth".to_string(),
             token_url: "https://api.login.yahoo.com/oauth2/get_token".to_string(),
+            device_authorization_url: None, // Yahoo has no device flow
+            userinfo_url: None,
             redirect_uri: "http://localhost:8080/oauth/callback".to_string(),
             scopes: vec![OAuth2Scope::YahooMailRead, OAuth2Scope::YahooMailWrite],
             additional_params: HashMap::new(),
@@ -157,6 +176,8 @@ impl ProviderConfig {
             client_secret,
             authorization_url,
             token_url,
+            device_authorization_url: None,
+            userinfo_url: None,
             redirect_uri: "http://localhost:8080/oauth/callback".to_string(),
             scopes: Vec::new(),
             additional_params: HashMap::new(),
@@ -179,14 +200,22 @@ impl ProviderConfig {
     }
 
     /// Get provider configuration by provider type
+    ///
+    /// Custom providers are looked up by name in `oauth2_providers.toml`.
     pub fn get_config(provider: &OAuth2Provider) -> OAuth2Result<Self> {
         match provider {
             OAuth2Provider::Gmail => Ok(Self::gmail()),
             OAuth2Provider::Outlook => Ok(Self::outlook()),
             OAuth2Provider::Yahoo => Ok(Self::yahoo()),
-            OAuth2Provider::Custom(_) => Err(OAuth2Error::InvalidConfig(
-                "Custom provider configuration must be provided".to_string(),
-            )),
+            OAuth2Provider::Custom(name) => CustomProviders::load()?
+                .find(name)
+                .map(|custom| custom.to_config())
+                .ok_or_else(|| {
+                    OAuth2Error::InvalidConfig(format!(
+                        "No custom provider named '{}' in oauth2_providers.toml",
+                        name
+                    ))
+                }),
         }
     }
 
@@ -203,6 +232,17 @@ impl ProviderConfig {
         self
     }
 
+    /// Set the device authorization endpoint
+    pub fn with_device_authorization_url(mut self, url: String) -> Self {
+        self.device_authorization_url = Some(url);
+        self
+    }
+
+    /// Whether the provider can sign in with a code entered on another device
+    pub fn supports_device_flow(&self) -> bool {
+        self.device_authorization_url.is_some()
+    }
+
     /// Add custom scopes
     pub fn with_scopes(mut self, scopes: Vec<OAuth2Scope>) -> Self {
         self.scopes = scopes;
@@ -337,6 +377,12 @@ impl ProviderConfig {
                 "  - Register all ports 8080-8084 to fix this".to_string(),
                 "".to_string(),
                 "For production use, submit your app for Google verification.".to_string(),
+                "".to_string(),
+                "SIGNING IN OVER SSH (no local browser):".to_string(),
+                "• Press Ctrl+D on the credentials screen to sign in with a code".to_string(),
+                "• This needs an OAuth client of type 'TVs and Limited Input devices'".to_string(),
+                "• Google limits which scopes that client type may request; if it".to_string(),
+                "  answers 'invalid_scope', use the browser flow on a desktop instead".to_string(),
             ],
             OAuth2Provider::Outlook => vec![
                 "To set up Outlook OAuth2 access:".to_string(),
@@ -357,6 +403,9 @@ impl ProviderConfig {
                 "12. Select 'Microsoft Graph' > 'Delegated permissions'".to_string(),
                 "13. Add: Mail.Read, Mail.ReadWrite, Mail.Send, Contacts.Read, offline_access".to_string(),
                 "14. Click 'Grant admin consent' (if you have admin rights)".to_string(),
+                "".to_string(),
+                "To sign in over SSH with a code (Ctrl+D on the credentials screen):".to_string(),
+                "15. Go to 'Authentication' and set 'Allow public client flows' to Yes".to_string(),
             ],
             OAuth2Provider::Yahoo => vec![
                 "To set up Yahoo OAuth2 access:".to_string(),
@@ -382,8 +431,12 @@ impl ProviderConfig {
                 "1. Check provider's OAuth2 documentation".to_string(),
                 "2. Register application with provider".to_string(),
                 "3. Configure redirect URI: http://localhost:8080/oauth/callback".to_string(),
+                "   (Comunicado will use ports 8080-8089 automatically)".to_string(),
                 "4. Obtain client credentials".to_string(),
-                "5. Configure IMAP/SMTP server settings".to_string(),
+                "5. Check the endpoints and IMAP/SMTP servers in oauth2_providers.toml".to_string(),
+                "".to_string(),
+                "Set userinfo_url so Comunicado can look up your address,".to_string(),
+                "and request the offline_access scope so tokens can be refreshed.".to_string(),
             ],
         }
     }
@@ -401,7 +454,13 @@ impl ProviderDetector {
             "gmail.com" | "googlemail.com" => Some(OAuth2Provider::Gmail),
             "outlook.com" | "hotmail.com" | "live.com" | "msn.com" => Some(OAuth2Provider::Outlook),
             "yahoo.com" | "yahoo.co.uk" | "yahoo.ca" | "yahoo.au" => Some(OAuth2Provider::Yahoo),
-            _ => None,
+            _ => {
+                // Fall back to the domains of the custom providers
+                let custom = CustomProviders::load().ok()?;
+                custom
+                    .detect_from_email(email)
+                    .map(|provider| OAuth2Provider::Custom(provider.name.clone()))
+            }
         }
     }
 
@@ -442,6 +501,15 @@ mod tests {
         assert!(OAuth2Provider::from_str("invalid").is_err());
     }
 
+    #[test]
+    fn test_provider_from_name() {
+        assert_eq!(OAuth2Provider::from_name("Gmail"), OAuth2Provider::Gmail);
+        assert_eq!(
+            OAuth2Provider::from_name("Keycloak"),
+            OAuth2Provider::Custom("Keycloak".to_string())
+        );
+    }
+
     #[test]
     fn test_provider_display_names() {
         assert_eq!(OAuth2Provider::Gmail.display_name(), "Gmail");
@@ -489,6 +557,28 @@ mod tests {
         assert!(scope_string.contains("https://mail.google.com/"));
     }
 
+    #[test]
+    fn test_device_flow_support() {
+        assert!(ProviderConfig::gmail().supports_device_flow());
+        assert!(ProviderConfig::outlook().supports_device_flow());
+        assert!(!ProviderConfig::yahoo().supports_device_flow());
+
+        let custom = ProviderConfig::custom(
+            "Example".to_string(),
+            "client".to_string(),
+            None,
+            "https://auth.example.com/authorize".to_string(),
+            "https://auth.example.com/token".to_string(),
+            "imap.example.com".to_string(),
+            993,
+            "smtp.example.com".to_string(),
+            587,
+        );
+        assert!(!custom.supports_device_flow());
+        let custom = custom.with_device_authorization_url("https://auth.example.com/device".to_string());
+        assert!(custom.supports_device_flow());
+    }
+
     #[test]
     fn test_supported_providers() {
         let providers = ProviderConfig::supported_providers();
@@ -1,15 +1,22 @@
-use crate::oauth2::{AccountConfig, OAuth2Error, OAuth2Result};
+use crate::oauth2::credential_backend::{
+    self, AccountCredentials, BackendStatus, CredentialBackend,
+};
+use crate::oauth2::{vault, AccountConfig, OAuth2Error, OAuth2Result};
 use base64::prelude::*;
 use keyring::Entry;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Keyring user and file name prefix for the master passphrase hash
+const MASTER_ACCOUNT: &str = "_master";
+
 /// Secure storage for OAuth2 tokens and account configurations
 #[derive(Clone)]
 pub struct SecureStorage {
     app_name: String,
     config_dir: PathBuf,
+    backend: BackendStatus,
 }
 
 impl SecureStorage {
@@ -20,6 +27,11 @@ impl SecureStorage {
         let config_dir = Self::get_config_directory(&app_name)?;
         tracing::debug!("Config directory determined: {:?}", config_dir);
 
+        Self::with_config_dir(app_name, config_dir)
+    }
+
+    /// Create a secure storage instance that keeps account files in `config_dir`
+    pub fn with_config_dir(app_name: String, config_dir: PathBuf) -> OAuth2Result<Self> {
         // Ensure config directory exists
         if !config_dir.exists() {
             tracing::debug!("Creating config directory: {:?}", config_dir);
@@ -28,10 +40,16 @@ impl SecureStorage {
             })?;
         }
 
+        let backend = BackendStatus::detect(&config_dir, || {
+            Self::has_keyring_only_tokens(&config_dir)
+        });
+        tracing::debug!("Using {} credential backend", backend.backend);
+
         tracing::debug!("SecureStorage initialized successfully");
         Ok(Self {
             app_name,
             config_dir,
+            backend,
         })
     }
 
@@ -46,8 +64,8 @@ impl SecureStorage {
         let client_id_service = format!("{}-oauth-client-id", self.app_name);
         let client_secret_service = format!("{}-oauth-client-secret", self.app_name);
 
-        match Entry::new(&client_id_service, account_id) {
-            Ok(entry) => {
+        match self.keyring_entry(&client_id_service, account_id) {
+            Some(entry) => {
                 if entry.set_password(client_id).is_err() {
                     tracing::warn!(
                         "Failed to store OAuth client ID in keyring, using file fallback"
@@ -55,13 +73,13 @@ impl SecureStorage {
                     self.store_credential_to_file(account_id, "client_id", client_id)?;
                 }
             }
-            Err(_) => {
+            None => {
                 self.store_credential_to_file(account_id, "client_id", client_id)?;
             }
         }
 
-        match Entry::new(&client_secret_service, account_id) {
-            Ok(entry) => {
+        match self.keyring_entry(&client_secret_service, account_id) {
+            Some(entry) => {
                 if entry.set_password(client_secret).is_err() {
                     tracing::warn!(
                         "Failed to store OAuth client secret in keyring, using file fallback"
@@ -69,7 +87,7 @@ impl SecureStorage {
                     self.store_credential_to_file(account_id, "client_secret", client_secret)?;
                 }
             }
-            Err(_) => {
+            None => {
                 self.store_credential_to_file(account_id, "client_secret", client_secret)?;
             }
         }
@@ -341,8 +359,8 @@ impl SecureStorage {
     fn store_access_token(&self, account_id: &str, token: &str) -> OAuth2Result<()> {
         // Try keyring first, but catch all keyring-related errors gracefully
         let service = format!("{}-access-token", self.app_name);
-        match Entry::new(&service, account_id) {
-            Ok(entry) => {
+        match self.keyring_entry(&service, account_id) {
+            Some(entry) => {
                 if entry.set_password(token).is_ok() {
                     tracing::debug!("Successfully stored access token in keyring");
                     return Ok(());
@@ -350,8 +368,8 @@ impl SecureStorage {
                     tracing::debug!("Failed to set password in keyring, using file fallback");
                 }
             }
-            Err(e) => {
-                tracing::debug!("Keyring Entry::new failed ({}), using file fallback", e);
+            None => {
+                tracing::debug!("No keyring entry, using file fallback");
             }
         }
 
@@ -366,8 +384,8 @@ impl SecureStorage {
         
         // Try keyring first, but catch all keyring-related errors gracefully
         let service = format!("{}-access-token", self.app_name);
-        match Entry::new(&service, account_id) {
-            Ok(entry) => {
+        match self.keyring_entry(&service, account_id) {
+            Some(entry) => {
                 if let Ok(token) = entry.get_password() {
                     tracing::debug!("Successfully loaded access token from keyring for {}", account_id);
                     return Some(token);
@@ -375,8 +393,8 @@ impl SecureStorage {
                     tracing::debug!("No access token found in keyring for {}, trying file fallback", account_id);
                 }
             }
-            Err(e) => {
-                tracing::debug!("Keyring Entry::new failed for {} ({}), trying file fallback", account_id, e);
+            None => {
+                tracing::debug!("No keyring entry for {}, trying file fallback", account_id);
             }
         }
 
@@ -418,8 +436,8 @@ impl SecureStorage {
     fn store_refresh_token(&self, account_id: &str, token: &str) -> OAuth2Result<()> {
         // Try keyring first, but catch all keyring-related errors gracefully
         let service = format!("{}-refresh-token", self.app_name);
-        match Entry::new(&service, account_id) {
-            Ok(entry) => {
+        match self.keyring_entry(&service, account_id) {
+            Some(entry) => {
                 if entry.set_password(token).is_ok() {
                     tracing::debug!("Successfully stored refresh token in keyring");
                     return Ok(());
@@ -429,11 +447,8 @@ impl SecureStorage {
                     );
                 }
             }
-            Err(e) => {
-                tracing::debug!(
-                    "Keyring Entry::new failed for refresh token ({}), using file fallback",
-                    e
-                );
+            None => {
+                tracing::debug!("No keyring entry for refresh token, using file fallback");
             }
         }
 
@@ -446,8 +461,8 @@ impl SecureStorage {
     fn load_refresh_token(&self, account_id: &str) -> Option<String> {
         // Try keyring first, but catch all keyring-related errors gracefully
         let service = format!("{}-refresh-token", self.app_name);
-        match Entry::new(&service, account_id) {
-            Ok(entry) => {
+        match self.keyring_entry(&service, account_id) {
+            Some(entry) => {
                 if let Ok(token) = entry.get_password() {
                     tracing::debug!("Successfully loaded refresh token from keyring");
                     return Some(token);
@@ -455,11 +470,8 @@ impl SecureStorage {
                     tracing::debug!("No refresh token found in keyring, trying file fallback");
                 }
             }
-            Err(e) => {
-                tracing::debug!(
-                    "Keyring Entry::new failed for refresh token ({}), trying file fallback",
-                    e
-                );
+            None => {
+                tracing::debug!("No keyring entry for refresh token, trying file fallback");
             }
         }
 
@@ -470,13 +482,10 @@ impl SecureStorage {
     /// Load OAuth2 client ID
     fn load_oauth_client_id(&self, account_id: &str) -> Option<String> {
         let service = format!("{}-oauth-client-id", self.app_name);
-        match Entry::new(&service, account_id) {
-            Ok(entry) => {
-                if let Ok(client_id) = entry.get_password() {
-                    return Some(client_id);
-                }
+        if let Some(entry) = self.keyring_entry(&service, account_id) {
+            if let Ok(client_id) = entry.get_password() {
+                return Some(client_id);
             }
-            Err(_) => {}
         }
 
         // Fallback to file storage
@@ -486,13 +495,10 @@ impl SecureStorage {
     /// Load OAuth2 client secret
     fn load_oauth_client_secret(&self, account_id: &str) -> Option<String> {
         let service = format!("{}-oauth-client-secret", self.app_name);
-        match Entry::new(&service, account_id) {
-            Ok(entry) => {
-                if let Ok(client_secret) = entry.get_password() {
-                    return Some(client_secret);
-                }
+        if let Some(entry) = self.keyring_entry(&service, account_id) {
+            if let Ok(client_secret) = entry.get_password() {
+                return Some(client_secret);
             }
-            Err(_) => {}
         }
 
         // Fallback to file storage
@@ -546,12 +552,9 @@ impl SecureStorage {
         token_type: &str,
         token: &str,
     ) -> OAuth2Result<()> {
-        let token_file = self
-            .config_dir
-            .join(format!("{}.{}.token", account_id, token_type));
-
-        // Simple base64 encoding for basic obfuscation (not cryptographically secure)
-        let encoded_token = base64::prelude::BASE64_STANDARD.encode(token);
+        let file_name = format!("{}.{}.token", account_id, token_type);
+        let token_file = self.config_dir.join(&file_name);
+        let encoded_token = self.encode_file_secret(&file_name, token)?;
 
         fs::write(&token_file, encoded_token)
             .map_err(|e| OAuth2Error::StorageError(format!("Failed to write token file: {}", e)))?;
@@ -571,9 +574,8 @@ impl SecureStorage {
 
     /// Load token from file (fallback when keyring unavailable)
     fn load_token_from_file(&self, account_id: &str, token_type: &str) -> Option<String> {
-        let token_file = self
-            .config_dir
-            .join(format!("{}.{}.token", account_id, token_type));
+        let file_name = format!("{}.{}.token", account_id, token_type);
+        let token_file = self.config_dir.join(&file_name);
 
         if !token_file.exists() {
             tracing::debug!("Token file does not exist: {:?}", token_file);
@@ -582,7 +584,11 @@ impl SecureStorage {
 
         let encoded_token = fs::read_to_string(&token_file).ok()?;
         tracing::debug!("Loading {} token for {}, file size: {} bytes", token_type, account_id, encoded_token.len());
-        
+
+        if vault::is_sealed(&encoded_token) {
+            return Self::open_sealed(&file_name, &encoded_token);
+        }
+
         match base64::prelude::BASE64_STANDARD.decode(encoded_token.trim()) {
             Ok(decoded) => {
                 match String::from_utf8(decoded) {
@@ -610,12 +616,9 @@ impl SecureStorage {
         credential_type: &str,
         credential: &str,
     ) -> OAuth2Result<()> {
-        let cred_file = self
-            .config_dir
-            .join(format!("{}.{}.cred", account_id, credential_type));
-
-        // Simple base64 encoding for basic obfuscation (not cryptographically secure)
-        let encoded_credential = base64::prelude::BASE64_STANDARD.encode(credential);
+        let file_name = format!("{}.{}.cred", account_id, credential_type);
+        let cred_file = self.config_dir.join(&file_name);
+        let encoded_credential = self.encode_file_secret(&file_name, credential)?;
 
         fs::write(&cred_file, encoded_credential).map_err(|e| {
             OAuth2Error::StorageError(format!("Failed to write credential file: {}", e))
@@ -639,21 +642,38 @@ impl SecureStorage {
 
     /// Load OAuth2 credential from file
     fn load_credential_from_file(&self, account_id: &str, credential_type: &str) -> Option<String> {
-        let cred_file = self
-            .config_dir
-            .join(format!("{}.{}.cred", account_id, credential_type));
+        let file_name = format!("{}.{}.cred", account_id, credential_type);
+        let cred_file = self.config_dir.join(&file_name);
 
         if !cred_file.exists() {
             return None;
         }
 
         let encoded_credential = fs::read_to_string(&cred_file).ok()?;
+        if vault::is_sealed(&encoded_credential) {
+            return Self::open_sealed(&file_name, &encoded_credential);
+        }
         base64::prelude::BASE64_STANDARD
             .decode(encoded_credential.trim())
             .ok()
             .and_then(|decoded| String::from_utf8(decoded).ok())
     }
 
+    /// File contents for a secret: sealed with the vault key for the
+    /// encrypted backend, base64 otherwise (obfuscation, not encryption)
+    fn encode_file_secret(&self, file_name: &str, secret: &str) -> OAuth2Result<String> {
+        match self.backend.backend {
+            CredentialBackend::Encrypted => vault::seal(file_name, secret),
+            _ => Ok(base64::prelude::BASE64_STANDARD.encode(secret)),
+        }
+    }
+
+    fn open_sealed(file_name: &str, sealed: &str) -> Option<String> {
+        vault::open(file_name, sealed)
+            .map_err(|e| tracing::warn!("Cannot read {}: {}", file_name, e))
+            .ok()
+    }
+
     /// Get configuration directory path
     fn get_config_directory(app_name: &str) -> OAuth2Result<PathBuf> {
         // Use XDG Base Directory specification on Linux/Unix
@@ -710,6 +730,226 @@ impl SecureStorage {
 
         Ok(configs)
     }
+
+    /// Set the master passphrase, or remove it when `passphrase` is empty
+    pub fn set_master_passphrase(&self, passphrase: &str) -> OAuth2Result<()> {
+        let service = format!("{}-master-passphrase", self.app_name);
+        if passphrase.is_empty() {
+            if let Some(entry) = self.keyring_entry(&service, MASTER_ACCOUNT) {
+                let _ = entry.delete_password();
+            }
+            let cred_file = self
+                .config_dir
+                .join(format!("{}.passphrase.cred", MASTER_ACCOUNT));
+            if cred_file.exists() {
+                fs::remove_file(&cred_file).map_err(|e| {
+                    OAuth2Error::StorageError(format!("Failed to remove passphrase file: {}", e))
+                })?;
+            }
+            tracing::info!("Master passphrase removed");
+            return Ok(());
+        }
+
+        let stored = credential_backend::hash_passphrase(passphrase)?;
+        self.store_master_passphrase_hash(&stored)?;
+        tracing::info!("Master passphrase set");
+        Ok(())
+    }
+
+    fn store_master_passphrase_hash(&self, stored: &str) -> OAuth2Result<()> {
+        let service = format!("{}-master-passphrase", self.app_name);
+        match self.keyring_entry(&service, MASTER_ACCOUNT) {
+            Some(entry) if entry.set_password(stored).is_ok() => Ok(()),
+            _ => self.store_credential_to_file(MASTER_ACCOUNT, "passphrase", stored),
+        }
+    }
+
+    fn load_master_passphrase_hash(&self) -> Option<String> {
+        let service = format!("{}-master-passphrase", self.app_name);
+        self.keyring_entry(&service, MASTER_ACCOUNT)
+            .and_then(|entry| entry.get_password().ok())
+            .or_else(|| self.load_credential_from_file(MASTER_ACCOUNT, "passphrase"))
+    }
+
+    /// Whether a master passphrase has been set
+    pub fn has_master_passphrase(&self) -> bool {
+        self.load_master_passphrase_hash().is_some()
+    }
+
+    /// Check `passphrase` against the master passphrase
+    ///
+    /// Deliberately slow; run it off the UI thread.
+    pub fn verify_master_passphrase(&self, passphrase: &str) -> bool {
+        self.load_master_passphrase_hash()
+            .is_some_and(|stored| credential_backend::verify_passphrase(passphrase, &stored))
+    }
+
+    /// Whether the encrypted backend is in use and waits for its passphrase
+    pub fn needs_unlock(&self) -> bool {
+        self.backend.backend == CredentialBackend::Encrypted && !vault::is_unlocked()
+    }
+
+    /// Unlock the credential vault for this process
+    ///
+    /// Deliberately slow; run it off the UI thread.
+    pub fn unlock_vault(&self, passphrase: &str) -> OAuth2Result<()> {
+        vault::unlock(&self.config_dir, passphrase)
+    }
+
+    /// Encrypt every stored secret with `passphrase` and use the encrypted
+    /// backend from now on, returning the account count
+    ///
+    /// Only meant for systems without a keyring. The plain token files are
+    /// overwritten with their sealed versions.
+    pub fn encrypt_with_passphrase(&mut self, passphrase: &str) -> OAuth2Result<usize> {
+        if !vault::exists(&self.config_dir) {
+            vault::create(&self.config_dir, passphrase)?;
+        } else if !vault::is_unlocked() {
+            vault::unlock(&self.config_dir, passphrase)?;
+        }
+        self.migrate_backend(CredentialBackend::Encrypted)
+    }
+
+    /// Credential backend in use, and the one that couldn't be reached if any
+    pub fn backend_status(&self) -> BackendStatus {
+        self.backend
+    }
+
+    /// Keyring entry for a secret in the current backend
+    fn keyring_entry(&self, service: &str, account_id: &str) -> Option<Entry> {
+        self.backend.backend.entry(service, account_id)
+    }
+
+    /// Whether any account has tokens but no token file, so they can only be
+    /// in a keyring
+    fn has_keyring_only_tokens(config_dir: &Path) -> bool {
+        fs::read_dir(config_dir)
+            .map(|entries| {
+                entries.flatten().any(|entry| {
+                    let path = entry.path();
+                    path.extension().and_then(|s| s.to_str()) == Some("json")
+                        && path
+                            .file_stem()
+                            .and_then(|s| s.to_str())
+                            .is_some_and(|id| {
+                                !config_dir.join(format!("{}.access.token", id)).exists()
+                            })
+                })
+            })
+            .unwrap_or(false)
+    }
+
+    /// Read every account's secrets from the current backend
+    fn collect_credentials(&self) -> OAuth2Result<Vec<AccountCredentials>> {
+        let mut credentials = Vec::new();
+        for account_id in self.list_account_ids()? {
+            let config = fs::read_to_string(self.get_account_config_path(&account_id))
+                .ok()
+                .and_then(|json| serde_json::from_str(&json).ok());
+            credentials.push(AccountCredentials {
+                access_token: self.load_access_token(&account_id),
+                refresh_token: self.load_refresh_token(&account_id),
+                client_id: self.load_oauth_client_id(&account_id),
+                client_secret: self.load_oauth_client_secret(&account_id),
+                config,
+                account_id,
+            });
+        }
+        Ok(credentials)
+    }
+
+    /// Write an account's secrets to the current backend
+    fn restore_credentials(&self, credentials: &AccountCredentials) -> OAuth2Result<()> {
+        let account_id = &credentials.account_id;
+        if let Some(config) = &credentials.config {
+            let config_path = self.get_account_config_path(account_id);
+            if !config_path.exists() {
+                let config_json = serde_json::to_string_pretty(config).map_err(|e| {
+                    OAuth2Error::StorageError(format!("Failed to serialize account config: {}", e))
+                })?;
+                fs::write(&config_path, config_json).map_err(|e| {
+                    OAuth2Error::StorageError(format!("Failed to write account config: {}", e))
+                })?;
+            }
+        }
+        if let Some(token) = &credentials.access_token {
+            self.store_access_token(account_id, token)?;
+        }
+        if let Some(token) = &credentials.refresh_token {
+            self.store_refresh_token(account_id, token)?;
+        }
+        if let (Some(client_id), Some(client_secret)) =
+            (&credentials.client_id, &credentials.client_secret)
+        {
+            self.store_oauth_credentials(account_id, client_id, client_secret)?;
+        }
+        Ok(())
+    }
+
+    /// Export all accounts and their secrets, encrypted with `passphrase`
+    pub fn export_credentials(&self, passphrase: &str) -> OAuth2Result<String> {
+        let credentials = self.collect_credentials()?;
+        credential_backend::encrypt_credentials(&credentials, passphrase)
+    }
+
+    /// Import an export into the current backend, returning the account count
+    ///
+    /// Existing account files are kept; their tokens are replaced.
+    pub fn import_credentials(&self, export: &str, passphrase: &str) -> OAuth2Result<usize> {
+        let credentials = credential_backend::decrypt_credentials(export, passphrase)?;
+        for account in &credentials {
+            self.restore_credentials(account)?;
+        }
+        tracing::info!("Imported credentials for {} accounts", credentials.len());
+        Ok(credentials.len())
+    }
+
+    /// Copy all secrets to `target` and make it the backend from now on
+    ///
+    /// The secrets are left in the old backend so going back is possible,
+    /// except between the two file backends, which share their files.
+    pub fn migrate_backend(&mut self, target: CredentialBackend) -> OAuth2Result<usize> {
+        if !target.is_available() {
+            return Err(OAuth2Error::StorageError(format!(
+                "The {} is not available on this system",
+                target.description()
+            )));
+        }
+        if target == CredentialBackend::Encrypted && !vault::is_unlocked() {
+            return Err(OAuth2Error::StorageError(
+                "The credential vault is locked".to_string(),
+            ));
+        }
+
+        let credentials = self.collect_credentials()?;
+        let master_passphrase = self.load_master_passphrase_hash();
+        let previous = self.backend;
+        self.backend = BackendStatus {
+            backend: target,
+            missing: None,
+        };
+        for account in &credentials {
+            if let Err(e) = self.restore_credentials(account) {
+                self.backend = previous;
+                return Err(e);
+            }
+        }
+        if let Some(stored) = &master_passphrase {
+            if let Err(e) = self.store_master_passphrase_hash(stored) {
+                self.backend = previous;
+                return Err(e);
+            }
+        }
+
+        credential_backend::write_recorded_backend(&self.config_dir, target)?;
+        tracing::info!(
+            "Migrated credentials for {} accounts from {} to {}",
+            credentials.len(),
+            previous.backend,
+            target
+        );
+        Ok(credentials.len())
+    }
 }
 
 /// Account configuration for storage (without sensitive tokens)
@@ -856,6 +1096,10 @@ mod tests {
         let storage = SecureStorage {
             app_name: "comunicado-test".to_string(),
             config_dir: temp_dir.path().to_path_buf(),
+            backend: BackendStatus {
+                backend: CredentialBackend::File,
+                missing: None,
+            },
         };
 
         (storage, temp_dir)
@@ -1,8 +1,8 @@
-use crate::oauth2::{OAuth2Error, OAuth2Result, TokenResponse};
+use crate::oauth2::{OAuth2Error, OAuth2Result, TokenResponse, REFRESH_WINDOW_MINUTES};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
 
 /// Access token with metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -125,10 +125,15 @@ impl TokenManager {
         token_response: &TokenResponse,
     ) -> OAuth2Result<()> {
         let access_token = AccessToken::from_response(token_response);
-        let refresh_token = token_response
-            .refresh_token
-            .as_ref()
-            .map(|token| RefreshToken::new(token.clone()));
+        let mut tokens = self.tokens.write().await;
+
+        // Refresh responses usually leave the refresh token out; keep the old one
+        let refresh_token = match &token_response.refresh_token {
+            Some(token) => Some(RefreshToken::new(token.clone())),
+            None => tokens
+                .get(&account_id)
+                .and_then(|pair| pair.refresh_token.clone()),
+        };
 
         let token_pair = TokenPair {
             access_token,
@@ -137,7 +142,6 @@ impl TokenManager {
             provider,
         };
 
-        let mut tokens = self.tokens.write().await;
         tokens.insert(account_id, token_pair);
 
         Ok(())
@@ -166,7 +170,7 @@ impl TokenManager {
         
         let (needs_refresh, is_expired, _token_from_storage) = if let Some(token_pair) = token_in_cache {
             (
-                token_pair.access_token.needs_refresh(5), // 5 minute buffer
+                token_pair.access_token.needs_refresh(REFRESH_WINDOW_MINUTES),
                 token_pair.access_token.is_expired(),
                 None
             )
@@ -202,7 +206,7 @@ impl TokenManager {
                         }
                         
                         // Calculate refresh flags for the loaded token
-                        let needs_refresh = access_token.needs_refresh(5);
+                        let needs_refresh = access_token.needs_refresh(REFRESH_WINDOW_MINUTES);
                         let is_expired = access_token.is_expired();
                         
                         (needs_refresh, is_expired, Some(access_token))
@@ -447,6 +451,10 @@ impl TokenManager {
                 tracing::info!("Successfully refreshed token for account {}", account_id);
                 Ok(new_access_token)
             }
+            Err(OAuth2Error::RefreshTokenRevoked(reason)) => {
+                tracing::error!("Refresh token for account {} was revoked: {}", account_id, reason);
+                Err(OAuth2Error::RefreshTokenRevoked(reason))
+            }
             Err(e) => {
                 tracing::error!("Failed to refresh token for account {}: {}", account_id, e);
                 Err(OAuth2Error::TokenRefreshFailed(
@@ -462,7 +470,16 @@ impl TokenManager {
         refresh_token: &str,
         provider: &str,
     ) -> OAuth2Result<TokenResponse> {
-        use crate::oauth2::{OAuth2Client, ProviderConfig};
+        use crate::oauth2::{OAuth2Client, OAuth2Provider, ProviderConfig};
+
+        // Token endpoint of the account's provider, built in or custom
+        let provider_config = ProviderConfig::get_config(&OAuth2Provider::from_name(provider))
+            .map_err(|e| {
+                OAuth2Error::InvalidProvider(format!(
+                    "Token refresh not supported for provider {}: {}",
+                    provider, e
+                ))
+            })?;
 
         // First, get the account ID for this refresh token to load stored credentials
         let account_id = {
@@ -486,6 +503,15 @@ impl TokenManager {
         let (client_id, client_secret) = if let Some(ref storage) = self.storage {
             match storage.load_oauth_credentials(&account_id)? {
                 Some((id, secret)) => (id, secret),
+                // Custom providers may carry their credentials in their config file
+                None if matches!(provider_config.provider, OAuth2Provider::Custom(_))
+                    && !provider_config.client_id.is_empty() =>
+                {
+                    (
+                        provider_config.client_id.clone(),
+                        provider_config.client_secret.clone().unwrap_or_default(),
+                    )
+                }
                 None => {
                     // Fallback to environment variables
                     tracing::warn!("No stored OAuth2 credentials found for account {}, trying environment variables", account_id);
@@ -518,20 +544,9 @@ impl TokenManager {
         };
 
         // Create provider configuration with stored credentials
-        let config = match provider {
-            "gmail" => {
-                let mut config = ProviderConfig::gmail();
-                config.client_id = client_id;
-                config.client_secret = Some(client_secret);
-                config
-            }
-            _ => {
-                return Err(OAuth2Error::InvalidProvider(format!(
-                    "Token refresh not supported for provider: {}",
-                    provider
-                )));
-            }
-        };
+        // (an empty secret means a PKCE client without one)
+        let client_secret = Some(client_secret).filter(|secret| !secret.is_empty());
+        let config = provider_config.with_credentials(client_id, client_secret);
 
         // Create OAuth2 client and refresh token
         let client = OAuth2Client::new(config)?;
@@ -542,6 +557,41 @@ impl TokenManager {
         client.refresh_token(refresh_token).await
     }
 
+    /// Pick up tokens another process stored, such as `comunicado oauth2 reauth`
+    ///
+    /// Returns whether the stored refresh token replaced a different one.
+    async fn reload_from_storage(&self, account_id: &str) -> bool {
+        let Some(storage) = &self.storage else {
+            return false;
+        };
+        let Ok(Some(account)) = storage.load_account(account_id) else {
+            return false;
+        };
+
+        let mut tokens = self.tokens.write().await;
+        let current = tokens
+            .get(account_id)
+            .and_then(|pair| pair.refresh_token.as_ref())
+            .map(|token| token.token.as_str());
+        if account.access_token.is_empty() || account.refresh_token.as_deref() == current {
+            return false;
+        }
+
+        let token_pair = TokenPair {
+            access_token: AccessToken {
+                token: account.access_token,
+                token_type: "Bearer".to_string(),
+                expires_at: account.token_expires_at,
+                scopes: account.scopes,
+            },
+            refresh_token: account.refresh_token.map(RefreshToken::new),
+            account_id: account_id.to_string(),
+            provider: account.provider,
+        };
+        tokens.insert(account_id.to_string(), token_pair);
+        true
+    }
+
     /// Remove tokens for an account
     pub async fn remove_tokens(&self, account_id: &str) -> OAuth2Result<()> {
         let mut tokens = self.tokens.write().await;
@@ -705,7 +755,7 @@ impl TokenManager {
                         expired_at: access_token.expires_at,
                     }
                 }
-            } else if access_token.needs_refresh(5) {
+            } else if access_token.needs_refresh(REFRESH_WINDOW_MINUTES) {
                 TokenDiagnosis::ExpiringSoon {
                     account_id: account_id.to_string(),
                     expires_at: access_token.expires_at,
@@ -893,17 +943,53 @@ impl TokenDiagnosis {
     }
 }
 
+/// Progress of a background token refresh, for the UI to report
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenRefreshEvent {
+    /// An access token is about to expire and is being refreshed
+    Refreshing { account_id: String },
+    /// The account has a fresh access token
+    Refreshed { account_id: String },
+    /// The refresh failed but may work on a later attempt
+    Failed { account_id: String, error: String },
+    /// The provider rejected the refresh token; the account must sign in again
+    Revoked { account_id: String, reason: String },
+}
+
+/// What the scheduler remembers between checks
+#[derive(Debug, Default)]
+struct RefreshState {
+    /// Accounts whose last refresh failed, so a failure is reported once
+    failing: HashSet<String>,
+    /// Refresh tokens the provider rejected, by account; not retried until
+    /// the account signs in again and gets a new one
+    revoked: HashMap<String, String>,
+}
+
+/// Whether the scheduler should refresh this access token now
+fn refresh_due(pair: &TokenPair) -> bool {
+    pair.access_token.needs_refresh(REFRESH_WINDOW_MINUTES)
+}
+
 /// Token refresh scheduler for automatic token refresh
+///
+/// Checks every account at a short interval and refreshes access tokens
+/// that expire within [`REFRESH_WINDOW_MINUTES`], so IMAP, SMTP and the
+/// calendar never have to refresh one in the middle of a request.
 pub struct TokenRefreshScheduler {
     token_manager: Arc<TokenManager>,
     refresh_interval: chrono::Duration,
+    events: Option<mpsc::UnboundedSender<TokenRefreshEvent>>,
 }
 
 impl TokenRefreshScheduler {
     pub fn new(token_manager: Arc<TokenManager>) -> Self {
         Self {
             token_manager,
-            refresh_interval: chrono::Duration::minutes(30), // Check every 30 minutes for more responsive refreshing
+            // A check only reads expiry times, so it can run often enough to
+            // stay inside the refresh window
+            refresh_interval: chrono::Duration::minutes(1),
+            events: None,
         }
     }
 
@@ -912,13 +998,21 @@ impl TokenRefreshScheduler {
         Self {
             token_manager,
             refresh_interval: interval,
+            events: None,
         }
     }
 
+    /// Report refresh progress and failures on `events`
+    pub fn with_events(mut self, events: mpsc::UnboundedSender<TokenRefreshEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
     /// Start the token refresh scheduler
     pub async fn start(&self) -> OAuth2Result<()> {
         let token_manager = Arc::clone(&self.token_manager);
         let interval = self.refresh_interval;
+        let events = self.events.clone();
 
         // Skip initial token check during startup to avoid blocking - background scheduler will handle it
         tracing::debug!("Starting token refresh scheduler - initial validation will happen in background");
@@ -927,6 +1021,7 @@ impl TokenRefreshScheduler {
             let mut refresh_interval = tokio::time::interval(tokio::time::Duration::from_secs(
                 interval.num_seconds() as u64,
             ));
+            let mut state = RefreshState::default();
 
             // Wait for first interval before checking tokens
             refresh_interval.tick().await;
@@ -934,9 +1029,7 @@ impl TokenRefreshScheduler {
             loop {
                 refresh_interval.tick().await;
 
-                if let Err(e) = Self::refresh_expiring_tokens(&token_manager).await {
-                    tracing::warn!("Failed to refresh expiring tokens: {}", e);
-                }
+                Self::refresh_expiring_tokens(&token_manager, &mut state, events.as_ref()).await;
             }
         });
 
@@ -944,77 +1037,119 @@ impl TokenRefreshScheduler {
         Ok(())
     }
 
-    async fn refresh_expiring_tokens(token_manager: &TokenManager) -> OAuth2Result<()> {
+    async fn refresh_expiring_tokens(
+        token_manager: &TokenManager,
+        state: &mut RefreshState,
+        events: Option<&mpsc::UnboundedSender<TokenRefreshEvent>>,
+    ) {
+        let emit = |event: TokenRefreshEvent| {
+            if let Some(events) = events {
+                let _ = events.send(event);
+            }
+        };
+
         let account_ids = token_manager.get_account_ids().await;
 
         if account_ids.is_empty() {
             tracing::debug!("No accounts found for token refresh check");
-            return Ok(());
+            return;
         }
 
         tracing::debug!("Checking {} accounts for token refresh", account_ids.len());
 
         for account_id in account_ids {
-            // Use timeout for each token refresh to prevent hanging
-            let refresh_result = tokio::time::timeout(
-                std::time::Duration::from_secs(30), // 30 second timeout per refresh
-                async {
-                    // Check if token needs refresh - first check if already expired, then proactive refresh
-                    let (is_expired, needs_refresh) = {
-                        let tokens = token_manager.tokens.read().await;
-                        if let Some(pair) = tokens.get(&account_id) {
-                            let is_expired = pair.access_token.needs_refresh(0); // Already expired
-                            let needs_refresh = pair.access_token.needs_refresh(120); // 2 hour proactive buffer
-                            (is_expired, needs_refresh)
-                        } else {
-                            (false, false)
-                        }
-                    };
+            // Signing in again from the command line stores new tokens
+            if state.revoked.contains_key(&account_id)
+                && token_manager.reload_from_storage(&account_id).await
+            {
+                tracing::info!("Account {} has new tokens after signing in again", account_id);
+                state.revoked.remove(&account_id);
+                emit(TokenRefreshEvent::Refreshed {
+                    account_id: account_id.clone(),
+                });
+            }
 
-                    if is_expired {
-                        tracing::info!(
-                            "Refreshing expired token for account {}",
-                            account_id
-                        );
-                        token_manager.refresh_access_token(&account_id).await
-                    } else if needs_refresh {
-                        tracing::info!(
-                            "Proactively refreshing token for account {} (2 hour buffer)",
-                            account_id
-                        );
-                        token_manager.refresh_access_token(&account_id).await
-                    } else {
+            let refresh_token = {
+                let tokens = token_manager.tokens.read().await;
+                match tokens.get(&account_id) {
+                    Some(pair) if refresh_due(pair) => {
+                        pair.refresh_token.as_ref().map(|token| token.token.clone())
+                    }
+                    _ => {
                         tracing::debug!(
                             "Token for account {} does not need refresh yet",
                             account_id
                         );
-                        Ok(AccessToken::new("dummy".to_string(), "Bearer".to_string()))
-                        // Dummy return for no-refresh case
+                        continue;
                     }
-                },
+                }
+            };
+
+            // Without a refresh token only signing in again helps
+            let Some(refresh_token) = refresh_token else {
+                if !state.revoked.contains_key(&account_id) {
+                    state.revoked.insert(account_id.clone(), String::new());
+                    emit(TokenRefreshEvent::Revoked {
+                        account_id,
+                        reason: "no refresh token is stored".to_string(),
+                    });
+                }
+                continue;
+            };
+            if state.revoked.get(&account_id) == Some(&refresh_token) {
+                continue;
+            }
+            state.revoked.remove(&account_id);
+
+            // An account that keeps failing is retried quietly
+            let retrying = state.failing.contains(&account_id);
+            tracing::info!("Proactively refreshing token for account {}", account_id);
+            if !retrying {
+                emit(TokenRefreshEvent::Refreshing {
+                    account_id: account_id.clone(),
+                });
+            }
+
+            // Use timeout for each token refresh to prevent hanging
+            let refresh_result = tokio::time::timeout(
+                std::time::Duration::from_secs(30), // 30 second timeout per refresh
+                token_manager.refresh_access_token(&account_id),
             )
             .await;
 
-            match refresh_result {
+            let error = match refresh_result {
                 Ok(Ok(_)) => {
                     tracing::debug!("Token refresh check completed for account {}", account_id);
+                    state.failing.remove(&account_id);
+                    emit(TokenRefreshEvent::Refreshed { account_id });
+                    continue;
+                }
+                Ok(Err(OAuth2Error::RefreshTokenRevoked(reason))) => {
+                    state.failing.remove(&account_id);
+                    state.revoked.insert(account_id.clone(), refresh_token);
+                    emit(TokenRefreshEvent::Revoked { account_id, reason });
+                    continue;
                 }
                 Ok(Err(e)) => {
                     tracing::warn!("Failed to refresh token for account {}: {}", account_id, e);
-                    // Continue with other accounts
+                    e.to_string()
                 }
                 Err(_) => {
                     tracing::error!(
                         "Token refresh timed out for account {} after 30 seconds",
                         account_id
                     );
-                    // Continue with other accounts
+                    "the token endpoint did not answer within 30 seconds".to_string()
                 }
+            };
+
+            if !retrying {
+                state.failing.insert(account_id.clone());
+                emit(TokenRefreshEvent::Failed { account_id, error });
             }
         }
 
         tracing::debug!("Token refresh check completed for all accounts");
-        Ok(())
     }
 
     /// Force refresh all tokens that can be refreshed (for startup or manual refresh)
@@ -1109,8 +1244,7 @@ impl TokenRefreshScheduler {
 
                 if token_pair.access_token.is_expired() {
                     stats.accounts_expired += 1;
-                } else if token_pair.access_token.needs_refresh(120) {
-                    // 2 hours
+                } else if token_pair.access_token.needs_refresh(REFRESH_WINDOW_MINUTES) {
                     stats.accounts_needing_refresh += 1;
                 }
 
@@ -1288,4 +1422,66 @@ mod tests {
         assert_eq!(stats.valid_tokens, 1);
         assert_eq!(stats.expired_tokens, 0);
     }
+
+    #[tokio::test]
+    async fn test_refresh_keeps_refresh_token() {
+        let token_manager = TokenManager::new();
+        let response = |refresh_token: Option<&str>| TokenResponse {
+            access_token: "access".to_string(),
+            refresh_token: refresh_token.map(String::from),
+            token_type: "Bearer".to_string(),
+            expires_in: Some(3600),
+            scope: None,
+        };
+
+        token_manager
+            .store_tokens("work".to_string(), "gmail".to_string(), &response(Some("1//refresh")))
+            .await
+            .unwrap();
+        token_manager
+            .store_tokens("work".to_string(), "gmail".to_string(), &response(None))
+            .await
+            .unwrap();
+
+        let tokens = token_manager.tokens.read().await;
+        let refresh_token = tokens["work"].refresh_token.as_ref().unwrap();
+        assert_eq!(refresh_token.token, "1//refresh");
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_reports_accounts_needing_sign_in() {
+        let token_manager = TokenManager::new();
+        let response = |expires_in| TokenResponse {
+            access_token: "access".to_string(),
+            refresh_token: None,
+            token_type: "Bearer".to_string(),
+            expires_in: Some(expires_in),
+            scope: None,
+        };
+        token_manager
+            .store_tokens("fresh".to_string(), "gmail".to_string(), &response(3600))
+            .await
+            .unwrap();
+        token_manager
+            .store_tokens("stale".to_string(), "gmail".to_string(), &response(60))
+            .await
+            .unwrap();
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut state = RefreshState::default();
+        TokenRefreshScheduler::refresh_expiring_tokens(&token_manager, &mut state, Some(&sender))
+            .await;
+
+        // Only the expiring token is acted on, and it can't be refreshed
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(TokenRefreshEvent::Revoked { account_id, .. }) if account_id == "stale"
+        ));
+        assert!(receiver.try_recv().is_err());
+
+        // The next check doesn't report it again
+        TokenRefreshScheduler::refresh_expiring_tokens(&token_manager, &mut state, Some(&sender))
+            .await;
+        assert!(receiver.try_recv().is_err());
+    }
 }
@@ -0,0 +1,293 @@
+//! Passphrase-encrypted credential files
+//!
+//! Without a system keyring, tokens and client secrets sit in the config
+//! directory as files that any process running as the user can read. The
+//! `encrypted` backend seals each of those files with XChaCha20-Poly1305
+//! under a key derived from a passphrase with Argon2id. `vault.toml` holds
+//! the salt, the Argon2 parameters and a check value to recognise a wrong
+//! passphrase; neither the passphrase nor the key is ever written.
+//!
+//! The vault is unlocked once per process, before the TUI or a command
+//! starts, and the key is then shared by every [`SecureStorage`].
+//!
+//! [`SecureStorage`]: crate::oauth2::SecureStorage
+
+use crate::oauth2::{OAuth2Error, OAuth2Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::prelude::*;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// File in the config directory describing the vault key
+pub const VAULT_FILE: &str = "vault.toml";
+
+/// Prefix of sealed file contents; anything else is a plain base64 file
+const SEALED_PREFIX: &str = "xchacha20poly1305$";
+
+/// Bound into every sealed value together with its file name, so a sealed
+/// refresh token can't be swapped in for another file
+const AAD_PREFIX: &str = "comunicado-vault-v1:";
+
+/// Label of the check value in `vault.toml`
+const CHECK_LABEL: &str = "check";
+const CHECK_VALUE: &str = "comunicado";
+
+/// Argon2id cost for new vaults, exports and passphrase hashes: 64 MiB,
+/// three passes
+pub(crate) const MEMORY_KIB: u32 = 64 * 1024;
+pub(crate) const ITERATIONS: u32 = 3;
+pub(crate) const PARALLELISM: u32 = 1;
+
+/// Key of the unlocked vault
+static KEY: RwLock<Option<[u8; 32]>> = RwLock::new(None);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VaultHeader {
+    version: u32,
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    /// [`CHECK_VALUE`] sealed under the key
+    check: String,
+}
+
+/// Whether a vault has been set up in `config_dir`
+pub fn exists(config_dir: &Path) -> bool {
+    config_dir.join(VAULT_FILE).exists()
+}
+
+/// Whether the key is in memory
+pub fn is_unlocked() -> bool {
+    KEY.read().map(|key| key.is_some()).unwrap_or(false)
+}
+
+/// Set up a new vault with `passphrase` and unlock it
+pub fn create(config_dir: &Path, passphrase: &str) -> OAuth2Result<()> {
+    create_with_cost(config_dir, passphrase, MEMORY_KIB, ITERATIONS)
+}
+
+fn create_with_cost(
+    config_dir: &Path,
+    passphrase: &str,
+    memory_kib: u32,
+    iterations: u32,
+) -> OAuth2Result<()> {
+    if exists(config_dir) {
+        return Err(OAuth2Error::StorageError(
+            "A credential vault already exists".to_string(),
+        ));
+    }
+
+    let mut salt = [0u8; 16];
+    random_fill(&mut salt)?;
+    let key = derive_key(passphrase, &salt, memory_kib, iterations, PARALLELISM)?;
+
+    let header = VaultHeader {
+        version: 1,
+        salt: BASE64_STANDARD.encode(salt),
+        memory_kib,
+        iterations,
+        parallelism: PARALLELISM,
+        check: seal_with(&key, CHECK_LABEL, CHECK_VALUE)?,
+    };
+    let content = toml::to_string_pretty(&header)
+        .map_err(|e| OAuth2Error::StorageError(format!("Failed to serialize vault: {}", e)))?;
+    write_private(&config_dir.join(VAULT_FILE), &content)?;
+
+    set_key(Some(key));
+    Ok(())
+}
+
+/// Unlock the vault in `config_dir`
+///
+/// Argon2 is slow on purpose, so this takes a moment.
+pub fn unlock(config_dir: &Path, passphrase: &str) -> OAuth2Result<()> {
+    let invalid = |what: &str| OAuth2Error::StorageError(format!("Invalid {}: {}", VAULT_FILE, what));
+
+    let content = fs::read_to_string(config_dir.join(VAULT_FILE))
+        .map_err(|e| OAuth2Error::StorageError(format!("Failed to read {}: {}", VAULT_FILE, e)))?;
+    let header: VaultHeader = toml::from_str(&content).map_err(|e| invalid(&e.to_string()))?;
+    if header.version != 1 {
+        return Err(invalid(&format!("unsupported version {}", header.version)));
+    }
+    let salt = BASE64_STANDARD
+        .decode(&header.salt)
+        .map_err(|_| invalid("bad salt"))?;
+
+    let key = derive_key(
+        passphrase,
+        &salt,
+        header.memory_kib,
+        header.iterations,
+        header.parallelism,
+    )?;
+    match open_with(&key, CHECK_LABEL, &header.check) {
+        Ok(check) if check == CHECK_VALUE => {
+            set_key(Some(key));
+            Ok(())
+        }
+        _ => Err(OAuth2Error::StorageError("Wrong passphrase".to_string())),
+    }
+}
+
+/// Whether file contents were sealed by the vault
+pub fn is_sealed(content: &str) -> bool {
+    content.starts_with(SEALED_PREFIX)
+}
+
+/// Encrypt a secret for the file named `label`
+pub fn seal(label: &str, plaintext: &str) -> OAuth2Result<String> {
+    seal_with(&current_key()?, label, plaintext)
+}
+
+/// Decrypt the contents of the file named `label`
+pub fn open(label: &str, sealed: &str) -> OAuth2Result<String> {
+    open_with(&current_key()?, label, sealed)
+}
+
+fn current_key() -> OAuth2Result<[u8; 32]> {
+    KEY.read()
+        .ok()
+        .and_then(|key| *key)
+        .ok_or_else(|| OAuth2Error::StorageError("The credential vault is locked".to_string()))
+}
+
+fn set_key(key: Option<[u8; 32]>) {
+    if let Ok(mut current) = KEY.write() {
+        *current = key;
+    }
+}
+
+pub(crate) fn seal_with(key: &[u8; 32], label: &str, plaintext: &str) -> OAuth2Result<String> {
+    let mut nonce = [0u8; 24];
+    random_fill(&mut nonce)?;
+    let aad = format!("{}{}", AAD_PREFIX, label);
+
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext.as_bytes(),
+                aad: aad.as_bytes(),
+            },
+        )
+        .map_err(|_| OAuth2Error::StorageError("Failed to encrypt credential".to_string()))?;
+
+    Ok(format!(
+        "{}{}${}",
+        SEALED_PREFIX,
+        BASE64_STANDARD.encode(nonce),
+        BASE64_STANDARD.encode(ciphertext)
+    ))
+}
+
+/// A wrong key, a tampered file and a file renamed from another account
+/// all give the same error
+pub(crate) fn open_with(key: &[u8; 32], label: &str, sealed: &str) -> OAuth2Result<String> {
+    let invalid = || OAuth2Error::StorageError(format!("Cannot decrypt {}", label));
+
+    let (nonce, ciphertext) = sealed
+        .trim()
+        .strip_prefix(SEALED_PREFIX)
+        .and_then(|rest| rest.split_once('$'))
+        .ok_or_else(invalid)?;
+    let nonce: [u8; 24] = BASE64_STANDARD
+        .decode(nonce)
+        .ok()
+        .and_then(|nonce| nonce.try_into().ok())
+        .ok_or_else(invalid)?;
+    let ciphertext = BASE64_STANDARD.decode(ciphertext).map_err(|_| invalid())?;
+    let aad = format!("{}{}", AAD_PREFIX, label);
+
+    let plaintext = XChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: aad.as_bytes(),
+            },
+        )
+        .map_err(|_| invalid())?;
+    String::from_utf8(plaintext).map_err(|_| invalid())
+}
+
+pub(crate) fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> OAuth2Result<[u8; 32]> {
+    let params = Params::new(memory_kib, iterations, parallelism, Some(32))
+        .map_err(|e| OAuth2Error::StorageError(format!("Invalid Argon2 parameters: {}", e)))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| OAuth2Error::StorageError(format!("Failed to derive key: {}", e)))?;
+    Ok(key)
+}
+
+pub(crate) fn random_fill(buffer: &mut [u8]) -> OAuth2Result<()> {
+    SystemRandom::new()
+        .fill(buffer)
+        .map_err(|_| OAuth2Error::StorageError("No secure random source".to_string()))
+}
+
+fn write_private(path: &Path, content: &str) -> OAuth2Result<()> {
+    fs::write(path, content)
+        .map_err(|e| OAuth2Error::StorageError(format!("Failed to write {}: {}", path.display(), e)))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600)).map_err(|e| {
+            OAuth2Error::StorageError(format!("Failed to set {} permissions: {}", path.display(), e))
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = [7u8; 32];
+        let sealed = seal_with(&key, "work.refresh.token", "1//refresh").unwrap();
+        assert!(is_sealed(&sealed));
+        assert!(!sealed.contains("1//refresh"));
+        assert_eq!(open_with(&key, "work.refresh.token", &sealed).unwrap(), "1//refresh");
+
+        // Another key, or the same value moved to another file, won't open
+        assert!(open_with(&[8u8; 32], "work.refresh.token", &sealed).is_err());
+        assert!(open_with(&key, "home.refresh.token", &sealed).is_err());
+        assert!(open_with(&key, "work.refresh.token", "MS8vcmVmcmVzaA==").is_err());
+    }
+
+    #[test]
+    fn test_create_and_unlock() {
+        let dir = TempDir::new().unwrap();
+        assert!(!exists(dir.path()));
+
+        // Cheap Argon2 parameters keep the test fast
+        create_with_cost(dir.path(), "correct horse", 64, 1).unwrap();
+        assert!(exists(dir.path()));
+        assert!(create_with_cost(dir.path(), "other", 64, 1).is_err());
+
+        let header = fs::read_to_string(dir.path().join(VAULT_FILE)).unwrap();
+        assert!(!header.contains("correct horse"));
+
+        assert!(unlock(dir.path(), "wrong horse").is_err());
+        unlock(dir.path(), "correct horse").unwrap();
+        assert!(is_unlocked());
+    }
+}
@@ -1,5 +1,6 @@
+use crate::oauth2::client::{DeviceAuthorization, DevicePoll};
 use crate::oauth2::{
-    AccountConfig, OAuth2Client, OAuth2Error, OAuth2Provider, OAuth2Result, ProviderConfig,
+    AccountConfig, CustomProviders, OAuth2Client, OAuth2Error, OAuth2Provider, OAuth2Result, ProviderConfig,
     ProviderDetector, SecureStorage,
 };
 use crate::theme::Theme;
@@ -17,7 +18,7 @@ use ratatui::{
     Frame, Terminal,
 };
 use std::io;
-use tokio::time::Duration;
+use tokio::time::{Duration, Instant};
 
 /// OAuth2 setup wizard states
 #[derive(Debug, Clone)]
@@ -44,6 +45,7 @@ pub struct SetupWizard {
     display_name_input: String,
     selected_provider: Option<OAuth2Provider>,
     provider_list_state: ListState,
+    custom_providers: CustomProviders,
     client_id_input: String,
     client_secret_input: String,
 
@@ -53,12 +55,37 @@ pub struct SetupWizard {
     auth_request: Option<crate::oauth2::client::AuthorizationRequest>,
     authorization_started: bool,
 
+    // Sign in with a code on another device instead of a local browser
+    use_device_flow: bool,
+    device_auth: Option<PendingDeviceAuth>,
+
     // UI state
     input_mode: InputMode,
     scroll_offset: usize,
     show_help: bool,
 }
 
+/// Device authorization waiting for the user to enter the code
+struct PendingDeviceAuth {
+    authorization: DeviceAuthorization,
+    interval: Duration,
+    next_poll: Instant,
+    expires_at: Instant,
+}
+
+impl PendingDeviceAuth {
+    fn new(authorization: DeviceAuthorization) -> Self {
+        let now = Instant::now();
+        let interval = Duration::from_secs(authorization.interval);
+        Self {
+            interval,
+            next_poll: now + interval,
+            expires_at: now + Duration::from_secs(authorization.expires_in),
+            authorization,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 enum InputMode {
     Email,
@@ -88,18 +115,48 @@ impl SetupWizard {
             display_name_input: String::new(),
             selected_provider: None,
             provider_list_state,
+            custom_providers: CustomProviders::load().unwrap_or_else(|e| {
+                tracing::warn!("Ignoring custom OAuth2 providers: {}", e);
+                CustomProviders::default()
+            }),
             client_id_input: String::new(),
             client_secret_input: String::new(),
             oauth_client: None,
             account_config: None,
             auth_request: None,
             authorization_started: false,
+            use_device_flow: is_headless_session(),
+            device_auth: None,
             input_mode: InputMode::Navigation,
             scroll_offset: 0,
             show_help: false,
         })
     }
 
+    /// Create a wizard that signs an existing account in again
+    ///
+    /// Starts at the credentials screen with the account's address, provider
+    /// and stored client credentials filled in. Finishing it replaces the
+    /// account's tokens, since the account ID follows from the same address.
+    pub fn for_account(account: &AccountConfig) -> OAuth2Result<Self> {
+        let mut wizard = Self::new()?;
+        wizard.email_input = account.email_address.clone();
+        wizard.display_name_input = account.display_name.clone();
+        wizard.selected_provider = Some(OAuth2Provider::from_name(&account.provider));
+
+        if let Some((client_id, client_secret)) =
+            wizard.storage.load_oauth_credentials(&account.account_id)?
+        {
+            wizard.client_id_input = client_id;
+            wizard.client_secret_input = client_secret;
+        }
+        wizard.prefill_custom_credentials();
+
+        wizard.state = WizardState::CredentialsInput;
+        wizard.input_mode = InputMode::ClientId;
+        Ok(wizard)
+    }
+
     /// Run the setup wizard
     pub async fn run(&mut self) -> OAuth2Result<Option<AccountConfig>> {
         tracing::debug!("Starting wizard run() method");
@@ -202,6 +259,10 @@ impl SetupWizard {
                     self.show_help = !self.show_help;
                     return Ok(true);
                 }
+                KeyCode::Char('d') if matches!(self.state, WizardState::CredentialsInput) => {
+                    self.use_device_flow = !self.use_device_flow;
+                    return Ok(true);
+                }
                 _ => {}
             }
         }
@@ -276,7 +337,7 @@ impl SetupWizard {
 
             (WizardState::ProviderSelection, _) => match key.code {
                 KeyCode::Up => {
-                    let providers = ProviderConfig::supported_providers();
+                    let providers = self.providers();
                     let selected = self.provider_list_state.selected().unwrap_or(0);
                     let new_selected = if selected == 0 {
                         providers.len() - 1
@@ -286,13 +347,13 @@ impl SetupWizard {
                     self.provider_list_state.select(Some(new_selected));
                 }
                 KeyCode::Down => {
-                    let providers = ProviderConfig::supported_providers();
+                    let providers = self.providers();
                     let selected = self.provider_list_state.selected().unwrap_or(0);
                     let new_selected = (selected + 1) % providers.len();
                     self.provider_list_state.select(Some(new_selected));
                 }
                 KeyCode::Enter => {
-                    let providers = ProviderConfig::supported_providers();
+                    let providers = self.providers();
                     if let Some(selected) = self.provider_list_state.selected() {
                         self.selected_provider = Some(providers[selected].clone());
                         self.state = WizardState::ProviderInstructions;
@@ -307,6 +368,7 @@ impl SetupWizard {
 
             (WizardState::ProviderInstructions, _) => match key.code {
                 KeyCode::Enter | KeyCode::Char(' ') => {
+                    self.prefill_custom_credentials();
                     self.state = WizardState::CredentialsInput;
                     self.input_mode = InputMode::ClientId;
                 }
@@ -384,9 +446,58 @@ impl SetupWizard {
         Ok(true)
     }
 
+    /// Built-in providers followed by the custom ones
+    fn providers(&self) -> Vec<OAuth2Provider> {
+        let mut providers = ProviderConfig::supported_providers();
+        providers.extend(
+            self.custom_providers
+                .providers
+                .iter()
+                .map(|provider| OAuth2Provider::Custom(provider.name.clone())),
+        );
+        providers
+    }
+
+    fn provider_config(&self, provider: &OAuth2Provider) -> OAuth2Result<ProviderConfig> {
+        match provider {
+            OAuth2Provider::Custom(name) => self
+                .custom_providers
+                .find(name)
+                .map(|custom| custom.to_config())
+                .ok_or_else(|| {
+                    OAuth2Error::InvalidConfig(format!(
+                        "No custom provider named '{}' in oauth2_providers.toml",
+                        name
+                    ))
+                }),
+            _ => ProviderConfig::get_config(provider),
+        }
+    }
+
+    /// Start from the credentials a custom provider's config already has
+    fn prefill_custom_credentials(&mut self) {
+        let Some(custom) = self
+            .selected_provider
+            .as_ref()
+            .and_then(|provider| match provider {
+                OAuth2Provider::Custom(name) => self.custom_providers.find(name),
+                _ => None,
+            })
+        else {
+            return;
+        };
+
+        if self.client_id_input.is_empty() {
+            self.client_id_input = custom.client_id.clone();
+        }
+        if self.client_secret_input.is_empty() {
+            self.client_secret_input = custom.client_secret.clone().unwrap_or_default();
+        }
+    }
+
     async fn setup_oauth_client(&mut self) -> OAuth2Result<()> {
         if let Some(provider) = &self.selected_provider {
-            let config = ProviderConfig::get_config(provider)?.with_credentials(
+            let config = self.provider_config(provider)?.with_credentials(
                 self.client_id_input.clone(),
                 if self.client_secret_input.is_empty() {
                     None
@@ -402,7 +513,21 @@ impl SetupWizard {
         Ok(())
     }
 
+    /// Whether sign-in uses a device code for the selected provider
+    fn device_flow_active(&self) -> bool {
+        self.use_device_flow
+            && self
+                .selected_provider
+                .as_ref()
+                .and_then(|provider| self.provider_config(provider).ok())
+                .is_some_and(|config| config.supports_device_flow())
+    }
+
     async fn handle_authorization(&mut self) -> OAuth2Result<()> {
+        if self.device_flow_active() {
+            return self.handle_device_authorization().await;
+        }
+
         if let Some(oauth_client) = &mut self.oauth_client {
             // Only start authorization once
             if !self.authorization_started {
@@ -431,6 +556,55 @@ impl SetupWizard {
             // Exchange code for tokens
             let token_response = oauth_client.exchange_code(&auth_code).await?;
 
+            self.finish_authorization(token_response).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Start or poll a device authorization, without blocking the UI
+    async fn handle_device_authorization(&mut self) -> OAuth2Result<()> {
+        let Some(oauth_client) = &self.oauth_client else {
+            return Ok(());
+        };
+
+        let Some(pending) = &mut self.device_auth else {
+            let authorization = oauth_client.start_device_authorization().await?;
+            self.device_auth = Some(PendingDeviceAuth::new(authorization));
+            return Ok(()); // Return early, let the UI show the code
+        };
+
+        let now = Instant::now();
+        if now >= pending.expires_at {
+            return Err(OAuth2Error::AuthorizationTimeout);
+        }
+        if now < pending.next_poll {
+            return Ok(());
+        }
+
+        let token_response = match oauth_client.poll_device_token(&pending.authorization).await? {
+            DevicePoll::Complete(token_response) => token_response,
+            DevicePoll::Pending => {
+                pending.next_poll = now + pending.interval;
+                return Ok(());
+            }
+            DevicePoll::SlowDown => {
+                // RFC 8628: add five seconds to the interval on every slow_down
+                pending.interval += Duration::from_secs(5);
+                pending.next_poll = now + pending.interval;
+                return Ok(());
+            }
+        };
+
+        self.finish_authorization(token_response).await
+    }
+
+    /// Build the account from fresh tokens and move on to testing
+    async fn finish_authorization(
+        &mut self,
+        token_response: crate::oauth2::TokenResponse,
+    ) -> OAuth2Result<()> {
+        if let Some(oauth_client) = &self.oauth_client {
             // Create account configuration
             let display_name = if self.display_name_input.is_empty() {
                 None
@@ -500,6 +674,8 @@ impl SetupWizard {
         self.account_config = None;
         self.auth_request = None;
         self.authorization_started = false;
+        self.use_device_flow = is_headless_session();
+        self.device_auth = None;
         self.input_mode = InputMode::Navigation;
         self.scroll_offset = 0;
     }
@@ -632,6 +808,7 @@ impl SetupWizard {
             Line::from("• Gmail (@gmail.com, @googlemail.com)"),
             Line::from("• Outlook (@outlook.com, @hotmail.com, @live.com)"),
             Line::from("• Yahoo (@yahoo.com, @yahoo.co.uk, etc.)"),
+            Line::from("• Custom providers, by the domains in oauth2_providers.toml"),
             Line::from(""),
             Line::from("Use Tab to switch between fields."),
             Line::from("Press Enter when ready to continue."),
@@ -645,7 +822,7 @@ impl SetupWizard {
     }
 
     fn draw_provider_selection(&mut self, f: &mut Frame, area: Rect) {
-        let providers = ProviderConfig::supported_providers();
+        let providers = self.providers();
         let items: Vec<ListItem> = providers
             .iter()
             .map(|provider| ListItem::new(provider.display_name()))
@@ -669,8 +846,10 @@ impl SetupWizard {
 
     fn draw_provider_instructions(&mut self, f: &mut Frame, area: Rect) {
         if let Some(provider) = &self.selected_provider {
-            let config = ProviderConfig::get_config(provider).unwrap();
-            let instructions = config.setup_instructions();
+            let instructions = match self.provider_config(provider) {
+                Ok(config) => config.setup_instructions(),
+                Err(e) => vec![e.to_string()],
+            };
 
             let text: Vec<Line> = instructions
                 .iter()
@@ -733,7 +912,7 @@ impl SetupWizard {
         f.render_widget(client_secret_input, chunks[1]);
 
         // Instructions
-        let instructions = vec![
+        let mut instructions = vec![
             Line::from("Enter the OAuth2 credentials from your provider."),
             Line::from(""),
             Line::from("The Client ID is always required."),
@@ -743,6 +922,26 @@ impl SetupWizard {
             Line::from("Press Enter when ready to start authorization."),
         ];
 
+        let supports_device_flow = self
+            .selected_provider
+            .as_ref()
+            .and_then(|provider| self.provider_config(provider).ok())
+            .is_some_and(|config| config.supports_device_flow());
+        if supports_device_flow {
+            let sign_in = if self.use_device_flow {
+                "Sign in: with a code on another device"
+            } else {
+                "Sign in: in a browser on this machine"
+            };
+            instructions.extend(vec![
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled(sign_in, Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(" (Ctrl+D to switch)", Style::default().fg(Color::DarkGray)),
+                ]),
+            ]);
+        }
+
         let paragraph = Paragraph::new(instructions)
             .wrap(Wrap { trim: true })
             .block(
@@ -755,6 +954,11 @@ impl SetupWizard {
     }
 
     fn draw_authorization(&mut self, f: &mut Frame, area: Rect) {
+        if self.device_flow_active() {
+            self.draw_device_authorization(f, area);
+            return;
+        }
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(5), Constraint::Min(0)])
@@ -821,6 +1025,73 @@ impl SetupWizard {
         f.render_widget(paragraph, chunks[1]);
     }
 
+    fn draw_device_authorization(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(5), Constraint::Min(0)])
+            .split(area);
+
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Authorization in Progress"),
+            )
+            .gauge_style(Style::default().fg(self.theme.colors.palette.accent))
+            .percent(50)
+            .label("Waiting for sign-in on another device...");
+        f.render_widget(gauge, chunks[0]);
+
+        let mut instructions = vec![
+            Line::from("Sign in from any device with a browser, such as your phone."),
+            Line::from(""),
+        ];
+
+        if let Some(pending) = &self.device_auth {
+            let device = &pending.authorization;
+            let minutes_left = pending
+                .expires_at
+                .saturating_duration_since(Instant::now())
+                .as_secs()
+                .div_ceil(60);
+
+            instructions.extend(vec![
+                Line::from("1. Open this URL:"),
+                create_clickable_url_line(&device.verification_uri),
+                Line::from(""),
+                Line::from("2. Enter this code:"),
+                Line::from(Span::styled(
+                    device.user_code.clone(),
+                    Style::default()
+                        .fg(self.theme.colors.palette.accent)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+                Line::from("3. Log in and grant permission to Comunicado"),
+                Line::from(""),
+            ]);
+            if let Some(complete) = &device.verification_uri_complete {
+                instructions.extend(vec![
+                    Line::from("Or open this URL, which has the code filled in:"),
+                    create_clickable_url_line(complete),
+                    Line::from(""),
+                ]);
+            }
+            instructions.push(Line::from(format!(
+                "The code expires in {} minutes.",
+                minutes_left
+            )));
+        } else {
+            instructions.push(Line::from("Requesting a sign-in code..."));
+        }
+
+        let paragraph = Paragraph::new(instructions)
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL).title("Device Sign-in"));
+
+        f.render_widget(paragraph, chunks[1]);
+    }
+
     fn draw_testing(&mut self, f: &mut Frame, area: Rect) {
         let gauge = Gauge::default()
             .block(
@@ -901,6 +1172,9 @@ impl SetupWizard {
             WizardState::ProviderSelection => "Select your email provider",
             WizardState::ProviderInstructions => "Follow provider setup instructions",
             WizardState::CredentialsInput => "Enter OAuth2 credentials",
+            WizardState::Authorization if self.device_flow_active() => {
+                "Device sign-in in progress"
+            }
             WizardState::Authorization => "Browser authorization in progress",
             WizardState::Testing => "Testing account configuration",
             WizardState::Complete => "Setup completed successfully",
@@ -938,13 +1212,14 @@ impl SetupWizard {
             Line::from("  ↑/↓ - Navigate lists"),
             Line::from("  Ctrl+C - Exit wizard"),
             Line::from("  Ctrl+H - Toggle this help"),
+            Line::from("  Ctrl+D - Sign in with a code on another device"),
             Line::from(""),
             Line::from("Setup Process:"),
             Line::from("  1. Enter email address"),
             Line::from("  2. Select or detect provider"),
             Line::from("  3. Follow setup instructions"),
             Line::from("  4. Enter OAuth2 credentials"),
-            Line::from("  5. Complete browser authorization"),
+            Line::from("  5. Authorize in a browser, or enter the code shown"),
             Line::from("  6. Test configuration"),
             Line::from(""),
             Line::from("Press Ctrl+H to close this help."),
@@ -988,6 +1263,17 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// Whether a browser on this machine is unlikely to be usable
+///
+/// True over SSH, or on Linux without a graphical session.
+fn is_headless_session() -> bool {
+    let is_set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+
+    is_set("SSH_CONNECTION")
+        || is_set("SSH_TTY")
+        || (cfg!(target_os = "linux") && !is_set("DISPLAY") && !is_set("WAYLAND_DISPLAY"))
+}
+
 /// Attempt to open URL in the default browser
 fn open_browser_url(url: &str) -> Result<(), String> {
     use std::process::Command;
@@ -1062,6 +1348,23 @@ mod tests {
         assert!(matches!(wizard.input_mode, InputMode::Navigation));
     }
 
+    #[test]
+    fn test_device_flow_follows_provider() {
+        let mut wizard = SetupWizard::new().unwrap();
+        wizard.use_device_flow = true;
+
+        wizard.selected_provider = Some(OAuth2Provider::Outlook);
+        assert!(wizard.device_flow_active());
+
+        // Yahoo has no device flow, so the browser flow is used regardless
+        wizard.selected_provider = Some(OAuth2Provider::Yahoo);
+        assert!(!wizard.device_flow_active());
+
+        wizard.selected_provider = Some(OAuth2Provider::Gmail);
+        wizard.use_device_flow = false;
+        assert!(!wizard.device_flow_active());
+    }
+
     #[test]
     fn test_clickable_url_creation() {
         let test_url =
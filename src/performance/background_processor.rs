@@ -5,8 +5,11 @@
 //! without blocking the UI thread.
 
 use crate::email::sync_engine::{SyncProgress, SyncStrategy, SyncPhase, SyncEngine};
-use crate::email::database::EmailDatabase;
+use crate::email::database::{EmailDatabase, StoredMessage};
+use crate::calendar::{CalendarManager, DueReminder};
 use crate::imap::ImapAccountManager;
+use crate::smtp::{SendResult, SmtpService};
+use super::cache::CacheManager;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -100,6 +103,15 @@ pub enum BackgroundTaskType {
         folder_name: String,
         message_count: usize,
     },
+    /// Bring back the account's snoozed messages that are due
+    SnoozeWake,
+    /// Send a scheduled draft whose time has come
+    ScheduledSend {
+        draft_id: String,
+        from_address: String,
+    },
+    /// Collect the calendar reminders that are due
+    CalendarReminders,
     /// Calendar sync operations
     CalendarSync {
         calendar_id: String,
@@ -147,6 +159,10 @@ pub enum TaskResultData {
     MessageCount(usize),
     SearchResults(Vec<String>), // Message IDs
     CacheStats(usize), // Number of cached items
+    /// A scheduled send went out; `None` if it was cancelled meanwhile
+    ScheduledSend(Option<Box<SendResult>>),
+    /// Calendar reminders that went off
+    CalendarReminders(Vec<DueReminder>),
     CalendarSyncResult {
         events_synced: usize,
         events_updated: usize,
@@ -209,6 +225,12 @@ pub struct BackgroundProcessor {
     account_manager: Arc<ImapAccountManager>,
     /// Email database
     database: Arc<EmailDatabase>,
+    /// Message cache filled by `CachePreload` tasks
+    cache_manager: Arc<CacheManager>,
+    /// Sends `ScheduledSend` tasks; set once SMTP is up
+    smtp_service: Arc<RwLock<Option<SmtpService>>>,
+    /// Checked by `CalendarReminders` tasks; set once the calendar is up
+    calendar_manager: Arc<RwLock<Option<Arc<CalendarManager>>>>,
 }
 
 /// Background processor settings
@@ -273,6 +295,9 @@ impl BackgroundProcessor {
             sync_engine,
             account_manager,
             database,
+            cache_manager: Arc::new(CacheManager::new()),
+            smtp_service: Arc::new(RwLock::new(None)),
+            calendar_manager: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -315,6 +340,9 @@ impl BackgroundProcessor {
             sync_engine: dummy_sync_engine,
             account_manager: dummy_account_manager,
             database: dummy_database,
+            cache_manager: Arc::new(CacheManager::new()),
+            smtp_service: Arc::new(RwLock::new(None)),
+            calendar_manager: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -358,9 +386,27 @@ impl BackgroundProcessor {
             sync_engine,
             account_manager,
             database,
+            cache_manager: Arc::new(CacheManager::new()),
+            smtp_service: Arc::new(RwLock::new(None)),
+            calendar_manager: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Message cache that `CachePreload` tasks fill
+    pub fn cache_manager(&self) -> Arc<CacheManager> {
+        self.cache_manager.clone()
+    }
+
+    /// SMTP service that `ScheduledSend` tasks send with
+    pub async fn set_smtp_service(&self, smtp_service: SmtpService) {
+        *self.smtp_service.write().await = Some(smtp_service);
+    }
+
+    /// Calendar manager that `CalendarReminders` tasks check
+    pub async fn set_calendar_manager(&self, calendar_manager: Arc<CalendarManager>) {
+        *self.calendar_manager.write().await = Some(calendar_manager);
+    }
+
     /// Starts the background processor task queue
     /// 
     /// This spawns the main processor loop that handles task scheduling and execution.
@@ -392,6 +438,9 @@ impl BackgroundProcessor {
         let sync_engine = self.sync_engine.clone();
         let account_manager = self.account_manager.clone();
         let database = self.database.clone();
+        let cache_manager = self.cache_manager.clone();
+        let smtp_service = self.smtp_service.clone();
+        let calendar_manager = self.calendar_manager.clone();
 
         tokio::spawn(async move {
             let mut processing_interval = tokio::time::interval(settings.processing_interval);
@@ -409,6 +458,9 @@ impl BackgroundProcessor {
                             &sync_engine,
                             &account_manager,
                             &database,
+                            &cache_manager,
+                            &smtp_service,
+                            &calendar_manager,
                         ).await;
                     }
                     _ = shutdown_rx.recv() => {
@@ -734,6 +786,9 @@ impl BackgroundProcessor {
         sync_engine: &Arc<SyncEngine>,
         account_manager: &Arc<ImapAccountManager>,
         database: &Arc<EmailDatabase>,
+        cache_manager: &Arc<CacheManager>,
+        smtp_service: &Arc<RwLock<Option<SmtpService>>>,
+        calendar_manager: &Arc<RwLock<Option<Arc<CalendarManager>>>>,
     ) {
         // Clean up completed tasks
         {
@@ -765,8 +820,16 @@ impl BackgroundProcessor {
             let mut queue = task_queue.write().await;
             let mut selected_task = None;
             
+            // Low priority work never takes the last free slot, so a
+            // user-requested task can always start right away
+            let low_allowed = current_running + 1 < settings.max_concurrent_tasks
+                || settings.max_concurrent_tasks == 1;
+
             // Process priorities from high to low
             for priority in [TaskPriority::Critical, TaskPriority::High, TaskPriority::Normal, TaskPriority::Low] {
+                if priority == TaskPriority::Low && !low_allowed {
+                    break;
+                }
                 if let Some(priority_queue) = queue.get_mut(&priority) {
                     if !priority_queue.is_empty() {
                         selected_task = Some(priority_queue.remove(0));
@@ -786,6 +849,9 @@ impl BackgroundProcessor {
             let sync_engine_clone = sync_engine.clone();
             let account_manager_clone = account_manager.clone();
             let database_clone = database.clone();
+            let cache_manager_clone = cache_manager.clone();
+            let smtp_service_clone = smtp_service.clone();
+            let calendar_manager_clone = calendar_manager.clone();
             let task_timeout = settings.task_timeout;
             
             let handle = tokio::spawn(async move {
@@ -803,7 +869,10 @@ impl BackgroundProcessor {
                         progress_sender_clone,
                         sync_engine_clone,
                         account_manager_clone,
-                        database_clone
+                        database_clone,
+                        cache_manager_clone,
+                        smtp_service_clone,
+                        calendar_manager_clone,
                     )
                 ).await;
 
@@ -874,13 +943,18 @@ impl BackgroundProcessor {
     /// - `AccountSync`: Complete account synchronization across all folders
     /// - `Search`: Search operation across specified folders
     /// - `Indexing`: Message indexing for search functionality
-    /// - `CachePreload`: Preload message cache for faster access
+    /// - `CachePreload`: Fetch the newest message bodies into the message cache
+    /// - `ScheduledSend`: Send a draft whose scheduled time has come
+    /// - `CalendarReminders`: Collect the event reminders that are due
     async fn execute_task(
         task: BackgroundTask,
         progress_sender: Arc<mpsc::UnboundedSender<SyncProgress>>,
         sync_engine: Arc<SyncEngine>,
         account_manager: Arc<ImapAccountManager>,
-        _database: Arc<EmailDatabase>,
+        database: Arc<EmailDatabase>,
+        cache_manager: Arc<CacheManager>,
+        smtp_service: Arc<RwLock<Option<SmtpService>>>,
+        calendar_manager: Arc<RwLock<Option<Arc<CalendarManager>>>>,
     ) -> Result<TaskResultData, String> {
         match task.task_type {
             BackgroundTaskType::FolderRefresh { folder_name } => {
@@ -927,6 +1001,7 @@ impl BackgroundProcessor {
                     bytes_downloaded: 0,
                     started_at: Utc::now(),
                     estimated_completion: Some(Utc::now() + chrono::Duration::seconds(10)),
+                    account_progress: None,
                 };
                 let _ = progress_sender.send(progress);
 
@@ -944,6 +1019,7 @@ impl BackgroundProcessor {
                     bytes_downloaded: 0,
                     started_at: Utc::now(),
                     estimated_completion: Some(Utc::now() + chrono::Duration::minutes(1)),
+                    account_progress: None,
                 };
                 let _ = progress_sender.send(progress);
 
@@ -951,21 +1027,45 @@ impl BackgroundProcessor {
                 Ok(TaskResultData::MessageCount(1000))
             }
             BackgroundTaskType::CachePreload { folder_name, message_count } => {
-                // Send cache preload progress
-                let progress = SyncProgress {
-                    account_id: task.account_id.clone(),
-                    folder_name: folder_name.clone(),
-                    phase: SyncPhase::ProcessingChanges,
-                    messages_processed: 0,
-                    total_messages: message_count as u32,
-                    bytes_downloaded: 0,
-                    started_at: Utc::now(),
-                    estimated_completion: Some(Utc::now() + chrono::Duration::seconds(5)),
+                // Prefetching runs quietly: no progress updates for the overlay
+                Self::preload_folder(
+                    &account_manager,
+                    &database,
+                    &cache_manager,
+                    &task.account_id,
+                    &folder_name,
+                    message_count,
+                ).await
+            }
+            BackgroundTaskType::SnoozeWake => {
+                // Also quiet: nothing to show unless messages come back
+                let operations = crate::email::EmailOperationsService::new(account_manager, database.clone());
+                crate::email::snooze::wake_due_snoozes(&database, &operations, &task.account_id, Utc::now())
+                    .await
+                    .map(TaskResultData::MessageCount)
+                    .map_err(|e| e.to_string())
+            }
+            BackgroundTaskType::ScheduledSend { draft_id, from_address } => {
+                let smtp_service = smtp_service.read().await.clone();
+                crate::smtp::scheduled::send_scheduled_draft(
+                    smtp_service.as_ref(),
+                    &database,
+                    &draft_id,
+                    &from_address,
+                    Utc::now(),
+                )
+                .await
+                .map(|result| TaskResultData::ScheduledSend(result.map(Box::new)))
+            }
+            BackgroundTaskType::CalendarReminders => {
+                let Some(calendar_manager) = calendar_manager.read().await.clone() else {
+                    return Err("Calendar is not initialized".to_string());
                 };
-                let _ = progress_sender.send(progress);
-
-                tokio::time::sleep(Duration::from_millis(200)).await;
-                Ok(TaskResultData::CacheStats(message_count))
+                calendar_manager
+                    .take_due_reminders(Utc::now())
+                    .await
+                    .map(TaskResultData::CalendarReminders)
+                    .map_err(|e| e.to_string())
             }
             BackgroundTaskType::CalendarSync { calendar_id, sync_type } => {
                 // Send calendar sync progress
@@ -978,6 +1078,7 @@ impl BackgroundProcessor {
                     bytes_downloaded: 0,
                     started_at: Utc::now(),
                     estimated_completion: Some(Utc::now() + chrono::Duration::seconds(30)),
+                    account_progress: None,
                 };
                 let _ = progress_sender.send(progress);
 
@@ -996,6 +1097,7 @@ impl BackgroundProcessor {
                                 bytes_downloaded: (i * 512) as u64,
                                 started_at: Utc::now(),
                                 estimated_completion: Some(Utc::now() + chrono::Duration::seconds((30 - i * 3) as i64)),
+                                account_progress: None,
                             };
                             let _ = progress_sender.send(progress);
                         }
@@ -1042,6 +1144,7 @@ impl BackgroundProcessor {
                     bytes_downloaded: 0,
                     started_at: Utc::now(),
                     estimated_completion: Some(Utc::now() + chrono::Duration::seconds(10)),
+                    account_progress: None,
                 };
                 let _ = progress_sender.send(progress);
 
@@ -1066,6 +1169,7 @@ impl BackgroundProcessor {
                     bytes_downloaded: 0,
                     started_at: Utc::now(),
                     estimated_completion: Some(Utc::now() + chrono::Duration::seconds(2)),
+                    account_progress: None,
                 };
                 let _ = progress_sender.send(progress);
 
@@ -1118,6 +1222,181 @@ impl BackgroundProcessor {
         }
     }
 
+    /// Fetch the bodies of a folder's newest messages and cache them
+    ///
+    /// Bodies already stored locally are not fetched again. When the server
+    /// can't be reached the messages are cached with what is stored.
+    async fn preload_folder(
+        account_manager: &Arc<ImapAccountManager>,
+        database: &Arc<EmailDatabase>,
+        cache_manager: &Arc<CacheManager>,
+        account_id: &str,
+        folder_name: &str,
+        message_count: usize,
+    ) -> Result<TaskResultData, String> {
+        let mut messages = database
+            .get_messages(account_id, folder_name, Some(message_count as u32), None)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let missing: Vec<u32> = messages
+            .iter()
+            .filter(|message| !Self::has_body(message) && message.imap_uid != 0)
+            .map(|message| message.imap_uid)
+            .collect();
+
+        if !missing.is_empty() {
+            let fetched = match account_manager.get_jmap_client(account_id).await {
+                Some(jmap) => Self::fetch_jmap_bodies(&jmap, database, account_id, &missing).await,
+                None => Self::fetch_bodies(account_manager, account_id, folder_name, &missing).await,
+            };
+            match fetched {
+                Ok(bodies) => {
+                    let text_only = account_manager.is_plain_text_only(account_id).await;
+                    for message in messages.iter_mut() {
+                        let Some(raw) = bodies.get(&message.imap_uid) else {
+                            continue;
+                        };
+                        let mut parsed = crate::mime::parse_message(raw);
+                        if text_only {
+                            parsed.text = crate::email::plain_text::text_only_body(
+                                parsed.text.as_deref(),
+                                parsed.html.as_deref(),
+                            );
+                            parsed.html = None;
+                        }
+                        if let Err(e) = database
+                            .update_message_body(message.id, parsed.text.clone(), parsed.html.clone())
+                            .await
+                        {
+                            tracing::warn!("Failed to store prefetched body for {}: {}", message.id, e);
+                        }
+                        if !parsed.defects.is_empty() {
+                            if let Err(e) = database.store_message_defects(message.id, &parsed.defects).await {
+                                tracing::warn!("Failed to store MIME defects for {}: {}", message.id, e);
+                            }
+                        }
+                        if crate::email::flowed::detect(raw).is_some() {
+                            if let Err(e) = database.mark_flowed(message.id).await {
+                                tracing::warn!("Failed to record flowed body for {}: {}", message.id, e);
+                            }
+                        }
+                        message.body_text = parsed.text;
+                        message.body_html = parsed.html;
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("Prefetch of {} bodies in {} skipped: {}", missing.len(), folder_name, e);
+                }
+            }
+        }
+
+        let count = messages.len();
+        cache_manager
+            .message_cache()
+            .prefetch_messages(account_id, folder_name, messages)
+            .await;
+
+        Ok(TaskResultData::CacheStats(count))
+    }
+
+    /// Whether a message's body is already stored locally
+    fn has_body(message: &StoredMessage) -> bool {
+        let present = |body: &Option<String>| body.as_deref().is_some_and(|body| !body.trim().is_empty());
+        present(&message.body_text) || present(&message.body_html)
+    }
+
+    /// Fetch raw messages by UID without marking them as read
+    ///
+    /// The account's connection is shared with foreground actions, so it is
+    /// only held for a few messages at a time.
+    async fn fetch_bodies(
+        account_manager: &Arc<ImapAccountManager>,
+        account_id: &str,
+        folder_name: &str,
+        uids: &[u32],
+    ) -> Result<HashMap<u32, String>, String> {
+        const BATCH_SIZE: usize = 5;
+
+        let client = account_manager
+            .get_client(account_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut bodies = HashMap::new();
+
+        // HTML parts of plain-text-only accounts stay on the server
+        if account_manager.is_plain_text_only(account_id).await {
+            for batch in uids.chunks(BATCH_SIZE) {
+                let mut client = client.lock().await;
+                client
+                    .select_folder(folder_name)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                for uid in batch {
+                    let raw = client
+                        .uid_fetch_without_html(*uid)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    bodies.insert(*uid, raw);
+                }
+            }
+            return Ok(bodies);
+        }
+
+        for batch in uids.chunks(BATCH_SIZE) {
+            let uid_set = batch
+                .iter()
+                .map(|uid| uid.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+
+            let fetched = {
+                let mut client = client.lock().await;
+                client.select_folder(folder_name).await.map_err(|e| e.to_string())?;
+                client
+                    .uid_fetch_messages(&uid_set, &["UID", "BODY.PEEK[]"])
+                    .await
+                    .map_err(|e| e.to_string())?
+            };
+
+            bodies.extend(
+                fetched
+                    .into_iter()
+                    .filter_map(|message| Some((message.uid?, message.body?))),
+            );
+        }
+
+        Ok(bodies)
+    }
+
+    /// Download raw messages of a JMAP account by their local UID
+    async fn fetch_jmap_bodies(
+        jmap: &tokio::sync::Mutex<crate::jmap::JmapClient>,
+        database: &EmailDatabase,
+        account_id: &str,
+        uids: &[u32],
+    ) -> Result<HashMap<u32, String>, String> {
+        let mut bodies = HashMap::new();
+        for uid in uids {
+            let Some(email_id) = database
+                .jmap_email_ids(account_id, &[*uid])
+                .await
+                .map_err(|e| e.to_string())?
+                .pop()
+            else {
+                continue;
+            };
+            let raw = jmap
+                .lock()
+                .await
+                .download_email(&email_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            bodies.insert(*uid, raw);
+        }
+        Ok(bodies)
+    }
+
     /// Queue a calendar synchronization task
     /// 
     /// Convenience method for queuing calendar sync operations as background tasks.
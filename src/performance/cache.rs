@@ -61,6 +61,8 @@ pub struct CacheStats {
     pub misses: u64,
     pub evictions: u64,
     pub total_requests: u64,
+    /// Messages put in the cache ahead of being opened
+    pub prefetched: u64,
 }
 
 impl CacheStats {
@@ -168,7 +170,7 @@ impl MessageCache {
         message: StoredMessage,
     ) {
         let cache_key = format!("{}:{}", account_id, folder_name);
-        let uid = message.id.as_u128() as u32;
+        let uid = message.imap_uid;
         
         let cached_message = CachedMessage {
             message,
@@ -235,6 +237,60 @@ impl MessageCache {
         }
     }
 
+    /// Get a message from cache by its database ID, from any folder
+    pub async fn get_message_by_id(&self, id: uuid::Uuid) -> Option<StoredMessage> {
+        let mut found = None;
+        {
+            let mut cache = self.cache.write().unwrap();
+            for folder_cache in cache.values_mut() {
+                let Some((&uid, cached_msg)) = folder_cache
+                    .messages
+                    .iter_mut()
+                    .find(|(_, cached)| cached.message.id == id)
+                else {
+                    continue;
+                };
+
+                if cached_msg.cached_at.elapsed() < MESSAGE_CACHE_TTL {
+                    cached_msg.access_count += 1;
+                    found = Some(cached_msg.message.clone());
+                } else {
+                    // Remove expired message
+                    folder_cache.messages.remove(&uid);
+                }
+                break;
+            }
+        }
+
+        let hit = found.is_some();
+        tokio::spawn({
+            let stats = self.stats.clone();
+            async move {
+                let mut stats = stats.lock().await;
+                if hit {
+                    stats.hits += 1;
+                } else {
+                    stats.misses += 1;
+                }
+                stats.total_requests += 1;
+            }
+        });
+
+        found
+    }
+
+    /// Cache messages fetched ahead of being opened
+    pub async fn prefetch_messages(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        messages: Vec<StoredMessage>,
+    ) {
+        let count = messages.len() as u64;
+        self.cache_messages_batch(account_id, folder_name, messages).await;
+        self.stats.lock().await.prefetched += count;
+    }
+
     /// Clear cache for a specific folder
     pub async fn clear_folder_cache(&self, account_id: &str, folder_name: &str) {
         let cache_key = format!("{}:{}", account_id, folder_name);
@@ -3,6 +3,7 @@
 //! This module provides comprehensive performance optimizations including:
 //! - Async background processing for non-blocking operations
 //! - Intelligent caching systems for messages and folders
+//! - Prefetching of the folders and messages likely to be opened next
 //! - Real-time progress indicators and status updates
 //! - Optimized startup process with deferred loading
 //! - UI responsiveness improvements
@@ -11,6 +12,7 @@ pub mod cache;
 pub mod background_processor;
 pub mod progress_tracker;
 pub mod startup_optimizer;
+pub mod prefetch;
 
 // Re-export main types for easy access
 pub use cache::{MessageCache, FolderCache, CacheManager, CacheSettings, CacheStats};
@@ -25,6 +27,7 @@ pub use startup_optimizer::{
     StartupOptimizer, StartupPhase, StartupProgress, StartupSettings, 
     DeferredTask, DeferredTaskPriority
 };
+pub use prefetch::PrefetchConfig;
 
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -46,10 +49,12 @@ impl PerformanceSystem {
     pub fn new() -> Self {
         let (progress_tx, _) = mpsc::unbounded_channel();
         let (completion_tx, _) = mpsc::unbounded_channel();
+        let background_processor = Arc::new(BackgroundProcessor::new_standalone(progress_tx, completion_tx));
         
         Self {
-            cache_manager: Arc::new(CacheManager::new()),
-            background_processor: Arc::new(BackgroundProcessor::new_standalone(progress_tx, completion_tx)),
+            // Shared so prefetched messages count towards the hit rate
+            cache_manager: background_processor.cache_manager(),
+            background_processor,
             progress_tracker: Arc::new(ProgressTracker::new()),
             startup_optimizer: Arc::new(StartupOptimizer::new()),
         }
@@ -85,6 +90,7 @@ impl PerformanceSystem {
         PerformanceStats {
             message_cache_hit_rate: message_cache_stats.hit_rate(),
             folder_cache_hit_rate: folder_cache_stats.hit_rate(),
+            prefetched_messages: message_cache_stats.prefetched,
             active_background_operations: active_operations,
             startup_complete: startup_progress.current_phase == StartupPhase::Complete,
             total_startup_time: self.startup_optimizer.total_startup_time(),
@@ -103,6 +109,8 @@ impl Default for PerformanceSystem {
 pub struct PerformanceStats {
     pub message_cache_hit_rate: f64,
     pub folder_cache_hit_rate: f64,
+    /// Messages cached ahead of being opened
+    pub prefetched_messages: u64,
     pub active_background_operations: usize,
     pub startup_complete: bool,
     pub total_startup_time: std::time::Duration,
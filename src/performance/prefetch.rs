@@ -0,0 +1,157 @@
+//! Prefetching of the folders and messages likely to be opened next
+//!
+//! Opening a folder queues low-priority `CachePreload` tasks on the
+//! [`BackgroundProcessor`](super::BackgroundProcessor): one for the folder
+//! itself and one for each of the folders most likely to be opened next,
+//! ranked by unread mail and then by their newest message. A task fetches
+//! the missing bodies of the folder's newest messages and keeps them in the
+//! message cache, where the preview looks first. Tasks still queued for the
+//! previous folder are dropped, so walking through the folder tree doesn't
+//! pile up work.
+
+use super::background_processor::{BackgroundTask, BackgroundTaskType, TaskPriority};
+use crate::email::database::FolderActivity;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Prefetch configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrefetchConfig {
+    pub enabled: bool,
+    /// Other folders to preload when a folder is opened
+    pub adjacent_folders: usize,
+    /// Newest messages per folder whose bodies are fetched
+    pub messages_per_folder: usize,
+}
+
+impl Default for PrefetchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            adjacent_folders: 2,
+            messages_per_folder: 10,
+        }
+    }
+}
+
+impl PrefetchConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "prefetch.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Preload tasks for opening `folder_name`, the opened folder first
+    ///
+    /// `activity` is the account's folders as reported by
+    /// `EmailDatabase::get_folder_activity`.
+    pub fn plan(
+        &self,
+        account_id: &str,
+        folder_name: &str,
+        activity: &[FolderActivity],
+    ) -> Vec<BackgroundTask> {
+        if !self.enabled || self.messages_per_folder == 0 {
+            return Vec::new();
+        }
+
+        std::iter::once(folder_name.to_string())
+            .chain(likely_next_folders(folder_name, activity, self.adjacent_folders))
+            .map(|folder| BackgroundTask {
+                id: Uuid::new_v4(),
+                name: format!("Prefetch: {}", folder),
+                priority: TaskPriority::Low,
+                account_id: account_id.to_string(),
+                folder_name: Some(folder.clone()),
+                task_type: BackgroundTaskType::CachePreload {
+                    folder_name: folder,
+                    message_count: self.messages_per_folder,
+                },
+                created_at: Instant::now(),
+                estimated_duration: Some(Duration::from_secs(5)),
+            })
+            .collect()
+    }
+}
+
+/// Folders other than `current` most likely to be opened next
+///
+/// Folders with more unread mail come first; ties go to the folder with the
+/// most recent message.
+pub fn likely_next_folders(current: &str, activity: &[FolderActivity], limit: usize) -> Vec<String> {
+    let mut candidates: Vec<&FolderActivity> = activity
+        .iter()
+        .filter(|folder| folder.folder_name != current)
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.unread_count
+            .cmp(&a.unread_count)
+            .then(b.latest_message.cmp(&a.latest_message))
+    });
+
+    candidates
+        .into_iter()
+        .take(limit)
+        .map(|folder| folder.folder_name.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn folder(name: &str, unread_count: u32, day: u32) -> FolderActivity {
+        FolderActivity {
+            folder_name: name.to_string(),
+            unread_count,
+            latest_message: Some(Utc.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_plan_ranks_unread_then_recent() {
+        let activity = vec![
+            folder("INBOX", 4, 16),
+            folder("Archive", 0, 15),
+            folder("Lists/dev", 3, 10),
+            folder("Lists/announce", 3, 14),
+            folder("Sent", 0, 16),
+        ];
+
+        assert_eq!(
+            likely_next_folders("INBOX", &activity, 3),
+            vec!["Lists/announce", "Lists/dev", "Sent"]
+        );
+
+        let tasks = PrefetchConfig::default().plan("work", "INBOX", &activity);
+        let folders: Vec<_> = tasks
+            .iter()
+            .map(|task| match &task.task_type {
+                BackgroundTaskType::CachePreload { folder_name, message_count } => {
+                    assert_eq!(*message_count, 10);
+                    folder_name.as_str()
+                }
+                other => panic!("unexpected task {:?}", other),
+            })
+            .collect();
+        assert_eq!(folders, vec!["INBOX", "Lists/announce", "Lists/dev"]);
+        assert!(tasks.iter().all(|task| task.priority == TaskPriority::Low));
+
+        let disabled = PrefetchConfig {
+            enabled: false,
+            ..PrefetchConfig::default()
+        };
+        assert!(disabled.plan("work", "INBOX", &activity).is_empty());
+    }
+}
@@ -172,6 +172,9 @@ pub struct PluginInfo {
     pub capabilities: Vec<String>,
     /// Plugin tags for categorization
     pub tags: Vec<String>,
+    /// Module or script to load, relative to the plugin directory in `plugin.json`
+    #[serde(default)]
+    pub entry_point: Option<std::path::PathBuf>,
 }
 
 impl PluginInfo {
@@ -201,6 +204,7 @@ impl PluginInfo {
             config_schema: None,
             capabilities: Vec::new(),
             tags: Vec::new(),
+            entry_point: None,
         }
     }
 
@@ -215,14 +215,31 @@ impl PluginLoadingStrategy for WasmPluginStrategy {
             || plugin_info.capabilities.contains(&"webassembly".to_string())
     }
 
+    #[cfg(feature = "wasm-plugins")]
+    fn load_plugin(&self, plugin_info: &PluginInfo) -> PluginResult<Box<dyn Plugin>> {
+        use super::core::PluginType;
+        use super::wasm::WasmEmailPlugin;
+
+        if plugin_info.plugin_type != PluginType::Email {
+            return Err(PluginError::InitializationFailed(format!(
+                "WASM plugins must be email plugins: {}",
+                plugin_info.name
+            )));
+        }
+        let path = plugin_info.entry_point.as_ref().ok_or_else(|| {
+            PluginError::ConfigurationError(format!(
+                "WASM plugin {} has no entry_point",
+                plugin_info.name
+            ))
+        })?;
+
+        Ok(Box::new(WasmEmailPlugin::from_file(plugin_info.clone(), path)?))
+    }
+
+    #[cfg(not(feature = "wasm-plugins"))]
     fn load_plugin(&self, plugin_info: &PluginInfo) -> PluginResult<Box<dyn Plugin>> {
-        // In a real implementation, this would:
-        // 1. Set up a WebAssembly runtime (wasmtime, wasmer, etc.)
-        // 2. Load the WASM module
-        // 3. Create a wrapper that implements the Plugin trait
-        
         Err(PluginError::Unknown(format!(
-            "WASM plugin loading not yet implemented: {}",
+            "WASM plugin support is not enabled in this build (wasm-plugins feature): {}",
             plugin_info.name
         )))
     }
@@ -10,6 +10,10 @@
 use super::core::{PluginConfig, PluginError, PluginInfo, PluginResult, PluginStatus, PluginType, PluginContext, PluginEnvironment};
 use super::registry::PluginRegistry;
 use super::loader::PluginLoader;
+use super::core::Plugin;
+use super::types::{EmailPlugin, EmailPluginContext, EmailProcessResult};
+use crate::email::StoredMessage;
+use crate::ui::EmailComposeData;
 
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -19,6 +23,21 @@ use tokio::sync::Mutex;
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 
+/// Calls a registered plugin's `on_before_send` through its concrete type
+type BeforeSendHook =
+    fn(&mut dyn Plugin, &mut EmailComposeData, &EmailPluginContext) -> PluginResult<()>;
+
+fn before_send_hook<P: EmailPlugin + 'static>(
+    plugin: &mut dyn Plugin,
+    compose: &mut EmailComposeData,
+    context: &EmailPluginContext,
+) -> PluginResult<()> {
+    match plugin.as_any_mut().downcast_mut::<P>() {
+        Some(plugin) => plugin.on_before_send(compose, context),
+        None => Ok(()),
+    }
+}
+
 /// Plugin manager for coordinating all plugin operations
 pub struct PluginManager {
     /// Plugin registry for tracking loaded plugins
@@ -27,6 +46,10 @@ pub struct PluginManager {
     loader: PluginLoader,
     /// Plugin configurations
     configs: Arc<RwLock<HashMap<Uuid, PluginConfig>>>,
+    /// Compose-time hooks of registered email plugins, in registration order
+    send_hooks: Arc<RwLock<Vec<(Uuid, BeforeSendHook)>>>,
+    /// Plugins found by the last directory scan
+    discovered: Arc<RwLock<HashMap<Uuid, PluginInfo>>>,
     /// Plugin execution contexts
     contexts: Arc<RwLock<HashMap<Uuid, PluginContext>>>,
     /// Plugin health monitoring
@@ -142,6 +165,8 @@ impl PluginManager {
             registry,
             loader,
             configs,
+            send_hooks: Arc::new(RwLock::new(Vec::new())),
+            discovered: Arc::new(RwLock::new(HashMap::new())),
             contexts,
             health_monitor,
             plugin_directories,
@@ -195,6 +220,14 @@ impl PluginManager {
             }
         }
 
+        {
+            let mut discovered = self.discovered.write().unwrap();
+            discovered.clear();
+            for plugin_info in &discovered_plugins {
+                discovered.insert(plugin_info.id, plugin_info.clone());
+            }
+        }
+
         Ok(discovered_plugins)
     }
 
@@ -209,9 +242,12 @@ impl PluginManager {
         let manifest_content = tokio::fs::read_to_string(&manifest_path).await
             .map_err(|e| PluginError::Io(e))?;
 
-        let plugin_info: PluginInfo = serde_json::from_str(&manifest_content)
+        let mut plugin_info: PluginInfo = serde_json::from_str(&manifest_content)
             .map_err(|e| PluginError::Serialization(e))?;
 
+        // Entry points in the manifest are relative to the plugin directory
+        plugin_info.entry_point = plugin_info.entry_point.map(|entry| path.join(entry));
+
         // Validate plugin compatibility
         if !plugin_info.is_compatible(&self.app_version) {
             return Err(PluginError::VersionIncompatible {
@@ -277,9 +313,50 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Register an email plugin built into the application
+    ///
+    /// Unlike plugins loaded from a directory, its `on_before_send` hook takes
+    /// part in sending mail.
+    pub async fn register_email_plugin<P: EmailPlugin + 'static>(&mut self, plugin: P) -> PluginResult<Uuid> {
+        let plugin_info = plugin.info();
+        let plugin_id = plugin_info.id;
+
+        let environment = PluginEnvironment::new(self.base_data_dir.clone(), plugin_id)
+            .map_err(PluginError::Io)?;
+        let config = self.get_or_create_config(plugin_id);
+        let context = PluginContext {
+            plugin_id,
+            config: config.clone(),
+            app_version: self.app_version.clone(),
+            environment,
+        };
+
+        {
+            let mut registry = self.registry.write().unwrap();
+            registry.register_plugin(Box::new(plugin), plugin_info)?;
+        }
+        {
+            let mut configs = self.configs.write().unwrap();
+            configs.insert(plugin_id, config);
+        }
+        {
+            let mut contexts = self.contexts.write().unwrap();
+            contexts.insert(plugin_id, context);
+        }
+
+        self.initialize_plugin(plugin_id).await?;
+
+        {
+            let mut send_hooks = self.send_hooks.write().unwrap();
+            send_hooks.push((plugin_id, before_send_hook::<P>));
+        }
+
+        Ok(plugin_id)
+    }
+
     /// Initialize a loaded plugin
     async fn initialize_plugin(&mut self, plugin_id: Uuid) -> PluginResult<()> {
-        let _config = {
+        let config = {
             let configs = self.configs.read().unwrap();
             configs.get(&plugin_id).cloned()
                 .ok_or_else(|| PluginError::NotFound("Plugin config not found".to_string()))?
@@ -287,9 +364,8 @@ impl PluginManager {
 
         {
             let mut registry = self.registry.write().unwrap();
-            if registry.get_plugin_mut(&plugin_id) {
-                // Plugin initialization would happen here
-                // For now, just set status
+            if let Some(plugin) = registry.plugin_instance_mut(&plugin_id) {
+                plugin.initialize(&config)?;
                 registry.set_plugin_status(plugin_id, PluginStatus::Initialized);
             }
         }
@@ -381,6 +457,11 @@ impl PluginManager {
             contexts.remove(&plugin_id);
         }
 
+        {
+            let mut send_hooks = self.send_hooks.write().unwrap();
+            send_hooks.retain(|(id, _)| *id != plugin_id);
+        }
+
         // Remove from health monitoring
         {
             let mut health_monitor = self.health_monitor.lock().await;
@@ -465,6 +546,47 @@ impl PluginManager {
         health_monitor.get_metrics(plugin_id)
     }
 
+    /// Run an incoming message through the loaded email plugins
+    ///
+    /// Only sandboxed WASM plugins are dispatched here; each result is paired
+    /// with the ID of the plugin that produced it, in priority order.
+    pub fn process_incoming_email(
+        &self,
+        message: &StoredMessage,
+        context: &EmailPluginContext,
+    ) -> Vec<(Uuid, PluginResult<EmailProcessResult>)> {
+        self.incoming_email_hooks().run(message, context)
+    }
+
+    /// Handle for running the email plugins on arriving mail from the sync engine
+    pub fn incoming_email_hooks(&self) -> IncomingEmailHooks {
+        IncomingEmailHooks {
+            registry: self.registry.clone(),
+            configs: self.configs.clone(),
+        }
+    }
+
+    /// Let email plugins rewrite a message before it is sent
+    ///
+    /// Hooks run by plugin priority, then in registration order, each seeing
+    /// the previous one's changes. The first failing hook stops the send.
+    pub fn run_before_send(
+        &self,
+        compose: &mut EmailComposeData,
+        context: &EmailPluginContext,
+    ) -> PluginResult<()> {
+        self.before_send_hooks().run(compose, context)
+    }
+
+    /// Handle for running the compose-time hooks from the send path
+    pub fn before_send_hooks(&self) -> BeforeSendHooks {
+        BeforeSendHooks {
+            registry: self.registry.clone(),
+            configs: self.configs.clone(),
+            hooks: self.send_hooks.clone(),
+        }
+    }
+
     /// Auto-load plugins based on configuration
     async fn auto_load_plugins(&mut self) -> PluginResult<()> {
         let available_plugins = self.scan_plugins().await?;
@@ -552,12 +674,111 @@ impl PluginManager {
 
     /// Find plugin info by ID
     fn find_plugin_info(&self, plugin_id: Uuid) -> PluginResult<PluginInfo> {
+        if let Some(plugin_info) = self.discovered.read().unwrap().get(&plugin_id) {
+            return Ok(plugin_info.clone());
+        }
         let registry = self.registry.read().unwrap();
         registry.get_plugin_info(&plugin_id)
             .ok_or_else(|| PluginError::NotFound(plugin_id.to_string()))
     }
 }
 
+/// The registered `on_before_send` hooks, shareable with the send path
+#[derive(Clone)]
+pub struct BeforeSendHooks {
+    registry: Arc<RwLock<PluginRegistry>>,
+    configs: Arc<RwLock<HashMap<Uuid, PluginConfig>>>,
+    hooks: Arc<RwLock<Vec<(Uuid, BeforeSendHook)>>>,
+}
+
+impl BeforeSendHooks {
+    /// Run the hooks on a message; see [`PluginManager::run_before_send`]
+    pub fn run(&self, compose: &mut EmailComposeData, context: &EmailPluginContext) -> PluginResult<()> {
+        let hooks = self.hooks.read().unwrap().clone();
+        let plugin_ids = active_by_priority(
+            &self.registry,
+            &self.configs,
+            hooks.iter().map(|(id, _)| *id).collect(),
+        );
+
+        let mut registry = self.registry.write().unwrap();
+        for plugin_id in plugin_ids {
+            let Some((_, hook)) = hooks.iter().find(|(id, _)| *id == plugin_id) else {
+                continue;
+            };
+            let name = registry.get_plugin_info(&plugin_id)
+                .map(|info| info.name)
+                .unwrap_or_default();
+            if let Some(plugin) = registry.plugin_instance_mut(&plugin_id) {
+                hook(plugin, compose, context).map_err(|e| {
+                    PluginError::ExecutionFailed(format!("{} rejected the message: {}", name, e))
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The loaded email plugins, shareable with the sync engine
+#[derive(Clone)]
+pub struct IncomingEmailHooks {
+    registry: Arc<RwLock<PluginRegistry>>,
+    configs: Arc<RwLock<HashMap<Uuid, PluginConfig>>>,
+}
+
+impl IncomingEmailHooks {
+    /// Run the plugins on a message; see [`PluginManager::process_incoming_email`]
+    pub fn run(
+        &self,
+        message: &StoredMessage,
+        context: &EmailPluginContext,
+    ) -> Vec<(Uuid, PluginResult<EmailProcessResult>)> {
+        let plugin_ids = {
+            let registry = self.registry.read().unwrap();
+            registry.get_plugin_ids_by_type(PluginType::Email)
+        };
+        let plugin_ids = active_by_priority(&self.registry, &self.configs, plugin_ids);
+
+        #[cfg(feature = "wasm-plugins")]
+        {
+            let mut registry = self.registry.write().unwrap();
+            let mut results = Vec::new();
+            for plugin_id in plugin_ids {
+                let plugin = registry.plugin_instance_mut(&plugin_id)
+                    .and_then(|plugin| plugin.as_any_mut().downcast_mut::<super::wasm::WasmEmailPlugin>());
+                if let Some(plugin) = plugin {
+                    results.push((plugin_id, plugin.process_incoming(message, context)));
+                }
+            }
+            results
+        }
+        #[cfg(not(feature = "wasm-plugins"))]
+        {
+            let _ = (plugin_ids, message, context);
+            Vec::new()
+        }
+    }
+}
+
+/// Initialized or running plugins among `plugin_ids`, highest priority first
+fn active_by_priority(
+    registry: &RwLock<PluginRegistry>,
+    configs: &RwLock<HashMap<Uuid, PluginConfig>>,
+    mut plugin_ids: Vec<Uuid>,
+) -> Vec<Uuid> {
+    {
+        let registry = registry.read().unwrap();
+        plugin_ids.retain(|id| matches!(
+            registry.get_plugin_status(id),
+            Some(PluginStatus::Initialized | PluginStatus::Running)
+        ));
+    }
+    let configs = configs.read().unwrap();
+    plugin_ids.sort_by_key(|id| std::cmp::Reverse(configs.get(id).map_or(0, |c| c.priority)));
+    plugin_ids
+}
+
 impl PluginHealthMonitor {
     fn new() -> Self {
         Self {
@@ -581,4 +802,156 @@ impl PluginHealthMonitor {
         self.metrics.remove(&plugin_id);
         self.last_health_check.remove(&plugin_id);
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::types::EmailCapability;
+    use std::any::Any;
+
+    /// Rewrites the body with a fixed function
+    struct BodyRewriter {
+        info: PluginInfo,
+        rewrite: fn(&str) -> Result<String, String>,
+    }
+
+    impl BodyRewriter {
+        fn new(name: &str, rewrite: fn(&str) -> Result<String, String>) -> Self {
+            let info = PluginInfo::new(
+                name.to_string(),
+                "1.0.0".to_string(),
+                String::new(),
+                String::new(),
+                PluginType::Email,
+                "0.1.0".to_string(),
+            );
+            Self { info, rewrite }
+        }
+    }
+
+    impl Plugin for BodyRewriter {
+        fn info(&self) -> PluginInfo {
+            self.info.clone()
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    impl EmailPlugin for BodyRewriter {
+        async fn process_incoming_email(
+            &mut self,
+            _message: &StoredMessage,
+            _context: &EmailPluginContext,
+        ) -> PluginResult<EmailProcessResult> {
+            Ok(EmailProcessResult::NoChange)
+        }
+
+        async fn process_outgoing_email(
+            &mut self,
+            _message: &StoredMessage,
+            _context: &EmailPluginContext,
+        ) -> PluginResult<EmailProcessResult> {
+            Ok(EmailProcessResult::NoChange)
+        }
+
+        async fn filter_emails(
+            &self,
+            messages: &[StoredMessage],
+            _context: &EmailPluginContext,
+        ) -> PluginResult<Vec<bool>> {
+            Ok(vec![true; messages.len()])
+        }
+
+        fn get_email_capabilities(&self) -> Vec<EmailCapability> {
+            Vec::new()
+        }
+
+        fn on_before_send(
+            &mut self,
+            compose: &mut EmailComposeData,
+            _context: &EmailPluginContext,
+        ) -> PluginResult<()> {
+            compose.body = (self.rewrite)(&compose.body).map_err(PluginError::ExecutionFailed)?;
+            Ok(())
+        }
+    }
+
+    fn context() -> EmailPluginContext {
+        EmailPluginContext {
+            account_id: "work".to_string(),
+            folder_name: String::new(),
+            context_data: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_before_send_hooks_run_in_order() {
+        let data_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = PluginManager::new(
+            Vec::new(),
+            "0.1.0".to_string(),
+            data_dir.path().to_path_buf(),
+        )
+        .unwrap();
+
+        let footer = manager
+            .register_email_plugin(BodyRewriter::new("Footer", |body| {
+                Ok(format!("{}\n-- \n;sig", body))
+            }))
+            .await
+            .unwrap();
+        let snippets = manager
+            .register_email_plugin(BodyRewriter::new("Snippets", |body| {
+                Ok(body
+                    .replace(";thx", "Thanks for getting back to me.")
+                    .replace(";sig", "Ana Lima"))
+            }))
+            .await
+            .unwrap();
+
+        // Registration order: the footer's snippet is expanded too
+        let mut compose = EmailComposeData {
+            body: "Hi Sam,\n;thx".to_string(),
+            ..Default::default()
+        };
+        manager.run_before_send(&mut compose, &context()).unwrap();
+        assert_eq!(
+            compose.body,
+            "Hi Sam,\nThanks for getting back to me.\n-- \nAna Lima"
+        );
+
+        // A higher priority runs first, before the footer is added
+        let mut config = manager.get_plugin_config(snippets).unwrap();
+        config.priority = 1;
+        manager.configs.write().unwrap().insert(snippets, config);
+        let mut compose = EmailComposeData {
+            body: ";thx".to_string(),
+            ..Default::default()
+        };
+        manager
+            .before_send_hooks()
+            .run(&mut compose, &context())
+            .unwrap();
+        assert_eq!(compose.body, "Thanks for getting back to me.\n-- \n;sig");
+
+        manager
+            .register_email_plugin(BodyRewriter::new("Strict", |_| {
+                Err("no subject".to_string())
+            }))
+            .await
+            .unwrap();
+        let error = manager
+            .run_before_send(&mut EmailComposeData::default(), &context())
+            .unwrap_err();
+        assert!(error.to_string().contains("Strict"));
+
+        manager.unload_plugin(footer).await.unwrap();
+        assert_eq!(manager.send_hooks.read().unwrap().len(), 2);
+    }
+}
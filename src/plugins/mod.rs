@@ -45,13 +45,17 @@ pub mod manager;
 pub mod registry;
 pub mod loader;
 pub mod types;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm;
 
 
 // Re-export main types for convenience
 pub use core::{Plugin, PluginInfo, PluginResult, PluginError, PluginType, PluginStatus};
-pub use manager::PluginManager;
+pub use manager::{BeforeSendHooks, IncomingEmailHooks, PluginManager};
 pub use registry::PluginRegistry;
 pub use loader::PluginLoader;
+#[cfg(feature = "wasm-plugins")]
+pub use wasm::WasmEmailPlugin;
 
 // Plugin trait specializations
 pub use types::{
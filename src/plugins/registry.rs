@@ -113,6 +113,11 @@ impl PluginRegistry {
         self.plugins.contains_key(plugin_id)
     }
 
+    /// Get a plugin instance by ID for calls that need mutable access
+    pub fn plugin_instance_mut(&mut self, plugin_id: &Uuid) -> Option<&mut (dyn Plugin + 'static)> {
+        self.plugins.get_mut(plugin_id).map(|p| p.as_mut())
+    }
+
     /// Get a plugin by name
     pub fn get_plugin_by_name(&self, name: &str) -> Option<&dyn Plugin> {
         self.name_to_id
@@ -5,6 +5,7 @@
 
 use super::core::{Plugin, PluginResult};
 use crate::email::StoredMessage;
+use crate::ui::EmailComposeData;
 use crate::calendar::event::Event;
 
 use ratatui::layout::Rect;
@@ -40,6 +41,19 @@ pub trait EmailPlugin: Plugin {
 
     /// Get supported email processing capabilities
     fn get_email_capabilities(&self) -> Vec<EmailCapability>;
+
+    /// Rewrite a composed message just before it is sent
+    ///
+    /// Runs after the user confirms sending and before the message reaches
+    /// SMTP, so changes such as expanded snippets or an added footer go out
+    /// with the message. Returning an error stops the send.
+    fn on_before_send(
+        &mut self,
+        _compose: &mut EmailComposeData,
+        _context: &EmailPluginContext,
+    ) -> PluginResult<()> {
+        Ok(())
+    }
 }
 
 /// Context provided to email plugins
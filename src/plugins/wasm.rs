@@ -0,0 +1,614 @@
+//! Sandboxed WebAssembly email plugins
+//!
+//! A WASM email plugin is a core WebAssembly module (binary `.wasm` or text
+//! `.wat`) that exports its linear memory and one or more entry points:
+//!
+//! - `process_incoming() -> i32`: called for each incoming message
+//! - `process_outgoing() -> i32`: called for each outgoing message (optional)
+//! - `filter() -> i32`: non-zero keeps the message in a filtered view (optional)
+//!
+//! The entry points return 0 on success; any other value is reported as an
+//! execution failure. Plugins can only reach Comunicado through the host
+//! functions in the `comunicado` import module:
+//!
+//! | Import | Description |
+//! |--------|-------------|
+//! | `field_len(name_ptr, name_len) -> i32` | Byte length of a field, -1 if unknown |
+//! | `read_field(name_ptr, name_len, dst_ptr, dst_len) -> i32` | Copy a field into guest memory, returns bytes written |
+//! | `add_tag(ptr, len)` | Add a label to the message |
+//! | `set_flag(ptr, len)` | Set an IMAP flag on the message |
+//! | `move_to(ptr, len)` | Move the message to a folder |
+//! | `delete()` | Delete the message |
+//! | `log(ptr, len)` | Write a line to the Comunicado log |
+//!
+//! Fields are UTF-8: `account`, `folder`, `message_id`, `subject`, `from`,
+//! `from_name`, `to`, `cc`, `date` (RFC 3339), `body`, `flags` and `labels`
+//! (address and flag lists are comma-separated), plus `context.<key>` for the
+//! values in [`EmailPluginContext::context_data`].
+//!
+//! There is no WASI: a plugin has no file system, network or clock access.
+//! Every call gets a fresh instance whose memory and fuel are bounded by the
+//! plugin's [`PluginSettings`](super::core::PluginSettings), so a runaway
+//! filter traps instead of stalling mail processing.
+
+use super::core::{Plugin, PluginConfig, PluginError, PluginInfo, PluginResult, PluginSettings};
+use super::types::{EmailCapability, EmailPlugin, EmailPluginContext, EmailProcessResult};
+use crate::email::StoredMessage;
+
+use std::any::Any;
+use std::path::Path;
+use wasmtime::{Caller, Config, Engine, Extern, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Import module name for the host API
+const HOST_MODULE: &str = "comunicado";
+
+/// Fuel granted per millisecond of the plugin's execution budget
+const FUEL_PER_MS: u64 = 10_000;
+
+/// A WebAssembly module running as an email plugin
+pub struct WasmEmailPlugin {
+    info: PluginInfo,
+    engine: Engine,
+    module: Module,
+    linker: Linker<HostState>,
+    fuel: u64,
+    max_memory: usize,
+}
+
+/// Per-call state visible to the host functions
+struct HostState {
+    message: StoredMessage,
+    context: EmailPluginContext,
+    actions: PluginActions,
+    limits: StoreLimits,
+}
+
+/// Changes a plugin requested while handling one message
+#[derive(Debug, Default)]
+struct PluginActions {
+    tags: Vec<String>,
+    flags: Vec<String>,
+    move_to: Option<String>,
+    delete: bool,
+}
+
+impl WasmEmailPlugin {
+    /// Compile a plugin from a `.wasm` or `.wat` file
+    pub fn from_file(info: PluginInfo, path: &Path) -> PluginResult<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::from_bytes(info, &bytes)
+    }
+
+    /// Compile a plugin from WebAssembly binary or text
+    pub fn from_bytes(info: PluginInfo, bytes: &[u8]) -> PluginResult<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|e| {
+            PluginError::InitializationFailed(format!("Failed to create WASM engine: {}", e))
+        })?;
+        let module = Module::new(&engine, bytes).map_err(|e| {
+            PluginError::InitializationFailed(format!(
+                "Failed to compile WASM plugin {}: {}",
+                info.name, e
+            ))
+        })?;
+        if module.get_export("process_incoming").is_none() {
+            return Err(PluginError::InitializationFailed(format!(
+                "WASM plugin {} does not export process_incoming",
+                info.name
+            )));
+        }
+        let linker = host_linker(&engine)?;
+
+        let mut plugin = Self {
+            info,
+            engine,
+            module,
+            linker,
+            fuel: 0,
+            max_memory: 0,
+        };
+        plugin.apply_settings(&PluginSettings::default());
+        Ok(plugin)
+    }
+
+    /// Run the plugin's incoming-mail hook on a message
+    pub fn process_incoming(
+        &self,
+        message: &StoredMessage,
+        context: &EmailPluginContext,
+    ) -> PluginResult<EmailProcessResult> {
+        self.process("process_incoming", message, context)
+    }
+
+    /// Run the plugin's outgoing-mail hook on a message
+    pub fn process_outgoing(
+        &self,
+        message: &StoredMessage,
+        context: &EmailPluginContext,
+    ) -> PluginResult<EmailProcessResult> {
+        if !self.exports("process_outgoing") {
+            return Ok(EmailProcessResult::NoChange);
+        }
+        self.process("process_outgoing", message, context)
+    }
+
+    /// Ask the plugin which messages to keep; all are kept if it has no filter
+    pub fn filter(
+        &self,
+        messages: &[StoredMessage],
+        context: &EmailPluginContext,
+    ) -> PluginResult<Vec<bool>> {
+        if !self.exports("filter") {
+            return Ok(vec![true; messages.len()]);
+        }
+        messages
+            .iter()
+            .map(|message| {
+                let (keep, _) = self.call("filter", message, context)?;
+                Ok(keep != 0)
+            })
+            .collect()
+    }
+
+    fn process(
+        &self,
+        entry_point: &str,
+        message: &StoredMessage,
+        context: &EmailPluginContext,
+    ) -> PluginResult<EmailProcessResult> {
+        let (status, actions) = self.call(entry_point, message, context)?;
+        if status != 0 {
+            return Err(PluginError::ExecutionFailed(format!(
+                "{} {} returned {}",
+                self.info.name, entry_point, status
+            )));
+        }
+        Ok(actions.into_result(message))
+    }
+
+    /// Instantiate the module for one message and call an entry point
+    fn call(
+        &self,
+        entry_point: &str,
+        message: &StoredMessage,
+        context: &EmailPluginContext,
+    ) -> PluginResult<(i32, PluginActions)> {
+        let state = HostState {
+            message: message.clone(),
+            context: context.clone(),
+            actions: PluginActions::default(),
+            limits: StoreLimitsBuilder::new()
+                .memory_size(self.max_memory)
+                .instances(1)
+                .build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store
+            .set_fuel(self.fuel)
+            .map_err(|e| self.execution_error(entry_point, e))?;
+
+        let instance = self
+            .linker
+            .instantiate(&mut store, &self.module)
+            .map_err(|e| self.execution_error(entry_point, e))?;
+        let func = instance
+            .get_typed_func::<(), i32>(&mut store, entry_point)
+            .map_err(|e| self.execution_error(entry_point, e))?;
+        let status = func
+            .call(&mut store, ())
+            .map_err(|e| self.execution_error(entry_point, e))?;
+
+        Ok((status, store.into_data().actions))
+    }
+
+    fn exports(&self, name: &str) -> bool {
+        self.module.get_export(name).is_some()
+    }
+
+    fn apply_settings(&mut self, settings: &PluginSettings) {
+        self.fuel = settings.max_execution_time.saturating_mul(FUEL_PER_MS);
+        self.max_memory = usize::try_from(settings.max_memory_usage).unwrap_or(usize::MAX);
+    }
+
+    fn execution_error(&self, entry_point: &str, error: impl std::fmt::Display) -> PluginError {
+        PluginError::ExecutionFailed(format!(
+            "{} {} failed: {}",
+            self.info.name, entry_point, error
+        ))
+    }
+}
+
+impl PluginActions {
+    /// Turn the requested changes into a single processing result
+    ///
+    /// Deleting wins over moving, and moving wins over tagging and flagging.
+    fn into_result(self, message: &StoredMessage) -> EmailProcessResult {
+        if self.delete {
+            return EmailProcessResult::Delete;
+        }
+        if let Some(folder) = self.move_to {
+            return EmailProcessResult::Move(folder);
+        }
+        if self.tags.is_empty() {
+            return if self.flags.is_empty() {
+                EmailProcessResult::NoChange
+            } else {
+                EmailProcessResult::SetFlags(self.flags)
+            };
+        }
+
+        let mut modified = message.clone();
+        for tag in self.tags {
+            if !modified.labels.contains(&tag) {
+                modified.labels.push(tag);
+            }
+        }
+        for flag in self.flags {
+            if !modified.flags.contains(&flag) {
+                modified.flags.push(flag);
+            }
+        }
+        if modified.labels == message.labels && modified.flags == message.flags {
+            EmailProcessResult::NoChange
+        } else {
+            EmailProcessResult::Modified(modified)
+        }
+    }
+}
+
+impl HostState {
+    /// Value of a field the plugin asked for, or None if there is no such field
+    fn field(&self, name: &str) -> Option<String> {
+        let message = &self.message;
+        let value = match name {
+            "account" => self.context.account_id.clone(),
+            "folder" => self.context.folder_name.clone(),
+            "message_id" => message.message_id.clone().unwrap_or_default(),
+            "subject" => message.subject.clone(),
+            "from" => message.from_addr.clone(),
+            "from_name" => message.from_name.clone().unwrap_or_default(),
+            "to" => message.to_addrs.join(","),
+            "cc" => message.cc_addrs.join(","),
+            "date" => message.date.to_rfc3339(),
+            "body" => message
+                .body_text
+                .clone()
+                .or_else(|| message.body_html.clone())
+                .unwrap_or_default(),
+            "flags" => message.flags.join(","),
+            "labels" => message.labels.join(","),
+            _ => {
+                let key = name.strip_prefix("context.")?;
+                match self.context.context_data.get(key)? {
+                    serde_json::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                }
+            }
+        };
+        Some(value)
+    }
+}
+
+/// Build the linker exposing the host API; nothing else is importable
+fn host_linker(engine: &Engine) -> PluginResult<Linker<HostState>> {
+    let mut linker = Linker::new(engine);
+    let link_error =
+        |e| PluginError::InitializationFailed(format!("Failed to link WASM host API: {}", e));
+
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "field_len",
+            |mut caller: Caller<'_, HostState>, name_ptr: i32, name_len: i32| {
+                let name = read_string(&mut caller, name_ptr, name_len)?;
+                Ok(caller
+                    .data()
+                    .field(&name)
+                    .map_or(-1, |value| i32::try_from(value.len()).unwrap_or(i32::MAX)))
+            },
+        )
+        .map_err(link_error)?;
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "read_field",
+            |mut caller: Caller<'_, HostState>,
+             name_ptr: i32,
+             name_len: i32,
+             dst_ptr: i32,
+             dst_len: i32| {
+                let name = read_string(&mut caller, name_ptr, name_len)?;
+                let Some(value) = caller.data().field(&name) else {
+                    return Ok(-1);
+                };
+                let count = value.len().min(guest_len(dst_len)?);
+                write_bytes(&mut caller, dst_ptr, &value.as_bytes()[..count])?;
+                Ok(count as i32)
+            },
+        )
+        .map_err(link_error)?;
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "add_tag",
+            |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+                let tag = read_string(&mut caller, ptr, len)?;
+                caller.data_mut().actions.tags.push(tag);
+                Ok(())
+            },
+        )
+        .map_err(link_error)?;
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "set_flag",
+            |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+                let flag = read_string(&mut caller, ptr, len)?;
+                caller.data_mut().actions.flags.push(flag);
+                Ok(())
+            },
+        )
+        .map_err(link_error)?;
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "move_to",
+            |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+                let folder = read_string(&mut caller, ptr, len)?;
+                caller.data_mut().actions.move_to = Some(folder);
+                Ok(())
+            },
+        )
+        .map_err(link_error)?;
+    linker
+        .func_wrap(HOST_MODULE, "delete", |mut caller: Caller<'_, HostState>| {
+            caller.data_mut().actions.delete = true;
+        })
+        .map_err(link_error)?;
+    linker
+        .func_wrap(
+            HOST_MODULE,
+            "log",
+            |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+                let line = read_string(&mut caller, ptr, len)?;
+                tracing::info!("WASM plugin: {}", line);
+                Ok(())
+            },
+        )
+        .map_err(link_error)?;
+
+    Ok(linker)
+}
+
+fn guest_len(len: i32) -> wasmtime::Result<usize> {
+    usize::try_from(len).map_err(|_| wasmtime::Error::msg("negative length"))
+}
+
+/// Read a UTF-8 string out of the guest's exported memory
+fn read_string(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> wasmtime::Result<String> {
+    let memory = guest_memory(caller)?;
+    let start = ptr as u32 as usize;
+    let end = start
+        .checked_add(guest_len(len)?)
+        .ok_or_else(|| wasmtime::Error::msg("string out of bounds"))?;
+    let bytes = memory
+        .data(&*caller)
+        .get(start..end)
+        .ok_or_else(|| wasmtime::Error::msg("string out of bounds"))?;
+    String::from_utf8(bytes.to_vec()).map_err(|_| wasmtime::Error::msg("string is not UTF-8"))
+}
+
+fn write_bytes(caller: &mut Caller<'_, HostState>, ptr: i32, bytes: &[u8]) -> wasmtime::Result<()> {
+    let memory = guest_memory(caller)?;
+    memory
+        .write(&mut *caller, ptr as u32 as usize, bytes)
+        .map_err(|_| wasmtime::Error::msg("buffer out of bounds"))
+}
+
+fn guest_memory(caller: &mut Caller<'_, HostState>) -> wasmtime::Result<wasmtime::Memory> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => Err(wasmtime::Error::msg("plugin does not export its memory")),
+    }
+}
+
+impl Plugin for WasmEmailPlugin {
+    fn info(&self) -> PluginInfo {
+        self.info.clone()
+    }
+
+    fn initialize(&mut self, config: &PluginConfig) -> PluginResult<()> {
+        self.apply_settings(&config.settings);
+        Ok(())
+    }
+
+    fn update_config(&mut self, config: &PluginConfig) -> PluginResult<()> {
+        self.apply_settings(&config.settings);
+        Ok(())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl EmailPlugin for WasmEmailPlugin {
+    async fn process_incoming_email(
+        &mut self,
+        message: &StoredMessage,
+        context: &EmailPluginContext,
+    ) -> PluginResult<EmailProcessResult> {
+        self.process_incoming(message, context)
+    }
+
+    async fn process_outgoing_email(
+        &mut self,
+        message: &StoredMessage,
+        context: &EmailPluginContext,
+    ) -> PluginResult<EmailProcessResult> {
+        self.process_outgoing(message, context)
+    }
+
+    async fn filter_emails(
+        &self,
+        messages: &[StoredMessage],
+        context: &EmailPluginContext,
+    ) -> PluginResult<Vec<bool>> {
+        self.filter(messages, context)
+    }
+
+    fn get_email_capabilities(&self) -> Vec<EmailCapability> {
+        let mut capabilities = vec![EmailCapability::ContentAnalysis];
+        if self.exports("filter") {
+            capabilities.push(EmailCapability::ContentFilter);
+        }
+        capabilities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::core::PluginType;
+    use crate::plugins::PluginManager;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn message(subject: &str, body: &str) -> StoredMessage {
+        let now = Utc::now();
+        StoredMessage {
+            id: Uuid::new_v4(),
+            account_id: "work".to_string(),
+            folder_name: "INBOX".to_string(),
+            imap_uid: 1,
+            message_id: Some("<1@example.com>".to_string()),
+            thread_id: None,
+            in_reply_to: None,
+            references: Vec::new(),
+            subject: subject.to_string(),
+            from_addr: "billing@example.com".to_string(),
+            from_name: None,
+            to_addrs: vec!["me@example.com".to_string()],
+            cc_addrs: Vec::new(),
+            bcc_addrs: Vec::new(),
+            reply_to: None,
+            date: now,
+            body_text: Some(body.to_string()),
+            body_html: None,
+            attachments: Vec::new(),
+            flags: Vec::new(),
+            labels: Vec::new(),
+            size: None,
+            priority: None,
+            created_at: now,
+            updated_at: now,
+            last_synced: now,
+            sync_version: 0,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        }
+    }
+
+    fn context() -> EmailPluginContext {
+        EmailPluginContext {
+            account_id: "work".to_string(),
+            folder_name: "INBOX".to_string(),
+            context_data: HashMap::new(),
+        }
+    }
+
+    fn info() -> PluginInfo {
+        PluginInfo::new(
+            "Test".to_string(),
+            "1.0.0".to_string(),
+            String::new(),
+            String::new(),
+            PluginType::Email,
+            "0.1.0".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_invoice_tagger_runs_on_incoming_mail() {
+        let data_dir = tempfile::TempDir::new().unwrap();
+        let mut manager = PluginManager::new(
+            vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/plugins")],
+            env!("CARGO_PKG_VERSION").to_string(),
+            data_dir.path().to_path_buf(),
+        )
+        .unwrap();
+        let discovered = manager.scan_plugins().await.unwrap();
+        let tagger = discovered
+            .iter()
+            .find(|plugin| plugin.name == "Invoice Tagger")
+            .unwrap();
+        manager.load_plugin(tagger.id).await.unwrap();
+
+        let invoice = message("Your INVOICE for March", "");
+        let results = manager.process_incoming_email(&invoice, &context());
+        assert_eq!(results.len(), 1);
+        match &results[0].1 {
+            Ok(EmailProcessResult::Modified(tagged)) => assert_eq!(tagged.labels, vec!["Invoice"]),
+            other => panic!("expected a tagged message, got {:?}", other),
+        }
+
+        let in_body = message("March", "The invoice is attached.");
+        assert!(matches!(
+            manager.process_incoming_email(&in_body, &context())[0].1,
+            Ok(EmailProcessResult::Modified(_))
+        ));
+
+        let unrelated = message("Lunch?", "Noon at the usual place.");
+        assert!(matches!(
+            manager.process_incoming_email(&unrelated, &context())[0].1,
+            Ok(EmailProcessResult::NoChange)
+        ));
+    }
+
+    #[test]
+    fn test_plugins_are_sandboxed() {
+        // A plugin that asks for anything outside the host API fails to load
+        let wasi = r#"(module
+            (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32)))
+            (memory (export "memory") 1)
+            (func (export "process_incoming") (result i32) i32.const 0))"#;
+        let plugin = WasmEmailPlugin::from_bytes(info(), wasi.as_bytes()).unwrap();
+        assert!(matches!(
+            plugin.process_incoming(&message("", ""), &context()),
+            Err(PluginError::ExecutionFailed(_))
+        ));
+
+        // An endless loop runs out of fuel
+        let spin = r#"(module
+            (memory (export "memory") 1)
+            (func (export "process_incoming") (result i32) (loop (br 0)) i32.const 0))"#;
+        let plugin = WasmEmailPlugin::from_bytes(info(), spin.as_bytes()).unwrap();
+        assert!(plugin.process_incoming(&message("", ""), &context()).is_err());
+
+        // Actions map onto processing results, deletion first
+        let mover = r#"(module
+            (import "comunicado" "move_to" (func $move_to (param i32 i32)))
+            (import "comunicado" "set_flag" (func $set_flag (param i32 i32)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "Receipts\\Seen")
+            (func (export "process_incoming") (result i32)
+                (call $set_flag (i32.const 8) (i32.const 5))
+                (call $move_to (i32.const 0) (i32.const 8))
+                i32.const 0))"#;
+        let plugin = WasmEmailPlugin::from_bytes(info(), mover.as_bytes()).unwrap();
+        match plugin.process_incoming(&message("", ""), &context()).unwrap() {
+            EmailProcessResult::Move(folder) => assert_eq!(folder, "Receipts"),
+            other => panic!("expected a move, got {:?}", other),
+        }
+    }
+}
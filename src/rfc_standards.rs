@@ -4,6 +4,7 @@
 use crate::calendar::{Event, EventStatus, EventPriority};
 use crate::contacts::{Contact, ContactEmail, ContactPhone, ContactSource};
 use calcard::{Parser, Entry};
+use calcard::common::PartialDateTime;
 use calcard::vcard::{VCard, VCardProperty, VCardValue, VCardParameter};
 use calcard::icalendar::ICalendar;
 use chrono::{DateTime, Utc};
@@ -86,17 +87,14 @@ impl RfcStandardsParser {
 
         let mut contact = Contact::new(external_id, source, display_name);
 
-        // Extract name components from N property
-        if let Some(name_value) = get_property_value(&VCardProperty::N) {
-            let name_parts: Vec<&str> = name_value.split(';').collect();
-            if name_parts.len() >= 2 {
-                if !name_parts[0].is_empty() {
-                    contact.last_name = Some(name_parts[0].to_string());
-                }
-                if !name_parts[1].is_empty() {
-                    contact.first_name = Some(name_parts[1].to_string());
-                }
-            }
+        // Extract name components from N property, one value per component
+        if let Some(name) = vcard.entries.iter().find(|entry| entry.name == VCardProperty::N) {
+            let component = |index: usize| match name.values.get(index) {
+                Some(VCardValue::Text(text)) if !text.is_empty() => Some(text.clone()),
+                _ => None,
+            };
+            contact.last_name = component(0);
+            contact.first_name = component(1);
         }
 
         // Extract email addresses
@@ -176,16 +174,43 @@ impl RfcStandardsParser {
         }
 
         // Extract revision timestamp
-        if let Some(rev_value) = get_property_value(&VCardProperty::Rev) {
-            if let Ok(dt) = DateTime::parse_from_rfc3339(&rev_value) {
-                contact.updated_at = dt.with_timezone(&Utc);
+        let rev = vcard.entries.iter().find(|entry| entry.name == VCardProperty::Rev);
+        match rev.and_then(|entry| entry.values.first()) {
+            Some(VCardValue::PartialDateTime(rev)) => {
+                if let Some(dt) = Self::partial_date_time_to_utc(rev) {
+                    contact.updated_at = dt;
+                }
+            }
+            Some(VCardValue::Text(rev)) => {
+                if let Ok(dt) = DateTime::parse_from_rfc3339(rev) {
+                    contact.updated_at = dt.with_timezone(&Utc);
+                }
             }
+            _ => {}
         }
 
         debug!("Successfully parsed vCard for contact: {}", contact.display_name);
         Ok(contact)
     }
 
+    /// A complete vCard timestamp as UTC; `None` when parts are missing
+    fn partial_date_time_to_utc(value: &PartialDateTime) -> Option<DateTime<Utc>> {
+        let local = chrono::NaiveDate::from_ymd_opt(
+            value.year? as i32,
+            value.month? as u32,
+            value.day? as u32,
+        )?
+        .and_hms_opt(
+            value.hour.unwrap_or(0) as u32,
+            value.minute.unwrap_or(0) as u32,
+            value.second.unwrap_or(0) as u32,
+        )?;
+        let offset = value.tz_hour.unwrap_or(0) as i64 * 3600
+            + value.tz_minute.unwrap_or(0) as i64 * 60;
+        let offset = if value.tz_minus { -offset } else { offset };
+        Some((local - chrono::Duration::seconds(offset)).and_utc())
+    }
+
     /// Convert Contact to vCard format (RFC 6350)
     pub fn contact_to_vcard(contact: &Contact) -> Result<String, RfcStandardsError> {
         debug!("Converting contact to RFC 6350 vCard format: {}", contact.display_name);
@@ -197,13 +222,17 @@ impl RfcStandardsParser {
         vcard_lines.push("BEGIN:VCARD".to_string());
         vcard_lines.push("VERSION:4.0".to_string());
         vcard_lines.push(format!("UID:{}", contact.external_id));
-        vcard_lines.push(format!("FN:{}", contact.display_name));
+        vcard_lines.push(format!("FN:{}", Self::escape_text(&contact.display_name)));
         
         // Add name components
         if contact.first_name.is_some() || contact.last_name.is_some() {
             let family_name = contact.last_name.as_deref().unwrap_or("");
             let given_name = contact.first_name.as_deref().unwrap_or("");
-            vcard_lines.push(format!("N:{};{};;;", family_name, given_name));
+            vcard_lines.push(format!(
+                "N:{};{};;;",
+                Self::escape_text(family_name),
+                Self::escape_text(given_name)
+            ));
         }
         
         // Add email addresses
@@ -220,12 +249,12 @@ impl RfcStandardsParser {
         
         // Add organization
         if let Some(company) = &contact.company {
-            vcard_lines.push(format!("ORG:{}", company));
+            vcard_lines.push(format!("ORG:{}", Self::escape_text(company)));
         }
         
         // Add title
         if let Some(title) = &contact.job_title {
-            vcard_lines.push(format!("TITLE:{}", title));
+            vcard_lines.push(format!("TITLE:{}", Self::escape_text(title)));
         }
         
         // Add photo URL
@@ -242,6 +271,15 @@ impl RfcStandardsParser {
         Ok(vcard_string)
     }
 
+    /// Escape a vCard text value (RFC 6350 section 3.4)
+    fn escape_text(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace(',', "\\,")
+            .replace(';', "\\;")
+            .replace('\n', "\\n")
+    }
+
     /// Parse iCalendar data (RFC 5545) into Event objects
     pub fn parse_icalendar_to_event(
         icalendar_data: &str,
@@ -316,6 +354,7 @@ impl RfcStandardsParser {
             organizer: None,
             attendees: Vec::new(),
             recurrence: None,
+            recurrence_id: None,
             reminders: Vec::new(),
             categories: Vec::new(),
             url: None,
@@ -323,6 +362,7 @@ impl RfcStandardsParser {
             updated_at: Utc::now(),
             sequence: 0,
             etag: None,
+            timezone: None,
         };
 
         Ok(event)
@@ -0,0 +1,57 @@
+//! CPU and memory usage for the today panel
+
+use sysinfo::System;
+
+/// A point-in-time reading of system load
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SystemStats {
+    /// Average CPU usage across cores, 0-100
+    pub cpu_usage: f32,
+    pub memory_used_bytes: u64,
+    pub memory_total_bytes: u64,
+}
+
+impl SystemStats {
+    /// Memory in use as a percentage of the total
+    pub fn memory_percent(&self) -> f32 {
+        if self.memory_total_bytes == 0 {
+            0.0
+        } else {
+            self.memory_used_bytes as f32 / self.memory_total_bytes as f32 * 100.0
+        }
+    }
+}
+
+/// Samples CPU and memory usage
+///
+/// CPU usage is measured between two refreshes, so the first snapshot after
+/// creation reads as idle.
+pub struct SystemStatsService {
+    system: System,
+}
+
+impl SystemStatsService {
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+        }
+    }
+
+    /// Refresh and return current usage
+    pub fn snapshot(&mut self) -> SystemStats {
+        self.system.refresh_cpu();
+        self.system.refresh_memory();
+
+        SystemStats {
+            cpu_usage: self.system.global_cpu_info().cpu_usage(),
+            memory_used_bytes: self.system.used_memory(),
+            memory_total_bytes: self.system.total_memory(),
+        }
+    }
+}
+
+impl Default for SystemStatsService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
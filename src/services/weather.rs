@@ -0,0 +1,233 @@
+//! Current weather for the today panel
+//!
+//! Uses the OpenWeatherMap current weather endpoint. Nothing is fetched until
+//! an API key is configured.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use thiserror::Error;
+
+const API_URL: &str = "https://api.openweathermap.org/data/2.5/weather";
+
+/// Weather service errors
+#[derive(Error, Debug)]
+pub enum WeatherError {
+    #[error("No weather API key configured")]
+    MissingApiKey,
+
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Weather API error: {0}")]
+    Api(String),
+
+    #[error("Invalid weather response: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+pub type WeatherResult<T> = Result<T, WeatherError>;
+
+/// Units temperatures are reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureUnits {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+impl TemperatureUnits {
+    fn api_value(&self) -> &'static str {
+        match self {
+            TemperatureUnits::Metric => "metric",
+            TemperatureUnits::Imperial => "imperial",
+        }
+    }
+
+    /// Suffix shown after temperatures
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TemperatureUnits::Metric => "°C",
+            TemperatureUnits::Imperial => "°F",
+        }
+    }
+}
+
+/// Current conditions at a location
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeatherReport {
+    pub location: String,
+    /// Condition group such as `Rain` or `Clouds`
+    pub condition: String,
+    pub description: String,
+    pub temperature: f64,
+    pub feels_like: f64,
+    pub humidity: u8,
+    pub units: TemperatureUnits,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl WeatherReport {
+    /// Icon for the condition group
+    pub fn icon(&self) -> &'static str {
+        match self.condition.as_str() {
+            "Clear" => "☀",
+            "Clouds" => "☁",
+            "Rain" | "Drizzle" => "🌧",
+            "Thunderstorm" => "⛈",
+            "Snow" => "❄",
+            _ => "🌫",
+        }
+    }
+
+    /// One-line summary, e.g. `☁ 12°C broken clouds`
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {:.0}{} {}",
+            self.icon(),
+            self.temperature,
+            self.units.symbol(),
+            self.description
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiResponse {
+    name: String,
+    weather: Vec<ApiCondition>,
+    main: ApiMain,
+}
+
+#[derive(Deserialize)]
+struct ApiCondition {
+    main: String,
+    description: String,
+}
+
+#[derive(Deserialize)]
+struct ApiMain {
+    temp: f64,
+    feels_like: f64,
+    humidity: u8,
+}
+
+/// Fetches current weather from OpenWeatherMap
+#[derive(Debug, Clone)]
+pub struct WeatherService {
+    client: reqwest::Client,
+    api_key: Option<String>,
+    location: String,
+    units: TemperatureUnits,
+}
+
+impl WeatherService {
+    /// Create a service without an API key; fetching fails until one is set
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            api_key: None,
+            location: String::new(),
+            units: TemperatureUnits::Metric,
+        }
+    }
+
+    /// Create a service with an OpenWeatherMap API key
+    pub fn with_api_key(api_key: String) -> Self {
+        let mut service = Self::new();
+        service.api_key = Some(api_key).filter(|key| !key.trim().is_empty());
+        service
+    }
+
+    /// Set the location, e.g. `Oslo` or `London,GB`
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = location.into();
+        self
+    }
+
+    /// Set the units temperatures are reported in
+    pub fn with_units(mut self, units: TemperatureUnits) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Check if an API key is configured
+    pub fn has_api_key(&self) -> bool {
+        self.api_key.is_some()
+    }
+
+    /// Fetch current conditions for the configured location
+    pub async fn fetch_current(&self) -> WeatherResult<WeatherReport> {
+        let api_key = self.api_key.as_ref().ok_or(WeatherError::MissingApiKey)?;
+
+        let response = self
+            .client
+            .get(API_URL)
+            .query(&[
+                ("q", self.location.as_str()),
+                ("appid", api_key.as_str()),
+                ("units", self.units.api_value()),
+            ])
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(WeatherError::Api(format!("{}: {}", status, body)));
+        }
+
+        parse_report(&body, self.units)
+    }
+}
+
+impl Default for WeatherService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_report(body: &str, units: TemperatureUnits) -> WeatherResult<WeatherReport> {
+    let response: ApiResponse = serde_json::from_str(body)?;
+    let condition = response
+        .weather
+        .into_iter()
+        .next()
+        .ok_or_else(|| WeatherError::Api("response has no conditions".to_string()))?;
+
+    Ok(WeatherReport {
+        location: response.name,
+        condition: condition.main,
+        description: condition.description,
+        temperature: response.main.temp,
+        feels_like: response.main.feels_like,
+        humidity: response.main.humidity,
+        units,
+        fetched_at: Utc::now(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_report() {
+        let body = r#"{
+            "weather": [{"id": 803, "main": "Clouds", "description": "broken clouds", "icon": "04d"}],
+            "main": {"temp": 11.6, "feels_like": 10.9, "temp_min": 10.0, "temp_max": 13.0, "humidity": 81},
+            "name": "Oslo"
+        }"#;
+
+        let report = parse_report(body, TemperatureUnits::Metric).unwrap();
+        assert_eq!(report.location, "Oslo");
+        assert_eq!(report.humidity, 81);
+        assert_eq!(report.summary(), "☁ 12°C broken clouds");
+
+        assert!(parse_report(r#"{"name": "Oslo"}"#, TemperatureUnits::Metric).is_err());
+    }
+}
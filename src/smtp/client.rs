@@ -166,6 +166,7 @@ impl SmtpClient {
         // Get recipients for result tracking
         let envelope = message.envelope();
         let to_addresses: Vec<String> = envelope.to().iter().map(|addr| addr.to_string()).collect();
+        let raw_message = message.formatted();
 
         // Send the message
         let result = self.transport.send(message).await;
@@ -177,6 +178,7 @@ impl SmtpClient {
                     accepted_recipients: to_addresses,
                     rejected_recipients: Vec::new(),
                     sent_at: chrono::Utc::now(),
+                    raw_message,
                 };
 
                 tracing::info!(
@@ -1,9 +1,15 @@
+use crate::email::{crypto, MailPriority};
 use crate::smtp::{SmtpError, SmtpResult};
+use crate::ui::compose_attachments::ComposeAttachment;
 use crate::ui::EmailComposeData;
 use lettre::{
-    message::{header::ContentType, Mailbox, MultiPart, SinglePart},
+    message::{
+        header::{ContentDisposition, ContentTransferEncoding, ContentType, HeaderName, HeaderValue},
+        Mailbox, MultiPart, SinglePart,
+    },
     Address, Message,
 };
+use std::path::Path;
 use std::str::FromStr;
 
 /// Email message builder with MIME support
@@ -18,7 +24,44 @@ pub struct MessageBuilder {
     message_id: Option<String>,
     in_reply_to: Option<String>,
     references: Option<String>,
+    priority: MailPriority,
     user_agent: String,
+    flowed_line_width: Option<usize>,
+    icalendar: Option<String>,
+    encrypt: bool,
+    sign: bool,
+    attachments: Vec<ComposeAttachment>,
+}
+
+/// Body of a message, before it is attached to the headers
+enum Body {
+    Single(SinglePart),
+    Multi(MultiPart),
+}
+
+impl Body {
+    /// The body as a MIME entity, as it appears inside a multipart
+    ///
+    /// The line break after the entity belongs to the next boundary, so it
+    /// isn't part of what gets signed or encrypted.
+    fn entity(&self) -> Vec<u8> {
+        let mut formatted = match self {
+            Body::Single(part) => part.formatted(),
+            Body::Multi(part) => part.formatted(),
+        };
+        if formatted.ends_with(b"\r\n") {
+            formatted.truncate(formatted.len() - 2);
+        }
+        formatted
+    }
+
+    fn attach(self, builder: lettre::message::MessageBuilder) -> SmtpResult<Message> {
+        let message = match self {
+            Body::Single(part) => builder.singlepart(part),
+            Body::Multi(part) => builder.multipart(part),
+        };
+        message.map_err(SmtpError::MessageBuildError)
+    }
 }
 
 impl MessageBuilder {
@@ -35,7 +78,13 @@ impl MessageBuilder {
             message_id: None,
             in_reply_to: None,
             references: None,
+            priority: MailPriority::Normal,
             user_agent: "Comunicado/0.1.0".to_string(),
+            flowed_line_width: None,
+            icalendar: None,
+            encrypt: false,
+            sign: false,
+            attachments: Vec::new(),
         }
     }
 
@@ -127,14 +176,124 @@ impl MessageBuilder {
         self
     }
 
+    /// Set the priority headers
+    pub fn priority(mut self, priority: MailPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Set user agent
     pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
         self.user_agent = user_agent.into();
         self
     }
 
+    /// Send the plain text body as `format=flowed`, soft-wrapped at `line_width`
+    pub fn format_flowed(mut self, line_width: usize) -> Self {
+        self.flowed_line_width = Some(line_width);
+        self
+    }
+
+    /// Encrypt the body to the recipients' PGP keys; takes effect with
+    /// [`build_pgp`](Self::build_pgp)
+    pub fn encrypt(mut self, encrypt: bool) -> Self {
+        self.encrypt = encrypt;
+        self
+    }
+
+    /// Sign the body with the sender's PGP key; takes effect with
+    /// [`build_pgp`](Self::build_pgp)
+    pub fn sign(mut self, sign: bool) -> Self {
+        self.sign = sign;
+        self
+    }
+
+    /// Send an iCalendar object, such as an iTIP reply, as a `text/calendar`
+    /// alternative to the text body
+    pub fn icalendar<S: Into<String>>(mut self, data: S) -> Self {
+        self.icalendar = Some(data.into());
+        self
+    }
+
+    /// Attach a file; it is read when the message is built
+    pub fn attachment(mut self, attachment: ComposeAttachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
     /// Build the message
     pub fn build(self) -> SmtpResult<Message> {
+        let (message_builder, body) = self.into_parts()?;
+        body.attach(message_builder)
+    }
+
+    /// Build the message as PGP/MIME (RFC 3156), using the keys in `keyring`
+    /// or gpg's default keyring
+    ///
+    /// Encrypted messages become `multipart/encrypted`, signed inside the
+    /// encryption when signing too; signed-only messages become
+    /// `multipart/signed`. The body is also encrypted to the sender when
+    /// their key is in the keyring, so the copy filed in Sent stays readable.
+    /// Bcc recipients aren't named in the encrypted data.
+    pub async fn build_pgp(self, keyring: Option<&Path>) -> SmtpResult<Message> {
+        if !self.encrypt && !self.sign {
+            return self.build();
+        }
+        let (encrypt, sign) = (self.encrypt, self.sign);
+        let sender = self.from.as_ref().map(|from| from.email.to_string());
+        let recipients: Vec<String> = self
+            .to
+            .iter()
+            .chain(&self.cc)
+            .map(|mailbox| mailbox.email.to_string())
+            .collect();
+        let mut hidden_recipients: Vec<String> =
+            self.bcc.iter().map(|mailbox| mailbox.email.to_string()).collect();
+
+        let (message_builder, body) = self.into_parts()?;
+        let sender = sender.unwrap_or_default();
+        let entity = body.entity();
+
+        let body = if encrypt {
+            if crypto::missing_keys(std::slice::from_ref(&sender), keyring)
+                .await?
+                .is_empty()
+            {
+                hidden_recipients.push(sender.clone());
+            }
+            let signer = sign.then_some(sender.as_str());
+            let armored =
+                crypto::encrypt(&entity, &recipients, &hidden_recipients, signer, keyring).await?;
+
+            let version = SinglePart::builder()
+                .header(content_type("application/pgp-encrypted")?)
+                .body("Version: 1".to_string());
+            let encrypted = SinglePart::builder()
+                .header(content_type("application/octet-stream; name=\"encrypted.asc\"")?)
+                .header(ContentDisposition::inline_with_name("encrypted.asc"))
+                .body(armored);
+            MultiPart::encrypted("application/pgp-encrypted".to_string())
+                .singlepart(version)
+                .singlepart(encrypted)
+        } else {
+            let signature = crypto::sign(&entity, &sender, keyring).await?;
+            let signature_part = SinglePart::builder()
+                .header(content_type("application/pgp-signature; name=\"signature.asc\"")?)
+                .header(ContentDisposition::attachment("signature.asc"))
+                .body(signature.armored);
+            let signed = MultiPart::signed("application/pgp-signature".to_string(), signature.micalg);
+            match body {
+                Body::Single(part) => signed.singlepart(part),
+                Body::Multi(part) => signed.multipart(part),
+            }
+            .singlepart(signature_part)
+        };
+
+        Body::Multi(body).attach(message_builder)
+    }
+
+    /// Validated headers and the body, not yet put together
+    fn into_parts(self) -> SmtpResult<(lettre::message::MessageBuilder, Body)> {
         // Validate required fields
         let from = self
             .from
@@ -181,33 +340,90 @@ impl MessageBuilder {
             message_builder = message_builder.references(references);
         }
 
+        for (name, value) in self.priority.headers() {
+            message_builder = message_builder.raw_header(HeaderValue::new(
+                HeaderName::new_from_ascii_str(name),
+                value.to_string(),
+            ));
+        }
+
         // Add User-Agent header (skip for now as lettre requires specific header types)
 
         // Add Date header
         message_builder = message_builder.date_now();
 
         // Create message body
-        let message = if let Some(html_body) = self.body_html {
-            // Multipart message with both text and HTML
-            let text_part = SinglePart::builder()
-                .header(ContentType::TEXT_PLAIN)
-                .body(self.body_text);
-
-            let html_part = SinglePart::builder()
-                .header(ContentType::TEXT_HTML)
-                .body(html_body);
-
-            let multipart = MultiPart::alternative()
-                .singlepart(text_part)
-                .singlepart(html_part);
-
-            message_builder.multipart(multipart)
+        let (text_type, body_text) = match self.flowed_line_width {
+            Some(width) => (
+                content_type("text/plain; charset=utf-8; format=flowed")?,
+                crate::email::flowed::encode(&self.body_text, width),
+            ),
+            None => (ContentType::TEXT_PLAIN, self.body_text),
+        };
+        let mut text_part = SinglePart::builder().header(text_type);
+        if self.sign && !self.encrypt {
+            // Mail servers may strip trailing spaces or rewrap long lines in
+            // plain text, which would break the signature
+            text_part = text_part.header(ContentTransferEncoding::QuotedPrintable);
+        }
+        let text_part = text_part.body(body_text);
+
+        let body = if self.body_html.is_some() || self.icalendar.is_some() {
+            // Multipart message with text, HTML and calendar alternatives
+            let mut alternative = MultiPart::alternative().singlepart(text_part);
+            if let Some(html_body) = self.body_html {
+                alternative = alternative.singlepart(
+                    SinglePart::builder()
+                        .header(ContentType::TEXT_HTML)
+                        .body(html_body),
+                );
+            }
+            if let Some(data) = self.icalendar {
+                // Calendar clients act on the part's method parameter
+                let method = data
+                    .lines()
+                    .find_map(|line| line.trim_end().strip_prefix("METHOD:"))
+                    .unwrap_or("PUBLISH")
+                    .to_string();
+                alternative = alternative.singlepart(
+                    SinglePart::builder()
+                        .header(content_type(&format!(
+                            "text/calendar; charset=utf-8; method={}",
+                            method
+                        ))?)
+                        .body(data),
+                );
+            }
+            Body::Multi(alternative)
         } else {
             // Plain text only
-            message_builder.body(self.body_text)
+            Body::Single(text_part)
+        };
+
+        if self.attachments.is_empty() {
+            return Ok((message_builder, body));
+        }
+
+        // Files follow the body in a multipart/mixed
+        let mut mixed = match body {
+            Body::Single(part) => MultiPart::mixed().singlepart(part),
+            Body::Multi(part) => MultiPart::mixed().multipart(part),
         };
+        for attachment in self.attachments {
+            let data = attachment.read().map_err(|e| {
+                SmtpError::MessageFormatError(format!(
+                    "Can't read attachment {}: {}",
+                    attachment.path.display(),
+                    e
+                ))
+            })?;
+            mixed = mixed.singlepart(
+                lettre::message::Attachment::new(attachment.filename)
+                    .body(data, content_type(&attachment.content_type)?),
+            );
+        }
 
-        message.map_err(|e| SmtpError::MessageBuildError(e))
+        Ok((message_builder, Body::Multi(mixed)))
     }
 }
 
@@ -230,6 +446,17 @@ pub struct EmailMessage {
     pub message_id: Option<String>,
     pub in_reply_to: Option<String>,
     pub references: Option<String>,
+    pub priority: MailPriority,
+    /// Send the text body as `format=flowed` wrapped at this width
+    pub flowed_line_width: Option<usize>,
+    /// iCalendar object sent along as a `text/calendar` part
+    pub icalendar: Option<String>,
+    /// Encrypt to the recipients' PGP keys when sent
+    pub encrypt: bool,
+    /// Sign with the sender's PGP key when sent
+    pub sign: bool,
+    /// Files sent along, read when the message is built
+    pub attachments: Vec<ComposeAttachment>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -247,6 +474,12 @@ impl EmailMessage {
             message_id: None,
             in_reply_to: None,
             references: None,
+            priority: MailPriority::Normal,
+            flowed_line_width: None,
+            icalendar: None,
+            encrypt: false,
+            sign: false,
+            attachments: Vec::new(),
             created_at: chrono::Utc::now(),
         }
     }
@@ -273,8 +506,14 @@ impl EmailMessage {
             body_text: data.body.clone(),
             body_html: None,
             message_id: None,
-            in_reply_to: None,
-            references: None,
+            in_reply_to: data.in_reply_to.clone(),
+            references: data.references.clone(),
+            priority: data.priority,
+            flowed_line_width: None,
+            icalendar: None,
+            encrypt: data.encrypt,
+            sign: data.sign,
+            attachments: data.attachments.clone(),
             created_at: chrono::Utc::now(),
         })
     }
@@ -305,6 +544,12 @@ impl EmailMessage {
             message_id: None,
             in_reply_to: original.message_id.clone(),
             references: None,
+            priority: MailPriority::Normal,
+            flowed_line_width: None,
+            icalendar: None,
+            encrypt: false,
+            sign: false,
+            attachments: Vec::new(),
             created_at: chrono::Utc::now(),
         };
 
@@ -345,12 +590,32 @@ impl EmailMessage {
             message_id: None,
             in_reply_to: None,
             references: None,
+            priority: MailPriority::Normal,
+            flowed_line_width: None,
+            icalendar: None,
+            encrypt: false,
+            sign: false,
+            attachments: Vec::new(),
             created_at: chrono::Utc::now(),
         }
     }
 
     /// Convert to lettre Message
     pub fn to_lettre_message(&self) -> SmtpResult<Message> {
+        self.builder()?.build()
+    }
+
+    /// Convert to lettre Message, signed and encrypted as chosen with PGP
+    /// keys from `keyring`
+    pub async fn to_pgp_message(&self, keyring: Option<&Path>) -> SmtpResult<Message> {
+        self.builder()?
+            .encrypt(self.encrypt)
+            .sign(self.sign)
+            .build_pgp(keyring)
+            .await
+    }
+
+    fn builder(&self) -> SmtpResult<MessageBuilder> {
         let mut builder = MessageBuilder::new()
             .from_str(&self.from)?
             .subject(&self.subject)
@@ -386,7 +651,19 @@ impl EmailMessage {
             builder = builder.references(refs);
         }
 
-        builder.build()
+        if let Some(width) = self.flowed_line_width {
+            builder = builder.format_flowed(width);
+        }
+
+        if let Some(ref data) = self.icalendar {
+            builder = builder.icalendar(data);
+        }
+
+        for attachment in &self.attachments {
+            builder = builder.attachment(attachment.clone());
+        }
+
+        Ok(builder.priority(self.priority))
     }
 
     /// Generate a unique message ID
@@ -463,27 +740,32 @@ impl EmailMessage {
             message_id: None,
             in_reply_to: None,
             references: None,
+            priority: MailPriority::Normal,
+            flowed_line_width: None,
+            icalendar: None,
+            encrypt: false,
+            sign: false,
+            attachments: Vec::new(),
             created_at: chrono::Utc::now(),
         };
 
         // Generate a unique message ID
         message.generate_message_id("comunicado.local");
-
-        // For now, include the iCalendar in the body - later we can add proper attachment support
-        message.body_text = format!(
-            "{}\n\n--- iCalendar Reply ---\n{}",
-            message.body_text, reply_ical
-        );
+        message.icalendar = Some(reply_ical);
 
         Ok(message)
     }
 
     /// Create iCalendar REPLY content for RSVP
+    ///
+    /// Repeats what RFC 5546 asks a reply to carry over from the request,
+    /// such as the organizer, sequence and times, along with the time zones
+    /// those times use, so the organizer's client can match it up.
     fn create_ical_reply(
         meeting_uid: &str,
         attendee_email: &str,
         response: &str, // "ACCEPTED", "DECLINED", "TENTATIVE"
-        _original_request_ical: &str,
+        original_request_ical: &str,
     ) -> SmtpResult<String> {
         let now = chrono::Utc::now();
         let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
@@ -496,19 +778,89 @@ impl EmailMessage {
             _ => "NEEDS-ACTION",
         };
 
-        // Create basic iCalendar REPLY
+        let mut time_zones = String::new();
+        let mut event_properties = String::new();
+        let mut attendee_name = None;
+        let mut in_time_zone = false;
+        let mut in_event = false;
+        let mut nested = 0;
+        for line in crate::calendar::event::unfold_icalendar_lines(original_request_ical) {
+            if line == "BEGIN:VTIMEZONE" {
+                in_time_zone = true;
+            }
+            if in_time_zone {
+                time_zones.push_str(&line);
+                time_zones.push_str("\r\n");
+                in_time_zone = line != "END:VTIMEZONE";
+                continue;
+            }
+            if line == "BEGIN:VEVENT" && !in_event {
+                in_event = true;
+                continue;
+            }
+            if line == "END:VEVENT" {
+                // Only the first event; later ones change single occurrences
+                break;
+            }
+            if !in_event {
+                continue;
+            }
+            // Alarms and other components inside the event aren't replied to
+            if line.starts_with("BEGIN:") {
+                nested += 1;
+                continue;
+            }
+            if nested > 0 {
+                if line.starts_with("END:") {
+                    nested -= 1;
+                }
+                continue;
+            }
+
+            let name = line.split([';', ':']).next().unwrap_or_default();
+            match name.to_ascii_uppercase().as_str() {
+                "ORGANIZER" | "DTSTART" | "DTEND" | "DURATION" | "SUMMARY" | "SEQUENCE"
+                | "RECURRENCE-ID" => {
+                    event_properties.push_str(&line);
+                    event_properties.push_str("\r\n");
+                }
+                "ATTENDEE" => {
+                    if let Some(attendee) =
+                        crate::calendar::EventAttendee::from_icalendar_property(&line)
+                    {
+                        if attendee.email.eq_ignore_ascii_case(attendee_email) {
+                            attendee_name = attendee.name;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let attendee = match attendee_name {
+            Some(name) => format!(
+                "ATTENDEE;PARTSTAT={};CN=\"{}\":mailto:{}",
+                partstat,
+                name.replace('"', ""),
+                attendee_email
+            ),
+            None => format!("ATTENDEE;PARTSTAT={}:mailto:{}", partstat, attendee_email),
+        };
+
         let ical_reply = format!(
             "BEGIN:VCALENDAR\r\n\
             VERSION:2.0\r\n\
             PRODID:-//Comunicado//Calendar//EN\r\n\
             METHOD:REPLY\r\n\
+            {}\
             BEGIN:VEVENT\r\n\
             UID:{}\r\n\
             DTSTAMP:{}\r\n\
-            ATTENDEE;PARTSTAT={}:mailto:{}\r\n\
+            {}\
+            {}\r\n\
             END:VEVENT\r\n\
             END:VCALENDAR\r\n",
-            meeting_uid, timestamp, partstat, attendee_email
+            time_zones, meeting_uid, timestamp, event_properties, attendee
         );
 
         Ok(ical_reply)
@@ -545,6 +897,10 @@ impl EmailMessage {
     }
 }
 
+fn content_type(value: &str) -> SmtpResult<ContentType> {
+    ContentType::parse(value).map_err(|e| SmtpError::MessageFormatError(e.to_string()))
+}
+
 /// Parse a single email address or mailbox
 fn parse_mailbox(address: &str) -> SmtpResult<Mailbox> {
     let trimmed = address.trim();
@@ -655,6 +1011,50 @@ mod tests {
         assert!(message.headers().get_raw("To").is_some());
     }
 
+    #[test]
+    fn test_attachments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "attached text").unwrap();
+        let attachment = ComposeAttachment::from_path(&path).unwrap();
+
+        let builder = || {
+            MessageBuilder::new()
+                .from_str("sender@example.com")
+                .unwrap()
+                .to_str("recipient@example.com")
+                .unwrap()
+                .subject("Notes")
+                .body_text("See attached")
+                .attachment(attachment.clone())
+        };
+        let formatted = String::from_utf8(builder().build().unwrap().formatted()).unwrap();
+        assert!(formatted.contains("multipart/mixed"));
+        assert!(formatted.contains("Content-Disposition: attachment; filename=\"notes.txt\""));
+        assert!(formatted.contains("See attached"));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(builder().build(), Err(SmtpError::MessageFormatError(_))));
+    }
+
+    #[test]
+    fn test_priority_headers() {
+        let message = MessageBuilder::new()
+            .from_str("sender@example.com")
+            .unwrap()
+            .to_str("recipient@example.com")
+            .unwrap()
+            .subject("Outage")
+            .body_text("The site is down")
+            .priority(MailPriority::High)
+            .build()
+            .unwrap();
+
+        assert_eq!(message.headers().get_raw("X-Priority"), Some("1 (Highest)"));
+        assert_eq!(message.headers().get_raw("Importance"), Some("High"));
+        assert_eq!(message.headers().get_raw("X-MSMail-Priority"), Some("High"));
+    }
+
     #[test]
     fn test_email_message_validation() {
         let mut msg = EmailMessage::new(
@@ -675,4 +1075,57 @@ mod tests {
         msg.to.clear();
         assert!(msg.validate().is_err());
     }
+
+    #[test]
+    fn test_rsvp_reply_calendar_part() {
+        let request = "BEGIN:VCALENDAR\r\n\
+METHOD:REQUEST\r\n\
+BEGIN:VTIMEZONE\r\n\
+TZID:Europe/Oslo\r\n\
+END:VTIMEZONE\r\n\
+BEGIN:VEVENT\r\n\
+UID:review@example.com\r\n\
+SUMMARY:Design review\r\n\
+DTSTART;TZID=Europe/Oslo:20250310T140000\r\n\
+DTEND;TZID=Europe/Oslo:20250310T150000\r\n\
+SEQUENCE:2\r\n\
+ORGANIZER;CN=Bob:mailto:bob@example.com\r\n\
+ATTENDEE;CN=Alice;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:alice@example.com\r\n\
+ATTENDEE;CN=Me;PARTSTAT=NEEDS-ACTION;RSVP=\r\n \
+TRUE:mailto:me@example.com\r\n\
+BEGIN:VALARM\r\n\
+TRIGGER:-PT10M\r\n\
+END:VALARM\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let message = EmailMessage::create_rsvp_response(
+            "me@example.com".to_string(),
+            "bob@example.com".to_string(),
+            "Design review",
+            "review@example.com",
+            "accepted",
+            None,
+            request,
+        )
+        .unwrap();
+        assert_eq!(message.subject, "Accepted: Design review");
+        assert!(!message.body_text.contains("BEGIN:VCALENDAR"));
+
+        let reply = message.icalendar.as_deref().unwrap();
+        assert!(reply.contains("METHOD:REPLY\r\n"));
+        assert!(reply.contains("BEGIN:VTIMEZONE\r\nTZID:Europe/Oslo\r\nEND:VTIMEZONE\r\n"));
+        assert!(reply.contains("ORGANIZER;CN=Bob:mailto:bob@example.com\r\n"));
+        assert!(reply.contains("SEQUENCE:2\r\n"));
+        assert!(reply.contains("DTSTART;TZID=Europe/Oslo:20250310T140000\r\n"));
+        assert!(reply.contains("ATTENDEE;PARTSTAT=ACCEPTED;CN=\"Me\":mailto:me@example.com\r\n"));
+        // Only the one replying is listed, and alarms stay behind
+        assert!(!reply.contains("alice@example.com"));
+        assert!(!reply.contains("TRIGGER"));
+
+        let formatted =
+            String::from_utf8(message.to_lettre_message().unwrap().formatted()).unwrap();
+        assert!(formatted.contains("multipart/alternative"));
+        assert!(formatted.contains("text/calendar; charset=utf-8; method=REPLY"));
+    }
 }
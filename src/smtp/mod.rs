@@ -1,6 +1,8 @@
 pub mod client;
 pub mod message;
+pub mod outbox;
 pub mod providers;
+pub mod scheduled;
 pub mod service;
 
 pub use client::{SmtpClient, SmtpConfig};
@@ -42,6 +44,9 @@ pub enum SmtpError {
 
     #[error("Message build error: {0}")]
     MessageBuildError(#[from] lettre::error::Error),
+
+    #[error("PGP error: {0}")]
+    CryptoError(#[from] crate::email::CryptoError),
 }
 
 pub type SmtpResult<T> = Result<T, SmtpError>;
@@ -84,6 +89,8 @@ pub struct SendResult {
     pub accepted_recipients: Vec<String>,
     pub rejected_recipients: Vec<String>,
     pub sent_at: chrono::DateTime<chrono::Utc>,
+    /// The message as sent, for filing a copy in the Sent folder
+    pub raw_message: Vec<u8>,
 }
 
 impl SendResult {
@@ -0,0 +1,111 @@
+//! Holding sent mail back for a moment so the send can be undone
+//!
+//! After Send the message waits in the outbox for a configurable grace
+//! period before it is handed to SMTP. Pressing `u` in that time takes it
+//! back into compose unchanged. Only one message waits at a time: sending
+//! another, or quitting, sends the waiting one straight away.
+
+use crate::ui::EmailComposeData;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Undo-send configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UndoSendConfig {
+    /// Seconds a sent message waits before it goes out; 0 sends at once
+    pub grace_secs: u64,
+}
+
+impl Default for UndoSendConfig {
+    fn default() -> Self {
+        Self { grace_secs: 5 }
+    }
+}
+
+impl UndoSendConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "undo_send.toml";
+
+    /// How long to hold a message back, if undo-send is on
+    pub fn grace_period(&self) -> Option<Duration> {
+        (self.grace_secs > 0).then(|| Duration::from_secs(self.grace_secs))
+    }
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// A message waiting out its grace period
+#[derive(Debug, Clone)]
+pub struct PendingSend {
+    pub account_id: String,
+    pub from_address: String,
+    pub compose_data: EmailComposeData,
+    /// Draft the message was written in, discarded once it is sent
+    pub draft_id: Option<String>,
+    /// Toast counting down the grace period
+    pub toast_id: String,
+    send_at: Instant,
+}
+
+impl PendingSend {
+    pub fn new(
+        account_id: String,
+        from_address: String,
+        compose_data: EmailComposeData,
+        draft_id: Option<String>,
+        toast_id: String,
+        grace: Duration,
+    ) -> Self {
+        Self {
+            account_id,
+            from_address,
+            compose_data,
+            draft_id,
+            toast_id,
+            send_at: Instant::now() + grace,
+        }
+    }
+
+    /// Whether the grace period is over and the message should go out
+    pub fn is_due(&self) -> bool {
+        Instant::now() >= self.send_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grace_period() {
+        assert_eq!(UndoSendConfig::default().grace_period(), Some(Duration::from_secs(5)));
+        let config: UndoSendConfig = toml::from_str("grace_secs = 0").unwrap();
+        assert_eq!(config.grace_period(), None);
+    }
+
+    #[test]
+    fn test_pending_send_is_due() {
+        let pending = |grace| {
+            PendingSend::new(
+                "work".to_string(),
+                "me@example.com".to_string(),
+                EmailComposeData::default(),
+                None,
+                String::new(),
+                grace,
+            )
+        };
+        assert!(!pending(Duration::from_secs(30)).is_due());
+        assert!(pending(Duration::ZERO).is_due());
+    }
+}
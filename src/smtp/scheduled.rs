@@ -0,0 +1,85 @@
+//! Sending drafts that were scheduled for later
+//!
+//! A scheduled send is a draft with a `scheduled_at` time. Once that time has
+//! passed, a background task sends it and deletes the draft. A send that
+//! fails is never dropped: the error is kept on the draft and it is tried
+//! again after a delay that doubles with each failure, up to an hour.
+
+use crate::email::EmailDatabase;
+use crate::smtp::{SendResult, SmtpService};
+use chrono::{DateTime, Duration, Utc};
+
+/// Delay after the first failure
+const FIRST_RETRY_MINUTES: i64 = 1;
+/// Longest delay between attempts
+const MAX_RETRY_MINUTES: i64 = 60;
+
+/// How long to wait before the next attempt after `failures` failed sends
+pub fn retry_delay(failures: u32) -> Duration {
+    let doublings = failures.saturating_sub(1).min(16);
+    Duration::minutes((FIRST_RETRY_MINUTES << doublings).min(MAX_RETRY_MINUTES))
+}
+
+/// Send a due scheduled draft and delete it once it is out
+///
+/// Returns `Ok(None)` when the draft was cancelled or already sent in the
+/// meantime. On failure the attempt is recorded with its retry time and the
+/// error returned.
+pub async fn send_scheduled_draft(
+    smtp_service: Option<&SmtpService>,
+    database: &EmailDatabase,
+    draft_id: &str,
+    from_address: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<SendResult>, String> {
+    let draft = match database.load_draft(draft_id).await {
+        Ok(Some(draft)) if draft.scheduled_at.is_some() => draft,
+        Ok(_) => return Ok(None),
+        Err(e) => return Err(format!("Failed to load scheduled send: {}", e)),
+    };
+
+    let sent = match smtp_service {
+        Some(service) => service
+            .send_email(&draft.account_id, from_address, &draft.to_compose_data())
+            .await
+            .map_err(|e| e.to_string()),
+        None => Err("SMTP service not ready".to_string()),
+    };
+
+    match sent {
+        Ok(result) => {
+            if let Err(e) = database.delete_draft(draft_id).await {
+                tracing::warn!("Sent scheduled message {} but could not delete it: {}", draft_id, e);
+            }
+            Ok(Some(result))
+        }
+        Err(error) => {
+            let next_attempt = now + retry_delay(draft.send_attempts + 1);
+            if let Err(e) = database
+                .record_scheduled_send_failure(draft_id, &error, next_attempt)
+                .await
+            {
+                tracing::warn!("Failed to record scheduled send failure: {}", e);
+            }
+            Err(format!(
+                "{}; retrying at {}",
+                error,
+                next_attempt.with_timezone(&chrono::Local).format("%H:%M")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), Duration::minutes(1));
+        assert_eq!(retry_delay(2), Duration::minutes(2));
+        assert_eq!(retry_delay(4), Duration::minutes(8));
+        assert_eq!(retry_delay(7), Duration::minutes(60));
+        assert_eq!(retry_delay(u32::MAX), Duration::minutes(60));
+    }
+}
@@ -1,10 +1,14 @@
+use crate::contacts::{split_recipients, SentRecipientCollector};
 use crate::email::{database::StoredDraft, EmailDatabase};
 use crate::oauth2::TokenManager;
+use crate::plugins::{BeforeSendHooks, EmailPluginContext};
 use crate::smtp::{
     EmailMessage, SendResult, SmtpClient, SmtpError, SmtpProviderRegistry, SmtpResult,
 };
 use crate::ui::EmailComposeData;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -15,6 +19,10 @@ pub struct SmtpService {
     clients: Arc<RwLock<HashMap<String, SmtpClient>>>,
     token_manager: Arc<TokenManager>,
     database: Arc<EmailDatabase>,
+    flowed_line_width: Option<usize>,
+    pgp_keyring: Option<PathBuf>,
+    contact_collector: Option<Arc<SentRecipientCollector>>,
+    before_send_hooks: Option<BeforeSendHooks>,
 }
 
 impl SmtpService {
@@ -25,9 +33,45 @@ impl SmtpService {
             clients: Arc::new(RwLock::new(HashMap::new())),
             token_manager,
             database,
+            flowed_line_width: None,
+            pgp_keyring: None,
+            contact_collector: None,
+            before_send_hooks: None,
         }
     }
 
+    /// Send composed mail as `format=flowed` wrapped at this width, or as
+    /// plain fixed lines with `None`
+    pub fn set_flowed_line_width(&mut self, line_width: Option<usize>) {
+        self.flowed_line_width = line_width;
+    }
+
+    /// GnuPG home to take PGP keys from, or gpg's default with `None`
+    pub fn set_pgp_keyring(&mut self, keyring: Option<PathBuf>) {
+        self.pgp_keyring = keyring;
+    }
+
+    /// Add the recipients of sent mail to the address book through `collector`
+    pub fn set_contact_collector(&mut self, collector: Option<Arc<SentRecipientCollector>>) {
+        self.contact_collector = collector;
+    }
+
+    /// Let email plugins rewrite messages through these hooks before they are sent
+    pub fn set_before_send_hooks(&mut self, hooks: Option<BeforeSendHooks>) {
+        self.before_send_hooks = hooks;
+    }
+
+    /// Recipients of `compose_data` without a PGP key to encrypt to
+    pub async fn missing_pgp_keys(&self, compose_data: &EmailComposeData) -> SmtpResult<Vec<String>> {
+        Ok(
+            crate::email::crypto::missing_keys(
+                &compose_data.get_all_recipients(),
+                self.pgp_keyring.as_deref(),
+            )
+            .await?,
+        )
+    }
+
     /// Initialize SMTP client for an account
     pub async fn initialize_account(
         &self,
@@ -99,27 +143,82 @@ impl SmtpService {
         from_address: &str,
         compose_data: &EmailComposeData,
     ) -> SmtpResult<SendResult> {
+        let compose_data = self.run_send_hooks(account_id, from_address, compose_data)?;
+        let compose_data = compose_data.as_ref();
+
         // Create email message from compose data
-        let email_message =
+        let mut email_message =
             EmailMessage::from_compose_data(compose_data, from_address.to_string())?;
+        email_message.flowed_line_width = self.flowed_line_width;
 
         // Validate the message
         email_message.validate()?;
 
+        // Convert to lettre message, signing and encrypting it as chosen.
+        // gpg may wait for a passphrase, so this happens before taking the lock
+        let message = email_message
+            .to_pgp_message(self.pgp_keyring.as_deref())
+            .await?;
+
         // Get the SMTP client for this account
         let clients = self.clients.read().await;
         let client = clients.get(account_id).ok_or_else(|| {
             SmtpError::InvalidConfig(format!("No SMTP client found for account: {}", account_id))
         })?;
 
-        // Convert to lettre message
-        let message = email_message.to_lettre_message()?;
-
         // Send the email with automatic token refresh
         let mut client_clone = client.clone();
         drop(clients); // Release the read lock
 
-        client_clone.send_with_refresh(message, account_id).await
+        let result = client_clone.send_with_refresh(message, account_id).await?;
+        if result.is_success() {
+            // The compose fields keep the display names that were typed
+            let recipients = [&compose_data.to, &compose_data.cc, &compose_data.bcc]
+                .into_iter()
+                .flat_map(|field| split_recipients(field))
+                .collect();
+            self.collect_recipients(from_address, recipients);
+        }
+        Ok(result)
+    }
+
+    /// Compose data as rewritten by the plugins' `on_before_send` hooks
+    fn run_send_hooks<'a>(
+        &self,
+        account_id: &str,
+        from_address: &str,
+        compose_data: &'a EmailComposeData,
+    ) -> SmtpResult<Cow<'a, EmailComposeData>> {
+        let Some(ref hooks) = self.before_send_hooks else {
+            return Ok(Cow::Borrowed(compose_data));
+        };
+
+        let mut compose_data = compose_data.clone();
+        let context = EmailPluginContext {
+            account_id: account_id.to_string(),
+            folder_name: String::new(),
+            context_data: HashMap::from([(
+                "from".to_string(),
+                serde_json::Value::String(from_address.to_string()),
+            )]),
+        };
+        hooks
+            .run(&mut compose_data, &context)
+            .map_err(|e| SmtpError::MessageFormatError(e.to_string()))?;
+        Ok(Cow::Owned(compose_data))
+    }
+
+    /// The message compose data would send, formatted for IMAP APPEND, e.g.
+    /// to file a draft on the server
+    pub fn format_message(
+        &self,
+        from_address: &str,
+        compose_data: &EmailComposeData,
+    ) -> SmtpResult<Vec<u8>> {
+        let mut email_message =
+            EmailMessage::from_compose_data(compose_data, from_address.to_string())?;
+        email_message.flowed_line_width = self.flowed_line_width;
+        Ok(email_message.to_lettre_message()?.formatted())
     }
 
     /// Send a pre-built email message
@@ -131,20 +230,43 @@ impl SmtpService {
         // Validate the message
         email_message.validate()?;
 
+        // Convert to lettre message, signing and encrypting it as chosen.
+        // gpg may wait for a passphrase, so this happens before taking the lock
+        let message = email_message
+            .to_pgp_message(self.pgp_keyring.as_deref())
+            .await?;
+
         // Get the SMTP client for this account
         let clients = self.clients.read().await;
         let client = clients.get(account_id).ok_or_else(|| {
             SmtpError::InvalidConfig(format!("No SMTP client found for account: {}", account_id))
         })?;
 
-        // Convert to lettre message
-        let message = email_message.to_lettre_message()?;
-
         // Send the email with automatic token refresh
         let mut client_clone = client.clone();
         drop(clients); // Release the read lock
 
-        client_clone.send_with_refresh(message, account_id).await
+        let result = client_clone.send_with_refresh(message, account_id).await?;
+        if result.is_success() {
+            self.collect_recipients(&email_message.from, email_message.all_recipients());
+        }
+        Ok(result)
+    }
+
+    /// Add sent-to addresses that aren't contacts yet, in the background so
+    /// sending never waits on the contacts database
+    fn collect_recipients(&self, from_address: &str, recipients: Vec<String>) {
+        let Some(collector) = self.contact_collector.clone() else {
+            return;
+        };
+        let from_address = from_address.to_string();
+        tokio::spawn(async move {
+            match collector.collect(&from_address, &recipients).await {
+                Ok(0) => {}
+                Ok(added) => tracing::info!("Added {} recipients to contacts", added),
+                Err(e) => tracing::warn!("Failed to add recipients to contacts: {}", e),
+            }
+        });
     }
 
     /// Send a reply email
@@ -561,6 +683,12 @@ mod tests {
             bcc: String::new(),
             subject: "Test Subject".to_string(),
             body: "Test body content".to_string(),
+            in_reply_to: None,
+            references: None,
+            priority: crate::email::MailPriority::Normal,
+            encrypt: false,
+            sign: false,
+            attachments: Vec::new(),
         };
 
         // Test saving a draft
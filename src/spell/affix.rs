@@ -0,0 +1,410 @@
+//! Hunspell affix rules
+//!
+//! A Hunspell `.dic` entry such as `walk/DGS` only lists the stem; the
+//! `.aff` file says what the `D`, `G` and `S` flags add to it. This module
+//! parses the prefix and suffix rules from the `.aff` file and expands each
+//! dictionary entry into every word form they allow, so "walked", "walking"
+//! and "walks" are accepted along with "walk". Compounding and
+//! morphological fields are not supported.
+
+use std::collections::{HashMap, HashSet};
+
+/// How flags are written in the `.dic` and `.aff` files (the `FLAG` option)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagType {
+    /// One character per flag (the default)
+    Char,
+    /// Two characters per flag (`FLAG long`)
+    Long,
+    /// Comma-separated numbers (`FLAG num`)
+    Numeric,
+}
+
+/// One character position in an affix condition
+#[derive(Debug, Clone)]
+enum ConditionChar {
+    Any,
+    Literal(char),
+    Set { chars: Vec<char>, negated: bool },
+}
+
+impl ConditionChar {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            ConditionChar::Any => true,
+            ConditionChar::Literal(literal) => *literal == c,
+            ConditionChar::Set { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+/// A single `PFX`/`SFX` rule line
+#[derive(Debug, Clone)]
+struct AffixRule {
+    strip: String,
+    add: String,
+    continuation: Vec<String>,
+    condition: Vec<ConditionChar>,
+}
+
+/// All rules sharing one flag
+#[derive(Debug, Clone)]
+struct AffixGroup {
+    cross_product: bool,
+    rules: Vec<AffixRule>,
+}
+
+/// A word form produced by a suffix, remembered so prefixes can be added
+/// to it when both allow cross products
+struct Suffixed {
+    word: String,
+    cross_product: bool,
+}
+
+/// Prefix and suffix rules parsed from a Hunspell `.aff` file
+#[derive(Debug, Clone)]
+pub struct AffixRules {
+    flag_type: FlagType,
+    prefixes: HashMap<String, AffixGroup>,
+    suffixes: HashMap<String, AffixGroup>,
+    aliases: Vec<Vec<String>>,
+    need_affix: Option<String>,
+    forbidden: Option<String>,
+    only_in_compound: Option<String>,
+}
+
+impl Default for AffixRules {
+    fn default() -> Self {
+        Self {
+            flag_type: FlagType::Char,
+            prefixes: HashMap::new(),
+            suffixes: HashMap::new(),
+            aliases: Vec::new(),
+            need_affix: None,
+            forbidden: None,
+            only_in_compound: None,
+        }
+    }
+}
+
+impl AffixRules {
+    /// Parse the contents of an `.aff` file. Options this module doesn't
+    /// use, and malformed lines, are skipped
+    pub fn parse(content: &str) -> Self {
+        let mut rules = Self::default();
+
+        for line in content.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let Some(&keyword) = fields.first() else {
+                continue;
+            };
+
+            match keyword {
+                "FLAG" => {
+                    rules.flag_type = match fields.get(1).copied() {
+                        Some("long") => FlagType::Long,
+                        Some("num") => FlagType::Numeric,
+                        // UTF-8 flags are single characters, as is the default
+                        _ => FlagType::Char,
+                    };
+                }
+                "AF" if fields.len() == 2 && fields[1].parse::<usize>().is_err() => {
+                    let flags = rules.parse_flags(fields[1]);
+                    rules.aliases.push(flags);
+                }
+                "NEEDAFFIX" | "PSEUDOROOT" => {
+                    rules.need_affix = fields.get(1).map(|f| f.to_string())
+                }
+                "FORBIDDENWORD" => rules.forbidden = fields.get(1).map(|f| f.to_string()),
+                "ONLYINCOMPOUND" => rules.only_in_compound = fields.get(1).map(|f| f.to_string()),
+                "PFX" | "SFX" => rules.parse_affix_line(keyword == "PFX", &fields),
+                _ => {}
+            }
+        }
+
+        rules
+    }
+
+    /// Handle a `PFX`/`SFX` header (`SFX S Y 4`) or rule
+    /// (`SFX S y ies [^aeiou]y`) line
+    fn parse_affix_line(&mut self, is_prefix: bool, fields: &[&str]) {
+        let Some(&flag) = fields.get(1) else {
+            return;
+        };
+
+        // Header lines have a Y/N cross product field and a rule count
+        if fields.len() == 4 && matches!(fields[2], "Y" | "N") && fields[3].parse::<usize>().is_ok()
+        {
+            let groups = if is_prefix {
+                &mut self.prefixes
+            } else {
+                &mut self.suffixes
+            };
+            groups.insert(
+                flag.to_string(),
+                AffixGroup {
+                    cross_product: fields[2] == "Y",
+                    rules: Vec::new(),
+                },
+            );
+            return;
+        }
+
+        if fields.len() < 4 {
+            return;
+        }
+
+        let strip = match fields[2] {
+            "0" => String::new(),
+            strip => strip.to_string(),
+        };
+        let (add, continuation) = match fields[3].split_once('/') {
+            Some((add, flags)) => (add, self.resolve_flags(flags)),
+            None => (fields[3], Vec::new()),
+        };
+        let add = match add {
+            "0" => String::new(),
+            add => add.to_string(),
+        };
+        let condition = parse_condition(fields.get(4).copied().unwrap_or("."));
+
+        let groups = if is_prefix {
+            &mut self.prefixes
+        } else {
+            &mut self.suffixes
+        };
+        if let Some(group) = groups.get_mut(flag) {
+            group.rules.push(AffixRule {
+                strip,
+                add,
+                continuation,
+                condition,
+            });
+        }
+    }
+
+    /// Split a flag string according to the `FLAG` type
+    fn parse_flags(&self, flags: &str) -> Vec<String> {
+        match self.flag_type {
+            FlagType::Char => flags.chars().map(String::from).collect(),
+            FlagType::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                chars.chunks(2).map(|pair| pair.iter().collect()).collect()
+            }
+            FlagType::Numeric => flags
+                .split(',')
+                .map(|flag| flag.trim().to_string())
+                .filter(|flag| !flag.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Flags of a `.dic` entry or affix, looking up `AF` aliases, which
+    /// replace flags with their 1-based index
+    fn resolve_flags(&self, flags: &str) -> Vec<String> {
+        if !self.aliases.is_empty() {
+            if let Ok(index) = flags.parse::<usize>() {
+                return index
+                    .checked_sub(1)
+                    .and_then(|index| self.aliases.get(index))
+                    .cloned()
+                    .unwrap_or_default();
+            }
+        }
+        self.parse_flags(flags)
+    }
+
+    /// Every word form of a `.dic` entry (`stem` or `stem/FLAGS`). Forbidden
+    /// entries yield nothing
+    pub fn expand_entry(&self, entry: &str) -> Vec<String> {
+        let entry = entry.split(['\t', ' ']).next().unwrap_or(entry);
+        let (stem, flags) = split_entry(entry);
+        if stem.is_empty() {
+            return Vec::new();
+        }
+        let flags = flags
+            .map(|flags| self.resolve_flags(flags))
+            .unwrap_or_default();
+        self.expand(&stem, &flags)
+    }
+
+    fn expand(&self, stem: &str, flags: &[String]) -> Vec<String> {
+        let has_flag = |flag: &Option<String>| flag.as_ref().is_some_and(|f| flags.contains(f));
+        if has_flag(&self.forbidden) || has_flag(&self.only_in_compound) {
+            return Vec::new();
+        }
+
+        let mut words = Vec::new();
+        if !has_flag(&self.need_affix) {
+            words.push(stem.to_string());
+        }
+
+        // Suffixes, plus one further suffix from continuation flags
+        let mut suffixed = Vec::new();
+        for flag in flags {
+            let Some(group) = self.suffixes.get(flag) else {
+                continue;
+            };
+            for rule in &group.rules {
+                let Some(word) = apply_suffix(stem, rule) else {
+                    continue;
+                };
+                for continuation in &rule.continuation {
+                    if let Some(next) = self.suffixes.get(continuation) {
+                        for next_rule in &next.rules {
+                            if let Some(word) = apply_suffix(&word, next_rule) {
+                                words.push(word);
+                            }
+                        }
+                    }
+                }
+                if !self.needs_affix(&rule.continuation) {
+                    words.push(word.clone());
+                }
+                suffixed.push(Suffixed {
+                    word,
+                    cross_product: group.cross_product,
+                });
+            }
+        }
+
+        // Prefixes, on the stem and on suffixed forms that allow it
+        for flag in flags {
+            let Some(group) = self.prefixes.get(flag) else {
+                continue;
+            };
+            for rule in &group.rules {
+                if let Some(word) = apply_prefix(stem, rule) {
+                    if !self.needs_affix(&rule.continuation) {
+                        words.push(word);
+                    }
+                }
+                if !group.cross_product {
+                    continue;
+                }
+                for form in suffixed.iter().filter(|form| form.cross_product) {
+                    if let Some(word) = apply_prefix(&form.word, rule) {
+                        words.push(word);
+                    }
+                }
+            }
+        }
+
+        words
+    }
+
+    fn needs_affix(&self, flags: &[String]) -> bool {
+        self.need_affix
+            .as_ref()
+            .is_some_and(|flag| flags.contains(flag))
+    }
+}
+
+/// Expand a whole `.dic` file (the first line is the entry count) into a
+/// set of lowercase words
+pub fn expand_dictionary(dic: &str, rules: &AffixRules) -> HashSet<String> {
+    dic.lines()
+        .skip(1)
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .flat_map(|line| rules.expand_entry(line))
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Read dictionary file contents as UTF-8, falling back to ISO-8859-1,
+/// which many older Hunspell dictionaries use
+pub fn decode(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&byte| byte as char).collect(),
+    }
+}
+
+/// Split `stem/FLAGS` on the first slash that isn't escaped as `\/`
+fn split_entry(entry: &str) -> (String, Option<&str>) {
+    let mut stem = String::new();
+    let mut chars = entry.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' if chars.peek().is_some_and(|&(_, next)| next == '/') => {
+                stem.push('/');
+                chars.next();
+            }
+            '/' => return (stem, Some(&entry[index + 1..])),
+            c => stem.push(c),
+        }
+    }
+    (stem, None)
+}
+
+/// Parse a condition such as `[^aeiou]y` or `.`
+fn parse_condition(condition: &str) -> Vec<ConditionChar> {
+    if condition == "." {
+        return Vec::new();
+    }
+
+    let mut parsed = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => parsed.push(ConditionChar::Any),
+            '[' => {
+                let mut set: Vec<char> = chars.by_ref().take_while(|&c| c != ']').collect();
+                let negated = set.first() == Some(&'^');
+                if negated {
+                    set.remove(0);
+                }
+                parsed.push(ConditionChar::Set {
+                    chars: set,
+                    negated,
+                });
+            }
+            c => parsed.push(ConditionChar::Literal(c)),
+        }
+    }
+    parsed
+}
+
+fn apply_suffix(word: &str, rule: &AffixRule) -> Option<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() < rule.condition.len() || !word.ends_with(&rule.strip) {
+        return None;
+    }
+    let tail = &chars[chars.len() - rule.condition.len()..];
+    if !rule
+        .condition
+        .iter()
+        .zip(tail)
+        .all(|(condition, &c)| condition.matches(c))
+    {
+        return None;
+    }
+
+    let stem = &word[..word.len() - rule.strip.len()];
+    if stem.is_empty() {
+        return None;
+    }
+    Some(format!("{}{}", stem, rule.add))
+}
+
+fn apply_prefix(word: &str, rule: &AffixRule) -> Option<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() < rule.condition.len() || !word.starts_with(&rule.strip) {
+        return None;
+    }
+    if !rule
+        .condition
+        .iter()
+        .zip(&chars)
+        .all(|(condition, &c)| condition.matches(c))
+    {
+        return None;
+    }
+
+    let stem = &word[rule.strip.len()..];
+    if stem.is_empty() {
+        return None;
+    }
+    Some(format!("{}{}", rule.add, stem))
+}
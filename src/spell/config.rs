@@ -1,7 +1,5 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use tokio::fs;
 
 /// Spell check configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +22,10 @@ pub struct SpellCheckConfig {
     /// Maximum number of suggestions to show
     pub max_suggestions: usize,
 
+    /// Also suggest words that sound alike, e.g. "fone" for "phone"
+    #[serde(default = "default_phonetic_suggestions")]
+    pub phonetic_suggestions: bool,
+
     /// Ignore words in ALL CAPS
     pub ignore_all_caps: bool,
 
@@ -53,6 +55,10 @@ pub enum UnderlineStyle {
     Custom { color: String, style: String },
 }
 
+fn default_phonetic_suggestions() -> bool {
+    true
+}
+
 impl Default for SpellCheckConfig {
     fn default() -> Self {
         Self {
@@ -62,6 +68,7 @@ impl Default for SpellCheckConfig {
             check_as_you_type: true,
             underline_style: UnderlineStyle::RedWavy,
             max_suggestions: 5,
+            phonetic_suggestions: default_phonetic_suggestions(),
             ignore_all_caps: true,
             ignore_words_with_numbers: true,
             min_word_length: 2,
@@ -166,43 +173,17 @@ impl Default for SpellCheckConfig {
 }
 
 impl SpellCheckConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "spell_check.toml";
+
     /// Load configuration from file
     pub async fn load() -> Result<Self> {
-        let config_path = Self::config_file_path()?;
-
-        if config_path.exists() {
-            let content = fs::read_to_string(&config_path).await?;
-            let config: SpellCheckConfig = toml::from_str(&content)?;
-            Ok(config)
-        } else {
-            // Create default config and save it
-            let config = Self::default();
-            config.save().await?;
-            Ok(config)
-        }
+        crate::config::load_or_default(Self::CONFIG_FILE).await
     }
 
     /// Save configuration to file
     pub async fn save(&self) -> Result<()> {
-        let config_path = Self::config_file_path()?;
-
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent).await?;
-        }
-
-        let content = toml::to_string_pretty(self)?;
-        fs::write(&config_path, content).await?;
-
-        Ok(())
-    }
-
-    /// Get configuration file path
-    fn config_file_path() -> Result<PathBuf> {
-        if let Some(config_dir) = dirs::config_dir() {
-            Ok(config_dir.join("comunicado").join("spell_check.toml"))
-        } else {
-            Ok(PathBuf::from(".").join("spell_check.toml"))
-        }
+        crate::config::save(self, Self::CONFIG_FILE).await
     }
 
     /// Add custom word
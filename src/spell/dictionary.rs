@@ -263,6 +263,11 @@ impl DictionaryManager {
             });
         }
 
+        // Then a Hunspell dictionary installed on the system
+        if let Some(paths) = Self::find_system_dictionary(language) {
+            return Ok(paths);
+        }
+
         // Try to download if not available locally
         self.download_dictionary(language).await?;
 
@@ -279,6 +284,32 @@ impl DictionaryManager {
         }
     }
 
+    /// Look for `<language>.aff`/`.dic` in the directories Hunspell searches:
+    /// those in `DICPATH`, then the usual system locations
+    fn find_system_dictionary(language: &str) -> Option<DictionaryPaths> {
+        let mut dirs: Vec<PathBuf> = std::env::var_os("DICPATH")
+            .map(|path| std::env::split_paths(&path).collect())
+            .unwrap_or_default();
+        dirs.extend(
+            [
+                "/usr/share/hunspell",
+                "/usr/share/myspell",
+                "/usr/share/myspell/dicts",
+                "/usr/local/share/hunspell",
+                "/run/current-system/sw/share/hunspell",
+                "/opt/homebrew/share/hunspell",
+            ]
+            .iter()
+            .map(PathBuf::from),
+        );
+
+        dirs.into_iter().find_map(|dir| {
+            let aff = dir.join(format!("{}.aff", language));
+            let dic = dir.join(format!("{}.dic", language));
+            (aff.exists() && dic.exists()).then_some(DictionaryPaths { aff, dic })
+        })
+    }
+
     /// Download dictionary from LibreOffice dictionaries
     async fn download_dictionary(&self, language: &str) -> Result<()> {
         // Create basic English dictionary content as fallback
@@ -322,6 +353,25 @@ REP s z
 REP z s
 REP ss s
 REP s ss
+
+SFX D Y 4
+SFX D 0 d e
+SFX D y ied [^aeiou]y
+SFX D 0 ed [^ey]
+SFX D 0 ed [aeiou]y
+
+SFX G Y 2
+SFX G e ing e
+SFX G 0 ing [^e]
+
+SFX S Y 4
+SFX S y ies [^aeiou]y
+SFX S 0 s [aeiou]y
+SFX S 0 es [sxzh]
+SFX S 0 s [^sxzhy]
+
+SFX M Y 1
+SFX M 0 's .
 "#;
 
         let dic_content = r#"5000
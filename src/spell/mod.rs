@@ -1,16 +1,25 @@
 use anyhow::Result;
+use regex::Regex;
 use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
 
+pub mod affix;
 pub mod config;
 pub mod dictionary;
 pub mod suggestion;
 
+use affix::AffixRules;
 use config::SpellCheckConfig;
 use dictionary::DictionaryManager;
+use suggestion::{damerau_levenshtein_distance, phonetic_key};
+
+static WORD_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b\w+\b").unwrap());
 
 /// Spell checking service for multi-language text validation
 pub struct SpellChecker {
     dictionaries: HashMap<String, HashSet<String>>,
+    /// Each dictionary's words, most frequent first, for ranking suggestions
+    word_lists: HashMap<String, Vec<String>>,
     dictionary_manager: DictionaryManager,
     config: SpellCheckConfig,
     current_language: String,
@@ -46,6 +55,7 @@ impl SpellChecker {
 
         let mut spell_checker = Self {
             dictionaries: HashMap::new(),
+            word_lists: HashMap::new(),
             dictionary_manager,
             config,
             current_language: "en_US".to_string(),
@@ -77,6 +87,7 @@ impl SpellChecker {
 
         Ok(Self {
             dictionaries: HashMap::new(),
+            word_lists: HashMap::new(),
             dictionary_manager,
             config,
             current_language,
@@ -95,23 +106,31 @@ impl SpellChecker {
             .get_dictionary_path(language)
             .await?;
 
-        // Load dictionary from .dic file
-        let dictionary_content = tokio::fs::read_to_string(&dictionary_path.dic).await?;
-        let mut word_set = HashSet::new();
-
-        // Parse dictionary file (skip first line which contains word count)
-        for (i, line) in dictionary_content.lines().enumerate() {
-            if i == 0 {
-                continue; // Skip word count line
+        // Expand the .dic stems with the .aff prefix and suffix rules, so
+        // inflected forms like plurals are accepted too
+        let dictionary_content = affix::decode(&tokio::fs::read(&dictionary_path.dic).await?);
+        let rules = match tokio::fs::read(&dictionary_path.aff).await {
+            Ok(content) => AffixRules::parse(&affix::decode(&content)),
+            Err(e) => {
+                tracing::warn!("Failed to read affix file {:?}: {}", dictionary_path.aff, e);
+                AffixRules::default()
             }
+        };
+        let word_set = affix::expand_dictionary(&dictionary_content, &rules);
+
+        // An optional word list next to the dictionary, most common first,
+        // breaks ties between equally close suggestions
+        let frequent_words: Vec<String> =
+            match tokio::fs::read_to_string(dictionary_path.dic.with_extension("freq")).await {
+                Ok(content) => content
+                    .lines()
+                    .map(|line| line.trim().to_lowercase())
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .collect(),
+                Err(_) => Vec::new(),
+            };
 
-            let word = line.split('/').next().unwrap_or(line).trim().to_lowercase();
-            if !word.is_empty() {
-                word_set.insert(word);
-            }
-        }
-
-        self.dictionaries.insert(language.to_string(), word_set);
+        self.add_dictionary(language, word_set, &frequent_words);
         tracing::info!(
             "Loaded spell check dictionary for language: {} ({} words)",
             language,
@@ -121,6 +140,30 @@ impl SpellChecker {
         Ok(())
     }
 
+    /// Store a dictionary along with its words ordered by `frequent_words`,
+    /// then alphabetically
+    fn add_dictionary(
+        &mut self,
+        language: &str,
+        words: HashSet<String>,
+        frequent_words: &[String],
+    ) {
+        let mut ranks = HashMap::new();
+        for (rank, word) in frequent_words.iter().enumerate() {
+            ranks.entry(word.as_str()).or_insert(rank);
+        }
+
+        let mut word_list: Vec<String> = words.iter().cloned().collect();
+        word_list.sort_by(|a, b| {
+            let rank_a = ranks.get(a.as_str()).copied().unwrap_or(usize::MAX);
+            let rank_b = ranks.get(b.as_str()).copied().unwrap_or(usize::MAX);
+            rank_a.cmp(&rank_b).then_with(|| a.cmp(b))
+        });
+
+        self.word_lists.insert(language.to_string(), word_list);
+        self.dictionaries.insert(language.to_string(), words);
+    }
+
     /// Set the current active language
     pub async fn set_language(&mut self, language: &str) -> Result<()> {
         if !self.dictionaries.contains_key(language) {
@@ -131,6 +174,11 @@ impl SpellChecker {
         Ok(())
     }
 
+    /// Whether the dictionary for `language` has been loaded
+    pub fn has_dictionary(&self, language: &str) -> bool {
+        self.dictionaries.contains_key(language)
+    }
+
     /// Get the current active language
     pub fn current_language(&self) -> &str {
         &self.current_language
@@ -216,41 +264,79 @@ impl SpellChecker {
         }
     }
 
-    /// Get spelling suggestions for a word
+    /// Get spelling suggestions for a word, closest first.
+    ///
+    /// Words are ranked by Damerau-Levenshtein distance, so a swapped pair of
+    /// letters counts as one edit. A word that sounds alike counts as one
+    /// edit closer and comes first among equals, then more frequent words.
     pub fn suggest_word(&self, word: &str) -> Vec<String> {
-        if let Some(dictionary) = self.dictionaries.get(&self.current_language) {
-            // Simple suggestion algorithm based on edit distance
-            let word_lower = word.to_lowercase();
-            let mut suggestions = Vec::new();
-
-            // Find words with similar length and characters
-            for dict_word in dictionary.iter() {
-                if self.is_similar_word(&word_lower, dict_word) {
-                    suggestions.push(dict_word.clone());
-                    if suggestions.len() >= 5 {
-                        break;
-                    }
+        let Some(word_list) = self.word_lists.get(&self.current_language) else {
+            return Vec::new();
+        };
+
+        let word_lower = word.to_lowercase();
+        let word_len = word_lower.chars().count();
+        // Short words have many neighbours, so allow fewer edits
+        let max_distance = if word_len <= 4 { 1 } else { 2 };
+        let word_key = self
+            .config
+            .phonetic_suggestions
+            .then(|| phonetic_key(&word_lower));
+        // Sounding alike takes one edit off the distance
+        let phonetic_limit = max_distance + usize::from(word_key.is_some());
+
+        // The list is ordered by frequency and the sort is stable, so ties
+        // keep the more common word first
+        let mut candidates: Vec<(usize, bool, &String)> = word_list
+            .iter()
+            .filter(|dict_word| *dict_word != &word_lower)
+            .filter_map(|dict_word| {
+                if word_len.abs_diff(dict_word.chars().count()) > phonetic_limit {
+                    return None;
                 }
-            }
+                let distance = damerau_levenshtein_distance(&word_lower, dict_word);
+                let sounds_alike = word_key
+                    .as_ref()
+                    .is_some_and(|key| !key.is_empty() && *key == phonetic_key(dict_word));
+                let score = distance - usize::from(sounds_alike && distance > 0);
+                (score <= max_distance).then_some((score, !sounds_alike, dict_word))
+            })
+            .collect();
+        candidates.sort_by_key(|(score, sounds_different, _)| (*score, *sounds_different));
 
-            suggestions
-        } else {
-            Vec::new()
-        }
+        candidates
+            .into_iter()
+            .take(self.config.max_suggestions)
+            .map(|(_, _, dict_word)| dict_word.clone())
+            .collect()
     }
 
     /// Check spelling of entire text
     pub fn check_text(&self, text: &str) -> SpellCheckResult {
+        self.check_text_with_suggestions(text, true)
+    }
+
+    /// Find the misspelled words in text without looking up suggestions,
+    /// which is much faster for checking as the user types
+    pub fn find_misspelled(&self, text: &str) -> SpellCheckResult {
+        self.check_text_with_suggestions(text, false)
+    }
+
+    fn check_text_with_suggestions(&self, text: &str, with_suggestions: bool) -> SpellCheckResult {
         let mut misspelled_words = Vec::new();
         let mut word_count = 0;
         // Split text into words while preserving positions
-        for word_match in regex::Regex::new(r"\b\w+\b").unwrap().find_iter(text) {
+        for word_match in WORD_PATTERN.find_iter(text) {
             let word = word_match.as_str();
             word_count += 1;
             let current_pos = word_match.start();
 
-            if !self.check_word(word) {
-                let suggestions = self.suggest_word(word);
+            if !self.config.should_ignore_word(word) && !self.check_word(word) {
+                let suggestions = if with_suggestions {
+                    self.suggest_word(word)
+                } else {
+                    Vec::new()
+                };
                 misspelled_words.push(MisspelledWord {
                     word: word.to_string(),
                     position: current_pos,
@@ -329,35 +415,146 @@ impl SpellChecker {
         self.config.save().await
     }
 
+    /// Current configuration, including custom words added since loading
+    pub fn config(&self) -> &SpellCheckConfig {
+        &self.config
+    }
+
     /// Update configuration
     pub fn update_config(&mut self, config: SpellCheckConfig) {
         self.config = config;
     }
+}
 
-    /// Helper method to check word similarity for suggestions
-    fn is_similar_word(&self, word1: &str, word2: &str) -> bool {
-        let len_diff = (word1.len() as i32 - word2.len() as i32).abs();
-        if len_diff > 2 {
-            return false;
-        }
+impl Default for SpellChecker {
+    fn default() -> Self {
+        Self::new().expect("Failed to create default SpellChecker")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker(words: &[&str], frequent_words: &[&str]) -> SpellChecker {
+        let mut checker = SpellChecker::with_config(SpellCheckConfig::default()).unwrap();
+        checker.add_dictionary(
+            "en_US",
+            words.iter().map(|word| word.to_string()).collect(),
+            &frequent_words
+                .iter()
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>(),
+        );
+        checker
+    }
 
-        // Simple character overlap check
-        let chars1: std::collections::HashSet<char> = word1.chars().collect();
-        let chars2: std::collections::HashSet<char> = word2.chars().collect();
-        let intersection = chars1.intersection(&chars2).count();
-        let union = chars1.union(&chars2).count();
+    #[test]
+    fn test_suggestions_ranked_by_distance_then_frequency() {
+        let checker = checker(
+            &["tea", "ten", "the", "then", "they", "toe", "zebra"],
+            &["the", "they", "then"],
+        );
 
-        if union == 0 {
-            return false;
-        }
+        let suggestions = checker.suggest_word("teh");
+        assert_eq!(suggestions.first().map(String::as_str), Some("the"));
+        assert!(!suggestions.contains(&"zebra".to_string()));
 
-        let similarity = intersection as f32 / union as f32;
-        similarity > 0.6
+        // A swapped pair of letters is a single edit
+        assert_eq!(
+            checker.suggest_word("tehn").first().map(String::as_str),
+            Some("then")
+        );
     }
-}
 
-impl Default for SpellChecker {
-    fn default() -> Self {
-        Self::new().expect("Failed to create default SpellChecker")
+    #[test]
+    fn test_suggestions_respect_limit_and_phonetics() {
+        let mut checker = checker(
+            &[
+                "phone", "bone", "cone", "done", "gone", "hone", "lone", "none", "tone",
+            ],
+            &[],
+        );
+
+        let suggestions = checker.suggest_word("fone");
+        assert_eq!(suggestions.len(), 5);
+        assert_eq!(suggestions[0], "phone");
+
+        let mut config = SpellCheckConfig::default();
+        config.phonetic_suggestions = false;
+        config.max_suggestions = 3;
+        checker.update_config(config);
+        let suggestions = checker.suggest_word("fone");
+        assert_eq!(suggestions, vec!["bone", "cone", "done"]);
+    }
+
+    #[test]
+    fn test_find_misspelled_positions() {
+        let mut checker = checker(&["the", "cat", "sat"], &[]);
+        checker.add_custom_word("Comunicado".to_string());
+
+        let result = checker.find_misspelled("The catt sat in Comunicado at 10am, NASA");
+        let words: Vec<(&str, usize)> = result
+            .misspelled_words
+            .iter()
+            .map(|word| (word.word.as_str(), word.position))
+            .collect();
+        assert_eq!(words, vec![("catt", 4), ("in", 13), ("at", 27)]);
+        assert!(result.misspelled_words[0].suggestions.is_empty());
+
+        let result = checker.check_text("The catt");
+        assert_eq!(result.misspelled_words[0].suggestions, vec!["cat"]);
+    }
+
+    #[test]
+    fn test_edit_distance_and_phonetic_key() {
+        assert_eq!(damerau_levenshtein_distance("teh", "the"), 1);
+        assert_eq!(damerau_levenshtein_distance("recieve", "receive"), 1);
+        assert_eq!(damerau_levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(damerau_levenshtein_distance("", "abc"), 3);
+
+        assert_eq!(phonetic_key("Robert"), "R163");
+        assert_eq!(phonetic_key("Rupert"), "R163");
+        assert_eq!(phonetic_key("fone"), phonetic_key("phone"));
+        assert_eq!(phonetic_key("nife"), phonetic_key("knife"));
+        assert_eq!(phonetic_key("123"), "");
+    }
+
+    #[test]
+    fn test_affix_expansion() {
+        let rules = AffixRules::parse(
+            "SET UTF-8\n\
+             PFX U Y 1\n\
+             PFX U 0 un .\n\
+             SFX S Y 3\n\
+             SFX S y ies [^aeiou]y\n\
+             SFX S 0 es [sxzh]\n\
+             SFX S 0 s [^sxzhy]\n\
+             SFX D Y 2\n\
+             SFX D 0 d e\n\
+             SFX D 0 ed [^ey]\n\
+             SFX G Y 2\n\
+             SFX G e ing e\n\
+             SFX G 0 ing [^e]\n\
+             FORBIDDENWORD !\n",
+        );
+        let words = affix::expand_dictionary(
+            "5\ntry/S\nbox/S\nlock/UDGS\nmake/G\nirregardless/!\n",
+            &rules,
+        );
+
+        for word in [
+            "try", "tries", "boxes", "lock", "locked", "locking", "locks", "unlock", "unlocked",
+            "unlocks", "making",
+        ] {
+            assert!(words.contains(word), "missing {}", word);
+        }
+        for word in ["trys", "boxs", "makeing", "irregardless"] {
+            assert!(!words.contains(word), "unexpected {}", word);
+        }
+
+        // Two-character flags
+        let rules = AffixRules::parse("FLAG long\nSFX Ab Y 1\nSFX Ab 0 s .\n");
+        assert_eq!(rules.expand_entry("cat/Ab"), vec!["cat", "cats"]);
     }
 }
@@ -417,3 +417,80 @@ impl Default for SuggestionManager {
         Self::new()
     }
 }
+
+/// Edits needed to turn `word1` into `word2`, where an edit inserts,
+/// deletes or substitutes a character or swaps two adjacent ones (the
+/// optimal string alignment form of Damerau-Levenshtein distance)
+pub fn damerau_levenshtein_distance(word1: &str, word2: &str) -> usize {
+    let chars1: Vec<char> = word1.chars().collect();
+    let chars2: Vec<char> = word2.chars().collect();
+    let (len1, len2) = (chars1.len(), chars2.len());
+
+    let mut matrix = vec![vec![0; len2 + 1]; len1 + 1];
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=len1 {
+        for j in 1..=len2 {
+            let cost = usize::from(chars1[i - 1] != chars2[j - 1]);
+            let mut distance = (matrix[i - 1][j] + 1) // deletion
+                .min(matrix[i][j - 1] + 1) // insertion
+                .min(matrix[i - 1][j - 1] + cost); // substitution
+            if i > 1 && j > 1 && chars1[i - 1] == chars2[j - 2] && chars1[i - 2] == chars2[j - 1] {
+                distance = distance.min(matrix[i - 2][j - 2] + 1); // transposition
+            }
+            matrix[i][j] = distance;
+        }
+    }
+
+    matrix[len1][len2]
+}
+
+/// Soundex code of a word, so words that sound alike share a key.
+/// Silent or respelled openings ("ph", "kn", "wr") are normalised first
+pub fn phonetic_key(word: &str) -> String {
+    let mut word = word.to_lowercase();
+    for (prefix, sound) in [("ph", "f"), ("kn", "n"), ("wr", "r"), ("gn", "n")] {
+        if let Some(rest) = word.strip_prefix(prefix) {
+            word = format!("{}{}", sound, rest);
+            break;
+        }
+    }
+
+    let code = |c: char| match c {
+        'b' | 'f' | 'p' | 'v' => Some('1'),
+        'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+        'd' | 't' => Some('3'),
+        'l' => Some('4'),
+        'm' | 'n' => Some('5'),
+        'r' => Some('6'),
+        _ => None,
+    };
+
+    let mut letters = word.chars().filter(|c| c.is_ascii_alphabetic());
+    let Some(first) = letters.next() else {
+        return String::new();
+    };
+
+    let mut key = first.to_ascii_uppercase().to_string();
+    let mut previous = code(first);
+    for c in letters {
+        let current = code(c);
+        if let Some(digit) = current.filter(|_| current != previous) {
+            key.push(digit);
+        }
+        // 'h' and 'w' don't separate letters with the same code
+        if c != 'h' && c != 'w' {
+            previous = current;
+        }
+        if key.len() == 4 {
+            break;
+        }
+    }
+
+    format!("{:0<4}", key)
+}
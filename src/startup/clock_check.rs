@@ -0,0 +1,235 @@
+//! System clock skew detection
+//!
+//! OAuth2 token validation and TLS certificate checks both depend on the local
+//! clock. When it is off by a few minutes, logins fail with opaque
+//! authorization or certificate errors. When enabled, the local time is
+//! compared at startup against the `Date` header of a reference server so the
+//! user can be told what is actually wrong. The check contacts a third party,
+//! so it is off unless the user turns it on, and only HTTPS servers are used
+//! since a plain HTTP answer could be forged.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Clock check configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClockCheckConfig {
+    /// Check the clock at startup
+    pub enabled: bool,
+    /// HTTPS servers whose `Date` header is used as the reference time, tried in order
+    pub reference_urls: Vec<String>,
+    /// Skew in seconds above which a warning is shown
+    pub warn_threshold_seconds: i64,
+    /// Per-request timeout in seconds
+    pub timeout_seconds: u64,
+}
+
+impl Default for ClockCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            reference_urls: vec![
+                "https://www.google.com".to_string(),
+                "https://www.cloudflare.com".to_string(),
+            ],
+            warn_threshold_seconds: 120,
+            timeout_seconds: 5,
+        }
+    }
+}
+
+impl ClockCheckConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "clock_check.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// Measured difference between the local clock and a reference server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClockSkew {
+    /// Local time minus reference time; positive when the local clock is ahead
+    pub offset: ChronoDuration,
+    /// Where the reference time came from
+    pub source: String,
+}
+
+impl ClockSkew {
+    /// Compute the skew from a server time observed between two local timestamps
+    ///
+    /// The midpoint of the request is compared so network latency doesn't count as skew.
+    pub fn from_reference(
+        sent_at: DateTime<Utc>,
+        received_at: DateTime<Utc>,
+        server_time: DateTime<Utc>,
+        source: impl Into<String>,
+    ) -> Self {
+        let local_midpoint = sent_at + received_at.signed_duration_since(sent_at) / 2;
+        Self {
+            offset: local_midpoint.signed_duration_since(server_time),
+            source: source.into(),
+        }
+    }
+
+    /// Whether the skew is large enough to cause authentication problems
+    pub fn exceeds(&self, threshold_seconds: i64) -> bool {
+        self.offset.num_seconds().abs() > threshold_seconds
+    }
+
+    /// Human-readable size of the skew, e.g. "6 minutes"
+    pub fn magnitude(&self) -> String {
+        let seconds = self.offset.num_seconds().abs();
+        if seconds < 60 {
+            plural(seconds, "second")
+        } else if seconds < 3600 {
+            plural((seconds + 30) / 60, "minute")
+        } else if seconds < 86400 {
+            plural((seconds + 1800) / 3600, "hour")
+        } else {
+            plural((seconds + 43200) / 86400, "day")
+        }
+    }
+
+    /// Warning shown to the user
+    pub fn warning_message(&self) -> String {
+        let direction = if self.offset > ChronoDuration::zero() {
+            "ahead"
+        } else {
+            "behind"
+        };
+        format!(
+            "Your clock is off by {} ({}); this can break login and certificate checks",
+            self.magnitude(),
+            direction
+        )
+    }
+}
+
+fn plural(count: i64, unit: &str) -> String {
+    if count == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+/// Parse an HTTP `Date` header (IMF-fixdate, e.g. "Sun, 06 Nov 1994 08:49:37 GMT")
+pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+/// Measure clock skew against the first reference server that answers
+pub async fn measure_clock_skew(config: &ClockCheckConfig) -> Result<ClockSkew> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_seconds))
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?;
+
+    let mut last_error = anyhow!("No reference servers configured");
+
+    for url in &config.reference_urls {
+        if !url.starts_with("https://") {
+            tracing::warn!("Skipping clock reference {}: only https URLs are used", url);
+            last_error = anyhow!("{} is not an https URL", url);
+            continue;
+        }
+
+        let sent_at = Utc::now();
+        let response = match client.head(url).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::debug!("Clock check against {} failed: {}", url, e);
+                last_error = anyhow!("Failed to reach {}: {}", url, e);
+                continue;
+            }
+        };
+        let received_at = Utc::now();
+
+        let server_time = response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_http_date);
+
+        match server_time {
+            Some(server_time) => {
+                return Ok(ClockSkew::from_reference(
+                    sent_at,
+                    received_at,
+                    server_time,
+                    url.clone(),
+                ));
+            }
+            None => last_error = anyhow!("{} did not return a usable Date header", url),
+        }
+    }
+
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_http_date() {
+        let date = parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT").unwrap();
+        assert_eq!(date.to_rfc3339(), "1994-11-06T08:49:37+00:00");
+        assert!(parse_http_date("not a date").is_none());
+    }
+
+    #[test]
+    fn test_skew_uses_request_midpoint() {
+        let server_time = parse_http_date("Mon, 01 Jan 2024 12:00:00 GMT").unwrap();
+
+        // Request took 2 seconds and the local clock is 6 minutes ahead
+        let sent_at = server_time + ChronoDuration::minutes(6) - ChronoDuration::seconds(1);
+        let received_at = sent_at + ChronoDuration::seconds(2);
+        let skew = ClockSkew::from_reference(sent_at, received_at, server_time, "test");
+
+        assert_eq!(skew.offset, ChronoDuration::minutes(6));
+        assert!(skew.exceeds(120));
+        assert_eq!(
+            skew.warning_message(),
+            "Your clock is off by 6 minutes (ahead); this can break login and certificate checks"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plain_http_reference_rejected() {
+        assert!(!ClockCheckConfig::default().enabled);
+
+        let config = ClockCheckConfig {
+            enabled: true,
+            reference_urls: vec!["http://www.example.com".to_string()],
+            ..ClockCheckConfig::default()
+        };
+        let error = measure_clock_skew(&config).await.unwrap_err();
+        assert!(error.to_string().contains("not an https URL"));
+    }
+
+    #[test]
+    fn test_small_skew_is_ignored() {
+        let skew = ClockSkew {
+            offset: ChronoDuration::seconds(-45),
+            source: "test".to_string(),
+        };
+
+        assert!(!skew.exceeds(120));
+        assert_eq!(skew.magnitude(), "45 seconds");
+        assert!(skew.warning_message().contains("behind"));
+    }
+}
@@ -1,9 +1,11 @@
+pub mod clock_check;
 pub mod manager;
 pub mod progress;
 pub mod screen;
 pub mod lazy_init;
 pub mod optimization;
 
+pub use clock_check::{ClockCheckConfig, ClockSkew};
 pub use manager::StartupProgressManager;
 pub use progress::{PhaseStatus, StartupPhase};
 pub use screen::StartupProgressScreen;
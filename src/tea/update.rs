@@ -39,7 +39,9 @@ fn update_system(mut model: Model, message: SystemMessage) -> UpdateResult<Model
         SystemMessage::Initialize(startup_mode) => {
             // Set view based on startup mode
             model.current_view = match startup_mode {
-                crate::cli::StartupMode::Default => ViewMode::Email,
+                crate::cli::StartupMode::Default
+                | crate::cli::StartupMode::Resume
+                | crate::cli::StartupMode::Daemon => ViewMode::Email,
                 crate::cli::StartupMode::Email => ViewMode::Email,
                 crate::cli::StartupMode::Calendar => ViewMode::Calendar,
                 crate::cli::StartupMode::Contacts => ViewMode::Contacts,
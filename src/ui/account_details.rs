@@ -0,0 +1,316 @@
+//! Account connection details
+//!
+//! Shows the capabilities an account's IMAP server advertises and the
+//! negotiated TLS parameters, and lets the user switch individual
+//! capabilities off when a server's implementation of them misbehaves.
+
+use crate::imap::{ConnectionDetails, TlsInfo};
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Capabilities offered for disabling before the account has connected
+const COMMON_OVERRIDES: &[&str] = &[
+    "IDLE",
+    "COMPRESS=DEFLATE",
+    "CONDSTORE",
+    "QRESYNC",
+    "MOVE",
+    "UIDPLUS",
+    "LITERAL+",
+];
+
+/// One capability and whether it's in effect
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapabilityRow {
+    pub name: String,
+    /// Advertised by the server on the current connection
+    pub advertised: bool,
+    pub disabled: bool,
+}
+
+/// Popup listing an account's server capabilities and TLS parameters
+pub struct AccountDetailsView {
+    account_id: String,
+    account_name: String,
+    /// `host:port`
+    server: String,
+    connected: bool,
+    tls: Option<TlsInfo>,
+    rows: Vec<CapabilityRow>,
+    state: ListState,
+}
+
+impl AccountDetailsView {
+    /// Build the view from the live connection, if there is one, and the
+    /// account's current overrides
+    pub fn new(
+        account_id: String,
+        account_name: String,
+        server: String,
+        details: Option<ConnectionDetails>,
+        disabled: &[String],
+    ) -> Self {
+        let is_disabled = |name: &str| disabled.iter().any(|d| d.eq_ignore_ascii_case(name));
+
+        let mut rows: Vec<CapabilityRow> = match details {
+            Some(ref details) => details
+                .server_capabilities
+                .iter()
+                .map(|capability| CapabilityRow {
+                    name: capability.name().to_string(),
+                    advertised: true,
+                    disabled: is_disabled(capability.name()),
+                })
+                .collect(),
+            None => COMMON_OVERRIDES
+                .iter()
+                .map(|name| CapabilityRow {
+                    name: name.to_string(),
+                    advertised: false,
+                    disabled: is_disabled(name),
+                })
+                .collect(),
+        };
+
+        // Keep overrides visible even when the server stopped advertising them
+        for name in disabled {
+            if !rows.iter().any(|row| row.name.eq_ignore_ascii_case(name)) {
+                rows.push(CapabilityRow {
+                    name: name.clone(),
+                    advertised: false,
+                    disabled: true,
+                });
+            }
+        }
+
+        let mut state = ListState::default();
+        if !rows.is_empty() {
+            state.select(Some(0));
+        }
+
+        Self {
+            account_id,
+            account_name,
+            server,
+            connected: details.is_some(),
+            tls: details.and_then(|details| details.tls),
+            rows,
+            state,
+        }
+    }
+
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    pub fn rows(&self) -> &[CapabilityRow] {
+        &self.rows
+    }
+
+    pub fn select_next(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let next = self
+            .state
+            .selected()
+            .map_or(0, |selected| (selected + 1) % self.rows.len());
+        self.state.select(Some(next));
+    }
+
+    pub fn select_previous(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let previous = match self.state.selected() {
+            Some(0) | None => self.rows.len() - 1,
+            Some(selected) => selected - 1,
+        };
+        self.state.select(Some(previous));
+    }
+
+    /// Flip the selected capability, returning its name and new disabled state
+    pub fn toggle_selected(&mut self) -> Option<(String, bool)> {
+        let row = self.rows.get_mut(self.state.selected()?)?;
+        row.disabled = !row.disabled;
+        Some((row.name.clone(), row.disabled))
+    }
+
+    /// Names of all disabled capabilities
+    pub fn disabled_capabilities(&self) -> Vec<String> {
+        self.rows
+            .iter()
+            .filter(|row| row.disabled)
+            .map(|row| row.name.clone())
+            .collect()
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(60, 70, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" Account Details · {} ", self.account_name))
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4), // Connection
+                Constraint::Min(3),    // Capabilities
+                Constraint::Length(2), // Help
+            ])
+            .split(inner);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let security = match (&self.tls, self.connected) {
+            (Some(tls), _) => Line::from(vec![
+                Span::styled("TLS:      ", label),
+                Span::raw(format!("{} · {}", tls.protocol, tls.cipher_suite)),
+            ]),
+            (None, true) => Line::from(vec![
+                Span::styled("TLS:      ", label),
+                Span::styled(
+                    "not encrypted",
+                    Style::default().fg(theme.colors.palette.warning),
+                ),
+            ]),
+            (None, false) => Line::from(vec![
+                Span::styled("TLS:      ", label),
+                Span::styled("not connected", label),
+            ]),
+        };
+        let connection = vec![
+            Line::from(vec![
+                Span::styled("Server:   ", label),
+                Span::raw(self.server.clone()),
+            ]),
+            security,
+            Line::from(Span::styled(
+                if self.connected {
+                    "Capabilities advertised by the server:"
+                } else {
+                    "Not connected yet (or busy); showing common capabilities:"
+                },
+                label,
+            )),
+        ];
+        frame.render_widget(Paragraph::new(connection), sections[0]);
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let (marker, style) = if row.disabled {
+                    (
+                        "[ ]",
+                        Style::default()
+                            .fg(theme.colors.palette.text_muted)
+                            .add_modifier(Modifier::CROSSED_OUT),
+                    )
+                } else {
+                    ("[x]", Style::default().fg(theme.colors.palette.text_primary))
+                };
+                let mut spans = vec![
+                    Span::styled(format!("{} ", marker), style),
+                    Span::styled(row.name.clone(), style),
+                ];
+                if row.disabled {
+                    spans.push(Span::styled(
+                        "  disabled",
+                        Style::default().fg(theme.colors.palette.warning),
+                    ));
+                }
+                if self.connected && !row.advertised {
+                    spans.push(Span::styled("  not advertised", label));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(theme.colors.palette.selection)
+                .fg(theme.colors.palette.selection_text)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(list, sections[1], &mut self.state);
+
+        let help = Paragraph::new(Line::from(Span::styled(
+            "j/k: move · Space: enable/disable for this account · Esc: close",
+            label,
+        )))
+        .block(Block::default().borders(Borders::TOP));
+        frame.render_widget(help, sections[2]);
+    }
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::imap::ImapCapability;
+
+    #[test]
+    fn test_account_details_rows() {
+        let details = ConnectionDetails {
+            server_capabilities: vec![ImapCapability::Imap4Rev1, ImapCapability::Idle],
+            tls: None,
+        };
+        let disabled = vec!["IDLE".to_string(), "COMPRESS=DEFLATE".to_string()];
+        let mut view = AccountDetailsView::new(
+            "work".to_string(),
+            "Work".to_string(),
+            "imap.example.com:993".to_string(),
+            Some(details),
+            &disabled,
+        );
+
+        let names: Vec<(&str, bool, bool)> = view
+            .rows()
+            .iter()
+            .map(|row| (row.name.as_str(), row.advertised, row.disabled))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("IMAP4rev1", true, false),
+                ("IDLE", true, true),
+                ("COMPRESS=DEFLATE", false, true),
+            ]
+        );
+
+        view.select_next();
+        assert_eq!(view.toggle_selected(), Some(("IDLE".to_string(), false)));
+        assert_eq!(view.disabled_capabilities(), vec!["COMPRESS=DEFLATE".to_string()]);
+    }
+}
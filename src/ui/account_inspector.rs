@@ -91,7 +91,7 @@ impl AccountInspector {
         // Split the area into two parts: list and details
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
             .split(area);
 
         self.render_account_list(f, chunks[0]);
@@ -181,7 +181,7 @@ impl AccountInspector {
         if let Some(account) = self.get_selected_account() {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Length(12), Constraint::Min(6)].as_ref())
+                .constraints([Constraint::Length(12), Constraint::Min(6)])
                 .split(area);
 
             self.render_account_info(f, chunks[0], account);
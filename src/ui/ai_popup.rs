@@ -1,5 +1,6 @@
 //! AI popup system with animations and interactive features
 
+use crate::ai::{CacheStatistics, ThreadSummary};
 use crate::email::{EmailSummary, EmailReplyAssistance};
 use crate::calendar::{CalendarInsights, EventModificationSuggestions, MeetingScheduleAnalysis, ParsedEventInfo};
 use crate::theme::Theme;
@@ -13,6 +14,18 @@ use ratatui::{
 };
 use std::time::{Duration, Instant};
 
+/// A cache TTL in the largest whole unit, such as "12h"
+fn format_ttl(ttl: Duration) -> String {
+    let secs = ttl.as_secs();
+    match secs {
+        0 => "0s".to_string(),
+        _ if secs.is_multiple_of(86_400) => format!("{}d", secs / 86_400),
+        _ if secs.is_multiple_of(3_600) => format!("{}h", secs / 3_600),
+        _ if secs.is_multiple_of(60) => format!("{}m", secs / 60),
+        _ => format!("{}s", secs),
+    }
+}
+
 /// AI popup animation states
 #[derive(Debug, Clone, PartialEq)]
 pub enum PopupAnimationState {
@@ -146,6 +159,11 @@ pub enum AIPopupContent {
         selected_tone: ReplyTone,
         generating_reply: bool,
     },
+    /// Bullet summary of a whole thread
+    ThreadSummary {
+        subject: String,
+        summary: ThreadSummary,
+    },
     /// Natural language event creation
     EventCreation {
         parsed_event: ParsedEventInfo,
@@ -166,11 +184,20 @@ pub enum AIPopupContent {
         insights: CalendarInsights,
         selected_category: InsightsCategory,
     },
+    /// Response cache statistics
+    CacheDiagnostics {
+        stats: CacheStatistics,
+    },
     /// Loading state
     Loading {
         message: String,
         progress: f32,
     },
+    /// Answer being streamed in by the provider
+    Streaming {
+        title: String,
+        text: String,
+    },
     /// Error state
     Error {
         message: String,
@@ -250,6 +277,29 @@ impl AIPopup {
         self.start_animation_in();
     }
 
+    /// Show a thread summary popup with animation
+    pub fn show_thread_summary(&mut self, subject: String, summary: ThreadSummary) {
+        self.content = Some(AIPopupContent::ThreadSummary { subject, summary });
+        self.current_tab = PopupTab::Summary;
+        self.selected_index = 0;
+        self.list_state.select(Some(0));
+        self.start_animation_in();
+    }
+
+    /// Show response cache statistics, or refresh them if already shown
+    pub fn show_cache_diagnostics(&mut self, stats: CacheStatistics) {
+        let refresh = self.is_showing_diagnostics();
+        self.content = Some(AIPopupContent::CacheDiagnostics { stats });
+        if !refresh {
+            self.start_animation_in();
+        }
+    }
+
+    /// Whether the popup is showing cache statistics
+    pub fn is_showing_diagnostics(&self) -> bool {
+        self.is_visible() && matches!(self.content, Some(AIPopupContent::CacheDiagnostics { .. }))
+    }
+
     /// Show loading state
     pub fn show_loading(&mut self, message: String) {
         self.content = Some(AIPopupContent::Loading {
@@ -259,6 +309,30 @@ impl AIPopup {
         self.start_animation_in();
     }
 
+    /// Add streamed text to the answer being written, in place of the
+    /// loading message once the first piece arrives
+    pub fn append_stream(&mut self, piece: &str) {
+        match &mut self.content {
+            Some(AIPopupContent::Streaming { text, .. }) => text.push_str(piece),
+            Some(AIPopupContent::Loading { message, .. }) => {
+                let title = std::mem::take(message);
+                self.content = Some(AIPopupContent::Streaming {
+                    title,
+                    text: piece.to_string(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether the popup is waiting for an answer
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            self.content,
+            Some(AIPopupContent::Loading { .. }) | Some(AIPopupContent::Streaming { .. })
+        )
+    }
+
     /// Update loading progress
     pub fn update_loading_progress(&mut self, progress: f32, message: Option<String>) {
         if let Some(AIPopupContent::Loading { progress: ref mut p, message: ref mut m }) = &mut self.content {
@@ -458,6 +532,7 @@ impl AIPopup {
             (Some(AIPopupContent::EmailSummary { summary, .. }), PopupTab::Actions) => {
                 summary.action_items.len()
             }
+            (Some(AIPopupContent::ThreadSummary { summary, .. }), _) => summary.bullets.len(),
             (Some(AIPopupContent::EventModification { selected_category, suggestions }), _) => {
                 match selected_category {
                     ModificationCategory::Time => suggestions.time_suggestions.len(),
@@ -537,6 +612,10 @@ impl AIPopup {
                 // For loading state, Enter should close the popup
                 return Some(PopupAction::Close);
             }
+            (Some(AIPopupContent::ThreadSummary { .. }), _)
+            | (Some(AIPopupContent::CacheDiagnostics { .. }), _) => {
+                return Some(PopupAction::Close);
+            }
             _ => {}
         }
 
@@ -603,6 +682,9 @@ impl AIPopup {
             Some(AIPopupContent::EmailSummary { summary, reply_assistance, selected_tone, generating_reply }) => {
                 self.render_email_summary(frame, inner_area, theme, typography, summary, reply_assistance, selected_tone, *generating_reply);
             }
+            Some(AIPopupContent::ThreadSummary { subject, summary }) => {
+                self.render_thread_summary(frame, inner_area, theme, typography, subject, summary);
+            }
             Some(AIPopupContent::EventCreation { parsed_event, confirmed }) => {
                 self.render_event_creation(frame, inner_area, theme, typography, parsed_event, *confirmed);
             }
@@ -615,9 +697,15 @@ impl AIPopup {
             Some(AIPopupContent::CalendarInsights { insights, selected_category }) => {
                 self.render_calendar_insights(frame, inner_area, theme, typography, insights, selected_category);
             }
+            Some(AIPopupContent::CacheDiagnostics { stats }) => {
+                self.render_cache_diagnostics(frame, inner_area, theme, typography, stats);
+            }
             Some(AIPopupContent::Loading { message, progress }) => {
                 self.render_loading(frame, inner_area, theme, typography, message, *progress);
             }
+            Some(AIPopupContent::Streaming { title, text }) => {
+                self.render_streaming(frame, inner_area, theme, typography, title, text);
+            }
             Some(AIPopupContent::Error { message, retry_available }) => {
                 self.render_error(frame, inner_area, theme, typography, message, *retry_available);
             }
@@ -688,6 +776,124 @@ impl AIPopup {
         }
     }
 
+    /// Render thread summary content
+    fn render_thread_summary(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        typography: &TypographySystem,
+        subject: &str,
+        summary: &ThreadSummary,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Subject and size
+                Constraint::Min(3),    // Bullets
+                Constraint::Length(1), // Footer
+            ])
+            .split(area);
+
+        let header_text = Line::from(vec![
+            typography.create_emphasis(subject, theme),
+            typography.create_span(
+                format!("  ({} messages)", summary.message_count),
+                TypographyLevel::Caption,
+                theme,
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(header_text), chunks[0]);
+
+        let bullets: Vec<Line> = summary.bullets
+            .iter()
+            .map(|bullet| Line::from(format!("• {}", bullet)))
+            .collect();
+        let bullets_para = Paragraph::new(bullets)
+            .wrap(Wrap { trim: true })
+            .scroll((self.selected_index as u16, 0))
+            .block(Block::default().title("Thread Summary").borders(Borders::ALL))
+            .style(typography.get_typography_style(TypographyLevel::Body, theme));
+        frame.render_widget(bullets_para, chunks[1]);
+
+        let footer_text = Line::from(vec![
+            typography.create_emphasis("↑/↓", theme),
+            typography.create_span(": Scroll  ".to_string(), TypographyLevel::Caption, theme),
+            typography.create_emphasis("Enter", theme),
+            typography.create_span("/".to_string(), TypographyLevel::Caption, theme),
+            typography.create_emphasis("Esc", theme),
+            typography.create_span(": Close".to_string(), TypographyLevel::Caption, theme),
+        ]);
+        let footer_para = Paragraph::new(footer_text)
+            .alignment(Alignment::Center)
+            .style(typography.get_typography_style(TypographyLevel::Caption, theme));
+        frame.render_widget(footer_para, chunks[2]);
+    }
+
+    /// Render response cache statistics
+    fn render_cache_diagnostics(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        typography: &TypographySystem,
+        stats: &CacheStatistics,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),    // Statistics
+                Constraint::Length(1), // Footer
+            ])
+            .split(area);
+
+        let row = |label: &str, value: String| {
+            Line::from(vec![
+                typography.create_span(format!("{:<18}", label), TypographyLevel::Caption, theme),
+                typography.create_span(value, TypographyLevel::Body, theme),
+            ])
+        };
+        let rows = vec![
+            row(
+                "Cached responses",
+                format!("{} ({} expired)", stats.valid_entries, stats.expired_entries),
+            ),
+            row(
+                "Hit rate",
+                format!(
+                    "{:.0}% ({} hits, {} misses)",
+                    stats.hit_rate * 100.0,
+                    stats.total_hits,
+                    stats.total_misses
+                ),
+            ),
+            row("Kept for", format_ttl(stats.default_ttl)),
+            row(
+                "Memory",
+                format!(
+                    "{} KB of {} MB",
+                    stats.memory_usage_bytes / 1024,
+                    stats.max_memory_bytes / (1024 * 1024)
+                ),
+            ),
+            row("Evictions", stats.total_evictions.to_string()),
+        ];
+        let stats_para = Paragraph::new(rows)
+            .block(Block::default().title("Response Cache").borders(Borders::ALL));
+        frame.render_widget(stats_para, chunks[0]);
+
+        let footer_text = Line::from(vec![
+            typography.create_emphasis("f", theme),
+            typography.create_span(": Flush cache  ".to_string(), TypographyLevel::Caption, theme),
+            typography.create_emphasis("Esc", theme),
+            typography.create_span(": Close".to_string(), TypographyLevel::Caption, theme),
+        ]);
+        let footer_para = Paragraph::new(footer_text)
+            .alignment(Alignment::Center)
+            .style(typography.get_typography_style(TypographyLevel::Caption, theme));
+        frame.render_widget(footer_para, chunks[1]);
+    }
+
     /// Render summary tab
     fn render_summary_tab(&self, frame: &mut Frame, area: Rect, theme: &Theme, typography: &TypographySystem, summary: &EmailSummary) {
         let chunks = Layout::default()
@@ -852,6 +1058,41 @@ impl AIPopup {
         frame.render_widget(progress_block, progress_area);
     }
 
+    /// Render an answer as it streams in, following its end
+    fn render_streaming(&self, frame: &mut Frame, area: Rect, theme: &Theme, typography: &TypographySystem, title: &str, text: &str) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Title
+                Constraint::Min(1),    // Streamed text
+                Constraint::Length(1), // Hint
+            ])
+            .split(area);
+
+        let title_para = Paragraph::new(typography.create_text(title, TypographyLevel::Body, theme))
+            .alignment(Alignment::Center);
+        frame.render_widget(title_para, chunks[0]);
+
+        // Scroll so the newest text stays in view
+        let width = chunks[1].width.max(1) as usize;
+        let wrapped_lines: usize = text
+            .lines()
+            .map(|line| line.chars().count().max(1).div_ceil(width))
+            .sum();
+        let scroll = wrapped_lines.saturating_sub(chunks[1].height as usize) as u16;
+        let text_para = Paragraph::new(text.to_string())
+            .style(Style::default().fg(theme.colors.palette.text_primary))
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0));
+        frame.render_widget(text_para, chunks[1]);
+
+        let hint = Line::from(vec![
+            typography.create_emphasis("Esc", theme),
+            typography.create_span(" Stop".to_string(), TypographyLevel::Caption, theme),
+        ]);
+        frame.render_widget(Paragraph::new(hint).alignment(Alignment::Center), chunks[2]);
+    }
+
     /// Render error state
     fn render_error(&self, frame: &mut Frame, area: Rect, theme: &Theme, typography: &TypographySystem, message: &str, retry_available: bool) {
         let error_text = typography.create_text(message, TypographyLevel::Body, theme);
@@ -2,13 +2,17 @@
 //!
 //! This module integrates the animation system with email content rendering,
 //! allowing GIFs and other animated content in emails to be displayed properly.
+//!
+//! Playback is user controlled: `animations.toml` can turn animations off
+//! or keep them from starting on their own, and they start paused while the
+//! machine runs on battery.
 
 use crate::ui::animation::{AnimationManager, AnimationSettings, AnimationError, AnimationFormat};
 use crate::ui::graphics::{ImageRenderer, RenderConfig, GraphicsProtocol};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::RwLock;
@@ -35,6 +39,85 @@ pub enum AnimatedContentError {
 
 pub type AnimatedContentResult<T> = Result<T, AnimatedContentError>;
 
+/// Animation preferences
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnimationConfig {
+    /// Decode and show animated images at all
+    pub enabled: bool,
+    /// Start animations once they scroll into view
+    pub autoplay: bool,
+    /// Keep animations paused while the machine runs on battery
+    pub pause_on_battery: bool,
+}
+
+impl Default for AnimationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            autoplay: true,
+            pause_on_battery: true,
+        }
+    }
+}
+
+impl AnimationConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "animations.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> anyhow::Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> anyhow::Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Whether animations should start on their own right now
+    pub fn autoplay_now(&self) -> bool {
+        self.enabled && self.autoplay && !(self.pause_on_battery && on_battery_power())
+    }
+}
+
+/// Whether the machine is running on battery
+pub fn on_battery_power() -> bool {
+    if cfg!(target_os = "macos") {
+        std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains("'Battery Power'"))
+            .unwrap_or(false)
+    } else {
+        battery_discharging(Path::new("/sys/class/power_supply"))
+    }
+}
+
+/// Read a Linux power supply class directory. Any online adapter means
+/// mains power; otherwise a discharging battery means battery power.
+fn battery_discharging(power_supply: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(power_supply) else {
+        return false;
+    };
+    let read = |path: PathBuf| {
+        std::fs::read_to_string(path)
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut discharging = false;
+    for entry in entries.flatten() {
+        let supply = entry.path();
+        match read(supply.join("type")).as_str() {
+            "Mains" | "USB" if read(supply.join("online")) == "1" => return false,
+            "Battery" if read(supply.join("status")) == "Discharging" => discharging = true,
+            _ => {}
+        }
+    }
+    discharging
+}
+
 /// Animated attachment information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnimatedAttachment {
@@ -285,6 +368,10 @@ impl AnimatedContentManager {
         self.animation_manager.stop_animation(animation_id)
     }
     
+    pub fn step_animation(&self, animation_id: Uuid) -> bool {
+        self.animation_manager.step_animation(animation_id)
+    }
+    
     /// Control all animations in an email
     pub async fn play_email_animations(&self, email_id: Uuid) {
         if let Some(state) = self.email_states.read().await.get(&email_id) {
@@ -404,20 +491,35 @@ pub struct EmailAnimationStats {
 }
 
 /// Animation control widget for email viewer
+///
+/// Tracks which animations the user wants running. An animation only plays
+/// while it is also on screen, so scrolling it away pauses it and scrolling
+/// back resumes it.
 pub struct AnimationControlWidget {
     animations: Vec<AnimatedAttachment>,
     selected_index: usize,
     show_controls: bool,
+    /// Animation manager IDs the user wants playing
+    wanted: HashSet<Uuid>,
+    /// Animation manager IDs currently on screen
+    visible: HashSet<Uuid>,
 }
 
 impl AnimationControlWidget {
     /// Create new animation control widget
     pub fn new(animations: Vec<AnimatedAttachment>) -> Self {
         let show_controls = !animations.is_empty();
+        let wanted = animations
+            .iter()
+            .filter(|animation| animation.auto_play)
+            .filter_map(|animation| animation.animation_id)
+            .collect();
         Self {
             animations,
             selected_index: 0,
             show_controls,
+            wanted,
+            visible: HashSet::new(),
         }
     }
     
@@ -457,6 +559,57 @@ impl AnimationControlWidget {
     pub fn animation_count(&self) -> usize {
         self.animations.len()
     }
+    
+    /// All animations, in display order
+    pub fn animations(&self) -> &[AnimatedAttachment] {
+        &self.animations
+    }
+    
+    /// Whether the user wants this animation playing
+    pub fn is_playing(&self, animation_id: Uuid) -> bool {
+        self.wanted.contains(&animation_id)
+    }
+    
+    /// Play or pause the selected animation, returning whether it now plays
+    pub fn toggle_playback(&mut self, manager: &AnimationManager) -> Option<bool> {
+        let animation_id = self.selected_animation()?.animation_id?;
+        if self.wanted.remove(&animation_id) {
+            manager.pause_animation(animation_id);
+            Some(false)
+        } else {
+            self.wanted.insert(animation_id);
+            if self.visible.contains(&animation_id) {
+                manager.play_animation(animation_id);
+            }
+            Some(true)
+        }
+    }
+    
+    /// Pause the selected animation and advance it by one frame
+    pub fn step(&mut self, manager: &AnimationManager) -> bool {
+        match self
+            .selected_animation()
+            .and_then(|animation| animation.animation_id)
+        {
+            Some(animation_id) => {
+                self.wanted.remove(&animation_id);
+                manager.step_animation(animation_id)
+            }
+            None => false,
+        }
+    }
+    
+    /// Record whether an animation is on screen, pausing it when scrolled
+    /// away and resuming it when it comes back if the user wants it playing
+    pub fn set_visible(&mut self, animation_id: Uuid, visible: bool, manager: &AnimationManager) {
+        if visible {
+            if self.visible.insert(animation_id) && self.wanted.contains(&animation_id) {
+                manager.play_animation(animation_id);
+            }
+        } else if self.visible.remove(&animation_id) {
+            manager.pause_animation(animation_id);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -554,4 +707,124 @@ mod tests {
         widget.previous_animation();
         assert_eq!(widget.selected_index, 1);
     }
-}
\ No newline at end of file
+
+    fn animated(animation_id: Uuid, auto_play: bool) -> AnimatedAttachment {
+        AnimatedAttachment {
+            id: Uuid::new_v4(),
+            filename: "signature.gif".to_string(),
+            content_type: "image/gif".to_string(),
+            format: AnimationFormat::Gif,
+            size: 1024,
+            width: Some(4),
+            height: Some(4),
+            duration_ms: Some(80),
+            frame_count: Some(2),
+            auto_play,
+            animation_id: Some(animation_id),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn two_frame_gif() -> Vec<u8> {
+        use image::codecs::gif::GifEncoder;
+        use image::{Delay, Frame, Rgba, RgbaImage};
+
+        let mut data = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut data);
+            for shade in [0, 255] {
+                let image = RgbaImage::from_pixel(4, 4, Rgba([shade, shade, shade, 255]));
+                let frame = Frame::from_parts(image, 0, 0, Delay::from_numer_denom_ms(40, 1));
+                encoder.encode_frame(frame).unwrap();
+            }
+        }
+        data
+    }
+
+    fn manager() -> AnimationManager {
+        AnimationManager::new(
+            Arc::new(ImageRenderer::new(RenderConfig::default())),
+            AnimationSettings::default(),
+        )
+    }
+
+    #[test]
+    fn test_animations_only_play_while_visible() {
+        use crate::ui::animation::AnimationState;
+
+        let manager = manager();
+        let id = manager.load_gif(&two_frame_gif()).unwrap();
+        let mut widget = AnimationControlWidget::new(vec![animated(id, true)]);
+        let state = |manager: &AnimationManager| manager.playback_status(id).unwrap().0;
+
+        // Nothing runs before the animation is drawn on screen
+        assert!(widget.is_playing(id));
+        assert_eq!(state(&manager), AnimationState::Stopped);
+
+        widget.set_visible(id, true, &manager);
+        assert_eq!(state(&manager), AnimationState::Playing);
+
+        widget.set_visible(id, false, &manager);
+        assert_eq!(state(&manager), AnimationState::Paused);
+        assert!(widget.is_playing(id));
+
+        widget.set_visible(id, true, &manager);
+        assert_eq!(state(&manager), AnimationState::Playing);
+
+        // A user pause holds even when scrolled back into view
+        assert_eq!(widget.toggle_playback(&manager), Some(false));
+        widget.set_visible(id, false, &manager);
+        widget.set_visible(id, true, &manager);
+        assert_eq!(state(&manager), AnimationState::Paused);
+
+        assert_eq!(widget.toggle_playback(&manager), Some(true));
+        assert_eq!(state(&manager), AnimationState::Playing);
+
+        assert!(widget.step(&manager));
+        assert!(!widget.is_playing(id));
+        assert_eq!(state(&manager), AnimationState::Paused);
+    }
+
+    #[test]
+    fn test_paused_by_default_without_autoplay() {
+        let manager = manager();
+        let id = manager.load_gif(&two_frame_gif()).unwrap();
+        let mut widget = AnimationControlWidget::new(vec![animated(id, false)]);
+
+        widget.set_visible(id, true, &manager);
+        assert!(!widget.is_playing(id));
+        assert_eq!(
+            manager.playback_status(id).unwrap().0,
+            crate::ui::animation::AnimationState::Stopped
+        );
+
+        let disabled = AnimationConfig {
+            enabled: false,
+            ..AnimationConfig::default()
+        };
+        assert!(!disabled.autoplay_now());
+    }
+
+    #[test]
+    fn test_battery_detection_reads_power_supplies() {
+        let root = tempfile::tempdir().unwrap();
+        let supply = |name: &str, files: &[(&str, &str)]| {
+            let dir = root.path().join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            for (file, value) in files {
+                std::fs::write(dir.join(file), format!("{}\n", value)).unwrap();
+            }
+        };
+
+        assert!(!battery_discharging(root.path()));
+
+        supply("BAT0", &[("type", "Battery"), ("status", "Discharging")]);
+        supply("AC", &[("type", "Mains"), ("online", "0")]);
+        assert!(battery_discharging(root.path()));
+
+        supply("AC", &[("type", "Mains"), ("online", "1")]);
+        assert!(!battery_discharging(root.path()));
+
+        assert!(!battery_discharging(&root.path().join("missing")));
+    }
+}
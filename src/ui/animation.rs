@@ -5,9 +5,11 @@
 
 use crate::ui::graphics::{GraphicsProtocol, ImageRenderer};
 use chrono::{DateTime, Utc};
-use image::{ImageError, DynamicImage, GenericImageView};
+use image::codecs::gif::GifDecoder;
+use image::{AnimationDecoder as _, DynamicImage, ImageDecoder, ImageError};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::io::Cursor;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -202,24 +204,16 @@ impl AnimationDecoder {
         source_path: Option<PathBuf>,
     ) -> AnimationResult<Animation> {
         match format {
-            AnimationFormat::Gif => self.decode_gif(data, source_path).await,
+            AnimationFormat::Gif => self.decode_gif(data, source_path),
             AnimationFormat::WebP => self.decode_webp(data, source_path).await,
             _ => Err(AnimationError::UnsupportedFormat(format!("{:?}", format))),
         }
     }
     
     /// Decode GIF animation
-    async fn decode_gif(
-        &self,
-        data: &[u8],
-        source_path: Option<PathBuf>,
-    ) -> AnimationResult<Animation> {
-        // Simple placeholder implementation for GIF support
-        // In a real implementation, this would use a GIF decoding library
-        
-        // Create a single-frame "animation" from the GIF as a static image
-        let image = image::load_from_memory_with_format(data, image::ImageFormat::Gif)?;
-        let (width, height) = image.dimensions();
+    fn decode_gif(&self, data: &[u8], source_path: Option<PathBuf>) -> AnimationResult<Animation> {
+        let decoder = GifDecoder::new(Cursor::new(data))?;
+        let (width, height) = decoder.dimensions();
         
         // Check size limits
         if width > self.settings.max_size.0 || height > self.settings.max_size.1 {
@@ -231,31 +225,48 @@ impl AnimationDecoder {
             });
         }
         
-        // Create a single frame
-        let frame = AnimationFrame {
-            image,
-            delay_ms: 100, // Default delay
-            disposal_method: FrameDisposal::None,
-            blend_method: FrameBlend::Source,
-            x_offset: 0,
-            y_offset: 0,
-        };
+        // Frames come out composited onto the full canvas
+        let mut frames = Vec::new();
+        for frame in decoder.into_frames() {
+            let frame = frame?;
+            let (numer, denom) = frame.delay().numer_denom_ms();
+            // Browsers play delays below 20ms at 100ms, and so do we
+            let delay_ms = match numer / denom.max(1) {
+                delay if delay < 20 => 100,
+                delay => delay,
+            };
+            
+            frames.push(AnimationFrame {
+                image: DynamicImage::ImageRgba8(frame.into_buffer()),
+                delay_ms,
+                disposal_method: FrameDisposal::None,
+                blend_method: FrameBlend::Source,
+                x_offset: 0,
+                y_offset: 0,
+            });
+        }
+        
+        if frames.is_empty() {
+            return Err(AnimationError::UnsupportedFormat(
+                "GIF without frames".to_string(),
+            ));
+        }
         
         let metadata = AnimationMetadata {
             id: Uuid::new_v4(),
             format: AnimationFormat::Gif,
             width,
             height,
-            frame_count: 1,
-            duration_ms: 100,
-            loop_count: Some(1),
+            frame_count: frames.len(),
+            duration_ms: frames.iter().map(|frame| frame.delay_ms).sum(),
+            loop_count: None,
             background_color: None,
             created_at: Utc::now(),
             file_size: Some(data.len() as u64),
             source_path,
         };
         
-        Ok(Animation::new(metadata, vec![frame], self.settings.clone()))
+        Ok(Animation::new(metadata, frames, self.settings.clone()))
     }
     
     /// Decode WebP animation (placeholder)
@@ -341,6 +352,15 @@ impl Animation {
         }
     }
     
+    /// Pause playback and show the next frame, wrapping at the end
+    pub fn step(&mut self) {
+        if self.frames.is_empty() {
+            return;
+        }
+        self.state = AnimationState::Paused;
+        self.current_frame = (self.current_frame + 1) % self.frames.len();
+    }
+    
     /// Stop playback and reset to first frame
     pub fn stop(&mut self) {
         self.state = AnimationState::Stopped;
@@ -578,6 +598,43 @@ impl AnimationManager {
         }
     }
     
+    pub fn step_animation(&self, id: Uuid) -> bool {
+        let mut animations = self.animations.lock().unwrap();
+        if let Some(animation) = animations.iter_mut().find(|a| a.metadata.id == id) {
+            animation.step();
+            true
+        } else {
+            false
+        }
+    }
+    
+    /// Decode a GIF held in memory, for callers outside an async context
+    pub fn load_gif(&self, data: &[u8]) -> AnimationResult<Uuid> {
+        let animation = self.decoder.decode_gif(data, None)?;
+        let id = animation.metadata.id;
+        self.animations.lock().unwrap().push(animation);
+        Ok(id)
+    }
+    
+    /// Get playback state and `(current, total)` frame position
+    pub fn playback_status(&self, id: Uuid) -> Option<(AnimationState, usize, usize)> {
+        let animations = self.animations.lock().unwrap();
+        animations
+            .iter()
+            .find(|a| a.metadata.id == id)
+            .map(|a| (a.state(), a.current_frame(), a.frame_count()))
+    }
+    
+    /// Advance a playing animation and return a copy of the frame to show
+    pub fn current_frame_image(&self, id: Uuid) -> Option<DynamicImage> {
+        let mut animations = self.animations.lock().unwrap();
+        let animation = animations.iter_mut().find(|a| a.metadata.id == id)?;
+        animation.update();
+        animation
+            .current_frame_data()
+            .map(|frame| frame.image.clone())
+    }
+    
     /// Get current frame for rendering
     pub async fn render_animation(
         &self,
@@ -689,4 +746,52 @@ mod tests {
         assert!(decoder.supports_format(&AnimationFormat::Gif));
         assert!(!decoder.supports_format(&AnimationFormat::Apng));
     }
-}
\ No newline at end of file
+
+    fn encode_gif(frame_count: u8) -> Vec<u8> {
+        use image::codecs::gif::GifEncoder;
+        use image::{Delay, Frame, Rgba, RgbaImage};
+
+        let mut data = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut data);
+            for index in 0..frame_count {
+                let image = RgbaImage::from_pixel(4, 4, Rgba([index * 60, 0, 0, 255]));
+                let frame = Frame::from_parts(image, 0, 0, Delay::from_numer_denom_ms(40, 1));
+                encoder.encode_frame(frame).unwrap();
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_gif_decodes_every_frame_and_steps() {
+        let manager = AnimationManager::new(
+            Arc::new(ImageRenderer::new(
+                crate::ui::graphics::RenderConfig::default(),
+            )),
+            AnimationSettings::default(),
+        );
+        let id = manager.load_gif(&encode_gif(3)).unwrap();
+
+        let metadata = manager.get_metadata(id).unwrap();
+        assert_eq!(metadata.frame_count, 3);
+        assert_eq!(metadata.duration_ms, 120);
+        assert_eq!(metadata.loop_count, None);
+
+        manager.play_animation(id);
+        assert!(manager.step_animation(id));
+        assert_eq!(
+            manager.playback_status(id),
+            Some((AnimationState::Paused, 1, 3))
+        );
+
+        // Stepping wraps, and a paused animation stays on its frame
+        manager.step_animation(id);
+        manager.step_animation(id);
+        assert!(manager.current_frame_image(id).is_some());
+        assert_eq!(
+            manager.playback_status(id),
+            Some((AnimationState::Paused, 0, 3))
+        );
+    }
+}
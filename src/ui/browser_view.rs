@@ -0,0 +1,214 @@
+//! Open a message in the web browser
+//!
+//! Some HTML mail can't be shown faithfully in a terminal. This writes the
+//! sanitized HTML to a temporary file, with inline (`cid:`) images embedded
+//! as data URIs, and opens it with the `text/html` handler from
+//! `opener.toml`, or the default browser. Scripts are always removed.
+//! With `block_remote_content` set, remote images are replaced by their alt
+//! text and stylesheet URLs are dropped, and a Content-Security-Policy
+//! stops the browser from fetching anything else. Tracking pixels are
+//! always removed, and with `match_theme` the colors follow the theme. The file is deleted after `cleanup_delay_secs`,
+//! once the browser has had time to read it.
+
+use crate::email::StoredAttachment;
+use crate::html::{EmailSanitizer, RecolorPalette};
+use crate::theme::Theme;
+use crate::ui::opener::OpenerConfig;
+use anyhow::Result;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::fs;
+
+/// Browser view configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BrowserViewConfig {
+    /// Leave out remote images and other content loaded from the network
+    pub block_remote_content: bool,
+    /// Seconds before the temporary file is deleted
+    pub cleanup_delay_secs: u64,
+    /// Recolor the message to the current theme, so a white email doesn't
+    /// light up a dark setup
+    pub match_theme: bool,
+}
+
+impl Default for BrowserViewConfig {
+    fn default() -> Self {
+        Self {
+            block_remote_content: true,
+            cleanup_delay_secs: 60,
+            match_theme: true,
+        }
+    }
+}
+
+impl BrowserViewConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "browser_view.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// Data URIs for the inline images of a message, keyed by Content-ID
+///
+/// Attachments without stored data are skipped.
+pub async fn inline_images(attachments: &[StoredAttachment]) -> Vec<(String, String)> {
+    let mut images = Vec::new();
+
+    for attachment in attachments {
+        let Some(ref content_id) = attachment.content_id else {
+            continue;
+        };
+        let data = match (&attachment.data, &attachment.file_path) {
+            (Some(data), _) => Cow::Borrowed(data),
+            (None, Some(path)) => match fs::read(path).await {
+                Ok(data) => Cow::Owned(data),
+                Err(e) => {
+                    tracing::debug!("Inline image {} unreadable: {}", attachment.filename, e);
+                    continue;
+                }
+            },
+            (None, None) => continue,
+        };
+
+        images.push((
+            content_id.trim_matches(['<', '>']).to_string(),
+            format!(
+                "data:{};base64,{}",
+                attachment.content_type,
+                base64::engine::general_purpose::STANDARD.encode(data.as_ref())
+            ),
+        ));
+    }
+
+    images
+}
+
+/// Build a standalone, sanitized HTML document for the browser
+///
+/// With a `theme`, inline colors are recolored to suit it and the page
+/// takes the theme's background and text colors.
+pub fn build_document(
+    subject: &str,
+    html: &str,
+    inline_images: &[(String, String)],
+    block_remote_content: bool,
+    theme: Option<&Theme>,
+) -> String {
+    let mut html = html.to_string();
+    for (content_id, data_uri) in inline_images {
+        html = html.replace(&format!("cid:{}", content_id), data_uri);
+    }
+
+    let mut sanitizer = EmailSanitizer::new().load_remote_images(!block_remote_content);
+    if let Some(theme) = theme {
+        sanitizer = sanitizer.recolor_for(theme);
+    }
+    let body = sanitizer.sanitize(&html).html;
+
+    let page_style = theme
+        .map(|theme| {
+            let palette = RecolorPalette::from_theme(theme);
+            let hex = |(r, g, b): (u8, u8, u8)| format!("#{:02x}{:02x}{:02x}", r, g, b);
+            format!(
+                "<style>body {{ background-color: {}; color: {}; }}</style>\n",
+                hex(palette.background),
+                hex(palette.foreground)
+            )
+        })
+        .unwrap_or_default();
+
+    let image_sources = if block_remote_content {
+        "data:"
+    } else {
+        "data: https: http:"
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'none'; style-src 'unsafe-inline'; img-src {}\">\n\
+         <title>{}</title>\n{}</head>\n<body>\n{}\n</body>\n</html>\n",
+        image_sources,
+        escape_text(subject),
+        page_style,
+        body
+    )
+}
+
+/// Write the document to a temporary file and open it in the browser
+///
+/// The file is removed after the configured delay, or when the app exits.
+pub async fn open_in_browser(
+    document: &str,
+    config: &BrowserViewConfig,
+    opener: &OpenerConfig,
+) -> Result<PathBuf> {
+    let temp_path = tempfile::Builder::new()
+        .prefix("comunicado-")
+        .suffix(".html")
+        .tempfile()?
+        .into_temp_path();
+    fs::write(&temp_path, document).await?;
+    let path = temp_path.to_path_buf();
+
+    opener.open_file(&path, "text/html").await?;
+
+    let delay = Duration::from_secs(config.cleanup_delay_secs);
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        if let Err(e) = temp_path.close() {
+            tracing::debug!("Failed to remove browser view file: {}", e);
+        }
+    });
+
+    Ok(path)
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_document() {
+        let html = r#"<p onclick="steal()">Hi <b>there</b></p>
+            <script>alert(1)</script>
+            <img src="cid:logo@example.com" alt="logo">
+            <img src="https://tracker.example/pixel.gif">
+            <div style="background: url(https://tracker.example/bg.png)">Styled</div>"#;
+        let images = vec![(
+            "logo@example.com".to_string(),
+            "data:image/png;base64,iVBORw0KGgo=".to_string(),
+        )];
+
+        let blocked = build_document("Q1 <report>", html, &images, true, None);
+        assert!(blocked.contains("<title>Q1 &lt;report&gt;</title>"));
+        assert!(blocked.contains("img-src data:\""));
+        assert!(blocked.contains(r#"src="data:image/png;base64,iVBORw0KGgo=""#));
+        assert!(blocked.contains("<b>there</b>"));
+        assert!(!blocked.contains("script"));
+        assert!(!blocked.contains("onclick"));
+        assert!(!blocked.contains("tracker.example"));
+
+        let allowed = build_document("Report", html, &images, false, None);
+        assert!(allowed.contains("https://tracker.example/pixel.gif"));
+        assert!(allowed.contains("url(https://tracker.example/bg.png)"));
+        assert!(!allowed.contains("script"));
+    }
+}
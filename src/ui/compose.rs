@@ -1,17 +1,30 @@
+use crate::ai::smart_compose::{ReferencedEmail, SentimentType, StylePreferences};
+use crate::ai::{
+    AIResult, ComposeSuggestion, CompositionContext, EmailType, ReplyTone, SmartComposeService,
+};
 use crate::contacts::{ContactAutocomplete, ContactsManager};
-use crate::spell::{SpellCheckResult, SpellChecker};
+use crate::email::{MailPriority, QuotePosition};
+use crate::spell::{config::SpellCheckConfig, SpellCheckResult, SpellChecker};
 use crate::theme::Theme;
+use crate::ui::compose_attachments::{self, ComposeAttachment};
+use crate::ui::compose_wrap::{self, ComposeWrapConfig, WrapMode};
 use crate::ui::external_editor::{ExternalEditor, EditorConfig};
+use crate::ui::file_picker::{FilePicker, FilePickerAction};
+use crate::ui::preview_limits::format_size;
+use crate::ui::schedule_picker::{SchedulePicker, SchedulePickerAction};
 use crossterm::event::KeyModifiers;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Text},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 use std::sync::Arc;
 
+/// Pause in typing before the text is spell checked again
+const SPELL_CHECK_DELAY: std::time::Duration = std::time::Duration::from_millis(400);
+
 /// Email composition UI with contact autocomplete and spell checking
 pub struct ComposeUI {
     #[allow(dead_code)]
@@ -40,6 +53,15 @@ pub struct ComposeUI {
     current_spell_error: usize,
     spell_suggestions: Vec<String>,
     spell_suggestion_selected: usize,
+    /// Field the spell check result belongs to
+    spell_check_field: ComposeField,
+    /// When to check spelling again; edits push it back so typing isn't slowed
+    spell_check_due: Option<std::time::Instant>,
+    /// Misspellings from this offset on are hidden until the next check, as
+    /// edits have moved them
+    spell_stale_from: Option<usize>,
+    /// Whether the spell check settings and dictionary have been loaded
+    spell_checker_ready: bool,
 
     // Spell check configuration state
     is_spell_config_visible: bool,
@@ -66,6 +88,91 @@ pub struct ComposeUI {
     last_auto_save: Option<std::time::Instant>,
     auto_save_interval: std::time::Duration,
     has_auto_save_changes: bool,
+
+    /// Account a reply or forward is sent from, the one holding the original
+    account_id: Option<String>,
+    /// Which recipients a reply was addressed to and why
+    reply_note: Option<String>,
+    /// Threading headers of a reply
+    in_reply_to: Option<String>,
+    references: Option<String>,
+    /// Priority set with Ctrl+T
+    priority: MailPriority,
+    /// PGP encryption and signing, toggled with Ctrl+G and Ctrl+K
+    encrypt: bool,
+    sign: bool,
+    /// Recipients without a PGP key, while asking whether to send unencrypted
+    missing_keys: Option<Vec<String>>,
+    /// Open while choosing when to send the message, with Ctrl+L
+    schedule_picker: Option<SchedulePicker>,
+    /// Files sent along with the message
+    attachments: Vec<ComposeAttachment>,
+    /// Open while choosing files to attach, with Ctrl+A
+    file_picker: Option<FilePicker>,
+
+    /// Wrap mode and column; the mode can be toggled per message
+    wrap_config: ComposeWrapConfig,
+
+    /// Inline completion service and how long typing must pause before it
+    /// is asked
+    smart_compose: Option<(Arc<SmartComposeService>, std::time::Duration)>,
+    /// New message, reply or forward, for the completion prompt
+    email_type: EmailType,
+    /// Message being replied to
+    referenced_email: Option<ReferencedEmail>,
+    /// When to ask for a completion; edits to the body push it back
+    completion_due: Option<std::time::Instant>,
+    /// Completion request in flight, aborted when typing goes on
+    completion_task: Option<tokio::task::JoinHandle<AIResult<Option<ComposeSuggestion>>>>,
+    /// Predicted continuation shown dimmed after the cursor
+    ghost_completion: Option<ComposeSuggestion>,
+    /// AI reply draft written into the body, which can be redrafted
+    reply_draft: Option<ReplyDraft>,
+}
+
+/// An AI-drafted reply and what is needed to draft it again in another tone
+struct ReplyDraft {
+    assistant: Arc<crate::email::AIEmailAssistant>,
+    message: crate::email::StoredMessage,
+    tone: ReplyTone,
+    task: Option<tokio::task::JoinHandle<anyhow::Result<String>>>,
+    /// Stream the draft request writes its partial answer under
+    operation_id: uuid::Uuid,
+    /// Pieces of the draft streamed so far but not yet shown
+    partial: Option<tokio::sync::mpsc::UnboundedReceiver<String>>,
+    /// Draft text streamed so far
+    streamed: String,
+    /// Body lines, line and cursor from before the draft went in
+    body_before: Option<(Vec<String>, usize, usize)>,
+    /// Body lines right after the draft went in; any difference is the
+    /// user's edits
+    body_after: Vec<String>,
+    /// Tone to redraft in once replacing the edits is confirmed
+    confirm_tone: Option<ReplyTone>,
+}
+
+impl ReplyDraft {
+    /// Stop the draft request in flight and close its stream
+    fn abort(&mut self) {
+        let Some(task) = self.task.take() else {
+            return;
+        };
+        task.abort();
+        self.partial = None;
+        let assistant = self.assistant.clone();
+        let operation_id = self.operation_id;
+        tokio::spawn(async move {
+            let _ = assistant.streaming().end_stream(operation_id).await;
+        });
+    }
+}
+
+impl Drop for ReplyDraft {
+    fn drop(&mut self) {
+        if self.task.is_some() && tokio::runtime::Handle::try_current().is_ok() {
+            self.abort();
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -104,6 +211,10 @@ impl ComposeUI {
             current_spell_error: 0,
             spell_suggestions: Vec::new(),
             spell_suggestion_selected: 0,
+            spell_check_field: ComposeField::Body,
+            spell_check_due: None,
+            spell_stale_from: None,
+            spell_checker_ready: false,
             is_spell_config_visible: false,
             available_languages,
             language_selected: 0,
@@ -120,14 +231,36 @@ impl ComposeUI {
             last_auto_save: None,
             auto_save_interval: std::time::Duration::from_secs(30), // Auto-save every 30 seconds
             has_auto_save_changes: false,
+            account_id: None,
+            reply_note: None,
+            in_reply_to: None,
+            references: None,
+            priority: MailPriority::Normal,
+            encrypt: false,
+            sign: false,
+            attachments: Vec::new(),
+            file_picker: None,
+            missing_keys: None,
+            schedule_picker: None,
+            wrap_config: ComposeWrapConfig::default(),
+            smart_compose: None,
+            email_type: EmailType::New,
+            referenced_email: None,
+            completion_due: None,
+            completion_task: None,
+            ghost_completion: None,
+            reply_draft: None,
         }
     }
 
-    /// Create a new compose UI for replying to a message
+    /// Create a new compose UI for replying to a message, with the quoted
+    /// original and the cursor placed for top- or bottom-posting
     pub fn new_reply(
         contacts_manager: Arc<ContactsManager>,
         reply_to: &str,
         subject: &str,
+        quoted_body: &str,
+        position: QuotePosition,
     ) -> Self {
         let mut compose = Self::new(contacts_manager);
         compose.to_field = reply_to.to_string();
@@ -136,13 +269,78 @@ impl ComposeUI {
         } else {
             format!("Re: {}", subject)
         };
+        if !quoted_body.is_empty() {
+            compose.body_text = quoted_body.to_string();
+            compose.body_lines = quoted_body.split('\n').map(|s| s.to_string()).collect();
+            if position == QuotePosition::Bottom {
+                compose.body_line_index = compose.body_lines.len() - 1;
+            }
+        }
         compose.current_field = ComposeField::Body;
         compose.to_cursor = compose.to_field.len();
         compose.subject_cursor = compose.subject_field.len();
+        compose.email_type = EmailType::Reply;
+        compose.referenced_email = Some(ReferencedEmail {
+            subject: subject.to_string(),
+            content_summary: quoted_body
+                .lines()
+                .map(|line| line.trim_start_matches(['>', ' ']))
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n")
+                .chars()
+                .take(1_000)
+                .collect(),
+            sender: reply_to.to_string(),
+            key_points: Vec::new(),
+            sentiment: SentimentType::Neutral,
+        });
         compose
     }
 
-    /// Create a new compose UI for forwarding a message
+    /// Address a reply to the chosen recipients and explain the choice in
+    /// the header
+    pub fn set_reply_recipients(&mut self, recipients: &crate::email::ReplyRecipients) {
+        self.to_field = recipients.to.join(", ");
+        self.cc_field = recipients.cc.join(", ");
+        self.to_cursor = self.to_field.len();
+        self.cc_cursor = self.cc_field.len();
+        self.reply_note = Some(format!("Replying to {}", recipients.reason));
+    }
+
+    /// Set the In-Reply-To and References headers sent with a reply
+    pub fn set_reply_threading(&mut self, in_reply_to: Option<String>, references: Option<String>) {
+        self.in_reply_to = in_reply_to;
+        self.references = references;
+    }
+
+    /// Insert text typed elsewhere, such as a quick reply, at the body cursor
+    pub fn insert_body_text(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let mut lines: Vec<String> = text.split('\n').map(|s| s.to_string()).collect();
+        let current = &mut self.body_lines[self.body_line_index];
+        let cursor = self.body_cursor.min(current.len());
+        let tail = current.split_off(cursor);
+        current.push_str(&lines[0]);
+        let last = lines.len() - 1;
+        if last == 0 {
+            self.body_cursor = current.len();
+            current.push_str(&tail);
+        } else {
+            self.body_cursor = lines[last].len();
+            lines[last].push_str(&tail);
+            let at = self.body_line_index + 1;
+            self.body_lines.splice(at..at, lines.drain(1..));
+            self.body_line_index += last;
+        }
+        self.body_text = self.body_lines.join("\n");
+        self.is_modified = true;
+    }
+
+    /// Create a new compose UI for forwarding a message, with the body
+    /// already holding the forwarded content
     pub fn new_forward(
         contacts_manager: Arc<ContactsManager>,
         subject: &str,
@@ -154,16 +352,32 @@ impl ComposeUI {
         } else {
             format!("Fwd: {}", subject)
         };
-        compose.body_text = format!("\n\n--- Forwarded Message ---\n{}", original_body);
+        compose.body_text = original_body.to_string();
         compose.body_lines = compose.body_text.lines().map(|s| s.to_string()).collect();
         if compose.body_lines.is_empty() {
             compose.body_lines.push(String::new());
         }
         compose.current_field = ComposeField::To;
         compose.subject_cursor = compose.subject_field.len();
+        compose.email_type = EmailType::Forward;
         compose
     }
 
+    /// Set how the body is wrapped
+    pub fn set_wrap_config(&mut self, config: ComposeWrapConfig) {
+        self.wrap_config = config;
+    }
+
+    /// Suggest how the body continues once typing pauses for `delay`
+    pub fn set_smart_compose(&mut self, service: Arc<SmartComposeService>, delay: std::time::Duration) {
+        self.smart_compose = Some((service, delay));
+    }
+
+    /// Switch between soft and hard wrapping for this message
+    fn toggle_wrap_mode(&mut self) {
+        self.wrap_config.mode = self.wrap_config.mode.toggled();
+    }
+
     /// Render the compose UI
     pub fn render(&mut self, f: &mut Frame, area: Rect, theme: &Theme) {
         // Main compose window
@@ -176,15 +390,19 @@ impl ComposeUI {
         f.render_widget(block, area);
 
         // Layout: header fields + body
+        let note_height = u16::from(self.reply_note.is_some());
+        let attachments_height = u16::from(!self.attachments.is_empty());
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(2), // To field
-                Constraint::Length(2), // Cc field
-                Constraint::Length(2), // Bcc field
-                Constraint::Length(2), // Subject field
-                Constraint::Length(1), // Separator
-                Constraint::Min(0),    // Body
+                Constraint::Length(2),                  // To field
+                Constraint::Length(2),                  // Cc field
+                Constraint::Length(2),                  // Bcc field
+                Constraint::Length(2),                  // Subject field
+                Constraint::Length(note_height),        // Reply recipients note
+                Constraint::Length(attachments_height), // Attached files
+                Constraint::Length(1),                  // Separator
+                Constraint::Min(0),                     // Body
             ])
             .split(inner);
 
@@ -226,13 +444,26 @@ impl ComposeUI {
             theme,
         );
 
+        if let Some(ref note) = self.reply_note {
+            let note = Paragraph::new(format!("↩ {}", note)).style(
+                Style::default()
+                    .fg(theme.colors.palette.text_muted)
+                    .add_modifier(Modifier::ITALIC),
+            );
+            f.render_widget(note, chunks[4]);
+        }
+
+        if !self.attachments.is_empty() {
+            self.render_attachments(f, chunks[5], theme);
+        }
+
         // Separator line
-        let separator = Paragraph::new("─".repeat(chunks[4].width as usize))
+        let separator = Paragraph::new("─".repeat(chunks[6].width as usize))
             .style(Style::default().fg(Color::Gray));
-        f.render_widget(separator, chunks[4]);
+        f.render_widget(separator, chunks[6]);
 
         // Body area
-        self.render_body(f, chunks[5], theme);
+        self.render_body(f, chunks[7], theme);
 
         // Render autocomplete suggestions if visible
         if self.contact_autocomplete.is_visible() {
@@ -255,6 +486,14 @@ impl ComposeUI {
             self.render_editor_config_popup(f, area, theme);
         }
 
+        if let Some(ref mut picker) = self.schedule_picker {
+            picker.render(f, area, theme);
+        }
+
+        if let Some(ref mut picker) = self.file_picker {
+            picker.render(f, area, theme);
+        }
+
         // Status line at bottom
         self.render_status_line(f, area, theme);
     }
@@ -316,6 +555,67 @@ impl ComposeUI {
         f.render_widget(input_paragraph, chunks[1]);
     }
 
+    /// Render the attached files with their total size, warning when the
+    /// message gets too large for most providers
+    fn render_attachments(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let names: Vec<String> = self
+            .attachments
+            .iter()
+            .map(|attachment| {
+                format!(
+                    "{} ({})",
+                    attachment.filename,
+                    format_size(attachment.size as usize)
+                )
+            })
+            .collect();
+        let total = self.attachments_size();
+
+        let mut spans = vec![
+            Span::styled("📎 ", Style::default().fg(theme.colors.palette.accent)),
+            Span::raw(names.join(", ")),
+            Span::styled(
+                format!("  · {} total", format_size(total as usize)),
+                Style::default().fg(theme.colors.palette.text_muted),
+            ),
+        ];
+        if let Some(warning) = compose_attachments::size_warning(total) {
+            spans.push(Span::styled(
+                format!("  ⚠ {}", warning),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Bytes attached to the message
+    fn attachments_size(&self) -> u64 {
+        self.attachments.iter().map(|attachment| attachment.size).sum()
+    }
+
+    /// Open the file picker, in the directory a file was last attached from
+    fn open_file_picker(&mut self) {
+        let dir = self
+            .attachments
+            .last()
+            .and_then(|attachment| attachment.path.parent())
+            .map(|dir| dir.to_path_buf())
+            .or_else(dirs::home_dir)
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| std::path::PathBuf::from("/"));
+        self.file_picker = Some(FilePicker::new(dir, self.attachments_size()));
+    }
+
+    /// Add picked files, skipping any already attached
+    fn add_attachments(&mut self, picked: Vec<ComposeAttachment>) {
+        for attachment in picked {
+            if !self.attachments.iter().any(|a| a.path == attachment.path) {
+                self.attachments.push(attachment);
+                self.is_modified = true;
+            }
+        }
+    }
+
     /// Render the email body
     fn render_body(&self, f: &mut Frame, area: Rect, theme: &Theme) {
         let is_focused = self.current_field == ComposeField::Body;
@@ -326,31 +626,61 @@ impl ComposeUI {
             theme.get_component_style("input", false)
         };
 
+        let column = self.wrap_config.column;
+        let title = if column > 0 {
+            format!("Message Body ({} wrap at {})", self.wrap_config.mode.label(), column)
+        } else {
+            format!("Message Body ({} wrap)", self.wrap_config.mode.label())
+        };
         let block = Block::default()
-            .title("Message Body")
+            .title(title)
             .borders(Borders::ALL)
             .border_style(style);
 
-        let inner = block.inner(area);
+        let mut inner = block.inner(area);
         f.render_widget(block, area);
 
+        // Wrap at the column rather than the pane, with the guide just past it
+        if column > 0 && (column as u16) < inner.width {
+            if self.wrap_config.show_guide {
+                let guide_area = Rect {
+                    x: inner.x + column as u16,
+                    width: 1,
+                    ..inner
+                };
+                let guide = Paragraph::new(vec![Line::from("│"); guide_area.height as usize])
+                    .style(Style::default().fg(theme.colors.palette.border));
+                f.render_widget(guide, guide_area);
+            }
+            inner.width = column as u16;
+        }
+
         // Create text with cursor and spell check highlighting if focused
         let mut text = Text::default();
 
+        let highlight_spelling =
+            self.spell_check_enabled && self.spell_check_field == ComposeField::Body;
         for (line_idx, line) in self.body_lines.iter().enumerate() {
             if is_focused && line_idx == self.body_line_index {
                 // Show cursor on current line with spell check highlighting
-                let line_with_cursor =
-                    if self.spell_check_enabled && self.current_field == ComposeField::Body {
-                        self.create_highlighted_line_with_cursor(line, self.body_cursor)
-                    } else {
-                        let mut chars: Vec<char> = line.chars().collect();
-                        let cursor_pos = self.body_cursor.min(chars.len());
-                        chars.insert(cursor_pos, '|');
-                        Line::from(chars.into_iter().collect::<String>())
-                    };
+                let mut line_with_cursor = if highlight_spelling {
+                    self.create_highlighted_line_with_cursor(line, self.body_cursor)
+                } else {
+                    let mut chars: Vec<char> = line.chars().collect();
+                    let cursor_pos = self.body_cursor.min(chars.len());
+                    chars.insert(cursor_pos, '|');
+                    Line::from(chars.into_iter().collect::<String>())
+                };
+                if let Some(ghost) = &self.ghost_completion {
+                    line_with_cursor.spans.push(Span::styled(
+                        ghost.content.clone(),
+                        Style::default()
+                            .fg(theme.colors.palette.text_muted)
+                            .add_modifier(Modifier::ITALIC),
+                    ));
+                }
                 text.lines.push(line_with_cursor);
-            } else if self.spell_check_enabled && self.current_field == ComposeField::Body {
+            } else if highlight_spelling {
                 // Highlight misspelled words without cursor
                 text.lines
                     .push(self.create_highlighted_line(line, line_idx));
@@ -390,16 +720,29 @@ impl ComposeUI {
             height: 1,
         };
 
-        let status_text = if self.contact_autocomplete.is_visible() {
+        let status_text = if let Some(ref missing) = self.missing_keys {
+            format!(
+                "No trusted PGP key for {} | u Send unencrypted | Esc Abort",
+                missing.join(", ")
+            )
+        } else if let Some(tone) = self.reply_draft.as_ref().and_then(|d| d.confirm_tone) {
+            format!("Replace your edits with a {} draft? | y Replace | Any other key Keep", tone)
+        } else if let Some(draft) = self.reply_draft.as_ref().filter(|d| d.task.is_some()) {
+            format!("Drafting {} reply... | Ctrl+R Try {} instead | Esc Stop", draft.tone, draft.tone.next())
+        } else if self.schedule_picker.is_some() {
+            "Choose when to send | Enter Schedule | Esc Back".to_string()
+        } else if self.file_picker.is_some() {
+            "Choose files to attach | Space Mark | Enter Attach | Esc Back".to_string()
+        } else if self.contact_autocomplete.is_visible() {
             "↑↓ Navigate suggestions | Tab Complete | Esc Cancel | Enter Select".to_string()
         } else if self.is_editor_config_visible {
             "Editor config | Esc Close".to_string()
         } else if self.is_spell_config_visible {
             "↑↓ Select language | Enter Apply | Esc/F10 Close".to_string()
         } else if self.spell_check_enabled && self.is_spell_check_visible {
-            "F7 Toggle | F8/F9 Next/Prev error | F10 Config | ↑↓ Navigate suggestions | Tab Apply | Esc Cancel".to_string()
+            "1-9 Replace | ↑↓ Select | Enter/Tab Apply | 0 Add to dictionary | Ctrl+N/Ctrl+P Next/Prev error | Esc Close".to_string()
         } else {
-            format!("Tab Next field | F1 Send | F2 Save | F7 Spell check | F8/F9 Errors | F10 Config | Ctrl+E Editor ({}) | Esc Cancel | @ Contact", self.editor_config.name)
+            format!("Tab Next field | F1 Send | F2 Save | Ctrl+L Send later | Ctrl+A Attach{} | Ctrl+Z Spell check | Ctrl+N/Ctrl+P Spelling errors | Ctrl+, Language | Ctrl+E Editor ({}) | Ctrl+W Wrap: {} | Ctrl+T Priority: {} | Ctrl+G Encrypt: {} | Ctrl+K Sign: {}{} | Esc Cancel | @ Contact", if self.attachments.is_empty() { "" } else { " | Ctrl+X Remove last" }, self.editor_config.name, self.wrap_config.mode.label(), self.priority.label(), on_off(self.encrypt), on_off(self.sign), self.reply_draft.as_ref().map(|d| format!(" | Ctrl+R Redraft {}", d.tone.next())).unwrap_or_default())
         };
 
         let modified_indicator = if self.is_modified { " [Modified]" } else { "" };
@@ -417,7 +760,7 @@ impl ComposeUI {
             if let Some(error) = result.misspelled_words.get(self.current_spell_error) {
                 // Calculate position for spell check popup
                 let popup_width = 60;
-                let popup_height = (self.spell_suggestions.len() + 5).min(12) as u16;
+                let popup_height = (self.spell_suggestions.len().max(1) + 7).min(14) as u16;
 
                 let popup_area = Rect {
                     x: compose_area.x + compose_area.width.saturating_sub(popup_width + 2),
@@ -436,12 +779,21 @@ impl ComposeUI {
                     Line::from("Suggestions:"),
                 ];
 
+                if self.spell_suggestions.is_empty() {
+                    lines.push(Line::from("  (none)"));
+                }
                 for (i, suggestion) in self.spell_suggestions.iter().enumerate() {
                     let marker = if i == self.spell_suggestion_selected {
                         "▶ "
                     } else {
                         "  "
                     };
+                    // The first nine can be picked with their number key
+                    let number = if i < 9 {
+                        format!("{} ", i + 1)
+                    } else {
+                        "  ".to_string()
+                    };
                     let style = if i == self.spell_suggestion_selected {
                         Style::default()
                             .bg(Color::DarkGray)
@@ -451,6 +803,7 @@ impl ComposeUI {
                     };
                     lines.push(Line::from(vec![
                         ratatui::text::Span::styled(marker, style),
+                        ratatui::text::Span::styled(number, style),
                         ratatui::text::Span::styled(suggestion, style),
                     ]));
                 }
@@ -471,7 +824,7 @@ impl ComposeUI {
                 };
                 lines.push(Line::from(vec![
                     ratatui::text::Span::styled(add_marker, add_style),
-                    ratatui::text::Span::styled("Add to dictionary", add_style),
+                    ratatui::text::Span::styled("0 Add to dictionary", add_style),
                 ]));
 
                 let content = Text::from(lines);
@@ -634,82 +987,98 @@ impl ComposeUI {
     }
 
     /// Create highlighted line with misspelled words marked
-    fn create_highlighted_line(&self, line: &str, _line_idx: usize) -> Line<'static> {
-        if let Some(ref result) = self.spell_check_result {
-            let mut spans = Vec::new();
-            let mut last_pos = 0;
-
-            // Find misspelled words in this line
-            let line_start = self.calculate_line_offset(_line_idx);
-            let line_end = line_start + line.len();
-
-            for error in &result.misspelled_words {
-                if error.position >= line_start && error.position < line_end {
-                    let error_start = error.position - line_start;
-                    let error_end = error_start + error.length;
-
-                    // Add text before error
-                    if error_start > last_pos {
-                        spans.push(ratatui::text::Span::raw(
-                            line[last_pos..error_start].to_string(),
-                        ));
-                    }
+    fn create_highlighted_line(&self, line: &str, line_idx: usize) -> Line<'static> {
+        let Some(ref result) = self.spell_check_result else {
+            return Line::from(line.to_string());
+        };
 
-                    // Add highlighted error
-                    let error_style = if self.current_spell_error < result.misspelled_words.len()
-                        && result.misspelled_words[self.current_spell_error].position
-                            == error.position
-                    {
-                        Style::default()
-                            .bg(Color::Red)
-                            .fg(Color::White)
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().bg(Color::Yellow).fg(Color::Black)
-                    };
+        let mut spans = Vec::new();
+        let mut last_pos = 0;
 
-                    spans.push(ratatui::text::Span::styled(
-                        line[error_start..error_end.min(line.len())].to_string(),
-                        error_style,
-                    ));
+        // Find misspelled words in this line
+        let line_start = self.calculate_line_offset(line_idx);
+        let line_end = line_start + line.len();
+        let stale_from = self.spell_stale_from.unwrap_or(usize::MAX);
 
-                    last_pos = error_end.min(line.len());
-                }
-            }
+        for (index, error) in result.misspelled_words.iter().enumerate() {
+            if error.position < line_start
+                || error.position >= line_end
+                || error.position + error.length >= stale_from
+            {
+                continue;
+            }
+            let error_start = error.position - line_start;
+            let error_end = (error_start + error.length).min(line.len());
+            let (Some(before), Some(word)) = (
+                line.get(last_pos..error_start),
+                line.get(error_start..error_end),
+            ) else {
+                continue;
+            };
 
-            // Add remaining text
-            if last_pos < line.len() {
-                spans.push(ratatui::text::Span::raw(line[last_pos..].to_string()));
+            // Add text before error
+            if !before.is_empty() {
+                spans.push(ratatui::text::Span::raw(before.to_string()));
             }
 
-            Line::from(spans)
-        } else {
-            Line::from(line.to_string())
+            // Add highlighted error; the one shown in the popup stands out
+            let error_style = if self.is_spell_check_visible && index == self.current_spell_error {
+                Style::default()
+                    .bg(Color::Red)
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+                    .fg(Color::Red)
+                    .add_modifier(Modifier::UNDERLINED)
+            };
+            spans.push(ratatui::text::Span::styled(word.to_string(), error_style));
+
+            last_pos = error_end;
         }
+
+        // Add remaining text
+        if let Some(rest) = line.get(last_pos..) {
+            if !rest.is_empty() {
+                spans.push(ratatui::text::Span::raw(rest.to_string()));
+            }
+        }
+
+        Line::from(spans)
     }
 
     /// Create highlighted line with cursor
     fn create_highlighted_line_with_cursor(&self, line: &str, cursor_pos: usize) -> Line<'static> {
         let highlighted_line = self.create_highlighted_line(line, self.body_line_index);
 
-        // Insert cursor character
-        let cursor_inserted = if cursor_pos <= line.len() {
-            let mut line_chars: Vec<char> = line.chars().collect();
-            line_chars.insert(cursor_pos, '|');
-            line_chars.into_iter().collect::<String>()
-        } else {
-            format!("{}|", line)
-        };
-
-        // For simplicity, recreate the line with cursor - in a more sophisticated version
-        // we would preserve the highlighting and insert cursor properly
-        let spans = highlighted_line.spans;
-        if spans.is_empty() {
-            Line::from(cursor_inserted)
-        } else {
-            // Use highlighted version but note this is simplified
-            Line::from(spans)
+        // Insert the cursor into whichever span it falls in
+        let mut spans = Vec::new();
+        let mut remaining = cursor_pos.min(line.chars().count());
+        let mut cursor_placed = false;
+        for span in highlighted_line.spans {
+            let length = span.content.chars().count();
+            if cursor_placed || remaining > length {
+                remaining = remaining.saturating_sub(length);
+                spans.push(span);
+                continue;
+            }
+            let (before, after): (String, String) = {
+                let chars: Vec<char> = span.content.chars().collect();
+                (
+                    chars[..remaining].iter().collect(),
+                    chars[remaining..].iter().collect(),
+                )
+            };
+            spans.push(ratatui::text::Span::styled(before, span.style));
+            spans.push(ratatui::text::Span::raw("|"));
+            spans.push(ratatui::text::Span::styled(after, span.style));
+            cursor_placed = true;
+        }
+        if !cursor_placed {
+            spans.push(ratatui::text::Span::raw("|"));
         }
+
+        Line::from(spans)
     }
 
     /// Calculate character offset of a line in the full body text
@@ -725,6 +1094,57 @@ impl ComposeUI {
     pub async fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> ComposeAction {
         use crossterm::event::KeyCode;
 
+        if self.missing_keys.is_some() {
+            return self.handle_missing_keys_key(key);
+        }
+
+        if let Some(tone) = self.reply_draft.as_mut().and_then(|d| d.confirm_tone.take()) {
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                self.redraft_reply(tone);
+            }
+            return ComposeAction::Continue;
+        }
+
+        // The body is being rewritten while a draft streams in
+        if self.reply_draft.as_ref().is_some_and(|d| d.task.is_some()) {
+            match key.code {
+                KeyCode::Esc => self.stop_reply_draft(),
+                KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    if let Some(tone) = self.reply_draft.as_ref().map(|d| d.tone.next()) {
+                        self.redraft_reply(tone);
+                    }
+                }
+                _ => {}
+            }
+            return ComposeAction::Continue;
+        }
+
+        if let Some(ref mut picker) = self.schedule_picker {
+            return match picker.handle_key(key.code) {
+                SchedulePickerAction::Continue => ComposeAction::Continue,
+                SchedulePickerAction::Cancel => {
+                    self.schedule_picker = None;
+                    ComposeAction::Continue
+                }
+                SchedulePickerAction::Pick(at) => {
+                    self.schedule_picker = None;
+                    ComposeAction::ScheduleSend(at)
+                }
+            };
+        }
+
+        if let Some(ref mut picker) = self.file_picker {
+            match picker.handle_key(key.code) {
+                FilePickerAction::Continue => {}
+                FilePickerAction::Cancel => self.file_picker = None,
+                FilePickerAction::Attach(picked) => {
+                    self.file_picker = None;
+                    self.add_attachments(picked);
+                }
+            }
+            return ComposeAction::Continue;
+        }
+
         if self.contact_autocomplete.is_visible() {
             return self.handle_autocomplete_key(key).await;
         }
@@ -737,10 +1157,56 @@ impl ComposeUI {
             return self.handle_editor_config_key(key);
         }
 
+        if self.is_spell_check_visible {
+            if let Some(action) = self.handle_spell_popup_key(key) {
+                return action;
+            }
+        }
+
+        // A suggestion is taken with Tab and dropped with Esc or any other key
+        if self.ghost_completion.is_some() {
+            match key.code {
+                KeyCode::Tab => {
+                    self.accept_inline_completion();
+                    return ComposeAction::Continue;
+                }
+                KeyCode::Esc => {
+                    self.cancel_inline_completion();
+                    return ComposeAction::Continue;
+                }
+                _ => self.cancel_inline_completion(),
+            }
+        } else {
+            self.cancel_inline_completion();
+        }
+
         match key.code {
             KeyCode::Esc => ComposeAction::Cancel,
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.request_redraft();
+                ComposeAction::Continue
+            }
             KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => ComposeAction::Send,
             KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => ComposeAction::SaveDraft,
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                let subject = if self.subject_field.is_empty() {
+                    "(No subject)".to_string()
+                } else {
+                    self.subject_field.clone()
+                };
+                self.schedule_picker = Some(SchedulePicker::new("Send Later", "Send at", subject));
+                ComposeAction::Continue
+            }
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.open_file_picker();
+                ComposeAction::Continue
+            }
+            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if self.attachments.pop().is_some() {
+                    self.is_modified = true;
+                }
+                ComposeAction::Continue
+            }
             KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 // Toggle spell checking
                 self.toggle_spell_check().await;
@@ -765,6 +1231,25 @@ impl ComposeUI {
                 self.toggle_spell_config();
                 ComposeAction::Continue
             }
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.toggle_wrap_mode();
+                ComposeAction::Continue
+            }
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.priority = self.priority.next();
+                self.is_modified = true;
+                ComposeAction::Continue
+            }
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.encrypt = !self.encrypt;
+                self.is_modified = true;
+                ComposeAction::Continue
+            }
+            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.sign = !self.sign;
+                self.is_modified = true;
+                ComposeAction::Continue
+            }
             KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 // Launch external editor for body text
                 if self.current_field == ComposeField::Body {
@@ -776,13 +1261,7 @@ impl ComposeUI {
                 ComposeAction::Continue
             }
             KeyCode::Tab => {
-                if self.is_spell_check_visible && self.spell_check_enabled {
-                    // Apply selected spell suggestion
-                    self.apply_spell_suggestion();
-                    self.next_spell_error();
-                } else {
-                    self.next_field();
-                }
+                self.next_field();
                 ComposeAction::Continue
             }
             KeyCode::BackTab => {
@@ -837,24 +1316,13 @@ impl ComposeUI {
                 ComposeAction::Continue
             }
             KeyCode::Up => {
-                if self.is_spell_check_visible && self.spell_check_enabled {
-                    // Navigate spell check suggestions
-                    if self.spell_suggestion_selected > 0 {
-                        self.spell_suggestion_selected -= 1;
-                    }
-                } else if self.current_field == ComposeField::Body {
+                if self.current_field == ComposeField::Body {
                     self.move_cursor_up();
                 }
                 ComposeAction::Continue
             }
             KeyCode::Down => {
-                if self.is_spell_check_visible && self.spell_check_enabled {
-                    // Navigate spell check suggestions (including "Add to dictionary" option)
-                    let max_index = self.spell_suggestions.len(); // +1 for "Add to dictionary"
-                    if self.spell_suggestion_selected < max_index {
-                        self.spell_suggestion_selected += 1;
-                    }
-                } else if self.current_field == ComposeField::Body {
+                if self.current_field == ComposeField::Body {
                     self.move_cursor_down();
                 }
                 ComposeAction::Continue
@@ -863,6 +1331,47 @@ impl ComposeUI {
         }
     }
 
+    /// Handle keys while the spelling suggestions popup is open. Returns
+    /// `None` for keys it leaves to the editor, closing the popup for any
+    /// that edit or move
+    fn handle_spell_popup_key(&mut self, key: crossterm::event::KeyEvent) -> Option<ComposeAction> {
+        use crossterm::event::KeyCode;
+
+        let plain = !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Esc => self.is_spell_check_visible = false,
+            KeyCode::Up => {
+                self.spell_suggestion_selected = self.spell_suggestion_selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                // The entry after the suggestions is "Add to dictionary"
+                if self.spell_suggestion_selected < self.spell_suggestions.len() {
+                    self.spell_suggestion_selected += 1;
+                }
+            }
+            KeyCode::Enter | KeyCode::Tab => self.apply_spell_suggestion(),
+            KeyCode::Char(c @ '0'..='9') if plain => {
+                // 1-9 pick a suggestion, 0 adds the word to the dictionary
+                let index = match c.to_digit(10).unwrap_or(0) as usize {
+                    0 => self.spell_suggestions.len(),
+                    number => number - 1,
+                };
+                if index <= self.spell_suggestions.len() {
+                    self.spell_suggestion_selected = index;
+                    self.apply_spell_suggestion();
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('p') if !plain => return None,
+            _ => {
+                self.is_spell_check_visible = false;
+                return None;
+            }
+        }
+        Some(ComposeAction::Continue)
+    }
+
     /// Launch external editor for body text editing
     async fn launch_external_editor(&mut self) -> ComposeAction {
         // Get current body text
@@ -1032,6 +1541,7 @@ impl ComposeUI {
 
     /// Navigation methods
     fn next_field(&mut self) {
+        self.check_spelling_soon();
         self.current_field = match self.current_field {
             ComposeField::To => ComposeField::Cc,
             ComposeField::Cc => ComposeField::Bcc,
@@ -1042,6 +1552,7 @@ impl ComposeUI {
     }
 
     fn previous_field(&mut self) {
+        self.check_spelling_soon();
         self.current_field = match self.current_field {
             ComposeField::To => ComposeField::Body,
             ComposeField::Cc => ComposeField::To,
@@ -1077,10 +1588,38 @@ impl ComposeUI {
                     line.insert(self.body_cursor, c);
                     self.body_cursor += 1;
                 }
+                if !c.is_whitespace() {
+                    self.wrap_current_line();
+                }
             }
         }
     }
 
+    /// Break the line being typed at the wrap column when hard wrapping
+    ///
+    /// Only lines typed at their end are wrapped, so the cursor stays on the
+    /// word being typed; lines edited in the middle are wrapped when sent.
+    fn wrap_current_line(&mut self) {
+        if self.wrap_config.mode != WrapMode::Hard {
+            return;
+        }
+        let Some(line) = self.body_lines.get(self.body_line_index) else {
+            return;
+        };
+        if self.body_cursor != line.len() {
+            return;
+        }
+
+        let wrapped = compose_wrap::hard_wrap(line, self.wrap_config.column);
+        if wrapped.len() > 1 {
+            self.body_cursor = wrapped.last().map(|line| line.len()).unwrap_or(0);
+            let count = wrapped.len();
+            self.body_lines
+                .splice(self.body_line_index..=self.body_line_index, wrapped);
+            self.body_line_index += count - 1;
+        }
+    }
+
     fn delete_char(&mut self) {
         if self.get_cursor_position() == 0 {
             return;
@@ -1314,7 +1853,40 @@ impl ComposeUI {
             cc: self.cc_field.clone(),
             bcc: self.bcc_field.clone(),
             subject: self.subject_field.clone(),
-            body: self.body_lines.join("\n"),
+            body: match self.wrap_config.mode {
+                WrapMode::Soft => self.body_lines.join("\n"),
+                WrapMode::Hard => {
+                    compose_wrap::hard_wrap_body(&self.body_lines, self.wrap_config.column)
+                        .join("\n")
+                }
+            },
+            in_reply_to: self.in_reply_to.clone(),
+            references: self.references.clone(),
+            priority: self.priority,
+            encrypt: self.encrypt,
+            sign: self.sign,
+            attachments: self.attachments.clone(),
+        }
+    }
+
+    /// Ask whether to send unencrypted because `missing` recipients have no
+    /// PGP key
+    pub fn ask_send_unencrypted(&mut self, missing: Vec<String>) {
+        self.missing_keys = Some(missing);
+    }
+
+    /// `u` turns encryption off for this message and sends it; anything else
+    /// goes back to editing
+    fn handle_missing_keys_key(&mut self, key: crossterm::event::KeyEvent) -> ComposeAction {
+        use crossterm::event::KeyCode;
+
+        self.missing_keys = None;
+        match key.code {
+            KeyCode::Char('u') | KeyCode::Char('U') => {
+                self.encrypt = false;
+                ComposeAction::Send
+            }
+            _ => ComposeAction::Continue,
         }
     }
 
@@ -1346,6 +1918,16 @@ impl ComposeUI {
         self.current_draft_id.as_ref()
     }
 
+    /// Account to send from, if not the default one
+    pub fn account_id(&self) -> Option<&String> {
+        self.account_id.as_ref()
+    }
+
+    /// Send from the account holding the message replied to or forwarded
+    pub fn set_account_id(&mut self, account_id: Option<String>) {
+        self.account_id = account_id;
+    }
+
     /// Set the current draft ID (when loading an existing draft)
     pub fn set_current_draft_id(&mut self, draft_id: Option<String>) {
         self.current_draft_id = draft_id;
@@ -1363,6 +1945,11 @@ impl ComposeUI {
         self.cc_field = compose_data.cc;
         self.bcc_field = compose_data.bcc;
         self.subject_field = compose_data.subject;
+        self.in_reply_to = compose_data.in_reply_to;
+        self.references = compose_data.references;
+        self.priority = compose_data.priority;
+        self.encrypt = compose_data.encrypt;
+        self.sign = compose_data.sign;
         self.body_text = compose_data.body.clone();
         self.body_lines = if compose_data.body.is_empty() {
             vec![String::new()]
@@ -1373,6 +1960,7 @@ impl ComposeUI {
         self.is_modified = false;
         self.has_auto_save_changes = false;
         self.last_auto_save = Some(std::time::Instant::now());
+        self.check_spelling_soon();
     }
 
     /// Get auto-save interval in seconds
@@ -1389,6 +1977,274 @@ impl ComposeUI {
     fn mark_content_modified(&mut self) {
         self.is_modified = true;
         self.has_auto_save_changes = true;
+
+        // Re-check spelling once typing pauses; until then hide the
+        // misspellings the edit may have moved
+        if self.spell_check_enabled
+            && matches!(
+                self.current_field,
+                ComposeField::Subject | ComposeField::Body
+            )
+        {
+            let edit_offset = self.spell_cursor_offset().saturating_sub(1);
+            self.spell_stale_from = Some(
+                self.spell_stale_from
+                    .map_or(edit_offset, |offset| offset.min(edit_offset)),
+            );
+            self.spell_check_due = Some(std::time::Instant::now() + SPELL_CHECK_DELAY);
+        }
+
+        if let Some((_, delay)) = &self.smart_compose {
+            if self.current_field == ComposeField::Body {
+                self.completion_due = Some(std::time::Instant::now() + *delay);
+            }
+        }
+    }
+
+    /// Ask for a completion once typing in the body has paused, and show
+    /// the answer when it arrives. Called from the main loop
+    pub fn update_inline_completion(&mut self) {
+        if let Some(task) = &mut self.completion_task {
+            let Some(result) = futures::FutureExt::now_or_never(task) else {
+                return;
+            };
+            self.completion_task = None;
+            match result {
+                Ok(Ok(suggestion)) => self.ghost_completion = suggestion,
+                Ok(Err(e)) => tracing::debug!("Inline completion failed: {}", e),
+                Err(e) => tracing::debug!("Inline completion task ended: {}", e),
+            }
+            return;
+        }
+
+        let Some((service, _)) = &self.smart_compose else {
+            return;
+        };
+        if self
+            .completion_due
+            .is_none_or(|due| std::time::Instant::now() < due)
+        {
+            return;
+        }
+        self.completion_due = None;
+
+        // Ghost text only makes sense at the end of the line being typed
+        let at_line_end = self
+            .body_lines
+            .get(self.body_line_index)
+            .is_some_and(|line| self.body_cursor == line.len());
+        if self.current_field != ComposeField::Body || !at_line_end {
+            return;
+        }
+
+        let text = self.body_lines[..=self.body_line_index].join("\n");
+        let context = self.composition_context();
+        let service = service.clone();
+        self.completion_task = Some(tokio::spawn(async move {
+            service.complete_inline(&text, &context).await
+        }));
+    }
+
+    /// Drop the suggestion shown and any request still running
+    fn cancel_inline_completion(&mut self) {
+        self.ghost_completion = None;
+        self.completion_due = None;
+        if let Some(task) = self.completion_task.take() {
+            task.abort();
+        }
+    }
+
+    /// Insert the suggestion shown at the cursor
+    fn accept_inline_completion(&mut self) {
+        let Some(suggestion) = self.ghost_completion.take() else {
+            return;
+        };
+        self.mark_content_modified();
+        self.insert_body_text(&suggestion.content);
+
+        if let Some((service, _)) = &self.smart_compose {
+            let service = service.clone();
+            tokio::spawn(async move {
+                if let Err(e) = service
+                    .record_suggestion_feedback(suggestion.id, true, Some(suggestion.content))
+                    .await
+                {
+                    tracing::debug!("Failed to record accepted completion: {}", e);
+                }
+            });
+        }
+    }
+
+    /// Have the AI draft the reply to `message` in `tone` and write it into
+    /// the body at the cursor once it arrives
+    pub fn start_reply_draft(
+        &mut self,
+        assistant: Arc<crate::email::AIEmailAssistant>,
+        message: crate::email::StoredMessage,
+        tone: ReplyTone,
+    ) {
+        self.reply_draft = Some(ReplyDraft {
+            assistant,
+            message,
+            tone,
+            task: None,
+            operation_id: uuid::Uuid::new_v4(),
+            partial: None,
+            streamed: String::new(),
+            body_before: None,
+            body_after: Vec::new(),
+            confirm_tone: None,
+        });
+        self.spawn_reply_draft();
+    }
+
+    fn spawn_reply_draft(&mut self) {
+        let Some(draft) = &mut self.reply_draft else {
+            return;
+        };
+        draft.abort();
+        draft.body_before = Some((self.body_lines.clone(), self.body_line_index, self.body_cursor));
+        draft.streamed.clear();
+
+        let (partial_tx, partial_rx) = tokio::sync::mpsc::unbounded_channel();
+        draft.partial = Some(partial_rx);
+        draft.operation_id = uuid::Uuid::new_v4();
+        let operation_id = draft.operation_id;
+        let assistant = draft.assistant.clone();
+        let message = draft.message.clone();
+        let tone = draft.tone;
+        draft.task = Some(tokio::spawn(async move {
+            assistant
+                .streaming()
+                .relay(
+                    operation_id,
+                    move |content| {
+                        let _ = partial_tx.send(content);
+                    },
+                    assistant.draft_reply(&message, tone, Some(operation_id)),
+                )
+                .await
+        }));
+    }
+
+    /// Write the draft into the body as it streams in. Called from the main
+    /// loop; returns the tone on success and the error otherwise once the
+    /// draft request is done
+    pub fn update_reply_draft(&mut self) -> Option<Result<ReplyTone, String>> {
+        let draft = self.reply_draft.as_mut()?;
+        let mut streamed = false;
+        if let Some(partial) = &mut draft.partial {
+            while let Ok(content) = partial.try_recv() {
+                draft.streamed.push_str(&content);
+                streamed = true;
+            }
+        }
+        if streamed {
+            let text = draft.streamed.trim_start().to_string();
+            self.replace_reply_draft(&text);
+        }
+
+        let draft = self.reply_draft.as_mut()?;
+        let result = futures::FutureExt::now_or_never(draft.task.as_mut()?)?;
+        draft.task = None;
+        draft.partial = None;
+        let tone = draft.tone;
+
+        match result {
+            Ok(Ok(text)) => {
+                self.replace_reply_draft(&text);
+                if let Some(draft) = &mut self.reply_draft {
+                    draft.body_after = self.body_lines.clone();
+                }
+                Some(Ok(tone))
+            }
+            Ok(Err(e)) => {
+                self.restore_body_before_draft();
+                Some(Err(e.to_string()))
+            }
+            Err(e) => {
+                self.restore_body_before_draft();
+                Some(Err(e.to_string()))
+            }
+        }
+    }
+
+    /// Put `text` into the body where the draft goes, in place of whatever
+    /// of the draft is there now
+    fn replace_reply_draft(&mut self, text: &str) {
+        self.restore_body_before_draft();
+        self.mark_content_modified();
+        self.insert_body_text(text);
+    }
+
+    /// Put the body back the way it was before the draft went in
+    fn restore_body_before_draft(&mut self) {
+        let Some((lines, line_index, cursor)) =
+            self.reply_draft.as_ref().and_then(|d| d.body_before.clone())
+        else {
+            return;
+        };
+        self.body_lines = lines;
+        self.body_line_index = line_index;
+        self.body_cursor = cursor;
+        self.body_text = self.body_lines.join("\n");
+    }
+
+    /// Draft again in the next tone, asking first if that would throw away
+    /// edits made to the current draft
+    fn request_redraft(&mut self) {
+        let Some(draft) = &mut self.reply_draft else {
+            return;
+        };
+        let tone = draft.tone.next();
+        let edited = draft.body_after != self.body_lines;
+        if edited {
+            draft.confirm_tone = Some(tone);
+        } else {
+            self.redraft_reply(tone);
+        }
+    }
+
+    /// Take the current draft out of the body and draft again in `tone`
+    fn redraft_reply(&mut self, tone: ReplyTone) {
+        self.restore_body_before_draft();
+        let Some(draft) = &mut self.reply_draft else {
+            return;
+        };
+        draft.tone = tone;
+        draft.body_after.clear();
+        self.spawn_reply_draft();
+        self.mark_content_modified();
+    }
+
+    /// Stop the draft being written, keeping what has arrived so far
+    fn stop_reply_draft(&mut self) {
+        let Some(draft) = &mut self.reply_draft else {
+            return;
+        };
+        draft.abort();
+        draft.body_after = self.body_lines.clone();
+    }
+
+    /// What is being written and to whom, for inline completion
+    fn composition_context(&self) -> CompositionContext {
+        CompositionContext {
+            email_type: self.email_type.clone(),
+            recipients: EmailComposeData::parse_addresses(&self.to_field)
+                .into_iter()
+                .map(|email| crate::ai::smart_compose::Recipient {
+                    email,
+                    name: None,
+                    relationship: crate::ai::smart_compose::RelationshipType::Unknown,
+                    history_summary: None,
+                })
+                .collect(),
+            referenced_email: self.referenced_email.clone(),
+            style_preferences: StylePreferences::default(),
+            business_context: None,
+            thread_context: None,
+            urgency: crate::ai::smart_compose::UrgencyLevel::Normal,
+        }
     }
 
     /// Check if auto-save should be triggered and return action if needed
@@ -1400,62 +2256,183 @@ impl ComposeUI {
         }
     }
 
+    /// Load the spell checker's dictionary on first use, then re-check the
+    /// field being edited once typing has paused for `SPELL_CHECK_DELAY`.
+    /// Called from the main loop so long emails aren't checked per keystroke
+    pub async fn update_spell_check(&mut self) {
+        if !self.spell_check_enabled {
+            return;
+        }
+
+        if !self.spell_checker_ready {
+            self.spell_checker_ready = true;
+            let config = SpellCheckConfig::load().await.unwrap_or_else(|e| {
+                tracing::warn!("Failed to load spell check config: {}", e);
+                SpellCheckConfig::default()
+            });
+            match SpellChecker::with_config(config) {
+                Ok(checker) => self.spell_checker = checker,
+                Err(e) => tracing::warn!("Failed to create spell checker: {}", e),
+            }
+            let language = self.spell_checker.current_language().to_string();
+            if let Err(e) = self.spell_checker.set_language(&language).await {
+                tracing::warn!("Failed to load {} dictionary: {}", language, e);
+            }
+            self.available_languages = self.spell_checker.available_languages();
+            self.check_spelling_soon();
+        }
+
+        if self
+            .spell_check_due
+            .is_some_and(|due| std::time::Instant::now() >= due)
+        {
+            self.refresh_spell_check();
+        }
+    }
+
+    /// Re-check spelling on the next tick rather than after the typing delay
+    fn check_spelling_soon(&mut self) {
+        if self.spell_check_enabled {
+            self.spell_check_due = Some(std::time::Instant::now());
+        }
+    }
+
     /// Toggle spell checking on/off
     async fn toggle_spell_check(&mut self) {
         self.spell_check_enabled = !self.spell_check_enabled;
 
         if self.spell_check_enabled {
             // Perform spell check on current content
-            self.run_spell_check().await;
+            self.refresh_spell_check();
         } else {
             // Clear spell check results
             self.spell_check_result = None;
             self.is_spell_check_visible = false;
+            self.spell_check_due = None;
+            self.spell_stale_from = None;
         }
     }
 
-    /// Run spell check on current field content
-    async fn run_spell_check(&mut self) {
-        let text_to_check = match self.current_field {
-            ComposeField::Subject => &self.subject_field,
-            ComposeField::Body => &self.body_lines.join("\n"),
-            _ => return, // Don't spell check email addresses
+    /// Check the subject or body (whichever is being edited) for misspelled
+    /// words, keeping the selected error where it was
+    fn refresh_spell_check(&mut self) {
+        self.spell_check_due = None;
+        self.spell_stale_from = None;
+        self.spell_check_field = if self.current_field == ComposeField::Subject {
+            ComposeField::Subject
+        } else {
+            ComposeField::Body
+        };
+
+        let previous_position = self
+            .spell_check_result
+            .as_ref()
+            .and_then(|result| result.misspelled_words.get(self.current_spell_error))
+            .map(|error| error.position)
+            .unwrap_or(0);
+
+        let result = match self.spell_check_field {
+            ComposeField::Subject => self.spell_checker.find_misspelled(&self.subject_field),
+            _ => self
+                .spell_checker
+                .find_misspelled(&self.body_lines.join("\n")),
         };
 
-        if !text_to_check.trim().is_empty() {
-            let result = self.spell_checker.check_text(text_to_check);
-            self.spell_check_result = Some(result);
-            self.current_spell_error = 0;
-            self.is_spell_check_visible = self
-                .spell_check_result
-                .as_ref()
-                .map(|r| !r.misspelled_words.is_empty())
-                .unwrap_or(false);
+        self.current_spell_error = result
+            .misspelled_words
+            .iter()
+            .position(|error| error.position >= previous_position)
+            .unwrap_or(0);
+        if result.misspelled_words.is_empty() {
+            self.is_spell_check_visible = false;
+        }
+        self.spell_check_result = Some(result);
+        if self.is_spell_check_visible {
+            self.update_spell_suggestions();
+        }
+    }
+
+    /// Character offset of the cursor in the field being spell checked
+    fn spell_cursor_offset(&self) -> usize {
+        match self.current_field {
+            ComposeField::Subject => self.subject_cursor,
+            _ => self.calculate_line_offset(self.body_line_index) + self.body_cursor,
+        }
+    }
+
+    /// Move the body cursor to a character offset in the full body text
+    fn set_body_cursor_offset(&mut self, offset: usize) {
+        let mut remaining = offset;
+        for (index, line) in self.body_lines.iter().enumerate() {
+            if remaining <= line.len() {
+                self.body_line_index = index;
+                self.body_cursor = remaining;
+                return;
+            }
+            remaining -= line.len() + 1;
         }
+        self.body_line_index = self.body_lines.len().saturating_sub(1);
+        self.body_cursor = self.body_lines.last().map(|line| line.len()).unwrap_or(0);
     }
 
-    /// Move to next spell check error
+    /// Run a pending check now so error positions match the text
+    fn ensure_spell_check_current(&mut self) {
+        let field = if self.current_field == ComposeField::Subject {
+            ComposeField::Subject
+        } else {
+            ComposeField::Body
+        };
+        if self.spell_check_due.is_some()
+            || self.spell_check_result.is_none()
+            || self.spell_check_field != field
+        {
+            self.refresh_spell_check();
+        }
+    }
+
+    /// Move to next spell check error, opening the suggestions popup at the
+    /// first error after the cursor if it isn't already open
     pub fn next_spell_error(&mut self) {
+        self.ensure_spell_check_current();
+        let cursor = self.spell_cursor_offset();
         if let Some(ref result) = self.spell_check_result {
-            if !result.misspelled_words.is_empty() {
-                self.current_spell_error =
-                    (self.current_spell_error + 1) % result.misspelled_words.len();
-                self.update_spell_suggestions();
+            let errors = &result.misspelled_words;
+            if errors.is_empty() {
+                return;
             }
+            self.current_spell_error = if self.is_spell_check_visible {
+                (self.current_spell_error + 1) % errors.len()
+            } else {
+                errors
+                    .iter()
+                    .position(|error| error.position >= cursor)
+                    .unwrap_or(0)
+            };
+            self.is_spell_check_visible = true;
+            self.update_spell_suggestions();
         }
     }
 
-    /// Move to previous spell check error
+    /// Move to previous spell check error, opening the suggestions popup at
+    /// the last error before the cursor if it isn't already open
     pub fn previous_spell_error(&mut self) {
+        self.ensure_spell_check_current();
+        let cursor = self.spell_cursor_offset();
         if let Some(ref result) = self.spell_check_result {
-            if !result.misspelled_words.is_empty() {
-                if self.current_spell_error == 0 {
-                    self.current_spell_error = result.misspelled_words.len() - 1;
-                } else {
-                    self.current_spell_error -= 1;
-                }
-                self.update_spell_suggestions();
+            let errors = &result.misspelled_words;
+            if errors.is_empty() {
+                return;
             }
+            self.current_spell_error = if self.is_spell_check_visible {
+                (self.current_spell_error + errors.len() - 1) % errors.len()
+            } else {
+                errors
+                    .iter()
+                    .rposition(|error| error.position < cursor)
+                    .unwrap_or(errors.len() - 1)
+            };
+            self.is_spell_check_visible = true;
+            self.update_spell_suggestions();
         }
     }
 
@@ -1463,83 +2440,78 @@ impl ComposeUI {
     fn update_spell_suggestions(&mut self) {
         if let Some(ref result) = self.spell_check_result {
             if let Some(error) = result.misspelled_words.get(self.current_spell_error) {
-                self.spell_suggestions = error.suggestions.clone();
+                self.spell_suggestions = self.spell_checker.suggest_word(&error.word);
                 self.spell_suggestion_selected = 0;
             }
         }
     }
 
-    /// Apply selected spell suggestion or add to dictionary
+    /// Apply selected spell suggestion or add to dictionary, then move on to
+    /// the next misspelled word
     pub fn apply_spell_suggestion(&mut self) {
-        if let Some(ref result) = self.spell_check_result {
-            if let Some(error) = result.misspelled_words.get(self.current_spell_error) {
-                if self.spell_suggestion_selected >= self.spell_suggestions.len() {
-                    // "Add to dictionary" option selected
-                    self.add_word_to_dictionary(error.word.clone());
-                    return;
-                }
+        let Some(error) = self
+            .spell_check_result
+            .as_ref()
+            .and_then(|result| result.misspelled_words.get(self.current_spell_error))
+            .cloned()
+        else {
+            return;
+        };
 
-                if self.spell_suggestions.is_empty()
-                    || self.spell_suggestion_selected >= self.spell_suggestions.len()
-                {
+        let Some(replacement) = self
+            .spell_suggestions
+            .get(self.spell_suggestion_selected)
+            .cloned()
+        else {
+            // "Add to dictionary" option selected
+            self.add_word_to_dictionary(error.word);
+            return;
+        };
+
+        let start = error.position;
+        let end = start + error.length;
+        match self.spell_check_field {
+            ComposeField::Subject => {
+                if self.subject_field.get(start..end) != Some(error.word.as_str()) {
                     return;
                 }
-
-                let replacement = &self.spell_suggestions[self.spell_suggestion_selected];
-
-                match self.current_field {
-                    ComposeField::Subject => {
-                        let start = error.position;
-                        let end = start + error.length;
-                        if end <= self.subject_field.len() {
-                            self.subject_field.replace_range(start..end, replacement);
-                            self.subject_cursor = start + replacement.len();
-                        }
-                    }
-                    ComposeField::Body => {
-                        // Replace in body text - more complex due to line structure
-                        let full_text = self.body_lines.join("\n");
-                        let start = error.position;
-                        let end = start + error.length;
-                        if end <= full_text.len() {
-                            let mut new_text = full_text;
-                            new_text.replace_range(start..end, replacement);
-                            self.body_lines = new_text.lines().map(|s| s.to_string()).collect();
-                            if self.body_lines.is_empty() {
-                                self.body_lines.push(String::new());
-                            }
-                        }
-                    }
-                    _ => {}
+                self.subject_field.replace_range(start..end, &replacement);
+                self.subject_cursor = start + replacement.len();
+            }
+            _ => {
+                let mut full_text = self.body_lines.join("\n");
+                if full_text.get(start..end) != Some(error.word.as_str()) {
+                    return;
                 }
-
-                self.mark_content_modified();
+                full_text.replace_range(start..end, &replacement);
+                self.body_lines = full_text.split('\n').map(|s| s.to_string()).collect();
+                self.set_body_cursor_offset(start + replacement.len());
             }
         }
+
+        self.mark_content_modified();
+        self.refresh_spell_check();
+        self.is_spell_check_visible = self
+            .spell_check_result
+            .as_ref()
+            .is_some_and(|result| !result.misspelled_words.is_empty());
+        if self.is_spell_check_visible {
+            self.update_spell_suggestions();
+        }
     }
 
-    /// Add word to custom dictionary
+    /// Add word to custom dictionary and save it to the spell check config
     fn add_word_to_dictionary(&mut self, word: String) {
-        // Add to spell checker's custom words
-        self.spell_checker.add_custom_word(word.clone());
+        self.spell_checker.add_custom_word(word);
 
-        // Re-run spell check to update results
-        if self.spell_check_enabled {
-            // This would need to be async, but for now we'll mark it as handled
-            // by removing it from current results
-            if let Some(ref mut result) = self.spell_check_result {
-                result.misspelled_words.retain(|w| w.word != word);
-                result.error_count = result.misspelled_words.len();
-
-                // Update visibility
-                self.is_spell_check_visible = !result.misspelled_words.is_empty();
-
-                // Reset current error if we're at the end
-                if self.current_spell_error >= result.misspelled_words.len() {
-                    self.current_spell_error = result.misspelled_words.len().saturating_sub(1);
-                }
+        let config = self.spell_checker.config().clone();
+        tokio::spawn(async move {
+            if let Err(e) = config.save().await {
+                tracing::warn!("Failed to save custom dictionary word: {}", e);
             }
-        }
+        });
+
+        self.refresh_spell_check();
     }
 
     /// Toggle spell check configuration popup
@@ -1589,7 +2561,7 @@ impl ComposeUI {
                         tracing::info!("Spell check language changed to: {}", selected_lang);
                         // Re-run spell check if enabled
                         if self.spell_check_enabled {
-                            self.run_spell_check().await;
+                            self.refresh_spell_check();
                         }
                     }
                 }
@@ -1601,29 +2573,52 @@ impl ComposeUI {
     }
 }
 
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
 /// Actions that can be returned from the compose UI
 #[derive(Debug, Clone, PartialEq)]
 pub enum ComposeAction {
     Continue,
     Send,
+    /// Keep the message as a scheduled draft and send it at this time
+    ScheduleSend(chrono::DateTime<chrono::Utc>),
     SaveDraft,
     AutoSave,
     Cancel,
     StartCompose,
     StartReplyFromMessage(crate::email::StoredMessage),
     StartReplyAllFromMessage(crate::email::StoredMessage),
+    StartReplyToListFromMessage(crate::email::StoredMessage),
     StartForwardFromMessage(crate::email::StoredMessage),
     StartEditFromMessage(crate::email::StoredMessage),
 }
 
 /// Email composition data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct EmailComposeData {
     pub to: String,
     pub cc: String,
     pub bcc: String,
     pub subject: String,
     pub body: String,
+    /// Message-ID of the message being replied to
+    pub in_reply_to: Option<String>,
+    /// Space-separated Message-IDs of the thread being replied to
+    pub references: Option<String>,
+    /// Sent as the X-Priority, Importance and X-MSMail-Priority headers
+    pub priority: MailPriority,
+    /// Encrypt to the recipients' PGP keys
+    pub encrypt: bool,
+    /// Sign with the sender's PGP key
+    pub sign: bool,
+    /// Files attached with Ctrl+A
+    pub attachments: Vec<ComposeAttachment>,
 }
 
 impl EmailComposeData {
@@ -0,0 +1,157 @@
+//! Files attached to a message being written
+//!
+//! Attachments are kept as paths and read when the message is built, so a
+//! large file isn't held in memory while composing. Sizes are checked
+//! against the 25 MB most providers (Gmail, Outlook.com, Yahoo) accept per
+//! message, counting the base64 encoding attachments are sent in.
+
+use std::path::{Path, PathBuf};
+
+/// Largest message most providers accept
+pub const PROVIDER_SIZE_LIMIT: u64 = 25 * 1024 * 1024;
+
+/// Share of the limit from which a warning is shown, in percent
+const WARN_PERCENT: u64 = 80;
+
+/// A file attached in compose
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeAttachment {
+    pub path: PathBuf,
+    /// Name the recipient sees
+    pub filename: String,
+    pub content_type: String,
+    /// Size on disk in bytes
+    pub size: u64,
+}
+
+impl ComposeAttachment {
+    /// Attachment for a regular file, with its type guessed from the name
+    pub fn from_path(path: &Path) -> std::io::Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        if !metadata.is_file() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{} is not a file", path.display()),
+            ));
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            filename: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "attachment".to_string()),
+            content_type: mime_type_for(path).to_string(),
+            size: metadata.len(),
+        })
+    }
+
+    /// The file's contents, read when the message is sent
+    pub fn read(&self) -> std::io::Result<Vec<u8>> {
+        std::fs::read(&self.path)
+    }
+}
+
+/// MIME type for a file name, `application/octet-stream` when unknown
+pub fn mime_type_for(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "pdf" => "application/pdf",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        "ods" => "application/vnd.oasis.opendocument.spreadsheet",
+        "odp" => "application/vnd.oasis.opendocument.presentation",
+        "rtf" => "application/rtf",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "xml" => "application/xml",
+        "json" => "application/json",
+        "ics" => "text/calendar",
+        "vcf" => "text/vcard",
+        "eml" => "message/rfc822",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "tif" | "tiff" => "image/tiff",
+        "heic" => "image/heic",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "webm" => "video/webm",
+        "zip" => "application/zip",
+        "gz" | "tgz" => "application/gzip",
+        "tar" => "application/x-tar",
+        "7z" => "application/x-7z-compressed",
+        "rar" => "application/vnd.rar",
+        "asc" => "application/pgp-keys",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Size of an attachment once base64 encoded in 76-character lines
+pub fn encoded_size(bytes: u64) -> u64 {
+    bytes.div_ceil(57) * 78
+}
+
+/// Total encoded size of attachments
+pub fn total_encoded_size<'a>(attachments: impl IntoIterator<Item = &'a ComposeAttachment>) -> u64 {
+    attachments
+        .into_iter()
+        .map(|attachment| encoded_size(attachment.size))
+        .sum()
+}
+
+/// Warning for attachments of `raw_size` bytes in total, once they come
+/// close to or go over the provider limit
+pub fn size_warning(raw_size: u64) -> Option<String> {
+    let encoded = encoded_size(raw_size);
+    let limit = crate::ui::preview_limits::format_size(PROVIDER_SIZE_LIMIT as usize);
+    if encoded > PROVIDER_SIZE_LIMIT {
+        Some(format!(
+            "Over the {} most providers accept; the message may be rejected",
+            limit
+        ))
+    } else if encoded * 100 >= PROVIDER_SIZE_LIMIT * WARN_PERCENT {
+        Some(format!("Close to the {} most providers accept", limit))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_types_and_size_limits() {
+        assert_eq!(mime_type_for(Path::new("/tmp/Report.PDF")), "application/pdf");
+        assert_eq!(mime_type_for(Path::new("notes")), "application/octet-stream");
+
+        assert_eq!(encoded_size(0), 0);
+        assert_eq!(encoded_size(57), 78);
+        assert_eq!(encoded_size(58), 156);
+
+        assert_eq!(size_warning(10 * 1024 * 1024), None);
+        assert!(size_warning(16 * 1024 * 1024)
+            .is_some_and(|warning| warning.starts_with("Close to")));
+        assert!(size_warning(19 * 1024 * 1024)
+            .is_some_and(|warning| warning.starts_with("Over")));
+    }
+}
@@ -0,0 +1,145 @@
+//! Line wrapping while composing
+//!
+//! Soft wrapping keeps each paragraph one logical line and only wraps it on
+//! screen, at the wrap column. That is what `format=flowed` wants: the
+//! sender encodes the paragraph and the recipient reflows it. Hard wrapping
+//! breaks lines at the wrap column as you type and again before sending, for
+//! mailing lists and clients that expect traditional 72 or 76 column text.
+//! Quoted lines keep their `>` prefix on every wrapped line, and the
+//! signature separator is never touched.
+
+use super::text_width::display_width;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// How composed text is wrapped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WrapMode {
+    /// Wrap on screen only; each paragraph is sent as one line
+    Soft,
+    /// Insert line breaks at the wrap column
+    Hard,
+}
+
+impl WrapMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Soft => Self::Hard,
+            Self::Hard => Self::Soft,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Soft => "soft",
+            Self::Hard => "hard",
+        }
+    }
+}
+
+/// Compose wrapping configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComposeWrapConfig {
+    pub mode: WrapMode,
+    /// Column text is wrapped at; 0 wraps at the width of the pane
+    pub column: usize,
+    /// Draw a guide line at the wrap column
+    pub show_guide: bool,
+}
+
+impl Default for ComposeWrapConfig {
+    fn default() -> Self {
+        Self {
+            mode: WrapMode::Soft,
+            column: 72,
+            show_guide: true,
+        }
+    }
+}
+
+impl ComposeWrapConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "compose_wrap.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// Length in bytes of a line's quote markers and the indentation after them
+fn prefix_len(line: &str) -> usize {
+    let quoted = line.trim_start_matches(['>', ' ']);
+    if line.starts_with('>') {
+        line.len() - quoted.len()
+    } else {
+        line.len() - line.trim_start_matches(' ').len()
+    }
+}
+
+/// Break `line` into lines no wider than `column`, at spaces
+///
+/// Words longer than the column are left whole. Runs of spaces between
+/// words become one space where the line is re-broken.
+pub fn hard_wrap(line: &str, column: usize) -> Vec<String> {
+    if column == 0 || line == "-- " || display_width(line) <= column {
+        return vec![line.to_string()];
+    }
+
+    let (prefix, text) = line.split_at(prefix_len(line));
+    let room = column.saturating_sub(display_width(prefix)).max(1);
+
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if current.is_empty() {
+            current.push_str(word);
+        } else if display_width(&current) + 1 + display_width(word) <= room {
+            current.push(' ');
+            current.push_str(word);
+        } else {
+            lines.push(format!("{}{}", prefix, current));
+            current = word.to_string();
+        }
+    }
+    lines.push(format!("{}{}", prefix, current));
+    lines
+}
+
+/// Hard-wrap every line of a body
+pub fn hard_wrap_body(lines: &[String], column: usize) -> Vec<String> {
+    lines
+        .iter()
+        .flat_map(|line| hard_wrap(line, column))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hard_wrap_keeps_quote_prefix() {
+        assert_eq!(
+            hard_wrap("the quick brown fox jumps over the lazy dog", 20),
+            vec!["the quick brown fox", "jumps over the lazy", "dog"]
+        );
+        assert_eq!(
+            hard_wrap("> > nested quoted text that runs long", 20),
+            vec!["> > nested quoted", "> > text that runs", "> > long"]
+        );
+        assert_eq!(hard_wrap("short", 20), vec!["short"]);
+        assert_eq!(
+            hard_wrap("see https://example.com/a/very/long/path", 10),
+            vec!["see", "https://example.com/a/very/long/path"]
+        );
+        assert_eq!(hard_wrap("-- ", 1), vec!["-- "]);
+    }
+}
@@ -0,0 +1,101 @@
+//! Yes/no confirmation for bulk actions
+//!
+//! Shown over the normal interface before an action that touches many
+//! messages at once. The dialog only carries the action; the app runs it
+//! once the user confirms.
+
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Action waiting for confirmation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmAction {
+    /// Mark every unread message read, in one folder or (`None`) the whole account
+    MarkAllRead {
+        account_id: String,
+        folder: Option<String>,
+    },
+    /// Permanently delete messages, which may sit in different folders
+    DeleteMessages {
+        account_id: String,
+        message_ids: Vec<uuid::Uuid>,
+    },
+}
+
+/// Popup asking whether to go ahead with an action
+#[derive(Debug, Clone)]
+pub struct ConfirmDialog {
+    title: String,
+    message: String,
+    action: ConfirmAction,
+}
+
+impl ConfirmDialog {
+    pub fn new(title: String, message: String, action: ConfirmAction) -> Self {
+        Self {
+            title,
+            message,
+            action,
+        }
+    }
+
+    pub fn action(&self) -> &ConfirmAction {
+        &self.action
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(50, 8, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.colors.palette.warning));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let lines = vec![
+            Line::from(Span::styled(
+                self.message.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled("y/Enter: confirm · n/Esc: cancel", label)),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            inner,
+        );
+    }
+}
+
+/// Rectangle of `percent_x` width and `height` rows, centered in `r`
+fn centered_rect(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
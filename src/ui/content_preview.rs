@@ -1,8 +1,14 @@
 use crate::animation::AnimationManager;
 use crate::clipboard::ClipboardManager;
-use crate::email::{AttachmentInfo, AttachmentViewer, EmailDatabase, StoredMessage};
+use crate::email::{AttachmentInfo, AttachmentSaveConfig, AttachmentViewer, EmailDatabase, StoredMessage};
 use crate::images::{extract_images_from_html, ImageManager};
 use crate::theme::Theme;
+use crate::ui::browser_view::{self, BrowserViewConfig};
+use crate::ui::opener::OpenerConfig;
+use crate::ui::preview_cache::{RenderCache, RenderSettings, RenderedMessage};
+use crate::ui::preview_limits::{self, PreviewLimitsConfig};
+use crate::ui::reflow::{reflow, ReflowConfig};
+use crate::ui::typography::InformationDensity;
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -11,8 +17,9 @@ use ratatui::{
     Frame,
 };
 use regex::Regex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -36,6 +43,14 @@ pub struct Attachment {
     pub is_inline: bool,
 }
 
+/// Outcome of starting to save an attachment
+pub enum AttachmentSave {
+    /// The attachment was stored locally and has been written here
+    Saved(std::path::PathBuf),
+    /// The attachment has to be downloaded from the server first
+    Download(crate::email::AttachmentDownload),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ContentType {
     PlainText,
@@ -84,6 +99,19 @@ pub enum ViewMode {
     Headers,   // Show headers only
 }
 
+/// A neighbouring message loaded and rendered off the UI thread
+struct PrerenderedMessage {
+    message: StoredMessage,
+    body: String,
+    content_type: ContentType,
+    html_lines: Option<Vec<Line<'static>>>,
+    image_sources: Vec<String>,
+    /// Full body size when the body was cut to the render limit
+    truncated_from: Option<usize>,
+    /// Wrap width the HTML was rendered at
+    width: usize,
+}
+
 pub struct ContentPreview {
     email_content: Option<EmailContent>,
     raw_content: Vec<String>,
@@ -100,19 +128,50 @@ pub struct ContentPreview {
     animation_manager: Option<Arc<AnimationManager>>,
     processed_images: HashMap<String, String>, // URL -> rendered content
     active_animations: HashMap<String, String>, // Animation ID -> display content
+    /// Whether GIFs in HTML bodies may be decoded and started
+    animation_config: crate::ui::animated_content::AnimationConfig,
     selected_attachment: Option<usize>,        // Index of selected attachment
     attachment_viewer: AttachmentViewer,
     is_viewing_attachment: bool,
     clipboard_manager: ClipboardManager,
     imap_manager: Option<Arc<crate::imap::ImapAccountManager>>,
+    /// Messages prefetched in the background, looked up before the database
+    message_cache: Option<Arc<crate::performance::CacheManager>>,
+    attachment_save_config: AttachmentSaveConfig,
+    render_cache: RenderCache,
+    density: InformationDensity,
+    prerender_tx: mpsc::UnboundedSender<PrerenderedMessage>,
+    prerender_rx: mpsc::UnboundedReceiver<PrerenderedMessage>,
+    /// Messages with a background render in flight
+    prerendering: HashSet<Uuid>,
+    preview_limits: PreviewLimitsConfig,
+    /// Full body size of the displayed message when only part of it is shown
+    truncated_from: Option<usize>,
+    /// Messages the user asked to render in full despite the size limit
+    render_full: HashSet<Uuid>,
+    /// Problems found while parsing the displayed message's MIME structure
+    mime_defects: Vec<crate::mime::MimeDefect>,
+    browser_view: BrowserViewConfig,
+    opener: OpenerConfig,
+    /// Messages whose remote images the user chose to load
+    remote_images_allowed: HashSet<Uuid>,
+    reflow_config: ReflowConfig,
+    /// Plain text bodies are shown reflowed
+    reflow: bool,
+    /// The displayed body arrived as `format=flowed`
+    flowed: bool,
+    /// Reflowed body lines and the column they were wrapped at
+    reflowed: Option<(usize, Vec<ContentLine>)>,
 }
 
 impl ContentPreview {
     pub fn new() -> Self {
-        let url_regex = Regex::new(r"https?://[^\s]+").unwrap();
+        let url_regex = Regex::new(r#"(?:https?://|mailto:|tel:)[^\s<>"]+"#).unwrap();
         let email_regex =
             Regex::new(r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Z|a-z]{2,}\b").unwrap();
 
+        let (prerender_tx, prerender_rx) = mpsc::unbounded_channel();
+
         let mut preview = Self {
             email_content: None,
             raw_content: Vec::new(),
@@ -129,11 +188,30 @@ impl ContentPreview {
             animation_manager: None, // Will be initialized later
             processed_images: HashMap::new(),
             active_animations: HashMap::new(),
+            animation_config: Default::default(),
             selected_attachment: None,
             attachment_viewer: AttachmentViewer::default(),
             is_viewing_attachment: false,
             clipboard_manager: ClipboardManager::new(),
+            attachment_save_config: AttachmentSaveConfig::default(),
             imap_manager: None,
+            message_cache: None,
+            render_cache: RenderCache::default(),
+            density: InformationDensity::Comfortable,
+            prerender_tx,
+            prerender_rx,
+            prerendering: HashSet::new(),
+            preview_limits: PreviewLimitsConfig::default(),
+            truncated_from: None,
+            render_full: HashSet::new(),
+            mime_defects: Vec::new(),
+            browser_view: BrowserViewConfig::default(),
+            opener: OpenerConfig::default(),
+            remote_images_allowed: HashSet::new(),
+            reflow_config: ReflowConfig::default(),
+            reflow: false,
+            flowed: false,
+            reflowed: None,
         };
 
         // Initialize with sample content
@@ -258,16 +336,24 @@ This is a sample email showcasing the modern email display format."
 
         // Update image dimensions based on current area
         self.update_image_dimensions(area);
+        self.update_render_settings(&theme.name);
+        self.collect_prerendered();
 
         let content_height = area.height.saturating_sub(2) as usize; // Account for block borders
+        let content_width = area.width.saturating_sub(2) as usize;
 
         // Extract values needed to avoid borrowing conflicts
         let view_mode = self.view_mode;
         let current_scroll = self.scroll;
         let raw_content_len = self.raw_content.len();
+        let truncated_indicator = if self.truncated_from.is_some() {
+            " [Truncated]"
+        } else {
+            ""
+        };
         let email_lines_estimate = if let Some(ref email) = self.email_content {
             match view_mode {
-                ViewMode::Formatted => email.parsed_content.len() + 10, // Headers + content
+                ViewMode::Formatted => self.body_line_count(email) + 10, // Headers + content
                 ViewMode::Html => email.body.lines().count() + 10, // Estimate HTML rendered line count
                 ViewMode::Headers => 8,                            // Typical header count
                 ViewMode::Raw => raw_content_len,
@@ -276,9 +362,12 @@ This is a sample email showcasing the modern email display format."
             raw_content_len
         };
 
+        let reflowing = self.is_reflowing();
         let lines = match view_mode {
             ViewMode::Raw => self.render_raw_content(content_height, theme),
-            ViewMode::Formatted => self.render_formatted_content(content_height, theme),
+            ViewMode::Formatted => {
+                self.render_formatted_content(content_height, content_width, theme)
+            }
             ViewMode::Html => self.render_html_content(content_height, theme),
             ViewMode::Headers => self.render_headers_only(content_height, theme),
         };
@@ -299,15 +388,16 @@ This is a sample email showcasing the modern email display format."
 
         let view_mode_indicator = match view_mode {
             ViewMode::Raw => " [Raw]",
+            ViewMode::Formatted if reflowing => " [Reflowed]",
             ViewMode::Formatted => " [Formatted]",
             ViewMode::Html => " [HTML]",
             ViewMode::Headers => " [Headers]",
         };
 
         let title = if is_focused {
-            format!("Content{}{}", view_mode_indicator, scroll_indicator)
+            format!("Content{}{}{}", view_mode_indicator, truncated_indicator, scroll_indicator)
         } else {
-            format!("Content{}{}", view_mode_indicator, scroll_indicator)
+            format!("Content{}{}{}", view_mode_indicator, truncated_indicator, scroll_indicator)
         };
 
         let paragraph = Paragraph::new(lines)
@@ -332,7 +422,12 @@ This is a sample email showcasing the modern email display format."
             .collect()
     }
 
-    fn render_formatted_content(&mut self, content_height: usize, theme: &Theme) -> Vec<Line> {
+    fn render_formatted_content(
+        &mut self,
+        content_height: usize,
+        content_width: usize,
+        theme: &Theme,
+    ) -> Vec<Line<'_>> {
         if let Some(email) = self.email_content.clone() {
             // Extract all data we need before any mutable borrows
             let show_headers_expanded = self.show_headers_expanded;
@@ -350,21 +445,21 @@ This is a sample email showcasing the modern email display format."
                 );
 
                 // Always try HTML rendering first - the new renderer handles detection internally
-                let email_body = email.body.clone();
-                let rendered_text = self.html_renderer.render_html(&email_body);
-
-                tracing::debug!(
-                    "Content Preview: HTML renderer generated {} lines",
-                    rendered_text.lines.len()
-                );
-
-                // Return rendered HTML lines directly
-                Some(rendered_text.lines)
+                Some(self.current_html_lines(&email))
             } else {
                 tracing::debug!("Content Preview: Processing plain text content");
                 None
             };
 
+            // Use parsed content lines for plain text with header filtering
+            let mut plain_lines = Vec::new();
+            if html_lines.is_none() {
+                plain_lines = self.filter_raw_headers_from_content(&email.parsed_content);
+                if self.reflow {
+                    plain_lines = self.reflowed_content(&plain_lines, content_width);
+                }
+            }
+
             // Now do immutable operations
             // Add header information based on user preference
             if show_headers_expanded {
@@ -387,17 +482,25 @@ This is a sample email showcasing the modern email display format."
             )]));
             all_lines.push(Line::from("")); // Spacing after separator
 
+            if let Some(banner) = self.malformed_banner(theme) {
+                all_lines.push(banner);
+                all_lines.push(Line::from(""));
+            }
+
             // Add formatted content lines with HTML detection
             if let Some(processed_lines) = html_lines {
                 all_lines.extend(processed_lines);
             } else {
-                // Use parsed content lines for plain text with header filtering
-                let filtered_content = self.filter_raw_headers_from_content(&email.parsed_content);
-                for content_line in &filtered_content {
+                for content_line in &plain_lines {
                     all_lines.push(self.render_content_line(content_line, theme));
                 }
             }
 
+            if let Some(notice) = self.truncation_notice(theme) {
+                all_lines.push(Line::from(""));
+                all_lines.push(notice);
+            }
+
             // Add attachments section if there are any
             let attachments = email.attachments.clone(); // Clone to avoid borrowing issues
             if !attachments.is_empty() {
@@ -500,8 +603,8 @@ This is a sample email showcasing the modern email display format."
         }
     }
 
-    fn render_html_content(&mut self, content_height: usize, _theme: &Theme) -> Vec<Line> {
-        if let Some(ref email) = self.email_content {
+    fn render_html_content(&mut self, content_height: usize, theme: &Theme) -> Vec<Line<'_>> {
+        if let Some(email) = self.email_content.clone() {
             let mut all_lines = Vec::new();
 
             // Add compact headers first
@@ -515,6 +618,11 @@ This is a sample email showcasing the modern email display format."
             ]));
             all_lines.push(Line::from("")); // Separator
 
+            if let Some(banner) = self.malformed_banner(theme) {
+                all_lines.push(banner);
+                all_lines.push(Line::from(""));
+            }
+
             // Always render HTML content - the renderer handles everything internally
             if !email.body.is_empty() {
                 tracing::debug!(
@@ -523,10 +631,10 @@ This is a sample email showcasing the modern email display format."
                 );
 
                 // Use the HTML renderer to convert HTML to readable text
-                let mut rendered_text = self.html_renderer.render_html(&email.body);
+                let mut rendered_lines = self.current_html_lines(&email);
 
                 // Replace any animation placeholders with current frame data
-                for line in &mut rendered_text.lines {
+                for line in &mut rendered_lines {
                     for span in &mut line.spans {
                         // Only modify span content if we have active animations
                         if !self.active_animations.is_empty() {
@@ -536,7 +644,12 @@ This is a sample email showcasing the modern email display format."
                 }
 
                 // Add rendered HTML lines directly
-                all_lines.extend(rendered_text.lines);
+                all_lines.extend(rendered_lines);
+
+                if let Some(notice) = self.truncation_notice(theme) {
+                    all_lines.push(Line::from(""));
+                    all_lines.push(notice);
+                }
 
                 tracing::debug!(
                     "HTML Content Rendering: Generated {} lines after processing",
@@ -566,6 +679,41 @@ This is a sample email showcasing the modern email display format."
         }
     }
 
+    /// Banner shown above a body whose MIME structure was broken
+    fn malformed_banner(&self, theme: &Theme) -> Option<Line<'static>> {
+        if self.mime_defects.is_empty() {
+            return None;
+        }
+
+        Some(Line::from(Span::styled(
+            "⚠ This message has malformed parts; some content may be missing",
+            Style::default()
+                .fg(theme.colors.palette.warning)
+                .add_modifier(Modifier::BOLD),
+        )))
+    }
+
+    /// Notice shown under a body that was cut to the render limit
+    fn truncation_notice(&self, theme: &Theme) -> Option<Line<'static>> {
+        let full_size = self.truncated_from?;
+        let shown = self
+            .email_content
+            .as_ref()
+            .map(|email| email.body.len())
+            .unwrap_or_default();
+
+        Some(Line::from(Span::styled(
+            format!(
+                "✂ Message truncated: showing {} of {}. Press F to render it fully",
+                preview_limits::format_size(shown),
+                preview_limits::format_size(full_size)
+            ),
+            Style::default()
+                .fg(theme.colors.palette.warning)
+                .add_modifier(Modifier::BOLD),
+        )))
+    }
+
     fn render_headers_only(&mut self, content_height: usize, theme: &Theme) -> Vec<Line> {
         if let Some(ref email) = self.email_content {
             let header_lines = self.render_email_headers(&email.headers, theme);
@@ -1212,13 +1360,18 @@ This is a sample email showcasing the modern email display format."
         self.scroll
     }
 
+    /// Set the scroll position, e.g. when restoring a previous session
+    pub fn set_scroll_position(&mut self, position: usize) {
+        self.scroll = position;
+    }
+
     /// Get maximum scroll position based on content
     pub fn get_max_scroll(&self, visible_height: usize) -> usize {
         let total_lines = match self.view_mode {
             ViewMode::Raw => self.raw_content.len(),
             ViewMode::Formatted => {
                 if let Some(ref email) = self.email_content {
-                    email.parsed_content.len() + 15 // Headers + content + spacing
+                    self.body_line_count(email) + 15 // Headers + content + spacing
                 } else {
                     self.raw_content.len()
                 }
@@ -1276,6 +1429,7 @@ This is a sample email showcasing the modern email display format."
     pub fn set_email_content(&mut self, email_content: EmailContent) {
         // Always use Formatted mode - it handles HTML content properly
         self.view_mode = ViewMode::Formatted;
+        self.reflowed = None;
 
         // Clear any existing animations before setting new content
         if !self.active_animations.is_empty() {
@@ -1663,6 +1817,11 @@ This is a sample email showcasing the modern email display format."
         self.imap_manager = Some(imap_manager);
     }
 
+    /// Set the cache the background prefetch fills
+    pub fn set_message_cache(&mut self, cache: Arc<crate::performance::CacheManager>) {
+        self.message_cache = Some(cache);
+    }
+
     /// Load email content from database by message ID
     pub async fn load_message_by_id(
         &mut self,
@@ -1672,8 +1831,16 @@ This is a sample email showcasing the modern email display format."
             self.loading = true;
             self.current_message_id = Some(message_id);
 
+            let cached = match self.message_cache {
+                Some(ref cache) => cache.message_cache().get_message_by_id(message_id).await,
+                None => None,
+            };
+
             // Find the message in the database by ID
-            if let Some(message) = self.find_message_by_id(database, message_id).await? {
+            if let Some(message) = cached {
+                tracing::debug!("Content Preview: Using prefetched message {}", message_id);
+                self.load_stored_message(&message).await?;
+            } else if let Some(message) = self.find_message_by_id(database, message_id).await? {
                 self.load_stored_message(&message).await?;
             } else {
                 self.show_error_message(&format!("Message with ID {} not found", message_id));
@@ -1692,26 +1859,365 @@ This is a sample email showcasing the modern email display format."
     ) -> Result<(), Box<dyn std::error::Error>> {
         self.current_message_id = Some(message.id);
 
-        // Convert StoredMessage to EmailContent with on-demand body fetching
-        let email_content = self.convert_stored_message_to_email_content(message).await;
-
-        // Check if we have HTML content and load images
-        if email_content.content_type == ContentType::Html && !email_content.body.is_empty() {
-            if crate::html::is_html_content(&email_content.body) {
-                self.load_images_from_html(&email_content.body).await;
+        // Revisiting a message (or moving onto a pre-rendered neighbour) skips
+        // parsing and HTML rendering entirely
+        if let Some(rendered) = self.render_cache.get(&message.id).cloned() {
+            tracing::debug!("Content Preview: Using cached render for {}", message.id);
+            let images_missing = rendered
+                .image_sources
+                .iter()
+                .any(|src| !self.processed_images.contains_key(src));
+            if images_missing {
+                self.load_images_from_html(&rendered.content.body).await;
             }
+            self.truncated_from = rendered.truncated_from;
+            self.load_mime_defects(message.id).await;
+            self.load_flowed(message.id).await;
+            self.set_email_content(rendered.content);
+            return Ok(());
         }
 
+        // Convert StoredMessage to EmailContent with on-demand body fetching
+        let (email_content, truncated_from) =
+            self.convert_stored_message_to_email_content(message).await;
+
+        // Check if we have HTML content and load images; a truncated body
+        // doesn't fetch remote resources
+        let mut image_sources = Vec::new();
+        if email_content.content_type == ContentType::Html
+            && !email_content.body.is_empty()
+            && truncated_from.is_none()
+            && crate::html::is_html_content(&email_content.body)
+        {
+            image_sources = extract_images_from_html(&email_content.body)
+                .into_iter()
+                .map(|image| image.src)
+                .collect();
+            self.load_images_from_html(&email_content.body).await;
+        }
+
+        let html_lines = (email_content.content_type == ContentType::Html)
+            .then(|| self.html_renderer.render_html(&email_content.body).lines);
+        self.render_cache.insert(
+            message.id,
+            RenderedMessage {
+                content: email_content.clone(),
+                html_lines,
+                image_sources,
+                truncated_from,
+            },
+        );
+
         // Set the content
+        self.truncated_from = truncated_from;
+        self.load_mime_defects(message.id).await;
+        self.load_flowed(message.id).await;
         self.set_email_content(email_content);
 
         Ok(())
     }
 
+    /// Header report for the displayed message, if its headers were kept
+    pub async fn load_header_report(&self) -> Option<crate::email::HeaderReport> {
+        let (database, message_id) = (self.database.as_ref()?, self.current_message_id?);
+        match database.get_raw_headers(message_id).await {
+            Ok(headers) => headers.map(|headers| crate::email::HeaderReport::from_raw_headers(&headers)),
+            Err(e) => {
+                tracing::warn!("Failed to load headers for {}: {}", message_id, e);
+                None
+            }
+        }
+    }
+
+    /// Look up the MIME defects recorded for a message during sync
+    async fn load_mime_defects(&mut self, message_id: Uuid) {
+        self.mime_defects = match &self.database {
+            Some(database) => database
+                .get_message_defects(message_id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::debug!("Failed to load MIME defects for {}: {}", message_id, e);
+                    Vec::new()
+                }),
+            None => Vec::new(),
+        };
+    }
+
+    /// Look up whether a message's body arrived as `format=flowed`
+    async fn load_flowed(&mut self, message_id: Uuid) {
+        self.flowed = match &self.database {
+            Some(database) => database.is_flowed(message_id).await.unwrap_or_else(|e| {
+                tracing::debug!("Failed to load flowed flag for {}: {}", message_id, e);
+                false
+            }),
+            None => false,
+        };
+    }
+
+    /// Set the size limit for rendered bodies
+    pub fn set_preview_limits(&mut self, config: PreviewLimitsConfig) {
+        self.preview_limits = config;
+    }
+
+    /// Whether the displayed message was cut to the render limit
+    pub fn is_truncated(&self) -> bool {
+        self.truncated_from.is_some()
+    }
+
+    /// Reload the displayed message without the size limit
+    ///
+    /// Returns false when the message wasn't truncated.
+    pub async fn render_full_message(&mut self) -> Result<bool, Box<dyn std::error::Error>> {
+        let Some(message_id) = self.current_message_id.filter(|_| self.truncated_from.is_some())
+        else {
+            return Ok(false);
+        };
+
+        self.render_full.insert(message_id);
+        self.render_cache.remove(&message_id);
+        self.load_message_by_id(message_id).await?;
+        Ok(true)
+    }
+
+    /// Set how messages are opened in the web browser
+    pub fn set_browser_view_config(&mut self, config: BrowserViewConfig) {
+        self.browser_view = config;
+    }
+
+    /// Set the handlers used for links and attachments
+    pub fn set_opener_config(&mut self, config: OpenerConfig) {
+        self.opener = config;
+    }
+
+    pub fn opener(&self) -> &OpenerConfig {
+        &self.opener
+    }
+
+    /// Open the displayed message's HTML in the default web browser
+    ///
+    /// Uses the full stored body, even when the preview is truncated.
+    pub async fn open_in_browser(&self, theme: &Theme) -> Result<(), Box<dyn std::error::Error>> {
+        let (Some(database), Some(message_id)) = (&self.database, self.current_message_id) else {
+            return Err("No message selected".into());
+        };
+        let message = self
+            .find_message_by_id(database, message_id)
+            .await?
+            .ok_or("Message not found")?;
+        let html = message
+            .body_html
+            .as_deref()
+            .filter(|html| !html.trim().is_empty())
+            .ok_or("This message has no HTML content")?;
+
+        let images = browser_view::inline_images(&message.attachments).await;
+        let document = browser_view::build_document(
+            &message.subject,
+            html,
+            &images,
+            self.browser_view.block_remote_content
+                && !self.remote_images_allowed.contains(&message_id),
+            self.browser_view.match_theme.then_some(theme),
+        );
+        let path =
+            browser_view::open_in_browser(&document, &self.browser_view, &self.opener).await?;
+        tracing::info!("Opened message {} in browser from {}", message_id, path.display());
+        Ok(())
+    }
+
+    /// Set the information density; a change re-renders cached messages
+    pub fn set_information_density(&mut self, density: InformationDensity) {
+        self.density = density;
+    }
+
+    /// Number of messages currently held rendered
+    pub fn cached_render_count(&self) -> usize {
+        self.render_cache.len()
+    }
+
+    /// Load and render messages in the background so moving to them is instant
+    ///
+    /// Intended for the messages either side of the selection. Messages whose
+    /// body isn't stored locally are skipped; fetching them from IMAP is left
+    /// to the normal load when they're actually opened.
+    pub fn prerender_messages(&mut self, message_ids: &[Uuid]) {
+        let Some(database) = self.database.clone() else {
+            return;
+        };
+
+        let message_ids: Vec<Uuid> = message_ids
+            .iter()
+            .copied()
+            .filter(|id| {
+                Some(*id) != self.current_message_id
+                    && !self.render_cache.contains(id)
+                    && !self.prerendering.contains(id)
+            })
+            .collect();
+        if message_ids.is_empty() {
+            return;
+        }
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        self.prerendering.extend(message_ids.iter().copied());
+        let jobs: Vec<(Uuid, Option<usize>)> = message_ids
+            .iter()
+            .map(|id| (*id, self.body_limit(*id)))
+            .collect();
+        let width = self.html_renderer.max_width;
+        let sender = self.prerender_tx.clone();
+        let imap_manager = self.imap_manager.clone();
+
+        runtime.spawn(async move {
+            let mut renderer = crate::html::HtmlRenderer::new(width);
+
+            for (message_id, max_bytes) in jobs {
+                let message = match database.get_message_by_id(message_id).await {
+                    Ok(Some(message)) => message,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        tracing::debug!("Pre-render: failed to load {}: {}", message_id, e);
+                        continue;
+                    }
+                };
+                let text_only = match &imap_manager {
+                    Some(imap_manager) => {
+                        imap_manager.is_plain_text_only(&message.account_id).await
+                    }
+                    None => false,
+                };
+                let Some((body, content_type)) = Self::local_body(&message, text_only) else {
+                    continue;
+                };
+                let (body, truncated_from) = Self::limit_body(body, &content_type, max_bytes);
+
+                let (html_lines, image_sources) = if content_type == ContentType::Html {
+                    let image_sources = if truncated_from.is_none() {
+                        extract_images_from_html(&body)
+                            .into_iter()
+                            .map(|image| image.src)
+                            .collect()
+                    } else {
+                        Vec::new()
+                    };
+                    (Some(renderer.render_html(&body).lines), image_sources)
+                } else {
+                    (None, Vec::new())
+                };
+
+                let prerendered = PrerenderedMessage {
+                    message,
+                    body,
+                    content_type,
+                    html_lines,
+                    image_sources,
+                    truncated_from,
+                    width,
+                };
+                if sender.send(prerendered).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Move finished background renders into the cache
+    fn collect_prerendered(&mut self) {
+        while let Ok(prerendered) = self.prerender_rx.try_recv() {
+            let message_id = prerendered.message.id;
+            self.prerendering.remove(&message_id);
+
+            // Rendered for a width that no longer applies
+            if prerendered.width != self.html_renderer.max_width
+                || self.render_cache.contains(&message_id)
+            {
+                continue;
+            }
+
+            let content = self.build_email_content(
+                &prerendered.message,
+                prerendered.body,
+                prerendered.content_type,
+            );
+            self.render_cache.insert(
+                message_id,
+                RenderedMessage {
+                    content,
+                    html_lines: prerendered.html_lines,
+                    image_sources: prerendered.image_sources,
+                    truncated_from: prerendered.truncated_from,
+                },
+            );
+        }
+    }
+
+    /// Drop cached renders when the theme, density or width changed
+    fn update_render_settings(&mut self, theme_name: &str) {
+        let changed = self.render_cache.settings().is_none_or(|settings| {
+            settings.theme != theme_name
+                || settings.density != self.density
+                || settings.width != self.html_renderer.max_width
+        });
+        if changed {
+            self.render_cache.update_settings(RenderSettings {
+                theme: theme_name.to_string(),
+                density: self.density,
+                width: self.html_renderer.max_width,
+            });
+        }
+    }
+
+    /// Rendered HTML body of the displayed message, reusing the cached render
+    fn current_html_lines(&mut self, email: &EmailContent) -> Vec<Line<'static>> {
+        if let Some(lines) = self
+            .current_message_id
+            .and_then(|id| self.render_cache.peek(&id))
+            .and_then(|rendered| rendered.html_lines.clone())
+        {
+            return lines;
+        }
+
+        let lines = self.html_renderer.render_html(&email.body).lines;
+        tracing::debug!(
+            "Content Preview: HTML renderer generated {} lines",
+            lines.len()
+        );
+
+        // Keep it for later frames, e.g. after a theme change emptied the cache
+        if let (Some(message_id), ContentType::Html) = (self.current_message_id, &email.content_type) {
+            let image_sources = if self.truncated_from.is_none() {
+                extract_images_from_html(&email.body)
+                    .into_iter()
+                    .map(|image| image.src)
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            self.render_cache.insert(
+                message_id,
+                RenderedMessage {
+                    content: email.clone(),
+                    html_lines: Some(lines.clone()),
+                    image_sources,
+                    truncated_from: self.truncated_from,
+                },
+            );
+        }
+
+        lines
+    }
+
     /// Clear the current message and show empty state
     pub fn clear_message(&mut self) {
         self.email_content = None;
         self.current_message_id = None;
+        self.truncated_from = None;
+        self.mime_defects.clear();
+        self.flowed = false;
+        self.reflowed = None;
         self.loading = false;
         self.scroll = 0;
 
@@ -1755,7 +2261,7 @@ This is a sample email showcasing the modern email display format."
                            subject, from_addr, from_name, to_addrs, cc_addrs, bcc_addrs, reply_to, date,
                            body_text, body_html, attachments,
                            flags, labels, size, priority,
-                           created_at, updated_at, last_synced, sync_version, is_draft, is_deleted
+                           created_at, updated_at, last_synced, sync_version, is_draft, is_deleted, snoozed_until, inbox_tab
                     FROM messages WHERE id = ? AND is_deleted = FALSE");
 
         let row = sqlx::query(&query)
@@ -1773,47 +2279,20 @@ This is a sample email showcasing the modern email display format."
     }
 
     /// Convert a StoredMessage to EmailContent for display with on-demand IMAP body fetching
-    async fn convert_stored_message_to_email_content(&self, message: &StoredMessage) -> EmailContent {
-        let headers = EmailHeader {
-            from: format!(
-                "{} <{}>",
-                message.from_name.as_deref().unwrap_or(""),
-                message.from_addr
-            ),
-            to: message.to_addrs.clone(),
-            cc: message.cc_addrs.clone(),
-            bcc: message.bcc_addrs.clone(),
-            subject: message.subject.clone(),
-            date: message.date.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
-            message_id: message.message_id.clone().unwrap_or_default(),
-            reply_to: message.reply_to.clone(),
-            in_reply_to: message.in_reply_to.clone(),
+    ///
+    /// Also returns the full body size when the body was cut to the render limit.
+    async fn convert_stored_message_to_email_content(
+        &self,
+        message: &StoredMessage,
+    ) -> (EmailContent, Option<usize>) {
+        let text_only = match &self.imap_manager {
+            Some(imap_manager) => imap_manager.is_plain_text_only(&message.account_id).await,
+            None => false,
         };
-
-        // Prefer HTML body if available and has content, otherwise use text body
-        // NOTE: Content should already be cleaned by the database layer, so we use it directly
-        let (body, content_type) = if let Some(ref html_body) = message.body_html {
-            if !html_body.trim().is_empty() {
-                // Always treat HTML as HTML regardless of detection - the renderer will handle it
-                tracing::debug!(
-                    "Content Preview: Using pre-cleaned HTML body (length: {})",
-                    html_body.len()
-                );
-                (html_body.clone(), ContentType::Html)
-            } else if let Some(ref text_body) = message.body_text {
-                // Use pre-cleaned text body and check if it contains HTML content
-                if crate::html::is_html_content(text_body) {
-                    tracing::debug!("Content Preview: HTML body empty, but text body contains HTML (length: {})", text_body.len());
-                    (text_body.clone(), ContentType::Html)
-                } else {
-                    tracing::debug!(
-                        "Content Preview: HTML body empty, using plain text body (length: {})",
-                        text_body.len()
-                    );
-                    (text_body.clone(), ContentType::PlainText)
-                }
-            } else {
-                tracing::debug!("Content Preview: Only empty HTML body available, attempting IMAP fetch");
+        let (body, content_type) = match Self::local_body(message, text_only) {
+            Some(local) => local,
+            None => {
+                tracing::debug!("Content Preview: No body content available, attempting IMAP fetch");
                 // Try to fetch body from IMAP if available
                 match self.fetch_message_body_from_imap(message).await {
                     Ok(Some((fetched_body, content_type))) => {
@@ -1830,38 +2309,99 @@ This is a sample email showcasing the modern email display format."
                     }
                 }
             }
-        } else if let Some(ref text_body) = message.body_text {
-            // Use pre-cleaned text body and check if it contains HTML content
-            if crate::html::is_html_content(text_body) {
-                tracing::debug!(
-                    "Content Preview: No HTML body, but text body contains HTML (length: {})",
-                    text_body.len()
-                );
-                (text_body.clone(), ContentType::Html)
-            } else {
+        };
+
+        let (body, truncated_from) =
+            Self::limit_body(body, &content_type, self.body_limit(message.id));
+        (self.build_email_content(message, body, content_type), truncated_from)
+    }
+
+    /// Render limit for a message, unless the user asked to see it in full
+    fn body_limit(&self, message_id: Uuid) -> Option<usize> {
+        if self.render_full.contains(&message_id) {
+            None
+        } else {
+            self.preview_limits.max_render_bytes()
+        }
+    }
+
+    /// Cut a body to the render limit, returning the size it was cut from
+    fn limit_body(
+        body: String,
+        content_type: &ContentType,
+        max_bytes: Option<usize>,
+    ) -> (String, Option<usize>) {
+        let is_html = *content_type == ContentType::Html;
+        match max_bytes.and_then(|max| preview_limits::truncate_body(&body, max, is_html)) {
+            Some(truncated) => (truncated, Some(body.len())),
+            None => (body, None),
+        }
+    }
+
+    /// Body stored locally, preferring HTML when it has content
+    ///
+    /// `text_only` accounts get plain text, converted from the HTML when
+    /// that's all there is. Returns `None` when the body still has to be
+    /// fetched from the server.
+    fn local_body(message: &StoredMessage, text_only: bool) -> Option<(String, ContentType)> {
+        if text_only {
+            return crate::email::plain_text::text_only_body(
+                message.body_text.as_deref(),
+                message.body_html.as_deref(),
+            )
+            .map(|body| (body, ContentType::PlainText));
+        }
+
+        // NOTE: Content should already be cleaned by the database layer, so we use it directly
+        if let Some(ref html_body) = message.body_html {
+            if !html_body.trim().is_empty() {
+                // Always treat HTML as HTML regardless of detection - the renderer will handle it
                 tracing::debug!(
-                    "Content Preview: Using plain text body (length: {})",
-                    text_body.len()
+                    "Content Preview: Using pre-cleaned HTML body (length: {})",
+                    html_body.len()
                 );
-                (text_body.clone(), ContentType::PlainText)
+                return Some((html_body.clone(), ContentType::Html));
             }
+        }
+
+        let text_body = message.body_text.as_ref()?;
+        // Use pre-cleaned text body and check if it contains HTML content
+        if crate::html::is_html_content(text_body) {
+            tracing::debug!(
+                "Content Preview: Text body contains HTML (length: {})",
+                text_body.len()
+            );
+            Some((text_body.clone(), ContentType::Html))
         } else {
-            tracing::debug!("Content Preview: No body content available, attempting IMAP fetch");
-            // Try to fetch body from IMAP if available
-            match self.fetch_message_body_from_imap(message).await {
-                Ok(Some((fetched_body, content_type))) => {
-                    tracing::info!("Content Preview: Successfully fetched body from IMAP (length: {}, type: {:?})", fetched_body.len(), content_type);
-                    (fetched_body, content_type)
-                }
-                Ok(None) => {
-                    tracing::warn!("Content Preview: IMAP fetch returned no content");
-                    ("No content available".to_string(), ContentType::PlainText)
-                }
-                Err(e) => {
-                    tracing::error!("Content Preview: Failed to fetch from IMAP: {}", e);
-                    ("No content available (IMAP fetch failed)".to_string(), ContentType::PlainText)
-                }
-            }
+            tracing::debug!(
+                "Content Preview: Using plain text body (length: {})",
+                text_body.len()
+            );
+            Some((text_body.clone(), ContentType::PlainText))
+        }
+    }
+
+    /// Assemble display content from a message and its body
+    fn build_email_content(
+        &self,
+        message: &StoredMessage,
+        body: String,
+        content_type: ContentType,
+    ) -> EmailContent {
+        let headers = EmailHeader {
+            from: format!(
+                "{} <{}>",
+                message.from_name.as_deref().unwrap_or(""),
+                message.from_addr
+            ),
+            to: message.to_addrs.clone(),
+            cc: message.cc_addrs.clone(),
+            bcc: message.bcc_addrs.clone(),
+            subject: message.subject.clone(),
+            date: message.date.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            message_id: message.message_id.clone().unwrap_or_default(),
+            reply_to: message.reply_to.clone(),
+            in_reply_to: message.in_reply_to.clone(),
         };
 
         // Parse URLs from the body
@@ -1918,6 +2458,9 @@ This is a sample email showcasing the modern email display format."
         }
 
         let image_refs = extract_images_from_html(html_content);
+        let load_remote = self
+            .current_message_id
+            .is_some_and(|id| self.remote_images_allowed.contains(&id));
 
         for img_ref in image_refs {
             // Skip if already processed
@@ -1925,6 +2468,12 @@ This is a sample email showcasing the modern email display format."
                 continue;
             }
 
+            // Fetching a remote image tells the sender the message was read,
+            // so only do it when asked, and never for tracking pixels
+            if img_ref.is_http_url() && (!load_remote || img_ref.is_tracking_pixel()) {
+                continue;
+            }
+
             let rendered_content = if img_ref.is_data_url() {
                 // Handle base64 embedded images
                 if let Some((data, mime_type)) = img_ref.parse_data_url() {
@@ -1978,6 +2527,28 @@ This is a sample email showcasing the modern email display format."
         }
     }
 
+    /// Load the remote images of a message, which are held back by default
+    pub async fn allow_remote_images(&mut self, message_id: Uuid) {
+        if !self.remote_images_allowed.insert(message_id) {
+            return;
+        }
+        if self.current_message_id == Some(message_id) {
+            if let Some(body) = self
+                .email_content
+                .as_ref()
+                .filter(|content| content.content_type == ContentType::Html)
+                .map(|content| content.body.clone())
+            {
+                self.load_images_from_html(&body).await;
+            }
+        }
+    }
+
+    /// Whether the remote images of a message have been allowed to load
+    pub fn remote_images_allowed(&self, message_id: Uuid) -> bool {
+        self.remote_images_allowed.contains(&message_id)
+    }
+
     /// Clear image cache
     pub async fn clear_image_cache(&mut self) {
         self.processed_images.clear();
@@ -2097,51 +2668,155 @@ This is a sample email showcasing the modern email display format."
         }
     }
 
-    /// Save a specific attachment to the specified path or default downloads location
+    /// Save a specific attachment to the specified path or the configured download directory
     pub async fn save_attachment(
         &self,
         attachment: &Attachment,
         save_path: Option<std::path::PathBuf>,
     ) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
-        let target_path = if let Some(path) = save_path {
-            // Use provided path
-            if path.is_dir() {
-                path.join(&attachment.filename)
-            } else {
-                path
-            }
-        } else {
-            // Use default downloads directory
-            let downloads_dir = self.get_downloads_directory()?;
-            std::fs::create_dir_all(&downloads_dir)?;
-            downloads_dir.join(&attachment.filename)
-        };
+        let target_path = self.attachment_target_path(attachment, save_path).await?;
 
         // Check if we need to download the attachment data
         let attachment_data = self.get_attachment_data(attachment).await?;
 
         // Write the data to file
-        std::fs::write(&target_path, attachment_data)?;
+        tokio::fs::write(&target_path, attachment_data).await?;
 
         Ok(target_path)
     }
 
-    /// Get the default downloads directory
-    fn get_downloads_directory(&self) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
-        // Try to find standard downloads directory
-        if let Some(home_dir) = dirs::home_dir() {
-            let downloads = home_dir.join("Downloads");
-            if downloads.exists() {
-                return Ok(downloads);
+    /// Start saving the selected attachment
+    ///
+    /// Attachments already stored locally are written straight away. Others
+    /// come back as a download for the caller to run in the background, so a
+    /// large attachment doesn't hold up the interface.
+    pub async fn begin_save_selected_attachment(
+        &self,
+    ) -> Result<AttachmentSave, Box<dyn std::error::Error>> {
+        let attachment = self
+            .get_selected_attachment()
+            .ok_or("No attachment selected")?;
+        let target = self.attachment_target_path(attachment, None).await?;
+
+        let (Some(ref database), Some(message_id)) = (&self.database, self.current_message_id)
+        else {
+            return Err("No message loaded".into());
+        };
+        let message = self
+            .find_message_by_id(database, message_id)
+            .await?
+            .ok_or("Message not found")?;
+
+        let stored = message
+            .attachments
+            .iter()
+            .find(|stored| stored.filename == attachment.filename);
+        if let Some(data) = stored.and_then(|stored| stored.data.as_ref()) {
+            tokio::fs::write(&target, data).await?;
+            return Ok(AttachmentSave::Saved(target));
+        }
+        if let Some(file_path) = stored.and_then(|stored| stored.file_path.as_ref()) {
+            tokio::fs::copy(file_path, &target).await?;
+            return Ok(AttachmentSave::Saved(target));
+        }
+
+        let imap_manager = self
+            .imap_manager
+            .as_ref()
+            .ok_or("IMAP manager not available for attachment download")?;
+        if let Some(jmap) = imap_manager.get_jmap_client(&message.account_id).await {
+            let email_id = database
+                .jmap_email_ids(&message.account_id, &[message.imap_uid])
+                .await?
+                .pop()
+                .ok_or("Message is not on the JMAP server")?;
+            let data = jmap
+                .lock()
+                .await
+                .download_attachment(&email_id, &attachment.filename)
+                .await?;
+            tokio::fs::write(&target, data).await?;
+            return Ok(AttachmentSave::Saved(target));
+        }
+        let client = imap_manager.get_client(&message.account_id).await?;
+        Ok(AttachmentSave::Download(crate::email::AttachmentDownload {
+            client,
+            folder: message.folder_name.clone(),
+            uid: message.imap_uid,
+            filename: attachment.filename.clone(),
+            target,
+        }))
+    }
+
+    /// Where an attachment is saved: `save_path`, a file in `save_path` when
+    /// it is a directory, or the configured download directory
+    async fn attachment_target_path(
+        &self,
+        attachment: &Attachment,
+        save_path: Option<std::path::PathBuf>,
+    ) -> std::io::Result<std::path::PathBuf> {
+        Ok(match save_path {
+            Some(path) if path.is_dir() => self
+                .attachment_save_config
+                .target_path(&path, &attachment.filename),
+            Some(path) => path,
+            None => {
+                // Use the configured download directory and layout
+                let target_dir = self.attachment_target_directory();
+                tokio::fs::create_dir_all(&target_dir).await?;
+                self.attachment_save_config
+                    .target_path(&target_dir, &attachment.filename)
             }
+        })
+    }
 
-            // Fallback to creating a comunicado downloads folder in home
-            let comunicado_downloads = home_dir.join("comunicado-downloads");
-            return Ok(comunicado_downloads);
+    /// Save every attachment of the current message, returning the saved paths
+    pub async fn save_all_attachments(
+        &self,
+    ) -> Result<Vec<std::path::PathBuf>, Box<dyn std::error::Error>> {
+        let attachments = match self.email_content {
+            Some(ref email) if !email.attachments.is_empty() => email.attachments.clone(),
+            _ => return Err("Message has no attachments".into()),
+        };
+
+        let mut saved_paths = Vec::with_capacity(attachments.len());
+        for attachment in &attachments {
+            saved_paths.push(self.save_attachment(attachment, None).await?);
         }
 
-        // Ultimate fallback to current directory
-        Ok(std::path::PathBuf::from("./downloads"))
+        Ok(saved_paths)
+    }
+
+    /// Set the attachment save configuration
+    pub fn set_attachment_save_config(&mut self, config: AttachmentSaveConfig) {
+        self.attachment_save_config = config;
+    }
+
+    /// Get the attachment save configuration
+    pub fn attachment_save_config(&self) -> &AttachmentSaveConfig {
+        &self.attachment_save_config
+    }
+
+    /// Directory for saving attachments of the current message
+    fn attachment_target_directory(&self) -> std::path::PathBuf {
+        let (sender, date) = match self.email_content {
+            Some(ref email) => {
+                let date = chrono::NaiveDateTime::parse_from_str(
+                    &email.headers.date,
+                    "%Y-%m-%d %H:%M:%S UTC",
+                )
+                .ok()
+                .map(|naive| {
+                    naive
+                        .and_utc()
+                        .with_timezone(&chrono::Local)
+                });
+                (email.headers.from.as_str(), date)
+            }
+            None => ("", None),
+        };
+
+        self.attachment_save_config.target_directory(sender, date)
     }
 
     /// Get attachment data (either from memory or by downloading from IMAP server)
@@ -2259,7 +2934,7 @@ This is a sample email showcasing the modern email display format."
         self.attachment_viewer.clear();
     }
 
-    /// Open the selected attachment with the system default application (xdg-open)
+    /// Open the selected attachment with its MIME type's handler, or the system default
     pub async fn open_attachment_with_system(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(attachment) = self.get_selected_attachment() {
             // Get attachment data
@@ -2272,26 +2947,14 @@ This is a sample email showcasing the modern email display format."
             // Write attachment data to temporary file
             std::fs::write(&temp_path, &attachment_data)?;
 
-            // Use xdg-open to open the file with system default application
-            let output = std::process::Command::new("xdg-open")
-                .arg(&temp_path)
-                .output();
-
-            match output {
-                Ok(result) => {
-                    if result.status.success() {
-                        tracing::info!(
-                            "Successfully opened attachment '{}' with system application",
-                            attachment.filename
-                        );
-                        Ok(())
-                    } else {
-                        let error_msg = String::from_utf8_lossy(&result.stderr);
-                        Err(format!("xdg-open failed: {}", error_msg).into())
-                    }
-                }
-                Err(e) => Err(format!("Failed to execute xdg-open: {}", e).into()),
-            }
+            self.opener
+                .open_file(&temp_path, &attachment.content_type)
+                .await?;
+            tracing::info!(
+                "Successfully opened attachment '{}' with system application",
+                attachment.filename
+            );
+            Ok(())
         } else {
             Err("No attachment selected".into())
         }
@@ -2316,6 +2979,15 @@ This is a sample email showcasing the modern email display format."
                 self.attachment_viewer.switch_to_text_mode().await?;
                 Ok(true)
             }
+            'o' => {
+                if let Some((path, mime_type)) =
+                    self.attachment_viewer.export_for_external().await?
+                {
+                    self.opener.open_file(&path, &mime_type).await?;
+                    tracing::info!("Opened attachment {:?} externally", path);
+                }
+                Ok(true)
+            }
             's' => {
                 if let Some(attachment_info) = self.attachment_viewer.current_attachment() {
                     let path = self
@@ -2421,6 +3093,63 @@ This is a sample email showcasing the modern email display format."
         self.clipboard_manager.is_available()
     }
 
+    /// Set animation preferences; playing none skips decoding entirely
+    pub fn set_animation_config(&mut self, config: crate::ui::animated_content::AnimationConfig) {
+        self.animation_config = config;
+    }
+
+    /// Set reading pane reflow preferences
+    pub fn set_reflow_config(&mut self, config: ReflowConfig) {
+        self.reflow = config.enabled;
+        self.reflow_config = config;
+        self.reflowed = None;
+    }
+
+    /// Switch plain text bodies between as-sent and reflowed, returning
+    /// whether reflow is now on
+    pub fn toggle_reflow(&mut self) -> bool {
+        self.reflow = !self.reflow;
+        self.scroll = 0;
+        self.reflow
+    }
+
+    /// Whether the displayed body is shown reflowed
+    pub fn is_reflowing(&self) -> bool {
+        self.reflow
+            && self.view_mode == ViewMode::Formatted
+            && self
+                .email_content
+                .as_ref()
+                .is_some_and(|email| email.content_type != ContentType::Html)
+    }
+
+    /// Body lines reflowed to the configured column, cached per column
+    fn reflowed_content(&mut self, content: &[ContentLine], pane_width: usize) -> Vec<ContentLine> {
+        let column = self.reflow_config.wrap_column(pane_width);
+        if let Some((cached_column, lines)) = &self.reflowed {
+            if *cached_column == column {
+                return lines.clone();
+            }
+        }
+
+        let text = content
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lines = self.parse_content_lines(&reflow(&text, column, self.flowed).join("\n"));
+        self.reflowed = Some((column, lines.clone()));
+        lines
+    }
+
+    /// Number of body lines as displayed
+    fn body_line_count(&self, email: &EmailContent) -> usize {
+        match &self.reflowed {
+            Some((_, lines)) if self.is_reflowing() => lines.len(),
+            _ => email.parsed_content.len(),
+        }
+    }
+
     /// Initialize animation manager
     pub fn initialize_animation_manager(&mut self) {
         if self.animation_manager.is_none() {
@@ -2566,8 +3295,15 @@ This is a sample email showcasing the modern email display format."
         use crate::images::extract_images_from_html;
 
         let images = extract_images_from_html(html_content);
+        let load_remote = self
+            .current_message_id
+            .is_some_and(|id| self.remote_images_allowed.contains(&id));
 
         for image_ref in images {
+            if image_ref.is_http_url() && (!load_remote || image_ref.is_tracking_pixel()) {
+                continue;
+            }
+
             // Check if it's a GIF image
             if image_ref.src.to_lowercase().contains(".gif") {
                 if image_ref.is_http_url() {
@@ -2606,9 +3342,18 @@ This is a sample email showcasing the modern email display format."
 
     /// Schedule animation processing for HTML content in the background
     fn schedule_animation_processing(&mut self, html_body: String) {
+        // The preview has no playback controls, so only autoplay gets this far
+        if !self.animation_config.autoplay_now() {
+            return;
+        }
+
         // Only process if we have an animation manager and animations are supported
         if let Some(animation_manager) = self.animation_manager.clone() {
             if animation_manager.supports_animations() {
+                let load_remote = self
+                    .current_message_id
+                    .is_some_and(|id| self.remote_images_allowed.contains(&id));
+
                 // Create a channel to communicate animation updates back to the main thread
                 let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
 
@@ -2619,6 +3364,13 @@ This is a sample email showcasing the modern email display format."
                     let images = extract_images_from_html(&html_body);
 
                     for image_ref in images {
+                        // Remote images only load once allowed for the message
+                        if image_ref.is_http_url()
+                            && (!load_remote || image_ref.is_tracking_pixel())
+                        {
+                            continue;
+                        }
+
                         // Check if it's a GIF image
                         if image_ref.src.to_lowercase().contains(".gif") {
                             let animation_id = if image_ref.is_http_url() {
@@ -2972,6 +3724,62 @@ This is a sample email showcasing the modern email display format."
             message.imap_uid
         );
 
+        // JMAP accounts store synthetic UIDs, so fetch by email id instead
+        let jmap = imap_manager.get_jmap_client(&message.account_id).await;
+        let fetched_messages = if let Some(jmap) = jmap {
+            let database = self.database.as_ref().ok_or("Database not available")?;
+            let ids = database
+                .jmap_email_ids(&message.account_id, &[message.imap_uid])
+                .await?;
+            let Some(email_id) = ids.first() else {
+                tracing::warn!("Cannot fetch body: no JMAP email for UID {}", message.imap_uid);
+                return Ok(None);
+            };
+            let mut jmap_message = crate::imap::ImapMessage::new(0);
+            jmap_message.body = Some(jmap.lock().await.download_email(email_id).await?);
+            vec![jmap_message]
+        } else {
+            Self::fetch_imap_body(imap_manager, message).await?
+        };
+
+        // Extract body from the first (and should be only) message
+        if let Some(imap_message) = fetched_messages.first() {
+            if let Some(ref body) = imap_message.body {
+                tracing::info!("Successfully fetched message body from IMAP (length: {})", body.len());
+                
+                // Parse the raw email body to extract HTML/text content
+                let (mut clean_body, mut content_type, defects) = self.parse_email_body(body)?;
+                if content_type == ContentType::Html
+                    && imap_manager.is_plain_text_only(&message.account_id).await
+                {
+                    clean_body = crate::email::plain_text::text_only_body(None, Some(&clean_body))
+                        .unwrap_or_default();
+                    content_type = ContentType::PlainText;
+                }
+                
+                // Update the database with the fetched content
+                self.update_message_body_in_database(message, &clean_body, &content_type).await?;
+                if !defects.is_empty() {
+                    if let Some(ref database) = self.database {
+                        if let Err(e) = database.store_message_defects(message.id, &defects).await {
+                            tracing::warn!("Failed to store MIME defects for {}: {}", message.id, e);
+                        }
+                    }
+                }
+                
+                return Ok(Some((clean_body, content_type)));
+            }
+        }
+
+        tracing::warn!("No body content found in IMAP response");
+        Ok(None)
+    }
+
+    /// Fetch a message's text parts, or the whole message, by IMAP UID
+    async fn fetch_imap_body(
+        imap_manager: &crate::imap::ImapAccountManager,
+        message: &StoredMessage,
+    ) -> Result<Vec<crate::imap::ImapMessage>, Box<dyn std::error::Error + Send + Sync>> {
         // Get IMAP client for the account
         let imap_client_arc = match imap_manager.get_client(&message.account_id).await {
             Ok(client) => client,
@@ -2991,60 +3799,132 @@ This is a sample email showcasing the modern email display format."
                 return Err(format!("Failed to select folder: {}", e).into());
             }
 
-            // Fetch the message body using UID
-            let uid_set = message.imap_uid.to_string();
-            let fetch_items = &["BODY[]"]; // Fetch the entire message body
+            // Plain-text-only accounts never download HTML
+            if imap_manager.is_plain_text_only(&message.account_id).await {
+                let mut text_message = crate::imap::ImapMessage::new(0);
+                text_message.body = Some(imap_client.uid_fetch_without_html(message.imap_uid).await?);
+                return Ok(vec![text_message]);
+            }
 
-            match imap_client.uid_fetch_messages(&uid_set, fetch_items).await {
-                Ok(messages) => messages,
+            // Multipart messages render from their text parts alone, leaving
+            // attachments on the server until they are saved
+            let text_parts = match imap_client.uid_fetch_bodystructure(message.imap_uid).await {
+                Ok(Some(structure)) if structure.is_multipart() => {
+                    Self::fetch_text_parts(&mut imap_client, message.imap_uid, &structure).await
+                }
+                Ok(_) => None,
                 Err(e) => {
-                    tracing::error!("Failed to fetch message body: {}", e);
-                    return Err(format!("IMAP fetch failed: {}", e).into());
+                    tracing::debug!("BODYSTRUCTURE fetch failed, fetching whole message: {}", e);
+                    None
+                }
+            };
+
+            match text_parts {
+                Some(body) => {
+                    let mut text_message = crate::imap::ImapMessage::new(0);
+                    text_message.body = Some(body);
+                    vec![text_message]
+                }
+                None => {
+                    // Fetch the message body using UID
+                    let uid_set = message.imap_uid.to_string();
+                    let fetch_items = &["BODY[]"]; // Fetch the entire message body
+
+                    match imap_client.uid_fetch_messages(&uid_set, fetch_items).await {
+                        Ok(messages) => messages,
+                        Err(e) => {
+                            tracing::error!("Failed to fetch message body: {}", e);
+                            return Err(format!("IMAP fetch failed: {}", e).into());
+                        }
+                    }
                 }
             }
         };
 
-        // Extract body from the first (and should be only) message
-        if let Some(imap_message) = fetched_messages.first() {
-            if let Some(ref body) = imap_message.body {
-                tracing::info!("Successfully fetched message body from IMAP (length: {})", body.len());
-                
-                // Parse the raw email body to extract HTML/text content
-                let (clean_body, content_type) = self.parse_email_body(body)?;
-                
-                // Update the database with the fetched content
-                self.update_message_body_in_database(message, &clean_body, &content_type).await?;
-                
-                return Ok(Some((clean_body, content_type)));
+        Ok(fetched_messages)
+    }
+
+    /// Fetch just the text/plain and text/html parts of a multipart message
+    ///
+    /// The parts are returned wrapped in a multipart/alternative message of
+    /// their own, so the usual MIME parsing applies to them. Returns `None`
+    /// when the message has no text parts or a fetch fails, leaving the
+    /// caller to fetch the whole message.
+    async fn fetch_text_parts(
+        client: &mut crate::imap::ImapClient,
+        uid: u32,
+        structure: &crate::imap::BodyStructure,
+    ) -> Option<String> {
+        const BOUNDARY: &str = "comunicado-text-parts";
+
+        let mut message = format!(
+            "MIME-Version: 1.0\r\nContent-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n",
+            BOUNDARY
+        );
+        let mut found = false;
+        for (section, part) in structure.sections() {
+            let disposed = part
+                .disposition
+                .as_deref()
+                .is_some_and(|disposition| disposition == "attachment");
+            if disposed || !(part.is_plain_text() || part.is_html()) {
+                continue;
             }
+
+            let header = client
+                .uid_fetch_section(uid, &format!("{}.MIME", section))
+                .await
+                .ok()?;
+            let body = client.uid_fetch_section(uid, &section).await.ok()?;
+            tracing::debug!(
+                "Fetched text part {} ({} bytes) without the rest of the message",
+                section,
+                body.len()
+            );
+
+            message.push_str(&format!("--{}\r\n", BOUNDARY));
+            message.push_str(&String::from_utf8_lossy(&header));
+            message.push_str(&String::from_utf8_lossy(&body));
+            message.push_str("\r\n");
+            found = true;
         }
+        message.push_str(&format!("--{}--\r\n", BOUNDARY));
 
-        tracing::warn!("No body content found in IMAP response");
-        Ok(None)
+        found.then_some(message)
     }
 
     /// Parse raw email body to extract clean HTML/text content
-    fn parse_email_body(&self, raw_body: &str) -> Result<(String, ContentType), Box<dyn std::error::Error + Send + Sync>> {
-        // Use the existing email parsing logic to extract HTML/text from raw email
-        // This is a simplified implementation - in a real system you'd use a proper MIME parser
-        
-        // Look for HTML content first
+    ///
+    /// Also returns the MIME defects the parser recovered from.
+    fn parse_email_body(
+        &self,
+        raw_body: &str,
+    ) -> Result<(String, ContentType, Vec<crate::mime::MimeDefect>), Box<dyn std::error::Error + Send + Sync>> {
+        let parsed = crate::mime::parse_message(raw_body);
+        if let Some(html) = parsed.html {
+            return Ok((html, ContentType::Html, parsed.defects));
+        }
+        if let Some(text) = parsed.text {
+            return Ok((text, ContentType::PlainText, parsed.defects));
+        }
+
+        // Nothing the parser could recover: look for the parts by name
         if let Some(html_start) = raw_body.find("Content-Type: text/html") {
             if let Some(html_content) = self.extract_html_from_raw_body(&raw_body[html_start..]) {
-                return Ok((html_content, ContentType::Html));
+                return Ok((html_content, ContentType::Html, parsed.defects));
             }
         }
         
         // Look for plain text content
         if let Some(text_start) = raw_body.find("Content-Type: text/plain") {
             if let Some(text_content) = self.extract_text_from_raw_body(&raw_body[text_start..]) {
-                return Ok((text_content, ContentType::PlainText));
+                return Ok((text_content, ContentType::PlainText, parsed.defects));
             }
         }
         
         // Fallback: try to extract any readable content from the body
         let clean_content = self.extract_fallback_content(raw_body);
-        Ok((clean_content, ContentType::PlainText))
+        Ok((clean_content, ContentType::PlainText, parsed.defects))
     }
 
     /// Extract HTML content from raw email body
@@ -178,6 +178,16 @@ impl ContextShortcutsPopup {
             UIMode::KeyboardShortcuts => "Help",
             UIMode::Settings => "Settings",
             UIMode::ContactsPopup => "Contacts",
+            UIMode::AccountDetails => "Account Details",
+            UIMode::SpecialFolders => "Special Folders",
+            UIMode::Retention => "Retention",
+            UIMode::FilterRules => "Filter Rules",
+            UIMode::MboxWizard => "Mbox",
+            UIMode::Headers => "Headers",
+            UIMode::Confirm => "Confirm",
+            UIMode::Links => "Links",
+            UIMode::Snooze => "Snooze",
+            UIMode::Sms => "SMS",
         }
     }
 
@@ -198,6 +208,16 @@ impl ContextShortcutsPopup {
             UIMode::KeyboardShortcuts => "View all available keyboard shortcuts",
             UIMode::Settings => "Configure application settings and preferences",
             UIMode::ContactsPopup => "Browse and manage contacts",
+            UIMode::AccountDetails => "Inspect and override server capabilities",
+            UIMode::SpecialFolders => "Map Sent, Drafts, Trash, Junk and Archive folders",
+            UIMode::Retention => "Preview retention rules and restore pending removals",
+            UIMode::FilterRules => "Try filter rules on the current folder and enable them",
+            UIMode::MboxWizard => "Import mbox files or export folders to mbox",
+            UIMode::Headers => "Inspect headers, the Received chain and authentication results",
+            UIMode::Confirm => "Confirm or cancel a bulk action",
+            UIMode::Links => "Open or copy the links of the displayed message",
+            UIMode::Snooze => "Choose when a snoozed message comes back",
+            UIMode::Sms => "Read SMS conversations and reply through KDE Connect",
         }
     }
 
@@ -33,6 +33,7 @@ pub enum DraftAction {
     Continue,
     LoadDraft(String),   // draft_id
     DeleteDraft(String), // draft_id
+    CancelSchedule(String), // draft_id; keeps it as a plain draft
     Close,
     ToggleSort,
     ToggleDetails,
@@ -153,6 +154,15 @@ impl DraftListUI {
                     DraftAction::Continue
                 }
             }
+            KeyCode::Char('u') => {
+                // Cancel a scheduled send
+                match self.drafts.get(self.selected_index) {
+                    Some(draft) if draft.scheduled_at.is_some() => {
+                        DraftAction::CancelSchedule(draft.id.clone())
+                    }
+                    _ => DraftAction::Continue,
+                }
+            }
             KeyCode::Char('s') => {
                 // Toggle sort
                 DraftAction::ToggleSort
@@ -358,15 +368,28 @@ impl DraftListUI {
 
         // Create line with draft info
         let auto_saved_indicator = if draft.auto_saved { " [Auto]" } else { "" };
+        let schedule_indicator = match draft.scheduled_at {
+            Some(at) if draft.send_attempts > 0 => format!(
+                " ⏰ {} (failed {}×)",
+                at.with_timezone(&Local).format("%m/%d %H:%M"),
+                draft.send_attempts
+            ),
+            Some(at) => format!(" ⏰ {}", at.with_timezone(&Local).format("%m/%d %H:%M")),
+            None => String::new(),
+        };
         let line_text = format!(
-            "{} │ {} │ {}{}",
-            updated_time, to_display, subject_display, auto_saved_indicator
+            "{} │ {} │ {}{}{}",
+            updated_time, to_display, subject_display, auto_saved_indicator, schedule_indicator
         );
 
         let style = if is_selected {
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD)
+        } else if draft.send_attempts > 0 {
+            Style::default().fg(Color::Red)
+        } else if draft.scheduled_at.is_some() {
+            Style::default().fg(Color::Cyan)
         } else if draft.auto_saved {
             Style::default().fg(Color::Gray)
         } else {
@@ -434,6 +457,28 @@ impl DraftListUI {
                     "Auto-saved: {}",
                     if draft.auto_saved { "Yes" } else { "No" }
                 )),
+            ]);
+
+            if let Some(at) = draft.scheduled_at {
+                lines.push(Line::from(format!(
+                    "Send at: {}",
+                    at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                )));
+            }
+            if let Some(ref error) = draft.last_send_error {
+                lines.push(Line::from(format!(
+                    "Failed {} time(s): {}",
+                    draft.send_attempts, error
+                )));
+                if let Some(next) = draft.next_attempt_at {
+                    lines.push(Line::from(format!(
+                        "Next attempt: {}",
+                        next.with_timezone(&Local).format("%H:%M")
+                    )));
+                }
+            }
+
+            lines.extend(vec![
                 Line::from(""),
                 Line::from("Body Preview:"),
                 Line::from("─".repeat(inner.width as usize)),
@@ -475,7 +520,7 @@ impl DraftListUI {
         let status_text = if self.drafts.is_empty() {
             "Esc Close | c Compose"
         } else {
-            "Enter Load | d Delete | s Sort | Tab Details | F5 Refresh | Esc Close"
+            "Enter Load | d Delete | u Unschedule | s Sort | Tab Details | F5 Refresh | Esc Close"
         };
 
         let status = Paragraph::new(status_text)
@@ -6,17 +6,39 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
-
-use crate::email::StoredMessage;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::contacts::avatar::{AvatarCache, AVATAR_HEIGHT, AVATAR_WIDTH};
+use crate::contacts::SenderRecognition;
+use crate::ui::text_width::{display_width, fit_to_width, truncate_to_width};
+use crate::email::{
+    AuthVerdict, CryptoScheme, MessageSecurity, SecurityBadge, SignatureStatus, StoredMessage,
+};
+use crate::html::{EmailSanitizer, SanitizedHtml};
+use crate::mime::{MimeDefect, ParsedBody};
 use crate::theme::Theme;
+use crate::ui::animated_content::{AnimatedAttachment, AnimationConfig, AnimationControlWidget};
+use crate::ui::animation::{AnimationFormat, AnimationManager, AnimationSettings, AnimationState};
 use crate::ui::content_preview::{ContentType, EmailContent, EmailHeader, ViewMode};
 use crate::images::{ImageManager, extract_images_from_html};
+use crate::ui::graphics::{half_block_lines, ImageRenderer, RenderConfig};
+use crate::ui::passphrase_prompt::PassphrasePrompt;
+use crate::ui::quick_reply::QuickReply;
+use crate::ui::quote_fold::{self, FoldedLine, QuoteFold, QuoteFoldConfig};
+
+/// Largest size an animation is drawn at, in terminal cells
+const ANIMATION_MAX_COLUMNS: u16 = 48;
+const ANIMATION_MAX_ROWS: u16 = 12;
 
 /// Email viewer actions
 #[derive(Debug, Clone, PartialEq)]
 pub enum EmailViewerAction {
     Reply,
     ReplyAll,
+    ReplyToList,
+    /// Type a reply in a box under the message
+    QuickReply,
     Forward,
     Edit,
     Delete,
@@ -24,6 +46,12 @@ pub enum EmailViewerAction {
     MarkAsRead,
     MarkAsUnread,
     AddToContacts,
+    /// Open the sender's contact card
+    ViewSenderContact,
+    /// Open the meeting invitation carried by the message
+    ViewInvitation,
+    /// Load the remote images held back in the message
+    LoadRemoteImages,
     Close,
 }
 
@@ -40,6 +68,33 @@ pub struct EmailViewer {
     actions: Vec<EmailViewerAction>,
     #[allow(dead_code)]
     image_manager: ImageManager,
+    /// Sender avatars; the header shows none until a cache is set
+    avatars: Option<AvatarCache>,
+    /// Authentication and signature status computed during sync
+    security: Option<MessageSecurity>,
+    /// Contact match and mail history of the sender
+    recognition: Option<SenderRecognition>,
+    /// Problems the MIME parser recovered from during sync
+    mime_defects: Vec<MimeDefect>,
+    /// Reply being typed under the message
+    quick_reply: Option<QuickReply>,
+    /// Passphrase being typed to decrypt the message
+    passphrase_prompt: Option<PassphrasePrompt>,
+    /// Whether the remote images of the message may load
+    remote_images_loaded: bool,
+    /// HTML body with tracking pixels removed and, until loading is
+    /// allowed, remote images replaced by their alt text
+    sanitized: Option<SanitizedHtml>,
+    /// Animated GIFs attached to the message
+    animation_manager: Arc<AnimationManager>,
+    /// Which animations play; they pause while scrolled out of view
+    animation_controls: AnimationControlWidget,
+    animation_config: AnimationConfig,
+    quote_fold_config: QuoteFoldConfig,
+    /// Quotes the user unfolded, kept per message for the session
+    expanded_quotes: HashMap<uuid::Uuid, HashSet<QuoteFold>>,
+    /// Row of each fold marker in the last rendered content
+    fold_markers: Vec<(usize, QuoteFold)>,
 }
 
 impl EmailViewer {
@@ -56,31 +111,295 @@ impl EmailViewer {
             actions: vec![
                 EmailViewerAction::Reply,
                 EmailViewerAction::ReplyAll,
+                EmailViewerAction::ReplyToList,
+                EmailViewerAction::QuickReply,
                 EmailViewerAction::Forward,
                 EmailViewerAction::Edit,
                 EmailViewerAction::Delete,
                 EmailViewerAction::Archive,
                 EmailViewerAction::MarkAsRead,
                 EmailViewerAction::AddToContacts,
+                EmailViewerAction::ViewSenderContact,
                 EmailViewerAction::Close,
             ],
             image_manager: ImageManager::new().unwrap_or_default(),
+            avatars: None,
+            security: None,
+            recognition: None,
+            mime_defects: Vec::new(),
+            quick_reply: None,
+            passphrase_prompt: None,
+            remote_images_loaded: false,
+            sanitized: None,
+            animation_manager: Arc::new(AnimationManager::new(
+                Arc::new(ImageRenderer::new(RenderConfig::default())),
+                AnimationSettings::default(),
+            )),
+            animation_controls: AnimationControlWidget::new(Vec::new()),
+            animation_config: AnimationConfig::default(),
+            quote_fold_config: QuoteFoldConfig::default(),
+            expanded_quotes: HashMap::new(),
+            fold_markers: Vec::new(),
         }
     }
 
+    /// Show sender avatars from this cache in the header
+    pub fn set_avatar_cache(&mut self, avatars: AvatarCache) {
+        self.avatars = Some(avatars);
+    }
+
+    /// Apply animation preferences to the messages opened from now on
+    pub fn set_animation_config(&mut self, config: AnimationConfig) {
+        self.animation_config = config;
+    }
+
+    /// Set when quotes and forwarded messages are folded
+    pub fn set_quote_fold_config(&mut self, config: QuoteFoldConfig) {
+        self.quote_fold_config = config;
+    }
+
+    /// Whether the terminal can draw avatar photos
+    pub fn supports_images(&self) -> bool {
+        self.image_manager.supports_images()
+    }
+
     /// Set email content to display
     pub fn set_email(&mut self, message: StoredMessage, email_content: EmailContent) {
         self.current_message = Some(message);
         self.email_content = Some(email_content);
         self.sender_contact = None; // Reset contact info when setting new email
+        self.security = None;
+        self.recognition = None;
+        self.mime_defects.clear();
+        self.quick_reply = None;
+        self.passphrase_prompt = None;
         self.scroll_position = 0;
+        self.fold_markers.clear();
         self.show_actions = false;
         self.selected_action = 0;
+        self.remote_images_loaded = false;
+        self.sanitize_body();
+        self.load_animations();
+
+        self.actions.retain(|action| {
+            !matches!(
+                action,
+                EmailViewerAction::ViewInvitation | EmailViewerAction::LoadRemoteImages
+            )
+        });
+        if self.has_invitation() {
+            self.actions.insert(0, EmailViewerAction::ViewInvitation);
+        }
+        if self.has_blocked_images() {
+            let close = self.actions.len() - 1;
+            self.actions
+                .insert(close, EmailViewerAction::LoadRemoteImages);
+        }
+    }
+
+    /// Decode the animated GIFs attached to the current message. Nothing is
+    /// decoded while animations are turned off, and they start paused unless
+    /// autoplay applies.
+    fn load_animations(&mut self) {
+        for animation in self.animation_controls.animations() {
+            if let Some(animation_id) = animation.animation_id {
+                self.animation_manager.remove_animation(animation_id);
+            }
+        }
+
+        let mut animations = Vec::new();
+        if self.animation_config.enabled {
+            let auto_play = self.animation_config.autoplay_now();
+            let gifs = self
+                .current_message
+                .iter()
+                .flat_map(|message| &message.attachments)
+                .filter(|attachment| {
+                    attachment
+                        .content_type
+                        .to_lowercase()
+                        .starts_with("image/gif")
+                });
+
+            for attachment in gifs {
+                let Some(ref data) = attachment.data else {
+                    continue;
+                };
+                let animation_id = match self.animation_manager.load_gif(data) {
+                    Ok(animation_id) => animation_id,
+                    Err(e) => {
+                        tracing::debug!("Not animating {}: {}", attachment.filename, e);
+                        continue;
+                    }
+                };
+                let Some(metadata) = self.animation_manager.get_metadata(animation_id) else {
+                    continue;
+                };
+                // A single frame is a still image, not an animation
+                if metadata.frame_count < 2 {
+                    self.animation_manager.remove_animation(animation_id);
+                    continue;
+                }
+
+                animations.push(AnimatedAttachment {
+                    id: uuid::Uuid::new_v4(),
+                    filename: attachment.filename.clone(),
+                    content_type: attachment.content_type.clone(),
+                    format: AnimationFormat::Gif,
+                    size: attachment.size as u64,
+                    width: Some(metadata.width),
+                    height: Some(metadata.height),
+                    duration_ms: Some(metadata.duration_ms),
+                    frame_count: Some(metadata.frame_count),
+                    auto_play,
+                    animation_id: Some(animation_id),
+                    created_at: chrono::Utc::now(),
+                });
+            }
+        }
+
+        self.animation_controls = AnimationControlWidget::new(animations);
+    }
+
+    /// Whether the message has animations to control
+    pub fn has_animations(&self) -> bool {
+        self.animation_controls.animation_count() > 0
+    }
+
+    /// Whether the current message carries a meeting invitation
+    pub fn has_invitation(&self) -> bool {
+        self.current_message.as_ref().is_some_and(|message| {
+            crate::calendar::InvitationProcessor::new(Vec::new()).has_invitation(message)
+        })
+    }
+
+    /// Open the quick reply box under the message
+    pub fn open_quick_reply(&mut self, quick_reply: QuickReply) {
+        self.show_actions = false;
+        self.quick_reply = Some(quick_reply);
+    }
+
+    /// Close the quick reply box, returning what was typed
+    pub fn close_quick_reply(&mut self) -> Option<QuickReply> {
+        self.quick_reply.take()
+    }
+
+    pub fn quick_reply(&self) -> Option<&QuickReply> {
+        self.quick_reply.as_ref()
+    }
+
+    pub fn quick_reply_mut(&mut self) -> Option<&mut QuickReply> {
+        self.quick_reply.as_mut()
+    }
+
+    /// Whether the message is PGP encrypted and can be decrypted here
+    pub fn is_pgp_encrypted(&self) -> bool {
+        self.security
+            .as_ref()
+            .is_some_and(|security| security.encrypted == Some(CryptoScheme::Pgp))
+    }
+
+    /// Ask for the passphrase of a PGP encrypted message
+    pub fn open_passphrase_prompt(&mut self) {
+        if self.is_pgp_encrypted() && self.quick_reply.is_none() {
+            self.show_actions = false;
+            self.passphrase_prompt = Some(PassphrasePrompt::new());
+        }
+    }
+
+    pub fn close_passphrase_prompt(&mut self) {
+        self.passphrase_prompt = None;
+    }
+
+    pub fn passphrase_prompt(&self) -> Option<&PassphrasePrompt> {
+        self.passphrase_prompt.as_ref()
+    }
+
+    pub fn passphrase_prompt_mut(&mut self) -> Option<&mut PassphrasePrompt> {
+        self.passphrase_prompt.as_mut()
+    }
+
+    /// Show the decrypted content in place of the encrypted message
+    pub fn show_decrypted(&mut self, parsed: &ParsedBody) {
+        let Some(ref mut email) = self.email_content else {
+            return;
+        };
+        let (body, content_type) = match (&parsed.html, &parsed.text) {
+            (Some(html), _) => (html.clone(), ContentType::Html),
+            (None, Some(text)) => (text.clone(), ContentType::PlainText),
+            (None, None) => return,
+        };
+        email.body = body;
+        email.content_type = content_type;
+        self.mime_defects = parsed.defects.clone();
+        self.passphrase_prompt = None;
+        self.scroll_position = 0;
+        self.sanitize_body();
+    }
+
+    /// Remove tracking pixels from the HTML body and hold back remote
+    /// images unless they've been allowed
+    fn sanitize_body(&mut self) {
+        self.sanitized = self
+            .email_content
+            .as_ref()
+            .filter(|email| email.content_type == ContentType::Html)
+            .map(|email| {
+                EmailSanitizer::new()
+                    .load_remote_images(self.remote_images_loaded)
+                    .sanitize(&email.body)
+            });
+    }
+
+    /// Whether the message has remote images that haven't been loaded
+    pub fn has_blocked_images(&self) -> bool {
+        self.sanitized
+            .as_ref()
+            .is_some_and(|sanitized| sanitized.blocked_images > 0)
+    }
+
+    /// Let the message's remote images load. Returns false when there were
+    /// none held back
+    pub fn load_remote_images(&mut self) -> bool {
+        if !self.has_blocked_images() {
+            return false;
+        }
+        self.remote_images_loaded = true;
+        self.sanitize_body();
+        self.actions
+            .retain(|action| *action != EmailViewerAction::LoadRemoteImages);
+        self.selected_action = self.selected_action.min(self.actions.len() - 1);
+        true
     }
 
     /// Set sender contact information
     pub fn set_sender_contact(&mut self, contact: Option<crate::contacts::Contact>) {
         self.sender_contact = contact;
+
+        // Start loading the avatar now that the contact photo is known
+        if let (Some(avatars), Some(email)) = (&self.avatars, &self.email_content) {
+            let (_, address) = Self::parse_sender_info_static(&email.headers.from);
+            let photo = self
+                .sender_contact
+                .as_ref()
+                .and_then(|contact| contact.photo_url.as_deref());
+            avatars.request(&address, photo);
+        }
+    }
+
+    /// Set the security status shown above the message
+    pub fn set_message_security(&mut self, security: Option<MessageSecurity>) {
+        self.security = security;
+    }
+
+    /// Set the sender recognition details shown above the message
+    pub fn set_sender_recognition(&mut self, recognition: Option<SenderRecognition>) {
+        self.recognition = recognition;
+    }
+
+    /// Set the MIME defects behind the malformed-message banner
+    pub fn set_mime_defects(&mut self, defects: Vec<MimeDefect>) {
+        self.mime_defects = defects;
     }
 
     /// Get sender contact information
@@ -134,6 +453,61 @@ impl EmailViewer {
         self.show_raw_headers = !self.show_raw_headers;
     }
 
+    /// Quotes unfolded in the current message
+    fn expanded_quotes(&self) -> Option<&HashSet<QuoteFold>> {
+        let message_id = self.current_message.as_ref()?.id;
+        self.expanded_quotes.get(&message_id)
+    }
+
+    /// Unfold the first folded quote on screen
+    pub fn expand_visible_quote(&mut self, viewport_height: usize) -> bool {
+        let visible = self.scroll_position..self.scroll_position + viewport_height;
+        let Some(fold) = self
+            .fold_markers
+            .iter()
+            .find(|(row, _)| visible.contains(row))
+            .map(|(_, fold)| *fold)
+        else {
+            return false;
+        };
+        let Some(message_id) = self.current_message.as_ref().map(|message| message.id) else {
+            return false;
+        };
+        self.expanded_quotes
+            .entry(message_id)
+            .or_default()
+            .insert(fold);
+        true
+    }
+
+    /// Fold every quote again if any is unfolded, otherwise unfold them all
+    pub fn toggle_all_quotes(&mut self) {
+        let Some(message_id) = self.current_message.as_ref().map(|message| message.id) else {
+            return;
+        };
+        if self
+            .expanded_quotes
+            .remove(&message_id)
+            .is_some_and(|expanded| !expanded.is_empty())
+        {
+            return;
+        }
+
+        let Some(email) = self.display_content() else {
+            return;
+        };
+        let body = Self::body_lines(&email);
+        let lines: Vec<&str> = body.iter().map(String::as_str).collect();
+        let folds = quote_fold::all_folds(&lines, self.quote_fold_config.fold_threshold());
+        self.expanded_quotes
+            .insert(message_id, folds.into_iter().collect());
+    }
+
+    /// Whether the current message has quotes that fold
+    pub fn has_folded_quotes(&self) -> bool {
+        !self.fold_markers.is_empty() || self.expanded_quotes().is_some_and(|set| !set.is_empty())
+    }
+
     /// Toggle actions panel
     pub fn toggle_actions(&mut self) {
         self.show_actions = !self.show_actions;
@@ -230,6 +604,8 @@ impl EmailViewer {
             KeyCode::Char('q') => Some(EmailViewerAction::Close),
             KeyCode::Char('r') => Some(EmailViewerAction::Reply),
             KeyCode::Char('R') => Some(EmailViewerAction::ReplyAll),
+            KeyCode::Char('L') => Some(EmailViewerAction::ReplyToList),
+            KeyCode::Char('Q') => Some(EmailViewerAction::QuickReply),
             KeyCode::Char('f') => Some(EmailViewerAction::Forward),
             KeyCode::Char('e') => Some(EmailViewerAction::Edit),
             KeyCode::Char('d') => Some(EmailViewerAction::Delete),
@@ -237,6 +613,36 @@ impl EmailViewer {
             KeyCode::Char('m') => Some(EmailViewerAction::MarkAsRead),
             KeyCode::Char('u') => Some(EmailViewerAction::MarkAsUnread),
             KeyCode::Char('c') => Some(EmailViewerAction::AddToContacts),
+            KeyCode::Char('i') => Some(EmailViewerAction::ViewSenderContact),
+            KeyCode::Char('I') => Some(EmailViewerAction::ViewInvitation),
+            KeyCode::Char('P') => self
+                .load_remote_images()
+                .then_some(EmailViewerAction::LoadRemoteImages),
+            KeyCode::Char('D') => {
+                self.open_passphrase_prompt();
+                None
+            }
+            KeyCode::Char('p') => {
+                self.animation_controls
+                    .toggle_playback(&self.animation_manager);
+                None
+            }
+            KeyCode::Char('.') => {
+                self.animation_controls.step(&self.animation_manager);
+                None
+            }
+            KeyCode::Char('n') => {
+                self.animation_controls.next_animation();
+                None
+            }
+            KeyCode::Char('z') => {
+                self.expand_visible_quote(viewport_height);
+                None
+            }
+            KeyCode::Char('Z') => {
+                self.toggle_all_quotes();
+                None
+            }
             KeyCode::Char('v') => {
                 self.toggle_view_mode();
                 None
@@ -298,12 +704,26 @@ impl EmailViewer {
         // Clear the background
         frame.render_widget(Clear, area);
 
-        // Create main layout with header, content, and footer
+        // Make room for the sender avatar when avatars are enabled
+        let header_height = if self.avatars.is_some() && self.email_content.is_some() {
+            AVATAR_HEIGHT + 2
+        } else {
+            3
+        };
+
+        let quick_reply_height = match (&self.quick_reply, &self.passphrase_prompt) {
+            (Some(quick_reply), _) => quick_reply.height(),
+            (None, Some(prompt)) => prompt.height(),
+            (None, None) => 0,
+        };
+
+        // Create main layout with header, content, quick reply, and footer
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Header bar
+                Constraint::Length(header_height), // Header bar
                 Constraint::Min(0),    // Email content
+                Constraint::Length(quick_reply_height), // Quick reply box
                 Constraint::Length(3), // Status/instructions bar
             ])
             .split(area);
@@ -325,8 +745,14 @@ impl EmailViewer {
             self.render_email_content(frame, chunks[1], theme);
         }
 
+        if let Some(ref quick_reply) = self.quick_reply {
+            quick_reply.render(frame, chunks[2], theme);
+        } else if let Some(ref prompt) = self.passphrase_prompt {
+            prompt.render(frame, chunks[2], theme);
+        }
+
         // Render footer bar
-        self.render_footer_bar(frame, chunks[2], theme);
+        self.render_footer_bar(frame, chunks[3], theme);
     }
 
     fn render_header_bar(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
@@ -344,14 +770,50 @@ impl EmailViewer {
         };
 
         let header_text = format!("{} {}", title, view_mode_text);
+        let block = Block::default()
+            .title("Email Viewer")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.colors.palette.accent));
+
+        if let (Some(avatars), Some(email)) = (&self.avatars, &self.email_content) {
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(AVATAR_WIDTH + 1), Constraint::Min(0)])
+                .split(inner);
+
+            let (sender_name, sender_email) = Self::parse_sender_info_static(&email.headers.from);
+            let name = self
+                .sender_contact
+                .as_ref()
+                .map(|contact| contact.display_name.clone())
+                .filter(|name| !name.is_empty())
+                .unwrap_or(sender_name);
+            frame.render_widget(Paragraph::new(avatars.lines(&name, &sender_email)), columns[0]);
+
+            let lines = vec![
+                Line::from(Span::styled(
+                    header_text,
+                    Style::default()
+                        .fg(theme.colors.palette.accent)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(vec![
+                    Span::styled(name, Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(
+                        format!(" <{}>", sender_email),
+                        Style::default().fg(theme.colors.palette.text_secondary),
+                    ),
+                ]),
+            ];
+            frame.render_widget(Paragraph::new(lines), columns[1]);
+            return;
+        }
 
         let header = Paragraph::new(header_text)
-            .block(
-                Block::default()
-                    .title("Email Viewer")
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(theme.colors.palette.accent)),
-            )
+            .block(block)
             .style(
                 Style::default()
                     .fg(theme.colors.palette.accent)
@@ -370,16 +832,72 @@ impl EmailViewer {
         let _sender_contact_ref = self.sender_contact.as_ref();
         let view_mode = self.view_mode;
 
-        let lines = if let Some(email) = email_ref {
-            match view_mode {
-                ViewMode::Formatted => Self::render_formatted_email_static(email, theme),
+        // Formatted and HTML views show the sanitized body
+        let sanitized_email = self.display_content();
+        let display_ref = sanitized_email.as_ref().or(email_ref);
+
+        let empty = HashSet::new();
+        let expanded = self.expanded_quotes().unwrap_or(&empty);
+        let threshold = self.quote_fold_config.fold_threshold();
+        let mut fold_markers = Vec::new();
+        let mut lines = match (email_ref, display_ref) {
+            (Some(email), Some(display)) => match view_mode {
+                ViewMode::Formatted => {
+                    let (lines, markers) =
+                        Self::render_formatted_email_static(display, expanded, threshold, theme);
+                    fold_markers = markers;
+                    lines
+                }
                 ViewMode::Raw => Self::render_raw_email_static(email, theme),
-                ViewMode::Html => Self::render_html_email_static(email, theme),
+                ViewMode::Html => Self::render_html_email_static(display, theme),
                 ViewMode::Headers => Self::render_headers_email_static(email, theme),
-            }
-        } else {
-            vec![Line::from("No email content available")]
+            },
+            _ => vec![Line::from("No email content available")],
         };
+        let body_len = lines.len();
+
+        if matches!(view_mode, ViewMode::Formatted | ViewMode::Html) {
+            if let Some(ref sanitized) = self.sanitized {
+                lines.splice(
+                    0..0,
+                    Self::render_remote_content_banner(sanitized, self.remote_images_loaded, theme),
+                );
+            }
+        }
+
+        if matches!(view_mode, ViewMode::Formatted | ViewMode::Headers) {
+            if let Some(ref security) = self.security {
+                lines.splice(0..0, Self::render_security_panel(security, theme));
+            }
+            if let Some(ref recognition) = self.recognition {
+                lines.splice(0..0, Self::render_sender_panel(recognition, theme));
+            }
+        }
+        if view_mode != ViewMode::Raw && !self.mime_defects.is_empty() {
+            lines.splice(0..0, Self::render_malformed_banner(&self.mime_defects, theme));
+        }
+
+        // Banners went in above the body, moving its fold markers down
+        let body_offset = lines.len() - body_len;
+        self.fold_markers = fold_markers
+            .into_iter()
+            .map(|(row, fold)| (row + body_offset, fold))
+            .collect();
+
+        // Animations follow the body, where signatures put them
+        let mut animation_rows = Vec::new();
+        if matches!(view_mode, ViewMode::Formatted | ViewMode::Html) {
+            let columns = area.width.saturating_sub(2);
+            for index in 0..self.animation_controls.animation_count() {
+                lines.push(Line::from(""));
+                let start = lines.len();
+                let (animation_id, animation_lines) = self.render_animation(index, columns, theme);
+                lines.extend(animation_lines);
+                if let Some(animation_id) = animation_id {
+                    animation_rows.push((animation_id, start..lines.len()));
+                }
+            }
+        }
 
         // Calculate proper scroll bounds
         let max_scroll = if lines.len() > content_height {
@@ -394,6 +912,13 @@ impl EmailViewer {
         // Apply scrolling
         let start_line = self.scroll_position;
         let end_line = (start_line + content_height).min(lines.len());
+
+        // Animations scrolled out of view pause until they come back
+        for (animation_id, rows) in animation_rows {
+            let visible = rows.start < end_line && rows.end > start_line;
+            self.animation_controls
+                .set_visible(animation_id, visible, &self.animation_manager);
+        }
         let visible_lines = if end_line > start_line {
             lines[start_line..end_line].to_vec()
         } else if lines.is_empty() {
@@ -436,6 +961,72 @@ impl EmailViewer {
         frame.render_widget(content_paragraph, area);
     }
 
+    /// Current frame of an animation followed by its playback status
+    fn render_animation(
+        &self,
+        index: usize,
+        columns: u16,
+        theme: &Theme,
+    ) -> (Option<uuid::Uuid>, Vec<Line<'static>>) {
+        let Some(animation) = self.animation_controls.animations().get(index) else {
+            return (None, Vec::new());
+        };
+        let Some(animation_id) = animation.animation_id else {
+            return (None, Vec::new());
+        };
+
+        let mut lines = Vec::new();
+        if let Some(image) = self.animation_manager.current_frame_image(animation_id) {
+            // Half blocks draw two pixel rows per terminal row
+            let width = animation.width.unwrap_or(1).max(1);
+            let height = animation.height.unwrap_or(1).max(1);
+            let frame_columns = columns.min(ANIMATION_MAX_COLUMNS).min(width as u16).max(1);
+            let frame_rows = ((height * frame_columns as u32).div_ceil(width * 2) as u16)
+                .clamp(1, ANIMATION_MAX_ROWS);
+            lines.extend(half_block_lines(&image, frame_columns, frame_rows));
+        }
+
+        let (state, current, total) = self
+            .animation_manager
+            .playback_status(animation_id)
+            .unwrap_or((AnimationState::Stopped, 0, 0));
+        let (icon, hint) = if state == AnimationState::Playing {
+            ("▶", "p: Pause")
+        } else if self.animation_controls.is_playing(animation_id) {
+            ("⏸", "p: Pause · resumes in view")
+        } else {
+            ("⏸", "p: Play")
+        };
+        let selected = self
+            .animation_controls
+            .selected_animation()
+            .is_some_and(|selected| selected.id == animation.id);
+        let marker = if selected && self.animation_controls.animation_count() > 1 {
+            "► "
+        } else {
+            ""
+        };
+
+        let mut status = format!(
+            "{}{} {}  {}/{}  {} | .: Step",
+            marker,
+            icon,
+            animation.filename,
+            current + 1,
+            total.max(1),
+            hint
+        );
+        if self.animation_controls.animation_count() > 1 {
+            status.push_str(" | n: Next");
+        }
+        lines.push(Line::from(Span::styled(
+            truncate_to_width(&status, columns as usize),
+            Style::default().fg(theme.colors.palette.text_muted),
+        )));
+
+        (Some(animation_id), lines)
+    }
+
     fn render_actions_panel(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let mut action_lines = Vec::new();
 
@@ -462,6 +1053,8 @@ impl EmailViewer {
             let action_text = match action {
                 EmailViewerAction::Reply => "Reply",
                 EmailViewerAction::ReplyAll => "Reply All",
+                EmailViewerAction::ReplyToList => "Reply to List",
+                EmailViewerAction::QuickReply => "Quick Reply",
                 EmailViewerAction::Forward => "Forward",
                 EmailViewerAction::Edit => "Edit",
                 EmailViewerAction::Delete => "Delete",
@@ -469,6 +1062,9 @@ impl EmailViewer {
                 EmailViewerAction::MarkAsRead => "Mark Read",
                 EmailViewerAction::MarkAsUnread => "Mark Unread",
                 EmailViewerAction::AddToContacts => "Add to Contacts",
+                EmailViewerAction::ViewSenderContact => "Sender Details",
+                EmailViewerAction::ViewInvitation => "Meeting Invitation",
+                EmailViewerAction::LoadRemoteImages => "Load Images",
                 EmailViewerAction::Close => "Close",
             };
 
@@ -491,10 +1087,24 @@ impl EmailViewer {
     }
 
     fn render_footer_bar(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let instructions = if self.show_actions {
-            "↑↓: Select Action | Enter: Execute | Esc: Hide Actions | r: Reply | f: Forward | c: Add Contact | q: Quit"
+        let instructions = if self.quick_reply.is_some() {
+            "Type your reply | Enter: New Line | Ctrl+S: Send | Ctrl+E: Full Compose | Esc: Discard"
+        } else if self.passphrase_prompt.is_some() {
+            "Type the passphrase | Enter: Decrypt | Ctrl+U: Clear | Esc: Cancel"
+        } else if self.is_pgp_encrypted() {
+            "j/k/↑↓: Scroll | D: Decrypt | Space: Actions | v: View | Q: Quick Reply | i: Sender | q: Quit"
+        } else if self.show_actions {
+            "↑↓: Select Action | Enter: Execute | Esc: Hide Actions | r: Reply | Q: Quick Reply | f: Forward | c: Add Contact | q: Quit"
+        } else if self.has_invitation() {
+            "j/k/↑↓: Scroll | Space: Actions | v: View | Q: Quick Reply | I: Invitation | i: Sender | q: Quit"
+        } else if self.has_blocked_images() {
+            "j/k/↑↓: Scroll | Space: Actions | v: View | P: Load Images | Q: Quick Reply | i: Sender | q: Quit"
+        } else if self.has_folded_quotes() {
+            "j/k/↑↓: Scroll | Space: Actions | v: View | z: Unfold Quote | Z: Fold/Unfold All | Q: Quick Reply | i: Sender | q: Quit"
+        } else if self.has_animations() {
+            "j/k/↑↓: Scroll | Space: Actions | v: View | p: Play/Pause | .: Step | Q: Quick Reply | i: Sender | q: Quit"
         } else {
-            "j/k/↑↓: Scroll | PgUp/PgDn: Page | Home/End: Top/Bottom | Space: Actions | v: View | c: Add Contact | q: Quit"
+            "j/k/↑↓: Scroll | PgUp/PgDn: Page | Home/End: Top/Bottom | Space: Actions | v: View | Q: Quick Reply | c: Add Contact | i: Sender | q: Quit"
         };
 
         let footer = Paragraph::new(instructions)
@@ -506,10 +1116,25 @@ impl EmailViewer {
         frame.render_widget(footer, area);
     }
 
+    /// The message with its HTML body sanitized, as the formatted and HTML
+    /// views show it
+    fn display_content(&self) -> Option<EmailContent> {
+        self.email_content
+            .as_ref()
+            .zip(self.sanitized.as_ref())
+            .map(|(email, sanitized)| EmailContent {
+                body: sanitized.html.clone(),
+                ..email.clone()
+            })
+    }
+
+    /// Formatted view of a message, with the row of each fold marker in it
     fn render_formatted_email_static<'a>(
         email: &'a EmailContent,
+        expanded: &HashSet<QuoteFold>,
+        fold_threshold: usize,
         theme: &'a Theme,
-    ) -> Vec<Line<'a>> {
+    ) -> (Vec<Line<'a>>, Vec<(usize, QuoteFold)>) {
         let mut lines = Vec::new();
 
         // Modern sender box
@@ -540,6 +1165,34 @@ impl EmailViewer {
         )]));
         lines.push(Line::from(""));
 
+        let body = Self::body_lines(email);
+        let body_refs: Vec<&str> = body.iter().map(String::as_str).collect();
+        let mut fold_markers = Vec::new();
+        for folded in quote_fold::fold_lines(&body_refs, fold_threshold, expanded) {
+            match folded {
+                FoldedLine::Line(index) => lines.push(Line::from(body[index].clone())),
+                FoldedLine::Folded { fold, lines: count } => {
+                    fold_markers.push((lines.len(), fold));
+                    lines.push(Line::from(vec![
+                        Span::raw("> ".repeat(fold.depth.saturating_sub(1))),
+                        Span::styled(
+                            FoldedLine::marker_text(fold, count),
+                            Style::default()
+                                .fg(theme.colors.palette.text_muted)
+                                .add_modifier(Modifier::ITALIC),
+                        ),
+                    ]));
+                }
+            }
+        }
+
+        (lines, fold_markers)
+    }
+
+    /// Body text lines of the formatted view
+    fn body_lines(email: &EmailContent) -> Vec<String> {
+        let mut lines = Vec::new();
+
         // Email body - render properly based on content type with aggressive header filtering
         let cleaned_body = Self::filter_email_headers_and_metadata(&email.body);
 
@@ -553,9 +1206,9 @@ impl EmailViewer {
                 for line in plain_text.lines() {
                     let trimmed_line = line.trim();
                     if !trimmed_line.is_empty() && Self::is_content_line(trimmed_line) {
-                        lines.push(Line::from(trimmed_line.to_string()));
+                        lines.push(trimmed_line.to_string());
                     } else if trimmed_line.is_empty() {
-                        lines.push(Line::from(""));
+                        lines.push(String::new());
                     }
                 }
             }
@@ -571,9 +1224,9 @@ impl EmailViewer {
 
                     let trimmed_line = cleaned_line.trim();
                     if !trimmed_line.is_empty() && Self::is_content_line(trimmed_line) {
-                        lines.push(Line::from(trimmed_line.to_string()));
+                        lines.push(trimmed_line.to_string());
                     } else if trimmed_line.is_empty() {
-                        lines.push(Line::from(""));
+                        lines.push(String::new());
                     }
                 }
             }
@@ -819,11 +1472,219 @@ impl EmailViewer {
         lines
     }
 
+    /// Sender recognition: the matching contact, when you last wrote to
+    /// them, and warnings for new or lookalike senders
+    fn render_sender_panel(recognition: &SenderRecognition, theme: &Theme) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(theme.colors.palette.text_muted);
+        let warning = Style::default().fg(theme.colors.palette.warning);
+
+        let (icon, headline_style) = if recognition.is_known_contact() {
+            ("👤", Style::default().fg(theme.colors.palette.success).add_modifier(Modifier::BOLD))
+        } else {
+            ("❔", muted.add_modifier(Modifier::BOLD))
+        };
+        let mut spans = vec![Span::styled(
+            format!("{} {}", icon, recognition.headline()),
+            headline_style,
+        )];
+        if let Some(last_emailed) = recognition.last_emailed_text(chrono::Utc::now()) {
+            spans.push(Span::styled(format!(" · {}", last_emailed), muted));
+        }
+        if recognition.is_known_contact() {
+            spans.push(Span::styled(" (i: contact card)", muted));
+        }
+
+        let mut lines = vec![Line::from(spans)];
+        for note in recognition.warnings() {
+            lines.push(Line::from(Span::styled(format!("⚠ {}", note), warning)));
+        }
+        lines
+    }
+
+    /// Warning that parts of the message couldn't be parsed, with what went wrong
+    /// Notice of the remote images held back and tracking pixels removed
+    fn render_remote_content_banner(
+        sanitized: &SanitizedHtml,
+        loaded: bool,
+        theme: &Theme,
+    ) -> Vec<Line<'static>> {
+        let plural = |count: usize, noun: &str| {
+            format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+        };
+
+        let mut parts = Vec::new();
+        if sanitized.blocked_images > 0 {
+            parts.push(format!(
+                "{} blocked",
+                plural(sanitized.blocked_images, "remote image")
+            ));
+        } else if loaded {
+            parts.push("Remote images allowed".to_string());
+        }
+        if sanitized.tracking_pixels > 0 {
+            parts.push(format!(
+                "{} removed",
+                plural(sanitized.tracking_pixels, "tracking pixel")
+            ));
+        }
+        if parts.is_empty() {
+            return Vec::new();
+        }
+
+        let mut spans = vec![Span::styled(
+            format!("🛡 {}", parts.join(" · ")),
+            Style::default().fg(theme.colors.palette.warning),
+        )];
+        if sanitized.blocked_images > 0 {
+            spans.push(Span::styled(
+                "  P: Load images",
+                Style::default().fg(theme.colors.palette.text_muted),
+            ));
+        }
+        vec![Line::from(spans), Line::from("")]
+    }
+
+    fn render_malformed_banner(defects: &[MimeDefect], theme: &Theme) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(theme.colors.palette.text_muted);
+        let mut lines = vec![Line::from(Span::styled(
+            "⚠ This message has malformed parts; some content may be missing",
+            Style::default()
+                .fg(theme.colors.palette.warning)
+                .add_modifier(Modifier::BOLD),
+        ))];
+        for defect in defects {
+            lines.push(Line::from(Span::styled(format!("  • {}", defect), muted)));
+        }
+        lines.push(Line::from(""));
+        lines
+    }
+
+    /// Security details: authentication verdicts, signatures and encryption
+    fn render_security_panel(security: &MessageSecurity, theme: &Theme) -> Vec<Line<'static>> {
+        let muted = Style::default().fg(theme.colors.palette.text_muted);
+        let verdict_style = |verdict: Option<AuthVerdict>| match verdict {
+            Some(AuthVerdict::Pass) => Style::default().fg(theme.colors.palette.success),
+            Some(AuthVerdict::Fail | AuthVerdict::SoftFail | AuthVerdict::PermError) => {
+                Style::default().fg(theme.colors.palette.error)
+            }
+            _ => muted,
+        };
+
+        let badge = security.badge();
+        let (title, title_style) = match badge {
+            Some(SecurityBadge::Suspicious) => (
+                format!("{} {}", SecurityBadge::Suspicious.icon(), SecurityBadge::Suspicious.label()),
+                Style::default()
+                    .fg(theme.colors.palette.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Some(badge) => (
+                format!("{} {}", badge.icon(), badge.label()),
+                Style::default()
+                    .fg(theme.colors.palette.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            None => ("Security".to_string(), muted.add_modifier(Modifier::BOLD)),
+        };
+
+        let mut lines = vec![Line::from(Span::styled(title, title_style))];
+
+        if security.has_authentication_results() {
+            let mut spans = Vec::new();
+            for (name, verdict) in [
+                ("DKIM", security.dkim),
+                ("SPF", security.spf),
+                ("DMARC", security.dmarc),
+            ] {
+                if !spans.is_empty() {
+                    spans.push(Span::styled(" · ", muted));
+                }
+                spans.push(Span::styled(format!("{} ", name), muted));
+                spans.push(Span::styled(
+                    verdict.map_or("—", |v| v.as_str()),
+                    verdict_style(verdict),
+                ));
+            }
+            if let Some(ref policy) = security.dmarc_policy {
+                spans.push(Span::styled(format!(" (policy {})", policy), muted));
+            }
+            if let Some(ref authserv_id) = security.authserv_id {
+                spans.push(Span::styled(format!(" checked by {}", authserv_id), muted));
+            }
+            lines.push(Line::from(spans));
+        } else {
+            lines.push(Line::from(Span::styled(
+                "No authentication results from the receiving server",
+                muted,
+            )));
+        }
+
+        match (&security.signature, security.signed) {
+            (Some(check), _) => {
+                let signer = check.signer.as_deref().unwrap_or("an unknown key");
+                let (text, color) = match check.status {
+                    SignatureStatus::Valid => (
+                        format!("🔏 Valid {} signature from {}", check.scheme.name(), signer),
+                        theme.colors.palette.success,
+                    ),
+                    SignatureStatus::Invalid => (
+                        format!(
+                            "✗ Invalid {} signature from {}; the message was changed after signing",
+                            check.scheme.name(),
+                            signer
+                        ),
+                        theme.colors.palette.error,
+                    ),
+                    SignatureStatus::UnknownSigner => (
+                        format!(
+                            "🔏 {} signature from {}, who isn't a trusted signer",
+                            check.scheme.name(),
+                            signer
+                        ),
+                        theme.colors.palette.warning,
+                    ),
+                };
+                lines.push(Line::from(Span::styled(text, Style::default().fg(color))));
+            }
+            (None, Some(scheme)) => {
+                lines.push(Line::from(Span::styled(
+                    format!("🔏 Signed with {} (signature not verified)", scheme.name()),
+                    muted,
+                )));
+            }
+            (None, None) => {}
+        }
+        if let Some(scheme) = security.encrypted {
+            lines.push(Line::from(Span::styled(
+                format!("🔒 Encrypted with {}", scheme.name()),
+                muted,
+            )));
+        }
+        if badge == Some(SecurityBadge::Suspicious) && !security.has_invalid_signature() {
+            let domain = security.from_domain.as_deref().unwrap_or("The sender's domain");
+            let reason = if security.dmarc == Some(AuthVerdict::Fail) {
+                format!("{} failed DMARC; the sender may be forged", domain)
+            } else {
+                format!(
+                    "{} does not enforce DMARC and nothing authenticated this message; the sender may be forged",
+                    domain
+                )
+            };
+            lines.push(Line::from(Span::styled(
+                reason,
+                Style::default().fg(theme.colors.palette.warning),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines
+    }
+
     /// Render sender box with contact information (non-static version)
     #[allow(dead_code)]
     fn render_sender_box<'a>(&'a self, headers: &'a EmailHeader, theme: &'a Theme) -> Vec<Line<'a>> {
         let mut lines = Vec::new();
-        let box_width = 70;
+        let box_width: usize = 70;
 
         // Parse sender info
         let (sender_name, sender_email) = Self::parse_sender_info_static(&headers.from);
@@ -848,7 +1709,7 @@ impl EmailViewer {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                "─".repeat(box_width - title.len() - 2),
+                "─".repeat(box_width.saturating_sub(display_width(title) + 2)),
                 Style::default().fg(theme.colors.palette.border),
             ),
             Span::styled("┐", Style::default().fg(theme.colors.palette.border)),
@@ -867,16 +1728,11 @@ impl EmailViewer {
 
         if !display_name.is_empty() {
             let content_width = box_width - 4;
-            let truncated_name = if display_name.len() > content_width {
-                format!("{}...", &display_name[..content_width.saturating_sub(3)])
-            } else {
-                display_name
-            };
 
             lines.push(Line::from(vec![
                 Span::styled("│ ", Style::default().fg(theme.colors.palette.border)),
                 Span::styled(
-                    format!("{:width$}", truncated_name, width = content_width),
+                    fit_to_width(&display_name, content_width),
                     Style::default()
                         .fg(if self.sender_contact.is_some() {
                             theme.colors.palette.success
@@ -891,16 +1747,11 @@ impl EmailViewer {
 
         // Email address
         let content_width = box_width - 4;
-        let truncated_email = if sender_email.len() > content_width {
-            format!("{}...", &sender_email[..content_width.saturating_sub(3)])
-        } else {
-            sender_email
-        };
 
         lines.push(Line::from(vec![
             Span::styled("│ ", Style::default().fg(theme.colors.palette.border)),
             Span::styled(
-                format!("{:width$}", truncated_email, width = content_width),
+                fit_to_width(&sender_email, content_width),
                 Style::default().fg(theme.colors.palette.text_secondary),
             ),
             Span::styled(" │", Style::default().fg(theme.colors.palette.border)),
@@ -910,16 +1761,10 @@ impl EmailViewer {
         if let Some(ref contact) = self.sender_contact {
             if let Some(company) = &contact.company {
                 if !company.is_empty() {
-                    let truncated_company = if company.len() > content_width {
-                        format!("{}...", &company[..content_width.saturating_sub(3)])
-                    } else {
-                        company.clone()
-                    };
-
                     lines.push(Line::from(vec![
                         Span::styled("│ ", Style::default().fg(theme.colors.palette.border)),
                         Span::styled(
-                            format!("🏢 {:width$}", truncated_company, width = content_width - 2),
+                            format!("🏢 {}", fit_to_width(company, content_width.saturating_sub(3))),
                             Style::default().fg(theme.colors.palette.text_secondary),
                         ),
                         Span::styled(" │", Style::default().fg(theme.colors.palette.border)),
@@ -929,16 +1774,10 @@ impl EmailViewer {
 
             if let Some(title) = &contact.job_title {
                 if !title.is_empty() {
-                    let truncated_title = if title.len() > content_width {
-                        format!("{}...", &title[..content_width.saturating_sub(3)])
-                    } else {
-                        title.clone()
-                    };
-
                     lines.push(Line::from(vec![
                         Span::styled("│ ", Style::default().fg(theme.colors.palette.border)),
                         Span::styled(
-                            format!("💼 {:width$}", truncated_title, width = content_width - 2),
+                            format!("💼 {}", fit_to_width(title, content_width.saturating_sub(3))),
                             Style::default().fg(theme.colors.palette.text_secondary),
                         ),
                         Span::styled(" │", Style::default().fg(theme.colors.palette.border)),
@@ -1598,12 +2437,8 @@ impl EmailViewer {
         // Sender name
         if !sender_name.is_empty() {
             let content_width = box_width - 4;
-            let truncated_name = if sender_name.len() > content_width {
-                format!("{}...", &sender_name[..content_width.saturating_sub(3)])
-            } else {
-                sender_name
-            };
-            let padding = content_width.saturating_sub(truncated_name.len());
+            let truncated_name = truncate_to_width(&sender_name, content_width);
+            let padding = content_width.saturating_sub(display_width(&truncated_name));
 
             lines.push(Line::from(vec![
                 Span::styled("│ ", Style::default().fg(theme.colors.palette.border)),
@@ -1622,12 +2457,8 @@ impl EmailViewer {
         if !sender_email.is_empty() {
             let email_display = format!("<{}>", sender_email);
             let content_width = box_width - 4;
-            let truncated_email = if email_display.len() > content_width {
-                format!("{}...", &email_display[..content_width.saturating_sub(3)])
-            } else {
-                email_display
-            };
-            let padding = content_width.saturating_sub(truncated_email.len());
+            let truncated_email = truncate_to_width(&email_display, content_width);
+            let padding = content_width.saturating_sub(display_width(&truncated_email));
 
             lines.push(Line::from(vec![
                 Span::styled("│ ", Style::default().fg(theme.colors.palette.border)),
@@ -1645,7 +2476,7 @@ impl EmailViewer {
             let formatted_date = Self::format_date_static(&headers.date);
             let date_display = format!("📅 {}", formatted_date);
             let content_width = box_width - 4;
-            let padding = content_width.saturating_sub(date_display.len());
+            let padding = content_width.saturating_sub(display_width(&date_display));
 
             lines.push(Line::from(vec![
                 Span::styled("│ ", Style::default().fg(theme.colors.palette.border)),
@@ -1654,7 +2485,7 @@ impl EmailViewer {
                     formatted_date,
                     Style::default().fg(theme.colors.content_preview.body),
                 ),
-                Span::styled(" ".repeat(padding.saturating_sub(2)), Style::default()),
+                Span::styled(" ".repeat(padding), Style::default()),
                 Span::styled(" │", Style::default().fg(theme.colors.palette.border)),
             ]));
         }
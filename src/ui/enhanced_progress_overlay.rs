@@ -116,7 +116,7 @@ impl EnhancedProgressOverlay {
                 progress.total_messages,
                 Self::format_phase(&progress.phase)
             ),
-            can_cancel: matches!(progress.phase, SyncPhase::Initializing | SyncPhase::CheckingFolders | SyncPhase::FetchingHeaders | SyncPhase::FetchingBodies | SyncPhase::ProcessingChanges),
+            can_cancel: matches!(progress.phase, SyncPhase::Initializing | SyncPhase::CheckingFolders | SyncPhase::FetchingHeaders | SyncPhase::FetchingBodies | SyncPhase::ProcessingChanges | SyncPhase::Paused),
             started_at: Instant::now() - std::time::Duration::from_secs(
                 (Utc::now().timestamp() - progress.started_at.timestamp()) as u64
             ),
@@ -325,6 +325,7 @@ impl EnhancedProgressOverlay {
             SyncPhase::FetchingHeaders => "Fetching headers".to_string(),
             SyncPhase::FetchingBodies => "Fetching messages".to_string(),
             SyncPhase::ProcessingChanges => "Processing changes".to_string(),
+            SyncPhase::Paused => "Paused".to_string(),
             SyncPhase::Complete => "Complete".to_string(),
             SyncPhase::Error(err) => format!("Error: {}", err),
         }
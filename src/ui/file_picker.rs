@@ -0,0 +1,434 @@
+//! Picking files to attach from inside compose
+//!
+//! Browses the file system the way the folder tree is browsed: `j`/`k`
+//! move, `l` or Enter opens a directory and `h` goes up. Space marks files,
+//! and Enter attaches the marked files, or the one under the cursor. Typing
+//! `/` or `~` starts a path instead, so a path can be pasted and attached
+//! with Enter.
+
+use crate::theme::Theme;
+use crate::ui::compose_attachments::{self, ComposeAttachment};
+use crate::ui::preview_limits::format_size;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use std::path::{Path, PathBuf};
+
+/// Outcome of a key press in the picker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilePickerAction {
+    Continue,
+    Cancel,
+    Attach(Vec<ComposeAttachment>),
+}
+
+/// A directory entry in the listing
+#[derive(Debug, Clone)]
+struct Entry {
+    name: String,
+    path: PathBuf,
+    is_dir: bool,
+    size: u64,
+}
+
+/// File browser popup
+pub struct FilePicker {
+    dir: PathBuf,
+    entries: Vec<Entry>,
+    state: ListState,
+    /// Files marked with Space, in the order they were marked
+    marked: Vec<ComposeAttachment>,
+    show_hidden: bool,
+    /// Path being typed or pasted, after `/` or `~`
+    path_input: Option<String>,
+    /// Bytes already attached to the message, for the running total
+    attached_size: u64,
+    error: Option<String>,
+}
+
+impl FilePicker {
+    /// Open in `dir`, with `attached_size` bytes already attached
+    pub fn new(dir: PathBuf, attached_size: u64) -> Self {
+        let mut picker = Self {
+            dir,
+            entries: Vec::new(),
+            state: ListState::default(),
+            marked: Vec::new(),
+            show_hidden: false,
+            path_input: None,
+            attached_size,
+            error: None,
+        };
+        picker.load_dir();
+        picker
+    }
+
+    /// Directory being shown
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// List the current directory: parent first, then directories, then files
+    fn load_dir(&mut self) {
+        self.entries.clear();
+        self.error = None;
+
+        if let Some(parent) = self.dir.parent() {
+            self.entries.push(Entry {
+                name: "..".to_string(),
+                path: parent.to_path_buf(),
+                is_dir: true,
+                size: 0,
+            });
+        }
+
+        match std::fs::read_dir(&self.dir) {
+            Ok(read_dir) => {
+                let mut entries: Vec<Entry> = read_dir
+                    .flatten()
+                    .filter_map(|entry| {
+                        let name = entry.file_name().to_string_lossy().into_owned();
+                        if name.starts_with('.') && !self.show_hidden {
+                            return None;
+                        }
+                        // Follow symlinks so a link to a directory opens
+                        let metadata = std::fs::metadata(entry.path()).ok()?;
+                        Some(Entry {
+                            name,
+                            path: entry.path(),
+                            is_dir: metadata.is_dir(),
+                            size: metadata.len(),
+                        })
+                    })
+                    .collect();
+                entries.sort_by(|a, b| {
+                    b.is_dir
+                        .cmp(&a.is_dir)
+                        .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+                });
+                self.entries.extend(entries);
+            }
+            Err(e) => self.error = Some(format!("Can't read {}: {}", self.dir.display(), e)),
+        }
+
+        let first_child = usize::from(self.dir.parent().is_some());
+        self.state
+            .select((!self.entries.is_empty()).then(|| first_child.min(self.entries.len() - 1)));
+    }
+
+    fn open_dir(&mut self, dir: PathBuf) {
+        let previous = std::mem::replace(&mut self.dir, dir);
+        self.load_dir();
+        // Coming back up, keep the cursor on the directory just left
+        if let Some(index) = self.entries.iter().position(|entry| entry.path == previous) {
+            self.state.select(Some(index));
+        }
+    }
+
+    fn selected(&self) -> Option<&Entry> {
+        self.state.selected().and_then(|index| self.entries.get(index))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let current = self.state.selected().unwrap_or(0) as isize;
+        let last = self.entries.len() as isize - 1;
+        self.state.select(Some((current + delta).clamp(0, last) as usize));
+    }
+
+    fn is_marked(&self, path: &Path) -> bool {
+        self.marked.iter().any(|attachment| attachment.path == path)
+    }
+
+    /// Mark or unmark the file under the cursor
+    fn toggle_mark(&mut self) {
+        let Some(entry) = self.selected().filter(|entry| !entry.is_dir).cloned() else {
+            return;
+        };
+        if self.is_marked(&entry.path) {
+            self.marked.retain(|attachment| attachment.path != entry.path);
+        } else {
+            match ComposeAttachment::from_path(&entry.path) {
+                Ok(attachment) => self.marked.push(attachment),
+                Err(e) => self.error = Some(e.to_string()),
+            }
+        }
+        self.move_selection(1);
+    }
+
+    /// Bytes attached once the marked files are added
+    fn total_size(&self) -> u64 {
+        self.attached_size + self.marked.iter().map(|attachment| attachment.size).sum::<u64>()
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, key: KeyCode) -> FilePickerAction {
+        if self.path_input.is_some() {
+            return self.handle_path_key(key);
+        }
+
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => return FilePickerAction::Cancel,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Home | KeyCode::Char('g') => self.state.select(Some(0)),
+            KeyCode::End | KeyCode::Char('G') => {
+                self.move_selection(self.entries.len() as isize)
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Backspace => {
+                if let Some(parent) = self.dir.parent().map(Path::to_path_buf) {
+                    self.open_dir(parent);
+                }
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                if let Some(entry) = self.selected().filter(|entry| entry.is_dir).cloned() {
+                    self.open_dir(entry.path);
+                }
+            }
+            KeyCode::Char(' ') => self.toggle_mark(),
+            KeyCode::Char('.') => {
+                self.show_hidden = !self.show_hidden;
+                self.load_dir();
+            }
+            KeyCode::Char(c @ ('/' | '~')) => self.path_input = Some(c.to_string()),
+            KeyCode::Enter => {
+                let Some(entry) = self.selected().cloned() else {
+                    return FilePickerAction::Continue;
+                };
+                if entry.is_dir {
+                    self.open_dir(entry.path);
+                } else if !self.marked.is_empty() {
+                    return FilePickerAction::Attach(std::mem::take(&mut self.marked));
+                } else {
+                    match ComposeAttachment::from_path(&entry.path) {
+                        Ok(attachment) => return FilePickerAction::Attach(vec![attachment]),
+                        Err(e) => self.error = Some(e.to_string()),
+                    }
+                }
+            }
+            _ => {}
+        }
+        FilePickerAction::Continue
+    }
+
+    /// Keys while a path is typed or pasted
+    fn handle_path_key(&mut self, key: KeyCode) -> FilePickerAction {
+        let Some(input) = self.path_input.as_mut() else {
+            return FilePickerAction::Continue;
+        };
+        match key {
+            KeyCode::Esc => self.path_input = None,
+            KeyCode::Backspace => {
+                input.pop();
+                if input.is_empty() {
+                    self.path_input = None;
+                }
+            }
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Enter => {
+                let path = expand_path(input);
+                self.path_input = None;
+                if path.is_dir() {
+                    self.open_dir(path);
+                } else {
+                    match ComposeAttachment::from_path(&path) {
+                        Ok(attachment) => {
+                            let mut attachments = std::mem::take(&mut self.marked);
+                            attachments.retain(|marked| marked.path != attachment.path);
+                            attachments.push(attachment);
+                            return FilePickerAction::Attach(attachments);
+                        }
+                        Err(e) => {
+                            self.error = Some(format!("{}: {}", path.display(), e));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        FilePickerAction::Continue
+    }
+
+    /// Render the picker over the compose window
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(80, 80, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Attach Files ")
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Directory or path being typed
+                Constraint::Min(3),    // Listing
+                Constraint::Length(2), // Running total and warning
+                Constraint::Length(1), // Hints
+            ])
+            .split(inner);
+
+        let muted = Style::default().fg(theme.colors.palette.text_muted);
+        let header = match &self.path_input {
+            Some(input) => Line::from(vec![
+                Span::styled("Path: ", Style::default().fg(Color::Yellow)),
+                Span::raw(format!("{}|", input)),
+            ]),
+            None => Line::from(Span::styled(
+                self.dir.display().to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+        };
+        frame.render_widget(Paragraph::new(header), chunks[0]);
+
+        let width = chunks[1].width as usize;
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                if entry.is_dir {
+                    return ListItem::new(Line::from(Span::styled(
+                        format!("  📁 {}/", entry.name),
+                        Style::default().fg(theme.colors.palette.accent),
+                    )));
+                }
+                let mark = if self.is_marked(&entry.path) { "✓ " } else { "  " };
+                let details = format!(
+                    "{:>9}  {}",
+                    format_size(entry.size as usize),
+                    compose_attachments::mime_type_for(&entry.path)
+                );
+                let name = format!("{}{}", mark, entry.name);
+                let padding = width.saturating_sub(name.chars().count() + details.len() + 1);
+                ListItem::new(Line::from(vec![
+                    Span::raw(name),
+                    Span::raw(" ".repeat(padding.max(1))),
+                    Span::styled(details, muted),
+                ]))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(
+            theme
+                .get_component_style("selected", true)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(list, chunks[1], &mut self.state);
+
+        let total = self.total_size();
+        let mut summary = vec![Line::from(format!(
+            "{} marked · {} attached in total",
+            self.marked.len(),
+            format_size(total as usize)
+        ))];
+        if let Some(error) = &self.error {
+            summary.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(Color::Red),
+            )));
+        } else if let Some(warning) = compose_attachments::size_warning(total) {
+            summary.push(Line::from(Span::styled(
+                format!("⚠ {}", warning),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        frame.render_widget(Paragraph::new(summary), chunks[2]);
+
+        let hints = if self.path_input.is_some() {
+            "Enter: attach or open · Esc: back to list"
+        } else {
+            "Space: mark · Enter: attach · h/l: up/open · /,~: type a path · .: hidden · Esc: cancel"
+        };
+        frame.render_widget(Paragraph::new(Span::styled(hints, muted)), chunks[3]);
+    }
+}
+
+/// Path typed or pasted, with a leading `~` for the home directory and
+/// surrounding quotes removed
+fn expand_path(input: &str) -> PathBuf {
+    let input = input.trim();
+    let input = input
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+        .or_else(|| input.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')))
+        .unwrap_or(input);
+
+    match input.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => dirs::home_dir()
+            .map(|home| home.join(rest.trim_start_matches('/')))
+            .unwrap_or_else(|| PathBuf::from(input)),
+        _ => PathBuf::from(input),
+    }
+}
+
+/// Center a popup of relative size
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let height = r.height * percent_y / 100;
+    let width = r.width * percent_x / 100;
+    Rect {
+        x: r.x + (r.width - width) / 2,
+        y: r.y + (r.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_browse_mark_and_attach() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("b.pdf"), b"%PDF-1.4").unwrap();
+        std::fs::write(dir.path().join("a.txt"), b"hello").unwrap();
+        std::fs::write(dir.path().join(".hidden"), b"").unwrap();
+
+        let mut picker = FilePicker::new(dir.path().to_path_buf(), 0);
+        let names: Vec<&str> = picker.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["..", "sub", "a.txt", "b.pdf"]);
+        assert_eq!(picker.selected().unwrap().name, "sub");
+
+        // Mark both files, then attach them in order
+        picker.handle_key(KeyCode::Char('j'));
+        picker.handle_key(KeyCode::Char(' '));
+        picker.handle_key(KeyCode::Char(' '));
+        assert_eq!(picker.total_size(), 13);
+        let FilePickerAction::Attach(attached) = picker.handle_key(KeyCode::Enter) else {
+            panic!("expected attachments");
+        };
+        let names: Vec<&str> = attached.iter().map(|a| a.filename.as_str()).collect();
+        assert_eq!(names, vec!["a.txt", "b.pdf"]);
+        assert_eq!(attached[1].content_type, "application/pdf");
+
+        // Into a directory and back up lands on it again
+        picker.state.select(Some(1));
+        picker.handle_key(KeyCode::Enter);
+        assert_eq!(picker.dir(), dir.path().join("sub"));
+        picker.handle_key(KeyCode::Char('h'));
+        assert_eq!(picker.selected().unwrap().name, "sub");
+
+        // A pasted path is attached with Enter
+        let path = dir.path().join("a.txt");
+        for c in path.display().to_string().chars() {
+            assert_eq!(picker.handle_key(KeyCode::Char(c)), FilePickerAction::Continue);
+        }
+        let FilePickerAction::Attach(attached) = picker.handle_key(KeyCode::Enter) else {
+            panic!("expected the pasted file");
+        };
+        assert_eq!(attached[0].path, path);
+        assert_eq!(expand_path("'/tmp/a b.txt' "), PathBuf::from("/tmp/a b.txt"));
+    }
+}
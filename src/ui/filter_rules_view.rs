@@ -0,0 +1,239 @@
+//! Filter rules popup
+//!
+//! Lists the rules from `filters.toml` in the order they run. A rule can be
+//! tried on the current folder to see which messages it would catch, then
+//! switched on so it runs on incoming mail.
+
+use crate::email::{EmailFilter, FilterAction, FilterField, StoredMessage};
+use crate::theme::Theme;
+use chrono::Local;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use uuid::Uuid;
+
+/// Messages a rule matched in a folder
+pub struct RuleTest {
+    pub filter_id: Uuid,
+    pub folder: String,
+    /// Messages looked at, most recent first
+    pub checked: usize,
+    pub matches: Vec<StoredMessage>,
+}
+
+/// Popup listing filter rules, with the result of the last test
+pub struct FilterRulesView {
+    rules: Vec<EmailFilter>,
+    test: Option<RuleTest>,
+    state: ListState,
+}
+
+impl FilterRulesView {
+    pub fn new(mut rules: Vec<EmailFilter>) -> Self {
+        rules.sort_by_key(|rule| rule.priority);
+        let mut state = ListState::default();
+        if !rules.is_empty() {
+            state.select(Some(0));
+        }
+        Self {
+            rules,
+            test: None,
+            state,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.rules.is_empty() {
+            return;
+        }
+        let next = self
+            .state
+            .selected()
+            .map_or(0, |selected| (selected + 1) % self.rules.len());
+        self.state.select(Some(next));
+    }
+
+    pub fn select_previous(&mut self) {
+        if self.rules.is_empty() {
+            return;
+        }
+        let previous = match self.state.selected() {
+            Some(0) | None => self.rules.len() - 1,
+            Some(selected) => selected - 1,
+        };
+        self.state.select(Some(previous));
+    }
+
+    /// The rule under the cursor
+    pub fn selected_rule(&self) -> Option<&EmailFilter> {
+        self.rules.get(self.state.selected()?)
+    }
+
+    /// Show a rule's new enabled state
+    pub fn set_enabled(&mut self, filter_id: Uuid, enabled: bool) {
+        if let Some(rule) = self.rules.iter_mut().find(|rule| rule.id == filter_id) {
+            rule.enabled = enabled;
+        }
+    }
+
+    /// Show which messages a rule matched
+    pub fn set_test(&mut self, test: RuleTest) {
+        self.test = Some(test);
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(80, 80, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Filter Rules ")
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(45),
+                Constraint::Min(3),
+                Constraint::Length(2),
+            ])
+            .split(inner);
+
+        let items: Vec<ListItem> = self
+            .rules
+            .iter()
+            .map(|rule| {
+                let enabled = if rule.enabled { "[x]" } else { "[ ]" };
+                ListItem::new(vec![
+                    Line::from(vec![
+                        Span::raw(format!("{} {:>4}  ", enabled, rule.priority)),
+                        Span::styled(
+                            rule.name.clone(),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                    ]),
+                    Line::from(Span::styled(
+                        format!("           {}", describe(rule)),
+                        label,
+                    )),
+                ])
+            })
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(theme.colors.palette.selection)
+                .fg(theme.colors.palette.selection_text)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(list, sections[0], &mut self.state);
+
+        let results = Block::default().borders(Borders::TOP);
+        let results_area = results.inner(sections[1]);
+        frame.render_widget(results, sections[1]);
+        frame.render_widget(Paragraph::new(self.test_lines(label)), results_area);
+
+        let help = Paragraph::new(Line::from(Span::styled(
+            "j/k: move · Enter: test on current folder · Space: enable/disable · Esc: close",
+            label,
+        )))
+        .block(Block::default().borders(Borders::TOP));
+        frame.render_widget(help, sections[2]);
+    }
+
+    fn test_lines(&self, label: Style) -> Vec<Line<'static>> {
+        let Some(ref test) = self.test else {
+            return vec![Line::from(Span::styled(
+                "Press Enter to see which messages of the current folder a rule matches.",
+                label,
+            ))];
+        };
+        let name = self
+            .rules
+            .iter()
+            .find(|rule| rule.id == test.filter_id)
+            .map_or("Rule", |rule| rule.name.as_str());
+
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{} matches {} of the {} most recent messages in {}",
+                name,
+                test.matches.len(),
+                test.checked,
+                test.folder
+            ),
+            label,
+        ))];
+        lines.extend(test.matches.iter().map(|message| {
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{}  ",
+                        message.date.with_timezone(&Local).format("%Y-%m-%d")
+                    ),
+                    label,
+                ),
+                Span::raw(format!("{} — {}", message.from_addr, message.subject)),
+            ])
+        }));
+        lines
+    }
+}
+
+/// One-line summary of a rule's conditions and actions
+fn describe(rule: &EmailFilter) -> String {
+    let conditions: Vec<String> = rule
+        .conditions
+        .iter()
+        .map(|condition| {
+            let field = match condition.field {
+                FilterField::Header(ref name) => name.clone(),
+                ref field => format!("{:?}", field),
+            };
+            format!("{} {:?} \"{}\"", field, condition.operator, condition.value)
+        })
+        .collect();
+    let actions: Vec<String> = rule
+        .actions
+        .iter()
+        .map(|action| match action {
+            FilterAction::MoveToFolder(folder) => format!("move to {}", folder),
+            FilterAction::CopyToFolder(folder) => format!("copy to {}", folder),
+            FilterAction::AddLabel(label) => format!("label {}", label),
+            FilterAction::SetFlag(flag) => format!("flag {}", flag),
+            FilterAction::MarkAsRead => "mark read".to_string(),
+            FilterAction::MarkAsImportant => "flag".to_string(),
+            FilterAction::Notify => "notify".to_string(),
+            FilterAction::StopProcessing => "stop".to_string(),
+            action => format!("{:?}", action),
+        })
+        .collect();
+    format!("{} → {}", conditions.join(" and "), actions.join(", "))
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
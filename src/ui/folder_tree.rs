@@ -1,5 +1,6 @@
 use crate::email::EmailDatabase;
 use crate::theme::Theme;
+use crate::ui::text_width::{display_width, truncate_to_width};
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -9,6 +10,12 @@ use ratatui::{
 };
 use std::sync::Arc;
 
+/// Path of the virtual folder that merges the INBOX of every account
+pub const ALL_INBOXES: &str = "\u{1}All Inboxes";
+
+/// Path of the virtual folder holding the account's snoozed messages
+pub const SNOOZED: &str = "\u{1}Snoozed";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FolderType {
     Inbox,
@@ -43,6 +50,8 @@ pub struct FolderItem {
     pub is_subscribed: bool,
     pub can_create_children: bool,
     pub children: Vec<String>, // Paths of child folders
+    /// Gmail label view: lists the All Mail messages carrying a label
+    pub is_label: bool,
 }
 
 impl FolderItem {
@@ -83,6 +92,7 @@ impl FolderItem {
             is_subscribed: true,
             can_create_children: true,
             children: Vec::new(),
+            is_label: false,
         }
     }
 
@@ -106,6 +116,7 @@ impl FolderItem {
             is_subscribed: true,
             can_create_children: true,
             children: Vec::new(),
+            is_label: false,
         }
     }
 
@@ -211,17 +222,23 @@ impl FolderItem {
         }
     }
 
+    /// Whether this folder exists only locally, like All Inboxes or Snoozed
+    pub fn is_virtual(&self) -> bool {
+        self.path.starts_with('\u{1}')
+    }
+
     /// Check if this folder can be deleted
     pub fn is_deletable(&self) -> bool {
-        !matches!(
-            self.folder_type,
-            FolderType::Inbox | FolderType::Sent | FolderType::Drafts | FolderType::Trash
-        )
+        !self.is_virtual()
+            && !matches!(
+                self.folder_type,
+                FolderType::Inbox | FolderType::Sent | FolderType::Drafts | FolderType::Trash
+            )
     }
 
     /// Check if this folder can be renamed
     pub fn is_renamable(&self) -> bool {
-        matches!(self.folder_type, FolderType::Custom(_))
+        !self.is_virtual() && matches!(self.folder_type, FolderType::Custom(_))
     }
 }
 
@@ -235,9 +252,13 @@ pub enum FolderOperation {
     Unsubscribe,
     Refresh,
     MarkAllRead,
+    /// Mark every folder of the account read
+    MarkAccountRead,
     EmptyFolder,
     Properties,
     CreateSubfolder,
+    /// Cycle the folder's sync mode override
+    SyncMode,
 }
 
 pub struct FolderTree {
@@ -537,6 +558,8 @@ impl FolderTree {
 
         // Type indicator with better icons that match your screenshot
         let type_indicator = match folder.folder_type {
+            FolderType::Custom(_) if folder.is_label => "🏷️ ",
+            FolderType::Custom(_) if folder.path == SNOOZED => "💤 ",
             FolderType::Inbox => "📥 ",
             FolderType::Sent => "📤 ", 
             FolderType::Drafts => "📝 ",
@@ -561,19 +584,12 @@ impl FolderTree {
         };
 
         // Calculate available space for folder name
-        let right_content_width = size_display.len() + unread_badge.len() + 5; // padding
+        let right_content_width = size_display.len() + display_width(&unread_badge) + 5; // padding
         let name_max_width = available_width.saturating_sub(
-            indent.len() + expand_icon.len() + 3 + right_content_width
+            display_width(&indent) + display_width(expand_icon) + 3 + right_content_width
         );
         
-        let folder_name = if folder.name.len() > name_max_width {
-            // Use Unicode-safe string truncation to avoid byte boundary panics
-            let max_chars = name_max_width.saturating_sub(3);
-            let truncated: String = folder.name.chars().take(max_chars).collect();
-            format!("{}...", truncated)
-        } else {
-            folder.name.clone()
-        };
+        let folder_name = truncate_to_width(&folder.name, name_max_width);
 
         // Determine folder style
         let folder_style = if is_selected && is_focused {
@@ -626,13 +642,7 @@ impl FolderTree {
         // Add size display aligned to the right
         if !size_display.is_empty() {
             // Calculate current content length
-            let current_content_len: usize = spans.iter().map(|s| {
-                // Handle the different content types in Span
-                match s.content {
-                    std::borrow::Cow::Borrowed(s) => s.len(),
-                    std::borrow::Cow::Owned(ref s) => s.len(),
-                }
-            }).sum();
+            let current_content_len: usize = spans.iter().map(|s| display_width(&s.content)).sum();
             
             let padding_len = available_width.saturating_sub(current_content_len + size_display.len() + 2);
             if padding_len > 0 {
@@ -991,6 +1001,7 @@ impl FolderTree {
             'p' => Some(FolderOperation::Properties),
             's' => Some(FolderOperation::Subscribe),
             'u' => Some(FolderOperation::Unsubscribe),
+            'y' => Some(FolderOperation::SyncMode),
             '?' => {
                 // Show context menu
                 self.show_context_menu();
@@ -1017,6 +1028,28 @@ impl FolderTree {
             .and_then(|&folder_i| self.folders.get(folder_i))
     }
 
+    /// First folder, in tree order, matching a predicate
+    pub fn find_folder(&self, matches: impl Fn(&FolderItem) -> bool) -> Option<&FolderItem> {
+        self.folders.iter().find(|folder| matches(folder))
+    }
+
+    /// Select the folder with the given path, returning whether it is visible in the tree
+    pub fn select_folder_by_path(&mut self, path: &str) -> bool {
+        let position = self.filtered_folders.iter().position(|&folder_i| {
+            self.folders
+                .get(folder_i)
+                .map(|folder| folder.path == path)
+                .unwrap_or(false)
+        });
+
+        if let Some(display_i) = position {
+            self.state.select(Some(display_i));
+            true
+        } else {
+            false
+        }
+    }
+
     // New folder management methods
 
     pub fn set_search_query(&mut self, query: String) {
@@ -1268,7 +1301,8 @@ impl FolderTree {
 
     /// Show context menu for the currently selected folder
     pub fn show_context_menu(&mut self) {
-        if let Some(selected_folder) = self.selected_folder() {
+        // Virtual folders have nothing on the server to operate on
+        if let Some(selected_folder) = self.selected_folder().filter(|f| !f.is_virtual()) {
             self.context_menu_items = self.build_context_menu_items(selected_folder);
             self.context_menu_visible = true;
             self.context_menu_selected = 0;
@@ -1336,11 +1370,21 @@ impl FolderTree {
             "✓ Mark All Read".to_string(),
             folder.unread_count > 0,
         ));
+        items.push((
+            FolderOperation::MarkAccountRead,
+            "✓ Mark Account Read".to_string(),
+            true,
+        ));
         items.push((
             FolderOperation::Properties,
             "ℹ Properties".to_string(),
             true,
         ));
+        items.push((
+            FolderOperation::SyncMode,
+            "⇅ Change Sync Mode".to_string(),
+            true,
+        ));
 
         // Separator (we'll handle this in rendering)
 
@@ -1401,6 +1445,7 @@ impl FolderTree {
     ) -> Result<(), Box<dyn std::error::Error>> {
         if let Some(database) = &self.database {
             let stored_folders = database.get_folders(account_id).await?;
+            let label_layout = database.get_label_layout(account_id).await?;
 
             // Convert stored folders to FolderItems
             let mut folder_items = Vec::new();
@@ -1425,6 +1470,9 @@ impl FolderTree {
 
                 // Set folder as synced since it exists in database
                 folder_item.sync_status = SyncStatus::Synced;
+                folder_item.is_label = label_layout
+                    .as_ref()
+                    .is_some_and(|layout| layout.label_for(&stored_folder.full_name).is_some());
 
                 folder_items.push(folder_item);
             }
@@ -1464,6 +1512,24 @@ impl FolderTree {
                 _ => a.name.cmp(&b.name),
             });
 
+            // With more than one account, offer the merged inbox on top
+            if database.get_inbox_accounts().await?.len() > 1 {
+                let mut all_inboxes = FolderItem::new_with_type(
+                    "All Inboxes".to_string(),
+                    ALL_INBOXES.to_string(),
+                    0,
+                    FolderType::Inbox,
+                );
+                all_inboxes.sync_status = SyncStatus::Synced;
+                all_inboxes.can_create_children = false;
+                all_inboxes.unread_count = Self::all_inboxes_unread(database).await?;
+                folder_items.insert(0, all_inboxes);
+            }
+
+            if let Some(snoozed) = Self::snoozed_folder(database, account_id).await? {
+                folder_items.push(snoozed);
+            }
+
             self.folders = folder_items;
             self.rebuild_filtered_list();
 
@@ -1471,7 +1537,7 @@ impl FolderTree {
             if let Some(inbox_index) = self
                 .folders
                 .iter()
-                .position(|f| matches!(f.folder_type, FolderType::Inbox))
+                .position(|f| matches!(f.folder_type, FolderType::Inbox) && f.path != ALL_INBOXES)
             {
                 self.state.select(Some(inbox_index));
             } else if !self.folders.is_empty() {
@@ -1485,6 +1551,73 @@ impl FolderTree {
 
         Ok(())
     }
+
+    /// Recount the unread messages of the All Inboxes folder, if shown
+    pub async fn refresh_all_inboxes_unread(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(database) = &self.database else {
+            return Ok(());
+        };
+        if !self.folders.iter().any(|f| f.path == ALL_INBOXES) {
+            return Ok(());
+        }
+
+        let unread = Self::all_inboxes_unread(database).await?;
+        if let Some(folder) = self.folders.iter_mut().find(|f| f.path == ALL_INBOXES) {
+            folder.unread_count = unread;
+        }
+        Ok(())
+    }
+
+    /// Show, update or drop the Snoozed folder after messages were snoozed or woke up
+    pub async fn refresh_snoozed(&mut self, account_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(database) = &self.database else {
+            return Ok(());
+        };
+
+        let snoozed = Self::snoozed_folder(database, account_id).await?;
+        let existing = self.folders.iter().position(|f| f.path == SNOOZED);
+        match (snoozed, existing) {
+            (Some(snoozed), Some(index)) => self.folders[index] = snoozed,
+            (Some(snoozed), None) => self.folders.push(snoozed),
+            (None, Some(index)) => {
+                self.folders.remove(index);
+            }
+            (None, None) => return Ok(()),
+        }
+        self.rebuild_filtered_list();
+        Ok(())
+    }
+
+    /// The Snoozed folder of an account, if anything is snoozed
+    async fn snoozed_folder(
+        database: &EmailDatabase,
+        account_id: &str,
+    ) -> Result<Option<FolderItem>, Box<dyn std::error::Error>> {
+        let count = database.count_snoozed_messages(account_id).await?;
+        if count == 0 {
+            return Ok(None);
+        }
+
+        let mut snoozed = FolderItem::new_with_type(
+            "Snoozed".to_string(),
+            SNOOZED.to_string(),
+            0,
+            FolderType::Custom("Snoozed".to_string()),
+        );
+        snoozed.sync_status = SyncStatus::Synced;
+        snoozed.can_create_children = false;
+        snoozed.total_count = count as usize;
+        Ok(Some(snoozed))
+    }
+
+    async fn all_inboxes_unread(database: &EmailDatabase) -> Result<usize, Box<dyn std::error::Error>> {
+        Ok(database
+            .get_inbox_unread_counts()
+            .await?
+            .iter()
+            .map(|(_, count)| *count as usize)
+            .sum())
+    }
 }
 
 impl Default for FolderTree {
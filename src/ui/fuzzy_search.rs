@@ -192,7 +192,7 @@ impl FuzzySearchEngine {
     /// Get searchable text fields based on search mode
     fn get_searchable_texts(&self, message: &StoredMessage, mode: &SearchMode) -> Vec<(String, String)> {
         match mode {
-            SearchMode::FullText => vec![
+            SearchMode::FullText | SearchMode::Server => vec![
                 ("subject".to_string(), message.subject.clone()),
                 ("from_name".to_string(), message.from_name.clone().unwrap_or_default()),
                 ("from_addr".to_string(), message.from_addr.clone()),
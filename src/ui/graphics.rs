@@ -5,13 +5,26 @@
 //! - Sixel protocol
 //! - iTerm2 inline images
 //! - Fallback text representations
+//!
+//! Which protocol is used comes from [`TerminalCapabilities`], read once
+//! from the environment. Inside tmux or screen, which pass graphics
+//! through unreliably if at all, images fall back to coloured half-block
+//! characters, or to a box with the alt text when the terminal can't show
+//! true colour either. `COMUNICADO_GRAPHICS` overrides the detection.
 
+use crate::ui::text_width::{display_width, truncate_to_width};
 use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
-use image::{DynamicImage, ImageFormat, RgbaImage, GenericImageView};
+use image::imageops::FilterType;
+use image::{DynamicImage, GenericImageView, ImageFormat};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
 use std::io::{Cursor, Write};
+use std::sync::OnceLock;
 use thiserror::Error;
 
 /// Graphics rendering errors
@@ -58,66 +71,36 @@ pub enum GraphicsProtocol {
 impl GraphicsProtocol {
     /// Detect supported graphics protocols
     pub fn detect_supported() -> Vec<GraphicsProtocol> {
-        let mut supported = Vec::new();
-        
-        // Check environment variables and terminal capabilities
-        if let Ok(term) = env::var("TERM") {
-            match term.as_str() {
-                t if t.contains("kitty") => supported.push(GraphicsProtocol::Kitty),
-                t if t.contains("xterm") => {
-                    // Check for Sixel support
-                    if supports_sixel() {
-                        supported.push(GraphicsProtocol::Sixel);
-                    }
-                },
-                _ => {}
-            }
-        }
-        
-        // Check for specific terminal programs
-        if let Ok(term_program) = env::var("TERM_PROGRAM") {
-            match term_program.as_str() {
-                "iTerm.app" => supported.push(GraphicsProtocol::ITerm2),
-                "WezTerm" => supported.push(GraphicsProtocol::WezTerm),
-                _ => {}
-            }
-        }
-        
-        // Check for Kitty terminal
-        if env::var("KITTY_WINDOW_ID").is_ok() {
-            supported.push(GraphicsProtocol::Kitty);
-        }
-        
-        // Always support Unicode fallback
-        supported.push(GraphicsProtocol::Unicode);
-        
-        if supported.is_empty() {
-            supported.push(GraphicsProtocol::None);
-        }
-        
-        supported
+        TerminalCapabilities::detect().protocols.clone()
     }
-    
+
     /// Get the best available protocol
     pub fn best_available() -> GraphicsProtocol {
-        let supported = Self::detect_supported();
-        
-        // Priority order: Kitty > Sixel > iTerm2 > WezTerm > Unicode > None
-        for protocol in &[
-            GraphicsProtocol::Kitty,
-            GraphicsProtocol::Sixel,
-            GraphicsProtocol::ITerm2,
-            GraphicsProtocol::WezTerm,
-            GraphicsProtocol::Unicode,
-        ] {
-            if supported.contains(protocol) {
-                return *protocol;
+        TerminalCapabilities::detect().best_protocol()
+    }
+
+    /// Whether the protocol draws pixels with escape sequences, rather than
+    /// with ordinary characters
+    pub fn is_pixel_protocol(&self) -> bool {
+        !matches!(self, GraphicsProtocol::Unicode | GraphicsProtocol::None)
+    }
+
+    /// Parse a `COMUNICADO_GRAPHICS` value; `None` means detect
+    fn from_override(value: &str) -> Option<Vec<GraphicsProtocol>> {
+        let forced = match value.trim().to_lowercase().as_str() {
+            "kitty" => GraphicsProtocol::Kitty,
+            "sixel" => GraphicsProtocol::Sixel,
+            "iterm2" | "iterm" => GraphicsProtocol::ITerm2,
+            "wezterm" => GraphicsProtocol::WezTerm,
+            "unicode" | "blocks" | "halfblocks" => return Some(vec![GraphicsProtocol::Unicode]),
+            "none" | "off" | "false" | "0" | "disable" | "disabled" => {
+                return Some(vec![GraphicsProtocol::None])
             }
-        }
-        
-        GraphicsProtocol::None
+            _ => return None,
+        };
+        Some(vec![forced, GraphicsProtocol::Unicode])
     }
-    
+
     /// Check if protocol supports animation
     pub fn supports_animation(&self) -> bool {
         matches!(self, 
@@ -140,6 +123,130 @@ impl GraphicsProtocol {
     }
 }
 
+/// Terminal multiplexers, which sit between us and the real terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
+/// What the terminal can display, worked out from `$TERM` and the
+/// variables terminals set for their programs
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalCapabilities {
+    /// Usable protocols, best first; always ends with `Unicode` or `None`
+    pub protocols: Vec<GraphicsProtocol>,
+    /// Multiplexer we run inside, if any
+    pub multiplexer: Option<Multiplexer>,
+    /// Whether 24-bit colour works, which half-block images need
+    pub truecolor: bool,
+}
+
+impl TerminalCapabilities {
+    /// Capabilities of the terminal we're running in, detected once
+    pub fn detect() -> &'static TerminalCapabilities {
+        static CAPABILITIES: OnceLock<TerminalCapabilities> = OnceLock::new();
+        CAPABILITIES.get_or_init(|| {
+            let capabilities = Self::from_env(|name| env::var(name).ok());
+            tracing::debug!("Terminal graphics: {:?}", capabilities);
+            capabilities
+        })
+    }
+
+    /// Work out capabilities from environment variables looked up by `var`
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        let term = var("TERM").unwrap_or_default().to_lowercase();
+        let program = var("TERM_PROGRAM").unwrap_or_default().to_lowercase();
+        let colorterm = var("COLORTERM").unwrap_or_default().to_lowercase();
+
+        let multiplexer = if var("TMUX").is_some() || term.starts_with("tmux") || program == "tmux"
+        {
+            Some(Multiplexer::Tmux)
+        } else if var("STY").is_some() || term.starts_with("screen") {
+            Some(Multiplexer::Screen)
+        } else {
+            None
+        };
+
+        let kitty = term.contains("kitty")
+            || term.contains("ghostty")
+            || var("KITTY_WINDOW_ID").is_some()
+            || matches!(program.as_str(), "kitty" | "ghostty");
+        let iterm2 = program == "iterm.app" || var("LC_TERMINAL").as_deref() == Some("iTerm2");
+        let wezterm = program == "wezterm";
+        let konsole = var("KONSOLE_VERSION").is_some();
+        let sixel = colorterm.contains("sixel")
+            || ["foot", "mlterm", "contour", "yaft"]
+                .iter()
+                .any(|name| term.contains(name))
+            || program == "mintty"
+            || konsole;
+
+        let truecolor = matches!(colorterm.as_str(), "truecolor" | "24bit")
+            || term.contains("direct")
+            || kitty
+            || iterm2
+            || wezterm
+            || konsole
+            || term.contains("foot");
+
+        let protocols = match var("COMUNICADO_GRAPHICS")
+            .as_deref()
+            .and_then(GraphicsProtocol::from_override)
+        {
+            Some(forced) => forced,
+            None => {
+                let mut protocols = Vec::new();
+                // Multiplexers drop or mangle graphics escapes unless
+                // passthrough is set up just right, which we can't check
+                if multiplexer.is_none() {
+                    let detected = [
+                        (kitty, GraphicsProtocol::Kitty),
+                        (sixel, GraphicsProtocol::Sixel),
+                        (iterm2, GraphicsProtocol::ITerm2),
+                        (wezterm, GraphicsProtocol::WezTerm),
+                    ];
+                    protocols.extend(
+                        detected
+                            .iter()
+                            .filter(|(supported, _)| *supported)
+                            .map(|(_, protocol)| *protocol),
+                    );
+                }
+                protocols.push(if truecolor {
+                    GraphicsProtocol::Unicode
+                } else {
+                    GraphicsProtocol::None
+                });
+                protocols
+            }
+        };
+
+        Self {
+            protocols,
+            multiplexer,
+            truecolor,
+        }
+    }
+
+    /// Best protocol available
+    pub fn best_protocol(&self) -> GraphicsProtocol {
+        self.protocols
+            .first()
+            .copied()
+            .unwrap_or(GraphicsProtocol::None)
+    }
+
+    /// Whether images can be drawn with a pixel protocol
+    pub fn supports_pixel_graphics(&self) -> bool {
+        self.best_protocol().is_pixel_protocol()
+    }
+
+    pub fn supports(&self, protocol: GraphicsProtocol) -> bool {
+        self.protocols.contains(&protocol)
+    }
+}
+
 /// Image rendering configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RenderConfig {
@@ -423,86 +530,142 @@ impl ImageRenderer {
         Ok(output.into_bytes())
     }
     
-    /// Render using Unicode block elements (fallback)
+    /// Render using coloured half blocks (fallback)
     async fn render_unicode(&self, image: &DynamicImage) -> GraphicsResult<Vec<u8>> {
-        let rgba_image = image.to_rgba8();
-        let (width, height) = rgba_image.dimensions();
-        
         let mut output = Vec::new();
-        
-        // Use Unicode block elements to approximate the image
-        // Process in 2x4 pixel blocks using Unicode characters like ▀▄█
-        for y in (0..height).step_by(4) {
-            for x in (0..width).step_by(2) {
-                let block_char = self.get_unicode_block(&rgba_image, x, y);
-                output.extend_from_slice(block_char.as_bytes());
+        for row in half_block_cells(image) {
+            for ((tr, tg, tb), (br, bg, bb)) in row {
+                write!(
+                    output,
+                    "\x1b[38;2;{};{};{};48;2;{};{};{}m▀",
+                    tr, tg, tb, br, bg, bb
+                )?;
             }
-            output.push(b'\n');
+            output.extend_from_slice(b"\x1b[0m\n");
         }
-        
         Ok(output)
     }
-    
-    /// Get appropriate Unicode block character for a 2x4 pixel area
-    fn get_unicode_block(&self, image: &RgbaImage, x: u32, y: u32) -> &'static str {
-        // Sample the 2x4 area and determine which Unicode block character best represents it
-        let mut brightness_sum = 0.0;
-        let mut pixel_count = 0;
-        
-        for dy in 0..4 {
-            for dx in 0..2 {
-                if x + dx < image.width() && y + dy < image.height() {
-                    let pixel = image.get_pixel(x + dx, y + dy);
-                    // Calculate brightness using standard RGB to grayscale formula
-                    let brightness = (0.299 * pixel[0] as f32 + 
-                                    0.587 * pixel[1] as f32 + 
-                                    0.114 * pixel[2] as f32) / 255.0;
-                    brightness_sum += brightness;
-                    pixel_count += 1;
-                }
+
+    /// Lines a widget can draw for `image` in at most `columns` by `rows`
+    /// cells: coloured half blocks where the terminal shows true colour,
+    /// otherwise a box with the alt text
+    ///
+    /// Pixel protocols can't go through the widget buffer, so this is also
+    /// what to draw in their place when an image has to sit among text.
+    pub fn render_lines(
+        &self,
+        image: Option<&DynamicImage>,
+        alt: Option<&str>,
+        columns: u16,
+        rows: u16,
+    ) -> Vec<Line<'static>> {
+        match image {
+            Some(image) if self.config.protocol != GraphicsProtocol::None => {
+                half_block_lines(image, columns, rows)
             }
-        }
-        
-        if pixel_count == 0 {
-            return " ";
-        }
-        
-        let avg_brightness = brightness_sum / pixel_count as f32;
-        
-        // Map brightness to Unicode block characters
-        match avg_brightness {
-            b if b > 0.875 => "█", // Full block
-            b if b > 0.750 => "▉", // 7/8 block
-            b if b > 0.625 => "▊", // 3/4 block
-            b if b > 0.500 => "▋", // 5/8 block
-            b if b > 0.375 => "▌", // 1/2 block
-            b if b > 0.250 => "▍", // 3/8 block
-            b if b > 0.125 => "▎", // 1/4 block
-            b if b > 0.000 => "▏", // 1/8 block
-            _ => " ",              // Empty
+            _ => placeholder_lines(alt, columns, rows),
         }
     }
 }
 
-/// Check if terminal supports Sixel graphics
-fn supports_sixel() -> bool {
-    // Check for Sixel support through terminfo or environment variables
-    if let Ok(colorterm) = env::var("COLORTERM") {
-        if colorterm.contains("sixel") {
-            return true;
+/// Colours of the upper and lower pixel drawn in one cell
+type HalfBlockCell = ((u8, u8, u8), (u8, u8, u8));
+
+/// Cells of each row, two pixel rows per cell
+fn half_block_cells(image: &DynamicImage) -> Vec<Vec<HalfBlockCell>> {
+    let rgba = image.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    // Transparent pixels blend towards black, like a dark terminal
+    let pixel = |x: u32, y: u32| {
+        if y >= height {
+            return (0, 0, 0);
         }
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |c: u8| (c as u16 * a as u16 / 255) as u8;
+        (blend(r), blend(g), blend(b))
+    };
+
+    (0..height)
+        .step_by(2)
+        .map(|y| (0..width).map(|x| (pixel(x, y), pixel(x, y + 1))).collect())
+        .collect()
+}
+
+/// Approximate `image` with `▀` characters, the foreground colouring the
+/// upper pixel and the background the lower one, scaled to fit `columns`
+/// by `rows` cells
+pub fn half_block_lines(image: &DynamicImage, columns: u16, rows: u16) -> Vec<Line<'static>> {
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 || columns == 0 || rows == 0 {
+        return Vec::new();
     }
-    
-    // Check TERM variable
-    if let Ok(term) = env::var("TERM") {
-        // Some terminals that support Sixel
-        if term.contains("xterm") || term.contains("mlterm") || term.contains("mintty") {
-            // This is a simplification - in reality you'd query terminal capabilities
-            return true;
-        }
+    let scale = (columns as f32 / width as f32)
+        .min(rows as f32 * 2.0 / height as f32)
+        .min(1.0);
+    let scaled = image.resize_exact(
+        ((width as f32 * scale) as u32).max(1),
+        ((height as f32 * scale) as u32).max(1),
+        FilterType::Triangle,
+    );
+
+    half_block_cells(&scaled)
+        .into_iter()
+        .map(|row| {
+            Line::from(
+                row.into_iter()
+                    .map(|((tr, tg, tb), (br, bg, bb))| {
+                        Span::styled(
+                            "▀",
+                            Style::default()
+                                .fg(Color::Rgb(tr, tg, tb))
+                                .bg(Color::Rgb(br, bg, bb)),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
+/// A box of `columns` by `rows` cells with the alt text in the middle,
+/// shortened to fit
+pub fn placeholder_lines(alt: Option<&str>, columns: u16, rows: u16) -> Vec<Line<'static>> {
+    let inner = columns.max(3) as usize - 2;
+    let rows = rows.max(3) as usize;
+    let label = alt
+        .map(str::trim)
+        .filter(|alt| !alt.is_empty())
+        .unwrap_or("Image");
+    let label = truncate_to_width(label, inner);
+    let padding = inner - display_width(&label);
+    let style = Style::default().fg(Color::DarkGray);
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!("┌{}┐", "─".repeat(inner)),
+        style,
+    ))];
+    for row in 1..rows - 1 {
+        let content = if row == (rows - 1) / 2 {
+            format!(
+                "{}{}{}",
+                " ".repeat(padding / 2),
+                label,
+                " ".repeat(padding - padding / 2)
+            )
+        } else {
+            " ".repeat(inner)
+        };
+        lines.push(Line::from(vec![
+            Span::styled("│", style),
+            Span::raw(content),
+            Span::styled("│", style),
+        ]));
     }
-    
-    false
+    lines.push(Line::from(Span::styled(
+        format!("└{}┘", "─".repeat(inner)),
+        style,
+    )));
+    lines
 }
 
 #[cfg(test)]
@@ -574,9 +737,10 @@ mod tests {
         let output = result.unwrap();
         assert!(!output.is_empty());
         
-        // Should contain Unicode block characters
+        // Two rows of coloured half blocks, white over black first
         let output_str = String::from_utf8_lossy(&output);
-        assert!(output_str.chars().any(|c| "█▉▊▋▌▍▎▏ ".contains(c)));
+        assert_eq!(output_str.lines().count(), 2);
+        assert!(output_str.starts_with("\x1b[38;2;255;255;255;48;2;0;0;0m▀"));
     }
 
     #[test]
@@ -593,4 +757,78 @@ mod tests {
         assert!(!GraphicsProtocol::ITerm2.supports_animation());
         assert!(!GraphicsProtocol::Unicode.supports_animation());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_capabilities_from_env() {
+        let detect = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            TerminalCapabilities::from_env(|name| vars.get(name).cloned())
+        };
+
+        let kitty = detect(&[("TERM", "xterm-kitty"), ("KITTY_WINDOW_ID", "1")]);
+        assert_eq!(kitty.best_protocol(), GraphicsProtocol::Kitty);
+        assert!(kitty.supports(GraphicsProtocol::Unicode));
+
+        // The same terminal under tmux falls back to half blocks
+        let tmux = detect(&[
+            ("TERM", "tmux-256color"),
+            ("TMUX", "/tmp/tmux-1000/default,1,0"),
+            ("KITTY_WINDOW_ID", "1"),
+            ("COLORTERM", "truecolor"),
+        ]);
+        assert_eq!(tmux.multiplexer, Some(Multiplexer::Tmux));
+        assert_eq!(tmux.protocols, vec![GraphicsProtocol::Unicode]);
+
+        // Without true colour there is nothing better than a placeholder
+        let plain = detect(&[("TERM", "xterm-256color")]);
+        assert_eq!(plain.protocols, vec![GraphicsProtocol::None]);
+
+        let foot = detect(&[("TERM", "foot")]);
+        assert_eq!(foot.best_protocol(), GraphicsProtocol::Sixel);
+
+        let iterm = detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "iTerm.app")]);
+        assert_eq!(iterm.best_protocol(), GraphicsProtocol::ITerm2);
+
+        // The override wins, even inside a multiplexer
+        let forced = detect(&[("TMUX", "x"), ("COMUNICADO_GRAPHICS", "sixel")]);
+        assert_eq!(forced.best_protocol(), GraphicsProtocol::Sixel);
+        let disabled = detect(&[("TERM", "xterm-kitty"), ("COMUNICADO_GRAPHICS", "false")]);
+        assert_eq!(disabled.protocols, vec![GraphicsProtocol::None]);
+    }
+
+    #[test]
+    fn test_fallback_lines() {
+        let image = DynamicImage::ImageRgba8(RgbaImage::from_pixel(40, 40, Rgba([255, 0, 0, 255])));
+        let lines = half_block_lines(&image, 10, 4);
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| line.spans.len() == 8));
+        assert_eq!(lines[0].spans[0].style.fg, Some(Color::Rgb(255, 0, 0)));
+
+        let text = |lines: Vec<Line>| -> Vec<String> {
+            lines
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect()
+                })
+                .collect()
+        };
+        let placeholder = text(placeholder_lines(Some("Quarterly results chart"), 12, 3));
+        assert_eq!(
+            placeholder,
+            vec!["┌──────────┐", "│Quarter...│", "└──────────┘"]
+        );
+
+        let renderer = ImageRenderer::new(RenderConfig {
+            protocol: GraphicsProtocol::None,
+            ..Default::default()
+        });
+        let lines = renderer.render_lines(Some(&image), Some("Logo"), 8, 5);
+        assert_eq!(text(lines)[2], "│ Logo │");
+    }
+}
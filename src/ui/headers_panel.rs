@@ -0,0 +1,325 @@
+//! Headers panel
+//!
+//! Popup listing a message's authentication results, its Received chain
+//! with the time spent on each hop, and every header with its value. Any
+//! row can be copied to the clipboard.
+
+use crate::clipboard::ClipboardManager;
+use crate::email::header_report::{describe_auth_result, HeaderReport};
+use crate::email::AuthVerdict;
+use crate::theme::Theme;
+use chrono::Duration;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// What a row shows
+#[derive(Debug, Clone)]
+enum RowKind {
+    Section,
+    Auth(AuthVerdict),
+    Hop { delay: Option<Duration> },
+    Field { name: String, name_width: usize },
+    Note,
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    kind: RowKind,
+    text: String,
+    /// What Enter copies; section titles and notes copy nothing
+    copy: Option<String>,
+}
+
+/// Popup with the structured headers of one message
+pub struct HeadersPanel {
+    subject: String,
+    rows: Vec<Row>,
+    state: ListState,
+    clipboard: ClipboardManager,
+}
+
+impl HeadersPanel {
+    /// `report` is `None` when the message's headers weren't kept during sync
+    pub fn new(subject: String, report: Option<HeaderReport>) -> Self {
+        let rows = match report {
+            Some(report) => Self::build_rows(&report),
+            None => vec![Row {
+                kind: RowKind::Note,
+                text: "Headers are kept for messages synced from now on; this one was synced earlier."
+                    .to_string(),
+                copy: None,
+            }],
+        };
+        let mut panel = Self {
+            subject,
+            rows,
+            state: ListState::default(),
+            clipboard: ClipboardManager::new(),
+        };
+        panel.state.select(panel.rows.iter().position(|row| row.copy.is_some()));
+        panel
+    }
+
+    fn build_rows(report: &HeaderReport) -> Vec<Row> {
+        let section = |title: String| Row {
+            kind: RowKind::Section,
+            text: title,
+            copy: None,
+        };
+        let note = |text: &str| Row {
+            kind: RowKind::Note,
+            text: text.to_string(),
+            copy: None,
+        };
+        let mut rows = Vec::new();
+
+        rows.push(section("Authentication".to_string()));
+        if report.authentication.is_empty() {
+            rows.push(note("No Authentication-Results header"));
+        }
+        for (index, results) in report.authentication.iter().enumerate() {
+            let server = results.authserv_id.as_deref().unwrap_or("unknown server");
+            // Only the topmost header was added by our own server
+            if index > 0 {
+                rows.push(note(&format!("Added before delivery by {} (not trusted)", server)));
+            } else if report.authentication.len() > 1 {
+                rows.push(note(&format!("Checked by {}", server)));
+            }
+            for result in &results.methods {
+                let text = describe_auth_result(result);
+                rows.push(Row {
+                    kind: RowKind::Auth(result.verdict),
+                    copy: Some(text.clone()),
+                    text,
+                });
+            }
+        }
+
+        let transit = report
+            .transit_time()
+            .map(|time| format!(" — {} in transit", format_delay(time)))
+            .unwrap_or_default();
+        rows.push(section(format!("Received chain ({} hops{})", report.received.len(), transit)));
+        if report.received.is_empty() {
+            rows.push(note("No Received headers"));
+        }
+        for hop in &report.received {
+            let text = format!(
+                "{} → {}{}",
+                hop.from.as_deref().unwrap_or("?"),
+                hop.by.as_deref().unwrap_or("?"),
+                hop.with
+                    .as_deref()
+                    .map(|with| format!(" ({})", with))
+                    .unwrap_or_default()
+            );
+            rows.push(Row {
+                kind: RowKind::Hop { delay: hop.delay },
+                text,
+                copy: Some(format!("Received: {}", hop.raw)),
+            });
+        }
+
+        rows.push(section(format!("All headers ({})", report.fields.len())));
+        let name_width = report
+            .fields
+            .iter()
+            .map(|field| field.name.len())
+            .max()
+            .unwrap_or(0)
+            .min(28);
+        for field in &report.fields {
+            rows.push(Row {
+                kind: RowKind::Field {
+                    name: field.name.clone(),
+                    name_width,
+                },
+                text: field.value.clone(),
+                copy: Some(format!("{}: {}", field.name, field.value)),
+            });
+        }
+
+        rows
+    }
+
+    fn step(&mut self, forward: bool, count: usize) {
+        let Some(mut index) = self.state.selected() else {
+            return;
+        };
+        for _ in 0..count {
+            let mut next = index;
+            loop {
+                next = if forward {
+                    next + 1
+                } else {
+                    match next.checked_sub(1) {
+                        Some(previous) => previous,
+                        None => break,
+                    }
+                };
+                match self.rows.get(next) {
+                    Some(row) if row.copy.is_some() => {
+                        index = next;
+                        break;
+                    }
+                    Some(_) => continue,
+                    None => break,
+                }
+            }
+        }
+        self.state.select(Some(index));
+    }
+
+    pub fn select_next(&mut self) {
+        self.step(true, 1);
+    }
+
+    pub fn select_previous(&mut self) {
+        self.step(false, 1);
+    }
+
+    pub fn page_down(&mut self) {
+        self.step(true, 10);
+    }
+
+    pub fn page_up(&mut self) {
+        self.step(false, 10);
+    }
+
+    /// Copy the selected row, returning what was copied
+    pub fn copy_selected(&mut self) -> Result<String, Box<dyn std::error::Error>> {
+        let text = self
+            .state
+            .selected()
+            .and_then(|index| self.rows.get(index))
+            .and_then(|row| row.copy.clone())
+            .ok_or("Nothing selected")?;
+        self.clipboard.copy(&text)?;
+        Ok(text)
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(85, 85, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" Headers: {} ", self.subject))
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(2)])
+            .split(inner);
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| ListItem::new(render_row(row, theme, label)))
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(theme.colors.palette.selection)
+                .fg(theme.colors.palette.selection_text)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(list, sections[0], &mut self.state);
+
+        let help = Paragraph::new(Line::from(Span::styled(
+            "j/k: move · PgUp/PgDn: page · Enter/y: copy row · Esc: close",
+            label,
+        )))
+        .block(Block::default().borders(Borders::TOP));
+        frame.render_widget(help, sections[1]);
+    }
+}
+
+fn render_row(row: &Row, theme: &Theme, label: Style) -> Line<'static> {
+    let palette = &theme.colors.palette;
+    match &row.kind {
+        RowKind::Section => Line::from(Span::styled(
+            row.text.clone(),
+            Style::default()
+                .fg(palette.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        RowKind::Note => Line::from(Span::styled(format!("  {}", row.text), label)),
+        RowKind::Auth(verdict) => {
+            let (mark, color) = match *verdict {
+                AuthVerdict::Pass => ("✓", palette.success),
+                AuthVerdict::Fail | AuthVerdict::PermError => ("✗", palette.error),
+                AuthVerdict::SoftFail | AuthVerdict::TempError => ("!", palette.warning),
+                _ => ("·", palette.text_secondary),
+            };
+            Line::from(vec![
+                Span::styled(format!("  {} ", mark), Style::default().fg(color)),
+                Span::raw(row.text.clone()),
+            ])
+        }
+        RowKind::Hop { delay } => {
+            let delay_style = match *delay {
+                Some(delay) if delay > Duration::minutes(5) => Style::default().fg(palette.warning),
+                _ => label,
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("  {:>8} ", delay.map(format_delay).unwrap_or_default()),
+                    delay_style,
+                ),
+                Span::raw(row.text.clone()),
+            ])
+        }
+        RowKind::Field { name, name_width } => Line::from(vec![
+            Span::styled(
+                format!("  {:<width$} ", format!("{}:", name), width = name_width + 1),
+                Style::default()
+                    .fg(palette.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(row.text.clone()),
+        ]),
+    }
+}
+
+/// Short form of a hop delay; negative when the servers' clocks disagree
+fn format_delay(delay: Duration) -> String {
+    let seconds = delay.num_seconds();
+    let sign = if seconds < 0 { "-" } else { "+" };
+    let seconds = seconds.abs();
+    if seconds < 60 {
+        format!("{}{}s", sign, seconds)
+    } else if seconds < 3600 {
+        format!("{}{}m{:02}s", sign, seconds / 60, seconds % 60)
+    } else {
+        format!("{}{}h{:02}m", sign, seconds / 3600, (seconds % 3600) / 60)
+    }
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -23,6 +23,8 @@ pub enum InvitationAction {
     Close,
     /// Add to calendar without responding
     AddToCalendar,
+    /// Remove a cancelled meeting from the calendar
+    RemoveFromCalendar,
     /// View details
     ViewDetails,
 }
@@ -161,9 +163,11 @@ impl InvitationViewer {
                     }
                 }
                 InvitationMethod::Cancel => {
-                    // Meeting cancelled, show informational actions
-                    self.actions
-                        .push((InvitationAction::ViewDetails, "View Details".to_string()));
+                    // Meeting cancelled, offer to drop it from the calendar
+                    self.actions.push((
+                        InvitationAction::RemoveFromCalendar,
+                        "Remove from Calendar".to_string(),
+                    ));
                 }
                 InvitationMethod::Reply => {
                     // Response from someone else, view only
@@ -203,18 +207,20 @@ impl InvitationViewer {
             }
             '\n' | ' ' => {
                 // Execute selected action
-                if let Some((action, _)) = self.actions.get(self.selected_action) {
-                    Some(action.clone())
-                } else {
-                    None
+                match self.actions.get(self.selected_action) {
+                    Some((InvitationAction::ViewDetails, _)) => {
+                        self.toggle_details();
+                        None
+                    }
+                    Some((action, _)) => Some(action.clone()),
+                    None => None,
                 }
             }
             'a' | 'A' => Some(InvitationAction::Accept),
             'd' | 'D' => Some(InvitationAction::Decline),
             't' | 'T' => Some(InvitationAction::Tentative),
             'v' | 'V' => {
-                self.show_details = !self.show_details;
-                self.update_actions();
+                self.toggle_details();
                 None
             }
             'q' | 'Q' | '\x1b' => Some(InvitationAction::Close), // ESC
@@ -222,6 +228,14 @@ impl InvitationViewer {
         }
     }
 
+    fn toggle_details(&mut self) {
+        self.show_details = !self.show_details;
+        self.update_actions();
+        self.selected_action = self
+            .selected_action
+            .min(self.actions.len().saturating_sub(1));
+    }
+
     /// Set error message
     pub fn set_error(&mut self, error: String) {
         self.error_message = Some(error);
@@ -241,6 +255,11 @@ impl InvitationViewer {
         self.invitation.is_some()
     }
 
+    /// The invitation being displayed
+    pub fn invitation(&self) -> Option<&MeetingInvitation> {
+        self.invitation.as_ref()
+    }
+
     /// Render the invitation viewer
     pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         if let Some(ref invitation) = self.invitation {
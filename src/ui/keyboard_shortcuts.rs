@@ -178,12 +178,15 @@ impl KeyboardShortcutsUI {
                 | KeyboardAction::AIEmailSuggestions
                 | KeyboardAction::AIComposeSuggestions
                 | KeyboardAction::AISummarizeEmail
+                | KeyboardAction::AISummarizeThread
                 | KeyboardAction::AICalendarAssist
                 | KeyboardAction::AIConfigureSettings
                 | KeyboardAction::AIQuickReply
+                | KeyboardAction::AIDraftReply
                 | KeyboardAction::AIEmailAnalysis
                 | KeyboardAction::AIScheduleRequest
-                | KeyboardAction::AIContentGeneration => 1,
+                | KeyboardAction::AIContentGeneration
+                | KeyboardAction::AIDiagnostics => 1,
                 
                 // Navigation (2)
                 KeyboardAction::NextPane
@@ -193,7 +196,9 @@ impl KeyboardShortcutsUI {
                 | KeyboardAction::MoveLeft
                 | KeyboardAction::MoveRight
                 | KeyboardAction::NextMessage
-                | KeyboardAction::PreviousMessage => 2,
+                | KeyboardAction::PreviousMessage
+                | KeyboardAction::GoToInbox
+                | KeyboardAction::GoToStarred => 2,
                 
                 // Selection & Interaction (3)
                 KeyboardAction::Select
@@ -204,11 +209,28 @@ impl KeyboardShortcutsUI {
                 KeyboardAction::ComposeEmail
                 | KeyboardAction::ReplyEmail
                 | KeyboardAction::ReplyAllEmail
+                | KeyboardAction::ReplyToList
                 | KeyboardAction::ForwardEmail
                 | KeyboardAction::DeleteEmail
                 | KeyboardAction::MarkAsRead
                 | KeyboardAction::MarkAsUnread
+                | KeyboardAction::MarkThreadRead
+                | KeyboardAction::MarkThreadUnread
+                | KeyboardAction::MarkAllRead
+                | KeyboardAction::UndoMarkAllRead
                 | KeyboardAction::ArchiveEmail
+                | KeyboardAction::BlockSender
+                | KeyboardAction::SnoozeMessage
+                | KeyboardAction::NextInboxTab
+                | KeyboardAction::PreviousInboxTab
+                | KeyboardAction::MoveToPrimaryTab
+                | KeyboardAction::MoveToPromotionsTab
+                | KeyboardAction::MoveToSocialTab
+                | KeyboardAction::MoveToUpdatesTab
+                | KeyboardAction::MoveToForumsTab
+                | KeyboardAction::ToggleMessageMark
+                | KeyboardAction::ReportSpam
+                | KeyboardAction::ReportNotSpam
                 | KeyboardAction::ShowDraftList => 4,
                 
                 // Calendar & Events (5)
@@ -218,6 +240,8 @@ impl KeyboardShortcutsUI {
                 | KeyboardAction::EditEvent
                 | KeyboardAction::DeleteEvent
                 | KeyboardAction::ViewEventDetails
+                | KeyboardAction::NudgeAttendees
+                | KeyboardAction::SnoozeReminder
                 | KeyboardAction::CreateTodo
                 | KeyboardAction::ToggleTodoComplete
                 | KeyboardAction::ViewTodos
@@ -231,11 +255,22 @@ impl KeyboardShortcutsUI {
                 
                 // View & Attachments (6)
                 KeyboardAction::OpenEmailViewer
+                | KeyboardAction::ToggleTodayPanel
+                | KeyboardAction::ToggleUnifiedView
+                | KeyboardAction::ToggleLargestMessages
+                | KeyboardAction::ToggleReflow
+                | KeyboardAction::LockScreen
                 | KeyboardAction::ViewAttachment
                 | KeyboardAction::SelectFirstAttachment
                 | KeyboardAction::OpenAttachmentWithSystem
+                | KeyboardAction::SaveAttachment
+                | KeyboardAction::SaveAllAttachments
                 | KeyboardAction::ToggleViewMode
                 | KeyboardAction::ToggleHeaders
+                | KeyboardAction::RenderFullMessage
+                | KeyboardAction::OpenInBrowser
+                | KeyboardAction::ShowHeadersPanel
+                | KeyboardAction::ShowLinks
                 | KeyboardAction::ScrollToTop
                 | KeyboardAction::ScrollToBottom
                 | KeyboardAction::ToggleThreadedView
@@ -246,6 +281,10 @@ impl KeyboardShortcutsUI {
                 KeyboardAction::SortByDate
                 | KeyboardAction::SortBySender
                 | KeyboardAction::SortBySubject
+                | KeyboardAction::SortBySize
+                | KeyboardAction::SortByPriority
+                | KeyboardAction::CycleSortField
+                | KeyboardAction::ToggleSortOrder
                 | KeyboardAction::StartSearch
                 | KeyboardAction::StartFolderSearch
                 | KeyboardAction::EndSearch => 7,
@@ -254,7 +293,12 @@ impl KeyboardShortcutsUI {
                 KeyboardAction::AddAccount
                 | KeyboardAction::RemoveAccount
                 | KeyboardAction::RefreshAccount
-                | KeyboardAction::SwitchAccount => 8,
+                | KeyboardAction::SwitchAccount
+                | KeyboardAction::ShowAccountDetails
+                | KeyboardAction::ShowSpecialFolders
+                | KeyboardAction::ShowRetention
+                | KeyboardAction::ShowFilterRules
+                | KeyboardAction::ShowMboxWizard => 8,
                 
                 // Folder Management (9)
                 KeyboardAction::RefreshFolder
@@ -298,22 +342,46 @@ impl KeyboardShortcutsUI {
             KeyboardAction::MoveDown => "Move down",
             KeyboardAction::MoveLeft => "Move left",
             KeyboardAction::MoveRight => "Move right",
+            KeyboardAction::GoToInbox => "Go to the inbox",
+            KeyboardAction::GoToStarred => "Go to the Starred folder",
             KeyboardAction::Select => "Select current item",
             KeyboardAction::Escape => "Cancel or go back",
             KeyboardAction::ToggleExpanded => "Toggle expanded/collapsed",
             KeyboardAction::ComposeEmail => "Compose new email",
             KeyboardAction::ReplyEmail => "Reply to email (message list/preview)",
             KeyboardAction::ReplyAllEmail => "Reply all to email (message list/preview)",
+            KeyboardAction::ReplyToList => "Reply to the mailing list (message list/viewer)",
             KeyboardAction::ForwardEmail => "Forward email (message list/preview)",
             KeyboardAction::DeleteEmail => "Delete email (message list/preview)",
             KeyboardAction::MarkAsRead => "Mark as read (message list/preview)",
             KeyboardAction::MarkAsUnread => "Mark as unread (message list/preview)",
+            KeyboardAction::MarkThreadRead => "Mark thread as read (threaded view)",
+            KeyboardAction::MarkThreadUnread => "Mark thread as unread (threaded view)",
+            KeyboardAction::MarkAllRead => "Mark folder as read (account switcher: whole account)",
+            KeyboardAction::UndoMarkAllRead => "Cancel or undo mark all read",
             KeyboardAction::ShowDraftList => "Show draft list",
             KeyboardAction::ArchiveEmail => "Archive email (message list/preview)",
+            KeyboardAction::BlockSender => "Block sender (message list/preview)",
+            KeyboardAction::SnoozeMessage => "Snooze message until a chosen time (message list/preview)",
+            KeyboardAction::NextInboxTab => "Next INBOX tab (AI triage)",
+            KeyboardAction::PreviousInboxTab => "Previous INBOX tab (AI triage)",
+            KeyboardAction::MoveToPrimaryTab => "Move sender's mail to Primary and remember it",
+            KeyboardAction::MoveToPromotionsTab => "Move sender's mail to Promotions and remember it",
+            KeyboardAction::MoveToSocialTab => "Move sender's mail to Social and remember it",
+            KeyboardAction::MoveToUpdatesTab => "Move sender's mail to Updates and remember it",
+            KeyboardAction::MoveToForumsTab => "Move sender's mail to Forums and remember it",
+            KeyboardAction::ToggleMessageMark => "Mark/unmark message for batch actions",
+            KeyboardAction::ReportSpam => "Report spam (marked or selected messages)",
+            KeyboardAction::ReportNotSpam => "Report not spam and allowlist sender",
             KeyboardAction::AddAccount => "Add new account",
             KeyboardAction::RemoveAccount => "Remove account (account switcher)",
             KeyboardAction::RefreshAccount => "Refresh account (account switcher)",
             KeyboardAction::SwitchAccount => "Switch account",
+            KeyboardAction::ShowAccountDetails => "Server capabilities and TLS (current account)",
+            KeyboardAction::ShowSpecialFolders => "Sent/Drafts/Trash/Junk/Archive mapping (current account)",
+            KeyboardAction::ShowRetention => "Retention preview and pending removals",
+            KeyboardAction::ShowFilterRules => "Filter rules: test on the current folder, enable",
+            KeyboardAction::ShowMboxWizard => "Import from or export to mbox",
             KeyboardAction::StartSearch => "Start search (message list)",
             KeyboardAction::StartFolderSearch => "Search folders (folder tree)",
             KeyboardAction::EndSearch => "End search",
@@ -321,16 +389,31 @@ impl KeyboardShortcutsUI {
             KeyboardAction::ExpandThread => "Expand thread (message list)",
             KeyboardAction::CollapseThread => "Collapse thread (message list)",
             KeyboardAction::OpenEmailViewer => "Open email in full-screen viewer",
+            KeyboardAction::ToggleTodayPanel => "Toggle today panel",
+            KeyboardAction::ToggleUnifiedView => "Toggle unified view of all folders (message list)",
+            KeyboardAction::ToggleLargestMessages => "Largest messages of folder or account (message list/folder tree)",
+            KeyboardAction::ToggleReflow => "Reflow plain text to the reading column (content preview)",
+            KeyboardAction::LockScreen => "Lock the screen",
             KeyboardAction::ViewAttachment => "View selected attachment (content preview)",
             KeyboardAction::SelectFirstAttachment => "Select first attachment (content preview)",
             KeyboardAction::OpenAttachmentWithSystem => "Open attachment with system app (content preview)",
+            KeyboardAction::SaveAttachment => "Save selected attachment (content preview)",
+            KeyboardAction::SaveAllAttachments => "Save all attachments of the message",
             KeyboardAction::ToggleViewMode => "Toggle view mode (content preview)",
             KeyboardAction::ToggleHeaders => "Toggle extended headers (content preview)",
+            KeyboardAction::RenderFullMessage => "Render truncated message in full (content preview)",
+            KeyboardAction::OpenInBrowser => "Open message HTML in web browser",
+            KeyboardAction::ShowHeadersPanel => "Show headers panel (content preview)",
+            KeyboardAction::ShowLinks => "Open or copy links (content preview)",
             KeyboardAction::ScrollToTop => "Scroll to top (content preview)",
             KeyboardAction::ScrollToBottom => "Scroll to bottom (content preview)",
             KeyboardAction::SortByDate => "Sort by date (message list)",
             KeyboardAction::SortBySender => "Sort by sender (message list)",
             KeyboardAction::SortBySubject => "Sort by subject (message list)",
+            KeyboardAction::SortBySize => "Sort by size (message list)",
+            KeyboardAction::SortByPriority => "Sort by priority (message list)",
+            KeyboardAction::CycleSortField => "Sort by next field (message list)",
+            KeyboardAction::ToggleSortOrder => "Reverse sort order (message list)",
             KeyboardAction::NextMessage => "Next message (message list/preview)",
             KeyboardAction::PreviousMessage => "Previous message (message list/preview)",
             KeyboardAction::RefreshFolder => "Refresh current folder (folder tree)",
@@ -345,12 +428,15 @@ impl KeyboardShortcutsUI {
             KeyboardAction::AIEmailSuggestions => "Get AI suggestions for current email (message list/preview)",
             KeyboardAction::AIComposeSuggestions => "AI assistance for email composition (compose mode)",
             KeyboardAction::AISummarizeEmail => "Generate AI summary of current email (message list/preview)",
+            KeyboardAction::AISummarizeThread => "Summarize the current email's whole thread with AI (message list/preview)",
             KeyboardAction::AICalendarAssist => "AI calendar assistance (calendar mode)",
             KeyboardAction::AIConfigureSettings => "Open AI configuration and settings",
             KeyboardAction::AIQuickReply => "Generate quick reply suggestions (message list/preview)",
+            KeyboardAction::AIDraftReply => "Draft a whole reply with AI and open it in compose (message list/preview)",
             KeyboardAction::AIEmailAnalysis => "Analyze email content with AI (message list/preview)",
             KeyboardAction::AIScheduleRequest => "Parse scheduling requests with AI (message list/preview)",
             KeyboardAction::AIContentGeneration => "Generate email content with AI (compose mode)",
+            KeyboardAction::AIDiagnostics => "Show AI cache size and hit rate; f flushes the cache",
             
             // Calendar and Event actions
             KeyboardAction::ShowCalendar => "Switch to calendar view",
@@ -359,6 +445,8 @@ impl KeyboardShortcutsUI {
             KeyboardAction::EditEvent => "Edit selected event",
             KeyboardAction::DeleteEvent => "Delete selected event",
             KeyboardAction::ViewEventDetails => "View event details",
+            KeyboardAction::NudgeAttendees => "Remind attendees who haven't responded",
+            KeyboardAction::SnoozeReminder => "Snooze the last event reminder (any view)",
             KeyboardAction::CalendarNextMonth => "Next month in calendar",
             KeyboardAction::CalendarPrevMonth => "Previous month in calendar",
             KeyboardAction::CalendarToday => "Jump to today in calendar",
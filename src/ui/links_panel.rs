@@ -0,0 +1,129 @@
+//! Links panel
+//!
+//! Popup listing the links found in the displayed message. Enter opens the
+//! selected one through the configured handler for its scheme.
+
+use crate::clipboard::ClipboardManager;
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Popup with the links of one message
+pub struct LinksPanel {
+    links: Vec<String>,
+    state: ListState,
+    clipboard: ClipboardManager,
+}
+
+impl LinksPanel {
+    /// Links are listed once each, in the order they first appear
+    pub fn new(links: &[String]) -> Self {
+        let mut unique: Vec<String> = Vec::new();
+        for link in links {
+            if !unique.contains(link) {
+                unique.push(link.clone());
+            }
+        }
+        let mut state = ListState::default();
+        state.select((!unique.is_empty()).then_some(0));
+        Self {
+            links: unique,
+            state,
+            clipboard: ClipboardManager::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    pub fn selected(&self) -> Option<&str> {
+        self.state
+            .selected()
+            .and_then(|index| self.links.get(index))
+            .map(String::as_str)
+    }
+
+    pub fn select_next(&mut self) {
+        if let Some(index) = self.state.selected() {
+            self.state.select(Some((index + 1).min(self.links.len().saturating_sub(1))));
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if let Some(index) = self.state.selected() {
+            self.state.select(Some(index.saturating_sub(1)));
+        }
+    }
+
+    /// Copy the selected link, returning it
+    pub fn copy_selected(&mut self) -> Result<String, Box<dyn std::error::Error>> {
+        let link = self.selected().ok_or("Nothing selected")?.to_string();
+        self.clipboard.copy(&link)?;
+        Ok(link)
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(70, 60, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" Links ({}) ", self.links.len()))
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(2)])
+            .split(inner);
+
+        let items: Vec<ListItem> = self
+            .links
+            .iter()
+            .map(|link| ListItem::new(Line::from(format!("  {}", link))))
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(theme.colors.palette.selection)
+                .fg(theme.colors.palette.selection_text)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(list, sections[0], &mut self.state);
+
+        let help = Paragraph::new(Line::from(Span::styled(
+            "j/k: move · Enter/o: open · y: copy · Esc: close",
+            label,
+        )))
+        .block(Block::default().borders(Borders::TOP));
+        frame.render_widget(help, sections[1]);
+    }
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
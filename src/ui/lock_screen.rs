@@ -0,0 +1,178 @@
+//! Locking the TUI on a shared terminal
+//!
+//! After a configurable idle time, or when the user asks, the whole screen
+//! is replaced by a passphrase prompt. Nothing else is drawn while locked,
+//! so message lists, previews and notifications stay hidden until the master
+//! passphrase from `SecureStorage` is entered. Locking needs a master
+//! passphrase; without one the idle timer does nothing.
+
+use crate::theme::Theme;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Auto-lock configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LockConfig {
+    /// Minutes without input before the TUI locks; 0 turns auto-lock off
+    pub idle_timeout_mins: u64,
+}
+
+impl Default for LockConfig {
+    fn default() -> Self {
+        Self {
+            idle_timeout_mins: 15,
+        }
+    }
+}
+
+impl LockConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "lock.toml";
+
+    /// Idle time after which to lock, if auto-lock is on
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        (self.idle_timeout_mins > 0).then(|| Duration::from_secs(self.idle_timeout_mins * 60))
+    }
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+/// What a key press on the lock screen asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockAction {
+    None,
+    /// Check the entered passphrase
+    Submit(String),
+    Quit,
+}
+
+/// Passphrase prompt shown while the TUI is locked
+#[derive(Debug, Default)]
+pub struct LockScreen {
+    input: String,
+    error: Option<String>,
+    verifying: bool,
+    failed_attempts: u32,
+}
+
+impl LockScreen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> LockAction {
+        if self.verifying {
+            return LockAction::None;
+        }
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => LockAction::Quit,
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.input.clear();
+                LockAction::None
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.error = None;
+                LockAction::None
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                LockAction::None
+            }
+            KeyCode::Esc => {
+                self.input.clear();
+                LockAction::None
+            }
+            KeyCode::Enter if !self.input.is_empty() => {
+                self.verifying = true;
+                LockAction::Submit(std::mem::take(&mut self.input))
+            }
+            _ => LockAction::None,
+        }
+    }
+
+    /// Record a wrong passphrase
+    pub fn reject(&mut self) {
+        self.verifying = false;
+        self.failed_attempts += 1;
+        self.error = Some(format!("Wrong passphrase ({} failed)", self.failed_attempts));
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        // Cover everything drawn so far
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Block::default().style(Style::default().bg(theme.colors.palette.background)),
+            area,
+        );
+
+        let popup_area = centered_rect(50, 9, area);
+        let block = Block::default()
+            .title(" Comunicado is locked ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let status = if self.verifying {
+            Span::styled("Checking…", label)
+        } else if let Some(ref error) = self.error {
+            Span::styled(error.clone(), Style::default().fg(theme.colors.palette.error))
+        } else {
+            Span::styled("Enter the master passphrase to unlock", label)
+        };
+        let masked = format!("{}▏", "•".repeat(self.input.chars().count()));
+        let lines = vec![
+            Line::from(status),
+            Line::from(""),
+            Line::from(Span::styled(
+                masked,
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(Span::styled("Enter: unlock · Esc: clear · Ctrl+C: quit", label)),
+        ];
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), inner);
+    }
+}
+
+/// Rectangle of `percent_x` width and `height` rows, centered in `r`
+fn centered_rect(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,260 @@
+//! Viewers from the system's mailcap files
+//!
+//! Mailcap (RFC 1524) is the traditional Unix table of which program shows
+//! which MIME type, read here from `$MAILCAPS` or `~/.mailcap`,
+//! `/etc/mailcap`, `/usr/etc/mailcap` and `/usr/local/etc/mailcap`. Only
+//! entries that can run outside the terminal on a file are used: those
+//! marked `needsterminal` or `copiousoutput`, those reading standard input
+//! (no `%s`) and those needing shell pipes or redirection are skipped, as
+//! is any entry whose `test` command fails.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+
+/// Shell syntax a mailcap command can't use, since it isn't run by a shell
+const SHELL_SYNTAX: &[char] = &['|', '&', '<', '>', '$', '`'];
+
+/// One view entry from a mailcap file
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MailcapEntry {
+    /// MIME type, possibly `type/*`
+    mime_type: String,
+    /// View command, with `%s` standing for the file
+    command: String,
+    /// Command that must succeed for the entry to apply
+    test: Option<String>,
+    needs_terminal: bool,
+    copious_output: bool,
+}
+
+impl MailcapEntry {
+    fn matches(&self, mime_type: &str) -> bool {
+        if self.mime_type.eq_ignore_ascii_case(mime_type) {
+            return true;
+        }
+        match (self.mime_type.split_once('/'), mime_type.split_once('/')) {
+            (Some((kind, "*")), Some((wanted, _))) => kind.eq_ignore_ascii_case(wanted),
+            _ => false,
+        }
+    }
+
+    /// Whether the entry can be run directly on a saved file
+    fn usable(&self) -> bool {
+        !self.needs_terminal
+            && !self.copious_output
+            && self.command.contains("%s")
+            && !self.command.contains("%{")
+            && !self.command.contains(SHELL_SYNTAX)
+    }
+}
+
+/// Mailcap view command for a MIME type, with `%t` filled in
+///
+/// The command still contains `%s` for the file path; it goes through the
+/// same splitting and substitution as commands from `opener.toml`.
+pub async fn command_for(mime_type: &str) -> Option<String> {
+    let mime_type = mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    for path in mailcap_files() {
+        let Ok(contents) = tokio::fs::read_to_string(&path).await else {
+            continue;
+        };
+        for entry in parse(&contents) {
+            if !entry.matches(&mime_type) || !entry.usable() {
+                continue;
+            }
+            if let Some(test) = &entry.test {
+                if !passes(&test.replace("%t", &mime_type)).await {
+                    continue;
+                }
+            }
+            return Some(expand(&entry.command, &mime_type));
+        }
+    }
+    None
+}
+
+/// Mailcap files in the order they are searched
+fn mailcap_files() -> Vec<PathBuf> {
+    if let Some(paths) = std::env::var_os("MAILCAPS") {
+        return std::env::split_paths(&paths).collect();
+    }
+
+    let mut files = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        files.push(home.join(".mailcap"));
+    }
+    files.extend(
+        ["/etc/mailcap", "/usr/etc/mailcap", "/usr/local/etc/mailcap"]
+            .iter()
+            .map(PathBuf::from),
+    );
+    files
+}
+
+/// Run a `test` command through the shell, as mailcap expects
+///
+/// Tests that look at the file itself can't be run before it is chosen, so
+/// they count as failed.
+async fn passes(test: &str) -> bool {
+    if test.contains("%s") {
+        return false;
+    }
+    tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(test)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .is_ok_and(|status| status.success())
+}
+
+/// Replace `%t` with the MIME type and `%%` with a percent sign
+fn expand(command: &str, mime_type: &str) -> String {
+    let mut expanded = String::with_capacity(command.len());
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('%', Some('t')) => {
+                chars.next();
+                expanded.push_str(mime_type);
+            }
+            ('%', Some('%')) => {
+                chars.next();
+                expanded.push('%');
+            }
+            _ => expanded.push(c),
+        }
+    }
+    expanded
+}
+
+/// View entries of a mailcap file, in file order
+fn parse(contents: &str) -> Vec<MailcapEntry> {
+    let mut entries = Vec::new();
+    let mut line = String::new();
+
+    for raw in contents.lines() {
+        if line.is_empty() && raw.trim_start().starts_with('#') {
+            continue;
+        }
+        // A trailing backslash continues the entry on the next line
+        if let Some(continued) = raw.strip_suffix('\\') {
+            line.push_str(continued);
+            continue;
+        }
+        line.push_str(raw);
+        if let Some(entry) = parse_entry(&std::mem::take(&mut line)) {
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+fn parse_entry(line: &str) -> Option<MailcapEntry> {
+    let mut fields = split_fields(line).into_iter();
+    let mime_type = fields.next()?.to_ascii_lowercase();
+    let command = fields.next()?;
+    if mime_type.is_empty() || command.is_empty() {
+        return None;
+    }
+
+    let mut entry = MailcapEntry {
+        // A bare major type means any subtype
+        mime_type: if mime_type.contains('/') {
+            mime_type
+        } else {
+            format!("{}/*", mime_type)
+        },
+        command,
+        test: None,
+        needs_terminal: false,
+        copious_output: false,
+    };
+    for field in fields {
+        let (name, value) = match field.split_once('=') {
+            Some((name, value)) => (name.trim().to_ascii_lowercase(), Some(value.trim())),
+            None => (field.to_ascii_lowercase(), None),
+        };
+        match (name.as_str(), value) {
+            ("needsterminal", _) => entry.needs_terminal = true,
+            ("copiousoutput", _) => entry.copious_output = true,
+            ("test", Some(test)) => entry.test = Some(test.to_string()),
+            _ => {}
+        }
+    }
+    Some(entry)
+}
+
+/// Split an entry on unescaped semicolons, trimming each field
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    if next != ';' && next != '\\' {
+                        field.push('\\');
+                    }
+                    field.push(next);
+                }
+            }
+            ';' => fields.push(std::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_match_entries() {
+        let mailcap = r#"
+# Viewers
+text/html; w3m -T text/html '%s'; needsterminal
+text/plain; cat %s; copiousoutput
+application/pdf; evince '%s'; test=test -n "$DISPLAY"
+image; imv %s
+audio/*; showaudio
+application/x-foo; foo --title "a\;b" \
+    --type %t %s
+video/*; mpv %s | tee log
+"#;
+        let entries = parse(mailcap);
+        assert_eq!(entries.len(), 7);
+
+        let pdf = &entries[2];
+        assert!(pdf.matches("application/pdf") && pdf.usable());
+        assert_eq!(pdf.test.as_deref(), Some(r#"test -n "$DISPLAY""#));
+
+        let image = &entries[3];
+        assert_eq!(image.mime_type, "image/*");
+        assert!(image.matches("image/png") && !image.matches("video/png"));
+
+        let usable: Vec<&str> = entries
+            .iter()
+            .filter(|entry| entry.usable())
+            .map(|entry| entry.mime_type.as_str())
+            .collect();
+        assert_eq!(usable, vec!["application/pdf", "image/*", "application/x-foo"]);
+
+        assert_eq!(
+            expand(&entries[5].command, "application/x-foo"),
+            r#"foo --title "a;b"     --type application/x-foo %s"#
+        );
+        assert_eq!(expand("view 100%% %s", "text/plain"), "view 100% %s");
+    }
+}
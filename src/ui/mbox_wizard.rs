@@ -0,0 +1,661 @@
+//! Mbox import and export wizard
+//!
+//! Import: type the path of an mbox file or of a directory holding them, such
+//! as mutt's `~/Mail`, pick the mailboxes and the folder each one goes into,
+//! then import them into the chosen account. Export: type a directory and pick
+//! folders of an account; each folder is written to its own mbox file.
+
+use crate::email::{MboxMailbox, MboxOutcome, MboxProgress, MboxTarget};
+use crate::theme::Theme;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Gauge, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+use std::path::PathBuf;
+
+/// Whether mail comes in from mbox files or goes out to them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MboxDirection {
+    Import,
+    Export,
+}
+
+/// Steps of the wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MboxWizardStep {
+    /// Typing the file or directory
+    Path,
+    /// Picking mailboxes or folders, with their message counts
+    Select,
+    Running,
+    Done,
+}
+
+/// What the app should do after a key in the wizard
+#[derive(Debug, Clone)]
+pub enum MboxWizardAction {
+    Continue,
+    Close,
+    /// Look for mbox files at the path
+    Scan(PathBuf),
+    /// Show the folders of another account to export
+    LoadFolders(String),
+    Import {
+        account_id: String,
+        targets: Vec<MboxTarget>,
+    },
+    Export {
+        account_id: String,
+        folders: Vec<String>,
+        directory: PathBuf,
+    },
+    /// Stop the running import or export
+    Cancel,
+}
+
+/// A mailbox found for import and the folder it goes into
+struct ImportRow {
+    mailbox: MboxMailbox,
+    folder: String,
+    selected: bool,
+}
+
+/// A folder offered for export
+struct ExportRow {
+    folder: String,
+    message_count: u32,
+    selected: bool,
+}
+
+/// Popup walking through an mbox import or export
+pub struct MboxWizard {
+    direction: MboxDirection,
+    step: MboxWizardStep,
+    path: String,
+    /// (account id, display name)
+    accounts: Vec<(String, String)>,
+    account: usize,
+    imports: Vec<ImportRow>,
+    exports: Vec<ExportRow>,
+    state: ListState,
+    /// Target folder being typed for the selected mailbox
+    editing: Option<String>,
+    progress: Option<MboxProgress>,
+    outcome: Option<MboxOutcome>,
+    error: Option<String>,
+}
+
+impl MboxWizard {
+    /// Open on the path step, with `account_id` chosen if it is in the list
+    pub fn new(accounts: Vec<(String, String)>, account_id: Option<&str>) -> Self {
+        let account = account_id
+            .and_then(|id| accounts.iter().position(|(account, _)| account == id))
+            .unwrap_or(0);
+        Self {
+            direction: MboxDirection::Import,
+            step: MboxWizardStep::Path,
+            path: default_path(MboxDirection::Import).to_string(),
+            accounts,
+            account,
+            imports: Vec::new(),
+            exports: Vec::new(),
+            state: ListState::default(),
+            editing: None,
+            progress: None,
+            outcome: None,
+            error: None,
+        }
+    }
+
+    /// The chosen account
+    pub fn account_id(&self) -> Option<&str> {
+        self.accounts.get(self.account).map(|(id, _)| id.as_str())
+    }
+
+    /// Show the mailboxes found at the path, each going into a folder of the same name
+    pub fn set_mailboxes(&mut self, mailboxes: Vec<MboxMailbox>) {
+        self.imports = mailboxes
+            .into_iter()
+            .map(|mailbox| ImportRow {
+                folder: mailbox.name.clone(),
+                mailbox,
+                selected: true,
+            })
+            .collect();
+        self.error = None;
+        self.select_first(self.imports.len());
+        self.step = MboxWizardStep::Select;
+    }
+
+    /// Show the folders of the chosen account that have messages
+    pub fn set_folders(&mut self, folders: Vec<(String, u32)>) {
+        self.exports = folders
+            .into_iter()
+            .map(|(folder, message_count)| ExportRow {
+                folder,
+                message_count,
+                selected: true,
+            })
+            .collect();
+        self.select_first(self.exports.len());
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn set_progress(&mut self, progress: MboxProgress) {
+        self.progress = Some(progress);
+    }
+
+    /// Show how the run ended, or why it failed
+    pub fn finish(&mut self, outcome: Result<MboxOutcome, String>) {
+        match outcome {
+            Ok(outcome) => self.outcome = Some(outcome),
+            Err(error) => self.error = Some(error),
+        }
+        self.step = MboxWizardStep::Done;
+    }
+
+    fn select_first(&mut self, len: usize) {
+        self.state.select(if len == 0 { None } else { Some(0) });
+    }
+
+    fn rows(&self) -> usize {
+        match self.direction {
+            MboxDirection::Import => self.imports.len(),
+            MboxDirection::Export => self.exports.len(),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> MboxWizardAction {
+        if let Some(ref mut folder) = self.editing {
+            match key.code {
+                KeyCode::Esc => self.editing = None,
+                KeyCode::Enter => {
+                    let folder = std::mem::take(folder).trim().to_string();
+                    self.editing = None;
+                    let selected = self.state.selected();
+                    if let Some(row) = selected.and_then(|index| self.imports.get_mut(index)) {
+                        if !folder.is_empty() {
+                            row.folder = folder;
+                        }
+                    }
+                }
+                KeyCode::Backspace => {
+                    folder.pop();
+                }
+                KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    folder.push(c)
+                }
+                _ => {}
+            }
+            return MboxWizardAction::Continue;
+        }
+
+        match self.step {
+            MboxWizardStep::Path => self.handle_path_key(key),
+            MboxWizardStep::Select => self.handle_select_key(key),
+            MboxWizardStep::Running => match key.code {
+                KeyCode::Esc | KeyCode::Char('x') => MboxWizardAction::Cancel,
+                _ => MboxWizardAction::Continue,
+            },
+            MboxWizardStep::Done => match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => MboxWizardAction::Close,
+                _ => MboxWizardAction::Continue,
+            },
+        }
+    }
+
+    fn handle_path_key(&mut self, key: KeyEvent) -> MboxWizardAction {
+        match key.code {
+            KeyCode::Esc => return MboxWizardAction::Close,
+            KeyCode::Tab => {
+                let previous = default_path(self.direction);
+                self.direction = match self.direction {
+                    MboxDirection::Import => MboxDirection::Export,
+                    MboxDirection::Export => MboxDirection::Import,
+                };
+                if self.path == previous {
+                    self.path = default_path(self.direction).to_string();
+                }
+                self.error = None;
+                if self.direction == MboxDirection::Export {
+                    if let Some(account_id) = self.account_id() {
+                        return MboxWizardAction::LoadFolders(account_id.to_string());
+                    }
+                }
+            }
+            KeyCode::Enter if !self.path.trim().is_empty() => {
+                let path = expand_home(self.path.trim());
+                match self.direction {
+                    MboxDirection::Import => return MboxWizardAction::Scan(path),
+                    MboxDirection::Export => {
+                        self.error = None;
+                        self.step = MboxWizardStep::Select;
+                    }
+                }
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.path.clear()
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => self.path.push(c),
+            KeyCode::Backspace => {
+                self.path.pop();
+            }
+            _ => {}
+        }
+        MboxWizardAction::Continue
+    }
+
+    fn handle_select_key(&mut self, key: KeyEvent) -> MboxWizardAction {
+        let rows = self.rows();
+        let selected = self.state.selected();
+        match key.code {
+            KeyCode::Esc => return MboxWizardAction::Close,
+            KeyCode::Backspace => {
+                self.error = None;
+                self.step = MboxWizardStep::Path;
+            }
+            KeyCode::Up | KeyCode::Char('k') if rows > 0 => {
+                let index = selected.map_or(0, |index| (index + rows - 1) % rows);
+                self.state.select(Some(index));
+            }
+            KeyCode::Down | KeyCode::Char('j') if rows > 0 => {
+                let index = selected.map_or(0, |index| (index + 1) % rows);
+                self.state.select(Some(index));
+            }
+            KeyCode::Char(' ') => {
+                if let Some(index) = selected {
+                    match self.direction {
+                        MboxDirection::Import => {
+                            if let Some(row) = self.imports.get_mut(index) {
+                                row.selected = !row.selected;
+                            }
+                        }
+                        MboxDirection::Export => {
+                            if let Some(row) = self.exports.get_mut(index) {
+                                row.selected = !row.selected;
+                            }
+                        }
+                    }
+                }
+            }
+            KeyCode::Char(c @ ('a' | 'n')) => {
+                let all = c == 'a';
+                self.imports.iter_mut().for_each(|row| row.selected = all);
+                self.exports.iter_mut().for_each(|row| row.selected = all);
+            }
+            KeyCode::Char('e') if self.direction == MboxDirection::Import => {
+                if let Some(row) = selected.and_then(|index| self.imports.get(index)) {
+                    self.editing = Some(row.folder.clone());
+                }
+            }
+            KeyCode::Tab if !self.accounts.is_empty() => {
+                self.account = (self.account + 1) % self.accounts.len();
+                if self.direction == MboxDirection::Export {
+                    if let Some(account_id) = self.account_id() {
+                        return MboxWizardAction::LoadFolders(account_id.to_string());
+                    }
+                }
+            }
+            KeyCode::Enter => return self.start(),
+            _ => {}
+        }
+        MboxWizardAction::Continue
+    }
+
+    fn start(&mut self) -> MboxWizardAction {
+        let Some(account_id) = self.account_id().map(str::to_string) else {
+            self.error = Some("No account to use".to_string());
+            return MboxWizardAction::Continue;
+        };
+
+        let action = match self.direction {
+            MboxDirection::Import => {
+                let targets: Vec<MboxTarget> = self
+                    .imports
+                    .iter()
+                    .filter(|row| row.selected)
+                    .map(|row| MboxTarget {
+                        mailbox: row.mailbox.clone(),
+                        folder: row.folder.clone(),
+                    })
+                    .collect();
+                if targets.is_empty() {
+                    return MboxWizardAction::Continue;
+                }
+                MboxWizardAction::Import {
+                    account_id,
+                    targets,
+                }
+            }
+            MboxDirection::Export => {
+                let folders: Vec<String> = self
+                    .exports
+                    .iter()
+                    .filter(|row| row.selected)
+                    .map(|row| row.folder.clone())
+                    .collect();
+                if folders.is_empty() {
+                    return MboxWizardAction::Continue;
+                }
+                MboxWizardAction::Export {
+                    account_id,
+                    folders,
+                    directory: expand_home(self.path.trim()),
+                }
+            }
+        };
+
+        self.error = None;
+        self.progress = None;
+        self.step = MboxWizardStep::Running;
+        action
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(80, 80, area);
+        frame.render_widget(Clear, popup_area);
+
+        let title = match self.direction {
+            MboxDirection::Import => " Import from mbox ",
+            MboxDirection::Export => " Export to mbox ",
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(3),
+                Constraint::Length(2),
+            ])
+            .split(inner);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let path_label = match self.direction {
+            MboxDirection::Import => "Mbox file or directory: ",
+            MboxDirection::Export => "Write files to: ",
+        };
+        let cursor = if self.step == MboxWizardStep::Path {
+            "▏"
+        } else {
+            ""
+        };
+        let account = self
+            .accounts
+            .get(self.account)
+            .map_or("none", |(_, name)| name.as_str());
+        let account_label = match self.direction {
+            MboxDirection::Import => "Into account: ",
+            MboxDirection::Export => "From account: ",
+        };
+        let header = vec![
+            Line::from(vec![
+                Span::styled(path_label, label),
+                Span::styled(format!("{}{}", self.path, cursor), bold),
+            ]),
+            Line::from(vec![
+                Span::styled(account_label, label),
+                Span::raw(account.to_string()),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(header), sections[0]);
+
+        match self.step {
+            MboxWizardStep::Path => self.render_path_hint(frame, sections[1], label),
+            MboxWizardStep::Select => self.render_rows(frame, sections[1], theme, label),
+            MboxWizardStep::Running => self.render_progress(frame, sections[1], theme, label),
+            MboxWizardStep::Done => self.render_outcome(frame, sections[1], theme, label),
+        }
+
+        let help = match (self.step, self.editing.is_some()) {
+            (_, true) => "Type the folder name · Enter: keep · Esc: cancel",
+            (MboxWizardStep::Path, _) => {
+                "Enter: continue · Tab: switch import/export · Ctrl+U: clear · Esc: close"
+            }
+            (MboxWizardStep::Select, _) if self.direction == MboxDirection::Import => {
+                "Space: toggle · a/n: all/none · e: target folder · Tab: account · Enter: import · Backspace: back"
+            }
+            (MboxWizardStep::Select, _) => {
+                "Space: toggle · a/n: all/none · Tab: account · Enter: export · Backspace: back"
+            }
+            (MboxWizardStep::Running, _) => "Esc: cancel",
+            (MboxWizardStep::Done, _) => "Enter: close",
+        };
+        let help = Paragraph::new(Line::from(Span::styled(help, label)))
+            .block(Block::default().borders(Borders::TOP));
+        frame.render_widget(help, sections[2]);
+    }
+
+    fn render_path_hint(&self, frame: &mut Frame, area: Rect, label: Style) {
+        let mut lines = vec![Line::from(Span::styled(
+            match self.direction {
+                MboxDirection::Import => {
+                    "A directory is searched for mbox files; each becomes a folder of the same name, which you can change."
+                }
+                MboxDirection::Export => {
+                    "Each folder is written to its own file, such as Lists.dev.mbox. Existing files are not overwritten."
+                }
+            },
+            label,
+        ))];
+        if let Some(ref error) = self.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(error.clone()));
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), area);
+    }
+
+    fn render_rows(&mut self, frame: &mut Frame, area: Rect, theme: &Theme, label: Style) {
+        let check = |selected: bool| if selected { "[x]" } else { "[ ]" };
+        let items: Vec<ListItem> = match self.direction {
+            MboxDirection::Import => self
+                .imports
+                .iter()
+                .enumerate()
+                .map(|(index, row)| {
+                    let folder = match self.editing {
+                        Some(ref folder) if self.state.selected() == Some(index) => {
+                            format!("{}▏", folder)
+                        }
+                        _ => row.folder.clone(),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("{} {:<30} ", check(row.selected), row.mailbox.name)),
+                        Span::styled(
+                            format!(
+                                "{:>6} messages, {:>5} unread  → ",
+                                row.mailbox.message_count, row.mailbox.unread_count
+                            ),
+                            label,
+                        ),
+                        Span::raw(folder),
+                    ]))
+                })
+                .collect(),
+            MboxDirection::Export => self
+                .exports
+                .iter()
+                .map(|row| {
+                    ListItem::new(Line::from(vec![
+                        Span::raw(format!("{} {:<30} ", check(row.selected), row.folder)),
+                        Span::styled(format!("{:>6} messages", row.message_count), label),
+                    ]))
+                })
+                .collect(),
+        };
+
+        if items.is_empty() {
+            let empty = match self.direction {
+                MboxDirection::Import => "No mbox files found",
+                MboxDirection::Export => "No folders with messages in this account",
+            };
+            frame.render_widget(Paragraph::new(Span::styled(empty, label)), area);
+            return;
+        }
+
+        let (messages, count) = match self.direction {
+            MboxDirection::Import => (
+                self.imports
+                    .iter()
+                    .filter(|row| row.selected)
+                    .map(|row| row.mailbox.message_count)
+                    .sum::<usize>(),
+                self.imports.iter().filter(|row| row.selected).count(),
+            ),
+            MboxDirection::Export => (
+                self.exports
+                    .iter()
+                    .filter(|row| row.selected)
+                    .map(|row| row.message_count as usize)
+                    .sum(),
+                self.exports.iter().filter(|row| row.selected).count(),
+            ),
+        };
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(area);
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(theme.colors.palette.selection)
+                .fg(theme.colors.palette.selection_text),
+        );
+        frame.render_stateful_widget(list, sections[0], &mut self.state);
+
+        let mut summary = format!("{} selected, {} messages", count, messages);
+        if let Some(ref error) = self.error {
+            summary = format!("{} · {}", summary, error);
+        }
+        frame.render_widget(Paragraph::new(Span::styled(summary, label)), sections[1]);
+    }
+
+    fn render_progress(&self, frame: &mut Frame, area: Rect, theme: &Theme, label: Style) {
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .split(area);
+        let (done, total, current) = self.progress.as_ref().map_or((0, 0, ""), |progress| {
+            (progress.done, progress.total, progress.current.as_str())
+        });
+        let ratio = if total == 0 {
+            0.0
+        } else {
+            (done as f64 / total as f64).min(1.0)
+        };
+
+        frame.render_widget(
+            Paragraph::new(Span::styled(format!("Copying {}", current), label)),
+            sections[0],
+        );
+        frame.render_widget(
+            Gauge::default()
+                .gauge_style(Style::default().fg(theme.colors.palette.accent))
+                .ratio(ratio)
+                .label(format!("{} / {}", done, total)),
+            sections[1],
+        );
+    }
+
+    fn render_outcome(&self, frame: &mut Frame, area: Rect, theme: &Theme, label: Style) {
+        let mut lines = Vec::new();
+        if let Some(ref error) = self.error {
+            lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(theme.colors.palette.error),
+            )));
+        }
+        if let Some(ref outcome) = self.outcome {
+            let verb = match self.direction {
+                MboxDirection::Import => "Imported",
+                MboxDirection::Export => "Exported",
+            };
+            let mut summary = format!("{} {} messages", verb, outcome.messages);
+            if outcome.duplicates_skipped > 0 {
+                summary.push_str(&format!(
+                    ", skipped {} already in the folder",
+                    outcome.duplicates_skipped
+                ));
+            }
+            if outcome.cancelled {
+                summary.push_str(" before cancelling");
+            }
+            lines.push(Line::from(Span::styled(
+                summary,
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            lines.extend(
+                outcome
+                    .files
+                    .iter()
+                    .map(|file| Line::from(Span::styled(file.display().to_string(), label))),
+            );
+            if !outcome.errors.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(format!("{} problems:", outcome.errors.len())));
+                lines.extend(
+                    outcome
+                        .errors
+                        .iter()
+                        .map(|error| Line::from(Span::styled(error.clone(), label))),
+                );
+            }
+        }
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+    }
+}
+
+fn default_path(direction: MboxDirection) -> &'static str {
+    match direction {
+        MboxDirection::Import => "~/Mail",
+        MboxDirection::Export => "~/mbox-export",
+    }
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix('~') {
+        Some(rest) => match dirs::home_dir() {
+            Some(home) => home.join(rest.trim_start_matches('/')),
+            None => PathBuf::from(path),
+        },
+        None => PathBuf::from(path),
+    }
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -1,29 +1,62 @@
 use crate::contacts::{SenderInfo, SenderRecognitionService};
 use crate::email::{
-    EmailDatabase, EmailMessage, EmailThread, MessageId, MultiCriteriaSorter, SortCriteria,
-    StoredMessage, ThreadingAlgorithm, ThreadingEngine,
+    dedup, DedupConfig, DedupedMessage, EmailDatabase, EmailMessage, EmailThread, InboxTab, MessageId,
+    MailPriority, MessageSecurity, MultiCriteriaSorter, SecurityBadge, SortCriteria, SortOrder, StoredMessage,
+    ThreadingAlgorithm, ThreadingEngine,
 };
 use crate::theme::Theme;
-use chrono::{DateTime, Utc};
+use crate::ui::folder_tree::{ALL_INBOXES, SNOOZED};
+use crate::ui::thread_behavior::ThreadBehaviorConfig;
+use crate::ui::preview_limits::format_size;
+use crate::ui::text_width::{display_width, take_width, truncate_to_width};
+use chrono::{DateTime, Local, Utc};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs},
     Frame,
 };
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+/// Width of the size column, enough for "999.9 MB"
+const SIZE_COLUMN_WIDTH: usize = 8;
+
+/// Widths of the message table's columns, in terminal cells
+#[derive(Debug, Clone, Copy)]
+struct ColumnWidths {
+    subject: usize,
+    correspondents: usize,
+    date: usize,
+    size: usize,
+    between: usize,
+}
+
+/// How a message row is drawn: its selection state, theme and column widths
+#[derive(Clone, Copy)]
+struct RowContext<'a> {
+    is_selected: bool,
+    is_focused: bool,
+    theme: &'a Theme,
+    widths: ColumnWidths,
+}
+
+/// How many messages the largest-messages view lists
+const LARGEST_MESSAGES_LIMIT: u32 = 200;
+
 #[derive(Debug, Clone)]
 pub struct MessageItem {
     pub subject: String,
     pub sender: String,
     pub date: String,
+    pub timestamp: Option<DateTime<Utc>>, // When the message was sent, for sorting by date
     pub is_read: bool,
     pub is_important: bool,
     pub has_attachments: bool,
+    pub size: Option<u32>, // RFC822.SIZE as reported by the server
+    pub priority: MailPriority, // From the X-Priority/Importance headers
     pub thread_depth: usize,
     pub thread_id: Option<String>,
     pub message_count: usize, // For thread root, number of messages in thread
@@ -31,6 +64,8 @@ pub struct MessageItem {
     pub is_thread_root: bool,
     pub message_id: Option<Uuid>, // Database ID for loading full content
     pub sender_info: Option<SenderInfo>, // Contact information for sender
+    pub security: Option<SecurityBadge>, // Signed/encrypted/authentication badge
+    pub locations: Vec<String>, // Folders and labels holding copies, when deduplicated
 }
 
 impl MessageItem {
@@ -39,9 +74,12 @@ impl MessageItem {
             subject,
             sender,
             date,
+            timestamp: None,
             is_read: true,
             is_important: false,
             has_attachments: false,
+            size: None,
+            priority: MailPriority::Normal,
             thread_depth: 0,
             thread_id: None,
             message_count: 1,
@@ -49,6 +87,8 @@ impl MessageItem {
             is_thread_root: false,
             message_id: None,
             sender_info: None,
+            security: None,
+            locations: Vec::new(),
         }
     }
 
@@ -63,9 +103,12 @@ impl MessageItem {
             subject,
             sender,
             date,
+            timestamp: None,
             is_read: true,
             is_important: false,
             has_attachments: false,
+            size: None,
+            priority: MailPriority::Normal,
             thread_depth,
             thread_id: Some(thread_id),
             message_count: 1,
@@ -73,6 +116,8 @@ impl MessageItem {
             is_thread_root: thread_depth == 0,
             message_id: None,
             sender_info: None,
+            security: None,
+            locations: Vec::new(),
         }
     }
 
@@ -136,11 +181,44 @@ pub struct MessageList {
     search_query: String,
     search_active: bool,
     search_results_count: usize,
+    // Search results came from the IMAP server rather than the loaded messages
+    server_search: bool,
     // Threading cache to avoid blocking database calls
     threading_cache: HashMap<String, Vec<StoredMessage>>,
     threading_cache_key: Option<String>,
     // Sender recognition service for contact lookup
     sender_recognition: Option<Arc<SenderRecognitionService>>,
+    // Messages marked for batch operations
+    marked_messages: HashSet<Uuid>,
+    // Conversation behaviour of the threaded view
+    thread_config: ThreadBehaviorConfig,
+    // Threads folded or unfolded by the user, overriding collapse_read_threads,
+    // per threading cache key so each folder keeps its own
+    thread_folds: HashMap<String, ThreadFolds>,
+    // Every message of each thread, including replies hidden by collapsing
+    thread_members: HashMap<String, Vec<Uuid>>,
+    // Where copies of the same message are collapsed
+    dedup_config: DedupConfig,
+    // Showing every folder of the current account instead of one folder
+    unified: bool,
+    // Showing the INBOX of every account
+    all_inboxes: bool,
+    // Showing the snoozed messages of the current account
+    snoozed: bool,
+    // Showing the biggest messages of one folder, or (None) of the whole account
+    largest: Option<Option<String>>,
+    // INBOX split into tabs by AI triage
+    inbox_tabs: bool,
+    inbox_tab: InboxTab,
+    // Unread messages per tab among those loaded
+    tab_unread: HashMap<InboxTab, usize>,
+}
+
+/// Threads folded or unfolded by hand in one folder
+#[derive(Debug, Clone, Default)]
+struct ThreadFolds {
+    collapsed: HashSet<String>,
+    expanded: HashSet<String>,
 }
 
 impl MessageList {
@@ -159,9 +237,22 @@ impl MessageList {
             search_query: String::new(),
             search_active: false,
             search_results_count: 0,
+            server_search: false,
             threading_cache: HashMap::new(),
             threading_cache_key: None,
             sender_recognition: None,
+            marked_messages: HashSet::new(),
+            thread_config: ThreadBehaviorConfig::default(),
+            thread_folds: HashMap::new(),
+            thread_members: HashMap::new(),
+            dedup_config: DedupConfig::default(),
+            unified: false,
+            all_inboxes: false,
+            snoozed: false,
+            largest: None,
+            inbox_tabs: false,
+            inbox_tab: InboxTab::default(),
+            tab_unread: HashMap::new(),
         };
 
         // Don't initialize with sample messages initially - they will be loaded from database
@@ -290,17 +381,23 @@ impl MessageList {
         is_focused: bool,
         theme: &Theme,
     ) {
-        // Split area into header and message list
+        // Split area into inbox tabs, header and message list
+        let tabs_height = if self.shows_inbox_tabs() { 1 } else { 0 };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Header row
-                Constraint::Min(0),    // Message list
+                Constraint::Length(tabs_height), // Inbox tabs
+                Constraint::Length(3),           // Header row
+                Constraint::Min(0),              // Message list
             ])
             .split(area);
         
-        let header_area = chunks[0];
-        let list_area = chunks[1];
+        let header_area = chunks[1];
+        let list_area = chunks[2];
+
+        if self.shows_inbox_tabs() {
+            self.render_inbox_tabs(frame, chunks[0], theme);
+        }
 
         // Render table header
         self.render_table_header(frame, header_area, theme);
@@ -316,25 +413,19 @@ impl MessageList {
 
         // Calculate column widths based on available area
         let available_width = list_area.width.saturating_sub(4) as usize; // Account for borders
-        let (subject_width, correspondents_width, date_width, between_width) = 
-            self.calculate_column_widths(available_width);
+        let widths = self.calculate_column_widths(available_width);
 
         let items: Vec<ListItem> = messages_to_display
             .iter()
             .enumerate()
             .map(|(i, message)| {
-                let is_selected = self.state.selected() == Some(i);
-                
-                self.render_message_row(
-                    message, 
-                    is_selected, 
-                    is_focused, 
-                    theme, 
-                    subject_width, 
-                    correspondents_width, 
-                    date_width, 
-                    between_width
-                )
+                let row = RowContext {
+                    is_selected: self.state.selected() == Some(i),
+                    is_focused,
+                    theme,
+                    widths,
+                };
+                ListItem::new(self.message_row_line(message, &row))
             })
             .collect();
 
@@ -345,17 +436,65 @@ impl MessageList {
         frame.render_stateful_widget(list, list_area, &mut self.state.clone());
     }
     
+    /// Render the Gmail-style tab row above the INBOX
+    fn render_inbox_tabs(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let titles: Vec<Line> = InboxTab::ALL
+            .iter()
+            .map(|tab| match self.tab_unread.get(tab) {
+                Some(&unread) if unread > 0 => Line::from(format!("{} ({})", tab, unread)),
+                _ => Line::from(tab.as_str()),
+            })
+            .collect();
+        let selected = InboxTab::ALL
+            .iter()
+            .position(|tab| *tab == self.inbox_tab)
+            .unwrap_or(0);
+
+        let tabs = Tabs::new(titles)
+            .select(selected)
+            .style(Style::default().fg(theme.colors.palette.text_muted))
+            .highlight_style(
+                Style::default()
+                    .fg(theme.colors.palette.accent)
+                    .add_modifier(Modifier::BOLD),
+            );
+        frame.render_widget(tabs, area);
+    }
+
     /// Render the table header row
     fn render_table_header(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let available_width = area.width.saturating_sub(4) as usize;
-        let (subject_width, correspondents_width, date_width, _between_width) = 
-            self.calculate_column_widths(available_width);
+        let ColumnWidths {
+            subject: subject_width,
+            correspondents: correspondents_width,
+            date: date_width,
+            size: size_width,
+            ..
+        } = self.calculate_column_widths(available_width);
         
+        // The sorted column is marked with the direction of the sort
+        let sort = if self.largest.is_some() {
+            SortCriteria::Size(SortOrder::Descending)
+        } else {
+            self.sort_criteria()
+        };
+        let label = |name: &str, sorted: bool| {
+            match (sorted, sort.get_sort_order()) {
+                (false, _) => name.to_string(),
+                (true, SortOrder::Ascending) => format!("{} ▲", name),
+                (true, SortOrder::Descending) => format!("{} ▼", name),
+            }
+        };
+        let subject_label = label("Subject", matches!(sort, SortCriteria::Subject(_)));
+        let correspondents_label = label("Correspondents", matches!(sort, SortCriteria::Sender(_)));
+        let date_label = label("Date", matches!(sort, SortCriteria::Date(_)));
+        let size_label = label("Size", matches!(sort, SortCriteria::Size(_)));
+
         // Create header text with proper spacing
         let mut header_spans = vec![
             Span::raw(" "), // Small left padding
             Span::styled(
-                "Subject".to_string(),
+                subject_label.clone(),
                 Style::default()
                     .fg(theme.colors.palette.text_secondary)
                     .add_modifier(Modifier::BOLD)
@@ -363,7 +502,7 @@ impl MessageList {
         ];
         
         // Add padding to correspondents column
-        let subject_len = "Subject".len();
+        let subject_len = display_width(&subject_label);
         if subject_len < subject_width {
             header_spans.push(Span::raw(" ".repeat(subject_width - subject_len)));
         }
@@ -371,14 +510,14 @@ impl MessageList {
         // Correspondents column
         header_spans.push(Span::raw(" "));
         header_spans.push(Span::styled(
-            "Correspondents".to_string(),
+            correspondents_label.clone(),
             Style::default()
                 .fg(theme.colors.palette.text_secondary)
                 .add_modifier(Modifier::BOLD)
         ));
         
         // Add padding to date column
-        let correspondents_len = "Correspondents".len();
+        let correspondents_len = display_width(&correspondents_label);
         if correspondents_len < correspondents_width {
             header_spans.push(Span::raw(" ".repeat(correspondents_width - correspondents_len)));
         }
@@ -386,22 +525,40 @@ impl MessageList {
         // Date column
         header_spans.push(Span::raw(" "));
         header_spans.push(Span::styled(
-            "Date".to_string(),
+            date_label.clone(),
             Style::default()
                 .fg(theme.colors.palette.text_secondary)
                 .add_modifier(Modifier::BOLD)
         ));
         
-        // Add padding to between column
-        let date_len = "Date".len();
+        // Add padding to size column
+        let date_len = display_width(&date_label);
         if date_len < date_width {
             header_spans.push(Span::raw(" ".repeat(date_width - date_len)));
         }
+
+        // Size column, right-aligned like the values under it
+        header_spans.push(Span::raw(" "));
+        header_spans.push(Span::styled(
+            format!("{:>width$}", size_label, width = size_width),
+            Style::default()
+                .fg(theme.colors.palette.text_secondary)
+                .add_modifier(Modifier::BOLD)
+        ));
         
         // Between column
         header_spans.push(Span::raw(" "));
         header_spans.push(Span::styled(
-            "Between".to_string(),
+            if self.all_inboxes {
+                "Account"
+            } else if self.snoozed {
+                "Until"
+            } else if self.unified || self.largest.is_some() {
+                "Folders"
+            } else {
+                "Between"
+            }
+            .to_string(),
             Style::default()
                 .fg(theme.colors.palette.text_secondary)
                 .add_modifier(Modifier::BOLD)
@@ -419,34 +576,49 @@ impl MessageList {
     }
     
     /// Calculate optimal column widths based on available space
-    fn calculate_column_widths(&self, available_width: usize) -> (usize, usize, usize, usize) {
+    fn calculate_column_widths(&self, available_width: usize) -> ColumnWidths {
         // Based on your screenshot proportions:
-        // Subject: ~40%, Correspondents: ~30%, Date: ~15%, Between: ~15%
+        // Subject: ~40%, Correspondents: ~30%, Date: ~15%, Size: fixed, Between: the rest
         let subject_width = (available_width * 40) / 100;
         let correspondents_width = (available_width * 30) / 100;
         let date_width = (available_width * 15) / 100;
-        let between_width = available_width.saturating_sub(subject_width + correspondents_width + date_width);
+        let between_width = available_width
+            .saturating_sub(subject_width + correspondents_width + date_width + SIZE_COLUMN_WIDTH + 1);
         
-        (subject_width.max(20), correspondents_width.max(15), date_width.max(8), between_width.max(10))
+        ColumnWidths {
+            subject: subject_width.max(20),
+            correspondents: correspondents_width.max(15),
+            date: date_width.max(8),
+            size: SIZE_COLUMN_WIDTH,
+            between: between_width.max(10),
+        }
     }
     
     /// Render a single message row in table format
-    fn render_message_row(
-        &self,
-        message: &MessageItem,
-        is_selected: bool,
-        is_focused: bool,
-        theme: &Theme,
-        subject_width: usize,
-        correspondents_width: usize,
-        date_width: usize,
-        between_width: usize,
-    ) -> ListItem {
+    fn message_row_line(&self, message: &MessageItem, row: &RowContext) -> Line<'static> {
+        let RowContext {
+            is_selected,
+            is_focused,
+            theme,
+            widths,
+        } = *row;
+        let ColumnWidths {
+            subject: subject_width,
+            correspondents: correspondents_width,
+            date: date_width,
+            size: size_width,
+            between: between_width,
+        } = widths;
+
         // Threading visualization
         let threading_prefix = self.get_threading_prefix(message);
+        let thread_badge = self.collapsed_thread_badge(message);
         
         // Status indicators (unread dot, importance, attachments)
         let mut status_icons = String::new();
+        if message.message_id.is_some_and(|id| self.marked_messages.contains(&id)) {
+            status_icons.push('✓'); // Marked for a batch operation
+        }
         if !message.is_read {
             status_icons.push('●'); // Unread indicator
         } else {
@@ -458,30 +630,37 @@ impl MessageList {
         if message.has_attachments {
             status_icons.push('📎'); // Attachment icon
         }
+        if let Some(badge) = message.security {
+            status_icons.push(badge.icon()); // Signed/encrypted/authentication status
+        }
         
-        // Format subject with threading and truncation
-        let subject_available = subject_width.saturating_sub(threading_prefix.len() + status_icons.len() + 2);
-        let subject_text = if message.subject.len() > subject_available {
-            format!("{}...", &message.subject[..subject_available.saturating_sub(3)])
-        } else {
-            message.subject.clone()
+        // High-priority mail gets a red "!", low-priority a muted arrow
+        let priority_marker = match message.priority {
+            MailPriority::High => "!",
+            MailPriority::Low => "↓",
+            MailPriority::Normal => "",
         };
+
+        // Format subject with threading and truncation; widths are in
+        // terminal cells so emoji and CJK text keep the columns aligned
+        let prefix_width = display_width(&threading_prefix)
+            + display_width(&status_icons)
+            + display_width(priority_marker)
+            + 1;
+        let subject_available =
+            subject_width.saturating_sub(prefix_width + display_width(&thread_badge) + 1);
+        let subject_text = truncate_to_width(&message.subject, subject_available);
         
         // Format correspondents (sender with contact info)
         let (sender_display, sender_indicators) = self.format_sender_with_contact_info(message);
-        let correspondents_text = if sender_display.len() > correspondents_width.saturating_sub(2) {
-            format!("{}...", &sender_display[..correspondents_width.saturating_sub(5)])
-        } else {
-            sender_display
-        };
+        let correspondents_text = truncate_to_width(
+            &sender_display,
+            correspondents_width.saturating_sub(display_width(&sender_indicators) + 2),
+        );
         
         // Format date to fit column
-        let date_text = if message.date.len() > date_width {
-            if message.date.len() > 8 {
-                message.date[..8].to_string()
-            } else {
-                message.date.clone()
-            }
+        let date_text = if display_width(&message.date) > date_width {
+            take_width(&message.date, 8).to_string()
         } else {
             message.date.clone()
         };
@@ -490,19 +669,18 @@ impl MessageList {
         let current_user = self.current_account.as_ref()
             .and_then(|account| account.split('@').next())
             .unwrap_or("Me");
-        let between_text = format!("{} and {}", 
-            if correspondents_text.len() > 10 {
-                &correspondents_text[..10]
-            } else {
-                &correspondents_text
-            }, 
-            current_user
-        );
-        let between_truncated = if between_text.len() > between_width {
-            format!("{}...", &between_text[..between_width.saturating_sub(3)])
+        // Collapsed copies show where the message is instead
+        let between_text = if self.unified
+            || self.all_inboxes
+            || self.snoozed
+            || self.largest.is_some()
+            || message.locations.len() > 1
+        {
+            message.locations.join(", ")
         } else {
-            between_text
+            format!("{} and {}", take_width(&correspondents_text, 10), current_user)
         };
+        let between_truncated = truncate_to_width(&between_text, between_width);
         
         // Determine styles based on message state and selection
         let base_style = if is_selected && is_focused {
@@ -529,6 +707,9 @@ impl MessageList {
         
         let correspondents_style = if is_selected {
             base_style
+        } else if message.security == Some(SecurityBadge::Suspicious) {
+            // Unauthenticated mail from a spoofable domain
+            base_style.fg(theme.colors.palette.warning)
         } else {
             base_style.fg(theme.colors.message_list.sender)
         };
@@ -544,20 +725,38 @@ impl MessageList {
         } else {
             base_style.fg(theme.colors.palette.text_muted)
         };
+
+        let priority_style = match message.priority {
+            _ if is_selected => base_style.add_modifier(Modifier::BOLD),
+            MailPriority::High => base_style
+                .fg(theme.colors.palette.error)
+                .add_modifier(Modifier::BOLD),
+            _ => base_style.fg(theme.colors.palette.text_muted),
+        };
         
         // Build the line with proper spacing and alignment
+        let current_len =
+            prefix_width + display_width(&subject_text) + display_width(&thread_badge);
+        let badge_style = if is_selected {
+            base_style.add_modifier(Modifier::BOLD)
+        } else {
+            base_style
+                .fg(theme.colors.message_list.subject_unread)
+                .add_modifier(Modifier::BOLD)
+        };
         let mut spans = vec![
             // Status indicators and threading
             Span::raw(threading_prefix.clone()),
             Span::styled(status_icons.clone(), subject_style),
+            Span::styled(priority_marker, priority_style),
             Span::raw(" "),
             
             // Subject column
             Span::styled(subject_text, subject_style),
+            Span::styled(thread_badge, badge_style),
         ];
         
         // Add padding to reach correspondents column
-        let current_len = threading_prefix.len() + status_icons.len() + 1 + message.subject.len().min(subject_available);
         if current_len < subject_width {
             spans.push(Span::raw(" ".repeat(subject_width - current_len)));
         }
@@ -568,26 +767,37 @@ impl MessageList {
         spans.push(Span::styled(correspondents_text.clone(), correspondents_style));
         
         // Add padding to reach date column
-        let correspondents_len = sender_indicators.len() + correspondents_text.len();
+        let correspondents_len = display_width(&sender_indicators) + display_width(&correspondents_text);
         if correspondents_len < correspondents_width {
             spans.push(Span::raw(" ".repeat(correspondents_width - correspondents_len)));
         }
         
         // Date column
         spans.push(Span::raw(" "));
+        let date_len = display_width(&date_text);
         spans.push(Span::styled(date_text, date_style));
         
-        // Add padding to reach between column
-        let date_len = message.date.len().min(date_width);
+        // Add padding to reach size column
         if date_len < date_width {
             spans.push(Span::raw(" ".repeat(date_width - date_len)));
         }
+
+        // Size column
+        let size_text = message
+            .size
+            .map(|size| format_size(size as usize))
+            .unwrap_or_default();
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(
+            format!("{:>width$}", size_text, width = size_width),
+            date_style,
+        ));
         
         // Between column
         spans.push(Span::raw(" "));
         spans.push(Span::styled(between_truncated, between_style));
         
-        ListItem::new(Line::from(spans))
+        Line::from(spans)
     }
 
     /// Handle up arrow key press - move selection up with wraparound
@@ -668,6 +878,60 @@ impl MessageList {
         self.state.selected().and_then(|i| messages_to_check.get(i))
     }
 
+    /// Toggle the batch mark on the selected message
+    ///
+    /// Returns the number of marked messages afterwards.
+    pub fn toggle_mark_selected(&mut self) -> usize {
+        if let Some(id) = self.selected_message().and_then(|message| message.message_id) {
+            if !self.marked_messages.remove(&id) {
+                self.marked_messages.insert(id);
+            }
+        }
+        self.marked_messages.len()
+    }
+
+    /// Clear all batch marks
+    pub fn clear_marks(&mut self) {
+        self.marked_messages.clear();
+    }
+
+    /// Whether any message is marked for a batch operation
+    pub fn has_marks(&self) -> bool {
+        !self.marked_messages.is_empty()
+    }
+
+    /// Messages a batch operation applies to: the marked messages in list order,
+    /// or the selected message when nothing is marked
+    pub fn action_targets(&self) -> Vec<Uuid> {
+        if self.marked_messages.is_empty() {
+            return self
+                .selected_message()
+                .and_then(|message| message.message_id)
+                .into_iter()
+                .collect();
+        }
+
+        let targets = self
+            .messages
+            .iter()
+            .filter_map(|message| message.message_id)
+            .filter(|id| self.marked_messages.contains(id));
+
+        // Batch actions run against one account, the selected message's
+        if self.all_inboxes {
+            let account = self.current_account();
+            let accounts: HashMap<Uuid, &String> = self
+                .cached_stored_messages()
+                .iter()
+                .map(|stored| (stored.id, &stored.account_id))
+                .collect();
+            return targets
+                .filter(|id| accounts.get(id).copied() == account)
+                .collect();
+        }
+        targets.collect()
+    }
+
     /// Mark the currently selected message as read
     pub fn mark_selected_as_read(&mut self) {
         if let Some(selected) = self.state.selected() {
@@ -687,15 +951,85 @@ impl MessageList {
     }
 
     /// Get the current account ID
+    ///
+    /// In All Inboxes this is the account of the selected message.
     pub fn current_account(&self) -> Option<&String> {
+        if self.all_inboxes {
+            if let Some(stored) = self.selected_stored_message() {
+                return Some(&stored.account_id);
+            }
+        }
         self.current_account.as_ref()
     }
 
     /// Get the current folder name
+    ///
+    /// In the unified view, All Inboxes and Snoozed this is the folder of the
+    /// selected message, so actions on it go to the right mailbox.
     pub fn current_folder(&self) -> Option<&String> {
+        if self.unified || self.all_inboxes || self.snoozed {
+            return self.selected_stored_message().map(|stored| &stored.folder_name);
+        }
         self.current_folder.as_ref()
     }
 
+    /// Whether new mail in `folder_name` of `account_id` belongs in the list
+    pub fn shows_folder(&self, account_id: &str, folder_name: &str) -> bool {
+        if self.all_inboxes {
+            return folder_name.eq_ignore_ascii_case("INBOX");
+        }
+        self.current_account.as_deref() == Some(account_id)
+            && self.current_folder.as_deref() == Some(folder_name)
+    }
+
+    /// Whether the list shows the INBOX of every account
+    pub fn is_all_inboxes(&self) -> bool {
+        self.all_inboxes
+    }
+
+    /// Whether the list shows the snoozed messages of the account
+    pub fn is_snoozed_view(&self) -> bool {
+        self.snoozed
+    }
+
+    /// Split the INBOX into tabs filled by AI triage
+    pub fn set_inbox_tabs_enabled(&mut self, enabled: bool) {
+        self.inbox_tabs = enabled;
+    }
+
+    /// Whether the list shows one tab of an account's INBOX
+    pub fn shows_inbox_tabs(&self) -> bool {
+        self.inbox_tabs
+            && !self.unified
+            && !self.all_inboxes
+            && !self.snoozed
+            && self.largest.is_none()
+            && self
+                .current_folder
+                .as_deref()
+                .is_some_and(|folder| folder.eq_ignore_ascii_case("INBOX"))
+    }
+
+    /// INBOX tab shown when the INBOX is split into tabs
+    pub fn inbox_tab(&self) -> InboxTab {
+        self.inbox_tab
+    }
+
+    /// Show another INBOX tab; takes effect when the messages are reloaded
+    pub fn set_inbox_tab(&mut self, tab: InboxTab) {
+        if tab != self.inbox_tab {
+            self.inbox_tab = tab;
+            self.state.select(None);
+        }
+    }
+
+    fn selected_stored_message(&self) -> Option<&StoredMessage> {
+        let message_id = self.selected_message()?.message_id?;
+        self.cached_stored_messages()
+            .iter()
+            .find(|stored| stored.id == message_id)
+    }
+
     // Threading and view mode methods
 
     /// Toggle between list and threaded view modes
@@ -728,6 +1062,33 @@ impl MessageList {
         }
     }
 
+    /// The sort the list is ordered by
+    pub fn sort_criteria(&self) -> SortCriteria {
+        self.sorter
+            .primary()
+            .cloned()
+            .unwrap_or(SortCriteria::Date(SortOrder::Descending))
+    }
+
+    /// Sort by the next field: date, sender, subject, size, priority
+    pub fn cycle_sort_field(&mut self) {
+        self.set_sort_criteria(self.sort_criteria().next_field());
+    }
+
+    /// Reverse the order of the current sort field
+    pub fn toggle_sort_order(&mut self) {
+        self.set_sort_criteria(self.sort_criteria().reversed());
+    }
+
+    /// The account and folder whose sort the user is choosing, for saving it;
+    /// `None` in views that are not a single folder
+    pub fn sort_folder(&self) -> Option<(String, String)> {
+        if self.unified || self.all_inboxes || self.snoozed || self.largest.is_some() {
+            return None;
+        }
+        Some((self.current_account.clone()?, self.current_folder.clone()?))
+    }
+
     pub fn set_sort_criteria(&mut self, criteria: SortCriteria) {
         self.sorter = MultiCriteriaSorter::new(vec![criteria]);
         self.rebuild_view();
@@ -752,6 +1113,11 @@ impl MessageList {
             if let Some(message) = self.messages.get_mut(selected) {
                 if message.is_thread_root && !message.is_thread_expanded {
                     message.is_thread_expanded = true;
+                    if let Some(thread_id) = message.thread_id.clone() {
+                        let folds = self.thread_folds_mut();
+                        folds.collapsed.remove(&thread_id);
+                        folds.expanded.insert(thread_id);
+                    }
                     self.rebuild_view();
                 }
             }
@@ -767,6 +1133,11 @@ impl MessageList {
             if let Some(message) = self.messages.get_mut(selected) {
                 if message.is_thread_root && message.is_thread_expanded {
                     message.is_thread_expanded = false;
+                    if let Some(thread_id) = message.thread_id.clone() {
+                        let folds = self.thread_folds_mut();
+                        folds.expanded.remove(&thread_id);
+                        folds.collapsed.insert(thread_id);
+                    }
                     self.rebuild_view();
                 }
             }
@@ -814,12 +1185,53 @@ impl MessageList {
                 "Using real messages for flat view, {} messages available",
                 self.messages.len()
             );
-            // Apply sorting to existing real messages
-            // Sort by date (newest first) as default
-            self.messages.sort_by(|a, b| b.date.cmp(&a.date));
+            self.sort_flat();
         }
     }
 
+    /// Order the flat list: biggest first in the largest-messages view, by
+    /// the chosen sort otherwise, newest first among equals
+    fn sort_flat(&mut self) {
+        let primary = if self.largest.is_some() {
+            SortCriteria::Size(SortOrder::Descending)
+        } else {
+            self.sort_criteria()
+        };
+
+        let directed = |ordering: std::cmp::Ordering, order: SortOrder| match order {
+            SortOrder::Ascending => ordering,
+            SortOrder::Descending => ordering.reverse(),
+        };
+        self.messages.sort_by(|a, b| {
+            let ordering = match &primary {
+                SortCriteria::Date(order) => directed(a.timestamp.cmp(&b.timestamp), *order),
+                SortCriteria::Sender(order) => {
+                    directed(a.sender.to_lowercase().cmp(&b.sender.to_lowercase()), *order)
+                }
+                SortCriteria::Subject(order) => directed(
+                    EmailThread::normalize_subject(&a.subject)
+                        .to_lowercase()
+                        .cmp(&EmailThread::normalize_subject(&b.subject).to_lowercase()),
+                    *order,
+                ),
+                // Unknown sizes last either way
+                SortCriteria::Size(SortOrder::Descending) => b.size.cmp(&a.size),
+                SortCriteria::Size(SortOrder::Ascending) => {
+                    a.size.is_none().cmp(&b.size.is_none()).then(a.size.cmp(&b.size))
+                }
+                SortCriteria::Priority(order) => directed(a.priority.cmp(&b.priority), *order),
+                _ => std::cmp::Ordering::Equal,
+            };
+            ordering.then_with(|| Self::newest_first(a, b))
+        });
+    }
+
+    fn newest_first(a: &MessageItem, b: &MessageItem) -> std::cmp::Ordering {
+        b.timestamp
+            .cmp(&a.timestamp)
+            .then_with(|| b.date.cmp(&a.date))
+    }
+
     fn build_threaded_view(&mut self) {
         // Only use sample threaded messages if we don't have real messages loaded
         if self.current_account.is_none() && self.current_folder.is_none() {
@@ -835,6 +1247,7 @@ impl MessageList {
             );
             // Apply threading algorithm to real messages
             self.apply_threading_to_real_messages();
+            self.apply_thread_collapse();
         }
     }
 
@@ -956,6 +1369,25 @@ impl MessageList {
         prefix
     }
 
+    /// Unread count shown after the subject of a collapsed thread
+    fn collapsed_thread_badge(&self, message: &MessageItem) -> String {
+        if self.view_mode != ViewMode::Threaded
+            || !message.is_thread_root
+            || message.is_thread_expanded
+            || message.message_count <= 1
+        {
+            return String::new();
+        }
+        match message
+            .thread_id
+            .as_deref()
+            .map(|thread_id| self.thread_unread_count(thread_id))
+        {
+            Some(unread) if unread > 0 => format!(" ({} unread)", unread),
+            _ => String::new(),
+        }
+    }
+
     /// Set the database for loading real messages
     pub fn set_database(&mut self, database: Arc<EmailDatabase>) {
         self.database = Some(database);
@@ -1012,11 +1444,7 @@ impl MessageList {
                 
                 // Add company info if available (abbreviated)
                 if let Some(company) = sender_info.company_info() {
-                    if company.len() > 10 {
-                        display_name = format!("{} ({}...)", display_name, &company[..7]);
-                    } else {
-                        display_name = format!("{} ({})", display_name, company);
-                    }
+                    display_name = format!("{} ({})", display_name, truncate_to_width(company, 10));
                 }
             }
             
@@ -1054,24 +1482,48 @@ impl MessageList {
             folder_name
         );
 
+        if folder_name == ALL_INBOXES {
+            return self.load_all_inboxes(account_id).await;
+        }
+        if folder_name == SNOOZED {
+            return self.load_snoozed(account_id).await;
+        }
+
         // Check if we're switching folders - if so, clear threading cache
         let folder_changed = self.current_account.as_ref() != Some(&account_id) 
             || self.current_folder.as_ref() != Some(&folder_name);
         
         if folder_changed {
             self.clear_threading_cache();
+            self.clear_marks();
         }
         
         if let Some(ref database) = self.database {
+            // Each folder comes back in the order last chosen for it
+            if folder_changed {
+                let sort = database
+                    .get_folder_sort(&account_id, &folder_name)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to load sort order of {}: {}", folder_name, e);
+                        None
+                    })
+                    .unwrap_or(SortCriteria::Date(SortOrder::Descending));
+                self.sorter = MultiCriteriaSorter::new(vec![sort]);
+            }
+
             self.current_account = Some(account_id.clone());
             self.current_folder = Some(folder_name.clone());
 
             // Load messages from database
             tracing::info!("Loading messages from database...");
             tracing::info!("Database query params: account_id='{}', folder_name='{}'", account_id, folder_name);
-            let stored_messages = database
+            let stored_messages: Vec<StoredMessage> = database
                 .get_messages(&account_id, &folder_name, Some(100), None)
-                .await?;
+                .await?
+                .into_iter()
+                .filter(|message| message.snoozed_until.is_none())
+                .collect();
             tracing::info!("Loaded {} messages from database", stored_messages.len());
             
             // Debug: Log first few message subjects if any
@@ -1092,63 +1544,417 @@ impl MessageList {
                 // This provides the user control over when to sync folders
             }
 
-            // Convert stored messages to MessageItems
-            self.messages = stored_messages
-                .into_iter()
-                .map(|msg| MessageItem::from_stored_message(&msg))
-                .collect();
+            // Security badges computed during sync
+            let security = database
+                .get_folder_message_security(&account_id, &folder_name)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to load message security status: {}", e);
+                    Default::default()
+                });
+
+            let messages = if self.dedup_config.folder_view {
+                dedup::dedupe(stored_messages, Some(&folder_name))
+            } else {
+                stored_messages
+                    .into_iter()
+                    .map(|message| DedupedMessage {
+                        message,
+                        locations: Vec::new(),
+                    })
+                    .collect()
+            };
+
+            self.unified = false;
+            self.all_inboxes = false;
+            self.snoozed = false;
+            self.largest = None;
+
+            // Only the chosen tab is listed; uncategorized mail is Primary
+            let messages = if self.shows_inbox_tabs() {
+                self.tab_unread = InboxTab::ALL.iter().map(|tab| (*tab, 0)).collect();
+                for deduped in &messages {
+                    if !deduped.message.flags.iter().any(|flag| flag == "\\Seen") {
+                        *self
+                            .tab_unread
+                            .entry(deduped.message.inbox_tab.unwrap_or_default())
+                            .or_default() += 1;
+                    }
+                }
+                messages
+                    .into_iter()
+                    .filter(|deduped| deduped.message.inbox_tab.unwrap_or_default() == self.inbox_tab)
+                    .collect()
+            } else {
+                messages
+            };
+            self.show_stored_messages(messages, &security).await;
+        } else {
+            tracing::error!("Database not available in MessageList");
+            return Err("Database not available".into());
+        }
 
-            tracing::info!("Converted to {} MessageItems", self.messages.len());
+        Ok(())
+    }
 
-            // Enrich messages with sender recognition
-            self.enrich_with_sender_recognition().await;
+    /// Show the newest messages of every folder of an account
+    ///
+    /// Trash, spam and drafts are left out. Copies of the same message in
+    /// several folders are collapsed unless `unified_view` dedup is off.
+    pub async fn load_unified_messages(
+        &mut self,
+        account_id: String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(database) = self.database.clone() else {
+            return Err("Database not available".into());
+        };
 
-            // Sort messages by date (newest first)
-            self.messages.sort_by(|a, b| b.date.cmp(&a.date));
+        if !self.unified || self.current_account.as_ref() != Some(&account_id) {
+            self.clear_threading_cache();
+            self.clear_marks();
+        }
+        self.current_account = Some(account_id.clone());
 
-            // Reset selection
-            if !self.messages.is_empty() {
-                self.state.select(Some(0));
-                tracing::info!(
-                    "Selected first message, total messages: {}",
-                    self.messages.len()
-                );
-            } else {
-                self.state.select(None);
-                tracing::warn!("No messages loaded, selection cleared");
+        let stored_messages: Vec<StoredMessage> = database
+            .get_account_messages(&account_id, Some(300))
+            .await?
+            .into_iter()
+            .filter(|message| !dedup::excluded_from_unified(&message.folder_name))
+            .collect();
+
+        let mut security = HashMap::new();
+        let folders: HashSet<&str> = stored_messages
+            .iter()
+            .map(|message| message.folder_name.as_str())
+            .collect();
+        for folder in folders {
+            match database.get_folder_message_security(&account_id, folder).await {
+                Ok(folder_security) => security.extend(folder_security),
+                Err(e) => tracing::warn!("Failed to load message security status: {}", e),
             }
+        }
+
+        let messages = if self.dedup_config.unified_view {
+            dedup::dedupe(stored_messages, None)
         } else {
-            tracing::error!("Database not available in MessageList");
+            stored_messages
+                .into_iter()
+                .map(|message| DedupedMessage {
+                    locations: vec![message.folder_name.clone()],
+                    message,
+                })
+                .collect()
+        };
+        tracing::info!("Unified view of {}: {} messages", account_id, messages.len());
+
+        self.unified = true;
+        self.all_inboxes = false;
+        self.snoozed = false;
+        self.largest = None;
+        self.show_stored_messages(messages, &security).await;
+        Ok(())
+    }
+
+    /// Show the INBOX messages of every account, newest first
+    ///
+    /// `account_id` is the account whose folders are shown next to the list.
+    /// Each message keeps its own account, listed in the Account column.
+    pub async fn load_all_inboxes(
+        &mut self,
+        account_id: String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(database) = self.database.clone() else {
             return Err("Database not available".into());
+        };
+
+        if !self.all_inboxes {
+            self.clear_threading_cache();
+            self.clear_marks();
         }
+        self.current_account = Some(account_id);
+        self.current_folder = Some(ALL_INBOXES.to_string());
 
-        // Schedule threading cache preloading in the background (non-blocking)
-        if folder_changed {
-            // Don't await this - let it run in background to avoid blocking the UI
-            let account_id_clone = account_id.clone();
-            let folder_name_clone = folder_name.clone();
-            if let Some(database_clone) = self.database.clone() {
-                tokio::spawn(async move {
-                    tracing::info!("Background: Preloading threading cache for {}:{}", account_id_clone, folder_name_clone);
-                    // This runs in background without blocking folder loading
-                    match database_clone.get_messages(&account_id_clone, &folder_name_clone, Some(1000), None).await {
-                        Ok(messages) => {
-                            tracing::info!("Background: Cached {} messages for threading", messages.len());
-                            // TODO: In a full implementation, we'd send the cached data back to the UI
-                        }
-                        Err(e) => {
-                            tracing::warn!("Background: Failed to preload threading cache: {}", e);
-                        }
-                    }
-                });
+        let stored_messages = database.get_all_inbox_messages(Some(300)).await?;
+
+        let mut security = HashMap::new();
+        let inboxes: HashSet<(&str, &str)> = stored_messages
+            .iter()
+            .map(|message| (message.account_id.as_str(), message.folder_name.as_str()))
+            .collect();
+        for (account, folder) in inboxes {
+            match database.get_folder_message_security(account, folder).await {
+                Ok(folder_security) => security.extend(folder_security),
+                Err(e) => tracing::warn!("Failed to load message security status: {}", e),
+            }
+        }
+
+        let messages: Vec<DedupedMessage> = stored_messages
+            .into_iter()
+            .map(|message| DedupedMessage {
+                locations: vec![message.account_id.clone()],
+                message,
+            })
+            .collect();
+        tracing::info!("All Inboxes: {} messages", messages.len());
+
+        self.unified = false;
+        self.all_inboxes = true;
+        self.snoozed = false;
+        self.largest = None;
+        self.show_stored_messages(messages, &security).await;
+        Ok(())
+    }
+
+    /// Show the snoozed messages of an account
+    ///
+    /// The Until column shows when each message returns to the INBOX.
+    pub async fn load_snoozed(
+        &mut self,
+        account_id: String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(database) = self.database.clone() else {
+            return Err("Database not available".into());
+        };
+
+        if !self.snoozed || self.current_account.as_ref() != Some(&account_id) {
+            self.clear_threading_cache();
+            self.clear_marks();
+        }
+        self.current_account = Some(account_id.clone());
+        self.current_folder = Some(SNOOZED.to_string());
+
+        let stored_messages = database.get_snoozed_messages(&account_id).await?;
+
+        let mut security = HashMap::new();
+        let folders: HashSet<&str> = stored_messages
+            .iter()
+            .map(|message| message.folder_name.as_str())
+            .collect();
+        for folder in folders {
+            match database.get_folder_message_security(&account_id, folder).await {
+                Ok(folder_security) => security.extend(folder_security),
+                Err(e) => tracing::warn!("Failed to load message security status: {}", e),
             }
         }
 
+        let messages: Vec<DedupedMessage> = stored_messages
+            .into_iter()
+            .map(|message| DedupedMessage {
+                locations: message
+                    .snoozed_until
+                    .map(|until| until.with_timezone(&Local).format("%a %-d %b %H:%M").to_string())
+                    .into_iter()
+                    .collect(),
+                message,
+            })
+            .collect();
+
+        self.unified = false;
+        self.all_inboxes = false;
+        self.snoozed = true;
+        self.largest = None;
+        self.show_stored_messages(messages, &security).await;
         Ok(())
     }
 
+    /// Show the biggest messages of a folder, or of the whole account when
+    /// `folder_name` is `None`, to find what takes up space
+    pub async fn load_largest_messages(
+        &mut self,
+        account_id: String,
+        folder_name: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(database) = self.database.clone() else {
+            return Err("Database not available".into());
+        };
+
+        if self.largest.as_ref() != Some(&folder_name)
+            || self.current_account.as_ref() != Some(&account_id)
+        {
+            self.clear_threading_cache();
+            self.clear_marks();
+        }
+        self.current_account = Some(account_id.clone());
+
+        let stored_messages = database
+            .get_largest_messages(&account_id, folder_name.as_deref(), LARGEST_MESSAGES_LIMIT)
+            .await?;
+        tracing::info!(
+            "Largest messages of {}/{}: {}",
+            account_id,
+            folder_name.as_deref().unwrap_or("*"),
+            stored_messages.len()
+        );
+
+        let mut security = HashMap::new();
+        let folders: HashSet<&str> = stored_messages
+            .iter()
+            .map(|message| message.folder_name.as_str())
+            .collect();
+        for folder in folders {
+            match database.get_folder_message_security(&account_id, folder).await {
+                Ok(folder_security) => security.extend(folder_security),
+                Err(e) => tracing::warn!("Failed to load message security status: {}", e),
+            }
+        }
+
+        let messages = stored_messages
+            .into_iter()
+            .map(|message| DedupedMessage {
+                locations: vec![message.folder_name.clone()],
+                message,
+            })
+            .collect();
+
+        self.unified = false;
+        self.all_inboxes = false;
+        self.snoozed = false;
+        self.largest = Some(folder_name);
+        self.show_stored_messages(messages, &security).await;
+        Ok(())
+    }
+
+    /// Switch between the largest messages of `folder_name` (or the whole
+    /// account) and the folder shown before
+    pub async fn toggle_largest(
+        &mut self,
+        folder_name: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(account_id) = self.current_account.clone() else {
+            return Err("No account selected".into());
+        };
+        if self.largest.as_ref() == Some(&folder_name) {
+            let folder = self.current_folder.clone().unwrap_or_else(|| "INBOX".to_string());
+            self.load_messages(account_id, folder).await
+        } else {
+            self.load_largest_messages(account_id, folder_name).await
+        }
+    }
+
+    /// Folder scope of the largest-messages view: `Some(None)` for the whole
+    /// account, `None` when the view isn't shown
+    pub fn largest_scope(&self) -> Option<Option<&str>> {
+        self.largest.as_ref().map(|folder| folder.as_deref())
+    }
+
+    /// Switch between the unified view and the folder shown before it
+    pub async fn toggle_unified(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(account_id) = self.current_account.clone() else {
+            return Err("No account selected".into());
+        };
+        if self.unified {
+            let folder = self.current_folder.clone().unwrap_or_else(|| "INBOX".to_string());
+            self.load_messages(account_id, folder).await
+        } else {
+            self.load_unified_messages(account_id).await
+        }
+    }
+
+    /// Whether the list shows every folder of the account
+    pub fn is_unified(&self) -> bool {
+        self.unified
+    }
+
+    /// Set where copies of the same message are collapsed
+    pub fn set_dedup_config(&mut self, config: DedupConfig) {
+        self.dedup_config = config;
+    }
+
+    /// Threading cache key of what the list shows
+    fn cache_key(&self) -> Option<String> {
+        if self.all_inboxes {
+            return Some(ALL_INBOXES.to_string());
+        }
+        let account_id = self.current_account.as_ref()?;
+        if self.snoozed {
+            return Some(format!("{}:{}", account_id, SNOOZED));
+        }
+        if let Some(folder) = &self.largest {
+            Some(format!("{}:largest:{}", account_id, folder.as_deref().unwrap_or("*")))
+        } else if self.unified {
+            Some(format!("{}:*", account_id))
+        } else {
+            Some(format!("{}:{}", account_id, self.current_folder.as_ref()?))
+        }
+    }
+
+    /// Replace the list with `messages`, newest first
+    async fn show_stored_messages(
+        &mut self,
+        messages: Vec<DedupedMessage>,
+        security: &HashMap<Uuid, MessageSecurity>,
+    ) {
+        let (stored_messages, locations): (Vec<StoredMessage>, Vec<Vec<String>>) = messages
+            .into_iter()
+            .map(|deduped| (deduped.message, deduped.locations))
+            .unzip();
+
+        // The threaded view is built from the same messages
+        if let Some(cache_key) = self.cache_key() {
+            self.threading_cache.insert(cache_key.clone(), stored_messages.clone());
+            self.threading_cache_key = Some(cache_key);
+        }
+
+        // Convert stored messages to MessageItems
+        self.messages = stored_messages
+            .into_iter()
+            .zip(locations)
+            .map(|(msg, locations)| {
+                let mut item = MessageItem::from_stored_message(&msg);
+                item.security = security.get(&msg.id).and_then(|s| s.badge());
+                item.locations = locations;
+                item
+            })
+            .collect();
+
+        tracing::info!("Converted to {} MessageItems", self.messages.len());
+
+        // Enrich messages with sender recognition
+        self.enrich_with_sender_recognition().await;
+
+        self.sort_flat();
+
+        if self.view_mode == ViewMode::Threaded {
+            self.apply_threading_to_real_messages();
+            self.apply_thread_collapse();
+        }
+
+        // Reset selection
+        if !self.messages.is_empty() {
+            self.state.select(Some(0));
+            tracing::info!(
+                "Selected first message, total messages: {}",
+                self.messages.len()
+            );
+        } else {
+            self.state.select(None);
+            tracing::warn!("No messages loaded, selection cleared");
+        }
+    }
+
     /// Refresh current folder's messages
     pub async fn refresh_messages(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if let (Some(folder), Some(account)) = (self.largest.clone(), self.current_account.clone()) {
+            return self.load_largest_messages(account, folder).await;
+        }
+        if self.unified {
+            if let Some(account) = self.current_account.clone() {
+                self.load_unified_messages(account).await?;
+            }
+            return Ok(());
+        }
+        if self.all_inboxes {
+            if let Some(account) = self.current_account.clone() {
+                self.load_all_inboxes(account).await?;
+            }
+            return Ok(());
+        }
+        if self.snoozed {
+            if let Some(account) = self.current_account.clone() {
+                self.load_snoozed(account).await?;
+            }
+            return Ok(());
+        }
         if let (Some(account), Some(folder)) =
             (self.current_account.clone(), self.current_folder.clone())
         {
@@ -1190,6 +1996,20 @@ impl MessageList {
         }
     }
 
+    /// IDs of the messages directly above and below the selection
+    pub fn adjacent_message_ids(&self) -> Vec<Uuid> {
+        let Some(selected_index) = self.state.selected() else {
+            return Vec::new();
+        };
+
+        [selected_index.checked_sub(1), selected_index.checked_add(1)]
+            .into_iter()
+            .flatten()
+            .filter_map(|index| self.messages.get(index))
+            .filter_map(|message| message.message_id)
+            .collect()
+    }
+
     /// Get the current selection state
     pub fn get_selection_state(&self) -> Option<usize> {
         self.state.selected()
@@ -1200,6 +2020,7 @@ impl MessageList {
     /// Start search mode
     pub fn start_search(&mut self) {
         self.search_active = true;
+        self.server_search = false;
         self.search_query.clear();
         self.filtered_messages.clear();
         self.search_results_count = 0;
@@ -1209,6 +2030,7 @@ impl MessageList {
     /// End search mode and return to normal view
     pub fn end_search(&mut self) {
         self.search_active = false;
+        self.server_search = false;
         self.search_query.clear();
         self.filtered_messages.clear();
         self.search_results_count = 0;
@@ -1223,9 +2045,29 @@ impl MessageList {
     /// Update search query and filter messages
     pub fn update_search(&mut self, query: String) {
         self.search_query = query.to_lowercase();
+        self.server_search = false;
         self.filter_messages();
     }
 
+    /// Account, folder and query for searching the shown folder on the server
+    pub fn server_search_target(&self) -> Option<(String, String, String)> {
+        let query = self.search_query.trim();
+        if !self.search_active || query.len() < 2 {
+            return None;
+        }
+        let (account_id, folder) = self.sort_folder()?;
+        Some((account_id, folder, query.to_string()))
+    }
+
+    /// List the results of a server search in place of the local matches
+    pub fn show_server_results(&mut self, messages: &[StoredMessage]) {
+        self.filtered_messages = messages.iter().map(MessageItem::from_stored_message).collect();
+        self.search_results_count = self.filtered_messages.len();
+        self.server_search = true;
+        self.state
+            .select((!self.filtered_messages.is_empty()).then_some(0));
+    }
+
     /// Get current search query
     pub fn search_query(&self) -> &str {
         &self.search_query
@@ -1294,6 +2136,11 @@ impl MessageList {
         if self.search_active {
             if self.search_query.is_empty() {
                 "Search: (type to search)".to_string()
+            } else if self.server_search {
+                format!(
+                    "Server search: {} ({} results)",
+                    self.search_query, self.search_results_count
+                )
             } else {
                 format!(
                     "Search: {} ({} results)",
@@ -1323,6 +2170,25 @@ impl MessageList {
         }
     }
 
+    /// Select the message with the given database ID, returning whether it was found
+    pub fn select_message_by_id(&mut self, message_id: Uuid) -> bool {
+        let messages_to_check = if self.search_active {
+            &self.filtered_messages
+        } else {
+            &self.messages
+        };
+
+        if let Some(index) = messages_to_check
+            .iter()
+            .position(|message| message.message_id == Some(message_id))
+        {
+            self.state.select(Some(index));
+            true
+        } else {
+            false
+        }
+    }
+
     /// Clear the threading cache (call when switching folders)
     pub fn clear_threading_cache(&mut self) {
         self.threading_cache.clear();
@@ -1332,6 +2198,10 @@ impl MessageList {
 
     /// Preload threading data into cache (call this asynchronously when folder changes)
     pub async fn preload_threading_cache(&mut self) {
+        // The unified view, All Inboxes and Snoozed cache exactly what they show
+        if self.unified || self.all_inboxes || self.snoozed {
+            return;
+        }
         if let Some(ref database) = self.database {
             if let (Some(ref account_id), Some(ref folder_name)) =
                 (self.current_account.as_ref(), self.current_folder.as_ref())
@@ -1343,7 +2213,8 @@ impl MessageList {
                     tracing::info!("Preloading threading cache for {}", cache_key);
                     
                     match database.get_messages(account_id, folder_name, Some(1000), None).await {
-                        Ok(stored_messages) => {
+                        Ok(mut stored_messages) => {
+                            stored_messages.retain(|message| message.snoozed_until.is_none());
                             tracing::info!("Cached {} messages for threading", stored_messages.len());
                             self.threading_cache.insert(cache_key.clone(), stored_messages);
                             self.threading_cache_key = Some(cache_key);
@@ -1365,10 +2236,7 @@ impl MessageList {
         );
 
         // Step 1: Use cached stored messages for threading (non-blocking)
-        if let (Some(ref account_id), Some(ref folder_name)) =
-            (self.current_account.as_ref(), self.current_folder.as_ref())
-        {
-            let cache_key = format!("{}:{}", account_id, folder_name);
+        if let Some(cache_key) = self.cache_key() {
             
             // Try to get stored messages from cache first
             if let Some(stored_messages) = self.threading_cache.get(&cache_key) {
@@ -1389,34 +2257,258 @@ impl MessageList {
                 );
 
                 // Apply threading algorithm using the ThreadingEngine
-                let threads = self.threading_engine.thread_messages(email_messages);
+                let mut threads = self.threading_engine.thread_messages(email_messages);
                 tracing::info!("Threading algorithm produced {} threads", threads.len());
 
+                // Sort threads by the chosen date, sender or subject order, newest first
+                // otherwise, keeping replies under their root
+                let primary = self.sort_criteria();
+                threads.sort_by(|a, b| {
+                    let ordering = match primary {
+                        SortCriteria::Date(_) | SortCriteria::Sender(_) | SortCriteria::Subject(_) => {
+                            primary.compare_threads(a, b)
+                        }
+                        _ => std::cmp::Ordering::Equal,
+                    };
+                    ordering.then_with(|| b.latest_timestamp().cmp(&a.latest_timestamp()))
+                });
+
+                // Keep badges and contact info computed for the flat list
+                let previous: HashMap<Uuid, MessageItem> = self
+                    .messages
+                    .drain(..)
+                    .filter_map(|item| item.message_id.map(|id| (id, item)))
+                    .collect();
+
                 // Convert threads back to MessageItems for display
-                self.messages = Self::threads_to_message_items(threads, &stored_messages);
+                self.messages = Self::threads_to_message_items(threads, stored_messages);
+                for item in &mut self.messages {
+                    if let Some(old) = item.message_id.and_then(|id| previous.get(&id)) {
+                        item.security = old.security;
+                        item.sender_info = old.sender_info.clone();
+                        item.locations = old.locations.clone();
+                    }
+                }
                 tracing::info!(
                     "Converted threads to {} MessageItems for display",
                     self.messages.len()
                 );
 
-                // Sort threads by latest message date (newest first)
-                self.messages.sort_by(|a, b| b.date.cmp(&a.date));
+                self.thread_members.clear();
+                for item in &self.messages {
+                    if let (Some(thread_id), Some(message_id)) = (&item.thread_id, item.message_id) {
+                        self.thread_members
+                            .entry(thread_id.clone())
+                            .or_default()
+                            .push(message_id);
+                    }
+                }
             } else {
                 tracing::warn!("Threading cache not available for {}. Threading disabled until cache is populated.", cache_key);
                 // Fall back to flat view without threading
-                self.messages.sort_by(|a, b| b.date.cmp(&a.date));
+                self.messages.sort_by(Self::newest_first);
+                self.thread_members.clear();
+            }
+        }
+    }
+
+    /// Hide the replies of collapsed threads
+    fn apply_thread_collapse(&mut self) {
+        let collapsed: HashSet<String> = self
+            .thread_members
+            .keys()
+            .filter(|thread_id| self.is_thread_collapsed(thread_id))
+            .cloned()
+            .collect();
+
+        for message in &mut self.messages {
+            if message.is_thread_root {
+                message.is_thread_expanded = message
+                    .thread_id
+                    .as_ref()
+                    .is_some_and(|thread_id| !collapsed.contains(thread_id));
+            }
+        }
+        self.messages.retain(|message| {
+            message.thread_depth == 0
+                || message
+                    .thread_id
+                    .as_ref()
+                    .is_none_or(|thread_id| !collapsed.contains(thread_id))
+        });
+
+        if let Some(selected) = self.state.selected() {
+            if selected >= self.messages.len() {
+                self.state
+                    .select(self.messages.len().checked_sub(1));
             }
         }
     }
 
+    fn is_thread_collapsed(&self, thread_id: &str) -> bool {
+        if let Some(folds) = self.thread_folds.get(&self.thread_folds_key()) {
+            if folds.expanded.contains(thread_id) {
+                return false;
+            }
+            if folds.collapsed.contains(thread_id) {
+                return true;
+            }
+        }
+        self.thread_config.collapse_read_threads && self.thread_unread_count(thread_id) == 0
+    }
+
+    /// Key of the current folder's fold state
+    fn thread_folds_key(&self) -> String {
+        self.cache_key().unwrap_or_default()
+    }
+
+    fn thread_folds_mut(&mut self) -> &mut ThreadFolds {
+        let key = self.thread_folds_key();
+        self.thread_folds.entry(key).or_default()
+    }
+
+    /// Unread messages of a thread, using the threading cache so hidden replies count
+    fn thread_unread_count(&self, thread_id: &str) -> usize {
+        let Some(members) = self.thread_members.get(thread_id) else {
+            return 0;
+        };
+        self.cached_stored_messages()
+            .iter()
+            .filter(|stored| members.contains(&stored.id))
+            .filter(|stored| !stored.flags.iter().any(|flag| flag == "\\Seen"))
+            .count()
+    }
+
+    /// Whole conversation a message belongs to, whatever the view mode
+    pub fn thread_containing(&self, message_id: Uuid) -> Option<EmailThread> {
+        let stored_messages = self.cached_stored_messages();
+        let target = stored_messages.iter().find(|stored| stored.id == message_id)?;
+        let target_id = Self::threading_message_id(target);
+
+        let email_messages: Vec<EmailMessage> = stored_messages
+            .iter()
+            .filter_map(Self::stored_message_to_email_message)
+            .collect();
+        ThreadingEngine::new(*self.threading_engine.algorithm())
+            .thread_messages(email_messages)
+            .into_iter()
+            .find(|thread| thread.find_message(&target_id).is_some())
+    }
+
+    /// Stored messages of the current folder held for threading
+    fn cached_stored_messages(&self) -> &[StoredMessage] {
+        self.threading_cache_key
+            .as_ref()
+            .and_then(|key| self.threading_cache.get(key))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Apply the threaded view settings
+    pub fn set_thread_behavior(&mut self, config: ThreadBehaviorConfig) {
+        if self.thread_config != config {
+            self.thread_config = config;
+            if self.view_mode == ViewMode::Threaded {
+                self.rebuild_view();
+            }
+        }
+    }
+
+    pub fn thread_behavior(&self) -> &ThreadBehaviorConfig {
+        &self.thread_config
+    }
+
+    /// Thread of a message in the threaded view
+    pub fn thread_of(&self, message_id: Uuid) -> Option<&str> {
+        if self.view_mode != ViewMode::Threaded {
+            return None;
+        }
+        self.thread_members
+            .iter()
+            .find(|(_, members)| members.contains(&message_id))
+            .map(|(thread_id, _)| thread_id.as_str())
+    }
+
+    /// Thread of the selected message in the threaded view
+    pub fn selected_thread_id(&self) -> Option<String> {
+        let message_id = self.selected_message()?.message_id?;
+        self.thread_of(message_id).map(str::to_string)
+    }
+
+    /// All messages of a thread, including collapsed replies
+    pub fn thread_message_ids(&self, thread_id: &str) -> Vec<Uuid> {
+        self.thread_members.get(thread_id).cloned().unwrap_or_default()
+    }
+
+    /// Whether a message is the newest one of its thread
+    pub fn is_latest_in_thread(&self, message_id: Uuid) -> bool {
+        let Some(members) = self
+            .thread_of(message_id)
+            .and_then(|thread_id| self.thread_members.get(thread_id))
+        else {
+            return false;
+        };
+        self.cached_stored_messages()
+            .iter()
+            .filter(|stored| members.contains(&stored.id))
+            .max_by_key(|stored| stored.date)
+            .is_some_and(|latest| latest.id == message_id)
+    }
+
+    /// Record a whole thread as read or unread after the server was updated
+    ///
+    /// Read threads fold away when `collapse_read_threads` is on.
+    pub fn set_thread_read(&mut self, thread_id: &str, read: bool) {
+        let members = self.thread_message_ids(thread_id);
+        for message in &mut self.messages {
+            if message.message_id.is_some_and(|id| members.contains(&id)) {
+                message.is_read = read;
+            }
+        }
+        if let Some(key) = self.threading_cache_key.clone() {
+            if let Some(stored_messages) = self.threading_cache.get_mut(&key) {
+                for stored in stored_messages.iter_mut().filter(|stored| members.contains(&stored.id)) {
+                    stored.flags.retain(|flag| flag != "\\Seen");
+                    if read {
+                        stored.flags.push("\\Seen".to_string());
+                    }
+                }
+            }
+        }
+
+        if read && self.thread_config.collapse_read_threads {
+            self.thread_folds_mut().expanded.remove(thread_id);
+        }
+        if self.view_mode == ViewMode::Threaded {
+            let selected = self.selected_message().and_then(|message| message.message_id);
+            self.rebuild_view();
+            self.select_message_id(selected);
+        }
+    }
+
+    /// Keep the cursor on a message after the view was rebuilt, or on its thread root if hidden
+    fn select_message_id(&mut self, message_id: Option<Uuid>) {
+        let Some(message_id) = message_id else {
+            return;
+        };
+        let thread_id = self.thread_of(message_id).map(str::to_string);
+        let position = self
+            .messages
+            .iter()
+            .position(|message| message.message_id == Some(message_id))
+            .or_else(|| {
+                self.messages.iter().position(|message| {
+                    message.is_thread_root && message.thread_id.is_some() && message.thread_id == thread_id
+                })
+            });
+        if let Some(position) = position {
+            self.state.select(Some(position));
+        }
+    }
+
     /// Convert StoredMessage to EmailMessage for threading
     fn stored_message_to_email_message(stored: &StoredMessage) -> Option<EmailMessage> {
-        // Create MessageId from stored message_id
-        let message_id = stored
-            .message_id
-            .as_ref()
-            .and_then(|id| MessageId::parse(id).ok())
-            .unwrap_or_else(|| MessageId::new(format!("local-{}", stored.id)));
+        let message_id = Self::threading_message_id(stored);
 
         // Determine sender name/address
         let sender = if let Some(ref name) = stored.from_name {
@@ -1467,6 +2559,15 @@ impl MessageList {
         Some(email_message)
     }
 
+    /// Message-ID as the threading engine sees it, without angle brackets
+    fn threading_message_id(stored: &StoredMessage) -> MessageId {
+        stored
+            .message_id
+            .as_ref()
+            .and_then(|id| MessageId::parse(id).ok())
+            .unwrap_or_else(|| MessageId::new(format!("local-{}", stored.id)))
+    }
+
     /// Convert threads back to MessageItems for display
     fn threads_to_message_items(
         threads: Vec<EmailThread>,
@@ -1477,11 +2578,11 @@ impl MessageList {
         // Create a lookup map for stored messages by message ID
         let stored_lookup: std::collections::HashMap<String, &StoredMessage> = stored_messages
             .iter()
-            .filter_map(|stored| stored.message_id.as_ref().map(|id| (id.clone(), stored)))
+            .map(|stored| (Self::threading_message_id(stored).as_str().to_string(), stored))
             .collect();
 
         for thread in threads {
-            Self::add_thread_to_message_items(&thread, &mut message_items, &stored_lookup, 0, true);
+            Self::add_thread_to_message_items(&thread, &mut message_items, &stored_lookup, 0, None);
         }
 
         message_items
@@ -1493,8 +2594,9 @@ impl MessageList {
         items: &mut Vec<MessageItem>,
         stored_lookup: &std::collections::HashMap<String, &StoredMessage>,
         depth: usize,
-        is_root: bool,
+        root_thread_id: Option<&str>,
     ) {
+        let is_root = root_thread_id.is_none();
         let root_message = thread.root_message();
         let message_id_str = root_message.message_id().as_str();
 
@@ -1507,10 +2609,14 @@ impl MessageList {
                 stored.from_addr.clone()
             };
 
-            let thread_id = stored
-                .thread_id
-                .clone()
-                .unwrap_or_else(|| format!("thread-{}", stored.id));
+            // Replies share their root's key so the whole thread can be addressed at once
+            let thread_id = match root_thread_id {
+                Some(root_thread_id) => root_thread_id.to_string(),
+                None => stored
+                    .thread_id
+                    .clone()
+                    .unwrap_or_else(|| format!("thread-{}", stored.id)),
+            };
 
             let mut message_item = if depth == 0 {
                 // Root message
@@ -1555,17 +2661,15 @@ impl MessageList {
 
             items.push(message_item);
 
-            // Add children if thread is expanded (or if we're showing all for now)
-            if is_root && thread.has_children() {
-                for child_thread in thread.children() {
-                    Self::add_thread_to_message_items(
-                        child_thread,
-                        items,
-                        stored_lookup,
-                        depth + 1,
-                        false,
-                    );
-                }
+            // Add replies at every depth; collapsed threads are hidden afterwards
+            for child_thread in thread.children() {
+                Self::add_thread_to_message_items(
+                    child_thread,
+                    items,
+                    stored_lookup,
+                    depth + 1,
+                    Some(&thread_id),
+                );
             }
         }
     }
@@ -1585,9 +2689,12 @@ impl MessageItem {
                 stored.from_addr.clone()
             },
             date: date_str,
+            timestamp: Some(stored.date),
             is_read: stored.flags.contains(&"\\Seen".to_string()),
             is_important: stored.flags.contains(&"\\Flagged".to_string()),
             has_attachments: !stored.attachments.is_empty(),
+            size: stored.size,
+            priority: stored.message_priority(),
             thread_depth: 0,
             thread_id: stored.thread_id.clone(),
             message_count: 1,
@@ -1595,6 +2702,8 @@ impl MessageItem {
             is_thread_root: false,
             message_id: Some(stored.id),
             sender_info: None,
+            security: None,
+            locations: Vec::new(),
         }
     }
 
@@ -1627,3 +2736,303 @@ impl Default for MessageList {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn stored(message_id: &str, in_reply_to: Option<&str>, hours_ago: i64, seen: bool) -> StoredMessage {
+        let now = Utc::now();
+        StoredMessage {
+            id: Uuid::new_v4(),
+            account_id: "work".to_string(),
+            folder_name: "INBOX".to_string(),
+            imap_uid: 1,
+            message_id: Some(format!("<{}>", message_id)),
+            thread_id: None,
+            in_reply_to: in_reply_to.map(|id| format!("<{}>", id)),
+            references: in_reply_to.map(|id| vec![format!("<{}>", id)]).unwrap_or_default(),
+            subject: "Plans".to_string(),
+            from_addr: "alice@example.com".to_string(),
+            from_name: None,
+            to_addrs: Vec::new(),
+            cc_addrs: Vec::new(),
+            bcc_addrs: Vec::new(),
+            reply_to: None,
+            date: now - Duration::hours(hours_ago),
+            body_text: None,
+            body_html: None,
+            attachments: Vec::new(),
+            flags: if seen { vec!["\\Seen".to_string()] } else { Vec::new() },
+            labels: Vec::new(),
+            size: None,
+            priority: None,
+            created_at: now,
+            updated_at: now,
+            last_synced: now,
+            sync_version: 1,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        }
+    }
+
+    #[test]
+    fn test_thread_read_and_collapse() {
+        let root = stored("root@example.com", None, 3, true);
+        let reply = stored("reply@example.com", Some("root@example.com"), 1, false);
+        let (root_id, reply_id) = (root.id, reply.id);
+
+        let mut list = MessageList::new();
+        list.current_account = Some("work".to_string());
+        list.current_folder = Some("INBOX".to_string());
+        list.threading_cache
+            .insert("work:INBOX".to_string(), vec![root, reply]);
+        list.threading_cache_key = Some("work:INBOX".to_string());
+        list.set_thread_behavior(ThreadBehaviorConfig {
+            mark_thread_read_on_latest: true,
+            collapse_read_threads: true,
+        });
+        assert_eq!(list.thread_containing(root_id).unwrap().message_count(), 2);
+        list.set_view_mode(ViewMode::Threaded);
+
+        // The unread reply keeps the thread open
+        assert_eq!(list.messages().len(), 2);
+        let thread_id = list.thread_of(reply_id).unwrap().to_string();
+        assert_eq!(list.thread_of(root_id), Some(thread_id.as_str()));
+        assert!(list.is_latest_in_thread(reply_id));
+        assert!(!list.is_latest_in_thread(root_id));
+
+        list.set_thread_read(&thread_id, true);
+        assert_eq!(list.messages().len(), 1);
+        assert!(!list.messages()[0].is_thread_expanded);
+        assert_eq!(list.thread_message_ids(&thread_id).len(), 2);
+
+        // Unfolding by hand wins over collapse_read_threads
+        list.state.select(Some(0));
+        list.expand_selected_thread();
+        assert_eq!(list.messages().len(), 2);
+    }
+
+    #[test]
+    fn test_thread_folds_kept_per_folder() {
+        let mut list = MessageList::new();
+        list.current_account = Some("work".to_string());
+        for folder in ["INBOX", "Archive"] {
+            let root = stored("root@example.com", None, 3, false);
+            let reply = stored("reply@example.com", Some("root@example.com"), 1, false);
+            list.threading_cache
+                .insert(format!("work:{}", folder), vec![root, reply]);
+        }
+        let open_folder = |list: &mut MessageList, folder: &str| {
+            list.current_folder = Some(folder.to_string());
+            list.threading_cache_key = Some(format!("work:{}", folder));
+            list.set_view_mode(ViewMode::Threaded);
+            list.state.select(Some(0));
+        };
+
+        open_folder(&mut list, "INBOX");
+        assert_eq!(list.messages().len(), 2);
+        list.collapse_selected_thread();
+        assert_eq!(list.messages().len(), 1);
+
+        // The collapsed thread shows how many of its messages are unread
+        let row = RowContext {
+            is_selected: false,
+            is_focused: false,
+            theme: &Theme::default(),
+            widths: list.calculate_column_widths(120),
+        };
+        let line = list.message_row_line(&list.messages()[0], &row);
+        assert!(line.spans.iter().any(|span| span.content == " (2 unread)"));
+
+        // Another folder has its own fold state, and INBOX's survives the switch
+        list.set_view_mode(ViewMode::List);
+        open_folder(&mut list, "Archive");
+        assert_eq!(list.messages().len(), 2);
+        list.set_view_mode(ViewMode::List);
+        open_folder(&mut list, "INBOX");
+        assert_eq!(list.messages().len(), 1);
+    }
+
+    #[test]
+    fn test_all_inboxes_act_on_message_account() {
+        let work = stored("work@example.com", None, 2, false);
+        let mut home = stored("home@example.com", None, 1, true);
+        home.account_id = "home".to_string();
+        home.folder_name = "Inbox".to_string();
+
+        let mut list = MessageList::new();
+        list.current_account = Some("work".to_string());
+        list.current_folder = Some(ALL_INBOXES.to_string());
+        list.all_inboxes = true;
+        list.threading_cache
+            .insert(ALL_INBOXES.to_string(), vec![work.clone(), home.clone()]);
+        list.threading_cache_key = Some(ALL_INBOXES.to_string());
+        list.messages = [&home, &work].into_iter().map(MessageItem::from_stored_message).collect();
+
+        // Actions go to the account and folder of the selected message
+        list.state.select(Some(0));
+        assert_eq!(list.current_account().map(String::as_str), Some("home"));
+        assert_eq!(list.current_folder().map(String::as_str), Some("Inbox"));
+
+        // New mail in any INBOX shows up, other folders don't
+        assert!(list.shows_folder("home", "INBOX"));
+        assert!(list.shows_folder("work", "Inbox"));
+        assert!(!list.shows_folder("work", "Archive"));
+
+        // Batch actions keep to the selected message's account
+        list.toggle_mark_selected();
+        list.state.select(Some(1));
+        list.toggle_mark_selected();
+        assert_eq!(list.action_targets(), vec![work.id]);
+    }
+
+    #[test]
+    fn test_sort_by_size() {
+        let mut list = MessageList::new();
+        list.current_account = Some("work".to_string());
+        list.current_folder = Some("INBOX".to_string());
+        list.messages = [Some(2048), None, Some(5 * 1024 * 1024)]
+            .into_iter()
+            .map(|size| {
+                let mut message = MessageItem::new("Photos".to_string(), "Bob".to_string(), "Mon 09:15".to_string());
+                message.size = size;
+                message
+            })
+            .collect();
+
+        list.set_sort_criteria(SortCriteria::Size(SortOrder::Descending));
+        let sizes: Vec<Option<u32>> = list.messages().iter().map(|message| message.size).collect();
+        assert_eq!(sizes, vec![Some(5 * 1024 * 1024), Some(2048), None]);
+
+        let row = RowContext {
+            is_selected: false,
+            is_focused: false,
+            theme: &Theme::default(),
+            widths: list.calculate_column_widths(120),
+        };
+        let line = list.message_row_line(&list.messages()[0], &row);
+        let text: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+        assert!(text.contains("  5.0 MB"), "size missing: {}", text);
+    }
+
+    #[test]
+    fn test_sort_by_priority() {
+        let mut list = MessageList::new();
+        list.current_account = Some("work".to_string());
+        list.current_folder = Some("INBOX".to_string());
+        list.messages = [
+            (MailPriority::Normal, "Tue 10:00"),
+            (MailPriority::High, "Mon 09:00"),
+            (MailPriority::Low, "Wed 11:00"),
+            (MailPriority::High, "Thu 12:00"),
+        ]
+        .into_iter()
+        .map(|(priority, date)| {
+            let mut message = MessageItem::new("Outage".to_string(), "Ops".to_string(), date.to_string());
+            message.priority = priority;
+            message
+        })
+        .collect();
+
+        list.set_sort_criteria(SortCriteria::Priority(SortOrder::Descending));
+        let order: Vec<(MailPriority, &str)> = list
+            .messages()
+            .iter()
+            .map(|message| (message.priority, message.date.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (MailPriority::High, "Thu 12:00"),
+                (MailPriority::High, "Mon 09:00"),
+                (MailPriority::Normal, "Tue 10:00"),
+                (MailPriority::Low, "Wed 11:00"),
+            ]
+        );
+
+        let row = RowContext {
+            is_selected: false,
+            is_focused: false,
+            theme: &Theme::default(),
+            widths: list.calculate_column_widths(120),
+        };
+        let line = list.message_row_line(&list.messages()[0], &row);
+        assert!(line.spans.iter().any(|span| span.content == "!"));
+    }
+
+    #[test]
+    fn test_cycle_and_reverse_sort() {
+        let mut list = MessageList::new();
+        list.current_account = Some("work".to_string());
+        list.current_folder = Some("INBOX".to_string());
+        let now = Utc::now();
+        list.messages = [("bob", "Budget", 2), ("Alice", "Re: Agenda", 1), ("carol", "Cake", 3)]
+            .into_iter()
+            .map(|(sender, subject, days_ago)| {
+                let mut message = MessageItem::new(subject.to_string(), sender.to_string(), String::new());
+                message.timestamp = Some(now - Duration::days(days_ago));
+                message
+            })
+            .collect();
+        let senders = |list: &MessageList| -> Vec<String> {
+            list.messages().iter().map(|message| message.sender.clone()).collect()
+        };
+
+        // A new folder starts newest first
+        assert_eq!(list.sort_criteria(), SortCriteria::Date(SortOrder::Descending));
+        list.toggle_sort_order();
+        assert_eq!(senders(&list), vec!["carol", "bob", "Alice"]);
+
+        list.cycle_sort_field();
+        assert_eq!(list.sort_criteria(), SortCriteria::Sender(SortOrder::Ascending));
+        assert_eq!(senders(&list), vec!["Alice", "bob", "carol"]);
+        list.toggle_sort_order();
+        assert_eq!(senders(&list), vec!["carol", "bob", "Alice"]);
+
+        // Reply prefixes are ignored when sorting by subject
+        list.cycle_sort_field();
+        assert_eq!(senders(&list), vec!["Alice", "bob", "carol"]);
+
+        assert_eq!(list.sort_folder(), Some(("work".to_string(), "INBOX".to_string())));
+        list.unified = true;
+        assert_eq!(list.sort_folder(), None);
+    }
+
+    #[test]
+    fn test_row_columns_align_with_wide_text() {
+        let list = MessageList::new();
+        let row = RowContext {
+            is_selected: false,
+            is_focused: false,
+            theme: &Theme::default(),
+            widths: list.calculate_column_widths(120),
+        };
+
+        let rows = [
+            ("Weekly report", "Alice Smith"),
+            ("🎉🎉 Launch day 🚀 Everyone is invited to the party on the roof 🍾🥂", "Bob"),
+            ("会議の議事録と来週のスケジュールについてのご案内とお願い", "山田太郎"),
+            ("Re: Café ☕ at 👍🏽 o'clock", "张伟 (北京办公室的市场部门负责人)"),
+        ];
+
+        for (subject, sender) in rows {
+            let mut message = MessageItem::new(subject.to_string(), sender.to_string(), "Mon 09:15".to_string());
+            message.has_attachments = true;
+            let line = list.message_row_line(&message, &row);
+            let text: String = line.spans.iter().map(|span| span.content.as_ref()).collect();
+
+            // The date column starts at the same cell on every row
+            let date_at = text.find("Mon 09:15").unwrap();
+            assert_eq!(
+                display_width(&text[..date_at]),
+                row.widths.subject + row.widths.correspondents + 2,
+                "misaligned row: {}",
+                text
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod account_details;
 pub mod account_inspector;
 pub mod account_switcher;
 pub mod ai_assistant_ui;
@@ -7,10 +8,15 @@ pub mod ai_popup;
 pub mod ai_privacy_dialog;
 pub mod animated_content;
 pub mod animation;
+pub mod browser_view;
 pub mod calendar;
 pub mod compose;
+pub mod compose_attachments;
+pub mod compose_wrap;
+pub mod confirm_dialog;
 pub mod content_preview;
 pub mod external_editor;
+pub mod file_picker;
 pub mod context_calendar;
 pub mod context_menu;
 pub mod context_shortcuts;
@@ -19,23 +25,44 @@ pub mod progressive_disclosure;
 pub mod date_picker;
 pub mod draft_list;
 pub mod email_viewer;
+pub mod filter_rules_view;
 pub mod folder_tree;
 pub mod fuzzy_search;
 pub mod graphics;
+pub mod headers_panel;
 pub mod help;
 pub mod integrated_layout;
 pub mod invitation_viewer;
 pub mod keyboard_shortcuts;
 pub mod layout;
+pub mod links_panel;
+pub mod lock_screen;
+pub mod mbox_wizard;
 pub mod message_list;
+pub mod mailcap;
+pub mod opener;
+pub mod passphrase_prompt;
+pub mod preview_cache;
+pub mod preview_limits;
+pub mod quick_reply;
+pub mod quote_fold;
+pub mod reflow;
+pub mod retention_view;
+pub mod schedule_picker;
 pub mod search;
+pub mod session_state;
 pub mod settings_ui;
+pub mod snooze_picker;
+pub mod special_folders_view;
 pub mod startup_progress;
 pub mod status_bar;
 pub mod sync_progress;
+pub mod text_width;
+pub mod thread_behavior;
 pub mod enhanced_progress_overlay;
 pub mod time_picker;
 pub mod toast;
+pub mod today_panel;
 pub mod toast_integration_simple;
 pub mod typography;
 pub mod unified_sidebar;
@@ -70,8 +97,8 @@ use self::{
     layout::AppLayout,
     message_list::MessageList,
     status_bar::{
-        CalendarStatusSegment, EmailStatusSegment, NavigationHintsSegment, StatusBar, SyncStatus,
-        SystemInfoSegment,
+        CalendarStatusSegment, CredentialsStatusSegment, EmailStatusSegment,
+        NavigationHintsSegment, StatusBar, SyncStatus, SystemInfoSegment,
     },
     sync_progress::SyncProgressOverlay,
     toast::ToastManager,
@@ -105,6 +132,9 @@ pub use search::{SearchAction, SearchEngine, SearchEngineType, SearchMode, Searc
 // Re-export settings types
 pub use settings_ui::{SettingsUI, SettingsUIState, SettingsTab};
 
+// Re-export session state types
+pub use session_state::{SessionConfig, SessionPane, SessionState, SessionView};
+
 // Re-export fuzzy search types
 pub use fuzzy_search::{FuzzySearchEngine, FuzzySearchConfig};
 
@@ -118,7 +148,9 @@ pub use context_menu::{ContextMenu, ContextMenuAction, ContextMenuItem, ContextT
 // (Types already imported above in use self::progressive_disclosure::...)
 
 // Re-export animation and graphics types
-pub use animated_content::{AnimatedContentManager, AnimatedEmailContent, AnimationControlWidget};
+pub use animated_content::{
+    AnimatedContentManager, AnimatedEmailContent, AnimationConfig, AnimationControlWidget,
+};
 pub use animation::{Animation, AnimationDecoder, AnimationFormat, AnimationManager, AnimationSettings};
 pub use graphics::{GraphicsProtocol, ImageRenderer, RenderConfig};
 
@@ -157,6 +189,16 @@ pub enum UIMode {
     KeyboardShortcuts,
     Settings,
     ContactsPopup, // Quick contacts popup overlay
+    AccountDetails, // Server capabilities and TLS for one account
+    SpecialFolders, // Sent/Drafts/Trash/Junk/Archive mapping for one account
+    Retention,      // Retention dry-run preview and pending removals
+    FilterRules,    // Filter rules, tried on the current folder before enabling
+    MboxWizard,     // Mbox import and export
+    Headers,        // Structured headers of the displayed message
+    Confirm,        // Yes/no confirmation before a bulk action
+    Links,          // Links of the displayed message
+    Snooze,         // Date and time a message is snoozed until
+    Sms,            // SMS conversations through KDE Connect
 }
 
 /// AI operation results for async communication
@@ -174,6 +216,22 @@ pub enum AIOperationResult {
         request_id: uuid::Uuid,
         result: Result<crate::email::EmailCompositionAssistance, String>,
     },
+    ThreadSummarized {
+        request_id: uuid::Uuid,
+        subject: String,
+        result: Result<crate::ai::ThreadSummary, String>,
+    },
+    CacheStats {
+        stats: crate::ai::CacheStatistics,
+    },
+    CacheCleared {
+        result: Result<usize, String>,
+    },
+    /// Next piece of an answer still being streamed
+    Streamed {
+        request_id: uuid::Uuid,
+        content: String,
+    },
 }
 
 pub struct UI {
@@ -188,13 +246,18 @@ pub struct UI {
     email_updater: Option<UIEmailUpdater>,
     sync_progress_overlay: SyncProgressOverlay,
     enhanced_progress_overlay: enhanced_progress_overlay::EnhancedProgressOverlay,
+    today_panel: today_panel::TodayPanel,
     mode: UIMode,
     compose_ui: Option<ComposeUI>,
+    /// Compose window of a sent message still inside its undo-send window
+    held_compose: Option<ComposeUI>,
     draft_list: DraftListUI,
     calendar_ui: CalendarUI,
     event_form_ui: Option<crate::calendar::EventFormUI>,
     email_viewer: EmailViewer,
     invitation_viewer: InvitationViewer,
+    /// Mode to go back to when the invitation viewer closes
+    invitation_return_mode: UIMode,
     search_ui: SearchUI,
     search_engine: Option<SearchEngine>,
     fuzzy_search_engine: Option<FuzzySearchEngine>,
@@ -222,6 +285,31 @@ pub struct UI {
     notification_expires_at: Option<tokio::time::Instant>,
     // Contacts popup
     contacts_popup: Option<crate::contacts::ContactPopup>,
+    // Shared by the message list and the viewer's sender details
+    sender_recognition: Option<Arc<crate::contacts::SenderRecognitionService>>,
+    account_details: Option<account_details::AccountDetailsView>,
+    special_folders_view: Option<special_folders_view::SpecialFoldersView>,
+    retention_view: Option<retention_view::RetentionView>,
+    filter_rules_view: Option<filter_rules_view::FilterRulesView>,
+    mbox_wizard: Option<mbox_wizard::MboxWizard>,
+    headers_panel: Option<headers_panel::HeadersPanel>,
+    confirm_dialog: Option<confirm_dialog::ConfirmDialog>,
+    links_panel: Option<links_panel::LinksPanel>,
+    snooze_picker: Option<snooze_picker::SnoozePicker>,
+    sms_screen: Option<Arc<crate::mobile::SmsUi>>,
+    // Sender and contact avatars, shared by the viewer and contacts popup
+    avatars: Option<crate::contacts::AvatarCache>,
+    // Leaves signatures out of reply quotes
+    signature_stripper: crate::email::SignatureStripper,
+    quote_config: crate::email::QuoteConfig,
+    compose_wrap_config: compose_wrap::ComposeWrapConfig,
+    // Inline completion for the compose body, with its typing pause
+    smart_compose: Option<(Arc<crate::ai::SmartComposeService>, std::time::Duration)>,
+    // Tone of the last AI reply draft kept, used for the next one
+    reply_tone: crate::ai::ReplyTone,
+    // Replaces the whole screen while the TUI is locked
+    lock_screen: Option<lock_screen::LockScreen>,
+    reply_config: crate::email::ReplyConfig,
     
     // AI Assistant components
     ai_assistant: crate::ui::ai_assistant_ui::AIAssistantUI,
@@ -232,6 +320,8 @@ pub struct UI {
     
     // AI operation result channel
     ai_result_rx: Option<tokio::sync::mpsc::UnboundedReceiver<AIOperationResult>>,
+    // Streamed popup operation in flight, stopped when the popup closes
+    ai_task: Option<(uuid::Uuid, tokio::task::JoinHandle<()>)>,
 }
 
 impl UI {
@@ -249,13 +339,16 @@ impl UI {
             email_updater: None,
             sync_progress_overlay: SyncProgressOverlay::new(),
             enhanced_progress_overlay: enhanced_progress_overlay::EnhancedProgressOverlay::new(),
+            today_panel: today_panel::TodayPanel::new(),
             mode: UIMode::Normal,
             compose_ui: None,
+            held_compose: None,
             draft_list: DraftListUI::new(),
             calendar_ui: CalendarUI::new(),
             event_form_ui: None,
             email_viewer: EmailViewer::new(),
             invitation_viewer: InvitationViewer::new(),
+            invitation_return_mode: UIMode::Normal,
             search_ui: SearchUI::new(),
             search_engine: None,
             fuzzy_search_engine: None,
@@ -283,6 +376,25 @@ impl UI {
             notification_expires_at: None,
             // Initialize contacts popup
             contacts_popup: None,
+            sender_recognition: None,
+            account_details: None,
+            special_folders_view: None,
+            retention_view: None,
+            filter_rules_view: None,
+            mbox_wizard: None,
+            headers_panel: None,
+            confirm_dialog: None,
+            links_panel: None,
+            snooze_picker: None,
+            sms_screen: None,
+            avatars: None,
+            signature_stripper: crate::email::SignatureStripper::default(),
+            quote_config: crate::email::QuoteConfig::default(),
+            compose_wrap_config: compose_wrap::ComposeWrapConfig::default(),
+            smart_compose: None,
+            reply_tone: crate::ai::ReplyTone::default(),
+            lock_screen: None,
+            reply_config: crate::email::ReplyConfig::default(),
             
             // Initialize AI components - will be properly initialized when AI service is set
             ai_assistant: {
@@ -311,6 +423,7 @@ impl UI {
             ai_config_state: crate::ui::ai_config_ui::AIConfigUIState::new(),
             ai_popup: crate::ui::ai_popup::AIPopup::new(),
             ai_result_rx: None, // Will be set when AI operations are started
+            ai_task: None,
         };
 
         // Initialize status bar with default segments
@@ -378,6 +491,11 @@ impl UI {
     ) {
         let size = frame.size();
 
+        if let Some(ref lock_screen) = self.lock_screen {
+            lock_screen.render(frame, size, self.theme_manager.current_theme());
+            return;
+        }
+
         match self.mode {
             UIMode::Normal => {
                 let chunks = self.layout.calculate_layout(size);
@@ -395,6 +513,7 @@ impl UI {
 
                 // Render progress overlays (enhanced takes priority over sync)
                 let theme = self.theme_manager.current_theme();
+                self.today_panel.render(frame, size, theme);
                 if self.enhanced_progress_overlay.is_visible() {
                     self.enhanced_progress_overlay.render(frame, size, theme);
                 } else if self.sync_progress_overlay.is_visible() {
@@ -518,6 +637,181 @@ impl UI {
                     contacts_popup.render(frame, size, theme);
                 }
             }
+            UIMode::AccountDetails => {
+                // Render account details over the normal interface
+                let chunks = self.layout.calculate_layout(size);
+
+                // Render normal interface in background
+                self.render_account_switcher(frame, chunks[0]);
+                self.render_folder_tree(frame, chunks[1]);
+                self.render_message_list(frame, chunks[2]);
+                self.render_content_preview(frame, chunks[3]);
+
+                // Render the status bar
+                if chunks.len() > 4 {
+                    self.render_status_bar(frame, chunks[4]);
+                }
+
+                if let Some(ref mut account_details) = self.account_details {
+                    let theme = self.theme_manager.current_theme();
+                    account_details.render(frame, size, theme);
+                }
+            }
+            UIMode::SpecialFolders => {
+                // Render the special folder setup over the normal interface
+                let chunks = self.layout.calculate_layout(size);
+
+                self.render_account_switcher(frame, chunks[0]);
+                self.render_folder_tree(frame, chunks[1]);
+                self.render_message_list(frame, chunks[2]);
+                self.render_content_preview(frame, chunks[3]);
+                if chunks.len() > 4 {
+                    self.render_status_bar(frame, chunks[4]);
+                }
+
+                if let Some(ref mut view) = self.special_folders_view {
+                    let theme = self.theme_manager.current_theme();
+                    view.render(frame, size, theme);
+                }
+            }
+            UIMode::Retention => {
+                // Render the retention popup over the normal interface
+                let chunks = self.layout.calculate_layout(size);
+
+                self.render_account_switcher(frame, chunks[0]);
+                self.render_folder_tree(frame, chunks[1]);
+                self.render_message_list(frame, chunks[2]);
+                self.render_content_preview(frame, chunks[3]);
+
+                if chunks.len() > 4 {
+                    self.render_status_bar(frame, chunks[4]);
+                }
+
+                if let Some(ref mut retention_view) = self.retention_view {
+                    let theme = self.theme_manager.current_theme();
+                    retention_view.render(frame, size, theme);
+                }
+            }
+            UIMode::FilterRules => {
+                // Render the filter rules popup over the normal interface
+                let chunks = self.layout.calculate_layout(size);
+
+                self.render_account_switcher(frame, chunks[0]);
+                self.render_folder_tree(frame, chunks[1]);
+                self.render_message_list(frame, chunks[2]);
+                self.render_content_preview(frame, chunks[3]);
+
+                if chunks.len() > 4 {
+                    self.render_status_bar(frame, chunks[4]);
+                }
+
+                if let Some(ref mut view) = self.filter_rules_view {
+                    let theme = self.theme_manager.current_theme();
+                    view.render(frame, size, theme);
+                }
+            }
+            UIMode::MboxWizard => {
+                // Render the mbox wizard over the normal interface
+                let chunks = self.layout.calculate_layout(size);
+
+                self.render_account_switcher(frame, chunks[0]);
+                self.render_folder_tree(frame, chunks[1]);
+                self.render_message_list(frame, chunks[2]);
+                self.render_content_preview(frame, chunks[3]);
+
+                if chunks.len() > 4 {
+                    self.render_status_bar(frame, chunks[4]);
+                }
+
+                if let Some(ref mut wizard) = self.mbox_wizard {
+                    let theme = self.theme_manager.current_theme();
+                    wizard.render(frame, size, theme);
+                }
+            }
+            UIMode::Headers => {
+                // Render the headers panel over the normal interface
+                let chunks = self.layout.calculate_layout(size);
+
+                self.render_account_switcher(frame, chunks[0]);
+                self.render_folder_tree(frame, chunks[1]);
+                self.render_message_list(frame, chunks[2]);
+                self.render_content_preview(frame, chunks[3]);
+
+                if chunks.len() > 4 {
+                    self.render_status_bar(frame, chunks[4]);
+                }
+
+                if let Some(ref mut headers_panel) = self.headers_panel {
+                    let theme = self.theme_manager.current_theme();
+                    headers_panel.render(frame, size, theme);
+                }
+            }
+            UIMode::Confirm => {
+                // Render the confirmation over the normal interface
+                let chunks = self.layout.calculate_layout(size);
+
+                self.render_account_switcher(frame, chunks[0]);
+                self.render_folder_tree(frame, chunks[1]);
+                self.render_message_list(frame, chunks[2]);
+                self.render_content_preview(frame, chunks[3]);
+
+                if chunks.len() > 4 {
+                    self.render_status_bar(frame, chunks[4]);
+                }
+
+                if let Some(ref confirm_dialog) = self.confirm_dialog {
+                    let theme = self.theme_manager.current_theme();
+                    confirm_dialog.render(frame, size, theme);
+                }
+            }
+            UIMode::Links => {
+                // Render the links panel over the normal interface
+                let chunks = self.layout.calculate_layout(size);
+
+                self.render_account_switcher(frame, chunks[0]);
+                self.render_folder_tree(frame, chunks[1]);
+                self.render_message_list(frame, chunks[2]);
+                self.render_content_preview(frame, chunks[3]);
+
+                if chunks.len() > 4 {
+                    self.render_status_bar(frame, chunks[4]);
+                }
+
+                if let Some(ref mut links_panel) = self.links_panel {
+                    let theme = self.theme_manager.current_theme();
+                    links_panel.render(frame, size, theme);
+                }
+            }
+            UIMode::Snooze => {
+                // Render the snooze picker over the normal interface
+                let chunks = self.layout.calculate_layout(size);
+
+                self.render_account_switcher(frame, chunks[0]);
+                self.render_folder_tree(frame, chunks[1]);
+                self.render_message_list(frame, chunks[2]);
+                self.render_content_preview(frame, chunks[3]);
+
+                if chunks.len() > 4 {
+                    self.render_status_bar(frame, chunks[4]);
+                }
+
+                if let Some(ref mut snooze_picker) = self.snooze_picker {
+                    let theme = self.theme_manager.current_theme();
+                    snooze_picker.render(frame, size, theme);
+                }
+            }
+            UIMode::Sms => {
+                // Render the SMS screen in full screen. Its state sits behind
+                // async locks that key handling releases before the next draw,
+                // so they are free here.
+                if let Some(sms_screen) = self.sms_screen.clone() {
+                    let config = crate::mobile::SmsRenderConfig::default();
+                    let render = sms_screen.render(frame, size, &config);
+                    if let Err(e) = futures::executor::block_on(tokio::task::unconstrained(render)) {
+                        tracing::warn!("Failed to render SMS screen: {}", e);
+                    }
+                }
+            }
         }
 
         // Render toast notifications on top of everything
@@ -590,7 +884,7 @@ impl UI {
 
         let border_style = theme.get_component_style("border", is_focused);
         let block = Block::default()
-            .title("Messages")
+            .title(self.message_list_title())
             .borders(Borders::ALL)
             .border_style(border_style);
 
@@ -684,13 +978,22 @@ impl UI {
             .render(frame, area, block, is_focused, theme);
     }
 
+    fn message_list_title(&self) -> String {
+        match self.message_list.largest_scope() {
+            Some(Some(folder)) => format!("Largest messages ({})", folder),
+            Some(None) => "Largest messages (all folders)".to_string(),
+            None if self.message_list.is_unified() => "Messages (all folders)".to_string(),
+            None => "Messages".to_string(),
+        }
+    }
+
     fn render_message_list(&self, frame: &mut Frame, area: Rect) {
         let is_focused = matches!(self.focused_pane, FocusedPane::MessageList);
         let theme = self.theme_manager.current_theme();
 
         let border_style = theme.get_component_style("border", is_focused);
         let block = Block::default()
-            .title("Messages")
+            .title(self.message_list_title())
             .borders(Borders::ALL)
             .border_style(border_style);
 
@@ -884,6 +1187,16 @@ impl UI {
             UIMode::KeyboardShortcuts => "Keyboard Shortcuts",
             UIMode::Settings => "Settings",
             UIMode::ContactsPopup => "Contacts",
+            UIMode::AccountDetails => "Account Details",
+            UIMode::SpecialFolders => "Special Folders",
+            UIMode::Retention => "Retention",
+            UIMode::FilterRules => "Filter Rules",
+            UIMode::MboxWizard => "Mbox",
+            UIMode::Headers => "Headers",
+            UIMode::Confirm => "Confirm",
+            UIMode::Links => "Links",
+            UIMode::Snooze => "Snooze",
+            UIMode::Sms => "SMS",
         };
 
         let nav_segment = NavigationHintsSegment {
@@ -1047,6 +1360,66 @@ impl UI {
                 ("Tab".to_string(), "Change Mode".to_string()),
                 ("Esc".to_string(), "Close".to_string()),
             ],
+            UIMode::AccountDetails => vec![
+                ("↑↓/j/k".to_string(), "Navigate".to_string()),
+                ("Space".to_string(), "Enable/Disable".to_string()),
+                ("Esc/q".to_string(), "Close".to_string()),
+            ],
+            UIMode::SpecialFolders => vec![
+                ("↑↓/j/k".to_string(), "Navigate".to_string()),
+                ("←→/h/l".to_string(), "Pick Folder".to_string()),
+                ("Enter".to_string(), "Use Detected".to_string()),
+                ("a".to_string(), "Use All Detected".to_string()),
+                ("x".to_string(), "Clear".to_string()),
+                ("Esc/q".to_string(), "Close".to_string()),
+            ],
+            UIMode::Retention => vec![
+                ("↑↓/j/k".to_string(), "Navigate".to_string()),
+                ("Enter".to_string(), "Accept Preview".to_string()),
+                ("u".to_string(), "Restore".to_string()),
+                ("Esc/q".to_string(), "Close".to_string()),
+            ],
+            UIMode::FilterRules => vec![
+                ("↑↓/j/k".to_string(), "Navigate".to_string()),
+                ("Enter".to_string(), "Test on Folder".to_string()),
+                ("Space".to_string(), "Enable/Disable".to_string()),
+                ("Esc/q".to_string(), "Close".to_string()),
+            ],
+            UIMode::MboxWizard => vec![
+                ("Tab".to_string(), "Import/Export or Account".to_string()),
+                ("Space".to_string(), "Toggle".to_string()),
+                ("e".to_string(), "Target Folder".to_string()),
+                ("Enter".to_string(), "Continue".to_string()),
+                ("Esc".to_string(), "Close/Cancel".to_string()),
+            ],
+            UIMode::Headers => vec![
+                ("↑↓/j/k".to_string(), "Navigate".to_string()),
+                ("Enter/y".to_string(), "Copy".to_string()),
+                ("Esc/q".to_string(), "Close".to_string()),
+            ],
+            UIMode::Confirm => vec![
+                ("y/Enter".to_string(), "Confirm".to_string()),
+                ("n/Esc".to_string(), "Cancel".to_string()),
+            ],
+            UIMode::Links => vec![
+                ("↑↓/j/k".to_string(), "Navigate".to_string()),
+                ("Enter".to_string(), "Open".to_string()),
+                ("y".to_string(), "Copy".to_string()),
+                ("Esc/q".to_string(), "Close".to_string()),
+            ],
+            UIMode::Snooze => vec![
+                ("hjkl/←→↑↓".to_string(), "Change".to_string()),
+                ("Tab".to_string(), "Date/Time".to_string()),
+                ("Enter".to_string(), "Snooze".to_string()),
+                ("Esc".to_string(), "Cancel".to_string()),
+            ],
+            UIMode::Sms => vec![
+                ("↑↓/j/k".to_string(), "Navigate".to_string()),
+                ("Enter".to_string(), "Open".to_string()),
+                ("r".to_string(), "Reply".to_string()),
+                ("Ctrl+S".to_string(), "Send".to_string()),
+                ("Esc/q".to_string(), "Back".to_string()),
+            ],
         }
     }
 
@@ -1060,6 +1433,14 @@ impl UI {
             .add_segment("email".to_string(), email_segment);
     }
 
+    /// Show "Refreshing credentials…" while any account's token is being refreshed
+    pub fn update_credentials_status(&mut self, refreshing_accounts: usize) {
+        self.status_bar.add_segment(
+            "credentials".to_string(),
+            CredentialsStatusSegment { refreshing_accounts },
+        );
+    }
+
     /// Update calendar status with real-time calendar information
     pub fn update_calendar_status(
         &mut self,
@@ -1144,8 +1525,9 @@ impl UI {
     /// Update calendar status with data from calendar UI
     fn refresh_calendar_status(&mut self) {
         // Get current date for today's events calculation
-        let today = chrono::Local::now().date_naive();
+        let timezone = self.calendar_ui.timezone();
         let now = chrono::Utc::now();
+        let today = now.with_timezone(&timezone).date_naive();
         
         // Get calendar events from calendar UI
         let events = self.calendar_ui.get_events();
@@ -1154,7 +1536,7 @@ impl UI {
         let events_today = events
             .iter()
             .filter(|event| {
-                let local_start = event.start_time.with_timezone(&chrono::Local);
+                let local_start = event.start_time.with_timezone(&timezone);
                 local_start.date_naive() == today
             })
             .count();
@@ -1202,7 +1584,13 @@ impl UI {
         let sender_recognition = Arc::new(SenderRecognitionService::new(contacts_manager));
         
         // Set it up in the message list
-        self.message_list.set_sender_recognition(sender_recognition);
+        self.message_list.set_sender_recognition(sender_recognition.clone());
+        self.sender_recognition = Some(sender_recognition);
+    }
+
+    /// Sender recognition, once a contacts manager is set
+    pub fn sender_recognition(&self) -> Option<Arc<crate::contacts::SenderRecognitionService>> {
+        self.sender_recognition.clone()
     }
 
     /// Set the notification manager for real-time updates
@@ -1222,6 +1610,30 @@ impl UI {
         Ok(())
     }
 
+    /// Switch the message list between the unified view and a single folder
+    pub async fn toggle_unified_view(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.message_list.toggle_unified().await?;
+
+        let message_count = self.message_list.messages().len();
+        let unread_count = self
+            .message_list
+            .messages()
+            .iter()
+            .filter(|msg| !msg.is_read)
+            .count();
+        self.update_email_status(unread_count, message_count, SyncStatus::Online);
+        Ok(())
+    }
+
+    /// Switch the message list between the largest messages of a folder (or
+    /// the whole account) and the folder shown before
+    pub async fn toggle_largest_messages(
+        &mut self,
+        folder_name: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.message_list.toggle_largest(folder_name).await
+    }
+
     /// Load messages for a specific account and folder
     pub async fn load_messages(
         &mut self,
@@ -1232,11 +1644,19 @@ impl UI {
             .load_messages(account_id.clone(), folder_name.clone())
             .await?;
 
-        // Subscribe to notifications for this folder
+        // Subscribe to notifications for this folder, or every INBOX for All Inboxes
         if let Some(ref mut updater) = self.email_updater {
-            updater.subscribe_to_folder(account_id, folder_name);
+            if folder_name == folder_tree::ALL_INBOXES {
+                for account in self.account_switcher.accounts() {
+                    updater.subscribe_to_folder(account.account_id.clone(), "INBOX".to_string());
+                }
+            } else {
+                updater.subscribe_to_folder(account_id, folder_name);
+            }
         }
 
+        self.refresh_all_inboxes_unread().await;
+
         // Update email status after loading
         let message_count = self.message_list.messages().len();
         let unread_count = self
@@ -1316,6 +1736,23 @@ impl UI {
             .update_account_status(account_id, status, unread_count);
     }
 
+    /// Recount the unread messages of All Inboxes after read states changed
+    pub async fn refresh_all_inboxes_unread(&mut self) {
+        if let Err(e) = self.folder_tree.refresh_all_inboxes_unread().await {
+            tracing::warn!("Failed to count unread messages in All Inboxes: {}", e);
+        }
+    }
+
+    /// Update the Snoozed folder of the shown account after messages were snoozed or woken
+    pub async fn refresh_snoozed_folder(&mut self) {
+        let Some(account_id) = self.account_switcher.get_current_account_id().cloned() else {
+            return;
+        };
+        if let Err(e) = self.folder_tree.refresh_snoozed(&account_id).await {
+            tracing::warn!("Failed to count snoozed messages: {}", e);
+        }
+    }
+
     /// Refresh current folder's messages
     pub async fn refresh_messages(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.message_list.refresh_messages().await
@@ -1336,6 +1773,10 @@ impl UI {
                     // Show error in preview if loading fails
                     self.content_preview.clear_message();
                 }
+
+                // Have the neighbours ready for j/k
+                let adjacent = self.message_list.adjacent_message_ids();
+                self.content_preview.prerender_messages(&adjacent);
             } else {
                 // No message ID available (probably sample data)
                 self.content_preview.clear_message();
@@ -1375,23 +1816,19 @@ impl UI {
                 message,
             } => {
                 // Check if this notification is for the currently displayed folder
-                if let (Some(current_account), Some(current_folder)) =
-                    self.message_list.get_current_context()
-                {
-                    if current_account == &account_id && current_folder == &folder_name {
-                        // Refresh the message list to show the new message
-                        let _ = self.message_list.refresh_messages().await;
-
-                        // Update status bar with new counts
-                        let message_count = self.message_list.messages().len();
-                        let unread_count = self
-                            .message_list
-                            .messages()
-                            .iter()
-                            .filter(|msg| !msg.is_read)
-                            .count();
-                        self.update_email_status(unread_count, message_count, SyncStatus::Online);
-                    }
+                if self.message_list.shows_folder(&account_id, &folder_name) {
+                    // Refresh the message list to show the new message
+                    let _ = self.message_list.refresh_messages().await;
+
+                    // Update status bar with new counts
+                    let message_count = self.message_list.messages().len();
+                    let unread_count = self
+                        .message_list
+                        .messages()
+                        .iter()
+                        .filter(|msg| !msg.is_read)
+                        .count();
+                    self.update_email_status(unread_count, message_count, SyncStatus::Online);
                 }
 
                 tracing::info!(
@@ -1408,23 +1845,19 @@ impl UI {
                 ..
             } => {
                 // Check if this notification is for the currently displayed folder
-                if let (Some(current_account), Some(current_folder)) =
-                    self.message_list.get_current_context()
-                {
-                    if current_account == &account_id && current_folder == &folder_name {
-                        // Refresh the message list to show updated message
-                        let _ = self.message_list.refresh_messages().await;
-
-                        // Update status bar
-                        let message_count = self.message_list.messages().len();
-                        let unread_count = self
-                            .message_list
-                            .messages()
-                            .iter()
-                            .filter(|msg| !msg.is_read)
-                            .count();
-                        self.update_email_status(unread_count, message_count, SyncStatus::Online);
-                    }
+                if self.message_list.shows_folder(&account_id, &folder_name) {
+                    // Refresh the message list to show updated message
+                    let _ = self.message_list.refresh_messages().await;
+
+                    // Update status bar
+                    let message_count = self.message_list.messages().len();
+                    let unread_count = self
+                        .message_list
+                        .messages()
+                        .iter()
+                        .filter(|msg| !msg.is_read)
+                        .count();
+                    self.update_email_status(unread_count, message_count, SyncStatus::Online);
                 }
 
                 tracing::info!("Message updated: {}", message.subject);
@@ -1436,23 +1869,19 @@ impl UI {
                 ..
             } => {
                 // Check if this notification is for the currently displayed folder
-                if let (Some(current_account), Some(current_folder)) =
-                    self.message_list.get_current_context()
-                {
-                    if current_account == &account_id && current_folder == &folder_name {
-                        // Refresh the message list to remove deleted message
-                        let _ = self.message_list.refresh_messages().await;
-
-                        // Update status bar
-                        let message_count = self.message_list.messages().len();
-                        let unread_count = self
-                            .message_list
-                            .messages()
-                            .iter()
-                            .filter(|msg| !msg.is_read)
-                            .count();
-                        self.update_email_status(unread_count, message_count, SyncStatus::Online);
-                    }
+                if self.message_list.shows_folder(&account_id, &folder_name) {
+                    // Refresh the message list to remove deleted message
+                    let _ = self.message_list.refresh_messages().await;
+
+                    // Update status bar
+                    let message_count = self.message_list.messages().len();
+                    let unread_count = self
+                        .message_list
+                        .messages()
+                        .iter()
+                        .filter(|msg| !msg.is_read)
+                        .count();
+                    self.update_email_status(unread_count, message_count, SyncStatus::Online);
                 }
 
                 tracing::info!("Message deleted from {}/{}", account_id, folder_name);
@@ -1463,19 +1892,15 @@ impl UI {
                 folder_name,
             } => {
                 // Update status bar to show sync in progress
-                if let (Some(current_account), Some(current_folder)) =
-                    self.message_list.get_current_context()
-                {
-                    if current_account == &account_id && current_folder == &folder_name {
-                        let message_count = self.message_list.messages().len();
-                        let unread_count = self
-                            .message_list
-                            .messages()
-                            .iter()
-                            .filter(|msg| !msg.is_read)
-                            .count();
-                        self.update_email_status(unread_count, message_count, SyncStatus::Syncing);
-                    }
+                if self.message_list.shows_folder(&account_id, &folder_name) {
+                    let message_count = self.message_list.messages().len();
+                    let unread_count = self
+                        .message_list
+                        .messages()
+                        .iter()
+                        .filter(|msg| !msg.is_read)
+                        .count();
+                    self.update_email_status(unread_count, message_count, SyncStatus::Syncing);
                 }
 
                 // Create initial sync progress entry
@@ -1488,6 +1913,7 @@ impl UI {
                     bytes_downloaded: 0,
                     started_at: chrono::Utc::now(),
                     estimated_completion: None,
+                    account_progress: None,
                 };
                 self.update_sync_progress(initial_progress);
 
@@ -1501,22 +1927,18 @@ impl UI {
                 updated_count,
             } => {
                 // Update status bar to show sync completed
-                if let (Some(current_account), Some(current_folder)) =
-                    self.message_list.get_current_context()
-                {
-                    if current_account == &account_id && current_folder == &folder_name {
-                        // Refresh messages after sync
-                        let _ = self.message_list.refresh_messages().await;
-
-                        let message_count = self.message_list.messages().len();
-                        let unread_count = self
-                            .message_list
-                            .messages()
-                            .iter()
-                            .filter(|msg| !msg.is_read)
-                            .count();
-                        self.update_email_status(unread_count, message_count, SyncStatus::Online);
-                    }
+                if self.message_list.shows_folder(&account_id, &folder_name) {
+                    // Refresh messages after sync
+                    let _ = self.message_list.refresh_messages().await;
+
+                    let message_count = self.message_list.messages().len();
+                    let unread_count = self
+                        .message_list
+                        .messages()
+                        .iter()
+                        .filter(|msg| !msg.is_read)
+                        .count();
+                    self.update_email_status(unread_count, message_count, SyncStatus::Online);
                 }
 
                 // Update sync progress to completed
@@ -1529,6 +1951,7 @@ impl UI {
                     bytes_downloaded: 0, // TODO: Get actual bytes from sync engine
                     started_at: chrono::Utc::now() - chrono::Duration::seconds(1), // Approximate
                     estimated_completion: Some(chrono::Utc::now()),
+                    account_progress: None,
                 };
                 self.update_sync_progress(completed_progress);
 
@@ -1547,19 +1970,15 @@ impl UI {
                 error,
             } => {
                 // Update status bar to show sync error
-                if let (Some(current_account), Some(current_folder)) =
-                    self.message_list.get_current_context()
-                {
-                    if current_account == &account_id && current_folder == &folder_name {
-                        let message_count = self.message_list.messages().len();
-                        let unread_count = self
-                            .message_list
-                            .messages()
-                            .iter()
-                            .filter(|msg| !msg.is_read)
-                            .count();
-                        self.update_email_status(unread_count, message_count, SyncStatus::Error);
-                    }
+                if self.message_list.shows_folder(&account_id, &folder_name) {
+                    let message_count = self.message_list.messages().len();
+                    let unread_count = self
+                        .message_list
+                        .messages()
+                        .iter()
+                        .filter(|msg| !msg.is_read)
+                        .count();
+                    self.update_email_status(unread_count, message_count, SyncStatus::Error);
                 }
 
                 // Update sync progress to error state
@@ -1572,6 +1991,7 @@ impl UI {
                     bytes_downloaded: 0,
                     started_at: chrono::Utc::now() - chrono::Duration::seconds(1), // Approximate
                     estimated_completion: None,
+                    account_progress: None,
                 };
                 self.update_sync_progress(error_progress);
 
@@ -1585,35 +2005,31 @@ impl UI {
         self.sync_progress_overlay.update_progress(progress.clone());
 
         // Also update status bar with progress if this is for the current folder
-        if let (Some(current_account), Some(current_folder)) =
-            self.message_list.get_current_context()
-        {
-            if current_account == &progress.account_id && current_folder == &progress.folder_name {
-                let message_count = self.message_list.messages().len();
-                let unread_count = self
-                    .message_list
-                    .messages()
-                    .iter()
-                    .filter(|msg| !msg.is_read)
-                    .count();
-
-                let sync_status = match progress.phase {
-                    crate::email::sync_engine::SyncPhase::Complete => SyncStatus::Online,
-                    crate::email::sync_engine::SyncPhase::Error(_) => SyncStatus::Error,
-                    _ => {
-                        if progress.total_messages > 0 {
-                            SyncStatus::SyncingWithProgress(
-                                progress.messages_processed,
-                                progress.total_messages,
-                            )
-                        } else {
-                            SyncStatus::Syncing
-                        }
+        if self.message_list.shows_folder(&progress.account_id, &progress.folder_name) {
+            let message_count = self.message_list.messages().len();
+            let unread_count = self
+                .message_list
+                .messages()
+                .iter()
+                .filter(|msg| !msg.is_read)
+                .count();
+
+            let sync_status = match progress.phase {
+                crate::email::sync_engine::SyncPhase::Complete => SyncStatus::Online,
+                crate::email::sync_engine::SyncPhase::Error(_) => SyncStatus::Error,
+                _ => {
+                    if progress.total_messages > 0 {
+                        SyncStatus::SyncingWithProgress(
+                            progress.messages_processed,
+                            progress.total_messages,
+                        )
+                    } else {
+                        SyncStatus::Syncing
                     }
-                };
+                }
+            };
 
-                self.update_email_status(unread_count, message_count, sync_status);
-            }
+            self.update_email_status(unread_count, message_count, sync_status);
         }
     }
 
@@ -1638,11 +2054,31 @@ impl UI {
         self.sync_progress_overlay.previous_sync();
     }
 
+    /// Toggle the today panel
+    pub fn toggle_today_panel(&mut self) {
+        self.today_panel.toggle_visibility();
+    }
+
+    /// Check if the today panel is visible
+    pub fn is_today_panel_visible(&self) -> bool {
+        self.today_panel.is_visible()
+    }
+
+    /// Get mutable access to the today panel
+    pub fn today_panel_mut(&mut self) -> &mut today_panel::TodayPanel {
+        &mut self.today_panel
+    }
+
     /// Check if sync progress overlay is currently visible
     pub fn is_sync_progress_visible(&self) -> bool {
         self.sync_progress_overlay.is_visible()
     }
 
+    /// Sync currently selected in the sync progress overlay
+    pub fn selected_sync_progress(&self) -> Option<&SyncProgress> {
+        self.sync_progress_overlay.selected_progress()
+    }
+
     /// Enhanced progress overlay methods
     ///
     /// Get mutable access to enhanced progress overlay
@@ -1705,7 +2141,16 @@ impl UI {
 
     /// Enter compose mode with a new email
     pub fn start_compose(&mut self, contacts_manager: Arc<crate::contacts::ContactsManager>) {
-        self.compose_ui = Some(ComposeUI::new(contacts_manager));
+        self.open_compose(ComposeUI::new(contacts_manager));
+    }
+
+    /// Show `compose_ui` with the compose settings applied
+    fn open_compose(&mut self, mut compose_ui: ComposeUI) {
+        compose_ui.set_wrap_config(self.compose_wrap_config.clone());
+        if let Some((service, delay)) = &self.smart_compose {
+            compose_ui.set_smart_compose(service.clone(), *delay);
+        }
+        self.compose_ui = Some(compose_ui);
         self.mode = UIMode::Compose;
         self.focused_pane = FocusedPane::Compose;
     }
@@ -1716,10 +2161,16 @@ impl UI {
         contacts_manager: Arc<crate::contacts::ContactsManager>,
         reply_to: &str,
         subject: &str,
+        quoted_body: &str,
+        position: crate::email::QuotePosition,
     ) {
-        self.compose_ui = Some(ComposeUI::new_reply(contacts_manager, reply_to, subject));
-        self.mode = UIMode::Compose;
-        self.focused_pane = FocusedPane::Compose;
+        self.open_compose(ComposeUI::new_reply(
+            contacts_manager,
+            reply_to,
+            subject,
+            quoted_body,
+            position,
+        ));
     }
 
     /// Enter compose mode for forwarding a message
@@ -1729,49 +2180,105 @@ impl UI {
         subject: &str,
         body: &str,
     ) {
-        self.compose_ui = Some(ComposeUI::new_forward(contacts_manager, subject, body));
-        self.mode = UIMode::Compose;
-        self.focused_pane = FocusedPane::Compose;
+        self.open_compose(ComposeUI::new_forward(contacts_manager, subject, body));
     }
 
     /// Enter compose mode for replying to a specific message
+    ///
+    /// The recipients follow `mode`, the message's list and reply headers and
+    /// the reply settings; the compose header says how they were chosen.
     pub fn start_reply_from_message(
         &mut self,
         message: crate::email::StoredMessage,
+        mode: crate::email::ReplyMode,
+        headers: &crate::email::ReplyHeaders,
         contacts_manager: Arc<crate::contacts::ContactsManager>,
     ) {
-        // Extract sender information for reply
-        let reply_to = message.reply_to.unwrap_or(message.from_addr.clone());
-
-        // Add "Re: " prefix to subject if not already present
-        let subject = if message.subject.starts_with("Re: ") {
-            message.subject
-        } else {
-            format!("Re: {}", message.subject)
-        };
-
-        self.start_reply(contacts_manager, &reply_to, &subject);
+        let position = self.quote_config.style_for(&message.account_id).position;
+        let quote = self.reply_quote(&message);
+        let recipients = self.reply_recipients_for(&message, headers, mode);
+        let (in_reply_to, references) = crate::email::reply_threading(&message);
+        let subject = reply_subject(&message.subject);
+
+        self.start_reply(contacts_manager, "", &subject, &quote, position);
+        if let Some(ref mut compose) = self.compose_ui {
+            compose.set_reply_recipients(&recipients);
+            compose.set_reply_threading(in_reply_to, references);
+            compose.set_account_id(Some(message.account_id.clone()));
+        }
     }
 
-    /// Enter compose mode for replying to all recipients of a specific message
-    pub fn start_reply_all_from_message(
+    /// Reply to `message` with a body drafted by the AI in the tone used
+    /// last; the draft is written into compose when it arrives
+    pub fn start_ai_reply_draft(
         &mut self,
         message: crate::email::StoredMessage,
+        headers: &crate::email::ReplyHeaders,
         contacts_manager: Arc<crate::contacts::ContactsManager>,
     ) {
-        // Extract sender information for reply
-        let reply_to = message.reply_to.unwrap_or(message.from_addr.clone());
+        let assistant = self.ai_assistant.assistant().clone();
+        self.start_reply_from_message(
+            message.clone(),
+            crate::email::ReplyMode::Sender,
+            headers,
+            contacts_manager,
+        );
+        if let Some(ref mut compose) = self.compose_ui {
+            compose.start_reply_draft(assistant, message, self.reply_tone);
+        }
+    }
 
-        // Add "Re: " prefix to subject if not already present
-        let subject = if message.subject.starts_with("Re: ") {
-            message.subject
-        } else {
-            format!("Re: {}", message.subject)
-        };
+    /// Open the quick reply box under the message in the email viewer,
+    /// addressed to the sender
+    pub fn start_quick_reply(
+        &mut self,
+        message: crate::email::StoredMessage,
+        headers: &crate::email::ReplyHeaders,
+    ) {
+        let recipients =
+            self.reply_recipients_for(&message, headers, crate::email::ReplyMode::Sender);
+        let subject = reply_subject(&message.subject);
+        self.email_viewer
+            .open_quick_reply(quick_reply::QuickReply::new(message, &recipients, subject));
+    }
 
-        // For reply-all, we would need to include all original recipients
-        // For now, just reply to sender (this needs to be enhanced)
-        self.start_reply(contacts_manager, &reply_to, &subject);
+    /// Insert text at the compose body cursor
+    pub fn insert_compose_text(&mut self, text: &str) {
+        if let Some(ref mut compose) = self.compose_ui {
+            compose.insert_body_text(text);
+        }
+    }
+
+    fn reply_recipients_for(
+        &self,
+        message: &crate::email::StoredMessage,
+        headers: &crate::email::ReplyHeaders,
+        mode: crate::email::ReplyMode,
+    ) -> crate::email::ReplyRecipients {
+        let own_address = self
+            .account_switcher
+            .accounts()
+            .iter()
+            .find(|account| account.account_id == message.account_id)
+            .map(|account| account.email_address.clone());
+        crate::email::reply_target::reply_recipients(
+            message,
+            headers,
+            mode,
+            &self.reply_config,
+            own_address.as_deref(),
+        )
+    }
+
+    /// Quoted original for a reply in the account's quote style, without the
+    /// sender's signature or disclaimer
+    fn reply_quote(&self, message: &crate::email::StoredMessage) -> String {
+        let Some(ref text) = message.body_text else {
+            return String::new();
+        };
+        self.quote_config
+            .style_for(&message.account_id)
+            .reply_body(message, self.signature_stripper.for_reply(text))
     }
 
     /// Enter compose mode for forwarding a specific message
@@ -1780,44 +2287,23 @@ impl UI {
         message: crate::email::StoredMessage,
         contacts_manager: Arc<crate::contacts::ContactsManager>,
     ) {
-        // Store original subject for use in the forwarded message body
-        let original_subject = message.subject.clone();
-
         // Add "Fwd: " prefix to subject if not already present
-        let subject = if original_subject.starts_with("Fwd: ") {
-            original_subject.clone()
+        let subject = if message.subject.starts_with("Fwd: ") {
+            message.subject.clone()
         } else {
-            format!("Fwd: {}", original_subject)
+            format!("Fwd: {}", message.subject)
         };
 
-        // Create forwarded message body with original content
-        let body = match message.body_text {
-            Some(ref text) => format!(
-                "\n\n---------- Forwarded message ----------\n\
-                From: {}\n\
-                Date: {}\n\
-                Subject: {}\n\
-                To: {}\n\n{}",
-                message.from_addr,
-                message.date.format("%Y-%m-%d %H:%M:%S"),
-                original_subject,
-                message.to_addrs.join(", "),
-                text
-            ),
-            None => format!(
-                "\n\n---------- Forwarded message ----------\n\
-                From: {}\n\
-                Date: {}\n\
-                Subject: {}\n\
-                To: {}\n\n(No message content)",
-                message.from_addr,
-                message.date.format("%Y-%m-%d %H:%M:%S"),
-                original_subject,
-                message.to_addrs.join(", ")
-            ),
-        };
+        // Original headers and content in the account's quote style
+        let body = self
+            .quote_config
+            .style_for(&message.account_id)
+            .forward_body(&message);
 
         self.start_forward(contacts_manager, &subject, &body);
+        if let Some(ref mut compose) = self.compose_ui {
+            compose.set_account_id(Some(message.account_id));
+        }
     }
 
     /// Enter compose mode for editing a specific message (draft)
@@ -1831,13 +2317,11 @@ impl UI {
         let body = message.body_text.unwrap_or_else(|| String::new());
 
         // Use the forward constructor as a base and customize it for editing
-        self.compose_ui = Some(ComposeUI::new_forward(
+        self.open_compose(ComposeUI::new_forward(
             contacts_manager,
             &message.subject,
             &body,
         ));
-        self.mode = UIMode::Compose;
-        self.focused_pane = FocusedPane::Compose;
     }
 
     /// Exit compose mode and return to normal view
@@ -1847,13 +2331,39 @@ impl UI {
         self.focused_pane = FocusedPane::FolderTree;
     }
 
+    /// Leave compose mode but keep the window, so an undone send reopens it as it was
+    pub fn hold_compose(&mut self) {
+        self.held_compose = self.compose_ui.take();
+        self.exit_compose();
+    }
+
+    /// Whether a sent message can still be taken back into compose
+    pub fn has_held_compose(&self) -> bool {
+        self.held_compose.is_some()
+    }
+
+    /// Reopen the held compose window, cursor and settings intact
+    pub fn restore_held_compose(&mut self) -> bool {
+        let Some(compose_ui) = self.held_compose.take() else {
+            return false;
+        };
+        self.compose_ui = Some(compose_ui);
+        self.mode = UIMode::Compose;
+        self.focused_pane = FocusedPane::Compose;
+        true
+    }
+
+    /// Forget the held compose window once its message has gone out
+    pub fn drop_held_compose(&mut self) {
+        self.held_compose = None;
+    }
+
     /// Handle key input for compose mode
     pub async fn handle_compose_key(
         &mut self,
-        key: crossterm::event::KeyCode,
+        key_event: crossterm::event::KeyEvent,
     ) -> Option<ComposeAction> {
         if let Some(ref mut compose_ui) = self.compose_ui {
-            let key_event = crossterm::event::KeyEvent::new(key, crossterm::event::KeyModifiers::empty());
             Some(compose_ui.handle_key(key_event).await)
         } else {
             None
@@ -1865,6 +2375,13 @@ impl UI {
         self.compose_ui.as_ref().map(|ui| ui.get_email_data())
     }
 
+    /// Ask in the compose view whether to send without encryption
+    pub fn ask_compose_send_unencrypted(&mut self, missing: Vec<String>) {
+        if let Some(ref mut compose_ui) = self.compose_ui {
+            compose_ui.ask_send_unencrypted(missing);
+        }
+    }
+
     /// Check if compose form has been modified
     pub fn is_compose_modified(&self) -> bool {
         self.compose_ui
@@ -1887,6 +2404,11 @@ impl UI {
             .and_then(|ui| ui.current_draft_id())
     }
 
+    /// Account the message being composed is sent from, if not the default
+    pub fn get_compose_account_id(&self) -> Option<&String> {
+        self.compose_ui.as_ref().and_then(|ui| ui.account_id())
+    }
+
     /// Set the current draft ID
     pub fn set_compose_draft_id(&mut self, draft_id: Option<String>) {
         if let Some(ref mut compose_ui) = self.compose_ui {
@@ -1906,6 +2428,30 @@ impl UI {
         self.compose_ui.as_ref().and_then(|ui| ui.check_auto_save())
     }
 
+    /// Run the compose window's pending spell check, if typing has paused
+    pub async fn update_compose_spell_check(&mut self) {
+        if let Some(ref mut compose_ui) = self.compose_ui {
+            compose_ui.update_spell_check().await;
+        }
+    }
+
+    /// Pick up the compose window's inline completion and reply draft once
+    /// the provider has answered
+    pub fn update_compose_ai(&mut self) {
+        let Some(ref mut compose_ui) = self.compose_ui else {
+            return;
+        };
+        compose_ui.update_inline_completion();
+        match compose_ui.update_reply_draft() {
+            Some(Ok(tone)) => {
+                self.reply_tone = tone;
+                self.show_toast_success(format!("Drafted a {} reply", tone));
+            }
+            Some(Err(error)) => self.show_toast_error(format!("AI error: {}", error)),
+            None => {}
+        }
+    }
+
     /// Show draft list UI
     pub fn show_draft_list(&mut self) {
         self.draft_list.show();
@@ -1958,9 +2504,7 @@ impl UI {
         let mut compose_ui = ComposeUI::new(contacts_manager);
         compose_ui.load_from_draft(compose_data, draft_id);
 
-        self.compose_ui = Some(compose_ui);
-        self.mode = UIMode::Compose;
-        self.focused_pane = FocusedPane::Compose;
+        self.open_compose(compose_ui);
     }
 
     /// Get current UI mode
@@ -1979,6 +2523,165 @@ impl UI {
         self.mode = UIMode::KeyboardShortcuts;
     }
 
+    /// Show server capabilities and TLS details for an account
+    pub fn show_account_details(&mut self, view: account_details::AccountDetailsView) {
+        self.account_details = Some(view);
+        self.mode = UIMode::AccountDetails;
+    }
+
+    /// Close the account details popup
+    pub fn hide_account_details(&mut self) {
+        self.account_details = None;
+        self.show_email_interface();
+    }
+
+    pub fn account_details_mut(&mut self) -> Option<&mut account_details::AccountDetailsView> {
+        self.account_details.as_mut()
+    }
+
+    /// Show the special folder setup for an account
+    pub fn show_special_folders(&mut self, view: special_folders_view::SpecialFoldersView) {
+        self.special_folders_view = Some(view);
+        self.mode = UIMode::SpecialFolders;
+    }
+
+    /// Close the special folder setup
+    pub fn hide_special_folders(&mut self) {
+        self.special_folders_view = None;
+        self.show_email_interface();
+    }
+
+    pub fn special_folders_view_mut(
+        &mut self,
+    ) -> Option<&mut special_folders_view::SpecialFoldersView> {
+        self.special_folders_view.as_mut()
+    }
+
+    /// Show the retention preview or the messages pending removal
+    pub fn show_retention(&mut self, view: retention_view::RetentionView) {
+        self.retention_view = Some(view);
+        self.mode = UIMode::Retention;
+    }
+
+    /// Close the retention popup
+    pub fn hide_retention(&mut self) {
+        self.retention_view = None;
+        self.show_email_interface();
+    }
+
+    pub fn retention_view_mut(&mut self) -> Option<&mut retention_view::RetentionView> {
+        self.retention_view.as_mut()
+    }
+
+    /// Show the mbox import and export wizard
+    pub fn show_mbox_wizard(&mut self, wizard: mbox_wizard::MboxWizard) {
+        self.mbox_wizard = Some(wizard);
+        self.mode = UIMode::MboxWizard;
+    }
+
+    /// Close the mbox wizard
+    pub fn hide_mbox_wizard(&mut self) {
+        self.mbox_wizard = None;
+        self.show_email_interface();
+    }
+
+    pub fn mbox_wizard_mut(&mut self) -> Option<&mut mbox_wizard::MboxWizard> {
+        self.mbox_wizard.as_mut()
+    }
+
+    /// Show the filter rules popup
+    pub fn show_filter_rules(&mut self, view: filter_rules_view::FilterRulesView) {
+        self.filter_rules_view = Some(view);
+        self.mode = UIMode::FilterRules;
+    }
+
+    /// Close the filter rules popup
+    pub fn hide_filter_rules(&mut self) {
+        self.filter_rules_view = None;
+        self.show_email_interface();
+    }
+
+    pub fn filter_rules_view_mut(&mut self) -> Option<&mut filter_rules_view::FilterRulesView> {
+        self.filter_rules_view.as_mut()
+    }
+
+    /// Show the headers panel for the displayed message
+    pub fn show_headers_panel(&mut self, panel: headers_panel::HeadersPanel) {
+        self.headers_panel = Some(panel);
+        self.mode = UIMode::Headers;
+    }
+
+    /// Close the headers panel
+    pub fn hide_headers_panel(&mut self) {
+        self.headers_panel = None;
+        self.show_email_interface();
+    }
+
+    pub fn headers_panel_mut(&mut self) -> Option<&mut headers_panel::HeadersPanel> {
+        self.headers_panel.as_mut()
+    }
+
+    /// Show the links of the displayed message
+    pub fn show_links_panel(&mut self, panel: links_panel::LinksPanel) {
+        self.links_panel = Some(panel);
+        self.mode = UIMode::Links;
+    }
+
+    /// Close the links panel
+    pub fn hide_links_panel(&mut self) {
+        self.links_panel = None;
+        self.show_email_interface();
+    }
+
+    pub fn links_panel_mut(&mut self) -> Option<&mut links_panel::LinksPanel> {
+        self.links_panel.as_mut()
+    }
+
+    /// Ask when the selected message should come back
+    pub fn show_snooze_picker(&mut self, picker: snooze_picker::SnoozePicker) {
+        self.snooze_picker = Some(picker);
+        self.mode = UIMode::Snooze;
+    }
+
+    /// Close the snooze picker
+    pub fn hide_snooze_picker(&mut self) {
+        self.snooze_picker = None;
+        self.show_email_interface();
+    }
+
+    pub fn snooze_picker_mut(&mut self) -> Option<&mut snooze_picker::SnoozePicker> {
+        self.snooze_picker.as_mut()
+    }
+
+    /// Show the SMS conversations
+    pub fn show_sms_screen(&mut self, screen: Arc<crate::mobile::SmsUi>) {
+        self.sms_screen = Some(screen);
+        self.mode = UIMode::Sms;
+    }
+
+    /// Close the SMS screen
+    pub fn hide_sms_screen(&mut self) {
+        self.sms_screen = None;
+        self.show_email_interface();
+    }
+
+    pub fn sms_screen(&self) -> Option<Arc<crate::mobile::SmsUi>> {
+        self.sms_screen.clone()
+    }
+
+    /// Ask for confirmation before a bulk action
+    pub fn show_confirm(&mut self, dialog: confirm_dialog::ConfirmDialog) {
+        self.confirm_dialog = Some(dialog);
+        self.mode = UIMode::Confirm;
+    }
+
+    /// Close the confirmation, returning the action it was asking about
+    pub fn take_confirm(&mut self) -> Option<confirm_dialog::ConfirmAction> {
+        let dialog = self.confirm_dialog.take();
+        self.show_email_interface();
+        dialog.map(|dialog| dialog.action().clone())
+    }
+
     /// Show application settings
     pub fn show_settings(&mut self) {
         self.mode = UIMode::Settings;
@@ -2019,7 +2722,7 @@ impl UI {
     pub fn set_initial_mode(&mut self, mode: crate::cli::StartupMode) {
         use crate::cli::StartupMode;
         match mode {
-            StartupMode::Default | StartupMode::Email => {
+            StartupMode::Default | StartupMode::Resume | StartupMode::Email | StartupMode::Daemon => {
                 self.mode = UIMode::Normal;
                 self.focused_pane = FocusedPane::AccountSwitcher;
             }
@@ -2035,6 +2738,105 @@ impl UI {
         }
     }
 
+    /// Capture the current navigation state so it can be restored on the next launch
+    pub fn capture_session_state(&self) -> SessionState {
+        let (account_id, folder_path) = self.message_list.get_current_context();
+        let selected = self.message_list.selected_message();
+
+        let focused_pane = match self.focused_pane {
+            FocusedPane::FolderTree => SessionPane::FolderTree,
+            FocusedPane::MessageList => SessionPane::MessageList,
+            FocusedPane::ContentPreview => SessionPane::ContentPreview,
+            _ => SessionPane::AccountSwitcher,
+        };
+
+        let view = match self.mode {
+            UIMode::Calendar => SessionView::Calendar,
+            _ => SessionView::Email,
+        };
+
+        SessionState {
+            account_id: account_id
+                .cloned()
+                .or_else(|| self.account_switcher.get_current_account_id().cloned()),
+            folder_path: folder_path.cloned(),
+            selected_message_id: selected.and_then(|message| message.message_id),
+            selected_index: self.message_list.get_selection_state(),
+            focused_pane,
+            view,
+            threaded_view: self.message_list.current_view_mode()
+                == message_list::ViewMode::Threaded,
+            preview_scroll: self.content_preview.get_scroll_position(),
+            saved_at: None,
+        }
+    }
+
+    /// Restore navigation state saved by a previous session
+    ///
+    /// Returns false when the saved account no longer exists, leaving the UI untouched.
+    pub async fn restore_session_state(&mut self, state: &SessionState) -> bool {
+        if let Some(account_id) = &state.account_id {
+            if !self.account_switcher.set_current_account(account_id) {
+                tracing::info!("Saved session account {} no longer exists", account_id);
+                return false;
+            }
+
+            if let Err(e) = self.load_folders(account_id).await {
+                tracing::warn!("Failed to load folders while restoring session: {}", e);
+            }
+
+            if let Some(folder_path) = &state.folder_path {
+                self.folder_tree.select_folder_by_path(folder_path);
+
+                if let Err(e) = self
+                    .load_messages(account_id.clone(), folder_path.clone())
+                    .await
+                {
+                    tracing::warn!("Failed to load messages while restoring session: {}", e);
+                }
+
+                let view_mode = if state.threaded_view {
+                    message_list::ViewMode::Threaded
+                } else {
+                    message_list::ViewMode::List
+                };
+                self.message_list.set_view_mode(view_mode);
+
+                let restored_by_id = state
+                    .selected_message_id
+                    .map(|id| self.message_list.select_message_by_id(id))
+                    .unwrap_or(false);
+                if !restored_by_id {
+                    if let Some(index) = state.selected_index {
+                        self.message_list.set_selected_index(index);
+                    }
+                }
+
+                self.handle_message_selection().await;
+                self.content_preview.set_scroll_position(state.preview_scroll);
+            }
+
+            self.update_status_bar_account_info();
+        }
+
+        self.focused_pane = match state.focused_pane {
+            SessionPane::AccountSwitcher => FocusedPane::AccountSwitcher,
+            SessionPane::FolderTree => FocusedPane::FolderTree,
+            SessionPane::MessageList => FocusedPane::MessageList,
+            SessionPane::ContentPreview => FocusedPane::ContentPreview,
+        };
+
+        match state.view {
+            SessionView::Calendar => self.show_calendar(),
+            SessionView::Email => {
+                self.mode = UIMode::Normal;
+                self.update_navigation_hints();
+            }
+        }
+
+        true
+    }
+
     /// Switch to normal email mode
     pub fn show_email_interface(&mut self) {
         self.mode = UIMode::Normal;
@@ -2090,6 +2892,7 @@ impl UI {
         self.event_form_ui = Some(crate::calendar::EventFormUI::new_create(
             calendars,
             default_calendar_id,
+            self.calendar_ui.timezone(),
         ));
         self.mode = UIMode::EventCreate;
         self.focused_pane = FocusedPane::Calendar;
@@ -2102,7 +2905,11 @@ impl UI {
         event: crate::calendar::Event,
         calendars: Vec<crate::calendar::Calendar>,
     ) {
-        self.event_form_ui = Some(crate::calendar::EventFormUI::new_edit(event, calendars));
+        self.event_form_ui = Some(crate::calendar::EventFormUI::new_edit(
+            event,
+            calendars,
+            self.calendar_ui.timezone(),
+        ));
         self.mode = UIMode::EventEdit;
         self.focused_pane = FocusedPane::Calendar;
         self.update_navigation_hints();
@@ -2114,7 +2921,11 @@ impl UI {
         event: crate::calendar::Event,
         calendars: Vec<crate::calendar::Calendar>,
     ) {
-        self.event_form_ui = Some(crate::calendar::EventFormUI::new_view(event, calendars));
+        self.event_form_ui = Some(crate::calendar::EventFormUI::new_view(
+            event,
+            calendars,
+            self.calendar_ui.timezone(),
+        ));
         self.mode = UIMode::EventView;
         self.focused_pane = FocusedPane::Calendar;
         self.update_navigation_hints();
@@ -2139,13 +2950,12 @@ impl UI {
     /// Handle event form key input
     pub async fn handle_event_form_key(
         &mut self,
-        key: crossterm::event::KeyCode,
+        key: crossterm::event::KeyEvent,
     ) -> Option<crate::calendar::EventFormAction> {
         let is_visible = self.is_event_form_visible();
         if is_visible {
             if let Some(ref mut event_form) = self.event_form_ui {
-                let key_event = crossterm::event::KeyEvent::new(key, crossterm::event::KeyModifiers::empty());
-                return event_form.handle_key(key_event).await;
+                return event_form.handle_key(key).await;
             }
         }
         None
@@ -2253,13 +3063,16 @@ impl UI {
     ) {
         self.invitation_viewer
             .set_invitation(invitation, user_status, user_invited);
+        if self.mode != UIMode::InvitationViewer {
+            self.invitation_return_mode = self.mode.clone();
+        }
         self.mode = UIMode::InvitationViewer;
     }
 
-    /// Exit invitation viewer mode
+    /// Exit invitation viewer mode, going back to where it was opened from
     pub fn exit_invitation_viewer(&mut self) {
         self.invitation_viewer.clear();
-        self.mode = UIMode::Normal;
+        self.mode = std::mem::replace(&mut self.invitation_return_mode, UIMode::Normal);
     }
 
     /// Check if invitation viewer is active
@@ -2272,6 +3085,11 @@ impl UI {
         self.invitation_viewer.handle_key(key)
     }
 
+    /// Get reference to invitation viewer
+    pub fn invitation_viewer(&self) -> &InvitationViewer {
+        &self.invitation_viewer
+    }
+
     /// Get mutable reference to invitation viewer
     pub fn invitation_viewer_mut(&mut self) -> &mut InvitationViewer {
         &mut self.invitation_viewer
@@ -2662,9 +3480,99 @@ impl UI {
         &mut self.unified_sidebar
     }
 
+    /// Enable avatars in the email viewer and contacts popup
+    pub fn set_avatar_config(&mut self, config: crate::contacts::AvatarConfig) {
+        // Avatars are drawn with half blocks, which work wherever true
+        // colour does, multiplexers included
+        let graphics = GraphicsProtocol::best_available() != GraphicsProtocol::None;
+        let avatars = crate::contacts::AvatarCache::new(config, graphics);
+        self.email_viewer.set_avatar_cache(avatars.clone());
+        self.avatars = Some(avatars);
+    }
+
+    /// Set animation preferences for the viewer and preview pane
+    pub fn set_animation_config(&mut self, config: AnimationConfig) {
+        self.content_preview.set_animation_config(config.clone());
+        self.email_viewer.set_animation_config(config);
+    }
+
+    /// Set when the email viewer folds quotes and forwarded messages
+    pub fn set_quote_fold_config(&mut self, config: quote_fold::QuoteFoldConfig) {
+        self.email_viewer.set_quote_fold_config(config);
+    }
+
+    /// Set reading pane reflow preferences
+    pub fn set_reflow_config(&mut self, config: reflow::ReflowConfig) {
+        self.content_preview.set_reflow_config(config);
+    }
+
+    /// Set the signature and disclaimer patterns used for reply quotes and AI input
+    pub async fn set_signature_stripper(&mut self, stripper: crate::email::SignatureStripper) {
+        self.ai_assistant
+            .assistant()
+            .set_signature_stripper(stripper.clone())
+            .await;
+        self.signature_stripper = stripper;
+    }
+
+    /// Set how replies and forwards quote the original message
+    pub fn set_quote_config(&mut self, config: crate::email::QuoteConfig) {
+        self.quote_config = config;
+    }
+
+    /// Set how composed text is wrapped
+    pub fn set_compose_wrap_config(&mut self, config: compose_wrap::ComposeWrapConfig) {
+        self.compose_wrap_config = config;
+    }
+
+    /// Offer inline completions in compose once typing pauses for `delay`
+    pub fn set_smart_compose(
+        &mut self,
+        service: Arc<crate::ai::SmartComposeService>,
+        delay: std::time::Duration,
+    ) {
+        self.smart_compose = Some((service, delay));
+    }
+
+    /// Hide everything behind the lock screen
+    pub fn lock(&mut self) {
+        if self.lock_screen.is_none() {
+            self.lock_screen = Some(lock_screen::LockScreen::new());
+        }
+    }
+
+    pub fn unlock(&mut self) {
+        self.lock_screen = None;
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.lock_screen.is_some()
+    }
+
+    pub fn lock_screen_mut(&mut self) -> Option<&mut lock_screen::LockScreen> {
+        self.lock_screen.as_mut()
+    }
+
+    /// Set where replies to list mail go
+    pub fn set_reply_config(&mut self, config: crate::email::ReplyConfig) {
+        self.reply_config = config;
+    }
+
+    /// Create a contacts popup sharing the avatar cache
+    fn new_contacts_popup(
+        &self,
+        contacts_manager: Arc<crate::contacts::ContactsManager>,
+    ) -> crate::contacts::ContactPopup {
+        let mut contacts_popup = crate::contacts::ContactPopup::new(contacts_manager);
+        if let Some(ref avatars) = self.avatars {
+            contacts_popup.set_avatar_cache(avatars.clone());
+        }
+        contacts_popup
+    }
+
     /// Show contacts popup
     pub fn show_contacts_popup(&mut self, contacts_manager: Arc<crate::contacts::ContactsManager>) {
-        self.contacts_popup = Some(crate::contacts::ContactPopup::new(contacts_manager));
+        self.contacts_popup = Some(self.new_contacts_popup(contacts_manager));
         self.mode = UIMode::ContactsPopup;
         self.update_navigation_hints();
     }
@@ -2704,7 +3612,7 @@ impl UI {
         contacts_manager: Arc<crate::contacts::ContactsManager>,
         contact: crate::contacts::Contact,
     ) {
-        let mut contacts_popup = crate::contacts::ContactPopup::new(contacts_manager);
+        let mut contacts_popup = self.new_contacts_popup(contacts_manager);
         contacts_popup.show_contact_details(contact);
         self.contacts_popup = Some(contacts_popup);
         self.mode = UIMode::ContactsPopup;
@@ -2727,7 +3635,7 @@ impl UI {
         contacts_manager: Arc<crate::contacts::ContactsManager>,
         contact: crate::contacts::Contact,
     ) {
-        let mut contacts_popup = crate::contacts::ContactPopup::new(contacts_manager);
+        let mut contacts_popup = self.new_contacts_popup(contacts_manager);
         contacts_popup.start_edit_contact(contact);
         self.contacts_popup = Some(contacts_popup);
         self.mode = UIMode::ContactsPopup;
@@ -2783,13 +3691,20 @@ impl UI {
     }
 
     /// Show a custom toast notification with specific level and duration
+    ///
+    /// Returns the toast's ID for `dismiss_toast`.
     pub fn show_custom_toast<S: Into<String>>(
         &mut self, 
         message: S, 
         level: crate::tea::message::ToastLevel, 
         duration: Duration
-    ) {
-        self.toast_manager.show_with_duration(message.into(), level, duration);
+    ) -> String {
+        self.toast_manager.show_with_duration(message.into(), level, duration)
+    }
+
+    /// Remove a toast before it expires
+    pub fn dismiss_toast(&mut self, toast_id: &str) {
+        self.toast_manager.remove_toast(toast_id);
     }
 
     /// Show a persistent toast (longer duration)
@@ -2850,6 +3765,7 @@ impl UI {
     /// Set information density for the interface
     pub fn set_information_density(&mut self, density: InformationDensity) {
         self.typography = self.typography.clone().with_density(density);
+        self.content_preview.set_information_density(density);
     }
 
     /// Get current information density
@@ -3351,13 +4267,23 @@ impl UI {
     }
 
     /// Spawn async task for AI email summarization
-    fn spawn_ai_summarization_task(&self, message: crate::email::EmailMessage, tx: tokio::sync::mpsc::UnboundedSender<AIOperationResult>) {
+    fn spawn_ai_summarization_task(&mut self, message: crate::email::EmailMessage, tx: tokio::sync::mpsc::UnboundedSender<AIOperationResult>) {
         let assistant = self.ai_assistant.assistant().clone();
         let request_id = uuid::Uuid::new_v4();
         
-        tokio::spawn(async move {
-            // Call real AI service for summarization
-            let result = assistant.summarize_email(&message).await;
+        let task = tokio::spawn(async move {
+            // Call real AI service for summarization, streaming the summary
+            let stream_tx = tx.clone();
+            let result = assistant
+                .streaming()
+                .relay(
+                    request_id,
+                    move |content| {
+                        let _ = stream_tx.send(AIOperationResult::Streamed { request_id, content });
+                    },
+                    assistant.summarize_email_streaming(&message, request_id),
+                )
+                .await;
             
             // Send result back to UI
             let ai_result = AIOperationResult::EmailSummarized {
@@ -3369,6 +4295,86 @@ impl UI {
                 tracing::error!("Failed to send AI summarization result: {}", e);
             }
         });
+        self.track_ai_task(request_id, task);
+    }
+
+    /// Keep a streamed popup operation so closing the popup can stop it
+    fn track_ai_task(&mut self, request_id: uuid::Uuid, task: tokio::task::JoinHandle<()>) {
+        self.cancel_ai_task();
+        self.ai_task = Some((request_id, task));
+    }
+
+    /// Stop the popup operation in flight, if any
+    fn cancel_ai_task(&mut self) {
+        let Some((request_id, task)) = self.ai_task.take() else {
+            return;
+        };
+        if task.is_finished() {
+            return;
+        }
+        task.abort();
+        let assistant = self.ai_assistant.assistant().clone();
+        tokio::spawn(async move {
+            let _ = assistant.streaming().end_stream(request_id).await;
+        });
+        tracing::debug!("Stopped AI operation {}", request_id);
+    }
+
+    /// Close the AI popup, stopping an answer still being written
+    pub fn close_ai_popup(&mut self) {
+        if self.ai_popup.is_pending() {
+            self.cancel_ai_task();
+        }
+        self.ai_popup.hide();
+    }
+
+    /// Run AI assistance through the configured service instead of the
+    /// disabled placeholder the UI starts with
+    pub fn set_ai_service(
+        &mut self,
+        ai_service: std::sync::Arc<crate::ai::AIService>,
+        config: std::sync::Arc<tokio::sync::RwLock<crate::ai::config::AIConfig>>,
+    ) {
+        let email_assistant =
+            std::sync::Arc::new(crate::email::AIEmailAssistant::new(ai_service, config));
+        self.ai_assistant = crate::ui::ai_assistant_ui::AIAssistantUI::new(email_assistant);
+    }
+
+    /// Start AI summarization of a whole thread with popup
+    pub fn start_ai_thread_summarization(&mut self, thread: crate::email::EmailThread) {
+        self.ai_popup.show_loading(format!(
+            "Summarizing {} messages in thread...",
+            thread.message_count()
+        ));
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        self.ai_result_rx = Some(rx);
+
+        let assistant = self.ai_assistant.assistant().clone();
+        let request_id = uuid::Uuid::new_v4();
+        let task = tokio::spawn(async move {
+            let stream_tx = tx.clone();
+            let result = assistant
+                .streaming()
+                .relay(
+                    request_id,
+                    move |content| {
+                        let _ = stream_tx.send(AIOperationResult::Streamed { request_id, content });
+                    },
+                    assistant.summarize_thread(&thread, Some(request_id)),
+                )
+                .await;
+            let ai_result = AIOperationResult::ThreadSummarized {
+                request_id,
+                subject: thread.subject().to_string(),
+                result: result.map_err(|e| e.to_string()),
+            };
+
+            if let Err(e) = tx.send(ai_result) {
+                tracing::error!("Failed to send AI thread summary: {}", e);
+            }
+        });
+        self.track_ai_task(request_id, task);
     }
 
     /// Show AI summarization for email content (legacy method)
@@ -3402,6 +4408,34 @@ impl UI {
         self.show_toast_info("AI configuration opened (Ctrl+Alt+G)");
     }
 
+    /// Show AI response cache statistics in the AI popup
+    pub fn show_ai_diagnostics(&mut self) {
+        self.cancel_ai_task();
+        self.ai_popup.show_loading("Reading AI cache statistics...".to_string());
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        self.ai_result_rx = Some(rx);
+
+        let service = self.ai_assistant.assistant().ai_service().clone();
+        tokio::spawn(async move {
+            let stats = service.cache_stats().await;
+            let _ = tx.send(AIOperationResult::CacheStats { stats });
+        });
+    }
+
+    /// Drop every cached AI response, then show the emptied statistics
+    pub fn clear_ai_cache(&mut self) {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        self.ai_result_rx = Some(rx);
+
+        let service = self.ai_assistant.assistant().ai_service().clone();
+        tokio::spawn(async move {
+            let result = service.clear_cache().await.map_err(|e| e.to_string());
+            let _ = tx.send(AIOperationResult::CacheCleared { result });
+            let stats = service.cache_stats().await;
+            let _ = tx.send(AIOperationResult::CacheStats { stats });
+        });
+    }
+
     /// Get mutable access to AI popup
     pub fn ai_popup_mut(&mut self) -> &mut crate::ui::ai_popup::AIPopup {
         &mut self.ai_popup
@@ -3585,6 +4619,36 @@ impl UI {
                         }
                     }
                 }
+                AIOperationResult::ThreadSummarized { request_id: _, subject, result } => {
+                    match result {
+                        Ok(summary) => {
+                            self.ai_popup.show_thread_summary(subject, summary);
+                            self.show_toast_success("Thread summary generated successfully");
+                        }
+                        Err(error) => {
+                            self.ai_popup.show_error(format!("Failed to summarize thread: {}", error), true);
+                            self.show_toast_error(format!("AI error: {}", error));
+                        }
+                    }
+                }
+                AIOperationResult::CacheStats { stats } => {
+                    if self.ai_popup.is_visible() {
+                        self.ai_popup.show_cache_diagnostics(stats);
+                    }
+                }
+                AIOperationResult::CacheCleared { result } => match result {
+                    Ok(removed) => {
+                        self.show_toast_success(format!("Flushed {} cached AI responses", removed));
+                    }
+                    Err(error) => {
+                        self.show_toast_error(format!("Failed to flush AI cache: {}", error));
+                    }
+                },
+                AIOperationResult::Streamed { request_id, content } => {
+                    if self.ai_task.as_ref().is_some_and(|(id, _)| *id == request_id) {
+                        self.ai_popup.append_stream(&content);
+                    }
+                }
                 AIOperationResult::CompositionAssisted { request_id: _, result } => {
                     match result {
                         Ok(_composition_assistance) => {
@@ -3652,3 +4716,12 @@ impl Default for UI {
         Self::new()
     }
 }
+
+/// Add "Re: " to a subject unless it is already there
+fn reply_subject(subject: &str) -> String {
+    if subject.starts_with("Re: ") {
+        subject.to_string()
+    } else {
+        format!("Re: {}", subject)
+    }
+}
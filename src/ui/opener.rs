@@ -0,0 +1,302 @@
+//! Opening links and files outside the terminal
+//!
+//! Links are routed by URL scheme and files by MIME type to a command from
+//! `opener.toml`, such as a browser with a particular profile or a specific
+//! PDF viewer. `%s` in the command is replaced by the URL or path; without
+//! it the target is appended. Files whose type has no handler here use the
+//! viewer from the system's mailcap files, if there is one; anything else
+//! goes to the OS default (`xdg-open`, `open` or `start`). Commands are split on whitespace
+//! with simple quoting and run directly, never through a shell, so a link
+//! from a message can't inject shell syntax.
+
+use crate::ui::mailcap;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+/// How long to wait for a handler to fail before assuming it's running fine
+const STARTUP_GRACE: Duration = Duration::from_secs(2);
+
+/// Link and file handler configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenerConfig {
+    /// Command per URL scheme, e.g. `https = "firefox -P work %s"`
+    pub schemes: HashMap<String, String>,
+    /// Command per MIME type; `image/*` matches every image type
+    pub mime_types: HashMap<String, String>,
+    /// Use mailcap viewers for types without a command here
+    pub mailcap: bool,
+}
+
+impl Default for OpenerConfig {
+    fn default() -> Self {
+        Self {
+            schemes: HashMap::new(),
+            mime_types: HashMap::new(),
+            mailcap: true,
+        }
+    }
+}
+
+impl OpenerConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "opener.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Problems with the configured commands, one message per handler
+    pub fn validate(&self) -> Vec<String> {
+        let handlers = self
+            .schemes
+            .iter()
+            .map(|(scheme, command)| (format!("scheme {}", scheme), command))
+            .chain(
+                self.mime_types
+                    .iter()
+                    .map(|(mime, command)| (format!("type {}", mime), command)),
+            );
+
+        let mut problems = Vec::new();
+        for (handler, command) in handlers {
+            match split_command(command) {
+                Err(e) => problems.push(format!("{}: {}", handler, e)),
+                Ok(words) => match words.first() {
+                    None => problems.push(format!("{}: empty command", handler)),
+                    Some(program) if find_program(program).is_none() => {
+                        problems.push(format!("{}: {} not found", handler, program))
+                    }
+                    Some(_) => {}
+                },
+            }
+        }
+        problems.sort();
+        problems
+    }
+
+    /// Command for a URL, by its scheme
+    pub fn command_for_url(&self, url: &str) -> Option<&str> {
+        let scheme = url.split_once(':')?.0.to_ascii_lowercase();
+        self.schemes
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(&scheme))
+            .map(|(_, command)| command.as_str())
+    }
+
+    /// Command for a MIME type; an exact match wins over `type/*`
+    pub fn command_for_mime(&self, mime_type: &str) -> Option<&str> {
+        let mime_type = mime_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let wildcard = mime_type
+            .split_once('/')
+            .map(|(kind, _)| format!("{}/*", kind));
+        let lookup = |wanted: &str| {
+            self.mime_types
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(wanted))
+                .map(|(_, command)| command.as_str())
+        };
+        lookup(&mime_type).or_else(|| wildcard.as_deref().and_then(lookup))
+    }
+
+    /// Open a link with its scheme's handler, or the OS default
+    pub async fn open_url(&self, url: &str) -> Result<()> {
+        match self.command_for_url(url) {
+            Some(command) => run(command, url).await,
+            None => open_with_default(url).await,
+        }
+    }
+
+    /// Open a file with its MIME type's handler, its mailcap viewer, or the
+    /// OS default
+    pub async fn open_file(&self, path: &Path, mime_type: &str) -> Result<()> {
+        let target = path.to_string_lossy();
+        if let Some(command) = self.command_for_mime(mime_type) {
+            return run(command, &target).await;
+        }
+        if self.mailcap {
+            if let Some(command) = mailcap::command_for(mime_type).await {
+                tracing::debug!("Opening {} with mailcap entry: {}", mime_type, command);
+                return run(&command, &target).await;
+            }
+        }
+        open_with_default(&target).await
+    }
+}
+
+/// Split a command line into words, honouring single and double quotes
+fn split_command(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+
+    for c in command.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if let Some(open) = quote {
+        return Err(anyhow::anyhow!("unclosed {} quote", open));
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Program and arguments for a handler, with the target substituted
+fn build_command(command: &str, target: &str) -> Result<(String, Vec<String>)> {
+    let mut words = split_command(command)?;
+    if words.is_empty() {
+        return Err(anyhow::anyhow!("Handler command is empty"));
+    }
+    let program = words.remove(0);
+    if !words.iter().any(|word| word.contains("%s")) {
+        words.push("%s".to_string());
+    }
+    let args = words
+        .into_iter()
+        .map(|word| word.replace("%s", target))
+        .collect();
+    Ok((program, args))
+}
+
+/// Look a program up on `PATH`, or check it exists when given as a path
+fn find_program(program: &str) -> Option<PathBuf> {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(program))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+async fn run(command: &str, target: &str) -> Result<()> {
+    let (program, args) = build_command(command, target)?;
+    spawn(&program, &args).await
+}
+
+async fn open_with_default(target: &str) -> Result<()> {
+    if cfg!(target_os = "macos") {
+        spawn("open", &[target.to_string()]).await
+    } else if cfg!(target_os = "windows") {
+        let args = ["/C", "start", "", target].map(str::to_string);
+        spawn("cmd", &args).await
+    } else {
+        spawn("xdg-open", &[target.to_string()]).await
+    }
+}
+
+/// Start a handler and report it if it fails straight away
+///
+/// Browsers and viewers often keep running, so this doesn't wait for them to
+/// exit; only a failure to start or a non-zero exit within a short grace
+/// period is an error.
+async fn spawn(program: &str, args: &[String]) -> Result<()> {
+    let mut child = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", program, e))?;
+
+    match tokio::time::timeout(STARTUP_GRACE, child.wait()).await {
+        Ok(Ok(status)) if !status.success() => {
+            Err(anyhow::anyhow!("{} exited with {}", program, status))
+        }
+        Ok(Err(e)) => Err(anyhow::anyhow!("Failed to wait for {}: {}", program, e)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handler_lookup_and_substitution() {
+        let config: OpenerConfig = toml::from_str(
+            r#"
+            [schemes]
+            HTTPS = "firefox -P 'Work profile' --new-tab %s"
+            mailto = "true"
+
+            [mime_types]
+            "image/*" = "imv"
+            "image/svg+xml" = "inkscape --with-gui=%s"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.command_for_url("https://example.com/a b"),
+            Some("firefox -P 'Work profile' --new-tab %s")
+        );
+        assert_eq!(config.command_for_url("tel:+4712345678"), None);
+        assert_eq!(config.command_for_mime("image/png; name=a.png"), Some("imv"));
+        assert_eq!(config.command_for_mime("image/svg+xml"), Some("inkscape --with-gui=%s"));
+        assert_eq!(config.command_for_mime("application/pdf"), None);
+
+        let (program, args) = build_command(
+            config.command_for_url("https://x").unwrap(),
+            "https://example.com/?q=1;rm -rf ~",
+        )
+        .unwrap();
+        assert_eq!(program, "firefox");
+        assert_eq!(
+            args,
+            vec!["-P", "Work profile", "--new-tab", "https://example.com/?q=1;rm -rf ~"]
+        );
+        let (_, args) = build_command("imv", "/tmp/a b.png").unwrap();
+        assert_eq!(args, vec!["/tmp/a b.png"]);
+
+        assert!(split_command("firefox 'unclosed").is_err());
+        let broken = OpenerConfig {
+            schemes: HashMap::from([("tel".to_string(), "no-such-dialer-xyz".to_string())]),
+            mime_types: HashMap::from([("text/html".to_string(), "  ".to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            broken.validate(),
+            vec![
+                "scheme tel: no-such-dialer-xyz not found".to_string(),
+                "type text/html: empty command".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Passphrase box at the bottom of the email viewer
+//!
+//! Asks for the PGP key passphrase of an encrypted message. The typed text is
+//! only shown masked and is cleared from the box when it is taken.
+
+use crate::theme::Theme;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// What the app should do after a key in the passphrase box
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassphraseAction {
+    Continue,
+    Submit,
+    Cancel,
+}
+
+/// Passphrase being typed to decrypt the viewed message
+#[derive(Debug, Default)]
+pub struct PassphrasePrompt {
+    input: String,
+    /// Why the last attempt failed
+    error: Option<String>,
+    decrypting: bool,
+}
+
+impl PassphrasePrompt {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn height(&self) -> u16 {
+        4
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PassphraseAction {
+        if self.decrypting {
+            return PassphraseAction::Continue;
+        }
+        match key.code {
+            KeyCode::Esc => return PassphraseAction::Cancel,
+            KeyCode::Enter => return PassphraseAction::Submit,
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.input.clear()
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.input.push(c)
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            _ => {}
+        }
+        PassphraseAction::Continue
+    }
+
+    /// Hand over the typed passphrase, emptying the box while it is tried
+    pub fn take_passphrase(&mut self) -> String {
+        self.error = None;
+        self.decrypting = true;
+        std::mem::take(&mut self.input)
+    }
+
+    /// Show why decrypting failed and let the user try again
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+        self.decrypting = false;
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(" Decrypt message ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.colors.palette.accent));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let status = if self.decrypting {
+            Span::styled("Decrypting…", label)
+        } else if let Some(ref error) = self.error {
+            Span::styled(error.clone(), Style::default().fg(theme.colors.palette.error))
+        } else {
+            Span::styled("Enter: decrypt · Esc: cancel", label)
+        };
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("PGP key passphrase: ", label),
+                Span::styled(
+                    format!("{}▏", "•".repeat(self.input.chars().count())),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(status),
+        ];
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
@@ -0,0 +1,213 @@
+//! Rendered message cache for the content preview
+//!
+//! Rendering a heavy HTML newsletter takes long enough to notice, and the
+//! preview used to redo it every time a message was selected. The cache keeps
+//! the loaded content and rendered body lines per message so revisiting one is
+//! instant. Entries are only valid for the settings they were rendered with;
+//! changing theme, density or width empties the cache.
+
+use crate::ui::content_preview::EmailContent;
+use crate::ui::typography::InformationDensity;
+use ratatui::text::Line;
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+
+/// Messages kept rendered before the least recently viewed is dropped
+pub const DEFAULT_RENDER_CACHE_CAPACITY: usize = 32;
+
+/// Everything that changes how a message renders
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderSettings {
+    pub theme: String,
+    pub density: InformationDensity,
+    /// Wrap width handed to the HTML renderer
+    pub width: usize,
+}
+
+/// A message ready to display without re-parsing or re-rendering
+#[derive(Debug, Clone)]
+pub struct RenderedMessage {
+    pub content: EmailContent,
+    /// Rendered HTML body; `None` for plain text, which is cheap to style
+    pub html_lines: Option<Vec<Line<'static>>>,
+    /// Image sources referenced by the body, already loaded into the
+    /// preview's image cache
+    pub image_sources: Vec<String>,
+    /// Size of the full body when `content` holds only the first part of it
+    pub truncated_from: Option<usize>,
+}
+
+/// Bounded LRU cache of rendered messages
+pub struct RenderCache {
+    capacity: usize,
+    settings: Option<RenderSettings>,
+    entries: HashMap<Uuid, RenderedMessage>,
+    /// Least recently used first
+    order: VecDeque<Uuid>,
+}
+
+impl RenderCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            settings: None,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Settings the cached entries were rendered with
+    pub fn settings(&self) -> Option<&RenderSettings> {
+        self.settings.as_ref()
+    }
+
+    /// Record the current settings, dropping every entry if they changed
+    ///
+    /// Returns true when the cache was invalidated.
+    pub fn update_settings(&mut self, settings: RenderSettings) -> bool {
+        if self.settings.as_ref() == Some(&settings) {
+            return false;
+        }
+
+        let invalidated = self.settings.is_some();
+        if invalidated {
+            tracing::debug!(
+                "Render settings changed, dropping {} cached messages",
+                self.entries.len()
+            );
+            self.clear();
+        }
+        self.settings = Some(settings);
+        invalidated
+    }
+
+    pub fn contains(&self, message_id: &Uuid) -> bool {
+        self.entries.contains_key(message_id)
+    }
+
+    /// Look up a message and mark it as recently used
+    pub fn get(&mut self, message_id: &Uuid) -> Option<&RenderedMessage> {
+        if self.entries.contains_key(message_id) {
+            self.touch(message_id);
+        }
+        self.entries.get(message_id)
+    }
+
+    /// Look up a message without changing its position
+    pub fn peek(&self, message_id: &Uuid) -> Option<&RenderedMessage> {
+        self.entries.get(message_id)
+    }
+
+    /// Store a rendered message, evicting the least recently used if full
+    pub fn insert(&mut self, message_id: Uuid, rendered: RenderedMessage) {
+        if self.entries.insert(message_id, rendered).is_some() {
+            self.touch(&message_id);
+            return;
+        }
+
+        self.order.push_back(message_id);
+        while self.entries.len() > self.capacity {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Forget one message, e.g. after its body was refetched
+    pub fn remove(&mut self, message_id: &Uuid) {
+        if self.entries.remove(message_id).is_some() {
+            self.order.retain(|id| id != message_id);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn touch(&mut self, message_id: &Uuid) {
+        if let Some(position) = self.order.iter().position(|id| id == message_id) {
+            self.order.remove(position);
+        }
+        self.order.push_back(*message_id);
+    }
+}
+
+impl Default for RenderCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_RENDER_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::content_preview::{ContentType, EmailHeader};
+
+    fn rendered(subject: &str) -> RenderedMessage {
+        RenderedMessage {
+            content: EmailContent {
+                headers: EmailHeader {
+                    from: "alice@example.com".to_string(),
+                    to: Vec::new(),
+                    cc: Vec::new(),
+                    bcc: Vec::new(),
+                    subject: subject.to_string(),
+                    date: String::new(),
+                    message_id: String::new(),
+                    reply_to: None,
+                    in_reply_to: None,
+                },
+                body: String::new(),
+                content_type: ContentType::PlainText,
+                attachments: Vec::new(),
+                parsed_urls: Vec::new(),
+                parsed_content: Vec::new(),
+            },
+            html_lines: None,
+            image_sources: Vec::new(),
+            truncated_from: None,
+        }
+    }
+
+    fn settings(theme: &str, width: usize) -> RenderSettings {
+        RenderSettings {
+            theme: theme.to_string(),
+            density: InformationDensity::Comfortable,
+            width,
+        }
+    }
+
+    #[test]
+    fn test_render_cache_eviction_and_invalidation() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut cache = RenderCache::new(2);
+        assert!(!cache.update_settings(settings("dark", 80)));
+
+        cache.insert(a, rendered("a"));
+        cache.insert(b, rendered("b"));
+        // Reading `a` makes `b` the least recently used
+        assert!(cache.get(&a).is_some());
+        cache.insert(c, rendered("c"));
+        assert!(cache.contains(&a));
+        assert!(!cache.contains(&b));
+        assert!(cache.contains(&c));
+        assert_eq!(cache.len(), 2);
+
+        assert!(!cache.update_settings(settings("dark", 80)));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.update_settings(settings("light", 80)));
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+//! Size limit for rendered message bodies
+//!
+//! Some marketing mail carries megabytes of HTML, and rendering all of it
+//! stalls the preview. Bodies over the limit are cut to the first
+//! `max_render_kb` kilobytes and shown with a notice; the full message is
+//! rendered on request. Remote images are not loaded for a truncated body.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Preview size limit configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreviewLimitsConfig {
+    /// Largest body rendered without asking, in kilobytes; 0 disables the limit
+    pub max_render_kb: usize,
+}
+
+impl Default for PreviewLimitsConfig {
+    fn default() -> Self {
+        Self { max_render_kb: 256 }
+    }
+}
+
+impl PreviewLimitsConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "preview_limits.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Limit in bytes, if one applies
+    pub fn max_render_bytes(&self) -> Option<usize> {
+        (self.max_render_kb > 0).then_some(self.max_render_kb * 1024)
+    }
+}
+
+/// Cut a body to at most `max_bytes`
+///
+/// Returns `None` when the body fits. The cut lands on a character boundary,
+/// and HTML is cut before a tag left open at the boundary so the renderer
+/// doesn't show half a tag as text.
+pub fn truncate_body(body: &str, max_bytes: usize, is_html: bool) -> Option<String> {
+    if body.len() <= max_bytes {
+        return None;
+    }
+
+    let mut end = max_bytes;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut truncated = &body[..end];
+
+    if is_html {
+        if let Some(open) = truncated.rfind('<') {
+            if truncated[open..].find('>').is_none() {
+                truncated = &truncated[..open];
+            }
+        }
+    }
+
+    Some(truncated.to_string())
+}
+
+/// Human readable size, e.g. "256 KB" or "3.2 MB"
+pub fn format_size(bytes: usize) -> String {
+    if bytes < 1024 * 1024 {
+        format!("{} KB", bytes.div_ceil(1024))
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_body() {
+        assert_eq!(truncate_body("short", 16, false), None);
+
+        // Never splits a multi-byte character
+        assert_eq!(truncate_body("aé€b", 3, false).as_deref(), Some("aé"));
+
+        // An HTML tag cut in half is dropped
+        let html = "<p>Hello</p><img src=\"https://tracker.example/pixel.gif\">";
+        assert_eq!(truncate_body(html, 20, true).as_deref(), Some("<p>Hello</p>"));
+        assert_eq!(truncate_body(html, 20, false).as_deref(), Some("<p>Hello</p><img src"));
+
+        assert_eq!(PreviewLimitsConfig::default().max_render_bytes(), Some(256 * 1024));
+        assert_eq!(PreviewLimitsConfig { max_render_kb: 0 }.max_render_bytes(), None);
+        assert_eq!(format_size(3 * 1024 * 1024 + 200 * 1024), "3.2 MB");
+        assert_eq!(format_size(1000), "1 KB");
+    }
+}
@@ -0,0 +1,367 @@
+//! Quick reply box at the bottom of the email viewer
+//!
+//! Holds a short plain-text reply to the message being read, already
+//! addressed and threaded. Sending goes through the same SMTP path as the
+//! compose screen; expanding moves the typed text into a full reply compose.
+
+use crate::email::{reply_threading, MailPriority, ReplyRecipients, StoredMessage};
+use crate::spell::SpellChecker;
+use crate::theme::Theme;
+use crate::ui::EmailComposeData;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Most body lines shown before the box scrolls
+const MAX_VISIBLE_LINES: u16 = 6;
+
+/// What the app should do after a key in the quick reply box
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuickReplyAction {
+    Continue,
+    Send,
+    /// Open a full reply compose holding the typed text
+    Expand,
+    Cancel,
+}
+
+/// Reply being typed under the message in the viewer
+pub struct QuickReply {
+    message: StoredMessage,
+    to: String,
+    cc: String,
+    subject: String,
+    in_reply_to: Option<String>,
+    references: Option<String>,
+    lines: Vec<String>,
+    line: usize,
+    /// Cursor within the current line, in characters
+    cursor: usize,
+    spell_checker: SpellChecker,
+}
+
+impl QuickReply {
+    pub fn new(message: StoredMessage, recipients: &ReplyRecipients, subject: String) -> Self {
+        let (in_reply_to, references) = reply_threading(&message);
+
+        Self {
+            to: recipients.to.join(", "),
+            cc: recipients.cc.join(", "),
+            subject,
+            in_reply_to,
+            references,
+            message,
+            lines: vec![String::new()],
+            line: 0,
+            cursor: 0,
+            spell_checker: SpellChecker::new().unwrap_or_default(),
+        }
+    }
+
+    /// The message being replied to
+    pub fn message(&self) -> &StoredMessage {
+        &self.message
+    }
+
+    pub fn account_id(&self) -> &str {
+        &self.message.account_id
+    }
+
+    /// The typed reply
+    pub fn body(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.trim().is_empty())
+    }
+
+    /// The reply as compose data for the SMTP service
+    pub fn compose_data(&self) -> EmailComposeData {
+        EmailComposeData {
+            to: self.to.clone(),
+            cc: self.cc.clone(),
+            bcc: String::new(),
+            subject: self.subject.clone(),
+            body: self.body(),
+            in_reply_to: self.in_reply_to.clone(),
+            references: self.references.clone(),
+            priority: MailPriority::Normal,
+            encrypt: false,
+            sign: false,
+            attachments: Vec::new(),
+        }
+    }
+
+    /// Rows the box needs, borders included
+    pub fn height(&self) -> u16 {
+        (self.lines.len() as u16).clamp(1, MAX_VISIBLE_LINES) + 4
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> QuickReplyAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return QuickReplyAction::Cancel,
+            KeyCode::Char('s') if ctrl && !self.is_empty() => return QuickReplyAction::Send,
+            KeyCode::Char('e') if ctrl => return QuickReplyAction::Expand,
+            KeyCode::Char(c) if !ctrl => self.insert_char(c),
+            KeyCode::Enter => self.insert_newline(),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => {
+                if self.cursor > 0 {
+                    self.cursor -= 1;
+                } else if self.line > 0 {
+                    self.line -= 1;
+                    self.cursor = self.line_len();
+                }
+            }
+            KeyCode::Right => {
+                if self.cursor < self.line_len() {
+                    self.cursor += 1;
+                } else if self.line + 1 < self.lines.len() {
+                    self.line += 1;
+                    self.cursor = 0;
+                }
+            }
+            KeyCode::Up if self.line > 0 => {
+                self.line -= 1;
+                self.cursor = self.cursor.min(self.line_len());
+            }
+            KeyCode::Down if self.line + 1 < self.lines.len() => {
+                self.line += 1;
+                self.cursor = self.cursor.min(self.line_len());
+            }
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.line_len(),
+            _ => {}
+        }
+        QuickReplyAction::Continue
+    }
+
+    fn line_len(&self) -> usize {
+        self.lines[self.line].chars().count()
+    }
+
+    /// Byte offset of a character position in the current line
+    fn byte_offset(&self, chars: usize) -> usize {
+        let line = &self.lines[self.line];
+        line.char_indices()
+            .nth(chars)
+            .map(|(offset, _)| offset)
+            .unwrap_or(line.len())
+    }
+
+    fn insert_char(&mut self, c: char) {
+        let offset = self.byte_offset(self.cursor);
+        self.lines[self.line].insert(offset, c);
+        self.cursor += 1;
+    }
+
+    fn insert_newline(&mut self) {
+        let offset = self.byte_offset(self.cursor);
+        let rest = self.lines[self.line].split_off(offset);
+        self.line += 1;
+        self.lines.insert(self.line, rest);
+        self.cursor = 0;
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let offset = self.byte_offset(self.cursor);
+            self.lines[self.line].remove(offset);
+        } else if self.line > 0 {
+            let current = self.lines.remove(self.line);
+            self.line -= 1;
+            self.cursor = self.line_len();
+            self.lines[self.line].push_str(&current);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.line_len() {
+            let offset = self.byte_offset(self.cursor);
+            self.lines[self.line].remove(offset);
+        } else if self.line + 1 < self.lines.len() {
+            let next = self.lines.remove(self.line + 1);
+            self.lines[self.line].push_str(&next);
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        frame.render_widget(Clear, area);
+
+        let block = Block::default()
+            .title(format!(" Quick reply · {} ", self.subject))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.colors.palette.accent));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let mut recipients = vec![Span::styled("To: ", label), Span::raw(self.to.clone())];
+        if !self.cc.is_empty() {
+            recipients.push(Span::styled("  Cc: ", label));
+            recipients.push(Span::raw(self.cc.clone()));
+        }
+
+        let mut lines = vec![Line::from(recipients)];
+        let visible = inner.height.saturating_sub(2).max(1) as usize;
+        let first = (self.line + 1).saturating_sub(visible);
+        let misspelled = Style::default()
+            .fg(theme.colors.palette.error)
+            .add_modifier(Modifier::UNDERLINED);
+        for (index, line) in self.lines.iter().enumerate().skip(first).take(visible) {
+            let cursor = (index == self.line).then_some(self.cursor);
+            lines.push(self.highlight_line(line, cursor, misspelled));
+        }
+        while lines.len() < visible + 1 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            "Ctrl+S: send · Ctrl+E: full compose · Esc: cancel",
+            Style::default().fg(theme.colors.palette.text_muted),
+        )));
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    /// A body line with misspelled words marked and the cursor drawn
+    fn highlight_line(
+        &self,
+        line: &str,
+        cursor: Option<usize>,
+        misspelled: Style,
+    ) -> Line<'static> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut styles = vec![Style::default(); chars.len()];
+
+        let mut start = 0;
+        while start < chars.len() {
+            if !chars[start].is_alphanumeric() {
+                start += 1;
+                continue;
+            }
+            let end = (start..chars.len())
+                .find(|&i| !chars[i].is_alphanumeric() && chars[i] != '\'')
+                .unwrap_or(chars.len());
+            let word: String = chars[start..end].iter().collect();
+            if !self.spell_checker.check_word(word.trim_end_matches('\'')) {
+                styles[start..end].fill(misspelled);
+            }
+            start = end;
+        }
+
+        let mut cells: Vec<(char, Style)> = chars.into_iter().zip(styles).collect();
+        if let Some(cursor) = cursor {
+            if cursor == cells.len() {
+                cells.push((' ', Style::default()));
+            }
+            cells[cursor].1 = cells[cursor].1.add_modifier(Modifier::REVERSED);
+        }
+
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        let mut text = String::new();
+        let mut current = None;
+        for (c, style) in cells {
+            if current.is_some_and(|s| s != style) {
+                spans.push(Span::styled(std::mem::take(&mut text), current.unwrap()));
+            }
+            current = Some(style);
+            text.push(c);
+        }
+        if let Some(style) = current {
+            spans.push(Span::styled(text, style));
+        }
+        Line::from(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEvent;
+
+    fn message() -> StoredMessage {
+        let date = chrono::Utc::now();
+        StoredMessage {
+            id: uuid::Uuid::new_v4(),
+            account_id: "work".to_string(),
+            folder_name: "INBOX".to_string(),
+            imap_uid: 1,
+            message_id: Some("<orig@example.com>".to_string()),
+            thread_id: None,
+            in_reply_to: None,
+            references: vec!["<root@example.com>".to_string()],
+            subject: "Hi".to_string(),
+            from_addr: "alice@example.com".to_string(),
+            from_name: None,
+            to_addrs: vec!["me@example.com".to_string()],
+            cc_addrs: Vec::new(),
+            bcc_addrs: Vec::new(),
+            reply_to: None,
+            date,
+            body_text: None,
+            body_html: None,
+            attachments: Vec::new(),
+            flags: Vec::new(),
+            labels: Vec::new(),
+            size: None,
+            priority: None,
+            created_at: date,
+            updated_at: date,
+            last_synced: date,
+            sync_version: 1,
+            is_draft: false,
+            is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
+        }
+    }
+
+    fn recipients() -> ReplyRecipients {
+        ReplyRecipients {
+            to: vec!["alice@example.com".to_string()],
+            cc: Vec::new(),
+            reason: "the sender".to_string(),
+        }
+    }
+
+    fn type_text(reply: &mut QuickReply, text: &str) {
+        for c in text.chars() {
+            let code = if c == '\n' { KeyCode::Enter } else { KeyCode::Char(c) };
+            reply.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_quick_reply_is_threaded() {
+        let mut reply = QuickReply::new(message(), &recipients(), "Re: Hi".to_string());
+        type_text(&mut reply, "Thanks\nSee you");
+
+        let data = reply.compose_data();
+        assert_eq!(data.to, "alice@example.com");
+        assert_eq!(data.body, "Thanks\nSee you");
+        assert_eq!(data.in_reply_to.as_deref(), Some("<orig@example.com>"));
+        assert_eq!(
+            data.references.as_deref(),
+            Some("<root@example.com> <orig@example.com>")
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_empty_quick_reply_does_not_send() {
+        let mut reply = QuickReply::new(message(), &recipients(), "Re: Hi".to_string());
+        let send = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        assert_eq!(reply.handle_key(send), QuickReplyAction::Continue);
+
+        type_text(&mut reply, "ok");
+        assert_eq!(reply.handle_key(send), QuickReplyAction::Send);
+    }
+}
@@ -0,0 +1,281 @@
+//! Folding of quoted text in the email viewer
+//!
+//! Runs of quoted lines at least `threshold` long collapse into a single
+//! marker line. Expanding a run shows its text with deeper quotes inside it
+//! still folded, so a long thread opens one level at a time. A forwarded
+//! message, from its `Forwarded message` separator to the end of the body,
+//! folds the same way.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Quote folding configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuoteFoldConfig {
+    /// Fold long quotes when a message opens
+    pub enabled: bool,
+    /// Fewest lines a quote or forwarded message needs to be folded
+    pub threshold: usize,
+}
+
+impl Default for QuoteFoldConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 8,
+        }
+    }
+}
+
+impl QuoteFoldConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "quote_fold.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Length from which runs are folded; nothing folds while disabled
+    pub fn fold_threshold(&self) -> usize {
+        if self.enabled {
+            self.threshold.max(1)
+        } else {
+            usize::MAX
+        }
+    }
+}
+
+/// A foldable run of body lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QuoteFold {
+    /// Index of the run's first line in the body
+    pub start: usize,
+    /// Quote depth of the run; 0 for a forwarded message
+    pub depth: usize,
+}
+
+/// A body line to show, or a marker standing in for a folded run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FoldedLine {
+    Line(usize),
+    Folded { fold: QuoteFold, lines: usize },
+}
+
+impl FoldedLine {
+    /// Text of a folded run's marker
+    pub fn marker_text(fold: QuoteFold, lines: usize) -> String {
+        let kind = if fold.depth == 0 {
+            "forwarded"
+        } else {
+            "quoted"
+        };
+        format!("[… {} {} lines …]", lines, kind)
+    }
+}
+
+/// Number of `>` markers a line starts with, spaces between them allowed
+pub fn quote_depth(line: &str) -> usize {
+    line.trim_start()
+        .chars()
+        .take_while(|c| *c == '>' || *c == ' ')
+        .filter(|c| *c == '>')
+        .count()
+}
+
+/// Separator above a forwarded message, as written by us and common clients
+fn is_forward_separator(line: &str) -> bool {
+    let line = line.trim();
+    let lower = line.to_lowercase();
+    (line.starts_with("-----")
+        && (lower.contains("forwarded message") || lower.contains("original message")))
+        || lower == "begin forwarded message:"
+}
+
+/// Lines of `lines` to show, with runs not in `expanded` folded
+pub fn fold_lines(
+    lines: &[&str],
+    threshold: usize,
+    expanded: &HashSet<QuoteFold>,
+) -> Vec<FoldedLine> {
+    let mut output = Vec::new();
+    match lines.iter().position(|line| is_forward_separator(line)) {
+        Some(separator) => {
+            fold_quotes(lines, 0..separator, 1, threshold, expanded, &mut output);
+            output.push(FoldedLine::Line(separator));
+
+            let forwarded = separator + 1..lines.len();
+            let fold = QuoteFold {
+                start: forwarded.start,
+                depth: 0,
+            };
+            if forwarded.len() >= threshold && !expanded.contains(&fold) {
+                output.push(FoldedLine::Folded {
+                    fold,
+                    lines: forwarded.len(),
+                });
+            } else {
+                fold_quotes(lines, forwarded, 1, threshold, expanded, &mut output);
+            }
+        }
+        None => fold_quotes(lines, 0..lines.len(), 1, threshold, expanded, &mut output),
+    }
+    output
+}
+
+/// Fold the runs of lines quoted at least `depth` deep within `range`
+fn fold_quotes(
+    lines: &[&str],
+    range: Range<usize>,
+    depth: usize,
+    threshold: usize,
+    expanded: &HashSet<QuoteFold>,
+    output: &mut Vec<FoldedLine>,
+) {
+    let mut index = range.start;
+    while index < range.end {
+        if quote_depth(lines[index]) < depth {
+            output.push(FoldedLine::Line(index));
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < range.end && quote_depth(lines[index]) >= depth {
+            index += 1;
+        }
+        let fold = QuoteFold { start, depth };
+        if index - start >= threshold && !expanded.contains(&fold) {
+            output.push(FoldedLine::Folded {
+                fold,
+                lines: index - start,
+            });
+        } else {
+            fold_quotes(lines, start..index, depth + 1, threshold, expanded, output);
+        }
+    }
+}
+
+/// Every run of `lines` that folds at `threshold`, whether expanded or not
+pub fn all_folds(lines: &[&str], threshold: usize) -> Vec<QuoteFold> {
+    let mut expanded = HashSet::new();
+    loop {
+        let folded: Vec<QuoteFold> = fold_lines(lines, threshold, &expanded)
+            .into_iter()
+            .filter_map(|line| match line {
+                FoldedLine::Folded { fold, .. } => Some(fold),
+                FoldedLine::Line(_) => None,
+            })
+            .collect();
+        if folded.is_empty() {
+            return expanded.into_iter().collect();
+        }
+        expanded.extend(folded);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_depth() {
+        assert_eq!(quote_depth("plain"), 0);
+        assert_eq!(quote_depth("> one"), 1);
+        assert_eq!(quote_depth(">> two"), 2);
+        assert_eq!(quote_depth("> > two"), 2);
+        assert_eq!(quote_depth("  >"), 1);
+    }
+
+    #[test]
+    fn test_long_quotes_fold_one_level_at_a_time() {
+        let lines = [
+            "Sounds good.",
+            "",
+            "On Monday, Sam wrote:",
+            "> Can we move the release?",
+            "> It clashes with the freeze.",
+            ">> Release is on Friday.",
+            ">> Please test the RC.",
+            ">> Thanks.",
+            "> Let me know.",
+        ];
+
+        let folded = fold_lines(&lines, 3, &HashSet::new());
+        let outer = QuoteFold { start: 3, depth: 1 };
+        assert_eq!(folded.len(), 4);
+        assert_eq!(
+            folded[3],
+            FoldedLine::Folded {
+                fold: outer,
+                lines: 6
+            }
+        );
+
+        let expanded = HashSet::from([outer]);
+        let folded = fold_lines(&lines, 3, &expanded);
+        let inner = QuoteFold { start: 5, depth: 2 };
+        assert_eq!(
+            folded[3..],
+            [
+                FoldedLine::Line(3),
+                FoldedLine::Line(4),
+                FoldedLine::Folded {
+                    fold: inner,
+                    lines: 3
+                },
+                FoldedLine::Line(8),
+            ]
+        );
+
+        // Short quotes stay as they are
+        assert!(fold_lines(&lines, 7, &HashSet::new())
+            .iter()
+            .all(|line| matches!(line, FoldedLine::Line(_))));
+        assert_eq!(all_folds(&lines, 3).len(), 2);
+    }
+
+    #[test]
+    fn test_forwarded_message_folds_to_the_end() {
+        let lines = [
+            "FYI",
+            "---------- Forwarded message ----------",
+            "From: sam@example.com",
+            "Subject: Report",
+            "",
+            "The report is attached.",
+        ];
+
+        let folded = fold_lines(&lines, 4, &HashSet::new());
+        let fold = QuoteFold { start: 2, depth: 0 };
+        assert_eq!(
+            folded,
+            [
+                FoldedLine::Line(0),
+                FoldedLine::Line(1),
+                FoldedLine::Folded { fold, lines: 4 },
+            ]
+        );
+        assert_eq!(FoldedLine::marker_text(fold, 4), "[… 4 forwarded lines …]");
+
+        let folded = fold_lines(&lines, 4, &HashSet::from([fold]));
+        assert_eq!(folded.len(), lines.len());
+    }
+
+    #[test]
+    fn test_disabled_config_folds_nothing() {
+        let config = QuoteFoldConfig {
+            enabled: false,
+            ..QuoteFoldConfig::default()
+        };
+        assert_eq!(config.fold_threshold(), usize::MAX);
+    }
+}
@@ -0,0 +1,336 @@
+//! Reflowing plain text in the reading pane
+//!
+//! Hard-wrapped mail is joined back into paragraphs and wrapped again at the
+//! reading column, so 72-column text doesn't break into ragged halves in a
+//! narrow pane and long unwrapped lines don't run across a wide one. A line
+//! is joined to the one before it only when its first word would not have
+//! fit there, so short lines such as sign-offs stay on their own.
+//!
+//! Quoted lines keep their `>` prefix and are only joined at the same quote
+//! depth. Code is left as sent: indented lines, fenced blocks, everything
+//! from a `diff --git` line on, and the signature. Bodies that arrived as
+//! `format=flowed` are already one line per paragraph, so their lines are
+//! wrapped but never joined.
+
+use super::text_width::display_width;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Reading pane reflow configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReflowConfig {
+    /// Show plain text reflowed when a message opens
+    pub enabled: bool,
+    /// Column paragraphs are wrapped at; 0 wraps at the width of the pane
+    pub column: usize,
+}
+
+impl Default for ReflowConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            column: 72,
+        }
+    }
+}
+
+impl ReflowConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "reflow.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Column to wrap at in a pane `pane_width` cells wide
+    pub fn wrap_column(&self, pane_width: usize) -> usize {
+        match self.column {
+            0 => pane_width,
+            column => column.min(pane_width),
+        }
+    }
+}
+
+/// A paragraph being collected from consecutive lines
+struct Paragraph {
+    /// Quote markers as written on the first line, e.g. `"> > "`
+    prefix: String,
+    depth: usize,
+    /// Indent of wrapped lines after the first, under a list item's text
+    hang: usize,
+    text: String,
+    /// Width of the last source line, to tell whether the next one was
+    /// broken off it
+    last_width: usize,
+}
+
+/// Split a line into its quote markers, quote depth and text
+///
+/// One space after each `>` belongs to the markers; more than that is
+/// indentation of the quoted text.
+fn split_quote(line: &str) -> (&str, usize, &str) {
+    let mut depth = 0;
+    let mut rest = line;
+    while let Some(after) = rest.strip_prefix('>') {
+        depth += 1;
+        rest = after.strip_prefix(' ').unwrap_or(after);
+    }
+    (&line[..line.len() - rest.len()], depth, rest)
+}
+
+/// Width of a list marker such as `- `, `* ` or `12. ` starting the text
+fn list_marker_width(text: &str) -> Option<usize> {
+    if ["- ", "* ", "+ ", "• "]
+        .iter()
+        .any(|marker| text.starts_with(marker))
+    {
+        return Some(2);
+    }
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    let after = &text[digits..];
+    (digits > 0 && (after.starts_with(". ") || after.starts_with(") "))).then_some(digits + 2)
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Rules such as `---` or `===`, which are never joined to their neighbours
+fn is_rule(text: &str) -> bool {
+    let text = text.trim();
+    text.len() >= 3 && text.chars().all(|c| "-_=*~#".contains(c))
+}
+
+/// Which lines are left exactly as sent
+///
+/// A patch runs from its `diff --git` line, or from the `---` line before
+/// its diffstat, to the end; so does the signature.
+fn verbatim_lines(lines: &[&str]) -> Vec<bool> {
+    let tail_start = lines
+        .iter()
+        .position(|line| *line == "-- " || line.starts_with("diff --git "))
+        .map(
+            |start| match lines[..start].iter().rposition(|line| *line == "---") {
+                Some(separator) if lines[start] != "-- " => separator,
+                _ => start,
+            },
+        )
+        .unwrap_or(lines.len());
+
+    let mut fenced = false;
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let fence = is_fence(line);
+            let verbatim = index >= tail_start || fenced || fence;
+            fenced ^= fence;
+            let (_, _, content) = split_quote(line);
+            verbatim || content.starts_with("    ") || content.starts_with('\t')
+        })
+        .collect()
+}
+
+/// Wrap a paragraph's text at `column`, repeating the quote prefix on every
+/// line. Words longer than the room stay whole.
+fn wrap_paragraph(paragraph: &Paragraph, column: usize, output: &mut Vec<String>) {
+    let room = column
+        .saturating_sub(display_width(&paragraph.prefix))
+        .max(1);
+    let indent = " ".repeat(paragraph.hang);
+
+    let mut current = String::new();
+    let mut width = 0;
+    for word in paragraph.text.split_whitespace() {
+        let word_width = display_width(word);
+        if current.is_empty() {
+            current.push_str(word);
+            width = word_width;
+        } else if width + 1 + word_width <= room {
+            current.push(' ');
+            current.push_str(word);
+            width += 1 + word_width;
+        } else {
+            output.push(format!("{}{}", paragraph.prefix, current));
+            // Continuation lines start under a list item's text
+            current = format!("{}{}", indent, word);
+            width = paragraph.hang + word_width;
+        }
+    }
+    output.push(format!("{}{}", paragraph.prefix, current));
+}
+
+/// Reflow a plain text body to `column`
+///
+/// `flowed` marks a body decoded from `format=flowed`, whose lines are
+/// already whole paragraphs.
+pub fn reflow(text: &str, column: usize, flowed: bool) -> Vec<String> {
+    let column = column.max(1);
+    let lines: Vec<&str> = text.lines().collect();
+    let verbatim = verbatim_lines(&lines);
+    // Hard-wrapped text was broken at roughly its longest prose line
+    let break_width = lines
+        .iter()
+        .zip(&verbatim)
+        .filter(|(_, verbatim)| !**verbatim)
+        .map(|(line, _)| display_width(line))
+        .max()
+        .unwrap_or(0);
+
+    let mut output = Vec::new();
+    let mut paragraph: Option<Paragraph> = None;
+    for (line, verbatim) in lines.into_iter().zip(verbatim) {
+        let (prefix, depth, content) = split_quote(line);
+        if verbatim || is_rule(content) {
+            flush(paragraph.take(), column, &mut output);
+            output.push(line.to_string());
+            continue;
+        }
+        if content.trim().is_empty() {
+            flush(paragraph.take(), column, &mut output);
+            output.push(prefix.trim_end().to_string());
+            continue;
+        }
+
+        let list_marker = list_marker_width(content.trim_start());
+        let first_word = content.split_whitespace().next().map_or(0, display_width);
+        match paragraph.as_mut() {
+            // Joined only when the first word would not have fit on the line before
+            Some(open)
+                if !flowed
+                    && list_marker.is_none()
+                    && open.depth == depth
+                    && open.last_width + 1 + first_word > break_width =>
+            {
+                open.text.push(' ');
+                open.text.push_str(content.trim());
+                open.last_width = display_width(line);
+            }
+            _ => {
+                flush(paragraph.take(), column, &mut output);
+                paragraph = Some(Paragraph {
+                    prefix: prefix.to_string(),
+                    depth,
+                    hang: list_marker.unwrap_or(0),
+                    text: content.trim_end().to_string(),
+                    last_width: display_width(line),
+                });
+            }
+        }
+    }
+    flush(paragraph, column, &mut output);
+
+    output
+}
+
+fn flush(paragraph: Option<Paragraph>, column: usize, output: &mut Vec<String>) {
+    if let Some(paragraph) = paragraph {
+        wrap_paragraph(&paragraph, column, output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reflow_joins_hard_wrapped_paragraphs() {
+        let text = "The build broke after the last merge because the\n\
+                    lockfile was regenerated with a newer toolchain\n\
+                    than CI uses.\n\
+                    \n\
+                    Thanks,\n\
+                    Sam";
+        assert_eq!(
+            reflow(text, 30, false),
+            vec![
+                "The build broke after the last",
+                "merge because the lockfile was",
+                "regenerated with a newer",
+                "toolchain than CI uses.",
+                "",
+                "Thanks,",
+                "Sam",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reflow_keeps_quotes_code_and_patches() {
+        let text = "> > an old reply that was\n\
+                    > > wrapped by someone else\n\
+                    > newer quoted text\n\
+                    \n\
+                    Try this:\n\
+                    \n\
+                    \x20   cargo build --release --locked\n\
+                    ```\n\
+                    let x = 1;   // not reflowed\n\
+                    ```\n\
+                    - a list item that wraps\n\
+                    ---\n\
+                    diff --git a/src/lib.rs b/src/lib.rs\n\
+                    +fn added() {}";
+        assert_eq!(
+            reflow(text, 20, false),
+            vec![
+                "> > an old reply",
+                "> > that was wrapped",
+                "> > by someone else",
+                "> newer quoted text",
+                "",
+                "Try this:",
+                "",
+                "    cargo build --release --locked",
+                "```",
+                "let x = 1;   // not reflowed",
+                "```",
+                "- a list item that",
+                "  wraps",
+                "---",
+                "diff --git a/src/lib.rs b/src/lib.rs",
+                "+fn added() {}",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reflow_wraps_flowed_paragraphs_without_joining() {
+        let text = "One flowed paragraph that arrived as a single long line.\n\
+                    Best regards,\n\
+                    -- \n\
+                    Sam Example, a signature line that stays long";
+        assert_eq!(
+            reflow(text, 24, true),
+            vec![
+                "One flowed paragraph",
+                "that arrived as a single",
+                "long line.",
+                "Best regards,",
+                "-- ",
+                "Sam Example, a signature line that stays long",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_column() {
+        let config = ReflowConfig::default();
+        assert_eq!(config.wrap_column(120), 72);
+        assert_eq!(config.wrap_column(50), 50);
+
+        let pane = ReflowConfig {
+            column: 0,
+            ..ReflowConfig::default()
+        };
+        assert_eq!(pane.wrap_column(120), 120);
+    }
+}
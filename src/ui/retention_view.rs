@@ -0,0 +1,251 @@
+//! Retention preview and undo
+//!
+//! Before the first retention pass takes effect, this popup shows which
+//! messages the configured rules would remove so the user can accept the
+//! rules. Afterwards it lists messages that were soft-deleted and are still
+//! within their grace window, any of which can be restored.
+
+use crate::email::{PendingRetention, RetentionAction, RetentionPlan};
+use crate::theme::Theme;
+use chrono::{DateTime, Local, Utc};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use uuid::Uuid;
+
+/// Popup for the retention dry-run preview and pending removals
+pub struct RetentionView {
+    /// Dry-run result awaiting acceptance
+    preview: Option<RetentionPlan>,
+    pending: Vec<PendingRetention>,
+    grace_days: u32,
+    state: ListState,
+}
+
+impl RetentionView {
+    pub fn new(
+        preview: Option<RetentionPlan>,
+        pending: Vec<PendingRetention>,
+        grace_days: u32,
+    ) -> Self {
+        let mut view = Self {
+            preview,
+            pending,
+            grace_days,
+            state: ListState::default(),
+        };
+        if view.row_count() > 0 {
+            view.state.select(Some(0));
+        }
+        view
+    }
+
+    /// Whether the popup is showing a dry run that can be accepted
+    pub fn is_preview(&self) -> bool {
+        self.preview.is_some()
+    }
+
+    fn row_count(&self) -> usize {
+        match self.preview {
+            Some(ref plan) => plan.len(),
+            None => self.pending.len(),
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.row_count();
+        if count == 0 {
+            return;
+        }
+        let next = self
+            .state
+            .selected()
+            .map_or(0, |selected| (selected + 1) % count);
+        self.state.select(Some(next));
+    }
+
+    pub fn select_previous(&mut self) {
+        let count = self.row_count();
+        if count == 0 {
+            return;
+        }
+        let previous = match self.state.selected() {
+            Some(0) | None => count - 1,
+            Some(selected) => selected - 1,
+        };
+        self.state.select(Some(previous));
+    }
+
+    /// The pending removal under the cursor, outside of preview mode
+    pub fn selected_pending(&self) -> Option<&PendingRetention> {
+        if self.is_preview() {
+            return None;
+        }
+        self.pending.get(self.state.selected()?)
+    }
+
+    /// Drop a restored message from the list
+    pub fn remove_pending(&mut self, message_id: Uuid) {
+        self.pending.retain(|p| p.message_id != message_id);
+        match self.state.selected() {
+            _ if self.pending.is_empty() => self.state.select(None),
+            Some(selected) if selected >= self.pending.len() => {
+                self.state.select(Some(self.pending.len() - 1))
+            }
+            _ => {}
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(70, 70, area);
+        frame.render_widget(Clear, popup_area);
+
+        let title = if self.is_preview() {
+            " Retention Preview (dry run) "
+        } else {
+            " Pending Removal "
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let summary = self.summary_lines(label);
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(summary.len() as u16),
+                Constraint::Min(3),
+                Constraint::Length(2),
+            ])
+            .split(inner);
+        frame.render_widget(Paragraph::new(summary), sections[0]);
+
+        let items: Vec<ListItem> = match self.preview {
+            Some(ref plan) => plan
+                .candidates
+                .iter()
+                .map(|c| row(c.action, &c.folder_name, &c.from_addr, &c.subject, date(c.date), label))
+                .collect(),
+            None => self
+                .pending
+                .iter()
+                .map(|p| {
+                    let expires = format!("until {}", date(p.expires_at(self.grace_days)));
+                    row(p.action, &p.folder_name, &p.from_addr, &p.subject, expires, label)
+                })
+                .collect(),
+        };
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(theme.colors.palette.selection)
+                .fg(theme.colors.palette.selection_text)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(list, sections[1], &mut self.state);
+
+        let help = if self.is_preview() {
+            "j/k: move · Enter: accept rules and start cleaning up · Esc: close (stay in dry run)"
+        } else {
+            "j/k: move · u/Space: restore message · Esc: close"
+        };
+        let help = Paragraph::new(Line::from(Span::styled(help, label)))
+            .block(Block::default().borders(Borders::TOP));
+        frame.render_widget(help, sections[2]);
+    }
+
+    fn summary_lines(&self, label: Style) -> Vec<Line<'static>> {
+        match self.preview {
+            Some(ref plan) if plan.is_empty() => vec![Line::from(Span::styled(
+                "No messages match the retention rules right now.",
+                label,
+            ))],
+            Some(ref plan) => {
+                let mut lines = vec![Line::from(Span::styled(
+                    format!(
+                        "Accepting moves these {} messages to pending removal; they can be restored for {} days:",
+                        plan.len(),
+                        self.grace_days
+                    ),
+                    label,
+                ))];
+                for (account_id, folder, action, count) in plan.summary() {
+                    lines.push(Line::from(format!(
+                        "  {} {} from {}/{}",
+                        verb(action),
+                        count,
+                        account_id,
+                        folder
+                    )));
+                }
+                lines
+            }
+            None if self.pending.is_empty() => vec![Line::from(Span::styled(
+                "Nothing is pending removal.",
+                label,
+            ))],
+            None => vec![Line::from(Span::styled(
+                format!(
+                    "{} messages are hidden and will be removed when their grace window ends:",
+                    self.pending.len()
+                ),
+                label,
+            ))],
+        }
+    }
+}
+
+fn verb(action: RetentionAction) -> &'static str {
+    match action {
+        RetentionAction::Archive => "Archive",
+        RetentionAction::Delete => "Delete",
+    }
+}
+
+fn date(date: DateTime<Utc>) -> String {
+    date.with_timezone(&Local).format("%Y-%m-%d").to_string()
+}
+
+fn row(
+    action: RetentionAction,
+    folder: &str,
+    from: &str,
+    subject: &str,
+    when: String,
+    label: Style,
+) -> ListItem<'static> {
+    ListItem::new(Line::from(vec![
+        Span::raw(format!("{:<8}", verb(action))),
+        Span::styled(format!("{:<16} ", folder), label),
+        Span::raw(format!("{} — {}", from, subject)),
+        Span::styled(format!("  {}", when), label),
+    ]))
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,210 @@
+//! Choosing a date and time in one popup
+//!
+//! Pairs the calendar `DatePicker` with the `TimePicker`. Tab moves between
+//! the two, and the chosen local time is handed back in UTC on Enter. Used to
+//! snooze messages and to send mail later.
+
+use crate::theme::Theme;
+use crate::ui::date_picker::DatePicker;
+use crate::ui::time_picker::TimePicker;
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Hour of the default "tomorrow morning" time
+const MORNING_HOUR: u32 = 9;
+
+/// Tomorrow at 09:00, relative to a local time; the usual choice
+pub fn tomorrow_morning(now: NaiveDateTime) -> NaiveDateTime {
+    let tomorrow = now.date() + ChronoDuration::days(1);
+    tomorrow.and_time(NaiveTime::from_hms_opt(MORNING_HOUR, 0, 0).unwrap())
+}
+
+/// Outcome of a key press in the popup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchedulePickerAction {
+    Continue,
+    Cancel,
+    Pick(DateTime<Utc>),
+}
+
+/// Date and time popup
+pub struct SchedulePicker {
+    title: &'static str,
+    /// Label in front of the chosen time, e.g. "Send at"
+    caption: &'static str,
+    subject: String,
+    date_picker: DatePicker,
+    time_picker: TimePicker,
+    editing_time: bool,
+}
+
+impl SchedulePicker {
+    /// Start at tomorrow morning
+    pub fn new(title: &'static str, caption: &'static str, subject: String) -> Self {
+        let initial = tomorrow_morning(Local::now().naive_local());
+        let mut date_picker = DatePicker::new(initial.date());
+        let mut time_picker = TimePicker::new(initial.time());
+        date_picker.open();
+        time_picker.open();
+
+        Self {
+            title,
+            caption,
+            subject,
+            date_picker,
+            time_picker,
+            editing_time: false,
+        }
+    }
+
+    /// The chosen local date and time
+    pub fn selected(&self) -> NaiveDateTime {
+        self.date_picker
+            .get_selected_date()
+            .and_time(self.time_picker.get_selected_time())
+    }
+
+    /// The chosen time in UTC; `None` for a local time skipped by a DST change
+    pub fn until(&self) -> Option<DateTime<Utc>> {
+        Local
+            .from_local_datetime(&self.selected())
+            .earliest()
+            .map(|local| local.with_timezone(&Utc))
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> SchedulePickerAction {
+        let key = match key {
+            KeyCode::Esc => return SchedulePickerAction::Cancel,
+            KeyCode::Enter => {
+                return match self.until() {
+                    Some(until) => SchedulePickerAction::Pick(until),
+                    None => SchedulePickerAction::Continue,
+                }
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.editing_time = !self.editing_time;
+                return SchedulePickerAction::Continue;
+            }
+            KeyCode::Left => 'h',
+            KeyCode::Right => 'l',
+            KeyCode::Up => 'k',
+            KeyCode::Down => 'j',
+            KeyCode::Char(c) => c,
+            _ => return SchedulePickerAction::Continue,
+        };
+
+        if self.editing_time {
+            if key.is_ascii_digit() {
+                self.time_picker.handle_number(key);
+            } else {
+                self.time_picker.handle_key(key);
+            }
+        } else if key.is_ascii_digit() {
+            self.date_picker.handle_number(key);
+        } else {
+            self.date_picker.handle_key(key);
+        }
+        SchedulePickerAction::Continue
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(64, 19, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Subject and chosen time
+                Constraint::Min(11),   // Pickers
+                Constraint::Length(1), // Hints
+            ])
+            .split(inner);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        let summary = vec![
+            Line::from(Span::styled(self.subject.clone(), label)),
+            Line::from(vec![
+                Span::raw(format!("{}: ", self.caption)),
+                Span::styled(
+                    self.selected().format("%a %-d %b %Y, %H:%M").to_string(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+            ]),
+        ];
+        frame.render_widget(Paragraph::new(summary), chunks[0]);
+
+        let pickers = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(chunks[1]);
+        self.date_picker.render(frame, pickers[0], theme);
+        self.time_picker.render(frame, pickers[1], theme);
+
+        let focus = if self.editing_time { "time" } else { "date" };
+        let hints = Paragraph::new(Line::from(Span::styled(
+            format!("Editing {} · Tab: date/time  Enter: confirm  Esc: cancel", focus),
+            label,
+        )));
+        frame.render_widget(hints, chunks[2]);
+    }
+}
+
+/// Center a popup of fixed height and relative width
+fn centered_rect(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(height.min(r.height)),
+            Constraint::Min(0),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_tomorrow_morning() {
+        let evening = NaiveDate::from_ymd_opt(2024, 12, 31)
+            .unwrap()
+            .and_hms_opt(22, 15, 0)
+            .unwrap();
+        let expected = NaiveDate::from_ymd_opt(2025, 1, 1)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        assert_eq!(tomorrow_morning(evening), expected);
+
+        let early = NaiveDate::from_ymd_opt(2025, 1, 1)
+            .unwrap()
+            .and_hms_opt(6, 0, 0)
+            .unwrap();
+        assert_eq!(tomorrow_morning(early).date(), NaiveDate::from_ymd_opt(2025, 1, 2).unwrap());
+    }
+}
@@ -6,8 +6,16 @@ use ratatui::{
     Frame,
 };
 
-use crate::email::{EmailDatabase, StoredMessage};
+use crate::email::{dedup, EmailDatabase, StoredMessage};
+use crate::imap::{ImapClient, SearchCriteria};
 use crate::theme::Theme;
+use std::collections::HashSet;
+
+/// Most matches a server search fetches from one folder, newest first
+pub const SERVER_SEARCH_LIMIT: usize = 200;
+
+/// Headers fetched for server matches that were never synced
+const SERVER_FETCH_ITEMS: &[&str] = &["UID", "FLAGS", "ENVELOPE", "INTERNALDATE", "RFC822.SIZE"];
 
 /// Search query modes
 #[derive(Debug, Clone, PartialEq)]
@@ -22,6 +30,9 @@ pub enum SearchMode {
     Body,
     /// Advanced search with multiple criteria
     Advanced,
+    /// Full-text search of the live folder on the IMAP server, merged with
+    /// the local full-text results
+    Server,
 }
 
 /// Search result with highlighting information
@@ -151,6 +162,11 @@ impl SearchUI {
                 "Advanced".to_string(),
                 "Advanced search with multiple criteria".to_string(),
             ),
+            (
+                SearchMode::Server,
+                "Server".to_string(),
+                "Also search the folder on the IMAP server".to_string(),
+            ),
         ];
 
         Self {
@@ -518,6 +534,7 @@ impl SearchUI {
             SearchMode::From => "👤 From",
             SearchMode::Body => "📝 Body",
             SearchMode::Advanced => "⚙️ Advanced",
+            SearchMode::Server => "🌐 Server",
         };
 
         let mode_paragraph = Paragraph::new(mode_text)
@@ -837,6 +854,80 @@ impl SearchEngine {
         Ok(results)
     }
 
+    /// Search a folder on the IMAP server as well as the local database
+    ///
+    /// Server matches that were never synced have their headers fetched and
+    /// stored, so they can be listed; bodies are downloaded when a message is
+    /// opened. Only the newest `limit` matches are fetched. Local and server
+    /// results are merged, keeping one copy per Message-ID.
+    pub async fn search_server(
+        &self,
+        client: &mut ImapClient,
+        account_id: &str,
+        folder_name: &str,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>, Box<dyn std::error::Error>> {
+        let local = self
+            .search(account_id, query, &SearchMode::Server, Some(limit as u32))
+            .await?;
+
+        client.select_folder(folder_name).await?;
+        let mut uids = client.uid_search(&Self::server_criteria(query)).await?;
+        uids.sort_unstable();
+        let newest = &uids[uids.len().saturating_sub(limit)..];
+
+        let mut server_messages = Vec::new();
+        let mut missing = Vec::new();
+        for &uid in newest.iter().rev() {
+            match self.database.get_message_by_uid(account_id, folder_name, uid).await? {
+                Some(message) => server_messages.push(message),
+                None => missing.push(uid),
+            }
+        }
+        for uids in missing.chunks(50) {
+            let uid_set = uids
+                .iter()
+                .map(|uid| uid.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            for fetched in client.uid_fetch_messages(&uid_set, SERVER_FETCH_ITEMS).await? {
+                let message = StoredMessage::from_imap_message(
+                    &fetched,
+                    account_id.to_string(),
+                    folder_name.to_string(),
+                );
+                self.database.store_message(&message).await?;
+                server_messages.push(message);
+            }
+        }
+
+        let mut results = local;
+        for message in server_messages {
+            results.push(self.create_search_result(message, query, &SearchMode::Server).await?);
+        }
+        Ok(Self::dedupe_by_message_id(results))
+    }
+
+    /// IMAP criteria for a server search: the words anywhere in the message
+    fn server_criteria(query: &str) -> SearchCriteria {
+        // Quotes and backslashes would end the quoted IMAP string early
+        let text: String = query
+            .chars()
+            .filter(|c| *c != '"' && *c != '\\')
+            .collect();
+        SearchCriteria::Text(text.trim().to_string())
+    }
+
+    /// Keep the first result of each message, matched by Message-ID
+    fn dedupe_by_message_id(results: Vec<SearchResult>) -> Vec<SearchResult> {
+        let mut seen = HashSet::new();
+        results
+            .into_iter()
+            .filter(|result| seen.insert(dedup::dedup_key(&result.message)))
+            .collect()
+    }
+
     /// Build FTS5 query string based on search mode
     fn build_fts_query(
         &self,
@@ -846,7 +937,7 @@ impl SearchEngine {
         let sanitized_query = self.sanitize_fts_query(query)?;
 
         let fts_query = match mode {
-            SearchMode::FullText => sanitized_query,
+            SearchMode::FullText | SearchMode::Server => sanitized_query,
             SearchMode::Subject => format!("subject:{}", sanitized_query),
             SearchMode::From => format!(
                 "(from_addr:{} OR from_name:{})",
@@ -1077,3 +1168,76 @@ impl SearchEngine {
         score
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    fn result(folder: &str, uid: u32, message_id: Option<&str>) -> SearchResult {
+        let date = Utc.with_ymd_and_hms(2026, 10, 16, 9, 30, 0).unwrap();
+        SearchResult {
+            message: StoredMessage {
+                id: Uuid::new_v4(),
+                account_id: "work".to_string(),
+                folder_name: folder.to_string(),
+                imap_uid: uid,
+                message_id: message_id.map(str::to_string),
+                thread_id: None,
+                in_reply_to: None,
+                references: Vec::new(),
+                subject: format!("Invoice {}", uid),
+                from_addr: "billing@example.com".to_string(),
+                from_name: None,
+                to_addrs: Vec::new(),
+                cc_addrs: Vec::new(),
+                bcc_addrs: Vec::new(),
+                reply_to: None,
+                date,
+                body_text: None,
+                body_html: None,
+                attachments: Vec::new(),
+                flags: Vec::new(),
+                labels: Vec::new(),
+                size: None,
+                priority: None,
+                created_at: date,
+                updated_at: date,
+                last_synced: date,
+                sync_version: 1,
+                is_draft: false,
+                is_deleted: false,
+                snoozed_until: None,
+                inbox_tab: None,
+            },
+            rank: 0.0,
+            snippets: Vec::new(),
+            matched_fields: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_server_results_merge_by_message_id() {
+        let results = vec![
+            // Local matches first
+            result("INBOX", 1, Some("<a@example.com>")),
+            result("Archive", 2, Some("<b@example.com>")),
+            // Server matches: one already found locally, one never synced
+            result("Archive", 2, Some("<B@example.com>")),
+            result("Archive", 3, Some("<c@example.com>")),
+        ];
+
+        let merged = SearchEngine::dedupe_by_message_id(results);
+        let uids: Vec<u32> = merged.iter().map(|result| result.message.imap_uid).collect();
+        assert_eq!(uids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_server_criteria_strips_quotes() {
+        assert_eq!(
+            SearchEngine::server_criteria(" say \"hi\" \\ there ").to_imap_string(),
+            "TEXT \"say hi  there\""
+        );
+    }
+}
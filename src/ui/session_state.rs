@@ -0,0 +1,218 @@
+//! UI session state persistence
+//!
+//! Remembers where the user was when they quit (account, folder, selected message,
+//! focused pane, view mode and preview scroll position) so the next launch can pick
+//! up from the same place instead of resetting to the account switcher.
+
+use crate::cli::StartupMode;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use uuid::Uuid;
+
+/// Pane that had focus when the session was saved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SessionPane {
+    #[default]
+    AccountSwitcher,
+    FolderTree,
+    MessageList,
+    ContentPreview,
+}
+
+/// Top-level view that was active when the session was saved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SessionView {
+    #[default]
+    Email,
+    Calendar,
+}
+
+/// Snapshot of the UI navigation state
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// Last active account
+    pub account_id: Option<String>,
+    /// Last open folder path within the account
+    pub folder_path: Option<String>,
+    /// Database ID of the selected message
+    pub selected_message_id: Option<Uuid>,
+    /// Index of the selected message, used when the message ID is no longer present
+    pub selected_index: Option<usize>,
+    /// Focused pane in the email interface
+    pub focused_pane: SessionPane,
+    /// Active top-level view
+    pub view: SessionView,
+    /// Whether the message list was in threaded mode
+    pub threaded_view: bool,
+    /// Scroll offset of the content preview
+    pub preview_scroll: usize,
+    /// When the snapshot was taken
+    pub saved_at: Option<DateTime<Utc>>,
+}
+
+impl SessionState {
+    /// Load the saved session from the default state file, if one exists
+    pub async fn load() -> Result<Option<Self>> {
+        Self::load_from(&Self::state_file_path()?).await
+    }
+
+    /// Load a saved session from a specific path
+    pub async fn load_from(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(path).await?;
+        let state: SessionState = serde_json::from_str(&content)?;
+        Ok(Some(state))
+    }
+
+    /// Save the session to the default state file
+    pub async fn save(&self) -> Result<()> {
+        self.save_to(&Self::state_file_path()?).await
+    }
+
+    /// Save the session to a specific path
+    pub async fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut state = self.clone();
+        state.saved_at = Some(Utc::now());
+
+        let content = serde_json::to_string_pretty(&state)?;
+        fs::write(path, content).await?;
+
+        Ok(())
+    }
+
+    /// Remove the saved session so the next launch starts fresh
+    pub async fn clear() -> Result<()> {
+        let path = Self::state_file_path()?;
+        if path.exists() {
+            fs::remove_file(path).await?;
+        }
+        Ok(())
+    }
+
+    /// Get the state file path
+    fn state_file_path() -> Result<PathBuf> {
+        if let Some(config_dir) = dirs::config_dir() {
+            Ok(config_dir.join("comunicado").join("session_state.json"))
+        } else {
+            Ok(PathBuf::from(".").join("session_state.json"))
+        }
+    }
+}
+
+/// Session restore configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// Restore the previous session on launch
+    pub restore_session: bool,
+    /// Where to start when no startup flag is given
+    pub startup: StartupMode,
+    /// Account `email` startup opens; the current account when unset
+    pub startup_account: Option<String>,
+    /// Folder `email` startup opens; without one the account list has focus
+    pub startup_folder: Option<String>,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            restore_session: true,
+            startup: StartupMode::Resume,
+            startup_account: None,
+            startup_folder: None,
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "session.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_session_state_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("session_state.json");
+
+        let state = SessionState {
+            account_id: Some("work".to_string()),
+            folder_path: Some("[Gmail]/Sent Mail".to_string()),
+            selected_message_id: Some(Uuid::new_v4()),
+            selected_index: Some(4),
+            focused_pane: SessionPane::MessageList,
+            view: SessionView::Email,
+            threaded_view: true,
+            preview_scroll: 12,
+            saved_at: None,
+        };
+
+        state.save_to(&path).await.unwrap();
+        let loaded = SessionState::load_from(&path).await.unwrap().unwrap();
+
+        assert_eq!(loaded.account_id, state.account_id);
+        assert_eq!(loaded.folder_path, state.folder_path);
+        assert_eq!(loaded.selected_message_id, state.selected_message_id);
+        assert_eq!(loaded.selected_index, Some(4));
+        assert_eq!(loaded.focused_pane, SessionPane::MessageList);
+        assert!(loaded.threaded_view);
+        assert_eq!(loaded.preview_scroll, 12);
+        assert!(loaded.saved_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_missing_state_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("missing.json");
+
+        assert!(SessionState::load_from(&path).await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_partial_state_uses_defaults() {
+        let state: SessionState = serde_json::from_str(r#"{"account_id":"personal"}"#).unwrap();
+
+        assert_eq!(state.account_id.as_deref(), Some("personal"));
+        assert_eq!(state.focused_pane, SessionPane::AccountSwitcher);
+        assert_eq!(state.view, SessionView::Email);
+        assert_eq!(state.preview_scroll, 0);
+    }
+
+    #[test]
+    fn test_session_config_default_enabled() {
+        let config: SessionConfig = toml::from_str("").unwrap();
+        assert!(config.restore_session);
+        assert_eq!(config.startup, StartupMode::Resume);
+
+        let config: SessionConfig =
+            toml::from_str("startup = \"email\"\nstartup_folder = \"INBOX\"").unwrap();
+        assert_eq!(config.startup, StartupMode::Email);
+        assert_eq!(config.startup_folder.as_deref(), Some("INBOX"));
+        assert!(toml::from_str::<SessionConfig>("startup = \"default\"").is_err());
+    }
+}
@@ -0,0 +1,40 @@
+//! Picking when a snoozed message comes back
+
+use crate::theme::Theme;
+use crate::ui::schedule_picker::{SchedulePicker, SchedulePickerAction};
+use crossterm::event::KeyCode;
+use ratatui::{layout::Rect, Frame};
+use uuid::Uuid;
+
+/// Popup for choosing when a snoozed message returns to the INBOX
+pub struct SnoozePicker {
+    account_id: String,
+    message_id: Uuid,
+    picker: SchedulePicker,
+}
+
+impl SnoozePicker {
+    pub fn new(account_id: String, message_id: Uuid, subject: String) -> Self {
+        Self {
+            account_id,
+            message_id,
+            picker: SchedulePicker::new("Snooze Until", "Back in INBOX", subject),
+        }
+    }
+
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    pub fn message_id(&self) -> Uuid {
+        self.message_id
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) -> SchedulePickerAction {
+        self.picker.handle_key(key)
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.picker.render(frame, area, theme);
+    }
+}
@@ -0,0 +1,295 @@
+//! Special folder setup
+//!
+//! Shows the Sent, Drafts, Trash, Junk and Archive folders detected for an
+//! account next to the ones mapped in configuration. Detected folders can be
+//! accepted as they are, or another of the account's folders picked when the
+//! server's advertised roles are wrong.
+
+use crate::email::{SpecialFolder, SpecialFolderMapping};
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// One special folder role
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecialFolderRow {
+    pub kind: SpecialFolder,
+    /// Suggested from the server's attributes and folder names
+    pub detected: Option<String>,
+    /// Set in configuration; wins over the detected folder
+    pub mapped: Option<String>,
+}
+
+impl SpecialFolderRow {
+    /// The folder actually used
+    pub fn effective(&self) -> Option<&str> {
+        self.mapped.as_deref().or(self.detected.as_deref())
+    }
+}
+
+/// Popup mapping an account's special folders
+pub struct SpecialFoldersView {
+    account_id: String,
+    account_name: String,
+    /// The account's folders, offered when picking a different one
+    folders: Vec<String>,
+    rows: Vec<SpecialFolderRow>,
+    state: ListState,
+}
+
+impl SpecialFoldersView {
+    pub fn new(
+        account_id: String,
+        account_name: String,
+        folders: Vec<String>,
+        detected: &SpecialFolderMapping,
+        mapped: &SpecialFolderMapping,
+    ) -> Self {
+        let rows = SpecialFolder::ALL
+            .iter()
+            .map(|kind| SpecialFolderRow {
+                kind: *kind,
+                detected: detected.get(*kind).map(str::to_string),
+                mapped: mapped.get(*kind).map(str::to_string),
+            })
+            .collect();
+
+        let mut state = ListState::default();
+        state.select(Some(0));
+
+        Self {
+            account_id,
+            account_name,
+            folders,
+            rows,
+            state,
+        }
+    }
+
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    pub fn rows(&self) -> &[SpecialFolderRow] {
+        &self.rows
+    }
+
+    /// The mapping as currently set in the popup
+    pub fn mapping(&self) -> SpecialFolderMapping {
+        let mut mapping = SpecialFolderMapping::default();
+        for row in &self.rows {
+            mapping.set(row.kind, row.mapped.clone());
+        }
+        mapping
+    }
+
+    pub fn select_next(&mut self) {
+        let next = self
+            .state
+            .selected()
+            .map_or(0, |selected| (selected + 1) % self.rows.len());
+        self.state.select(Some(next));
+    }
+
+    pub fn select_previous(&mut self) {
+        let previous = match self.state.selected() {
+            Some(0) | None => self.rows.len() - 1,
+            Some(selected) => selected - 1,
+        };
+        self.state.select(Some(previous));
+    }
+
+    fn selected_row(&mut self) -> Option<&mut SpecialFolderRow> {
+        self.rows.get_mut(self.state.selected()?)
+    }
+
+    /// Map the selected role to the next (or previous) of the account's
+    /// folders; stepping past either end goes back to detection
+    pub fn cycle_selected(&mut self, forward: bool) -> Option<SpecialFolderMapping> {
+        // Choices are "not mapped" followed by every folder
+        let choices = self.folders.len() + 1;
+        let folders = self.folders.clone();
+        let row = self.selected_row()?;
+        let current = row
+            .mapped
+            .as_ref()
+            .and_then(|mapped| folders.iter().position(|folder| folder == mapped))
+            .map_or(0, |index| index + 1);
+        let next = if forward {
+            (current + 1) % choices
+        } else {
+            (current + choices - 1) % choices
+        };
+        row.mapped = next.checked_sub(1).map(|index| folders[index].clone());
+        Some(self.mapping())
+    }
+
+    /// Map the selected role to its detected folder
+    pub fn accept_selected(&mut self) -> Option<SpecialFolderMapping> {
+        let row = self.selected_row()?;
+        row.mapped = Some(row.detected.clone()?);
+        Some(self.mapping())
+    }
+
+    /// Map every role that isn't mapped yet to its detected folder
+    pub fn accept_all(&mut self) -> SpecialFolderMapping {
+        for row in &mut self.rows {
+            if row.mapped.is_none() {
+                row.mapped = row.detected.clone();
+            }
+        }
+        self.mapping()
+    }
+
+    /// Drop the selected role's mapping so detection applies again
+    pub fn clear_selected(&mut self) -> Option<SpecialFolderMapping> {
+        self.selected_row()?.mapped = None;
+        Some(self.mapping())
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = centered_rect(60, 50, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" Special Folders · {} ", self.account_name))
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2), // Intro
+                Constraint::Min(3),    // Roles
+                Constraint::Length(2), // Help
+            ])
+            .split(inner);
+
+        let label = Style::default().fg(theme.colors.palette.text_secondary);
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                "Mapped folders override detection. Sent and Drafts copies are only filed when mapped.",
+                label,
+            )))
+            .wrap(ratatui::widgets::Wrap { trim: true }),
+            sections[0],
+        );
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut spans = vec![Span::styled(format!("{:<9}", row.kind.label()), label)];
+                match (&row.mapped, row.effective()) {
+                    (Some(mapped), _) => {
+                        spans.push(Span::styled(
+                            mapped.clone(),
+                            Style::default()
+                                .fg(theme.colors.palette.text_primary)
+                                .add_modifier(Modifier::BOLD),
+                        ));
+                        spans.push(Span::styled("  mapped", Style::default().fg(theme.colors.palette.accent)));
+                        if row.detected.as_ref().is_some_and(|detected| detected != mapped) {
+                            spans.push(Span::styled(
+                                format!("  (detected: {})", row.detected.as_deref().unwrap_or_default()),
+                                label,
+                            ));
+                        }
+                    }
+                    (None, Some(detected)) => {
+                        spans.push(Span::raw(detected.to_string()));
+                        spans.push(Span::styled("  detected", label));
+                    }
+                    (None, None) => spans.push(Span::styled(
+                        "not found",
+                        Style::default().fg(theme.colors.palette.warning),
+                    )),
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(theme.colors.palette.selection)
+                .fg(theme.colors.palette.selection_text)
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_stateful_widget(list, sections[1], &mut self.state);
+
+        let help = Paragraph::new(Line::from(Span::styled(
+            "j/k: move · h/l: pick folder · Enter: use detected · a: use all detected · x: clear · Esc: close",
+            label,
+        )))
+        .block(Block::default().borders(Borders::TOP));
+        frame.render_widget(help, sections[2]);
+    }
+}
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_special_folders_view() {
+        let mut detected = SpecialFolderMapping::default();
+        detected.set(SpecialFolder::Sent, Some("Sent Items".to_string()));
+        detected.set(SpecialFolder::Trash, Some("Trash".to_string()));
+        let mut mapped = SpecialFolderMapping::default();
+        mapped.set(SpecialFolder::Trash, Some("Deleted".to_string()));
+
+        let folders = vec!["INBOX".to_string(), "Sent".to_string(), "Sent Items".to_string()];
+        let mut view = SpecialFoldersView::new(
+            "work".to_string(),
+            "Work".to_string(),
+            folders,
+            &detected,
+            &mapped,
+        );
+        assert_eq!(view.rows()[2].effective(), Some("Deleted"));
+
+        // Sent: pick folders in turn, wrapping back to detection
+        assert_eq!(view.cycle_selected(true).unwrap().sent.as_deref(), Some("INBOX"));
+        assert_eq!(view.cycle_selected(true).unwrap().sent.as_deref(), Some("Sent"));
+        view.cycle_selected(false);
+        assert_eq!(view.cycle_selected(false).unwrap().sent, None);
+        assert_eq!(view.cycle_selected(false).unwrap().sent.as_deref(), Some("Sent Items"));
+
+        // Drafts has nothing to accept; accepting all keeps the Trash mapping
+        view.select_next();
+        assert_eq!(view.accept_selected(), None);
+        view.clear_selected();
+        let mapping = view.accept_all();
+        assert_eq!(mapping.sent.as_deref(), Some("Sent Items"));
+        assert_eq!(mapping.drafts, None);
+        assert_eq!(mapping.trash.as_deref(), Some("Deleted"));
+    }
+}
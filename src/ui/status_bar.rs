@@ -246,6 +246,34 @@ impl StatusSegment for SearchStatusSegment {
     }
 }
 
+/// Credentials segment, shown while access tokens are refreshed in the background
+#[derive(Debug, Clone)]
+pub struct CredentialsStatusSegment {
+    pub refreshing_accounts: usize,
+}
+
+impl StatusSegment for CredentialsStatusSegment {
+    fn content(&self) -> String {
+        "⟳ Refreshing credentials…".to_string()
+    }
+
+    fn min_width(&self) -> u16 {
+        26
+    }
+
+    fn priority(&self) -> u8 {
+        85
+    }
+
+    fn is_visible(&self) -> bool {
+        self.refreshing_accounts > 0
+    }
+
+    fn custom_style(&self, theme: &Theme) -> Option<Style> {
+        Some(Style::default().fg(theme.colors.palette.info))
+    }
+}
+
 /// Professional status bar with powerline-style segments
 pub struct StatusBar {
     segments: HashMap<String, Box<dyn StatusSegment>>,
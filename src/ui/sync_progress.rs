@@ -1,4 +1,4 @@
-use crate::email::sync_engine::{SyncPhase, SyncProgress};
+use crate::email::sync_engine::{AccountSyncProgress, SyncPhase, SyncProgress};
 use crate::theme::Theme;
 use chrono::{Duration as ChronoDuration, Utc};
 use ratatui::{
@@ -97,6 +97,11 @@ impl SyncProgressOverlay {
         }
     }
 
+    /// Currently selected sync, if any
+    pub fn selected_progress(&self) -> Option<&SyncProgress> {
+        self.active_syncs.values().nth(self.selected_sync)
+    }
+
     /// Render the sync progress overlay
     pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         if !self.is_visible() {
@@ -126,11 +131,21 @@ impl SyncProgressOverlay {
             return;
         }
 
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner_area);
+
         // Split area for list and details
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
-            .split(inner_area);
+            .split(sections[0]);
+
+        let hints = Paragraph::new("↑/↓: select • p: pause/resume • x: cancel • Esc: hide")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(theme.colors.palette.text_secondary));
+        frame.render_widget(hints, sections[1]);
 
         // Render sync list
         self.render_sync_list(frame, chunks[0], theme);
@@ -152,15 +167,12 @@ impl SyncProgressOverlay {
                     SyncPhase::FetchingHeaders => "📧",
                     SyncPhase::FetchingBodies => "📄",
                     SyncPhase::ProcessingChanges => "⚙️",
+                    SyncPhase::Paused => "⏸️",
                     SyncPhase::Complete => "✅",
                     SyncPhase::Error(_) => "❌",
                 };
 
-                let progress_percent = if progress.total_messages > 0 {
-                    (progress.messages_processed * 100) / progress.total_messages
-                } else {
-                    0
-                };
+                let progress_percent = progress.percent() as u32;
 
                 let style = if index == self.selected_sync {
                     Style::default()
@@ -201,24 +213,32 @@ impl SyncProgressOverlay {
         let syncs: Vec<_> = self.active_syncs.values().collect();
 
         if let Some(progress) = syncs.get(self.selected_sync) {
+            let overall_height = if progress.account_progress.is_some() { 3 } else { 0 };
+
             // Split details area
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(6), // Progress gauge
-                    Constraint::Length(8), // Stats
-                    Constraint::Min(0),    // Phase details
+                    Constraint::Length(overall_height), // Overall account gauge
+                    Constraint::Length(3),              // Folder progress gauge
+                    Constraint::Length(9),              // Stats
+                    Constraint::Min(0),                 // Phase details
                 ])
                 .split(area);
 
+            // Render overall account progress
+            if let Some(ref account) = progress.account_progress {
+                self.render_account_gauge(frame, chunks[0], account, theme);
+            }
+
             // Render progress gauge
-            self.render_progress_gauge(frame, chunks[0], progress, theme);
+            self.render_progress_gauge(frame, chunks[1], progress, theme);
 
             // Render sync statistics
-            self.render_sync_stats(frame, chunks[1], progress, theme);
+            self.render_sync_stats(frame, chunks[2], progress, theme);
 
             // Render phase details
-            self.render_phase_details(frame, chunks[2], progress, theme);
+            self.render_phase_details(frame, chunks[3], progress, theme);
         }
     }
 
@@ -229,32 +249,62 @@ impl SyncProgressOverlay {
         progress: &SyncProgress,
         theme: &Theme,
     ) {
-        let progress_percent = if progress.total_messages > 0 {
-            ((progress.messages_processed * 100) / progress.total_messages) as u16
-        } else {
-            0
-        };
+        let progress_percent = progress.percent();
 
         let gauge_color = match progress.phase {
             SyncPhase::Complete => Color::Green,
+            SyncPhase::Paused => Color::Yellow,
             SyncPhase::Error(_) => Color::Red,
             _ => theme.colors.palette.accent,
         };
 
         let label = format!(
             "{}/{} messages ({:.1}%)",
-            progress.messages_processed, progress.total_messages, progress_percent as f32
+            progress.messages_processed, progress.total_messages, progress_percent
         );
 
         let gauge = Gauge::default()
             .block(
                 Block::default()
-                    .title("Progress")
+                    .title(format!("Folder: {}", progress.folder_name))
                     .borders(Borders::ALL)
                     .border_style(theme.get_component_style("border", false)),
             )
             .gauge_style(Style::default().fg(gauge_color))
-            .percent(progress_percent)
+            .percent(progress_percent as u16)
+            .label(label);
+
+        frame.render_widget(gauge, area);
+    }
+
+    fn render_account_gauge(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        account: &AccountSyncProgress,
+        theme: &Theme,
+    ) {
+        let percent = account.percent();
+        let label = format!(
+            "{}/{} messages ({:.1}%)",
+            account.messages_completed(),
+            account.total_messages,
+            percent
+        );
+
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .title(format!(
+                        "Overall: folder {} of {}",
+                        (account.folder_index + 1).min(account.total_folders),
+                        account.total_folders
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(theme.get_component_style("border", false)),
+            )
+            .gauge_style(Style::default().fg(theme.colors.palette.accent))
+            .percent(percent as u16)
             .label(label);
 
         frame.render_widget(gauge, area);
@@ -267,18 +317,41 @@ impl SyncProgressOverlay {
         progress: &SyncProgress,
         theme: &Theme,
     ) {
-        let elapsed = Utc::now().signed_duration_since(progress.started_at);
-        let elapsed_str = format_duration(elapsed);
+        let now = Utc::now();
 
-        let eta_str = if let Some(eta) = progress.estimated_completion {
-            let remaining = eta.signed_duration_since(Utc::now());
-            if remaining > ChronoDuration::zero() {
-                format!("ETA: {}", format_duration(remaining))
-            } else {
-                "Completing...".to_string()
-            }
-        } else {
-            "Calculating...".to_string()
+        // Prefer the account-wide figures while a full account sync is running
+        let (started_at, rate, remaining) = match progress.account_progress {
+            Some(ref account) => (
+                account.started_at,
+                account.messages_per_second(now),
+                account.estimated_remaining(now),
+            ),
+            None => (
+                progress.started_at,
+                progress.messages_per_second(now),
+                progress
+                    .estimated_completion
+                    .map(|eta| eta.signed_duration_since(now)),
+            ),
+        };
+
+        let elapsed_str = format_duration(now.signed_duration_since(started_at));
+
+        let eta_str = match remaining {
+            Some(remaining) if remaining > ChronoDuration::zero() => format_duration(remaining),
+            Some(_) => "Completing...".to_string(),
+            None => "Calculating...".to_string(),
+        };
+
+        let folder_str = match progress.account_progress {
+            Some(ref account) => format!(
+                "{} ({} of {}, {} remaining)",
+                progress.folder_name,
+                (account.folder_index + 1).min(account.total_folders),
+                account.total_folders,
+                account.folders_remaining()
+            ),
+            None => progress.folder_name.clone(),
         };
 
         let bytes_str = format_bytes(progress.bytes_downloaded);
@@ -290,12 +363,16 @@ impl SyncProgressOverlay {
             ]),
             Line::from(vec![
                 Span::styled("Folder: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(&progress.folder_name),
+                Span::raw(folder_str),
             ]),
             Line::from(vec![
                 Span::styled("Elapsed: ", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(elapsed_str),
             ]),
+            Line::from(vec![
+                Span::styled("Rate: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{:.1} msg/s", rate)),
+            ]),
             Line::from(vec![
                 Span::styled("ETA: ", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(eta_str),
@@ -357,6 +434,17 @@ impl SyncProgressOverlay {
                 Line::from("• Resolving conflicts"),
                 Line::from("• Updating indices"),
             ],
+            SyncPhase::Paused => vec![
+                Line::from(Span::styled(
+                    "⏸️ Synchronization paused",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from("• Messages synced so far are kept"),
+                Line::from("• Press p to resume"),
+                Line::from("• Press x to cancel"),
+            ],
             SyncPhase::Complete => vec![
                 Line::from(Span::styled(
                     "✅ Synchronization completed successfully!",
@@ -490,6 +578,7 @@ mod tests {
             bytes_downloaded: 1024,
             started_at: Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         };
 
         overlay.update_progress(progress);
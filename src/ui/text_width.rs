@@ -0,0 +1,93 @@
+//! Terminal cell width of text
+//!
+//! Emoji and CJK characters take two cells and combining marks none, so
+//! lengths in bytes or chars misalign columns. These helpers measure with
+//! `unicode-width` and cut at grapheme boundaries, so truncation never splits
+//! a flag, a skin-tone emoji or an accented letter.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Appended to text cut to fit its column
+pub const ELLIPSIS: &str = "...";
+
+/// Number of terminal cells `text` occupies
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(grapheme_width).sum()
+}
+
+/// Cells taken by one grapheme cluster
+///
+/// Emoji sequences (skin tones, ZWJ families) render as a single wide
+/// glyph, while `unicode-width` would add up every code point in them.
+fn grapheme_width(grapheme: &str) -> usize {
+    if grapheme.chars().count() > 1 && grapheme.chars().any(|c| c.width() == Some(2)) {
+        2
+    } else {
+        grapheme.width()
+    }
+}
+
+/// Longest prefix of whole graphemes that fits in `max_width` cells
+pub fn take_width(text: &str, max_width: usize) -> &str {
+    let mut width = 0;
+    let mut end = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        width += grapheme_width(grapheme);
+        if width > max_width {
+            break;
+        }
+        end = index + grapheme.len();
+    }
+    &text[..end]
+}
+
+/// Fit `text` in `max_width` cells, ending with an ellipsis when it is cut
+pub fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if display_width(text) <= max_width {
+        return text.to_string();
+    }
+    if max_width <= ELLIPSIS.len() {
+        return take_width(text, max_width).to_string();
+    }
+    format!("{}{}", take_width(text, max_width - ELLIPSIS.len()), ELLIPSIS)
+}
+
+/// Fit `text` in exactly `width` cells: truncated if longer, padded with
+/// spaces if shorter
+pub fn fit_to_width(text: &str, width: usize) -> String {
+    let mut fitted = truncate_to_width(text, width);
+    // A double-width grapheme at the cut can leave one cell free
+    let used = display_width(&fitted);
+    fitted.push_str(&" ".repeat(width.saturating_sub(used)));
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wide_text_width_and_truncation() {
+        assert_eq!(display_width("Hello"), 5);
+        assert_eq!(display_width("会議の議事録"), 12);
+        assert_eq!(display_width("🎉 Party"), 8);
+        assert_eq!(display_width("👍🏽 👨‍👩‍👧"), 5);
+
+        // Truncation counts cells, not bytes or chars
+        assert_eq!(truncate_to_width("会議の議事録について", 11), "会議の議...");
+        assert_eq!(truncate_to_width("🎉🎉🎉🎉 Launch day", 10), "🎉🎉🎉...");
+        assert_eq!(truncate_to_width("Short", 10), "Short");
+
+        // Grapheme clusters stay whole: a flag, a skin tone, a combining accent
+        assert_eq!(take_width("🇳🇴🇳🇴", 3), "🇳🇴");
+        assert_eq!(take_width("👍🏽ok", 2), "👍🏽");
+        assert_eq!(take_width("e\u{301}te\u{301}", 1), "e\u{301}");
+
+        // Padded to exactly the column width, even when a wide char doesn't fit
+        let fitted = fit_to_width("山田太郎 <taro@example.jp>", 8);
+        assert_eq!(fitted, "山田... ");
+        assert_eq!(display_width(&fitted), 8);
+        assert_eq!(fit_to_width("Bob", 6), "Bob   ");
+    }
+}
@@ -0,0 +1,33 @@
+//! Conversation behaviour of the threaded message list
+//!
+//! Opt-in settings for conversation-centric reading: reading the newest
+//! message of a thread can mark the whole thread read, and threads with
+//! nothing unread left can fold away to their root message.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// Threaded view configuration
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThreadBehaviorConfig {
+    /// Opening the newest message of a thread marks every message in it read
+    pub mark_thread_read_on_latest: bool,
+    /// Threads without unread messages are shown collapsed
+    pub collapse_read_threads: bool,
+}
+
+impl ThreadBehaviorConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "threads.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+}
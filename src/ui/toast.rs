@@ -157,10 +157,12 @@ impl ToastManager {
         self.add_toast(toast);
     }
 
-    /// Add a toast with custom duration
-    pub fn show_with_duration(&mut self, message: String, level: ToastLevel, duration: Duration) {
+    /// Add a toast with custom duration, returning its ID
+    pub fn show_with_duration(&mut self, message: String, level: ToastLevel, duration: Duration) -> String {
         let toast = Toast::with_duration(message, level, duration);
+        let toast_id = toast.id.clone();
         self.add_toast(toast);
+        toast_id
     }
 
     /// Remove a specific toast by ID
@@ -0,0 +1,339 @@
+//! Optional "today" panel
+//!
+//! A compact overlay in the top-right corner with today's agenda, unread
+//! inbox counts, current weather and system load. It stays hidden unless the
+//! user toggles it or enables it at startup in `today_panel.toml`.
+
+use crate::calendar::Event;
+use crate::services::{SystemStats, TemperatureUnits, WeatherReport};
+use crate::theme::Theme;
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+
+/// Most agenda entries listed before collapsing the rest
+const MAX_AGENDA_ENTRIES: usize = 6;
+
+/// Today panel configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TodayPanelConfig {
+    /// Show the panel when the app starts
+    pub show_on_startup: bool,
+    /// Show current weather; requires `weather_api_key`
+    pub show_weather: bool,
+    /// OpenWeatherMap API key
+    pub weather_api_key: Option<String>,
+    /// Location passed to the weather API, e.g. `Oslo` or `London,GB`
+    pub weather_location: String,
+    pub weather_units: TemperatureUnits,
+    /// Minutes between weather refreshes while the panel is open
+    pub weather_refresh_minutes: u64,
+    /// Show CPU and memory usage
+    pub show_system_stats: bool,
+}
+
+impl Default for TodayPanelConfig {
+    fn default() -> Self {
+        Self {
+            show_on_startup: false,
+            show_weather: true,
+            weather_api_key: None,
+            weather_location: String::new(),
+            weather_units: TemperatureUnits::Metric,
+            weather_refresh_minutes: 30,
+            show_system_stats: false,
+        }
+    }
+}
+
+impl TodayPanelConfig {
+    /// Settings file in the config directory
+    const CONFIG_FILE: &str = "today_panel.toml";
+
+    /// Load configuration from file
+    pub async fn load() -> Result<Self> {
+        crate::config::load_or_default(Self::CONFIG_FILE).await
+    }
+
+    /// Save configuration to file
+    pub async fn save(&self) -> Result<()> {
+        crate::config::save(self, Self::CONFIG_FILE).await
+    }
+
+    /// Whether weather can be fetched with this configuration
+    pub fn weather_enabled(&self) -> bool {
+        self.show_weather
+            && self
+                .weather_api_key
+                .as_ref()
+                .is_some_and(|key| !key.trim().is_empty())
+            && !self.weather_location.trim().is_empty()
+    }
+}
+
+/// One event on today's agenda
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgendaEntry {
+    /// `HH:MM` start time, or `All day`
+    pub time: String,
+    pub title: String,
+    pub location: Option<String>,
+}
+
+/// Weather section state
+#[derive(Debug, Clone)]
+pub enum WeatherState {
+    /// Weather is turned off or not configured
+    Disabled,
+    Loading,
+    Ready(WeatherReport),
+    Failed(String),
+}
+
+/// Today's events in local time, all-day events first
+pub fn todays_agenda(events: &[Event], today: NaiveDate) -> Vec<AgendaEntry> {
+    let mut todays: Vec<&Event> = events
+        .iter()
+        .filter(|event| {
+            let start = event.start_time.with_timezone(&Local).date_naive();
+            let end = event.end_time.with_timezone(&Local).date_naive();
+            // Multi-day events show on every day they cover
+            start <= today && (end > today || (end == today && start == today))
+        })
+        .collect();
+    todays.sort_by_key(|event| (!event.all_day, event.start_time));
+
+    todays
+        .into_iter()
+        .map(|event| AgendaEntry {
+            time: if event.all_day {
+                "All day".to_string()
+            } else {
+                event
+                    .start_time
+                    .with_timezone(&Local)
+                    .format("%H:%M")
+                    .to_string()
+            },
+            title: event.title.clone(),
+            location: event.location.clone(),
+        })
+        .collect()
+}
+
+/// Compact overlay summarising the day
+pub struct TodayPanel {
+    visible: bool,
+    agenda: Vec<AgendaEntry>,
+    /// Unread inbox messages per account
+    unread: Vec<(String, u32)>,
+    weather: WeatherState,
+    system_stats: Option<SystemStats>,
+}
+
+impl TodayPanel {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            agenda: Vec::new(),
+            unread: Vec::new(),
+            weather: WeatherState::Disabled,
+            system_stats: None,
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn toggle_visibility(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn set_agenda(&mut self, agenda: Vec<AgendaEntry>) {
+        self.agenda = agenda;
+    }
+
+    pub fn set_unread_counts(&mut self, unread: Vec<(String, u32)>) {
+        self.unread = unread;
+    }
+
+    pub fn set_weather(&mut self, weather: WeatherState) {
+        self.weather = weather;
+    }
+
+    pub fn set_system_stats(&mut self, stats: Option<SystemStats>) {
+        self.system_stats = stats;
+    }
+
+    /// Render the panel in the top-right corner of `area`
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.visible {
+            return;
+        }
+
+        let lines = self.content_lines(theme);
+        let width = 46.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let panel_area = Rect {
+            x: area.x + area.width - width,
+            y: area.y,
+            width,
+            height,
+        };
+
+        frame.render_widget(Clear, panel_area);
+
+        let block = Block::default()
+            .title(format!(" Today · {} ", Local::now().format("%a %d %b")))
+            .borders(Borders::ALL)
+            .border_style(theme.get_component_style("border", true));
+
+        frame.render_widget(Paragraph::new(lines).block(block), panel_area);
+    }
+
+    fn content_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let heading = Style::default()
+            .fg(theme.colors.palette.accent)
+            .add_modifier(Modifier::BOLD);
+        let secondary = Style::default().fg(theme.colors.palette.text_secondary);
+        let mut lines = Vec::new();
+
+        lines.push(Line::from(Span::styled("Agenda", heading)));
+        if self.agenda.is_empty() {
+            lines.push(Line::from(Span::styled("  Nothing scheduled", secondary)));
+        }
+        for entry in self.agenda.iter().take(MAX_AGENDA_ENTRIES) {
+            let mut spans = vec![
+                Span::styled(format!("  {:<7} ", entry.time), secondary),
+                Span::raw(entry.title.clone()),
+            ];
+            if let Some(ref location) = entry.location {
+                spans.push(Span::styled(format!(" @ {}", location), secondary));
+            }
+            lines.push(Line::from(spans));
+        }
+        if self.agenda.len() > MAX_AGENDA_ENTRIES {
+            lines.push(Line::from(Span::styled(
+                format!("  +{} more", self.agenda.len() - MAX_AGENDA_ENTRIES),
+                secondary,
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Unread", heading)));
+        if self.unread.is_empty() {
+            lines.push(Line::from(Span::styled("  Inbox zero", secondary)));
+        }
+        for (account, count) in &self.unread {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:>4} ", count), Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(account.clone()),
+            ]));
+        }
+
+        match &self.weather {
+            WeatherState::Disabled => {}
+            WeatherState::Loading => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled("Weather", heading)));
+                lines.push(Line::from(Span::styled("  Loading…", secondary)));
+            }
+            WeatherState::Ready(report) => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!("Weather · {}", report.location),
+                    heading,
+                )));
+                lines.push(Line::from(format!("  {}", report.summary())));
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "  feels like {:.0}{}, humidity {}%",
+                        report.feels_like,
+                        report.units.symbol(),
+                        report.humidity
+                    ),
+                    secondary,
+                )));
+            }
+            WeatherState::Failed(error) => {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled("Weather", heading)));
+                lines.push(Line::from(Span::styled(format!("  {}", error), secondary)));
+            }
+        }
+
+        if let Some(stats) = self.system_stats {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("System", heading)));
+            lines.push(Line::from(format!(
+                "  CPU {:.0}% · Memory {:.0}% of {:.1} GiB",
+                stats.cpu_usage,
+                stats.memory_percent(),
+                stats.memory_total_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+            )));
+        }
+
+        lines
+    }
+}
+
+impl Default for TodayPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_todays_agenda() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let at = |day: u32, hour: u32| {
+            Local
+                .with_ymd_and_hms(2024, 3, day, hour, 0, 0)
+                .unwrap()
+                .with_timezone(&chrono::Utc)
+        };
+
+        let mut standup = Event::new("local".into(), "Standup".into(), at(4, 9), at(4, 9));
+        standup.end_time += Duration::minutes(15);
+        let review = Event::new("local".into(), "Review".into(), at(4, 14), at(4, 15));
+        let tomorrow = Event::new("local".into(), "Tomorrow".into(), at(5, 9), at(5, 10));
+        let mut offsite = Event::new("local".into(), "Offsite".into(), at(3, 0), at(5, 0));
+        offsite.all_day = true;
+        let yesterday_all_day = {
+            let mut event = Event::new("local".into(), "Holiday".into(), at(3, 0), at(4, 0));
+            event.all_day = true;
+            event
+        };
+
+        let agenda = todays_agenda(
+            &[review, tomorrow, standup, offsite, yesterday_all_day],
+            today,
+        );
+        let titles: Vec<(&str, &str)> = agenda
+            .iter()
+            .map(|entry| (entry.time.as_str(), entry.title.as_str()))
+            .collect();
+        assert_eq!(
+            titles,
+            vec![("All day", "Offsite"), ("09:00", "Standup"), ("14:00", "Review")]
+        );
+    }
+}
@@ -129,6 +129,8 @@ impl TestDataManager {
                 sync_version: 1,
                 is_draft: false,
                 is_deleted: false,
+                snoozed_until: None,
+                inbox_tab: None,
             },
             // Newsletter/bulk email
             StoredMessage {
@@ -161,6 +163,8 @@ impl TestDataManager {
                 sync_version: 1,
                 is_draft: false,
                 is_deleted: false,
+                snoozed_until: None,
+                inbox_tab: None,
             },
             // Meeting request email
             StoredMessage {
@@ -193,6 +197,8 @@ impl TestDataManager {
                 sync_version: 1,
                 is_draft: false,
                 is_deleted: false,
+                snoozed_until: None,
+                inbox_tab: None,
             },
             // Personal email
             StoredMessage {
@@ -225,6 +231,8 @@ impl TestDataManager {
                 sync_version: 1,
                 is_draft: false,
                 is_deleted: false,
+                snoozed_until: None,
+                inbox_tab: None,
             },
         ]
     }
@@ -731,6 +731,8 @@ impl AIIntegrationTestRunner {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         }
     }
 
@@ -766,6 +768,8 @@ impl AIIntegrationTestRunner {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         }
     }
 
@@ -801,6 +805,8 @@ impl AIIntegrationTestRunner {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         }
     }
 }
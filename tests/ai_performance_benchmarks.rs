@@ -168,6 +168,8 @@ impl BenchmarkTestData {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         });
 
         // Medium email (~5KB)
@@ -202,6 +204,8 @@ impl BenchmarkTestData {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         });
 
         // Large email (~20KB)
@@ -236,6 +240,8 @@ impl BenchmarkTestData {
             sync_version: 1,
             is_draft: false,
             is_deleted: false,
+            snoozed_until: None,
+            inbox_tab: None,
         });
 
         emails
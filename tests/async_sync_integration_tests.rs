@@ -33,6 +33,7 @@ async fn test_sync_progress_phases() {
         SyncPhase::FetchingHeaders,
         SyncPhase::FetchingBodies,
         SyncPhase::ProcessingChanges,
+        SyncPhase::Paused,
         SyncPhase::Complete,
     ];
     
@@ -46,6 +47,7 @@ async fn test_sync_progress_phases() {
             bytes_downloaded: 0,
             started_at: chrono::Utc::now(),
             estimated_completion: None,
+            account_progress: None,
         };
         
         // Each phase should match what we set
@@ -55,6 +57,7 @@ async fn test_sync_progress_phases() {
             SyncPhase::FetchingHeaders => assert!(matches!(progress.phase, SyncPhase::FetchingHeaders)),
             SyncPhase::FetchingBodies => assert!(matches!(progress.phase, SyncPhase::FetchingBodies)),
             SyncPhase::ProcessingChanges => assert!(matches!(progress.phase, SyncPhase::ProcessingChanges)),
+            SyncPhase::Paused => assert!(matches!(progress.phase, SyncPhase::Paused)),
             SyncPhase::Complete => assert!(matches!(progress.phase, SyncPhase::Complete)),
             SyncPhase::Error(_) => assert!(matches!(progress.phase, SyncPhase::Error(_))),
         }
@@ -167,6 +170,7 @@ async fn test_sync_progress_reporting() {
         bytes_downloaded: 0,
         started_at: chrono::Utc::now(),
         estimated_completion: None,
+        account_progress: None,
     };
     
     // Verify progress structure
@@ -142,6 +142,7 @@ async fn test_progress_tracking() {
         bytes_downloaded: 0,
         started_at: Utc::now(),
         estimated_completion: None,
+        account_progress: None,
     };
 
     sync_engine.update_progress(progress.clone()).await;
@@ -191,6 +192,7 @@ async fn test_sync_cancellation() {
         bytes_downloaded: 1000,
         started_at: Utc::now(),
         estimated_completion: None,
+        account_progress: None,
     };
 
     sync_engine.update_progress(progress).await;
@@ -254,6 +256,7 @@ async fn test_database_integration() {
         message_count: 10,
         unread_count: 5,
         sync_status: comunicado::email::SyncStatus::Complete,
+        sync_mode: None,
     };
 
     database
@@ -294,6 +297,8 @@ async fn test_oauth2_integration() {
         use_starttls: false,
         timeout_seconds: 30,
         validate_certificates: true,
+        fetch: comunicado::imap::FetchSettings::default(),
+        disabled_capabilities: Vec::new(),
     };
 
     let token_manager = TokenManager::new();
@@ -461,5 +466,7 @@ fn create_test_message(account_id: &str, folder: &str, uid: u32) -> StoredMessag
         sync_version: 1,
         is_draft: false,
         is_deleted: false,
+        snoozed_until: None,
+        inbox_tab: None,
     }
 }
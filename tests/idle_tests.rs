@@ -119,6 +119,8 @@ async fn test_idle_with_account_manager() {
         use_starttls: false,
         timeout_seconds: 30,
         validate_certificates: true,
+        fetch: comunicado::imap::FetchSettings::default(),
+        disabled_capabilities: Vec::new(),
     };
 
     // Test that we can create an account manager with config
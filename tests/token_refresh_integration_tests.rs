@@ -113,6 +113,7 @@ async fn test_sync_progress_reporting() {
         bytes_downloaded: 0,
         started_at: chrono::Utc::now(),
         estimated_completion: None,
+        account_progress: None,
     };
     
     // Verify progress structure